@@ -0,0 +1,100 @@
+//! Derive macros for necs.
+//!
+//! Re-exported from `necs`; depend on that crate rather than this one.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+/// Derive `SpawnBundle` for a struct so it can be passed to `World::spawn`.
+///
+/// Every field is a component, except fields marked `#[bundle]`, which are
+/// themselves bundles whose components are spliced in:
+///
+/// ```ignore
+/// #[derive(Bundle)]
+/// struct EnemyBundle {
+///     #[bundle]
+///     sprite: SpriteBundle,
+///     health: Health,
+///     enemy: Enemy,
+/// }
+/// ```
+#[proc_macro_derive(Bundle, attributes(bundle))]
+pub fn derive_bundle(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(name, "Bundle can only be derived for structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    // Components are pushed through the one-element tuple impl, so a field is
+    // either `(T,)` or a nested bundle `B` — both just `SpawnBundle`s.
+    let mut bundle_types = Vec::new();
+    let mut pushes = Vec::new();
+    for (i, field) in fields.iter().enumerate() {
+        let ty = &field.ty;
+        let access = match &field.ident {
+            Some(ident) => quote!(self.#ident),
+            None => {
+                let index = syn::Index::from(i);
+                quote!(self.#index)
+            }
+        };
+        if field.attrs.iter().any(|a| a.path().is_ident("bundle")) {
+            bundle_types.push(quote!(#ty));
+            pushes.push(quote!(::necs::ecs::SpawnBundle::push_into(#access, columns);));
+        } else {
+            bundle_types.push(quote!((#ty,)));
+            pushes.push(quote!(::necs::ecs::SpawnBundle::push_into((#access,), columns);));
+        }
+    }
+    if matches!(fields, Fields::Unit) || bundle_types.is_empty() {
+        return syn::Error::new_spanned(name, "Bundle needs at least one field")
+            .to_compile_error()
+            .into();
+    }
+
+    quote! {
+        impl #impl_generics ::necs::ecs::SpawnBundle for #name #ty_generics #where_clause {
+            fn type_ids() -> ::std::vec::Vec<::std::any::TypeId> {
+                let mut ids = ::std::vec::Vec::new();
+                #(ids.extend(<#bundle_types as ::necs::ecs::SpawnBundle>::type_ids());)*
+                ids
+            }
+
+            fn type_names() -> ::std::vec::Vec<(::std::any::TypeId, &'static str)> {
+                let mut names = ::std::vec::Vec::new();
+                #(names.extend(<#bundle_types as ::necs::ecs::SpawnBundle>::type_names());)*
+                names
+            }
+
+            fn create_columns() -> ::std::collections::HashMap<
+                ::std::any::TypeId,
+                ::necs::ecs::ComponentColumn,
+            > {
+                let mut columns = ::std::collections::HashMap::new();
+                #(columns.extend(<#bundle_types as ::necs::ecs::SpawnBundle>::create_columns());)*
+                columns
+            }
+
+            fn push_into(
+                self,
+                columns: &mut ::std::collections::HashMap<
+                    ::std::any::TypeId,
+                    ::necs::ecs::ComponentColumn,
+                >,
+            ) {
+                #(#pushes)*
+            }
+        }
+    }
+    .into()
+}
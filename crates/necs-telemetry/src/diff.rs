@@ -0,0 +1,382 @@
+//! World capture diffing for the Diff tab.
+//!
+//! The game sends a full world capture (every entity, every component's debug
+//! string) split across several datagrams. [`PendingCapture`] reassembles the
+//! chunks, and [`diff_captures`] compares a "before" and "after" capture:
+//!
+//! ```text
+//!   before (frame 120)          after (frame 480)
+//!   ┌──────────────────┐        ┌──────────────────┐
+//!   │ 3v0 Transform    │        │ 3v0 Transform'   │  ~ changed: translation.y
+//!   │ 7v0 Enemy        │        │                  │  - removed
+//!   │                  │        │ 9v1 Bullet       │  + added
+//!   └──────────────────┘        └──────────────────┘
+//! ```
+//!
+//! Entities are matched by `(id, generation)`, so a recycled slot shows up as
+//! one removal plus one addition. Component values are compared field by
+//! field, descending into nested structs (`translation.y`), so a one-field
+//! change in a large component is easy to spot.
+
+use std::collections::HashMap;
+
+use crate::{parse_debug_fields, CaptureChunk, EntityInfo};
+
+/// Which side of the diff a capture fills.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CaptureSlot {
+    Before,
+    After,
+}
+
+impl CaptureSlot {
+    pub fn label(self) -> &'static str {
+        match self {
+            CaptureSlot::Before => "before",
+            CaptureSlot::After => "after",
+        }
+    }
+}
+
+/// A complete world capture.
+#[derive(Clone, Default)]
+pub struct WorldCapture {
+    pub frame_count: u64,
+    pub entities: Vec<EntityInfo>,
+}
+
+/// A capture whose chunks are still arriving.
+pub struct PendingCapture {
+    pub capture_id: u32,
+    pub slot: CaptureSlot,
+    frame_count: u64,
+    chunks: Vec<Option<Vec<EntityInfo>>>,
+}
+
+impl PendingCapture {
+    pub fn new(capture_id: u32, slot: CaptureSlot) -> Self {
+        Self {
+            capture_id,
+            slot,
+            frame_count: 0,
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Store a chunk. Returns `true` once every chunk has arrived.
+    pub fn receive(&mut self, chunk: CaptureChunk) -> bool {
+        if chunk.capture_id != self.capture_id || chunk.chunk >= chunk.chunk_count {
+            return false;
+        }
+        if self.chunks.len() != chunk.chunk_count {
+            self.chunks = vec![None; chunk.chunk_count];
+        }
+        self.frame_count = chunk.frame_count;
+        self.chunks[chunk.chunk] = Some(chunk.entities);
+        self.is_complete()
+    }
+
+    /// `(received, total)` chunk counts; total is 0 before the first chunk.
+    pub fn progress(&self) -> (usize, usize) {
+        let received = self.chunks.iter().filter(|c| c.is_some()).count();
+        (received, self.chunks.len())
+    }
+
+    fn is_complete(&self) -> bool {
+        !self.chunks.is_empty() && self.chunks.iter().all(|c| c.is_some())
+    }
+
+    /// Concatenate the chunks into a capture.
+    pub fn finish(self) -> WorldCapture {
+        WorldCapture {
+            frame_count: self.frame_count,
+            entities: self.chunks.into_iter().flatten().flatten().collect(),
+        }
+    }
+}
+
+// ── Diff model ───────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Change {
+    Added,
+    Removed,
+    Changed,
+}
+
+impl Change {
+    pub fn symbol(self) -> char {
+        match self {
+            Change::Added => '+',
+            Change::Removed => '-',
+            Change::Changed => '~',
+        }
+    }
+}
+
+/// One differing value. `path` is dotted for nested fields and empty when
+/// the whole component value is shown.
+#[derive(Clone, PartialEq, Debug)]
+pub struct FieldDiff {
+    pub path: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ComponentDiff {
+    pub name: String,
+    pub change: Change,
+    pub fields: Vec<FieldDiff>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EntityDiff {
+    pub id: u32,
+    pub generation: u32,
+    pub change: Change,
+    pub components: Vec<ComponentDiff>,
+}
+
+/// Compare two captures. Unchanged entities are omitted; the result is
+/// sorted by entity id.
+pub fn diff_captures(before: &WorldCapture, after: &WorldCapture) -> Vec<EntityDiff> {
+    let key = |e: &EntityInfo| (e.id, e.generation);
+    let before_map: HashMap<_, _> = before.entities.iter().map(|e| (key(e), e)).collect();
+    let after_map: HashMap<_, _> = after.entities.iter().map(|e| (key(e), e)).collect();
+
+    let mut diffs = Vec::new();
+    for (k, old) in &before_map {
+        match after_map.get(k) {
+            None => diffs.push(whole_entity(old, Change::Removed)),
+            Some(new) => {
+                let components = diff_components(old, new);
+                if !components.is_empty() {
+                    diffs.push(EntityDiff {
+                        id: old.id,
+                        generation: old.generation,
+                        change: Change::Changed,
+                        components,
+                    });
+                }
+            }
+        }
+    }
+    for (k, new) in &after_map {
+        if !before_map.contains_key(k) {
+            diffs.push(whole_entity(new, Change::Added));
+        }
+    }
+
+    diffs.sort_by_key(|d| (d.id, d.generation));
+    diffs
+}
+
+/// `(added, removed, changed)` entity counts.
+pub fn summary(diffs: &[EntityDiff]) -> (usize, usize, usize) {
+    let count = |c| diffs.iter().filter(|d| d.change == c).count();
+    (count(Change::Added), count(Change::Removed), count(Change::Changed))
+}
+
+fn whole_entity(entity: &EntityInfo, change: Change) -> EntityDiff {
+    let components = entity
+        .components
+        .iter()
+        .map(|c| ComponentDiff {
+            name: c.name.clone(),
+            change,
+            fields: vec![whole_value(&c.debug_value, change)],
+        })
+        .collect();
+    EntityDiff {
+        id: entity.id,
+        generation: entity.generation,
+        change,
+        components,
+    }
+}
+
+fn whole_value(value: &str, change: Change) -> FieldDiff {
+    let value = Some(value.to_string());
+    FieldDiff {
+        path: String::new(),
+        before: if change == Change::Removed { value.clone() } else { None },
+        after: if change == Change::Removed { None } else { value },
+    }
+}
+
+fn diff_components(old: &EntityInfo, new: &EntityInfo) -> Vec<ComponentDiff> {
+    let mut diffs = Vec::new();
+    for before in &old.components {
+        match new.components.iter().find(|c| c.name == before.name) {
+            None => diffs.push(ComponentDiff {
+                name: before.name.clone(),
+                change: Change::Removed,
+                fields: vec![whole_value(&before.debug_value, Change::Removed)],
+            }),
+            Some(after) => {
+                let mut fields = Vec::new();
+                diff_values("", &before.debug_value, &after.debug_value, &mut fields);
+                if !fields.is_empty() {
+                    diffs.push(ComponentDiff {
+                        name: before.name.clone(),
+                        change: Change::Changed,
+                        fields,
+                    });
+                }
+            }
+        }
+    }
+    for after in &new.components {
+        if !old.components.iter().any(|c| c.name == after.name) {
+            diffs.push(ComponentDiff {
+                name: after.name.clone(),
+                change: Change::Added,
+                fields: vec![whole_value(&after.debug_value, Change::Added)],
+            });
+        }
+    }
+    diffs
+}
+
+/// Recursively compare two debug strings, descending into named fields.
+fn diff_values(path: &str, before: &str, after: &str, out: &mut Vec<FieldDiff>) {
+    if before == after {
+        return;
+    }
+    let (old_fields, new_fields) = (parse_debug_fields(before), parse_debug_fields(after));
+    if old_fields.is_empty() || new_fields.is_empty() {
+        out.push(FieldDiff {
+            path: path.to_string(),
+            before: Some(before.to_string()),
+            after: Some(after.to_string()),
+        });
+        return;
+    }
+
+    let join = |name: &str| {
+        if path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{path}.{name}")
+        }
+    };
+    for (name, old) in &old_fields {
+        match new_fields.iter().find(|(n, _)| n == name) {
+            Some((_, new)) => diff_values(&join(name), old, new, out),
+            None => out.push(FieldDiff {
+                path: join(name),
+                before: Some(old.clone()),
+                after: None,
+            }),
+        }
+    }
+    for (name, new) in &new_fields {
+        if !old_fields.iter().any(|(n, _)| n == name) {
+            out.push(FieldDiff {
+                path: join(name),
+                before: None,
+                after: Some(new.clone()),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ComponentInfo;
+
+    fn entity(id: u32, components: &[(&str, &str)]) -> EntityInfo {
+        EntityInfo {
+            id,
+            generation: 0,
+            components: components
+                .iter()
+                .map(|(name, value)| ComponentInfo {
+                    name: name.to_string(),
+                    debug_value: value.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn capture(entities: Vec<EntityInfo>) -> WorldCapture {
+        WorldCapture {
+            frame_count: 0,
+            entities,
+        }
+    }
+
+    #[test]
+    fn detects_added_removed_and_changed_entities() {
+        let before = capture(vec![
+            entity(1, &[("Health", "Health(10)")]),
+            entity(2, &[("Enemy", "Enemy")]),
+            entity(3, &[("Tag", "Tag")]),
+        ]);
+        let after = capture(vec![
+            entity(1, &[("Health", "Health(7)")]),
+            entity(3, &[("Tag", "Tag")]),
+            entity(4, &[("Bullet", "Bullet")]),
+        ]);
+
+        let diffs = diff_captures(&before, &after);
+        let changes: Vec<_> = diffs.iter().map(|d| (d.id, d.change)).collect();
+        assert_eq!(
+            changes,
+            vec![(1, Change::Changed), (2, Change::Removed), (4, Change::Added)]
+        );
+        assert_eq!(summary(&diffs), (1, 1, 1));
+    }
+
+    #[test]
+    fn nested_fields_are_reported_by_path() {
+        let before = capture(vec![entity(
+            1,
+            &[("Player", "Player { pos: Pos { x: 1.0, y: 2.0 }, name: \"a\" }")],
+        )]);
+        let after = capture(vec![entity(
+            1,
+            &[
+                ("Player", "Player { pos: Pos { x: 1.0, y: 5.0 }, name: \"a\" }"),
+                ("Stunned", "Stunned"),
+            ],
+        )]);
+
+        let diffs = diff_captures(&before, &after);
+        let components = &diffs[0].components;
+        assert_eq!(components[0].change, Change::Changed);
+        assert_eq!(
+            components[0].fields,
+            vec![FieldDiff {
+                path: "pos.y".to_string(),
+                before: Some("2.0".to_string()),
+                after: Some("5.0".to_string()),
+            }]
+        );
+        assert_eq!(components[1].name, "Stunned");
+        assert_eq!(components[1].change, Change::Added);
+    }
+
+    #[test]
+    fn capture_reassembles_out_of_order_chunks() {
+        let chunk = |chunk, id| CaptureChunk {
+            capture_id: 5,
+            frame_count: 42,
+            chunk,
+            chunk_count: 2,
+            entities: vec![entity(id, &[])],
+        };
+        let mut pending = PendingCapture::new(5, CaptureSlot::Before);
+        assert!(!pending.receive(chunk(1, 20)));
+        assert_eq!(pending.progress(), (1, 2));
+        assert!(pending.receive(chunk(0, 10)));
+
+        let capture = pending.finish();
+        assert_eq!(capture.frame_count, 42);
+        let ids: Vec<_> = capture.entities.iter().map(|e| e.id).collect();
+        assert_eq!(ids, vec![10, 20]);
+    }
+}
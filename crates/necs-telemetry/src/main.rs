@@ -4,6 +4,9 @@
 //! in an interactive btop-style terminal dashboard using ratatui.
 //!
 //! Run a necs game with `--features diagnostics`, then run `cargo run -p necs-telemetry`.
+//! If the game was started with `--diag-port N`, pass the same `--port N` here.
+
+mod diff;
 
 use std::collections::{HashSet, VecDeque};
 use std::io;
@@ -21,6 +24,8 @@ use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
 use ratatui::Terminal;
 use serde::Deserialize;
 
+use diff::{diff_captures, Change, CaptureSlot, EntityDiff, PendingCapture, WorldCapture};
+
 // ── Wire types (must match necs's JSON format) ─────────────────────────
 
 #[derive(Deserialize, Clone, Default)]
@@ -34,6 +39,8 @@ struct DiagSnapshot {
     archetypes: Vec<ArchetypeInfo>,
     render: Option<RenderStats>,
     #[serde(default)]
+    input_latency: Option<InputLatencyInfo>,
+    #[serde(default)]
     system_timings: Option<Vec<SystemTimingInfo>>,
     #[serde(default)]
     frame_budget: Option<FrameBudgetInfo>,
@@ -47,6 +54,13 @@ struct DiagSnapshot {
     hierarchy: Option<HierarchyInfo>,
     #[serde(default)]
     scene: Option<SceneInfo>,
+    #[serde(default)]
+    propagation: Option<PropagationInfo>,
+    #[serde(default)]
+    shader_diff: Option<ShaderDiffInfo>,
+    /// Sent with the first snapshot, and after a `build_info` request.
+    #[serde(default)]
+    build: Option<BuildInfo>,
 }
 
 #[derive(Deserialize, Clone, Default)]
@@ -78,6 +92,21 @@ struct RenderStats {
     draw_calls: u32,
     vertices: u32,
     textures_loaded: u32,
+    #[serde(default)]
+    meshes_submitted: u32,
+    #[serde(default)]
+    meshes_culled: u32,
+    #[serde(default)]
+    sprites_submitted: u32,
+    #[serde(default)]
+    sprites_culled: u32,
+}
+
+#[derive(Deserialize, Clone, Default)]
+struct InputLatencyInfo {
+    last_ms: f32,
+    avg_ms: f32,
+    max_ms: f32,
 }
 
 #[derive(Deserialize, Clone, Default)]
@@ -108,6 +137,9 @@ struct AssetInfo {
     watcher_active: bool,
     pending_count: usize,
     watched_files: Vec<(String, String)>,
+    /// Dependency edges as (dependent, dependency) file names.
+    #[serde(default)]
+    dependencies: Vec<(String, String)>,
     reload_events: Vec<ReloadEventInfo>,
 }
 
@@ -135,6 +167,39 @@ struct HierarchyInfo {
     max_depth: u32,
 }
 
+#[derive(Deserialize, Clone, Default)]
+struct PropagationInfo {
+    updated: usize,
+    roots: usize,
+    deferred_roots: usize,
+    duration_us: f64,
+}
+
+/// Before/after stats of the game's latest shader hot-reload.
+#[derive(Deserialize, Clone, Default)]
+struct ShaderDiffInfo {
+    shader: String,
+    /// "live", "before" or "after": what the game window shows.
+    view: String,
+    width: u32,
+    height: u32,
+    changed_percent: f32,
+    mean_delta: f32,
+    max_delta: u8,
+    /// Before and after PNG paths, when the game saves them.
+    saved: Option<(String, String)>,
+}
+
+/// The game's engine build and GPU backend.
+#[derive(Deserialize, Clone, Default)]
+struct BuildInfo {
+    version: String,
+    features: Vec<String>,
+    git_hash: Option<String>,
+    profile: String,
+    backend: Option<String>,
+}
+
 #[derive(Deserialize, Clone, Default)]
 struct SceneInfo {
     active_scene: Option<String>,
@@ -147,11 +212,50 @@ struct SceneCountEntryInfo {
     entity_count: usize,
 }
 
+/// Wrapper distinguishing capture datagrams from regular snapshots.
+#[derive(Deserialize)]
+struct CaptureMessage {
+    capture: CaptureChunk,
+}
+
+/// The game's ring of entity pool samples, sent on request.
+#[derive(Deserialize)]
+struct PoolHistoryMessage {
+    pool_history: PoolHistoryInfo,
+}
+
+#[derive(Deserialize)]
+struct PoolHistoryInfo {
+    interval_secs: f32,
+    samples: Vec<PoolSample>,
+}
+
+/// `(elapsed_secs, entity_count, archetype_count, fragmentation_pct)`.
+type PoolSample = (f32, usize, usize, f32);
+
+/// One slice of a full world capture (see `diff.rs`).
+#[derive(Deserialize)]
+struct CaptureChunk {
+    capture_id: u32,
+    frame_count: u64,
+    chunk: usize,
+    chunk_count: usize,
+    entities: Vec<EntityInfo>,
+}
+
 // ── Inspect request (sent to game) ───────────────────────────────────────
 
 #[derive(serde::Serialize)]
 struct InspectRequest {
     expanded_archetypes: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    capture_id: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shader_diff_view: Option<&'static str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pool_history: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    build_info: bool,
 }
 
 // ── Tabs ─────────────────────────────────────────────────────────────────
@@ -162,26 +266,29 @@ enum Tab {
     Systems,
     Assets,
     Logs,
+    Diff,
 }
 
 impl Tab {
-    const ALL: [Tab; 4] = [Tab::Overview, Tab::Systems, Tab::Assets, Tab::Logs];
+    const ALL: [Tab; 5] = [Tab::Overview, Tab::Systems, Tab::Assets, Tab::Logs, Tab::Diff];
 
     fn next(self) -> Self {
         match self {
             Tab::Overview => Tab::Systems,
             Tab::Systems => Tab::Assets,
             Tab::Assets => Tab::Logs,
-            Tab::Logs => Tab::Overview,
+            Tab::Logs => Tab::Diff,
+            Tab::Diff => Tab::Overview,
         }
     }
 
     fn prev(self) -> Self {
         match self {
-            Tab::Overview => Tab::Logs,
+            Tab::Overview => Tab::Diff,
             Tab::Systems => Tab::Overview,
             Tab::Assets => Tab::Systems,
             Tab::Logs => Tab::Assets,
+            Tab::Diff => Tab::Logs,
         }
     }
 
@@ -191,6 +298,7 @@ impl Tab {
             Tab::Systems => "Systems",
             Tab::Assets => "Assets",
             Tab::Logs => "Logs",
+            Tab::Diff => "Diff",
         }
     }
 }
@@ -316,6 +424,8 @@ struct AccumReloadEvent {
 const HISTORY_CAP: usize = 1200;
 const LOG_CAP: usize = 2000;
 const RELOAD_LOG_CAP: usize = 500;
+/// Matches the game's own ring: half an hour at one sample per 2 s.
+const POOL_TREND_CAP: usize = 900;
 
 struct App {
     latest: DiagSnapshot,
@@ -324,7 +434,7 @@ struct App {
     active_tab: Tab,
     paused: bool,
     connected: bool,
-    /// Socket for sending inspect requests to the game (port 9101).
+    /// Socket for sending inspect requests to the game (listen port + 1).
     request_socket: UdpSocket,
 
     // Tree state (Overview tab)
@@ -333,6 +443,11 @@ struct App {
     expanded_components: HashSet<(usize, usize, usize)>,
     /// Cursor index into the list of selectable rows.
     cursor: usize,
+    /// Pool trend panel visible; it fetches the game's history when opened
+    /// and then extends it from live snapshots.
+    show_pool_trend: bool,
+    pool_trend: VecDeque<PoolSample>,
+    pool_trend_interval: f32,
 
     // Sort
     sort_mode: SortMode,
@@ -350,6 +465,20 @@ struct App {
 
     // Assets tab state
     reload_log: Vec<AccumReloadEvent>,
+
+    // Diff tab state
+    next_capture_id: u32,
+    pending_capture: Option<PendingCapture>,
+    capture_before: Option<WorldCapture>,
+    capture_after: Option<WorldCapture>,
+    /// Diff of the two captures, recomputed when either changes.
+    diff: Vec<EntityDiff>,
+    diff_scroll_offset: usize,
+
+    /// The connected game's build, shown in the header.
+    build: Option<BuildInfo>,
+    /// A `build_info` request was sent and not answered yet.
+    build_requested: bool,
 }
 
 impl App {
@@ -366,6 +495,9 @@ impl App {
             expanded_entities: HashSet::new(),
             expanded_components: HashSet::new(),
             cursor: 0,
+            show_pool_trend: false,
+            pool_trend: VecDeque::new(),
+            pool_trend_interval: 2.0,
             sort_mode: SortMode::CountDesc,
             input_mode: InputMode::Normal,
             search_query: String::new(),
@@ -375,10 +507,27 @@ impl App {
             log_auto_scroll: true,
             log_scroll_offset: 0,
             reload_log: Vec::new(),
+            next_capture_id: 0,
+            pending_capture: None,
+            capture_before: None,
+            capture_after: None,
+            diff: Vec::new(),
+            diff_scroll_offset: 0,
+            build: None,
+            build_requested: false,
         }
     }
 
-    fn push_snapshot(&mut self, snap: DiagSnapshot) {
+    fn push_snapshot(&mut self, mut snap: DiagSnapshot) {
+        // The game sends its build with the first snapshot; one that was
+        // already running when we connected sends it when asked.
+        if let Some(build) = snap.build.take() {
+            self.build = Some(build);
+            self.build_requested = false;
+        } else if self.build.is_none() && !self.build_requested {
+            self.request_build_info();
+        }
+
         if self.paused {
             return;
         }
@@ -426,6 +575,30 @@ impl App {
             }
         }
 
+        if self.show_pool_trend
+            && let Some(pool) = &snap.entity_pool
+        {
+            // A game restart starts the clock over.
+            if self.pool_trend.back().is_some_and(|last| snap.elapsed_secs < last.0) {
+                self.pool_trend.clear();
+            }
+            let due = self
+                .pool_trend
+                .back()
+                .is_none_or(|last| snap.elapsed_secs >= last.0 + self.pool_trend_interval);
+            if due {
+                if self.pool_trend.len() >= POOL_TREND_CAP {
+                    self.pool_trend.pop_front();
+                }
+                self.pool_trend.push_back((
+                    snap.elapsed_secs,
+                    pool.alive_count,
+                    snap.archetype_count,
+                    pool.fragmentation_pct,
+                ));
+            }
+        }
+
         self.latest = snap;
         self.connected = true;
 
@@ -440,12 +613,138 @@ impl App {
         let expanded: Vec<usize> = self.expanded_archetypes.iter().copied().collect();
         let req = InspectRequest {
             expanded_archetypes: expanded,
+            capture_id: None,
+            shader_diff_view: None,
+            pool_history: false,
+            build_info: false,
+        };
+        if let Ok(json) = serde_json::to_vec(&req) {
+            let _ = self.request_socket.send(&json);
+        }
+    }
+
+    /// Ask the game for a full world capture to fill `slot`. Replaces any
+    /// capture still in flight.
+    fn request_capture(&mut self, slot: CaptureSlot) {
+        let capture_id = self.next_capture_id;
+        self.next_capture_id = self.next_capture_id.wrapping_add(1);
+        self.pending_capture = Some(PendingCapture::new(capture_id, slot));
+
+        let req = InspectRequest {
+            expanded_archetypes: self.expanded_archetypes.iter().copied().collect(),
+            capture_id: Some(capture_id),
+            shader_diff_view: None,
+            pool_history: false,
+            build_info: false,
+        };
+        if let Ok(json) = serde_json::to_vec(&req) {
+            let _ = self.request_socket.send(&json);
+        }
+    }
+
+    /// Ask the game for its build info.
+    fn request_build_info(&mut self) {
+        self.build_requested = true;
+        let req = InspectRequest {
+            expanded_archetypes: self.expanded_archetypes.iter().copied().collect(),
+            capture_id: None,
+            shader_diff_view: None,
+            pool_history: false,
+            build_info: true,
+        };
+        if let Ok(json) = serde_json::to_vec(&req) {
+            let _ = self.request_socket.send(&json);
+        }
+    }
+
+    /// Show or hide the pool trend panel, fetching the game's history when
+    /// it opens so the plot starts from before the TUI connected.
+    fn toggle_pool_trend(&mut self) {
+        self.show_pool_trend = !self.show_pool_trend;
+        if !self.show_pool_trend {
+            return;
+        }
+        let req = InspectRequest {
+            expanded_archetypes: self.expanded_archetypes.iter().copied().collect(),
+            capture_id: None,
+            shader_diff_view: None,
+            pool_history: true,
+            build_info: false,
         };
         if let Ok(json) = serde_json::to_vec(&req) {
             let _ = self.request_socket.send(&json);
         }
     }
 
+    /// Replace the trend with the game's history.
+    fn push_pool_history(&mut self, history: PoolHistoryInfo) {
+        if !self.show_pool_trend {
+            return;
+        }
+        self.pool_trend_interval = history.interval_secs.max(0.1);
+        self.pool_trend = history.samples.into();
+        while self.pool_trend.len() > POOL_TREND_CAP {
+            self.pool_trend.pop_front();
+        }
+    }
+
+    /// Switch the game window to the next shader diff frame:
+    /// live → before → after → live.
+    fn cycle_shader_diff_view(&self) {
+        let Some(diff) = &self.latest.shader_diff else {
+            return;
+        };
+        let next = match diff.view.as_str() {
+            "live" => "before",
+            "before" => "after",
+            _ => "live",
+        };
+        let req = InspectRequest {
+            expanded_archetypes: self.expanded_archetypes.iter().copied().collect(),
+            capture_id: None,
+            shader_diff_view: Some(next),
+            pool_history: false,
+            build_info: false,
+        };
+        if let Ok(json) = serde_json::to_vec(&req) {
+            let _ = self.request_socket.send(&json);
+        }
+    }
+
+    /// Store an incoming capture chunk, finishing the capture (and
+    /// recomputing the diff) once all chunks are in.
+    fn push_capture_chunk(&mut self, chunk: CaptureChunk) {
+        let Some(pending) = &mut self.pending_capture else {
+            return;
+        };
+        if !pending.receive(chunk) {
+            return;
+        }
+        let pending = self.pending_capture.take().unwrap();
+        let slot = pending.slot;
+        let capture = pending.finish();
+        match slot {
+            CaptureSlot::Before => self.capture_before = Some(capture),
+            CaptureSlot::After => self.capture_after = Some(capture),
+        }
+        self.refresh_diff();
+    }
+
+    fn clear_captures(&mut self) {
+        self.pending_capture = None;
+        self.capture_before = None;
+        self.capture_after = None;
+        self.refresh_diff();
+    }
+
+    fn refresh_diff(&mut self) {
+        self.diff = match (&self.capture_before, &self.capture_after) {
+            (Some(before), Some(after)) => diff_captures(before, after),
+            _ => Vec::new(),
+        };
+        self.diff_scroll_offset = 0;
+    }
+
     /// Get the ordered list of archetype indices after applying filter + sort.
     fn filtered_sorted_archetypes(&self) -> Vec<usize> {
         let mut indices: Vec<usize> = (0..self.latest.archetypes.len())
@@ -580,17 +879,36 @@ impl App {
 
 // ── Main ─────────────────────────────────────────────────────────────────
 
+/// UDP port to listen on: `--port N` (must match the game's `--diag-port`),
+/// default 9100. Requests go to the next port up.
+fn listen_port() -> u16 {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--port") {
+            Some("") => args.next(),
+            Some(rest) => rest.strip_prefix('=').map(str::to_string),
+            None => None,
+        };
+        if let Some(value) = value {
+            return value.parse().expect("--port needs a number between 1 and 65534");
+        }
+    }
+    9100
+}
+
 fn main() -> io::Result<()> {
-    let recv_socket = UdpSocket::bind("127.0.0.1:9100")
-        .expect("Failed to bind UDP port 9100 — is another necs-telemetry running?");
+    let port = listen_port();
+    let recv_socket = UdpSocket::bind(("127.0.0.1", port)).unwrap_or_else(|_| {
+        panic!("Failed to bind UDP port {port} — is another necs-telemetry running?")
+    });
     recv_socket
         .set_nonblocking(true)
         .expect("Failed to set non-blocking");
 
     let send_socket = UdpSocket::bind("127.0.0.1:0").expect("Failed to bind ephemeral port");
     send_socket
-        .connect("127.0.0.1:9101")
-        .expect("Failed to connect to port 9101");
+        .connect(("127.0.0.1", port + 1))
+        .unwrap_or_else(|_| panic!("Failed to connect to port {}", port + 1));
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -608,6 +926,10 @@ fn main() -> io::Result<()> {
                 Ok(n) => {
                     if let Ok(snap) = serde_json::from_slice::<DiagSnapshot>(&buf[..n]) {
                         app.push_snapshot(snap);
+                    } else if let Ok(msg) = serde_json::from_slice::<CaptureMessage>(&buf[..n]) {
+                        app.push_capture_chunk(msg.capture);
+                    } else if let Ok(msg) = serde_json::from_slice::<PoolHistoryMessage>(&buf[..n]) {
+                        app.push_pool_history(msg.pool_history);
                     }
                 }
                 Err(_) => break,
@@ -681,6 +1003,7 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
         KeyCode::Char('2') => app.active_tab = Tab::Systems,
         KeyCode::Char('3') => app.active_tab = Tab::Assets,
         KeyCode::Char('4') => app.active_tab = Tab::Logs,
+        KeyCode::Char('5') => app.active_tab = Tab::Diff,
 
         // Tab cycling.
         KeyCode::Tab => {
@@ -699,6 +1022,7 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
                 app.cursor = selectable.len() - 1;
             }
         }
+        KeyCode::Char('t') if app.active_tab == Tab::Overview => app.toggle_pool_trend(),
         KeyCode::Char('/') if app.active_tab == Tab::Overview => {
             if app.active_filter.is_some() {
                 app.active_filter = None;
@@ -757,6 +1081,24 @@ fn handle_key(app: &mut App, key: KeyEvent) -> bool {
             app.log_scroll_offset += 1;
         }
 
+        // Assets tab keys.
+        KeyCode::Char('v') if app.active_tab == Tab::Assets => app.cycle_shader_diff_view(),
+
+        // Diff tab keys.
+        KeyCode::Char('a') if app.active_tab == Tab::Diff => {
+            app.request_capture(CaptureSlot::Before);
+        }
+        KeyCode::Char('b') if app.active_tab == Tab::Diff => {
+            app.request_capture(CaptureSlot::After);
+        }
+        KeyCode::Char('x') if app.active_tab == Tab::Diff => app.clear_captures(),
+        KeyCode::Up if app.active_tab == Tab::Diff => {
+            app.diff_scroll_offset = app.diff_scroll_offset.saturating_sub(1);
+        }
+        KeyCode::Down if app.active_tab == Tab::Diff => {
+            app.diff_scroll_offset += 1;
+        }
+
         _ => {}
     }
     false
@@ -887,6 +1229,7 @@ fn ui(f: &mut ratatui::Frame, app: &App) {
         Tab::Systems => draw_systems_tab(f, app, chunks[2]),
         Tab::Assets => draw_assets_tab(f, app, chunks[2]),
         Tab::Logs => draw_logs_tab(f, app, chunks[2]),
+        Tab::Diff => draw_diff_tab(f, app, chunks[2]),
     }
 
     draw_render_panel(f, app, chunks[3]);
@@ -944,8 +1287,23 @@ fn draw_header(f: &mut ratatui::Frame, app: &App, area: Rect) {
         ),
     ]);
 
+    // e.g. " necs-telemetry ─ necs 0.1.0 3f9c2ab1e debug on Vulkan [render2d, audio] "
+    let title = match &app.build {
+        Some(build) => {
+            let mut title = format!(" necs-telemetry ─ necs {}", build.version);
+            if let Some(hash) = &build.git_hash {
+                title += &format!(" {hash}");
+            }
+            title += &format!(" {}", build.profile);
+            if let Some(backend) = &build.backend {
+                title += &format!(" on {backend}");
+            }
+            title + &format!(" [{}] ", build.features.join(", "))
+        }
+        None => " necs-telemetry ".to_string(),
+    };
     let block = Block::default()
-        .title(" necs-telemetry ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
     let paragraph = Paragraph::new(text).block(block);
@@ -984,8 +1342,10 @@ fn draw_tab_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
 // ── Overview Tab ─────────────────────────────────────────────────────────
 
 fn draw_overview_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
-    // Split into: sparklines, entity pool line, hierarchy/scene info, ECS tree
+    // Split into: sparklines, entity pool line, pool trend, hierarchy/scene
+    // info, ECS tree
     let has_pool = app.latest.entity_pool.is_some();
+    let has_trend = has_pool && app.show_pool_trend;
     let has_hierarchy = app.latest.hierarchy.is_some();
     let has_scene = app.latest.scene.is_some();
     let info_lines = (has_hierarchy as u16) + (has_scene as u16);
@@ -996,6 +1356,9 @@ fn draw_overview_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
     if has_pool {
         constraints.push(Constraint::Length(1)); // entity pool line
     }
+    if has_trend {
+        constraints.push(Constraint::Length(6)); // pool trend
+    }
     if info_lines > 0 {
         constraints.push(Constraint::Length(info_lines)); // hierarchy/scene info
     }
@@ -1015,6 +1378,11 @@ fn draw_overview_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
         chunk_idx += 1;
     }
 
+    if has_trend {
+        draw_pool_trend(f, app, chunks[chunk_idx]);
+        chunk_idx += 1;
+    }
+
     if info_lines > 0 {
         draw_hierarchy_scene_info(f, app, chunks[chunk_idx]);
         chunk_idx += 1;
@@ -1158,11 +1526,112 @@ fn draw_entity_pool_line(f: &mut ratatui::Frame, app: &App, area: Rect) {
     f.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
+/// Archetype count and fragmentation over the pool history, for spotting
+/// slow leaks a single snapshot can't show.
+fn draw_pool_trend(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    let span = match (app.pool_trend.front(), app.pool_trend.back()) {
+        (Some(first), Some(last)) => format_uptime(last.0 - first.0),
+        _ => "waiting for history".to_string(),
+    };
+
+    let archetypes: Vec<u64> = app.pool_trend.iter().map(|s| s.2 as u64).collect();
+    let entities: Vec<u64> = app.pool_trend.iter().map(|s| s.1 as u64).collect();
+    let arch_summary = match (archetypes.first(), archetypes.last(), entities.last()) {
+        (Some(first), Some(last), Some(alive)) => format!(
+            "{first} \u{2192} {last} ({:+})  entities: {alive}",
+            *last as i64 - *first as i64
+        ),
+        _ => String::new(),
+    };
+    draw_trend_panel(
+        f,
+        chunks[0],
+        &format!(" Archetypes ({span}) "),
+        &archetypes,
+        arch_summary,
+        Color::Cyan,
+    );
+
+    // Tenths of a percent so the sparkline keeps some resolution.
+    let frag: Vec<u64> = app
+        .pool_trend
+        .iter()
+        .map(|s| (s.3 * 10.0).round().max(0.0) as u64)
+        .collect();
+    let (f_min, f_avg, f_max) = stats(&frag);
+    draw_trend_panel(
+        f,
+        chunks[1],
+        &format!(" Fragmentation ({span}) "),
+        &frag,
+        format!(
+            "min: {:.0}%  avg: {:.0}%  max: {:.0}%",
+            f_min / 10.0,
+            f_avg / 10.0,
+            f_max / 10.0
+        ),
+        Color::Magenta,
+    );
+}
+
+fn draw_trend_panel(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    title: &str,
+    data: &[u64],
+    summary: String,
+    color: Color,
+) {
+    let block = Block::default()
+        .title(title.to_string())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    if inner.height < 2 {
+        return;
+    }
+    let spark_area = Rect { height: inner.height - 1, ..inner };
+    let stats_area = Rect {
+        y: inner.y + inner.height - 1,
+        height: 1,
+        ..inner
+    };
+    let data = downsample_max(data, inner.width as usize);
+    f.render_widget(
+        Sparkline::default().data(&data).style(Style::default().fg(color)),
+        spark_area,
+    );
+    f.render_widget(
+        Paragraph::new(Span::styled(summary, Style::default().fg(Color::DarkGray))),
+        stats_area,
+    );
+}
+
+/// Squeeze `data` into at most `width` columns, keeping each bucket's
+/// maximum so short spikes survive.
+fn downsample_max(data: &[u64], width: usize) -> Vec<u64> {
+    if width == 0 || data.len() <= width {
+        return data.to_vec();
+    }
+    (0..width)
+        .map(|col| {
+            let start = col * data.len() / width;
+            let end = ((col + 1) * data.len() / width).max(start + 1);
+            data[start..end].iter().copied().max().unwrap_or(0)
+        })
+        .collect()
+}
+
 fn draw_hierarchy_scene_info(f: &mut ratatui::Frame, app: &App, area: Rect) {
     let mut lines: Vec<Line> = Vec::new();
 
     if let Some(h) = &app.latest.hierarchy {
-        lines.push(Line::from(vec![
+        let mut spans = vec![
             Span::styled("  Hierarchy: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
                 format!("{}", h.root_count),
@@ -1178,7 +1647,22 @@ fn draw_hierarchy_scene_info(f: &mut ratatui::Frame, app: &App, area: Rect) {
                 format!("{}", h.max_depth),
                 Style::default().fg(Color::White),
             ),
-        ]));
+        ];
+        if let Some(p) = &app.latest.propagation {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled("Propagation: ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{} updated in {:.0} µs", p.updated, p.duration_us),
+                Style::default().fg(Color::White),
+            ));
+            if p.deferred_roots > 0 {
+                spans.push(Span::styled(
+                    format!(", {}/{} roots deferred", p.deferred_roots, p.roots),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+        }
+        lines.push(Line::from(spans));
     }
 
     if let Some(s) = &app.latest.scene {
@@ -1599,14 +2083,81 @@ fn draw_system_timings(f: &mut ratatui::Frame, app: &App, area: Rect) {
 
 // ── Assets Tab ───────────────────────────────────────────────────────────
 
+/// Maximum dependency edges shown in the watched-assets panel.
+const MAX_DEPENDENCY_LINES: usize = 8;
+
 fn draw_assets_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    // Grow the watched panel to fit the dependency graph (capped).
+    let dep_lines = app
+        .latest
+        .assets
+        .as_ref()
+        .map(|a| a.dependencies.len().min(MAX_DEPENDENCY_LINES))
+        .unwrap_or(0);
+    let diff_lines = match &app.latest.shader_diff {
+        Some(diff) if diff.saved.is_some() => 4,
+        Some(_) => 3,
+        None => 0,
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(6), Constraint::Min(4)])
+        .constraints([
+            Constraint::Length(6 + dep_lines as u16),
+            Constraint::Length(diff_lines),
+            Constraint::Min(4),
+        ])
         .split(area);
 
     draw_watched_assets(f, app, chunks[0]);
-    draw_reload_log(f, app, chunks[1]);
+    if let Some(diff) = &app.latest.shader_diff {
+        draw_shader_diff(f, diff, chunks[1]);
+    }
+    draw_reload_log(f, app, chunks[2]);
+}
+
+fn draw_shader_diff(f: &mut ratatui::Frame, diff: &ShaderDiffInfo, area: Rect) {
+    let block = Block::default()
+        .title(format!(" Shader A/B: {} ", diff.shader))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let view_style = |view: &str| {
+        if diff.view == view {
+            Style::default().bg(Color::Cyan).fg(Color::Black)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled("  Showing: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(" live ", view_style("live")),
+        Span::styled(" before ", view_style("before")),
+        Span::styled(" after ", view_style("after")),
+        Span::styled(
+            format!("   {}x{}  ", diff.width, diff.height),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(
+            format!("{:.1}% changed", diff.changed_percent),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            format!(", mean Δ {:.2}, max Δ {}", diff.mean_delta, diff.max_delta),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ])];
+    if let Some((before, after)) = &diff.saved {
+        lines.push(Line::from(vec![
+            Span::styled("  Saved: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(before.clone(), Style::default().fg(Color::White)),
+            Span::styled(", ", Style::default().fg(Color::DarkGray)),
+            Span::styled(after.clone(), Style::default().fg(Color::White)),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn draw_watched_assets(f: &mut ratatui::Frame, app: &App, area: Rect) {
@@ -1672,6 +2223,23 @@ fn draw_watched_assets(f: &mut ratatui::Frame, app: &App, area: Rect) {
         ]));
     }
 
+    // Dependency graph edges: dependent ← dependency.
+    for (i, (dependent, dependency)) in assets.dependencies.iter().enumerate() {
+        if i == MAX_DEPENDENCY_LINES {
+            break;
+        }
+        let label = if i == 0 { "Depends" } else { "" };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:12}", label),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(dependent.clone(), Style::default().fg(Color::White)),
+            Span::styled(" ← ", Style::default().fg(Color::DarkGray)),
+            Span::styled(dependency.clone(), Style::default().fg(Color::Cyan)),
+        ]));
+    }
+
     f.render_widget(Paragraph::new(lines), inner);
 }
 
@@ -1796,6 +2364,152 @@ fn draw_logs_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
     f.render_widget(Paragraph::new(lines), inner);
 }
 
+// ── Diff Tab ─────────────────────────────────────────────────────────────
+
+fn draw_diff_tab(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Min(3)])
+        .split(area);
+
+    // Capture status.
+    let slot_line = |label: &'static str, key: &'static str, capture: &Option<WorldCapture>| {
+        let status = match capture {
+            Some(c) => Span::styled(
+                format!("frame {}  ({} entities)", c.frame_count, c.entities.len()),
+                Style::default().fg(Color::White),
+            ),
+            None => Span::styled(
+                format!("not captured \u{2014} press [{}]", key),
+                Style::default().fg(Color::DarkGray),
+            ),
+        };
+        Line::from(vec![
+            Span::styled(format!("  {:<8}", label), Style::default().fg(Color::DarkGray)),
+            status,
+        ])
+    };
+    let mut status_lines = vec![
+        slot_line("Before:", "a", &app.capture_before),
+        slot_line("After:", "b", &app.capture_after),
+    ];
+    if let Some(pending) = &app.pending_capture {
+        let (received, total) = pending.progress();
+        status_lines[match pending.slot {
+            CaptureSlot::Before => 0,
+            CaptureSlot::After => 1,
+        }]
+        .spans
+        .push(Span::styled(
+            format!("  capturing {}\u{2026} {}/{}", pending.slot.label(), received, total.max(1)),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let status_block = Block::default()
+        .title(" Captures ")
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    f.render_widget(Paragraph::new(status_lines).block(status_block), chunks[0]);
+
+    // Diff tree.
+    let (added, removed, changed) = diff::summary(&app.diff);
+    let block = Block::default()
+        .title(format!(
+            " Diff  +{} added  -{} removed  ~{} changed ",
+            added, removed, changed
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    let inner = block.inner(chunks[1]);
+    f.render_widget(block, chunks[1]);
+
+    let message = match (&app.capture_before, &app.capture_after) {
+        (Some(_), Some(_)) if app.diff.is_empty() => Some("  No differences"),
+        (Some(_), Some(_)) => None,
+        _ => Some("  Capture both snapshots to see a diff"),
+    };
+    if let Some(message) = message {
+        let text = Span::styled(message, Style::default().fg(Color::DarkGray));
+        f.render_widget(Paragraph::new(text), inner);
+        return;
+    }
+
+    let lines = diff_lines(&app.diff, inner.width as usize);
+    let visible = inner.height as usize;
+    let offset = app.diff_scroll_offset.min(lines.len().saturating_sub(visible));
+    let lines: Vec<Line> = lines.into_iter().skip(offset).take(visible).collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+/// Flatten the diff into entity → component → field lines.
+fn diff_lines(diffs: &[EntityDiff], width: usize) -> Vec<Line<'static>> {
+    let change_color = |change| match change {
+        Change::Added => Color::Green,
+        Change::Removed => Color::Red,
+        Change::Changed => Color::Yellow,
+    };
+    let value_width = width.saturating_sub(12).max(16) / 2;
+
+    let mut lines = Vec::new();
+    for entity in diffs {
+        let color = change_color(entity.change);
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} ", entity.change.symbol()),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("Entity {}v{}", entity.id, entity.generation),
+                Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+
+        for component in &entity.components {
+            let color = change_color(component.change);
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("     {} ", component.change.symbol()),
+                    Style::default().fg(color),
+                ),
+                Span::styled(component.name.clone(), Style::default().fg(Color::Cyan)),
+            ]));
+
+            for field in &component.fields {
+                let mut spans = vec![Span::raw("         ")];
+                if !field.path.is_empty() {
+                    spans.push(Span::styled(
+                        format!("{}: ", field.path),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                match (&field.before, &field.after) {
+                    (Some(before), Some(after)) => {
+                        spans.push(Span::styled(
+                            compact_preview(before, value_width),
+                            Style::default().fg(Color::Red),
+                        ));
+                        spans.push(Span::styled(" \u{2192} ", Style::default().fg(Color::DarkGray)));
+                        spans.push(Span::styled(
+                            compact_preview(after, value_width),
+                            Style::default().fg(Color::Green),
+                        ));
+                    }
+                    (Some(value), None) | (None, Some(value)) => {
+                        let color = if field.before.is_some() { Color::Red } else { Color::Green };
+                        spans.push(Span::styled(
+                            compact_preview(value, value_width * 2),
+                            Style::default().fg(color),
+                        ));
+                    }
+                    (None, None) => {}
+                }
+                lines.push(Line::from(spans));
+            }
+        }
+    }
+    lines
+}
+
 // ── Render stats + help bar ──────────────────────────────────────────────
 
 fn draw_render_panel(f: &mut ratatui::Frame, app: &App, area: Rect) {
@@ -1805,7 +2519,7 @@ fn draw_render_panel(f: &mut ratatui::Frame, app: &App, area: Rect) {
         .border_style(Style::default().fg(Color::DarkGray));
 
     let text = if let Some(r) = &app.latest.render {
-        Line::from(vec![
+        let mut spans = vec![
             Span::styled("  Draw calls: ", Style::default().fg(Color::DarkGray)),
             Span::styled(format!("{}", r.draw_calls), Style::default().fg(Color::White)),
             Span::raw("  |  "),
@@ -1817,7 +2531,32 @@ fn draw_render_panel(f: &mut ratatui::Frame, app: &App, area: Rect) {
                 format!("{}", r.textures_loaded),
                 Style::default().fg(Color::White),
             ),
-        ])
+        ];
+        if r.meshes_culled > 0 {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled("Culled: ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{}/{}", r.meshes_culled, r.meshes_culled + r.meshes_submitted),
+                Style::default().fg(Color::White),
+            ));
+        }
+        if r.sprites_culled > 0 {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled("Sprites culled: ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{}/{}", r.sprites_culled, r.sprites_culled + r.sprites_submitted),
+                Style::default().fg(Color::White),
+            ));
+        }
+        if let Some(l) = &app.latest.input_latency {
+            spans.push(Span::raw("  |  "));
+            spans.push(Span::styled("Input→present: ", Style::default().fg(Color::DarkGray)));
+            spans.push(Span::styled(
+                format!("{:.1} ms (avg {:.1}, max {:.1})", l.last_ms, l.avg_ms, l.max_ms),
+                Style::default().fg(Color::White),
+            ));
+        }
+        Line::from(spans)
     } else {
         Line::from(Span::styled(
             "  No render stats (diagnostics not sending render data)",
@@ -1842,7 +2581,7 @@ fn draw_search_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
 
 fn draw_help_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
     let mut spans = vec![
-        Span::styled(" [1-4]", Style::default().fg(Color::Cyan)),
+        Span::styled(" [1-5]", Style::default().fg(Color::Cyan)),
         Span::raw(" tab  "),
         Span::styled("[Tab]", Style::default().fg(Color::Cyan)),
         Span::raw(" next  "),
@@ -1865,12 +2604,17 @@ fn draw_help_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
             }
             spans.push(Span::styled("[s]", Style::default().fg(Color::Cyan)));
             spans.push(Span::raw(" sort  "));
+            spans.push(Span::styled("[t]", Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(" pool trend  "));
         }
         Tab::Systems => {
             // No special keys for systems tab currently.
         }
         Tab::Assets => {
-            // No special keys for assets tab currently.
+            if app.latest.shader_diff.is_some() {
+                spans.push(Span::styled("[v]", Style::default().fg(Color::Cyan)));
+                spans.push(Span::raw(" shader A/B view  "));
+            }
         }
         Tab::Logs => {
             spans.push(Span::styled("[l]", Style::default().fg(Color::Cyan)));
@@ -1880,6 +2624,16 @@ fn draw_help_bar(f: &mut ratatui::Frame, app: &App, area: Rect) {
             spans.push(Span::styled("[\u{2191}\u{2193}]", Style::default().fg(Color::Cyan)));
             spans.push(Span::raw(" scroll  "));
         }
+        Tab::Diff => {
+            spans.push(Span::styled("[a]", Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(" capture before  "));
+            spans.push(Span::styled("[b]", Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(" capture after  "));
+            spans.push(Span::styled("[x]", Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(" clear  "));
+            spans.push(Span::styled("[\u{2191}\u{2193}]", Style::default().fg(Color::Cyan)));
+            spans.push(Span::raw(" scroll  "));
+        }
     }
 
     spans.push(Span::styled("[p]", Style::default().fg(Color::Cyan)));
@@ -0,0 +1,29 @@
+//! Records the git commit the engine is built from as `NECS_GIT_HASH`, for
+//! `necs::build_info()`. Outside a git checkout nothing is set.
+
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let git = |args: &[&str]| {
+        let output = Command::new("git").args(args).output().ok()?;
+        let text = String::from_utf8(output.stdout).ok()?;
+        (output.status.success() && !text.trim().is_empty()).then(|| text.trim().to_string())
+    };
+
+    if let Some(hash) = git(&["rev-parse", "--short=9", "HEAD"]) {
+        println!("cargo:rustc-env=NECS_GIT_HASH={hash}");
+    }
+
+    // Rerun when HEAD moves: a checkout changes HEAD, a commit changes the
+    // branch under refs/heads.
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        for watched in ["HEAD", "refs/heads", "packed-refs"] {
+            let path = Path::new(&git_dir).join(watched);
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
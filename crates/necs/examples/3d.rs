@@ -1,6 +1,7 @@
 //! Hello 3D — A lit rotating cube on a ground plane.
 //!
-//! Demonstrates Camera3d, Mesh3d, PBR Material, and lighting.
+//! Demonstrates Camera3d, Mesh3d, PBR Material, and lighting (directional,
+//! point and spot).
 
 use necs::prelude::*;
 
@@ -73,6 +74,19 @@ fn setup(ctx: &mut Context) {
             color: [1.0, 0.8, 0.6],
             intensity: 5.0,
             radius: 10.0,
+            ..Default::default()
+        });
+
+    // Spot light (cool, shining down on the sphere)
+    ctx.create()
+        .insert(Transform::from_xyz(2.0, 4.0, -1.0).looking_at(Vec3::new(2.0, 0.0, -1.0), Vec3::Z))
+        .insert(SpotLight {
+            color: [0.6, 0.8, 1.0],
+            intensity: 12.0,
+            range: 8.0,
+            inner_angle: 12.0,
+            outer_angle: 22.0,
+            attenuation: Attenuation::Smooth,
         });
 }
 
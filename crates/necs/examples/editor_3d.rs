@@ -128,6 +128,7 @@ fn setup(ctx: &mut Context) {
             color: [0.5, 0.7, 1.0],
             intensity: 50.0,
             radius: 20.0,
+            ..Default::default()
         })
         .tag("light");
 
@@ -0,0 +1,303 @@
+//! Example Gallery — every bundled example scene behind one menu.
+//!
+//! Click a scene to open it; Escape goes back to the menu. All scenes run
+//! in one game and share the files in `examples/assets`.
+//!
+//! With `--smoke-test <FRAMES>` the gallery runs headless instead: each
+//! scene in turn for that many frames, then it exits. A scene that panics
+//! fails the run, so CI can check that the features still work together.
+//! `cargo test --example gallery` does the same for a handful of frames.
+//! There is no GPU headless, so scenes skip loading textures and fonts.
+//!
+//! Run with: `cargo run -p necs --example gallery`
+//!
+//! Smoke test: `cargo run -p necs --example gallery -- --smoke-test 120`
+
+mod particles;
+mod pbr;
+#[cfg(feature = "physics2d")]
+mod physics;
+#[cfg(feature = "audio")]
+mod sound;
+mod sprites;
+mod ui_demo;
+
+use std::path::PathBuf;
+
+use necs::prelude::*;
+
+// ── Registry ─────────────────────────────────────────────────────────────
+
+/// One scene in the gallery. `setup` runs on an empty world; `update` runs
+/// every frame while the scene is open.
+struct Example {
+    name: &'static str,
+    description: &'static str,
+    setup: fn(&mut Context),
+    update: fn(&mut Context),
+}
+
+fn examples() -> Vec<Example> {
+    vec![
+        Example {
+            name: "Sprites",
+            description: "Textured and colored sprites with text",
+            setup: sprites::setup,
+            update: sprites::update,
+        },
+        Example {
+            name: "3D PBR",
+            description: "Metallic and rough materials under point and sun light",
+            setup: pbr::setup,
+            update: pbr::update,
+        },
+        Example {
+            name: "Particles",
+            description: "A fountain and a repeating firework burst",
+            setup: particles::setup,
+            update: particles::update,
+        },
+        Example {
+            name: "UI",
+            description: "Layout containers, labels and buttons",
+            setup: ui_demo::setup,
+            update: ui_demo::update,
+        },
+        #[cfg(feature = "physics2d")]
+        Example {
+            name: "Physics 2D",
+            description: "Balls raining into a fixed arena",
+            setup: physics::setup,
+            update: physics::update,
+        },
+        #[cfg(feature = "audio")]
+        Example {
+            name: "Spatial Audio",
+            description: "A looping sound circling the listener",
+            setup: sound::setup,
+            update: sound::update,
+        },
+    ]
+}
+
+// ── Gallery state ────────────────────────────────────────────────────────
+
+/// Which scene is open, and the one to switch to next frame.
+struct Gallery {
+    examples: Vec<Example>,
+    /// `None` is the menu.
+    active: Option<usize>,
+    pending: Option<Option<usize>>,
+    smoke_test: Option<SmokeTest>,
+}
+
+/// Frames per scene and frames left in the current one.
+struct SmokeTest {
+    frames: u32,
+    left: u32,
+}
+
+/// Menu button that opens an example.
+struct OpenExample(usize);
+
+/// The gallery font, loaded once and shared by every scene.
+struct GalleryFont(Option<FontHandle>);
+
+fn main() {
+    env_logger::init();
+    let smoke_frames = std::env::args()
+        .skip_while(|arg| arg != "--smoke-test")
+        .nth(1)
+        .map(|frames| frames.parse().expect("--smoke-test needs a frame count"));
+    gallery(smoke_frames).run();
+}
+
+/// The whole gallery as one game; headless when `smoke_frames` is set.
+fn gallery(smoke_frames: Option<u32>) -> Game {
+    let examples = examples();
+    let smoke_test = smoke_frames.map(|frames| SmokeTest { frames, left: frames });
+    let start = smoke_test.as_ref().map(|_| 0);
+    let mut game = Game::new("necs — example gallery (Escape for menu)")
+        .resource(ClearColor([0.08, 0.08, 0.12, 1.0]))
+        .resource(GalleryFont(None))
+        .resource(Gallery {
+            examples,
+            active: None,
+            pending: Some(start),
+            smoke_test,
+        })
+        .update(switch_scene)
+        .update(run_scene);
+
+    #[cfg(feature = "physics2d")]
+    {
+        game = game
+            .plugin(Physics2d)
+            .resource(PhysicsWorld2d::new().with_gravity(Vec2::new(0.0, -980.0)));
+    }
+    #[cfg(feature = "audio")]
+    {
+        game = game.plugin(Audio);
+    }
+    if smoke_frames.is_some() {
+        game = game.launch_options(LaunchOptions {
+            headless: true,
+            ..Default::default()
+        });
+    }
+    game
+}
+
+// ── Systems ──────────────────────────────────────────────────────────────
+
+/// Clear the world and set up the pending scene, if any.
+fn switch_scene(ctx: &mut Context) {
+    let gallery = ctx.world.resource_mut::<Gallery>();
+    let Some(target) = gallery.pending.take() else {
+        return;
+    };
+    gallery.active = target;
+    let setup = target.map(|i| (gallery.examples[i].name, gallery.examples[i].setup));
+
+    ctx.world.despawn_all();
+    match setup {
+        Some((name, setup)) => {
+            log::info!("Gallery: opening {name}");
+            setup(ctx);
+        }
+        None => spawn_menu(ctx),
+    }
+}
+
+/// Drive the open scene: menu clicks, Escape, and the smoke test clock.
+fn run_scene(ctx: &mut Context) {
+    let gallery = ctx.world.resource::<Gallery>();
+    let active = gallery.active;
+    let update = active.map(|i| gallery.examples[i].update);
+
+    match update {
+        Some(update) => {
+            update(ctx);
+            if ctx.input.just_pressed(KeyCode::Escape) {
+                ctx.world.resource_mut::<Gallery>().pending = Some(None);
+            }
+        }
+        None => {
+            let mut clicked = None;
+            ctx.world.query::<(&Button, &OpenExample)>(|_, (button, open)| {
+                if button.clicked() {
+                    clicked = Some(open.0);
+                }
+            });
+            if let Some(i) = clicked {
+                ctx.world.resource_mut::<Gallery>().pending = Some(Some(i));
+            }
+        }
+    }
+
+    advance_smoke_test(ctx);
+}
+
+/// Count down the current scene's frames, then move on or exit.
+fn advance_smoke_test(ctx: &mut Context) {
+    let gallery = ctx.world.resource_mut::<Gallery>();
+    let (Some(smoke), Some(active)) = (&mut gallery.smoke_test, gallery.active) else {
+        return;
+    };
+    smoke.left = smoke.left.saturating_sub(1);
+    if smoke.left > 0 {
+        return;
+    }
+    smoke.left = smoke.frames;
+    log::info!("Smoke test: {} ran {} frames", gallery.examples[active].name, smoke.frames);
+    if active + 1 < gallery.examples.len() {
+        gallery.pending = Some(Some(active + 1));
+    } else {
+        log::info!("Smoke test: all {} examples passed", gallery.examples.len());
+        ctx.exit();
+    }
+}
+
+// ── Menu ─────────────────────────────────────────────────────────────────
+
+fn spawn_menu(ctx: &mut Context) {
+    ctx.spawn("camera").insert(Transform::default()).insert(Camera2d);
+    let font = gallery_font(ctx);
+
+    let menu = ctx.world.spawn((UiNode::new()
+        .anchor(UiAnchor::Center)
+        .padding(UiEdges::all(24.0))
+        .gap(12.0)
+        .align(UiAlign::Stretch),
+        UiImage::solid(Color::rgba(0.12, 0.12, 0.16, 0.95)),
+    ));
+    if let Some(font) = font {
+        ctx.world.spawn_child(menu, (
+            UiNode::new().margin(UiEdges::axes(0.0, 8.0)),
+            UiText::new("necs example gallery", font),
+        ));
+    }
+
+    let entries: Vec<_> = ctx
+        .world
+        .resource::<Gallery>()
+        .examples
+        .iter()
+        .map(|example| (example.name, example.description))
+        .collect();
+    for (i, (name, description)) in entries.into_iter().enumerate() {
+        let button = ctx.world.spawn_child(menu, (
+            UiNode::new().width(UiSize::Px(420.0)).padding(UiEdges::all(10.0)).gap(4.0),
+            UiImage::solid(ButtonColors::default().normal),
+            Button::new().colors(ButtonColors::default()),
+            OpenExample(i),
+        ));
+        if let Some(font) = font {
+            ctx.world.spawn_child(button, (UiNode::new(), UiText::new(name, font)));
+            ctx.world.spawn_child(button, (
+                UiNode::new(),
+                UiText::new(description, font).color(Color::rgb(0.7, 0.7, 0.75)),
+            ));
+        }
+    }
+}
+
+// ── Shared helpers ───────────────────────────────────────────────────────
+
+/// Whether textures and fonts can be loaded (not in a headless run).
+pub fn can_load_assets(ctx: &Context) -> bool {
+    ctx.world
+        .get_resource::<LaunchOptions>()
+        .is_none_or(|options| !options.headless)
+}
+
+/// Path of a file in the shared `examples/assets` directory.
+pub fn asset_path(relative: &str) -> String {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join("assets")
+        .join(relative)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// The gallery font, loaded on first use. `None` when headless.
+pub fn gallery_font(ctx: &mut Context) -> Option<FontHandle> {
+    if let Some(font) = ctx.world.resource::<GalleryFont>().0 {
+        return Some(font);
+    }
+    if !can_load_assets(ctx) {
+        return None;
+    }
+    let font = ctx.load_font(&asset_path("LiberationSans-Regular.ttf"), 18.0);
+    ctx.world.resource_mut::<GalleryFont>().0 = Some(font);
+    Some(font)
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn every_example_runs_headless() {
+        super::gallery(Some(5)).run();
+    }
+}
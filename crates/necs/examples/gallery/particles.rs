@@ -0,0 +1,70 @@
+//! Particles — a fountain and a firework. Space pauses, R restarts.
+
+use necs::prelude::*;
+
+use crate::gallery_font;
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.04, 0.04, 0.08, 1.0]));
+    ctx.spawn("camera").insert(Transform::default()).insert(Camera2d);
+
+    // Fountain: a steady stream arcing up and falling back.
+    ctx.create()
+        .insert(Transform::from_xy(-200.0, -200.0))
+        .insert(
+            ParticleEmitter::new()
+                .rate(120.0)
+                .lifetime(1.6)
+                .lifetime_variance(0.2)
+                .velocity(Vec3::new(0.0, 420.0, 0.0))
+                .velocity_variance(Vec3::new(60.0, 40.0, 0.0))
+                .gravity(Vec3::new(0.0, -400.0, 0.0))
+                .size_over_life(ParticleCurve::linear(10.0, 3.0))
+                .color_over_life(ParticleCurve::linear([0.4, 0.7, 1.0, 1.0], [0.1, 0.2, 0.8, 0.0])),
+        )
+        .tag("emitter");
+
+    // Firework: a burst every two seconds that flares and fades.
+    ctx.create()
+        .insert(Transform::from_xy(200.0, 100.0))
+        .insert(
+            ParticleEmitter::new()
+                .burst_every(150, 2.0)
+                .lifetime(1.2)
+                .velocity(Vec3::ZERO)
+                .velocity_variance(Vec3::new(260.0, 260.0, 0.0))
+                .gravity(Vec3::new(0.0, -120.0, 0.0))
+                .speed_over_life(ParticleCurve::linear(1.0, 0.1))
+                .size_over_life(ParticleCurve::linear(4.0, 1.0).key(0.1, 9.0))
+                .color_over_life(
+                    ParticleCurve::linear([1.0, 1.0, 0.6, 1.0], [1.0, 0.1, 0.0, 0.0])
+                        .key(0.4, [1.0, 0.5, 0.1, 1.0]),
+                ),
+        )
+        .tag("emitter");
+
+    if let Some(font) = gallery_font(ctx) {
+        ctx.create()
+            .insert(Transform::from_xyz(-160.0, -280.0, 1.0))
+            .insert(Text::new("Space: pause   R: restart", font));
+    }
+}
+
+pub fn update(ctx: &mut Context) {
+    let pause = ctx.input.just_pressed(KeyCode::Space);
+    let restart = ctx.input.just_pressed(KeyCode::KeyR);
+    for entity in ctx.world.tagged("emitter") {
+        if let Some(emitter) = ctx.world.get_mut::<ParticleEmitter>(entity) {
+            if pause {
+                if emitter.paused {
+                    emitter.resume();
+                } else {
+                    emitter.pause();
+                }
+            }
+            if restart {
+                emitter.restart();
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! 3D PBR — spheres from rough to smooth and dielectric to metal, lit by a
+//! sun and an orbiting point light.
+
+use necs::prelude::*;
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.1, 0.1, 0.15, 1.0]));
+    ctx.world.insert_resource(AmbientLight {
+        color: [1.0, 1.0, 1.0],
+        intensity: 0.05,
+    });
+    ctx.spawn("camera")
+        .insert(Transform::from_xyz(0.0, 3.0, 7.0).looking_at(Vec3::new(0.0, 0.5, 0.0), Vec3::Y))
+        .insert(Camera3d::default());
+
+    ctx.create()
+        .insert(Transform::default().with_scale(12.0))
+        .insert(Mesh3d::plane())
+        .insert(Material {
+            base_color: [0.4, 0.4, 0.4, 1.0],
+            roughness: 0.9,
+            ..Default::default()
+        });
+
+    // Roughness rises left to right; the back row is metal.
+    for row in 0..2 {
+        for col in 0..5 {
+            let roughness = 0.1 + col as f32 * 0.2;
+            ctx.create()
+                .insert(Transform::from_xyz(col as f32 * 1.2 - 2.4, 0.5, -(row as f32) * 1.4))
+                .insert(Mesh3d::sphere())
+                .insert(Material {
+                    base_color: [0.9, 0.6, 0.2, 1.0],
+                    metallic: row as f32,
+                    roughness,
+                    ..Default::default()
+                });
+        }
+    }
+
+    ctx.create().insert(DirectionalLight {
+        direction: Vec3::new(-0.5, -1.0, -0.3),
+        color: [1.0, 0.98, 0.95],
+        intensity: 1.2,
+    });
+    ctx.create()
+        .insert(Transform::from_xyz(0.0, 2.0, 2.0))
+        .insert(PointLight {
+            color: [1.0, 0.7, 0.4],
+            intensity: 4.0,
+            radius: 8.0,
+            ..Default::default()
+        })
+        .tag("orbit");
+}
+
+pub fn update(ctx: &mut Context) {
+    let t = ctx.time.elapsed_secs();
+    for entity in ctx.world.tagged("orbit") {
+        if let Some(transform) = ctx.world.get_mut::<Transform>(entity) {
+            transform.translation = Vec3::new(t.cos() * 3.0, 2.0, t.sin() * 3.0);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Physics 2D — balls drop into a fixed arena, a few per second.
+
+use necs::prelude::*;
+
+/// Most balls alive at once; older ones are despawned to make room.
+const MAX_BALLS: usize = 80;
+
+/// Seconds until the next ball drops.
+struct DropTimer(f32);
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.08, 0.08, 0.12, 1.0]));
+    ctx.world.insert_resource(DropTimer(0.0));
+    ctx.spawn("camera").insert(Transform::default()).insert(Camera2d);
+
+    let wall = Color::rgb(0.25, 0.25, 0.3);
+    for (x, y, hx, hy) in [
+        (0.0, -250.0, 350.0, 20.0),
+        (-330.0, 0.0, 20.0, 300.0),
+        (330.0, 0.0, 20.0, 300.0),
+    ] {
+        ctx.world.spawn((
+            Transform::from_xy(x, y),
+            RigidBody2d::fixed(),
+            Collider2d::cuboid(hx, hy),
+            Shape2d::rectangle(hx * 2.0, hy * 2.0).color(wall),
+        ));
+    }
+}
+
+pub fn update(ctx: &mut Context) {
+    let timer = ctx.world.resource_mut::<DropTimer>();
+    timer.0 -= ctx.time.delta_secs();
+    if timer.0 > 0.0 {
+        return;
+    }
+    timer.0 = 0.15;
+
+    let balls = ctx.world.tagged("ball");
+    if balls.len() >= MAX_BALLS {
+        ctx.world.despawn(balls[0]);
+    }
+    let n = ctx.time.frame_count();
+    let x = ((n * 37) % 500) as f32 - 250.0;
+    let hue = (n % 7) as f32 / 7.0;
+    let ball = ctx.world.spawn((
+        Transform::from_xy(x, 280.0),
+        RigidBody2d::dynamic(),
+        Collider2d::ball(12.0),
+        Shape2d::circle(12.0).color(Color::rgb(hue, 0.6, 1.0 - hue)),
+    ));
+    ctx.world.tag(ball, "ball");
+}
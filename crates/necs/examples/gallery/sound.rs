@@ -0,0 +1,43 @@
+//! Spatial Audio — a looping sound circles the listener; hear it pan from
+//! side to side and fade as it moves away.
+
+use necs::prelude::*;
+
+use crate::asset_path;
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.08, 0.06, 0.12, 1.0]));
+    ctx.spawn("camera")
+        .insert(Transform::default())
+        .insert(Camera2d)
+        .insert(AudioListener);
+
+    // The listener, drawn at the center.
+    ctx.create()
+        .insert(Transform::default())
+        .insert(Shape2d::circle(16.0).color(Color::WHITE));
+
+    let music = match SoundData::from_file(asset_path("sounds/music.ogg")) {
+        Ok(music) => music,
+        Err(e) => {
+            log::warn!("Spatial audio example: {e}");
+            return;
+        }
+    };
+    ctx.spawn("emitter")
+        .insert(Transform::from_xy(250.0, 0.0))
+        .insert(Shape2d::circle(12.0).color(Color::rgb(1.0, 0.6, 0.2)))
+        .insert(AudioSource::new(music).auto_play().looping())
+        .insert(SpatialAudioSource::new(600.0).min_distance(60.0));
+}
+
+pub fn update(ctx: &mut Context) {
+    let t = ctx.time.elapsed_secs() * 0.8;
+    let Some(emitter) = ctx.world.try_named("emitter") else {
+        return;
+    };
+    if let Some(transform) = ctx.world.get_mut::<Transform>(emitter) {
+        // An ellipse, so it comes close on one side and far on the other.
+        transform.translation = Vec3::new(t.cos() * 350.0 + 100.0, t.sin() * 150.0, 0.0);
+    }
+}
@@ -0,0 +1,45 @@
+//! Sprites — a ring of colored quads around a textured sprite, with text.
+
+use necs::prelude::*;
+
+use crate::{asset_path, can_load_assets, gallery_font};
+
+const RING: usize = 12;
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.15, 0.15, 0.2, 1.0]));
+    ctx.spawn("camera").insert(Transform::default()).insert(Camera2d);
+
+    // Center sprite, textured when there's a GPU to upload to.
+    let mut center = Sprite::new().size(128.0, 128.0);
+    if can_load_assets(ctx) {
+        center = center.texture(ctx.load_texture(&asset_path("test.png")));
+    }
+    ctx.create().insert(Transform::default()).insert(center);
+
+    // Ring of colored quads, spun by `update`.
+    for i in 0..RING {
+        let t = i as f32 / RING as f32;
+        let angle = t * std::f32::consts::TAU;
+        ctx.create()
+            .insert(Transform::from_xy(angle.cos() * 220.0, angle.sin() * 220.0))
+            .insert(Sprite::new().color(Color::rgb(t, 0.4, 1.0 - t)).size(40.0, 40.0))
+            .tag("ring");
+    }
+
+    if let Some(font) = gallery_font(ctx) {
+        ctx.create()
+            .insert(Transform::from_xyz(-120.0, -280.0, 1.0))
+            .insert(Text::new("Sprites, textures and text", font));
+    }
+}
+
+pub fn update(ctx: &mut Context) {
+    let spin = Quat::from_rotation_z(0.5 * ctx.time.delta_secs());
+    for entity in ctx.world.tagged("ring") {
+        if let Some(transform) = ctx.world.get_mut::<Transform>(entity) {
+            transform.translation = spin * transform.translation;
+            transform.rotation *= spin;
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! UI — a top bar, a counter label and buttons that change it.
+
+use necs::prelude::*;
+
+use crate::gallery_font;
+
+/// The count shown in the panel.
+struct Counter(i32);
+
+/// What a panel button does to the counter.
+#[derive(Clone, Copy)]
+enum CounterButton {
+    Add(i32),
+    Reset,
+}
+
+pub fn setup(ctx: &mut Context) {
+    ctx.world.insert_resource(ClearColor([0.1, 0.12, 0.1, 1.0]));
+    ctx.world.insert_resource(Counter(0));
+    ctx.spawn("camera").insert(Transform::default()).insert(Camera2d);
+    let font = gallery_font(ctx);
+
+    // Full-width bar along the top, items pushed to either end.
+    let bar = ctx.world.spawn((
+        UiNode::new()
+            .row()
+            .width(UiSize::Percent(1.0))
+            .anchor(UiAnchor::Top)
+            .padding(UiEdges::axes(16.0, 8.0))
+            .justify(UiJustify::SpaceBetween),
+        UiImage::solid(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+    ));
+
+    // Centered panel: the count above a row of buttons.
+    let panel = ctx.world.spawn((
+        UiNode::new()
+            .anchor(UiAnchor::Center)
+            .padding(UiEdges::all(20.0))
+            .gap(16.0)
+            .align(UiAlign::Center),
+        UiImage::solid(Color::rgba(0.15, 0.18, 0.15, 0.95)),
+    ));
+    let buttons = ctx
+        .world
+        .spawn_child(panel, (UiNode::new().row().gap(8.0),));
+    for (label, action) in [
+        ("-1", CounterButton::Add(-1)),
+        ("+1", CounterButton::Add(1)),
+        ("Reset", CounterButton::Reset),
+    ] {
+        let button = ctx.world.spawn_child(buttons, (
+            UiNode::new().size(96.0, 40.0).padding(UiEdges::all(10.0)),
+            UiImage::solid(ButtonColors::default().normal),
+            Button::new().colors(ButtonColors::default()),
+            action,
+        ));
+        if let Some(font) = font {
+            ctx.world.spawn_child(button, (UiNode::new(), UiText::new(label, font)));
+        }
+    }
+
+    let Some(font) = font else {
+        return;
+    };
+    ctx.world.spawn_child(bar, (UiNode::new(), UiText::new("UI demo", font)));
+    ctx.world.spawn_child(bar, (UiNode::new(), UiText::new("Escape: menu", font)));
+    let count = ctx
+        .world
+        .spawn_child(panel, (UiNode::new(), UiText::new("Count: 0", font)));
+    ctx.world.tag(count, "count");
+}
+
+pub fn update(ctx: &mut Context) {
+    let mut pressed = None;
+    ctx.world.query::<(&Button, &CounterButton)>(|_, (button, action)| {
+        if button.clicked() {
+            pressed = Some(*action);
+        }
+    });
+    let Some(action) = pressed else {
+        return;
+    };
+
+    let counter = ctx.world.resource_mut::<Counter>();
+    counter.0 = match action {
+        CounterButton::Add(n) => counter.0 + n,
+        CounterButton::Reset => 0,
+    };
+    let label = format!("Count: {}", counter.0);
+    for entity in ctx.world.tagged("count") {
+        if let Some(text) = ctx.world.get_mut::<UiText>(entity) {
+            text.content = label.clone();
+        }
+    }
+}
@@ -40,7 +40,12 @@ fn setup(ctx: &mut Context) {
     // Point light
     ctx.create()
         .insert(Transform::from_xyz(4.0, 5.0, 4.0))
-        .insert(PointLight { color: [1.0, 0.9, 0.7], intensity: 8.0, radius: 20.0 });
+        .insert(PointLight {
+            color: [1.0, 0.9, 0.7],
+            intensity: 8.0,
+            radius: 20.0,
+            ..Default::default()
+        });
 
     spawn_ground_and_pyramid(&mut ctx.world);
 }
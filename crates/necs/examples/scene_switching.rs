@@ -78,6 +78,7 @@ fn make_scene_entity(
         id,
         components,
         children: vec![],
+        ..Default::default()
     }
 }
 
@@ -89,6 +90,7 @@ fn menu_scene_data() -> SceneData {
             make_scene_entity(2, 0.0, -100.0, 0.8, 0.2, 0.2, 200.0, 60.0, "Quit"),
             make_scene_entity(3, 0.0, 160.0, 0.9, 0.9, 0.2, 300.0, 20.0, "TitleBar"),
         ],
+        ..Default::default()
     }
 }
 
@@ -103,6 +105,7 @@ fn game_scene_data() -> SceneData {
             make_scene_entity(5, -180.0, -100.0, 0.35, 0.3, 0.3, 80.0, 20.0, "Platform-L"),
             make_scene_entity(6, 120.0, -80.0, 0.35, 0.3, 0.3, 100.0, 20.0, "Platform-R"),
         ],
+        ..Default::default()
     }
 }
 
@@ -31,10 +31,20 @@ fn setup(ctx: &mut Context) {
     });
     ctx.create()
         .insert(Transform::from_xyz(4.0, 4.0, 4.0))
-        .insert(PointLight { color: [1.0, 0.85, 0.7], intensity: 8.0, radius: 15.0 });
+        .insert(PointLight {
+            color: [1.0, 0.85, 0.7],
+            intensity: 8.0,
+            radius: 15.0,
+            ..Default::default()
+        });
     ctx.create()
         .insert(Transform::from_xyz(-4.0, 3.0, -2.0))
-        .insert(PointLight { color: [0.6, 0.8, 1.0], intensity: 6.0, radius: 12.0 });
+        .insert(PointLight {
+            color: [0.6, 0.8, 1.0],
+            intensity: 6.0,
+            radius: 12.0,
+            ..Default::default()
+        });
 
     // Ground plane
     ctx.create()
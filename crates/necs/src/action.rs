@@ -0,0 +1,622 @@
+//! # Actions — Named Input Bindings from a Hot-Reloaded File
+//!
+//! Game code that asks "is Space held?" hard-codes the keyboard layout into
+//! every system. Asking "is `jump` held?" instead lets the keys change
+//! without touching code — and when the bindings live in a file watched by
+//! the [`AssetServer`](crate::asset::AssetServer), they change while the game
+//! runs: edit the file, save, press the new key.
+//!
+//! Bindings are grouped into *contexts* — sets of actions that make sense
+//! together, like "gameplay" and "menu". The same key can mean different
+//! things in different contexts; only active contexts are checked.
+//!
+//! ```text
+//!   bindings.ron ──► load_actions ──► ActionMap resource ◄── AssetServer
+//!                                        │                   (reload on save)
+//!                                        ▼
+//!   ctx.action_pressed("jump") ──► active contexts ──► any binding held?
+//!                                  gameplay: jump = [Key(Space), Key(KeyW)]
+//!                                  menu:     (inactive, skipped)
+//! ```
+//!
+//! The file is [RON](https://github.com/ron-rs/ron). Key names are winit's
+//! [`KeyCode`] variants, mouse buttons are [`MouseButton`] variants,
+//! gamepad buttons are [`GamepadButton`] variants and sticks and triggers
+//! are [`GamepadAxis`] variants, pushed in their positive (`Axis`) or
+//! negative (`AxisNegative`) direction:
+//!
+//! ```text
+//! (
+//!     contexts: {
+//!         "gameplay": {
+//!             "jump": [Key(Space), Key(KeyW), Gamepad(South)],
+//!             "fire": [Mouse(Left), Key(KeyJ), Gamepad(RightTrigger)],
+//!             "left": [Key(KeyA), AxisNegative(LeftStickX)],
+//!             "right": [Key(KeyD), Axis(LeftStickX)],
+//!         },
+//!         "menu": {
+//!             "confirm": [Key(Enter)],
+//!             "back": [Key(Escape)],
+//!         },
+//!     },
+//! )
+//! ```
+//!
+//! ```ignore
+//! fn setup(ctx: &mut Context) {
+//!     ctx.load_actions("bindings.ron");
+//! }
+//!
+//! fn player(ctx: &mut Context) {
+//!     if ctx.action_just_pressed("jump") {
+//!         // ...
+//!     }
+//! }
+//! ```
+//!
+//! ## Analog Values
+//!
+//! Every binding also has a value from 0 to 1: 1 for a held key or button,
+//! how far a stick is pushed in the bound direction past a small deadzone.
+//! [`ActionMap::value`] is the largest over an action's bindings, and
+//! [`ActionMap::axis`] combines two opposite actions into -1 to 1 — the
+//! same code then moves at full speed on a keyboard and proportionally on a
+//! stick. A stick binding counts as *pressed* once pushed half way:
+//!
+//! ```ignore
+//! let run = ctx.action_axis("left", "right"); // -1..1
+//! player.velocity.x = run * SPEED;
+//! ```
+//!
+//! ## Rebinding and Saving
+//!
+//! Bindings can change at run time: [`ActionMap::rebind`] swaps one input
+//! for another, and [`Binding::first_just_pressed`] reports whatever the
+//! player pressed this frame, for "press a key" menus. Write the result
+//! back with [`ActionMap::save`] or
+//! [`Context::save_actions`](crate::context::Context::save_actions):
+//!
+//! ```ignore
+//! fn rebind_jump(ctx: &mut Context) {
+//!     if let Some(binding) = Binding::first_just_pressed(&ctx.input) {
+//!         let map = ctx.world.resource_mut::<ActionMap>();
+//!         map.rebind("gameplay", "jump", Binding::Key(KeyCode::Space), binding);
+//!         ctx.save_actions("bindings.ron");
+//!     }
+//! }
+//! ```
+//!
+//! ## Contexts and Conflicts
+//!
+//! Every context is active until the game narrows it with
+//! [`ActionMap::set_active_contexts`]. A key bound to two actions in the same
+//! context is a *conflict*: it is logged on load and reload, and listed (with
+//! every context's bindings) in the editor's Input Bindings window. A file
+//! that fails to parse on reload is logged and the previous bindings stay.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The Input System's `.inputactions` asset — action maps
+//!   (contexts) of actions with bindings, edited in a dedicated window.
+//! - **Bevy**: No built-in action layer; `leafwing-input-manager` provides
+//!   one, configured in code.
+//! - **Godot**: The `InputMap` singleton of actions with events and a
+//!   deadzone each; `Input.get_axis` combines two actions into one value.
+//! - **Our approach**: A plain text file of contexts → actions → keys,
+//!   queried by name and hot-reloaded like any other asset, with Godot-style
+//!   values for analog input.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetServer;
+use crate::context::InputState;
+use crate::ecs::World;
+use crate::input::{GamepadAxis, GamepadButton, InputEvent, KeyCode, MouseButton};
+
+/// Stick values closer to rest than this count as 0.
+pub const DEFAULT_DEADZONE: f32 = 0.15;
+
+/// Stick value at which an axis binding counts as pressed.
+const AXIS_PRESS: f32 = 0.5;
+
+/// One physical input an action can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+    Gamepad(GamepadButton),
+    /// A stick or trigger pushed toward positive values.
+    Axis(GamepadAxis),
+    /// A stick pushed toward negative values (left or down).
+    AxisNegative(GamepadAxis),
+}
+
+impl Binding {
+    /// The first input pressed this frame, for capturing a new binding: a
+    /// key, mouse or gamepad button, or a stick pushed past half way.
+    pub fn first_just_pressed(input: &InputState) -> Option<Binding> {
+        let pressed = input.events().iter().find_map(|timed| match timed.event {
+            InputEvent::KeyPressed(key) => Some(Binding::Key(key)),
+            InputEvent::MousePressed(button) => Some(Binding::Mouse(button)),
+            InputEvent::GamepadPressed(button) => Some(Binding::Gamepad(button)),
+            _ => None,
+        });
+        pressed.or_else(|| {
+            GamepadAxis::ALL
+                .into_iter()
+                .flat_map(|axis| [Binding::Axis(axis), Binding::AxisNegative(axis)])
+                .find(|binding| {
+                    binding.axis_value(input, false, 0.0) >= AXIS_PRESS
+                        && binding.axis_value(input, true, 0.0) < AXIS_PRESS
+                })
+        })
+    }
+
+    /// How far an axis binding is pushed in its direction, 0 to 1, now or
+    /// at the end of the previous frame. 0 for buttons.
+    fn axis_value(self, input: &InputState, previous: bool, deadzone: f32) -> f32 {
+        let (axis, sign) = match self {
+            Binding::Axis(axis) => (axis, 1.0),
+            Binding::AxisNegative(axis) => (axis, -1.0),
+            _ => return 0.0,
+        };
+        let raw = if previous {
+            input.gamepad_axis_previous(axis)
+        } else {
+            input.gamepad_axis(axis)
+        };
+        let value = (raw * sign).max(0.0);
+        if value <= deadzone {
+            0.0
+        } else {
+            ((value - deadzone) / (1.0 - deadzone)).min(1.0)
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key) => write!(f, "{key:?}"),
+            Binding::Mouse(button) => write!(f, "Mouse {button:?}"),
+            Binding::Gamepad(button) => write!(f, "Gamepad {button:?}"),
+            Binding::Axis(axis) => write!(f, "Gamepad {axis:?} +"),
+            Binding::AxisNegative(axis) => write!(f, "Gamepad {axis:?} -"),
+        }
+    }
+}
+
+/// The contents of a bindings file: context → action → bindings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActionBindings {
+    #[serde(default)]
+    pub contexts: BTreeMap<String, BTreeMap<String, Vec<Binding>>>,
+}
+
+impl ActionBindings {
+    /// Parse a bindings file's contents.
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+
+    /// Serialize to RON, e.g. to write out a default bindings file.
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .unwrap_or_default()
+    }
+
+    /// Write the bindings to a RON file that
+    /// [`load_actions`](crate::context::Context::load_actions) reads back.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_ron())
+    }
+
+    /// Bind `action` in `context` to one more input.
+    pub fn bind(mut self, context: &str, action: &str, binding: Binding) -> Self {
+        self.contexts
+            .entry(context.to_string())
+            .or_default()
+            .entry(action.to_string())
+            .or_default()
+            .push(binding);
+        self
+    }
+
+    /// Inputs bound to more than one action within the same context.
+    pub fn conflicts(&self) -> Vec<BindingConflict> {
+        let mut conflicts = Vec::new();
+        for (context, actions) in &self.contexts {
+            let mut users: BTreeMap<Binding, BTreeSet<&str>> = BTreeMap::new();
+            for (action, bindings) in actions {
+                for &binding in bindings {
+                    users.entry(binding).or_default().insert(action);
+                }
+            }
+            for (binding, actions) in users {
+                if actions.len() > 1 {
+                    conflicts.push(BindingConflict {
+                        context: context.clone(),
+                        binding,
+                        actions: actions.into_iter().map(str::to_string).collect(),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+/// An input bound to several actions in one context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingConflict {
+    pub context: String,
+    pub binding: Binding,
+    /// The actions sharing the binding, sorted.
+    pub actions: Vec<String>,
+}
+
+impl fmt::Display for BindingConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is bound to {} in context '{}'",
+            self.binding,
+            self.actions.join(", "),
+            self.context
+        )
+    }
+}
+
+/// Named input actions. Insert with [`Context::load_actions`](crate::context::Context::load_actions)
+/// (hot-reloaded) or directly with [`ActionMap::new`].
+#[derive(Debug, Clone)]
+pub struct ActionMap {
+    bindings: ActionBindings,
+    /// Contexts checked by queries. `None` means all of them.
+    active: Option<BTreeSet<String>>,
+    /// Stick values below this count as 0.
+    deadzone: f32,
+}
+
+impl Default for ActionMap {
+    fn default() -> Self {
+        Self::new(ActionBindings::default())
+    }
+}
+
+impl ActionMap {
+    pub fn new(bindings: ActionBindings) -> Self {
+        Self {
+            bindings,
+            active: None,
+            deadzone: DEFAULT_DEADZONE,
+        }
+    }
+
+    /// Set how far a stick must move from rest to register (0 to 1).
+    pub fn set_deadzone(&mut self, deadzone: f32) {
+        self.deadzone = deadzone.clamp(0.0, 0.99);
+    }
+
+    /// The current bindings.
+    pub fn bindings(&self) -> &ActionBindings {
+        &self.bindings
+    }
+
+    /// Replace the bindings, keeping the active contexts.
+    pub fn set_bindings(&mut self, bindings: ActionBindings) {
+        self.bindings = bindings;
+    }
+
+    /// Bind `action` in `context` to one more input at run time.
+    pub fn bind(&mut self, context: &str, action: &str, binding: Binding) {
+        let bindings = self.action_bindings(context, action);
+        if !bindings.contains(&binding) {
+            bindings.push(binding);
+        }
+    }
+
+    /// Remove one input from `action` in `context`. Returns whether it was
+    /// bound.
+    pub fn unbind(&mut self, context: &str, action: &str, binding: Binding) -> bool {
+        let Some(bindings) = self
+            .bindings
+            .contexts
+            .get_mut(context)
+            .and_then(|actions| actions.get_mut(action))
+        else {
+            return false;
+        };
+        let len = bindings.len();
+        bindings.retain(|&b| b != binding);
+        bindings.len() != len
+    }
+
+    /// Replace `old` with `new` in `action`'s bindings, keeping its place
+    /// (which decides the prompt shown for it). Adds `new` if `old` wasn't
+    /// bound.
+    pub fn rebind(&mut self, context: &str, action: &str, old: Binding, new: Binding) {
+        let bindings = self.action_bindings(context, action);
+        bindings.retain(|&b| b != new);
+        match bindings.iter().position(|&b| b == old) {
+            Some(index) => bindings[index] = new,
+            None => bindings.push(new),
+        }
+    }
+
+    /// Write the current bindings to a RON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        self.bindings.save(path)
+    }
+
+    fn action_bindings(&mut self, context: &str, action: &str) -> &mut Vec<Binding> {
+        self.bindings
+            .contexts
+            .entry(context.to_string())
+            .or_default()
+            .entry(action.to_string())
+            .or_default()
+    }
+
+    /// Only check actions in these contexts.
+    pub fn set_active_contexts<'a>(&mut self, contexts: impl IntoIterator<Item = &'a str>) {
+        self.active = Some(contexts.into_iter().map(str::to_string).collect());
+    }
+
+    /// Turn one context on or off, leaving the others as they are.
+    pub fn set_context_active(&mut self, context: &str, active: bool) {
+        let contexts = &self.bindings.contexts;
+        let set = self
+            .active
+            .get_or_insert_with(|| contexts.keys().cloned().collect());
+        if active {
+            set.insert(context.to_string());
+        } else {
+            set.remove(context);
+        }
+    }
+
+    /// Check actions in every context (the default).
+    pub fn activate_all(&mut self) {
+        self.active = None;
+    }
+
+    /// Whether actions in `context` are checked.
+    pub fn is_active(&self, context: &str) -> bool {
+        self.active.as_ref().is_none_or(|active| active.contains(context))
+    }
+
+    /// Every binding of `action` across the active contexts.
+    pub fn bindings_for<'a>(&'a self, action: &'a str) -> impl Iterator<Item = Binding> + 'a {
+        self.bindings
+            .contexts
+            .iter()
+            .filter(|(context, _)| self.is_active(context))
+            .filter_map(move |(_, actions)| actions.get(action))
+            .flatten()
+            .copied()
+    }
+
+    /// Returns `true` if any binding of `action` is held down.
+    pub fn pressed(&self, action: &str, input: &InputState) -> bool {
+        self.bindings_for(action).any(|b| held(b, input))
+    }
+
+    /// Returns `true` if any binding of `action` was pressed this frame.
+    pub fn just_pressed(&self, action: &str, input: &InputState) -> bool {
+        self.bindings_for(action).any(|b| match b {
+            Binding::Key(key) => input.just_pressed(key),
+            Binding::Mouse(button) => input.mouse_just_pressed(button),
+            Binding::Gamepad(button) => input.gamepad_just_pressed(button),
+            Binding::Axis(_) | Binding::AxisNegative(_) => {
+                b.axis_value(input, false, 0.0) >= AXIS_PRESS
+                    && b.axis_value(input, true, 0.0) < AXIS_PRESS
+            }
+        })
+    }
+
+    /// Returns `true` if any binding of `action` was released this frame.
+    pub fn just_released(&self, action: &str, input: &InputState) -> bool {
+        self.bindings_for(action).any(|b| match b {
+            Binding::Key(key) => input.just_released(key),
+            Binding::Mouse(button) => input.mouse_just_released(button),
+            Binding::Gamepad(button) => input.gamepad_just_released(button),
+            Binding::Axis(_) | Binding::AxisNegative(_) => {
+                b.axis_value(input, false, 0.0) < AXIS_PRESS
+                    && b.axis_value(input, true, 0.0) >= AXIS_PRESS
+            }
+        })
+    }
+
+    /// How strongly `action` is held, 0 to 1: 1 for a held key or button,
+    /// a stick's push past the deadzone. The largest of its bindings.
+    pub fn value(&self, action: &str, input: &InputState) -> f32 {
+        self.bindings_for(action)
+            .map(|b| match b {
+                Binding::Axis(_) | Binding::AxisNegative(_) => {
+                    b.axis_value(input, false, self.deadzone)
+                }
+                _ if held(b, input) => 1.0,
+                _ => 0.0,
+            })
+            .fold(0.0, f32::max)
+    }
+
+    /// `positive`'s value minus `negative`'s, -1 to 1 — e.g.
+    /// `axis("left", "right", input)` for horizontal movement.
+    pub fn axis(&self, negative: &str, positive: &str, input: &InputState) -> f32 {
+        (self.value(positive, input) - self.value(negative, input)).clamp(-1.0, 1.0)
+    }
+}
+
+fn held(binding: Binding, input: &InputState) -> bool {
+    match binding {
+        Binding::Key(key) => input.pressed(key),
+        Binding::Mouse(button) => input.mouse_pressed(button),
+        Binding::Gamepad(button) => input.gamepad_pressed(button),
+        Binding::Axis(_) | Binding::AxisNegative(_) => {
+            binding.axis_value(input, false, 0.0) >= AXIS_PRESS
+        }
+    }
+}
+
+/// Load a bindings file into the [`ActionMap`] resource and watch it.
+pub(crate) fn load_actions(world: &mut World, path: &str) {
+    let path = crate::launch::resolve_asset_path(world, path).into_owned();
+    let bindings = match read_bindings(Path::new(&path)) {
+        Ok(bindings) => bindings,
+        Err(e) => {
+            log::warn!("Failed to load input bindings '{path}': {e}");
+            ActionBindings::default()
+        }
+    };
+    warn_conflicts(&bindings);
+
+    if let Some(map) = world.get_resource_mut::<ActionMap>() {
+        map.set_bindings(bindings);
+    } else {
+        world.insert_resource(ActionMap::new(bindings));
+    }
+
+    if let Some(server) = world.get_resource_mut::<AssetServer>() {
+        server.watch_custom(path, reload_actions);
+    }
+}
+
+/// Hot-reload callback for watched bindings files.
+fn reload_actions(world: &mut World, path: &Path) {
+    let Some(map) = world.get_resource_mut::<ActionMap>() else {
+        return;
+    };
+    match read_bindings(path) {
+        Ok(bindings) => {
+            warn_conflicts(&bindings);
+            map.set_bindings(bindings);
+            log::info!("Reloaded input bindings '{}'", path.display());
+        }
+        Err(e) => log::warn!(
+            "Keeping previous input bindings, reload of '{}' failed: {e}",
+            path.display()
+        ),
+    }
+}
+
+fn read_bindings(path: &Path) -> Result<ActionBindings, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    ActionBindings::from_ron(&text).map_err(|e| e.to_string())
+}
+
+fn warn_conflicts(bindings: &ActionBindings) {
+    for conflict in bindings.conflicts() {
+        log::warn!("Input binding conflict: {conflict}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = r#"(
+        contexts: {
+            "gameplay": {
+                "jump": [Key(Space), Key(KeyW)],
+                "fire": [Mouse(Left), Key(KeyW)],
+            },
+            "menu": {
+                "confirm": [Key(Space)],
+            },
+        },
+    )"#;
+
+    #[test]
+    fn parses_and_round_trips() {
+        let bindings = ActionBindings::from_ron(FILE).unwrap();
+        assert_eq!(
+            bindings.contexts["gameplay"]["fire"],
+            [Binding::Mouse(MouseButton::Left), Binding::Key(KeyCode::KeyW)]
+        );
+        assert_eq!(ActionBindings::from_ron(&bindings.to_ron()).unwrap(), bindings);
+
+        let built = ActionBindings::default().bind("menu", "confirm", Binding::Key(KeyCode::Space));
+        assert_eq!(built.contexts["menu"], bindings.contexts["menu"]);
+        assert!(ActionBindings::from_ron("(contexts: {\"a\": {\"b\": [Key(Nope)]}})").is_err());
+    }
+
+    #[test]
+    fn conflicts_are_per_context() {
+        let conflicts = ActionBindings::from_ron(FILE).unwrap().conflicts();
+        assert_eq!(
+            conflicts,
+            [BindingConflict {
+                context: "gameplay".into(),
+                binding: Binding::Key(KeyCode::KeyW),
+                actions: vec!["fire".into(), "jump".into()],
+            }]
+        );
+    }
+
+    #[test]
+    fn queries_check_active_contexts_only() {
+        let mut map = ActionMap::new(ActionBindings::from_ron(FILE).unwrap());
+        let mut input = InputState::new();
+        input.keys.press(KeyCode::Space);
+
+        assert!(map.pressed("jump", &input) && map.just_pressed("confirm", &input));
+        assert!(!map.pressed("fire", &input));
+
+        map.set_active_contexts(["gameplay"]);
+        assert!(!map.pressed("confirm", &input));
+        assert!(map.pressed("jump", &input));
+        map.activate_all();
+        map.set_context_active("gameplay", false);
+        assert!(map.is_active("menu") && !map.pressed("jump", &input));
+        map.set_context_active("gameplay", true);
+
+        input.keys.clear_just();
+        input.keys.release(KeyCode::Space);
+        assert!(map.just_released("jump", &input) && !map.pressed("jump", &input));
+    }
+
+    #[test]
+    fn axes_give_values_and_rebinding_captures_input() {
+        let bindings = ActionBindings::from_ron(
+            "(contexts: {\"gameplay\": {\"left\": [Key(KeyA), AxisNegative(LeftStickX)], \"right\": [Axis(LeftStickX)]}})",
+        )
+        .unwrap();
+        let mut map = ActionMap::new(bindings);
+        let mut input = InputState::new();
+        let style = crate::input::GamepadStyle::Xbox;
+
+        input.set_gamepad_axis(style, GamepadAxis::LeftStickX, 0.1);
+        assert_eq!(map.axis("left", "right", &input), 0.0);
+        input.set_gamepad_axis(style, GamepadAxis::LeftStickX, -0.575);
+        assert!((map.axis("left", "right", &input) + 0.5).abs() < 1e-5);
+        assert!(map.just_pressed("left", &input) && !map.pressed("right", &input));
+        input.keys.press(KeyCode::KeyA);
+        assert_eq!(map.value("left", &input), 1.0);
+
+        input.clear_just();
+        assert!(map.pressed("left", &input) && !map.just_pressed("left", &input));
+        input.set_gamepad_axis(style, GamepadAxis::LeftStickX, 0.0);
+        assert!(map.just_released("left", &input));
+
+        input.clear_just();
+        assert_eq!(Binding::first_just_pressed(&input), None);
+        input.set_gamepad_button(style, GamepadButton::West, true);
+        let captured = Binding::first_just_pressed(&input).unwrap();
+        map.rebind("gameplay", "left", Binding::Key(KeyCode::KeyA), captured);
+        assert_eq!(
+            map.bindings().contexts["gameplay"]["left"],
+            [Binding::Gamepad(GamepadButton::West), Binding::AxisNegative(GamepadAxis::LeftStickX)]
+        );
+        assert!(map.unbind("gameplay", "left", captured));
+        assert!(!map.unbind("gameplay", "left", captured));
+        map.bind("menu", "back", Binding::Key(KeyCode::Escape));
+        let reloaded = ActionBindings::from_ron(&map.bindings().to_ron()).unwrap();
+        assert_eq!(&reloaded, map.bindings());
+    }
+}
@@ -19,6 +19,20 @@
 //!  └────┴────┴────┴────┘
 //! ```
 //!
+//! ## Sprite Atlases
+//!
+//! Not every sheet is a uniform grid. A [`SpriteAtlas`] is a JSON asset listing
+//! named pixel regions of a texture plus named clips (sequences of region
+//! names). Atlases are usually authored with the editor's sprite slicer panel
+//! and loaded at runtime with [`SpriteAtlas::from_file`]; [`SpriteAtlas::player`]
+//! turns a clip into an [`AnimationPlayer`].
+//!
+//! ```text
+//! { "texture": "assets/hero.png", "texture_size": [128, 64],
+//!   "regions": [{ "name": "idle_0", "x": 0, "y": 0, "w": 32, "h": 32 }, ...],
+//!   "clips":   [{ "name": "idle", "frames": ["idle_0", "idle_1"], "frame_time": 0.1, "looping": true }] }
+//! ```
+//!
 //! ## Property Tweening
 //!
 //! [`Tween`] interpolates a single property (position, scale, rotation, or
@@ -26,6 +40,10 @@
 //! [`EaseFunction`] curve. Supports looping and ping-pong modes. The
 //! [`advance_tweens`] system applies the interpolated values each frame.
 
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
 use crate::ecs::World;
 use crate::math::{Rect, Transform, Vec2};
 use crate::render2d::{Color, Sprite};
@@ -39,6 +57,9 @@ use crate::render2d::{Color, Sprite};
 /// All frames must be the same size. Frame indices are row-major (left-to-right,
 /// top-to-bottom). Supports optional padding between frames and an offset from
 /// the top-left corner of the texture.
+///
+/// Sheets built from a [`SpriteAtlas`] carry explicit per-frame `frames`
+/// instead, which take precedence over the grid.
 #[derive(Debug, Clone)]
 pub struct SpriteSheet {
     pub columns: u32,
@@ -51,6 +72,8 @@ pub struct SpriteSheet {
     pub offset: Vec2,
     /// Total texture dimensions in pixels.
     pub texture_size: Vec2,
+    /// Explicit UV rect per frame. When non-empty, replaces the grid layout.
+    pub frames: Vec<Rect>,
 }
 
 impl SpriteSheet {
@@ -68,6 +91,7 @@ impl SpriteSheet {
             padding: Vec2::ZERO,
             offset: Vec2::ZERO,
             texture_size,
+            frames: Vec::new(),
         }
     }
 
@@ -96,11 +120,15 @@ impl SpriteSheet {
             padding,
             offset,
             texture_size,
+            frames: Vec::new(),
         }
     }
 
     /// Returns the UV [`Rect`] for a given frame index (row-major, 0-based).
     pub fn frame_rect(&self, index: u32) -> Rect {
+        if !self.frames.is_empty() {
+            return self.frames.get(index as usize).copied().unwrap_or_default();
+        }
         let col = index % self.columns;
         let row = index / self.columns;
         let x = self.offset.x + col as f32 * (self.tile_size.x + self.padding.x);
@@ -117,7 +145,11 @@ impl SpriteSheet {
 
     /// Total number of frames in the sheet.
     pub fn frame_count(&self) -> u32 {
-        self.columns * self.rows
+        if self.frames.is_empty() {
+            self.columns * self.rows
+        } else {
+            self.frames.len() as u32
+        }
     }
 
     /// Play all frames at the given speed. Consumes the sheet.
@@ -256,6 +288,160 @@ pub fn animate_sprites(world: &mut World, dt: f32) {
     });
 }
 
+// ---------------------------------------------------------------------------
+// Sprite Atlases
+// ---------------------------------------------------------------------------
+
+/// A named rectangle of an atlas texture, in pixels (top-left origin).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtlasRegion {
+    pub name: String,
+    pub x: f32,
+    pub y: f32,
+    pub w: f32,
+    pub h: f32,
+}
+
+/// A named animation: an ordered list of region names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AtlasClip {
+    pub name: String,
+    /// Region names, played in order.
+    pub frames: Vec<String>,
+    /// Seconds per frame.
+    pub frame_time: f32,
+    #[serde(default)]
+    pub looping: bool,
+}
+
+/// A texture sliced into named regions and clips, stored as JSON.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpriteAtlas {
+    /// Path of the atlas texture, as passed to `load_texture`.
+    pub texture: String,
+    /// Texture dimensions in pixels.
+    pub texture_size: Vec2,
+    pub regions: Vec<AtlasRegion>,
+    #[serde(default)]
+    pub clips: Vec<AtlasClip>,
+}
+
+impl SpriteAtlas {
+    /// Create an empty atlas for a texture.
+    pub fn new(texture: &str, texture_size: Vec2) -> Self {
+        Self {
+            texture: texture.to_string(),
+            texture_size,
+            regions: Vec::new(),
+            clips: Vec::new(),
+        }
+    }
+
+    /// Load an atlas from a JSON file. Logs a warning and returns `None` on
+    /// failure.
+    pub fn from_file(path: impl AsRef<Path>) -> Option<Self> {
+        let path = path.as_ref();
+        let json = match std::fs::read_to_string(path) {
+            Ok(json) => json,
+            Err(e) => {
+                log::warn!("Failed to read atlas '{}': {e}", path.display());
+                return None;
+            }
+        };
+        match serde_json::from_str(&json) {
+            Ok(atlas) => Some(atlas),
+            Err(e) => {
+                log::warn!("Failed to parse atlas '{}': {e}", path.display());
+                None
+            }
+        }
+    }
+
+    /// Write the atlas to a JSON file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json)
+    }
+
+    /// Append regions for a uniform grid of `tile_size` cells, named
+    /// `{prefix}_{index}` in row-major order. Returns the number added.
+    pub fn slice_grid(&mut self, prefix: &str, tile_size: Vec2, padding: Vec2, offset: Vec2) -> usize {
+        if tile_size.x <= 0.0 || tile_size.y <= 0.0 {
+            return 0;
+        }
+        let step = tile_size + padding;
+        let columns = ((self.texture_size.x - offset.x + padding.x) / step.x).floor().max(0.0) as u32;
+        let rows = ((self.texture_size.y - offset.y + padding.y) / step.y).floor().max(0.0) as u32;
+        for row in 0..rows {
+            for col in 0..columns {
+                self.regions.push(AtlasRegion {
+                    name: format!("{prefix}_{}", row * columns + col),
+                    x: offset.x + col as f32 * step.x,
+                    y: offset.y + row as f32 * step.y,
+                    w: tile_size.x,
+                    h: tile_size.y,
+                });
+            }
+        }
+        (columns * rows) as usize
+    }
+
+    /// Look up a region by name.
+    pub fn region(&self, name: &str) -> Option<&AtlasRegion> {
+        self.regions.iter().find(|r| r.name == name)
+    }
+
+    /// UV rect of a region, ready for `Sprite.texture_rect`.
+    pub fn region_rect(&self, name: &str) -> Option<Rect> {
+        let r = self.region(name)?;
+        Some(self.uv_rect(r))
+    }
+
+    fn uv_rect(&self, r: &AtlasRegion) -> Rect {
+        Rect::from_pixels(r.x, r.y, r.w, r.h, self.texture_size.x, self.texture_size.y)
+    }
+
+    /// A [`SpriteSheet`] whose frame indices are this atlas's region indices.
+    pub fn sheet(&self) -> SpriteSheet {
+        SpriteSheet {
+            columns: self.regions.len() as u32,
+            rows: 1,
+            tile_size: Vec2::ZERO,
+            padding: Vec2::ZERO,
+            offset: Vec2::ZERO,
+            texture_size: self.texture_size,
+            frames: self.regions.iter().map(|r| self.uv_rect(r)).collect(),
+        }
+    }
+
+    /// Resolve a named clip to frame indices into [`sheet`](Self::sheet).
+    /// Unknown region names are skipped with a warning.
+    pub fn clip(&self, name: &str) -> Option<AnimationClip> {
+        let clip = self.clips.iter().find(|c| c.name == name)?;
+        let frames = clip
+            .frames
+            .iter()
+            .filter_map(|frame| {
+                let index = self.regions.iter().position(|r| &r.name == frame);
+                if index.is_none() {
+                    log::warn!("Atlas clip '{name}' references unknown region '{frame}'");
+                }
+                index.map(|i| i as u32)
+            })
+            .collect();
+        Some(AnimationClip {
+            frames,
+            frame_time: clip.frame_time,
+            looping: clip.looping,
+        })
+    }
+
+    /// Build an [`AnimationPlayer`] for a named clip.
+    pub fn player(&self, clip: &str) -> Option<AnimationPlayer> {
+        Some(AnimationPlayer::new(self.sheet(), self.clip(clip)?))
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Property Tweening
 // ---------------------------------------------------------------------------
@@ -465,3 +651,53 @@ pub fn advance_tweens(world: &mut World, dt: f32) {
         apply_color_tween(&tween.target, t, &mut sprite.color);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atlas() -> SpriteAtlas {
+        let mut atlas = SpriteAtlas::new("hero.png", Vec2::new(64.0, 32.0));
+        atlas.slice_grid("hero", Vec2::new(16.0, 16.0), Vec2::ZERO, Vec2::ZERO);
+        atlas.clips.push(AtlasClip {
+            name: "run".to_string(),
+            frames: vec!["hero_5".to_string(), "hero_6".to_string()],
+            frame_time: 0.1,
+            looping: true,
+        });
+        atlas
+    }
+
+    #[test]
+    fn slice_grid_is_row_major() {
+        let atlas = atlas();
+        assert_eq!(atlas.regions.len(), 8);
+        let r = atlas.region("hero_5").unwrap();
+        assert_eq!((r.x, r.y), (16.0, 16.0));
+    }
+
+    #[test]
+    fn slice_grid_respects_padding_and_offset() {
+        let mut atlas = SpriteAtlas::new("t.png", Vec2::new(36.0, 18.0));
+        let added = atlas.slice_grid("t", Vec2::new(16.0, 16.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 2.0));
+        assert_eq!(added, 2);
+        assert_eq!(atlas.regions[1].x, 20.0);
+    }
+
+    #[test]
+    fn clip_resolves_to_sheet_frames() {
+        let atlas = atlas();
+        let player = atlas.player("run").unwrap();
+        assert_eq!(player.clip.frames, vec![5, 6]);
+        assert!(player.clip.looping);
+        assert_eq!(player.current_rect(), atlas.region_rect("hero_5").unwrap());
+    }
+
+    #[test]
+    fn atlas_json_roundtrip() {
+        let atlas = atlas();
+        let json = serde_json::to_string(&atlas).unwrap();
+        let back: SpriteAtlas = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, atlas);
+    }
+}
@@ -0,0 +1,548 @@
+//! # Handles — Reference-Counted Asset Storage
+//!
+//! The engine's own handles (`TextureHandle`, `MeshHandle`, `FontHandle`)
+//! are `Copy` indices: cheap to put in components, but nothing knows when
+//! the last one is gone, so [`asset_gc`](crate::asset_gc) has to scan the
+//! world to find unused assets. [`Assets<T>`] is the counted alternative
+//! for any asset type: each [`Handle<T>`] keeps its asset alive, and once
+//! the last one is dropped the asset is unloaded at the start of the next
+//! frame.
+//!
+//! ```text
+//!   Assets<Level>                      handles
+//!   ┌─────┬──────────────┬───────┐
+//!   │ id  │ value        │ alive │     strong ──► keeps the slot
+//!   ├─────┼──────────────┼───────┤     weak   ──► can look it up, or
+//!   │ 0v0 │ Level "hub"  │  2    │◄──  upgrade while it still exists
+//!   │ 1v0 │ Level "cave" │  0 ✗  │     (nothing left: removed next frame,
+//!   │ 2v3 │ Level "boss" │  1    │      Removed event sent, slot reused)
+//!   └─────┴──────────────┴───────┘
+//! ```
+//!
+//! An [`AssetId`] is an index plus a generation. A removed slot is reused
+//! with the generation bumped, so an id that outlived its asset finds
+//! nothing instead of whatever moved in after it.
+//!
+//! ## Events
+//!
+//! Register a type with [`Game::asset`](crate::game::Game::asset) and every
+//! change is reported through [`Events<AssetEvent<T>>`](crate::ecs::Events):
+//!
+//! | Event | Sent when |
+//! |-------|-----------|
+//! | `Created` | [`Assets::add`] |
+//! | `Modified` | [`Assets::get_mut`] or [`Assets::set`] |
+//! | `Removed` | [`Assets::remove`], or the last strong handle dropped |
+//!
+//! Events are queued in the store and handed to the event queue at the
+//! start of each frame, after unused assets are swept.
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .asset::<Dialogue>()
+//!     .startup(|ctx| {
+//!         let intro = ctx.world.resource_mut::<Assets<Dialogue>>().add(load_dialogue("intro.json"));
+//!         ctx.world.insert_resource(CurrentDialogue(intro));
+//!     })
+//!     .update({
+//!         let mut reader = EventReader::<AssetEvent<Dialogue>>::default();
+//!         move |ctx| {
+//!             for event in reader.read(ctx.world.resource::<Events<AssetEvent<Dialogue>>>()) {
+//!                 log::info!("{event:?}");
+//!             }
+//!         }
+//!     })
+//!     .run();
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Assets are `UnityEngine.Object`s; unused ones are found by
+//!   a scan (`Resources.UnloadUnusedAssets`), not counted.
+//! - **Bevy**: `Assets<T>` with strong/weak `Handle<T>` and
+//!   `AssetEvent::{Added, Modified, Removed, Unused}` — the model here.
+//! - **Godot**: Every `Resource` is reference counted and freed with its
+//!   last reference.
+//! - **Our approach**: Bevy's model for game-defined asset types. The
+//!   engine's GPU stores keep their `Copy` handles, which scenes serialize
+//!   and components copy freely, and stay under `asset_gc`; a counted
+//!   [`Handle<AssetRef>`](crate::asset_gc::AssetRef) from
+//!   [`AssetGc::retain`](crate::asset_gc::AssetGc::retain) keeps one of
+//!   them through sweeps until its last clone is dropped.
+
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::sync::{Arc, Weak};
+
+use crate::ecs::{Events, World};
+
+// ── Ids and handles ─────────────────────────────────────────────────────
+
+/// Identifies one asset in an [`Assets<T>`]. Doesn't keep it alive.
+pub struct AssetId<T> {
+    index: u32,
+    generation: u32,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> AssetId<T> {
+    fn new(index: u32, generation: u32) -> Self {
+        Self {
+            index,
+            generation,
+            _marker: PhantomData,
+        }
+    }
+}
+
+// Manual impls: derives would require `T` to implement the traits too.
+impl<T> Clone for AssetId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AssetId<T> {}
+
+impl<T> PartialEq for AssetId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.index, self.generation) == (other.index, other.generation)
+    }
+}
+
+impl<T> Eq for AssetId<T> {}
+
+impl<T> Hash for AssetId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.index, self.generation).hash(state);
+    }
+}
+
+impl<T> fmt::Debug for AssetId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AssetId({}v{})", self.index, self.generation)
+    }
+}
+
+/// Strong handle: the asset stays loaded while any clone of this exists.
+pub struct Handle<T> {
+    id: AssetId<T>,
+    alive: Arc<()>,
+}
+
+impl<T> Handle<T> {
+    /// The asset's id.
+    pub fn id(&self) -> AssetId<T> {
+        self.id
+    }
+
+    /// A weak handle to the same asset.
+    pub fn downgrade(&self) -> WeakHandle<T> {
+        WeakHandle {
+            id: self.id,
+            alive: Arc::downgrade(&self.alive),
+        }
+    }
+
+    /// Number of strong handles to this asset, this one included.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(&self.alive)
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            alive: Arc::clone(&self.alive),
+        }
+    }
+}
+
+impl<T> PartialEq for Handle<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for Handle<T> {}
+
+impl<T> Hash for Handle<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Handle({}v{})", self.id.index, self.id.generation)
+    }
+}
+
+impl<T> From<&Handle<T>> for AssetId<T> {
+    fn from(handle: &Handle<T>) -> Self {
+        handle.id
+    }
+}
+
+/// Weak handle: names an asset without keeping it loaded.
+pub struct WeakHandle<T> {
+    id: AssetId<T>,
+    alive: Weak<()>,
+}
+
+impl<T> WeakHandle<T> {
+    /// The asset's id.
+    pub fn id(&self) -> AssetId<T> {
+        self.id
+    }
+
+    /// A strong handle, if some other strong handle still exists.
+    pub fn upgrade(&self) -> Option<Handle<T>> {
+        self.alive.upgrade().map(|alive| Handle { id: self.id, alive })
+    }
+}
+
+impl<T> Clone for WeakHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            alive: Weak::clone(&self.alive),
+        }
+    }
+}
+
+impl<T> fmt::Debug for WeakHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WeakHandle({}v{})", self.id.index, self.id.generation)
+    }
+}
+
+impl<T> From<&WeakHandle<T>> for AssetId<T> {
+    fn from(handle: &WeakHandle<T>) -> Self {
+        handle.id
+    }
+}
+
+// ── Events ──────────────────────────────────────────────────────────────
+
+/// A change to an [`Assets<T>`]. See the [module docs](self).
+pub enum AssetEvent<T> {
+    Created(AssetId<T>),
+    Modified(AssetId<T>),
+    Removed(AssetId<T>),
+}
+
+impl<T> AssetEvent<T> {
+    /// The asset the event is about.
+    pub fn id(&self) -> AssetId<T> {
+        match self {
+            Self::Created(id) | Self::Modified(id) | Self::Removed(id) => *id,
+        }
+    }
+}
+
+impl<T> Clone for AssetEvent<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for AssetEvent<T> {}
+
+impl<T> PartialEq for AssetEvent<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Created(a), Self::Created(b))
+            | (Self::Modified(a), Self::Modified(b))
+            | (Self::Removed(a), Self::Removed(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<T> Eq for AssetEvent<T> {}
+
+impl<T> fmt::Debug for AssetEvent<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Created(id) => write!(f, "Created({id:?})"),
+            Self::Modified(id) => write!(f, "Modified({id:?})"),
+            Self::Removed(id) => write!(f, "Removed({id:?})"),
+        }
+    }
+}
+
+// ── Storage ─────────────────────────────────────────────────────────────
+
+struct Slot<T> {
+    /// Bumped each time the slot is emptied.
+    generation: u32,
+    entry: Option<Entry<T>>,
+}
+
+struct Entry<T> {
+    value: T,
+    /// Dead once every strong handle is dropped.
+    alive: Weak<()>,
+}
+
+/// Resource: every loaded asset of type `T`. See the [module docs](self).
+pub struct Assets<T> {
+    slots: Vec<Slot<T>>,
+    /// Empty slot indices, reused before the vector grows.
+    free: Vec<u32>,
+    len: usize,
+    /// Events not yet handed to the `Events<AssetEvent<T>>` queue.
+    pending: Vec<AssetEvent<T>>,
+}
+
+impl<T> Default for Assets<T> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Assets<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<T> Assets<T> {
+    /// An empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `value` and return the first strong handle to it.
+    pub fn add(&mut self, value: T) -> Handle<T> {
+        let alive = Arc::new(());
+        let entry = Entry {
+            value,
+            alive: Arc::downgrade(&alive),
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index as usize].entry = Some(entry);
+                index
+            }
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    entry: Some(entry),
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let id = AssetId::new(index, self.slots[index as usize].generation);
+        self.len += 1;
+        self.pending.push(AssetEvent::Created(id));
+        Handle { id, alive }
+    }
+
+    /// The asset, if it is still loaded.
+    pub fn get(&self, id: impl Into<AssetId<T>>) -> Option<&T> {
+        self.entry(id.into()).map(|entry| &entry.value)
+    }
+
+    /// The asset for editing, if it is still loaded. Sends `Modified`.
+    pub fn get_mut(&mut self, id: impl Into<AssetId<T>>) -> Option<&mut T> {
+        let id = id.into();
+        self.entry(id)?;
+        self.pending.push(AssetEvent::Modified(id));
+        self.slots[id.index as usize]
+            .entry
+            .as_mut()
+            .map(|entry| &mut entry.value)
+    }
+
+    /// Replace a loaded asset's value, e.g. after a hot-reload. Sends
+    /// `Modified` and returns `true`, or returns `false` if it is gone.
+    pub fn set(&mut self, id: impl Into<AssetId<T>>, value: T) -> bool {
+        match self.get_mut(id) {
+            Some(slot) => {
+                *slot = value;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Whether the asset is still loaded.
+    pub fn contains(&self, id: impl Into<AssetId<T>>) -> bool {
+        self.entry(id.into()).is_some()
+    }
+
+    /// A new strong handle to a loaded asset, if one still exists (an asset
+    /// whose handles are all dropped is only waiting for the sweep).
+    pub fn handle(&self, id: AssetId<T>) -> Option<Handle<T>> {
+        let alive = self.entry(id)?.alive.upgrade()?;
+        Some(Handle { id, alive })
+    }
+
+    /// Unload an asset now, whatever handles remain; they will find nothing.
+    /// Sends `Removed`.
+    pub fn remove(&mut self, id: impl Into<AssetId<T>>) -> Option<T> {
+        let id = id.into();
+        self.entry(id)?;
+        let slot = &mut self.slots[id.index as usize];
+        let entry = slot.entry.take()?;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.index);
+        self.len -= 1;
+        self.pending.push(AssetEvent::Removed(id));
+        Some(entry.value)
+    }
+
+    /// Unload every asset with no strong handle left. Returns how many
+    /// were removed. Run automatically each frame for types registered with
+    /// [`Game::asset`](crate::game::Game::asset).
+    pub fn remove_unused(&mut self) -> usize {
+        let unused: Vec<AssetId<T>> = self
+            .iter_entries()
+            .filter(|(_, entry)| entry.alive.strong_count() == 0)
+            .map(|(id, _)| id)
+            .collect();
+        for &id in &unused {
+            self.remove(id);
+        }
+        unused.len()
+    }
+
+    /// Number of loaded assets.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether nothing is loaded.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Every loaded asset with its id.
+    pub fn iter(&self) -> impl Iterator<Item = (AssetId<T>, &T)> {
+        self.iter_entries().map(|(id, entry)| (id, &entry.value))
+    }
+
+    /// Take the events queued since the last call.
+    pub(crate) fn drain_events(&mut self) -> Vec<AssetEvent<T>> {
+        std::mem::take(&mut self.pending)
+    }
+
+    fn iter_entries(&self) -> impl Iterator<Item = (AssetId<T>, &Entry<T>)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            let entry = slot.entry.as_ref()?;
+            Some((AssetId::new(index as u32, slot.generation), entry))
+        })
+    }
+
+    fn entry(&self, id: AssetId<T>) -> Option<&Entry<T>> {
+        let slot = self.slots.get(id.index as usize)?;
+        if slot.generation != id.generation {
+            return None;
+        }
+        slot.entry.as_ref()
+    }
+}
+
+/// Sweep unused `T` assets and move their queued events into
+/// `Events<AssetEvent<T>>`. Registered as a frame-start hook by
+/// [`Game::asset`](crate::game::Game::asset).
+pub(crate) fn update_assets<T: 'static + Send + Sync>(world: &mut World) {
+    let Some(assets) = world.get_resource_mut::<Assets<T>>() else {
+        return;
+    };
+    assets.remove_unused();
+    let pending = assets.drain_events();
+    if let Some(events) = world.get_resource_mut::<Events<AssetEvent<T>>>() {
+        events.send_batch(pending);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dropping_the_last_handle_unloads_on_sweep() {
+        let mut assets = Assets::new();
+        let handle = assets.add("level");
+        let copy = handle.clone();
+        let id = handle.id();
+        assert_eq!(handle.strong_count(), 2);
+
+        drop(handle);
+        assert_eq!(assets.remove_unused(), 0);
+        assert_eq!(assets.get(&copy), Some(&"level"));
+
+        drop(copy);
+        assert_eq!(assets.remove_unused(), 1);
+        assert!(!assets.contains(id));
+        assert!(assets.is_empty());
+        assert_eq!(
+            assets.pending,
+            vec![AssetEvent::Created(id), AssetEvent::Removed(id)]
+        );
+    }
+
+    #[test]
+    fn weak_handles_do_not_keep_assets_alive() {
+        let mut assets = Assets::new();
+        let strong = assets.add(1);
+        let weak = strong.downgrade();
+        assert_eq!(weak.upgrade(), Some(strong.clone()));
+
+        drop(strong);
+        assert!(weak.upgrade().is_none());
+        assert!(assets.handle(weak.id()).is_none());
+        assets.remove_unused();
+        assert!(assets.get(&weak).is_none());
+    }
+
+    #[test]
+    fn reused_slot_does_not_answer_stale_ids() {
+        let mut assets = Assets::new();
+        let first = assets.add("old");
+        let stale = first.id();
+        assets.remove(&first);
+
+        let second = assets.add("new");
+        assert_eq!(second.id().index, stale.index);
+        assert_eq!(assets.get(stale), None);
+        assert_eq!(assets.get(&second), Some(&"new"));
+        assert_eq!(assets.len(), 1);
+    }
+
+    #[test]
+    fn edits_send_modified_events() {
+        let mut world = World::new();
+        world.insert_resource(Assets::<u32>::new());
+        world.insert_resource(Events::<AssetEvent<u32>>::default());
+
+        let handle = world.resource_mut::<Assets<u32>>().add(1);
+        *world.resource_mut::<Assets<u32>>().get_mut(&handle).unwrap() += 1;
+        assert!(world.resource_mut::<Assets<u32>>().set(&handle, 5));
+        update_assets::<u32>(&mut world);
+
+        let events = world.resource::<Events<AssetEvent<u32>>>();
+        let mut reader = crate::ecs::EventReader::default();
+        let seen: Vec<AssetEvent<u32>> = reader.read(events).copied().collect();
+        let id = handle.id();
+        assert_eq!(
+            seen,
+            vec![
+                AssetEvent::Created(id),
+                AssetEvent::Modified(id),
+                AssetEvent::Modified(id)
+            ]
+        );
+        assert_eq!(world.resource::<Assets<u32>>().get(&handle), Some(&5));
+    }
+}
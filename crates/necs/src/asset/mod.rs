@@ -0,0 +1,1170 @@
+//! # Asset Hot-Reload and Async Loading
+//!
+//! The asset system watches files on disk for changes and reloads them at
+//! runtime without restarting the application. When a texture PNG is edited in
+//! an image editor, or a shader `.wgsl` file is saved in a text editor, the
+//! engine detects the change and swaps in the new data — all while the game
+//! continues running.
+//!
+//! ## How It Works
+//!
+//! ```text
+//! ┌────────────────────────────────────────────────────────┐
+//! │  AssetServer (resource in World)                       │
+//! │                                                        │
+//! │  watcher ──► background thread (notify crate)          │
+//! │              watches registered file paths              │
+//! │              sends events over mpsc channel             │
+//! │                                                        │
+//! │  rx ◄──────── receives filesystem events               │
+//! │                                                        │
+//! │  watched_paths ── maps path → AssetKind                │
+//! │  pending_reloads ── debounce buffer (path → timestamp) │
+//! └────────────────────────────────────────────────────────┘
+//!
+//! Per-frame: process_asset_reloads(world)
+//!   1. Poll: drain rx into pending_reloads
+//!   2. Debounce: only act on entries older than 100ms
+//!   3. Dispatch: reload by asset kind (texture, shader, custom, loader)
+//! ```
+//!
+//! ## Debounce
+//!
+//! Text editors and image editors often perform *atomic saves*: write to a
+//! temporary file, then rename over the original. This generates multiple
+//! filesystem events (create, modify, rename) in rapid succession. Without
+//! debouncing, each event would trigger a separate reload — wasteful and
+//! sometimes incorrect (the file may be half-written).
+//!
+//! The debounce buffer collects events per path and waits 100ms of quiet time
+//! before triggering a reload. Repeated events for the same path just reset
+//! the timer. This ensures one burst of saves → exactly one reload.
+//!
+//! ## Handle Stability
+//!
+//! Handles (`TextureHandle`, `TextureHandle3d`) are indices into a `Vec`.
+//! On reload, the *data* at that index is replaced — the handle value stays
+//! the same. Any component holding a handle automatically sees the new data
+//! next frame. No reference counting or invalidation needed. Unused assets
+//! are released by a separate sweep, [`asset_gc`](crate::asset_gc), which
+//! also honors counted handles from
+//! [`AssetGc::retain`](crate::asset_gc::AssetGc::retain) for assets kept
+//! outside components. Game-defined asset types can instead use the reference-counted
+//! [`Assets<T>`] and [`Handle<T>`] from [`handle`], which unload an asset
+//! when its last handle is dropped.
+//!
+//! ## Dependencies and Cascading Reloads
+//!
+//! Assets often depend on other assets: a material file references a
+//! texture, a scene references a prefab which references a texture. Register
+//! those edges with [`AssetServer::add_dependency`]. When a file changes, the
+//! server reloads it *and* everything that transitively depends on it, in
+//! dependency order (dependencies first), so a dependent never re-reads stale
+//! data.
+//!
+//! ```text
+//!   texture.png ──► prefab.json ──► level.json
+//!        │
+//!        └────────► material.json
+//!
+//!   edit texture.png → reload texture.png, material.json, prefab.json, level.json
+//! ```
+//!
+//! Assets that are not textures or shaders can be hooked up with
+//! [`AssetServer::watch_custom`], which takes a reload function, or with an
+//! [`AssetLoader`] that receives the file's new bytes (see [`loader`]).
+//!
+//! ## Async Loading
+//!
+//! `load_texture`, `load_font` and `load_gltf` read and decode on the main
+//! thread, which stalls the frame for large files. The async variants split
+//! a load in two:
+//!
+//! ```text
+//!   main thread                      loader threads (≤ 4)
+//!   ───────────                      ────────────────────
+//!   load_texture_async(path)
+//!     reserve handle (placeholder)
+//!     queue job ───────────────────► read + decode file (PNG, font, glTF)
+//!   ...frames keep running...                 │
+//!   process_async_loads ◄──────── decoded data ┘
+//!     upload to GPU into the reserved handle
+//!     LoadState::Loading → Loaded / Failed
+//! ```
+//!
+//! Only the decode runs in the background; GPU uploads and World access stay
+//! on the main thread. The handle is valid immediately and draws as a
+//! placeholder (white texture, blank font) until the data arrives, the same
+//! handle-stability trick hot-reload uses. Poll
+//! [`AssetServer::load_state`] or [`AssetServer::load_progress`] to drive a
+//! loading screen. Custom asset types can use [`AssetServer::load_async`]
+//! directly with their own decode and finish steps.
+//!
+//! ## Graceful Degradation
+//!
+//! If the filesystem watcher fails to initialize (e.g., inotify limit
+//! reached), the `AssetServer` still works — assets load normally, they just
+//! won't hot-reload. Errors are logged, not panicked.
+
+pub mod handle;
+pub mod loader;
+
+pub use handle::{AssetEvent, AssetId, Assets, Handle, WeakHandle};
+pub use loader::{AssetLoader, load_custom};
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::ecs::World;
+use crate::render::pipelined::finish_render;
+
+/// The debounce window. Events within this duration of each other are collapsed
+/// into a single reload.
+const DEBOUNCE_DURATION: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// A record of one asset reload attempt (diagnostics only).
+#[cfg(feature = "diagnostics")]
+#[derive(Clone)]
+pub(crate) struct ReloadEvent {
+    pub timestamp_secs: f32,
+    pub path: String,
+    pub kind: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+/// Where an async load is at. See [`AssetServer::load_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadState {
+    /// Queued or being decoded on a loader thread.
+    Loading,
+    /// Decoded and uploaded; the handle now shows the real asset.
+    Loaded,
+    /// Reading or decoding failed. The handle keeps its placeholder.
+    Failed,
+}
+
+/// Counts of async loads by state, for loading screens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoadProgress {
+    /// Loads still in flight.
+    pub loading: usize,
+    /// Loads that finished successfully.
+    pub loaded: usize,
+    /// Loads that failed.
+    pub failed: usize,
+}
+
+impl LoadProgress {
+    /// Number of loads tracked.
+    pub fn total(&self) -> usize {
+        self.loading + self.loaded + self.failed
+    }
+
+    /// Fraction of loads that have finished (successfully or not), in
+    /// `0.0..=1.0`. `1.0` when nothing was loaded.
+    pub fn fraction(&self) -> f32 {
+        match self.total() {
+            0 => 1.0,
+            total => (self.loaded + self.failed) as f32 / total as f32,
+        }
+    }
+
+    /// True once nothing is in flight.
+    pub fn is_done(&self) -> bool {
+        self.loading == 0
+    }
+}
+
+/// The main-thread half of an async load: uploads decoded data into the World.
+type FinishFn = Box<dyn FnOnce(&mut World) + Send>;
+
+/// A background job for the loader threads.
+type LoadJob = Box<dyn FnOnce() + Send>;
+
+/// A decode that has come back from a loader thread.
+struct CompletedLoad {
+    path: PathBuf,
+    result: Result<FinishFn, String>,
+}
+
+/// The loader thread pool, started on the first async load.
+struct Loader {
+    jobs: mpsc::Sender<LoadJob>,
+}
+
+impl Loader {
+    fn start() -> Self {
+        let (jobs, rx) = mpsc::channel::<LoadJob>();
+        let rx = Arc::new(Mutex::new(rx));
+        let threads = std::thread::available_parallelism().map_or(2, |n| n.get()).min(4);
+        for i in 0..threads {
+            let rx = Arc::clone(&rx);
+            let spawned = std::thread::Builder::new()
+                .name(format!("necs-loader-{i}"))
+                .spawn(move || {
+                    loop {
+                        // Hold the lock only while waiting, not while decoding.
+                        let job = match rx.lock() {
+                            Ok(rx) => rx.recv(),
+                            Err(_) => break,
+                        };
+                        match job {
+                            Ok(job) => job(),
+                            // The server was dropped.
+                            Err(_) => break,
+                        }
+                    }
+                });
+            if let Err(e) = spawned {
+                log::warn!("Failed to start asset loader thread: {e}");
+            }
+        }
+        Self { jobs }
+    }
+}
+
+/// What kind of asset a watched path corresponds to.
+#[derive(Debug, Clone)]
+pub(crate) enum AssetKind {
+    /// A 2D texture at a specific handle index.
+    #[cfg(feature = "render2d")]
+    Texture2d(crate::render2d::TextureHandle),
+    /// A 3D texture at a specific handle index.
+    #[cfg(feature = "render3d")]
+    Texture3d(crate::render3d::TextureHandle3d),
+    /// The 2D sprite shader.
+    #[cfg(feature = "render2d")]
+    Shader2d,
+    /// The 3D PBR shader.
+    #[cfg(feature = "render3d")]
+    Shader3d,
+    /// A user asset reloaded by a custom function.
+    Custom(fn(&mut World, &Path)),
+    /// A user asset whose bytes are handed to a loader.
+    Loader(Arc<dyn AssetLoader>),
+}
+
+/// The asset server manages filesystem watching and hot-reload dispatch.
+///
+/// Stored as a resource in the [`World`]. Created by [`DefaultPlugins`](crate::app::DefaultPlugins).
+pub struct AssetServer {
+    /// The filesystem watcher. `None` if initialization failed.
+    watcher: Option<RecommendedWatcher>,
+    /// Receives filesystem events from the watcher's background thread.
+    /// Wrapped in `Mutex` to satisfy `Sync` (required by World resources).
+    /// Only accessed from the main thread via `poll()`, so contention is zero.
+    rx: Mutex<mpsc::Receiver<Result<notify::Event, notify::Error>>>,
+    /// Maps absolute file paths to their asset kind, so we know what to reload.
+    watched_paths: HashMap<PathBuf, AssetKind>,
+    /// Debounce buffer: path → timestamp of last event.
+    pending_reloads: HashMap<PathBuf, Instant>,
+    /// Dependency edges: dependent → the assets it depends on.
+    dependencies: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// Reverse edges: dependency → the assets that depend on it.
+    dependents: HashMap<PathBuf, HashSet<PathBuf>>,
+    /// Set to true if the receiver has disconnected (log once, then stop polling).
+    rx_disconnected: bool,
+    /// Log of reload events (diagnostics only).
+    #[cfg(feature = "diagnostics")]
+    reload_log: Vec<ReloadEvent>,
+    /// Loader threads for async loads. `None` until the first one.
+    loader: Option<Loader>,
+    /// Handed to loader jobs so they can report back.
+    completed_tx: mpsc::Sender<CompletedLoad>,
+    /// Finished decodes waiting for the main thread. `Mutex` for `Sync`,
+    /// as with `rx`.
+    completed_rx: Mutex<mpsc::Receiver<CompletedLoad>>,
+    /// State of every async load, by the path it was started with.
+    load_states: HashMap<PathBuf, LoadState>,
+    /// Registered [`AssetLoader`]s, in registration order.
+    loaders: Vec<Arc<dyn AssetLoader>>,
+}
+
+impl AssetServer {
+    /// Create a new asset server. Starts the filesystem watcher.
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel();
+        let (completed_tx, completed_rx) = mpsc::channel();
+
+        let watcher = notify::recommended_watcher(move |res| {
+            // Send the event to the main thread. Ignore send errors (receiver dropped).
+            let _ = tx.send(res);
+        });
+
+        let watcher = match watcher {
+            Ok(w) => Some(w),
+            Err(e) => {
+                log::warn!("Failed to create file watcher: {e}. Hot-reload disabled.");
+                None
+            }
+        };
+
+        Self {
+            watcher,
+            rx: Mutex::new(rx),
+            watched_paths: HashMap::new(),
+            pending_reloads: HashMap::new(),
+            dependencies: HashMap::new(),
+            dependents: HashMap::new(),
+            rx_disconnected: false,
+            #[cfg(feature = "diagnostics")]
+            reload_log: Vec::new(),
+            loader: None,
+            completed_tx,
+            completed_rx: Mutex::new(completed_rx),
+            load_states: HashMap::new(),
+            loaders: Vec::new(),
+        }
+    }
+
+    /// Register a file path for watching. The `kind` determines what reload
+    /// action to take when the file changes.
+    pub(crate) fn watch(&mut self, path: impl Into<PathBuf>, kind: AssetKind) {
+        let path = path.into();
+
+        // Canonicalize so we match events correctly.
+        let canonical = match path.canonicalize() {
+            Ok(p) => p,
+            Err(e) => {
+                log::warn!("Cannot watch '{}': {e}", path.display());
+                return;
+            }
+        };
+
+        if let Some(watcher) = &mut self.watcher {
+            if let Err(e) = watcher.watch(&canonical, RecursiveMode::NonRecursive) {
+                log::warn!("Failed to watch '{}': {e}", canonical.display());
+                return;
+            }
+        }
+
+        self.watched_paths.insert(canonical, kind);
+    }
+
+    /// Stop watching `path`, e.g. because its asset was unloaded. Files
+    /// other assets depend on stay watched.
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    pub(crate) fn unwatch(&mut self, path: impl AsRef<Path>) {
+        let Ok(canonical) = path.as_ref().canonicalize() else {
+            return;
+        };
+        self.pending_reloads.remove(&canonical);
+        if self.watched_paths.remove(&canonical).is_some()
+            && !self.dependents.contains_key(&canonical)
+            && let Some(watcher) = &mut self.watcher
+            && let Err(e) = watcher.unwatch(&canonical)
+        {
+            log::debug!("Failed to unwatch '{}': {e}", canonical.display());
+        }
+    }
+
+    /// Watch a file and call `reload` with its path whenever it changes.
+    ///
+    /// Use this for asset types the engine doesn't know how to reload (scene
+    /// files, prefabs, materials) so they can take part in cascading reloads.
+    pub fn watch_custom(&mut self, path: impl Into<PathBuf>, reload: fn(&mut World, &Path)) {
+        self.watch(path, AssetKind::Custom(reload));
+    }
+
+    /// Watch a file and call `reload` with its new contents whenever it
+    /// changes. Errors are logged and recorded like built-in reload
+    /// failures. For a whole file type, register an [`AssetLoader`] instead.
+    pub fn watch_bytes(
+        &mut self,
+        path: impl Into<PathBuf>,
+        reload: impl Fn(&mut World, &Path, &[u8]) -> Result<(), String> + Send + Sync + 'static,
+    ) {
+        self.watch(path, AssetKind::Loader(Arc::new(loader::FnLoader(reload))));
+    }
+
+    /// Register a loader for its file extensions. Load files with
+    /// [`load_custom`]; a later loader for the same extension takes over.
+    pub fn register_loader(&mut self, loader: impl AssetLoader) {
+        self.loaders.push(Arc::new(loader));
+    }
+
+    /// Record that `dependent` depends on `dependency`.
+    ///
+    /// When `dependency` changes on disk, `dependent` (and anything depending
+    /// on it) is reloaded after it. The dependency file is watched even if it
+    /// has no reload action of its own.
+    pub fn add_dependency(&mut self, dependent: impl AsRef<Path>, dependency: impl AsRef<Path>) {
+        let (dependent, dependency) = (dependent.as_ref(), dependency.as_ref());
+        let (dependent, dependency) = match (dependent.canonicalize(), dependency.canonicalize()) {
+            (Ok(a), Ok(b)) => (a, b),
+            (Err(e), _) | (_, Err(e)) => {
+                log::warn!(
+                    "Cannot track dependency '{}' -> '{}': {e}",
+                    dependent.display(),
+                    dependency.display()
+                );
+                return;
+            }
+        };
+
+        let already_watched = self.watched_paths.contains_key(&dependency)
+            || self.dependents.contains_key(&dependency);
+        if !already_watched
+            && let Some(watcher) = &mut self.watcher
+            && let Err(e) = watcher.watch(&dependency, RecursiveMode::NonRecursive)
+        {
+            log::warn!("Failed to watch '{}': {e}", dependency.display());
+        }
+
+        self.dependents
+            .entry(dependency.clone())
+            .or_default()
+            .insert(dependent.clone());
+        self.dependencies.entry(dependent).or_default().insert(dependency);
+    }
+
+    /// Forget all dependencies recorded for `dependent`.
+    pub fn clear_dependencies(&mut self, dependent: impl AsRef<Path>) {
+        let Ok(dependent) = dependent.as_ref().canonicalize() else {
+            return;
+        };
+        for dependency in self.dependencies.remove(&dependent).unwrap_or_default() {
+            if let Some(set) = self.dependents.get_mut(&dependency) {
+                set.remove(&dependent);
+                if set.is_empty() {
+                    self.dependents.remove(&dependency);
+                }
+            }
+        }
+    }
+
+    /// Load `path` in the background.
+    ///
+    /// `decode` runs on a loader thread and turns the file into CPU-side
+    /// data. When it succeeds, `finish` runs on the main thread at the start
+    /// of a later frame with World access, to upload or insert the result.
+    /// Until then [`load_state`](Self::load_state) reports
+    /// [`LoadState::Loading`]; a decode error is logged and marks the path
+    /// [`LoadState::Failed`].
+    pub fn load_async<T, D, F>(&mut self, path: impl Into<PathBuf>, decode: D, finish: F)
+    where
+        T: Send + 'static,
+        D: FnOnce(&Path) -> Result<T, String> + Send + 'static,
+        F: FnOnce(&mut World, T) + Send + 'static,
+    {
+        let path = path.into();
+        self.load_states.insert(path.clone(), LoadState::Loading);
+
+        let tx = self.completed_tx.clone();
+        let job_path = path.clone();
+        let job: LoadJob = Box::new(move || {
+            let result = decode(&job_path).map(|data| -> FinishFn {
+                Box::new(move |world: &mut World| finish(world, data))
+            });
+            // The server may have been dropped meanwhile; nothing to report to.
+            let _ = tx.send(CompletedLoad {
+                path: job_path,
+                result,
+            });
+        });
+
+        let loader = self.loader.get_or_insert_with(Loader::start);
+        if let Err(mpsc::SendError(job)) = loader.jobs.send(job) {
+            // No loader threads could be started: decode here instead.
+            log::warn!("Asset loader unavailable; loading '{}' synchronously", path.display());
+            job();
+        }
+    }
+
+    /// State of the async load started for `path`, or `None` if no async
+    /// load was started with that path.
+    pub fn load_state(&self, path: impl AsRef<Path>) -> Option<LoadState> {
+        self.load_states.get(path.as_ref()).copied()
+    }
+
+    /// Counts of all tracked async loads.
+    pub fn load_progress(&self) -> LoadProgress {
+        let mut progress = LoadProgress::default();
+        for state in self.load_states.values() {
+            match state {
+                LoadState::Loading => progress.loading += 1,
+                LoadState::Loaded => progress.loaded += 1,
+                LoadState::Failed => progress.failed += 1,
+            }
+        }
+        progress
+    }
+
+    /// Forget loads that have finished, so [`load_progress`](Self::load_progress)
+    /// starts over for the next loading screen.
+    pub fn clear_finished_loads(&mut self) {
+        self.load_states.retain(|_, state| *state == LoadState::Loading);
+    }
+
+    /// Drain filesystem events from the receiver into the debounce buffer.
+    fn poll(&mut self) {
+        if self.rx_disconnected {
+            return;
+        }
+
+        let rx = self.rx.get_mut().expect("AssetServer rx mutex poisoned");
+
+        loop {
+            match rx.try_recv() {
+                Ok(Ok(event)) => {
+                    // We care about modify and create events (atomic saves appear as create).
+                    use notify::EventKind;
+                    match event.kind {
+                        EventKind::Modify(_) | EventKind::Create(_) => {
+                            for path in &event.paths {
+                                // Canonicalize the event path to match our watched_paths keys.
+                                let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                                if self.watched_paths.contains_key(&canonical)
+                                    || self.dependents.contains_key(&canonical)
+                                {
+                                    self.pending_reloads.insert(canonical, Instant::now());
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(Err(e)) => {
+                    log::warn!("File watcher error: {e}");
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    log::warn!("File watcher disconnected. Hot-reload disabled.");
+                    self.rx_disconnected = true;
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Return entries that have been quiet for at least the debounce duration,
+    /// expanded with their transitive dependents and sorted so dependencies
+    /// reload before the assets that use them.
+    fn drain_ready(&mut self) -> Vec<(PathBuf, AssetKind)> {
+        let now = Instant::now();
+        let mut ready = Vec::new();
+
+        self.pending_reloads.retain(|path, timestamp| {
+            if now.duration_since(*timestamp) >= DEBOUNCE_DURATION {
+                ready.push(path.clone());
+                false // remove from pending
+            } else {
+                true // keep waiting
+            }
+        });
+
+        if ready.is_empty() {
+            return Vec::new();
+        }
+
+        reload_order(&ready, &self.dependents)
+            .into_iter()
+            .filter_map(|path| {
+                let kind = self.watched_paths.get(&path)?.clone();
+                Some((path, kind))
+            })
+            .collect()
+    }
+
+    /// Collect a diagnostics snapshot of asset state and drain the reload log.
+    #[cfg(feature = "diagnostics")]
+    pub(crate) fn diagnostics_snapshot(&mut self) -> crate::diag::AssetDiagSnapshot {
+        let watcher_active = self.watcher.is_some() && !self.rx_disconnected;
+        let pending_count = self.pending_reloads.len();
+        let watched_count = self.watched_paths.len();
+
+        // Group watched files by kind label.
+        let mut watched_files: Vec<(String, String)> = Vec::new();
+        for (path, kind) in &self.watched_paths {
+            let kind_label = match kind {
+                #[cfg(feature = "render2d")]
+                AssetKind::Texture2d(_) => "Texture2d",
+                #[cfg(feature = "render3d")]
+                AssetKind::Texture3d(_) => "Texture3d",
+                #[cfg(feature = "render2d")]
+                AssetKind::Shader2d => "Shader2d",
+                #[cfg(feature = "render3d")]
+                AssetKind::Shader3d => "Shader3d",
+                AssetKind::Custom(_) => "Custom",
+                AssetKind::Loader(loader) => loader.name(),
+            };
+            let filename = path
+                .file_name()
+                .map(|f| f.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            watched_files.push((kind_label.to_string(), filename));
+        }
+
+        let mut dependencies: Vec<(String, String)> = self
+            .dependencies
+            .iter()
+            .flat_map(|(dependent, deps)| {
+                deps.iter()
+                    .map(move |dep| (display_name(dependent), display_name(dep)))
+            })
+            .collect();
+        dependencies.sort();
+
+        let reload_events: Vec<crate::diag::ReloadEventSnapshot> = self
+            .reload_log
+            .drain(..)
+            .map(|e| crate::diag::ReloadEventSnapshot {
+                timestamp_secs: e.timestamp_secs,
+                path: e.path,
+                kind: e.kind,
+                success: e.success,
+                error: e.error,
+            })
+            .collect();
+
+        crate::diag::AssetDiagSnapshot {
+            watched_count,
+            watcher_active,
+            pending_count,
+            watched_files,
+            dependencies,
+            reload_events,
+        }
+    }
+}
+
+/// File name of a path for display, falling back to the full path.
+#[cfg(feature = "diagnostics")]
+fn display_name(path: &Path) -> String {
+    path.file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Compute the reload order for a set of changed paths: the changed paths
+/// plus everything that transitively depends on them, topologically sorted
+/// (Kahn's algorithm) so each asset comes after its dependencies. Ties are
+/// broken by path for a stable order. Cycles are logged and appended last.
+fn reload_order(
+    changed: &[PathBuf],
+    dependents: &HashMap<PathBuf, HashSet<PathBuf>>,
+) -> Vec<PathBuf> {
+    // 1. Collect the affected set (changed + transitive dependents).
+    let mut affected: BTreeSet<PathBuf> = BTreeSet::new();
+    let mut stack: Vec<PathBuf> = changed.to_vec();
+    while let Some(path) = stack.pop() {
+        if !affected.insert(path.clone()) {
+            continue;
+        }
+        if let Some(next) = dependents.get(&path) {
+            stack.extend(next.iter().cloned());
+        }
+    }
+
+    // 2. In-degree = number of affected dependencies of each affected node.
+    let mut in_degree: HashMap<&PathBuf, usize> = affected.iter().map(|p| (p, 0)).collect();
+    for path in &affected {
+        for dependent in dependents.get(path).into_iter().flatten() {
+            if let Some(d) = in_degree.get_mut(dependent) {
+                *d += 1;
+            }
+        }
+    }
+
+    // 3. Kahn's algorithm.
+    let mut ready: BTreeSet<&PathBuf> = in_degree
+        .iter()
+        .filter(|(_, d)| **d == 0)
+        .map(|(p, _)| *p)
+        .collect();
+    let mut order = Vec::with_capacity(affected.len());
+    while let Some(path) = ready.pop_first() {
+        order.push(path.clone());
+        for dependent in dependents.get(path).into_iter().flatten() {
+            if let Some(d) = in_degree.get_mut(dependent) {
+                *d -= 1;
+                if *d == 0 {
+                    ready.insert(dependent);
+                }
+            }
+        }
+    }
+
+    // 4. Anything left over is part of a cycle.
+    if order.len() < affected.len() {
+        let cyclic: Vec<PathBuf> = affected
+            .iter()
+            .filter(|p| !order.contains(p))
+            .cloned()
+            .collect();
+        log::warn!(
+            "Asset dependency cycle among {:?}; reloading in path order",
+            cyclic
+        );
+        order.extend(cyclic);
+    }
+
+    order
+}
+
+impl Default for AssetServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Poll for filesystem changes and dispatch reloads. Called once per frame
+/// from the main loop (before systems run).
+pub(crate) fn process_asset_reloads(world: &mut World) {
+    // Extract the asset server to avoid borrow conflicts.
+    let Some(mut server) = world.resource_remove::<AssetServer>() else {
+        return;
+    };
+
+    server.poll();
+    let ready = server.drain_ready();
+
+    // Put it back before dispatching reloads (dispatchers need world access).
+    world.insert_resource(server);
+    // Reloads replace textures and shaders the render thread may hold.
+    if !ready.is_empty() {
+        finish_render(world);
+    }
+
+    for (path, kind) in ready {
+        match kind {
+            #[cfg(feature = "render2d")]
+            AssetKind::Texture2d(handle) => {
+                reload_texture_2d(world, &path, handle);
+            }
+            #[cfg(feature = "render3d")]
+            AssetKind::Texture3d(handle) => {
+                reload_texture_3d(world, &path, handle);
+            }
+            #[cfg(feature = "render2d")]
+            AssetKind::Shader2d => {
+                reload_shader_2d(world, &path);
+            }
+            #[cfg(feature = "render3d")]
+            AssetKind::Shader3d => {
+                reload_shader_3d(world, &path);
+            }
+            AssetKind::Custom(reload) => {
+                reload(world, &path);
+                log::info!("Hot-reloaded asset: {}", path.display());
+                #[cfg(feature = "diagnostics")]
+                push_reload_event(world, &path, "Custom", true, None);
+            }
+            AssetKind::Loader(loader) => {
+                loader::run_loader(world, &loader, &path, true);
+            }
+        }
+    }
+}
+
+/// Finish async loads whose decode has completed: run their upload step and
+/// update their [`LoadState`]. Called once per frame from the main loop,
+/// next to [`process_asset_reloads`].
+pub(crate) fn process_async_loads(world: &mut World) {
+    let completed: Vec<CompletedLoad> = match world.get_resource::<AssetServer>() {
+        Some(server) => match server.completed_rx.lock() {
+            Ok(rx) => rx.try_iter().collect(),
+            Err(_) => return,
+        },
+        None => return,
+    };
+    // Finishing a load uploads into stores the render thread may hold.
+    if !completed.is_empty() {
+        finish_render(world);
+    }
+
+    for CompletedLoad { path, result } in completed {
+        let state = match result {
+            Ok(finish) => {
+                finish(world);
+                log::info!("Loaded asset: {}", path.display());
+                LoadState::Loaded
+            }
+            Err(e) => {
+                log::warn!("Failed to load '{}': {e}", path.display());
+                LoadState::Failed
+            }
+        };
+        if let Some(server) = world.get_resource_mut::<AssetServer>() {
+            server.load_states.insert(path, state);
+        }
+    }
+}
+
+// ── Reload Dispatchers ──────────────────────────────────────────────────────
+
+/// Reload a 2D texture from disk, replacing the GPU data at the existing handle.
+#[cfg(feature = "render2d")]
+fn reload_texture_2d(
+    world: &mut World,
+    path: &std::path::Path,
+    handle: crate::render2d::TextureHandle,
+) {
+    use crate::render2d::texture::TextureStore;
+    use crate::render::GpuContext;
+    use crate::render2d::pipeline::SpriteRenderer;
+
+    let (width, height, data) = match crate::import::load_rgba(world, path) {
+        Ok(image) => image,
+        Err(e) => {
+            log::warn!("Hot-reload failed for '{}': {e}", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Texture2d", false, Some(e.to_string()));
+            return;
+        }
+    };
+
+    // Extract resources needed for GPU upload.
+    let Some(gpu) = world.resource_remove::<GpuContext>() else { return };
+    let Some(renderer) = world.resource_remove::<SpriteRenderer>() else {
+        world.insert_resource(gpu);
+        return;
+    };
+    let Some(mut store) = world.resource_remove::<TextureStore>() else {
+        world.insert_resource(gpu);
+        world.insert_resource(renderer);
+        return;
+    };
+
+    store.reload_entry(&gpu, &renderer, handle, width, height, &data);
+    log::info!("Hot-reloaded 2D texture: {}", path.display());
+
+    world.insert_resource(store);
+    world.insert_resource(renderer);
+    world.insert_resource(gpu);
+
+    #[cfg(feature = "diagnostics")]
+    push_reload_event(world, path, "Texture2d", true, None);
+}
+
+/// Reload a 3D texture from disk, replacing the GPU data at the existing handle.
+#[cfg(feature = "render3d")]
+fn reload_texture_3d(
+    world: &mut World,
+    path: &std::path::Path,
+    handle: crate::render3d::TextureHandle3d,
+) {
+    use crate::render3d::texture::TextureStore3d;
+    use crate::render::GpuContext;
+
+    let (width, height, data) = match crate::import::load_rgba(world, path) {
+        Ok(image) => image,
+        Err(e) => {
+            log::warn!("Hot-reload failed for '{}': {e}", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Texture3d", false, Some(e.to_string()));
+            return;
+        }
+    };
+
+    let Some(gpu) = world.resource_remove::<GpuContext>() else { return };
+    let Some(mut store) = world.resource_remove::<TextureStore3d>() else {
+        world.insert_resource(gpu);
+        return;
+    };
+
+    store.reload_entry(&gpu, handle, width, height, &data);
+    log::info!("Hot-reloaded 3D texture: {}", path.display());
+
+    world.insert_resource(store);
+    world.insert_resource(gpu);
+
+    #[cfg(feature = "diagnostics")]
+    push_reload_event(world, path, "Texture3d", true, None);
+}
+
+/// Reload the 2D sprite shader from disk and recreate the pipeline.
+#[cfg(feature = "render2d")]
+fn reload_shader_2d(world: &mut World, path: &std::path::Path) {
+    use crate::render2d::pipeline::SpriteRenderer;
+    use crate::render::GpuContext;
+
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Hot-reload failed for shader '{}': {e}", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Shader2d", false, Some(e.to_string()));
+            return;
+        }
+    };
+
+    let Some(gpu) = world.resource_remove::<GpuContext>() else { return };
+    let Some(mut renderer) = world.resource_remove::<SpriteRenderer>() else {
+        world.insert_resource(gpu);
+        return;
+    };
+
+    // The classic pipelines use the shader without defs; GPUs with texture
+    // arrays also get its TEXTURE_ARRAY variant.
+    let variant = |defs: crate::render::ShaderDefs| crate::render::preprocess(&source, &defs);
+    let sources = variant(crate::render::ShaderDefs::new()).and_then(|base| {
+        let arrays = renderer.texture_arrays.is_some();
+        let array_source = arrays.then(|| variant(crate::render::ShaderDefs::new().with("TEXTURE_ARRAY")));
+        Ok((base, array_source.transpose()?))
+    });
+    let (base_source, array_source) = match sources {
+        Ok(sources) => sources,
+        Err(err) => {
+            log::warn!("Shader error in '{}': {err}. Keeping old pipeline.", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Shader2d", false, Some(err.to_string()));
+            world.insert_resource(renderer);
+            world.insert_resource(gpu);
+            return;
+        }
+    };
+
+    // Push an error scope so we can catch validation errors without panicking.
+    gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+    let shader = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("sprite shader (hot-reload)"),
+        source: wgpu::ShaderSource::Wgsl(base_source.into()),
+    });
+
+    let candidate = renderer.build_pipeline(&gpu, &shader, false, 1);
+    let instanced_candidate = renderer.build_pipeline(&gpu, &shader, true, 1);
+    let array_candidate = array_source.map(|array_source| {
+        let shader = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("sprite shader (texture array, hot-reload)"),
+            source: wgpu::ShaderSource::Wgsl(array_source.into()),
+        });
+        let pipeline = renderer.build_texture_array_pipeline(&gpu, &shader, false, 1);
+        let instanced = renderer.build_texture_array_pipeline(&gpu, &shader, true, 1);
+        (shader, pipeline, instanced)
+    });
+
+    // Check if the pipeline compiled successfully before swapping it in.
+    let error = pollster::block_on(gpu.device.pop_error_scope());
+    if let Some(err) = error {
+        log::warn!("Shader error in '{}': {err}. Keeping old pipeline.", path.display());
+        #[cfg(feature = "diagnostics")]
+        push_reload_event(world, path, "Shader2d", false, Some(err.to_string()));
+    } else {
+        renderer.replace_shader(shader, candidate, instanced_candidate, array_candidate);
+        log::info!("Hot-reloaded 2D shader: {}", path.display());
+        crate::render::shader_diff::request_shader_diff(world, path);
+        #[cfg(feature = "diagnostics")]
+        push_reload_event(world, path, "Shader2d", true, None);
+    }
+
+    world.insert_resource(renderer);
+    world.insert_resource(gpu);
+}
+
+/// Reload the 3D PBR shader from disk and recreate the pipeline.
+#[cfg(feature = "render3d")]
+fn reload_shader_3d(world: &mut World, path: &std::path::Path) {
+    use crate::render3d::pipeline::MeshRenderer;
+    use crate::render::GpuContext;
+
+    let source = match std::fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Hot-reload failed for shader '{}': {e}", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Shader3d", false, Some(e.to_string()));
+            return;
+        }
+    };
+
+    let Some(gpu) = world.resource_remove::<GpuContext>() else { return };
+    let Some(mut renderer) = world.resource_remove::<MeshRenderer>() else {
+        world.insert_resource(gpu);
+        return;
+    };
+
+    // The base pipelines use the shader without defs; variants recompile
+    // from `source` on demand.
+    let base_source = match crate::render::preprocess(&source, &crate::render::ShaderDefs::new()) {
+        Ok(base_source) => base_source,
+        Err(err) => {
+            log::warn!("Shader error in '{}': {err}. Keeping old pipeline.", path.display());
+            #[cfg(feature = "diagnostics")]
+            push_reload_event(world, path, "Shader3d", false, Some(err.to_string()));
+            world.insert_resource(renderer);
+            world.insert_resource(gpu);
+            return;
+        }
+    };
+
+    gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+    let shader = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("pbr shader (hot-reload)"),
+        source: wgpu::ShaderSource::Wgsl(base_source.into()),
+    });
+
+    let candidate = renderer.build_pipeline(&gpu, &shader, false);
+    let prepassed_candidate = renderer.build_pipeline(&gpu, &shader, true);
+
+    // Check if the pipeline compiled successfully before swapping it in.
+    let error = pollster::block_on(gpu.device.pop_error_scope());
+    if let Some(err) = error {
+        log::warn!("Shader error in '{}': {err}. Keeping old pipeline.", path.display());
+        #[cfg(feature = "diagnostics")]
+        push_reload_event(world, path, "Shader3d", false, Some(err.to_string()));
+    } else {
+        renderer.replace_pbr_shader(source, shader, candidate, prepassed_candidate);
+        log::info!("Hot-reloaded 3D shader: {}", path.display());
+        crate::render::shader_diff::request_shader_diff(world, path);
+        #[cfg(feature = "diagnostics")]
+        push_reload_event(world, path, "Shader3d", true, None);
+    }
+
+    world.insert_resource(renderer);
+    world.insert_resource(gpu);
+}
+
+/// Push a reload event into the AssetServer's reload log (diagnostics only).
+#[cfg(feature = "diagnostics")]
+pub(crate) fn push_reload_event(
+    world: &mut World,
+    path: &std::path::Path,
+    kind: &str,
+    success: bool,
+    error: Option<String>,
+) {
+    let timestamp_secs = world
+        .get_resource::<crate::time::Time>()
+        .map(|t| t.elapsed_secs())
+        .unwrap_or(0.0);
+    let filename = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string());
+    if let Some(server) = world.get_resource_mut::<AssetServer>() {
+        server.reload_log.push(ReloadEvent {
+            timestamp_secs,
+            path: filename,
+            kind: kind.to_string(),
+            success,
+            error,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(&str, &str)]) -> HashMap<PathBuf, HashSet<PathBuf>> {
+        let mut dependents: HashMap<PathBuf, HashSet<PathBuf>> = HashMap::new();
+        for (dependency, dependent) in edges {
+            dependents
+                .entry(PathBuf::from(dependency))
+                .or_default()
+                .insert(PathBuf::from(dependent));
+        }
+        dependents
+    }
+
+    fn names(order: Vec<PathBuf>) -> Vec<String> {
+        order.into_iter().map(|p| p.display().to_string()).collect()
+    }
+
+    #[test]
+    fn unrelated_change_reloads_only_itself() {
+        let deps = graph(&[("tex.png", "mat.json")]);
+        let order = reload_order(&[PathBuf::from("other.png")], &deps);
+        assert_eq!(names(order), vec!["other.png"]);
+    }
+
+    #[test]
+    fn cascade_reloads_dependencies_first() {
+        // scene → prefab → texture, and material → texture.
+        let deps = graph(&[
+            ("tex.png", "prefab.json"),
+            ("tex.png", "mat.json"),
+            ("prefab.json", "scene.json"),
+        ]);
+        let order = names(reload_order(&[PathBuf::from("tex.png")], &deps));
+        assert_eq!(order, vec!["tex.png", "mat.json", "prefab.json", "scene.json"]);
+    }
+
+    #[test]
+    fn diamond_dependent_reloads_once_after_both_parents() {
+        let deps = graph(&[
+            ("a.png", "b.json"),
+            ("a.png", "c.json"),
+            ("b.json", "d.json"),
+            ("c.json", "d.json"),
+        ]);
+        let order = names(reload_order(&[PathBuf::from("a.png")], &deps));
+        assert_eq!(order, vec!["a.png", "b.json", "c.json", "d.json"]);
+    }
+
+    #[test]
+    fn cycle_still_reloads_everything() {
+        let deps = graph(&[("a", "b"), ("b", "a")]);
+        let order = names(reload_order(&[PathBuf::from("a")], &deps));
+        assert_eq!(order.len(), 2);
+    }
+
+    /// Run `process_async_loads` until nothing is loading (or give up).
+    fn finish_loads(world: &mut World) {
+        for _ in 0..500 {
+            process_async_loads(world);
+            if world.resource::<AssetServer>().load_progress().is_done() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        panic!("async loads did not finish");
+    }
+
+    #[test]
+    fn async_load_decodes_in_background_and_finishes_on_main_thread() {
+        let dir = std::env::temp_dir().join(format!("necs-async-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("value.txt");
+        std::fs::write(&file, "42").unwrap();
+
+        struct Answer(u32);
+        let mut world = World::new();
+        world.insert_resource(AssetServer::new());
+        world.resource_mut::<AssetServer>().load_async(
+            file.clone(),
+            |path| {
+                let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+                text.trim().parse::<u32>().map_err(|e| e.to_string())
+            },
+            |world, value| world.insert_resource(Answer(value)),
+        );
+        let missing = dir.join("missing.txt");
+        world.resource_mut::<AssetServer>().load_async(
+            missing.clone(),
+            |path| std::fs::read(path).map_err(|e| e.to_string()),
+            |_, _| panic!("finish must not run for a failed load"),
+        );
+        assert_eq!(
+            world.resource::<AssetServer>().load_state(&file),
+            Some(LoadState::Loading)
+        );
+
+        finish_loads(&mut world);
+        let server = world.resource::<AssetServer>();
+        assert_eq!(server.load_state(&file), Some(LoadState::Loaded));
+        assert_eq!(server.load_state(&missing), Some(LoadState::Failed));
+        assert_eq!(
+            server.load_progress(),
+            LoadProgress {
+                loading: 0,
+                loaded: 1,
+                failed: 1
+            }
+        );
+        assert_eq!(world.resource::<Answer>().0, 42);
+
+        world.resource_mut::<AssetServer>().clear_finished_loads();
+        assert_eq!(world.resource::<AssetServer>().load_progress().total(), 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,457 @@
+//! # Asset GC — Releasing Textures and Meshes Nothing Uses
+//!
+//! Handles are plain indices (see [`asset`](crate::asset)), so loading never
+//! frees anything: a game that streams level after level keeps every texture
+//! and mesh it ever loaded on the GPU. [`free_unused_assets`] is a
+//! mark-and-sweep pass over the handle stores:
+//!
+//! ```text
+//!   mark: scan the world for handles in use
+//!     Sprite.texture, UiImage.texture, VideoPlayer frames,
+//!     Material's texture maps, Mesh3d.mesh, ParticleEmitter textures,
+//!     registered texture atlases, font atlases, AssetGc pins
+//!
+//!   sweep: free every loaded asset that wasn't marked
+//!     TextureStore    [0 white][1 hero ✓][2 level1 ✗][3 level2 ✓]
+//!                                           │
+//!                                           ▼ GPU texture dropped,
+//!                                   slot now draws as white,
+//!                                   path forgotten and unwatched
+//! ```
+//!
+//! A freed slot is never reused, so a stale handle can't show some other
+//! asset — it draws white (a mesh draws nothing). Loading the same path
+//! again uploads a fresh copy under a new handle. Built-in meshes, the white
+//! defaults, atlas pages and images packed into them are never freed.
+//!
+//! The mark phase only sees components and the engine's own registries. A
+//! handle kept anywhere else — say in a resource, to spawn from later — must
+//! be pinned with [`AssetGc::pin`] or held through a counted handle from
+//! [`AssetGc::retain`], or it will be freed out from under you:
+//!
+//! ```text
+//!   AssetGc::retain(level1_tiles) ──► Handle<AssetRef>   (strong count 1)
+//!     clone into each system that spawns tiles           (strong count 3)
+//!     level unloaded, every clone dropped                (strong count 0)
+//!   next sweep: retain entry removed, texture unmarked ──► freed
+//! ```
+//!
+//! A pin lasts until it is removed by hand; a retained handle lasts exactly
+//! as long as something holds it, like a [`Handle<T>`] from
+//! [`Assets<T>`](crate::asset::Assets).
+//!
+//! Run a sweep yourself after unloading a level with
+//! [`Context::free_unused_assets`](crate::context::Context::free_unused_assets),
+//! or let the engine do it on a timer:
+//!
+//! ```ignore
+//! Game::new("My Game").resource(AssetGc::every(30.0)).run();
+//!
+//! // in a startup system: a texture only ever spawned from later
+//! let cursor = ctx.load_texture("ui/cursor.png");
+//! ctx.world.resource_mut::<AssetGc>().set_pinned(cursor, true);
+//!
+//! // or: kept while the level resource holds it, freed after it's dropped
+//! let tiles = ctx.load_texture("levels/1/tiles.png");
+//! let tiles = ctx.world.resource_mut::<AssetGc>().retain(tiles);
+//! ctx.world.insert_resource(LevelAssets { tiles });
+//! ```
+//!
+//! Sounds need none of this: a [`SoundData`](crate::audio::SoundData) shares
+//! its samples through an `Arc`, dropped with the last clone.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Resources.UnloadUnusedAssets()` walks the scene for
+//!   references and unloads the rest; also run automatically on scene load.
+//! - **Bevy**: Strong `Handle<T>`s are reference counted; an asset is
+//!   dropped as soon as its last strong handle is.
+//! - **Godot**: Resources are reference counted and freed when the last
+//!   reference goes away.
+//! - **Our approach**: Unity's — an explicit sweep that scans components, so
+//!   handles stay `Copy` and loading stays free of bookkeeping — with
+//!   Bevy-style counted handles for the ones held outside components.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::asset::{Assets, Handle};
+use crate::ecs::World;
+use crate::particles::ParticleEmitter;
+use crate::render::pipelined::finish_render;
+
+#[cfg(feature = "render2d")]
+use crate::render2d::font::FontStore;
+#[cfg(feature = "render2d")]
+use crate::render2d::texture::{TextureHandle, TextureStore};
+#[cfg(feature = "render2d")]
+use crate::render2d::texture_atlas::TextureAtlases;
+#[cfg(feature = "render2d")]
+use crate::render2d::{Sprite, VideoPlayer};
+#[cfg(feature = "render2d")]
+use crate::ui::UiImage;
+#[cfg(feature = "render3d")]
+use crate::render3d::mesh::{MeshHandle, MeshStore};
+#[cfg(feature = "render3d")]
+use crate::render3d::texture::{MaterialTextures, TextureHandle3d, TextureStore3d};
+#[cfg(feature = "render3d")]
+use crate::render3d::{Material, Mesh3d};
+
+// ── Settings ────────────────────────────────────────────────────────────
+
+/// A handle of any kind, for [`AssetGc::pin`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetRef {
+    #[cfg(feature = "render2d")]
+    Texture(TextureHandle),
+    #[cfg(feature = "render3d")]
+    Texture3d(TextureHandle3d),
+    #[cfg(feature = "render3d")]
+    Mesh(MeshHandle),
+}
+
+#[cfg(feature = "render2d")]
+impl From<TextureHandle> for AssetRef {
+    fn from(handle: TextureHandle) -> Self {
+        Self::Texture(handle)
+    }
+}
+
+#[cfg(feature = "render3d")]
+impl From<TextureHandle3d> for AssetRef {
+    fn from(handle: TextureHandle3d) -> Self {
+        Self::Texture3d(handle)
+    }
+}
+
+#[cfg(feature = "render3d")]
+impl From<MeshHandle> for AssetRef {
+    fn from(handle: MeshHandle) -> Self {
+        Self::Mesh(handle)
+    }
+}
+
+/// Resource: when to free unused assets automatically, and which assets
+/// to keep even when no component uses them. Without it, assets are only
+/// freed by calling [`free_unused_assets`].
+///
+/// ```ignore
+/// Game::new("My Game").resource(AssetGc::every(30.0))
+/// ```
+#[derive(Debug, Default)]
+pub struct AssetGc {
+    /// Time between automatic sweeps. `None` sweeps only on request.
+    pub interval: Option<Duration>,
+    since_sweep: Duration,
+    pinned: HashSet<AssetRef>,
+    /// Assets kept alive by counted handles from [`AssetGc::retain`].
+    retained: Assets<AssetRef>,
+}
+
+impl AssetGc {
+    /// No automatic sweeps; only pins.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sweep every `secs` seconds.
+    pub fn every(secs: f32) -> Self {
+        Self {
+            interval: Some(Duration::from_secs_f32(secs.max(0.0))),
+            ..Self::default()
+        }
+    }
+
+    /// Never free `asset`, even when nothing references it (builder pattern).
+    pub fn pin(mut self, asset: impl Into<AssetRef>) -> Self {
+        self.pinned.insert(asset.into());
+        self
+    }
+
+    /// Pin or unpin `asset`.
+    pub fn set_pinned(&mut self, asset: impl Into<AssetRef>, pinned: bool) {
+        let asset = asset.into();
+        if pinned {
+            self.pinned.insert(asset);
+        } else {
+            self.pinned.remove(&asset);
+        }
+    }
+
+    /// Whether `asset` is pinned.
+    pub fn is_pinned(&self, asset: impl Into<AssetRef>) -> bool {
+        self.pinned.contains(&asset.into())
+    }
+
+    /// Keep `asset` through sweeps for as long as the returned handle, or
+    /// any clone of it, is alive. Once the last one is dropped the asset is
+    /// freed by the next sweep that finds no component using it.
+    pub fn retain(&mut self, asset: impl Into<AssetRef>) -> Handle<AssetRef> {
+        self.retained.add(asset.into())
+    }
+
+    /// Whether any counted handle to `asset` is still alive.
+    pub fn is_retained(&self, asset: impl Into<AssetRef>) -> bool {
+        let asset = asset.into();
+        self.retained
+            .iter()
+            .any(|(id, &held)| held == asset && self.retained.handle(id).is_some())
+    }
+
+    /// Forget retain entries whose handles are all dropped, and return the
+    /// assets still held.
+    fn held(&mut self) -> impl Iterator<Item = AssetRef> + '_ {
+        self.retained.remove_unused();
+        self.retained.drain_events();
+        self.retained.iter().map(|(_, &asset)| asset)
+    }
+
+    /// Advance the timer by `dt`; true when an automatic sweep is due.
+    fn tick(&mut self, dt: Duration) -> bool {
+        let Some(interval) = self.interval else {
+            return false;
+        };
+        self.since_sweep += dt;
+        if self.since_sweep < interval {
+            return false;
+        }
+        self.since_sweep = Duration::ZERO;
+        true
+    }
+}
+
+// ── Sweep ───────────────────────────────────────────────────────────────
+
+/// What a store's `free` released.
+pub(crate) struct FreedEntry {
+    /// GPU memory released, approximately.
+    pub bytes: u64,
+    /// The path the asset was loaded from, if any.
+    pub path: Option<String>,
+}
+
+/// What one [`free_unused_assets`] call released.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FreedAssets {
+    pub textures: usize,
+    pub textures_3d: usize,
+    pub meshes: usize,
+    /// GPU memory released, approximately.
+    pub bytes: u64,
+}
+
+impl FreedAssets {
+    /// Total number of assets freed.
+    pub fn count(&self) -> usize {
+        self.textures + self.textures_3d + self.meshes
+    }
+}
+
+/// Handles found in use by the mark phase.
+#[derive(Debug, Default)]
+struct LiveAssets {
+    #[cfg(feature = "render2d")]
+    textures: HashSet<TextureHandle>,
+    #[cfg(feature = "render3d")]
+    textures_3d: HashSet<TextureHandle3d>,
+    #[cfg(feature = "render3d")]
+    meshes: HashSet<MeshHandle>,
+}
+
+impl LiveAssets {
+    fn mark(&mut self, asset: AssetRef) {
+        match asset {
+            #[cfg(feature = "render2d")]
+            AssetRef::Texture(handle) => {
+                self.textures.insert(handle);
+            }
+            #[cfg(feature = "render3d")]
+            AssetRef::Texture3d(handle) => {
+                self.textures_3d.insert(handle);
+            }
+            #[cfg(feature = "render3d")]
+            AssetRef::Mesh(handle) => {
+                self.meshes.insert(handle);
+            }
+        }
+    }
+}
+
+/// Collect every handle referenced by a component, an engine registry, a
+/// pin or a retained handle.
+fn live_assets(world: &mut World) -> LiveAssets {
+    let mut live = LiveAssets::default();
+    if let Some(gc) = world.get_resource_mut::<AssetGc>() {
+        let pinned: Vec<AssetRef> = gc.pinned.iter().copied().collect();
+        for asset in pinned.into_iter().chain(gc.held()) {
+            live.mark(asset);
+        }
+    }
+
+    #[cfg(feature = "render2d")]
+    {
+        world.query::<(&Sprite,)>(|_, (sprite,)| {
+            live.textures.extend(sprite.texture);
+        });
+        world.query::<(&UiImage,)>(|_, (image,)| {
+            live.textures.extend(image.texture);
+        });
+        world.query::<(&VideoPlayer,)>(|_, (player,)| {
+            live.textures.extend(player.texture());
+        });
+        world.query::<(&ParticleEmitter,)>(|_, (emitter,)| {
+            live.textures.extend(emitter.texture);
+        });
+        if let Some(atlases) = world.get_resource::<TextureAtlases>() {
+            live.textures.extend(atlases.textures());
+        }
+        if let Some(fonts) = world.get_resource::<FontStore>() {
+            live.textures.extend(fonts.atlas_handles());
+        }
+    }
+
+    #[cfg(feature = "render3d")]
+    {
+        world.query::<(&Mesh3d,)>(|_, (mesh,)| {
+            live.meshes.insert(mesh.mesh);
+        });
+        world.query::<(&Material,)>(|_, (material,)| {
+            live.textures_3d.extend(MaterialTextures::of(material).handles());
+        });
+        world.query::<(&ParticleEmitter,)>(|_, (emitter,)| {
+            live.textures_3d.extend(emitter.texture_3d);
+        });
+    }
+
+    live
+}
+
+/// Free every texture and mesh that no component, registered atlas, font,
+/// [`AssetGc`] pin or retained handle refers to. See the [module docs](self).
+pub fn free_unused_assets(world: &mut World) -> FreedAssets {
+    // The render thread may hold the stores for the frame in flight.
+    finish_render(world);
+    let live = live_assets(world);
+    let mut freed = FreedAssets::default();
+    let mut paths = Vec::new();
+
+    #[cfg(feature = "render2d")]
+    if let Some(store) = world.get_resource_mut::<TextureStore>() {
+        let unused: Vec<_> = store.freeable().filter(|h| !live.textures.contains(h)).collect();
+        for entry in unused.into_iter().filter_map(|h| store.free(h)) {
+            freed.textures += 1;
+            freed.bytes += entry.bytes;
+            paths.extend(entry.path);
+        }
+    }
+
+    #[cfg(feature = "render3d")]
+    {
+        if let Some(store) = world.get_resource_mut::<TextureStore3d>() {
+            let unused: Vec<_> = store.freeable().filter(|h| !live.textures_3d.contains(h)).collect();
+            for entry in unused.into_iter().filter_map(|h| store.free(h)) {
+                freed.textures_3d += 1;
+                freed.bytes += entry.bytes;
+                paths.extend(entry.path);
+            }
+        }
+        if let Some(store) = world.get_resource_mut::<MeshStore>() {
+            let unused: Vec<_> = store.freeable().filter(|h| !live.meshes.contains(h)).collect();
+            for entry in unused.into_iter().filter_map(|h| store.free(h)) {
+                freed.meshes += 1;
+                freed.bytes += entry.bytes;
+            }
+        }
+    }
+
+    // Freed files shouldn't hot-reload back into their dead slots.
+    if let Some(server) = world.get_resource_mut::<crate::asset::AssetServer>() {
+        for path in &paths {
+            server.unwatch(path);
+        }
+    }
+
+    if freed.count() > 0 {
+        log::info!(
+            "Freed {} unused assets ({:.1} MiB)",
+            freed.count(),
+            freed.bytes as f64 / (1024.0 * 1024.0)
+        );
+    }
+    freed
+}
+
+/// Run an automatic sweep when the [`AssetGc`] timer is due. Called once
+/// per frame by the window loop.
+pub(crate) fn run_asset_gc(world: &mut World, dt: Duration) {
+    let due = world.get_resource_mut::<AssetGc>().is_some_and(|gc| gc.tick(dt));
+    if due {
+        free_unused_assets(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timer_fires_once_per_interval() {
+        let mut gc = AssetGc::every(1.0);
+        let step = Duration::from_millis(400);
+        let fired: Vec<bool> = (0..6).map(|_| gc.tick(step)).collect();
+        assert_eq!(fired, [false, false, true, false, false, true]);
+        assert!(!AssetGc::new().tick(Duration::from_secs(60)));
+    }
+
+    #[cfg(all(feature = "render2d", feature = "render3d"))]
+    #[test]
+    fn mark_finds_components_and_pins() {
+        let mut world = World::new();
+        world.spawn((Sprite::new().texture(TextureHandle(2)),));
+        world.spawn((Sprite::new(),));
+        world.spawn((
+            Mesh3d { mesh: MeshHandle(5) },
+            Material {
+                base_color_texture: Some(TextureHandle3d(1)),
+                normal_texture: Some(TextureHandle3d(3)),
+                ..Material::default()
+            },
+        ));
+        world.insert_resource(AssetGc::new().pin(TextureHandle(4)).pin(MeshHandle(6)));
+
+        let live = live_assets(&mut world);
+        assert_eq!(live.textures, HashSet::from([TextureHandle(2), TextureHandle(4)]));
+        assert_eq!(live.textures_3d, HashSet::from([TextureHandle3d(1), TextureHandle3d(3)]));
+        assert_eq!(live.meshes, HashSet::from([MeshHandle(5), MeshHandle(6)]));
+    }
+
+    #[cfg(feature = "render2d")]
+    #[test]
+    fn retained_handles_keep_assets_until_the_last_clone_drops() {
+        let mut world = World::new();
+        let mut gc = AssetGc::new();
+        let held = gc.retain(TextureHandle(3));
+        let copy = held.clone();
+        world.insert_resource(gc);
+
+        drop(held);
+        assert_eq!(live_assets(&mut world).textures, HashSet::from([TextureHandle(3)]));
+        assert!(world.resource::<AssetGc>().is_retained(TextureHandle(3)));
+
+        drop(copy);
+        assert!(!world.resource::<AssetGc>().is_retained(TextureHandle(3)));
+        assert!(live_assets(&mut world).textures.is_empty());
+        assert!(world.resource::<AssetGc>().retained.is_empty());
+    }
+
+    #[cfg(feature = "render2d")]
+    #[test]
+    fn ui_images_keep_their_textures() {
+        // The sweep frees exactly the unmarked handles; a texture store
+        // needs a GPU, so check the mark.
+        let mut world = World::new();
+        world.spawn((UiImage::new(TextureHandle(7)),));
+        world.spawn((UiImage { texture: None, color: crate::render2d::Color::RED },));
+        assert_eq!(live_assets(&mut world).textures, HashSet::from([TextureHandle(7)]));
+    }
+}
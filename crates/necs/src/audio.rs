@@ -5,6 +5,20 @@
 //! Run [`audio_system`] each frame to auto-play component-attached sounds and
 //! clean up finished handles.
 //!
+//! Sources marked [`occludable`](AudioSource::occludable) play on their own
+//! mixer track with a low-pass filter, so the occlusion system (the
+//! `audio_occlusion` module, with the `physics3d` feature) can
+//! muffle them when level geometry sits between them and the listener.
+//!
+//! Sources with a [`SpatialAudioSource`](crate::audio_spatial::SpatialAudioSource)
+//! are heard from their entity's position: attenuated with distance from the
+//! [`AudioListener`](crate::audio_spatial::AudioListener) and panned toward
+//! its side. See [`audio_spatial`](crate::audio_spatial).
+//!
+//! Procedural sound — synths, engine hums — is generated by a
+//! [`DspGraph`] of oscillators, filters and user nodes, started with
+//! [`AudioEngine::play_graph`]. See [`audio_graph`](crate::audio_graph).
+//!
 //! # Example
 //!
 //! ```ignore
@@ -17,12 +31,21 @@
 
 use std::fmt;
 use std::path::Path;
+use std::time::Duration;
 
+use kira::effect::filter::{FilterBuilder, FilterHandle};
+use kira::info::Info;
 use kira::sound::static_sound::{StaticSoundData, StaticSoundHandle};
-use kira::sound::PlaybackState;
-use kira::{AudioManager, AudioManagerSettings, Decibels, DefaultBackend, Tween};
+use kira::sound::{PlaybackState, Sound};
+use kira::track::{TrackBuilder, TrackHandle};
+use kira::{AudioManager, AudioManagerSettings, Decibels, DefaultBackend, Frame, Tween};
 
+use crate::audio_graph::{DspGraph, DspGraphHandle, GraphPlayer};
 use crate::ecs::World;
+use crate::import::{AudioImporter, ImportCache};
+
+/// Filter cutoff that leaves audible sound untouched (Hz).
+pub(crate) const OPEN_CUTOFF_HZ: f64 = 20_000.0;
 
 /// Convert a linear amplitude (0.0 = silence, 1.0 = full) to decibels.
 fn amplitude_to_db(amplitude: f64) -> Decibels {
@@ -77,6 +100,30 @@ impl SoundData {
         Ok(Self { inner: data })
     }
 
+    /// Decode audio already in memory, e.g. a WAV built from a video's
+    /// sound track. Same formats as [`from_file`](Self::from_file).
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, AudioError> {
+        let data = StaticSoundData::from_cursor(std::io::Cursor::new(bytes))
+            .map_err(|e| AudioError::Load(e.to_string()))?;
+        Ok(Self { inner: data })
+    }
+
+    /// Load through an [`ImportCache`]: WAV files are resampled to
+    /// [`AUDIO_SAMPLE_RATE`](crate::import::AUDIO_SAMPLE_RATE) once and read
+    /// back from the cache afterwards. Other formats, and any import
+    /// failure, load with [`from_file`](Self::from_file).
+    pub fn import(cache: &mut ImportCache, path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        let path = path.as_ref();
+        let is_wav = path.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav"));
+        if is_wav {
+            match cache.import(path, &AudioImporter) {
+                Ok(wav) => return Self::from_bytes(wav),
+                Err(e) => log::warn!("Import failed, loading directly: {e}"),
+            }
+        }
+        Self::from_file(path)
+    }
+
     /// Set the volume (amplitude scale, 1.0 = full).
     pub fn volume(mut self, volume: f64) -> Self {
         self.inner = self.inner.volume(amplitude_to_db(volume));
@@ -95,11 +142,16 @@ impl SoundData {
         self
     }
 
-    /// Set stereo panning (0.0 = left, 0.5 = center, 1.0 = right).
+    /// Set stereo panning (-1.0 = left, 0.0 = center, 1.0 = right).
     pub fn panning(mut self, panning: f64) -> Self {
         self.inner = self.inner.panning(panning as f32);
         self
     }
+
+    /// Length of the sound at normal speed.
+    pub fn duration(&self) -> Duration {
+        self.inner.duration()
+    }
 }
 
 impl fmt::Debug for SoundData {
@@ -142,6 +194,45 @@ impl SoundHandle {
         self.inner.set_playback_rate(rate, Tween::default());
     }
 
+    /// Set the stereo panning of this playing sound (-1.0 = left, 0.0 =
+    /// center, 1.0 = right).
+    pub fn set_panning(&mut self, panning: f64) {
+        self.inner.set_panning(panning as f32, Tween::default());
+    }
+
+    /// Set volume and panning together, gliding over `duration`. Used by
+    /// the spatial audio system.
+    pub(crate) fn set_spatial(&mut self, volume: f64, panning: f64, duration: Duration) {
+        let tween = Tween {
+            duration,
+            ..Default::default()
+        };
+        self.inner.set_volume(amplitude_to_db(volume), tween);
+        self.inner.set_panning(panning as f32, tween);
+    }
+
+    /// Glide to `volume` over `duration`. Used by the music controller.
+    pub(crate) fn fade_to(&mut self, volume: f64, duration: Duration) {
+        let tween = Tween {
+            duration,
+            ..Default::default()
+        };
+        self.inner.set_volume(amplitude_to_db(volume), tween);
+    }
+
+    /// Fade out over `duration`, then stop.
+    pub(crate) fn fade_out(&mut self, duration: Duration) {
+        self.inner.stop(Tween {
+            duration,
+            ..Default::default()
+        });
+    }
+
+    /// Playback position in seconds.
+    pub(crate) fn position(&self) -> f64 {
+        self.inner.position()
+    }
+
     /// Returns `true` if the sound has finished or been stopped.
     pub fn is_stopped(&self) -> bool {
         matches!(self.inner.state(), PlaybackState::Stopped)
@@ -164,6 +255,9 @@ impl fmt::Debug for SoundHandle {
 /// Insert as a resource and use directly or via [`audio_system`].
 pub struct AudioEngine {
     manager: AudioManager<DefaultBackend>,
+    /// Main volume requested by the user, restored when unmuting.
+    main_volume: f64,
+    muted: bool,
 }
 
 impl AudioEngine {
@@ -175,14 +269,22 @@ impl AudioEngine {
     pub fn new() -> Self {
         let manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())
             .expect("Failed to initialize audio backend");
-        Self { manager }
+        Self {
+            manager,
+            main_volume: 1.0,
+            muted: false,
+        }
     }
 
     /// Try to create a new audio engine, returning an error on failure.
     pub fn try_new() -> Result<Self, AudioError> {
         let manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())
             .map_err(|e| AudioError::BackendInit(e.to_string()))?;
-        Ok(Self { manager })
+        Ok(Self {
+            manager,
+            main_volume: 1.0,
+            muted: false,
+        })
     }
 
     /// Play a sound, returning a handle for controlling it.
@@ -203,12 +305,60 @@ impl AudioEngine {
         Ok(SoundHandle { inner: handle })
     }
 
+    /// Start playing a [`DspGraph`] of procedural sound. The returned handle
+    /// controls its volume and stops it; the graph's
+    /// [`DspParam`](crate::audio_graph::DspParam)s change its sound.
+    pub fn play_graph(&mut self, graph: DspGraph) -> Result<DspGraphHandle, AudioError> {
+        self.manager
+            .play(GraphSoundData(graph))
+            .map_err(|e| AudioError::Play(e.to_string()))
+    }
+
+    /// Play a sound on a new sub-track with its own low-pass filter, so it
+    /// can be muffled independently of every other sound.
+    pub(crate) fn try_play_filtered(
+        &mut self,
+        sound: &SoundData,
+    ) -> Result<(SoundHandle, FilteredTrack), AudioError> {
+        let mut builder = TrackBuilder::new();
+        let filter = builder.add_effect(FilterBuilder::new().cutoff(OPEN_CUTOFF_HZ));
+        let mut track = self
+            .manager
+            .add_sub_track(builder)
+            .map_err(|e| AudioError::Play(e.to_string()))?;
+        let handle = track
+            .play(sound.inner.clone())
+            .map_err(|e| AudioError::Play(e.to_string()))?;
+        Ok((SoundHandle { inner: handle }, FilteredTrack { track, filter }))
+    }
+
     /// Set the main (global) volume for all sounds (amplitude scale, 1.0 = full).
+    ///
+    /// While muted, the new volume is remembered and applied on unmute.
     pub fn set_main_volume(&mut self, volume: f64) {
+        self.main_volume = volume;
+        if !self.muted {
+            self.manager
+                .main_track()
+                .set_volume(amplitude_to_db(volume), Tween::default());
+        }
+    }
+
+    /// Mute or unmute all sounds without losing the main volume setting.
+    ///
+    /// Used by auto-pause when the window loses focus.
+    pub fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        let volume = if muted { 0.0 } else { self.main_volume };
         self.manager
             .main_track()
             .set_volume(amplitude_to_db(volume), Tween::default());
     }
+
+    /// Returns `true` if all sounds are muted.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
 }
 
 impl Default for AudioEngine {
@@ -223,6 +373,59 @@ impl fmt::Debug for AudioEngine {
     }
 }
 
+// ── DspGraph playback ───────────────────────────────────────────────────
+
+/// Kira sound data for a [`DspGraph`]; becomes a [`GraphSound`] when played.
+struct GraphSoundData(DspGraph);
+
+impl kira::sound::SoundData for GraphSoundData {
+    type Error = ();
+    type Handle = DspGraphHandle;
+
+    fn into_sound(self) -> Result<(Box<dyn Sound>, Self::Handle), Self::Error> {
+        let (player, handle) = GraphPlayer::new(self.0);
+        Ok((Box::new(GraphSound(player)), handle))
+    }
+}
+
+/// A playing [`DspGraph`] on kira's audio thread.
+struct GraphSound(GraphPlayer);
+
+impl Sound for GraphSound {
+    fn process(&mut self, out: &mut [Frame], dt: f64, _info: &Info) {
+        let samples = self.0.render(out.len(), (1.0 / dt) as f32);
+        for (frame, &sample) in out.iter_mut().zip(samples) {
+            *frame = Frame::from_mono(sample);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.0.finished()
+    }
+}
+
+// ── FilteredTrack ───────────────────────────────────────────────────────
+
+/// A mixer sub-track carrying one sound through a low-pass filter. Dropping
+/// it removes the track.
+pub(crate) struct FilteredTrack {
+    track: TrackHandle,
+    filter: FilterHandle,
+}
+
+impl FilteredTrack {
+    /// Set the low-pass cutoff (Hz) and the track volume (amplitude scale),
+    /// gliding over `duration`.
+    pub fn set(&mut self, cutoff_hz: f64, volume: f64, duration: Duration) {
+        let tween = Tween {
+            duration,
+            ..Default::default()
+        };
+        self.filter.set_cutoff(cutoff_hz, tween);
+        self.track.set_volume(amplitude_to_db(volume), tween);
+    }
+}
+
 // ── AudioSource component ───────────────────────────────────────────────
 
 /// An entity-attached audio source component.
@@ -247,8 +450,15 @@ pub struct AudioSource {
     pub looping: bool,
     /// Volume for this source (amplitude scale, 1.0 = full).
     pub volume: f32,
+    /// Whether level geometry can muffle this source (see
+    /// [`occludable`](Self::occludable)).
+    pub occludable: bool,
     /// Internal handle to the playing sound (managed by `audio_system`).
     pub(crate) handle: Option<SoundHandle>,
+    /// Filter track for occludable sources (managed by `audio_system`).
+    pub(crate) track: Option<FilteredTrack>,
+    /// Current occlusion, 0.0 (clear line) to 1.0 (fully blocked).
+    pub(crate) occlusion: f32,
 }
 
 impl AudioSource {
@@ -259,7 +469,10 @@ impl AudioSource {
             auto_play: false,
             looping: false,
             volume: 1.0,
+            occludable: false,
             handle: None,
+            track: None,
+            occlusion: 0.0,
         }
     }
 
@@ -280,6 +493,20 @@ impl AudioSource {
         self.volume = volume;
         self
     }
+
+    /// Let walls and other colliders between this source and the
+    /// listener muffle it (builder pattern). Takes effect from the next
+    /// playback; requires the `physics3d` feature to have any effect.
+    pub fn occludable(mut self) -> Self {
+        self.occludable = true;
+        self
+    }
+
+    /// How blocked this source currently is: 0.0 for a clear line to the
+    /// listener, 1.0 when fully occluded.
+    pub fn occlusion(&self) -> f32 {
+        self.occlusion
+    }
 }
 
 impl fmt::Debug for AudioSource {
@@ -288,6 +515,8 @@ impl fmt::Debug for AudioSource {
             .field("auto_play", &self.auto_play)
             .field("looping", &self.looping)
             .field("volume", &self.volume)
+            .field("occludable", &self.occludable)
+            .field("occlusion", &self.occlusion)
             .field("playing", &self.handle.is_some())
             .finish()
     }
@@ -295,7 +524,9 @@ impl fmt::Debug for AudioSource {
 
 // ── Plugin ──────────────────────────────────────────────────────────────
 
-/// Plugin that registers the audio engine resource and update system.
+/// Plugin that registers the audio engine and
+/// [`MusicController`](crate::music::MusicController) resources and the
+/// playback, music and spatial audio update systems.
 ///
 /// # Example
 ///
@@ -310,7 +541,10 @@ pub struct Audio;
 impl crate::game::Plugin for Audio {
     fn build(&self, game: &mut crate::game::Game) {
         game.insert_resource(AudioEngine::new());
+        game.insert_resource(crate::music::MusicController::new());
         game.add_update_system(|ctx| audio_system(&mut ctx.world));
+        game.add_update_system(|ctx| crate::music::music_system(&mut ctx.world));
+        game.add_update_system(|ctx| crate::audio_spatial::spatial_audio_system(&mut ctx.world));
     }
 }
 
@@ -325,23 +559,39 @@ pub(crate) fn audio_system(world: &mut World) {
     };
 
     // Collect entities that need to start playing.
-    let mut to_play: Vec<(crate::ecs::Entity, SoundData, bool, f32)> = Vec::new();
+    let mut to_play: Vec<(crate::ecs::Entity, SoundData, bool, f32, bool)> = Vec::new();
     world.query::<(&AudioSource,)>(|entity, (src,)| {
         if src.auto_play && src.handle.is_none() {
-            to_play.push((entity, src.sound.clone(), src.looping, src.volume));
+            to_play.push((entity, src.sound.clone(), src.looping, src.volume, src.occludable));
         }
     });
 
-    // Start playback for new auto-play sources.
-    for (entity, sound, looping, volume) in to_play {
+    // Start playback for new auto-play sources. Occludable ones get their
+    // own filter track; if that fails they still play, just unfiltered.
+    for (entity, sound, looping, volume, occludable) in to_play {
         let mut data = sound;
         if looping {
             data = data.looping();
         }
-        data = data.volume(volume as f64);
-        let handle = engine.play(&data);
+        // Spatial sources start at the gain and pan of where they are.
+        data = match crate::audio_spatial::initial_mix(world, entity) {
+            Some((gain, pan)) => data.volume((volume * gain) as f64).panning(pan as f64),
+            None => data.volume(volume as f64),
+        };
+        let (handle, track) = if occludable {
+            match engine.try_play_filtered(&data) {
+                Ok((handle, track)) => (handle, Some(track)),
+                Err(e) => {
+                    log::warn!("Occludable sound plays unfiltered: {e}");
+                    (engine.play(&data), None)
+                }
+            }
+        } else {
+            (engine.play(&data), None)
+        };
         if let Some(src) = world.get_mut::<AudioSource>(entity) {
             src.handle = Some(handle);
+            src.track = track;
         }
     }
 
@@ -357,6 +607,8 @@ pub(crate) fn audio_system(world: &mut World) {
     for entity in stopped {
         if let Some(src) = world.get_mut::<AudioSource>(entity) {
             src.handle = None;
+            src.track = None;
+            src.occlusion = 0.0;
         }
     }
 
@@ -0,0 +1,481 @@
+//! # Audio Graph — Procedural Sound in the Mixer
+//!
+//! A [`SoundData`](crate::audio::SoundData) can only play back a file. For
+//! sound that is *generated* while the game runs — an engine whose pitch
+//! follows its RPM, a synth, wind that rises with speed — build a
+//! [`DspGraph`] instead: a small network of nodes that the audio thread
+//! runs every block of samples.
+//!
+//! ```text
+//!   oscillator(Saw, rpm) ──╮
+//!                          ├──► low_pass(cutoff) ──► gain(throttle) ──► out
+//!   oscillator(Noise, 1) ──╯        ▲
+//!                                   │ DspParam, set from gameplay code
+//! ```
+//!
+//! Nodes are added in order, and a node only takes input from nodes added
+//! before it, so the graph can never contain a cycle and runs front to back
+//! in one pass. A node's inputs are summed; the last node added (or the
+//! one passed to [`DspGraph::output`]) is what you hear.
+//!
+//! | Node | Inputs | Does |
+//! |------|--------|------|
+//! | [`oscillator`](DspGraph::oscillator) | none | sine, square, saw, triangle or noise |
+//! | [`gain`](DspGraph::gain) | any | multiply by a volume |
+//! | [`low_pass`](DspGraph::low_pass) / [`high_pass`](DspGraph::high_pass) | any | one-pole filter |
+//! | [`mix`](DspGraph::mix) | any | sum only |
+//! | [`node`](DspGraph::node) | any | your own [`DspNode`] or closure |
+//!
+//! The graph is mono and plays on both speakers. Node settings are
+//! [`DspParam`]s: clone one before handing it to the graph and set it from
+//! any thread — the audio thread reads the new value at the next block,
+//! with no locks and no allocation.
+//!
+//! ```ignore
+//! let rpm = DspParam::new(60.0);
+//! let mut graph = DspGraph::new();
+//! let hum = graph.oscillator(Waveform::Saw, rpm.clone());
+//! let rumble = graph.oscillator(Waveform::Noise, 0.0);
+//! let quiet = graph.gain(&[rumble], 0.2);
+//! let muffled = graph.low_pass(&[hum, quiet], 900.0);
+//! // Soft clipping, written as a closure over sample buffers.
+//! graph.node(&[muffled], |input: &[f32], output: &mut [f32], _sample_rate: f32| {
+//!     for (out, x) in output.iter_mut().zip(input) {
+//!         *out = x.tanh();
+//!     }
+//! });
+//!
+//! let engine_sound = engine.play_graph(graph)?;
+//! rpm.set(180.0); // later, from gameplay
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `OnAudioFilterRead` callbacks on a component, or a
+//!   `DSPGraph` package (experimental) for node networks.
+//! - **Bevy**: `Decodable` sources you implement yourself; node graphs via
+//!   third-party crates such as `bevy_fundsp`.
+//! - **Godot**: `AudioStreamGenerator` that a script fills with frames, plus
+//!   effects on audio buses.
+//! - **Our approach**: A Web Audio–style node list with a handful of
+//!   built-in nodes and closures for the rest, played as one sound in the
+//!   mixer with atomics for live parameters.
+
+use std::f32::consts::TAU;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+/// Samples processed per pass through the graph. Longer requests from the
+/// mixer are split, so node buffers never grow on the audio thread.
+const BLOCK_SIZE: usize = 256;
+
+// ── Parameters ──────────────────────────────────────────────────────────
+
+/// A node setting that can change while the graph plays. Clones share the
+/// value; `f32` converts into a fixed one.
+#[derive(Clone)]
+pub struct DspParam(Arc<AtomicU32>);
+
+impl DspParam {
+    pub fn new(value: f32) -> Self {
+        Self(Arc::new(AtomicU32::new(value.to_bits())))
+    }
+
+    /// Change the value; the audio thread picks it up at its next block.
+    pub fn set(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+}
+
+impl From<f32> for DspParam {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl fmt::Debug for DspParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("DspParam").field(&self.get()).finish()
+    }
+}
+
+// ── Nodes ───────────────────────────────────────────────────────────────
+
+/// A user node: turns the sum of its inputs into one output buffer of the
+/// same length. Runs on the audio thread, so it must not block.
+///
+/// Closures `FnMut(&[f32], &mut [f32], f32)` are nodes too; the last
+/// argument is the sample rate in Hz.
+pub trait DspNode: Send + 'static {
+    fn process(&mut self, input: &[f32], output: &mut [f32], sample_rate: f32);
+}
+
+impl<F: FnMut(&[f32], &mut [f32], f32) + Send + 'static> DspNode for F {
+    fn process(&mut self, input: &[f32], output: &mut [f32], sample_rate: f32) {
+        self(input, output, sample_rate)
+    }
+}
+
+/// Shape of an [`oscillator`](DspGraph::oscillator).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    /// Rises from -1 to 1, then drops back.
+    Saw,
+    Triangle,
+    /// White noise; the frequency is ignored.
+    Noise,
+}
+
+/// A node in a [`DspGraph`], returned when it is added.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DspNodeId(usize);
+
+enum NodeKind {
+    Oscillator {
+        waveform: Waveform,
+        frequency: DspParam,
+        /// Position in the cycle, 0..1.
+        phase: f32,
+        /// Xorshift state for noise.
+        seed: u32,
+    },
+    Gain(DspParam),
+    Filter {
+        high_pass: bool,
+        cutoff: DspParam,
+        /// Low-passed signal from the previous sample.
+        low: f32,
+    },
+    Mix,
+    Custom(Box<dyn DspNode>),
+}
+
+impl NodeKind {
+    fn process(&mut self, input: &[f32], output: &mut [f32], sample_rate: f32) {
+        match self {
+            NodeKind::Oscillator { waveform, frequency, phase, seed } => {
+                let step = frequency.get() / sample_rate;
+                for out in output.iter_mut() {
+                    *out = match waveform {
+                        Waveform::Sine => (*phase * TAU).sin(),
+                        Waveform::Square => if *phase < 0.5 { 1.0 } else { -1.0 },
+                        Waveform::Saw => 2.0 * *phase - 1.0,
+                        Waveform::Triangle => 4.0 * (*phase - 0.5).abs() - 1.0,
+                        Waveform::Noise => {
+                            *seed ^= *seed << 13;
+                            *seed ^= *seed >> 17;
+                            *seed ^= *seed << 5;
+                            *seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+                        }
+                    };
+                    *phase = (*phase + step).rem_euclid(1.0);
+                }
+            }
+            NodeKind::Gain(gain) => {
+                let gain = gain.get();
+                for (out, x) in output.iter_mut().zip(input) {
+                    *out = x * gain;
+                }
+            }
+            NodeKind::Filter { high_pass, cutoff, low } => {
+                // One-pole low-pass: move a fraction of the way toward the
+                // input each sample. The high-pass is what it removes.
+                let alpha = 1.0 - (-TAU * cutoff.get().max(0.0) / sample_rate).exp();
+                for (out, x) in output.iter_mut().zip(input) {
+                    *low += alpha * (x - *low);
+                    *out = if *high_pass { x - *low } else { *low };
+                }
+            }
+            NodeKind::Mix => output.copy_from_slice(input),
+            NodeKind::Custom(node) => node.process(input, output, sample_rate),
+        }
+    }
+}
+
+struct Node {
+    kind: NodeKind,
+    inputs: Vec<DspNodeId>,
+}
+
+// ── Graph ───────────────────────────────────────────────────────────────
+
+/// A network of sound-generating and processing nodes, played with
+/// [`AudioEngine::play_graph`](crate::audio::AudioEngine::play_graph). See
+/// the [module docs](self).
+#[derive(Default)]
+pub struct DspGraph {
+    nodes: Vec<Node>,
+    output: Option<DspNodeId>,
+    /// One block per node, allocated as nodes are added.
+    buffers: Vec<Vec<f32>>,
+    /// Summed inputs of the node being processed.
+    scratch: Vec<f32>,
+}
+
+impl DspGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, kind: NodeKind, inputs: &[DspNodeId]) -> DspNodeId {
+        let id = DspNodeId(self.nodes.len());
+        assert!(
+            inputs.iter().all(|input| input.0 < id.0),
+            "DspGraph inputs must be nodes of the same graph"
+        );
+        self.nodes.push(Node {
+            kind,
+            inputs: inputs.to_vec(),
+        });
+        self.buffers.push(vec![0.0; BLOCK_SIZE]);
+        self.scratch.resize(BLOCK_SIZE, 0.0);
+        id
+    }
+
+    /// A tone generator at `frequency` Hz.
+    pub fn oscillator(&mut self, waveform: Waveform, frequency: impl Into<DspParam>) -> DspNodeId {
+        // Distinct noise per oscillator; xorshift needs a nonzero seed.
+        let seed = 0x9E37_79B9u32.wrapping_mul(self.nodes.len() as u32 + 1) | 1;
+        let kind = NodeKind::Oscillator {
+            waveform,
+            frequency: frequency.into(),
+            phase: 0.0,
+            seed,
+        };
+        self.add(kind, &[])
+    }
+
+    /// The inputs scaled by `gain` (1.0 = unchanged).
+    pub fn gain(&mut self, inputs: &[DspNodeId], gain: impl Into<DspParam>) -> DspNodeId {
+        self.add(NodeKind::Gain(gain.into()), inputs)
+    }
+
+    /// The inputs with frequencies above `cutoff_hz` softened.
+    pub fn low_pass(&mut self, inputs: &[DspNodeId], cutoff_hz: impl Into<DspParam>) -> DspNodeId {
+        self.filter(inputs, false, cutoff_hz.into())
+    }
+
+    /// The inputs with frequencies below `cutoff_hz` softened.
+    pub fn high_pass(&mut self, inputs: &[DspNodeId], cutoff_hz: impl Into<DspParam>) -> DspNodeId {
+        self.filter(inputs, true, cutoff_hz.into())
+    }
+
+    fn filter(&mut self, inputs: &[DspNodeId], high_pass: bool, cutoff: DspParam) -> DspNodeId {
+        let kind = NodeKind::Filter {
+            high_pass,
+            cutoff,
+            low: 0.0,
+        };
+        self.add(kind, inputs)
+    }
+
+    /// The sum of the inputs.
+    pub fn mix(&mut self, inputs: &[DspNodeId]) -> DspNodeId {
+        self.add(NodeKind::Mix, inputs)
+    }
+
+    /// A user node (or closure) fed the sum of the inputs.
+    pub fn node(&mut self, inputs: &[DspNodeId], node: impl DspNode) -> DspNodeId {
+        self.add(NodeKind::Custom(Box::new(node)), inputs)
+    }
+
+    /// Play `node` instead of the last node added (builder pattern).
+    pub fn output(mut self, node: DspNodeId) -> Self {
+        assert!(node.0 < self.nodes.len(), "DspGraph output must be a node of the graph");
+        self.output = Some(node);
+        self
+    }
+
+    /// Fill `out` with the next samples. Silent for an empty graph.
+    pub fn process(&mut self, out: &mut [f32], sample_rate: f32) {
+        let Some(output) = self.output.or(self.nodes.len().checked_sub(1).map(DspNodeId)) else {
+            out.fill(0.0);
+            return;
+        };
+        for block in out.chunks_mut(BLOCK_SIZE) {
+            let len = block.len();
+            for index in 0..self.nodes.len() {
+                let (done, rest) = self.buffers.split_at_mut(index);
+                let scratch = &mut self.scratch[..len];
+                scratch.fill(0.0);
+                for input in &self.nodes[index].inputs {
+                    for (sum, x) in scratch.iter_mut().zip(&done[input.0][..len]) {
+                        *sum += x;
+                    }
+                }
+                self.nodes[index].kind.process(scratch, &mut rest[0][..len], sample_rate);
+            }
+            block.copy_from_slice(&self.buffers[output.0][..len]);
+        }
+    }
+}
+
+impl fmt::Debug for DspGraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DspGraph")
+            .field("nodes", &self.nodes.len())
+            .field("output", &self.output)
+            .finish()
+    }
+}
+
+// ── Playback ────────────────────────────────────────────────────────────
+
+/// State shared between a playing graph and its [`DspGraphHandle`].
+struct GraphControl {
+    volume: DspParam,
+    stopped: AtomicBool,
+}
+
+/// Handle to a playing [`DspGraph`]. Dropping it leaves the graph playing.
+pub struct DspGraphHandle {
+    control: Arc<GraphControl>,
+}
+
+impl DspGraphHandle {
+    /// Set the volume of the whole graph (amplitude scale, 1.0 = full).
+    pub fn set_volume(&mut self, volume: f32) {
+        self.control.volume.set(volume);
+    }
+
+    /// Stop playback; the graph is dropped on the audio thread.
+    pub fn stop(&mut self) {
+        self.control.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once [`stop`](Self::stop) was called.
+    pub fn is_stopped(&self) -> bool {
+        self.control.stopped.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Debug for DspGraphHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DspGraphHandle")
+            .field("stopped", &self.is_stopped())
+            .finish()
+    }
+}
+
+/// The audio-thread side of a playing graph: renders blocks at the handle's
+/// volume until it is stopped.
+pub(crate) struct GraphPlayer {
+    graph: DspGraph,
+    control: Arc<GraphControl>,
+    buffer: Vec<f32>,
+}
+
+impl GraphPlayer {
+    pub fn new(graph: DspGraph) -> (Self, DspGraphHandle) {
+        let control = Arc::new(GraphControl {
+            volume: DspParam::new(1.0),
+            stopped: AtomicBool::new(false),
+        });
+        let player = Self {
+            graph,
+            control: control.clone(),
+            buffer: vec![0.0; BLOCK_SIZE],
+        };
+        (player, DspGraphHandle { control })
+    }
+
+    /// The next `len` samples.
+    pub fn render(&mut self, len: usize, sample_rate: f32) -> &[f32] {
+        if self.buffer.len() < len {
+            self.buffer.resize(len, 0.0);
+        }
+        let out = &mut self.buffer[..len];
+        self.graph.process(out, sample_rate);
+        let volume = self.control.volume.get();
+        for sample in out.iter_mut() {
+            *sample *= volume;
+        }
+        out
+    }
+
+    pub fn finished(&self) -> bool {
+        self.control.stopped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f32 = 48_000.0;
+
+    #[test]
+    fn nodes_sum_inputs_and_params_update_live() {
+        let level = DspParam::new(0.5);
+        let mut graph = DspGraph::new();
+        let a = graph.node(&[], |_: &[f32], out: &mut [f32], _: f32| out.fill(1.0));
+        let b = graph.node(&[], |_: &[f32], out: &mut [f32], _: f32| out.fill(2.0));
+        graph.gain(&[a, b], level.clone());
+
+        // Longer than a block, so the request is split.
+        let mut out = vec![0.0; BLOCK_SIZE + 10];
+        graph.process(&mut out, RATE);
+        assert!(out.iter().all(|&x| x == 1.5));
+        level.set(2.0);
+        graph.process(&mut out, RATE);
+        assert!(out.iter().all(|&x| x == 6.0));
+
+        // An earlier node can be chosen as the output.
+        let mut graph = graph.output(b);
+        graph.process(&mut out, RATE);
+        assert!(out.iter().all(|&x| x == 2.0));
+    }
+
+    #[test]
+    fn oscillators_and_filters_shape_the_signal() {
+        let mut graph = DspGraph::new();
+        graph.oscillator(Waveform::Square, 750.0);
+        let mut out = vec![0.0; 64];
+        graph.process(&mut out, RATE);
+        // 64 samples per cycle: high for the first half, then low.
+        assert!(out[..32].iter().all(|&x| x == 1.0));
+        assert!(out[32..].iter().all(|&x| x == -1.0));
+
+        // A constant signal passes a low-pass and is removed by a high-pass.
+        let mut graph = DspGraph::new();
+        let dc = graph.node(&[], |_: &[f32], out: &mut [f32], _: f32| out.fill(1.0));
+        let low = graph.low_pass(&[dc], 500.0);
+        let high = graph.high_pass(&[dc], 500.0);
+        let mut graph = graph.output(low);
+        let mut out = vec![0.0; 4800];
+        graph.process(&mut out, RATE);
+        assert!(out[0] < 0.1 && (out[4799] - 1.0).abs() < 1e-3);
+        let mut graph = graph.output(high);
+        graph.process(&mut out, RATE);
+        assert!(out[4799].abs() < 1e-3);
+
+        let mut graph = DspGraph::new();
+        graph.oscillator(Waveform::Noise, 0.0);
+        graph.process(&mut out, RATE);
+        assert!(out.iter().all(|x| (-1.0..=1.0).contains(x)));
+        assert!(out.windows(2).any(|w| w[0] != w[1]));
+    }
+
+    #[test]
+    fn player_applies_volume_until_stopped() {
+        let mut graph = DspGraph::new();
+        graph.node(&[], |_: &[f32], out: &mut [f32], _: f32| out.fill(1.0));
+        let (mut player, mut handle) = GraphPlayer::new(graph);
+        handle.set_volume(0.25);
+        assert!(player.render(600, RATE).iter().all(|&x| x == 0.25));
+        assert!(!player.finished());
+        handle.stop();
+        assert!(player.finished() && handle.is_stopped());
+
+        let mut out = [1.0; 8];
+        DspGraph::new().process(&mut out, RATE);
+        assert_eq!(out, [0.0; 8], "an empty graph is silent");
+    }
+}
@@ -0,0 +1,234 @@
+//! # Spatial Audio — Sounds That Come From Somewhere
+//!
+//! An [`AudioSource`] plays at the same loudness wherever its entity is. Add
+//! a [`SpatialAudioSource`] next to it and the sound is heard from the
+//! entity's position instead: quieter with distance from the
+//! [`AudioListener`], and panned toward the side it is on.
+//!
+//! ```text
+//!              listener's right (+X) ──►
+//!                 ♪ A            ● listener          ♪ B
+//!                 pan −0.7                            pan +1.0, quieter
+//!
+//!   gain                 Linear                  Inverse (default)
+//!   1.0 ┤────╮           ────╮                   ────╮
+//!       │     ╲                ╲                      ╲_
+//!   0.0 ┤      ╲___        min  max                min  ‾‾‾──___ max
+//! ```
+//!
+//! Everything is measured in the listener's local space, so it works the
+//! same for a 2D camera (X right, Y up) and a 3D one (X right, −Z forward):
+//! the pan is how far the source sits along the listener's right axis.
+//! Gain and pan are recomputed every frame from the entities'
+//! [`GlobalTransform`]s and applied to the playing sound with a short glide.
+//! Sounds with no listener in the world play unattenuated and centered.
+//!
+//! Spatial gain multiplies [`AudioSource::volume`], and stacks with
+//! [occlusion](crate::audio_occlusion) when that is enabled.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `AudioSource.spatialBlend` plus min/max distance and a
+//!   rolloff curve; one `AudioListener` per scene.
+//! - **Bevy**: `SpatialAudioSink` with a `SpatialListener` entity; stereo
+//!   panning from two virtual ears, gain by inverse square distance.
+//! - **Godot**: Separate `AudioStreamPlayer2D` / `3D` nodes with attenuation
+//!   models and an `AudioListener2D` / `3D`.
+//! - **Our approach**: Unity's model — a component that makes any source
+//!   positional, min/max distance with linear or inverse rolloff — and one
+//!   listener type for 2D and 3D.
+
+use std::time::Duration;
+
+use crate::audio::AudioSource;
+use crate::ecs::hierarchy::GlobalTransform;
+use crate::ecs::{Entity, World};
+use crate::math::{Mat4, Vec3};
+
+/// Seconds over which gain and pan glide to their new values.
+const SPATIAL_GLIDE_SECS: f32 = 0.05;
+
+// ── Components ──────────────────────────────────────────────────────────
+
+/// Marker component: the ears of the scene, usually the camera or player.
+/// The first one found is used.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioListener;
+
+/// How gain falls off between [`SpatialAudioSource::min_distance`] and
+/// [`SpatialAudioSource::max_distance`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceModel {
+    /// Straight line from full volume to silence.
+    Linear,
+    /// `min / distance`, like sound in open air, faded to silence at
+    /// `max_distance` so it doesn't cut off abruptly.
+    #[default]
+    Inverse,
+}
+
+/// Component: play this entity's [`AudioSource`] from its position. See the
+/// [module docs](self).
+///
+/// ```ignore
+/// world.spawn((
+///     Transform::from_xyz(300.0, 0.0, 0.0),
+///     AudioSource::new(engine_hum).auto_play().looping(),
+///     SpatialAudioSource::new(800.0).min_distance(50.0),
+/// ));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialAudioSource {
+    /// Distance within which the source plays at full volume.
+    pub min_distance: f32,
+    /// Distance at which the source becomes silent.
+    pub max_distance: f32,
+    pub model: DistanceModel,
+    /// How far the source pans, 0.0 (always centered) to 1.0 (hard left or
+    /// right when directly beside the listener).
+    pub pan_strength: f32,
+}
+
+impl SpatialAudioSource {
+    /// A source audible up to `max_distance` world units away.
+    pub fn new(max_distance: f32) -> Self {
+        Self {
+            min_distance: (max_distance * 0.1).max(0.0),
+            max_distance: max_distance.max(0.0),
+            model: DistanceModel::default(),
+            pan_strength: 1.0,
+        }
+    }
+
+    /// Set the full-volume radius (builder pattern).
+    pub fn min_distance(mut self, distance: f32) -> Self {
+        self.min_distance = distance.max(0.0);
+        self
+    }
+
+    /// Set the rolloff curve (builder pattern).
+    pub fn distance_model(mut self, model: DistanceModel) -> Self {
+        self.model = model;
+        self
+    }
+
+    /// Set how far the source pans, 0.0–1.0 (builder pattern).
+    pub fn pan_strength(mut self, strength: f32) -> Self {
+        self.pan_strength = strength.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Gain (0.0–1.0) at `distance` from the listener.
+    pub fn gain(&self, distance: f32) -> f32 {
+        let min = self.min_distance.min(self.max_distance);
+        if distance <= min {
+            return 1.0;
+        }
+        if distance >= self.max_distance {
+            return 0.0;
+        }
+        // Fraction of the way from min to max, for the linear model and the
+        // inverse model's fade-out.
+        let t = (distance - min) / (self.max_distance - min);
+        match self.model {
+            DistanceModel::Linear => 1.0 - t,
+            DistanceModel::Inverse => {
+                let inverse = if min > 0.0 { min / distance } else { 1.0 };
+                inverse * (1.0 - t)
+            }
+        }
+    }
+
+    /// Gain and pan (−1.0 left to 1.0 right) for a source at `position`,
+    /// heard by a listener with world matrix `listener`.
+    pub fn mix(&self, listener: &Mat4, position: Vec3) -> (f32, f32) {
+        let local = listener.inverse().transform_point3(position);
+        let distance = local.length();
+        let pan = if distance > f32::EPSILON {
+            (local.x / distance) * self.pan_strength
+        } else {
+            0.0
+        };
+        (self.gain(distance), pan.clamp(-1.0, 1.0))
+    }
+}
+
+// ── System ──────────────────────────────────────────────────────────────
+
+/// World matrix of the first [`AudioListener`].
+fn listener_matrix(world: &mut World) -> Option<Mat4> {
+    let mut listener = None;
+    world.query::<(&AudioListener, &GlobalTransform)>(|_, (_, gt)| {
+        if listener.is_none() {
+            listener = Some(gt.matrix);
+        }
+    });
+    listener
+}
+
+/// Gain and pan for a spatial source about to start playing, so it doesn't
+/// blare at full volume until the first update. `None` for sources that
+/// aren't spatial or have no listener to be heard by.
+pub(crate) fn initial_mix(world: &mut World, entity: Entity) -> Option<(f32, f32)> {
+    let listener = listener_matrix(world)?;
+    let spatial = world.get::<SpatialAudioSource>(entity)?;
+    let position = world.get::<GlobalTransform>(entity)?.matrix.col(3).truncate();
+    Some(spatial.mix(&listener, position))
+}
+
+/// Update the gain and pan of every playing spatial source from its
+/// position relative to the listener. Run by the [`Audio`](crate::audio::Audio)
+/// plugin after the audio system.
+pub(crate) fn spatial_audio_system(world: &mut World) {
+    let Some(listener) = listener_matrix(world) else {
+        return;
+    };
+    let glide = Duration::from_secs_f32(SPATIAL_GLIDE_SECS);
+    world.query::<(&mut AudioSource, &SpatialAudioSource, &GlobalTransform)>(
+        |_, (src, spatial, gt)| {
+            let volume = src.volume;
+            let Some(handle) = &mut src.handle else {
+                return;
+            };
+            let (gain, pan) = spatial.mix(&listener, gt.matrix.col(3).truncate());
+            handle.set_spatial((volume * gain) as f64, pan as f64, glide);
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gain_falls_off_between_min_and_max() {
+        let linear = SpatialAudioSource::new(100.0)
+            .min_distance(20.0)
+            .distance_model(DistanceModel::Linear);
+        assert_eq!(linear.gain(10.0), 1.0);
+        assert!((linear.gain(60.0) - 0.5).abs() < 1e-6);
+        assert_eq!(linear.gain(100.0), 0.0);
+
+        let inverse = linear.distance_model(DistanceModel::Inverse);
+        // 20/40 from the inverse law, faded by a quarter of the way to max.
+        assert!((inverse.gain(40.0) - 0.5 * 0.75).abs() < 1e-6);
+        assert!(inverse.gain(99.0) < 0.01);
+    }
+
+    #[test]
+    fn pan_follows_the_listener_right_axis() {
+        let source = SpatialAudioSource::new(100.0);
+        let listener = Mat4::from_translation(Vec3::new(10.0, 0.0, 0.0));
+        let (_, right) = source.mix(&listener, Vec3::new(30.0, 0.0, 0.0));
+        let (_, ahead) = source.mix(&listener, Vec3::new(10.0, 0.0, -20.0));
+        assert!((right - 1.0).abs() < 1e-6);
+        assert!(ahead.abs() < 1e-6);
+
+        // Turned around, the same source is on the left.
+        let turned = listener * Mat4::from_rotation_y(std::f32::consts::PI);
+        let (_, left) = source.mix(&turned, Vec3::new(30.0, 0.0, 0.0));
+        assert!((left + 1.0).abs() < 1e-5);
+        let (_, centered) = source.pan_strength(0.0).mix(&turned, Vec3::new(30.0, 0.0, 0.0));
+        assert_eq!(centered, 0.0);
+    }
+}
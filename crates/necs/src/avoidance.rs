@@ -0,0 +1,561 @@
+//! # Avoidance — Crowds That Don't Walk Through Each Other
+//!
+//! Steering every unit straight at its goal works until two of them meet in
+//! a corridor. [`Agent`] adds local avoidance: each fixed step, every agent
+//! picks the velocity closest to the one it wants
+//! ([`preferred_velocity`](Agent::preferred_velocity)) that won't collide
+//! with its neighbors within [`AvoidanceSettings::time_horizon`] seconds,
+//! then moves by it.
+//!
+//! ```text
+//!   ORCA (optimal reciprocal collision avoidance), per pair of agents:
+//!
+//!        velocities that collide              each agent takes half of the
+//!        within the time horizon               dodge, so both give way:
+//!              ╱‾‾‾‾╲                               A ──►  ╲
+//!     A ●───► (  B   )        ──►                          ╱ half-plane
+//!              ╲____╱                               B ◄──  ╲ per neighbor
+//!
+//!   new velocity = closest to preferred inside every half-plane and
+//!                  within max_speed (a small linear program)
+//! ```
+//!
+//! Games set the preferred velocity — typically toward the next waypoint at
+//! full speed — and read the result back from [`Agent::velocity`]:
+//!
+//! ```ignore
+//! game.plugin(Avoidance);
+//!
+//! ctx.spawn("villager")
+//!     .insert(Transform::from_xyz(0.0, 0.0, 0.0))
+//!     .insert(Agent::new(3.0, 0.4));
+//!
+//! fn walk(ctx: &mut Context, villager: Entity, goal: Vec2) {
+//!     let position = ctx.world.get::<Transform>(villager).unwrap().translation;
+//!     let agent = ctx.world.get_mut::<Agent>(villager).unwrap();
+//!     agent.preferred_velocity = (goal - position.truncate()).clamp_length_max(agent.max_speed);
+//! }
+//! ```
+//!
+//! Agents move their [`Transform`] in the [`AvoidancePlane`] (X/Y by
+//! default, X/Z for 3D ground) and leave the third axis alone. An agent
+//! driven by physics or a character controller should be
+//! [`steer_only`](Agent::steer_only): it still avoids and is avoided, but
+//! only reports the velocity for the game to apply. Neighbors are found by
+//! brute force, which is fine for a few hundred agents; static obstacles
+//! are not avoided. Like any reciprocal scheme, a perfectly symmetric
+//! crowd (agents evenly spaced on a circle, all heading for the opposite
+//! side) can stall in the middle; tiny differences in their goals or
+//! speeds are enough to break the tie.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `NavMeshAgent` obstacle avoidance (RVO-like), tied to the
+//!   navmesh and its path following.
+//! - **Godot**: `NavigationAgent2D/3D` with `avoidance_enabled`, RVO2 under
+//!   the hood; the safe velocity arrives through a signal.
+//! - **Our approach**: The RVO2 ORCA solver as a standalone component —
+//!   no navmesh needed, the game supplies the preferred velocity.
+
+use crate::ecs::{Entity, World};
+use crate::math::{Transform, Vec2, Vec3};
+
+/// Solver tolerance for parallel half-plane boundaries.
+const EPSILON: f32 = 1e-5;
+
+// ── Components ──────────────────────────────────────────────────────────
+
+/// Component: a disc that steers around other agents.
+#[derive(Debug, Clone, Copy)]
+pub struct Agent {
+    /// Fastest the agent ever moves, in units per second.
+    pub max_speed: f32,
+    /// Radius of the agent's disc.
+    pub radius: f32,
+    /// Velocity the agent would take with nobody around, in plane
+    /// coordinates. Set by the game; longer than `max_speed` is capped.
+    pub preferred_velocity: Vec2,
+    /// Move the `Transform` by the chosen velocity.
+    pub moves: bool,
+    velocity: Vec2,
+}
+
+impl Agent {
+    /// An agent standing still, moving at most `max_speed`.
+    pub fn new(max_speed: f32, radius: f32) -> Self {
+        Self {
+            max_speed,
+            radius,
+            preferred_velocity: Vec2::ZERO,
+            moves: true,
+            velocity: Vec2::ZERO,
+        }
+    }
+
+    /// Start out wanting `velocity`.
+    pub fn preferred_velocity(mut self, velocity: Vec2) -> Self {
+        self.preferred_velocity = velocity;
+        self
+    }
+
+    /// Only compute the velocity; something else moves the entity.
+    pub fn steer_only(mut self) -> Self {
+        self.moves = false;
+        self
+    }
+
+    /// Velocity chosen in the last step, in plane coordinates.
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+}
+
+// ── Resource ────────────────────────────────────────────────────────────
+
+/// Which two axes of [`Transform::translation`] agents move in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AvoidancePlane {
+    /// X and Y, for 2D games.
+    #[default]
+    XY,
+    /// X and Z, for characters on 3D ground. Plane Y is world Z.
+    XZ,
+}
+
+impl AvoidancePlane {
+    fn project(self, v: Vec3) -> Vec2 {
+        match self {
+            Self::XY => Vec2::new(v.x, v.y),
+            Self::XZ => Vec2::new(v.x, v.z),
+        }
+    }
+
+    fn lift(self, v: Vec2) -> Vec3 {
+        match self {
+            Self::XY => Vec3::new(v.x, v.y, 0.0),
+            Self::XZ => Vec3::new(v.x, 0.0, v.y),
+        }
+    }
+}
+
+/// Avoidance settings resource. Inserted by the [`Avoidance`] plugin.
+#[derive(Debug, Clone)]
+pub struct AvoidanceSettings {
+    /// Axes agents move in. Default: X/Y.
+    pub plane: AvoidancePlane,
+    /// How far ahead, in seconds, agents avoid collisions. Longer is safer
+    /// but makes agents swerve earlier. Default: 2.0.
+    pub time_horizon: f32,
+    /// Agents farther apart than this, center to center, ignore each other.
+    /// Default: 10.0.
+    pub neighbor_distance: f32,
+}
+
+impl Default for AvoidanceSettings {
+    fn default() -> Self {
+        Self {
+            plane: AvoidancePlane::XY,
+            time_horizon: 2.0,
+            neighbor_distance: 10.0,
+        }
+    }
+}
+
+// ── Plugin ──────────────────────────────────────────────────────────────
+
+/// Plugin that steers [`Agent`]s around each other every fixed step.
+///
+/// # Example
+///
+/// ```ignore
+/// Game::new("My Game")
+///     .plugin(Avoidance)
+///     .setup(setup)
+///     .run();
+/// ```
+pub struct Avoidance;
+
+impl crate::game::Plugin for Avoidance {
+    fn build(&self, game: &mut crate::game::Game) {
+        game.insert_resource(AvoidanceSettings::default());
+        game.add_fixed_update_system(|ctx| avoidance_system(&mut ctx.world));
+    }
+}
+
+// ── System ──────────────────────────────────────────────────────────────
+
+/// An agent as the solver sees it: a snapshot taken before anyone moves.
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    position: Vec2,
+    velocity: Vec2,
+    radius: f32,
+}
+
+/// Pick a new velocity for every agent and move the ones that move
+/// themselves, by one tick of
+/// [`Time::fixed_delta_secs`](crate::time::Time::fixed_delta_secs).
+pub(crate) fn avoidance_system(world: &mut World) {
+    let settings = world.get_resource::<AvoidanceSettings>().cloned().unwrap_or_default();
+    let dt = world.resource::<crate::time::Time>().fixed_delta_secs();
+    if dt <= 0.0 {
+        return;
+    }
+
+    let mut agents: Vec<(Entity, Agent, Snapshot)> = Vec::new();
+    world.query::<(&Agent, &Transform)>(|entity, (agent, tf)| {
+        let snapshot = Snapshot {
+            position: settings.plane.project(tf.translation),
+            velocity: agent.velocity,
+            radius: agent.radius,
+        };
+        agents.push((entity, *agent, snapshot));
+    });
+
+    // Every agent decides from the same snapshot, so order doesn't matter.
+    let velocities: Vec<Vec2> = agents
+        .iter()
+        .enumerate()
+        .map(|(i, (_, agent, me))| {
+            let neighbors = agents.iter().enumerate().filter_map(|(j, (_, _, other))| {
+                let near = me.position.distance(other.position)
+                    < settings.neighbor_distance + other.radius;
+                (i != j && near).then_some(other)
+            });
+            let lines: Vec<Line> = neighbors
+                .map(|other| orca_line(me, other, settings.time_horizon, dt))
+                .collect();
+            let preferred = agent.preferred_velocity;
+            solve(&lines, agent.max_speed.max(0.0), preferred)
+        })
+        .collect();
+
+    for ((entity, agent, _), velocity) in agents.into_iter().zip(velocities) {
+        if let Some(a) = world.get_mut::<Agent>(entity) {
+            a.velocity = velocity;
+        }
+        if agent.moves
+            && let Some(tf) = world.get_mut::<Transform>(entity)
+        {
+            tf.translation += settings.plane.lift(velocity * dt);
+        }
+    }
+}
+
+// ── Solver ──────────────────────────────────────────────────────────────
+//
+// A straight port of the RVO2 library's ORCA solver (van den Berg et al.),
+// without static obstacles.
+
+/// Half-plane of allowed velocities: everything to the left of `direction`
+/// through `point`.
+#[derive(Debug, Clone, Copy)]
+struct Line {
+    point: Vec2,
+    direction: Vec2,
+}
+
+/// The half-plane `me` keeps to so it and `other` don't collide within
+/// `horizon` seconds, taking half the responsibility for the dodge.
+fn orca_line(me: &Snapshot, other: &Snapshot, horizon: f32, dt: f32) -> Line {
+    let relative_position = other.position - me.position;
+    let relative_velocity = me.velocity - other.velocity;
+    let dist_sq = relative_position.length_squared();
+    let combined_radius = me.radius + other.radius;
+    let combined_radius_sq = combined_radius * combined_radius;
+
+    let (direction, u) = if dist_sq > combined_radius_sq {
+        let inv_horizon = 1.0 / horizon.max(EPSILON);
+        // Vector from the cutoff circle's center to the relative velocity.
+        let w = relative_velocity - inv_horizon * relative_position;
+        let w_length_sq = w.length_squared();
+        let dot = w.dot(relative_position);
+
+        if dot < 0.0 && dot * dot > combined_radius_sq * w_length_sq {
+            // Closest to the cutoff circle: push out along w.
+            let w_length = w_length_sq.sqrt();
+            let unit_w = w / w_length;
+            let direction = Vec2::new(unit_w.y, -unit_w.x);
+            (direction, (combined_radius * inv_horizon - w_length) * unit_w)
+        } else {
+            // Closest to one of the cone's legs: project onto it.
+            let leg = (dist_sq - combined_radius_sq).sqrt();
+            let p = relative_position;
+            let direction = if p.perp_dot(w) > 0.0 {
+                Vec2::new(p.x * leg - p.y * combined_radius, p.x * combined_radius + p.y * leg)
+                    / dist_sq
+            } else {
+                -Vec2::new(p.x * leg + p.y * combined_radius, -p.x * combined_radius + p.y * leg)
+                    / dist_sq
+            };
+            (direction, relative_velocity.dot(direction) * direction - relative_velocity)
+        }
+    } else {
+        // Already overlapping: separate within this step.
+        let inv_dt = 1.0 / dt;
+        let w = relative_velocity - inv_dt * relative_position;
+        let w_length = w.length();
+        let unit_w = if w_length > EPSILON { w / w_length } else { Vec2::X };
+        let direction = Vec2::new(unit_w.y, -unit_w.x);
+        (direction, (combined_radius * inv_dt - w_length) * unit_w)
+    };
+
+    Line {
+        point: me.velocity + 0.5 * u,
+        direction,
+    }
+}
+
+/// Velocity closest to `preferred` that satisfies every line and is no
+/// faster than `max_speed`. When the lines contradict each other, the one
+/// that violates them least.
+fn solve(lines: &[Line], max_speed: f32, preferred: Vec2) -> Vec2 {
+    let mut result = Vec2::ZERO;
+    let failed = linear_program_2(lines, max_speed, preferred, false, &mut result);
+    if failed < lines.len() {
+        linear_program_3(lines, failed, max_speed, &mut result);
+    }
+    result
+}
+
+/// Optimize along line `line_no`, subject to the lines before it and the
+/// speed circle. `false` when that segment is empty.
+fn linear_program_1(
+    lines: &[Line],
+    line_no: usize,
+    radius: f32,
+    optimal: Vec2,
+    direction_opt: bool,
+    result: &mut Vec2,
+) -> bool {
+    let line = lines[line_no];
+    let dot = line.point.dot(line.direction);
+    let discriminant = dot * dot + radius * radius - line.point.length_squared();
+    if discriminant < 0.0 {
+        // The speed circle misses the line entirely.
+        return false;
+    }
+
+    let sqrt_discriminant = discriminant.sqrt();
+    let mut t_left = -dot - sqrt_discriminant;
+    let mut t_right = -dot + sqrt_discriminant;
+
+    for other in &lines[..line_no] {
+        let denominator = line.direction.perp_dot(other.direction);
+        let numerator = other.direction.perp_dot(line.point - other.point);
+        if denominator.abs() <= EPSILON {
+            // Parallel: either all of this line is allowed or none of it.
+            if numerator < 0.0 {
+                return false;
+            }
+            continue;
+        }
+        let t = numerator / denominator;
+        if denominator >= 0.0 {
+            t_right = t_right.min(t);
+        } else {
+            t_left = t_left.max(t);
+        }
+        if t_left > t_right {
+            return false;
+        }
+    }
+
+    let t = if direction_opt {
+        if optimal.dot(line.direction) > 0.0 { t_right } else { t_left }
+    } else {
+        line.direction.dot(optimal - line.point).clamp(t_left, t_right)
+    };
+    *result = line.point + t * line.direction;
+    true
+}
+
+/// Closest point to `optimal` (or furthest along it, with `direction_opt`)
+/// inside all lines and the speed circle. Returns the index of the first
+/// line that couldn't be satisfied, or `lines.len()` on success.
+fn linear_program_2(
+    lines: &[Line],
+    radius: f32,
+    optimal: Vec2,
+    direction_opt: bool,
+    result: &mut Vec2,
+) -> usize {
+    *result = if direction_opt {
+        optimal * radius
+    } else {
+        optimal.clamp_length_max(radius)
+    };
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.direction.perp_dot(line.point - *result) > 0.0 {
+            // Outside this half-plane: the optimum lies on its boundary.
+            let previous = *result;
+            if !linear_program_1(lines, i, radius, optimal, direction_opt, result) {
+                *result = previous;
+                return i;
+            }
+        }
+    }
+    lines.len()
+}
+
+/// Infeasible case: minimize the largest violation of lines `begin..`.
+fn linear_program_3(lines: &[Line], begin: usize, radius: f32, result: &mut Vec2) {
+    let mut distance = 0.0;
+    for i in begin..lines.len() {
+        let line = lines[i];
+        if line.direction.perp_dot(line.point - *result) <= distance {
+            continue;
+        }
+
+        // Lines before `i`, re-expressed relative to line `i`.
+        let mut projected = Vec::with_capacity(i);
+        for other in &lines[..i] {
+            let determinant = line.direction.perp_dot(other.direction);
+            let point = if determinant.abs() <= EPSILON {
+                if line.direction.dot(other.direction) > 0.0 {
+                    // Same direction: line `i` already covers it.
+                    continue;
+                }
+                0.5 * (line.point + other.point)
+            } else {
+                let t = other.direction.perp_dot(line.point - other.point) / determinant;
+                line.point + t * line.direction
+            };
+            projected.push(Line {
+                point,
+                direction: (other.direction - line.direction).normalize_or_zero(),
+            });
+        }
+
+        let previous = *result;
+        let away = Vec2::new(-line.direction.y, line.direction.x);
+        if linear_program_2(&projected, radius, away, true, result) < projected.len() {
+            // Only rounding errors get here; keep the last good answer.
+            *result = previous;
+        }
+        distance = line.direction.perp_dot(line.point - *result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::Time;
+
+    fn world_with(settings: AvoidanceSettings) -> World {
+        let mut world = World::new();
+        world.insert_resource(Time::new());
+        world.insert_resource(settings);
+        world
+    }
+
+    fn position(world: &World, entity: Entity) -> Vec3 {
+        world.get::<Transform>(entity).unwrap().translation
+    }
+
+    #[test]
+    fn a_lone_agent_takes_its_capped_preferred_velocity() {
+        let mut world = world_with(AvoidanceSettings {
+            plane: AvoidancePlane::XZ,
+            ..Default::default()
+        });
+        let walker = world.spawn((
+            Transform::from_xyz(0.0, 1.0, 0.0),
+            Agent::new(2.0, 0.5).preferred_velocity(Vec2::new(0.0, 10.0)),
+        ));
+        let steered = world.spawn((
+            Transform::from_xyz(50.0, 0.0, 0.0),
+            Agent::new(2.0, 0.5).preferred_velocity(Vec2::X).steer_only(),
+        ));
+
+        avoidance_system(&mut world);
+
+        let dt = world.resource::<Time>().fixed_delta_secs();
+        let velocity = world.get::<Agent>(walker).unwrap().velocity();
+        assert!((velocity - Vec2::new(0.0, 2.0)).length() < 1e-4, "{velocity}");
+        let moved = position(&world, walker);
+        assert!((moved - Vec3::new(0.0, 1.0, 2.0 * dt)).length() < 1e-4, "{moved}");
+
+        assert!((world.get::<Agent>(steered).unwrap().velocity() - Vec2::X).length() < 1e-4);
+        assert_eq!(position(&world, steered), Vec3::new(50.0, 0.0, 0.0));
+    }
+
+    /// Walk agents toward their goals for `ticks` steps, returning the
+    /// closest any two of them came, relative to the sum of their radii.
+    fn walk(world: &mut World, agents: &[(Entity, Vec2)], ticks: usize) -> f32 {
+        let mut closest = f32::INFINITY;
+        for _ in 0..ticks {
+            for &(entity, goal) in agents {
+                let here = position(world, entity).truncate();
+                let agent = world.get_mut::<Agent>(entity).unwrap();
+                agent.preferred_velocity = (goal - here).clamp_length_max(agent.max_speed);
+            }
+            avoidance_system(world);
+            for (i, &(a, _)) in agents.iter().enumerate() {
+                for &(b, _) in &agents[i + 1..] {
+                    let gap = position(world, a).distance(position(world, b));
+                    let radii = world.get::<Agent>(a).unwrap().radius
+                        + world.get::<Agent>(b).unwrap().radius;
+                    closest = closest.min(gap / radii);
+                }
+            }
+        }
+        closest
+    }
+
+    #[test]
+    fn agents_walking_head_on_pass_each_other() {
+        let mut world = world_with(AvoidanceSettings::default());
+        let left = world.spawn((Transform::from_xyz(-5.0, 0.0, 0.0), Agent::new(1.5, 0.5)));
+        let right = world.spawn((Transform::from_xyz(5.0, 0.01, 0.0), Agent::new(1.5, 0.5)));
+        let goals = [(left, Vec2::new(5.0, 0.0)), (right, Vec2::new(-5.0, 0.0))];
+
+        let closest = walk(&mut world, &goals, 900);
+
+        assert!(closest > 0.99, "agents overlapped: {closest}");
+        for (entity, goal) in goals {
+            let at = position(&world, entity).truncate();
+            assert!(at.distance(goal) < 0.1, "stuck at {at}");
+        }
+    }
+
+    #[test]
+    fn a_crowd_swapping_sides_keeps_apart() {
+        let mut world = world_with(AvoidanceSettings::default());
+        let goals: Vec<(Entity, Vec2)> = (0..8)
+            .map(|i| {
+                let angle = i as f32 * std::f32::consts::TAU / 8.0;
+                let start = 6.0 * Vec2::from_angle(angle);
+                let entity = world.spawn((
+                    Transform::from_xyz(start.x, start.y, 0.0),
+                    // Different speeds: a perfectly symmetric crowd stalls.
+                    Agent::new(1.6 + 0.1 * i as f32, 0.5),
+                ));
+                (entity, -start)
+            })
+            .collect();
+
+        let closest = walk(&mut world, &goals, 1200);
+
+        assert!(closest > 0.95, "agents overlapped: {closest}");
+        for (entity, goal) in goals {
+            let at = position(&world, entity).truncate();
+            assert!(at.distance(goal) < 0.25, "stuck at {at}");
+        }
+    }
+
+    #[test]
+    fn overlapping_agents_are_pushed_apart() {
+        let mut world = world_with(AvoidanceSettings::default());
+        let a = world.spawn((Transform::from_xyz(0.0, 0.0, 0.0), Agent::new(2.0, 0.5)));
+        let b = world.spawn((Transform::from_xyz(0.5, 0.0, 0.0), Agent::new(2.0, 0.5)));
+
+        let before = position(&world, a).distance(position(&world, b));
+        for _ in 0..30 {
+            avoidance_system(&mut world);
+        }
+        let after = position(&world, a).distance(position(&world, b));
+        assert!(after > before + 0.4, "{before} → {after}");
+    }
+}
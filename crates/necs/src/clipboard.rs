@@ -0,0 +1,136 @@
+//! # Clipboard — Copy and Paste Text
+//!
+//! The [`Clipboard`] resource reads and writes the operating system's
+//! clipboard, so text copied from a [`TextInput`](crate::ui::TextInput) can be
+//! pasted into a browser and back.
+//!
+//! ```text
+//!   Clipboard::set("seed-4417") ──► system clipboard (arboard)
+//!                                    │  unavailable? (no display server,
+//!                                    │  `clipboard` feature off)
+//!                                    ▼
+//!                                  in-process text, shared by the game only
+//! ```
+//!
+//! The system clipboard is opened on first use, not at startup. Where it
+//! can't be opened — headless CI, a Wayland session without a clipboard
+//! manager, or a build without the `clipboard` feature — copy and paste
+//! still work within the game, and a warning is logged once.
+//!
+//! ```ignore
+//! if ctx.world.get::<Button>(copy_seed).is_some_and(|b| b.clicked()) {
+//!     ctx.world.resource_mut::<Clipboard>().set(&seed.to_string());
+//! }
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `GUIUtility.systemCopyBuffer`, a plain string property.
+//! - **Bevy**: No built-in clipboard; games pull in `arboard` themselves.
+//! - **Godot**: `DisplayServer.clipboard_get()` / `clipboard_set()`.
+//! - **Our approach**: A resource over `arboard`, falling back to an
+//!   in-process clipboard instead of failing.
+
+#[cfg(feature = "clipboard")]
+use std::sync::Mutex;
+
+/// Resource: the system clipboard, or an in-process stand-in where there is
+/// none. Inserted by the framework. See the [module docs](self).
+pub struct Clipboard {
+    #[cfg(feature = "clipboard")]
+    system: Mutex<SystemClipboard>,
+    local: String,
+}
+
+#[cfg(feature = "clipboard")]
+enum SystemClipboard {
+    /// Not opened yet.
+    Closed,
+    Open(arboard::Clipboard),
+    /// Opening failed; use the local text from now on.
+    Unavailable,
+    /// Never touch the system clipboard (see [`Clipboard::local`]).
+    Disabled,
+}
+
+impl Clipboard {
+    /// The system clipboard, opened on first use.
+    pub fn new() -> Self {
+        Self {
+            #[cfg(feature = "clipboard")]
+            system: Mutex::new(SystemClipboard::Closed),
+            local: String::new(),
+        }
+    }
+
+    /// A clipboard that only exists inside the game, e.g. for tests.
+    pub fn local() -> Self {
+        Self {
+            #[cfg(feature = "clipboard")]
+            system: Mutex::new(SystemClipboard::Disabled),
+            local: String::new(),
+        }
+    }
+
+    /// The clipboard's text, or an empty string if it holds none.
+    pub fn get(&mut self) -> String {
+        #[cfg(feature = "clipboard")]
+        if let Some(result) = self.with_system(|clipboard| clipboard.get_text()) {
+            match result {
+                Ok(text) => return text,
+                // Empty, or holding an image: nothing to paste.
+                Err(arboard::Error::ContentNotAvailable) => return String::new(),
+                Err(err) => log::warn!("Clipboard: read failed: {err}"),
+            }
+        }
+        self.local.clone()
+    }
+
+    /// Replace the clipboard's contents with `text`.
+    pub fn set(&mut self, text: &str) {
+        self.local = text.to_owned();
+        #[cfg(feature = "clipboard")]
+        if let Some(Err(err)) = self.with_system(|clipboard| clipboard.set_text(text)) {
+            log::warn!("Clipboard: write failed: {err}");
+        }
+    }
+
+    /// Run `f` on the system clipboard, opening it if needed. `None` if
+    /// there is no system clipboard.
+    #[cfg(feature = "clipboard")]
+    fn with_system<R>(&mut self, f: impl FnOnce(&mut arboard::Clipboard) -> R) -> Option<R> {
+        let system = self.system.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if matches!(system, SystemClipboard::Closed) {
+            *system = match arboard::Clipboard::new() {
+                Ok(clipboard) => SystemClipboard::Open(clipboard),
+                Err(err) => {
+                    log::warn!("Clipboard: no system clipboard ({err}); copy and paste stay in the game");
+                    SystemClipboard::Unavailable
+                }
+            };
+        }
+        match system {
+            SystemClipboard::Open(clipboard) => Some(f(clipboard)),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_clipboard_round_trips() {
+        let mut clipboard = Clipboard::local();
+        assert_eq!(clipboard.get(), "");
+        clipboard.set("seed-4417");
+        assert_eq!(clipboard.get(), "seed-4417");
+    }
+}
@@ -0,0 +1,254 @@
+//! # Transform Constraints — Look-At, Copy-Position, Lock-Axis
+//!
+//! Some motion is a rule rather than a script: a turret keeps its barrel on
+//! the player, a nameplate sits half a meter above a head, a tank turret
+//! turns but never tilts. Constraints are components stating that rule; the
+//! engine applies them every frame right after transform propagation, so no
+//! per-game system is needed:
+//!
+//! ```text
+//!   systems ──► propagate_transforms ──► apply_constraints ──► render
+//!                (GlobalTransform from      per constrained entity:
+//!                 the hierarchy)              1. CopyPosition
+//!                                             2. LookAt
+//!                                             3. LockAxis
+//!                                           then its children again
+//! ```
+//!
+//! Constraints work in world space and only write [`GlobalTransform`]; the
+//! entity's own [`Transform`](crate::math::Transform) is left alone, so
+//! removing the constraint puts it back where its hierarchy says. Children
+//! of a constrained entity follow it — a barrel parented to a turret head
+//! aims with it.
+//!
+//! A constraint reads its target's `GlobalTransform` as of the moment it
+//! runs. Entities are processed in spawn order, so a chain (a camera rig
+//! copying a head that looks at something) resolves in the same frame when
+//! the target was spawned first, and a frame late otherwise. A dead target
+//! leaves the entity unconstrained.
+//!
+//! For geometry that should face the camera, use
+//! [`Billboard`](crate::render3d::Billboard) (render3d), which orients at
+//! draw time and can keep a fixed screen size; `LookAt` on the camera entity
+//! works too when children need to follow.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `LookAtConstraint`, `PositionConstraint` and
+//!   `RotationConstraint` components with weights, several sources each,
+//!   and axis freezing.
+//! - **Bevy**: No constraint components; `Transform::look_at` in a system
+//!   that runs after propagation.
+//! - **Godot**: `RemoteTransform3D` pushes a node's transform onto another;
+//!   look-at is `Node3D.look_at()` in a script.
+//! - **Our approach**: Unity's components, one source each and no weights,
+//!   applied to world transforms after propagation.
+
+use crate::ecs::hierarchy::{GlobalTransform, propagate_subtree};
+use crate::ecs::{Entity, World};
+use crate::math::{Mat4, Quat, Vec3};
+
+/// Component: rotate so the entity's forward (-Z) points at `target`.
+///
+/// ```ignore
+/// ctx.spawn("turret_head")
+///     .insert(Transform::from_xyz(0.0, 1.0, 0.0))
+///     .insert(LookAt::new(player))
+///     .insert(LockAxis::yaw_only());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LookAt {
+    /// Entity to aim at.
+    pub target: Entity,
+    /// World-space up direction that keeps the entity from rolling.
+    pub up: Vec3,
+}
+
+impl LookAt {
+    /// Aim at `target`, keeping +Y up.
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            up: Vec3::Y,
+        }
+    }
+
+    /// Use a different up direction.
+    pub fn up(mut self, up: Vec3) -> Self {
+        self.up = up;
+        self
+    }
+}
+
+/// Component: move to `source`'s world position plus `offset`. Rotation and
+/// scale are the entity's own.
+#[derive(Debug, Clone, Copy)]
+pub struct CopyPosition {
+    /// Entity to follow.
+    pub source: Entity,
+    /// World-space offset from the source.
+    pub offset: Vec3,
+}
+
+impl CopyPosition {
+    /// Sit exactly on `source`.
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source,
+            offset: Vec3::ZERO,
+        }
+    }
+
+    /// Keep this world-space offset from the source.
+    pub fn offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+}
+
+/// Component: remove rotation about the locked world axes. Applied after
+/// [`LookAt`], so a turret can track a target while only turning about Y.
+///
+/// Rotation is split into yaw (Y), pitch (X) and roll (Z), in that order;
+/// locked parts are zeroed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LockAxis {
+    /// Lock pitch.
+    pub x: bool,
+    /// Lock yaw.
+    pub y: bool,
+    /// Lock roll.
+    pub z: bool,
+}
+
+impl LockAxis {
+    /// Turn about Y only — turrets, characters facing a target.
+    pub fn yaw_only() -> Self {
+        Self {
+            x: true,
+            y: false,
+            z: true,
+        }
+    }
+}
+
+/// Apply every constraint, updating the constrained entities' and their
+/// descendants' [`GlobalTransform`]s. Runs after
+/// [`propagate_transforms`](crate::ecs::propagate_transforms).
+pub(crate) fn apply_constraints(world: &mut World) {
+    let mut constrained = world.entities_with::<LookAt>();
+    constrained.extend(world.entities_with::<CopyPosition>());
+    constrained.extend(world.entities_with::<LockAxis>());
+    if constrained.is_empty() {
+        return;
+    }
+    constrained.sort_by_key(|e| e.index());
+    constrained.dedup();
+
+    for entity in constrained {
+        let Some(global) = world.get::<GlobalTransform>(entity) else {
+            continue;
+        };
+        let (scale, mut rotation, mut translation) = global.matrix.to_scale_rotation_translation();
+
+        if let Some(copy) = world.get::<CopyPosition>(entity).copied()
+            && let Some(source) = world_position(world, copy.source)
+        {
+            translation = source + copy.offset;
+        }
+        if let Some(look) = world.get::<LookAt>(entity).copied()
+            && let Some(target) = world_position(world, look.target)
+            && let Some(aim) = look_rotation(target - translation, look.up)
+        {
+            rotation = aim;
+        }
+        if let Some(lock) = world.get::<LockAxis>(entity).copied() {
+            rotation = lock_rotation(rotation, lock);
+        }
+
+        let matrix = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+        propagate_subtree(world, entity, matrix);
+    }
+}
+
+/// World-space position of a live entity that has been propagated.
+fn world_position(world: &World, entity: Entity) -> Option<Vec3> {
+    if !world.is_alive(entity) {
+        return None;
+    }
+    Some(world.get::<GlobalTransform>(entity)?.matrix.w_axis.truncate())
+}
+
+/// Rotation pointing -Z along `direction` with +Y toward `up`; `None` when
+/// `direction` is zero. Falls back to the shortest arc when `direction` is
+/// parallel to `up`.
+fn look_rotation(direction: Vec3, up: Vec3) -> Option<Quat> {
+    let forward = direction.try_normalize()?;
+    if forward.cross(up).length_squared() < 1e-8 {
+        return Some(Quat::from_rotation_arc(Vec3::NEG_Z, forward));
+    }
+    let view = Mat4::look_to_rh(Vec3::ZERO, forward, up);
+    Some(view.inverse().to_scale_rotation_translation().1)
+}
+
+fn lock_rotation(rotation: Quat, lock: LockAxis) -> Quat {
+    let (yaw, pitch, roll) = rotation.to_euler(glam::EulerRot::YXZ);
+    let keep = |angle: f32, locked: bool| if locked { 0.0 } else { angle };
+    Quat::from_euler(
+        glam::EulerRot::YXZ,
+        keep(yaw, lock.y),
+        keep(pitch, lock.x),
+        keep(roll, lock.z),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::hierarchy::propagate_transforms;
+    use crate::math::Transform;
+
+    fn global(world: &World, entity: Entity) -> Mat4 {
+        world.get::<GlobalTransform>(entity).unwrap().matrix
+    }
+
+    #[test]
+    fn look_at_aims_forward_and_lock_keeps_it_level() {
+        let mut world = World::new();
+        let target = world.spawn((Transform::from_xyz(10.0, 10.0, 0.0),));
+        let head = world.spawn((Transform::default(), LookAt::new(target)));
+        let turret = world.spawn((Transform::default(), LookAt::new(target), LockAxis::yaw_only()));
+        propagate_transforms(&mut world);
+        apply_constraints(&mut world);
+
+        let forward = global(&world, head).transform_vector3(Vec3::NEG_Z);
+        assert!(forward.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0).normalize(), 1e-5));
+        // The turret turns toward +X but doesn't tilt up.
+        let forward = global(&world, turret).transform_vector3(Vec3::NEG_Z);
+        assert!(forward.abs_diff_eq(Vec3::X, 1e-5));
+        // Local transforms are untouched.
+        assert_eq!(world.get::<Transform>(head).unwrap().rotation, Quat::IDENTITY);
+    }
+
+    #[test]
+    fn copy_position_moves_children_along() {
+        let mut world = World::new();
+        let source = world.spawn((Transform::from_xyz(3.0, 0.0, 0.0),));
+        let follower = world.spawn((
+            Transform::from_xyz(-50.0, 0.0, 0.0),
+            CopyPosition::new(source).offset(Vec3::Y),
+        ));
+        let child = world.spawn_child(follower, (Transform::from_xyz(0.0, 0.0, 1.0),));
+        propagate_transforms(&mut world);
+        apply_constraints(&mut world);
+
+        assert_eq!(global(&world, follower).w_axis.truncate(), Vec3::new(3.0, 1.0, 0.0));
+        assert_eq!(global(&world, child).w_axis.truncate(), Vec3::new(3.0, 1.0, 1.0));
+
+        // A dead source leaves the hierarchy's answer in place.
+        world.despawn(source);
+        propagate_transforms(&mut world);
+        apply_constraints(&mut world);
+        assert_eq!(global(&world, follower).w_axis.truncate(), Vec3::new(-50.0, 0.0, 0.0));
+    }
+}
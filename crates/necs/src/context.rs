@@ -4,19 +4,39 @@
 //! frame timing into a single struct. Startup and update systems receive
 //! `&mut Context`, giving them access to everything they need.
 
+use crate::action::ActionMap;
+use crate::ecs::system::ScheduleContext;
 use crate::ecs::world::World;
 use crate::ecs::Entity;
-use crate::input::{CursorPosition, Input, KeyCode, MouseButton};
+use crate::input::{
+    CursorPosition, GamepadAxis, GamepadButton, GamepadStyle, Input, InputDevice, InputEvent, KeyCode,
+    MouseButton, MouseScroll, TextEvent, TimedInput, TimedText,
+};
+use crate::local::SystemLocal;
+use crate::math::Vec2;
 use crate::time::Time;
+use crate::touch::Touches;
 
 // ── InputState ──────────────────────────────────────────────────────────
 
-/// Wraps keyboard and mouse input with convenience methods.
+/// Wraps keyboard, mouse, gamepad and touch input with convenience methods.
 ///
 /// Access via [`Context::input`].
 pub struct InputState {
     pub(crate) keys: Input<KeyCode>,
     pub(crate) mouse: Input<MouseButton>,
+    pub(crate) gamepad: Input<GamepadButton>,
+    /// Axis values by [`GamepadAxis`] index, now and at the end of the
+    /// previous frame.
+    pub(crate) axes: [f32; 6],
+    pub(crate) previous_axes: [f32; 6],
+    pub(crate) scroll: MouseScroll,
+    /// Raw mouse motion summed over the frame.
+    pub(crate) mouse_motion: Vec2,
+    pub(crate) device: InputDevice,
+    pub(crate) events: Vec<TimedInput>,
+    pub(crate) text: Vec<TimedText>,
+    pub(crate) touches: Touches,
 }
 
 impl InputState {
@@ -24,6 +44,15 @@ impl InputState {
         Self {
             keys: Input::new(),
             mouse: Input::new(),
+            gamepad: Input::new(),
+            axes: [0.0; 6],
+            previous_axes: [0.0; 6],
+            scroll: MouseScroll::default(),
+            mouse_motion: Vec2::ZERO,
+            device: InputDevice::default(),
+            events: Vec::new(),
+            text: Vec::new(),
+            touches: Touches::default(),
         }
     }
 
@@ -56,6 +85,147 @@ impl InputState {
     pub fn mouse_just_released(&self, button: MouseButton) -> bool {
         self.mouse.just_released(button)
     }
+
+    /// Mouse wheel and trackpad scrolling this frame, in lines and in
+    /// pixels. See [scrolling](crate::input#scrolling).
+    pub fn scroll(&self) -> MouseScroll {
+        self.scroll
+    }
+
+    /// How far the mouse moved this frame, in raw device units (x right,
+    /// y down). Unlike the cursor position it keeps changing with the cursor
+    /// [locked](crate::cursor), so use it for mouse look.
+    pub fn mouse_motion(&self) -> Vec2 {
+        self.mouse_motion
+    }
+
+    /// Returns `true` if the gamepad button is currently held down.
+    pub fn gamepad_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepad.pressed(button)
+    }
+
+    /// Returns `true` if the gamepad button was pressed this frame.
+    pub fn gamepad_just_pressed(&self, button: GamepadButton) -> bool {
+        self.gamepad.just_pressed(button)
+    }
+
+    /// Returns `true` if the gamepad button was released this frame.
+    pub fn gamepad_just_released(&self, button: GamepadButton) -> bool {
+        self.gamepad.just_released(button)
+    }
+
+    /// Report a gamepad button from a gamepad backend. Call it before update
+    /// systems run, e.g. from a [`Hook::FrameStart`](crate::hooks::Hook)
+    /// hook. A press makes `style` the active [`device`](Self::device).
+    pub fn set_gamepad_button(&mut self, style: GamepadStyle, button: GamepadButton, pressed: bool) {
+        let event = if pressed {
+            self.gamepad.press(button);
+            self.device = InputDevice::Gamepad(style);
+            InputEvent::GamepadPressed(button)
+        } else {
+            self.gamepad.release(button);
+            InputEvent::GamepadReleased(button)
+        };
+        self.events.push(TimedInput {
+            event,
+            at: std::time::Instant::now(),
+        });
+    }
+
+    /// A gamepad stick or trigger's position, -1 to 1 (0 to 1 for
+    /// triggers). 0 until a backend reports it.
+    pub fn gamepad_axis(&self, axis: GamepadAxis) -> f32 {
+        self.axes[axis as usize]
+    }
+
+    /// The axis' position at the end of the previous frame.
+    pub fn gamepad_axis_previous(&self, axis: GamepadAxis) -> f32 {
+        self.previous_axes[axis as usize]
+    }
+
+    /// Report a gamepad stick or trigger position from a gamepad backend,
+    /// like [`set_gamepad_button`](Self::set_gamepad_button). The value
+    /// holds until reported again. Pushing past half way makes `style` the
+    /// active device.
+    pub fn set_gamepad_axis(&mut self, style: GamepadStyle, axis: GamepadAxis, value: f32) {
+        let value = value.clamp(-1.0, 1.0);
+        if value.abs() >= 0.5 {
+            self.device = InputDevice::Gamepad(style);
+        }
+        self.axes[axis as usize] = value;
+    }
+
+    /// Every input event this frame, oldest first, with arrival times.
+    /// Unlike [`just_pressed`](Self::just_pressed), a key tapped twice in
+    /// one frame shows up twice. See the [input docs](crate::input).
+    ///
+    /// ```ignore
+    /// let taps = ctx.input.events().iter()
+    ///     .filter(|e| e.event == InputEvent::KeyPressed(KeyCode::Space))
+    ///     .count();
+    /// ```
+    pub fn events(&self) -> &[TimedInput] {
+        &self.events
+    }
+
+    /// Text typed this frame, oldest first: characters as the keyboard
+    /// layout produced them and IME compositions. See
+    /// [text input](crate::input#text).
+    pub fn text_events(&self) -> &[TimedText] {
+        &self.text
+    }
+
+    /// Text committed this frame, concatenated. Convenient for a field that
+    /// doesn't care about IME compositions or timing.
+    pub fn typed_text(&self) -> String {
+        self.text
+            .iter()
+            .filter_map(|timed| match &timed.event {
+                TextEvent::Commit(text) => Some(text.as_str()),
+                TextEvent::Preedit { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Fingers on the touch screen, and tap/drag/pinch gestures. See
+    /// [`touch`](crate::touch).
+    pub fn touches(&self) -> &Touches {
+        &self.touches
+    }
+
+    /// Whether the first finger down also drives the mouse cursor and left
+    /// button (on by default). See
+    /// [mouse emulation](crate::touch#mouse-emulation).
+    pub fn set_touch_mouse_emulation(&mut self, enabled: bool) {
+        self.touches.emulate_mouse = enabled;
+    }
+
+    /// The device the player last pressed something on. Button prompts
+    /// follow it; see [`glyph`](crate::glyph).
+    pub fn device(&self) -> InputDevice {
+        self.device
+    }
+
+    /// Switch to keyboard and mouse if a key or mouse button was pressed
+    /// this frame. Called after the input queue is drained.
+    pub(crate) fn update_device(&mut self) {
+        if self.keys.any_just_pressed() || self.mouse.any_just_pressed() {
+            self.device = InputDevice::KeyboardMouse;
+        }
+    }
+
+    /// Clear per-frame state of every device. Called after update systems.
+    pub(crate) fn clear_just(&mut self) {
+        self.keys.clear_just();
+        self.mouse.clear_just();
+        self.gamepad.clear_just();
+        self.previous_axes = self.axes;
+        self.scroll = MouseScroll::default();
+        self.mouse_motion = Vec2::ZERO;
+        self.events.clear();
+        self.text.clear();
+        self.touches.end_frame();
+    }
 }
 
 // ── Context ──────────────────────────────────────────────────────────────
@@ -89,6 +259,10 @@ pub struct Context {
     pub cursor: CursorPosition,
     /// Frame timing (delta time, elapsed time, FPS).
     pub time: Time,
+    /// State private to the running system. See [`local`](crate::local).
+    pub local: SystemLocal,
+    /// Set by [`exit`](Self::exit); checked by the game loop after each frame.
+    pub(crate) exit_requested: bool,
 }
 
 impl Context {
@@ -97,15 +271,29 @@ impl Context {
         let time = Time::new();
         world.insert_resource(time);
         world.insert_resource(crate::asset::AssetServer::new());
+        world.insert_resource(crate::lifecycle::WindowLifecycle::new());
+        world.insert_resource(crate::input::InputLatency::default());
+        world.insert_resource(crate::cursor::CursorOptions::default());
+        world.insert_resource(crate::render::FrameCapture::default());
+        world.insert_resource(crate::clipboard::Clipboard::new());
 
         Self {
             world,
             input: InputState::new(),
             cursor: CursorPosition::default(),
             time,
+            local: SystemLocal::default(),
+            exit_requested: false,
         }
     }
 
+    /// Exit the game at the end of the current frame. Unlike closing the
+    /// window, this skips close-request hooks — call it after the player
+    /// confirms a vetoed close. [`Hook::Shutdown`](crate::hooks::Hook) still runs.
+    pub fn exit(&mut self) {
+        self.exit_requested = true;
+    }
+
     /// Spawn a named entity. Returns an [`EntityBuilder`] for adding components.
     ///
     /// The name can later be used to look up the entity with
@@ -128,18 +316,160 @@ impl Context {
         }
     }
 
+    /// Capture the next rendered frame with RenderDoc. See
+    /// [`FrameCapture`](crate::render::FrameCapture).
+    pub fn capture_frame(&mut self) {
+        if let Some(capture) = self.world.get_resource_mut::<crate::render::FrameCapture>() {
+            capture.request();
+        }
+    }
+
+    /// Load a color grading LUT (`.cube`, strip PNG, or graded neutral
+    /// screenshot) into the [`ColorGrading`](crate::render::ColorGrading)
+    /// resource. The file is hot-reloaded.
+    pub fn load_color_grading(&mut self, path: &str) {
+        crate::render::color_grading::load_color_grading(&mut self.world, path);
+    }
+
+    /// Load input bindings (a RON file) into the
+    /// [`ActionMap`](crate::action::ActionMap) resource. The file is
+    /// hot-reloaded.
+    pub fn load_actions(&mut self, path: &str) {
+        crate::action::load_actions(&mut self.world, path);
+    }
+
+    /// Returns `true` if any binding of `action` is held down. `false` if no
+    /// [`ActionMap`](crate::action::ActionMap) is loaded.
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.world
+            .get_resource::<ActionMap>()
+            .is_some_and(|map| map.pressed(action, &self.input))
+    }
+
+    /// Returns `true` if any binding of `action` was pressed this frame.
+    pub fn action_just_pressed(&self, action: &str) -> bool {
+        self.world
+            .get_resource::<ActionMap>()
+            .is_some_and(|map| map.just_pressed(action, &self.input))
+    }
+
+    /// Returns `true` if any binding of `action` was released this frame.
+    pub fn action_just_released(&self, action: &str) -> bool {
+        self.world
+            .get_resource::<ActionMap>()
+            .is_some_and(|map| map.just_released(action, &self.input))
+    }
+
+    /// How strongly `action` is held, 0 to 1 — fractional for sticks. 0 if
+    /// no [`ActionMap`](crate::action::ActionMap) is loaded.
+    pub fn action_value(&self, action: &str) -> f32 {
+        self.world
+            .get_resource::<ActionMap>()
+            .map_or(0.0, |map| map.value(action, &self.input))
+    }
+
+    /// `positive`'s value minus `negative`'s, -1 to 1, e.g.
+    /// `action_axis("left", "right")`.
+    pub fn action_axis(&self, negative: &str, positive: &str) -> f32 {
+        self.world
+            .get_resource::<ActionMap>()
+            .map_or(0.0, |map| map.axis(negative, positive, &self.input))
+    }
+
+    /// Write the current (possibly rebound) bindings to a RON file that
+    /// [`load_actions`](Self::load_actions) reads back.
+    pub fn save_actions(&self, path: &str) {
+        let Some(map) = self.world.get_resource::<ActionMap>() else {
+            log::warn!("No action map to save to {path}");
+            return;
+        };
+        if let Err(err) = map.save(path) {
+            log::warn!("Failed to save actions to {path}: {err}");
+        }
+    }
+
+    /// `template` with each `{action}` replaced by the button for it on the
+    /// player's current device, e.g. `"Press [A] to jump"`. See
+    /// [`glyph`](crate::glyph).
+    pub fn input_prompt(&self, template: &str) -> String {
+        let unbound = ActionMap::default();
+        let map = self.world.get_resource::<ActionMap>().unwrap_or(&unbound);
+        crate::glyph::format_prompt(template, map, self.input.device)
+    }
+
     /// Load a 2D texture from disk and return a handle.
     #[cfg(feature = "render2d")]
     pub fn load_texture(&mut self, path: &str) -> crate::render2d::TextureHandle {
         crate::render2d::texture::load_texture(&mut self.world, path)
     }
 
+    /// Start loading a 2D texture in the background. The handle draws white
+    /// until the image arrives; see [`load_state`](Self::load_state).
+    #[cfg(feature = "render2d")]
+    pub fn load_texture_async(&mut self, path: &str) -> crate::render2d::TextureHandle {
+        crate::render2d::texture::load_texture_async(&mut self.world, path)
+    }
+
+    /// Load a 2D texture with explicit sampler settings, e.g. linear
+    /// filtering or repeat wrapping.
+    #[cfg(feature = "render2d")]
+    pub fn load_texture_with(
+        &mut self,
+        path: &str,
+        sampler: crate::render::SamplerSettings,
+    ) -> crate::render2d::TextureHandle {
+        crate::render2d::texture::load_texture_with(&mut self.world, path, sampler)
+    }
+
+    /// Change how a loaded 2D texture is sampled.
+    #[cfg(feature = "render2d")]
+    pub fn set_texture_sampler(
+        &mut self,
+        texture: crate::render2d::TextureHandle,
+        sampler: crate::render::SamplerSettings,
+    ) {
+        crate::render2d::texture::set_texture_sampler(&mut self.world, texture, sampler);
+    }
+
+    /// Load a texture atlas from a JSON sheet descriptor. See
+    /// [`texture_atlas`](crate::render2d::texture_atlas).
+    #[cfg(feature = "render2d")]
+    pub fn load_texture_atlas(
+        &mut self,
+        path: &str,
+    ) -> Option<crate::render2d::TextureAtlasHandle> {
+        crate::render2d::texture_atlas::load_texture_atlas(&mut self.world, path)
+    }
+
+    /// Load a Tiled map (`.tmx` or `.json`) as a tilemap, ready to spawn.
+    /// See [`tiled`](crate::render2d::tiled).
+    #[cfg(feature = "render2d")]
+    pub fn load_tilemap(&mut self, path: &str) -> Option<crate::render2d::Tilemap> {
+        crate::render2d::tiled::load_tilemap(&mut self.world, path)
+    }
+
+    /// Pack image files into one atlas texture; region `i` is `paths[i]`.
+    #[cfg(feature = "render2d")]
+    pub fn pack_texture_atlas(
+        &mut self,
+        paths: &[&str],
+    ) -> Option<crate::render2d::TextureAtlasHandle> {
+        crate::render2d::texture_atlas::pack_texture_atlas(&mut self.world, paths)
+    }
+
     /// Load a font from disk at the given pixel size and return a handle.
     #[cfg(feature = "render2d")]
     pub fn load_font(&mut self, path: &str, size: f32) -> crate::render2d::FontHandle {
         crate::render2d::font::load_font(&mut self.world, path, size)
     }
 
+    /// Start loading a font in the background. Text using it stays blank
+    /// until it arrives; see [`load_state`](Self::load_state).
+    #[cfg(feature = "render2d")]
+    pub fn load_font_async(&mut self, path: &str, size: f32) -> crate::render2d::FontHandle {
+        crate::render2d::font::load_font_async(&mut self.world, path, size)
+    }
+
     /// Create a texture from raw RGBA8 pixel data and return a handle.
     #[cfg(feature = "render2d")]
     pub fn create_texture(
@@ -157,6 +487,117 @@ impl Context {
     pub fn load_texture_3d(&mut self, path: &str) -> crate::render3d::TextureHandle3d {
         crate::render3d::texture::load_texture_3d(&mut self.world, path)
     }
+
+    /// Load a normal, metallic-roughness or AO map from disk, keeping its
+    /// values linear, and return a handle.
+    #[cfg(feature = "render3d")]
+    pub fn load_data_texture_3d(&mut self, path: &str) -> crate::render3d::TextureHandle3d {
+        crate::render3d::texture::load_data_texture_3d(&mut self.world, path)
+    }
+
+    /// Start loading a 3D texture in the background. The handle samples
+    /// white until the image arrives; see [`load_state`](Self::load_state).
+    #[cfg(feature = "render3d")]
+    pub fn load_texture_3d_async(&mut self, path: &str) -> crate::render3d::TextureHandle3d {
+        crate::render3d::texture::load_texture_3d_async(&mut self.world, path)
+    }
+
+    /// Load a glTF/GLB file in the background and call `on_loaded` with its
+    /// meshes and materials once it is ready.
+    #[cfg(feature = "render3d")]
+    pub fn load_gltf_async(
+        &mut self,
+        path: &str,
+        on_loaded: impl FnOnce(&mut World, Vec<(crate::render3d::MeshHandle, crate::render3d::Material)>)
+        + Send
+        + 'static,
+    ) {
+        crate::render3d::load_gltf_async(&mut self.world, path, on_loaded)
+    }
+
+    /// Load a game-defined file with the [`AssetLoader`](crate::asset::AssetLoader)
+    /// registered for its extension, and hot-reload it from then on.
+    /// Returns `false` if no loader matches or the load fails.
+    pub fn load_custom(&mut self, path: &str) -> bool {
+        crate::asset::load_custom(&mut self.world, path)
+    }
+
+    /// State of an async load started with `path`, or `None` if there was
+    /// none.
+    pub fn load_state(&self, path: &str) -> Option<crate::asset::LoadState> {
+        let path = crate::launch::resolve_asset_path(&self.world, path);
+        self.world
+            .get_resource::<crate::asset::AssetServer>()?
+            .load_state(path.as_ref())
+    }
+
+    /// Counts of all async loads, e.g. for a loading bar.
+    pub fn load_progress(&self) -> crate::asset::LoadProgress {
+        self.world
+            .get_resource::<crate::asset::AssetServer>()
+            .map(|server| server.load_progress())
+            .unwrap_or_default()
+    }
+
+    /// Load a 3D texture with explicit sampler settings, e.g. nearest
+    /// filtering or anisotropy.
+    #[cfg(feature = "render3d")]
+    pub fn load_texture_3d_with(
+        &mut self,
+        path: &str,
+        sampler: crate::render::SamplerSettings,
+    ) -> crate::render3d::TextureHandle3d {
+        crate::render3d::texture::load_texture_3d_with(&mut self.world, path, sampler)
+    }
+
+    /// Load a WGSL material shader. Returns `None` if the file can't be read.
+    #[cfg(feature = "render3d")]
+    pub fn load_material_shader(&mut self, path: &str) -> Option<crate::render3d::MaterialShader> {
+        crate::render3d::load_material_shader(&mut self.world, path)
+    }
+
+    /// Give a mesh extra per-vertex data (colors, a second UV set, custom
+    /// values) for its material shader.
+    #[cfg(feature = "render3d")]
+    pub fn set_vertex_attributes(
+        &mut self,
+        mesh: crate::render3d::MeshHandle,
+        attributes: crate::render3d::VertexAttributes,
+    ) {
+        crate::render3d::set_vertex_attributes(&mut self.world, mesh, attributes);
+    }
+
+    /// Change how a loaded 3D texture is sampled.
+    #[cfg(feature = "render3d")]
+    pub fn set_texture_sampler_3d(
+        &mut self,
+        texture: crate::render3d::TextureHandle3d,
+        sampler: crate::render::SamplerSettings,
+    ) {
+        crate::render3d::texture::set_texture_sampler_3d(&mut self.world, texture, sampler);
+    }
+
+    /// Free every texture and mesh nothing refers to any more, e.g. after
+    /// despawning a level. See [`asset_gc`](crate::asset_gc).
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    pub fn free_unused_assets(&mut self) -> crate::asset_gc::FreedAssets {
+        crate::asset_gc::free_unused_assets(&mut self.world)
+    }
+}
+
+/// Game systems run from a `Schedule<Context>`, each with its own
+/// [`SystemLocal`] swapped into [`Context::local`] while it runs.
+impl ScheduleContext for Context {
+    type Local = SystemLocal;
+
+    fn enter(&mut self, local: &mut SystemLocal) {
+        local.begin_run(self.time.frame_count(), self.time.elapsed());
+        std::mem::swap(&mut self.local, local);
+    }
+
+    fn exit(&mut self, local: &mut SystemLocal) {
+        std::mem::swap(&mut self.local, local);
+    }
 }
 
 // ── EntityBuilder ────────────────────────────────────────────────────────
@@ -0,0 +1,124 @@
+//! # Cursor — Grab, Hide and Warp the Mouse Cursor
+//!
+//! A first-person camera turns with the mouse, and the mouse must not wander
+//! off the window while it does. The [`CursorOptions`] resource says what the
+//! OS cursor should do; the window applies it after update systems each
+//! frame, calling the platform only when something changed:
+//!
+//! ```text
+//!   update systems ──► CursorOptions { grab, visible, warp }
+//!                                   │  (changed since last frame?)
+//!                                   ▼
+//!   window ── set_cursor_grab / set_cursor_visible / set_cursor_position
+//! ```
+//!
+//! [`CursorGrab`] picks how tightly the cursor is held:
+//!
+//! ```text
+//!   None       free to leave the window
+//!   Confined   kept inside the window, still moves      (strategy games)
+//!   Locked     pinned in place, only motion is reported (first person)
+//! ```
+//!
+//! A locked cursor doesn't move, so [`CursorPosition`](crate::input::CursorPosition)
+//! stops changing. Read raw mouse motion instead with
+//! [`InputState::mouse_motion`](crate::context::InputState::mouse_motion): the
+//! device's own deltas, before OS acceleration and not clamped at the screen
+//! edge.
+//!
+//! ```ignore
+//! fn look(ctx: &mut Context) {
+//!     let cursor = ctx.world.resource_mut::<CursorOptions>();
+//!     if ctx.input.mouse_just_pressed(MouseButton::Left) {
+//!         cursor.lock();
+//!     } else if ctx.input.just_pressed(KeyCode::Escape) {
+//!         cursor.release();
+//!     }
+//!     let turn = ctx.input.mouse_motion() * 0.002;
+//!     yaw -= turn.x;
+//!     pitch = (pitch - turn.y).clamp(-1.5, 1.5);
+//! }
+//! ```
+//!
+//! Platforms differ: Windows and X11 can't lock, so `Locked` falls back to
+//! `Confined` there, and macOS can't confine, so `Confined` falls back to
+//! `Locked`. The OS releases the grab when the window loses focus; it is
+//! applied again when focus returns. While the editor is open the cursor is
+//! left free so the editor can be used.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Cursor.lockState` (`None`, `Confined`, `Locked`) and
+//!   `Cursor.visible`; `Mouse.WarpCursorPosition` in the Input System.
+//! - **Bevy**: `CursorOptions` on the `Window` component with `grab_mode`
+//!   and `visible`, `Window::set_cursor_position`, and `MouseMotion` events.
+//! - **Godot**: `Input.mouse_mode` (`VISIBLE`, `HIDDEN`, `CAPTURED`,
+//!   `CONFINED`), `Input.warp_mouse`, and `InputEventMouseMotion.relative`.
+//! - **Our approach**: Unity's lock states plus a visibility flag as one
+//!   resource, with platform fallbacks so `Locked` works everywhere.
+
+use crate::math::Vec2;
+
+/// How the OS cursor is held. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CursorGrab {
+    /// Free to leave the window.
+    #[default]
+    None,
+    /// Kept inside the window.
+    Confined,
+    /// Pinned in place; read [mouse motion](crate::context::InputState::mouse_motion).
+    Locked,
+}
+
+/// Resource: what the OS cursor should do. Inserted by the framework and
+/// applied by the window after update systems. See the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CursorOptions {
+    pub grab: CursorGrab,
+    /// Whether the cursor is drawn over the window.
+    pub visible: bool,
+    /// Position to move the cursor to, taken by the window.
+    warp: Option<Vec2>,
+}
+
+impl Default for CursorOptions {
+    fn default() -> Self {
+        Self {
+            grab: CursorGrab::None,
+            visible: true,
+            warp: None,
+        }
+    }
+}
+
+impl CursorOptions {
+    /// Lock and hide the cursor, for mouse look.
+    pub fn lock(&mut self) {
+        self.grab = CursorGrab::Locked;
+        self.visible = false;
+    }
+
+    /// Free and show the cursor again.
+    pub fn release(&mut self) {
+        self.grab = CursorGrab::None;
+        self.visible = true;
+    }
+
+    /// Whether the cursor is held in any way.
+    pub fn is_grabbed(&self) -> bool {
+        self.grab != CursorGrab::None
+    }
+
+    /// Move the cursor to `position`, in window coordinates, at the end of
+    /// this frame. [`CursorPosition`](crate::input::CursorPosition) follows
+    /// from the next frame.
+    pub fn warp_to(&mut self, position: Vec2) {
+        self.warp = Some(position);
+    }
+
+    /// The requested warp, if any, clearing it.
+    pub(crate) fn take_warp(&mut self) -> Option<Vec2> {
+        self.warp.take()
+    }
+}
@@ -6,10 +6,36 @@
 //! UDP to `127.0.0.1:9100`.
 //!
 //! A second channel on port 9101 receives inspection requests from the TUI
-//! (e.g. "send entity details for archetype index N").
+//! (e.g. "send entity details for archetype index N"). Both ports move
+//! together with `--diag-port` (see [`LaunchOptions`](crate::launch::LaunchOptions)).
+//!
+//! A request can also ask for a *world capture*: every entity with every
+//! component's debug value, used by the telemetry diff view. A capture can be
+//! far larger than one datagram, so it is split into `CaptureChunk`s that
+//! the TUI reassembles by `capture_id`.
+//!
+//! The first snapshot carries the [build info](crate::version) — engine
+//! version, features, git hash and GPU backend — and so does the next one
+//! after a request asks for it, which the TUI does when it connects to a
+//! game that is already running.
+//!
+//! With a [`ShaderDiff`](crate::render::ShaderDiff) resource, snapshots carry
+//! the latest shader reload's before/after stats, and a request can switch
+//! which frame the game shows.
+//!
+//! Snapshots only show the present, so slow leaks — an archetype per level
+//! load, free slots that never get reused — are easy to miss. The sender
+//! also keeps a *pool history*: every two seconds it records the entity
+//! count, archetype count and slot fragmentation in a ring buffer covering
+//! the last half hour. The TUI asks for it on demand and plots the trend:
+//!
+//! ```text
+//!   archetypes  ▁▁▂▂▃▃▄▄▅▅▆▆▇▇██   12 → 48 over 30m   ← a leak
+//!   frag        ▃▅▃▅▃▅▃▅▃▅▃▅▃▅▃▅   steady churn        ← fine
+//! ```
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::net::UdpSocket;
 use std::sync::Mutex;
 use std::time::Instant;
@@ -18,6 +44,8 @@ use serde::Serialize;
 
 use crate::asset::AssetServer;
 use crate::ecs::world::World;
+use crate::input::InputLatency;
+use crate::render::{DiffView, ShaderDiff};
 
 // ── DiagSender ───────────────────────────────────────────────────────────
 
@@ -31,17 +59,33 @@ pub struct DiagSender {
     last_send: Instant,
     /// Currently-expanded archetype indices (set by TUI request).
     expanded_archetypes: Vec<usize>,
+    /// World capture requested by the TUI, sent on the next frame.
+    pending_capture: Option<u32>,
+    /// Shader diff view chosen in the TUI, applied on the next frame.
+    pending_diff_view: Option<DiffView>,
+    /// Entity/archetype/fragmentation samples over the last half hour.
+    pool_history: PoolHistory,
+    /// The TUI asked for `pool_history`; sent with the next snapshot.
+    pending_pool_history: bool,
+    /// Send the build info with the next snapshot. Starts set.
+    pending_build_info: bool,
 }
 
 impl DiagSender {
     /// Create a new sender. Binds an ephemeral port for sending and port 9101
     /// for receiving requests.
     pub fn new() -> Option<Self> {
+        Self::bind(crate::launch::DEFAULT_DIAGNOSTICS_PORT)
+    }
+
+    /// Create a sender for a TUI listening on `port` (`necs-telemetry --port`).
+    /// Requests are received on `port + 1`.
+    pub fn bind(port: u16) -> Option<Self> {
         let socket = UdpSocket::bind("127.0.0.1:0").ok()?;
-        socket.connect("127.0.0.1:9100").ok()?;
+        socket.connect(("127.0.0.1", port)).ok()?;
         socket.set_nonblocking(true).ok()?;
 
-        let request_socket = UdpSocket::bind("127.0.0.1:9101").ok()?;
+        let request_socket = UdpSocket::bind(("127.0.0.1", port.checked_add(1)?)).ok()?;
         request_socket.set_nonblocking(true).ok()?;
 
         Some(Self {
@@ -49,6 +93,11 @@ impl DiagSender {
             request_socket,
             last_send: Instant::now() - std::time::Duration::from_secs(1), // send immediately on first frame
             expanded_archetypes: Vec::new(),
+            pending_capture: None,
+            pending_diff_view: None,
+            pool_history: PoolHistory::new(),
+            pending_pool_history: false,
+            pending_build_info: true,
         })
     }
 
@@ -58,6 +107,14 @@ impl DiagSender {
         while let Ok(n) = self.request_socket.recv(&mut buf) {
             if let Ok(req) = serde_json::from_slice::<InspectRequest>(&buf[..n]) {
                 self.expanded_archetypes = req.expanded_archetypes;
+                if req.capture_id.is_some() {
+                    self.pending_capture = req.capture_id;
+                }
+                if req.shader_diff_view.is_some() {
+                    self.pending_diff_view = req.shader_diff_view;
+                }
+                self.pending_pool_history |= req.pool_history;
+                self.pending_build_info |= req.build_info;
             }
         }
     }
@@ -67,10 +124,101 @@ impl DiagSender {
 #[derive(serde::Deserialize)]
 struct InspectRequest {
     expanded_archetypes: Vec<usize>,
+    /// Ask for a full world capture tagged with this id.
+    #[serde(default)]
+    capture_id: Option<u32>,
+    /// Switch the [`ShaderDiff`](crate::render::ShaderDiff) view.
+    #[serde(default)]
+    shader_diff_view: Option<DiffView>,
+    /// Ask for the whole pool history ring.
+    #[serde(default)]
+    pool_history: bool,
+    /// Ask for the build info.
+    #[serde(default)]
+    build_info: bool,
+}
+
+// ── Pool history ────────────────────────────────────────────────────────
+
+/// Seconds between pool history samples.
+const POOL_HISTORY_INTERVAL_SECS: f32 = 2.0;
+
+/// Samples kept: half an hour at one per [`POOL_HISTORY_INTERVAL_SECS`].
+/// Serialized, the full ring stays well under one datagram.
+const POOL_HISTORY_CAP: usize = 900;
+
+/// One pool history sample, serialized as a compact JSON array:
+/// `[elapsed_secs, entity_count, archetype_count, fragmentation_pct]`.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+struct PoolSample(f32, usize, usize, f32);
+
+/// Ring buffer of [`PoolSample`]s, recorded at a fixed interval regardless
+/// of how often snapshots are sent.
+struct PoolHistory {
+    samples: VecDeque<PoolSample>,
+    /// Game time at which the next sample is due.
+    next_sample_secs: f32,
+}
+
+impl PoolHistory {
+    fn new() -> Self {
+        Self {
+            samples: VecDeque::with_capacity(POOL_HISTORY_CAP),
+            next_sample_secs: 0.0,
+        }
+    }
+
+    /// Record `sample` if the interval has passed since the last one.
+    fn record(&mut self, sample: PoolSample) {
+        let elapsed = sample.0;
+        if elapsed < self.next_sample_secs {
+            return;
+        }
+        if self.samples.len() >= POOL_HISTORY_CAP {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        self.next_sample_secs = elapsed + POOL_HISTORY_INTERVAL_SECS;
+    }
+}
+
+/// The pool history ring, wrapped as `{"pool_history": {...}}` like
+/// [`CaptureMessage`].
+#[derive(Serialize)]
+struct PoolHistoryMessage<'a> {
+    pool_history: PoolHistoryWire<'a>,
+}
+
+#[derive(Serialize)]
+struct PoolHistoryWire<'a> {
+    interval_secs: f32,
+    samples: &'a VecDeque<PoolSample>,
 }
 
 // ── Snapshot types (wire format) ────────────────────────────────────────
 
+/// Serialized size budget for one capture datagram, leaving headroom under
+/// the 64 KiB UDP limit for the chunk envelope.
+const CAPTURE_CHUNK_BYTES: usize = 48 * 1024;
+
+/// One datagram of a world capture, wrapped as `{"capture": {...}}` so the
+/// TUI can tell it apart from a regular snapshot.
+#[derive(Serialize)]
+struct CaptureMessage {
+    capture: CaptureChunk,
+}
+
+/// A slice of a world capture. The TUI waits for all `chunk_count` chunks
+/// with the same `capture_id` before using it.
+#[derive(Serialize)]
+struct CaptureChunk {
+    capture_id: u32,
+    frame_count: u64,
+    chunk: usize,
+    chunk_count: usize,
+    entities: Vec<EntityInfo>,
+}
+
 #[derive(Serialize)]
 struct DiagSnapshot {
     fps: f32,
@@ -82,6 +230,8 @@ struct DiagSnapshot {
     archetypes: Vec<ArchetypeInfo>,
     render: Option<RenderStatsSnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    input_latency: Option<InputLatencySnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     system_timings: Option<Vec<SystemTimingSnapshot>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     frame_budget: Option<FrameBudgetSnapshot>,
@@ -95,6 +245,12 @@ struct DiagSnapshot {
     hierarchy: Option<HierarchySnapshot>,
     #[serde(skip_serializing_if = "Option::is_none")]
     scene: Option<SceneSnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    propagation: Option<PropagationSnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shader_diff: Option<ShaderDiffSnapshot>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    build: Option<crate::version::BuildInfo>,
 }
 
 #[derive(Serialize)]
@@ -120,6 +276,25 @@ fn is_zero_u32(v: &u32) -> bool {
     *v == 0
 }
 
+impl From<EntitySnapshot> for EntityInfo {
+    fn from(e: EntitySnapshot) -> Self {
+        Self {
+            id: e.id,
+            generation: e.generation,
+            components: e
+                .components
+                .into_iter()
+                .map(|c| ComponentInfo {
+                    name: c.name,
+                    debug_value: c.debug_value,
+                })
+                .collect(),
+            parent_id: e.parent_id,
+            child_count: e.child_count,
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct ComponentInfo {
     name: String,
@@ -131,6 +306,17 @@ struct RenderStatsSnapshot {
     draw_calls: u32,
     vertices: u32,
     textures_loaded: u32,
+    meshes_submitted: u32,
+    meshes_culled: u32,
+    sprites_submitted: u32,
+    sprites_culled: u32,
+}
+
+#[derive(Serialize)]
+struct InputLatencySnapshot {
+    last_ms: f32,
+    avg_ms: f32,
+    max_ms: f32,
 }
 
 #[derive(Serialize)]
@@ -161,6 +347,8 @@ struct AssetSnapshot {
     watcher_active: bool,
     pending_count: usize,
     watched_files: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dependencies: Vec<(String, String)>,
     reload_events: Vec<ReloadEventWire>,
 }
 
@@ -189,6 +377,27 @@ struct HierarchySnapshot {
     max_depth: u32,
 }
 
+#[derive(Serialize)]
+struct PropagationSnapshot {
+    updated: usize,
+    roots: usize,
+    deferred_roots: usize,
+    duration_us: f64,
+}
+
+#[derive(Serialize)]
+struct ShaderDiffSnapshot {
+    shader: String,
+    view: DiffView,
+    width: u32,
+    height: u32,
+    changed_percent: f32,
+    mean_delta: f32,
+    max_delta: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    saved: Option<(String, String)>,
+}
+
 #[derive(Serialize)]
 struct SceneSnapshot {
     active_scene: Option<String>,
@@ -237,6 +446,8 @@ pub(crate) struct AssetDiagSnapshot {
     pub watcher_active: bool,
     pub pending_count: usize,
     pub watched_files: Vec<(String, String)>,
+    /// Dependency edges as (dependent, dependency) file names.
+    pub dependencies: Vec<(String, String)>,
     pub reload_events: Vec<ReloadEventSnapshot>,
 }
 
@@ -251,10 +462,19 @@ pub(crate) struct ReloadEventSnapshot {
 // ── Resources ───────────────────────────────────────────────────────────
 
 /// Per-frame render statistics, populated by the render pipeline.
+#[derive(Clone, Copy)]
 pub struct RenderStats {
     pub draw_calls: u32,
     pub vertices: u32,
     pub textures_loaded: u32,
+    /// 3D meshes drawn this frame, after frustum culling.
+    pub meshes_submitted: u32,
+    /// 3D meshes skipped this frame for being outside the camera's view.
+    pub meshes_culled: u32,
+    /// 2D sprites and shapes drawn this frame, after camera culling.
+    pub sprites_submitted: u32,
+    /// 2D sprites and shapes skipped this frame for being off-screen.
+    pub sprites_culled: u32,
 }
 
 impl RenderStats {
@@ -263,6 +483,10 @@ impl RenderStats {
             draw_calls: 0,
             vertices: 0,
             textures_loaded: 0,
+            meshes_submitted: 0,
+            meshes_culled: 0,
+            sprites_submitted: 0,
+            sprites_culled: 0,
         }
     }
 }
@@ -438,6 +662,53 @@ pub(crate) fn drain_captured_logs(max: usize) -> Vec<(String, String, String, f3
         .collect()
 }
 
+// ── World capture ────────────────────────────────────────────────────────
+
+/// Serialize every entity and send it as a sequence of `CaptureChunk`s.
+fn send_capture(world: &mut World, sender: &DiagSender, capture_id: u32, frame_count: u64) {
+    let all: Vec<usize> = (0..world.archetype_count()).collect();
+    let registry = world.resource_remove::<ComponentRegistry>();
+    let (_, _, arch_snapshots) = world.diagnostics_snapshot(&all, registry.as_ref());
+    if let Some(reg) = registry {
+        world.insert_resource(reg);
+    }
+
+    // Greedily pack entities into chunks by serialized size.
+    let mut chunks: Vec<Vec<EntityInfo>> = vec![Vec::new()];
+    let mut chunk_bytes = 0;
+    for entity in arch_snapshots
+        .into_iter()
+        .flat_map(|a| a.entities.unwrap_or_default())
+        .map(EntityInfo::from)
+    {
+        let bytes = serde_json::to_vec(&entity).map(|v| v.len()).unwrap_or(0);
+        if chunk_bytes + bytes > CAPTURE_CHUNK_BYTES && chunk_bytes > 0 {
+            chunks.push(Vec::new());
+            chunk_bytes = 0;
+        }
+        chunk_bytes += bytes;
+        chunks.last_mut().unwrap().push(entity);
+    }
+
+    let chunk_count = chunks.len();
+    for (chunk, entities) in chunks.into_iter().enumerate() {
+        let message = CaptureMessage {
+            capture: CaptureChunk {
+                capture_id,
+                frame_count,
+                chunk,
+                chunk_count,
+                entities,
+            },
+        };
+        if let Ok(json) = serde_json::to_vec(&message)
+            && let Err(e) = sender.socket.send(&json)
+        {
+            log::warn!("World capture {capture_id}: failed to send chunk {chunk}: {e}");
+        }
+    }
+}
+
 // ── send_diagnostics ─────────────────────────────────────────────────────
 
 /// Called once per frame. Throttled to 10 Hz internally.
@@ -450,6 +721,16 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
     // Process any incoming inspection requests.
     sender.process_requests();
 
+    // Captures bypass the throttle so they reflect the frame they were asked for.
+    if let Some(capture_id) = sender.pending_capture.take() {
+        send_capture(world, &sender, capture_id, time.frame_count());
+    }
+    if let Some(view) = sender.pending_diff_view.take()
+        && let Some(diff) = world.get_resource_mut::<ShaderDiff>()
+    {
+        diff.view = view;
+    }
+
     // Throttle to 10 Hz.
     let now = Instant::now();
     if now.duration_since(sender.last_send).as_millis() < 100 {
@@ -479,24 +760,9 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
         .map(|a| ArchetypeInfo {
             entity_count: a.entity_count,
             component_names: a.component_names,
-            entities: a.entities.map(|ents| {
-                ents.into_iter()
-                    .map(|e| EntityInfo {
-                        id: e.id,
-                        generation: e.generation,
-                        components: e
-                            .components
-                            .into_iter()
-                            .map(|c| ComponentInfo {
-                                name: c.name,
-                                debug_value: c.debug_value,
-                            })
-                            .collect(),
-                        parent_id: e.parent_id,
-                        child_count: e.child_count,
-                    })
-                    .collect()
-            }),
+            entities: a
+                .entities
+                .map(|ents| ents.into_iter().map(EntityInfo::from).collect()),
         })
         .collect();
 
@@ -505,6 +771,19 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
         draw_calls: r.draw_calls,
         vertices: r.vertices,
         textures_loaded: r.textures_loaded,
+        meshes_submitted: r.meshes_submitted,
+        meshes_culled: r.meshes_culled,
+        sprites_submitted: r.sprites_submitted,
+        sprites_culled: r.sprites_culled,
+    });
+
+    // Gather input-to-present latency (empty until a frame with input is shown).
+    let input_latency = world.get_resource::<InputLatency>().and_then(|l| {
+        Some(InputLatencySnapshot {
+            last_ms: l.last()?.as_secs_f32() * 1000.0,
+            avg_ms: l.average()?.as_secs_f32() * 1000.0,
+            max_ms: l.max()?.as_secs_f32() * 1000.0,
+        })
     });
 
     // Gather system timings.
@@ -532,6 +811,25 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
     } else {
         0.0
     };
+    sender.pool_history.record(PoolSample(
+        elapsed_secs,
+        pool_stats.alive_count,
+        archetype_count,
+        frag_pct,
+    ));
+    if std::mem::take(&mut sender.pending_pool_history) {
+        let message = PoolHistoryMessage {
+            pool_history: PoolHistoryWire {
+                interval_secs: POOL_HISTORY_INTERVAL_SECS,
+                samples: &sender.pool_history.samples,
+            },
+        };
+        if let Ok(json) = serde_json::to_vec(&message)
+            && let Err(e) = sender.socket.send(&json)
+        {
+            log::warn!("Failed to send pool history: {e}");
+        }
+    }
     let entity_pool = Some(EntityPoolSnapshot {
         total_slots: pool_stats.total_slots,
         free_count: pool_stats.free_count,
@@ -555,6 +853,7 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
             watcher_active: s.watcher_active,
             pending_count: s.pending_count,
             watched_files: s.watched_files,
+            dependencies: s.dependencies,
             reload_events: s
                 .reload_events
                 .into_iter()
@@ -663,6 +962,37 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
         None
     };
 
+    // Gather transform propagation cost.
+    let propagation = world
+        .get_resource::<crate::ecs::PropagationStats>()
+        .map(|p| PropagationSnapshot {
+            updated: p.updated,
+            roots: p.roots,
+            deferred_roots: p.deferred_roots,
+            duration_us: p.duration.as_secs_f64() * 1_000_000.0,
+        });
+
+    // Latest shader reload's before/after comparison.
+    let shader_diff = world.get_resource::<ShaderDiff>().and_then(|diff| {
+        let comparison = diff.comparison()?;
+        Some(ShaderDiffSnapshot {
+            shader: comparison
+                .shader
+                .file_name()
+                .map_or_else(|| comparison.shader.display().to_string(), |f| f.to_string_lossy().to_string()),
+            view: diff.view,
+            width: comparison.width,
+            height: comparison.height,
+            changed_percent: comparison.stats.changed * 100.0,
+            mean_delta: comparison.stats.mean_delta,
+            max_delta: comparison.stats.max_delta,
+            saved: comparison
+                .saved
+                .as_ref()
+                .map(|(before, after)| (before.display().to_string(), after.display().to_string())),
+        })
+    });
+
     let snapshot = DiagSnapshot {
         fps,
         delta_ms,
@@ -672,6 +1002,7 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
         archetype_count,
         archetypes,
         render,
+        input_latency,
         system_timings,
         frame_budget,
         entity_pool,
@@ -679,6 +1010,9 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
         logs,
         hierarchy,
         scene,
+        propagation,
+        shader_diff,
+        build: std::mem::take(&mut sender.pending_build_info).then(crate::build_info),
     };
 
     // Serialize and send (errors silently ignored — fire-and-forget).
@@ -688,3 +1022,36 @@ pub fn send_diagnostics(world: &mut World, time: &crate::time::Time) {
 
     world.insert_resource(sender);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_history_samples_at_an_interval_and_drops_the_oldest() {
+        let mut history = PoolHistory::new();
+        // Ten snapshots a second for the whole ring and then some.
+        let ticks = (POOL_HISTORY_CAP as f32 * POOL_HISTORY_INTERVAL_SECS * 10.0) as usize + 100;
+        for tick in 0..ticks {
+            history.record(PoolSample(tick as f32 * 0.1, tick, tick / 100, tick as f32 / 7.0 % 100.0));
+        }
+        assert_eq!(history.samples.len(), POOL_HISTORY_CAP);
+        let gaps: Vec<f32> = history
+            .samples
+            .iter()
+            .zip(history.samples.iter().skip(1))
+            .map(|(a, b)| b.0 - a.0)
+            .collect();
+        assert!(gaps.iter().all(|gap| (gap - POOL_HISTORY_INTERVAL_SECS).abs() < 0.15));
+        assert!(history.samples.front().unwrap().0 > 0.0, "oldest samples were dropped");
+
+        // The full ring fits in one datagram.
+        let wire = PoolHistoryMessage {
+            pool_history: PoolHistoryWire {
+                interval_secs: POOL_HISTORY_INTERVAL_SECS,
+                samples: &history.samples,
+            },
+        };
+        assert!(serde_json::to_vec(&wire).unwrap().len() < CAPTURE_CHUNK_BYTES);
+    }
+}
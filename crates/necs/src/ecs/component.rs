@@ -40,6 +40,7 @@ pub(crate) fn component_type_id<T: 'static>() -> TypeId {
 /// at runtime, with panics on mismatch (which indicates a framework bug).
 /// Opaque type-erased column used internally by queries and spawn bundles.
 /// Users interact with components through [`World`](super::world::World) methods.
+#[derive(Default)]
 pub struct ComponentColumn {
     data: Vec<Box<dyn Any + Send + Sync>>,
 }
@@ -87,6 +88,32 @@ impl ComponentColumn {
             })
     }
 
+    /// Shared references to every component, in row order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type doesn't match.
+    pub(crate) fn iter<T: 'static>(&self) -> impl Iterator<Item = &T> {
+        (0..self.data.len()).map(|index| self.get(index))
+    }
+
+    /// Mutable references to every component, in row order. Each is a
+    /// separate borrow, so they can be handed to different threads.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type doesn't match.
+    pub(crate) fn iter_mut<T: 'static>(&mut self) -> impl Iterator<Item = &mut T> {
+        self.data.iter_mut().map(|value| {
+            value.downcast_mut().unwrap_or_else(|| {
+                panic!(
+                    "Component type mismatch: expected `{}` in column",
+                    std::any::type_name::<T>()
+                )
+            })
+        })
+    }
+
     /// Swap-remove the component at `index`, returning whether a swap occurred.
     ///
     /// Returns `true` if the removed element wasn't the last one (i.e., the
@@ -119,6 +146,19 @@ impl ComponentColumn {
     pub fn len(&self) -> usize {
         self.data.len()
     }
+
+    /// Whether the column holds no components.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Estimated bytes held by this column: every allocated slot plus the
+    /// shallow size of each stored component.
+    pub(crate) fn memory_bytes(&self) -> usize {
+        let slots = self.data.capacity() * std::mem::size_of::<Box<dyn Any + Send + Sync>>();
+        let values: usize = self.data.iter().map(|value| std::mem::size_of_val(&**value)).sum();
+        slots + values
+    }
 }
 
 #[cfg(test)]
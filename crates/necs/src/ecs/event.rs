@@ -0,0 +1,280 @@
+//! # Events — Messages Between Systems
+//!
+//! Systems run one after another and share nothing but the [`World`](super::World). When
+//! the collision system wants to tell the health system "these two hit each
+//! other", it needs somewhere to put that message. [`Events<T>`] is that
+//! somewhere: a resource holding a queue of `T` values that any system can
+//! write to and any number of systems can read from.
+//!
+//! ```text
+//!              frame N                    frame N+1              frame N+2
+//!   ┌─────────────────────────┐ ┌─────────────────────────┐ ┌──────────────
+//!   │ physics sends Hit(a, b) │ │                         │ │
+//!   │ health reads  Hit(a, b) │ │ late reader still sees  │ │ Hit(a, b) is
+//!   │                         │ │ Hit(a, b)               │ │ gone
+//!   └─────────────────────────┘ └─────────────────────────┘ └──────────────
+//!        previous │ current          previous │ current
+//!                 │ [Hit]              [Hit]  │ []
+//! ```
+//!
+//! ## Double Buffering
+//!
+//! Events live in two buffers. [`Events::update`] runs at the end of every
+//! frame: it drops the older buffer and starts a fresh one. An event is
+//! therefore readable during the frame it was sent and the frame after,
+//! which means system order doesn't matter — a reader that runs *before* the
+//! writer still catches the event next frame. After that it's gone, so a
+//! queue nobody reads can't grow without bound.
+//!
+//! ## Readers
+//!
+//! Each consumer owns an [`EventReader<T>`], a cursor remembering which
+//! events it has already seen. Keep it in the system's closure so every
+//! reader sees each event exactly once:
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .event::<DamageEvent>()
+//!     .update(|ctx| {
+//!         ctx.world.send_event(DamageEvent { target, amount: 10 });
+//!     })
+//!     .update({
+//!         let mut reader = EventReader::<DamageEvent>::default();
+//!         move |ctx| {
+//!             for hit in reader.read(ctx.world.resource::<Events<DamageEvent>>()) {
+//!                 log::info!("{:?} took {}", hit.target, hit.amount);
+//!             }
+//!         }
+//!     })
+//!     .run();
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: C# `event`/`UnityEvent` callbacks, invoked immediately
+//!   inside the sender.
+//! - **Bevy**: `Events<T>` with `EventWriter`/`EventReader` system params —
+//!   the same two-buffer design this module follows.
+//! - **Godot**: Signals, connected per node and dispatched immediately.
+//! - **Our approach**: Bevy's buffers without system-param injection.
+//!   Readers are plain values you keep in your closure; writing is a method
+//!   on the world or a borrowed [`EventWriter`].
+
+use std::marker::PhantomData;
+
+/// Double-buffered queue of `T` events, stored as a resource. Register it
+/// with [`Game::event`](crate::game::Game::event) so it is updated every
+/// frame. See the [module docs](self).
+pub struct Events<T> {
+    /// Events sent during the previous frame.
+    previous: Vec<T>,
+    /// Events sent during this frame.
+    current: Vec<T>,
+    /// Id of `previous[0]`.
+    previous_start: usize,
+    /// Id of `current[0]`.
+    current_start: usize,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            previous: Vec::new(),
+            current: Vec::new(),
+            previous_start: 0,
+            current_start: 0,
+        }
+    }
+}
+
+impl<T> Events<T> {
+    /// Queue an event for this frame and the next.
+    pub fn send(&mut self, event: T) {
+        self.current.push(event);
+    }
+
+    /// Queue several events at once.
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.current.extend(events);
+    }
+
+    /// Borrow a writing handle.
+    pub fn writer(&mut self) -> EventWriter<'_, T> {
+        EventWriter { events: self }
+    }
+
+    /// A reader that skips everything already queued and only sees events
+    /// sent from now on. [`EventReader::default`] sees the queued ones too.
+    pub fn reader(&self) -> EventReader<T> {
+        EventReader {
+            next: self.end(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Advance one frame: drop the previous frame's events and start a new
+    /// buffer. Called automatically at the end of each frame for events
+    /// registered with [`Game::event`](crate::game::Game::event).
+    pub fn update(&mut self) {
+        self.previous_start = self.current_start;
+        self.current_start = self.end();
+        self.previous = std::mem::take(&mut self.current);
+    }
+
+    /// Drop all queued events immediately.
+    pub fn clear(&mut self) {
+        self.update();
+        self.update();
+    }
+
+    /// Number of events currently queued across both buffers.
+    pub fn len(&self) -> usize {
+        self.previous.len() + self.current.len()
+    }
+
+    /// Whether no events are queued.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Id one past the newest event.
+    fn end(&self) -> usize {
+        self.current_start + self.current.len()
+    }
+}
+
+/// Borrowed handle for sending events, e.g. to pass into a helper function
+/// that shouldn't see the rest of the queue.
+pub struct EventWriter<'a, T> {
+    events: &'a mut Events<T>,
+}
+
+impl<T> EventWriter<'_, T> {
+    /// Queue an event.
+    pub fn send(&mut self, event: T) {
+        self.events.send(event);
+    }
+
+    /// Queue several events.
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.events.send_batch(events);
+    }
+}
+
+/// Cursor into an [`Events<T>`] queue. Each reader sees every event once;
+/// keep one per consuming system.
+pub struct EventReader<T> {
+    /// Id of the next unread event.
+    next: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for EventReader<T> {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> EventReader<T> {
+    /// Events this reader hasn't seen yet, oldest first. Events that were
+    /// dropped before the reader got to them (it skipped two or more frames)
+    /// are lost.
+    pub fn read<'a>(&mut self, events: &'a Events<T>) -> impl Iterator<Item = &'a T> + use<'a, T> {
+        let start = self.next.max(events.previous_start);
+        self.next = events.end();
+        let previous = events
+            .previous
+            .get(start.saturating_sub(events.previous_start)..)
+            .unwrap_or_default();
+        let current = events
+            .current
+            .get(start.saturating_sub(events.current_start)..)
+            .unwrap_or_default();
+        previous.iter().chain(current)
+    }
+
+    /// Number of unread events.
+    pub fn len(&self, events: &Events<T>) -> usize {
+        events.end() - self.next.max(events.previous_start)
+    }
+
+    /// Whether there are no unread events.
+    pub fn is_empty(&self, events: &Events<T>) -> bool {
+        self.len(events) == 0
+    }
+
+    /// Mark everything queued as read without looking at it.
+    pub fn clear(&mut self, events: &Events<T>) {
+        self.next = events.end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::World;
+
+    #[derive(Debug, PartialEq)]
+    struct Hit(u32);
+
+    fn read_all(reader: &mut EventReader<Hit>, events: &Events<Hit>) -> Vec<u32> {
+        reader.read(events).map(|h| h.0).collect()
+    }
+
+    #[test]
+    fn events_survive_one_update_then_drop() {
+        let mut events = Events::default();
+        let mut late = EventReader::default();
+        events.send(Hit(1));
+        events.update();
+        events.send(Hit(2));
+        assert_eq!(events.len(), 2);
+        // A reader that missed frame N still catches frame N's event.
+        assert_eq!(read_all(&mut late, &events), vec![1, 2]);
+        assert!(late.is_empty(&events));
+
+        events.update();
+        events.update();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn each_reader_sees_each_event_once() {
+        let mut events = Events::default();
+        let mut a = EventReader::default();
+        events.writer().send_batch([Hit(1), Hit(2)]);
+        let mut b = events.reader();
+        assert_eq!(read_all(&mut a, &events), vec![1, 2]);
+        assert!(a.is_empty(&events));
+
+        events.update();
+        events.send(Hit(3));
+        assert_eq!(b.len(&events), 1);
+        assert_eq!(read_all(&mut a, &events), vec![3]);
+        assert_eq!(read_all(&mut b, &events), vec![3]);
+    }
+
+    #[test]
+    fn lagging_reader_skips_dropped_events() {
+        let mut events = Events::default();
+        let mut reader = EventReader::default();
+        events.send(Hit(1));
+        events.update();
+        events.update();
+        events.send(Hit(2));
+        assert_eq!(read_all(&mut reader, &events), vec![2]);
+    }
+
+    #[test]
+    fn send_event_requires_registration() {
+        let mut world = World::new();
+        world.send_event(Hit(1));
+        world.insert_resource(Events::<Hit>::default());
+        world.send_event(Hit(2));
+        let mut reader = EventReader::default();
+        assert_eq!(read_all(&mut reader, world.resource()), vec![2]);
+    }
+}
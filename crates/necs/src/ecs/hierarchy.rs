@@ -14,11 +14,33 @@
 //! // reflects the combined parent + child transform.
 //! propagate_transforms(&mut world);
 //! ```
+//!
+//! Very large worlds can spread propagation over several frames with a
+//! [`TransformPropagation`] budget.
+//!
+//! ## Walking and Editing the Tree
+//!
+//! [`World::descendants`] and [`World::ancestors`] iterate the hierarchy
+//! without touching the components directly. To move an entity, use
+//! [`World::set_parent`] or [`World::remove_parent`]: a parent link lives in
+//! three places, and editing one by hand leaves the others stale:
+//!
+//! ```text
+//!   set_parent(child, new)
+//!     old parent  Children [a, child, b]  ──►  [a, b]
+//!     new parent  Children [c]            ──►  [c, child]
+//!     child       Parent(old)             ──►  Parent(new)
+//!     child + subtree  GlobalTransform recomputed under `new`
+//! ```
+//!
+//! The child keeps its local [`Transform`], so it moves with its new parent
+//! straight away rather than on the next propagation.
 
 use std::collections::VecDeque;
-
+use std::time::{Duration, Instant};
 
 use crate::ecs::world::World;
+use crate::ecs::Entity;
 use crate::math::{Mat4, Transform};
 
 /// Marks an entity as a child of another entity.
@@ -38,12 +60,144 @@ pub struct GlobalTransform {
     pub matrix: Mat4,
 }
 
+/// Breadth-first iterator over an entity's descendants, from
+/// [`World::descendants`].
+pub struct Descendants<'w> {
+    world: &'w World,
+    queue: VecDeque<Entity>,
+}
+
+impl<'w> Descendants<'w> {
+    pub(crate) fn new(world: &'w World, entity: Entity) -> Self {
+        let mut descendants = Self {
+            world,
+            queue: VecDeque::new(),
+        };
+        descendants.push_children(entity);
+        descendants
+    }
+
+    fn push_children(&mut self, entity: Entity) {
+        if let Some(children) = self.world.get::<Children>(entity) {
+            self.queue.extend(&children.0);
+        }
+    }
+}
+
+impl Iterator for Descendants<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        let entity = self.queue.pop_front()?;
+        self.push_children(entity);
+        Some(entity)
+    }
+}
+
+/// Iterator from an entity's parent up to its root, from
+/// [`World::ancestors`].
+pub struct Ancestors<'w> {
+    world: &'w World,
+    current: Entity,
+}
+
+impl<'w> Ancestors<'w> {
+    pub(crate) fn new(world: &'w World, entity: Entity) -> Self {
+        Self { world, current: entity }
+    }
+}
+
+impl Iterator for Ancestors<'_> {
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Entity> {
+        self.current = self.world.get::<Parent>(self.current)?.0;
+        Some(self.current)
+    }
+}
+
+/// The world matrix of `entity` from the local [`Transform`]s along its
+/// ancestor chain; unlike [`GlobalTransform`] never stale.
+pub(crate) fn world_matrix(world: &World, entity: Entity) -> Mat4 {
+    let local = |e| world.get::<Transform>(e).map_or(Mat4::IDENTITY, Transform::matrix);
+    world
+        .ancestors(entity)
+        .fold(local(entity), |matrix, ancestor| local(ancestor) * matrix)
+}
+
+/// Resource: limits how much of the hierarchy [`propagate_transforms`]
+/// updates per frame.
+///
+/// Without it (or with `budget: None`) every entity is updated every frame.
+/// With a budget, root subtrees are updated round-robin until about
+/// `budget` entities have been visited, and the rest wait for a later frame:
+///
+/// ```text
+///   budget 3, roots by entity:  A(2)  B(1)  C(4)  D(1, hidden)
+///   frame 1: A B          (3 updated; C and D deferred)
+///   frame 2: C            (4 — a started subtree is always finished)
+///   frame 3: A B          (D is only reached when visible roots leave budget)
+/// ```
+///
+/// Subtrees whose root is hidden (by [`Visibility`](super::Visibility)
+/// or [`Hidden`](super::Hidden)) only get budget the visible ones
+/// leave over. Roots that have never been propagated are always updated, so
+/// new entities don't appear at the origin. Worth it only for very large
+/// worlds — 100k+ hierarchical entities — where most subtrees sit still;
+/// anything moving in a deferred subtree draws where it was last updated.
+/// See [`PropagationStats`] for what each frame actually did.
+///
+/// ```ignore
+/// Game::new("City").resource(TransformPropagation::budgeted(20_000))
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TransformPropagation {
+    /// Entities to update per frame; `None` updates all of them.
+    pub budget: Option<usize>,
+    /// Where the round-robin over visible roots resumes.
+    visible_cursor: usize,
+    /// Where the round-robin over hidden roots resumes.
+    hidden_cursor: usize,
+}
+
+impl TransformPropagation {
+    /// Update about `budget` entities per frame.
+    pub fn budgeted(budget: usize) -> Self {
+        Self {
+            budget: Some(budget),
+            ..Self::default()
+        }
+    }
+}
+
+/// Resource: what the last [`propagate_transforms`] call did. Also sent to
+/// the diagnostics TUI.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PropagationStats {
+    /// Entities whose [`GlobalTransform`] was written.
+    pub updated: usize,
+    /// Root entities in the world.
+    pub roots: usize,
+    /// Roots skipped for lack of budget.
+    pub deferred_roots: usize,
+    /// Time spent.
+    pub duration: Duration,
+}
+
 /// Propagate local transforms down the entity hierarchy.
 ///
 /// - Roots (entities with `Transform` but no `Parent`) get `GlobalTransform = Transform.matrix()`.
 /// - Children get `GlobalTransform = parent_global * child_local.matrix()`.
 /// - Traversal is BFS to ensure parents are computed before children.
+///
+/// With a [`TransformPropagation`] budget only part of the hierarchy is
+/// updated per call. Writes [`PropagationStats`].
 pub fn propagate_transforms(world: &mut World) {
+    let start = Instant::now();
+    let configured = world.resource_remove::<TransformPropagation>();
+    let keep_settings = configured.is_some();
+    let mut settings = configured.unwrap_or_default();
+
     // Collect root entities: have Transform but no Parent.
     let mut roots = Vec::new();
     world.query::<(&Transform,)>(|entity, (transform,)| {
@@ -51,23 +205,70 @@ pub fn propagate_transforms(world: &mut World) {
     });
 
     // Filter to only roots (no Parent component).
-    let roots: Vec<_> = roots
+    let mut roots: Vec<_> = roots
         .into_iter()
         .filter(|(entity, _)| world.get::<Parent>(*entity).is_none())
         .collect();
 
-    // Set GlobalTransform on roots and BFS through children.
-    let mut queue: VecDeque<(crate::ecs::Entity, Mat4)> = VecDeque::new();
+    let mut stats = PropagationStats {
+        roots: roots.len(),
+        ..PropagationStats::default()
+    };
+    match settings.budget {
+        None => {
+            for (entity, matrix) in roots {
+                stats.updated += propagate_subtree(world, entity, matrix);
+            }
+        }
+        Some(budget) => {
+            // Query order follows archetypes, which shift as components come
+            // and go; entity order keeps the round-robin stable.
+            roots.sort_by_key(|(entity, _)| entity.index());
+            let (mut visible, mut hidden) = (Vec::new(), Vec::new());
+            for (entity, matrix) in roots {
+                if world.get::<GlobalTransform>(entity).is_none() {
+                    stats.updated += propagate_subtree(world, entity, matrix);
+                } else if root_hidden(world, entity) {
+                    hidden.push((entity, matrix));
+                } else {
+                    visible.push((entity, matrix));
+                }
+            }
+            for (list, cursor) in [
+                (&visible, &mut settings.visible_cursor),
+                (&hidden, &mut settings.hidden_cursor),
+            ] {
+                let mut done = 0;
+                while done < list.len() && stats.updated < budget {
+                    let (entity, matrix) = list[(*cursor + done) % list.len()];
+                    stats.updated += propagate_subtree(world, entity, matrix);
+                    done += 1;
+                }
+                *cursor = if list.is_empty() { 0 } else { (*cursor + done) % list.len() };
+                stats.deferred_roots += list.len() - done;
+            }
+        }
+    }
 
-    for (entity, matrix) in roots {
-        world.insert(entity, GlobalTransform { matrix });
+    stats.duration = start.elapsed();
+    world.insert_resource(stats);
+    if keep_settings {
+        world.insert_resource(settings);
+    }
+}
 
-        // Enqueue children.
-        if let Some(children) = world.get::<Children>(entity) {
-            let child_list: Vec<_> = children.0.clone();
-            for child in child_list {
-                queue.push_back((child, matrix));
-            }
+/// Set [`GlobalTransform`] on `root` and everything below it. Returns the
+/// number of entities written.
+pub(crate) fn propagate_subtree(world: &mut World, root: Entity, matrix: Mat4) -> usize {
+    world.insert(root, GlobalTransform { matrix });
+    let mut updated = 1;
+
+    // Enqueue children.
+    let mut queue: VecDeque<(Entity, Mat4)> = VecDeque::new();
+    if let Some(children) = world.get::<Children>(root) {
+        let child_list: Vec<_> = children.0.clone();
+        for child in child_list {
+            queue.push_back((child, matrix));
         }
     }
 
@@ -79,6 +280,7 @@ pub fn propagate_transforms(world: &mut World) {
             .unwrap_or(Mat4::IDENTITY);
         let global_matrix = parent_matrix * local_matrix;
         world.insert(entity, GlobalTransform { matrix: global_matrix });
+        updated += 1;
 
         if let Some(children) = world.get::<Children>(entity) {
             let child_list: Vec<_> = children.0.clone();
@@ -87,6 +289,15 @@ pub fn propagate_transforms(world: &mut World) {
             }
         }
     }
+    updated
+}
+
+/// Whether a root hides its whole subtree.
+fn root_hidden(world: &World, root: Entity) -> bool {
+    world.get::<super::Hidden>(root).is_some()
+        || world
+            .get::<super::Visibility>(root)
+            .is_some_and(|v| !v.visible)
 }
 
 #[cfg(test)]
@@ -197,4 +408,72 @@ mod tests {
         let col3 = gt_c.matrix.col(3);
         assert!((col3.x - 6.0).abs() < 0.001); // 1 + 2 + 3
     }
+
+    #[test]
+    fn set_parent_moves_subtree_between_parents() {
+        let mut world = World::new();
+        let old = world.spawn((Transform::from_xyz(100.0, 0.0, 0.0),));
+        let new = world.spawn((Transform::from_xyz(0.0, 50.0, 0.0),));
+        let child = world.spawn_child(old, (Transform::from_xyz(1.0, 0.0, 0.0),));
+        let grandchild = world.spawn_child(child, (Transform::from_xyz(2.0, 0.0, 0.0),));
+        let sibling = world.spawn_child(old, (Transform::default(),));
+        propagate_transforms(&mut world);
+
+        assert!(world.set_parent(child, new));
+        assert_eq!(world.get::<Children>(old).unwrap().0, [sibling]);
+        assert_eq!(world.get::<Children>(new).unwrap().0, [child]);
+        assert_eq!(world.ancestors(grandchild).collect::<Vec<_>>(), [child, new]);
+        assert_eq!(world.descendants(new).collect::<Vec<_>>(), [child, grandchild]);
+        // Globals follow immediately, before the next propagation.
+        let col3 = world.get::<GlobalTransform>(grandchild).unwrap().matrix.col(3);
+        assert!((col3.x - 3.0).abs() < 0.001 && (col3.y - 50.0).abs() < 0.001);
+
+        // No cycles, no self-parenting.
+        assert!(!world.set_parent(new, grandchild));
+        assert!(!world.set_parent(child, child));
+
+        assert!(world.remove_parent(child));
+        assert!(world.get::<Parent>(child).is_none());
+        assert!(world.get::<Children>(new).unwrap().0.is_empty());
+        assert!(!world.remove_parent(child));
+        let col3 = world.get::<GlobalTransform>(grandchild).unwrap().matrix.col(3);
+        assert!((col3.x - 3.0).abs() < 0.001 && col3.y.abs() < 0.001);
+    }
+
+    #[test]
+    fn budget_time_slices_visible_roots_first() {
+        use crate::ecs::Visibility;
+
+        let mut world = World::new();
+        let a = world.spawn((Transform::default(),));
+        let a_child = world.spawn_child(a, (Transform::default(),));
+        let b = world.spawn((Transform::default(), Visibility::HIDDEN));
+        let c = world.spawn((Transform::default(),));
+        world.insert_resource(TransformPropagation::budgeted(2));
+
+        // First frame: everything is new, so everything is updated.
+        propagate_transforms(&mut world);
+        assert_eq!(world.resource::<PropagationStats>().updated, 4);
+
+        for e in [a, b, c] {
+            world.get_mut::<Transform>(e).unwrap().translation.x = 1.0;
+        }
+        let x = |world: &World, e| world.get::<GlobalTransform>(e).unwrap().matrix.col(3).x;
+
+        // a's subtree fills the budget; c and hidden b wait.
+        propagate_transforms(&mut world);
+        let stats = *world.resource::<PropagationStats>();
+        assert_eq!((stats.updated, stats.roots, stats.deferred_roots), (2, 3, 2));
+        assert_eq!((x(&world, a), x(&world, a_child), x(&world, c)), (1.0, 1.0, 0.0));
+
+        // c is next; hidden b only gets budget the visible roots leave.
+        propagate_transforms(&mut world);
+        assert_eq!(world.resource::<PropagationStats>().deferred_roots, 1);
+        assert_eq!((x(&world, b), x(&world, c)), (0.0, 1.0));
+
+        world.resource_mut::<TransformPropagation>().budget = Some(10);
+        propagate_transforms(&mut world);
+        assert_eq!(world.resource::<PropagationStats>().deferred_roots, 0);
+        assert_eq!(x(&world, b), 1.0);
+    }
 }
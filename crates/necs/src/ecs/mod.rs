@@ -11,18 +11,38 @@
 //! - [`entity`] — Generational entity IDs
 //! - [`component`] — Type-erased columnar storage (`Box<dyn Any>`)
 //! - [`archetype`] — Groups entities by component signature
+//! - [`event`] — Double-buffered event queues between systems
 //! - [`world`] — Central container (entities + components + resources)
+//! - [`pool`] — Recycling pre-spawned entities instead of respawning them
+//! - [`stats`] — Serializable snapshot of world contents for tools
 //! - [`query`] — Closure-based iteration over matching archetypes
 //! - [`system`] — System trait and schedule runner
+//! - [`visibility`] — Hiding entities and subtrees from rendering
 
 pub(crate) mod archetype;
 pub(crate) mod component;
 pub mod entity;
+pub mod event;
 pub mod hierarchy;
+pub mod pool;
 pub(crate) mod query;
+pub mod stats;
 pub mod system;
+pub mod visibility;
 pub mod world;
 
 pub use entity::Entity;
-pub use hierarchy::{propagate_transforms, Children, GlobalTransform, Parent};
-pub use world::World;
+pub use event::{EventReader, EventWriter, Events};
+pub use hierarchy::{
+    propagate_transforms, Ancestors, Children, Descendants, GlobalTransform, Parent, PropagationStats,
+    TransformPropagation,
+};
+pub use pool::{Pool, Pooled};
+pub use stats::WorldStats;
+pub use visibility::{propagate_visibility, ComputedVisibility, Hidden, Visibility};
+pub use world::{SpawnBundle, World};
+
+#[doc(hidden)]
+pub use component::ComponentColumn;
+/// Derive [`SpawnBundle`] for a struct of components. See its docs.
+pub use necs_macros::Bundle;
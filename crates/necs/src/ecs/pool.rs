@@ -0,0 +1,265 @@
+//! # Pools — Reusing Entities Instead of Respawning Them
+//!
+//! A shooter fires twenty bullets a second and each lives for half a second.
+//! Spawning and despawning every one costs a slot allocation, a row pushed
+//! into (and swap-removed from) an archetype's columns, and — if the bullet
+//! gains a `ComputedVisibility` or a physics handle on its first frame — an
+//! archetype move or two on top. A [`Pool`] keeps finished bullets around
+//! instead and hands them out again:
+//!
+//! ```text
+//!   prewarm(64)      acquire()              release(e)
+//!   ┌──────────┐     ┌──────────┐  reset   ┌──────────┐
+//!   │ free: 64 │ ──► │ free: 63 │ ──────►  │ free: 64 │
+//!   │ active: 0│     │ active: 1│  (e)     │ active: 0│
+//!   └──────────┘     └──────────┘          └──────────┘
+//!   all spawned      e: Pooled{active}      e: Pooled{inactive}
+//!   and hidden       + visible              + hidden, still in place
+//! ```
+//!
+//! A released entity keeps every component and stays in its archetype; only
+//! the [`Pooled`] flag and its [`Visibility`] change. When it is acquired
+//! again the pool's *reset* closure puts its components back to a fresh
+//! state.
+//!
+//! Code that doesn't know about the pool can still despawn the entity:
+//! [`World::despawn`] destroys it as usual — along with its physics body
+//! and triggers — and the pool forgets it on its next
+//! [`acquire`](Pool::acquire), spawning a replacement when it runs dry.
+//! Only [`Pool::release`] keeps an entity for reuse.
+//!
+//! Inactive entities are still matched by queries. Systems that move or
+//! collide pooled entities should skip them with [`Pooled::is_active`], and
+//! physics bodies on them keep simulating unless the reset closure (or the
+//! code releasing them) parks them somewhere harmless.
+//!
+//! ```ignore
+//! let mut bullets = Pool::new(|| (Transform::default(), Sprite::new(), Bullet::default()))
+//!     .reset(|world, e| *world.get_mut::<Bullet>(e).unwrap() = Bullet::default());
+//! bullets.prewarm(&mut ctx.world, 64);
+//!
+//! let b = bullets.acquire(&mut ctx.world);
+//! ctx.world.get_mut::<Transform>(b).unwrap().translation = muzzle;
+//! // when it hits something
+//! bullets.release(&mut ctx.world, b);
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `UnityEngine.Pool.ObjectPool<T>` with create / get / release
+//!   / destroy callbacks; released objects are deactivated with
+//!   `SetActive(false)`.
+//! - **Bevy**: Nothing built in; spawning is cheap enough that pooling is
+//!   rarely done, and `Visibility::Hidden` plus a marker is the usual recipe.
+//! - **Godot**: Nothing built in; nodes are pooled by hand with
+//!   `visible = false` and `process_mode = DISABLED`.
+//! - **Our approach**: Unity's `ObjectPool` shape — a create closure and a
+//!   reset closure — with deactivation as a flag rather than a structural
+//!   change, so recycling never moves an entity between archetypes.
+
+use std::collections::HashSet;
+
+use super::{Entity, SpawnBundle, Visibility, World};
+
+/// Component on every entity a [`Pool`] owns. `active` is `false` while the
+/// entity waits in the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pooled {
+    pub active: bool,
+}
+
+impl Pooled {
+    /// Whether the entity is in use. Pass `None` for entities that aren't
+    /// pooled, which always count as active.
+    pub fn is_active(pooled: Option<&Pooled>) -> bool {
+        pooled.is_none_or(|p| p.active)
+    }
+}
+
+type ResetFn = Box<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+/// A set of pre-spawned entities, all built from the same bundle, that are
+/// handed out and taken back instead of spawned and despawned. See the
+/// [module docs](self).
+pub struct Pool<B: SpawnBundle> {
+    create: Box<dyn Fn() -> B + Send + Sync>,
+    reset: Option<ResetFn>,
+    free: Vec<Entity>,
+    active: HashSet<Entity>,
+}
+
+impl<B: SpawnBundle> Pool<B> {
+    /// An empty pool that spawns new entities from `create`.
+    pub fn new(create: impl Fn() -> B + Send + Sync + 'static) -> Self {
+        Self {
+            create: Box::new(create),
+            reset: None,
+            free: Vec::new(),
+            active: HashSet::new(),
+        }
+    }
+
+    /// Run `reset` on an entity each time it is reused (builder pattern).
+    /// Freshly spawned entities don't need it and skip it.
+    pub fn reset(mut self, reset: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        self.reset = Some(Box::new(reset));
+        self
+    }
+
+    /// Spawn inactive entities until `count` are waiting in the pool.
+    pub fn prewarm(&mut self, world: &mut World, count: usize) {
+        while self.free.len() < count {
+            let entity = self.spawn(world);
+            park(world, entity);
+            self.free.push(entity);
+        }
+    }
+
+    /// Take an entity from the pool, or spawn one if it is empty. The entity
+    /// is active and visible.
+    pub fn acquire(&mut self, world: &mut World) -> Entity {
+        self.reclaim(world);
+        // Entities despawned behind the pool's back are forgotten.
+        while let Some(entity) = self.free.pop() {
+            if !world.is_alive(entity) {
+                continue;
+            }
+            if let Some(reset) = &self.reset {
+                reset(world, entity);
+            }
+            self.activate(world, entity);
+            return entity;
+        }
+        let entity = self.spawn(world);
+        self.activate(world, entity);
+        entity
+    }
+
+    /// Return an entity to the pool: it is hidden and marked inactive.
+    /// Returns `false` if it isn't an active entity of this pool.
+    pub fn release(&mut self, world: &mut World, entity: Entity) -> bool {
+        if !self.active.remove(&entity) || !world.is_alive(entity) {
+            return false;
+        }
+        park(world, entity);
+        self.free.push(entity);
+        true
+    }
+
+    /// Entities currently handed out. Ones destroyed with
+    /// [`World::despawn`] are counted until the next
+    /// [`acquire`](Self::acquire).
+    pub fn active_count(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Entities waiting to be reused.
+    pub fn free_count(&self) -> usize {
+        self.free.len()
+    }
+
+    /// Despawn every entity the pool owns, active or not.
+    pub fn clear(&mut self, world: &mut World) {
+        for entity in self.free.drain(..).chain(self.active.drain()) {
+            world.despawn(entity);
+        }
+    }
+
+    /// Forget handed-out entities that were despawned.
+    fn reclaim(&mut self, world: &World) {
+        self.active.retain(|&entity| world.is_alive(entity));
+    }
+
+    fn spawn(&self, world: &mut World) -> Entity {
+        let entity = world.spawn((self.create)());
+        world.insert(entity, Pooled { active: false });
+        world.insert(entity, Visibility::HIDDEN);
+        entity
+    }
+
+    fn activate(&mut self, world: &mut World, entity: Entity) {
+        world.insert(entity, Pooled { active: true });
+        world.insert(entity, Visibility::VISIBLE);
+        self.active.insert(entity);
+    }
+}
+
+/// Mark a pooled entity inactive and hide it.
+fn park(world: &mut World, entity: Entity) {
+    world.insert(entity, Pooled { active: false });
+    world.insert(entity, Visibility::HIDDEN);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Default)]
+    struct Bullet {
+        hits: u32,
+    }
+
+    fn pool() -> Pool<(Bullet,)> {
+        Pool::new(|| (Bullet::default(),)).reset(|world, e| {
+            *world.get_mut::<Bullet>(e).unwrap() = Bullet::default();
+        })
+    }
+
+    #[test]
+    fn released_entities_are_reused_and_reset() {
+        let mut world = World::new();
+        let mut bullets = pool();
+        bullets.prewarm(&mut world, 4);
+        assert_eq!((bullets.free_count(), bullets.active_count()), (4, 0));
+        let archetypes = world.archetype_count();
+
+        let a = bullets.acquire(&mut world);
+        world.get_mut::<Bullet>(a).unwrap().hits = 3;
+        assert_eq!(world.get::<Pooled>(a), Some(&Pooled { active: true }));
+        assert!(bullets.release(&mut world, a));
+        assert!(!bullets.release(&mut world, a));
+        assert_eq!(world.get::<Visibility>(a), Some(&Visibility::HIDDEN));
+
+        let b = bullets.acquire(&mut world);
+        assert_eq!(a, b);
+        assert_eq!(world.get::<Bullet>(b), Some(&Bullet::default()));
+        assert_eq!(world.get::<Visibility>(b), Some(&Visibility::VISIBLE));
+        assert_eq!(world.entity_count(), 4);
+        assert_eq!(world.archetype_count(), archetypes);
+    }
+
+    #[test]
+    fn empty_pool_spawns_and_skips_dead_entities() {
+        let mut world = World::new();
+        let mut bullets = pool();
+        bullets.prewarm(&mut world, 1);
+        let parked = bullets.acquire(&mut world);
+        bullets.release(&mut world, parked);
+        world.despawn(parked);
+
+        let fresh = bullets.acquire(&mut world);
+        assert_ne!(fresh, parked);
+        assert!(world.is_alive(fresh));
+        assert_eq!(bullets.active_count(), 1);
+
+        bullets.clear(&mut world);
+        assert_eq!(world.entity_count(), 0);
+    }
+
+    #[test]
+    fn despawned_active_entities_are_destroyed_and_forgotten() {
+        let mut world = World::new();
+        let mut bullets = pool();
+        let a = bullets.acquire(&mut world);
+
+        assert!(world.despawn(a));
+        assert!(!world.is_alive(a));
+        assert_eq!(bullets.active_count(), 1);
+
+        let b = bullets.acquire(&mut world);
+        assert_ne!(a, b);
+        assert_eq!((bullets.free_count(), bullets.active_count()), (0, 1));
+        assert!(!bullets.release(&mut world, a));
+        assert_eq!(world.entity_count(), 1);
+    }
+}
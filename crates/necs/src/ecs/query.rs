@@ -12,10 +12,10 @@
 //! });
 //!
 //! 1. Compute TypeIds: [TypeId::of::<Position>(), TypeId::of::<Velocity>()]
-//! 2. For each archetype in the world:
-//!    - Does it contain BOTH Position AND Velocity? (superset check)
-//!    - If yes, extract columns, iterate rows, restore columns
-//! 3. Closure receives (Entity, (&Position, &Velocity)) per matching entity.
+//! 2. Find the archetypes containing BOTH Position AND Velocity (superset
+//!    check) — remembered per signature until a new archetype appears
+//! 3. For each of them: extract columns, iterate rows, restore columns
+//! 4. Closure receives (Entity, (&Position, &Velocity)) per matching entity.
 //! ```
 //!
 //! ## Closure-Based Design
@@ -32,11 +32,33 @@
 //! "fetchable thing" implements. Tuples of query params are themselves query
 //! params, so `(&A, &mut B)` just works.
 //!
+//! ## Parallel Queries
+//!
+//! With the `rayon` feature, `World::query_par` runs the closure on rayon's thread pool. The columns are extracted the same
+//! way, then split into one independent borrow per row
+//! ([`QueryParam::fetch_all`]) — a `&mut T` per entity can't alias another,
+//! so the rows can go to different threads without any `unsafe`:
+//!
+//! ```text
+//!   archetype A: [a0 a1 a2]   archetype B: [b0 b1]
+//!                   └──────────┬─────────────┘
+//!                  rows (Entity, Item) × 5, split across threads
+//!            thread 1: a0 a1       thread 2: a2 b0 b1
+//! ```
+//!
+//! Work is split over rows of all matching archetypes together, so one
+//! large archetype parallelizes as well as many small ones. The closure is
+//! `Fn + Sync` — it may read shared data (borrowed locals included, the call
+//! blocks until every row is done) but mutates only its row.
+//! `World::query_par_scoped` adds a scratch value per worker for results
+//! that have to be gathered.
+//!
 //! ## Comparison
 //!
 //! - **hecs**: Uses `Query` trait on tuples, very similar to our approach.
 //! - **bevy_ecs**: Adds `FilteredAccess`, change detection, etc. Much more
-//!   complex but same core idea.
+//!   complex but same core idea. `Query::par_iter` splits archetype tables
+//!   into batches for its task pool, much like `query_par`.
 
 use std::any::TypeId;
 use std::collections::HashMap;
@@ -45,7 +67,8 @@ use super::component::ComponentColumn;
 
 /// Trait for types that can be fetched from an archetype column.
 ///
-/// Implemented for `&T` (shared read) and `&mut T` (exclusive write).
+/// Implemented for `&T` (shared read), `&mut T` (exclusive write) and
+/// `Option<&T>` (read if present).
 /// Tuple impls allow combining them: `(&A, &mut B, &C)`.
 ///
 /// The `Column` associated type enables the extract/restore pattern: columns
@@ -69,6 +92,10 @@ pub trait QueryParam {
 
     /// Fetch the item for a single entity at `index` from the extracted column.
     fn fetch(col: &mut Self::Column, index: usize) -> Self::Item<'_>;
+
+    /// Fetch the items for all `len` rows at once, as independent borrows.
+    /// Parallel queries hand them out to worker threads.
+    fn fetch_all(col: &mut Self::Column, len: usize) -> Vec<Self::Item<'_>>;
 }
 
 /// Shared read access to a component.
@@ -98,6 +125,10 @@ impl<T: 'static + Send + Sync> QueryParam for &T {
     fn fetch(col: &mut Self::Column, index: usize) -> Self::Item<'_> {
         col.1.get::<T>(index)
     }
+
+    fn fetch_all(col: &mut Self::Column, _len: usize) -> Vec<Self::Item<'_>> {
+        col.1.iter::<T>().collect()
+    }
 }
 
 /// Exclusive write access to a component.
@@ -127,6 +158,43 @@ impl<T: 'static + Send + Sync> QueryParam for &mut T {
     fn fetch(col: &mut Self::Column, index: usize) -> Self::Item<'_> {
         col.1.get_mut::<T>(index)
     }
+
+    fn fetch_all(col: &mut Self::Column, _len: usize) -> Vec<Self::Item<'_>> {
+        col.1.iter_mut::<T>().collect()
+    }
+}
+
+/// Optional read access: `Some(&T)` if the entity has the component, `None`
+/// otherwise. Doesn't affect which archetypes match.
+impl<T: 'static + Send + Sync> QueryParam for Option<&T> {
+    type Item<'w> = Option<&'w T>;
+    type Column = Option<(TypeId, ComponentColumn)>;
+
+    fn type_ids() -> Vec<TypeId> {
+        Vec::new()
+    }
+
+    fn extract(columns: &mut HashMap<TypeId, ComponentColumn>) -> Self::Column {
+        let tid = TypeId::of::<T>();
+        columns.remove(&tid).map(|col| (tid, col))
+    }
+
+    fn restore(col: Self::Column, columns: &mut HashMap<TypeId, ComponentColumn>) {
+        if let Some((tid, col)) = col {
+            columns.insert(tid, col);
+        }
+    }
+
+    fn fetch(col: &mut Self::Column, index: usize) -> Self::Item<'_> {
+        col.as_ref().map(|(_, c)| c.get::<T>(index))
+    }
+
+    fn fetch_all(col: &mut Self::Column, len: usize) -> Vec<Self::Item<'_>> {
+        match col {
+            Some((_, c)) => c.iter::<T>().map(Some).collect(),
+            None => vec![None; len],
+        }
+    }
 }
 
 /// Implement `QueryParam` for tuples of params.
@@ -161,6 +229,15 @@ macro_rules! impl_query_param_tuple {
                 let ($($P,)+) = col;
                 ($($P::fetch($P, index),)+)
             }
+
+            #[allow(non_snake_case)]
+            fn fetch_all(col: &mut Self::Column, len: usize) -> Vec<Self::Item<'_>> {
+                let ($($P,)+) = col;
+                $(let mut $P = $P::fetch_all($P, len).into_iter();)+
+                (0..len)
+                    .map(|_| ($($P.next().expect("query column shorter than its archetype"),)+))
+                    .collect()
+            }
         }
     };
 }
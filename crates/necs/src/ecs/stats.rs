@@ -0,0 +1,119 @@
+//! # Stats — World Introspection for Tools
+//!
+//! [`World::stats`](super::World::stats) takes a snapshot of what the world
+//! holds: how many entities, which component combinations (archetypes) they
+//! fall into, which resources exist, and roughly how much memory all of it
+//! takes. Everything is plain data that derives `Serialize`, so an editor,
+//! profiler, or test harness built on the crate can inspect a running game
+//! in-process or write the snapshot to disk.
+//!
+//! ```text
+//!   WorldStats
+//!   ├─ entity_count            1,204
+//!   ├─ archetypes              [Transform, Sprite, Enemy] × 900
+//!   │                          [Transform, Sprite]        × 300 ...
+//!   ├─ components              Transform: 1,200 × 48 B ...
+//!   ├─ resources               necs::time::Time, necs::input::Input ...
+//!   └─ memory                  components + resources + entity index
+//! ```
+//!
+//! Unlike the telemetry stream (the `diagnostics` feature), this API is
+//! always available and involves no networking.
+//!
+//! ## Memory Estimates
+//!
+//! Sizes are *shallow*: each component or resource counts its own
+//! `size_of` plus the storage slot holding it, but heap data it owns (a
+//! `Vec`'s buffer, a `String`'s bytes) isn't followed. Treat the numbers as a
+//! lower bound that is good for spotting which archetype grew, not for exact
+//! accounting.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The Profiler's Memory module and the Entities "Archetypes"
+//!   window — editor-only tooling.
+//! - **Bevy**: `World::archetypes()`, `World::components()` and
+//!   `iter_resources()` expose the raw metadata; summaries are up to you.
+//! - **Our approach**: One call returns a ready-made summary with stable,
+//!   serializable types; type names are the full Rust paths.
+
+use serde::{Deserialize, Serialize};
+
+/// Snapshot of a [`World`](super::World)'s contents. See the
+/// [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WorldStats {
+    /// Number of alive entities.
+    pub entity_count: usize,
+    /// Non-empty archetypes, most populated first.
+    pub archetypes: Vec<ArchetypeStats>,
+    /// Per component type totals across all archetypes, sorted by name.
+    pub components: Vec<ComponentStats>,
+    /// Every resource currently in the world, sorted by name.
+    pub resources: Vec<ResourceStats>,
+    /// Entities registered under a name.
+    pub named_entities: usize,
+    /// Distinct tags in use.
+    pub tag_count: usize,
+    /// Estimated memory use.
+    pub memory: MemoryEstimate,
+}
+
+impl WorldStats {
+    /// Totals for the component with this full type name, if any entity
+    /// has it.
+    pub fn component(&self, type_name: &str) -> Option<&ComponentStats> {
+        self.components.iter().find(|c| c.type_name == type_name)
+    }
+}
+
+/// One archetype: a set of component types and the entities that have
+/// exactly that set.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ArchetypeStats {
+    /// Component type names, sorted.
+    pub components: Vec<String>,
+    /// Entities in this archetype.
+    pub entity_count: usize,
+    /// Estimated bytes of component storage.
+    pub bytes: usize,
+}
+
+/// Totals for one component type.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComponentStats {
+    /// Full type name, e.g. `necs::math::Transform`.
+    pub type_name: String,
+    /// Entities with this component.
+    pub count: usize,
+    /// Estimated bytes of storage across all archetypes.
+    pub bytes: usize,
+}
+
+/// One resource.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceStats {
+    /// Full type name, e.g. `necs::time::Time`.
+    pub type_name: String,
+    /// Estimated bytes (shallow).
+    pub bytes: usize,
+}
+
+/// Estimated memory use, in bytes. Shallow — see the
+/// [module docs](self#memory-estimates).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEstimate {
+    /// Component values and their storage slots.
+    pub components: usize,
+    /// Resource values and their storage slots.
+    pub resources: usize,
+    /// Entity location index and archetype entity lists.
+    pub entity_index: usize,
+}
+
+impl MemoryEstimate {
+    /// Sum of all categories.
+    pub fn total(&self) -> usize {
+        self.components + self.resources + self.entity_index
+    }
+}
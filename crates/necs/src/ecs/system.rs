@@ -9,48 +9,161 @@
 //! graphs, parallel scheduling. We keep it radically simple:
 //!
 //! - A system is `FnMut(&mut World)`.
-//! - Systems run in the order they're added.
-//! - No automatic parallelism (you can use rayon inside a system if you want).
+//! - Systems run in the order they're added, unless you ask otherwise.
+//! - No automatic parallelism. A system can spread one query over threads
+//!   itself with `World::query_par` (`rayon` feature).
 //!
 //! This is enough for a learning framework. Automatic parallelism is a
 //! significant complexity budget we don't want to spend yet.
 //!
 //! ## Schedule
 //!
-//! A [`Schedule`] is just a `Vec` of systems. Call `run()` and they execute
+//! A [`Schedule`] is a list of systems. Call `run()` and they execute
 //! sequentially. Startup systems run once; regular systems run every frame.
 //!
+//! A schedule runs systems against a [`World`] by default. `Game` keeps its
+//! update, fixed-update and shutdown systems in `Schedule<Context>`s, so
+//! plugins order their `Context` systems the same way:
+//!
+//! ```ignore
+//! game.add_fixed_update_system(physics_step).label("physics");
+//! game.add_fixed_update_system(follow_body).after("physics");
+//! ```
+//!
+//! The context decides what happens around each system through
+//! [`ScheduleContext`]: a `Context` swaps in the system's own
+//! [`SystemLocal`](crate::local::SystemLocal), a `World` does nothing.
+//!
+//! ## Ordering
+//!
+//! Registration order works until two plugins each add systems and one of
+//! them must run first. [`Schedule::add_system`] returns a [`SystemConfig`]
+//! for saying so explicitly:
+//!
+//! ```ignore
+//! schedule.add_system(physics_step).label("physics");
+//! schedule.add_system(camera_follow).after("physics");
+//! schedule.add_system(read_input).in_stage(Stage::PreUpdate);
+//! ```
+//!
+//! ```text
+//!   PreUpdate          Update                      PostUpdate     Render
+//!   ┌──────────┐  ┌────────────────────────────┐  ┌──────────┐  ┌────────┐
+//!   │read_input│─►│physics ──► camera_follow   │─►│   ...    │─►│  ...   │
+//!   └──────────┘  │   (after "physics")        │  └──────────┘  └────────┘
+//!                 └────────────────────────────┘
+//! ```
+//!
+//! - **Stages** ([`Stage`]) run in a fixed order. A system without one is in
+//!   [`Stage::Update`].
+//! - **Labels** name a system, or a group of systems sharing the label.
+//! - **`before` / `after`** order a system against every system with a
+//!   label. Within a stage, systems with no constraint between them keep
+//!   registration order.
+//!
+//! The order is resolved the first time the schedule runs after a system is
+//! added. Constraints that can't be satisfied — a cycle, or `before` pointing
+//! at a system in an earlier stage — are reported by [`Schedule::resolve`];
+//! `run()` logs the error and falls back to stage + registration order rather
+//! than stopping the game. [`Schedule::dump_order`] prints what was decided.
+//!
 //! ## Comparison
 //!
 //! - **hecs**: Doesn't have a built-in system/schedule concept at all.
 //! - **bevy_ecs**: Has `SystemParam` for automatic injection, parallel
-//!   execution with conflict detection, run conditions, etc. Much more complex.
+//!   execution with conflict detection, run conditions, system sets with
+//!   `before`/`after`, and several schedules (`PreUpdate`, `Update`, ...).
+//!   Much more complex.
+//! - **Unity**: `[UpdateBefore]`/`[UpdateAfter]` attributes and system groups
+//!   in Entities; `Script Execution Order` for MonoBehaviours.
 //!
 //! We're closer to hecs: "systems are just functions, scheduling is your
-//! problem." But we do provide a simple `Schedule` for convenience.
+//! problem." But we do provide a simple `Schedule` with Bevy-style labels and
+//! stages for convenience — resolved once into a flat list, run on one
+//! thread.
+//!
+//! ## Panic Quarantine
+//!
+//! With [`Schedule::catch_panics`] enabled, each system runs inside
+//! `catch_unwind`. A panicking system is logged by name and *quarantined* —
+//! skipped on every later `run()` — instead of taking the whole game down.
+//! Handy during live demos; not a substitute for fixing the bug, since a
+//! system that panics halfway through may leave the world half-updated (e.g.
+//! a resource that was extracted and never reinserted).
+
+use std::any::Any;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
 
 use super::world::World;
 
-/// A system that can be executed on a [`World`].
+/// A system that can be executed on a [`World`] (or another
+/// [`ScheduleContext`]).
 ///
 /// Any `FnMut(&mut World)` implements this trait, so you can use closures or
 /// function pointers directly.
-pub trait System {
-    fn run(&mut self, world: &mut World);
+pub trait System<C = World> {
+    fn run(&mut self, ctx: &mut C);
 }
 
-/// Blanket impl: any `FnMut(&mut World)` is a `System`.
-impl<F: FnMut(&mut World)> System for F {
-    fn run(&mut self, world: &mut World) {
-        (self)(world);
+/// Blanket impl: any `FnMut(&mut C)` is a `System<C>`.
+impl<C, F: FnMut(&mut C)> System<C> for F {
+    fn run(&mut self, ctx: &mut C) {
+        (self)(ctx);
     }
 }
 
+/// What a [`Schedule`] runs its systems against.
+///
+/// Each system in the schedule owns a `Local`, handed to [`enter`](Self::enter)
+/// before the system runs and to [`exit`](Self::exit) after it returns (or
+/// panics, under `catch_panics`).
+pub trait ScheduleContext {
+    /// Per-system state kept by the schedule.
+    type Local: Default;
+    fn enter(&mut self, local: &mut Self::Local);
+    fn exit(&mut self, local: &mut Self::Local);
+}
+
+/// Plain world systems have no per-system state.
+impl ScheduleContext for World {
+    type Local = ();
+    fn enter(&mut self, _local: &mut ()) {}
+    fn exit(&mut self, _local: &mut ()) {}
+}
+
+/// A coarse phase of the frame. Every system in an earlier stage runs before
+/// any system in a later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Stage {
+    /// Input, timers — things the rest of the frame reads.
+    PreUpdate,
+    /// Gameplay. The default.
+    #[default]
+    Update,
+    /// Physics, transform and visibility follow-up.
+    PostUpdate,
+    /// Gathering what to draw.
+    Render,
+}
+
+impl Stage {
+    /// All stages, in execution order.
+    pub const ALL: [Stage; 4] = [Stage::PreUpdate, Stage::Update, Stage::PostUpdate, Stage::Render];
+}
+
 /// A named system wrapping a boxed [`System`] with a short name for diagnostics.
-struct NamedSystem {
-    #[cfg(any(feature = "diagnostics", test))]
+struct NamedSystem<C: ScheduleContext> {
     name: String,
-    system: Box<dyn System>,
+    system: Box<dyn System<C>>,
+    local: C::Local,
+    /// Set when the system panicked under `catch_panics`; it is skipped from then on.
+    quarantined: bool,
+    stage: Stage,
+    labels: Vec<&'static str>,
+    before: Vec<&'static str>,
+    after: Vec<&'static str>,
 }
 
 /// Per-system timing recorded during a single frame.
@@ -60,40 +173,187 @@ pub(crate) struct SystemTiming {
     pub duration_us: f64,
 }
 
-/// An ordered list of systems to run.
-pub struct Schedule {
-    systems: Vec<NamedSystem>,
+/// Ordering constraints that can't all be satisfied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleError {
+    /// `before`/`after` constraints form a loop. Holds the names of the
+    /// systems that couldn't be ordered — the loop and anything waiting on
+    /// it — in registration order.
+    Cycle(Vec<String>),
+    /// `first` must run before `then`, but `then` is in an earlier stage.
+    StageConflict { first: String, then: String },
+}
+
+impl fmt::Display for ScheduleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleError::Cycle(names) => {
+                write!(f, "ordering cycle between systems: {}", names.join(", "))
+            }
+            ScheduleError::StageConflict { first, then } => write!(
+                f,
+                "'{first}' must run before '{then}', but '{then}' is in an earlier stage"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ScheduleError {}
+
+/// An ordered list of systems to run against a `C` — a [`World`] unless
+/// stated otherwise.
+pub struct Schedule<C: ScheduleContext = World> {
+    systems: Vec<NamedSystem<C>>,
+    /// Resolved execution order (indices into `systems`); `None` after a
+    /// system is added.
+    order: Option<Vec<usize>>,
+    /// Catch panics per system and quarantine the offender.
+    catch_panics: bool,
     /// Per-system timings from the most recent `run()` call.
     #[cfg(feature = "diagnostics")]
     pub(crate) timings: Vec<SystemTiming>,
 }
 
+/// Ordering options for a system just added with [`Schedule::add_system`].
+pub struct SystemConfig<'a, C: ScheduleContext = World> {
+    system: &'a mut NamedSystem<C>,
+}
+
+impl<C: ScheduleContext> SystemConfig<'_, C> {
+    /// Tag the system with a label other systems can order against. A
+    /// system may have several labels, and a label may cover several systems.
+    pub fn label(self, label: &'static str) -> Self {
+        self.system.labels.push(label);
+        self
+    }
+
+    /// Run before every system labelled `label`.
+    pub fn before(self, label: &'static str) -> Self {
+        self.system.before.push(label);
+        self
+    }
+
+    /// Run after every system labelled `label`.
+    pub fn after(self, label: &'static str) -> Self {
+        self.system.after.push(label);
+        self
+    }
+
+    /// Put the system in a stage other than [`Stage::Update`].
+    pub fn in_stage(self, stage: Stage) -> Self {
+        self.system.stage = stage;
+        self
+    }
+}
+
 impl Schedule {
+    /// An empty schedule of world systems. Use `Schedule::default()` for
+    /// another context.
     pub fn new() -> Self {
-        Self {
-            systems: Vec::new(),
-            #[cfg(feature = "diagnostics")]
-            timings: Vec::new(),
-        }
+        Self::default()
+    }
+}
+
+impl<C: ScheduleContext> Schedule<C> {
+    /// Enable or disable per-system panic recovery (builder pattern).
+    pub fn catch_panics(mut self, enabled: bool) -> Self {
+        self.catch_panics = enabled;
+        self
+    }
+
+    /// Add a system to the schedule. Without further configuration it runs in
+    /// [`Stage::Update`], after the systems added before it.
+    pub fn add_system<S: System<C> + 'static>(&mut self, system: S) -> SystemConfig<'_, C> {
+        self.add_named_system(short_system_name(std::any::type_name::<S>()), system)
     }
 
-    /// Add a system to the end of the schedule.
-    pub fn add_system<S: System + 'static>(&mut self, system: S) {
+    /// [`add_system`](Self::add_system) under a given name, for wrappers
+    /// whose own type name says nothing.
+    pub(crate) fn add_named_system<S: System<C> + 'static>(
+        &mut self,
+        name: String,
+        system: S,
+    ) -> SystemConfig<'_, C> {
+        self.order = None;
         self.systems.push(NamedSystem {
-            #[cfg(any(feature = "diagnostics", test))]
-            name: short_system_name(std::any::type_name::<S>()),
+            name,
             system: Box::new(system),
+            local: C::Local::default(),
+            quarantined: false,
+            stage: Stage::default(),
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+        });
+        SystemConfig { system: self.systems.last_mut().unwrap() }
+    }
+
+    /// Work out the execution order from stages and `before`/`after`
+    /// constraints. Runs automatically on the next `run()` after systems
+    /// change; call it yourself to surface errors early.
+    ///
+    /// On error the schedule falls back to stage + registration order.
+    pub fn resolve(&mut self) -> Result<(), ScheduleError> {
+        let result = resolve_order(&self.systems);
+        self.order = Some(match &result {
+            Ok(order) => order.clone(),
+            Err(_) => fallback_order(&self.systems),
         });
+        result.map(|_| ())
     }
 
-    /// Run all systems in order on the given world.
-    pub fn run(&mut self, world: &mut World) {
+    /// Human-readable execution order, one system per line grouped by stage,
+    /// with labels and quarantine status. Resolves the order first if needed.
+    pub fn dump_order(&mut self) -> String {
+        self.ensure_resolved();
+        let order = self.order.as_deref().unwrap_or_default();
+        let mut out = String::new();
+        for stage in Stage::ALL {
+            let in_stage: Vec<_> = order
+                .iter()
+                .map(|&i| &self.systems[i])
+                .filter(|ns| ns.stage == stage)
+                .collect();
+            if in_stage.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("{stage:?}\n"));
+            for ns in in_stage {
+                out.push_str(&format!("  {}", ns.name));
+                if !ns.labels.is_empty() {
+                    out.push_str(&format!(" [{}]", ns.labels.join(", ")));
+                }
+                if ns.quarantined {
+                    out.push_str(" (quarantined)");
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn ensure_resolved(&mut self) {
+        if self.order.is_none()
+            && let Err(err) = self.resolve()
+        {
+            log::error!("Schedule: {err}; using registration order");
+        }
+    }
+
+    /// Run all systems in order on the given world (or context).
+    pub fn run(&mut self, world: &mut C) {
+        self.ensure_resolved();
+        let order = self.order.as_deref().unwrap_or_default();
         #[cfg(feature = "diagnostics")]
         {
             self.timings.clear();
-            for ns in &mut self.systems {
+            for &i in order {
+                let ns = &mut self.systems[i];
+                if ns.quarantined {
+                    continue;
+                }
                 let start = std::time::Instant::now();
-                ns.system.run(world);
+                Self::run_one(ns, world, self.catch_panics);
                 let elapsed = start.elapsed();
                 self.timings.push(SystemTiming {
                     name: ns.name.clone(),
@@ -103,29 +363,174 @@ impl Schedule {
         }
         #[cfg(not(feature = "diagnostics"))]
         {
-            for ns in &mut self.systems {
-                ns.system.run(world);
+            for &i in order {
+                let ns = &mut self.systems[i];
+                if !ns.quarantined {
+                    Self::run_one(ns, world, self.catch_panics);
+                }
             }
         }
     }
 
+    fn run_one(ns: &mut NamedSystem<C>, world: &mut C, catch_panics: bool) {
+        world.enter(&mut ns.local);
+        let system = &mut ns.system;
+        let panicked = run_guarded(&ns.name, catch_panics, || system.run(world));
+        world.exit(&mut ns.local);
+        if panicked {
+            ns.quarantined = true;
+        }
+    }
+
+    /// Names of systems that panicked and are no longer run.
+    pub fn quarantined(&self) -> Vec<&str> {
+        self.systems
+            .iter()
+            .filter(|ns| ns.quarantined)
+            .map(|ns| ns.name.as_str())
+            .collect()
+    }
+
     /// Returns the number of systems in this schedule.
     pub fn len(&self) -> usize {
         self.systems.len()
     }
 }
 
-impl Default for Schedule {
+impl<C: ScheduleContext> Default for Schedule<C> {
     fn default() -> Self {
-        Self::new()
+        Self {
+            systems: Vec::new(),
+            order: None,
+            catch_panics: false,
+            #[cfg(feature = "diagnostics")]
+            timings: Vec::new(),
+        }
+    }
+}
+
+// ── Order resolution ─────────────────────────────────────────────────────
+
+/// Stage, then registration order. Used when constraints can't be met.
+fn fallback_order<C: ScheduleContext>(systems: &[NamedSystem<C>]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..systems.len()).collect();
+    order.sort_by_key(|&i| systems[i].stage);
+    order
+}
+
+/// Topological sort of `systems` under their stage and `before`/`after`
+/// constraints. Among systems that are free to run, the earliest stage and
+/// then the earliest registered goes first, so unconstrained systems keep
+/// registration order.
+fn resolve_order<C: ScheduleContext>(
+    systems: &[NamedSystem<C>],
+) -> Result<Vec<usize>, ScheduleError> {
+    let mut by_label: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, ns) in systems.iter().enumerate() {
+        for &label in &ns.labels {
+            by_label.entry(label).or_default().push(i);
+        }
+    }
+
+    // Edges `first -> then` within a stage. Edges into a later stage are
+    // satisfied by stage order already; edges into an earlier one can't be.
+    let mut successors = vec![Vec::new(); systems.len()];
+    let mut in_degree = vec![0usize; systems.len()];
+    let mut add_edge = |first: usize, then: usize| -> Result<(), ScheduleError> {
+        if first == then {
+            return Ok(());
+        }
+        match systems[first].stage.cmp(&systems[then].stage) {
+            std::cmp::Ordering::Less => Ok(()),
+            std::cmp::Ordering::Greater => Err(ScheduleError::StageConflict {
+                first: systems[first].name.clone(),
+                then: systems[then].name.clone(),
+            }),
+            std::cmp::Ordering::Equal => {
+                successors[first].push(then);
+                in_degree[then] += 1;
+                Ok(())
+            }
+        }
+    };
+    for (i, ns) in systems.iter().enumerate() {
+        for (labels, is_before) in [(&ns.before, true), (&ns.after, false)] {
+            for &label in labels {
+                let Some(targets) = by_label.get(label) else {
+                    log::warn!("System '{}' is ordered against unknown label '{label}'", ns.name);
+                    continue;
+                };
+                for &j in targets {
+                    if is_before {
+                        add_edge(i, j)?;
+                    } else {
+                        add_edge(j, i)?;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut ready: BTreeSet<(Stage, usize)> = (0..systems.len())
+        .filter(|&i| in_degree[i] == 0)
+        .map(|i| (systems[i].stage, i))
+        .collect();
+    let mut order = Vec::with_capacity(systems.len());
+    while let Some((_, i)) = ready.pop_first() {
+        order.push(i);
+        for &j in &successors[i] {
+            in_degree[j] -= 1;
+            if in_degree[j] == 0 {
+                ready.insert((systems[j].stage, j));
+            }
+        }
+    }
+
+    if order.len() < systems.len() {
+        let names = (0..systems.len())
+            .filter(|&i| in_degree[i] > 0)
+            .map(|i| systems[i].name.clone())
+            .collect();
+        return Err(ScheduleError::Cycle(names));
+    }
+    Ok(order)
+}
+
+/// Run the system `name` through `run`. With `catch_panics`, a panic is
+/// caught and logged instead of unwinding further, and `true` is returned:
+/// the caller should quarantine the system.
+fn run_guarded(name: &str, catch_panics: bool, run: impl FnOnce()) -> bool {
+    if !catch_panics {
+        run();
+        return false;
+    }
+    match panic::catch_unwind(AssertUnwindSafe(run)) {
+        Ok(()) => false,
+        Err(payload) => {
+            log::error!(
+                "System '{name}' panicked and was quarantined: {}",
+                panic_message(payload.as_ref())
+            );
+            true
+        }
+    }
+}
+
+/// Extract a readable message from a `catch_unwind` panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "<non-string panic payload>".to_string()
     }
 }
 
 /// Strip the module path from a fully-qualified type name, keeping only the
 /// last meaningful segment (e.g. `hello_2d::movement_system` → `movement_system`,
 /// `{{closure}}` → `<closure>`).
-#[cfg(any(feature = "diagnostics", test))]
-fn short_system_name(full: &str) -> String {
+pub(crate) fn short_system_name(full: &str) -> String {
     let name = full.rsplit("::").next().unwrap_or(full);
     if name.contains("closure") {
         "<closure>".to_string()
@@ -154,4 +559,121 @@ mod tests {
         schedule.add_system(|_world: &mut World| {});
         assert_eq!(schedule.systems[0].name, "<closure>");
     }
+
+    fn panicking_system(_world: &mut World) {
+        panic!("boom");
+    }
+
+    #[test]
+    fn panicking_system_is_quarantined() {
+        struct Counter(u32);
+
+        let mut world = World::new();
+        world.insert_resource(Counter(0));
+        let mut schedule = Schedule::new().catch_panics(true);
+        schedule.add_system(panicking_system);
+        schedule.add_system(|world: &mut World| world.resource_mut::<Counter>().0 += 1);
+
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(schedule.quarantined(), vec!["panicking_system"]);
+        assert_eq!(world.resource::<Counter>().0, 2);
+    }
+
+    #[test]
+    fn panic_message_handles_both_payload_kinds() {
+        let static_str = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(panic_message(static_str.as_ref()), "static");
+        let formatted = panic::catch_unwind(|| panic!("{}", 42)).unwrap_err();
+        assert_eq!(panic_message(formatted.as_ref()), "42");
+    }
+
+    /// Records which systems ran, by tag, in a `Vec<&str>` resource.
+    fn push(tag: &'static str) -> impl FnMut(&mut World) {
+        move |world: &mut World| world.resource_mut::<Vec<&'static str>>().push(tag)
+    }
+
+    fn run_order(schedule: &mut Schedule) -> Vec<&'static str> {
+        let mut world = World::new();
+        world.insert_resource(Vec::<&'static str>::new());
+        schedule.run(&mut world);
+        world.resource::<Vec<&'static str>>().clone()
+    }
+
+    #[test]
+    fn constraints_and_stages_reorder_systems() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(push("render")).in_stage(Stage::Render);
+        schedule.add_system(push("camera")).after("physics");
+        schedule.add_system(push("physics")).label("physics");
+        schedule.add_system(push("ai")).before("physics");
+        schedule.add_system(push("input")).in_stage(Stage::PreUpdate);
+        schedule.add_system(push("audio"));
+
+        assert_eq!(
+            run_order(&mut schedule),
+            vec!["input", "ai", "physics", "camera", "audio", "render"]
+        );
+        assert_eq!(
+            schedule.dump_order(),
+            "PreUpdate\n  <closure>\n\
+             Update\n  <closure>\n  <closure> [physics]\n  <closure>\n  <closure>\n\
+             Render\n  <closure>\n"
+        );
+    }
+
+    #[test]
+    fn cycle_is_reported_and_falls_back_to_registration_order() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(push("a")).label("a").after("b");
+        schedule.add_system(push("b")).label("b").after("a");
+        schedule.add_system(push("c")).in_stage(Stage::PreUpdate);
+
+        assert!(matches!(schedule.resolve(), Err(ScheduleError::Cycle(names)) if names.len() == 2));
+        assert_eq!(run_order(&mut schedule), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn context_systems_are_ordered_and_keep_their_own_locals() {
+        use crate::context::Context;
+
+        let mut schedule = Schedule::<Context>::default();
+        schedule
+            .add_system(|ctx: &mut Context| {
+                *ctx.local.get::<u32>() += 10;
+                let seen = *ctx.local.get::<u32>();
+                ctx.world.resource_mut::<Vec<u32>>().push(seen);
+            })
+            .after("first");
+        schedule
+            .add_system(|ctx: &mut Context| {
+                *ctx.local.get::<u32>() += 1;
+                let seen = *ctx.local.get::<u32>();
+                ctx.world.resource_mut::<Vec<u32>>().push(seen);
+            })
+            .label("first");
+
+        let mut ctx = Context::new();
+        ctx.world.insert_resource(Vec::<u32>::new());
+        schedule.run(&mut ctx);
+        schedule.run(&mut ctx);
+        assert_eq!(*ctx.world.resource::<Vec<u32>>(), vec![1, 10, 2, 20]);
+        assert_eq!(*ctx.local.get::<u32>(), 0);
+    }
+
+    #[test]
+    fn before_an_earlier_stage_is_a_conflict() {
+        let mut schedule = Schedule::new();
+        schedule.add_system(push("input")).label("input").in_stage(Stage::PreUpdate);
+        schedule.add_system(dummy_system).before("input");
+
+        assert_eq!(
+            schedule.resolve(),
+            Err(ScheduleError::StageConflict {
+                first: "dummy_system".to_string(),
+                then: "<closure>".to_string(),
+            })
+        );
+    }
 }
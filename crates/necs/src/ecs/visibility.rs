@@ -0,0 +1,214 @@
+//! # Visibility — Hiding Whole Subtrees
+//!
+//! [`Visibility`] is a switch you flip on an entity; [`ComputedVisibility`]
+//! is what the renderers actually check. Once per frame, right after
+//! transform propagation, [`propagate_visibility`] walks the hierarchy and
+//! combines the two: an entity is visible only if it *and every ancestor*
+//! is visible.
+//!
+//! ```text
+//!   ship        Visibility(false)   ──►  computed: hidden
+//!   ├─ turret   (none)              ──►  computed: hidden  (inherited)
+//!   └─ engine   Visibility(true)    ──►  computed: hidden  (parent wins)
+//!   crate       Hidden              ──►  computed: hidden
+//!   └─ label    (none)              ──►  no ComputedVisibility: drawn
+//!   buoy        (none)              ──►  no ComputedVisibility: drawn
+//! ```
+//!
+//! The [`Hidden`] marker hides only the entity it's on; its children are
+//! still drawn. It is folded into the same `ComputedVisibility`, so sprite
+//! and mesh drawing, UI layout and hit testing, and the physics debug
+//! wireframes all check that one component.
+//!
+//! Entities outside any tree that uses `Visibility`, and not `Hidden`
+//! themselves, never get a `ComputedVisibility` and are drawn as before.
+//! Hidden entities keep all their components and keep simulating; they just
+//! aren't drawn. A change shows up once `propagate_visibility` has run, which
+//! the game loop does each frame before layout and rendering.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `GameObject.SetActive(false)` deactivates the subtree,
+//!   disabling scripts and physics too; `Renderer.enabled` hides one object.
+//! - **Bevy**: `Visibility` (`Inherited`/`Hidden`/`Visible`) propagated into
+//!   `InheritedVisibility` and `ViewVisibility`.
+//! - **Godot**: `CanvasItem.visible` / `Node3D.visible`, inherited by
+//!   children.
+//! - **Our approach**: A boolean per entity and one computed component, in
+//!   the Godot spirit. Only rendering is affected.
+
+use std::collections::VecDeque;
+
+use super::{Children, Entity, Parent, World};
+
+/// Show or hide an entity and all of its descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Visibility {
+    pub visible: bool,
+}
+
+impl Visibility {
+    /// Drawn, as long as every ancestor is too.
+    pub const VISIBLE: Self = Self { visible: true };
+    /// Not drawn, and neither are descendants.
+    pub const HIDDEN: Self = Self { visible: false };
+
+    /// Visibility from a flag.
+    pub fn new(visible: bool) -> Self {
+        Self { visible }
+    }
+}
+
+impl Default for Visibility {
+    fn default() -> Self {
+        Self::VISIBLE
+    }
+}
+
+/// Marker component: don't draw this entity. Its children are still drawn
+/// unless they are hidden too — use [`Visibility`] to hide a subtree, or
+/// [`World::set_visibility_tagged`] to hide a whole group, descendants
+/// included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Hidden;
+
+/// Effective visibility after combining the entity's own [`Visibility`]
+/// and [`Hidden`] with its ancestors' `Visibility`. Written by
+/// [`propagate_visibility`]; don't insert it yourself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComputedVisibility {
+    pub visible: bool,
+}
+
+/// Whether a renderer should skip an entity with this (optional)
+/// [`ComputedVisibility`].
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+pub(crate) fn is_hidden(computed: Option<&ComputedVisibility>) -> bool {
+    computed.is_some_and(|c| !c.visible)
+}
+
+/// Compute [`ComputedVisibility`] for every entity under a [`Visibility`],
+/// and for every [`Hidden`] one.
+///
+/// Traversal is BFS from parentless entities, like
+/// [`propagate_transforms`](crate::ecs::propagate_transforms), so parents
+/// are resolved before their children.
+pub fn propagate_visibility(world: &mut World) {
+    // Roots: any parentless entity that has children, a Visibility, Hidden,
+    // or a stale ComputedVisibility to clean up.
+    let mut candidates = Vec::new();
+    world.query::<(&Children,)>(|entity, _| candidates.push(entity));
+    world.query::<(&Visibility,)>(|entity, _| candidates.push(entity));
+    world.query::<(&Hidden,)>(|entity, _| candidates.push(entity));
+    world.query::<(&ComputedVisibility,)>(|entity, _| candidates.push(entity));
+    candidates.sort_unstable_by_key(|e| (e.index(), e.generation()));
+    candidates.dedup();
+
+    // `None` = no ancestor has a Visibility, so nothing to inherit.
+    let mut queue: VecDeque<(Entity, Option<bool>)> = candidates
+        .into_iter()
+        .filter(|&entity| world.get::<Parent>(entity).is_none())
+        .map(|entity| (entity, None))
+        .collect();
+
+    while let Some((entity, inherited)) = queue.pop_front() {
+        if !world.is_alive(entity) {
+            continue;
+        }
+        let own = world.get::<Visibility>(entity).map(|v| v.visible);
+        // What children inherit: `Hidden` stays out of it.
+        let subtree = match (inherited, own) {
+            (None, None) => None,
+            _ => Some(inherited.unwrap_or(true) && own.unwrap_or(true)),
+        };
+        let computed = match world.get::<Hidden>(entity) {
+            Some(_) => Some(false),
+            None => subtree,
+        };
+
+        match computed {
+            Some(visible) => match world.get_mut::<ComputedVisibility>(entity) {
+                Some(c) => c.visible = visible,
+                None => world.insert(entity, ComputedVisibility { visible }),
+            },
+            None => {
+                world.remove::<ComputedVisibility>(entity);
+            }
+        }
+
+        if let Some(children) = world.get::<Children>(entity) {
+            queue.extend(children.0.iter().map(|&child| (child, subtree)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Transform;
+
+    fn computed(world: &World, entity: Entity) -> Option<bool> {
+        world.get::<ComputedVisibility>(entity).map(|c| c.visible)
+    }
+
+    #[test]
+    fn hidden_parent_hides_subtree() {
+        let mut world = World::new();
+        let root = world.spawn((Transform::default(), Visibility::HIDDEN));
+        let child = world.spawn_child(root, (Transform::default(),));
+        let grandchild = world.spawn_child(child, (Transform::default(), Visibility::VISIBLE));
+        let loose = world.spawn((Transform::default(),));
+
+        propagate_visibility(&mut world);
+        assert_eq!(computed(&world, root), Some(false));
+        assert_eq!(computed(&world, child), Some(false));
+        assert_eq!(computed(&world, grandchild), Some(false));
+        assert_eq!(computed(&world, loose), None);
+
+        world.insert(root, Visibility::VISIBLE);
+        world.insert(child, Visibility::HIDDEN);
+        propagate_visibility(&mut world);
+        assert_eq!(computed(&world, root), Some(true));
+        assert_eq!(computed(&world, child), Some(false));
+        assert_eq!(computed(&world, grandchild), Some(false));
+    }
+
+    #[test]
+    fn removing_visibility_clears_computed() {
+        let mut world = World::new();
+        let root = world.spawn((Transform::default(), Visibility::HIDDEN));
+        let child = world.spawn_child(root, (Transform::default(),));
+        propagate_visibility(&mut world);
+        assert!(is_hidden(world.get::<ComputedVisibility>(child)));
+
+        world.remove::<Visibility>(root);
+        propagate_visibility(&mut world);
+        assert_eq!(computed(&world, root), None);
+        assert_eq!(computed(&world, child), None);
+        assert!(!is_hidden(world.get::<ComputedVisibility>(child)));
+    }
+
+    #[test]
+    fn hidden_marker_hides_only_its_entity() {
+        let mut world = World::new();
+        let root = world.spawn((Transform::default(), Hidden));
+        let child = world.spawn_child(root, (Transform::default(), Hidden));
+        let grandchild = world.spawn_child(child, (Transform::default(),));
+        let loose = world.spawn((Transform::default(), Hidden));
+
+        propagate_visibility(&mut world);
+        assert_eq!(computed(&world, root), Some(false));
+        assert_eq!(computed(&world, child), Some(false));
+        assert_eq!(computed(&world, grandchild), None);
+        assert_eq!(computed(&world, loose), Some(false));
+
+        // Under a hidden Visibility, Hidden changes nothing; without either
+        // the computed state goes away.
+        world.insert(root, Visibility::HIDDEN);
+        world.remove::<Hidden>(child);
+        world.remove::<Hidden>(loose);
+        propagate_visibility(&mut world);
+        assert_eq!(computed(&world, grandchild), Some(false));
+        assert_eq!(computed(&world, loose), None);
+    }
+}
@@ -18,6 +18,9 @@
 //! │  entity_locations: HashMap<u32, EntityLocation>     │
 //! │    maps entity index → (archetype key, row index)   │
 //! │                                                     │
+//! │  query_cache: HashMap<QuerySignature, [keys]>       │
+//! │    archetypes matching each query seen so far       │
+//! │                                                     │
 //! │  resources: HashMap<TypeId, Box<dyn Any>>           │
 //! │    singleton data not tied to an entity              │
 //! └─────────────────────────────────────────────────────┘
@@ -26,8 +29,46 @@
 //! ## Resources
 //!
 //! Resources are "global" data — things like the `Time`, `Input` state, or
-//! `AssetServer`. They're stored as type-erased `Box<dyn Any>` in a HashMap.
-//! This is simpler than making them entities with special components.
+//! `AssetServer`. They're stored as type-erased `Box<dyn Any + Send + Sync>` in
+//! a HashMap. This is simpler than making them entities with special
+//! components. Components and resources alike must be `Send + Sync`, so a
+//! whole `World` can move to another thread — the
+//! [render thread](crate::render::pipelined) renders from one.
+//!
+//! ## Query Cache
+//!
+//! A query only visits archetypes holding all of its components. Working
+//! that out means checking every archetype, which adds up when a world has
+//! hundreds of them and dozens of systems query each frame. The answer only
+//! changes when an archetype is created (archetypes are never removed), so
+//! the world remembers it per query signature — required types plus the
+//! excluded one — and forgets everything when a new archetype appears:
+//!
+//! ```text
+//!   query::<(&Transform, &Sprite)>   signature {Transform, Sprite}
+//!     cached? ──yes──► [k3, k7, k12]           no scan
+//!        └──no──► scan archetypes, remember the result
+//!
+//!   spawn/insert/remove creates an archetype ──► cache cleared
+//! ```
+//!
+//! In a running game new archetypes stop appearing after the first few
+//! frames, so from then on queries never scan.
+//!
+//! ## Tags
+//!
+//! Tags are string labels for ad-hoc groups — "enemies", "wave_3", "hud" —
+//! that don't deserve a marker type. Alongside [`World::tagged`], groups can
+//! be changed as a whole without the collect-then-loop dance:
+//!
+//! ```text
+//!   tags: "enemies" → {e4, e7, e9}
+//!
+//!   world.query_tagged::<(&mut Transform,)>("enemies", ..)  only e4, e7, e9
+//!   world.set_visibility_tagged("enemies", false)           Visibility, children inherit
+//!   world.insert_tagged("enemies", Frozen)                  + Frozen
+//!   world.despawn_tagged("enemies")                         gone (with children)
+//! ```
 //!
 //! ## Comparison
 //!
@@ -40,11 +81,14 @@
 
 use std::any::{Any, TypeId};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use super::archetype::{Archetype, ArchetypeKey, archetype_key};
 use super::component::{ComponentColumn, component_type_id};
 use super::entity::{Entity, EntityAllocator};
+use super::event::Events;
 use super::query::QueryParam;
+use super::stats::{ArchetypeStats, ComponentStats, MemoryEstimate, ResourceStats, WorldStats};
 
 /// Location of an entity within the archetype storage.
 #[derive(Clone)]
@@ -55,6 +99,14 @@ pub(crate) struct EntityLocation {
     row: usize,
 }
 
+/// What decides which archetypes a query visits: the component types it
+/// requires (sorted) and the one it excludes, if any.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct QuerySignature {
+    required: ArchetypeKey,
+    excluded: Option<TypeId>,
+}
+
 /// The central container for all game state.
 ///
 /// Owns all entities, their components (organized into archetypes), and
@@ -65,8 +117,13 @@ pub struct World {
     archetypes: HashMap<ArchetypeKey, Archetype>,
     /// Maps entity index → its location in archetype storage.
     entity_locations: HashMap<u32, EntityLocation>,
+    /// Archetypes matching each query signature. Cleared whenever an
+    /// archetype is created.
+    query_cache: HashMap<QuerySignature, Arc<[ArchetypeKey]>>,
     /// Global resources (singletons), keyed by TypeId.
-    resources: HashMap<TypeId, Box<dyn Any>>,
+    resources: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+    /// Type names of the resources above, for introspection.
+    resource_names: HashMap<TypeId, &'static str>,
     /// Named entity lookup: name → entity.
     names: HashMap<String, Entity>,
     /// Reverse lookup: entity index → name.
@@ -89,7 +146,9 @@ impl World {
             allocator: EntityAllocator::new(),
             archetypes: HashMap::new(),
             entity_locations: HashMap::new(),
+            query_cache: HashMap::new(),
             resources: HashMap::new(),
+            resource_names: HashMap::new(),
             names: HashMap::new(),
             names_reverse: HashMap::new(),
             tags: HashMap::new(),
@@ -107,6 +166,8 @@ impl World {
     /// the same type.
     pub fn insert_resource<T: 'static + Send + Sync>(&mut self, value: T) {
         self.resources.insert(TypeId::of::<T>(), Box::new(value));
+        self.resource_names
+            .insert(TypeId::of::<T>(), std::any::type_name::<T>());
     }
 
     /// Get a shared reference to a resource.
@@ -169,12 +230,32 @@ impl World {
     /// Use this for the extract/reinsert pattern when you need to borrow a
     /// resource while also borrowing the world (e.g., during rendering).
     pub fn resource_remove<T: 'static + Send + Sync>(&mut self) -> Option<T> {
+        self.resource_names.remove(&TypeId::of::<T>());
         self.resources
             .remove(&TypeId::of::<T>())
             .and_then(|r| r.downcast::<T>().ok())
             .map(|b| *b)
     }
 
+    /// Send an event into its [`Events<T>`] queue. Logs a warning and drops
+    /// the event if the event type was never registered.
+    pub fn send_event<T: 'static + Send + Sync>(&mut self, event: T) {
+        match self.get_resource_mut::<Events<T>>() {
+            Some(events) => events.send(event),
+            None => log::warn!(
+                "send_event: {} is not registered (use Game::event)",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
+    /// Drop every resource. Used by shutdown teardown, after the resources
+    /// that need a specific drop order have been removed.
+    pub(crate) fn clear_resources(&mut self) {
+        self.resources.clear();
+        self.resource_names.clear();
+    }
+
     /// Check if any non-empty archetype contains a component of type `T`.
     pub(crate) fn has_component_type<T: 'static + Send + Sync>(&self) -> bool {
         let type_id = TypeId::of::<T>();
@@ -201,6 +282,19 @@ impl World {
         self.names.get(name).copied()
     }
 
+    /// Find the entity with a stable [`Uid`](crate::scene::Uid) — how to hold
+    /// on to a scene entity across unloading and reloading its scene. Scans
+    /// the entities that have one, so look up once and keep the `Entity`
+    /// for the rest of the frame.
+    pub fn by_uid(&self, uid: crate::scene::Uid) -> Option<Entity> {
+        let tid = TypeId::of::<crate::scene::Uid>();
+        self.archetypes
+            .iter()
+            .filter(|(key, _)| key.contains(&tid))
+            .flat_map(|(_, arch)| arch.entities.iter().copied())
+            .find(|&entity| self.get::<crate::scene::Uid>(entity) == Some(&uid))
+    }
+
     /// Assign a name to an entity. Used internally by Context::spawn().
     ///
     /// # Panics
@@ -220,24 +314,103 @@ impl World {
     // ── Tags ──────────────────────────────────────────────────────────
 
     /// Add a tag to an entity. An entity can have multiple tags,
-    /// and many entities can share the same tag.
+    /// and many entities can share the same tag. Tagging twice is a no-op.
     pub fn tag(&mut self, entity: Entity, tag: &str) {
-        self.tags
+        let added = self
+            .tags
             .entry(tag.to_string())
             .or_insert_with(HashSet::new)
             .insert(entity);
-        self.entity_tags
-            .entry(entity.index())
-            .or_insert_with(Vec::new)
-            .push(tag.to_string());
+        if added {
+            self.entity_tags
+                .entry(entity.index())
+                .or_insert_with(Vec::new)
+                .push(tag.to_string());
+        }
+    }
+
+    /// Remove a tag from an entity. Returns `true` if the entity had it.
+    pub fn untag(&mut self, entity: Entity, tag: &str) -> bool {
+        let Some(set) = self.tags.get_mut(tag) else {
+            return false;
+        };
+        if !set.remove(&entity) {
+            return false;
+        }
+        if set.is_empty() {
+            self.tags.remove(tag);
+        }
+        if let Some(tags) = self.entity_tags.get_mut(&entity.index()) {
+            tags.retain(|t| t != tag);
+            if tags.is_empty() {
+                self.entity_tags.remove(&entity.index());
+            }
+        }
+        true
+    }
+
+    /// Whether an entity has a tag.
+    pub fn has_tag(&self, entity: Entity, tag: &str) -> bool {
+        self.tags.get(tag).is_some_and(|set| set.contains(&entity))
     }
 
     /// Get all entities with a given tag.
     pub fn tagged(&self, tag: &str) -> Vec<Entity> {
-        self.tags
-            .get(tag)
-            .map(|set| set.iter().copied().collect())
-            .unwrap_or_default()
+        self.iter_tagged(tag).collect()
+    }
+
+    /// Iterate over the entities with a tag without collecting them.
+    ///
+    /// The world can't be modified while iterating; use [`tagged`](Self::tagged)
+    /// or one of the `*_tagged` bulk operations for that.
+    pub fn iter_tagged(&self, tag: &str) -> impl Iterator<Item = Entity> + '_ {
+        self.tags.get(tag).into_iter().flatten().copied()
+    }
+
+    /// Number of entities with a tag.
+    pub fn tagged_count(&self, tag: &str) -> usize {
+        self.tags.get(tag).map_or(0, HashSet::len)
+    }
+
+    /// Despawn every entity with a tag, along with its descendants.
+    ///
+    /// Returns the number of tagged entities despawned.
+    pub fn despawn_tagged(&mut self, tag: &str) -> usize {
+        let entities = self.tagged(tag);
+        let count = entities.len();
+        for entity in entities {
+            // May already be gone as the descendant of another tagged entity.
+            self.despawn_recursive(entity);
+        }
+        count
+    }
+
+    /// Show or hide every entity with a tag, along with its descendants, by
+    /// setting the tagged entities' [`Visibility`](super::Visibility).
+    ///
+    /// Descendants inherit it through
+    /// [`propagate_visibility`](super::propagate_visibility) and keep their
+    /// own state: a child hidden on its own stays hidden when the group is
+    /// shown again.
+    pub fn set_visibility_tagged(&mut self, tag: &str, visible: bool) {
+        for entity in self.tagged(tag) {
+            self.insert(entity, super::Visibility::new(visible));
+        }
+    }
+
+    /// Insert a clone of `component` on every entity with a tag, replacing
+    /// any existing value.
+    pub fn insert_tagged<T: Clone + 'static + Send + Sync>(&mut self, tag: &str, component: T) {
+        for entity in self.tagged(tag) {
+            self.insert(entity, component.clone());
+        }
+    }
+
+    /// Remove component `T` from every entity with a tag that has it.
+    pub fn remove_tagged<T: 'static + Send + Sync>(&mut self, tag: &str) {
+        for entity in self.tagged(tag) {
+            self.remove::<T>(entity);
+        }
     }
 
     // ── Editor helpers ─────────────────────────────────────────────
@@ -283,6 +456,28 @@ impl World {
         arch.type_name_map.values().map(|s| s.as_ref()).collect()
     }
 
+    /// Type id and type name of every component on an entity.
+    #[cfg_attr(not(feature = "editor"), allow(dead_code))]
+    pub(crate) fn component_types(&self, entity: Entity) -> Vec<(TypeId, &str)> {
+        let Some(loc) = self.entity_locations.get(&entity.index) else {
+            return Vec::new();
+        };
+        let Some(arch) = self.archetypes.get(&loc.archetype_key) else {
+            return Vec::new();
+        };
+        arch.type_name_map.iter().map(|(&id, &name)| (id, name)).collect()
+    }
+
+    /// Type ids of all components on an entity (empty if it's dead).
+    pub(crate) fn component_type_ids(&self, entity: Entity) -> &[TypeId] {
+        if !self.allocator.is_alive(entity) {
+            return &[];
+        }
+        self.entity_locations
+            .get(&entity.index)
+            .map_or(&[], |loc| &loc.archetype_key)
+    }
+
     /// Collect all entities that have a component of type `T`.
     pub fn entities_with<T: 'static + Send + Sync>(&self) -> Vec<Entity> {
         let type_id = TypeId::of::<T>();
@@ -312,6 +507,88 @@ impl World {
         self.allocator.is_alive(entity)
     }
 
+    /// Summarize the world's contents for tools: entity and archetype
+    /// counts, component and resource type names, and memory estimates.
+    /// See [`WorldStats`](super::stats::WorldStats).
+    pub fn stats(&self) -> WorldStats {
+        let mut archetypes = Vec::new();
+        let mut components: HashMap<TypeId, ComponentStats> = HashMap::new();
+        let mut component_bytes = 0;
+        let mut entity_index = self.entity_locations.capacity()
+            * std::mem::size_of::<(u32, EntityLocation)>();
+
+        for (key, arch) in &self.archetypes {
+            if arch.entities.is_empty() {
+                continue;
+            }
+            entity_index += arch.entities.capacity() * std::mem::size_of::<Entity>();
+
+            let mut names = Vec::with_capacity(key.len());
+            let mut bytes = 0;
+            for tid in key {
+                let name = arch
+                    .type_name_map
+                    .get(tid)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| format!("{tid:?}"));
+                let column_bytes = arch.columns.get(tid).map_or(0, ComponentColumn::memory_bytes);
+                bytes += column_bytes;
+
+                let totals = components.entry(*tid).or_insert_with(|| ComponentStats {
+                    type_name: name.clone(),
+                    ..Default::default()
+                });
+                totals.count += arch.entities.len();
+                totals.bytes += column_bytes;
+                names.push(name);
+            }
+            names.sort();
+            component_bytes += bytes;
+            archetypes.push(ArchetypeStats {
+                components: names,
+                entity_count: arch.entities.len(),
+                bytes,
+            });
+        }
+        archetypes.sort_by(|a, b| {
+            b.entity_count
+                .cmp(&a.entity_count)
+                .then_with(|| a.components.cmp(&b.components))
+        });
+
+        let mut components: Vec<ComponentStats> = components.into_values().collect();
+        components.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+
+        let slot = std::mem::size_of::<(TypeId, Box<dyn Any + Send + Sync>)>();
+        let mut resources: Vec<ResourceStats> = self
+            .resources
+            .iter()
+            .map(|(tid, value)| ResourceStats {
+                type_name: self
+                    .resource_names
+                    .get(tid)
+                    .map(|n| n.to_string())
+                    .unwrap_or_else(|| format!("{tid:?}")),
+                bytes: slot + std::mem::size_of_val(&**value),
+            })
+            .collect();
+        resources.sort_by(|a, b| a.type_name.cmp(&b.type_name));
+
+        WorldStats {
+            entity_count: self.allocator.alive_count(),
+            memory: MemoryEstimate {
+                components: component_bytes,
+                resources: resources.iter().map(|r| r.bytes).sum(),
+                entity_index,
+            },
+            archetypes,
+            components,
+            resources,
+            named_entities: self.names.len(),
+            tag_count: self.tags.len(),
+        }
+    }
+
     /// Collect a diagnostics snapshot of ECS state.
     ///
     /// Returns (entity_count, archetype_count, archetype_infos).
@@ -435,9 +712,9 @@ impl World {
         #[cfg(feature = "diagnostics")]
         { self.spawned_this_frame += 1; }
         let key = archetype_key(vec![]);
-        self.archetypes
-            .entry(key.clone())
-            .or_insert_with(|| Archetype::new(HashMap::new()));
+        if !self.archetypes.contains_key(&key) {
+            self.add_archetype(key.clone(), HashMap::new());
+        }
         let arch = self.archetypes.get_mut(&key).unwrap();
         let row = arch.entities.len();
         arch.entities.push(entity);
@@ -480,45 +757,118 @@ impl World {
         child
     }
 
+    /// Spawn a copy of a prefab and its hierarchy. Returns the new root, or
+    /// `None` (logged) if the world has no `SceneRegistry` resource or the
+    /// prefab doesn't decode.
+    pub fn instantiate(&mut self, prefab: &crate::prefab::Prefab) -> Option<Entity> {
+        crate::prefab::instantiate(self, prefab, &crate::prefab::PrefabOverrides::default())
+    }
+
+    /// [`instantiate`](Self::instantiate) with per-instance overrides.
+    pub fn instantiate_with(
+        &mut self,
+        prefab: &crate::prefab::Prefab,
+        overrides: &crate::prefab::PrefabOverrides,
+    ) -> Option<Entity> {
+        crate::prefab::instantiate(self, prefab, overrides)
+    }
+
     /// Despawn an entity and all its descendants recursively.
     ///
     /// Also removes the entity from its parent's [`Children`] list if it has one.
     ///
     /// Returns `true` if the entity was alive and successfully despawned.
     pub fn despawn_recursive(&mut self, entity: Entity) -> bool {
-        use crate::ecs::hierarchy::{Children, Parent};
-
         if !self.allocator.is_alive(entity) {
             return false;
         }
 
-        // Remove from parent's Children list.
-        if let Some(parent_entity) = self.get::<Parent>(entity).map(|p| p.0) {
-            if let Some(children) = self.get_mut::<Children>(parent_entity) {
-                children.0.retain(|&c| c != entity);
-            }
+        self.detach_from_parent(entity);
+
+        // Despawn the entity and all its descendants.
+        for e in self.with_descendants(entity) {
+            self.despawn(e);
         }
 
-        // Collect all descendants via BFS.
-        let mut to_despawn = vec![entity];
-        let mut i = 0;
-        while i < to_despawn.len() {
-            let current = to_despawn[i];
-            if let Some(children) = self.get::<Children>(current) {
-                let child_list: Vec<_> = children.0.clone();
-                to_despawn.extend(child_list);
-            }
-            i += 1;
+        true
+    }
+
+    /// An entity followed by all its descendants, breadth-first.
+    fn with_descendants(&self, entity: Entity) -> Vec<Entity> {
+        std::iter::once(entity).chain(self.descendants(entity)).collect()
+    }
+
+    // ── Hierarchy ────────────────────────────────────────────────────
+
+    /// All descendants of `entity` (children, grandchildren, ...),
+    /// breadth-first. Empty for an entity without [`Children`].
+    pub fn descendants(&self, entity: Entity) -> crate::ecs::hierarchy::Descendants<'_> {
+        crate::ecs::hierarchy::Descendants::new(self, entity)
+    }
+
+    /// The parent of `entity`, its parent, and so on up to the root.
+    pub fn ancestors(&self, entity: Entity) -> crate::ecs::hierarchy::Ancestors<'_> {
+        crate::ecs::hierarchy::Ancestors::new(self, entity)
+    }
+
+    /// Move `child` (and its subtree) under `parent`, keeping its local
+    /// [`Transform`]. Updates both parents' [`Children`], the child's
+    /// [`Parent`], and the subtree's [`GlobalTransform`]s.
+    ///
+    /// Returns `false` and changes nothing if either entity is dead, or if
+    /// `parent` is `child` or one of its descendants.
+    pub fn set_parent(&mut self, child: Entity, parent: Entity) -> bool {
+        use crate::ecs::hierarchy::{Children, Parent, world_matrix};
+
+        if !self.is_alive(child)
+            || !self.is_alive(parent)
+            || parent == child
+            || self.ancestors(parent).any(|e| e == child)
+        {
+            return false;
+        }
+        if self.get::<Parent>(child).is_some_and(|p| p.0 == parent) {
+            return true;
         }
 
-        // Despawn all collected entities.
-        for e in to_despawn {
-            self.despawn(e);
+        self.detach_from_parent(child);
+        self.insert(child, Parent(parent));
+        match self.get_mut::<Children>(parent) {
+            Some(children) => children.0.push(child),
+            None => self.insert(parent, Children(vec![child])),
         }
+        let matrix = world_matrix(self, child);
+        crate::ecs::hierarchy::propagate_subtree(self, child, matrix);
+        true
+    }
 
+    /// Make `child` a root, keeping its local [`Transform`]. Returns `false`
+    /// if it had no parent.
+    pub fn remove_parent(&mut self, child: Entity) -> bool {
+        use crate::ecs::hierarchy::{Parent, world_matrix};
+
+        if self.get::<Parent>(child).is_none() {
+            return false;
+        }
+        self.detach_from_parent(child);
+        self.remove::<Parent>(child);
+        let matrix = world_matrix(self, child);
+        crate::ecs::hierarchy::propagate_subtree(self, child, matrix);
         true
     }
 
+    /// Remove `entity` from its parent's [`Children`] list, if it has one.
+    /// Leaves its [`Parent`] in place.
+    fn detach_from_parent(&mut self, entity: Entity) {
+        use crate::ecs::hierarchy::{Children, Parent};
+
+        if let Some(parent) = self.get::<Parent>(entity).map(|p| p.0)
+            && let Some(children) = self.get_mut::<Children>(parent)
+        {
+            children.0.retain(|&c| c != entity);
+        }
+    }
+
     /// Despawn every entity in the world.
     pub fn despawn_all(&mut self) {
         // Collect all alive entities.
@@ -540,7 +890,9 @@ impl World {
     }
 
     /// Despawn an entity, removing it from its archetype and freeing its ID
-    /// for reuse.
+    /// for reuse. Entities from a [`Pool`](super::Pool) are destroyed too;
+    /// the pool forgets them (use [`Pool::release`](super::Pool::release) to
+    /// hand one back instead).
     ///
     /// Returns `true` if the entity was alive and successfully despawned.
     pub fn despawn(&mut self, entity: Entity) -> bool {
@@ -653,8 +1005,7 @@ impl World {
             for &t in &new_key {
                 columns.insert(t, ComponentColumn::new());
             }
-            self.archetypes
-                .insert(new_key.clone(), Archetype::new(columns));
+            self.add_archetype(new_key.clone(), columns);
         }
 
         // Take all components from the old archetype for this entity.
@@ -719,9 +1070,17 @@ impl World {
             std::any::type_name::<T>(),
             entity
         );
+        self.remove_any_component(entity, TypeId::of::<T>())
+    }
 
+    /// [`remove`](Self::remove) by type id, for callers that only know the
+    /// type at runtime (the editor). Returns `false` if the entity is dead or
+    /// lacks the component.
+    pub(crate) fn remove_any_component(&mut self, entity: Entity, tid: TypeId) -> bool {
+        if !self.allocator.is_alive(entity) {
+            return false;
+        }
         let loc = self.entity_locations.get(&entity.index).unwrap().clone();
-        let tid = TypeId::of::<T>();
 
         // Check if entity actually has this component.
         if let Some(arch) = self.archetypes.get(&loc.archetype_key) {
@@ -746,8 +1105,7 @@ impl World {
             for &t in &new_key {
                 columns.insert(t, ComponentColumn::new());
             }
-            self.archetypes
-                .insert(new_key.clone(), Archetype::new(columns));
+            self.add_archetype(new_key.clone(), columns);
         }
 
         // Take all components from the old archetype for this entity.
@@ -832,8 +1190,7 @@ impl World {
             for &t in &new_key {
                 columns.insert(t, ComponentColumn::new());
             }
-            self.archetypes
-                .insert(new_key.clone(), Archetype::new(columns));
+            self.add_archetype(new_key.clone(), columns);
         }
 
         // Take all components from old archetype.
@@ -906,6 +1263,40 @@ impl World {
 
     // ── Query ────────────────────────────────────────────────────────
 
+    /// Keys of the archetypes that have every `required` type and not
+    /// `excluded`, from the cache when this signature was seen before.
+    fn matching_archetypes(
+        &mut self,
+        required: Vec<TypeId>,
+        excluded: Option<TypeId>,
+    ) -> Arc<[ArchetypeKey]> {
+        let signature = QuerySignature {
+            required: archetype_key(required),
+            excluded,
+        };
+        if let Some(keys) = self.query_cache.get(&signature) {
+            return keys.clone();
+        }
+        let keys: Arc<[ArchetypeKey]> = self
+            .archetypes
+            .iter()
+            .filter(|(_, arch)| {
+                signature.required.iter().all(|tid| arch.has_component(tid))
+                    && !excluded.is_some_and(|tid| arch.has_component(&tid))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        self.query_cache.insert(signature, keys.clone());
+        keys
+    }
+
+    /// Create an archetype. Any new archetype may match cached queries, so
+    /// the query cache starts over.
+    fn add_archetype(&mut self, key: ArchetypeKey, columns: HashMap<TypeId, ComponentColumn>) {
+        self.archetypes.insert(key, Archetype::new(columns));
+        self.query_cache.clear();
+    }
+
     /// Query all entities that have the requested component types.
     ///
     /// Takes a closure that receives `(Entity, Q::Item)` for each matching
@@ -919,18 +1310,10 @@ impl World {
     /// });
     /// ```
     pub fn query<Q: QueryParam>(&mut self, mut f: impl FnMut(Entity, Q::Item<'_>)) {
-        let required_types = Q::type_ids();
-
-        // Collect matching archetype keys first to avoid borrow issues.
-        let matching_keys: Vec<ArchetypeKey> = self
-            .archetypes
-            .iter()
-            .filter(|(_, arch)| required_types.iter().all(|tid| arch.has_component(tid)))
-            .map(|(key, _)| key.clone())
-            .collect();
+        let matching_keys = self.matching_archetypes(Q::type_ids(), None);
 
-        for key in matching_keys {
-            let arch = self.archetypes.get_mut(&key).unwrap();
+        for key in matching_keys.iter() {
+            let arch = self.archetypes.get_mut(key).unwrap();
             let mut cols = Q::extract(&mut arch.columns);
             let entity_count = arch.entities.len();
             for i in 0..entity_count {
@@ -959,16 +1342,38 @@ impl World {
     ) {
         let mut required_types = Q::type_ids();
         required_types.push(TypeId::of::<F>());
+        let matching_keys = self.matching_archetypes(required_types, None);
 
-        let matching_keys: Vec<ArchetypeKey> = self
-            .archetypes
-            .iter()
-            .filter(|(_, arch)| required_types.iter().all(|tid| arch.has_component(tid)))
-            .map(|(key, _)| key.clone())
-            .collect();
+        for key in matching_keys.iter() {
+            let arch = self.archetypes.get_mut(key).unwrap();
+            let mut cols = Q::extract(&mut arch.columns);
+            let entity_count = arch.entities.len();
+            for i in 0..entity_count {
+                let entity = arch.entities[i];
+                f(entity, Q::fetch(&mut cols, i));
+            }
+            Q::restore(cols, &mut arch.columns);
+        }
+    }
 
-        for key in matching_keys {
-            let arch = self.archetypes.get_mut(&key).unwrap();
+    /// Query with an exclusion filter: only entities that do *not* have a
+    /// marker component `F`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// world.query_without::<(&GlobalTransform, &Sprite), Hidden>(|entity, (gt, sprite)| {
+    ///     // draw
+    /// });
+    /// ```
+    pub fn query_without<Q: QueryParam, F: 'static + Send + Sync>(
+        &mut self,
+        mut f: impl FnMut(Entity, Q::Item<'_>),
+    ) {
+        let matching_keys = self.matching_archetypes(Q::type_ids(), Some(TypeId::of::<F>()));
+
+        for key in matching_keys.iter() {
+            let arch = self.archetypes.get_mut(key).unwrap();
             let mut cols = Q::extract(&mut arch.columns);
             let entity_count = arch.entities.len();
             for i in 0..entity_count {
@@ -979,6 +1384,39 @@ impl World {
         }
     }
 
+    /// Query only entities that have a tag.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// world.query_tagged::<(&mut Transform,)>("enemies", |entity, (transform,)| {
+    ///     transform.translation.y -= fall_speed;
+    /// });
+    /// ```
+    pub fn query_tagged<Q: QueryParam>(
+        &mut self,
+        tag: &str,
+        mut f: impl FnMut(Entity, Q::Item<'_>),
+    ) {
+        let matching_keys = self.matching_archetypes(Q::type_ids(), None);
+        let Some(members) = self.tags.get(tag) else {
+            return;
+        };
+
+        for key in matching_keys.iter() {
+            let arch = self.archetypes.get_mut(key).unwrap();
+            let mut cols = Q::extract(&mut arch.columns);
+            let entity_count = arch.entities.len();
+            for i in 0..entity_count {
+                let entity = arch.entities[i];
+                if members.contains(&entity) {
+                    f(entity, Q::fetch(&mut cols, i));
+                }
+            }
+            Q::restore(cols, &mut arch.columns);
+        }
+    }
+
     /// Query for a single entity that has the requested components and a
     /// marker component `F`.
     ///
@@ -999,17 +1437,11 @@ impl World {
     ) {
         let mut required_types = Q::type_ids();
         required_types.push(TypeId::of::<F>());
-
-        let matching_keys: Vec<ArchetypeKey> = self
-            .archetypes
-            .iter()
-            .filter(|(_, arch)| required_types.iter().all(|tid| arch.has_component(tid)))
-            .map(|(key, _)| key.clone())
-            .collect();
+        let matching_keys = self.matching_archetypes(required_types, None);
 
         // Find the single matching entity.
         let mut found: Option<(Entity, ArchetypeKey, usize)> = None;
-        for key in &matching_keys {
+        for key in matching_keys.iter() {
             let arch = self.archetypes.get(key).unwrap();
             for i in 0..arch.entities.len() {
                 if found.is_some() {
@@ -1029,6 +1461,94 @@ impl World {
             Q::restore(cols, &mut arch.columns);
         }
     }
+
+    // ── Parallel queries ────────────────────────────────────────────────
+
+    /// [`query`](Self::query), with the closure running on rayon's thread
+    /// pool: rows of every matching archetype are split across threads.
+    /// Blocks until all rows are done. See the
+    /// [query docs](super::query#parallel-queries).
+    ///
+    /// Pays off when the per-entity work outweighs handing rows to threads
+    /// (thousands of entities, or heavy math per entity).
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let gravity = Vec3::new(0.0, -9.8, 0.0);
+    /// world.query_par::<(&mut Velocity, &Mass)>(|_, (velocity, mass)| {
+    ///     velocity.0 += gravity * mass.0 * dt;
+    /// });
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn query_par<Q: QueryParam>(&mut self, f: impl Fn(Entity, Q::Item<'_>) + Send + Sync)
+    where
+        for<'w> Q::Item<'w>: Send,
+    {
+        use rayon::prelude::*;
+        self.par_rows::<Q, _>(|rows| rows.into_par_iter().for_each(|(entity, item)| f(entity, item)));
+    }
+
+    /// [`query_par`](Self::query_par) with a scratch value per worker:
+    /// `init` creates one for each batch of rows a thread takes on, `f` can
+    /// mutate it freely, and all of them are returned for merging.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // Collect the enemies in range without a lock.
+    /// let in_range: Vec<Entity> = world
+    ///     .query_par_scoped::<(&Transform,), Vec<Entity>>(Vec::new, |found, entity, (transform,)| {
+    ///         if transform.translation.distance(player) < range {
+    ///             found.push(entity);
+    ///         }
+    ///     })
+    ///     .into_iter()
+    ///     .flatten()
+    ///     .collect();
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn query_par_scoped<Q: QueryParam, S: Send>(
+        &mut self,
+        init: impl Fn() -> S + Send + Sync,
+        f: impl Fn(&mut S, Entity, Q::Item<'_>) + Send + Sync,
+    ) -> Vec<S>
+    where
+        for<'w> Q::Item<'w>: Send,
+    {
+        use rayon::prelude::*;
+        self.par_rows::<Q, _>(|rows| {
+            rows.into_par_iter()
+                .fold(&init, |mut scratch, (entity, item)| {
+                    f(&mut scratch, entity, item);
+                    scratch
+                })
+                .collect()
+        })
+    }
+
+    /// Extract the columns of every archetype matching `Q`, hand all rows
+    /// to `run` as independent borrows, then restore the columns.
+    #[cfg(feature = "rayon")]
+    fn par_rows<Q: QueryParam, R>(&mut self, run: impl FnOnce(Vec<(Entity, Q::Item<'_>)>) -> R) -> R {
+        let matching_keys = self.matching_archetypes(Q::type_ids(), None);
+        let mut extracted: Vec<Q::Column> = matching_keys
+            .iter()
+            .map(|key| Q::extract(&mut self.archetypes.get_mut(key).unwrap().columns))
+            .collect();
+
+        let mut rows = Vec::new();
+        for (key, cols) in matching_keys.iter().zip(&mut extracted) {
+            let entities = &self.archetypes[key].entities;
+            rows.extend(entities.iter().copied().zip(Q::fetch_all(cols, entities.len())));
+        }
+        let result = run(rows);
+
+        for (key, cols) in matching_keys.iter().zip(extracted) {
+            Q::restore(cols, &mut self.archetypes.get_mut(key).unwrap().columns);
+        }
+        result
+    }
 }
 
 impl Default for World {
@@ -1042,7 +1562,21 @@ impl Default for World {
 /// Trait for component bundles that can be spawned into the world.
 ///
 /// Implemented for tuples of components up to 8 elements. Each component must
-/// be `'static + Send + Sync`.
+/// be `'static + Send + Sync`. For larger or reusable groupings, derive it on
+/// a struct with [`#[derive(Bundle)]`](crate::ecs::Bundle): every field is a
+/// component, and fields marked `#[bundle]` nest another bundle.
+///
+/// ```ignore
+/// #[derive(Bundle)]
+/// struct EnemyBundle {
+///     #[bundle]
+///     sprite: SpriteBundle, // Sprite + Transform
+///     health: Health,
+///     enemy: Enemy,
+/// }
+///
+/// world.spawn(EnemyBundle { sprite, health: Health(3), enemy: Enemy });
+/// ```
 pub trait SpawnBundle {
     fn type_ids() -> Vec<TypeId>;
     /// Human-readable type names for each component type.
@@ -1091,24 +1625,38 @@ impl_spawn_bundle!(A, B, C, D, E, F, G);
 impl_spawn_bundle!(A, B, C, D, E, F, G, H);
 
 impl World {
-    /// Spawn an entity with a bundle of components (tuple).
+    /// Spawn an entity with a bundle of components (a tuple or a
+    /// [`#[derive(Bundle)]`](crate::ecs::Bundle) struct).
     ///
     /// # Example
     ///
     /// ```ignore
     /// let e = world.spawn((Position { x: 0.0, y: 0.0 }, Velocity { dx: 1.0, dy: 0.0 }));
     /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bundle contains the same component type twice, e.g. two
+    /// nested bundles that both carry a `Transform`.
     pub fn spawn<B: SpawnBundle>(&mut self, bundle: B) -> Entity {
+        let type_ids = B::type_ids();
+        let component_count = type_ids.len();
+        let key = archetype_key(type_ids);
+        if key.len() != component_count {
+            panic!(
+                "spawn: bundle `{}` contains a component type more than once",
+                std::any::type_name::<B>()
+            );
+        }
+
         let entity = self.allocator.allocate();
         #[cfg(feature = "diagnostics")]
         { self.spawned_this_frame += 1; }
-        let key = archetype_key(B::type_ids());
 
         // Ensure the archetype exists.
         if !self.archetypes.contains_key(&key) {
             let columns = B::create_columns();
-            self.archetypes
-                .insert(key.clone(), Archetype::new(columns));
+            self.add_archetype(key.clone(), columns);
         }
 
         let arch = self.archetypes.get_mut(&key).unwrap();
@@ -1160,7 +1708,7 @@ mod tests {
         x: f32,
         y: f32,
     }
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     struct Velocity {
         dx: f32,
         dy: f32,
@@ -1244,6 +1792,17 @@ mod tests {
         assert_eq!(world.resource_remove::<u64>(), None);
     }
 
+    #[test]
+    fn world_can_move_to_another_thread() {
+        let mut world = World::new();
+        world.spawn((Position { x: 1.0, y: 2.0 },));
+        world.insert_resource(String::from("hello"));
+
+        let world = std::thread::spawn(move || world).join().unwrap();
+        assert_eq!(world.resource::<String>(), "hello");
+        assert_eq!(world.entity_count(), 1);
+    }
+
     #[test]
     fn query_mutate() {
         let mut world = World::new();
@@ -1261,6 +1820,33 @@ mod tests {
         assert_eq!(results[0], (1.0, 2.0));
     }
 
+    #[test]
+    fn query_cache_picks_up_new_archetypes() {
+        let mut world = World::new();
+        world.spawn((Position { x: 0.0, y: 0.0 },));
+        let count = |world: &mut World| {
+            let mut n = 0;
+            world.query::<(&Position,)>(|_, _| n += 1);
+            n
+        };
+        assert_eq!(count(&mut world), 1);
+        assert_eq!(world.query_cache.len(), 1);
+
+        // Same archetype: the cached answer still holds.
+        world.spawn((Position { x: 1.0, y: 0.0 },));
+        assert_eq!(world.query_cache.len(), 1);
+        assert_eq!(count(&mut world), 2);
+
+        // A new archetype clears the cache, and the next query finds it.
+        let e = world.spawn((Position { x: 2.0, y: 0.0 }, Marker));
+        assert!(world.query_cache.is_empty());
+        assert_eq!(count(&mut world), 3);
+        world.remove::<Marker>(e);
+        world.query_without::<(&Position,), Marker>(|_, _| {});
+        assert_eq!(count(&mut world), 3);
+        assert_eq!(world.query_cache.len(), 2);
+    }
+
     #[test]
     fn query_filtered_with_marker() {
         let mut world = World::new();
@@ -1525,6 +2111,91 @@ mod tests {
         assert!(world.tagged("group").is_empty());
     }
 
+    #[test]
+    fn tag_twice_and_untag() {
+        let mut world = World::new();
+        let e = world.spawn((Marker,));
+        world.tag(e, "enemy");
+        world.tag(e, "enemy");
+        assert_eq!(world.entity_tags(e), ["enemy"]);
+        assert!(world.has_tag(e, "enemy"));
+
+        assert!(world.untag(e, "enemy"));
+        assert!(!world.untag(e, "enemy"));
+        assert!(!world.has_tag(e, "enemy"));
+        assert_eq!(world.tagged_count("enemy"), 0);
+        assert!(world.entity_tags(e).is_empty());
+    }
+
+    #[test]
+    fn despawn_tagged_takes_descendants() {
+        let mut world = World::new();
+        let a = world.spawn((Marker,));
+        let child = world.spawn_child(a, (Marker,));
+        let _grandchild = world.spawn_child(child, (Marker,));
+        let b = world.spawn((Marker,));
+        let keep = world.spawn((Marker,));
+        world.tag(a, "wave");
+        world.tag(b, "wave");
+        // Tagged and a descendant of another tagged entity.
+        world.tag(child, "wave");
+
+        assert_eq!(world.despawn_tagged("wave"), 3);
+        assert_eq!(world.entity_count(), 1);
+        assert!(world.is_alive(keep));
+    }
+
+    #[test]
+    fn set_visibility_tagged_hides_group_and_children() {
+        use crate::ecs::{ComputedVisibility, Hidden, propagate_visibility};
+
+        fn hidden(world: &World, entity: Entity) -> bool {
+            world.get::<ComputedVisibility>(entity).is_some_and(|c| !c.visible)
+        }
+
+        let mut world = World::new();
+        let a = world.spawn((Marker,));
+        let child = world.spawn_child(a, (Marker,));
+        let hidden_child = world.spawn_child(a, (Marker, Hidden));
+        let other = world.spawn((Marker,));
+        world.tag(a, "hud");
+
+        world.set_visibility_tagged("hud", false);
+        propagate_visibility(&mut world);
+        assert!(hidden(&world, a) && hidden(&world, child) && hidden(&world, hidden_child));
+        assert!(!hidden(&world, other));
+
+        // Showing the group leaves the independently hidden child hidden.
+        world.set_visibility_tagged("hud", true);
+        propagate_visibility(&mut world);
+        assert!(!hidden(&world, a) && !hidden(&world, child));
+        assert!(hidden(&world, hidden_child));
+    }
+
+    #[test]
+    fn query_and_bulk_edits_by_tag() {
+        let mut world = World::new();
+        let e1 = world.spawn((Position { x: 0.0, y: 0.0 },));
+        let e2 = world.spawn((Position { x: 0.0, y: 0.0 }, Marker));
+        let e3 = world.spawn((Position { x: 0.0, y: 0.0 },));
+        world.tag(e1, "enemy");
+        world.tag(e2, "enemy");
+
+        world.query_tagged::<(&mut Position,)>("enemy", |_, (pos,)| pos.x = 5.0);
+        assert_eq!(world.get::<Position>(e1).unwrap().x, 5.0);
+        assert_eq!(world.get::<Position>(e2).unwrap().x, 5.0);
+        assert_eq!(world.get::<Position>(e3).unwrap().x, 0.0);
+
+        world.insert_tagged("enemy", Velocity { dx: 1.0, dy: 0.0 });
+        assert_eq!(world.entities_with::<Velocity>().len(), 2);
+        world.remove_tagged::<Velocity>("enemy");
+        assert!(world.entities_with::<Velocity>().is_empty());
+
+        let mut count = 0;
+        world.query_tagged::<(&Position,)>("nobody", |_, _| count += 1);
+        assert_eq!(count, 0);
+    }
+
     // ── entities_with tests ──────────────────────────────────────────
 
     #[test]
@@ -1708,4 +2379,104 @@ mod tests {
         // After despawn, entity_tags returns empty.
         assert!(world.entity_tags(e).is_empty());
     }
+
+    #[test]
+    fn stats_summarize_archetypes_and_resources() {
+        let mut world = World::new();
+        for _ in 0..3 {
+            world.spawn((Position { x: 0.0, y: 0.0 }, Health(10)));
+        }
+        let e = world.spawn((Position { x: 1.0, y: 1.0 },));
+        world.tag(e, "player");
+        world.insert_resource(42u32);
+
+        let stats = world.stats();
+        assert_eq!(stats.entity_count, 4);
+        assert_eq!(stats.archetypes.len(), 2);
+        assert_eq!(stats.archetypes[0].entity_count, 3);
+        assert_eq!(stats.archetypes[0].components.len(), 2);
+        assert_eq!(stats.component(std::any::type_name::<Position>()).unwrap().count, 4);
+        assert_eq!(stats.component(std::any::type_name::<Health>()).unwrap().count, 3);
+        assert_eq!(stats.resources.len(), 1);
+        assert_eq!(stats.resources[0].type_name, "u32");
+        assert_eq!(stats.tag_count, 1);
+        assert!(stats.memory.components > 0);
+
+        let json = serde_json::to_string(&stats).unwrap();
+        let back: WorldStats = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, stats);
+
+        world.resource_remove::<u32>();
+        assert!(world.stats().resources.is_empty());
+    }
+
+    #[derive(crate::ecs::Bundle)]
+    struct Body {
+        position: Position,
+        velocity: Velocity,
+    }
+
+    #[derive(crate::ecs::Bundle)]
+    struct Enemy {
+        #[bundle]
+        body: Body,
+        health: Health,
+        marker: Marker,
+    }
+
+    #[test]
+    fn nested_bundles_spawn_all_components() {
+        let mut world = World::new();
+        let e = world.spawn(Enemy {
+            body: Body {
+                position: Position { x: 1.0, y: 2.0 },
+                velocity: Velocity { dx: 3.0, dy: 4.0 },
+            },
+            health: Health(5),
+            marker: Marker,
+        });
+        assert_eq!(world.get::<Position>(e), Some(&Position { x: 1.0, y: 2.0 }));
+        assert_eq!(world.get::<Velocity>(e).unwrap().dy, 4.0);
+        assert_eq!(world.get::<Health>(e).unwrap().0, 5);
+        assert!(world.get::<Marker>(e).is_some());
+
+        let mut count = 0;
+        world.query::<(&Position, &Health)>(|_, _| count += 1);
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "more than once")]
+    fn duplicate_component_in_bundle_panics() {
+        let mut world = World::new();
+        world.spawn((Health(1), Health(2)));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn parallel_queries_visit_every_row_once() {
+        let mut world = World::new();
+        for i in 0..500 {
+            world.spawn((Position { x: i as f32, y: 0.0 }, Velocity { dx: 1.0, dy: 2.0 }));
+        }
+        for i in 0..300 {
+            // A second archetype matching the same query.
+            world.spawn((Position { x: i as f32, y: 0.0 }, Velocity { dx: 1.0, dy: 2.0 }, Health(1)));
+        }
+
+        let scale = 2.0;
+        world.query_par::<(&mut Position, &Velocity, Option<&Health>)>(|_, (pos, vel, health)| {
+            pos.y = vel.dy * scale + health.map_or(0.0, |h| h.0 as f32);
+        });
+        let counts = world.query_par_scoped::<(&Position,), usize>(
+            || 0,
+            |count, _, (pos,)| *count += (pos.y == 4.0) as usize + 10 * (pos.y == 5.0) as usize,
+        );
+        assert_eq!(counts.iter().sum::<usize>(), 500 + 10 * 300);
+
+        // Columns are back in place for sequential queries.
+        let mut total = 0;
+        world.query::<(&Position, &Velocity)>(|_, _| total += 1);
+        assert_eq!(total, 800);
+    }
 }
@@ -0,0 +1,35 @@
+//! About window — the engine build and GPU backend, with a button that
+//! copies them for a bug report. See [`version`](crate::version).
+
+/// Draw the About window.
+pub(crate) fn about_window(ctx: &egui::Context, open: &mut bool) {
+    let info = crate::build_info();
+    egui::Window::new("About necs")
+        .open(open)
+        .resizable(false)
+        .show(ctx, |ui| {
+            egui::Grid::new("about_grid").num_columns(2).show(ui, |ui| {
+                let mut row = |label: &str, value: &str| {
+                    ui.label(label);
+                    ui.monospace(value);
+                    ui.end_row();
+                };
+                row("Version", &info.version);
+                row("Commit", info.git_hash.as_deref().unwrap_or("unknown"));
+                row("Profile", &info.profile);
+                row("Backend", info.backend.as_deref().unwrap_or("none"));
+                row("GPU", info.adapter.as_deref().unwrap_or("none"));
+            });
+            ui.separator();
+            ui.label("Features");
+            ui.horizontal_wrapped(|ui| {
+                for feature in &info.features {
+                    ui.monospace(feature);
+                }
+            });
+            ui.separator();
+            if ui.button("Copy build info").clicked() {
+                ui.ctx().copy_text(info.to_string());
+            }
+        });
+}
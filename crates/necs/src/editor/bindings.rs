@@ -0,0 +1,54 @@
+//! Input Bindings window — every context's actions and keys, which contexts
+//! are active, and binding conflicts.
+
+use crate::action::ActionMap;
+use crate::ecs::world::World;
+
+/// Draw the Input Bindings window. Context checkboxes toggle whether the
+/// context is active, for trying out context switches by hand.
+pub(crate) fn bindings_window(ctx: &egui::Context, world: &mut World, open: &mut bool) {
+    egui::Window::new("Input Bindings")
+        .open(open)
+        .default_size([360.0, 420.0])
+        .show(ctx, |ui| {
+            let Some(map) = world.get_resource_mut::<ActionMap>() else {
+                ui.label("No ActionMap loaded (see Context::load_actions)");
+                return;
+            };
+
+            let conflicts = map.bindings().conflicts();
+            for conflict in &conflicts {
+                ui.colored_label(egui::Color32::from_rgb(230, 90, 80), conflict.to_string());
+            }
+            if !conflicts.is_empty() {
+                ui.separator();
+            }
+
+            let mut toggled = Vec::new();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (context, actions) in &map.bindings().contexts {
+                    let mut active = map.is_active(context);
+                    if ui.checkbox(&mut active, egui::RichText::new(context).strong()).changed() {
+                        toggled.push((context.clone(), active));
+                    }
+                    egui::Grid::new(("bindings", context.as_str()))
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for (action, bindings) in actions {
+                                let keys: Vec<String> =
+                                    bindings.iter().map(ToString::to_string).collect();
+                                ui.label(action);
+                                ui.label(keys.join(", "));
+                                ui.end_row();
+                            }
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+
+            for (context, active) in toggled {
+                map.set_context_active(&context, active);
+            }
+        });
+}
@@ -0,0 +1,556 @@
+//! Transform gizmos — drag handles in the viewport that move, rotate and
+//! scale the selected entity.
+//!
+//! ```text
+//!   Translate (W)        Rotate (E)           Scale (R)
+//!
+//!        Y                  ╭──╮                  Y■
+//!        ▲                 ╭┼──┼╮                 │
+//!        │                 │╰──╯│                 │
+//!        └──► X            ╰────╯                 └──■ X
+//!       ╱                 one ring per           ╱
+//!      Z                  axis                  ■ Z
+//! ```
+//!
+//! Press on a handle and drag: the pointer's motion is projected onto the
+//! handle's axis on screen, so dragging along the arrow moves the entity
+//! along it whatever the camera angle. Rotation follows the pointer's angle
+//! around the entity's center.
+//!
+//! Translate and rotate handles use the parent's axes, because that is the
+//! space [`Transform`] lives in; scale handles use the entity's own axes.
+//! Handles stay the same size on screen. With a 2D camera, only the X/Y
+//! arrows and the Z ring are shown.
+//!
+//! Snapping (toolbar) rounds the dragged value: translation to the grid,
+//! rotation to the angle step (relative to where the drag started), scale to
+//! the scale step. Every finished drag is one entry in the
+//! [undo history](super::undo).
+//!
+//! The viewport is whatever the game's camera shows: the [`Camera3d`] with
+//! `render3d` (through the same projection the renderer uses, perspective
+//! or orthographic), otherwise the [`Camera2d`] with `render2d`.
+//!
+//! [`Camera3d`]: crate::render3d::Camera3d
+//! [`Camera2d`]: crate::render2d::Camera2d
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Move/Rotate/Scale tools on W/E/R with global/local pivot
+//!   modes and Ctrl-drag snapping to configurable increments.
+//! - **Bevy**: No built-in editor; `bevy_editor_pls` and
+//!   `transform-gizmo-bevy` add gizmos as plugins.
+//! - **Godot**: Select/Move/Rotate/Scale modes (W/E/R) with snap settings in
+//!   the viewport toolbar.
+//! - **Our approach**: Unity's W/E/R tools with a snap toggle, drawn with
+//!   egui on top of the game's own frame.
+
+use crate::ecs::Entity;
+use crate::ecs::hierarchy::{GlobalTransform, Parent};
+use crate::ecs::world::World;
+use crate::math::{Mat4, Quat, Transform, Vec2, Vec3};
+
+use super::undo::{Edit, UndoStack};
+
+/// Handle length on screen, in points.
+const HANDLE_LENGTH: f32 = 80.0;
+/// How close the pointer must be to a handle to grab it, in points.
+const HIT_DISTANCE: f32 = 8.0;
+/// Segments per rotation ring.
+const RING_SEGMENTS: usize = 48;
+
+const AXIS_COLORS: [egui::Color32; 3] = [
+    egui::Color32::from_rgb(230, 70, 70),
+    egui::Color32::from_rgb(90, 200, 80),
+    egui::Color32::from_rgb(70, 120, 240),
+];
+const HOVER_COLOR: egui::Color32 = egui::Color32::from_rgb(250, 220, 60);
+
+// ── Settings ─────────────────────────────────────────────────────────────
+
+/// Which property the handles edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) enum GizmoMode {
+    #[default]
+    Translate,
+    Rotate,
+    Scale,
+}
+
+/// Snap increments, applied while [`enabled`](Self::enabled).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Snap {
+    pub enabled: bool,
+    /// Translation grid, in world units.
+    pub grid: f32,
+    /// Rotation step, in degrees.
+    pub angle: f32,
+    /// Scale step.
+    pub scale: f32,
+}
+
+impl Default for Snap {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            grid: 1.0,
+            angle: 15.0,
+            scale: 0.1,
+        }
+    }
+}
+
+/// Gizmo settings and the drag in progress.
+#[derive(Debug, Default)]
+pub(crate) struct Gizmo {
+    pub mode: GizmoMode,
+    pub snap: Snap,
+    drag: Option<Drag>,
+}
+
+/// A handle being dragged.
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    entity: Entity,
+    axis: usize,
+    pointer: egui::Pos2,
+    start: Transform,
+}
+
+// ── Viewport ─────────────────────────────────────────────────────────────
+
+/// The game camera, mapped onto the egui screen.
+struct Viewport {
+    view_proj: Mat4,
+    /// Camera right, for measuring how big a world unit is on screen.
+    right: Vec3,
+    /// Camera forward, for telling which way a ring faces.
+    forward: Vec3,
+    /// 2D camera: only X/Y and rotation about Z make sense.
+    planar: bool,
+    rect: egui::Rect,
+}
+
+impl Viewport {
+    /// The active game camera, or `None` if there is nothing to project with.
+    fn find(world: &mut World, rect: egui::Rect, surface_size: (u32, u32)) -> Option<Viewport> {
+        #[cfg(feature = "render3d")]
+        if let Some(camera) = crate::render3d::collect::extract_camera_3d(world) {
+            let uniform = crate::render3d::collect::collect_camera(Some(&camera), surface_size);
+            return Some(Viewport {
+                view_proj: Mat4::from_cols_array_2d(&uniform.view_proj),
+                right: camera.matrix.x_axis.truncate().normalize_or_zero(),
+                forward: -camera.matrix.z_axis.truncate().normalize_or_zero(),
+                planar: false,
+                rect,
+            });
+        }
+        #[cfg(feature = "render2d")]
+        {
+            let mut camera = None;
+            world.query_single::<(&GlobalTransform,), crate::render2d::Camera2d>(|_entity, (gt,)| {
+                camera = Some(gt.matrix);
+            });
+            return Some(Viewport {
+                view_proj: crate::render2d::batch::compute_camera_vp(camera, surface_size),
+                right: camera.map_or(Vec3::X, |m| m.x_axis.truncate().normalize_or_zero()),
+                forward: Vec3::NEG_Z,
+                planar: true,
+                rect,
+            });
+        }
+        #[allow(unreachable_code)]
+        {
+            let _ = (world, rect, surface_size);
+            None
+        }
+    }
+
+    /// Screen position of a world point; `None` behind the camera.
+    fn project(&self, point: Vec3) -> Option<egui::Pos2> {
+        let clip = self.view_proj * point.extend(1.0);
+        if clip.w <= 1e-5 {
+            return None;
+        }
+        let ndc = clip.truncate() / clip.w;
+        Some(egui::pos2(
+            self.rect.left() + (ndc.x + 1.0) * 0.5 * self.rect.width(),
+            self.rect.top() + (1.0 - ndc.y) * 0.5 * self.rect.height(),
+        ))
+    }
+
+    /// World length that spans [`HANDLE_LENGTH`] points at `origin`.
+    fn handle_world_length(&self, origin: Vec3) -> Option<f32> {
+        let a = self.project(origin)?;
+        let b = self.project(origin + self.right)?;
+        let points_per_unit = (b - a).length();
+        (points_per_unit > 1e-5).then(|| HANDLE_LENGTH / points_per_unit)
+    }
+}
+
+// ── Handles ──────────────────────────────────────────────────────────────
+
+/// The selected entity's handles for one frame.
+struct Handles {
+    center: egui::Pos2,
+    /// Unnormalized world direction of each axis: one local unit.
+    axes: [Vec3; 3],
+    /// Screen outline of each handle; empty when hidden.
+    paths: [Vec<egui::Pos2>; 3],
+}
+
+impl Handles {
+    fn new(view: &Viewport, mode: GizmoMode, origin: Vec3, basis: Mat4) -> Option<Handles> {
+        let center = view.project(origin)?;
+        let length = view.handle_world_length(origin)?;
+        let axes = [Vec3::X, Vec3::Y, Vec3::Z].map(|axis| basis.transform_vector3(axis));
+        let paths = std::array::from_fn(|i| {
+            let direction = axes[i].normalize_or_zero();
+            if direction == Vec3::ZERO {
+                return Vec::new();
+            }
+            match mode {
+                GizmoMode::Translate | GizmoMode::Scale => {
+                    if view.planar && i == 2 {
+                        return Vec::new();
+                    }
+                    let Some(tip) = view.project(origin + direction * length) else {
+                        return Vec::new();
+                    };
+                    // An axis pointing at the camera has nothing to grab.
+                    if (tip - center).length() < HIT_DISTANCE {
+                        return Vec::new();
+                    }
+                    vec![center, tip]
+                }
+                GizmoMode::Rotate => {
+                    if view.planar && i != 2 {
+                        return Vec::new();
+                    }
+                    let u = direction.any_orthonormal_vector();
+                    let v = direction.cross(u);
+                    (0..=RING_SEGMENTS)
+                        .filter_map(|s| {
+                            let angle = s as f32 / RING_SEGMENTS as f32 * std::f32::consts::TAU;
+                            view.project(origin + (u * angle.cos() + v * angle.sin()) * length)
+                        })
+                        .collect()
+                }
+            }
+        });
+        Some(Handles { center, axes, paths })
+    }
+
+    /// The handle under `pointer`, if any.
+    fn hit(&self, pointer: egui::Pos2) -> Option<usize> {
+        (0..3)
+            .filter_map(|i| Some((i, distance_to_path(pointer, &self.paths[i])?)))
+            .filter(|&(_, distance)| distance <= HIT_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    }
+
+    fn draw(&self, painter: &egui::Painter, mode: GizmoMode, active: Option<usize>) {
+        for (i, path) in self.paths.iter().enumerate() {
+            let color = if active == Some(i) { HOVER_COLOR } else { AXIS_COLORS[i] };
+            let stroke = egui::Stroke::new(2.5, color);
+            match (mode, path.as_slice()) {
+                (GizmoMode::Translate, &[start, tip]) => {
+                    painter.line_segment([start, tip], stroke);
+                    let back = (start - tip).normalized() * 10.0;
+                    let side = back.rot90() * 0.5;
+                    painter.add(egui::Shape::convex_polygon(
+                        vec![tip, tip + back + side, tip + back - side],
+                        color,
+                        egui::Stroke::NONE,
+                    ));
+                }
+                (GizmoMode::Scale, &[start, tip]) => {
+                    painter.line_segment([start, tip], stroke);
+                    painter.rect_filled(egui::Rect::from_center_size(tip, egui::vec2(9.0, 9.0)), 0.0, color);
+                }
+                (GizmoMode::Rotate, points) if points.len() > 1 => {
+                    painter.line(points.to_vec(), stroke);
+                }
+                _ => {}
+            }
+        }
+        painter.circle_filled(self.center, 3.0, egui::Color32::WHITE);
+    }
+}
+
+/// Shortest distance from `point` to the polyline `path`.
+fn distance_to_path(point: egui::Pos2, path: &[egui::Pos2]) -> Option<f32> {
+    path.windows(2)
+        .map(|segment| {
+            let (a, b) = (segment[0], segment[1]);
+            let ab = b - a;
+            let t = ((point - a).dot(ab) / ab.length_sq().max(1e-6)).clamp(0.0, 1.0);
+            (a + ab * t - point).length()
+        })
+        .min_by(f32::total_cmp)
+}
+
+// ── Drag math ────────────────────────────────────────────────────────────
+
+/// How far along an axis a screen drag goes, given the axis's on-screen
+/// vector per unit.
+fn axis_amount(delta: Vec2, screen_axis: Vec2) -> f32 {
+    let length_sq = screen_axis.length_squared();
+    if length_sq < 1e-8 {
+        return 0.0;
+    }
+    delta.dot(screen_axis) / length_sq
+}
+
+/// Screen angle swept from `from` to `to` around `center`, clockwise
+/// positive (screen Y points down).
+fn swept_angle(center: Vec2, from: Vec2, to: Vec2) -> f32 {
+    let (a, b) = (from - center, to - center);
+    a.perp_dot(b).atan2(a.dot(b))
+}
+
+/// Round `value` to a multiple of `step`; non-positive steps leave it alone.
+fn snap_to(value: f32, step: f32) -> f32 {
+    if step > 0.0 { (value / step).round() * step } else { value }
+}
+
+fn to_vec2(pos: egui::Pos2) -> Vec2 {
+    Vec2::new(pos.x, pos.y)
+}
+
+/// The transform a drag from `drag.pointer` to `pointer` produces.
+fn dragged_transform(
+    view: &Viewport,
+    handles: &Handles,
+    mode: GizmoMode,
+    snap: Snap,
+    drag: &Drag,
+    origin: Vec3,
+    pointer: egui::Pos2,
+) -> Transform {
+    let axis = drag.axis;
+    let start = drag.start;
+    let delta = to_vec2(pointer) - to_vec2(drag.pointer);
+    let mut result = start;
+    match mode {
+        GizmoMode::Translate => {
+            // One local unit along the axis, measured on screen over a
+            // handle-sized stretch so perspective stays accurate.
+            let direction = handles.axes[axis];
+            let step = view.handle_world_length(origin).unwrap_or(1.0) / direction.length().max(1e-6);
+            let (Some(a), Some(b)) = (view.project(origin), view.project(origin + direction * step)) else {
+                return start;
+            };
+            let screen_axis = (to_vec2(b) - to_vec2(a)) / step;
+            let mut value = start.translation[axis] + axis_amount(delta, screen_axis);
+            if snap.enabled {
+                value = snap_to(value, snap.grid);
+            }
+            result.translation[axis] = value;
+        }
+        GizmoMode::Rotate => {
+            let mut angle = swept_angle(to_vec2(handles.center), to_vec2(drag.pointer), to_vec2(pointer));
+            // Seen from the tip of the axis, a positive rotation turns
+            // counter-clockwise, which is negative on screen.
+            if handles.axes[axis].dot(view.forward) < 0.0 {
+                angle = -angle;
+            }
+            if snap.enabled {
+                angle = snap_to(angle.to_degrees(), snap.angle).to_radians();
+            }
+            let local_axis = [Vec3::X, Vec3::Y, Vec3::Z][axis];
+            result.rotation = (Quat::from_axis_angle(local_axis, angle) * start.rotation).normalize();
+        }
+        GizmoMode::Scale => {
+            let &[a, b] = handles.paths[axis].as_slice() else {
+                return start;
+            };
+            let screen_axis = (to_vec2(b) - to_vec2(a)).normalize_or_zero() * HANDLE_LENGTH;
+            let mut value = start.scale[axis] * (1.0 + axis_amount(delta, screen_axis));
+            if snap.enabled {
+                value = snap_to(value, snap.scale);
+                if value == 0.0 {
+                    value = snap.scale;
+                }
+            }
+            result.scale[axis] = value;
+        }
+    }
+    result
+}
+
+// ── UI ───────────────────────────────────────────────────────────────────
+
+impl Gizmo {
+    /// Switch modes with W/E/R unless a text field has focus.
+    fn mode_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        ctx.input(|i| {
+            if i.key_pressed(egui::Key::W) {
+                self.mode = GizmoMode::Translate;
+            } else if i.key_pressed(egui::Key::E) {
+                self.mode = GizmoMode::Rotate;
+            } else if i.key_pressed(egui::Key::R) {
+                self.mode = GizmoMode::Scale;
+            }
+        });
+    }
+
+    /// Draw the selected entity's handles in the free space between the
+    /// editor panels and handle dragging them. Call after the panels.
+    pub fn ui(
+        &mut self,
+        ctx: &egui::Context,
+        world: &mut World,
+        selected: Option<Entity>,
+        history: &mut UndoStack,
+        surface_size: (u32, u32),
+    ) {
+        self.mode_shortcuts(ctx);
+
+        let Some(entity) = selected.filter(|&e| world.is_alive(e)) else {
+            self.drag = None;
+            return;
+        };
+        if self.drag.is_some_and(|drag| drag.entity != entity) {
+            self.drag = None;
+        }
+        let (Some(transform), Some(global)) = (
+            world.get::<Transform>(entity).copied(),
+            world.get::<GlobalTransform>(entity).map(|g| g.matrix),
+        ) else {
+            return;
+        };
+        let Some(view) = Viewport::find(world, ctx.viewport_rect(), surface_size) else {
+            return;
+        };
+
+        let origin = global.w_axis.truncate();
+        let basis = match self.mode {
+            GizmoMode::Scale => global,
+            GizmoMode::Translate | GizmoMode::Rotate => world
+                .get::<Parent>(entity)
+                .and_then(|parent| world.get::<GlobalTransform>(parent.0))
+                .map_or(Mat4::IDENTITY, |g| g.matrix),
+        };
+        let Some(handles) = Handles::new(&view, self.mode, origin, basis) else {
+            return;
+        };
+
+        let (pointer, pressed, down) = ctx.input(|i| {
+            (
+                i.pointer.latest_pos(),
+                i.pointer.primary_pressed(),
+                i.pointer.primary_down(),
+            )
+        });
+        let free = ctx.available_rect();
+        let hovered = pointer
+            .filter(|&p| free.contains(p) && !ctx.is_pointer_over_area())
+            .and_then(|p| handles.hit(p));
+
+        if pressed && self.drag.is_none()
+            && let (Some(axis), Some(pointer)) = (hovered, pointer)
+        {
+            self.drag = Some(Drag {
+                entity,
+                axis,
+                pointer,
+                start: transform,
+            });
+        }
+
+        if let Some(drag) = self.drag {
+            if let Some(pointer) = pointer
+                && let Some(tf) = world.get_mut::<Transform>(entity)
+            {
+                *tf = dragged_transform(&view, &handles, self.mode, self.snap, &drag, origin, pointer);
+            }
+            if !down {
+                self.drag = None;
+                let after = world.get::<Transform>(entity).copied().unwrap_or(transform);
+                if after != drag.start {
+                    history.push(Edit::Transform {
+                        entity,
+                        before: drag.start,
+                        after,
+                    });
+                }
+            }
+        }
+
+        let painter = ctx.layer_painter(egui::LayerId::background()).with_clip_rect(free);
+        let active = self.drag.map(|drag| drag.axis).or(hovered);
+        handles.draw(&painter, self.mode, active);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn view() -> Viewport {
+        // Orthographic, 1 world unit = 1 point, origin at the screen center.
+        Viewport {
+            view_proj: Mat4::orthographic_rh(-100.0, 100.0, -100.0, 100.0, -10.0, 10.0),
+            right: Vec3::X,
+            forward: Vec3::NEG_Z,
+            planar: true,
+            rect: egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(200.0, 200.0)),
+        }
+    }
+
+    fn drag(axis: usize, pointer: egui::Pos2) -> Drag {
+        Drag {
+            entity: Entity { index: 0, generation: 0 },
+            axis,
+            pointer,
+            start: Transform::default(),
+        }
+    }
+
+    #[test]
+    fn projection_and_handles_follow_the_camera() {
+        let view = view();
+        assert_eq!(view.project(Vec3::ZERO), Some(egui::pos2(100.0, 100.0)));
+        assert_eq!(view.project(Vec3::new(10.0, 10.0, 0.0)), Some(egui::pos2(110.0, 90.0)));
+
+        let handles = Handles::new(&view, GizmoMode::Translate, Vec3::ZERO, Mat4::IDENTITY).unwrap();
+        // X right, Y up, Z points at the camera and is hidden.
+        let near = |a: egui::Pos2, b: egui::Pos2| a.distance(b) < 1e-3;
+        assert!(near(handles.paths[0][0], egui::pos2(100.0, 100.0)));
+        assert!(near(handles.paths[0][1], egui::pos2(180.0, 100.0)));
+        assert!(near(handles.paths[1][1], egui::pos2(100.0, 20.0)));
+        assert!(handles.paths[2].is_empty());
+        assert_eq!(handles.hit(egui::pos2(150.0, 104.0)), Some(0));
+        assert_eq!(handles.hit(egui::pos2(150.0, 150.0)), None);
+    }
+
+    #[test]
+    fn drags_move_rotate_and_scale_with_snapping() {
+        let view = view();
+        let mut snap = Snap::default();
+        let start = egui::pos2(150.0, 100.0);
+        let translate = Handles::new(&view, GizmoMode::Translate, Vec3::ZERO, Mat4::IDENTITY).unwrap();
+
+        // Only the motion along the axis counts.
+        let tf = dragged_transform(&view, &translate, GizmoMode::Translate, snap, &drag(0, start), Vec3::ZERO, egui::pos2(162.4, 130.0));
+        assert!((tf.translation - Vec3::new(12.4, 0.0, 0.0)).length() < 1e-3);
+        snap.enabled = true;
+        let tf = dragged_transform(&view, &translate, GizmoMode::Translate, snap, &drag(0, start), Vec3::ZERO, egui::pos2(162.4, 130.0));
+        assert_eq!(tf.translation.x, 12.0);
+
+        // A quarter turn counter-clockwise on screen is +90° about Z.
+        let rotate = Handles::new(&view, GizmoMode::Rotate, Vec3::ZERO, Mat4::IDENTITY).unwrap();
+        let tf = dragged_transform(&view, &rotate, GizmoMode::Rotate, snap, &drag(2, start), Vec3::ZERO, egui::pos2(100.0, 48.0));
+        assert!(tf.rotation.abs_diff_eq(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2), 1e-4));
+
+        // Dragging a full handle length outward doubles the scale.
+        let scale = Handles::new(&view, GizmoMode::Scale, Vec3::ZERO, Mat4::IDENTITY).unwrap();
+        let tf = dragged_transform(&view, &scale, GizmoMode::Scale, snap, &drag(1, start), Vec3::ZERO, egui::pos2(150.0, 19.0));
+        assert!(tf.scale.abs_diff_eq(Vec3::new(1.0, 2.0, 1.0), 1e-5));
+    }
+}
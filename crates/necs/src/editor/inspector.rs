@@ -1,15 +1,23 @@
 //! Component inspector panel — right side panel showing the selected entity's
-//! components with editable fields.
+//! components with editable fields. Components registered in the
+//! [`SceneRegistry`] can be added and removed; every change goes through the
+//! [undo history](super::undo).
+
+use std::any::TypeId;
 
 use crate::ecs::Entity;
 use crate::ecs::world::World;
 use crate::math::Transform;
+use crate::scene::SceneRegistry;
+
+use super::undo::UndoStack;
 
 /// Draw the component inspector panel for the selected entity.
 pub(crate) fn inspector_panel(
     ctx: &egui::Context,
     world: &mut World,
     selected: Option<Entity>,
+    history: &mut UndoStack,
 ) {
     egui::SidePanel::right("inspector_panel")
         .default_width(280.0)
@@ -36,6 +44,8 @@ pub(crate) fn inspector_panel(
 
             // Transform component (editable).
             if let Some(tf) = world.get_mut::<Transform>(entity) {
+                let before = *tf;
+                let mut editing = false;
                 egui::CollapsingHeader::new("Transform")
                     .default_open(true)
                     .show(ui, |ui| {
@@ -44,22 +54,22 @@ pub(crate) fn inspector_panel(
                         });
                         ui.horizontal(|ui| {
                             ui.label("X:");
-                            ui.add(egui::DragValue::new(&mut tf.translation.x).speed(1.0));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.translation.x).speed(1.0)));
                             ui.label("Y:");
-                            ui.add(egui::DragValue::new(&mut tf.translation.y).speed(1.0));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.translation.y).speed(1.0)));
                             ui.label("Z:");
-                            ui.add(egui::DragValue::new(&mut tf.translation.z).speed(1.0));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.translation.z).speed(1.0)));
                         });
                         ui.horizontal(|ui| {
                             ui.label("Scale");
                         });
                         ui.horizontal(|ui| {
                             ui.label("X:");
-                            ui.add(egui::DragValue::new(&mut tf.scale.x).speed(0.01));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.scale.x).speed(0.01)));
                             ui.label("Y:");
-                            ui.add(egui::DragValue::new(&mut tf.scale.y).speed(0.01));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.scale.y).speed(0.01)));
                             ui.label("Z:");
-                            ui.add(egui::DragValue::new(&mut tf.scale.z).speed(0.01));
+                            editing |= in_use(ui.add(egui::DragValue::new(&mut tf.scale.z).speed(0.01)));
                         });
                         let (mut yaw, mut pitch, mut roll) =
                             tf.rotation.to_euler(glam::EulerRot::YXZ);
@@ -71,9 +81,11 @@ pub(crate) fn inspector_panel(
                         });
                         let mut changed = false;
                         ui.horizontal(|ui| {
-                            changed |= ui.add(egui::DragValue::new(&mut yaw).speed(0.5).prefix("Y: ")).changed();
-                            changed |= ui.add(egui::DragValue::new(&mut pitch).speed(0.5).prefix("X: ")).changed();
-                            changed |= ui.add(egui::DragValue::new(&mut roll).speed(0.5).prefix("Z: ")).changed();
+                            for (angle, prefix) in [(&mut yaw, "Y: "), (&mut pitch, "X: "), (&mut roll, "Z: ")] {
+                                let response = ui.add(egui::DragValue::new(angle).speed(0.5).prefix(prefix));
+                                changed |= response.changed();
+                                editing |= in_use(response);
+                            }
                         });
                         if changed {
                             tf.rotation = glam::Quat::from_euler(
@@ -84,19 +96,72 @@ pub(crate) fn inspector_panel(
                             );
                         }
                     });
+                let after = *tf;
+                history.track_transform(entity, before, after, editing);
             }
 
-            // List other component types (read-only for now).
-            let type_names = world.entity_component_names(entity);
-            for name in &type_names {
-                if *name == "Transform" {
-                    continue; // Already handled above.
-                }
-                egui::CollapsingHeader::new(*name)
+            // Other components: read-only, removable when registered.
+            let registry = world.get_resource::<SceneRegistry>();
+            let mut components: Vec<(String, Option<String>)> = world
+                .component_types(entity)
+                .into_iter()
+                .filter(|&(type_id, _)| type_id != TypeId::of::<Transform>())
+                .map(|(type_id, name)| {
+                    let registered = registry.and_then(|r| r.component_name(type_id)).map(str::to_string);
+                    (name.to_string(), registered)
+                })
+                .collect();
+            components.sort();
+
+            let mut remove = None;
+            for (name, registered) in &components {
+                egui::CollapsingHeader::new(name.as_str())
                     .default_open(false)
                     .show(ui, |ui| {
                         ui.label("(read-only view)");
+                        if let Some(registered) = registered
+                            && ui.button("Remove").clicked()
+                        {
+                            remove = Some(registered.clone());
+                        }
+                    });
+            }
+
+            // Registered components the entity doesn't have yet.
+            let mut addable: Vec<String> = registry
+                .map(|r| r.component_names().into_iter().map(str::to_string).collect())
+                .unwrap_or_default();
+            addable.retain(|name| {
+                registry.and_then(|r| r.default_value(name)).is_some()
+                    && !components.iter().any(|(_, registered)| registered.as_ref() == Some(name))
+                    && !(name == "Transform" && world.get::<Transform>(entity).is_some())
+            });
+            addable.sort();
+
+            let mut add = None;
+            if !addable.is_empty() {
+                ui.separator();
+                egui::ComboBox::from_id_salt("add_component")
+                    .selected_text("Add Component")
+                    .show_ui(ui, |ui| {
+                        for name in &addable {
+                            if ui.selectable_label(false, name).clicked() {
+                                add = Some(name.clone());
+                            }
+                        }
                     });
             }
+
+            if let Some(name) = remove {
+                history.remove_component(world, entity, &name);
+            }
+            if let Some(name) = add {
+                history.add_component(world, entity, &name);
+            }
         });
 }
+
+/// Whether a field is still being dragged or typed in.
+fn in_use(response: egui::Response) -> bool {
+    response.dragged() || response.has_focus()
+}
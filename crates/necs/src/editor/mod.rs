@@ -1,14 +1,42 @@
 //! In-engine editor overlay, toggled with F12.
 //!
 //! Feature-gated behind `#[cfg(feature = "editor")]`. Provides an entity
-//! hierarchy, component inspector, and toolbar using egui.
+//! hierarchy, component inspector, and toolbar using egui. With `render2d`,
+//! a sprite slicer window turns textures into sprite atlases. The Input
+//! Bindings window lists the [`ActionMap`](crate::action::ActionMap). The
+//! Shader A/B window flips the viewport between the frames before and after
+//! a shader reload (see [`ShaderDiff`](crate::render::ShaderDiff)). The
+//! About window shows the [build info](crate::version).
+//!
+//! The selected entity gets [transform gizmos](gizmo) in the viewport:
+//! W/E/R switch between move, rotate and scale, the toolbar sets snapping,
+//! and Ctrl+Z / Ctrl+Y undo and redo.
+//!
+//! The toolbar's [play controls](play) pause the game's systems, step them
+//! one frame at a time, and reset the world to how it was when Play was
+//! pressed.
+//!
+//! Every change made through the editor — transform edits, adding and
+//! removing components, creating and deleting entities — is recorded in an
+//! [undo history](undo) whose depth is set by the [`EditorSettings`]
+//! resource.
 //!
 //! The [`EditorState`] is stored directly in `WinitApp` rather than as a World
 //! resource because `egui_winit::State` is not `Sync`.
 
+mod about;
+mod bindings;
+mod gizmo;
 mod hierarchy;
 mod inspector;
+mod play;
+mod shader_diff;
+#[cfg(feature = "render2d")]
+mod sprite_slicer;
 mod toolbar;
+mod undo;
+
+pub use undo::EditorSettings;
 
 use std::sync::Arc;
 
@@ -26,6 +54,21 @@ pub struct EditorState {
     pub visible: bool,
     /// The currently selected entity in the hierarchy panel.
     pub selected: Option<Entity>,
+    /// Whether the Input Bindings window is open.
+    bindings_open: bool,
+    /// Whether the About window is open.
+    about_open: bool,
+    /// Shader A/B window state.
+    shader_diff: shader_diff::ShaderDiffWindow,
+    /// Transform gizmo mode, snapping and drag state.
+    gizmo: gizmo::Gizmo,
+    /// Undo/redo history of editor changes.
+    history: undo::UndoStack,
+    /// Play/pause/step state of the game's systems.
+    play: play::PlayControls,
+    /// Sprite slicer window state.
+    #[cfg(feature = "render2d")]
+    sprite_slicer: sprite_slicer::SpriteSlicer,
     /// Prepared paint jobs for the current frame.
     paint_jobs: Vec<egui::ClippedPrimitive>,
     /// Textures delta for the current frame.
@@ -60,12 +103,26 @@ impl EditorState {
             egui_renderer,
             visible: false,
             selected: None,
+            bindings_open: false,
+            about_open: false,
+            shader_diff: shader_diff::ShaderDiffWindow::default(),
+            gizmo: gizmo::Gizmo::default(),
+            history: undo::UndoStack::default(),
+            play: play::PlayControls::default(),
+            #[cfg(feature = "render2d")]
+            sprite_slicer: sprite_slicer::SpriteSlicer::new(),
             paint_jobs: Vec::new(),
             textures_delta: egui::TexturesDelta::default(),
             frame_ready: false,
         }
     }
 
+    /// Whether the game's systems should skip this frame: paused from the
+    /// toolbar and no step pending. Stays paused while the editor is hidden.
+    pub fn simulation_frozen(&mut self, world: &mut World) -> bool {
+        self.play.frozen(world)
+    }
+
     /// Forward a winit event to egui. Returns true if egui consumed the event.
     pub fn on_window_event(
         &mut self,
@@ -90,17 +147,54 @@ impl EditorState {
             return;
         }
 
+        #[cfg(feature = "render2d")]
+        self.sprite_slicer.prepare(world, &mut self.egui_renderer);
+
+        let depth = world.get_resource::<EditorSettings>().copied().unwrap_or_default().history_depth;
+        self.history.set_depth(depth);
+
         let raw_input = self.egui_winit.take_egui_input(window);
-        let selected = self.selected;
-        let mut new_selected = selected;
+        let mut selected = self.selected.filter(|&e| world.is_alive(e));
+        #[cfg(feature = "render2d")]
+        let slicer = &mut self.sprite_slicer;
+        let bindings_open = &mut self.bindings_open;
+        let about_open = &mut self.about_open;
+        let shader_diff = &mut self.shader_diff;
+        shader_diff.watch(world);
+        let gizmo = &mut self.gizmo;
+        let history = &mut self.history;
+        let play = &mut self.play;
+        let size = window.inner_size();
 
         let full_output = self.egui_ctx.run(raw_input, |ctx| {
-            toolbar::toolbar_panel(ctx);
-            new_selected = hierarchy::hierarchy_panel(ctx, world, selected);
-            inspector::inspector_panel(ctx, world, new_selected);
+            if let Some(entity) = undo::undo_shortcuts(ctx, world, history) {
+                selected = Some(entity);
+            }
+            #[cfg(feature = "render2d")]
+            toolbar::toolbar_panel(ctx, world, &mut selected, gizmo, history, play, Some(&mut slicer.open), bindings_open, &mut shader_diff.open, about_open);
+            #[cfg(not(feature = "render2d"))]
+            toolbar::toolbar_panel(ctx, world, &mut selected, gizmo, history, play, None, bindings_open, &mut shader_diff.open, about_open);
+            selected = selected.filter(|&e| world.is_alive(e));
+            selected = hierarchy::hierarchy_panel(ctx, world, selected);
+            inspector::inspector_panel(ctx, world, selected, history);
+            // After the panels, so the gizmo knows what space is left.
+            gizmo.ui(ctx, world, selected, history, (size.width, size.height));
+            if *bindings_open {
+                bindings::bindings_window(ctx, world, bindings_open);
+            }
+            if shader_diff.open {
+                shader_diff.ui(ctx, world);
+            }
+            if *about_open {
+                about::about_window(ctx, about_open);
+            }
+            #[cfg(feature = "render2d")]
+            if slicer.open {
+                slicer.ui(ctx);
+            }
         });
 
-        self.selected = new_selected;
+        self.selected = selected;
 
         self.egui_winit
             .handle_platform_output(window, full_output.platform_output);
@@ -0,0 +1,223 @@
+//! # Editor Play Controls — Pause, Step and Reset the Simulation
+//!
+//! The toolbar's Play / Pause / Step / Reset buttons control whether the
+//! game's update and fixed systems run. Everything else carries on while
+//! paused — rendering, transform propagation, the editor itself — so the
+//! scene can be inspected and edited with the gizmos mid-game:
+//!
+//! ```text
+//!   Playing ──Pause──► Paused ──Play──► Playing
+//!                       │  ▲
+//!                  Step └──┘ one frame of systems
+//!
+//!   The first frame the game runs captures the world, as does the first
+//!   Play or Step after a Reset; pauses keep the capture. Reset restores it
+//!   and pauses.
+//! ```
+//!
+//! Reset despawns every entity and reloads the capture through the world's
+//! [`SceneRegistry`](crate::scene::SceneRegistry), like a scene load: only
+//! registered components come back (just `Transform` without a registry),
+//! and entities get new ids, so the undo history is cleared. Capturing logs
+//! a warning naming the component types Reset will drop. Registered
+//! resources are restored; the rest keep their values from the moment of
+//! the Reset.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Play mode runs a copy of the scene and throws it away on
+//!   Stop; Pause and Step work during play.
+//! - **Bevy**: No editor; `Time<Virtual>::pause` stops time, not systems.
+//! - **Godot**: Play runs the game in a separate process; the remote scene
+//!   tree can be paused but not reset.
+//! - **Our approach**: Unity's buttons in the running game: Reset restores
+//!   a scene snapshot instead of discarding a copy.
+
+use std::collections::BTreeSet;
+
+use crate::ecs::Entity;
+use crate::ecs::world::World;
+use crate::scene::{SceneData, SceneRegistry, load_scene, save_scene, structural_types};
+
+use super::undo::{UndoStack, with_registry};
+
+/// Play/pause state and the world captured for Reset.
+#[derive(Debug, Default)]
+pub(crate) struct PlayControls {
+    paused: bool,
+    /// Let one frame of systems run while paused.
+    step: bool,
+    /// The world when play (re)started, for Reset.
+    saved: Option<SceneData>,
+}
+
+impl PlayControls {
+    /// Whether game systems should skip this frame. Consumes a pending step.
+    /// A running game with nothing captured is captured first, so Reset
+    /// works from the first frame.
+    pub fn frozen(&mut self, world: &mut World) -> bool {
+        if !self.paused {
+            self.capture(world);
+        }
+        self.paused && !std::mem::take(&mut self.step)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Resume, capturing the world first if nothing is captured.
+    pub fn play(&mut self, world: &mut World) {
+        self.capture(world);
+        self.paused = false;
+    }
+
+    /// Run one frame of systems, then stay paused.
+    pub fn step(&mut self, world: &mut World) {
+        self.capture(world);
+        self.paused = true;
+        self.step = true;
+    }
+
+    pub fn can_reset(&self) -> bool {
+        self.saved.is_some()
+    }
+
+    /// Restore the world captured at play start and pause.
+    pub fn reset(&mut self, world: &mut World, history: &mut UndoStack) {
+        let Some(saved) = self.saved.take() else {
+            return;
+        };
+        world.despawn_all();
+        let spawned = with_registry(world, |world, registry| load_scene(world, registry, &saved));
+        log::info!("[editor] Reset to the world from play start ({} entities)", spawned.len());
+        history.clear();
+        self.paused = true;
+        self.step = false;
+    }
+
+    fn capture(&mut self, world: &mut World) {
+        if self.saved.is_some() {
+            return;
+        }
+        self.saved = Some(with_registry(world, |world, registry| {
+            let dropped = unsaved_components(world, registry);
+            if !dropped.is_empty() {
+                let names: Vec<_> = dropped.into_iter().collect();
+                log::warn!(
+                    "[editor] Reset won't restore unregistered components: {}",
+                    names.join(", ")
+                );
+            }
+            save_scene(world, registry)
+        }));
+    }
+
+    /// The toolbar buttons. Returns `true` if the world was reset (the
+    /// selection is gone).
+    pub fn ui(&mut self, ui: &mut egui::Ui, world: &mut World, history: &mut UndoStack) -> bool {
+        if self.paused {
+            if ui.button("Play").clicked() {
+                self.play(world);
+            }
+        } else if ui.button("Pause").clicked() {
+            self.pause();
+        }
+        if ui.button("Step").on_hover_text("Run one frame, then pause").clicked() {
+            self.step(world);
+        }
+        let reset = ui
+            .add_enabled(self.can_reset(), egui::Button::new("Reset"))
+            .on_hover_text("Restore the world from when Play was pressed")
+            .clicked();
+        if reset {
+            self.reset(world, history);
+        }
+        if self.is_paused() {
+            ui.label(egui::RichText::new("Paused").color(egui::Color32::YELLOW));
+        }
+        reset
+    }
+}
+
+/// Short names of the component types in `world` that a scene saved with
+/// `registry` leaves out.
+fn unsaved_components(world: &World, registry: &SceneRegistry) -> BTreeSet<String> {
+    let skip = structural_types();
+    let mut dropped = BTreeSet::new();
+    world.for_each_entity(|entity, _| {
+        for (type_id, name) in world.component_types(entity) {
+            if !skip.contains(&type_id) && registry.component_name(type_id).is_none() {
+                dropped.insert(name.rsplit("::").next().unwrap_or(name).to_string());
+            }
+        }
+    });
+    dropped
+}
+
+/// Keep the selection across a reset when its name came back.
+pub(crate) fn reselect(world: &World, name: Option<String>) -> Option<Entity> {
+    name.and_then(|name| world.try_named(&name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Transform;
+
+    #[test]
+    fn step_lets_one_frame_through() {
+        let mut world = World::new();
+        let mut play = PlayControls::default();
+        assert!(!play.frozen(&mut world));
+        play.pause();
+        assert!(play.frozen(&mut world));
+        play.step(&mut world);
+        assert!(!play.frozen(&mut world));
+        assert!(play.frozen(&mut world) && play.is_paused());
+    }
+
+    #[test]
+    fn reset_restores_the_world_from_play_start() {
+        let mut world = World::new();
+        let player = world.spawn((Transform::from_xy(1.0, 2.0),));
+        world.name_entity(player, "player");
+        let mut play = PlayControls::default();
+        let mut history = UndoStack::default();
+
+        play.pause();
+        play.play(&mut world);
+        world.get_mut::<Transform>(player).unwrap().translation.x = 50.0;
+        world.spawn((Transform::default(),));
+        play.pause();
+        // A second Play keeps the first capture.
+        play.play(&mut world);
+
+        play.reset(&mut world, &mut history);
+        assert!(play.is_paused() && !play.can_reset());
+        assert_eq!(world.entity_count(), 1);
+        let player = reselect(&world, Some("player".into())).unwrap();
+        assert_eq!(world.get::<Transform>(player).unwrap().translation.x, 1.0);
+    }
+
+    #[test]
+    fn a_running_game_is_captured_on_its_first_frame() {
+        struct Health;
+
+        let mut world = World::new();
+        world.spawn((Transform::from_xy(1.0, 2.0), Health));
+        let mut play = PlayControls::default();
+        assert!(!play.can_reset());
+        assert!(!play.frozen(&mut world));
+        assert!(play.can_reset());
+
+        let dropped = with_registry(&mut world, |world, registry| {
+            unsaved_components(world, registry)
+        });
+        assert_eq!(dropped.into_iter().collect::<Vec<_>>(), vec!["Health"]);
+    }
+}
@@ -0,0 +1,84 @@
+//! Shader A/B window — the latest shader reload's before/after stats, and
+//! which of the two frames the viewport shows.
+
+use crate::ecs::world::World;
+use crate::render::{DiffView, ShaderDiff};
+
+/// Shader A/B window state. Opens by itself when a reload brings a new
+/// comparison.
+#[derive(Default)]
+pub(crate) struct ShaderDiffWindow {
+    pub open: bool,
+    /// `ShaderDiff::generation` last shown.
+    seen: u64,
+}
+
+impl ShaderDiffWindow {
+    /// Open the window if a shader reload captured a new comparison.
+    pub fn watch(&mut self, world: &World) {
+        if let Some(diff) = world.get_resource::<ShaderDiff>()
+            && diff.generation() != self.seen
+        {
+            self.seen = diff.generation();
+            self.open = true;
+        }
+    }
+
+    /// Draw the window.
+    pub fn ui(&mut self, ctx: &egui::Context, world: &mut World) {
+        let mut stop = false;
+        egui::Window::new("Shader A/B")
+            .open(&mut self.open)
+            .default_size([340.0, 160.0])
+            .show(ctx, |ui| {
+                let Some(diff) = world.get_resource_mut::<ShaderDiff>() else {
+                    ui.label("Shader reloads aren't being captured.");
+                    if ui.button("Capture before/after on reload").clicked() {
+                        world.insert_resource(ShaderDiff::new());
+                    }
+                    return;
+                };
+
+                match diff.comparison() {
+                    None => {
+                        ui.label("Save a shader to capture its before and after frames.");
+                    }
+                    Some(comparison) => {
+                        let stats = comparison.stats;
+                        ui.strong(comparison.shader.display().to_string());
+                        ui.label(format!(
+                            "{:.1}% of pixels changed, mean Δ {:.2}, max Δ {}",
+                            stats.changed * 100.0,
+                            stats.mean_delta,
+                            stats.max_delta
+                        ));
+                        if let Some((before, after)) = &comparison.saved {
+                            ui.label(format!("Saved {} and {}", before.display(), after.display()));
+                        }
+                    }
+                }
+
+                ui.separator();
+                ui.add_enabled_ui(diff.comparison().is_some(), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Viewport:");
+                        ui.selectable_value(&mut diff.view, DiffView::Live, "Live");
+                        ui.selectable_value(&mut diff.view, DiffView::Before, "Before");
+                        ui.selectable_value(&mut diff.view, DiffView::After, "After");
+                        if let Some(key) = diff.hotkey {
+                            ui.weak(format!("({key:?} cycles)"));
+                        }
+                    });
+                });
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(diff.comparison().is_some(), egui::Button::new("Clear")).clicked() {
+                        diff.clear();
+                    }
+                    stop = ui.button("Stop capturing").clicked();
+                });
+            });
+        if stop {
+            world.resource_remove::<ShaderDiff>();
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! Sprite slicer panel — cut a loaded texture into named regions, preview
+//! animations built from a selection, and save the result as a
+//! [`SpriteAtlas`] JSON file that the runtime can load.
+//!
+//! Regions are created either by slicing a uniform grid or by dragging
+//! freehand rectangles over the texture. Clicking a region toggles it in the
+//! selection; the selection order is the frame order used for the preview and
+//! for new clips.
+
+use crate::animation::{AtlasClip, AtlasRegion, SpriteAtlas};
+use crate::ecs::world::World;
+use crate::math::Vec2;
+use crate::render::gpu::GpuContext;
+use crate::render2d::texture::TextureStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SliceMode {
+    Grid,
+    Freehand,
+}
+
+/// The texture currently shown in the slicer, registered with egui.
+struct SlicerTexture {
+    id: egui::TextureId,
+    size: egui::Vec2,
+    /// The image's UV rectangle within the registered view — a sub-rect
+    /// when the texture was packed into an atlas page.
+    uv: egui::Rect,
+}
+
+impl SlicerTexture {
+    /// Map a UV rectangle relative to the image into the registered view.
+    fn sub_uv(&self, min: egui::Pos2, max: egui::Pos2) -> egui::Rect {
+        let at = |p: egui::Pos2| self.uv.min + p.to_vec2() * self.uv.size();
+        egui::Rect::from_min_max(at(min), at(max))
+    }
+}
+
+/// Sprite slicer state. Lives in `EditorState` across frames.
+pub(crate) struct SpriteSlicer {
+    /// Whether the slicer window is shown (toggled from the toolbar).
+    pub open: bool,
+    /// Texture path to open on the next frame (registration needs the renderer).
+    requested: Option<String>,
+    texture: Option<SlicerTexture>,
+    /// Paths of textures loaded in the `TextureStore`, for the picker.
+    available: Vec<String>,
+    path_input: String,
+    atlas: SpriteAtlas,
+    mode: SliceMode,
+    grid_prefix: String,
+    tile_size: [f32; 2],
+    padding: [f32; 2],
+    offset: [f32; 2],
+    /// Selected region indices, in click order.
+    selection: Vec<usize>,
+    /// Freehand drag start, in texture pixels.
+    drag_start: Option<egui::Pos2>,
+    zoom: f32,
+    clip_name: String,
+    frame_time: f32,
+    looping: bool,
+    preview_time: f32,
+    save_path: String,
+    status: String,
+}
+
+impl SpriteSlicer {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            requested: None,
+            texture: None,
+            available: Vec::new(),
+            path_input: String::new(),
+            atlas: SpriteAtlas::default(),
+            mode: SliceMode::Grid,
+            grid_prefix: "frame".to_string(),
+            tile_size: [16.0, 16.0],
+            padding: [0.0, 0.0],
+            offset: [0.0, 0.0],
+            selection: Vec::new(),
+            drag_start: None,
+            zoom: 2.0,
+            clip_name: "clip".to_string(),
+            frame_time: 0.1,
+            looping: true,
+            preview_time: 0.0,
+            save_path: "assets/atlas.json".to_string(),
+            status: String::new(),
+        }
+    }
+
+    /// Refresh the texture list and register a newly requested texture with
+    /// egui. Must run before the egui frame starts.
+    pub fn prepare(&mut self, world: &mut World, renderer: &mut egui_wgpu::Renderer) {
+        if !self.open {
+            return;
+        }
+
+        if let Some(store) = world.get_resource::<TextureStore>() {
+            self.available = store.loaded_paths().map(|(p, _)| p.to_string()).collect();
+            self.available.sort();
+        }
+
+        let Some(path) = self.requested.take() else {
+            return;
+        };
+
+        // Load the texture if the game hasn't already.
+        let loaded = self.available.contains(&path);
+        if !loaded {
+            if !std::path::Path::new(&path).exists() {
+                self.status = format!("No such file: {path}");
+                return;
+            }
+            crate::render2d::texture::load_texture(world, &path);
+        }
+
+        let (Some(gpu), Some(store)) = (
+            world.get_resource::<GpuContext>(),
+            world.get_resource::<TextureStore>(),
+        ) else {
+            return;
+        };
+        let Some((_, handle)) = store.loaded_paths().find(|(p, _)| *p == path) else {
+            return;
+        };
+        let entry = store.get(handle);
+
+        if let Some(old) = self.texture.take() {
+            renderer.free_texture(&old.id);
+        }
+        let id = renderer.register_native_texture(&gpu.device, &entry.view, wgpu::FilterMode::Nearest);
+        let size = egui::vec2(entry.width as f32, entry.height as f32);
+        let (_, region) = store.draw_source(handle);
+        let uv = egui::Rect::from_min_max(
+            egui::pos2(region.min.x, region.min.y),
+            egui::pos2(region.max.x, region.max.y),
+        );
+        self.texture = Some(SlicerTexture { id, size, uv });
+
+        // Keep regions when re-opening the atlas's own texture (e.g. after Load).
+        if self.atlas.texture != path {
+            self.atlas = SpriteAtlas::new(&path, Vec2::new(size.x, size.y));
+            self.selection.clear();
+        } else {
+            self.atlas.texture_size = Vec2::new(size.x, size.y);
+        }
+        self.path_input = path;
+        self.status.clear();
+    }
+
+    /// Draw the slicer window.
+    pub fn ui(&mut self, ctx: &egui::Context) {
+        let mut open = self.open;
+        egui::Window::new("Sprite Slicer")
+            .open(&mut open)
+            .default_size([760.0, 520.0])
+            .show(ctx, |ui| {
+                self.texture_picker(ui);
+                ui.separator();
+
+                if self.texture.is_none() {
+                    ui.label("Open a texture to start slicing.");
+                    return;
+                }
+
+                self.slice_controls(ui);
+                ui.separator();
+
+                ui.columns(2, |cols| {
+                    egui::ScrollArea::both()
+                        .id_salt("slicer_canvas")
+                        .show(&mut cols[0], |ui| self.canvas(ui));
+                    egui::ScrollArea::vertical()
+                        .id_salt("slicer_side")
+                        .show(&mut cols[1], |ui| {
+                            self.preview(ui);
+                            ui.separator();
+                            self.clip_list(ui);
+                            ui.separator();
+                            self.region_list(ui);
+                        });
+                });
+
+                ui.separator();
+                self.save_controls(ui);
+            });
+        self.open = open;
+    }
+
+    fn texture_picker(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Texture:");
+            egui::ComboBox::from_id_salt("slicer_texture")
+                .selected_text(if self.atlas.texture.is_empty() {
+                    "<none>"
+                } else {
+                    self.atlas.texture.as_str()
+                })
+                .show_ui(ui, |ui| {
+                    for path in &self.available {
+                        if ui.selectable_label(*path == self.atlas.texture, path).clicked() {
+                            self.requested = Some(path.clone());
+                        }
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.path_input).desired_width(180.0));
+            if ui.button("Open").clicked() && !self.path_input.is_empty() {
+                self.requested = Some(self.path_input.clone());
+            }
+        });
+    }
+
+    fn slice_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.radio_value(&mut self.mode, SliceMode::Grid, "Grid");
+            ui.radio_value(&mut self.mode, SliceMode::Freehand, "Freehand");
+            ui.separator();
+            ui.label("Zoom");
+            ui.add(egui::Slider::new(&mut self.zoom, 0.5..=8.0));
+            ui.separator();
+            if ui.button("Clear regions").clicked() {
+                self.atlas.regions.clear();
+                self.selection.clear();
+            }
+        });
+
+        if self.mode == SliceMode::Grid {
+            ui.horizontal(|ui| {
+                ui.label("Prefix");
+                ui.add(egui::TextEdit::singleline(&mut self.grid_prefix).desired_width(70.0));
+                ui.label("Tile");
+                ui.add(egui::DragValue::new(&mut self.tile_size[0]).range(1.0..=4096.0));
+                ui.add(egui::DragValue::new(&mut self.tile_size[1]).range(1.0..=4096.0));
+                ui.label("Padding");
+                ui.add(egui::DragValue::new(&mut self.padding[0]).range(0.0..=256.0));
+                ui.add(egui::DragValue::new(&mut self.padding[1]).range(0.0..=256.0));
+                ui.label("Offset");
+                ui.add(egui::DragValue::new(&mut self.offset[0]).range(0.0..=4096.0));
+                ui.add(egui::DragValue::new(&mut self.offset[1]).range(0.0..=4096.0));
+                if ui.button("Slice").clicked() {
+                    let added = self.atlas.slice_grid(
+                        &self.grid_prefix,
+                        Vec2::from(self.tile_size),
+                        Vec2::from(self.padding),
+                        Vec2::from(self.offset),
+                    );
+                    self.status = format!("Added {added} regions");
+                }
+            });
+        } else {
+            ui.label("Drag on the texture to add a region. Click a region to select it.");
+        }
+    }
+
+    /// The texture with region overlays. Handles selection and freehand drags.
+    fn canvas(&mut self, ui: &mut egui::Ui) {
+        let Some(tex) = &self.texture else { return };
+        let zoom = self.zoom;
+        let (rect, response) =
+            ui.allocate_exact_size(tex.size * zoom, egui::Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        painter.image(tex.id, rect, tex.uv, egui::Color32::WHITE);
+
+        let to_screen = |r: &AtlasRegion| {
+            egui::Rect::from_min_size(
+                rect.min + egui::vec2(r.x, r.y) * zoom,
+                egui::vec2(r.w, r.h) * zoom,
+            )
+        };
+        let to_texture = |pos: egui::Pos2| {
+            let p = (pos - rect.min) / zoom;
+            egui::pos2(
+                p.x.round().clamp(0.0, tex.size.x),
+                p.y.round().clamp(0.0, tex.size.y),
+            )
+        };
+
+        for (i, region) in self.atlas.regions.iter().enumerate() {
+            let color = if self.selection.contains(&i) {
+                egui::Color32::YELLOW
+            } else {
+                egui::Color32::from_rgb(80, 200, 120)
+            };
+            painter.rect_stroke(
+                to_screen(region),
+                0.0,
+                egui::Stroke::new(1.0, color),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        let pointer = response.interact_pointer_pos();
+
+        if response.clicked()
+            && let Some(pos) = pointer
+        {
+            let p = to_texture(pos);
+            let hit = self.atlas.regions.iter().position(|r| {
+                p.x >= r.x && p.x < r.x + r.w && p.y >= r.y && p.y < r.y + r.h
+            });
+            if let Some(i) = hit {
+                toggle_selection(&mut self.selection, i);
+            }
+        }
+
+        if self.mode != SliceMode::Freehand {
+            return;
+        }
+        if response.drag_started() {
+            self.drag_start = pointer.map(to_texture);
+        }
+        let (Some(start), Some(pos)) = (self.drag_start, pointer) else {
+            return;
+        };
+        let end = to_texture(pos);
+        let pending = AtlasRegion {
+            name: format!("region_{}", self.atlas.regions.len()),
+            x: start.x.min(end.x),
+            y: start.y.min(end.y),
+            w: (end.x - start.x).abs(),
+            h: (end.y - start.y).abs(),
+        };
+        if response.drag_stopped() {
+            self.drag_start = None;
+            if pending.w >= 1.0 && pending.h >= 1.0 {
+                self.atlas.regions.push(pending);
+            }
+        } else {
+            painter.rect_stroke(
+                to_screen(&pending),
+                0.0,
+                egui::Stroke::new(1.0, egui::Color32::WHITE),
+                egui::StrokeKind::Inside,
+            );
+        }
+    }
+
+    /// Animate the current selection.
+    fn preview(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Preview");
+        let Some(tex) = &self.texture else { return };
+        if self.selection.is_empty() {
+            ui.label("Select regions to preview them as an animation.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Frame time");
+            ui.add(egui::DragValue::new(&mut self.frame_time).speed(0.01).range(0.01..=5.0));
+            ui.checkbox(&mut self.looping, "Loop");
+        });
+
+        self.preview_time += ui.input(|i| i.stable_dt);
+        let step = (self.preview_time / self.frame_time) as usize;
+        let frame = if self.looping {
+            step % self.selection.len()
+        } else {
+            step.min(self.selection.len() - 1)
+        };
+        let Some(region) = self.atlas.regions.get(self.selection[frame]) else {
+            return;
+        };
+
+        let uv = tex.sub_uv(
+            egui::pos2(region.x / tex.size.x, region.y / tex.size.y),
+            egui::pos2((region.x + region.w) / tex.size.x, (region.y + region.h) / tex.size.y),
+        );
+        let scale = (128.0 / region.w.max(region.h)).min(4.0);
+        ui.add(egui::Image::new((tex.id, egui::vec2(region.w, region.h) * scale)).uv(uv));
+        ui.label(format!("{} ({}/{})", region.name, frame + 1, self.selection.len()));
+    }
+
+    fn clip_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Clips");
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.clip_name).desired_width(100.0));
+            let can_add = !self.selection.is_empty() && !self.clip_name.is_empty();
+            if ui.add_enabled(can_add, egui::Button::new("Add from selection")).clicked() {
+                let frames = self
+                    .selection
+                    .iter()
+                    .filter_map(|&i| self.atlas.regions.get(i))
+                    .map(|r| r.name.clone())
+                    .collect();
+                self.atlas.clips.retain(|c| c.name != self.clip_name);
+                self.atlas.clips.push(AtlasClip {
+                    name: self.clip_name.clone(),
+                    frames,
+                    frame_time: self.frame_time,
+                    looping: self.looping,
+                });
+            }
+        });
+
+        let mut remove = None;
+        for (i, clip) in self.atlas.clips.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{} ({} frames)", clip.name, clip.frames.len()));
+                if ui.small_button("Select").clicked() {
+                    self.selection = clip
+                        .frames
+                        .iter()
+                        .filter_map(|f| self.atlas.regions.iter().position(|r| &r.name == f))
+                        .collect();
+                    self.frame_time = clip.frame_time;
+                    self.looping = clip.looping;
+                    self.preview_time = 0.0;
+                }
+                if ui.small_button("x").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.atlas.clips.remove(i);
+        }
+    }
+
+    fn region_list(&mut self, ui: &mut egui::Ui) {
+        ui.heading(format!("Regions ({})", self.atlas.regions.len()));
+        let mut remove = None;
+        for (i, region) in self.atlas.regions.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let mut selected = self.selection.contains(&i);
+                if ui.checkbox(&mut selected, "").changed() {
+                    toggle_selection(&mut self.selection, i);
+                }
+                ui.add(egui::TextEdit::singleline(&mut region.name).desired_width(100.0));
+                ui.label(format!("{},{} {}x{}", region.x, region.y, region.w, region.h));
+                if ui.small_button("x").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.atlas.regions.remove(i);
+            self.selection.retain(|&s| s != i);
+            for s in &mut self.selection {
+                if *s > i {
+                    *s -= 1;
+                }
+            }
+        }
+    }
+
+    fn save_controls(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Atlas file:");
+            ui.add(egui::TextEdit::singleline(&mut self.save_path).desired_width(220.0));
+            if ui.button("Save").clicked() {
+                self.status = match self.atlas.save(&self.save_path) {
+                    Ok(()) => format!("Saved {}", self.save_path),
+                    Err(e) => format!("Save failed: {e}"),
+                };
+                log::info!("[editor] {}", self.status);
+            }
+            if ui.button("Load").clicked() {
+                match SpriteAtlas::from_file(&self.save_path) {
+                    Some(atlas) => {
+                        self.requested = Some(atlas.texture.clone());
+                        self.atlas = atlas;
+                        self.selection.clear();
+                        self.status = format!("Loaded {}", self.save_path);
+                    }
+                    None => self.status = format!("Could not load {}", self.save_path),
+                }
+            }
+            ui.label(&self.status);
+        });
+    }
+}
+
+/// Add `index` to the end of the selection, or remove it if already selected.
+fn toggle_selection(selection: &mut Vec<usize>, index: usize) {
+    if let Some(pos) = selection.iter().position(|&s| s == index) {
+        selection.remove(pos);
+    } else {
+        selection.push(index);
+    }
+}
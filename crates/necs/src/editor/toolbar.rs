@@ -1,17 +1,47 @@
-//! Top toolbar panel — save/load, new entity, delete entity.
+//! Top toolbar panel — play controls, save/load, new entity, delete entity,
+//! gizmo mode and snapping, undo/redo, tool windows, About.
 
-/// Draw the top toolbar panel.
-pub(crate) fn toolbar_panel(ctx: &egui::Context) {
+use crate::ecs::Entity;
+use crate::ecs::world::World;
+
+use super::gizmo::{Gizmo, GizmoMode};
+use super::play::{PlayControls, reselect};
+use super::undo::UndoStack;
+
+/// Draw the top toolbar panel. `sprite_slicer` is the slicer window's open
+/// flag, or `None` when the slicer isn't available (no `render2d`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn toolbar_panel(
+    ctx: &egui::Context,
+    world: &mut World,
+    selected: &mut Option<Entity>,
+    gizmo: &mut Gizmo,
+    history: &mut UndoStack,
+    play: &mut PlayControls,
+    sprite_slicer: Option<&mut bool>,
+    input_bindings: &mut bool,
+    shader_diff: &mut bool,
+    about: &mut bool,
+) {
     egui::TopBottomPanel::top("editor_toolbar").show(ctx, |ui| {
         egui::MenuBar::new().ui(ui, |ui| {
             ui.label("necs editor");
             ui.separator();
 
+            let selected_name = selected.and_then(|e| world.entity_name(e)).map(str::to_string);
+            if play.ui(ui, world, history) {
+                *selected = reselect(world, selected_name);
+            }
+
+            ui.separator();
+
             if ui.button("New Entity").clicked() {
-                log::info!("[editor] New Entity clicked (TODO)");
+                *selected = Some(history.spawn(world));
             }
-            if ui.button("Delete Entity").clicked() {
-                log::info!("[editor] Delete Entity clicked (TODO)");
+            if ui.add_enabled(selected.is_some(), egui::Button::new("Delete Entity")).clicked()
+                && let Some(entity) = selected.take()
+            {
+                history.despawn(world, entity);
             }
 
             ui.separator();
@@ -23,6 +53,39 @@ pub(crate) fn toolbar_panel(ctx: &egui::Context) {
                 log::info!("[editor] Load Scene clicked (TODO)");
             }
 
+            ui.separator();
+
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Translate, "Move (W)");
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Rotate, "Rotate (E)");
+            ui.selectable_value(&mut gizmo.mode, GizmoMode::Scale, "Scale (R)");
+            ui.checkbox(&mut gizmo.snap.enabled, "Snap");
+            ui.add_enabled_ui(gizmo.snap.enabled, |ui| {
+                ui.add(egui::DragValue::new(&mut gizmo.snap.grid).speed(0.05).range(0.01..=100.0).prefix("grid "));
+                ui.add(egui::DragValue::new(&mut gizmo.snap.angle).speed(0.5).range(1.0..=180.0).prefix("angle ").suffix("°"));
+                ui.add(egui::DragValue::new(&mut gizmo.snap.scale).speed(0.01).range(0.01..=10.0).prefix("scale "));
+            });
+
+            ui.separator();
+
+            if ui.add_enabled(history.can_undo(), egui::Button::new("Undo")).clicked()
+                && let Some(entity) = history.undo(world)
+            {
+                *selected = Some(entity);
+            }
+            if ui.add_enabled(history.can_redo(), egui::Button::new("Redo")).clicked()
+                && let Some(entity) = history.redo(world)
+            {
+                *selected = Some(entity);
+            }
+
+            ui.separator();
+            if let Some(open) = sprite_slicer {
+                ui.toggle_value(open, "Sprite Slicer");
+            }
+            ui.toggle_value(input_bindings, "Input Bindings");
+            ui.toggle_value(shader_diff, "Shader A/B");
+            ui.toggle_value(about, "About");
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.label("F12 to toggle");
             });
@@ -0,0 +1,571 @@
+//! # Editor Undo — Command History
+//!
+//! Every change the editor makes goes through the [`UndoStack`] as an
+//! [`Edit`] that knows how to apply itself in both directions:
+//!
+//! ```text
+//!   edit                 undo                    redo
+//!   ─────────────────    ─────────────────────   ─────────────────
+//!   Transform  a → b     write a                 write b
+//!   AddComponent C       remove C                insert C again
+//!   RemoveComponent C    insert C (saved JSON)   remove C
+//!   Spawn                despawn (+ children)    respawn snapshot
+//!   Despawn              respawn snapshot        despawn
+//!
+//!   undo stack: [e1 e2 e3 e4]  ◄── Ctrl+Z pops e4 ──► redo stack: [e4]
+//!               ▲ oldest dropped past EditorSettings::history_depth
+//! ```
+//!
+//! Ctrl+Z undoes, Ctrl+Y or Ctrl+Shift+Z redoes; a new edit clears the redo
+//! stack. An edit whose entity is gone (despawned by the game) is skipped.
+//!
+//! Components cross the history as JSON, so adding, removing and respawning
+//! needs them registered in the world's
+//! [`SceneRegistry`](crate::scene::SceneRegistry) resource — the same
+//! registry the inspector's "Add Component" menu lists. Without one, only
+//! [`Transform`] (plus names and tags) survives a delete and undo.
+//!
+//! A respawned entity gets a new [`Entity`] id; the history swaps the old id
+//! for the new one in every remaining edit, so older edits to it still undo.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Undo.RecordObject` snapshots serialized objects before a
+//!   change; every editor action is undoable, grouped per frame.
+//! - **Bevy**: No editor, no undo.
+//! - **Godot**: `UndoRedo` actions built from do/undo method calls and
+//!   property sets, merged while a slider is dragged.
+//! - **Our approach**: Godot-style command objects, with Unity-style
+//!   serialized snapshots for deletes.
+
+use std::collections::VecDeque;
+
+use serde_json::Value;
+
+use crate::ecs::Entity;
+use crate::ecs::hierarchy::{Children, GlobalTransform, Parent};
+use crate::ecs::world::World;
+use crate::math::Transform;
+use crate::prefab::{Prefab, snapshot};
+use crate::scene::{SceneLoadMode, SceneRegistry, spawn_scene};
+
+/// Editor settings, read from the world each frame the editor is open.
+///
+/// ```ignore
+/// Game::new().resource(EditorSettings { history_depth: 500 })
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EditorSettings {
+    /// Most undoable edits kept; the oldest are dropped first. Default: 100.
+    pub history_depth: usize,
+}
+
+impl Default for EditorSettings {
+    fn default() -> Self {
+        Self { history_depth: 100 }
+    }
+}
+
+// ── Edits ────────────────────────────────────────────────────────────────
+
+/// One undoable change.
+#[derive(Debug, Clone)]
+pub(crate) enum Edit {
+    Transform {
+        entity: Entity,
+        before: Transform,
+        after: Transform,
+    },
+    AddComponent {
+        entity: Entity,
+        component: String,
+        value: Value,
+    },
+    RemoveComponent {
+        entity: Entity,
+        component: String,
+        value: Value,
+    },
+    Spawn(Snapshot),
+    Despawn(Snapshot),
+}
+
+/// An entity and its descendants, saved so they can be put back.
+#[derive(Debug, Clone)]
+pub(crate) struct Snapshot {
+    prefab: Prefab,
+    /// The entities as last spawned, indexed by prefab id (root first).
+    entities: Vec<Entity>,
+    /// The root's parent and its position among the parent's children.
+    parent: Option<(Entity, usize)>,
+}
+
+impl Snapshot {
+    fn capture(world: &mut World, entity: Entity) -> Snapshot {
+        let parent = world.get::<Parent>(entity).map(|p| p.0).map(|parent| {
+            let index = world
+                .get::<Children>(parent)
+                .and_then(|c| c.0.iter().position(|&child| child == entity))
+                .unwrap_or(usize::MAX);
+            (parent, index)
+        });
+        let (prefab, entities) = with_registry(world, |world, registry| snapshot(world, registry, entity));
+        Snapshot {
+            prefab,
+            entities,
+            parent,
+        }
+    }
+
+    fn root(&self) -> Entity {
+        self.entities[0]
+    }
+
+    /// Despawn the live copy. `false` if it is already gone.
+    fn despawn(&self, world: &mut World) -> bool {
+        world.despawn_recursive(self.root())
+    }
+
+    /// Spawn the saved entities again, returning old → new ids.
+    fn respawn(&mut self, world: &mut World) -> Option<Vec<(Entity, Entity)>> {
+        if world.is_alive(self.root()) {
+            return None;
+        }
+        let spawned = with_registry(world, |world, registry| {
+            spawn_scene(world, registry, self.prefab.scene(), SceneLoadMode::Strict, None)
+        })
+        .ok()?;
+
+        let mut remapped = Vec::new();
+        for (id, old) in self.entities.iter_mut().enumerate() {
+            if let Some(&new) = spawned.get(&(id as u32)) {
+                remapped.push((*old, new));
+                *old = new;
+            }
+        }
+        if let Some((parent, index)) = self.parent
+            && world.is_alive(parent)
+        {
+            attach(world, parent, self.root(), index);
+        }
+        Some(remapped)
+    }
+
+    fn remap(&mut self, old: Entity, new: Entity) {
+        for entity in &mut self.entities {
+            if *entity == old {
+                *entity = new;
+            }
+        }
+        if let Some((parent, _)) = &mut self.parent
+            && *parent == old
+        {
+            *parent = new;
+        }
+    }
+}
+
+/// Make `child` the `index`th child of `parent`.
+fn attach(world: &mut World, parent: Entity, child: Entity, index: usize) {
+    world.insert(child, Parent(parent));
+    world.insert(child, GlobalTransform::default());
+    match world.get_mut::<Children>(parent) {
+        Some(children) => {
+            let index = index.min(children.0.len());
+            children.0.insert(index, child);
+        }
+        None => world.insert(parent, Children(vec![child])),
+    }
+}
+
+/// What applying an edit did.
+struct Applied {
+    /// Entity to select afterwards.
+    select: Option<Entity>,
+    /// Entities that were respawned under new ids.
+    remapped: Vec<(Entity, Entity)>,
+}
+
+impl Applied {
+    fn select(entity: Entity) -> Option<Applied> {
+        Some(Applied {
+            select: Some(entity),
+            remapped: Vec::new(),
+        })
+    }
+}
+
+impl Edit {
+    /// Redo (`forward`) or undo the edit. `None` if it no longer applies.
+    fn apply(&mut self, world: &mut World, forward: bool) -> Option<Applied> {
+        match self {
+            Edit::Transform { entity, before, after } => {
+                let tf = world.get_mut::<Transform>(*entity)?;
+                *tf = if forward { *after } else { *before };
+                Applied::select(*entity)
+            }
+            Edit::AddComponent { entity, component, value } => {
+                toggle_component(world, *entity, component, value, forward)
+            }
+            Edit::RemoveComponent { entity, component, value } => {
+                toggle_component(world, *entity, component, value, !forward)
+            }
+            Edit::Spawn(snapshot) if forward => respawned(world, snapshot),
+            Edit::Despawn(snapshot) if !forward => respawned(world, snapshot),
+            Edit::Spawn(snapshot) | Edit::Despawn(snapshot) => {
+                snapshot.despawn(world).then(|| Applied {
+                    select: None,
+                    remapped: Vec::new(),
+                })
+            }
+        }
+    }
+
+    fn remap(&mut self, old: Entity, new: Entity) {
+        match self {
+            Edit::Transform { entity, .. }
+            | Edit::AddComponent { entity, .. }
+            | Edit::RemoveComponent { entity, .. } => {
+                if *entity == old {
+                    *entity = new;
+                }
+            }
+            Edit::Spawn(snapshot) | Edit::Despawn(snapshot) => snapshot.remap(old, new),
+        }
+    }
+}
+
+fn respawned(world: &mut World, snapshot: &mut Snapshot) -> Option<Applied> {
+    let remapped = snapshot.respawn(world)?;
+    Some(Applied {
+        select: Some(snapshot.root()),
+        remapped,
+    })
+}
+
+/// Insert (`present`) or remove a registered component.
+fn toggle_component(
+    world: &mut World,
+    entity: Entity,
+    component: &str,
+    value: &Value,
+    present: bool,
+) -> Option<Applied> {
+    if !world.is_alive(entity) {
+        return None;
+    }
+    let done = with_registry(world, |world, registry| {
+        let type_id = registry.component_type(component)?;
+        if present {
+            if let Err(e) = registry.insert_component(world, entity, component, value.clone()) {
+                log::warn!("[editor] Can't restore {component}: {e}");
+                return None;
+            }
+            Some(())
+        } else {
+            world.remove_any_component(entity, type_id).then_some(())
+        }
+    });
+    done.and_then(|()| Applied::select(entity))
+}
+
+/// Run `f` with the world's [`SceneRegistry`], or one that only knows
+/// [`Transform`] if the world has none.
+pub(crate) fn with_registry<R>(world: &mut World, f: impl FnOnce(&mut World, &SceneRegistry) -> R) -> R {
+    match world.resource_remove::<SceneRegistry>() {
+        Some(registry) => {
+            let result = f(world, &registry);
+            world.insert_resource(registry);
+            result
+        }
+        None => {
+            let mut registry = SceneRegistry::new();
+            registry.register::<Transform>();
+            f(world, &registry)
+        }
+    }
+}
+
+// ── History ──────────────────────────────────────────────────────────────
+
+/// Undo and redo stacks, newest last.
+#[derive(Debug)]
+pub(crate) struct UndoStack {
+    undo: VecDeque<Edit>,
+    redo: Vec<Edit>,
+    depth: usize,
+    /// An inspector edit still in progress: entity, value before, latest.
+    pending: Option<(Entity, Transform, Transform)>,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            depth: EditorSettings::default().history_depth,
+            pending: None,
+        }
+    }
+}
+
+impl UndoStack {
+    /// Record a change that has already been made.
+    pub fn push(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.undo.push_back(edit);
+        self.trim();
+    }
+
+    /// Keep at most `depth` edits.
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = depth;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        while self.undo.len() > self.depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Forget every edit, e.g. after the world was replaced.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.pending = None;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Revert the newest edit that still applies. Returns the entity to
+    /// select, if the edit has one.
+    pub fn undo(&mut self, world: &mut World) -> Option<Entity> {
+        while let Some(mut edit) = self.undo.pop_back() {
+            if let Some(applied) = edit.apply(world, false) {
+                self.redo.push(edit);
+                self.remap(&applied.remapped);
+                return applied.select;
+            }
+        }
+        None
+    }
+
+    /// Reapply the newest undone edit that still applies. Returns the entity
+    /// to select, if the edit has one.
+    pub fn redo(&mut self, world: &mut World) -> Option<Entity> {
+        while let Some(mut edit) = self.redo.pop() {
+            if let Some(applied) = edit.apply(world, true) {
+                self.undo.push_back(edit);
+                self.remap(&applied.remapped);
+                return applied.select;
+            }
+        }
+        None
+    }
+
+    fn remap(&mut self, remapped: &[(Entity, Entity)]) {
+        for &(old, new) in remapped {
+            for edit in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+                edit.remap(old, new);
+            }
+        }
+    }
+
+    // ── Editor actions ───────────────────────────────────────────────────
+
+    /// Follow an inspector edit of `entity`'s transform. `editing` is true
+    /// while a field is still being dragged or typed in; the edit is
+    /// recorded once it stops, as one step.
+    pub fn track_transform(&mut self, entity: Entity, before: Transform, after: Transform, editing: bool) {
+        if before != after {
+            self.pending = match self.pending {
+                Some((pending, first, _)) if pending == entity => Some((entity, first, after)),
+                _ => Some((entity, before, after)),
+            };
+        }
+        if !editing
+            && let Some((entity, before, after)) = self.pending.take()
+            && before != after
+        {
+            self.push(Edit::Transform { entity, before, after });
+        }
+    }
+
+    /// Spawn an empty entity at the origin.
+    pub fn spawn(&mut self, world: &mut World) -> Entity {
+        let entity = world.spawn((Transform::default(),));
+        let snapshot = Snapshot::capture(world, entity);
+        self.push(Edit::Spawn(snapshot));
+        entity
+    }
+
+    /// Despawn `entity` and its descendants.
+    pub fn despawn(&mut self, world: &mut World, entity: Entity) {
+        if !world.is_alive(entity) {
+            return;
+        }
+        let snapshot = Snapshot::capture(world, entity);
+        world.despawn_recursive(entity);
+        self.push(Edit::Despawn(snapshot));
+    }
+
+    /// Add a registered component with its default value.
+    pub fn add_component(&mut self, world: &mut World, entity: Entity, component: &str) {
+        let Some(value) = world
+            .get_resource::<SceneRegistry>()
+            .and_then(|registry| registry.default_value(component))
+        else {
+            log::warn!("[editor] {component} has no registered default");
+            return;
+        };
+        let mut edit = Edit::AddComponent {
+            entity,
+            component: component.to_string(),
+            value,
+        };
+        if edit.apply(world, true).is_some() {
+            self.push(edit);
+        }
+    }
+
+    /// Remove a registered component, saving its value for undo.
+    pub fn remove_component(&mut self, world: &mut World, entity: Entity, component: &str) {
+        let Some(value) = world
+            .get_resource::<SceneRegistry>()
+            .and_then(|registry| registry.component_value(world, entity, component))
+        else {
+            log::warn!("[editor] Can't remove {component}: not registered");
+            return;
+        };
+        let mut edit = Edit::RemoveComponent {
+            entity,
+            component: component.to_string(),
+            value,
+        };
+        if edit.apply(world, true).is_some() {
+            self.push(edit);
+        }
+    }
+}
+
+/// Handle the undo/redo keyboard shortcuts. Returns the entity to select.
+pub(crate) fn undo_shortcuts(ctx: &egui::Context, world: &mut World, history: &mut UndoStack) -> Option<Entity> {
+    use egui::{Key, KeyboardShortcut, Modifiers};
+
+    let redo_shift = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+    let redo_y = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+    let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+
+    // Text fields keep their own undo.
+    if ctx.wants_keyboard_input() {
+        return None;
+    }
+    // Most specific shortcut first: Ctrl+Z would also match Ctrl+Shift+Z.
+    if ctx.input_mut(|i| i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo_y)) {
+        history.redo(world)
+    } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+        history.undo(world)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Health(u32);
+
+    fn at(x: f32) -> Transform {
+        Transform::from_xyz(x, 0.0, 0.0)
+    }
+
+    #[test]
+    fn transform_edits_undo_redo_and_trim() {
+        let mut world = World::new();
+        let entity = world.spawn((at(1.0),));
+        let gone = world.spawn((Transform::default(),));
+
+        let mut history = UndoStack::default();
+        history.push(Edit::Transform { entity, before: at(1.0), after: at(2.0) });
+        history.push(Edit::Transform { entity: gone, before: at(0.0), after: at(5.0) });
+        world.despawn(gone);
+        *world.get_mut::<Transform>(entity).unwrap() = at(2.0);
+
+        // The despawned entity's edit is skipped.
+        assert_eq!(history.undo(&mut world), Some(entity));
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 1.0);
+        assert!(!history.can_undo());
+
+        history.redo(&mut world);
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 2.0);
+
+        // A drag in the inspector is one step, and a fresh edit drops the
+        // redo list.
+        history.undo(&mut world);
+        history.track_transform(entity, at(1.0), at(1.5), true);
+        history.track_transform(entity, at(1.5), at(3.0), true);
+        history.track_transform(entity, at(3.0), at(3.0), false);
+        assert!(!history.can_redo());
+        history.undo(&mut world);
+        assert_eq!(world.get::<Transform>(entity).unwrap().translation.x, 1.0);
+
+        history.set_depth(0);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn despawn_and_component_edits_round_trip() {
+        let mut registry = SceneRegistry::new();
+        registry.register::<Transform>();
+        registry.register_with_default(Health(10));
+        let mut world = World::new();
+        world.insert_resource(registry);
+
+        let mut history = UndoStack::default();
+        let parent = world.spawn((Transform::default(),));
+        let entity = history.spawn(&mut world);
+        world.name_entity(entity, "hero");
+        world.insert(entity, Parent(parent));
+        world.insert(parent, Children(vec![entity]));
+        world.spawn_child(entity, (at(4.0),));
+
+        history.add_component(&mut world, entity, "Health");
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        history.push(Edit::Transform { entity, before: at(0.0), after: at(7.0) });
+        *world.get_mut::<Transform>(entity).unwrap() = at(7.0);
+
+        history.despawn(&mut world, entity);
+        assert!(!world.is_alive(entity));
+
+        // Undoing the delete brings back the entity, its child, its name and
+        // its place under the parent, under a new id.
+        let restored = history.undo(&mut world).unwrap();
+        assert_eq!(world.try_named("hero"), Some(restored));
+        assert_eq!(world.get::<Health>(restored), Some(&Health(10)));
+        assert_eq!(world.get::<Parent>(restored).map(|p| p.0), Some(parent));
+        assert_eq!(world.get::<Children>(parent).unwrap().0, [restored]);
+        assert_eq!(world.get::<Children>(restored).unwrap().0.len(), 1);
+
+        // Older edits follow the new id.
+        history.undo(&mut world);
+        assert_eq!(world.get::<Transform>(restored).unwrap().translation.x, 0.0);
+        history.undo(&mut world);
+        assert_eq!(world.get::<Health>(restored), None);
+        history.redo(&mut world);
+        assert_eq!(world.get::<Health>(restored), Some(&Health(10)));
+
+        history.remove_component(&mut world, restored, "Health");
+        assert_eq!(world.get::<Health>(restored), None);
+        history.undo(&mut world);
+        assert_eq!(world.get::<Health>(restored), Some(&Health(10)));
+    }
+}
@@ -0,0 +1,298 @@
+//! # Entity References — Pointing at Entities Across Scene Loads
+//!
+//! A component that stores a raw [`Entity`] — a door's target spawn point,
+//! a switch's gate — breaks as soon as it is saved: the id means nothing
+//! once the scene is loaded again. An [`EntityRef`] is saved as the
+//! target's [`Uid`] and turned back into an `Entity` after loading, even
+//! when the target lives in a scene that loads later:
+//!
+//! ```text
+//!   load "castle"  ──► Door { target: EntityRef(6f1c…) }   uid not in world
+//!                          │                               └► PendingRefs
+//!   load "dungeon" ──► entity with Uid(6f1c…) spawns
+//!                          │
+//!                   resolve pending ──► door.target.get(world) = Some(Entity(57v3))
+//!   unload "dungeon" ─► door.target.get(world) = None  (looked up again later)
+//! ```
+//!
+//! Every scene load resolves the references it brought in, plus any still
+//! waiting from earlier loads. References whose target never appears stay
+//! in the [`PendingRefs`] resource, listed by owning entity and component —
+//! call [`PendingRefs::warn_unresolved`] once streaming has settled, or
+//! show them in a debug overlay.
+//!
+//! ```ignore
+//! #[derive(Clone, Serialize, Deserialize)]
+//! struct Door { target: EntityRef }
+//!
+//! // when building the level
+//! let door = Door { target: EntityRef::to(&mut ctx.world, spawn_point) };
+//!
+//! // in a system, after any number of saves and loads
+//! if let Some(target) = door.target.get(&ctx.world) { teleport(ctx, player, target); }
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: References between scenes aren't serialized at all; the
+//!   usual workaround is a GUID component and a lookup at runtime.
+//! - **Bevy**: `MapEntities` remaps `Entity` fields when a scene is
+//!   spawned, but only within that scene.
+//! - **Godot**: `NodePath`s, resolved relative to the node at runtime.
+//! - **Our approach**: A GUID reference (Unity's workaround, built in) that
+//!   resolves itself once its target loads.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::{Entity, World};
+use crate::scene::Uid;
+
+// ── EntityRef ────────────────────────────────────────────────────────────
+
+/// A reference to an entity by its [`Uid`], for component fields that must
+/// survive saving and loading. See the [module docs](self).
+///
+/// Clones share the resolved entity.
+#[derive(Clone)]
+pub struct EntityRef {
+    uid: Uid,
+    /// The entity last resolved to. Shared with [`PendingRefs`] so loading
+    /// the target can fill it in.
+    resolved: Arc<Mutex<Option<Entity>>>,
+}
+
+impl EntityRef {
+    /// A reference to whichever entity has `uid`, resolved on first use.
+    pub fn new(uid: Uid) -> Self {
+        Self::with_entity(uid, None)
+    }
+
+    /// A reference to `entity`, giving it a [`Uid`] if it has none (named
+    /// entities get the one their name would be saved with).
+    pub fn to(world: &mut World, entity: Entity) -> Self {
+        let uid = match world.get::<Uid>(entity) {
+            Some(&uid) => uid,
+            None => {
+                let uid = Uid::from_names(world, entity).unwrap_or_default();
+                world.insert(entity, uid);
+                uid
+            }
+        };
+        Self::with_entity(uid, Some(entity))
+    }
+
+    fn with_entity(uid: Uid, entity: Option<Entity>) -> Self {
+        Self {
+            uid,
+            resolved: Arc::new(Mutex::new(entity)),
+        }
+    }
+
+    /// The target's uid.
+    pub fn uid(&self) -> Uid {
+        self.uid
+    }
+
+    /// The target entity, if it is loaded. Uses the last resolved entity
+    /// while it is still alive and still has the uid, otherwise looks it
+    /// up again.
+    pub fn get(&self, world: &World) -> Option<Entity> {
+        let mut resolved = self.resolved.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(entity) = *resolved
+            && world.get::<Uid>(entity) == Some(&self.uid)
+        {
+            return Some(entity);
+        }
+        *resolved = world.by_uid(self.uid);
+        *resolved
+    }
+
+    fn set(&self, entity: Entity) {
+        *self.resolved.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(entity);
+    }
+
+    /// Whether anything but the pending queue still holds this reference.
+    fn in_use(&self) -> bool {
+        Arc::strong_count(&self.resolved) > 1
+    }
+}
+
+impl PartialEq for EntityRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.uid == other.uid
+    }
+}
+
+impl Eq for EntityRef {}
+
+impl fmt::Debug for EntityRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EntityRef({})", self.uid)
+    }
+}
+
+impl Serialize for EntityRef {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.uid.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityRef {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let entity_ref = EntityRef::new(Uid::deserialize(deserializer)?);
+        COLLECTED.with(|collected| {
+            if let Some(collected) = collected.borrow_mut().as_mut() {
+                collected.push(entity_ref.clone());
+            }
+        });
+        Ok(entity_ref)
+    }
+}
+
+thread_local! {
+    /// References deserialized inside [`collect_refs`].
+    static COLLECTED: RefCell<Option<Vec<EntityRef>>> = const { RefCell::new(None) };
+}
+
+/// Run `f` (a component deserialization) and return the [`EntityRef`]s it
+/// created.
+pub(crate) fn collect_refs<R>(f: impl FnOnce() -> R) -> (R, Vec<EntityRef>) {
+    let outer = COLLECTED.with(|collected| collected.borrow_mut().replace(Vec::new()));
+    let result = f();
+    let refs = COLLECTED.with(|collected| std::mem::replace(&mut *collected.borrow_mut(), outer));
+    (result, refs.unwrap_or_default())
+}
+
+// ── Pending references ───────────────────────────────────────────────────
+
+/// A loaded [`EntityRef`] whose target isn't in the world yet.
+#[derive(Debug, Clone)]
+pub struct UnresolvedRef {
+    /// The entity whose component holds the reference.
+    pub entity: Entity,
+    /// The component's scene name.
+    pub component: String,
+    pub uid: Uid,
+    entity_ref: EntityRef,
+}
+
+/// Resource: [`EntityRef`]s loaded from scenes that are still waiting for
+/// their target to load. Inserted by the first scene load that needs it.
+#[derive(Debug, Default)]
+pub struct PendingRefs {
+    pending: Vec<UnresolvedRef>,
+}
+
+impl PendingRefs {
+    /// References still waiting for their target.
+    pub fn unresolved(&self) -> &[UnresolvedRef] {
+        &self.pending
+    }
+
+    /// Whether every loaded reference found its target.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Log a warning for each reference still waiting, e.g. once all the
+    /// scenes that should be loaded are.
+    pub fn warn_unresolved(&self) {
+        for pending in &self.pending {
+            log::warn!(
+                "Entity {:?}: {} references uid {}, which no loaded entity has",
+                pending.entity,
+                pending.component,
+                pending.uid
+            );
+        }
+    }
+}
+
+/// Queue the references a scene load brought in, then resolve everything
+/// queued against the entities now in the world.
+pub(crate) fn resolve_refs(world: &mut World, loaded: Vec<(Entity, String, EntityRef)>) {
+    if loaded.is_empty() && !world.has_resource::<PendingRefs>() {
+        return;
+    }
+    let mut refs = world.resource_remove::<PendingRefs>().unwrap_or_default();
+    refs.pending.extend(loaded.into_iter().map(|(entity, component, entity_ref)| UnresolvedRef {
+        entity,
+        component,
+        uid: entity_ref.uid(),
+        entity_ref,
+    }));
+
+    let mut uids = std::collections::HashMap::new();
+    world.query::<(&Uid,)>(|entity, (uid,)| {
+        uids.insert(*uid, entity);
+    });
+    // Dropped references (their component or entity is gone) need nothing.
+    refs.pending.retain(|pending| {
+        if !pending.entity_ref.in_use() {
+            return false;
+        }
+        match uids.get(&pending.uid) {
+            Some(&target) => {
+                pending.entity_ref.set(target);
+                false
+            }
+            None => true,
+        }
+    });
+    if !refs.pending.is_empty() {
+        log::debug!("{} entity references are waiting for their target to load", refs.pending.len());
+    }
+    world.insert_resource(refs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scene::{SceneRegistry, load_scene_tagged, save_scene, unload_scene};
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct Door {
+        target: EntityRef,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct SpawnPoint;
+
+    #[test]
+    fn references_resolve_when_their_scene_loads() {
+        let mut registry = SceneRegistry::new();
+        registry.register::<Door>();
+        registry.register::<SpawnPoint>();
+
+        let mut authoring = World::new();
+        let spawn = authoring.spawn((SpawnPoint,));
+        let door = Door { target: EntityRef::to(&mut authoring, spawn) };
+        assert_eq!(door.target.get(&authoring), Some(spawn));
+        let dungeon = save_scene(&authoring, &registry);
+        authoring.despawn(spawn);
+        authoring.spawn((door,));
+        let castle = save_scene(&authoring, &registry);
+
+        // The castle loads first: its door waits for the dungeon.
+        let mut world = World::new();
+        let loaded = load_scene_tagged(&mut world, &registry, &castle, "castle");
+        let door = world.get::<Door>(loaded[0]).unwrap().target.clone();
+        assert_eq!(door.get(&world), None);
+        assert_eq!(world.resource::<PendingRefs>().unresolved().len(), 1);
+
+        load_scene_tagged(&mut world, &registry, &dungeon, "dungeon");
+        assert!(world.resource::<PendingRefs>().is_empty());
+        let target = world.get::<Door>(loaded[0]).unwrap().target.get(&world);
+        assert!(target.is_some_and(|t| world.get::<SpawnPoint>(t).is_some()));
+
+        // Reloading the dungeon gives the spawn point a new entity.
+        unload_scene(&mut world, "dungeon");
+        assert_eq!(door.get(&world), None);
+        load_scene_tagged(&mut world, &registry, &dungeon, "dungeon");
+        assert!(door.get(&world).is_some_and(|t| Some(t) != target));
+    }
+}
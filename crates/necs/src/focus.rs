@@ -0,0 +1,598 @@
+//! # Focus — Keyboard and D-Pad Navigation Between Widgets
+//!
+//! Menus must be playable without a mouse. Mark interactive entities with
+//! [`Focusable`], add the [`FocusNavigation`] plugin, and one of them holds
+//! *focus* at a time:
+//!
+//! | Keyboard | Gamepad | Effect |
+//! |----------|---------|--------|
+//! | Tab / Shift+Tab | | next / previous in tab order |
+//! | Arrow keys | D-pad | nearest focusable in that direction |
+//! | Enter / Space | South (A / ✕) | activate the focused entity |
+//! | Escape | East (B / ○) | clear focus |
+//!
+//! Gamepad buttons are read from every pad in the
+//! [`Gamepads`](crate::gamepad::Gamepads) resource, when there is one.
+//! Activating a [`Button`](crate::ui::Button) clicks it: its
+//! [`clicked`](crate::ui::Button::clicked) is true for that frame, for
+//! systems that run after [`focus_system`].
+//!
+//! ## Spatial Navigation
+//!
+//! Arrow navigation looks at every other focusable whose position lies in the
+//! pressed direction and picks the one with the lowest score, where sideways
+//! offset counts double — so "down" prefers the button straight below over
+//! a closer one off to the side:
+//!
+//! ```text
+//!            [Play]  ◄── focused
+//!              │
+//!   [Quit]     ▼         score = along + 2 × across
+//!           [Options]    Options: 1 + 0   ✓
+//!                        Quit:    1 + 2×2
+//! ```
+//!
+//! Tab order is [`Focusable::order`] first, then top-to-bottom, left-to-right.
+//!
+//! A focusable UI node is placed at the center of its laid-out
+//! [`ComputedNode`](crate::ui::ComputedNode) rectangle (screen space, flipped
+//! so "up" is up the screen), and skipped while it has no layout, e.g. while
+//! hidden. Anything else is placed at its `GlobalTransform`. Keep one menu to
+//! one kind: screen pixels and world units don't compare.
+//!
+//! ## Other Input Sources
+//!
+//! Navigation is input-agnostic: [`FocusState::navigate`] and
+//! [`FocusState::activate`] queue the same actions the keyboard and d-pad
+//! do, so a custom binding can drive menus too.
+//!
+//! ## Comparison
+//!
+//! - **Unity UI**: `Selectable` with automatic navigation and an
+//!   `EventSystem` — same direction-scoring idea.
+//! - **Godot**: `Control.focus_neighbor_*` with automatic fallback to the
+//!   closest control.
+//! - **Our approach**: Positions come from the UI layout for widgets and
+//!   from `GlobalTransform` for everything else, so buttons, sprites and
+//!   shapes can all be focusable.
+
+use crate::context::Context;
+use crate::ecs::{Entity, GlobalTransform, World};
+use crate::gamepad::Gamepads;
+use crate::input::{GamepadButton, KeyCode};
+use crate::math::Vec2;
+
+/// A direction or step to move focus in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NavDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+    /// Next in tab order (wraps around).
+    Next,
+    /// Previous in tab order (wraps around).
+    Previous,
+}
+
+/// Component: this entity can receive focus. Pair with a
+/// [`UiNode`](crate::ui::UiNode) or a [`Transform`](crate::math::Transform).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Focusable {
+    /// Tab order group; lower comes first. Ties go top-to-bottom,
+    /// left-to-right.
+    pub order: i32,
+    /// Disabled entities are skipped by navigation and lose focus.
+    pub disabled: bool,
+}
+
+impl Focusable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tab order (builder pattern).
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+/// Something that happened to focus this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusEvent {
+    Focused(Entity),
+    Unfocused(Entity),
+    /// The focused entity was activated (Enter/Space, South on a gamepad, or
+    /// [`FocusState::activate`]).
+    Activated(Entity),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FocusRequest {
+    Set(Entity),
+    Clear,
+    Navigate(NavDirection),
+    Activate,
+}
+
+/// Resource: which entity has focus, plus this frame's [`FocusEvent`]s.
+///
+/// Requests (`focus`, `navigate`, `activate`, ...) are applied by the focus
+/// system on its next run, after keyboard input.
+#[derive(Debug, Default)]
+pub struct FocusState {
+    focused: Option<Entity>,
+    events: Vec<FocusEvent>,
+    requests: Vec<FocusRequest>,
+}
+
+impl FocusState {
+    /// The entity that currently has focus.
+    pub fn focused(&self) -> Option<Entity> {
+        self.focused
+    }
+
+    /// Returns `true` if `entity` has focus.
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.focused == Some(entity)
+    }
+
+    /// Focus events from this frame, oldest first.
+    pub fn events(&self) -> &[FocusEvent] {
+        &self.events
+    }
+
+    /// Returns `true` if `entity` was activated this frame.
+    pub fn just_activated(&self, entity: Entity) -> bool {
+        self.events.contains(&FocusEvent::Activated(entity))
+    }
+
+    /// Move focus to `entity`.
+    pub fn focus(&mut self, entity: Entity) {
+        self.requests.push(FocusRequest::Set(entity));
+    }
+
+    /// Remove focus from whatever has it.
+    pub fn clear(&mut self) {
+        self.requests.push(FocusRequest::Clear);
+    }
+
+    /// Move focus in `direction`, as if the matching key were pressed.
+    pub fn navigate(&mut self, direction: NavDirection) {
+        self.requests.push(FocusRequest::Navigate(direction));
+    }
+
+    /// Activate the focused entity, as if Enter were pressed.
+    pub fn activate(&mut self) {
+        self.requests.push(FocusRequest::Activate);
+    }
+
+    fn set_focus(&mut self, entity: Option<Entity>) {
+        if entity == self.focused {
+            return;
+        }
+        if let Some(old) = self.focused {
+            self.events.push(FocusEvent::Unfocused(old));
+        }
+        if let Some(new) = entity {
+            self.events.push(FocusEvent::Focused(new));
+        }
+        self.focused = entity;
+    }
+}
+
+/// Plugin that inserts [`FocusState`] and runs keyboard focus navigation.
+///
+/// ```ignore
+/// Game::new("Menu")
+///     .plugin(FocusNavigation)
+///     .setup(setup)
+///     .run();
+/// ```
+pub struct FocusNavigation;
+
+impl crate::game::Plugin for FocusNavigation {
+    fn build(&self, game: &mut crate::game::Game) {
+        game.insert_resource(FocusState::default());
+        game.add_update_system(focus_system);
+    }
+}
+
+/// Gamepad buttons that drive focus, and what they do.
+const PAD_REQUESTS: [(GamepadButton, FocusRequest); 6] = [
+    (GamepadButton::DPadUp, FocusRequest::Navigate(NavDirection::Up)),
+    (GamepadButton::DPadDown, FocusRequest::Navigate(NavDirection::Down)),
+    (GamepadButton::DPadLeft, FocusRequest::Navigate(NavDirection::Left)),
+    (GamepadButton::DPadRight, FocusRequest::Navigate(NavDirection::Right)),
+    (GamepadButton::South, FocusRequest::Activate),
+    (GamepadButton::East, FocusRequest::Clear),
+];
+
+/// Read keyboard and gamepad input and queued requests, then move focus and
+/// emit events.
+pub fn focus_system(ctx: &mut Context) {
+    let input = &ctx.input;
+    // Arrow keys and Enter belong to a text field while one is in use.
+    #[cfg(feature = "render2d")]
+    let typing = ctx
+        .world
+        .get_resource::<crate::ui::TextFocus>()
+        .is_some_and(|focus| focus.is_typing());
+    #[cfg(not(feature = "render2d"))]
+    let typing = false;
+    let shift = input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight);
+    let key_requests: Vec<FocusRequest> = [
+        (
+            KeyCode::Tab,
+            if shift {
+                NavDirection::Previous
+            } else {
+                NavDirection::Next
+            },
+        ),
+        (KeyCode::ArrowUp, NavDirection::Up),
+        (KeyCode::ArrowDown, NavDirection::Down),
+        (KeyCode::ArrowLeft, NavDirection::Left),
+        (KeyCode::ArrowRight, NavDirection::Right),
+    ]
+    .into_iter()
+    .filter(|(key, _)| !typing && input.just_pressed(*key))
+    .map(|(_, dir)| FocusRequest::Navigate(dir))
+    .chain(
+        (!typing && (input.just_pressed(KeyCode::Enter) || input.just_pressed(KeyCode::Space)))
+            .then_some(FocusRequest::Activate),
+    )
+    .chain(
+        (!typing && input.just_pressed(KeyCode::Escape)).then_some(FocusRequest::Clear),
+    )
+    .chain(if typing { Vec::new() } else { pad_requests(&ctx.world) })
+    .collect();
+
+    update_focus(&mut ctx.world, key_requests);
+}
+
+/// Requests from buttons any connected pad pressed this frame.
+fn pad_requests(world: &World) -> Vec<FocusRequest> {
+    let Some(pads) = world.get_resource::<Gamepads>() else {
+        return Vec::new();
+    };
+    let mut requests = Vec::new();
+    for id in pads.ids() {
+        for (button, request) in PAD_REQUESTS {
+            if pads.just_pressed(id, button) {
+                requests.push(request);
+            }
+        }
+    }
+    requests
+}
+
+/// Apply `input_requests`, then the queued ones, and emit events.
+fn update_focus(world: &mut World, input_requests: Vec<FocusRequest>) {
+    let Some(mut state) = world.resource_remove::<FocusState>() else {
+        return;
+    };
+    state.events.clear();
+
+    let candidates = collect_focusables(world);
+
+    // Lose focus if the entity was despawned, disabled, or made unfocusable.
+    if let Some(current) = state.focused
+        && !candidates.iter().any(|c| c.entity == current)
+    {
+        state.set_focus(None);
+    }
+
+    let requests: Vec<FocusRequest> = std::mem::take(&mut state.requests);
+    for request in input_requests.into_iter().chain(requests) {
+        match request {
+            FocusRequest::Set(entity) => {
+                if candidates.iter().any(|c| c.entity == entity) {
+                    state.set_focus(Some(entity));
+                }
+            }
+            FocusRequest::Clear => state.set_focus(None),
+            FocusRequest::Navigate(direction) => {
+                if let Some(target) = pick_target(&candidates, state.focused, direction) {
+                    state.set_focus(Some(target));
+                }
+            }
+            FocusRequest::Activate => {
+                if let Some(entity) = state.focused {
+                    state.events.push(FocusEvent::Activated(entity));
+                    #[cfg(feature = "render2d")]
+                    if let Some(button) = world.get_mut::<crate::ui::Button>(entity) {
+                        button.activate();
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "render2d")]
+    apply_focus_tints(world, state.focused);
+
+    world.insert_resource(state);
+}
+
+// ── Navigation ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    entity: Entity,
+    position: Vec2,
+    order: i32,
+}
+
+fn collect_focusables(world: &mut World) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    world.query::<(&Focusable,)>(|entity, (focusable,)| {
+        if !focusable.disabled {
+            candidates.push(Candidate {
+                entity,
+                position: Vec2::ZERO,
+                order: focusable.order,
+            });
+        }
+    });
+    candidates.retain_mut(|candidate| match focus_position(world, candidate.entity) {
+        Some(position) => {
+            candidate.position = position;
+            true
+        }
+        None => false,
+    });
+    candidates
+}
+
+/// Where navigation sees `entity`, Y up: the center of a UI node's layout
+/// rectangle, flipped from screen space, or else its `GlobalTransform`.
+/// `None` for a UI node without layout and for entities with neither.
+fn focus_position(world: &World, entity: Entity) -> Option<Vec2> {
+    #[cfg(feature = "render2d")]
+    if world.get::<crate::ui::UiNode>(entity).is_some() {
+        let rect = world.get::<crate::ui::ComputedNode>(entity)?.rect;
+        let center = (rect.min + rect.max) * 0.5;
+        return Some(Vec2::new(center.x, -center.y));
+    }
+    world
+        .get::<GlobalTransform>(entity)
+        .map(|gt| gt.matrix.col(3).truncate().truncate())
+}
+
+/// Choose the entity focus should move to, or `None` to stay put. With
+/// nothing focused, `Previous` picks the last entity in tab order and any
+/// other direction the first.
+fn pick_target(
+    candidates: &[Candidate],
+    focused: Option<Entity>,
+    direction: NavDirection,
+) -> Option<Entity> {
+    let mut tab_order: Vec<&Candidate> = candidates.iter().collect();
+    // Y-up: higher Y comes first (top-to-bottom), then left-to-right.
+    tab_order.sort_by(|a, b| {
+        a.order
+            .cmp(&b.order)
+            .then(b.position.y.total_cmp(&a.position.y))
+            .then(a.position.x.total_cmp(&b.position.x))
+    });
+
+    let Some(index) = focused.and_then(|f| tab_order.iter().position(|c| c.entity == f)) else {
+        let end = match direction {
+            NavDirection::Previous => tab_order.last(),
+            _ => tab_order.first(),
+        };
+        return end.map(|c| c.entity);
+    };
+    let len = tab_order.len();
+
+    let axis = match direction {
+        NavDirection::Next => return Some(tab_order[(index + 1) % len].entity),
+        NavDirection::Previous => return Some(tab_order[(index + len - 1) % len].entity),
+        NavDirection::Up => Vec2::Y,
+        NavDirection::Down => Vec2::NEG_Y,
+        NavDirection::Left => Vec2::NEG_X,
+        NavDirection::Right => Vec2::X,
+    };
+
+    let from = tab_order[index].position;
+    candidates
+        .iter()
+        .filter(|c| c.entity != tab_order[index].entity)
+        .filter_map(|c| {
+            let offset = c.position - from;
+            let along = offset.dot(axis);
+            if along <= f32::EPSILON {
+                return None;
+            }
+            let across = (offset - axis * along).length();
+            Some((c.entity, along + 2.0 * across))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+// ── Focus visuals ───────────────────────────────────────────────────────
+
+/// Component: tint the entity's [`Sprite`](crate::render2d::Sprite) or
+/// [`UiImage`](crate::ui::UiImage) while it has focus. The color it had when
+/// it gained focus is restored when focus moves away. The tint wins over a
+/// [`Button`](crate::ui::Button)'s hover colors while focused.
+#[cfg(feature = "render2d")]
+#[derive(Debug, Clone, Copy)]
+pub struct FocusTint {
+    pub color: crate::render2d::Color,
+    saved: Option<crate::render2d::Color>,
+}
+
+#[cfg(feature = "render2d")]
+impl FocusTint {
+    pub fn new(color: crate::render2d::Color) -> Self {
+        Self { color, saved: None }
+    }
+}
+
+#[cfg(feature = "render2d")]
+impl FocusTint {
+    /// Tint `color` while focused, restore it once focus leaves. Reapplied
+    /// every frame, since buttons recolor their image before this runs.
+    fn apply(&mut self, has_focus: bool, color: &mut crate::render2d::Color) {
+        match (has_focus, self.saved) {
+            (true, saved) => {
+                if saved.is_none() {
+                    self.saved = Some(*color);
+                }
+                *color = self.color;
+            }
+            (false, Some(original)) => {
+                *color = original;
+                self.saved = None;
+            }
+            (false, None) => {}
+        }
+    }
+}
+
+#[cfg(feature = "render2d")]
+fn apply_focus_tints(world: &mut World, focused: Option<Entity>) {
+    use crate::render2d::Sprite;
+    use crate::ui::UiImage;
+
+    world.query::<(&mut FocusTint, &mut Sprite)>(|entity, (tint, sprite)| {
+        tint.apply(focused == Some(entity), &mut sprite.color);
+    });
+    world.query::<(&mut FocusTint, &mut UiImage)>(|entity, (tint, image)| {
+        tint.apply(focused == Some(entity), &mut image.color);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A vertical menu (Play, Options below it) plus Quit off to the lower left.
+    fn menu() -> (World, Vec<Candidate>) {
+        let mut world = World::new();
+        let spots = [(0.0, 1.0, 0), (0.0, 0.0, 0), (-2.0, 0.0, 0)];
+        let candidates = spots
+            .iter()
+            .map(|&(x, y, order)| Candidate {
+                entity: world.spawn_empty(),
+                position: Vec2::new(x, y),
+                order,
+            })
+            .collect();
+        (world, candidates)
+    }
+
+    #[test]
+    fn first_navigation_focuses_first_in_tab_order() {
+        let (_world, c) = menu();
+        assert_eq!(pick_target(&c, None, NavDirection::Down), Some(c[0].entity));
+        // Shift+Tab from nothing wraps around to the end.
+        assert_eq!(pick_target(&c, None, NavDirection::Previous), Some(c[1].entity));
+        assert_eq!(pick_target(&[], None, NavDirection::Previous), None);
+    }
+
+    #[test]
+    fn tab_order_is_top_to_bottom_left_to_right_and_wraps() {
+        let (_world, c) = menu();
+        let play = Some(c[0].entity);
+        assert_eq!(pick_target(&c, play, NavDirection::Next), Some(c[2].entity));
+        assert_eq!(pick_target(&c, Some(c[1].entity), NavDirection::Next), play);
+        assert_eq!(
+            pick_target(&c, play, NavDirection::Previous),
+            Some(c[1].entity)
+        );
+    }
+
+    #[test]
+    fn arrows_prefer_aligned_targets() {
+        let (_world, c) = menu();
+        let play = Some(c[0].entity);
+        assert_eq!(pick_target(&c, play, NavDirection::Down), Some(c[1].entity));
+        assert_eq!(
+            pick_target(&c, Some(c[1].entity), NavDirection::Left),
+            Some(c[2].entity)
+        );
+        assert_eq!(pick_target(&c, play, NavDirection::Up), None);
+    }
+
+    #[cfg(feature = "render2d")]
+    #[test]
+    fn navigates_between_laid_out_buttons() {
+        use crate::input::GamepadStyle;
+        use crate::render2d::Color;
+        use crate::ui::{Button, ComputedNode, UiImage, UiNode};
+
+        // A centered column: Play above Quit, laid out in screen space.
+        let mut world = World::new();
+        let menu = world.spawn((UiNode::new().anchor(crate::ui::UiAnchor::Center).gap(10.0),));
+        let button = |world: &mut World| {
+            world.spawn_child(
+                menu,
+                (
+                    UiNode::new().size(120.0, 30.0),
+                    Button::new(),
+                    Focusable::new(),
+                    UiImage::solid(Color::BLACK),
+                    FocusTint::new(Color::WHITE),
+                ),
+            )
+        };
+        let play = button(&mut world);
+        let quit = button(&mut world);
+        crate::ui::layout::layout_nodes(&mut world, Vec2::new(800.0, 600.0));
+        let top = |e| world.get::<ComputedNode>(e).unwrap().rect.min.y;
+        assert!(top(play) < top(quit));
+        world.insert_resource(FocusState::default());
+
+        // Down from nothing starts at the top; then Quit, and no further.
+        let down = || vec![FocusRequest::Navigate(NavDirection::Down)];
+        update_focus(&mut world, down());
+        assert_eq!(world.resource::<FocusState>().focused(), Some(play));
+        update_focus(&mut world, down());
+        update_focus(&mut world, down());
+        assert_eq!(world.resource::<FocusState>().focused(), Some(quit));
+        assert_eq!(world.get::<UiImage>(quit).unwrap().color.to_array(), [1.0; 4]);
+        assert_eq!(world.get::<UiImage>(play).unwrap().color.to_array(), [0.0, 0.0, 0.0, 1.0]);
+
+        // The d-pad goes back up and South clicks the button.
+        let mut pads = Gamepads::new();
+        let mut input = crate::context::InputState::new();
+        pads.connect(crate::gamepad::GamepadInfo {
+            id: crate::gamepad::GamepadId(0),
+            name: "pad".into(),
+            style: GamepadStyle::Xbox,
+            rumble: false,
+        });
+        let pad = crate::gamepad::GamepadId(0);
+        pads.set_button(pad, GamepadButton::DPadUp, true, &mut input);
+        pads.set_button(pad, GamepadButton::South, true, &mut input);
+        world.insert_resource(pads);
+        let requests = pad_requests(&world);
+        update_focus(&mut world, requests);
+        assert_eq!(world.resource::<FocusState>().focused(), Some(play));
+        assert!(world.resource::<FocusState>().just_activated(play));
+        assert!(world.get::<Button>(play).unwrap().clicked());
+        assert!(!world.get::<Button>(quit).unwrap().clicked());
+    }
+
+    #[test]
+    fn set_focus_emits_unfocus_then_focus() {
+        let (_world, c) = menu();
+        let mut state = FocusState::default();
+        state.set_focus(Some(c[0].entity));
+        state.set_focus(Some(c[1].entity));
+        state.set_focus(Some(c[1].entity));
+        assert_eq!(
+            state.events(),
+            &[
+                FocusEvent::Focused(c[0].entity),
+                FocusEvent::Unfocused(c[0].entity),
+                FocusEvent::Focused(c[1].entity),
+            ]
+        );
+    }
+}
@@ -27,7 +27,13 @@
 //! }
 //! ```
 
+use crate::asset::handle::update_assets;
+use crate::asset::{AssetEvent, AssetLoader, AssetServer, Assets};
 use crate::context::Context;
+use crate::ecs::Events;
+use crate::ecs::system::{Schedule, SystemConfig, short_system_name};
+use crate::hooks::{Hook, Hooks};
+use crate::launch::LaunchOptions;
 
 /// A plugin that can extend a [`Game`] with additional systems and resources.
 ///
@@ -57,18 +63,36 @@ pub struct Game {
     title: String,
     ctx: Context,
     startup_systems: Vec<Box<dyn FnMut(&mut Context)>>,
-    update_systems: Vec<Box<dyn FnMut(&mut Context)>>,
+    update_systems: Schedule<Context>,
+    fixed_systems: Schedule<Context>,
+    shutdown_systems: Schedule<Context>,
+    hooks: Hooks,
+    catch_panics: bool,
 }
 
 impl Game {
-    /// Create a new game with the given window title.
+    /// Create a new game with the given window title. Engine flags on the
+    /// command line are parsed into the [`LaunchOptions`] resource.
     pub fn new(title: &str) -> Self {
-        Self {
+        let mut ctx = Context::new();
+        ctx.world.insert_resource(LaunchOptions::from_env());
+        ctx.world.insert_resource(crate::subsystems::Subsystems::all());
+        #[allow(unused_mut)]
+        let mut game = Self {
             title: title.to_string(),
-            ctx: Context::new(),
+            ctx,
             startup_systems: Vec::new(),
-            update_systems: Vec::new(),
-        }
+            update_systems: Schedule::default(),
+            fixed_systems: Schedule::default(),
+            // A panicking save must not skip teardown — always catch here.
+            shutdown_systems: Schedule::default().catch_panics(true),
+            hooks: Hooks::default(),
+            catch_panics: false,
+        };
+        game.add_event::<crate::render::ReadbackComplete>();
+        #[cfg(feature = "render2d")]
+        game.add_event::<crate::ui::TextSubmitted>();
+        game
     }
 
     /// Insert a resource into the world (builder pattern).
@@ -84,8 +108,39 @@ impl Game {
     }
 
     /// Register an update system that runs every frame.
-    pub fn update(mut self, system: fn(&mut Context)) -> Self {
-        self.update_systems.push(Box::new(system));
+    pub fn update<F: FnMut(&mut Context) + 'static>(mut self, system: F) -> Self {
+        self.update_systems.add_system(system);
+        self
+    }
+
+    /// Register a system that runs on the fixed tick (60 Hz by default),
+    /// before the frame's update systems. It may run zero, one or several
+    /// times per frame; use [`Time::fixed_delta_secs`](crate::time::Time::fixed_delta_secs)
+    /// as its timestep. See [`time`](crate::time#fixed-timestep).
+    pub fn fixed_update<F: FnMut(&mut Context) + 'static>(mut self, system: F) -> Self {
+        self.fixed_systems.add_system(system);
+        self
+    }
+
+    /// Set the fixed-update tick rate in ticks per second (builder pattern).
+    pub fn fixed_hz(mut self, hz: f64) -> Self {
+        self.ctx.time.set_fixed_hz(hz);
+        self
+    }
+
+    /// Limit how many fixed ticks may run in one frame to catch up after a
+    /// slow frame (builder pattern). Defaults to 5.
+    pub fn max_fixed_steps(mut self, steps: u32) -> Self {
+        self.ctx.time.set_max_fixed_steps(steps);
+        self
+    }
+
+    /// Register a shutdown system that runs once when the game exits, before
+    /// the world is torn down. Persist saves and settings here; the
+    /// [`ShutdownRequested`](crate::lifecycle::ShutdownRequested) resource
+    /// says why the game is exiting.
+    pub fn shutdown<F: FnMut(&mut Context) + 'static>(mut self, system: F) -> Self {
+        self.shutdown_systems.add_system(system);
         self
     }
 
@@ -93,10 +148,127 @@ impl Game {
     ///
     /// This wraps the system to work with the Context-based API. Prefer using
     /// plugins and Context-based systems for new code.
-    pub fn world_system(mut self, system: fn(&mut crate::ecs::World)) -> Self {
-        self.update_systems.push(Box::new(move |ctx: &mut Context| {
-            system(&mut ctx.world);
-        }));
+    pub fn world_system<F: FnMut(&mut crate::ecs::World) + 'static>(mut self, mut system: F) -> Self {
+        let name = short_system_name(std::any::type_name::<F>());
+        self.update_systems
+            .add_named_system(name, move |ctx: &mut Context| system(&mut ctx.world));
+        self
+    }
+
+    /// Replace the [`LaunchOptions`] parsed from the command line.
+    pub fn launch_options(mut self, options: LaunchOptions) -> Self {
+        self.ctx.world.insert_resource(options);
+        self
+    }
+
+    /// Catch panics in individual update systems instead of aborting.
+    ///
+    /// A system that panics is logged by name and quarantined (never run
+    /// again); the rest of the game keeps going. Useful for live demos and
+    /// playtests — the world may be left partially updated by the panicking
+    /// system, so treat the log entry as a bug report.
+    pub fn catch_system_panics(mut self, enabled: bool) -> Self {
+        self.catch_panics = enabled;
+        self
+    }
+
+    /// Record and present each frame on a render thread while the next
+    /// frame's update runs. Off by default. See
+    /// [pipelined rendering](crate::render::pipelined) for what moves to the
+    /// thread and when the two wait for each other.
+    pub fn pipelined_rendering(mut self, enabled: bool) -> Self {
+        if enabled {
+            self.ctx.world.insert_resource(crate::render::pipelined::RenderThread::new());
+        } else {
+            self.ctx.world.resource_remove::<crate::render::pipelined::RenderThread>();
+        }
+        self
+    }
+
+    /// Pause update systems and mute audio while the window is unfocused or
+    /// minimized. See [`WindowLifecycle`](crate::lifecycle::WindowLifecycle).
+    pub fn auto_pause(mut self, enabled: bool) -> Self {
+        self.ctx
+            .world
+            .resource_mut::<crate::lifecycle::WindowLifecycle>()
+            .set_auto_pause(enabled);
+        self
+    }
+
+    /// Run `f` at the start of every frame, after time is updated.
+    /// See [`hooks`](crate::hooks) for where each hook runs.
+    pub fn on_frame_start(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::FrameStart, f);
+        self
+    }
+
+    /// Run `f` at the end of every frame.
+    pub fn on_frame_end(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::FrameEnd, f);
+        self
+    }
+
+    /// Run `f` after update systems, just before the frame is rendered.
+    pub fn on_before_render(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::BeforeRender, f);
+        self
+    }
+
+    /// Run `f` after the frame is rendered and presented.
+    pub fn on_after_render(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::AfterRender, f);
+        self
+    }
+
+    /// Run `f` when the user tries to close the window. Return `false` to
+    /// veto the close; exit later with [`Context::exit`].
+    pub fn on_close_requested(mut self, f: impl FnMut(&mut Context) -> bool + 'static) -> Self {
+        self.hooks.add_close_requested(f);
+        self
+    }
+
+    /// Run `f` when a text field gains focus, to show a platform's
+    /// on-screen keyboard. See [on-screen keyboards](crate::hooks#on-screen-keyboards).
+    pub fn on_keyboard_requested(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::KeyboardRequested, f);
+        self
+    }
+
+    /// Run `f` when the focused text field loses focus, to hide the
+    /// on-screen keyboard.
+    pub fn on_keyboard_dismissed(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::KeyboardDismissed, f);
+        self
+    }
+
+    /// Run `f` once when the game exits.
+    pub fn on_shutdown(mut self, f: impl FnMut(&mut Context) + 'static) -> Self {
+        self.hooks.add(Hook::Shutdown, f);
+        self
+    }
+
+    /// Register an event type (builder pattern). Inserts an empty
+    /// [`Events<T>`](crate::ecs::Events) queue and advances it at the end of
+    /// every frame.
+    pub fn event<T: 'static + Send + Sync>(mut self) -> Self {
+        self.add_event::<T>();
+        self
+    }
+
+    /// Register a reference-counted asset type (builder pattern). Inserts an
+    /// empty [`Assets<T>`](crate::asset::Assets) store and its
+    /// [`AssetEvent<T>`](crate::asset::AssetEvent) queue; unused assets are
+    /// swept at the start of every frame.
+    pub fn asset<T: 'static + Send + Sync>(mut self) -> Self {
+        self.add_asset::<T>();
+        self
+    }
+
+    /// Register a loader for a game-defined file type (builder pattern).
+    /// Files it handles load with [`load_custom`](crate::asset::load_custom)
+    /// and hot-reload through it.
+    pub fn asset_loader(mut self, loader: impl AssetLoader) -> Self {
+        self.add_asset_loader(loader);
         self
     }
 
@@ -111,28 +283,141 @@ impl Game {
         self.ctx.world.insert_resource(value);
     }
 
+    /// Register an event type (non-consuming, for use by plugins). Calling
+    /// this more than once for the same type is harmless.
+    pub fn add_event<T: 'static + Send + Sync>(&mut self) {
+        if self.ctx.world.has_resource::<Events<T>>() {
+            return;
+        }
+        self.ctx.world.insert_resource(Events::<T>::default());
+        self.hooks.add(Hook::FrameEnd, |ctx| {
+            if let Some(events) = ctx.world.get_resource_mut::<Events<T>>() {
+                events.update();
+            }
+        });
+    }
+
+    /// Register a reference-counted asset type (non-consuming, for use by
+    /// plugins). Calling this more than once for the same type is harmless.
+    pub fn add_asset<T: 'static + Send + Sync>(&mut self) {
+        if self.ctx.world.has_resource::<Assets<T>>() {
+            return;
+        }
+        self.ctx.world.insert_resource(Assets::<T>::default());
+        self.add_event::<AssetEvent<T>>();
+        self.hooks
+            .add(Hook::FrameStart, |ctx| update_assets::<T>(&mut ctx.world));
+    }
+
+    /// Register an asset loader (non-consuming, for use by plugins).
+    pub fn add_asset_loader(&mut self, loader: impl AssetLoader) {
+        if let Some(server) = self.ctx.world.get_resource_mut::<AssetServer>() {
+            server.register_loader(loader);
+        }
+    }
+
     /// Register a startup system (non-consuming, for use by plugins).
     pub fn add_startup_system(&mut self, system: impl FnMut(&mut Context) + 'static) {
         self.startup_systems.push(Box::new(system));
     }
 
-    /// Register an update system (non-consuming, for use by plugins).
-    pub fn add_update_system(&mut self, system: impl FnMut(&mut Context) + 'static) {
-        self.update_systems.push(Box::new(system));
+    /// Register an update system (non-consuming, for use by plugins). The
+    /// returned [`SystemConfig`] orders it against other systems; see
+    /// [`Schedule`](crate::ecs::system#ordering).
+    pub fn add_update_system<F: FnMut(&mut Context) + 'static>(
+        &mut self,
+        system: F,
+    ) -> SystemConfig<'_, Context> {
+        self.update_systems.add_system(system)
+    }
+
+    /// Register a fixed-update system (non-consuming, for use by plugins).
+    pub fn add_fixed_update_system<F: FnMut(&mut Context) + 'static>(
+        &mut self,
+        system: F,
+    ) -> SystemConfig<'_, Context> {
+        self.fixed_systems.add_system(system)
+    }
+
+    /// Register a shutdown system (non-consuming, for use by plugins).
+    pub fn add_shutdown_system<F: FnMut(&mut Context) + 'static>(
+        &mut self,
+        system: F,
+    ) -> SystemConfig<'_, Context> {
+        self.shutdown_systems.add_system(system)
+    }
+
+    /// Register a hook (non-consuming, for use by plugins).
+    pub fn add_hook(&mut self, hook: Hook, f: impl FnMut(&mut Context) + 'static) {
+        self.hooks.add(hook, f);
+    }
+
+    /// Register a close-request hook (non-consuming, for use by plugins).
+    pub fn add_close_request_hook(&mut self, f: impl FnMut(&mut Context) -> bool + 'static) {
+        self.hooks.add_close_requested(f);
     }
 
     /// Start the event loop. This function does not return.
+    ///
+    /// With [`LaunchOptions::headless`] no event loop or window is created;
+    /// systems run on a plain loop until [`Context::exit`] is called.
+    ///
+    /// Logs the [build info](crate::version) first and installs its panic
+    /// hook, so crash output names the build.
     pub fn run(self) {
-        let event_loop = winit::event_loop::EventLoop::new()
-            .expect("Failed to create event loop");
+        crate::version::install_panic_hook();
+        log::info!("{}", crate::version::build_info());
+
+        let headless = self
+            .ctx
+            .world
+            .get_resource::<LaunchOptions>()
+            .is_some_and(|options| options.headless);
 
         let mut app = crate::window::WinitApp::new(
             self.ctx,
             self.startup_systems,
-            self.update_systems,
+            self.update_systems.catch_panics(self.catch_panics),
+            self.fixed_systems.catch_panics(self.catch_panics),
+            self.shutdown_systems,
+            self.hooks,
             self.title,
         );
 
+        if headless {
+            app.run_headless();
+            return;
+        }
+
+        let event_loop = winit::event_loop::EventLoop::new()
+            .expect("Failed to create event loop");
         event_loop.run_app(&mut app).expect("Event loop error");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn shutdown_systems_then_hooks_run_in_order() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let record = |tag: &'static str| {
+            let log = Rc::clone(&log);
+            move |_: &mut Context| log.borrow_mut().push(tag)
+        };
+
+        let mut game = Game::new("shutdown test")
+            .launch_options(LaunchOptions { headless: true, ..LaunchOptions::default() })
+            .update(|ctx| ctx.exit())
+            .shutdown(record("save"))
+            .shutdown(record("settings"));
+        game.add_shutdown_system(record("plugin"));
+        game.add_hook(Hook::Shutdown, record("hook"));
+        game.run();
+
+        assert_eq!(*log.borrow(), vec!["save", "settings", "plugin", "hook"]);
+    }
+}
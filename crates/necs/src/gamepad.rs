@@ -0,0 +1,707 @@
+//! # Gamepads — Controllers, Hot-Plugging and Rumble
+//!
+//! [`InputState`] has one merged gamepad: every connected controller drives
+//! the same buttons and sticks, which is what a single-player game and
+//! [action bindings](crate::action) want. The [`Gamepads`] resource keeps
+//! each controller apart as well, for local multiplayer and for showing who
+//! is connected:
+//!
+//! ```text
+//!   gilrs (GamepadInput plugin, FrameStart)
+//!     │  pad 0 South down, pad 1 left stick (0.04, -0.9), pad 2 plugged in
+//!     ▼
+//!   Gamepads ──────────────────────┬──► InputState (merged)
+//!     pad 0  South held, just      │      gamepad_pressed(South)
+//!     pad 1  stick (0, -0.89)      │      gamepad_axis(LeftStickY) = -0.89
+//!            └ deadzone applied    │
+//!     pad 2  "DualSense", rumble   └──► Events<GamepadEvent>
+//!                                         Connected(GamepadId(2))
+//! ```
+//!
+//! A button is held in `InputState` while any pad holds it; each merged
+//! axis is the pad pushing furthest. The controller that last moved decides
+//! the [`GamepadStyle`] of button prompts.
+//!
+//! ## Deadzones
+//!
+//! Worn sticks rest slightly off center. Stick positions within
+//! [`Gamepads::deadzone`] of the center read as exactly zero, and the rest
+//! of the range is stretched back to 0–1 so there's no jump at the edge.
+//! The deadzone is *radial* — it applies to the stick's distance from the
+//! center, not to X and Y separately, so diagonals don't snap to the axes:
+//!
+//! ```text
+//!   per axis (square)          radial (what we use)
+//!        ┌─┬─┐                       ╭─╮
+//!   ─────┤ ┼ ├─────              ────┤·├────   small diagonal drift is
+//!        └─┴─┘                       ╰─╯       cut, slow diagonal moves
+//!   a slow diagonal sticks                     stay diagonal
+//!   to an axis
+//! ```
+//!
+//! Triggers use the same value as a threshold. [Action values](crate::action#analog-values)
+//! add their own deadzone on top; lower one of them if sticks feel sluggish.
+//!
+//! ## Backend
+//!
+//! With the `gamepad` feature, the [`GamepadInput`] plugin polls
+//! [gilrs](https://docs.rs/gilrs) at the start of every frame:
+//!
+//! ```ignore
+//! Game::new("Co-op")
+//!     .plugin(GamepadInput)
+//!     .update(|ctx| {
+//!         let pads = ctx.world.resource_mut::<Gamepads>();
+//!         for pad in pads.ids() {
+//!             if pads.just_pressed(pad, GamepadButton::South) {
+//!                 pads.rumble(pad, 0.8, 0.3, Duration::from_millis(150));
+//!             }
+//!         }
+//!     })
+//!     .run();
+//! ```
+//!
+//! Without it, a game can feed [`Gamepads`] from any other source with
+//! [`connect`](Gamepads::connect), [`set_button`](Gamepads::set_button) and
+//! [`set_axis`](Gamepads::set_axis), and read rumble requests back with
+//! [`take_rumble`](Gamepads::take_rumble).
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The Input System's `Gamepad.all` with per-device controls,
+//!   stick deadzone processors, and `SetMotorSpeeds` for rumble.
+//! - **Bevy**: `bevy_gilrs` fills `Gamepad` components, one entity per
+//!   controller, with connection events and `GamepadRumbleRequest`.
+//! - **Godot**: `Input.get_joy_axis(device, axis)`, `joy_connection_changed`
+//!   signal and `start_joy_vibration`.
+//! - **Our approach**: gilrs like Bevy, but per-pad state lives in one
+//!   resource next to the merged pad the rest of the engine already reads.
+
+use std::time::Duration;
+
+use crate::context::InputState;
+use crate::ecs::World;
+use crate::input::{GamepadAxis, GamepadButton, GamepadStyle, Input};
+use crate::math::Vec2;
+
+/// Stick positions closer to the center than this read as zero.
+pub const DEFAULT_STICK_DEADZONE: f32 = 0.1;
+
+/// Identifies one connected controller. Stays the same if the controller is
+/// unplugged and plugged back in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GamepadId(pub usize);
+
+/// What the engine knows about a connected controller.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadInfo {
+    pub id: GamepadId,
+    /// Name reported by the driver, e.g. "Xbox Wireless Controller".
+    pub name: String,
+    /// Which button labels it has.
+    pub style: GamepadStyle,
+    /// Whether it can rumble.
+    pub rumble: bool,
+}
+
+/// Event: a controller was plugged in or unplugged. Sent by the
+/// [`GamepadInput`] plugin through [`Events`](crate::ecs::Events).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamepadEvent {
+    Connected(GamepadId),
+    Disconnected(GamepadId),
+}
+
+/// A request to rumble, read by the backend. Zero strengths stop rumbling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rumble {
+    /// The controller to rumble, or `None` for all of them.
+    pub gamepad: Option<GamepadId>,
+    /// Low-frequency (heavy) motor, 0 to 1.
+    pub strong: f32,
+    /// High-frequency (light) motor, 0 to 1.
+    pub weak: f32,
+    pub duration: Duration,
+}
+
+struct Pad {
+    info: GamepadInfo,
+    buttons: Input<GamepadButton>,
+    /// Raw positions by [`GamepadAxis`] index.
+    axes: [f32; 6],
+}
+
+/// Resource: every connected controller's buttons and sticks. See the
+/// [module docs](self).
+pub struct Gamepads {
+    /// Connected controllers, in the order they connected.
+    pads: Vec<Pad>,
+    /// Stick positions within this distance of the center read as zero;
+    /// also the threshold for triggers.
+    pub deadzone: f32,
+    rumble: Vec<Rumble>,
+    /// Connections since the backend last sent them as events.
+    pub(crate) events: Vec<GamepadEvent>,
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self {
+            pads: Vec::new(),
+            deadzone: DEFAULT_STICK_DEADZONE,
+            rumble: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+}
+
+impl Gamepads {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // ── Reading ──────────────────────────────────────────────────────────
+
+    /// Connected controllers, in the order they connected.
+    pub fn iter(&self) -> impl Iterator<Item = &GamepadInfo> {
+        self.pads.iter().map(|pad| &pad.info)
+    }
+
+    /// Ids of the connected controllers, in the order they connected.
+    pub fn ids(&self) -> Vec<GamepadId> {
+        self.iter().map(|info| info.id).collect()
+    }
+
+    /// Number of connected controllers.
+    pub fn len(&self) -> usize {
+        self.pads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pads.is_empty()
+    }
+
+    /// The controller with this id, if connected.
+    pub fn get(&self, id: GamepadId) -> Option<&GamepadInfo> {
+        self.pad(id).map(|pad| &pad.info)
+    }
+
+    /// Returns `true` if the controller is holding the button.
+    pub fn pressed(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.pad(id).is_some_and(|pad| pad.buttons.pressed(button))
+    }
+
+    /// Returns `true` if the controller pressed the button this frame.
+    pub fn just_pressed(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.pad(id).is_some_and(|pad| pad.buttons.just_pressed(button))
+    }
+
+    /// Returns `true` if the controller released the button this frame.
+    pub fn just_released(&self, id: GamepadId, button: GamepadButton) -> bool {
+        self.pad(id).is_some_and(|pad| pad.buttons.just_released(button))
+    }
+
+    /// A stick axis (-1 to 1) or trigger (0 to 1) with the deadzone
+    /// applied. 0 for a controller that isn't connected.
+    pub fn axis(&self, id: GamepadId, axis: GamepadAxis) -> f32 {
+        self.pad(id).map_or(0.0, |pad| filtered(&pad.axes, axis, self.deadzone))
+    }
+
+    /// The left stick, deadzone applied. +Y is up.
+    pub fn left_stick(&self, id: GamepadId) -> Vec2 {
+        Vec2::new(
+            self.axis(id, GamepadAxis::LeftStickX),
+            self.axis(id, GamepadAxis::LeftStickY),
+        )
+    }
+
+    /// The right stick, deadzone applied. +Y is up.
+    pub fn right_stick(&self, id: GamepadId) -> Vec2 {
+        Vec2::new(
+            self.axis(id, GamepadAxis::RightStickX),
+            self.axis(id, GamepadAxis::RightStickY),
+        )
+    }
+
+    // ── Rumble ───────────────────────────────────────────────────────────
+
+    /// Rumble a controller for `duration`, replacing any rumble it is
+    /// playing. Ignored by controllers without motors.
+    pub fn rumble(&mut self, id: GamepadId, strong: f32, weak: f32, duration: Duration) {
+        self.rumble.push(Rumble {
+            gamepad: Some(id),
+            strong: strong.clamp(0.0, 1.0),
+            weak: weak.clamp(0.0, 1.0),
+            duration,
+        });
+    }
+
+    /// Rumble every connected controller.
+    pub fn rumble_all(&mut self, strong: f32, weak: f32, duration: Duration) {
+        self.rumble.push(Rumble {
+            gamepad: None,
+            strong: strong.clamp(0.0, 1.0),
+            weak: weak.clamp(0.0, 1.0),
+            duration,
+        });
+    }
+
+    /// Stop a controller's rumble.
+    pub fn stop_rumble(&mut self, id: GamepadId) {
+        self.rumble(id, 0.0, 0.0, Duration::ZERO);
+    }
+
+    // ── Backend ──────────────────────────────────────────────────────────
+
+    /// Report a controller plugged in. Queues a [`GamepadEvent::Connected`].
+    pub fn connect(&mut self, info: GamepadInfo) {
+        self.events.push(GamepadEvent::Connected(info.id));
+        match self.pads.iter_mut().find(|pad| pad.info.id == info.id) {
+            Some(pad) => pad.info = info,
+            None => self.pads.push(Pad {
+                info,
+                buttons: Input::new(),
+                axes: [0.0; 6],
+            }),
+        }
+    }
+
+    /// Report a controller unplugged: whatever it held is released in the
+    /// merged state. Queues a [`GamepadEvent::Disconnected`].
+    pub fn disconnect(&mut self, id: GamepadId, input: &mut InputState) {
+        let Some(index) = self.pads.iter().position(|pad| pad.info.id == id) else {
+            return;
+        };
+        let pad = self.pads.remove(index);
+        self.events.push(GamepadEvent::Disconnected(id));
+        let style = pad.info.style;
+        for button in GamepadButton::ALL {
+            if pad.buttons.pressed(button) {
+                self.merge_button(button, style, input);
+            }
+        }
+        for axis in GamepadAxis::ALL {
+            self.merge_axis(axis, style, input);
+        }
+    }
+
+    /// Report a button from a controller, updating the merged state.
+    pub fn set_button(
+        &mut self,
+        id: GamepadId,
+        button: GamepadButton,
+        pressed: bool,
+        input: &mut InputState,
+    ) {
+        let Some(pad) = self.pad_mut(id) else {
+            return;
+        };
+        if pressed {
+            pad.buttons.press(button);
+        } else {
+            pad.buttons.release(button);
+        }
+        let style = pad.info.style;
+        self.merge_button(button, style, input);
+    }
+
+    /// Report a raw stick or trigger position from a controller, updating
+    /// the merged state.
+    pub fn set_axis(
+        &mut self,
+        id: GamepadId,
+        axis: GamepadAxis,
+        value: f32,
+        input: &mut InputState,
+    ) {
+        let Some(pad) = self.pad_mut(id) else {
+            return;
+        };
+        pad.axes[axis as usize] = value.clamp(-1.0, 1.0);
+        let style = pad.info.style;
+        // The radial deadzone ties a stick's two axes together.
+        self.merge_axis(axis, style, input);
+        if let Some(partner) = stick_partner(axis) {
+            self.merge_axis(partner, style, input);
+        }
+    }
+
+    /// Clear every controller's per-frame button state. Call once per frame
+    /// before reporting that frame's input.
+    pub fn clear_just(&mut self) {
+        for pad in &mut self.pads {
+            pad.buttons.clear_just();
+        }
+    }
+
+    /// Rumble requested since the last call, oldest first.
+    pub fn take_rumble(&mut self) -> Vec<Rumble> {
+        std::mem::take(&mut self.rumble)
+    }
+
+    fn pad(&self, id: GamepadId) -> Option<&Pad> {
+        self.pads.iter().find(|pad| pad.info.id == id)
+    }
+
+    fn pad_mut(&mut self, id: GamepadId) -> Option<&mut Pad> {
+        self.pads.iter_mut().find(|pad| pad.info.id == id)
+    }
+
+    /// Held in the merged state while any controller holds it.
+    fn merge_button(&self, button: GamepadButton, style: GamepadStyle, input: &mut InputState) {
+        let held = self.pads.iter().any(|pad| pad.buttons.pressed(button));
+        if held != input.gamepad_pressed(button) {
+            input.set_gamepad_button(style, button, held);
+        }
+    }
+
+    /// The merged axis is the controller pushing it furthest.
+    fn merge_axis(&self, axis: GamepadAxis, style: GamepadStyle, input: &mut InputState) {
+        let value = self
+            .pads
+            .iter()
+            .map(|pad| filtered(&pad.axes, axis, self.deadzone))
+            .fold(0.0, |a: f32, b: f32| if b.abs() > a.abs() { b } else { a });
+        input.set_gamepad_axis(style, axis, value);
+    }
+}
+
+/// The other axis of the same stick.
+fn stick_partner(axis: GamepadAxis) -> Option<GamepadAxis> {
+    match axis {
+        GamepadAxis::LeftStickX => Some(GamepadAxis::LeftStickY),
+        GamepadAxis::LeftStickY => Some(GamepadAxis::LeftStickX),
+        GamepadAxis::RightStickX => Some(GamepadAxis::RightStickY),
+        GamepadAxis::RightStickY => Some(GamepadAxis::RightStickX),
+        GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => None,
+    }
+}
+
+/// `axis` out of raw `axes` with the deadzone cut out and the remaining
+/// range stretched back to full.
+fn filtered(axes: &[f32; 6], axis: GamepadAxis, deadzone: f32) -> f32 {
+    let raw = axes[axis as usize];
+    let Some(partner) = stick_partner(axis) else {
+        return rescale(raw, deadzone);
+    };
+    let stick = Vec2::new(raw, axes[partner as usize]);
+    let length = stick.length();
+    if length <= deadzone {
+        return 0.0;
+    }
+    let scaled = rescale(length.min(1.0), deadzone);
+    raw / length * scaled
+}
+
+fn rescale(value: f32, deadzone: f32) -> f32 {
+    if value.abs() <= deadzone {
+        0.0
+    } else {
+        value.signum() * ((value.abs() - deadzone) / (1.0 - deadzone)).min(1.0)
+    }
+}
+
+// ── Plugin ──────────────────────────────────────────────────────────────
+
+/// Plugin that inserts [`Gamepads`], registers [`GamepadEvent`] and polls
+/// controllers through gilrs at the start of every frame.
+///
+/// # Example
+///
+/// ```ignore
+/// Game::new("My Game")
+///     .plugin(GamepadInput)
+///     .setup(setup)
+///     .run();
+/// ```
+#[cfg(feature = "gamepad")]
+pub struct GamepadInput;
+
+#[cfg(feature = "gamepad")]
+impl crate::game::Plugin for GamepadInput {
+    fn build(&self, game: &mut crate::game::Game) {
+        game.insert_resource(Gamepads::new());
+        game.add_event::<GamepadEvent>();
+        let mut backend = match gilrs::Gilrs::new() {
+            Ok(gilrs) => Some(backend::Backend::new(gilrs)),
+            Err(err) => {
+                log::warn!("Gamepads unavailable: {err}");
+                None
+            }
+        };
+        game.add_hook(crate::hooks::Hook::FrameStart, move |ctx| {
+            let Some(backend) = &mut backend else {
+                return;
+            };
+            let Some(mut gamepads) = ctx.world.resource_remove::<Gamepads>() else {
+                return;
+            };
+            backend.poll(&mut gamepads, &mut ctx.input);
+            send_events(&mut ctx.world, &mut gamepads);
+            ctx.world.insert_resource(gamepads);
+        });
+    }
+}
+
+/// Send queued connections as [`GamepadEvent`]s, if the event is registered.
+#[cfg_attr(not(feature = "gamepad"), allow(dead_code))]
+fn send_events(world: &mut World, gamepads: &mut Gamepads) {
+    if !world.has_resource::<crate::ecs::Events<GamepadEvent>>() {
+        gamepads.events.clear();
+        return;
+    }
+    for event in gamepads.events.drain(..) {
+        world.send_event(event);
+    }
+}
+
+#[cfg(feature = "gamepad")]
+mod backend {
+    use std::collections::HashMap;
+
+    use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+    use gilrs::{Axis, Button, EventType, Gilrs};
+
+    use super::{GamepadId, GamepadInfo, Gamepads, Rumble};
+    use crate::context::InputState;
+    use crate::input::{GamepadAxis, GamepadButton, GamepadStyle};
+
+    /// USB vendor id of Sony controllers.
+    const SONY_VENDOR: u16 = 0x054c;
+
+    pub(super) struct Backend {
+        gilrs: Gilrs,
+        /// Whether controllers connected at startup have been reported.
+        started: bool,
+        /// Rumble playing per controller; dropping one stops it.
+        effects: HashMap<usize, Effect>,
+    }
+
+    impl Backend {
+        pub(super) fn new(gilrs: Gilrs) -> Self {
+            Self {
+                gilrs,
+                started: false,
+                effects: HashMap::new(),
+            }
+        }
+
+        /// Apply every gilrs event since the last frame, then start
+        /// requested rumble. Controllers connected before the game started
+        /// are reported on the first poll.
+        pub(super) fn poll(&mut self, gamepads: &mut Gamepads, input: &mut InputState) {
+            gamepads.clear_just();
+            if !self.started {
+                self.started = true;
+                let present: Vec<_> = self.gilrs.gamepads().map(|(id, _)| id).collect();
+                for id in present {
+                    gamepads.connect(self.info(id));
+                }
+            }
+            while let Some(gilrs::Event { id, event, .. }) = self.gilrs.next_event() {
+                let pad = GamepadId(id.into());
+                match event {
+                    EventType::Connected if gamepads.get(pad).is_none() => {
+                        gamepads.connect(self.info(id));
+                    }
+                    EventType::Disconnected => {
+                        self.effects.remove(&pad.0);
+                        gamepads.disconnect(pad, input);
+                    }
+                    EventType::ButtonPressed(button, _) => {
+                        if let Some(button) = map_button(button) {
+                            gamepads.set_button(pad, button, true, input);
+                        }
+                    }
+                    EventType::ButtonReleased(button, _) => {
+                        if let Some(button) = map_button(button) {
+                            gamepads.set_button(pad, button, false, input);
+                        }
+                    }
+                    // Analog triggers report as buttons with a value.
+                    EventType::ButtonChanged(Button::LeftTrigger2, value, _) => {
+                        gamepads.set_axis(pad, GamepadAxis::LeftTrigger, value, input);
+                    }
+                    EventType::ButtonChanged(Button::RightTrigger2, value, _) => {
+                        gamepads.set_axis(pad, GamepadAxis::RightTrigger, value, input);
+                    }
+                    EventType::AxisChanged(axis, value, _) => {
+                        if let Some(axis) = map_axis(axis) {
+                            gamepads.set_axis(pad, axis, value, input);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            for rumble in gamepads.take_rumble() {
+                self.rumble(gamepads, rumble);
+            }
+        }
+
+        fn info(&self, id: gilrs::GamepadId) -> GamepadInfo {
+            let gamepad = self.gilrs.gamepad(id);
+            let style = if gamepad.vendor_id() == Some(SONY_VENDOR) {
+                GamepadStyle::PlayStation
+            } else {
+                GamepadStyle::Xbox
+            };
+            GamepadInfo {
+                id: GamepadId(id.into()),
+                name: gamepad.name().to_string(),
+                style,
+                rumble: gamepad.is_ff_supported(),
+            }
+        }
+
+        fn rumble(&mut self, gamepads: &Gamepads, rumble: Rumble) {
+            let targets: Vec<_> = match rumble.gamepad {
+                Some(id) => gamepads.get(id).into_iter().collect(),
+                None => gamepads.iter().collect(),
+            };
+            for info in targets.into_iter().filter(|info| info.rumble) {
+                // Replacing the old effect drops, and so stops, it.
+                self.effects.remove(&info.id.0);
+                if rumble.duration.is_zero() || rumble.strong + rumble.weak <= 0.0 {
+                    continue;
+                }
+                let Some((id, _)) =
+                    self.gilrs.gamepads().find(|&(id, _)| usize::from(id) == info.id.0)
+                else {
+                    continue;
+                };
+                let ticks = Ticks::from_ms(rumble.duration.as_millis().min(u32::MAX as u128) as u32);
+                let motor = |kind| BaseEffect {
+                    kind,
+                    scheduling: Replay {
+                        play_for: ticks,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                };
+                let effect = EffectBuilder::new()
+                    .add_effect(motor(BaseEffectType::Strong {
+                        magnitude: (rumble.strong * u16::MAX as f32) as u16,
+                    }))
+                    .add_effect(motor(BaseEffectType::Weak {
+                        magnitude: (rumble.weak * u16::MAX as f32) as u16,
+                    }))
+                    .gamepads(&[id])
+                    .repeat(Repeat::For(ticks))
+                    .finish(&mut self.gilrs)
+                    .and_then(|effect| effect.play().map(|()| effect));
+                match effect {
+                    Ok(effect) => {
+                        self.effects.insert(info.id.0, effect);
+                    }
+                    Err(err) => log::warn!("Failed to rumble {}: {err}", info.name),
+                }
+            }
+        }
+    }
+
+    fn map_button(button: Button) -> Option<GamepadButton> {
+        Some(match button {
+            Button::South => GamepadButton::South,
+            Button::East => GamepadButton::East,
+            Button::West => GamepadButton::West,
+            Button::North => GamepadButton::North,
+            // gilrs calls the shoulder buttons triggers and the triggers
+            // "trigger 2".
+            Button::LeftTrigger => GamepadButton::LeftShoulder,
+            Button::RightTrigger => GamepadButton::RightShoulder,
+            Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+            Button::RightTrigger2 => GamepadButton::RightTrigger,
+            Button::Select => GamepadButton::Select,
+            Button::Start => GamepadButton::Start,
+            Button::LeftThumb => GamepadButton::LeftStick,
+            Button::RightThumb => GamepadButton::RightStick,
+            Button::DPadUp => GamepadButton::DPadUp,
+            Button::DPadDown => GamepadButton::DPadDown,
+            Button::DPadLeft => GamepadButton::DPadLeft,
+            Button::DPadRight => GamepadButton::DPadRight,
+            _ => return None,
+        })
+    }
+
+    fn map_axis(axis: Axis) -> Option<GamepadAxis> {
+        Some(match axis {
+            Axis::LeftStickX => GamepadAxis::LeftStickX,
+            Axis::LeftStickY => GamepadAxis::LeftStickY,
+            Axis::RightStickX => GamepadAxis::RightStickX,
+            Axis::RightStickY => GamepadAxis::RightStickY,
+            _ => return None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(id: usize, style: GamepadStyle) -> GamepadInfo {
+        GamepadInfo {
+            id: GamepadId(id),
+            name: format!("pad {id}"),
+            style,
+            rumble: true,
+        }
+    }
+
+    #[test]
+    fn pads_merge_into_input_state() {
+        let mut pads = Gamepads::new();
+        let mut input = InputState::new();
+        pads.connect(info(0, GamepadStyle::Xbox));
+        pads.connect(info(1, GamepadStyle::PlayStation));
+
+        pads.set_button(GamepadId(0), GamepadButton::South, true, &mut input);
+        pads.set_button(GamepadId(1), GamepadButton::South, true, &mut input);
+        assert!(pads.just_pressed(GamepadId(1), GamepadButton::South));
+        assert!(input.gamepad_just_pressed(GamepadButton::South));
+
+        // Held while either pad holds it; pad 1 unplugging releases its share.
+        pads.clear_just();
+        input.clear_just();
+        pads.set_button(GamepadId(0), GamepadButton::South, false, &mut input);
+        assert!(pads.just_released(GamepadId(0), GamepadButton::South));
+        assert!(input.gamepad_pressed(GamepadButton::South));
+        pads.disconnect(GamepadId(1), &mut input);
+        assert!(input.gamepad_just_released(GamepadButton::South));
+        assert_eq!(
+            pads.events,
+            [
+                GamepadEvent::Connected(GamepadId(0)),
+                GamepadEvent::Connected(GamepadId(1)),
+                GamepadEvent::Disconnected(GamepadId(1)),
+            ]
+        );
+        assert_eq!(pads.ids(), [GamepadId(0)]);
+
+        pads.rumble_all(2.0, 0.5, Duration::from_millis(100));
+        assert_eq!(pads.take_rumble()[0].strong, 1.0);
+        assert!(pads.take_rumble().is_empty());
+    }
+
+    #[test]
+    fn radial_deadzone_rescales_sticks() {
+        let mut pads = Gamepads::new();
+        let mut input = InputState::new();
+        let pad = GamepadId(3);
+        pads.connect(info(3, GamepadStyle::Xbox));
+
+        // Drift inside the deadzone reads as rest, even on a diagonal.
+        pads.set_axis(pad, GamepadAxis::LeftStickX, 0.06, &mut input);
+        pads.set_axis(pad, GamepadAxis::LeftStickY, 0.06, &mut input);
+        assert_eq!(pads.left_stick(pad), Vec2::ZERO);
+        assert_eq!(input.gamepad_axis(GamepadAxis::LeftStickX), 0.0);
+
+        // Outside it the direction is kept and the length stretched.
+        pads.set_axis(pad, GamepadAxis::LeftStickX, 0.0, &mut input);
+        pads.set_axis(pad, GamepadAxis::LeftStickY, -0.55, &mut input);
+        assert!((pads.left_stick(pad).y + 0.5).abs() < 1e-5);
+        assert!((input.gamepad_axis(GamepadAxis::LeftStickY) + 0.5).abs() < 1e-5);
+        pads.set_axis(pad, GamepadAxis::RightTrigger, 1.0, &mut input);
+        assert_eq!(pads.axis(pad, GamepadAxis::RightTrigger), 1.0);
+        assert_eq!(pads.axis(GamepadId(9), GamepadAxis::RightTrigger), 0.0);
+    }
+}
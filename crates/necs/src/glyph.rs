@@ -0,0 +1,382 @@
+//! # Input Glyphs — Button Prompts That Match the Player's Device
+//!
+//! "Press [A] to jump" is wrong for someone holding a PlayStation pad, and
+//! wrong again the moment they reach for the keyboard. Prompts here are
+//! written against *actions*, and the button shown is looked up at draw time
+//! from the [`ActionMap`] and the device the player last pressed something
+//! on ([`InputState::device`](crate::context::InputState::device)):
+//!
+//! ```text
+//!   "Press {jump} to jump"          ActionMap: jump = [Key(Space), Gamepad(South)]
+//!            │
+//!            ▼  active device
+//!   KeyboardMouse  ──► Key(Space)       ──► "Press [Space] to jump"
+//!   Xbox           ──► Gamepad(South)   ──► "Press [A] to jump"
+//!   PlayStation    ──► Gamepad(South)   ──► "Press [Cross] to jump"
+//! ```
+//!
+//! ## Text and Icons
+//!
+//! [`format_prompt`] (or `ctx.input_prompt`) fills in text labels. For icons,
+//! register one glyph [`TextureAtlas`](crate::render2d::TextureAtlas) per
+//! device in [`InputGlyphs`], with regions named by [`glyph_name`] — `Space`,
+//! `KeyW`, `MouseLeft`, `South`, `DPadUp`. The Xbox and PlayStation atlases
+//! use the same names and differ only in their art.
+//!
+//! Two components keep prompts current without game code: [`InputPrompt`]
+//! points a [`Sprite`] at the icon for an action, and [`PromptText`] rewrites
+//! a [`Text`]'s content from a template. Both are updated every frame, after
+//! update systems.
+//!
+//! ```ignore
+//! let xbox = ctx.load_texture_atlas("ui/glyphs_xbox.json").unwrap();
+//! let keys = ctx.load_texture_atlas("ui/glyphs_keyboard.json").unwrap();
+//! ctx.world.insert_resource(
+//!     InputGlyphs::new()
+//!         .atlas(InputDevice::Gamepad(GamepadStyle::Xbox), xbox)
+//!         .atlas(InputDevice::KeyboardMouse, keys),
+//! );
+//! ctx.spawn("jump_icon").insert(Sprite::new()).insert(InputPrompt::new("jump"));
+//! ctx.spawn("jump_hint").insert(Text::new("", font)).insert(PromptText::new("{jump} Jump"));
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: No built-in glyphs; the Input System exposes
+//!   `GetBindingDisplayString` and the active control scheme, and icon
+//!   lookup is left to the game (see the Rebinding UI sample).
+//! - **Bevy**: Nothing built in.
+//! - **Godot**: Nothing built in; `InputEvent` text for keys only.
+//! - **Our approach**: Unity's pieces wired together — display strings and
+//!   icon regions per device, and components that follow the device switch.
+
+#[cfg(feature = "render2d")]
+use std::collections::HashMap;
+
+use crate::action::{ActionMap, Binding};
+#[cfg(feature = "render2d")]
+use crate::ecs::World;
+use crate::input::{GamepadAxis, GamepadButton, GamepadStyle, InputDevice, MouseButton};
+#[cfg(feature = "render2d")]
+use crate::render2d::texture_atlas::{AtlasSprite, TextureAtlasHandle, TextureAtlases};
+#[cfg(feature = "render2d")]
+use crate::render2d::{Sprite, Text};
+
+// ── Names and labels ─────────────────────────────────────────────────────
+
+/// Whether `binding` is something `device` has.
+pub fn binding_on_device(binding: Binding, device: InputDevice) -> bool {
+    match binding {
+        Binding::Key(_) | Binding::Mouse(_) => device == InputDevice::KeyboardMouse,
+        Binding::Gamepad(_) | Binding::Axis(_) | Binding::AxisNegative(_) => {
+            matches!(device, InputDevice::Gamepad(_))
+        }
+    }
+}
+
+/// The first binding of `action`, in the active contexts, that `device` has.
+pub fn prompt_binding(map: &ActionMap, action: &str, device: InputDevice) -> Option<Binding> {
+    map.bindings_for(action).find(|&b| binding_on_device(b, device))
+}
+
+/// Region name of `binding` in a glyph atlas: the [`KeyCode`](crate::input::KeyCode)
+/// or [`GamepadButton`] variant, `Mouse` + the [`MouseButton`] variant, and
+/// the [`GamepadAxis`] variant + `Pos` or `Neg`.
+pub fn glyph_name(binding: Binding) -> String {
+    match binding {
+        Binding::Key(key) => format!("{key:?}"),
+        Binding::Mouse(button) => format!("Mouse{button:?}"),
+        Binding::Gamepad(button) => format!("{button:?}"),
+        Binding::Axis(axis) => format!("{axis:?}Pos"),
+        Binding::AxisNegative(axis) => format!("{axis:?}Neg"),
+    }
+}
+
+/// What `binding` is called on `device`, e.g. `A` on Xbox and `Cross` on
+/// PlayStation for [`GamepadButton::South`].
+pub fn binding_label(binding: Binding, device: InputDevice) -> String {
+    match binding {
+        Binding::Key(key) => {
+            let name = format!("{key:?}");
+            let short = name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit"));
+            short.unwrap_or(&name).to_string()
+        }
+        Binding::Mouse(MouseButton::Left) => "LMB".into(),
+        Binding::Mouse(MouseButton::Right) => "RMB".into(),
+        Binding::Mouse(MouseButton::Middle) => "MMB".into(),
+        Binding::Mouse(button) => format!("Mouse {button:?}"),
+        Binding::Gamepad(button) => {
+            let style = match device {
+                InputDevice::Gamepad(style) => style,
+                InputDevice::KeyboardMouse => GamepadStyle::Xbox,
+            };
+            gamepad_label(button, style).into()
+        }
+        Binding::Axis(axis) => axis_label(axis, true).into(),
+        Binding::AxisNegative(axis) => axis_label(axis, false).into(),
+    }
+}
+
+fn axis_label(axis: GamepadAxis, positive: bool) -> &'static str {
+    use GamepadAxis::*;
+    match (axis, positive) {
+        (LeftStickX, true) => "LS Right",
+        (LeftStickX, false) => "LS Left",
+        (LeftStickY, true) => "LS Up",
+        (LeftStickY, false) => "LS Down",
+        (RightStickX, true) => "RS Right",
+        (RightStickX, false) => "RS Left",
+        (RightStickY, true) => "RS Up",
+        (RightStickY, false) => "RS Down",
+        (LeftTrigger, _) => "LT",
+        (RightTrigger, _) => "RT",
+    }
+}
+
+fn gamepad_label(button: GamepadButton, style: GamepadStyle) -> &'static str {
+    use GamepadButton::*;
+    match (button, style) {
+        (South, GamepadStyle::Xbox) => "A",
+        (East, GamepadStyle::Xbox) => "B",
+        (West, GamepadStyle::Xbox) => "X",
+        (North, GamepadStyle::Xbox) => "Y",
+        (LeftShoulder, GamepadStyle::Xbox) => "LB",
+        (RightShoulder, GamepadStyle::Xbox) => "RB",
+        (LeftTrigger, GamepadStyle::Xbox) => "LT",
+        (RightTrigger, GamepadStyle::Xbox) => "RT",
+        (Select, GamepadStyle::Xbox) => "View",
+        (Start, GamepadStyle::Xbox) => "Menu",
+        (LeftStick, GamepadStyle::Xbox) => "LS",
+        (RightStick, GamepadStyle::Xbox) => "RS",
+        (South, GamepadStyle::PlayStation) => "Cross",
+        (East, GamepadStyle::PlayStation) => "Circle",
+        (West, GamepadStyle::PlayStation) => "Square",
+        (North, GamepadStyle::PlayStation) => "Triangle",
+        (LeftShoulder, GamepadStyle::PlayStation) => "L1",
+        (RightShoulder, GamepadStyle::PlayStation) => "R1",
+        (LeftTrigger, GamepadStyle::PlayStation) => "L2",
+        (RightTrigger, GamepadStyle::PlayStation) => "R2",
+        (Select, GamepadStyle::PlayStation) => "Create",
+        (Start, GamepadStyle::PlayStation) => "Options",
+        (LeftStick, GamepadStyle::PlayStation) => "L3",
+        (RightStick, GamepadStyle::PlayStation) => "R3",
+        (DPadUp, _) => "Up",
+        (DPadDown, _) => "Down",
+        (DPadLeft, _) => "Left",
+        (DPadRight, _) => "Right",
+    }
+}
+
+/// Replace each `{action}` in `template` with `[label]` of its binding on
+/// `device`. Actions with no binding on the device show as `[action]`;
+/// `{{` and `}}` are literal braces.
+pub fn format_prompt(template: &str, map: &ActionMap, device: InputDevice) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        let brace = rest.as_bytes()[i];
+        rest = &rest[i + 1..];
+        if rest.as_bytes().first() == Some(&brace) {
+            out.push(brace as char);
+            rest = &rest[1..];
+            continue;
+        }
+        if brace == b'}' {
+            out.push('}');
+            continue;
+        }
+        let Some(end) = rest.find('}') else {
+            out.push('{');
+            continue;
+        };
+        let action = &rest[..end];
+        let label = prompt_binding(map, action, device)
+            .map_or_else(|| action.to_string(), |b| binding_label(b, device));
+        out.push('[');
+        out.push_str(&label);
+        out.push(']');
+        rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+// ── Icons ────────────────────────────────────────────────────────────────
+
+/// Resource: one glyph atlas per device. See the [module docs](self).
+#[cfg(feature = "render2d")]
+#[derive(Debug, Clone, Default)]
+pub struct InputGlyphs {
+    atlases: HashMap<InputDevice, TextureAtlasHandle>,
+}
+
+#[cfg(feature = "render2d")]
+impl InputGlyphs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `atlas` for `device`'s glyphs (builder pattern).
+    pub fn atlas(mut self, device: InputDevice, atlas: TextureAtlasHandle) -> Self {
+        self.atlases.insert(device, atlas);
+        self
+    }
+
+    /// Use `atlas` for `device`'s glyphs.
+    pub fn set_atlas(&mut self, device: InputDevice, atlas: TextureAtlasHandle) {
+        self.atlases.insert(device, atlas);
+    }
+
+    /// The icon for `action` on `device`: the first of its bindings on the
+    /// device that has a region in the device's atlas.
+    pub fn glyph(
+        &self,
+        atlases: &TextureAtlases,
+        map: &ActionMap,
+        action: &str,
+        device: InputDevice,
+    ) -> Option<AtlasSprite> {
+        let handle = *self.atlases.get(&device)?;
+        let atlas = atlases.get(handle)?;
+        map.bindings_for(action)
+            .filter(|&b| binding_on_device(b, device))
+            .find_map(|b| atlas.index_of(&glyph_name(b)))
+            .map(|index| AtlasSprite { atlas: handle, index })
+    }
+}
+
+/// Component: show the icon for `action` on this entity's [`Sprite`],
+/// following the active device. Needs an [`InputGlyphs`] resource.
+#[cfg(feature = "render2d")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputPrompt {
+    pub action: String,
+}
+
+#[cfg(feature = "render2d")]
+impl InputPrompt {
+    pub fn new(action: &str) -> Self {
+        Self {
+            action: action.to_string(),
+        }
+    }
+}
+
+/// Component: set this entity's [`Text`] to `template` with its `{action}`
+/// placeholders filled in by [`format_prompt`].
+#[cfg(feature = "render2d")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptText {
+    pub template: String,
+}
+
+#[cfg(feature = "render2d")]
+impl PromptText {
+    pub fn new(template: &str) -> Self {
+        Self {
+            template: template.to_string(),
+        }
+    }
+}
+
+/// Refresh every [`InputPrompt`] sprite and [`PromptText`] for `device`.
+/// Does nothing without an [`ActionMap`].
+#[cfg(feature = "render2d")]
+pub(crate) fn update_input_prompts(world: &mut World, device: InputDevice) {
+    let Some(map) = world.resource_remove::<ActionMap>() else {
+        return;
+    };
+
+    world.query::<(&PromptText, &mut Text)>(|_, (prompt, text)| {
+        let content = format_prompt(&prompt.template, &map, device);
+        if text.content != content {
+            text.content = content;
+        }
+    });
+
+    if let Some(glyphs) = world.resource_remove::<InputGlyphs>() {
+        let atlases = world.resource_remove::<TextureAtlases>().unwrap_or_default();
+        world.query::<(&InputPrompt, &mut Sprite)>(|_, (prompt, sprite)| {
+            sprite.atlas = glyphs.glyph(&atlases, &map, &prompt.action, device);
+        });
+        world.insert_resource(atlases);
+        world.insert_resource(glyphs);
+    }
+
+    world.insert_resource(map);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::ActionBindings;
+    use crate::input::KeyCode;
+
+    const XBOX: InputDevice = InputDevice::Gamepad(GamepadStyle::Xbox);
+    const PS: InputDevice = InputDevice::Gamepad(GamepadStyle::PlayStation);
+
+    fn map() -> ActionMap {
+        ActionMap::new(
+            ActionBindings::default()
+                .bind("gameplay", "jump", Binding::Key(KeyCode::Space))
+                .bind("gameplay", "jump", Binding::Gamepad(GamepadButton::South))
+                .bind("gameplay", "fire", Binding::Mouse(MouseButton::Left))
+                .bind("gameplay", "dash", Binding::Key(KeyCode::KeyQ)),
+        )
+    }
+
+    #[test]
+    fn prompts_follow_the_device() {
+        let map = map();
+        let template = "Press {jump} to jump, {fire} to fire";
+        assert_eq!(
+            format_prompt(template, &map, InputDevice::KeyboardMouse),
+            "Press [Space] to jump, [LMB] to fire"
+        );
+        assert_eq!(format_prompt(template, &map, XBOX), "Press [A] to jump, [fire] to fire");
+        assert_eq!(format_prompt("{jump} {{ok}}", &map, PS), "[Cross] {ok}");
+        assert_eq!(format_prompt("{dash", &map, PS), "{dash");
+        assert_eq!(binding_label(Binding::Key(KeyCode::KeyQ), XBOX), "Q");
+        assert_eq!(binding_label(Binding::Key(KeyCode::Digit1), XBOX), "1");
+    }
+
+    #[cfg(feature = "render2d")]
+    #[test]
+    fn prompt_sprites_pick_the_device_atlas() {
+        use crate::math::Vec2;
+        use crate::render2d::texture::TextureHandle;
+        use crate::render2d::TextureAtlas;
+
+        let mut atlases = TextureAtlases::default();
+        let mut keys = TextureAtlas::new(TextureHandle(0), Vec2::new(64.0, 16.0));
+        keys.add_region(Some("KeyQ"), 0.0, 0.0, 16.0, 16.0);
+        keys.add_region(Some("Space"), 16.0, 0.0, 32.0, 16.0);
+        let keys = atlases.add(keys);
+        let mut pad = TextureAtlas::new(TextureHandle(1), Vec2::new(16.0, 16.0));
+        pad.add_region(Some("South"), 0.0, 0.0, 16.0, 16.0);
+        let pad = atlases.add(pad);
+
+        let mut world = World::new();
+        world.insert_resource(map());
+        world.insert_resource(atlases);
+        world.insert_resource(
+            InputGlyphs::new()
+                .atlas(InputDevice::KeyboardMouse, keys)
+                .atlas(PS, pad),
+        );
+        let jump = world.spawn((Sprite::new(), InputPrompt::new("jump")));
+
+        update_input_prompts(&mut world, InputDevice::KeyboardMouse);
+        let sprite = world.get::<Sprite>(jump).unwrap().atlas;
+        assert_eq!(sprite, Some(AtlasSprite { atlas: keys, index: 1 }));
+
+        update_input_prompts(&mut world, PS);
+        let sprite = world.get::<Sprite>(jump).unwrap().atlas;
+        assert_eq!(sprite, Some(AtlasSprite { atlas: pad, index: 0 }));
+
+        // No Xbox atlas registered.
+        update_input_prompts(&mut world, XBOX);
+        assert_eq!(world.get::<Sprite>(jump).unwrap().atlas, None);
+    }
+}
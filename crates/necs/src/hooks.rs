@@ -0,0 +1,166 @@
+//! # Hooks — Engine Event Callbacks for Host Code and Plugins
+//!
+//! Update systems are for game logic. Some code needs to run at a specific
+//! point in the engine's frame instead — analytics flushing at frame end,
+//! a custom saver on shutdown, a "save changes?" prompt when the window is
+//! closed. Hooks give that code a named place to live rather than disguising
+//! it as an update system and hoping the ordering works out.
+//!
+//! ```text
+//!  RedrawRequested
+//!    │
+//!    ├─ FrameStart ──────── time updated, before asset reloads
+//!    ├─ input drained ───── queued key/mouse/cursor events applied
+//!    ├─ KeyboardRequested / KeyboardDismissed ── a TextInput gained/lost focus
+//!    ├─ update systems
+//!    ├─ transform propagation
+//!    ├─ BeforeRender ────── skipped while minimized/occluded
+//!    ├─ render
+//!    ├─ AfterRender ─────── skipped while minimized/occluded
+//!    └─ FrameEnd
+//!
+//!  CloseRequested ──► close-request hooks ──► any returned false? keep running
+//!                                              otherwise exit
+//!  event loop exit ──► shutdown systems ──► Shutdown ──► teardown
+//! ```
+//!
+//! Register hooks on the [`Game`](crate::game::Game) builder, or from a
+//! plugin with [`Game::add_hook`](crate::game::Game::add_hook):
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .on_frame_end(|ctx| analytics::flush(ctx.time.frame_count()))
+//!     .on_close_requested(|ctx| {
+//!         // Veto the close and show a confirmation dialog instead.
+//!         ctx.world.resource_mut::<Menu>().open_quit_prompt();
+//!         false
+//!     })
+//!     .on_shutdown(|ctx| save_settings(&ctx.world))
+//!     .run();
+//! ```
+//!
+//! A vetoed close leaves the game running; call
+//! [`Context::exit`](crate::context::Context::exit) once the player
+//! confirms. Hooks of the same kind run in registration order.
+//!
+//! ## On-Screen Keyboards
+//!
+//! When a [`TextInput`](crate::ui::TextInput) gains focus the engine enables
+//! IME on the window, which is enough to bring up the keyboard on iOS and
+//! in browsers. Other touch platforms need a platform call — on Android,
+//! `InputMethodManager.showSoftInput` through JNI. The keyboard hooks are
+//! where that call goes:
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .on_keyboard_requested(|ctx| android::show_soft_input())
+//!     .on_keyboard_dismissed(|ctx| android::hide_soft_input())
+//! ```
+//!
+//! [`TextFocus::rect`](crate::ui::TextFocus::rect) says where the field is,
+//! for scrolling it above the keyboard.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `MonoBehaviour` message methods (`OnApplicationQuit`,
+//!   `Application.wantsToQuit` for the veto) plus `Camera.onPreRender`.
+//! - **Bevy**: Schedules (`First`, `PostUpdate`, `Last`) and an `AppExit`
+//!   event; close vetoes need a custom `WindowCloseRequested` handler.
+//! - **Our approach**: Plain callbacks at fixed points — no schedule graph.
+
+use crate::context::Context;
+
+/// A point in the engine's frame or lifetime where hooks run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Hook {
+    /// Start of a frame, after `Time` is updated.
+    FrameStart,
+    /// After update systems and transform propagation, before rendering.
+    BeforeRender,
+    /// After the frame has been submitted and presented.
+    AfterRender,
+    /// A [`TextInput`](crate::ui::TextInput) gained focus while none had
+    /// it: show an on-screen keyboard. Runs before update systems.
+    KeyboardRequested,
+    /// The focused [`TextInput`](crate::ui::TextInput) lost focus: hide the
+    /// on-screen keyboard. Runs before update systems.
+    KeyboardDismissed,
+    /// End of a frame, whether or not it was rendered.
+    FrameEnd,
+    /// Once, when the event loop exits.
+    Shutdown,
+}
+
+type HookFn = Box<dyn FnMut(&mut Context)>;
+type CloseHookFn = Box<dyn FnMut(&mut Context) -> bool>;
+
+/// All registered hooks. Owned by the game loop.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    hooks: Vec<(Hook, HookFn)>,
+    close_requested: Vec<CloseHookFn>,
+}
+
+impl Hooks {
+    pub fn add(&mut self, hook: Hook, f: impl FnMut(&mut Context) + 'static) {
+        self.hooks.push((hook, Box::new(f)));
+    }
+
+    pub fn add_close_requested(&mut self, f: impl FnMut(&mut Context) -> bool + 'static) {
+        self.close_requested.push(Box::new(f));
+    }
+
+    /// Run every hook registered for `hook`, in registration order.
+    pub fn run(&mut self, hook: Hook, ctx: &mut Context) {
+        for (_, f) in self.hooks.iter_mut().filter(|(h, _)| *h == hook) {
+            f(ctx);
+        }
+    }
+
+    /// Ask every close-request hook whether the window may close. All hooks
+    /// run (so each can react to the request); any `false` vetoes.
+    pub fn close_allowed(&mut self, ctx: &mut Context) -> bool {
+        let mut allowed = true;
+        for f in &mut self.close_requested {
+            allowed &= f(ctx);
+        }
+        allowed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Trace(Vec<&'static str>);
+
+    #[test]
+    fn hooks_run_in_order_for_their_point_only() {
+        let mut ctx = Context::new();
+        ctx.world.insert_resource(Trace::default());
+        let mut hooks = Hooks::default();
+        hooks.add(Hook::FrameStart, |ctx| ctx.world.resource_mut::<Trace>().0.push("a"));
+        hooks.add(Hook::FrameEnd, |ctx| ctx.world.resource_mut::<Trace>().0.push("end"));
+        hooks.add(Hook::FrameStart, |ctx| ctx.world.resource_mut::<Trace>().0.push("b"));
+
+        hooks.run(Hook::FrameStart, &mut ctx);
+        assert_eq!(ctx.world.resource::<Trace>().0, ["a", "b"]);
+    }
+
+    #[test]
+    fn any_close_hook_can_veto_and_all_run() {
+        let mut ctx = Context::new();
+        ctx.world.insert_resource(Trace::default());
+        let mut hooks = Hooks::default();
+        assert!(hooks.close_allowed(&mut ctx));
+
+        hooks.add_close_requested(|_| false);
+        hooks.add_close_requested(|ctx| {
+            ctx.world.resource_mut::<Trace>().0.push("asked");
+            true
+        });
+        assert!(!hooks.close_allowed(&mut ctx));
+        assert_eq!(ctx.world.resource::<Trace>().0, ["asked"]);
+    }
+}
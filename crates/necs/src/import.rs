@@ -0,0 +1,664 @@
+//! # Import — Preprocessing Assets Once, Loading Them Fast
+//!
+//! Source assets are stored in formats that are good for artists and
+//! version control — PNG, JPEG, WAV at whatever rate it was recorded — but
+//! slow to load: every startup decodes every image again, and every sound
+//! at an odd sample rate is resampled again. The import step does that work once and keeps the
+//! result in a cache directory, in a form the engine can load with little
+//! more than a file read.
+//!
+//! ```text
+//!   load_texture("hero.png")
+//!         │
+//!         ▼
+//!   index.json: hero.png, same size + mtime? ──yes──► known content hash
+//!         │ no                                              │
+//!         ▼                                                 │
+//!   read + hash hero.png ─────────────────────────────────► │
+//!                                                           ▼
+//!   cache/<hash>-texture-v1.bin exists? ──yes──► read it (fast path)
+//!         │ no
+//!         ▼
+//!   importer: decode PNG → raw RGBA8 → write cache file
+//! ```
+//!
+//! Cache files are keyed by the *content* hash of the source plus the
+//! importer's name and version, so:
+//!
+//! - Editing a source re-imports it; touching it without changes doesn't.
+//! - Two copies of the same image share one cache file.
+//! - Bumping an importer's version invalidates everything it produced.
+//!
+//! The index (`index.json`) only remembers each source's size, modification
+//! time and last hash, so unchanged sources aren't even re-read. Stale cache
+//! files are never read again; delete the directory to reclaim the space.
+//!
+//! ## Using It
+//!
+//! Insert an [`ImportCache`] resource and every texture load goes through it
+//! (sounds load through it with
+//! [`SoundData::import`](crate::audio::SoundData::import)):
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .resource(ImportCache::new(".necs-cache"))
+//!     .run();
+//! ```
+//!
+//! To import ahead of time — in a build script or a release tool — call
+//! [`ImportCache::preprocess_dir`] on the asset directory and ship the cache
+//! with the game.
+//!
+//! ## Importers
+//!
+//! | Importer | Sources | Output |
+//! |----------|---------|--------|
+//! | [`TextureImporter`] | PNG, JPEG | raw RGBA8 |
+//! | [`AudioImporter`] | WAV (8/16/24/32-bit PCM, 32-bit float) | 16-bit PCM WAV at 48 kHz |
+//!
+//! Other asset types implement [`Importer`] and call [`ImportCache::import`]
+//! directly. Two importers are left as separate follow-ups: GPU-compressed
+//! textures (BCn/ASTC, which need an encoder and a per-adapter format
+//! fallback in the texture stores) and packed glTF meshes (which need
+//! `load_gltf` to read materials and images without re-parsing the file).
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Every asset is imported into the `Library/` folder on
+//!   change; per-type importers with settings in `.meta` files.
+//! - **Bevy**: Asset processor (`bevy_asset` "processed" mode) writes
+//!   `imported_assets/` with a hash-based meta file per asset.
+//! - **Godot**: `.godot/imported/` holds converted files, keyed by path +
+//!   MD5, configured through `.import` files.
+//! - **Our approach**: The same content-hashed cache, opt-in through one
+//!   resource, with built-in importers for textures and sounds.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::World;
+
+/// Name of the index file inside the cache directory.
+const INDEX_FILE: &str = "index.json";
+
+/// Magic bytes at the start of a [`TextureImporter`] cache file.
+const TEXTURE_MAGIC: &[u8; 4] = b"NTEX";
+
+/// Source extensions [`ImportCache::preprocess_dir`] imports as textures.
+const TEXTURE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg"];
+
+/// Source extensions [`ImportCache::preprocess_dir`] imports as sounds.
+const AUDIO_EXTENSIONS: &[&str] = &["wav"];
+
+/// Sample rate [`AudioImporter`] converts to: what most output devices run
+/// at, so the mixer plays the result without resampling.
+pub const AUDIO_SAMPLE_RATE: u32 = 48_000;
+
+// ── Errors ──────────────────────────────────────────────────────────────────
+
+/// Why an import failed.
+#[derive(Debug)]
+pub enum ImportError {
+    /// A source or cache file couldn't be read or written.
+    Io { path: PathBuf, error: std::io::Error },
+    /// The importer couldn't process the source.
+    Process { path: PathBuf, message: String },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Io { path, error } => write!(f, "'{}': {error}", path.display()),
+            ImportError::Process { path, message } => {
+                write!(f, "can't import '{}': {message}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+// ── Importers ───────────────────────────────────────────────────────────────
+
+/// Turns a source file's bytes into the engine's preprocessed form.
+pub trait Importer {
+    /// Short name, part of cache file names (`texture`, `mesh`, ...).
+    fn name(&self) -> &'static str;
+    /// Bump when the output format changes, to invalidate old cache files.
+    fn version(&self) -> u32;
+    /// Process one source file.
+    fn process(&self, source: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// Decodes PNG/JPEG into raw RGBA8 with a small header. Read the result
+/// back with [`decode_texture`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextureImporter;
+
+impl Importer for TextureImporter {
+    fn name(&self) -> &'static str {
+        "texture"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn process(&self, source: &[u8]) -> Result<Vec<u8>, String> {
+        let img = image::load_from_memory(source).map_err(|e| e.to_string())?.to_rgba8();
+        let (width, height) = img.dimensions();
+        let mut out = Vec::with_capacity(12 + img.as_raw().len());
+        out.extend_from_slice(TEXTURE_MAGIC);
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        out.extend_from_slice(img.as_raw());
+        Ok(out)
+    }
+}
+
+/// Split a [`TextureImporter`] output into `(width, height, rgba)`.
+/// Returns `None` if the data is truncated or not a texture.
+pub fn decode_texture(data: &[u8]) -> Option<(u32, u32, &[u8])> {
+    let (header, pixels) = data.split_at_checked(12)?;
+    if &header[..4] != TEXTURE_MAGIC {
+        return None;
+    }
+    let width = u32::from_le_bytes(header[4..8].try_into().ok()?);
+    let height = u32::from_le_bytes(header[8..12].try_into().ok()?);
+    (pixels.len() == width as usize * height as usize * 4).then_some((width, height, pixels))
+}
+
+/// Converts WAV files to 16-bit PCM at [`AUDIO_SAMPLE_RATE`], keeping the
+/// channel count. The output is itself a WAV file, ready for
+/// [`SoundData::from_bytes`](crate::audio::SoundData::from_bytes).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AudioImporter;
+
+impl Importer for AudioImporter {
+    fn name(&self) -> &'static str {
+        "audio"
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn process(&self, source: &[u8]) -> Result<Vec<u8>, String> {
+        let wav = decode_wav(source)?;
+        let channels = wav.channels as usize;
+        let samples = resample(&wav.samples, channels, wav.sample_rate, AUDIO_SAMPLE_RATE);
+        let mut pcm = Vec::with_capacity(samples.len() * 2);
+        for sample in samples {
+            let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16;
+            pcm.extend_from_slice(&value.to_le_bytes());
+        }
+        Ok(wav_file(wav.channels, AUDIO_SAMPLE_RATE, &pcm))
+    }
+}
+
+/// A decoded WAV file: interleaved samples in `-1.0..=1.0`.
+struct Wav {
+    channels: u16,
+    sample_rate: u32,
+    samples: Vec<f32>,
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    bytes
+        .get(offset..offset + 2)
+        .map_or(0, |b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    bytes
+        .get(offset..offset + 4)
+        .map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+/// Decode a RIFF WAVE file's `fmt ` and `data` chunks.
+fn decode_wav(bytes: &[u8]) -> Result<Wav, String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a WAV file".into());
+    }
+    let mut format = None;
+    let mut data = None;
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let id = &bytes[pos..pos + 4];
+        let size = u32_at(bytes, pos + 4) as usize;
+        let start = pos + 8;
+        let end = start.saturating_add(size).min(bytes.len());
+        match id {
+            b"fmt " => format = Some(&bytes[start..end]),
+            b"data" => data = Some(&bytes[start..end]),
+            _ => {}
+        }
+        // Chunks are padded to an even length.
+        pos = end + (size & 1);
+    }
+    let (Some(format), Some(data)) = (format, data) else {
+        return Err("WAV file without fmt and data chunks".into());
+    };
+
+    let mut tag = u16_at(format, 0);
+    let channels = u16_at(format, 2);
+    let sample_rate = u32_at(format, 4);
+    let bits = u16_at(format, 14);
+    if tag == 0xFFFE {
+        // WAVE_FORMAT_EXTENSIBLE: the real tag opens the sub-format GUID.
+        tag = u16_at(format, 24);
+    }
+    if channels == 0 || sample_rate == 0 {
+        return Err("WAV file with no channels or no sample rate".into());
+    }
+    let samples: Vec<f32> = match (tag, bits) {
+        (1, 8) => data.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect(),
+        (1, 16) => data
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+            .collect(),
+        (1, 24) => data
+            .chunks_exact(3)
+            .map(|b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (1, 32) => data
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0)
+            .collect(),
+        (3, 32) => data
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+        _ => return Err(format!("unsupported WAV encoding (format {tag}, {bits} bits)")),
+    };
+    Ok(Wav {
+        channels,
+        sample_rate,
+        samples,
+    })
+}
+
+/// Resample interleaved `samples` from `from` Hz to `to` Hz by linear
+/// interpolation between neighboring frames.
+fn resample(samples: &[f32], channels: usize, from: u32, to: u32) -> Vec<f32> {
+    if from == to {
+        return samples.to_vec();
+    }
+    let frames = samples.len() / channels;
+    if frames == 0 {
+        return Vec::new();
+    }
+    let out_frames = (frames as u64 * to as u64).div_ceil(from as u64) as usize;
+    let step = from as f64 / to as f64;
+    let mut out = Vec::with_capacity(out_frames * channels);
+    for frame in 0..out_frames {
+        let at = frame as f64 * step;
+        let before = (at as usize).min(frames - 1);
+        let after = (before + 1).min(frames - 1);
+        let t = (at - before as f64) as f32;
+        for channel in 0..channels {
+            let a = samples[before * channels + channel];
+            let b = samples[after * channels + channel];
+            out.push(a + (b - a) * t);
+        }
+    }
+    out
+}
+
+/// Wrap 16-bit PCM samples in a WAV header.
+fn wav_file(channels: u16, sample_rate: u32, pcm: &[u8]) -> Vec<u8> {
+    let block_align = channels * 2;
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
+
+// ── Cache ───────────────────────────────────────────────────────────────────
+
+/// What the index remembers about one source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct IndexEntry {
+    len: u64,
+    /// Modification time, nanoseconds since the Unix epoch.
+    modified: u64,
+    hash: u64,
+}
+
+/// How many files a [`preprocess_dir`](ImportCache::preprocess_dir) run
+/// imported, and how many were already cached.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    pub imported: usize,
+    pub cached: usize,
+}
+
+/// Resource: the on-disk import cache. See the [module docs](self).
+pub struct ImportCache {
+    dir: PathBuf,
+    /// Source path → last known size, mtime and content hash.
+    index: HashMap<String, IndexEntry>,
+    index_dirty: bool,
+    hits: usize,
+    misses: usize,
+}
+
+impl ImportCache {
+    /// Use `dir` as the cache directory, created on first write. An
+    /// existing index there is picked up.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        let dir = dir.into();
+        let index = match std::fs::read_to_string(dir.join(INDEX_FILE)) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!("Ignoring corrupt import index in '{}': {e}", dir.display());
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            dir,
+            index,
+            index_dirty: false,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// The cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Imports served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Imports that had to run their importer so far.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// The processed form of `source`, from the cache if it's up to date,
+    /// otherwise by running `importer` and caching the result.
+    pub fn import(&mut self, source: &Path, importer: &dyn Importer) -> Result<Vec<u8>, ImportError> {
+        let (data, hit) = self.import_inner(source, importer)?;
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        self.save_index();
+        Ok(data)
+    }
+
+    /// Import every texture and sound under `dir`, recursively. Stops at
+    /// the first failure.
+    pub fn preprocess_dir(&mut self, dir: impl AsRef<Path>) -> Result<ImportReport, ImportError> {
+        let mut sources = Vec::new();
+        collect_files(dir.as_ref(), &mut sources)?;
+        sources.sort();
+
+        let mut report = ImportReport::default();
+        for source in sources {
+            let extension = source
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_ascii_lowercase())
+                .unwrap_or_default();
+            let importer: &dyn Importer = if TEXTURE_EXTENSIONS.contains(&extension.as_str()) {
+                &TextureImporter
+            } else if AUDIO_EXTENSIONS.contains(&extension.as_str()) {
+                &AudioImporter
+            } else {
+                continue;
+            };
+            let (_, hit) = self.import_inner(&source, importer)?;
+            if hit {
+                report.cached += 1;
+            } else {
+                report.imported += 1;
+            }
+        }
+        self.save_index();
+        Ok(report)
+    }
+
+    fn import_inner(
+        &mut self,
+        source: &Path,
+        importer: &dyn Importer,
+    ) -> Result<(Vec<u8>, bool), ImportError> {
+        let io = |error| ImportError::Io {
+            path: source.to_path_buf(),
+            error,
+        };
+        let meta = std::fs::metadata(source).map_err(io)?;
+        let modified = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos() as u64);
+        let key = source.to_string_lossy().into_owned();
+
+        // Reuse the hash if the file looks unchanged, otherwise read it.
+        let mut bytes = None;
+        let hash = match self.index.get(&key) {
+            Some(entry) if entry.len == meta.len() && entry.modified == modified => entry.hash,
+            _ => {
+                let data = std::fs::read(source).map_err(io)?;
+                let hash = content_hash(&data);
+                bytes = Some(data);
+                self.index.insert(
+                    key,
+                    IndexEntry {
+                        len: meta.len(),
+                        modified,
+                        hash,
+                    },
+                );
+                self.index_dirty = true;
+                hash
+            }
+        };
+
+        let cached = self.dir.join(format!(
+            "{hash:016x}-{}-v{}.bin",
+            importer.name(),
+            importer.version()
+        ));
+        if let Ok(data) = std::fs::read(&cached) {
+            return Ok((data, true));
+        }
+
+        let source_bytes = match bytes {
+            Some(data) => data,
+            None => std::fs::read(source).map_err(io)?,
+        };
+        let processed = importer.process(&source_bytes).map_err(|message| ImportError::Process {
+            path: source.to_path_buf(),
+            message,
+        })?;
+        let write = std::fs::create_dir_all(&self.dir).and_then(|()| std::fs::write(&cached, &processed));
+        if let Err(e) = write {
+            // Still usable, just not cached.
+            log::warn!("Failed to write import cache '{}': {e}", cached.display());
+        }
+        Ok((processed, false))
+    }
+
+    fn save_index(&mut self) {
+        if !self.index_dirty {
+            return;
+        }
+        let json = serde_json::to_string_pretty(&self.index).expect("index serializes");
+        let path = self.dir.join(INDEX_FILE);
+        match std::fs::create_dir_all(&self.dir).and_then(|()| std::fs::write(&path, json)) {
+            Ok(()) => self.index_dirty = false,
+            Err(e) => log::warn!("Failed to write import index '{}': {e}", path.display()),
+        }
+    }
+}
+
+/// All files under `dir`, recursively.
+fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), ImportError> {
+    let io = |error| ImportError::Io {
+        path: dir.to_path_buf(),
+        error,
+    };
+    for entry in std::fs::read_dir(dir).map_err(io)? {
+        let path = entry.map_err(io)?.path();
+        if path.is_dir() {
+            collect_files(&path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// 64-bit FNV-1a. Stable across runs and Rust versions, unlike `std`'s
+/// hasher, which matters for a cache that outlives the process.
+fn content_hash(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for &byte in data {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+/// Load an image as `(width, height, rgba)`, through the world's
+/// [`ImportCache`] if there is one. Cache failures fall back to decoding the
+/// file directly.
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+pub(crate) fn load_rgba(world: &mut World, path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    if let Some(cache) = world.get_resource_mut::<ImportCache>() {
+        match cache.import(path, &TextureImporter) {
+            Ok(data) => match decode_texture(&data) {
+                Some((width, height, pixels)) => return Ok((width, height, pixels.to_vec())),
+                None => log::warn!("Corrupt import cache entry for '{}'", path.display()),
+            },
+            Err(e) => log::warn!("Import failed, loading directly: {e}"),
+        }
+    }
+    decode_rgba(path)
+}
+
+/// Decode an image file as `(width, height, rgba)`, bypassing the cache.
+/// Needs no World, so async loads call it from loader threads.
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+pub(crate) fn decode_rgba(path: &Path) -> Result<(u32, u32, Vec<u8>), String> {
+    let img = image::open(path).map_err(|e| e.to_string())?.to_rgba8();
+    let (width, height) = img.dimensions();
+    Ok((width, height, img.into_raw()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Upper(AtomicUsize);
+
+    impl Importer for Upper {
+        fn name(&self) -> &'static str {
+            "upper"
+        }
+        fn version(&self) -> u32 {
+            1
+        }
+        fn process(&self, source: &[u8]) -> Result<Vec<u8>, String> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            Ok(source.to_ascii_uppercase())
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("necs-import-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn imports_once_and_again_after_a_change() {
+        let dir = temp_dir("change");
+        let source = dir.join("note.txt");
+        std::fs::write(&source, "hello").unwrap();
+        let importer = Upper(AtomicUsize::new(0));
+
+        let mut cache = ImportCache::new(dir.join("cache"));
+        assert_eq!(cache.import(&source, &importer).unwrap(), b"HELLO");
+        assert_eq!(cache.import(&source, &importer).unwrap(), b"HELLO");
+        assert_eq!(importer.0.load(Ordering::Relaxed), 1);
+
+        // A fresh cache over the same directory reads the index back.
+        let mut reopened = ImportCache::new(dir.join("cache"));
+        reopened.import(&source, &importer).unwrap();
+        assert_eq!((reopened.hits(), reopened.misses()), (1, 0));
+
+        std::fs::write(&source, "changed!").unwrap();
+        assert_eq!(reopened.import(&source, &importer).unwrap(), b"CHANGED!");
+        assert_eq!(importer.0.load(Ordering::Relaxed), 2);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn textures_round_trip_and_preprocess() {
+        let dir = temp_dir("texture");
+        let img = image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 255]));
+        img.save(dir.join("tile.png")).unwrap();
+        std::fs::write(dir.join("readme.txt"), "not a texture").unwrap();
+
+        let mut cache = ImportCache::new(dir.join("cache"));
+        let report = cache.preprocess_dir(&dir).unwrap();
+        assert_eq!(report, ImportReport { imported: 1, cached: 0 });
+        assert_eq!(cache.preprocess_dir(&dir).unwrap().cached, 1);
+
+        let data = cache.import(&dir.join("tile.png"), &TextureImporter).unwrap();
+        let (w, h, pixels) = decode_texture(&data).unwrap();
+        assert_eq!((w, h), (3, 2));
+        assert_eq!(pixels, img.as_raw().as_slice());
+        assert!(decode_texture(&data[..20]).is_none());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn wav_is_resampled_to_the_mixer_rate() {
+        // Stereo at 24 kHz: left ramps up, right stays silent.
+        let pcm: Vec<u8> = [-32768i16, 0, -16384, 0, 0, 0, 16384, 0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let source = wav_file(2, 24_000, &pcm);
+
+        let out = decode_wav(&AudioImporter.process(&source).unwrap()).unwrap();
+        assert_eq!((out.channels, out.sample_rate), (2, AUDIO_SAMPLE_RATE));
+        // Twice the frames, every other one halfway between its neighbors.
+        let left: Vec<f32> = out.samples.iter().step_by(2).copied().collect();
+        let expected = [-1.0, -0.75, -0.5, -0.25, 0.0, 0.25, 0.5, 0.5];
+        assert_eq!(left.len(), expected.len());
+        for (got, want) in left.iter().zip(expected) {
+            assert!((got - want).abs() < 1e-3, "{left:?}");
+        }
+        assert!(out.samples.iter().skip(1).step_by(2).all(|&s| s == 0.0));
+
+        assert!(AudioImporter.process(b"OggS not a wav").is_err());
+    }
+}
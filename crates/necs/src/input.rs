@@ -1,12 +1,125 @@
-//! Keyboard and mouse input state.
+//! Keyboard, mouse, gamepad and touch input state.
 //!
 //! The [`Input`] resource tracks which keys/buttons are currently pressed,
 //! just pressed this frame, or just released this frame.
 //!
-//! Updated by the window event handler each frame.
+//! Gamepads come from the [`GamepadInput`](crate::gamepad::GamepadInput)
+//! plugin (`gamepad` feature), which polls gilrs and merges every connected
+//! controller into one pad here; see [`gamepad`](crate::gamepad) for
+//! per-controller state, hot-plugging and rumble. Another backend can report
+//! buttons with [`InputState::set_gamepad_button`](crate::context::InputState::set_gamepad_button)
+//! and sticks and triggers with [`InputState::set_gamepad_axis`](crate::context::InputState::set_gamepad_axis);
+//! either way they work in action bindings like keys do. The last device the player
+//! pressed something on is the [`InputDevice`] that
+//! [input glyphs](crate::glyph) are chosen for.
+//!
+//! Raw mouse motion, for mouse look with a locked cursor, is summed per
+//! frame in [`InputState::mouse_motion`](crate::context::InputState::mouse_motion);
+//! see [`cursor`](crate::cursor) for grabbing, hiding and warping the cursor.
+//!
+//! Touch screens report fingers as [`InputEvent::Touch`]; see
+//! [`touch`](crate::touch) for multi-finger state, tap/drag/pinch gestures
+//! and the mouse emulation that lets mouse-driven code run on them.
+//!
+//! Window events are not applied as they arrive. They are timestamped into
+//! an [`InputQueue`] and drained right before update systems run, so every
+//! system in a frame sees the same input, sampled as late as possible:
+//!
+//! ```text
+//!   event loop ──► KeyboardInput / MouseInput / CursorMoved / Touch ──► InputQueue
+//!                                                                   │
+//!   frame:  FrameStart ─► asset reloads ─► drain queue ─► update systems
+//!           ─► late latch (LateLatchCursor) ─► render ─► present
+//!                             │                             │
+//!                             └── oldest drained event ─────┴─► InputLatency
+//! ```
+//!
+//! [`InputLatency`] measures the time from the oldest event a frame consumed
+//! to that frame's present — the delay a player feels between pressing a key
+//! and seeing the result. Timestamps are taken when the event loop receives
+//! the event, so OS and display latency come on top.
+//!
+//! ## Per-Frame Events
+//!
+//! Pressed/just-pressed state collapses a frame's input into sets: at 20 FPS
+//! a double-tap inside one frame is a single `just_pressed`, and two keys
+//! pressed 30ms apart look simultaneous. The drained events are also kept,
+//! in order and timestamped, for the rest of the frame:
+//!
+//! ```text
+//!   frame N input queue          InputState
+//!   ┌──────────────────────┐     just_pressed(Space)  = true
+//!   │ t+ 2ms  Space down   │     just_released(Space) = true
+//!   │ t+ 9ms  Space up     │ ──► events() = [Space down @2ms, Space up @9ms,
+//!   │ t+31ms  Space down   │                 Space down @31ms]
+//!   └──────────────────────┘     (two presses, not one)
+//! ```
+//!
+//! Read them with [`InputState::events`](crate::context::InputState::events),
+//! e.g. for rhythm timing, combo inputs or dash double-taps. Gamepad buttons
+//! reported through `set_gamepad_button` are included, stamped when reported.
+//!
+//! ## Scrolling
+//!
+//! A mouse wheel scrolls in notches; a trackpad (or a high-resolution wheel)
+//! sends a stream of small pixel deltas, often several per frame. Rounding
+//! either to whole steps is what makes trackpad zoom feel janky, so both
+//! kinds are kept as floats and summed over the frame in [`MouseScroll`]:
+//!
+//! ```text
+//!   wheel:    Lines(0, 1)                         ──► lines  = (0, 1)
+//!   trackpad: Pixels(0, 3.5) Pixels(-1, 4) …      ──► pixels = (-1, 7.5)
+//!             (x: horizontal, y: vertical, + = up/right)
+//! ```
+//!
+//! Read it with [`InputState::scroll`](crate::context::InputState::scroll).
+//! [`MouseScroll::in_pixels`] folds both into one amount for code that
+//! doesn't care which device it came from:
+//!
+//! ```ignore
+//! let scroll = ctx.input.scroll().in_pixels(24.0);
+//! zoom *= 1.002f32.powf(scroll.y); // smooth on trackpads, 5% per wheel notch
+//! ```
+//!
+//! Each scroll event is also in the frame's [events](#per-frame-events) as
+//! [`InputEvent::Scroll`].
+//!
+//! ## Text
+//!
+//! Key codes say which physical key went down, not what it typed — Shift+2
+//! is `@` or `"` depending on the layout, and IME input (Japanese, Chinese,
+//! Korean) types whole words through a composition window. Typed text
+//! arrives separately as [`TextEvent`]s, read with
+//! [`InputState::text_events`](crate::context::InputState::text_events):
+//!
+//! ```text
+//!   key "a"                 ──► Commit("a")
+//!   IME: k, a, n, j, i      ──► Preedit("か") … Preedit("漢字")   (in progress)
+//!   IME: Enter              ──► Preedit("") , Commit("漢字")
+//! ```
+//!
+//! [`TextInput`](crate::ui::TextInput) handles all of this for UI fields.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The Input System's `InputEventTrace` and `onEvent` expose
+//!   timestamped events; the legacy `Input` class only has per-frame state.
+//! - **Bevy**: `EventReader<KeyboardInput>` alongside `ButtonInput<KeyCode>`
+//!   state, without timestamps.
+//! - **Godot**: `_input(event)` callbacks per event, `Input` singleton for
+//!   state.
+//! - **Our approach**: State for the common case, plus the frame's ordered
+//!   event list with arrival timestamps for the timing-sensitive one.
 
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
 use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::context::InputState;
+use crate::math::Vec2;
+use crate::touch::TouchPhase;
 
 pub use winit::keyboard::KeyCode;
 pub use winit::event::MouseButton;
@@ -60,6 +173,11 @@ impl<T: Eq + Hash + Copy> Input<T> {
         }
     }
 
+    /// Returns `true` if anything was pressed this frame.
+    pub fn any_just_pressed(&self) -> bool {
+        !self.just_pressed.is_empty()
+    }
+
     /// Clear per-frame state. Called at the start of each frame.
     pub(crate) fn clear_just(&mut self) {
         self.just_pressed.clear();
@@ -79,3 +197,443 @@ pub struct CursorPosition {
     pub x: f32,
     pub y: f32,
 }
+
+/// How far one scroll event moved. Positive `y` scrolls up (away from the
+/// user), positive `x` scrolls right.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollDelta {
+    /// Wheel notches. Can be fractional on high-resolution wheels.
+    Lines { x: f32, y: f32 },
+    /// Precise pixel deltas, from trackpads and some mice.
+    Pixels { x: f32, y: f32 },
+}
+
+/// Scrolling accumulated over one frame, kept separately for line and pixel
+/// deltas. See the [module docs](self#scrolling).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MouseScroll {
+    lines: Vec2,
+    pixels: Vec2,
+}
+
+impl MouseScroll {
+    /// Wheel notches scrolled this frame.
+    pub fn lines(&self) -> Vec2 {
+        self.lines
+    }
+
+    /// Pixels scrolled this frame by precise devices.
+    pub fn pixels(&self) -> Vec2 {
+        self.pixels
+    }
+
+    /// Everything scrolled this frame in pixels, counting each wheel notch
+    /// as `pixels_per_line`.
+    pub fn in_pixels(&self, pixels_per_line: f32) -> Vec2 {
+        self.pixels + self.lines * pixels_per_line
+    }
+
+    /// Everything scrolled this frame in lines, counting `pixels_per_line`
+    /// precise pixels as one notch.
+    pub fn in_lines(&self, pixels_per_line: f32) -> Vec2 {
+        self.lines + self.pixels / pixels_per_line
+    }
+
+    /// Whether nothing scrolled this frame.
+    pub fn is_zero(&self) -> bool {
+        self.lines == Vec2::ZERO && self.pixels == Vec2::ZERO
+    }
+
+    pub(crate) fn add(&mut self, delta: ScrollDelta) {
+        match delta {
+            ScrollDelta::Lines { x, y } => self.lines += Vec2::new(x, y),
+            ScrollDelta::Pixels { x, y } => self.pixels += Vec2::new(x, y),
+        }
+    }
+}
+
+// ── Gamepads ─────────────────────────────────────────────────────────────
+
+/// A gamepad button, named by position rather than label: `South` is A on
+/// an Xbox pad and Cross on a PlayStation pad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const ALL: [GamepadButton; 16] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::West,
+        GamepadButton::North,
+        GamepadButton::LeftShoulder,
+        GamepadButton::RightShoulder,
+        GamepadButton::LeftTrigger,
+        GamepadButton::RightTrigger,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::LeftStick,
+        GamepadButton::RightStick,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+    ];
+}
+
+/// A gamepad stick axis or analog trigger. Sticks run from -1 (left, down)
+/// to 1 (right, up); triggers from 0 to 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl GamepadAxis {
+    pub const ALL: [GamepadAxis; 6] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+        GamepadAxis::LeftTrigger,
+        GamepadAxis::RightTrigger,
+    ];
+}
+
+/// The button labels a gamepad uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadStyle {
+    Xbox,
+    PlayStation,
+}
+
+/// The kind of device the player is using, for showing matching button
+/// prompts. See [`InputState::device`](crate::context::InputState::device).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad(GamepadStyle),
+}
+
+// ── Input queue ──────────────────────────────────────────────────────────
+
+/// A single input event. Key events carry key repeats as extra presses.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEvent {
+    KeyPressed(KeyCode),
+    KeyReleased(KeyCode),
+    MousePressed(MouseButton),
+    MouseReleased(MouseButton),
+    GamepadPressed(GamepadButton),
+    GamepadReleased(GamepadButton),
+    /// The cursor moved to this position, in window coordinates.
+    CursorMoved { x: f32, y: f32 },
+    /// The mouse wheel or trackpad scrolled.
+    Scroll(ScrollDelta),
+    /// The mouse moved by this much, in raw device units (x right, y down),
+    /// even with the cursor [locked](crate::cursor::CursorGrab::Locked).
+    MouseMotion { x: f32, y: f32 },
+    /// A finger landed, moved or lifted, in window coordinates. See
+    /// [`touch`](crate::touch).
+    Touch { id: u64, phase: TouchPhase, x: f32, y: f32 },
+}
+
+/// Text typed this frame, separate from [`InputEvent`] because it owns a
+/// string. See the [module docs](self#text).
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextEvent {
+    /// Text to insert: a typed character or a finished IME composition.
+    /// Never contains control characters.
+    Commit(String),
+    /// The IME's composition in progress, to show at the cursor, with the
+    /// IME's cursor as a byte range within it. Empty when it ends.
+    Preedit {
+        text: String,
+        cursor: Option<(usize, usize)>,
+    },
+}
+
+/// A [`TextEvent`] and when the engine received it, comparable with
+/// [`TimedInput::at`] to interleave typing with key presses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedText {
+    pub event: TextEvent,
+    pub at: Instant,
+}
+
+/// An [`InputEvent`] and when the engine received it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimedInput {
+    pub event: InputEvent,
+    pub at: Instant,
+}
+
+/// Input events waiting for the next frame, in arrival order.
+#[derive(Debug, Default)]
+pub(crate) struct InputQueue {
+    events: Vec<TimedInput>,
+    text: Vec<TimedText>,
+}
+
+impl InputQueue {
+    pub fn push(&mut self, event: InputEvent) {
+        self.events.push(TimedInput {
+            event,
+            at: Instant::now(),
+        });
+    }
+
+    pub fn push_text(&mut self, event: TextEvent) {
+        self.text.push(TimedText {
+            event,
+            at: Instant::now(),
+        });
+    }
+
+    /// Apply every queued event in order and append it to the frame's
+    /// [`events`](InputState::events). Returns when the oldest one arrived,
+    /// or `None` if the queue was empty.
+    pub fn drain(&mut self, input: &mut InputState, cursor: &mut CursorPosition) -> Option<Instant> {
+        let oldest = self.events.first().map(|timed| timed.at);
+        input.text.append(&mut self.text);
+        for timed in self.events.drain(..) {
+            apply(input, cursor, timed);
+        }
+        // Gamepad buttons reported earlier this frame may be newer.
+        input.events.sort_by_key(|timed| timed.at);
+        oldest
+    }
+}
+
+/// Apply one event to the input state and record it. A touch is followed by
+/// the mouse events it [emulates](crate::touch#mouse-emulation).
+fn apply(input: &mut InputState, cursor: &mut CursorPosition, timed: TimedInput) {
+    match timed.event {
+        InputEvent::KeyPressed(key) => input.keys.press(key),
+        InputEvent::KeyReleased(key) => input.keys.release(key),
+        InputEvent::MousePressed(button) => input.mouse.press(button),
+        InputEvent::MouseReleased(button) => input.mouse.release(button),
+        InputEvent::GamepadPressed(button) => input.gamepad.press(button),
+        InputEvent::GamepadReleased(button) => input.gamepad.release(button),
+        InputEvent::CursorMoved { x, y } => *cursor = CursorPosition { x, y },
+        InputEvent::Scroll(delta) => input.scroll.add(delta),
+        InputEvent::MouseMotion { x, y } => input.mouse_motion += Vec2::new(x, y),
+        InputEvent::Touch { id, phase, x, y } => {
+            input.events.push(timed);
+            let emulated = input.touches.apply(id, phase, Vec2::new(x, y), timed.at);
+            for event in emulated.into_iter().flatten() {
+                apply(input, cursor, TimedInput { event, at: timed.at });
+            }
+            return;
+        }
+    }
+    input.events.push(timed);
+}
+
+// ── Input latency ────────────────────────────────────────────────────────
+
+/// Number of frames [`InputLatency`] averages over.
+const LATENCY_WINDOW: usize = 120;
+
+/// Measured input-to-present latency over the last 120 frames that consumed
+/// input. Inserted by the framework; see the [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct InputLatency {
+    samples: VecDeque<Duration>,
+}
+
+impl InputLatency {
+    /// The most recent measurement.
+    pub fn last(&self) -> Option<Duration> {
+        self.samples.back().copied()
+    }
+
+    /// Mean over the window, or `None` before the first input.
+    pub fn average(&self) -> Option<Duration> {
+        let n = self.samples.len() as u32;
+        (n > 0).then(|| self.samples.iter().sum::<Duration>() / n)
+    }
+
+    /// Worst case over the window.
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
+
+    pub(crate) fn record(&mut self, latency: Duration) {
+        if self.samples.len() == LATENCY_WINDOW {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(latency);
+    }
+}
+
+// ── Late latch ───────────────────────────────────────────────────────────
+
+/// Snap an entity to the cursor just before rendering.
+///
+/// After update systems run, the entity's `Transform` translation (x and y;
+/// z is kept) is set to the cursor's position under the 2D camera. A
+/// crosshair or drag preview then tracks the freshest cursor sample even if
+/// the systems that move it run on a fixed timestep or are interpolated.
+/// Use it on root entities — the position is written in world space.
+#[cfg(feature = "render2d")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LateLatchCursor;
+
+/// Move every [`LateLatchCursor`] entity to `cursor`, in world space.
+#[cfg(feature = "render2d")]
+pub(crate) fn late_latch_cursor(world: &mut crate::ecs::World, cursor: CursorPosition) {
+    use crate::ecs::GlobalTransform;
+    use crate::math::{Mat4, Transform, Vec3};
+
+    let Some(gpu) = world.get_resource::<crate::render::GpuContext>() else {
+        return;
+    };
+    let (width, height) = gpu.surface_size();
+
+    // Same projection as the sprite renderer: origin at the screen center,
+    // Y up, one world unit per pixel.
+    let mut camera = Mat4::IDENTITY;
+    world.query_single::<(&GlobalTransform,), crate::render2d::Camera2d>(|_, (gt,)| {
+        camera = gt.matrix;
+    });
+    let local = Vec3::new(cursor.x - width as f32 / 2.0, height as f32 / 2.0 - cursor.y, 0.0);
+    let target = camera.transform_point3(local);
+
+    world.query::<(&mut Transform, &LateLatchCursor)>(|_, (transform, _)| {
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_applies_events_in_order_and_reports_oldest() {
+        let mut queue = InputQueue::default();
+        let mut input = InputState::new();
+        let mut cursor = CursorPosition::default();
+        assert_eq!(queue.drain(&mut input, &mut cursor), None);
+
+        queue.push(InputEvent::KeyPressed(KeyCode::Space));
+        let first = queue.events[0].at;
+        queue.push(InputEvent::CursorMoved { x: 10.0, y: 20.0 });
+        queue.push(InputEvent::KeyReleased(KeyCode::Space));
+        queue.push(InputEvent::MousePressed(MouseButton::Left));
+        queue.push(InputEvent::CursorMoved { x: 30.0, y: 40.0 });
+
+        assert_eq!(queue.drain(&mut input, &mut cursor), Some(first));
+        // A tap within one frame is still seen as pressed and released.
+        assert!(input.just_pressed(KeyCode::Space) && input.just_released(KeyCode::Space));
+        assert!(!input.pressed(KeyCode::Space));
+        assert!(input.mouse_pressed(MouseButton::Left));
+        assert_eq!((cursor.x, cursor.y), (30.0, 40.0));
+        assert!(queue.events.is_empty());
+    }
+
+    #[test]
+    fn frame_events_keep_every_press_in_order() {
+        let mut queue = InputQueue::default();
+        let mut input = InputState::new();
+        let mut cursor = CursorPosition::default();
+
+        // Reported from a FrameStart hook, after the key events arrived.
+        queue.push(InputEvent::KeyPressed(KeyCode::Space));
+        queue.push(InputEvent::KeyReleased(KeyCode::Space));
+        std::thread::sleep(Duration::from_millis(1));
+        input.set_gamepad_button(GamepadStyle::Xbox, GamepadButton::South, true);
+        std::thread::sleep(Duration::from_millis(1));
+        queue.push(InputEvent::KeyPressed(KeyCode::Space));
+        queue.drain(&mut input, &mut cursor);
+
+        let events: Vec<InputEvent> = input.events().iter().map(|timed| timed.event).collect();
+        assert_eq!(
+            events,
+            [
+                InputEvent::KeyPressed(KeyCode::Space),
+                InputEvent::KeyReleased(KeyCode::Space),
+                InputEvent::GamepadPressed(GamepadButton::South),
+                InputEvent::KeyPressed(KeyCode::Space),
+            ]
+        );
+        assert!(input.events().windows(2).all(|pair| pair[0].at <= pair[1].at));
+
+        input.clear_just();
+        assert!(input.events().is_empty());
+    }
+
+    #[test]
+    fn scroll_accumulates_lines_and_pixels_separately() {
+        let mut queue = InputQueue::default();
+        let mut input = InputState::new();
+        let mut cursor = CursorPosition::default();
+
+        queue.push(InputEvent::Scroll(ScrollDelta::Lines { x: 0.0, y: 1.0 }));
+        queue.push(InputEvent::Scroll(ScrollDelta::Pixels { x: -1.0, y: 3.5 }));
+        queue.push(InputEvent::Scroll(ScrollDelta::Pixels { x: 0.25, y: 4.0 }));
+        queue.drain(&mut input, &mut cursor);
+
+        let scroll = input.scroll();
+        assert_eq!(scroll.lines(), Vec2::new(0.0, 1.0));
+        assert_eq!(scroll.pixels(), Vec2::new(-0.75, 7.5));
+        assert_eq!(scroll.in_pixels(20.0), Vec2::new(-0.75, 27.5));
+        assert_eq!(scroll.in_lines(10.0), Vec2::new(-0.075, 1.75));
+        assert_eq!(input.events().len(), 3);
+
+        input.clear_just();
+        assert!(input.scroll().is_zero());
+    }
+
+    #[test]
+    fn mouse_motion_sums_over_the_frame() {
+        let mut queue = InputQueue::default();
+        let mut input = InputState::new();
+        let mut cursor = CursorPosition::default();
+
+        queue.push(InputEvent::MouseMotion { x: 3.0, y: -1.0 });
+        queue.push(InputEvent::MouseMotion { x: 2.5, y: 4.0 });
+        queue.drain(&mut input, &mut cursor);
+        assert_eq!(input.mouse_motion(), Vec2::new(5.5, 3.0));
+        // Raw motion doesn't move the cursor.
+        assert_eq!((cursor.x, cursor.y), (0.0, 0.0));
+
+        input.clear_just();
+        assert_eq!(input.mouse_motion(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn latency_window_keeps_recent_samples() {
+        let mut latency = InputLatency::default();
+        assert_eq!(latency.average(), None);
+        for ms in 1..=LATENCY_WINDOW as u64 + 10 {
+            latency.record(Duration::from_millis(ms));
+        }
+        assert_eq!(latency.last(), Some(Duration::from_millis(130)));
+        assert_eq!(latency.max(), Some(Duration::from_millis(130)));
+        // Samples 11..=130 remain.
+        assert_eq!(latency.average(), Some(Duration::from_micros(70_500)));
+    }
+}
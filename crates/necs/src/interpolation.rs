@@ -0,0 +1,218 @@
+//! # Interpolation — Smooth Visuals for Fixed-Timestep Simulation
+//!
+//! Physics runs at a fixed rate (60 Hz by default) while rendering runs at
+//! whatever the display allows. Without help, a body only moves on frames
+//! where a physics step happened, so at 144 Hz rendering you see it hold still
+//! for a frame or two, then jump — visible stutter.
+//!
+//! Attach [`InterpolatedTransform`] to a physics entity and the physics step
+//! records the pose after each fixed step as a `previous`/`current` pair. Every
+//! frame, the entity's [`Transform`] is set to a blend of the two using the
+//! fixed-timestep *alpha* — how far [`Time`](crate::time::Time)'s accumulator
+//! is into the next step.
+//!
+//! ```text
+//!  fixed steps:   |---------|---------|---------|
+//!                 prev     cur   ▲
+//!                                 alpha = accumulator / dt
+//!
+//!  Interpolate:  lerp(prev, cur, alpha)       — always correct, one step behind
+//!  Extrapolate:  cur + (cur - prev) * alpha   — no latency, may overshoot
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Bevy** (`bevy_transform_interpolation`): Separate crate, same
+//!   previous/current scheme driven by `Time<Fixed>::overstep_fraction`.
+//! - **Unity**: `Rigidbody.interpolation` with Interpolate/Extrapolate modes —
+//!   the model this component follows.
+
+use crate::ecs::{Entity, World};
+use crate::math::{Quat, Transform, Vec3};
+
+/// Position and orientation of a body at the end of a fixed step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+impl Pose {
+    pub const IDENTITY: Self = Self {
+        translation: Vec3::ZERO,
+        rotation: Quat::IDENTITY,
+    };
+}
+
+/// How an [`InterpolatedTransform`] fills the gap between fixed steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InterpolationMode {
+    /// Blend between the last two steps. Smooth, one step of latency.
+    #[default]
+    Interpolate,
+    /// Project forward from the last step using the last step's motion.
+    Extrapolate,
+}
+
+/// Component: smooths a physics-driven [`Transform`] between fixed steps.
+///
+/// The physics step owns `previous`/`current`; the entity's `Transform`
+/// translation and rotation are overwritten every frame (scale is left alone).
+#[derive(Debug, Clone)]
+pub struct InterpolatedTransform {
+    pub mode: InterpolationMode,
+    /// Pose after the second-to-last fixed step.
+    pub previous: Pose,
+    /// Pose after the most recent fixed step.
+    pub current: Pose,
+    /// Whether any step has been recorded yet.
+    pub(crate) initialized: bool,
+}
+
+impl InterpolatedTransform {
+    /// Blend between the last two physics steps.
+    pub fn interpolate() -> Self {
+        Self {
+            mode: InterpolationMode::Interpolate,
+            previous: Pose::IDENTITY,
+            current: Pose::IDENTITY,
+            initialized: false,
+        }
+    }
+
+    /// Predict ahead of the last physics step.
+    pub fn extrapolate() -> Self {
+        Self {
+            mode: InterpolationMode::Extrapolate,
+            ..Self::interpolate()
+        }
+    }
+
+    /// Record the pose at the end of a fixed step. The physics plugins call
+    /// this; custom fixed-step code can too.
+    pub fn push(&mut self, pose: Pose) {
+        self.previous = if self.initialized { self.current } else { pose };
+        self.current = pose;
+        self.initialized = true;
+    }
+
+    /// The pose to display at the given fixed-timestep alpha (0..1).
+    pub fn sample(&self, alpha: f32) -> Pose {
+        let alpha = alpha.clamp(0.0, 1.0);
+        match self.mode {
+            InterpolationMode::Interpolate => Pose {
+                translation: self.previous.translation.lerp(self.current.translation, alpha),
+                rotation: self.previous.rotation.slerp(self.current.rotation, alpha),
+            },
+            InterpolationMode::Extrapolate => {
+                let step = self.current.rotation * self.previous.rotation.inverse();
+                Pose {
+                    translation: self.current.translation
+                        + (self.current.translation - self.previous.translation) * alpha,
+                    rotation: (Quat::IDENTITY.slerp(step, alpha) * self.current.rotation)
+                        .normalize(),
+                }
+            }
+        }
+    }
+}
+
+impl Default for InterpolatedTransform {
+    fn default() -> Self {
+        Self::interpolate()
+    }
+}
+
+/// Write the sampled pose of every initialized [`InterpolatedTransform`] into
+/// its `Transform`. With `planar`, only X/Y translation is written so 2D
+/// entities keep their Z layer.
+///
+/// The physics plugins do this for their bodies every frame; call it
+/// yourself when driving [`push`](InterpolatedTransform::push) from a
+/// [`fixed_update`](crate::game::Game::fixed_update) system, passing
+/// [`Time::fixed_alpha`](crate::time::Time::fixed_alpha).
+pub fn apply_interpolation(world: &mut World, alpha: f32, planar: bool) {
+    apply_interpolation_filtered(world, alpha, planar, |_| true);
+}
+
+/// [`apply_interpolation`] for the entities `filter` accepts only, e.g. the
+/// bodies of one physics plugin.
+pub(crate) fn apply_interpolation_filtered(
+    world: &mut World,
+    alpha: f32,
+    planar: bool,
+    filter: impl Fn(Entity) -> bool,
+) {
+    world.query::<(&InterpolatedTransform, &mut Transform)>(|entity, (interp, tf)| {
+        if !interp.initialized || !filter(entity) {
+            return;
+        }
+        let pose = interp.sample(alpha);
+        if planar {
+            tf.translation.x = pose.translation.x;
+            tf.translation.y = pose.translation.y;
+        } else {
+            tf.translation = pose.translation;
+        }
+        tf.rotation = pose.rotation;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pose(x: f32, angle: f32) -> Pose {
+        Pose {
+            translation: Vec3::new(x, 0.0, 0.0),
+            rotation: Quat::from_rotation_z(angle),
+        }
+    }
+
+    fn z_angle(q: Quat) -> f32 {
+        q.to_euler(glam::EulerRot::ZYX).0
+    }
+
+    #[test]
+    fn first_push_fills_both_poses() {
+        let mut interp = InterpolatedTransform::interpolate();
+        interp.push(pose(5.0, 0.0));
+        assert_eq!(interp.previous, interp.current);
+        assert_eq!(interp.sample(0.5).translation.x, 5.0);
+    }
+
+    #[test]
+    fn interpolate_blends_last_two_steps() {
+        let mut interp = InterpolatedTransform::interpolate();
+        interp.push(pose(0.0, 0.0));
+        interp.push(pose(10.0, 1.0));
+        let mid = interp.sample(0.25);
+        assert!((mid.translation.x - 2.5).abs() < 1e-5);
+        assert!((z_angle(mid.rotation) - 0.25).abs() < 1e-5);
+    }
+
+    #[test]
+    fn extrapolate_projects_forward() {
+        let mut interp = InterpolatedTransform::extrapolate();
+        interp.push(pose(0.0, 0.0));
+        interp.push(pose(10.0, 0.5));
+        let ahead = interp.sample(0.5);
+        assert!((ahead.translation.x - 15.0).abs() < 1e-4);
+        assert!((z_angle(ahead.rotation) - 0.75).abs() < 1e-5);
+    }
+
+    #[test]
+    fn apply_respects_planar_and_keeps_scale() {
+        let mut world = World::new();
+        let mut interp = InterpolatedTransform::interpolate();
+        interp.push(pose(0.0, 0.0));
+        interp.push(pose(4.0, 0.0));
+        let e = world.spawn((Transform::from_xyz(0.0, 0.0, 3.0).with_scale(2.0), interp));
+
+        apply_interpolation(&mut world, 0.5, true);
+
+        let tf = world.get::<Transform>(e).unwrap();
+        assert_eq!(tf.translation, Vec3::new(2.0, 0.0, 3.0));
+        assert_eq!(tf.scale, Vec3::splat(2.0));
+    }
+}
@@ -0,0 +1,302 @@
+//! # Launch Options — Engine Flags from the Command Line
+//!
+//! Every game ends up wanting the same handful of startup switches: run in a
+//! smaller window while testing, go fullscreen for a demo, turn vsync off to
+//! measure frame times, jump straight into the level being worked on. Rather
+//! than each project hand-rolling `std::env::args()` parsing for the same
+//! flags, [`Game::new`](crate::game::Game::new) parses them once and inserts
+//! the result as the [`LaunchOptions`] resource.
+//!
+//! ```text
+//!   my_game --window 800x600 --no-vsync --scene level_3 --difficulty hard
+//!           └──────────┬──────────────────────────────┘ └──────┬───────┘
+//!               engine flags → LaunchOptions         unrecognized → extra
+//!
+//!   Game::new ──► LaunchOptions resource ──► window size, fullscreen
+//!                                        ──► surface present mode (vsync)
+//!                                        ──► asset root for load_* paths
+//!                                        ──► diagnostics port
+//!                                        ──► headless loop (no window/GPU)
+//!                                        ──► SceneManager::goto(scene)
+//! ```
+//!
+//! | Flag                   | Effect                                           |
+//! |------------------------|--------------------------------------------------|
+//! | `--width <N>`          | Window width in logical pixels                   |
+//! | `--height <N>`         | Window height in logical pixels                  |
+//! | `--window <W>x<H>`     | Both at once, e.g. `--window 1920x1080`          |
+//! | `--fullscreen`         | Borderless fullscreen on the current monitor     |
+//! | `--no-vsync`/`--vsync` | Present without / with waiting for vblank        |
+//! | `--assets <PATH>`      | Directory relative asset paths are resolved from |
+//! | `--diag-port <PORT>`   | UDP port for `necs-telemetry` (requests on +1)   |
+//! | `--headless`           | No window, no GPU — systems only, at 60 Hz       |
+//! | `--scene <NAME>`       | Start in this [`Scenes`](crate::scene_builder::Scenes) scene |
+//!
+//! Values can also be written `--flag=value`. Anything the engine doesn't
+//! recognize — and everything after a bare `--` — is kept, in order, in
+//! [`LaunchOptions::extra`] so the game can parse its own flags from there:
+//!
+//! ```ignore
+//! fn setup(ctx: &mut Context) {
+//!     let launch = ctx.world.resource::<LaunchOptions>();
+//!     if launch.extra.iter().any(|a| a == "--god-mode") {
+//!         ctx.world.insert_resource(GodMode);
+//!     }
+//! }
+//! ```
+//!
+//! A malformed engine flag (`--width wide`) is logged and the whole command
+//! line falls back to defaults rather than aborting the game. To ignore the
+//! command line entirely — tests, or a launcher that passes its own
+//! settings — replace the resource with
+//! [`Game::launch_options`](crate::game::Game::launch_options).
+//!
+//! Headless mode runs startup, update and shutdown systems without creating
+//! a window or touching the GPU, so it works on a CI machine with no display.
+//! Nothing is rendered and there is no keyboard or mouse input; call
+//! [`Context::exit`](crate::context::Context::exit) to stop.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Built-in player flags (`-screen-width`, `-screen-fullscreen`,
+//!   `-batchmode -nographics`); custom ones come from
+//!   `Environment.GetCommandLineArgs()`.
+//! - **Bevy**: No built-in flags — `WindowPlugin` and `AssetPlugin` are
+//!   configured in code, and projects bring `clap` for the command line.
+//! - **Our approach**: A fixed set of engine flags parsed with no extra
+//!   dependency; game-specific flags pass through untouched.
+
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Window size used when no `--width`/`--height`/`--window` flag is given.
+pub const DEFAULT_WINDOW_SIZE: (u32, u32) = (1280, 720);
+
+/// Port `necs-telemetry` listens on by default. Inspection requests come back
+/// on the next port up.
+pub const DEFAULT_DIAGNOSTICS_PORT: u16 = 9100;
+
+/// Engine options parsed from the command line. Inserted as a resource by
+/// [`Game::new`](crate::game::Game::new). See the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaunchOptions {
+    /// Initial window size in logical pixels.
+    pub window_size: (u32, u32),
+    /// Start in borderless fullscreen.
+    pub fullscreen: bool,
+    /// Wait for vertical blank when presenting.
+    pub vsync: bool,
+    /// Directory that relative asset paths are resolved from. `None` uses
+    /// the working directory.
+    pub asset_root: Option<PathBuf>,
+    /// UDP port diagnostics are sent to (`diagnostics` feature).
+    pub diagnostics_port: u16,
+    /// Run without a window or GPU.
+    pub headless: bool,
+    /// Scene to start in, overriding [`Scenes::start`](crate::scene_builder::Scenes::start).
+    pub scene: Option<String>,
+    /// Arguments the engine did not recognize, in order.
+    pub extra: Vec<String>,
+}
+
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        Self {
+            window_size: DEFAULT_WINDOW_SIZE,
+            fullscreen: false,
+            vsync: true,
+            asset_root: None,
+            diagnostics_port: DEFAULT_DIAGNOSTICS_PORT,
+            headless: false,
+            scene: None,
+            extra: Vec::new(),
+        }
+    }
+}
+
+/// A malformed engine flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LaunchError {
+    /// The flag needs a value but was last on the command line.
+    MissingValue(&'static str),
+    /// The flag's value couldn't be parsed.
+    InvalidValue { flag: &'static str, value: String },
+}
+
+impl fmt::Display for LaunchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LaunchError::MissingValue(flag) => write!(f, "{flag} needs a value"),
+            LaunchError::InvalidValue { flag, value } => {
+                write!(f, "invalid value for {flag}: '{value}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LaunchError {}
+
+impl LaunchOptions {
+    /// Parse the process's command line. A malformed engine flag is logged and
+    /// defaults are used instead.
+    pub fn from_env() -> Self {
+        match Self::parse(std::env::args().skip(1)) {
+            Ok(options) => options,
+            Err(e) => {
+                log::warn!("Ignoring command line: {e}");
+                Self::default()
+            }
+        }
+    }
+
+    /// Parse arguments (without the program name).
+    pub fn parse<I, S>(args: I) -> Result<Self, LaunchError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut options = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                options.extra.extend(args.by_ref());
+                break;
+            }
+
+            // Split `--flag=value` so both spellings share one code path.
+            let (flag, inline) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_string(), Some(value.to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = |flag: &'static str| -> Result<String, LaunchError> {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or(LaunchError::MissingValue(flag))
+            };
+
+            match flag.as_str() {
+                "--width" => options.window_size.0 = parse_dimension("--width", &value("--width")?)?,
+                "--height" => {
+                    options.window_size.1 = parse_dimension("--height", &value("--height")?)?
+                }
+                "--window" => options.window_size = parse_window_size(&value("--window")?)?,
+                "--fullscreen" => options.fullscreen = true,
+                "--vsync" => options.vsync = true,
+                "--no-vsync" => options.vsync = false,
+                "--assets" => options.asset_root = Some(PathBuf::from(value("--assets")?)),
+                "--diag-port" => {
+                    let port = value("--diag-port")?;
+                    options.diagnostics_port = match port.parse::<u16>() {
+                        Ok(p) if p > 0 && p < u16::MAX => p,
+                        _ => return Err(invalid("--diag-port", port)),
+                    };
+                }
+                "--headless" => options.headless = true,
+                "--scene" => options.scene = Some(value("--scene")?),
+                _ => options.extra.push(arg),
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Resolve an asset path against [`asset_root`](Self::asset_root).
+    /// Absolute paths, and all paths when no root is set, are returned as-is.
+    pub fn asset_path<'a>(&self, path: &'a str) -> Cow<'a, str> {
+        match &self.asset_root {
+            Some(root) if !Path::new(path).is_absolute() => {
+                Cow::Owned(root.join(path).to_string_lossy().into_owned())
+            }
+            _ => Cow::Borrowed(path),
+        }
+    }
+}
+
+/// Resolve `path` with the world's [`LaunchOptions`], if any. Used by the
+/// `load_*` functions so `--assets` applies everywhere.
+pub(crate) fn resolve_asset_path<'a>(world: &crate::ecs::World, path: &'a str) -> Cow<'a, str> {
+    match world.get_resource::<LaunchOptions>() {
+        Some(options) => options.asset_path(path),
+        None => Cow::Borrowed(path),
+    }
+}
+
+fn invalid(flag: &'static str, value: String) -> LaunchError {
+    LaunchError::InvalidValue { flag, value }
+}
+
+fn parse_dimension(flag: &'static str, value: &str) -> Result<u32, LaunchError> {
+    match value.parse::<u32>() {
+        Ok(n) if n > 0 => Ok(n),
+        _ => Err(invalid(flag, value.to_string())),
+    }
+}
+
+fn parse_window_size(value: &str) -> Result<(u32, u32), LaunchError> {
+    let (w, h) = value
+        .split_once(['x', 'X'])
+        .ok_or_else(|| invalid("--window", value.to_string()))?;
+    let w = parse_dimension("--window", w).map_err(|_| invalid("--window", value.to_string()))?;
+    let h = parse_dimension("--window", h).map_err(|_| invalid("--window", value.to_string()))?;
+    Ok((w, h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn engine_flags_are_parsed_and_the_rest_kept() {
+        let options = LaunchOptions::parse([
+            "--window", "800x600", "--no-vsync", "--difficulty", "hard", "--scene=level_3",
+            "--headless", "--diag-port", "9200", "--fullscreen", "--", "--width", "5",
+        ])
+        .unwrap();
+
+        assert_eq!(options.window_size, (800, 600));
+        assert!(!options.vsync);
+        assert!(options.headless && options.fullscreen);
+        assert_eq!(options.diagnostics_port, 9200);
+        assert_eq!(options.scene.as_deref(), Some("level_3"));
+        assert_eq!(options.extra, ["--difficulty", "hard", "--width", "5"]);
+    }
+
+    #[test]
+    fn width_and_height_override_one_side() {
+        let options = LaunchOptions::parse(["--height", "480"]).unwrap();
+        assert_eq!(options.window_size, (DEFAULT_WINDOW_SIZE.0, 480));
+        assert_eq!(LaunchOptions::parse(Vec::<String>::new()).unwrap(), LaunchOptions::default());
+    }
+
+    #[test]
+    fn malformed_values_are_errors() {
+        assert_eq!(
+            LaunchOptions::parse(["--scene"]),
+            Err(LaunchError::MissingValue("--scene"))
+        );
+        assert_eq!(
+            LaunchOptions::parse(["--window", "800by600"]),
+            Err(invalid("--window", "800by600".to_string()))
+        );
+        assert_eq!(
+            LaunchOptions::parse(["--width=0"]),
+            Err(invalid("--width", "0".to_string()))
+        );
+    }
+
+    #[test]
+    fn asset_root_applies_to_relative_paths_only() {
+        let options = LaunchOptions::parse(["--assets", "/opt/game/assets"]).unwrap();
+        assert_eq!(
+            options.asset_path("sprites/hero.png"),
+            Path::new("/opt/game/assets").join("sprites/hero.png").to_string_lossy()
+        );
+        assert_eq!(options.asset_path("/tmp/a.png"), "/tmp/a.png");
+        assert_eq!(LaunchOptions::default().asset_path("a.png"), "a.png");
+    }
+}
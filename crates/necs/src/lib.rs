@@ -5,23 +5,54 @@
 //!
 //! Start with `use necs::prelude::*` and build a [`Game`](game::Game).
 
+// Lets `#[derive(Bundle)]` refer to `::necs` from inside this crate too.
+extern crate self as necs;
+
+pub mod action;
 pub mod asset;
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub mod asset_gc;
+pub mod avoidance;
+pub mod clipboard;
+pub mod constraint;
 pub mod context;
+pub mod cursor;
 pub mod ecs;
+pub mod entity_ref;
+pub mod focus;
+pub mod gamepad;
 pub mod game;
+pub mod glyph;
+pub mod hooks;
+pub mod import;
 pub mod input;
+pub mod interpolation;
+pub mod launch;
+pub mod lifecycle;
+pub mod local;
 pub mod math;
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub mod particles;
+pub mod prefab;
 pub mod prelude;
 pub mod render;
 pub mod scene;
 pub mod scene_builder;
+pub mod subsystems;
 pub mod time;
+pub mod touch;
+pub mod transform2d;
+pub mod version;
 pub(crate) mod window;
 
+pub use version::build_info;
+
 #[cfg(feature = "render2d")]
 pub mod animation;
 #[cfg(feature = "render2d")]
 pub mod render2d;
+#[cfg(feature = "render2d")]
+pub mod ui;
 
 #[cfg(feature = "render3d")]
 pub mod render3d;
@@ -29,12 +60,27 @@ pub mod render3d;
 #[cfg(feature = "audio")]
 pub mod audio;
 
+#[cfg(feature = "audio")]
+pub mod audio_graph;
+
+#[cfg(all(feature = "audio", feature = "physics3d"))]
+pub mod audio_occlusion;
+
+#[cfg(feature = "audio")]
+pub mod audio_spatial;
+
+#[cfg(feature = "audio")]
+pub mod music;
+
 #[cfg(feature = "physics2d")]
 pub mod physics2d;
 
 #[cfg(feature = "physics3d")]
 pub mod physics3d;
 
+#[cfg(any(feature = "physics2d", feature = "physics3d"))]
+pub mod trigger;
+
 #[cfg(feature = "diagnostics")]
 pub mod diag;
 
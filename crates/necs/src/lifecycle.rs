@@ -0,0 +1,233 @@
+//! Window lifecycle — focus, minimize, and occlusion tracking.
+//!
+//! The [`WindowLifecycle`] resource is inserted by the framework and updated
+//! from winit window events. Systems can read the current state (focused,
+//! minimized, occluded) or inspect the [`LifecycleEvent`]s that arrived since
+//! the last time update systems ran.
+//!
+//! ## Auto-pause
+//!
+//! Most desktop games want to stop simulating when the player alt-tabs away.
+//! Enable it with [`Game::auto_pause`](crate::game::Game::auto_pause): while
+//! the window is unfocused or minimized, update systems are skipped and audio
+//! (if the `audio` feature is enabled) is muted. Time keeps ticking so the
+//! first frame after resuming does not see a giant delta.
+//!
+//! ```ignore
+//! fn update(ctx: &mut Context) {
+//!     let lifecycle = ctx.world.resource::<WindowLifecycle>();
+//!     if lifecycle.just(LifecycleEvent::FocusLost) {
+//!         // open the pause menu
+//!     }
+//! }
+//! ```
+//!
+//! ## Shutdown
+//!
+//! Exiting — closing the window, [`Context::exit`](crate::context::Context::exit),
+//! or a fatal GPU error — runs a fixed sequence instead of dropping everything
+//! in whatever order the world's hash maps happen to yield:
+//!
+//! ```text
+//!   1. ShutdownRequested resource inserted (with the reason)
+//!   2. shutdown systems ─── Game::shutdown — persist saves here
+//!   3. Shutdown hooks
+//!   4. teardown
+//!        audio engine        stop the mixer thread first
+//!        editor              egui renderer owns GPU resources
+//!        entities, resources renderers and GPU stores
+//!        wait for GPU idle
+//!        GpuContext          device, queue, surface
+//!        window
+//! ```
+//!
+//! Shutdown systems always run with panic catching, so a failing save can't
+//! skip teardown.
+
+/// A change in the window's lifecycle state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    /// The window gained keyboard focus.
+    FocusGained,
+    /// The window lost keyboard focus (e.g. alt-tab).
+    FocusLost,
+    /// The window was minimized (surface size dropped to zero).
+    Minimized,
+    /// The window was restored from a minimized state.
+    Restored,
+    /// The window became fully hidden behind other windows.
+    Occluded,
+    /// The window became visible again after being occluded.
+    Visible,
+}
+
+/// Why the game is shutting down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShutdownReason {
+    /// The window was closed and no close-request hook vetoed it.
+    WindowClosed,
+    /// A system called [`Context::exit`](crate::context::Context::exit).
+    Exit,
+    /// The GPU ran out of memory or the device was lost.
+    GpuError,
+}
+
+/// Resource inserted when shutdown begins, before shutdown systems run.
+/// Its presence means the world is about to be torn down.
+#[derive(Debug, Clone, Copy)]
+pub struct ShutdownRequested {
+    pub reason: ShutdownReason,
+}
+
+/// Resource tracking the window's focus/minimize/occlusion state.
+///
+/// Inserted by the framework. Events are accumulated as they arrive and
+/// cleared after update systems have run, so every event is observed by
+/// systems exactly once — including events that arrived while paused.
+#[derive(Debug, Clone)]
+pub struct WindowLifecycle {
+    focused: bool,
+    minimized: bool,
+    occluded: bool,
+    auto_pause: bool,
+    paused: bool,
+    events: Vec<LifecycleEvent>,
+}
+
+impl WindowLifecycle {
+    pub(crate) fn new() -> Self {
+        Self {
+            focused: true,
+            minimized: false,
+            occluded: false,
+            auto_pause: false,
+            paused: false,
+            events: Vec::new(),
+        }
+    }
+
+    /// Returns `true` if the window currently has keyboard focus.
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// Returns `true` if the window is minimized.
+    pub fn is_minimized(&self) -> bool {
+        self.minimized
+    }
+
+    /// Returns `true` if the window is fully hidden behind other windows.
+    pub fn is_occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns `true` if the game is auto-paused (update systems skipped).
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns `true` if auto-pause is enabled.
+    pub fn auto_pause(&self) -> bool {
+        self.auto_pause
+    }
+
+    /// Enable or disable auto-pause at runtime.
+    pub fn set_auto_pause(&mut self, enabled: bool) {
+        self.auto_pause = enabled;
+    }
+
+    /// Lifecycle events received since update systems last ran, oldest first.
+    pub fn events(&self) -> &[LifecycleEvent] {
+        &self.events
+    }
+
+    /// Returns `true` if `event` was received since update systems last ran.
+    pub fn just(&self, event: LifecycleEvent) -> bool {
+        self.events.contains(&event)
+    }
+
+    /// Returns `true` if the window has nothing to present to (minimized or
+    /// occluded), so rendering can be skipped.
+    pub(crate) fn is_hidden(&self) -> bool {
+        self.minimized || self.occluded
+    }
+
+    /// Record an event. Redundant events (e.g. a second `FocusLost`) are
+    /// dropped so the event list only contains real state transitions.
+    pub(crate) fn push(&mut self, event: LifecycleEvent) {
+        let changed = match event {
+            LifecycleEvent::FocusGained => !std::mem::replace(&mut self.focused, true),
+            LifecycleEvent::FocusLost => std::mem::replace(&mut self.focused, false),
+            LifecycleEvent::Minimized => !std::mem::replace(&mut self.minimized, true),
+            LifecycleEvent::Restored => std::mem::replace(&mut self.minimized, false),
+            LifecycleEvent::Occluded => !std::mem::replace(&mut self.occluded, true),
+            LifecycleEvent::Visible => std::mem::replace(&mut self.occluded, false),
+        };
+        if changed {
+            self.events.push(event);
+        }
+    }
+
+    /// Recompute the auto-pause state. Returns `Some(paused)` when it changed.
+    pub(crate) fn update_pause(&mut self) -> Option<bool> {
+        let paused = self.auto_pause && (!self.focused || self.minimized);
+        if paused == self.paused {
+            return None;
+        }
+        self.paused = paused;
+        Some(paused)
+    }
+
+    /// Clear accumulated events. Called after update systems have run.
+    pub(crate) fn clear_events(&mut self) {
+        self.events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redundant_events_are_dropped() {
+        let mut lc = WindowLifecycle::new();
+        lc.push(LifecycleEvent::FocusGained);
+        lc.push(LifecycleEvent::FocusLost);
+        lc.push(LifecycleEvent::FocusLost);
+        assert_eq!(lc.events(), &[LifecycleEvent::FocusLost]);
+        assert!(!lc.is_focused());
+    }
+
+    #[test]
+    fn auto_pause_off_never_pauses() {
+        let mut lc = WindowLifecycle::new();
+        lc.push(LifecycleEvent::FocusLost);
+        assert_eq!(lc.update_pause(), None);
+        assert!(!lc.is_paused());
+    }
+
+    #[test]
+    fn auto_pause_follows_focus_and_minimize() {
+        let mut lc = WindowLifecycle::new();
+        lc.set_auto_pause(true);
+
+        lc.push(LifecycleEvent::FocusLost);
+        assert_eq!(lc.update_pause(), Some(true));
+        lc.push(LifecycleEvent::Minimized);
+        assert_eq!(lc.update_pause(), None);
+        lc.push(LifecycleEvent::FocusGained);
+        assert_eq!(lc.update_pause(), None, "still minimized");
+        lc.push(LifecycleEvent::Restored);
+        assert_eq!(lc.update_pause(), Some(false));
+    }
+
+    #[test]
+    fn clear_events_keeps_state() {
+        let mut lc = WindowLifecycle::new();
+        lc.push(LifecycleEvent::Occluded);
+        lc.clear_events();
+        assert!(lc.events().is_empty());
+        assert!(lc.is_occluded());
+        assert!(lc.is_hidden());
+    }
+}
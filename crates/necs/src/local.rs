@@ -0,0 +1,157 @@
+//! # System-Local State — Counters and Caches Owned by One System
+//!
+//! A system often needs a little memory of its own: a cooldown timer, a
+//! "frames since the player moved" counter, a cache of last frame's query.
+//! Putting that in a resource works, but the world then carries a type that
+//! only one function ever touches, and two copies of the system would share
+//! it by accident.
+//!
+//! Each update, fixed-update and shutdown system instead gets its own
+//! [`SystemLocal`], swapped into [`Context::local`](crate::context::Context)
+//! while it runs:
+//!
+//! ```text
+//!   run_systems
+//!     ├─ spawn_waves   ctx.local ◄─► spawn_waves' state   { Timer, LastRun }
+//!     ├─ spin          ctx.local ◄─► spin's state          { u32,   LastRun }
+//!     └─ ...
+//! ```
+//!
+//! ```ignore
+//! fn spawn_waves(ctx: &mut Context) {
+//!     // Default-initialized the first time this system asks for it.
+//!     let next_wave = ctx.local.get::<f32>();
+//!     if ctx.time.elapsed_secs() >= *next_wave {
+//!         *next_wave += 10.0;
+//!         ctx.spawn("wave");
+//!     }
+//!     log::debug!("{:?} since the last run", ctx.local.last_run().delta());
+//! }
+//! ```
+//!
+//! State is keyed by type, one value per type per system; wrap values in a
+//! newtype to keep two of the same type. [`LastRun`] says when the system
+//! ran before — which differs from the frame delta whenever it was skipped
+//! (the game paused, the editor stepping). Fixed-update systems can run
+//! several times in one frame: those runs share a frame number and see a
+//! zero delta, so use [`Time::fixed_delta`](crate::time::Time::fixed_delta).
+//!
+//! Startup systems and hooks see a shared scratch state outside any system.
+//! World systems (`Game::world_system`, a plain
+//! [`Schedule`](crate::ecs::system::Schedule)) take `&mut World` only;
+//! capture state in the closure instead.
+//!
+//! ## Comparison
+//!
+//! - **Bevy**: `Local<T>` system parameter, plus `SystemChangeTick` with the
+//!   `last_run` and `this_run` ticks.
+//! - **Unity**: Fields on the `MonoBehaviour` or `SystemBase` instance.
+//! - **Our approach**: Bevy's idea without parameter injection: the state
+//!   rides along in the context, looked up by type.
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// When the running system last ran. See the [module docs](self).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LastRun {
+    frame: Option<u64>,
+    delta: Duration,
+    runs: u64,
+}
+
+impl LastRun {
+    /// Frame number ([`Time::frame_count`](crate::time::Time::frame_count))
+    /// of the previous run; `None` on the first.
+    pub fn frame(&self) -> Option<u64> {
+        self.frame
+    }
+
+    /// Time since the previous run started; zero on the first.
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    /// [`delta`](Self::delta) in seconds.
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    /// How many times the system ran before this run.
+    pub fn runs(&self) -> u64 {
+        self.runs
+    }
+
+    /// Whether this is the system's first run.
+    pub fn is_first(&self) -> bool {
+        self.runs == 0
+    }
+}
+
+/// One system's private state. See the [module docs](self).
+#[derive(Default)]
+pub struct SystemLocal {
+    values: HashMap<TypeId, Box<dyn Any>>,
+    last_run: LastRun,
+    /// Frame and elapsed time of the current (latest) run.
+    this_run: Option<(u64, Duration)>,
+}
+
+impl SystemLocal {
+    /// This system's `T`, default-initialized on first use.
+    pub fn get<T: Default + 'static>(&mut self) -> &mut T {
+        self.values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(T::default()))
+            .downcast_mut()
+            .expect("SystemLocal value stored under the wrong type")
+    }
+
+    /// Drop this system's `T`; the next [`get`](Self::get) starts over.
+    pub fn reset<T: 'static>(&mut self) {
+        self.values.remove(&TypeId::of::<T>());
+    }
+
+    /// When the system last ran.
+    pub fn last_run(&self) -> LastRun {
+        self.last_run
+    }
+
+    /// Record a run starting at `frame`, `elapsed` since startup.
+    pub(crate) fn begin_run(&mut self, frame: u64, elapsed: Duration) {
+        self.last_run = match self.this_run {
+            Some((last_frame, last_elapsed)) => LastRun {
+                frame: Some(last_frame),
+                delta: elapsed.saturating_sub(last_elapsed),
+                runs: self.last_run.runs + 1,
+            },
+            None => LastRun::default(),
+        };
+        self.this_run = Some((frame, elapsed));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_run_spans_skipped_frames() {
+        let mut local = SystemLocal::default();
+        local.begin_run(1, Duration::from_millis(16));
+        assert!(local.last_run().is_first());
+        assert_eq!(local.last_run().frame(), None);
+
+        // Skipped frames 2–4 (paused).
+        local.begin_run(5, Duration::from_millis(80));
+        let last = local.last_run();
+        assert_eq!((last.frame(), last.delta(), last.runs()), (Some(1), Duration::from_millis(64), 1));
+
+        *local.get::<u32>() += 2;
+        *local.get::<u32>() += 1;
+        assert_eq!(*local.get::<u32>(), 3);
+        local.reset::<u32>();
+        assert_eq!(*local.get::<u32>(), 0);
+    }
+}
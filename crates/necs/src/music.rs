@@ -0,0 +1,507 @@
+//! # Music — Playlists and Adaptive Layers
+//!
+//! Background music has two common shapes, and [`MusicController`] handles
+//! both:
+//!
+//! - **Playlists** (horizontal): tracks one after another, optionally
+//!   shuffled, each crossfading into the next a little before it ends.
+//! - **Layers** (vertical): stems of one piece — drums, bass, strings,
+//!   brass — started together and looping, each faded in or out by a
+//!   single *intensity* value the game sets (0 = calm, 1 = all-out).
+//!
+//! ```text
+//!   Playlist  ──► [ track 2 ]────╮
+//!                          ╰──────[ track 0 ]────╮     (crossfade)
+//!                                         ╰──────[ track 1 ] ...
+//!
+//!   Layers    intensity:  0.0        0.5        1.0
+//!     pads     0.0..0.0   ██████████████████████████   always on
+//!     drums    0.2..0.5        ░░▒▒▓▓████████████████
+//!     brass    0.7..1.0                      ░▒▓█████
+//! ```
+//!
+//! A layer is silent at or below its `from` intensity and at full volume
+//! from its `to` intensity, rising linearly in between. Intensity changes
+//! glide over the music's fade time, so setting it straight from gameplay
+//! ("enemies nearby") doesn't cause jumps.
+//!
+//! A playlist and layered music can play at the same time; usually a game
+//! uses one or the other per scene.
+//!
+//! ```ignore
+//! let mut music = ctx.world.resource_mut::<MusicController>();
+//! music.play_playlist(Playlist::new().track(calm).track(town).shuffle());
+//!
+//! music.play_layers(
+//!     AdaptiveMusic::new()
+//!         .layer(pads, 0.0, 0.0)
+//!         .layer(drums, 0.2, 0.5)
+//!         .layer(brass, 0.7, 1.0),
+//! );
+//! music.set_intensity(0.6);
+//! ```
+//!
+//! Stems stay in sync when they have the same length: they start in the
+//! same frame and loop over their whole length.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: No built-in music system; playlists are scripts over
+//!   `AudioSource`s, layering is usually done in FMOD or Wwise.
+//! - **Bevy**: Plays sounds only; crossfades and layering are left to the
+//!   game or third-party crates.
+//! - **Godot**: `AudioStreamPlaylist` (shuffle, fade) and
+//!   `AudioStreamSynchronized` (stems with per-stream volume) resources.
+//! - **Our approach**: Godot's two stream types behind one resource, with
+//!   the stem volumes driven by a single intensity parameter as in FMOD.
+
+use std::time::Duration;
+
+use crate::audio::{AudioEngine, SoundData, SoundHandle};
+use crate::ecs::World;
+
+/// Glide used when the music volume changes.
+const VOLUME_GLIDE: Duration = Duration::from_millis(100);
+
+// ── Playlist ────────────────────────────────────────────────────────────
+
+/// Tracks played in order (or shuffled), crossfading between them.
+#[derive(Debug, Clone)]
+pub struct Playlist {
+    tracks: Vec<SoundData>,
+    shuffle: bool,
+    repeat: bool,
+    crossfade: Duration,
+}
+
+impl Playlist {
+    /// An empty playlist that repeats, with a two-second crossfade.
+    pub fn new() -> Self {
+        Self {
+            tracks: Vec::new(),
+            shuffle: false,
+            repeat: true,
+            crossfade: Duration::from_secs(2),
+        }
+    }
+
+    /// Add a track.
+    pub fn track(mut self, sound: SoundData) -> Self {
+        self.tracks.push(sound);
+        self
+    }
+
+    /// Play in random order, reshuffled on every pass.
+    pub fn shuffle(mut self) -> Self {
+        self.shuffle = true;
+        self
+    }
+
+    /// Stop after the last track instead of starting over.
+    pub fn once(mut self) -> Self {
+        self.repeat = false;
+        self
+    }
+
+    /// How long consecutive tracks overlap. Zero cuts straight over.
+    pub fn crossfade(mut self, crossfade: Duration) -> Self {
+        self.crossfade = crossfade;
+        self
+    }
+}
+
+impl Default for Playlist {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A playlist being played.
+#[derive(Debug)]
+struct PlaylistState {
+    playlist: Playlist,
+    /// Track indices for this pass.
+    order: Vec<usize>,
+    /// Position in `order` of the current (or next to start) track.
+    cursor: usize,
+    /// The playing track and its length.
+    current: Option<(SoundHandle, Duration)>,
+    /// Set when a non-repeating playlist has played its last track.
+    finished: bool,
+    /// xorshift32 state for shuffling.
+    rng: u32,
+}
+
+impl PlaylistState {
+    fn new(playlist: Playlist) -> Self {
+        let rng = seed();
+        let mut state = Self {
+            order: (0..playlist.tracks.len()).collect(),
+            playlist,
+            cursor: 0,
+            current: None,
+            finished: false,
+            rng,
+        };
+        if state.playlist.shuffle {
+            state.order = shuffled(state.order.len(), None, &mut state.rng);
+        }
+        state
+    }
+
+    /// Move to the next track, starting a new pass (or finishing) at the end.
+    fn advance(&mut self) {
+        self.cursor += 1;
+        if self.cursor < self.order.len() {
+            return;
+        }
+        if !self.playlist.repeat {
+            self.finished = true;
+            return;
+        }
+        self.cursor = 0;
+        if self.playlist.shuffle {
+            let last = self.order.last().copied();
+            self.order = shuffled(self.order.len(), last, &mut self.rng);
+        }
+    }
+}
+
+/// A random order of `0..len`, not starting with `avoid_first` when there is
+/// a choice (so a reshuffle doesn't repeat the track that just ended).
+fn shuffled(len: usize, avoid_first: Option<usize>, rng: &mut u32) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    for i in (1..len).rev() {
+        let j = next_random(rng) as usize % (i + 1);
+        order.swap(i, j);
+    }
+    if len > 1 && order.first() == avoid_first.as_ref() {
+        let j = 1 + next_random(rng) as usize % (len - 1);
+        order.swap(0, j);
+    }
+    order
+}
+
+fn next_random(state: &mut u32) -> u32 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *state = x;
+    x
+}
+
+/// A shuffle seed that differs between runs.
+fn seed() -> u32 {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    nanos.max(1)
+}
+
+// ── Layers ──────────────────────────────────────────────────────────────
+
+/// One stem of [`AdaptiveMusic`].
+#[derive(Debug, Clone)]
+pub struct MusicLayer {
+    pub sound: SoundData,
+    /// Intensity at or below which the layer is silent.
+    pub from: f32,
+    /// Intensity from which the layer plays at full volume.
+    pub to: f32,
+}
+
+impl MusicLayer {
+    /// Volume (0..=1) at `intensity`.
+    pub fn gain(&self, intensity: f32) -> f32 {
+        if intensity >= self.to {
+            1.0
+        } else if intensity <= self.from {
+            0.0
+        } else {
+            (intensity - self.from) / (self.to - self.from)
+        }
+    }
+}
+
+/// Stems of one piece, mixed by intensity.
+#[derive(Debug, Clone)]
+pub struct AdaptiveMusic {
+    layers: Vec<MusicLayer>,
+    fade: Duration,
+}
+
+impl AdaptiveMusic {
+    /// No layers yet; intensity changes glide over one second.
+    pub fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            fade: Duration::from_secs(1),
+        }
+    }
+
+    /// Add a stem, silent up to intensity `from` and full from `to`. Use
+    /// `0.0, 0.0` for a layer that always plays.
+    pub fn layer(mut self, sound: SoundData, from: f32, to: f32) -> Self {
+        self.layers.push(MusicLayer { sound, from, to });
+        self
+    }
+
+    /// How long layers take to fade in and out.
+    pub fn fade(mut self, fade: Duration) -> Self {
+        self.fade = fade;
+        self
+    }
+}
+
+impl Default for AdaptiveMusic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Layered music being played.
+#[derive(Debug)]
+struct LayerState {
+    music: AdaptiveMusic,
+    /// One per layer once started.
+    handles: Vec<SoundHandle>,
+    /// Volume last sent to each handle.
+    applied: Vec<f32>,
+}
+
+// ── MusicController ─────────────────────────────────────────────────────
+
+/// Resource: the game's music. Inserted by the [`Audio`](crate::audio::Audio)
+/// plugin; the music system starts, crossfades and mixes the sounds each
+/// frame.
+#[derive(Debug)]
+pub struct MusicController {
+    playlist: Option<PlaylistState>,
+    layers: Option<LayerState>,
+    intensity: f32,
+    volume: f32,
+    /// Set when the volume changed and playing sounds need it.
+    volume_changed: bool,
+}
+
+impl MusicController {
+    pub fn new() -> Self {
+        Self {
+            playlist: None,
+            layers: None,
+            intensity: 0.0,
+            volume: 1.0,
+            volume_changed: false,
+        }
+    }
+
+    /// Start a playlist, crossfading out of the current one.
+    pub fn play_playlist(&mut self, playlist: Playlist) {
+        let fade = playlist.crossfade;
+        self.stop_playlist(fade);
+        if playlist.tracks.is_empty() {
+            log::warn!("MusicController: playlist has no tracks");
+            return;
+        }
+        self.playlist = Some(PlaylistState::new(playlist));
+    }
+
+    /// Crossfade to the next track of the playlist now.
+    pub fn skip(&mut self) {
+        let Some(state) = &mut self.playlist else {
+            return;
+        };
+        if let Some((mut handle, _)) = state.current.take() {
+            handle.fade_out(state.playlist.crossfade);
+            state.advance();
+        }
+    }
+
+    /// Index (in the order tracks were added) of the playing track.
+    pub fn current_track(&self) -> Option<usize> {
+        let state = self.playlist.as_ref()?;
+        state.current.as_ref()?;
+        state.order.get(state.cursor).copied()
+    }
+
+    /// Fade out the playlist over `fade`.
+    pub fn stop_playlist(&mut self, fade: Duration) {
+        if let Some(state) = self.playlist.take()
+            && let Some((mut handle, _)) = state.current
+        {
+            handle.fade_out(fade);
+        }
+    }
+
+    /// Start layered music, fading out any that is playing.
+    pub fn play_layers(&mut self, music: AdaptiveMusic) {
+        let fade = music.fade;
+        self.stop_layers(fade);
+        self.layers = Some(LayerState {
+            music,
+            handles: Vec::new(),
+            applied: Vec::new(),
+        });
+    }
+
+    /// Fade out the layered music over `fade`.
+    pub fn stop_layers(&mut self, fade: Duration) {
+        if let Some(state) = self.layers.take() {
+            for mut handle in state.handles {
+                handle.fade_out(fade);
+            }
+        }
+    }
+
+    /// Fade out everything over `fade`.
+    pub fn stop(&mut self, fade: Duration) {
+        self.stop_playlist(fade);
+        self.stop_layers(fade);
+    }
+
+    /// Set the game intensity (clamped to 0..=1) that mixes the layers.
+    pub fn set_intensity(&mut self, intensity: f32) {
+        self.intensity = intensity.clamp(0.0, 1.0);
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// Set the music volume (amplitude scale, 1.0 = full), on top of the
+    /// engine's main volume.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.max(0.0);
+        self.volume_changed = true;
+    }
+
+    pub fn volume(&self) -> f32 {
+        self.volume
+    }
+}
+
+impl Default for MusicController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ── System ──────────────────────────────────────────────────────────────
+
+/// Start, crossfade and mix music. Extracts [`AudioEngine`] and
+/// [`MusicController`] and reinserts them.
+pub(crate) fn music_system(world: &mut World) {
+    let Some(mut music) = world.resource_remove::<MusicController>() else {
+        return;
+    };
+    if let Some(mut engine) = world.resource_remove::<AudioEngine>() {
+        update_playlist(&mut music, &mut engine);
+        update_layers(&mut music, &mut engine);
+        music.volume_changed = false;
+        world.insert_resource(engine);
+    }
+    world.insert_resource(music);
+}
+
+fn update_playlist(music: &mut MusicController, engine: &mut AudioEngine) {
+    let volume = music.volume as f64;
+    let Some(state) = &mut music.playlist else {
+        return;
+    };
+    let crossfade = state.playlist.crossfade;
+
+    if let Some((handle, length)) = &mut state.current {
+        let remaining = length.as_secs_f64() - handle.position();
+        if handle.is_stopped() || remaining <= crossfade.as_secs_f64() {
+            handle.fade_out(crossfade);
+            state.current = None;
+            state.advance();
+        } else if music.volume_changed {
+            handle.fade_to(volume, VOLUME_GLIDE);
+        }
+    }
+
+    if state.current.is_none() && !state.finished {
+        let track = &state.playlist.tracks[state.order[state.cursor]];
+        // Start silent and fade in, overlapping the outgoing track.
+        match engine.try_play(&track.clone().volume(0.0)) {
+            Ok(mut handle) => {
+                handle.fade_to(volume, crossfade);
+                state.current = Some((handle, track.duration()));
+            }
+            Err(e) => {
+                log::warn!("Music track failed to play: {e}");
+                state.advance();
+            }
+        }
+    }
+
+    if state.finished && state.current.is_none() {
+        music.playlist = None;
+    }
+}
+
+fn update_layers(music: &mut MusicController, engine: &mut AudioEngine) {
+    let (intensity, volume) = (music.intensity, music.volume);
+    let Some(state) = &mut music.layers else {
+        return;
+    };
+
+    // Start every stem in the same frame so they stay aligned.
+    if state.handles.is_empty() && !state.music.layers.is_empty() {
+        let mut failed = false;
+        for layer in &state.music.layers {
+            let gain = layer.gain(intensity) * volume;
+            match engine.try_play(&layer.sound.clone().looping().volume(gain as f64)) {
+                Ok(handle) => {
+                    state.handles.push(handle);
+                    state.applied.push(gain);
+                }
+                Err(e) => {
+                    log::warn!("Music layer failed to play: {e}");
+                    failed = true;
+                    break;
+                }
+            }
+        }
+        // Half a mix is worse than none.
+        if failed {
+            music.stop_layers(Duration::ZERO);
+        }
+        return;
+    }
+
+    for ((layer, handle), applied) in state
+        .music
+        .layers
+        .iter()
+        .zip(&mut state.handles)
+        .zip(&mut state.applied)
+    {
+        let gain = layer.gain(intensity) * volume;
+        if (gain - *applied).abs() > 1e-3 {
+            handle.fade_to(gain as f64, state.music.fade);
+            *applied = gain;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shuffles_are_permutations_that_avoid_a_repeat() {
+        let mut rng = 12345;
+        for _ in 0..50 {
+            let order = shuffled(4, Some(2), &mut rng);
+            let mut sorted = order.clone();
+            sorted.sort();
+            assert_eq!(sorted, [0, 1, 2, 3]);
+            assert_ne!(order[0], 2);
+        }
+        assert_eq!(shuffled(1, Some(0), &mut rng), [0]);
+        assert!(shuffled(0, None, &mut rng).is_empty());
+    }
+}
@@ -0,0 +1,651 @@
+//! # Particles — Sparks, Smoke and Dust from an Emitter
+//!
+//! A [`ParticleEmitter`] is a component that owns a small pool of short-lived
+//! particles. Each frame the emitter spawns new particles at its position,
+//! moves the live ones, and drops those that have outlived their lifetime.
+//! Everything a particle looks like — speed, size, color — is a
+//! [`ParticleCurve`] sampled by its *life fraction*: 0 at spawn, 1 at death.
+//!
+//! ```text
+//!   emitter (GlobalTransform)
+//!      │ spawn: rate × dt, or `count` at once (burst)
+//!      ▼
+//!   ● ● ●  velocity ± variance, lifetime ± variance
+//!      │ each frame: velocity += gravity × dt
+//!      │             position += velocity × speed_over_life(t) × dt
+//!      ▼
+//!   size_over_life(t), color_over_life(t) ──► one quad per particle
+//!                                             t = age / lifetime
+//! ```
+//!
+//! ## Rendering
+//!
+//! Particles are drawn by whichever scene is active. In a 2D scene each
+//! particle is a sprite quad, merged into the sprite batches at the
+//! emitter's Z (set [`texture`](ParticleEmitter::texture) to draw an
+//! image). In a 3D scene each particle is a camera-facing billboard, sorted
+//! far → near and alpha-blended after the opaque meshes (set
+//! [`texture_3d`](ParticleEmitter::texture_3d)). Sizes are in world units
+//! either way — pixels in a typical 2D scene, meters in 3D.
+//!
+//! Particles live in world space: moving the emitter moves where new ones
+//! spawn, not the ones already flying. `velocity` and `spawn_extent` turn
+//! with the emitter's rotation, so a rotated thruster sprays sideways.
+//!
+//! ## Control and Scenes
+//!
+//! [`pause`](ParticleEmitter::pause) freezes the emitter and its particles in
+//! place; [`restart`](ParticleEmitter::restart) clears them and starts over,
+//! firing a burst again. The emitter's settings are serializable — register
+//! it with a [`SceneRegistry`](crate::scene::SceneRegistry) to save it in
+//! scenes. Live particles and texture handles are not saved; a loaded
+//! emitter starts empty, and textures are set again after loading.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The Shuriken `ParticleSystem` — modules like "Size over
+//!   Lifetime" and "Color over Lifetime" are the model for the curves here.
+//! - **Godot**: `CPUParticles2D`/`CPUParticles3D` (CPU) and
+//!   `GPUParticles*` (compute); curves are `Curve` and `Gradient` resources.
+//! - **Bevy**: No built-in particles; `bevy_hanabi` simulates on the GPU.
+//! - **Our approach**: CPU simulation with piecewise-linear curves. Fine for
+//!   thousands of particles; the GPU approach scales further but can't be
+//!   read back by gameplay code.
+
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::hierarchy::GlobalTransform;
+use crate::ecs::{Entity, World};
+use crate::math::{Quat, Vec3};
+#[cfg(feature = "render2d")]
+use crate::render2d::texture::TextureHandle;
+#[cfg(feature = "render3d")]
+use crate::render3d::TextureHandle3d;
+
+/// Shortest lifetime a particle can get after variance is applied.
+const MIN_LIFETIME: f32 = 1e-3;
+
+// ── Curves ──────────────────────────────────────────────────────────────
+
+/// A value a [`ParticleCurve`] can interpolate.
+pub trait CurveValue: Copy + Default {
+    /// Blend from `a` (at `t = 0`) to `b` (at `t = 1`).
+    fn lerp(a: Self, b: Self, t: f32) -> Self;
+}
+
+impl CurveValue for f32 {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+impl CurveValue for [f32; 4] {
+    fn lerp(a: Self, b: Self, t: f32) -> Self {
+        std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+    }
+}
+
+/// A value over a particle's life: keys at life fractions `0.0..=1.0`,
+/// linearly interpolated, and held flat before the first key and after the
+/// last.
+///
+/// ```ignore
+/// // Grow quickly, then shrink away.
+/// let size = ParticleCurve::linear(2.0, 0.0).key(0.2, 6.0);
+/// // Yellow → red → transparent (linear RGBA).
+/// let color = ParticleCurve::linear([1.0, 1.0, 0.2, 1.0], [0.5, 0.0, 0.0, 0.0])
+///     .key(0.5, [1.0, 0.2, 0.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticleCurve<T> {
+    /// `(life fraction, value)`, sorted by life fraction.
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: CurveValue> ParticleCurve<T> {
+    /// The same value for the whole life.
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    /// `start` at spawn, blending to `end` at death.
+    pub fn linear(start: T, end: T) -> Self {
+        Self {
+            keys: vec![(0.0, start), (1.0, end)],
+        }
+    }
+
+    /// Add a key at life fraction `t`, clamped to `0.0..=1.0` (builder
+    /// pattern).
+    pub fn key(mut self, t: f32, value: T) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let at = self.keys.partition_point(|&(key, _)| key <= t);
+        self.keys.insert(at, (t, value));
+        self
+    }
+
+    /// The keys, sorted by life fraction.
+    pub fn keys(&self) -> &[(f32, T)] {
+        &self.keys
+    }
+
+    /// The value at life fraction `t`. A curve without keys (e.g. from a
+    /// hand-edited scene) samples as the type's default.
+    pub fn sample(&self, t: f32) -> T {
+        let next = self.keys.partition_point(|&(key, _)| key <= t);
+        if next == 0 {
+            return self.keys.first().map_or_else(T::default, |&(_, value)| value);
+        }
+        let (t0, a) = self.keys[next - 1];
+        let Some(&(t1, b)) = self.keys.get(next) else {
+            return a;
+        };
+        T::lerp(a, b, (t - t0) / (t1 - t0))
+    }
+}
+
+// ── Emitter ─────────────────────────────────────────────────────────────
+
+/// When a [`ParticleEmitter`] spawns particles.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Emission {
+    /// A steady stream of `rate` particles per second.
+    Continuous { rate: f32 },
+    /// `count` particles at once, then again every `interval` seconds, or
+    /// only once when `interval` is `None`.
+    Burst { count: u32, interval: Option<f32> },
+}
+
+/// One live particle, in world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    pub position: Vec3,
+    pub velocity: Vec3,
+    /// Seconds since spawn.
+    pub age: f32,
+    /// Seconds from spawn to death.
+    pub lifetime: f32,
+}
+
+impl Particle {
+    /// How far through its life the particle is: 0 at spawn, 1 at death.
+    pub fn life_fraction(&self) -> f32 {
+        (self.age / self.lifetime).clamp(0.0, 1.0)
+    }
+}
+
+/// Runtime state: never saved, so a loaded emitter starts empty.
+#[derive(Debug, Clone, Default)]
+struct EmitterState {
+    particles: Vec<Particle>,
+    /// Fractional particles owed by a continuous emitter.
+    owed: f32,
+    /// Seconds until the next repeating burst.
+    burst_timer: f32,
+    bursts_fired: u32,
+    /// xorshift32 state; 0 until the first step seeds it.
+    rng: u32,
+}
+
+impl EmitterState {
+    /// Uniform in `[0, 1)`.
+    fn random(&mut self) -> f32 {
+        let mut x = self.rng;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.rng = x;
+        (x >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    /// Uniform in `[-1, 1)`.
+    fn signed(&mut self) -> f32 {
+        self.random() * 2.0 - 1.0
+    }
+
+    /// A random point in the box `-extent..extent`.
+    fn in_box(&mut self, extent: Vec3) -> Vec3 {
+        extent * Vec3::new(self.signed(), self.signed(), self.signed())
+    }
+}
+
+/// Component: spawns, moves and draws particles at the entity's position.
+/// Pair with [`Transform`](crate::math::Transform). See the
+/// [module docs](self).
+///
+/// ```ignore
+/// // A one-shot explosion in a 2D scene.
+/// ctx.world.spawn((
+///     Transform::from_xyz(0.0, 0.0, 5.0),
+///     ParticleEmitter::new()
+///         .burst(80)
+///         .lifetime(0.8)
+///         .velocity(Vec3::ZERO)
+///         .velocity_variance(Vec3::new(300.0, 300.0, 0.0))
+///         .gravity(Vec3::new(0.0, -600.0, 0.0))
+///         .size_over_life(ParticleCurve::linear(8.0, 2.0))
+///         .color_over_life(ParticleCurve::linear([1.0, 0.8, 0.2, 1.0], [1.0, 0.1, 0.0, 0.0])),
+/// ));
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParticleEmitter {
+    pub emission: Emission,
+    /// Seconds each particle lives.
+    pub lifetime: f32,
+    /// Random spread of `lifetime` as a fraction, e.g. 0.2 for ±20%.
+    pub lifetime_variance: f32,
+    /// Initial velocity, in the emitter's local orientation.
+    pub velocity: Vec3,
+    /// Random spread added to `velocity` per axis (±).
+    pub velocity_variance: Vec3,
+    /// Half-size of the box particles spawn in, around the emitter.
+    pub spawn_extent: Vec3,
+    /// World-space acceleration applied to every particle.
+    pub gravity: Vec3,
+    /// Multiplier on each particle's velocity over its life.
+    pub speed_over_life: ParticleCurve<f32>,
+    /// Quad size in world units over each particle's life.
+    pub size_over_life: ParticleCurve<f32>,
+    /// Linear RGBA tint over each particle's life.
+    pub color_over_life: ParticleCurve<[f32; 4]>,
+    /// Live particles are capped here; spawns past the cap are dropped.
+    pub max_particles: usize,
+    /// Freeze spawning and movement. See [`pause`](Self::pause).
+    pub paused: bool,
+    /// Image for 2D particles. `None` draws plain colored quads.
+    #[cfg(feature = "render2d")]
+    #[serde(skip)]
+    pub texture: Option<TextureHandle>,
+    /// Image for 3D billboard particles. `None` draws plain colored quads.
+    #[cfg(feature = "render3d")]
+    #[serde(skip)]
+    pub texture_3d: Option<TextureHandle3d>,
+    #[serde(skip)]
+    state: EmitterState,
+}
+
+impl Default for ParticleEmitter {
+    fn default() -> Self {
+        Self {
+            emission: Emission::Continuous { rate: 20.0 },
+            lifetime: 1.0,
+            lifetime_variance: 0.0,
+            velocity: Vec3::Y,
+            velocity_variance: Vec3::ZERO,
+            spawn_extent: Vec3::ZERO,
+            gravity: Vec3::ZERO,
+            speed_over_life: ParticleCurve::constant(1.0),
+            size_over_life: ParticleCurve::constant(1.0),
+            color_over_life: ParticleCurve::constant([1.0; 4]),
+            max_particles: 1000,
+            paused: false,
+            #[cfg(feature = "render2d")]
+            texture: None,
+            #[cfg(feature = "render3d")]
+            texture_3d: None,
+            state: EmitterState::default(),
+        }
+    }
+}
+
+impl ParticleEmitter {
+    /// A continuous emitter: 20 white particles per second, one unit wide,
+    /// rising at one unit per second for one second.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emit `rate` particles per second (builder pattern).
+    pub fn rate(mut self, rate: f32) -> Self {
+        self.emission = Emission::Continuous { rate };
+        self
+    }
+
+    /// Emit `count` particles once (builder pattern).
+    pub fn burst(mut self, count: u32) -> Self {
+        self.emission = Emission::Burst { count, interval: None };
+        self
+    }
+
+    /// Emit `count` particles every `interval` seconds (builder pattern).
+    pub fn burst_every(mut self, count: u32, interval: f32) -> Self {
+        self.emission = Emission::Burst {
+            count,
+            interval: Some(interval),
+        };
+        self
+    }
+
+    /// Set the particle lifetime in seconds (builder pattern).
+    pub fn lifetime(mut self, secs: f32) -> Self {
+        self.lifetime = secs;
+        self
+    }
+
+    /// Randomize lifetimes by ± this fraction (builder pattern).
+    pub fn lifetime_variance(mut self, fraction: f32) -> Self {
+        self.lifetime_variance = fraction;
+        self
+    }
+
+    /// Set the initial velocity (builder pattern).
+    pub fn velocity(mut self, velocity: Vec3) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Randomize initial velocities by ± this much per axis (builder pattern).
+    pub fn velocity_variance(mut self, variance: Vec3) -> Self {
+        self.velocity_variance = variance;
+        self
+    }
+
+    /// Spawn within a box of this half-size (builder pattern).
+    pub fn spawn_extent(mut self, extent: Vec3) -> Self {
+        self.spawn_extent = extent;
+        self
+    }
+
+    /// Set the world-space acceleration (builder pattern).
+    pub fn gravity(mut self, gravity: Vec3) -> Self {
+        self.gravity = gravity;
+        self
+    }
+
+    /// Scale velocity over each particle's life (builder pattern).
+    pub fn speed_over_life(mut self, curve: ParticleCurve<f32>) -> Self {
+        self.speed_over_life = curve;
+        self
+    }
+
+    /// Set the size over each particle's life (builder pattern).
+    pub fn size_over_life(mut self, curve: ParticleCurve<f32>) -> Self {
+        self.size_over_life = curve;
+        self
+    }
+
+    /// Set the color over each particle's life (builder pattern).
+    pub fn color_over_life(mut self, curve: ParticleCurve<[f32; 4]>) -> Self {
+        self.color_over_life = curve;
+        self
+    }
+
+    /// Cap the number of live particles (builder pattern).
+    pub fn max_particles(mut self, max: usize) -> Self {
+        self.max_particles = max;
+        self
+    }
+
+    /// Draw 2D particles with this image (builder pattern).
+    #[cfg(feature = "render2d")]
+    pub fn texture(mut self, texture: TextureHandle) -> Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// Draw 3D particles with this image (builder pattern).
+    #[cfg(feature = "render3d")]
+    pub fn texture_3d(mut self, texture: TextureHandle3d) -> Self {
+        self.texture_3d = Some(texture);
+        self
+    }
+
+    /// Freeze the emitter: nothing spawns, and live particles hold still
+    /// (they stay visible).
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continue after [`pause`](Self::pause).
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Clear every particle and start over, as if just spawned. A burst
+    /// emitter fires again.
+    pub fn restart(&mut self) {
+        let rng = self.state.rng;
+        self.state = EmitterState {
+            rng,
+            ..Default::default()
+        };
+    }
+
+    /// The live particles.
+    pub fn particles(&self) -> &[Particle] {
+        &self.state.particles
+    }
+
+    /// Returns `true` once a one-shot burst has fired and all its particles
+    /// have died — despawn the entity or [`restart`](Self::restart) it.
+    pub fn is_finished(&self) -> bool {
+        matches!(self.emission, Emission::Burst { interval: None, .. })
+            && self.state.bursts_fired > 0
+            && self.state.particles.is_empty()
+    }
+
+    /// Each live particle's size and color for this frame.
+    pub(crate) fn quads(&self) -> impl Iterator<Item = ParticleQuad> + '_ {
+        self.state.particles.iter().map(|particle| {
+            let t = particle.life_fraction();
+            ParticleQuad {
+                position: particle.position,
+                size: self.size_over_life.sample(t),
+                color: self.color_over_life.sample(t),
+            }
+        })
+    }
+
+    /// Advance `dt` seconds: move and expire live particles, then spawn new
+    /// ones at `origin`. `seed` seeds the random stream on the first step.
+    fn step(&mut self, origin: Vec3, rotation: Quat, dt: f32, seed: u32) {
+        if self.paused || dt <= 0.0 {
+            return;
+        }
+        let state = &mut self.state;
+        if state.rng == 0 {
+            state.rng = seed.max(1);
+        }
+
+        let (gravity, speed) = (self.gravity, &self.speed_over_life);
+        state.particles.retain_mut(|particle| {
+            particle.age += dt;
+            if particle.age >= particle.lifetime {
+                return false;
+            }
+            particle.velocity += gravity * dt;
+            particle.position += particle.velocity * speed.sample(particle.life_fraction()) * dt;
+            true
+        });
+
+        let due = match self.emission {
+            Emission::Continuous { rate } => {
+                state.owed += rate.max(0.0) * dt;
+                let whole = state.owed.floor();
+                state.owed -= whole;
+                whole as usize
+            }
+            Emission::Burst { count, interval } => {
+                state.burst_timer -= dt;
+                let repeat = interval.is_some_and(|secs| secs > 0.0) && state.burst_timer <= 0.0;
+                if state.bursts_fired == 0 || repeat {
+                    state.bursts_fired += 1;
+                    state.burst_timer = interval.unwrap_or(0.0);
+                    count as usize
+                } else {
+                    0
+                }
+            }
+        };
+
+        let room = self.max_particles.saturating_sub(state.particles.len());
+        for _ in 0..due.min(room) {
+            let offset = state.in_box(self.spawn_extent);
+            let velocity = self.velocity + state.in_box(self.velocity_variance);
+            let lifetime = self.lifetime * (1.0 + self.lifetime_variance * state.signed());
+            state.particles.push(Particle {
+                position: origin + rotation * offset,
+                velocity: rotation * velocity,
+                age: 0.0,
+                lifetime: lifetime.max(MIN_LIFETIME),
+            });
+        }
+    }
+}
+
+/// One particle as the renderers draw it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ParticleQuad {
+    pub position: Vec3,
+    pub size: f32,
+    pub color: [f32; 4],
+}
+
+// ── System ──────────────────────────────────────────────────────────────
+
+/// Advance every [`ParticleEmitter`] by `dt` seconds. Runs automatically
+/// each frame after transform propagation (not while the game is paused).
+pub fn update_particles(world: &mut World, dt: f32) {
+    if !world.has_component_type::<ParticleEmitter>() {
+        return;
+    }
+    world.query::<(&GlobalTransform, &mut ParticleEmitter)>(|entity, (gt, emitter)| {
+        let (_, rotation, origin) = gt.matrix.to_scale_rotation_translation();
+        emitter.step(origin, rotation, dt, seed(entity));
+    });
+}
+
+/// A per-entity seed, so neighbouring emitters don't move in lockstep.
+fn seed(entity: Entity) -> u32 {
+    (entity.index() + 1).wrapping_mul(0x9E37_79B9)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Mat4;
+
+    fn run(emitter: &mut ParticleEmitter, frames: usize, dt: f32) {
+        for _ in 0..frames {
+            emitter.step(Vec3::ZERO, Quat::IDENTITY, dt, 7);
+        }
+    }
+
+    #[test]
+    fn curves_interpolate_between_keys_and_hold_at_the_ends() {
+        let curve = ParticleCurve::linear(0.0, 10.0).key(0.5, 2.0);
+        assert_eq!(curve.keys().len(), 3);
+        assert_eq!(curve.sample(0.25), 1.0);
+        assert_eq!(curve.sample(0.75), 6.0);
+        assert_eq!(curve.sample(1.5), 10.0);
+
+        let late = ParticleCurve::constant(3.0).key(0.5, 5.0);
+        assert_eq!(late.sample(0.0), 3.0);
+        let empty: ParticleCurve<f32> = serde_json::from_str(r#"{"keys":[]}"#).unwrap();
+        assert_eq!(empty.sample(0.5), 0.0);
+    }
+
+    #[test]
+    fn continuous_emission_spawns_at_rate_and_expires_by_lifetime() {
+        let mut emitter = ParticleEmitter::new().rate(8.0).lifetime(1.0).max_particles(100);
+        run(&mut emitter, 5, 0.125);
+        assert_eq!(emitter.particles().len(), 5);
+        // Steady state: eight spawned per second, each living one second.
+        run(&mut emitter, 40, 0.125);
+        assert_eq!(emitter.particles().len(), 8);
+
+        let mut capped = ParticleEmitter::new().rate(100.0).max_particles(4);
+        run(&mut capped, 10, 0.1);
+        assert_eq!(capped.particles().len(), 4);
+    }
+
+    #[test]
+    fn particles_follow_velocity_gravity_and_speed_curve() {
+        let mut emitter = ParticleEmitter::new()
+            .burst(1)
+            .lifetime(10.0)
+            .velocity(Vec3::new(2.0, 0.0, 0.0))
+            .gravity(Vec3::new(0.0, -1.0, 0.0));
+        run(&mut emitter, 1, 0.5);
+        run(&mut emitter, 1, 0.5);
+        let particle = emitter.particles()[0];
+        assert_eq!(particle.velocity, Vec3::new(2.0, -0.5, 0.0));
+        assert_eq!(particle.position, Vec3::new(1.0, -0.25, 0.0));
+
+        let mut frozen = ParticleEmitter::new().burst(1).speed_over_life(ParticleCurve::constant(0.0));
+        run(&mut frozen, 3, 0.1);
+        assert_eq!(frozen.particles()[0].position, Vec3::ZERO);
+    }
+
+    #[test]
+    fn bursts_fire_once_or_repeat() {
+        let mut once = ParticleEmitter::new().burst(8).lifetime(0.5);
+        run(&mut once, 1, 0.1);
+        assert_eq!(once.particles().len(), 8);
+        assert!(!once.is_finished());
+        run(&mut once, 10, 0.1);
+        assert!(once.particles().is_empty() && once.is_finished());
+
+        once.restart();
+        run(&mut once, 1, 0.1);
+        assert_eq!(once.particles().len(), 8);
+
+        let mut repeating = ParticleEmitter::new().burst_every(3, 1.0).lifetime(10.0);
+        run(&mut repeating, 25, 0.1);
+        assert_eq!(repeating.particles().len(), 9);
+        assert!(!repeating.is_finished());
+    }
+
+    #[test]
+    fn paused_emitters_freeze() {
+        let mut emitter = ParticleEmitter::new().rate(10.0);
+        run(&mut emitter, 3, 0.1);
+        let before = emitter.particles().to_vec();
+        emitter.pause();
+        run(&mut emitter, 10, 0.1);
+        assert_eq!(emitter.particles(), before.as_slice());
+        emitter.resume();
+        run(&mut emitter, 1, 0.1);
+        assert_ne!(emitter.particles()[0], before[0]);
+    }
+
+    #[test]
+    fn scenes_save_settings_but_not_particles() {
+        let mut emitter = ParticleEmitter::new()
+            .burst_every(5, 2.0)
+            .color_over_life(ParticleCurve::linear([1.0; 4], [0.0; 4]));
+        run(&mut emitter, 1, 0.1);
+
+        let json = serde_json::to_value(&emitter).unwrap();
+        let loaded: ParticleEmitter = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.emission, emitter.emission);
+        assert_eq!(loaded.color_over_life, emitter.color_over_life);
+        assert!(loaded.particles().is_empty());
+
+        // Missing fields take their defaults.
+        let sparse: ParticleEmitter = serde_json::from_str(r#"{"lifetime":3.0}"#).unwrap();
+        assert_eq!(sparse.lifetime, 3.0);
+        assert_eq!(sparse.max_particles, 1000);
+    }
+
+    #[test]
+    fn emitters_spawn_at_their_global_position_with_their_rotation() {
+        let mut world = World::new();
+        let matrix = Mat4::from_rotation_translation(
+            Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+            Vec3::new(5.0, 0.0, 0.0),
+        );
+        let entity = world.spawn((
+            GlobalTransform { matrix },
+            ParticleEmitter::new().burst(1).velocity(Vec3::X),
+        ));
+        update_particles(&mut world, 0.1);
+
+        let particle = world.get::<ParticleEmitter>(entity).unwrap().particles()[0];
+        assert_eq!(particle.position, Vec3::new(5.0, 0.0, 0.0));
+        assert!(particle.velocity.abs_diff_eq(Vec3::Y, 1e-6));
+    }
+}
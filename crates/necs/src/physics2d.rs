@@ -2,15 +2,110 @@
 //!
 //! Provides rigid-body and collider components that automatically synchronize
 //! with an internal Rapier simulation. Add [`PhysicsWorld2d`] as a resource,
-//! attach [`RigidBody2d`] and [`Collider2d`] to your entities, and run
-//! [`physics_step_2d`] each frame.
+//! attach [`RigidBody2d`] and [`Collider2d`] to your entities, and add the
+//! [`Physics2d`] plugin, which steps the simulation once per
+//! [fixed tick](crate::time) and smooths [`InterpolatedTransform`]s in
+//! between.
+//!
+//! [`TriggerVolume2d`] adds a sensor with no rigid body; overlaps are
+//! reported as [`TriggerEntered`](crate::trigger::TriggerEntered) /
+//! [`TriggerExited`](crate::trigger::TriggerExited) in [`TriggerEvents`].
+//!
+//! ## Character Controller
+//!
+//! Platformer movement wants exact, snappy control that a dynamic body
+//! can't give: no bouncing off ledges, no sliding down gentle slopes, no
+//! tipping over. [`CharacterController2d`] moves a capsule with Rapier's
+//! kinematic character controller instead, sliding along walls, climbing
+//! slopes and small steps and sticking to the ground. After each step it
+//! reports what it touched:
+//!
+//! ```text
+//!             on_ceiling()
+//!               ┌───┐
+//!   on_wall_left│ ● │on_wall_right        grounded_within(0.1) — still "on the
+//!               └───┘                     ground" for 0.1s after running off a
+//!             is_grounded()               ledge, for coyote-time jumps
+//! ```
+//!
+//! ```ignore
+//! fn run(ctx: &mut Context, player: Entity) {
+//!     let dt = ctx.time.delta_secs();
+//!     let ctrl = ctx.world.get_mut::<CharacterController2d>(player).unwrap();
+//!     let mut velocity = ctrl.velocity();
+//!     velocity.x = ctx.input.gamepad_axis(GamepadAxis::LeftStickX) * 200.0;
+//!     velocity.y = if ctrl.is_grounded() { 0.0 } else { velocity.y - 900.0 * dt };
+//!     if ctx.input.just_pressed(KeyCode::Space) && ctrl.grounded_within(0.1) {
+//!         velocity.y = 400.0;
+//!     }
+//!     ctrl.move_and_slide(velocity);
+//! }
+//! ```
+//!
+//! Colliders made [one-way](Collider2d::with_one_way) are platforms the
+//! controller jumps up through and lands on: they only block it while its
+//! feet are above their top. Other bodies collide with them normally.
+//!
+//! ## Gravity Fields
+//!
+//! [`GravityField2d`] gives an area its own gravity — a planet that pulls
+//! everything toward its center, a room where "down" is up. Dynamic bodies
+//! whose center is inside the field's shape fall its way instead of (or as
+//! well as) the world's way:
+//!
+//! ```text
+//!   world gravity ↓            Directional((0, 9.81))      Radial { strength: 9.81 }
+//!   ─────────────────          ┌──────────────────┐              ↘  ↓  ↙
+//!                              │  ↑    ↑    ↑     │             →   ●   ←
+//!                              └──────────────────┘              ↗  ↑  ↖
+//! ```
+//!
+//! ```ignore
+//! // A planet: inside 300 units of its center, fall toward it.
+//! ctx.spawn("planet")
+//!     .insert(Transform::from_xy(0.0, 0.0))
+//!     .insert(GravityField2d::radial(ColliderShape2d::Ball { radius: 300.0 }, 9.81));
+//! // Flip gravity in a room by turning it upside down.
+//! world.get_mut::<Transform2d>(room).unwrap().rotate(std::f32::consts::PI);
+//! ```
+//!
+//! A field [overrides](GravityFieldMode::Override) world gravity by
+//! default; where override fields overlap, the highest
+//! [`priority`](GravityField2d::priority) wins. [Additive](GravityFieldMode::Add)
+//! fields (wind, a conveyor of air) add on top of whichever gravity applies.
+//! A body's `gravity_scale` scales field gravity too. Moving, turning,
+//! resizing or otherwise changing a field wakes the sleeping bodies inside
+//! it, both where it was and where it is now; removing a field wakes the
+//! bodies it used to cover, so nothing stays asleep under gravity that no
+//! longer applies.
+//!
+//! ## Multiple Worlds
+//!
+//! [`PhysicsWorlds2d`] holds further, independent simulations by name — a
+//! background scene, a physics toy in the UI — whose bodies never touch the
+//! main world's. Entities join one with [`InPhysicsWorld2d`], or by carrying a
+//! [`SceneMarker`] with the world's name, so a scene loaded with
+//! [`load_scene_tagged`](crate::scene::load_scene_tagged) can get a world of
+//! its own. Removing the world drops every body in it at once:
+//!
+//! ```ignore
+//! world.resource_mut::<PhysicsWorlds2d>().insert("level_2", PhysicsWorld2d::new());
+//! load_scene_tagged(world, &registry, &level_2, "level_2");
+//! // later
+//! unload_scene(world, "level_2");
+//! world.resource_mut::<PhysicsWorlds2d>().remove("level_2");
+//! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use rapier2d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
 use rapier2d::prelude::*;
 
 use crate::ecs::{Entity, World};
+use crate::interpolation::{apply_interpolation_filtered, InterpolatedTransform};
 use crate::math::{Quat, Transform};
+use crate::scene::SceneMarker;
+use crate::trigger::{TriggerEvents, TriggerOverlaps};
 
 // ── Conversion helpers ──────────────────────────────────────────────────
 
@@ -145,7 +240,7 @@ impl RigidBody2d {
 }
 
 /// Collider shape for 2D physics.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColliderShape2d {
     Ball { radius: f32 },
     Cuboid { hx: f32, hy: f32 },
@@ -163,6 +258,9 @@ pub struct Collider2d {
     pub friction: f32,
     pub density: f32,
     pub sensor: bool,
+    /// A platform that [`CharacterController2d`]s pass up through and land
+    /// on. Other bodies collide with it normally.
+    pub one_way: bool,
     pub(crate) handle: Option<ColliderHandle>,
 }
 
@@ -175,6 +273,7 @@ impl Collider2d {
             friction: 0.5,
             density: 1.0,
             sensor: false,
+            one_way: false,
             handle: None,
         }
     }
@@ -228,11 +327,298 @@ impl Collider2d {
         self.sensor = s;
         self
     }
+
+    /// Make it a one-way platform for character controllers.
+    pub fn with_one_way(mut self, one_way: bool) -> Self {
+        self.one_way = one_way;
+        self
+    }
+}
+
+/// Rapier `user_data` marking a one-way platform collider.
+const ONE_WAY: u128 = 1;
+
+/// Component: a capsule moved by [`move_and_slide`](Self::move_and_slide)
+/// that slides along walls, climbs slopes and steps, stays on the ground
+/// and lands on one-way platforms. Pair with a [`Transform`] (the capsule's
+/// center) and no [`RigidBody2d`]. See the
+/// [module docs](self#character-controller).
+///
+/// The capsule's size is read when the controller joins the physics world.
+#[derive(Debug, Clone)]
+pub struct CharacterController2d {
+    /// Capsule radius.
+    pub radius: f32,
+    /// Half the length of the capsule's straight middle; the capsule is
+    /// `2 * (half_height + radius)` tall.
+    pub half_height: f32,
+    /// Steepest slope it walks up, in radians; steeper ones are slid down.
+    pub max_slope: f32,
+    /// Tallest ledge it steps onto without jumping; 0 disables stepping.
+    pub step_offset: f32,
+    /// How far below it looks for ground to stick to when walking down
+    /// slopes and steps; 0 disables snapping.
+    pub snap_distance: f32,
+    /// Gap kept between the capsule and what it touches, so it doesn't get
+    /// stuck in surfaces.
+    pub skin_width: f32,
+    /// Wanted velocity, held until the next `move_and_slide`.
+    desired: Vec2,
+    /// Velocity actually achieved over the last step.
+    velocity: Vec2,
+    grounded: bool,
+    /// Seconds since it was last grounded.
+    airborne: f32,
+    wall_left: bool,
+    wall_right: bool,
+    ceiling: bool,
+    pub(crate) handle: Option<RigidBodyHandle>,
+}
+
+impl CharacterController2d {
+    /// A capsule of `half_height` and `radius`, climbing slopes up to 45°
+    /// and steps up to a third of its height.
+    pub fn new(half_height: f32, radius: f32) -> Self {
+        Self {
+            radius,
+            half_height,
+            max_slope: 45f32.to_radians(),
+            step_offset: (half_height + radius) * 2.0 / 3.0,
+            snap_distance: radius * 0.5,
+            skin_width: radius * 0.02,
+            desired: Vec2::ZERO,
+            velocity: Vec2::ZERO,
+            grounded: false,
+            airborne: f32::INFINITY,
+            wall_left: false,
+            wall_right: false,
+            ceiling: false,
+            handle: None,
+        }
+    }
+
+    pub fn with_max_slope(mut self, radians: f32) -> Self {
+        self.max_slope = radians;
+        self
+    }
+
+    pub fn with_step_offset(mut self, height: f32) -> Self {
+        self.step_offset = height;
+        self
+    }
+
+    pub fn with_snap_distance(mut self, distance: f32) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    /// Move at `velocity` (units per second), sliding along whatever is in
+    /// the way. Takes effect at the next physics step and holds until the
+    /// next call, so call it every frame.
+    pub fn move_and_slide(&mut self, velocity: Vec2) {
+        self.desired = velocity;
+    }
+
+    /// Whether it stood on walkable ground after the last step.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Whether it was grounded at some point in the last `seconds` — allow
+    /// a jump for a moment after running off a ledge ("coyote time").
+    pub fn grounded_within(&self, seconds: f32) -> bool {
+        self.airborne <= seconds
+    }
+
+    /// Seconds since it was last grounded; 0 while grounded.
+    pub fn airborne_time(&self) -> f32 {
+        self.airborne
+    }
+
+    /// Whether it ran into a wall on its left during the last step.
+    pub fn on_wall_left(&self) -> bool {
+        self.wall_left
+    }
+
+    /// Whether it ran into a wall on its right during the last step.
+    pub fn on_wall_right(&self) -> bool {
+        self.wall_right
+    }
+
+    /// Whether it bumped its head during the last step.
+    pub fn on_ceiling(&self) -> bool {
+        self.ceiling
+    }
+
+    /// Velocity actually achieved over the last step: the wanted velocity
+    /// less whatever walls, floors and ceilings took away.
+    pub fn velocity(&self) -> Vec2 {
+        self.velocity
+    }
+
+    /// Rapier's controller configured from these settings.
+    fn rapier_controller(&self) -> KinematicCharacterController {
+        let absolute = |length: f32| (length > 0.0).then_some(CharacterLength::Absolute(length));
+        KinematicCharacterController {
+            offset: CharacterLength::Absolute(self.skin_width),
+            slide: true,
+            autostep: absolute(self.step_offset).map(|max_height| CharacterAutostep {
+                max_height,
+                min_width: CharacterLength::Absolute(self.radius * 0.5),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: self.max_slope,
+            min_slope_slide_angle: self.max_slope,
+            snap_to_ground: absolute(self.snap_distance),
+            ..KinematicCharacterController::default()
+        }
+    }
+}
+
+/// A trigger volume: a sensor collider that needs no rigid body.
+///
+/// Follows the entity's [`Transform`] every step and reports bodies that
+/// start or stop overlapping it through [`TriggerEvents`]. Only entities with
+/// a [`RigidBody2d`] and [`Collider2d`] are detected. Changing `shape`
+/// resizes the sensor on the next step; removing the component removes it.
+#[derive(Debug, Clone)]
+pub struct TriggerVolume2d {
+    pub shape: ColliderShape2d,
+    pub(crate) handle: Option<ColliderHandle>,
+    /// The shape the sensor was last built with.
+    built_shape: Option<ColliderShape2d>,
+}
+
+impl TriggerVolume2d {
+    /// A trigger with the given shape.
+    pub fn new(shape: ColliderShape2d) -> Self {
+        Self {
+            shape,
+            handle: None,
+            built_shape: None,
+        }
+    }
+
+    /// A circular trigger.
+    pub fn ball(radius: f32) -> Self {
+        Self::new(ColliderShape2d::Ball { radius })
+    }
+
+    /// A rectangular trigger (half-extents).
+    pub fn cuboid(hx: f32, hy: f32) -> Self {
+        Self::new(ColliderShape2d::Cuboid { hx, hy })
+    }
+}
+
+/// How a [`GravityField2d`] pulls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldGravity2d {
+    /// A constant acceleration, rotated with the field's [`Transform`].
+    Directional(Vec2),
+    /// Toward the field's center at `strength` units/s², whatever the
+    /// distance. Negative pushes away.
+    Radial { strength: f32 },
+}
+
+/// How a [`GravityField2d`] combines with other gravity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GravityFieldMode {
+    /// Replace world gravity (and lower-priority overrides).
+    #[default]
+    Override,
+    /// Add to whichever gravity applies.
+    Add,
+}
+
+/// Component: an area with its own gravity for the dynamic bodies inside
+/// it. Pair with a [`Transform`]; needs no rigid body. See the
+/// [module docs](self#gravity-fields).
+#[derive(Debug, Clone)]
+pub struct GravityField2d {
+    /// The area, around the entity's Transform.
+    pub shape: ColliderShape2d,
+    pub gravity: FieldGravity2d,
+    pub mode: GravityFieldMode,
+    /// Where override fields overlap, the highest priority wins.
+    pub priority: i32,
+}
+
+impl GravityField2d {
+    /// Constant gravity `acceleration` inside `shape`, turning with the
+    /// field.
+    pub fn directional(shape: ColliderShape2d, acceleration: Vec2) -> Self {
+        Self {
+            shape,
+            gravity: FieldGravity2d::Directional(acceleration),
+            mode: GravityFieldMode::Override,
+            priority: 0,
+        }
+    }
+
+    /// Gravity of `strength` toward the field's center inside `shape`.
+    pub fn radial(shape: ColliderShape2d, strength: f32) -> Self {
+        Self {
+            gravity: FieldGravity2d::Radial { strength },
+            ..Self::directional(shape, Vec2::ZERO)
+        }
+    }
+
+    /// Add to gravity instead of replacing it.
+    pub fn additive(mut self) -> Self {
+        self.mode = GravityFieldMode::Add;
+        self
+    }
+
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+}
+
+/// A [`GravityField2d`] as placed for one step. Compared with the previous
+/// step's to notice changes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ActiveField {
+    center: Vec2,
+    angle: f32,
+    shape: ColliderShape2d,
+    gravity: FieldGravity2d,
+    mode: GravityFieldMode,
+    priority: i32,
+}
+
+impl ActiveField {
+    /// Whether `point` is inside the field's shape.
+    fn contains(&self, point: Vec2) -> bool {
+        let local = Vec2::from_angle(-self.angle).rotate(point - self.center);
+        match self.shape {
+            ColliderShape2d::Ball { radius } => local.length() <= radius,
+            ColliderShape2d::Cuboid { hx, hy } => local.x.abs() <= hx && local.y.abs() <= hy,
+            ColliderShape2d::CapsuleY { half_height, radius } => {
+                Vec2::new(local.x, (local.y.abs() - half_height).max(0.0)).length() <= radius
+            }
+            ColliderShape2d::CapsuleX { half_height, radius } => {
+                Vec2::new((local.x.abs() - half_height).max(0.0), local.y).length() <= radius
+            }
+        }
+    }
+
+    /// The field's acceleration at `point`.
+    fn acceleration(&self, point: Vec2) -> Vec2 {
+        match self.gravity {
+            FieldGravity2d::Directional(acceleration) => {
+                Vec2::from_angle(self.angle).rotate(acceleration)
+            }
+            FieldGravity2d::Radial { strength } => {
+                (self.center - point).normalize_or_zero() * strength
+            }
+        }
+    }
 }
 
 // ── Resource ────────────────────────────────────────────────────────────
 
-/// The 2D physics world. Insert as a resource and run [`physics_step_2d`] each frame.
+/// The 2D physics world. Inserted by [`Physics2d`] and stepped on every fixed tick.
 pub struct PhysicsWorld2d {
     gravity: Vec2,
     pipeline: PhysicsPipeline,
@@ -247,7 +633,14 @@ pub struct PhysicsWorld2d {
     ccd_solver: CCDSolver,
     body_to_entity: HashMap<RigidBodyHandle, Entity>,
     entity_to_body: HashMap<u32, RigidBodyHandle>,
-    accumulator: f32,
+    /// Colliders of [`Collider2d`]s. Handles are only meaningful in the
+    /// world that made them, so a component's handle counts only if it's here.
+    collider_to_entity: HashMap<ColliderHandle, Entity>,
+    trigger_to_entity: HashMap<ColliderHandle, Entity>,
+    trigger_overlaps: TriggerOverlaps,
+    /// Gravity fields as placed for the last step, to wake sleepers where a
+    /// field was once it moves, changes or goes away.
+    gravity_fields: HashMap<Entity, ActiveField>,
 }
 
 impl std::fmt::Debug for PhysicsWorld2d {
@@ -256,6 +649,7 @@ impl std::fmt::Debug for PhysicsWorld2d {
             .field("gravity", &self.gravity)
             .field("bodies", &self.bodies.len())
             .field("colliders", &self.colliders.len())
+            .field("triggers", &self.trigger_to_entity.len())
             .finish()
     }
 }
@@ -277,7 +671,10 @@ impl PhysicsWorld2d {
             ccd_solver: CCDSolver::new(),
             body_to_entity: HashMap::new(),
             entity_to_body: HashMap::new(),
-            accumulator: 0.0,
+            collider_to_entity: HashMap::new(),
+            trigger_to_entity: HashMap::new(),
+            trigger_overlaps: TriggerOverlaps::default(),
+            gravity_fields: HashMap::new(),
         }
     }
 
@@ -294,9 +691,51 @@ impl Default for PhysicsWorld2d {
     }
 }
 
+/// Component: simulate this entity in the named world of
+/// [`PhysicsWorlds2d`] instead of the main [`PhysicsWorld2d`]. Moving an
+/// entity to another world rebuilds its body there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InPhysicsWorld2d(pub String);
+
+/// Resource: additional 2D physics worlds, keyed by name. Each steps
+/// independently of the main [`PhysicsWorld2d`] and of each other.
+///
+/// Entities join a world through [`InPhysicsWorld2d`], or — without one —
+/// through a [`SceneMarker`] naming it.
+#[derive(Debug, Default)]
+pub struct PhysicsWorlds2d {
+    worlds: HashMap<String, PhysicsWorld2d>,
+}
+
+impl PhysicsWorlds2d {
+    /// Add a world, returning the one it replaces.
+    pub fn insert(&mut self, name: impl Into<String>, world: PhysicsWorld2d) -> Option<PhysicsWorld2d> {
+        self.worlds.insert(name.into(), world)
+    }
+
+    /// Remove a world, dropping all of its bodies and colliders.
+    pub fn remove(&mut self, name: &str) -> Option<PhysicsWorld2d> {
+        self.worlds.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PhysicsWorld2d> {
+        self.worlds.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut PhysicsWorld2d> {
+        self.worlds.get_mut(name)
+    }
+
+    /// Names of all worlds, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.worlds.keys().map(String::as_str)
+    }
+}
+
 // ── Plugin ──────────────────────────────────────────────────────────────
 
-/// Plugin that registers the 2D physics resource and update system.
+/// Plugin that registers the 2D physics resources, a fixed-update system
+/// stepping them, and an update system interpolating between steps.
 ///
 /// # Example
 ///
@@ -311,41 +750,107 @@ pub struct Physics2d;
 impl crate::game::Plugin for Physics2d {
     fn build(&self, game: &mut crate::game::Game) {
         game.insert_resource(PhysicsWorld2d::new());
-        game.add_update_system(|ctx| physics_step_2d(&mut ctx.world));
+        game.insert_resource(PhysicsWorlds2d::default());
+        game.insert_resource(TriggerEvents::default());
+        game.add_fixed_update_system(|ctx| physics_step_2d(&mut ctx.world));
+        game.add_update_system(|ctx| interpolate_physics_2d(&mut ctx.world));
     }
 }
 
 // ── System ──────────────────────────────────────────────────────────────
 
-/// Advance the 2D physics simulation by one frame.
+/// Advance the 2D physics simulation by one fixed tick of
+/// [`Time::fixed_delta_secs`](crate::time::Time::fixed_delta_secs).
 ///
 /// Uses the extract/reinsert pattern to borrow the physics world and the ECS
-/// world simultaneously. Physics runs with a fixed timestep (default 1/60s)
-/// using an accumulator to decouple simulation from frame rate.
+/// world simultaneously. Runs as a fixed-update system, so the tick rate
+/// and catch-up limit are the ones set on [`Time`](crate::time::Time). The main
+/// world and each of [`PhysicsWorlds2d`] are stepped in turn, each seeing
+/// only its own entities. Nothing is stepped while
+/// [`Subsystems::physics`](crate::subsystems::Subsystems::physics) is off.
 pub(crate) fn physics_step_2d(world: &mut World) {
-    let frame_dt = world.resource::<crate::time::Time>().delta_secs();
-    if frame_dt <= 0.0 {
+    if !crate::subsystems::Subsystems::of(world).physics {
         return;
     }
-
-    let Some(mut pw) = world.resource_remove::<PhysicsWorld2d>() else {
+    let dt = world.resource::<crate::time::Time>().fixed_delta_secs();
+    if !world.has_resource::<PhysicsWorld2d>() && !world.has_resource::<PhysicsWorlds2d>() {
         return;
-    };
+    }
 
-    // Add frame delta to accumulator, capped to prevent spiral of death.
-    pw.accumulator += frame_dt.min(0.25);
+    let frame = world.resource::<crate::time::Time>().frame_count();
+    if !world.has_resource::<TriggerEvents>() {
+        world.insert_resource(TriggerEvents::default());
+    }
+    world.resource_mut::<TriggerEvents>().begin_frame(frame);
+
+    // Tilemap colliders are ordinary fixed bodies, so spawn them before the
+    // sync below picks up new bodies.
+    #[cfg(feature = "render2d")]
+    crate::render2d::tilemap::update_tilemap_colliders(world);
 
-    // If not enough time has accumulated for a single step, bail early.
-    if pw.accumulator < pw.params.dt {
+    let mut extra = world.resource_remove::<PhysicsWorlds2d>();
+    let membership = world_membership(world, extra.as_ref());
+
+    if let Some(mut pw) = world.resource_remove::<PhysicsWorld2d>() {
+        step_world(world, &mut pw, dt, &|e: Entity| !membership.contains_key(&e));
         world.insert_resource(pw);
-        return;
     }
+    if let Some(extra) = &mut extra {
+        for (name, pw) in &mut extra.worlds {
+            step_world(world, pw, dt, &|e: Entity| membership.get(&e) == Some(name));
+        }
+    }
+    if let Some(extra) = extra {
+        world.insert_resource(extra);
+    }
+}
+
+/// Blend every physics body's [`InterpolatedTransform`] between its last
+/// two steps by [`Time::fixed_alpha`](crate::time::Time::fixed_alpha). Runs
+/// every frame, including frames without a fixed tick.
+pub(crate) fn interpolate_physics_2d(world: &mut World) {
+    let alpha = world.resource::<crate::time::Time>().fixed_alpha();
+    let mut bodies = HashSet::new();
+    world.query::<(&RigidBody2d, &InterpolatedTransform)>(|entity, _| {
+        bodies.insert(entity);
+    });
+    if !bodies.is_empty() {
+        apply_interpolation_filtered(world, alpha, true, |e| bodies.contains(&e));
+    }
+}
+
+/// Which extra world each entity belongs to. Entities not in the map use the
+/// main world; [`InPhysicsWorld2d`] wins over a [`SceneMarker`].
+fn world_membership(world: &mut World, extra: Option<&PhysicsWorlds2d>) -> HashMap<Entity, String> {
+    let mut membership = HashMap::new();
+    if let Some(extra) = extra {
+        world.query::<(&SceneMarker,)>(|entity, (marker,)| {
+            if extra.worlds.contains_key(&marker.0) {
+                membership.insert(entity, marker.0.clone());
+            }
+        });
+    }
+    world.query::<(&InPhysicsWorld2d,)>(|entity, (member,)| {
+        membership.insert(entity, member.0.clone());
+    });
+    membership
+}
+
+/// Step one physics world by `dt` with the entities `in_world` accepts.
+fn step_world(
+    world: &mut World,
+    pw: &mut PhysicsWorld2d,
+    dt: f32,
+    in_world: &dyn Fn(Entity) -> bool,
+) {
+    pw.params.dt = dt;
 
-    // 1. Cleanup: remove bodies whose entities have been despawned.
+    // 1. Cleanup: remove bodies whose entities have been despawned or
+    //    moved to another world.
     let dead: Vec<RigidBodyHandle> = pw
         .body_to_entity
         .iter()
-        .filter(|(_h, e)| !world.is_alive(**e))
+        .filter(|(_h, e)| !world.is_alive(**e) || !in_world(**e))
         .map(|(h, _e)| *h)
         .collect();
     for handle in dead {
@@ -361,13 +866,38 @@ pub(crate) fn physics_step_2d(world: &mut World) {
             true,
         );
     }
+    // Removing a body removed its colliders too.
+    let colliders = &pw.colliders;
+    pw.collider_to_entity.retain(|handle, _| colliders.contains(*handle));
 
-    // 2. Discover new rigid bodies (handle is None).
+    // Remove trigger colliders whose entities have been despawned or moved,
+    // or no longer have this trigger (the component was removed or replaced).
+    let dead_triggers: Vec<(ColliderHandle, Entity)> = pw
+        .trigger_to_entity
+        .iter()
+        .filter(|(h, e)| {
+            !world.is_alive(**e)
+                || !in_world(**e)
+                || world
+                    .get::<TriggerVolume2d>(**e)
+                    .is_none_or(|trigger| trigger.handle != Some(**h))
+        })
+        .map(|(h, e)| (*h, *e))
+        .collect();
+    for (handle, entity) in dead_triggers {
+        pw.trigger_to_entity.remove(&handle);
+        pw.trigger_overlaps.forget_trigger(entity);
+        pw.colliders.remove(handle, &mut pw.islands, &mut pw.bodies, false);
+    }
+
+    // 2. Discover new rigid bodies: no handle yet, or a handle from another
+    //    world.
     {
         let mut new_bodies: Vec<(Entity, RigidBodyType2d, Vec2, f32, f32, f32, f32, bool, Vec2, f32)> =
             Vec::new();
         world.query::<(&RigidBody2d, &Transform)>(|entity, (rb, tf)| {
-            if rb.handle.is_none() {
+            let known = rb.handle.is_some_and(|h| pw.body_to_entity.get(&h) == Some(&entity));
+            if in_world(entity) && !known {
                 new_bodies.push((
                     entity,
                     rb.body_type,
@@ -404,13 +934,21 @@ pub(crate) fn physics_step_2d(world: &mut World) {
         }
     }
 
-    // 3. Discover new colliders (handle is None, parent body already registered).
+    // 3. Discover new colliders (no handle from this world, parent body
+    //    already registered).
     {
-        let mut new_colliders: Vec<(Entity, ColliderShape2d, f32, f32, f32, bool, RigidBodyHandle)> =
+        let mut new_colliders: Vec<(Entity, ColliderShape2d, f32, f32, f32, bool, bool, RigidBodyHandle)> =
             Vec::new();
         world.query::<(&Collider2d, &RigidBody2d)>(|entity, (coll, rb)| {
-            if coll.handle.is_none() {
-                if let Some(body_handle) = rb.handle {
+            if !in_world(entity) {
+                return;
+            }
+            if let Some(body_handle) = rb.handle {
+                let attached = coll.handle.is_some_and(|h| {
+                    pw.collider_to_entity.get(&h) == Some(&entity)
+                        && pw.colliders.get(h).is_some_and(|c| c.parent() == Some(body_handle))
+                });
+                if !attached {
                     new_colliders.push((
                         entity,
                         coll.shape,
@@ -418,32 +956,96 @@ pub(crate) fn physics_step_2d(world: &mut World) {
                         coll.friction,
                         coll.density,
                         coll.sensor,
+                        coll.one_way,
                         body_handle,
                     ));
                 }
             }
         });
-        for (entity, shape, restitution, friction, density, sensor, body_handle) in new_colliders {
+        for (entity, shape, restitution, friction, density, sensor, one_way, body_handle) in
+            new_colliders
+        {
             let coll = shape_to_collider_builder(&shape)
                 .restitution(restitution)
                 .friction(friction)
                 .density(density)
                 .sensor(sensor)
+                .user_data(if one_way { ONE_WAY } else { 0 })
                 .build();
             let handle =
                 pw.colliders
                     .insert_with_parent(coll, body_handle, &mut pw.bodies);
+            pw.collider_to_entity.insert(handle, entity);
             if let Some(comp) = world.get_mut::<Collider2d>(entity) {
                 comp.handle = Some(handle);
             }
         }
     }
 
+    // 3b. Trigger volumes: parentless sensor colliders that follow their Transform.
+    {
+        let mut triggers: Vec<(Entity, Option<ColliderHandle>, ColliderShape2d, bool, Vec2, f32)> =
+            Vec::new();
+        world.query::<(&TriggerVolume2d, &Transform)>(|entity, (trigger, tf)| {
+            if !in_world(entity) {
+                return;
+            }
+            triggers.push((
+                entity,
+                trigger.handle.filter(|h| pw.trigger_to_entity.get(h) == Some(&entity)),
+                trigger.shape,
+                trigger.built_shape != Some(trigger.shape),
+                Vec2::new(tf.translation.x, tf.translation.y),
+                quat_to_angle(tf.rotation),
+            ));
+        });
+        for (entity, handle, shape, reshape, pos, angle) in triggers {
+            let pose = Pose::new(pos, angle);
+            match handle.and_then(|h| pw.colliders.get_mut(h)) {
+                Some(coll) => {
+                    coll.set_position(pose);
+                    if reshape {
+                        coll.set_shape(shape_to_collider_builder(&shape).shape);
+                    }
+                }
+                None => {
+                    // A parentless collider counts as fixed, and fixed–kinematic
+                    // pairs are skipped by default; triggers should still
+                    // notice kinematic characters.
+                    let coll = shape_to_collider_builder(&shape)
+                        .sensor(true)
+                        .active_collision_types(ActiveCollisionTypes::all())
+                        .position(pose)
+                        .build();
+                    let handle = pw.colliders.insert(coll);
+                    pw.trigger_to_entity.insert(handle, entity);
+                    if let Some(comp) = world.get_mut::<TriggerVolume2d>(entity) {
+                        comp.handle = Some(handle);
+                    }
+                }
+            }
+            if let Some(comp) = world.get_mut::<TriggerVolume2d>(entity) {
+                comp.built_shape = Some(shape);
+            }
+        }
+    }
+
+    // 3c. Gravity fields, placed at their Transforms for this step, and the
+    //    regions whose gravity changed since the last one.
+    let placed = collect_gravity_fields(world, in_world);
+    let woken = changed_field_regions(&pw.gravity_fields, &placed);
+    let fields: Vec<ActiveField> = placed.values().copied().collect();
+    pw.gravity_fields = placed;
+
+    // 3d. Character controllers: a kinematic capsule each, placed at the
+    //     Transform so systems can teleport them between steps.
+    let characters = register_characters(world, pw, in_world);
+
     // 4. Sync kinematic bodies: push Transform → Rapier.
     {
         let mut kinematic_updates: Vec<(RigidBodyHandle, Vec2, f32)> = Vec::new();
-        world.query::<(&RigidBody2d, &Transform)>(|_entity, (rb, tf)| {
-            if rb.body_type == RigidBodyType2d::KinematicPositionBased {
+        world.query::<(&RigidBody2d, &Transform)>(|entity, (rb, tf)| {
+            if in_world(entity) && rb.body_type == RigidBodyType2d::KinematicPositionBased {
                 if let Some(handle) = rb.handle {
                     kinematic_updates.push((
                         handle,
@@ -460,30 +1062,62 @@ pub(crate) fn physics_step_2d(world: &mut World) {
         }
     }
 
-    // 5. Step the simulation with fixed dt, consuming the accumulator.
-    let fixed_dt = pw.params.dt;
-    while pw.accumulator >= fixed_dt {
-        pw.pipeline.step(
-            pw.gravity,
-            &pw.params,
-            &mut pw.islands,
-            &mut pw.broad_phase,
-            &mut pw.narrow_phase,
-            &mut pw.bodies,
-            &mut pw.colliders,
-            &mut pw.impulse_joints,
-            &mut pw.multibody_joints,
-            &mut pw.ccd_solver,
-            &(),
-            &(),
-        );
-        pw.accumulator -= fixed_dt;
+    // 5. Step the simulation. Characters pick their move first, so the
+    //    step carries their kinematic bodies there.
+    if !fields.is_empty() || !woken.is_empty() {
+        apply_gravity_fields(pw, &fields, &woken, dt);
+    }
+    let moved = move_characters(world, pw, &characters, dt);
+    pw.pipeline.step(
+        pw.gravity,
+        &pw.params,
+        &mut pw.islands,
+        &mut pw.broad_phase,
+        &mut pw.narrow_phase,
+        &mut pw.bodies,
+        &mut pw.colliders,
+        &mut pw.impulse_joints,
+        &mut pw.multibody_joints,
+        &mut pw.ccd_solver,
+        &(),
+        &(),
+    );
+
+    if !pw.trigger_to_entity.is_empty() {
+        let pairs = trigger_pairs(pw);
+        pw.trigger_overlaps
+            .update(pairs, world.resource_mut::<TriggerEvents>());
+    }
+
+    // 5b. Interpolated bodies record their pose after every step, so the
+    //     last two steps form the previous/current pair.
+    let mut interpolated: Vec<(Entity, RigidBodyHandle)> = Vec::new();
+    world.query::<(&RigidBody2d, &InterpolatedTransform)>(|entity, (rb, _interp)| {
+        if in_world(entity) && rb.body_type != RigidBodyType2d::KinematicPositionBased {
+            if let Some(handle) = rb.handle {
+                interpolated.push((entity, handle));
+            }
+        }
+    });
+    for (entity, handle) in interpolated {
+        if let Some(body) = pw.bodies.get(handle) {
+            let pose = crate::interpolation::Pose {
+                translation: crate::math::Vec3::new(body.translation().x, body.translation().y, 0.0),
+                rotation: angle_to_quat(body.rotation().angle()),
+            };
+            if let Some(interp) = world.get_mut::<InterpolatedTransform>(entity) {
+                interp.push(pose);
+            }
+        }
     }
 
     // 6. Sync dynamic/kinematic-velocity bodies: pull Rapier → Transform.
     {
         let mut sync_updates: Vec<(Entity, Vec2, f32)> = Vec::new();
         world.query::<(&RigidBody2d,)>(|entity, (rb,)| {
+            if !in_world(entity) {
+                return;
+            }
             if rb.body_type == RigidBodyType2d::Dynamic
                 || rb.body_type == RigidBodyType2d::KinematicVelocityBased
             {
@@ -505,6 +1139,472 @@ pub(crate) fn physics_step_2d(world: &mut World) {
         }
     }
 
-    world.insert_resource(pw);
+    // 6b. Character controllers: where each moved → Transform.
+    for (entity, position) in moved {
+        if let Some(tf) = world.get_mut::<Transform>(entity) {
+            tf.translation.x = position.x;
+            tf.translation.y = position.y;
+        }
+    }
 }
 
+/// Give new [`CharacterController2d`]s a body and capsule in this world, and
+/// move every character's body to its Transform. Returns the characters.
+fn register_characters(
+    world: &mut World,
+    pw: &mut PhysicsWorld2d,
+    in_world: &dyn Fn(Entity) -> bool,
+) -> Vec<(Entity, RigidBodyHandle)> {
+    let mut found: Vec<(Entity, Option<RigidBodyHandle>, f32, f32, Vec2)> = Vec::new();
+    world.query::<(&CharacterController2d, &Transform)>(|entity, (ctrl, tf)| {
+        if in_world(entity) {
+            let handle = ctrl.handle.filter(|h| pw.body_to_entity.get(h) == Some(&entity));
+            let pos = Vec2::new(tf.translation.x, tf.translation.y);
+            found.push((entity, handle, ctrl.half_height, ctrl.radius, pos));
+        }
+    });
+
+    let mut characters = Vec::with_capacity(found.len());
+    for (entity, handle, half_height, radius, pos) in found {
+        let handle = match handle {
+            Some(handle) => {
+                if let Some(body) = pw.bodies.get_mut(handle) {
+                    body.set_translation(pos, true);
+                    body.set_next_kinematic_translation(pos);
+                }
+                handle
+            }
+            None => {
+                let body = RigidBodyBuilder::kinematic_position_based().translation(pos).build();
+                let handle = pw.bodies.insert(body);
+                let capsule = ColliderBuilder::capsule_y(half_height, radius).build();
+                pw.colliders.insert_with_parent(capsule, handle, &mut pw.bodies);
+                pw.body_to_entity.insert(handle, entity);
+                pw.entity_to_body.insert(entity.index(), handle);
+                if let Some(ctrl) = world.get_mut::<CharacterController2d>(entity) {
+                    ctrl.handle = Some(handle);
+                }
+                handle
+            }
+        };
+        characters.push((entity, handle));
+    }
+    characters
+}
+
+/// Pick each character's move for the coming step along its wanted
+/// velocity, with Rapier's character controller resolving what's in the
+/// way, send its kinematic body there, and record what it touched. Returns
+/// where each ends up.
+fn move_characters(
+    world: &mut World,
+    pw: &mut PhysicsWorld2d,
+    characters: &[(Entity, RigidBodyHandle)],
+    dt: f32,
+) -> Vec<(Entity, Vec2)> {
+    let mut moved = Vec::with_capacity(characters.len());
+    for &(entity, handle) in characters {
+        let Some(ctrl) = world.get::<CharacterController2d>(entity) else {
+            continue;
+        };
+        let Some(body) = pw.bodies.get(handle) else {
+            continue;
+        };
+        let start = body.translation();
+        let shape = SharedShape::capsule_y(ctrl.half_height, ctrl.radius);
+
+        // One-way platforms block only while the feet are above their top.
+        let feet = start.y - ctrl.half_height - ctrl.radius;
+        let solid = |_: ColliderHandle, collider: &Collider| {
+            collider.user_data != ONE_WAY
+                || feet >= collider.compute_aabb().maxs.y - ctrl.skin_width
+        };
+        let query = pw.broad_phase.as_query_pipeline(
+            pw.narrow_phase.query_dispatcher(),
+            &pw.bodies,
+            &pw.colliders,
+            QueryFilter::default().exclude_sensors().exclude_rigid_body(handle).predicate(&solid),
+        );
+
+        // Which way each blocked part of the move was heading tells the
+        // side of the wall, whatever the contact normal's sign.
+        let (mut wall_left, mut wall_right, mut ceiling) = (false, false, false);
+        let movement = ctrl.rapier_controller().move_shape(
+            dt,
+            &query,
+            &*shape,
+            &Pose::new(start, 0.0),
+            ctrl.desired * dt,
+            |collision| {
+                let normal = collision.hit.normal1;
+                let blocked = collision.translation_remaining;
+                if normal.x.abs() > 0.7 {
+                    wall_left |= blocked.x < 0.0;
+                    wall_right |= blocked.x > 0.0;
+                } else if normal.y.abs() > 0.7 && blocked.y > 0.0 {
+                    ceiling = true;
+                }
+            },
+        );
+
+        let end = start + movement.translation;
+        if let Some(body) = pw.bodies.get_mut(handle) {
+            body.set_next_kinematic_translation(end);
+        }
+        if let Some(ctrl) = world.get_mut::<CharacterController2d>(entity) {
+            ctrl.grounded = movement.grounded;
+            ctrl.airborne = if movement.grounded { 0.0 } else { ctrl.airborne + dt };
+            ctrl.velocity = movement.translation / dt;
+            ctrl.wall_left = wall_left;
+            ctrl.wall_right = wall_right;
+            ctrl.ceiling = ceiling;
+        }
+        moved.push((entity, end));
+    }
+    moved
+}
+
+/// Every [`GravityField2d`] in this world, placed at its Transform.
+fn collect_gravity_fields(
+    world: &mut World,
+    in_world: &dyn Fn(Entity) -> bool,
+) -> HashMap<Entity, ActiveField> {
+    let mut fields = HashMap::new();
+    world.query::<(&GravityField2d, &Transform)>(|entity, (field, tf)| {
+        if !in_world(entity) {
+            return;
+        }
+        fields.insert(entity, ActiveField {
+            center: Vec2::new(tf.translation.x, tf.translation.y),
+            angle: quat_to_angle(tf.rotation),
+            shape: field.shape,
+            gravity: field.gravity,
+            mode: field.mode,
+            priority: field.priority,
+        });
+    });
+    fields
+}
+
+/// The regions where gravity may differ from the last step: for each field
+/// that moved or changed, where it was and where it is; for each field that
+/// was removed (or despawned, or left this world), where it was.
+fn changed_field_regions(
+    previous: &HashMap<Entity, ActiveField>,
+    current: &HashMap<Entity, ActiveField>,
+) -> Vec<ActiveField> {
+    let mut regions = Vec::new();
+    for (entity, field) in current {
+        match previous.get(entity) {
+            Some(old) if old == field => {}
+            Some(old) => regions.extend([*old, *field]),
+            None => regions.push(*field),
+        }
+    }
+    for (entity, old) in previous {
+        if !current.contains_key(entity) {
+            regions.push(*old);
+        }
+    }
+    regions
+}
+
+/// Swap world gravity for field gravity in the velocity of each dynamic
+/// body inside a field, ahead of one step. Rapier applies world gravity
+/// itself, so only the difference is added. Sleeping bodies are woken only
+/// inside the `woken` regions, where gravity changed since the last step.
+fn apply_gravity_fields(
+    pw: &mut PhysicsWorld2d,
+    fields: &[ActiveField],
+    woken: &[ActiveField],
+    dt: f32,
+) {
+    let world_gravity = pw.gravity;
+    for (_handle, body) in pw.bodies.iter_mut() {
+        if !body.is_dynamic() {
+            continue;
+        }
+        let point = body.translation();
+        if body.is_sleeping() {
+            if !woken.iter().any(|region| region.contains(point)) {
+                continue;
+            }
+            body.wake_up(true);
+        }
+        let inside: Vec<&ActiveField> =
+            fields.iter().filter(|field| field.contains(point)).collect();
+        if inside.is_empty() {
+            continue;
+        }
+
+        let base = inside
+            .iter()
+            .filter(|field| field.mode == GravityFieldMode::Override)
+            .max_by_key(|field| field.priority)
+            .map_or(world_gravity, |field| field.acceleration(point));
+        let added: Vec2 = inside
+            .iter()
+            .filter(|field| field.mode == GravityFieldMode::Add)
+            .map(|field| field.acceleration(point))
+            .sum();
+        // Without waking: an awake body stays awake for the step anyway,
+        // and a body at rest in a field must still be allowed to sleep.
+        let extra = (base + added - world_gravity) * body.gravity_scale();
+        if extra != Vec2::ZERO {
+            body.set_linvel(body.linvel() + extra * dt, false);
+        }
+    }
+}
+
+/// All `(trigger, other)` entity pairs currently overlapping. Only colliders
+/// attached to rigid bodies count as `other`, so overlapping triggers don't
+/// report each other.
+fn trigger_pairs(pw: &PhysicsWorld2d) -> HashSet<(Entity, Entity)> {
+    let body_entity = |handle: ColliderHandle| {
+        let body = pw.colliders.get(handle)?.parent()?;
+        pw.body_to_entity.get(&body).copied()
+    };
+
+    let mut pairs = HashSet::new();
+    for (h1, h2, intersecting) in pw.narrow_phase.intersection_pairs() {
+        if !intersecting {
+            continue;
+        }
+        for (a, b) in [(h1, h2), (h2, h1)] {
+            if let Some(&trigger) = pw.trigger_to_entity.get(&a)
+                && let Some(other) = body_entity(b)
+                && other != trigger
+            {
+                pairs.insert((trigger, other));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn character_moves_in_one_tick() {
+        let mut world = World::new();
+        world.insert_resource(crate::time::Time::new());
+        world.insert_resource(PhysicsWorld2d::new());
+        let player = world.spawn((Transform::default(), CharacterController2d::new(8.0, 6.0)));
+        world
+            .get_mut::<CharacterController2d>(player)
+            .unwrap()
+            .move_and_slide(Vec2::new(120.0, 0.0));
+
+        physics_step_2d(&mut world);
+
+        let dt = world.resource::<crate::time::Time>().fixed_delta_secs();
+        let x = world.get::<Transform>(player).unwrap().translation.x;
+        assert!((x - 120.0 * dt).abs() < 1e-3, "moved to {x}");
+        let ctrl = world.get::<CharacterController2d>(player).unwrap();
+        assert!((ctrl.velocity().x - 120.0).abs() < 0.05);
+        assert!(!ctrl.on_wall_left() && !ctrl.on_wall_right());
+
+        // The next tick starts from there instead of undoing the move.
+        physics_step_2d(&mut world);
+        let x = world.get::<Transform>(player).unwrap().translation.x;
+        assert!((x - 240.0 * dt).abs() < 1e-3, "moved to {x}");
+    }
+
+    fn field(shape: ColliderShape2d, gravity: FieldGravity2d, angle: f32) -> ActiveField {
+        ActiveField {
+            center: Vec2::new(100.0, 50.0),
+            angle,
+            shape,
+            gravity,
+            mode: GravityFieldMode::Override,
+            priority: 0,
+        }
+    }
+
+    #[test]
+    fn field_contains_points_in_its_rotated_shape() {
+        let down = FieldGravity2d::Directional(Vec2::new(0.0, -10.0));
+        let ball = field(ColliderShape2d::Ball { radius: 10.0 }, down, 0.0);
+        assert!(ball.contains(Vec2::new(100.0, 59.0)));
+        assert!(!ball.contains(Vec2::new(108.0, 58.0)));
+
+        // A 40×10 box turned a quarter: tall, not wide.
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let bar = field(ColliderShape2d::Cuboid { hx: 20.0, hy: 5.0 }, down, quarter);
+        assert!(bar.contains(Vec2::new(100.0, 68.0)));
+        assert!(!bar.contains(Vec2::new(118.0, 50.0)));
+
+        let capsule = ColliderShape2d::CapsuleY { half_height: 10.0, radius: 5.0 };
+        let capsule = field(capsule, down, 0.0);
+        assert!(capsule.contains(Vec2::new(100.0, 64.0)));
+        assert!(!capsule.contains(Vec2::new(104.0, 64.0)));
+    }
+
+    #[test]
+    fn field_acceleration_turns_with_the_field_or_points_inward() {
+        let quarter = std::f32::consts::FRAC_PI_2;
+        let shape = ColliderShape2d::Ball { radius: 100.0 };
+        let down = FieldGravity2d::Directional(Vec2::new(0.0, -10.0));
+        let directional = field(shape, down, quarter);
+        let turned = directional.acceleration(Vec2::new(120.0, 50.0));
+        assert!((turned - Vec2::new(10.0, 0.0)).length() < 1e-4, "{turned}");
+
+        let radial = field(shape, FieldGravity2d::Radial { strength: 5.0 }, 0.0);
+        let pull = radial.acceleration(Vec2::new(100.0, 80.0));
+        assert!((pull - Vec2::new(0.0, -5.0)).length() < 1e-4, "{pull}");
+        assert_eq!(radial.acceleration(Vec2::new(100.0, 50.0)), Vec2::ZERO);
+    }
+
+    #[test]
+    fn resizing_or_reprioritizing_a_field_marks_its_region() {
+        let mut world = World::new();
+        let entity = world.spawn_empty();
+        let pull = FieldGravity2d::Radial { strength: 1.0 };
+        let ball = field(ColliderShape2d::Ball { radius: 10.0 }, pull, 0.0);
+        let before = HashMap::from([(entity, ball)]);
+        assert!(changed_field_regions(&before, &before).is_empty());
+
+        let bigger = ActiveField {
+            shape: ColliderShape2d::Ball { radius: 20.0 },
+            ..ball
+        };
+        let regions = changed_field_regions(&before, &HashMap::from([(entity, bigger)]));
+        assert_eq!(regions, [ball, bigger]);
+        let urgent = ActiveField { priority: 5, ..ball };
+        assert_eq!(changed_field_regions(&before, &HashMap::from([(entity, urgent)])).len(), 2);
+    }
+
+    /// A ball inside a field that pulls it up, at rest and put to sleep.
+    fn sleeper_in_a_field() -> (World, Entity, RigidBodyHandle) {
+        let mut world = World::new();
+        world.insert_resource(crate::time::Time::new());
+        world.insert_resource(PhysicsWorld2d::new());
+        let room = world.spawn((
+            Transform::default(),
+            GravityField2d::directional(ColliderShape2d::Ball { radius: 50.0 }, Vec2::Y * 10.0),
+        ));
+        let ball = world.spawn((
+            Transform::default(),
+            RigidBody2d::dynamic(),
+            Collider2d::ball(1.0),
+        ));
+        physics_step_2d(&mut world);
+        let handle = world.get::<RigidBody2d>(ball).unwrap().handle.unwrap();
+        world.resource_mut::<PhysicsWorld2d>().bodies.get_mut(handle).unwrap().sleep();
+        (world, room, handle)
+    }
+
+    fn is_sleeping(world: &World, handle: RigidBodyHandle) -> bool {
+        world.resource::<PhysicsWorld2d>().bodies.get(handle).unwrap().is_sleeping()
+    }
+
+    #[test]
+    fn moving_a_field_away_wakes_the_bodies_it_left() {
+        let (mut world, room, handle) = sleeper_in_a_field();
+        physics_step_2d(&mut world);
+        assert!(is_sleeping(&world, handle), "an unchanged field lets it sleep");
+
+        world.get_mut::<Transform>(room).unwrap().translation.x = 500.0;
+        physics_step_2d(&mut world);
+        assert!(!is_sleeping(&world, handle));
+    }
+
+    #[test]
+    fn removing_a_field_wakes_the_bodies_it_covered() {
+        let (mut world, room, handle) = sleeper_in_a_field();
+        world.remove::<GravityField2d>(room);
+        physics_step_2d(&mut world);
+        assert!(!is_sleeping(&world, handle));
+        assert!(world.resource::<PhysicsWorld2d>().gravity_fields.is_empty());
+    }
+
+    /// The main world, an extra one named "side", and the time to step them.
+    fn two_worlds() -> World {
+        let mut world = World::new();
+        world.insert_resource(crate::time::Time::new());
+        world.insert_resource(PhysicsWorld2d::new());
+        let mut extra = PhysicsWorlds2d::default();
+        extra.insert("side", PhysicsWorld2d::new());
+        world.insert_resource(extra);
+        world
+    }
+
+    fn body(world: &mut World) -> Entity {
+        world.spawn((Transform::default(), RigidBody2d::dynamic(), Collider2d::ball(1.0)))
+    }
+
+    #[test]
+    fn membership_prefers_the_component_over_the_scene() {
+        let mut world = two_worlds();
+        let main = body(&mut world);
+        let tagged = body(&mut world);
+        world.insert(tagged, SceneMarker("side".into()));
+        let unknown = body(&mut world);
+        world.insert(unknown, SceneMarker("nowhere".into()));
+        let both = body(&mut world);
+        world.insert(both, SceneMarker("nowhere".into()));
+        world.insert(both, InPhysicsWorld2d("side".into()));
+
+        let extra = world.resource_remove::<PhysicsWorlds2d>();
+        let membership = world_membership(&mut world, extra.as_ref());
+        assert_eq!(membership.get(&main), None);
+        assert_eq!(membership.get(&tagged).map(String::as_str), Some("side"));
+        assert_eq!(membership.get(&unknown), None);
+        assert_eq!(membership.get(&both).map(String::as_str), Some("side"));
+    }
+
+    #[test]
+    fn entity_moves_between_worlds() {
+        let mut world = two_worlds();
+        let mover = body(&mut world);
+        let other = body(&mut world);
+        world.insert(other, InPhysicsWorld2d("side".into()));
+        physics_step_2d(&mut world);
+
+        // The first collider of each world: the same handle in both.
+        let handle = |world: &World, entity| world.get::<Collider2d>(entity).unwrap().handle;
+        assert_eq!(handle(&world, mover), handle(&world, other));
+        let counts = |pw: &PhysicsWorld2d| (pw.bodies.len(), pw.colliders.len());
+        assert_eq!(counts(world.resource::<PhysicsWorld2d>()), (1, 1));
+        assert_eq!(counts(world.resource::<PhysicsWorlds2d>().get("side").unwrap()), (1, 1));
+
+        // Its old handle names `other`'s collider in "side"; it still gets
+        // a collider of its own there.
+        world.insert(mover, InPhysicsWorld2d("side".into()));
+        physics_step_2d(&mut world);
+        assert_eq!(counts(world.resource::<PhysicsWorld2d>()), (0, 0));
+        let side = world.resource::<PhysicsWorlds2d>().get("side").unwrap();
+        assert_eq!(counts(side), (2, 2));
+        let moved = handle(&world, mover).unwrap();
+        assert_eq!(side.collider_to_entity.get(&moved), Some(&mover));
+        assert_ne!(Some(moved), handle(&world, other));
+    }
+
+    #[test]
+    fn triggers_follow_their_shape_and_go_with_their_component() {
+        let mut world = two_worlds();
+        let zone = world.spawn((Transform::default(), TriggerVolume2d::ball(2.0)));
+        physics_step_2d(&mut world);
+        let handle = world.get::<TriggerVolume2d>(zone).unwrap().handle.unwrap();
+        let radius = |world: &World| {
+            let pw = world.resource::<PhysicsWorld2d>();
+            pw.colliders.get(handle).unwrap().shape().as_ball().unwrap().radius
+        };
+        assert_eq!(radius(&world), 2.0);
+
+        let bigger = ColliderShape2d::Ball { radius: 5.0 };
+        world.get_mut::<TriggerVolume2d>(zone).unwrap().shape = bigger;
+        physics_step_2d(&mut world);
+        assert_eq!(radius(&world), 5.0);
+        assert_eq!(world.get::<TriggerVolume2d>(zone).unwrap().handle, Some(handle));
+
+        // The entity lives on; its sensor doesn't.
+        world.remove::<TriggerVolume2d>(zone);
+        physics_step_2d(&mut world);
+        let pw = world.resource::<PhysicsWorld2d>();
+        assert!(pw.colliders.get(handle).is_none());
+        assert!(pw.trigger_to_entity.is_empty());
+    }
+}
@@ -2,15 +2,77 @@
 //!
 //! Provides rigid-body and collider components that automatically synchronize
 //! with an internal Rapier simulation. Add [`PhysicsWorld3d`] as a resource,
-//! attach [`RigidBody3d`] and [`Collider3d`] to your entities, and run
-//! [`physics_step_3d`] each frame.
+//! attach [`RigidBody3d`] and [`Collider3d`] to your entities, and add the
+//! [`Physics3d`] plugin, which steps the simulation once per
+//! [fixed tick](crate::time) and smooths [`InterpolatedTransform`]s in
+//! between.
+//!
+//! [`TriggerVolume3d`] adds a sensor with no rigid body; overlaps are
+//! reported as [`TriggerEntered`](crate::trigger::TriggerEntered) /
+//! [`TriggerExited`](crate::trigger::TriggerExited) in [`TriggerEvents`].
+//!
+//! ## Character Controller
+//!
+//! A player driven through a dynamic body tips over, slides down gentle
+//! slopes and bounces off stair edges. [`CharacterController3d`] moves a
+//! capsule the way players expect instead, using Rapier's kinematic
+//! character controller each fixed step:
+//!
+//! ```text
+//!   move_and_slide(velocity)            each fixed step
+//!   ────────────────────────            ───────────────
+//!   wanted: velocity × dt     ──►  sweep capsule ─► hit wall? slide along it
+//!                                  slope ≤ max_slope? climb it
+//!                                  ledge ≤ step_offset? step up
+//!                                  ground ≤ snap_distance below? stick to it
+//!                              ──►  Transform.translation, is_grounded()
+//! ```
+//!
+//! ```ignore
+//! fn walk(ctx: &mut Context, player: Entity) {
+//!     let dt = ctx.time.delta_secs();
+//!     let ctrl = ctx.world.get_mut::<CharacterController3d>(player).unwrap();
+//!     let mut velocity = input_direction(&ctx.input) * 5.0;
+//!     velocity.y = if ctrl.is_grounded() {
+//!         if ctx.input.just_pressed(KeyCode::Space) { 6.0 } else { 0.0 }
+//!     } else {
+//!         ctrl.velocity().y - 9.81 * dt
+//!     };
+//!     ctrl.move_and_slide(velocity);
+//! }
+//! ```
+//!
+//! The controller gets a kinematic body of its own, so dynamic bodies it
+//! walks into are pushed and [`TriggerVolume3d`]s notice it. Gravity is the
+//! caller's job, as above: the controller only goes where it's told.
+//!
+//! ## Multiple Worlds
+//!
+//! [`PhysicsWorlds3d`] holds further, independent simulations by name — a
+//! background scene, a physics toy in the UI — whose bodies never touch the
+//! main world's. Entities join one with [`InPhysicsWorld3d`], or by carrying a
+//! [`SceneMarker`] with the world's name, so a scene loaded with
+//! [`load_scene_tagged`](crate::scene::load_scene_tagged) can get a world of
+//! its own. Removing the world drops every body in it at once:
+//!
+//! ```ignore
+//! world.resource_mut::<PhysicsWorlds3d>().insert("level_2", PhysicsWorld3d::new());
+//! load_scene_tagged(world, &registry, &level_2, "level_2");
+//! // later
+//! unload_scene(world, "level_2");
+//! world.resource_mut::<PhysicsWorlds3d>().remove("level_2");
+//! ```
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use rapier3d::control::{CharacterAutostep, CharacterLength, KinematicCharacterController};
 use rapier3d::prelude::*;
 
 use crate::ecs::{Entity, World};
+use crate::interpolation::{apply_interpolation_filtered, InterpolatedTransform};
 use crate::math::{Quat, Transform};
+use crate::scene::SceneMarker;
+use crate::trigger::{TriggerEvents, TriggerOverlaps};
 
 // ── Conversion helpers ──────────────────────────────────────────────────
 
@@ -145,7 +207,7 @@ impl RigidBody3d {
 }
 
 /// Collider shape for 3D physics.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ColliderShape3d {
     Ball { radius: f32 },
     Cuboid { hx: f32, hy: f32, hz: f32 },
@@ -242,9 +304,145 @@ impl Collider3d {
     }
 }
 
+/// A trigger volume: a sensor collider that needs no rigid body.
+///
+/// Follows the entity's [`Transform`] every step and reports bodies that
+/// start or stop overlapping it through [`TriggerEvents`]. Only entities with
+/// a [`RigidBody3d`] and [`Collider3d`] are detected. Changing `shape`
+/// resizes the sensor on the next step; removing the component removes it.
+#[derive(Debug, Clone)]
+pub struct TriggerVolume3d {
+    pub shape: ColliderShape3d,
+    pub(crate) handle: Option<ColliderHandle>,
+    /// The shape the sensor was last built with.
+    built_shape: Option<ColliderShape3d>,
+}
+
+impl TriggerVolume3d {
+    /// A trigger with the given shape.
+    pub fn new(shape: ColliderShape3d) -> Self {
+        Self {
+            shape,
+            handle: None,
+            built_shape: None,
+        }
+    }
+
+    /// A spherical trigger.
+    pub fn ball(radius: f32) -> Self {
+        Self::new(ColliderShape3d::Ball { radius })
+    }
+
+    /// A box trigger (half-extents).
+    pub fn cuboid(hx: f32, hy: f32, hz: f32) -> Self {
+        Self::new(ColliderShape3d::Cuboid { hx, hy, hz })
+    }
+}
+
+/// Component: a capsule moved by [`move_and_slide`](Self::move_and_slide)
+/// that slides along walls, climbs slopes and steps, and stays on the ground.
+/// Pair with a [`Transform`] (the capsule's center) and no [`RigidBody3d`].
+/// See the [module docs](self#character-controller).
+///
+/// The capsule's size is read when the controller joins the physics world.
+#[derive(Debug, Clone)]
+pub struct CharacterController3d {
+    /// Capsule radius.
+    pub radius: f32,
+    /// Half the length of the capsule's straight middle; the capsule is
+    /// `2 * (half_height + radius)` tall.
+    pub half_height: f32,
+    /// Steepest slope it walks up, in radians; steeper ones are slid down.
+    pub max_slope: f32,
+    /// Tallest ledge it steps onto without jumping; 0 disables stepping.
+    pub step_offset: f32,
+    /// How far below it looks for ground to stick to when walking down
+    /// slopes and steps; 0 disables snapping.
+    pub snap_distance: f32,
+    /// Gap kept between the capsule and what it touches, so it doesn't get
+    /// stuck in surfaces.
+    pub skin_width: f32,
+    /// Wanted velocity, held until the next `move_and_slide`.
+    desired: Vec3,
+    /// Velocity actually achieved over the last step.
+    velocity: Vec3,
+    grounded: bool,
+    pub(crate) handle: Option<RigidBodyHandle>,
+}
+
+impl CharacterController3d {
+    /// A capsule of `half_height` and `radius`, climbing slopes up to 45°
+    /// and steps up to 0.3 units.
+    pub fn new(half_height: f32, radius: f32) -> Self {
+        Self {
+            radius,
+            half_height,
+            max_slope: 45f32.to_radians(),
+            step_offset: 0.3,
+            snap_distance: 0.2,
+            skin_width: 0.01,
+            desired: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            grounded: false,
+            handle: None,
+        }
+    }
+
+    pub fn with_max_slope(mut self, radians: f32) -> Self {
+        self.max_slope = radians;
+        self
+    }
+
+    pub fn with_step_offset(mut self, height: f32) -> Self {
+        self.step_offset = height;
+        self
+    }
+
+    pub fn with_snap_distance(mut self, distance: f32) -> Self {
+        self.snap_distance = distance;
+        self
+    }
+
+    /// Move at `velocity` (units per second), sliding along whatever is in
+    /// the way. Takes effect at the next physics step and holds until the
+    /// next call, so call it every frame.
+    pub fn move_and_slide(&mut self, velocity: Vec3) {
+        self.desired = velocity;
+    }
+
+    /// Whether it stood on walkable ground after the last step.
+    pub fn is_grounded(&self) -> bool {
+        self.grounded
+    }
+
+    /// Velocity actually achieved over the last step: the wanted velocity
+    /// less whatever walls and floors took away.
+    pub fn velocity(&self) -> Vec3 {
+        self.velocity
+    }
+
+    /// Rapier's controller configured from these settings.
+    fn rapier_controller(&self) -> KinematicCharacterController {
+        let absolute = |length: f32| (length > 0.0).then_some(CharacterLength::Absolute(length));
+        KinematicCharacterController {
+            offset: CharacterLength::Absolute(self.skin_width),
+            slide: true,
+            autostep: absolute(self.step_offset).map(|max_height| CharacterAutostep {
+                max_height,
+                min_width: CharacterLength::Absolute(self.radius * 0.5),
+                include_dynamic_bodies: false,
+            }),
+            max_slope_climb_angle: self.max_slope,
+            min_slope_slide_angle: self.max_slope,
+            snap_to_ground: absolute(self.snap_distance),
+            ..KinematicCharacterController::default()
+        }
+    }
+}
+
 // ── Resource ────────────────────────────────────────────────────────────
 
-/// The 3D physics world. Insert as a resource and run [`physics_step_3d`] each frame.
+/// The 3D physics world. Inserted by [`Physics3d`] and stepped on every fixed tick.
 pub struct PhysicsWorld3d {
     gravity: Vec3,
     pipeline: PhysicsPipeline,
@@ -259,7 +457,11 @@ pub struct PhysicsWorld3d {
     ccd_solver: CCDSolver,
     body_to_entity: HashMap<RigidBodyHandle, Entity>,
     entity_to_body: HashMap<u32, RigidBodyHandle>,
-    accumulator: f32,
+    /// Colliders of [`Collider3d`]s. Handles are only meaningful in the
+    /// world that made them, so a component's handle counts only if it's here.
+    collider_to_entity: HashMap<ColliderHandle, Entity>,
+    trigger_to_entity: HashMap<ColliderHandle, Entity>,
+    trigger_overlaps: TriggerOverlaps,
 }
 
 impl std::fmt::Debug for PhysicsWorld3d {
@@ -268,6 +470,7 @@ impl std::fmt::Debug for PhysicsWorld3d {
             .field("gravity", &self.gravity)
             .field("bodies", &self.bodies.len())
             .field("colliders", &self.colliders.len())
+            .field("triggers", &self.trigger_to_entity.len())
             .finish()
     }
 }
@@ -289,7 +492,9 @@ impl PhysicsWorld3d {
             ccd_solver: CCDSolver::new(),
             body_to_entity: HashMap::new(),
             entity_to_body: HashMap::new(),
-            accumulator: 0.0,
+            collider_to_entity: HashMap::new(),
+            trigger_to_entity: HashMap::new(),
+            trigger_overlaps: TriggerOverlaps::default(),
         }
     }
 
@@ -298,6 +503,39 @@ impl PhysicsWorld3d {
         self.gravity = g;
         self
     }
+
+    /// Entities whose solid (non-sensor) colliders cross the segment
+    /// `from → to`, nearest first. Each entity is listed once, however many
+    /// of its colliders the segment passes through. Reflects poses as of the
+    /// last step.
+    pub(crate) fn segment_hits(&self, from: Vec3, to: Vec3) -> Vec<Entity> {
+        let delta = to - from;
+        let length = delta.length();
+        if length <= f32::EPSILON {
+            return Vec::new();
+        }
+
+        let query = self.broad_phase.as_query_pipeline(
+            self.narrow_phase.query_dispatcher(),
+            &self.bodies,
+            &self.colliders,
+            QueryFilter::default().exclude_sensors(),
+        );
+        let ray = Ray::new(from, delta / length);
+        let mut hits: Vec<(f32, Entity)> = query
+            .intersect_ray(ray, length, true)
+            .filter_map(|(_handle, collider, hit)| {
+                let entity = self.body_to_entity.get(&collider.parent()?)?;
+                Some((hit.time_of_impact, *entity))
+            })
+            .collect();
+        hits.sort_by(|a, b| a.0.total_cmp(&b.0));
+        let mut seen = HashSet::new();
+        hits.into_iter()
+            .map(|(_, entity)| entity)
+            .filter(|entity| seen.insert(*entity))
+            .collect()
+    }
 }
 
 impl Default for PhysicsWorld3d {
@@ -306,9 +544,51 @@ impl Default for PhysicsWorld3d {
     }
 }
 
+/// Component: simulate this entity in the named world of
+/// [`PhysicsWorlds3d`] instead of the main [`PhysicsWorld3d`]. Moving an
+/// entity to another world rebuilds its body there.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InPhysicsWorld3d(pub String);
+
+/// Resource: additional 3D physics worlds, keyed by name. Each steps
+/// independently of the main [`PhysicsWorld3d`] and of each other.
+///
+/// Entities join a world through [`InPhysicsWorld3d`], or — without one —
+/// through a [`SceneMarker`] naming it.
+#[derive(Debug, Default)]
+pub struct PhysicsWorlds3d {
+    worlds: HashMap<String, PhysicsWorld3d>,
+}
+
+impl PhysicsWorlds3d {
+    /// Add a world, returning the one it replaces.
+    pub fn insert(&mut self, name: impl Into<String>, world: PhysicsWorld3d) -> Option<PhysicsWorld3d> {
+        self.worlds.insert(name.into(), world)
+    }
+
+    /// Remove a world, dropping all of its bodies and colliders.
+    pub fn remove(&mut self, name: &str) -> Option<PhysicsWorld3d> {
+        self.worlds.remove(name)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&PhysicsWorld3d> {
+        self.worlds.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut PhysicsWorld3d> {
+        self.worlds.get_mut(name)
+    }
+
+    /// Names of all worlds, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.worlds.keys().map(String::as_str)
+    }
+}
+
 // ── Plugin ──────────────────────────────────────────────────────────────
 
-/// Plugin that registers the 3D physics resource and update system.
+/// Plugin that registers the 3D physics resources, a fixed-update system
+/// stepping them, and an update system interpolating between steps.
 ///
 /// # Example
 ///
@@ -323,41 +603,118 @@ pub struct Physics3d;
 impl crate::game::Plugin for Physics3d {
     fn build(&self, game: &mut crate::game::Game) {
         game.insert_resource(PhysicsWorld3d::new());
-        game.add_update_system(|ctx| physics_step_3d(&mut ctx.world));
+        game.insert_resource(PhysicsWorlds3d::default());
+        game.insert_resource(TriggerEvents::default());
+        game.add_fixed_update_system(|ctx| physics_step_3d(&mut ctx.world));
+        game.add_update_system(|ctx| interpolate_physics_3d(&mut ctx.world));
     }
 }
 
 // ── System ──────────────────────────────────────────────────────────────
 
-/// Advance the 3D physics simulation by one frame.
+/// Advance the 3D physics simulation by one fixed tick of
+/// [`Time::fixed_delta_secs`](crate::time::Time::fixed_delta_secs).
 ///
 /// Uses the extract/reinsert pattern to borrow the physics world and the ECS
-/// world simultaneously. Physics runs with a fixed timestep (default 1/60s)
-/// using an accumulator to decouple simulation from frame rate.
+/// world simultaneously. Runs as a fixed-update system, so the tick rate
+/// and catch-up limit are the ones set on [`Time`](crate::time::Time). The main
+/// world and each of [`PhysicsWorlds3d`] are stepped in turn, each seeing
+/// only its own entities. Nothing is stepped while
+/// [`Subsystems::physics`](crate::subsystems::Subsystems::physics) is off.
 pub(crate) fn physics_step_3d(world: &mut World) {
-    let frame_dt = world.resource::<crate::time::Time>().delta_secs();
-    if frame_dt <= 0.0 {
+    if !crate::subsystems::Subsystems::of(world).physics {
         return;
     }
-
-    let Some(mut pw) = world.resource_remove::<PhysicsWorld3d>() else {
+    let dt = world.resource::<crate::time::Time>().fixed_delta_secs();
+    if !world.has_resource::<PhysicsWorld3d>() && !world.has_resource::<PhysicsWorlds3d>() {
         return;
-    };
+    }
+
+    let frame = world.resource::<crate::time::Time>().frame_count();
+    if !world.has_resource::<TriggerEvents>() {
+        world.insert_resource(TriggerEvents::default());
+    }
+    world.resource_mut::<TriggerEvents>().begin_frame(frame);
 
-    // Add frame delta to accumulator, capped to prevent spiral of death.
-    pw.accumulator += frame_dt.min(0.25);
+    let mut extra = world.resource_remove::<PhysicsWorlds3d>();
+    let membership = world_membership(world, extra.as_ref());
 
-    // If not enough time has accumulated for a single step, bail early.
-    if pw.accumulator < pw.params.dt {
+    if let Some(mut pw) = world.resource_remove::<PhysicsWorld3d>() {
+        step_world(world, &mut pw, dt, &|e: Entity| !membership.contains_key(&e));
         world.insert_resource(pw);
-        return;
     }
+    if let Some(extra) = &mut extra {
+        for (name, pw) in &mut extra.worlds {
+            step_world(world, pw, dt, &|e: Entity| membership.get(&e) == Some(name));
+        }
+    }
+    if let Some(extra) = extra {
+        world.insert_resource(extra);
+    }
+}
 
-    // 1. Cleanup: remove bodies whose entities have been despawned.
+/// Blend every physics body's [`InterpolatedTransform`] between its last
+/// two steps by [`Time::fixed_alpha`](crate::time::Time::fixed_alpha). Runs
+/// every frame, including frames without a fixed tick.
+pub(crate) fn interpolate_physics_3d(world: &mut World) {
+    let alpha = world.resource::<crate::time::Time>().fixed_alpha();
+    let mut bodies = HashSet::new();
+    world.query::<(&RigidBody3d, &InterpolatedTransform)>(|entity, _| {
+        bodies.insert(entity);
+    });
+    if !bodies.is_empty() {
+        apply_interpolation_filtered(world, alpha, false, |e| bodies.contains(&e));
+    }
+}
+
+/// Which extra world each entity belongs to. Entities not in the map use the
+/// main world; [`InPhysicsWorld3d`] wins over a [`SceneMarker`].
+fn world_membership(world: &mut World, extra: Option<&PhysicsWorlds3d>) -> HashMap<Entity, String> {
+    let mut membership = HashMap::new();
+    if let Some(extra) = extra {
+        world.query::<(&SceneMarker,)>(|entity, (marker,)| {
+            if extra.worlds.contains_key(&marker.0) {
+                membership.insert(entity, marker.0.clone());
+            }
+        });
+    }
+    world.query::<(&InPhysicsWorld3d,)>(|entity, (member,)| {
+        membership.insert(entity, member.0.clone());
+    });
+    membership
+}
+
+/// Name of the extra world `entity` belongs to, by the same rules as
+/// [`world_membership`]. `None` means the main world.
+pub(crate) fn membership_of(
+    world: &World,
+    extra: Option<&PhysicsWorlds3d>,
+    entity: Entity,
+) -> Option<String> {
+    if let Some(member) = world.get::<InPhysicsWorld3d>(entity) {
+        return Some(member.0.clone());
+    }
+    let marker = world.get::<SceneMarker>(entity)?;
+    extra
+        .is_some_and(|extra| extra.worlds.contains_key(&marker.0))
+        .then(|| marker.0.clone())
+}
+
+/// Step one physics world by `dt` with the entities `in_world` accepts.
+fn step_world(
+    world: &mut World,
+    pw: &mut PhysicsWorld3d,
+    dt: f32,
+    in_world: &dyn Fn(Entity) -> bool,
+) {
+    pw.params.dt = dt;
+
+    // 1. Cleanup: remove bodies whose entities have been despawned or
+    //    moved to another world.
     let dead: Vec<RigidBodyHandle> = pw
         .body_to_entity
         .iter()
-        .filter(|(_h, e)| !world.is_alive(**e))
+        .filter(|(_h, e)| !world.is_alive(**e) || !in_world(**e))
         .map(|(h, _e)| *h)
         .collect();
     for handle in dead {
@@ -373,13 +730,38 @@ pub(crate) fn physics_step_3d(world: &mut World) {
             true,
         );
     }
+    // Removing a body removed its colliders too.
+    let colliders = &pw.colliders;
+    pw.collider_to_entity.retain(|handle, _| colliders.contains(*handle));
 
-    // 2. Discover new rigid bodies (handle is None).
+    // Remove trigger colliders whose entities have been despawned or moved,
+    // or no longer have this trigger (the component was removed or replaced).
+    let dead_triggers: Vec<(ColliderHandle, Entity)> = pw
+        .trigger_to_entity
+        .iter()
+        .filter(|(h, e)| {
+            !world.is_alive(**e)
+                || !in_world(**e)
+                || world
+                    .get::<TriggerVolume3d>(**e)
+                    .is_none_or(|trigger| trigger.handle != Some(**h))
+        })
+        .map(|(h, e)| (*h, *e))
+        .collect();
+    for (handle, entity) in dead_triggers {
+        pw.trigger_to_entity.remove(&handle);
+        pw.trigger_overlaps.forget_trigger(entity);
+        pw.colliders.remove(handle, &mut pw.islands, &mut pw.bodies, false);
+    }
+
+    // 2. Discover new rigid bodies: no handle yet, or a handle from another
+    //    world.
     {
         let mut new_bodies: Vec<(Entity, RigidBodyType3d, Vec3, Vec3, f32, f32, f32, bool, Vec3, Quat)> =
             Vec::new();
         world.query::<(&RigidBody3d, &Transform)>(|entity, (rb, tf)| {
-            if rb.handle.is_none() {
+            let known = rb.handle.is_some_and(|h| pw.body_to_entity.get(&h) == Some(&entity));
+            if in_world(entity) && !known {
                 new_bodies.push((
                     entity,
                     rb.body_type,
@@ -416,13 +798,21 @@ pub(crate) fn physics_step_3d(world: &mut World) {
         }
     }
 
-    // 3. Discover new colliders (handle is None, parent body already registered).
+    // 3. Discover new colliders (no handle from this world, parent body
+    //    already registered).
     {
         let mut new_colliders: Vec<(Entity, ColliderShape3d, f32, f32, f32, bool, RigidBodyHandle)> =
             Vec::new();
         world.query::<(&Collider3d, &RigidBody3d)>(|entity, (coll, rb)| {
-            if coll.handle.is_none() {
-                if let Some(body_handle) = rb.handle {
+            if !in_world(entity) {
+                return;
+            }
+            if let Some(body_handle) = rb.handle {
+                let attached = coll.handle.is_some_and(|h| {
+                    pw.collider_to_entity.get(&h) == Some(&entity)
+                        && pw.colliders.get(h).is_some_and(|c| c.parent() == Some(body_handle))
+                });
+                if !attached {
                     new_colliders.push((
                         entity,
                         coll.shape,
@@ -445,17 +835,65 @@ pub(crate) fn physics_step_3d(world: &mut World) {
             let handle =
                 pw.colliders
                     .insert_with_parent(coll, body_handle, &mut pw.bodies);
+            pw.collider_to_entity.insert(handle, entity);
             if let Some(comp) = world.get_mut::<Collider3d>(entity) {
                 comp.handle = Some(handle);
             }
         }
     }
 
+    // 3b. Trigger volumes: parentless sensor colliders that follow their Transform.
+    {
+        let mut triggers: Vec<(Entity, Option<ColliderHandle>, ColliderShape3d, bool, Vec3, Quat)> =
+            Vec::new();
+        world.query::<(&TriggerVolume3d, &Transform)>(|entity, (trigger, tf)| {
+            if !in_world(entity) {
+                return;
+            }
+            let handle = trigger.handle.filter(|h| pw.trigger_to_entity.get(h) == Some(&entity));
+            let reshape = trigger.built_shape != Some(trigger.shape);
+            triggers.push((entity, handle, trigger.shape, reshape, tf.translation, tf.rotation));
+        });
+        for (entity, handle, shape, reshape, pos, rot) in triggers {
+            let pose = Pose::from_parts(pos, rot);
+            match handle.and_then(|h| pw.colliders.get_mut(h)) {
+                Some(coll) => {
+                    coll.set_position(pose);
+                    if reshape {
+                        coll.set_shape(shape_to_collider_builder(&shape).shape);
+                    }
+                }
+                None => {
+                    // A parentless collider counts as fixed, and fixed–kinematic
+                    // pairs are skipped by default; triggers should still
+                    // notice kinematic characters.
+                    let coll = shape_to_collider_builder(&shape)
+                        .sensor(true)
+                        .active_collision_types(ActiveCollisionTypes::all())
+                        .position(pose)
+                        .build();
+                    let handle = pw.colliders.insert(coll);
+                    pw.trigger_to_entity.insert(handle, entity);
+                    if let Some(comp) = world.get_mut::<TriggerVolume3d>(entity) {
+                        comp.handle = Some(handle);
+                    }
+                }
+            }
+            if let Some(comp) = world.get_mut::<TriggerVolume3d>(entity) {
+                comp.built_shape = Some(shape);
+            }
+        }
+    }
+
+    // 3c. Character controllers: an upright kinematic capsule each, placed
+    //     at the Transform so systems can teleport them between steps.
+    let characters = register_characters(world, pw, in_world);
+
     // 4. Sync kinematic bodies: push Transform → Rapier.
     {
         let mut kinematic_updates: Vec<(RigidBodyHandle, Vec3, Quat)> = Vec::new();
-        world.query::<(&RigidBody3d, &Transform)>(|_entity, (rb, tf)| {
-            if rb.body_type == RigidBodyType3d::KinematicPositionBased {
+        world.query::<(&RigidBody3d, &Transform)>(|entity, (rb, tf)| {
+            if in_world(entity) && rb.body_type == RigidBodyType3d::KinematicPositionBased {
                 if let Some(handle) = rb.handle {
                     kinematic_updates.push((handle, tf.translation, tf.rotation));
                 }
@@ -468,30 +906,59 @@ pub(crate) fn physics_step_3d(world: &mut World) {
         }
     }
 
-    // 5. Step the simulation with fixed dt, consuming the accumulator.
-    let fixed_dt = pw.params.dt;
-    while pw.accumulator >= fixed_dt {
-        pw.pipeline.step(
-            pw.gravity,
-            &pw.params,
-            &mut pw.islands,
-            &mut pw.broad_phase,
-            &mut pw.narrow_phase,
-            &mut pw.bodies,
-            &mut pw.colliders,
-            &mut pw.impulse_joints,
-            &mut pw.multibody_joints,
-            &mut pw.ccd_solver,
-            &(),
-            &(),
-        );
-        pw.accumulator -= fixed_dt;
+    // 5. Step the simulation. Characters pick their move first, so the
+    //    step carries their kinematic bodies there.
+    let moved = move_characters(world, pw, &characters, dt);
+    pw.pipeline.step(
+        pw.gravity,
+        &pw.params,
+        &mut pw.islands,
+        &mut pw.broad_phase,
+        &mut pw.narrow_phase,
+        &mut pw.bodies,
+        &mut pw.colliders,
+        &mut pw.impulse_joints,
+        &mut pw.multibody_joints,
+        &mut pw.ccd_solver,
+        &(),
+        &(),
+    );
+
+    if !pw.trigger_to_entity.is_empty() {
+        let pairs = trigger_pairs(pw);
+        pw.trigger_overlaps
+            .update(pairs, world.resource_mut::<TriggerEvents>());
+    }
+
+    // 5b. Interpolated bodies record their pose after every step, so the
+    //     last two steps form the previous/current pair.
+    let mut interpolated: Vec<(Entity, RigidBodyHandle)> = Vec::new();
+    world.query::<(&RigidBody3d, &InterpolatedTransform)>(|entity, (rb, _interp)| {
+        if in_world(entity) && rb.body_type != RigidBodyType3d::KinematicPositionBased {
+            if let Some(handle) = rb.handle {
+                interpolated.push((entity, handle));
+            }
+        }
+    });
+    for (entity, handle) in interpolated {
+        if let Some(body) = pw.bodies.get(handle) {
+            let pose = crate::interpolation::Pose {
+                translation: body.translation(),
+                rotation: *body.rotation(),
+            };
+            if let Some(interp) = world.get_mut::<InterpolatedTransform>(entity) {
+                interp.push(pose);
+            }
+        }
     }
 
     // 6. Sync dynamic/kinematic-velocity bodies: pull Rapier → Transform.
     {
         let mut sync_updates: Vec<(Entity, Vec3, Quat)> = Vec::new();
         world.query::<(&RigidBody3d,)>(|entity, (rb,)| {
+            if !in_world(entity) {
+                return;
+            }
             if rb.body_type == RigidBodyType3d::Dynamic
                 || rb.body_type == RigidBodyType3d::KinematicVelocityBased
             {
@@ -512,6 +979,222 @@ pub(crate) fn physics_step_3d(world: &mut World) {
         }
     }
 
-    world.insert_resource(pw);
+    // 6b. Character controllers: where each moved → Transform.
+    for (entity, position) in moved {
+        if let Some(tf) = world.get_mut::<Transform>(entity) {
+            tf.translation = position;
+        }
+    }
 }
 
+/// Give new [`CharacterController3d`]s a body and capsule in this world, and
+/// move every character's body to its Transform. Returns the characters.
+fn register_characters(
+    world: &mut World,
+    pw: &mut PhysicsWorld3d,
+    in_world: &dyn Fn(Entity) -> bool,
+) -> Vec<(Entity, RigidBodyHandle)> {
+    let mut found: Vec<(Entity, Option<RigidBodyHandle>, f32, f32, Vec3)> = Vec::new();
+    world.query::<(&CharacterController3d, &Transform)>(|entity, (ctrl, tf)| {
+        if in_world(entity) {
+            let handle = ctrl.handle.filter(|h| pw.body_to_entity.get(h) == Some(&entity));
+            found.push((entity, handle, ctrl.half_height, ctrl.radius, tf.translation));
+        }
+    });
+
+    let mut characters = Vec::with_capacity(found.len());
+    for (entity, handle, half_height, radius, pos) in found {
+        let handle = match handle {
+            Some(handle) => {
+                if let Some(body) = pw.bodies.get_mut(handle) {
+                    body.set_translation(pos, true);
+                    body.set_next_kinematic_translation(pos);
+                }
+                handle
+            }
+            None => {
+                let body = RigidBodyBuilder::kinematic_position_based().translation(pos).build();
+                let handle = pw.bodies.insert(body);
+                let capsule = ColliderBuilder::capsule_y(half_height, radius).build();
+                pw.colliders.insert_with_parent(capsule, handle, &mut pw.bodies);
+                pw.body_to_entity.insert(handle, entity);
+                pw.entity_to_body.insert(entity.index(), handle);
+                if let Some(ctrl) = world.get_mut::<CharacterController3d>(entity) {
+                    ctrl.handle = Some(handle);
+                }
+                handle
+            }
+        };
+        characters.push((entity, handle));
+    }
+    characters
+}
+
+/// Pick each character's move for the coming step along its wanted
+/// velocity, with Rapier's character controller resolving what's in the
+/// way, and send its kinematic body there. Returns where each ends up.
+fn move_characters(
+    world: &mut World,
+    pw: &mut PhysicsWorld3d,
+    characters: &[(Entity, RigidBodyHandle)],
+    dt: f32,
+) -> Vec<(Entity, Vec3)> {
+    let mut moved = Vec::with_capacity(characters.len());
+    for &(entity, handle) in characters {
+        let Some(ctrl) = world.get::<CharacterController3d>(entity) else {
+            continue;
+        };
+        let Some(body) = pw.bodies.get(handle) else {
+            continue;
+        };
+        let start = body.translation();
+        let shape = SharedShape::capsule_y(ctrl.half_height, ctrl.radius);
+        let query = pw.broad_phase.as_query_pipeline(
+            pw.narrow_phase.query_dispatcher(),
+            &pw.bodies,
+            &pw.colliders,
+            QueryFilter::default().exclude_sensors().exclude_rigid_body(handle),
+        );
+        let movement = ctrl.rapier_controller().move_shape(
+            dt,
+            &query,
+            &*shape,
+            &Pose::from_parts(start, Quat::IDENTITY),
+            ctrl.desired * dt,
+            |_| {},
+        );
+
+        let end = start + movement.translation;
+        if let Some(body) = pw.bodies.get_mut(handle) {
+            body.set_next_kinematic_translation(end);
+        }
+        if let Some(ctrl) = world.get_mut::<CharacterController3d>(entity) {
+            ctrl.grounded = movement.grounded;
+            ctrl.velocity = movement.translation / dt;
+        }
+        moved.push((entity, end));
+    }
+    moved
+}
+
+/// All `(trigger, other)` entity pairs currently overlapping. Only colliders
+/// attached to rigid bodies count as `other`, so overlapping triggers don't
+/// report each other.
+fn trigger_pairs(pw: &PhysicsWorld3d) -> HashSet<(Entity, Entity)> {
+    let body_entity = |handle: ColliderHandle| {
+        let body = pw.colliders.get(handle)?.parent()?;
+        pw.body_to_entity.get(&body).copied()
+    };
+
+    let mut pairs = HashSet::new();
+    for (h1, h2, intersecting) in pw.narrow_phase.intersection_pairs() {
+        if !intersecting {
+            continue;
+        }
+        for (a, b) in [(h1, h2), (h2, h1)] {
+            if let Some(&trigger) = pw.trigger_to_entity.get(&a)
+                && let Some(other) = body_entity(b)
+                && other != trigger
+            {
+                pairs.insert((trigger, other));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn character_moves_in_one_tick() {
+        let mut world = World::new();
+        world.insert_resource(crate::time::Time::new());
+        world.insert_resource(PhysicsWorld3d::new());
+        let player = world.spawn((Transform::default(), CharacterController3d::new(0.5, 0.3)));
+        world
+            .get_mut::<CharacterController3d>(player)
+            .unwrap()
+            .move_and_slide(Vec3::new(6.0, 0.0, 0.0));
+
+        physics_step_3d(&mut world);
+
+        let dt = world.resource::<crate::time::Time>().fixed_delta_secs();
+        let x = world.get::<Transform>(player).unwrap().translation.x;
+        assert!((x - 6.0 * dt).abs() < 1e-3, "moved to {x}");
+        let ctrl = world.get::<CharacterController3d>(player).unwrap();
+        assert!((ctrl.velocity().x - 6.0).abs() < 0.05);
+
+        // The next tick starts from there instead of undoing the move.
+        physics_step_3d(&mut world);
+        let x = world.get::<Transform>(player).unwrap().translation.x;
+        assert!((x - 12.0 * dt).abs() < 1e-3, "moved to {x}");
+    }
+
+    /// The main world, an extra one named "side", and the time to step them.
+    fn two_worlds() -> World {
+        let mut world = World::new();
+        world.insert_resource(crate::time::Time::new());
+        world.insert_resource(PhysicsWorld3d::new());
+        let mut extra = PhysicsWorlds3d::default();
+        extra.insert("side", PhysicsWorld3d::new());
+        world.insert_resource(extra);
+        world
+    }
+
+    fn body(world: &mut World) -> Entity {
+        world.spawn((Transform::default(), RigidBody3d::dynamic(), Collider3d::ball(1.0)))
+    }
+
+    #[test]
+    fn membership_prefers_the_component_over_the_scene() {
+        let mut world = two_worlds();
+        let main = body(&mut world);
+        let tagged = body(&mut world);
+        world.insert(tagged, SceneMarker("side".into()));
+        let unknown = body(&mut world);
+        world.insert(unknown, SceneMarker("nowhere".into()));
+        let both = body(&mut world);
+        world.insert(both, SceneMarker("nowhere".into()));
+        world.insert(both, InPhysicsWorld3d("side".into()));
+
+        let extra = world.resource_remove::<PhysicsWorlds3d>();
+        let membership = world_membership(&mut world, extra.as_ref());
+        assert_eq!(membership.get(&main), None);
+        assert_eq!(membership.get(&tagged).map(String::as_str), Some("side"));
+        assert_eq!(membership.get(&unknown), None);
+        assert_eq!(membership.get(&both).map(String::as_str), Some("side"));
+        for entity in [main, tagged, unknown, both] {
+            let single = membership_of(&world, extra.as_ref(), entity);
+            assert_eq!(single.as_ref(), membership.get(&entity));
+        }
+    }
+
+    #[test]
+    fn entity_moves_between_worlds() {
+        let mut world = two_worlds();
+        let mover = body(&mut world);
+        let other = body(&mut world);
+        world.insert(other, InPhysicsWorld3d("side".into()));
+        physics_step_3d(&mut world);
+
+        // The first collider of each world: the same handle in both.
+        let handle = |world: &World, entity| world.get::<Collider3d>(entity).unwrap().handle;
+        assert_eq!(handle(&world, mover), handle(&world, other));
+        let counts = |pw: &PhysicsWorld3d| (pw.bodies.len(), pw.colliders.len());
+        assert_eq!(counts(world.resource::<PhysicsWorld3d>()), (1, 1));
+        assert_eq!(counts(world.resource::<PhysicsWorlds3d>().get("side").unwrap()), (1, 1));
+
+        // Its old handle names `other`'s collider in "side"; it still gets
+        // a collider of its own there.
+        world.insert(mover, InPhysicsWorld3d("side".into()));
+        physics_step_3d(&mut world);
+        assert_eq!(counts(world.resource::<PhysicsWorld3d>()), (0, 0));
+        let side = world.resource::<PhysicsWorlds3d>().get("side").unwrap();
+        assert_eq!(counts(side), (2, 2));
+        let moved = handle(&world, mover).unwrap();
+        assert_eq!(side.collider_to_entity.get(&moved), Some(&mover));
+        assert_ne!(Some(moved), handle(&world, other));
+    }
+}
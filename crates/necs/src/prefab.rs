@@ -0,0 +1,333 @@
+//! # Prefabs — Entity Templates with Per-Instance Overrides
+//!
+//! A prefab is an entity and its descendants captured once and spawned as
+//! many times as needed: a goblin with its sword and health bar, a street
+//! lamp with its light. Each instance can override a few component fields
+//! without copying the whole template.
+//!
+//! ```text
+//!   goblin ─┬─ sword            Prefab::from_entity(world, goblin)
+//!           └─ health_bar             │
+//!                                     ▼
+//!                         Prefab (scene format, root = id 0)
+//!                           0 goblin   { Health: 10, Transform: .. }
+//!                           1 sword    { Damage: 2 }
+//!                           2 bar      { .. }
+//!                                     │
+//!   world.instantiate_with(&prefab, &PrefabOverrides::new()
+//!       .set("Health", json!(30))          ← root
+//!       .set_on(1, "Damage", json!(5)))    ← sword
+//!                                     │
+//!                                     ▼
+//!                     goblin' ─┬─ sword'   (Damage 5)
+//!                    (Health 30)└─ bar'
+//! ```
+//!
+//! Prefabs are built on the [`SceneRegistry`]: only registered components
+//! are captured, and instantiating decodes them the same way a scene load
+//! does. The world needs the registry as a resource
+//! (`Game::resource(registry)`).
+//!
+//! A prefab *is* a scene — [`Prefab::save`] writes an ordinary scene file
+//! and [`Prefab::load`] reads one, old format versions included.
+//! [`PrefabOverrides`] use the same JSON as scene components, keyed by
+//! prefab entity id, so they can be stored next to the scene that places
+//! the instances. Objects are merged field by field (override
+//! `{"max": 30}` and the other fields of `Health` keep the prefab's
+//! values); anything else replaces the prefab's value outright, and a
+//! component the prefab entity doesn't have is added.
+//!
+//! Entity names and [`Uid`](crate::scene::Uid)s are left out of captured
+//! prefabs, since each must be unique in the world. Tags are kept.
+//!
+//! [`Template`](crate::scene_builder::Template) is the code-side cousin: a
+//! blueprint assembled from typed values. A prefab is captured from a live
+//! entity and kept as data, so it can be saved, loaded and overridden.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Prefab assets with per-instance property modifications
+//!   recorded as overrides; nested prefabs and variants on top.
+//! - **Bevy**: No prefab type; spawning a scene asset (`SceneRoot`)
+//!   repeatedly fills the role, without per-instance overrides.
+//! - **Godot**: Any saved scene can be instanced; edited properties on the
+//!   instance are stored in the parent scene as overrides.
+//! - **Our approach**: Godot's "a prefab is a scene", with Unity-style
+//!   field overrides as plain JSON merged before decoding.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ecs::hierarchy::Children;
+use crate::ecs::world::World;
+use crate::ecs::Entity;
+use crate::scene::{
+    SceneData, SceneEntity, SceneError, SceneLoadMode, SceneRegistry, Uid, parse_scene,
+    save_entity, spawn_scene,
+};
+
+// ── Prefab ───────────────────────────────────────────────────────────────
+
+/// An entity template: a root entity and its descendants in scene format.
+/// Spawn copies with [`World::instantiate`] / [`World::instantiate_with`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Prefab {
+    scene: SceneData,
+}
+
+impl Prefab {
+    /// Capture `entity` and all its descendants, using the world's
+    /// [`SceneRegistry`] resource. The root gets id 0 and descendants are
+    /// numbered depth-first.
+    ///
+    /// Returns `None` (and logs why) if the entity is dead or the world has
+    /// no registry.
+    pub fn from_entity(world: &World, entity: Entity) -> Option<Prefab> {
+        if !world.is_alive(entity) {
+            log::warn!("Cannot make a prefab from dead entity {entity:?}");
+            return None;
+        }
+        let Some(registry) = world.get_resource::<SceneRegistry>() else {
+            log::warn!("Cannot make a prefab: no SceneRegistry resource");
+            return None;
+        };
+        let (mut prefab, _) = snapshot(world, registry, entity);
+        for scene_entity in &mut prefab.scene.entities {
+            scene_entity.name = None;
+            scene_entity.uid = None;
+        }
+        Some(prefab)
+    }
+
+    /// Use a scene as a prefab. Its first entity is the root; the rest
+    /// should be reachable from it through `children`.
+    pub fn from_scene(scene: SceneData) -> Self {
+        Self { scene }
+    }
+
+    /// The prefab's entities, root first.
+    pub fn scene(&self) -> &SceneData {
+        &self.scene
+    }
+
+    /// Read a prefab from a scene file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Prefab, SceneError> {
+        let json = std::fs::read_to_string(path).map_err(|e| SceneError::Io(e.to_string()))?;
+        parse_scene(&json).map(Prefab::from_scene)
+    }
+
+    /// Write the prefab as a scene file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.scene)?;
+        std::fs::write(path, json)
+    }
+}
+
+/// Capture `entity` and its descendants with their names, for putting the
+/// same entities back later (editor undo). Also returns the captured
+/// entities, indexed by prefab id.
+pub(crate) fn snapshot(world: &World, registry: &SceneRegistry, entity: Entity) -> (Prefab, Vec<Entity>) {
+    let mut entities = Vec::new();
+    let mut order = Vec::new();
+    capture(world, registry, entity, &mut entities, &mut order);
+    let prefab = Prefab {
+        scene: SceneData {
+            entities,
+            ..Default::default()
+        },
+    };
+    (prefab, order)
+}
+
+/// Push `entity` and its descendants onto `out` (and `order`), numbering
+/// them in order. Returns `entity`'s id.
+fn capture(
+    world: &World,
+    registry: &SceneRegistry,
+    entity: Entity,
+    out: &mut Vec<SceneEntity>,
+    order: &mut Vec<Entity>,
+) -> u32 {
+    let id = out.len() as u32;
+    let mut scene_entity = save_entity(world, registry, entity, world.component_type_ids(entity));
+    scene_entity.id = id;
+    // Keep only a real Uid; the one hashed from names is for scene files.
+    scene_entity.uid = world.get::<Uid>(entity).copied();
+    out.push(scene_entity);
+    order.push(entity);
+
+    let children = world.get::<Children>(entity).map(|c| c.0.clone()).unwrap_or_default();
+    let child_ids = children
+        .into_iter()
+        .filter(|&child| world.is_alive(child))
+        .map(|child| capture(world, registry, child, out, order))
+        .collect();
+    out[id as usize].children = child_ids;
+    id
+}
+
+// ── Overrides ────────────────────────────────────────────────────────────
+
+/// Per-instance changes to a prefab: component JSON keyed by prefab entity
+/// id, merged over the prefab's values when instantiating.
+///
+/// ```ignore
+/// let overrides = PrefabOverrides::new()
+///     .set("Health", json!({ "max": 30 }))
+///     .set_on(1, "Damage", json!(5));
+/// world.instantiate_with(&goblin, &overrides);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PrefabOverrides(pub BTreeMap<u32, HashMap<String, serde_json::Value>>);
+
+impl PrefabOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override a component on the root (id 0).
+    pub fn set(self, component: &str, value: serde_json::Value) -> Self {
+        self.set_on(0, component, value)
+    }
+
+    /// Override a component on the prefab entity with id `id`.
+    pub fn set_on(mut self, id: u32, component: &str, value: serde_json::Value) -> Self {
+        self.0.entry(id).or_default().insert(component.to_string(), value);
+        self
+    }
+
+    /// Whether nothing is overridden.
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(HashMap::is_empty)
+    }
+
+    /// The prefab's scene with these overrides merged in.
+    fn apply(&self, scene: &SceneData) -> SceneData {
+        let mut scene = scene.clone();
+        for (&id, components) in &self.0 {
+            let Some(entity) = scene.entities.iter_mut().find(|e| e.id == id) else {
+                log::warn!("Prefab override: no entity with id {id} in the prefab");
+                continue;
+            };
+            for (name, value) in components {
+                merge(
+                    entity
+                        .components
+                        .entry(name.clone())
+                        .or_insert(serde_json::Value::Null),
+                    value,
+                );
+            }
+        }
+        scene
+    }
+}
+
+/// Merge `patch` into `base`: objects field by field, anything else replaced.
+fn merge(base: &mut serde_json::Value, patch: &serde_json::Value) {
+    match (base, patch) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge(base.entry(key.clone()).or_insert(serde_json::Value::Null), value);
+            }
+        }
+        (base, patch) => *base = patch.clone(),
+    }
+}
+
+// ── Instantiation ────────────────────────────────────────────────────────
+
+/// Spawn a copy of `prefab` with `overrides` applied. See
+/// [`World::instantiate_with`].
+pub(crate) fn instantiate(
+    world: &mut World,
+    prefab: &Prefab,
+    overrides: &PrefabOverrides,
+) -> Option<Entity> {
+    let Some(root) = prefab.scene.entities.first().map(|e| e.id) else {
+        log::warn!("Cannot instantiate an empty prefab");
+        return None;
+    };
+    let Some(registry) = world.resource_remove::<SceneRegistry>() else {
+        log::warn!("Cannot instantiate a prefab: no SceneRegistry resource");
+        return None;
+    };
+    let scene = overrides.apply(&prefab.scene);
+    let spawned = spawn_scene(world, &registry, &scene, SceneLoadMode::Strict, None);
+    world.insert_resource(registry);
+    spawned.ok()?.get(&root).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::hierarchy::Parent;
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct Health {
+        current: u32,
+        max: u32,
+    }
+
+    #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+    struct Damage(u32);
+
+    fn world_with_goblin() -> (World, Entity) {
+        let mut registry = SceneRegistry::new();
+        registry.register::<Health>();
+        registry.register::<Damage>();
+        let mut world = World::new();
+        world.insert_resource(registry);
+
+        let goblin = world.spawn((Health { current: 10, max: 10 },));
+        world.name_entity(goblin, "goblin");
+        world.tag(goblin, "enemy");
+        world.spawn_child(goblin, (Damage(2),));
+        (world, goblin)
+    }
+
+    #[test]
+    fn instances_copy_the_hierarchy() {
+        let (mut world, goblin) = world_with_goblin();
+        let prefab = Prefab::from_entity(&world, goblin).unwrap();
+        assert_eq!(prefab.scene().entities.len(), 2);
+        assert_eq!(prefab.scene().entities[0].children, [1]);
+
+        let copy = world.instantiate(&prefab).unwrap();
+        assert_ne!(copy, goblin);
+        assert_eq!(world.get::<Health>(copy), Some(&Health { current: 10, max: 10 }));
+        assert!(world.has_tag(copy, "enemy"));
+        assert_eq!(world.entity_name(copy), None);
+        let sword = world.get::<Children>(copy).unwrap().0[0];
+        assert_eq!(world.get::<Damage>(sword), Some(&Damage(2)));
+        assert_eq!(world.get::<Parent>(sword).map(|p| p.0), Some(copy));
+        assert!(world.has_resource::<SceneRegistry>());
+    }
+
+    #[test]
+    fn overrides_merge_fields_and_survive_json() {
+        let (mut world, goblin) = world_with_goblin();
+        let prefab = Prefab::from_entity(&world, goblin).unwrap();
+
+        let overrides = PrefabOverrides::new()
+            .set("Health", serde_json::json!({ "max": 30 }))
+            .set_on(1, "Damage", serde_json::json!(5));
+        let json = serde_json::to_string(&overrides).unwrap();
+        let overrides: PrefabOverrides = serde_json::from_str(&json).unwrap();
+
+        let boss = world.instantiate_with(&prefab, &overrides).unwrap();
+        assert_eq!(world.get::<Health>(boss), Some(&Health { current: 10, max: 30 }));
+        let sword = world.get::<Children>(boss).unwrap().0[0];
+        assert_eq!(world.get::<Damage>(sword), Some(&Damage(5)));
+
+        // A bad override fails the whole instance rather than half of it.
+        let count = world.entity_count();
+        let broken = PrefabOverrides::new().set("Health", serde_json::json!("lots"));
+        assert_eq!(world.instantiate_with(&prefab, &broken), None);
+        assert_eq!(world.entity_count(), count);
+    }
+}
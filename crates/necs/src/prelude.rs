@@ -4,31 +4,100 @@
 //! not free functions.
 
 // Core
-pub use crate::asset::AssetServer;
+pub use crate::action::{ActionBindings, ActionMap, Binding};
+pub use crate::asset::{
+    AssetEvent, AssetId, AssetLoader, AssetServer, Assets, Handle, LoadProgress, LoadState,
+    WeakHandle,
+};
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub use crate::asset_gc::{AssetGc, AssetRef, FreedAssets};
+pub use crate::avoidance::{Agent, Avoidance, AvoidancePlane, AvoidanceSettings};
+pub use crate::clipboard::Clipboard;
+pub use crate::constraint::{CopyPosition, LockAxis, LookAt};
 pub use crate::context::{Context, EntityBuilder, InputState};
-pub use crate::ecs::{Children, Entity, GlobalTransform, Parent, World};
+pub use crate::cursor::{CursorGrab, CursorOptions};
+#[cfg(feature = "editor")]
+pub use crate::editor::EditorSettings;
+pub use crate::ecs::{
+    Bundle, Children, Entity, EventReader, EventWriter, Events, GlobalTransform, Parent, Pool,
+    Pooled, SpawnBundle, TransformPropagation, World,
+};
+pub use crate::entity_ref::{EntityRef, PendingRefs, UnresolvedRef};
+pub use crate::focus::{FocusEvent, FocusNavigation, FocusState, Focusable, NavDirection};
 pub use crate::game::{Game, Plugin};
-pub use crate::input::{CursorPosition, Input, KeyCode, MouseButton};
+pub use crate::gamepad::{GamepadEvent, GamepadId, GamepadInfo, Gamepads};
+#[cfg(feature = "gamepad")]
+pub use crate::gamepad::GamepadInput;
+pub use crate::hooks::Hook;
+pub use crate::import::ImportCache;
+pub use crate::input::{
+    CursorPosition, GamepadAxis, GamepadButton, GamepadStyle, Input, InputDevice, InputEvent, InputLatency,
+    KeyCode, MouseButton, MouseScroll, ScrollDelta, TextEvent, TimedInput, TimedText,
+};
+pub use crate::interpolation::{InterpolatedTransform, InterpolationMode};
+pub use crate::launch::LaunchOptions;
+pub use crate::lifecycle::{
+    LifecycleEvent, ShutdownReason, ShutdownRequested, WindowLifecycle,
+};
+pub use crate::local::{LastRun, SystemLocal};
 pub use crate::math::{Mat4, Quat, Rect, Transform, Vec2, Vec3, Vec4};
-pub use crate::render::{ClearColor, GpuContext};
-pub use crate::scene::{SceneData, SceneMarker, SceneRegistry};
-pub use crate::scene_builder::{SceneBuilder, SceneManager, Scenes, Template};
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub use crate::particles::{Emission, Particle, ParticleCurve, ParticleEmitter};
+pub use crate::prefab::{Prefab, PrefabOverrides};
+pub use crate::render::{
+    AdapterInfo, AdapterPreference, AdapterSelection, CameraClear, ClearColor, ColorGrading,
+    ComputedVisibility, DiffStats, DiffView, FrameCapture, GpuContext, GraphicsSettings, Hidden,
+    Lut3d, PostEffects, QualityPreset, ReadbackComplete, ReadbackData, ReadbackId, SamplerSettings,
+    ShaderComparison, ShaderDefs, ShaderDiff, TextureFilter, TextureWrap, Visibility,
+};
+pub use crate::scene::{SceneData, SceneError, SceneLoadMode, SceneMarker, SceneRegistry, Uid};
+pub use crate::scene_builder::{
+    Persistent, SceneBuilder, SceneCleanup, SceneManager, Scenes, Template, Transient,
+};
+pub use crate::subsystems::Subsystems;
 pub use crate::time::Time;
+pub use crate::touch::{Pinch, Touch, TouchPhase, Touches};
+pub use crate::transform2d::Transform2d;
 
 // Render 2D (feature-gated)
 #[cfg(feature = "render2d")]
 pub use crate::animation::{
-    AnimationClip, AnimationPlayer, EaseFunction, SpriteSheet, Tween, TweenTarget,
+    AnimationClip, AnimationPlayer, AtlasClip, AtlasRegion, EaseFunction, SpriteAtlas,
+    SpriteSheet, Tween, TweenTarget,
+};
+#[cfg(feature = "render2d")]
+pub use crate::render2d::{
+    Camera2d, ChromaticAberration, Color, CustomEffect, FontHandle, PaletteSwap, PostEffect,
+    PostEffects2d, ProgressBar2d, ScreenShake, Shape2d, ShapeKind2d, Sprite, SpriteAnchor,
+    SpriteBundle, SpriteRenderMode, SpriteTiling, Text, TextAlign, TextAnchor, TextOutline,
+    TextShadow, TextSpan, TextureAtlas, TextureAtlasHandle, TextureAtlasing, TextureHandle, Tile,
+    TileLayer, Tilemap, UvScroll, Video, VideoPlayer, Vignette,
 };
+#[cfg(all(feature = "render2d", feature = "physics2d"))]
+pub use crate::render2d::TileCollider;
+#[cfg(feature = "render2d")]
+pub use crate::focus::FocusTint;
+#[cfg(feature = "render2d")]
+pub use crate::glyph::{InputGlyphs, InputPrompt, PromptText};
+#[cfg(feature = "render2d")]
+pub use crate::input::LateLatchCursor;
 #[cfg(feature = "render2d")]
-pub use crate::render2d::{Camera2d, Color, FontHandle, Shape2d, ShapeKind2d, Sprite, Text, TextureHandle};
+pub use crate::ui::{
+    Button, ButtonColors, ComputedNode, Interaction, TextFocus, TextInput, TextInputColors,
+    TextSubmitted, UiAlign, UiAnchor, UiEdges, UiImage, UiJustify, UiLayout, UiNode, UiPointer,
+    UiSize, UiText,
+};
 
 // Render 3D (feature-gated)
 #[cfg(feature = "render3d")]
 pub use crate::render3d::{
-    AmbientLight, Camera3d, DirectionalLight, Material, Mesh3d, MeshHandle, PointLight,
-    Shape3d, ShapeKind3d, TextureHandle3d,
+    AlphaMode, AmbientLight, Attenuation, Billboard, Bloom, Camera3d, DirectionalLight,
+    Material, MaterialShader, Mesh3d, MeshAttributes, MeshHandle, OrthographicSize, PointLight,
+    PostProcess, Projection3d, Shape3d, ShapeKind3d, Skybox, SkyboxSource, SoftParticle, SpotLight, TextureHandle3d,
+    Tonemapping, Transparency, VertexAttributes,
 };
+#[cfg(all(feature = "render2d", feature = "render3d"))]
+pub use crate::render3d::Text3d;
 
 // Debug colliders
 #[cfg(all(feature = "render2d", feature = "physics2d"))]
@@ -39,16 +108,29 @@ pub use crate::render3d::DebugColliders3d;
 // Audio (feature-gated)
 #[cfg(feature = "audio")]
 pub use crate::audio::{Audio, AudioEngine, AudioError, AudioSource, SoundData, SoundHandle};
+#[cfg(feature = "audio")]
+pub use crate::audio_graph::{DspGraph, DspGraphHandle, DspNode, DspNodeId, DspParam, Waveform};
+#[cfg(feature = "audio")]
+pub use crate::audio_spatial::{AudioListener, DistanceModel, SpatialAudioSource};
+#[cfg(feature = "audio")]
+pub use crate::music::{AdaptiveMusic, MusicController, MusicLayer, Playlist};
+#[cfg(all(feature = "audio", feature = "physics3d"))]
+pub use crate::audio_occlusion::{AcousticMaterial, AudioOcclusion, OcclusionSettings};
 
 // Physics (feature-gated)
 #[cfg(feature = "physics2d")]
 pub use crate::physics2d::{
-    Collider2d, ColliderShape2d, Physics2d, PhysicsWorld2d, RigidBody2d, RigidBodyType2d,
+    CharacterController2d, Collider2d, ColliderShape2d, FieldGravity2d, GravityField2d,
+    GravityFieldMode, InPhysicsWorld2d, Physics2d, PhysicsWorld2d, PhysicsWorlds2d, RigidBody2d,
+    RigidBodyType2d, TriggerVolume2d,
 };
 #[cfg(feature = "physics3d")]
 pub use crate::physics3d::{
-    Collider3d, ColliderShape3d, Physics3d, PhysicsWorld3d, RigidBody3d, RigidBodyType3d,
+    CharacterController3d, Collider3d, ColliderShape3d, InPhysicsWorld3d, Physics3d, PhysicsWorld3d,
+    PhysicsWorlds3d, RigidBody3d, RigidBodyType3d, TriggerVolume3d,
 };
+#[cfg(any(feature = "physics2d", feature = "physics3d"))]
+pub use crate::trigger::{TriggerEntered, TriggerEvents, TriggerExited};
 
 // Diagnostics (feature-gated)
 #[cfg(feature = "diagnostics")]
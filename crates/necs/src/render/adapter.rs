@@ -0,0 +1,274 @@
+//! # Adapter — Choosing the GPU and Reporting What It Can Do
+//!
+//! An *adapter* is one GPU as seen through one graphics API: "NVIDIA RTX 4060
+//! via Vulkan", "Intel Iris Xe via Vulkan", "llvmpipe (software)". A laptop
+//! with hybrid graphics exposes at least two, and wgpu's default choice is
+//! not always the one the player wants — a game can end up on the power-
+//! saving integrated GPU while the discrete one idles.
+//!
+//! Insert an [`AdapterSelection`] resource before [`Game::run`](crate::game::Game::run)
+//! to steer the choice. Once the device exists, the [`AdapterInfo`] resource
+//! says which GPU was picked and what the device supports:
+//!
+//! ```text
+//!   AdapterSelection ──► enumerate adapters (restricted to `backends`)
+//!                          │ drop those that can't present to the window
+//!                          ▼
+//!                        name match?  ── yes ──► use it
+//!                          │ no
+//!                          ▼
+//!                        rank by preference (Discrete / Integrated / Software)
+//!                          │ Default, or nothing usable
+//!                          ▼
+//!                        wgpu's own choice
+//!                          │
+//!                          ▼
+//!                        device ──► AdapterInfo { info, limits, features }
+//! ```
+//!
+//! ```ignore
+//! Game::new("My Game")
+//!     .resource(AdapterSelection::discrete().backends(wgpu::Backends::VULKAN))
+//!     .setup(|ctx| {
+//!         let gpu = ctx.world.resource::<AdapterInfo>();
+//!         log::info!("Rendering on {}", gpu.info.name);
+//!         if gpu.limits.max_texture_dimension_2d < 4096 {
+//!             ctx.world.insert_resource(TextureAtlasing { page_size: 1024, ..Default::default() });
+//!         }
+//!     })
+//!     .run();
+//! ```
+//!
+//! [`available_adapters`] lists every adapter without creating a device —
+//! for a graphics settings menu that stores the player's pick by name.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: No runtime choice; the OS or driver control panel decides.
+//!   `SystemInfo` exposes the device name and capability flags.
+//! - **Bevy**: `WgpuSettings { power_preference, backends, .. }` on the render
+//!   plugin, and a `RenderAdapterInfo` resource.
+//! - **Our approach**: The same knobs plus a name match, with a warning (not
+//!   a crash) when the requested GPU isn't there.
+
+/// Which kind of GPU to prefer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AdapterPreference {
+    /// Let wgpu decide (its default power preference).
+    #[default]
+    Default,
+    /// A discrete GPU, if present — the fast one on hybrid laptops.
+    Discrete,
+    /// An integrated GPU, if present — lower power draw.
+    Integrated,
+    /// A software rasterizer (e.g. llvmpipe, WARP). Useful on CI machines.
+    Software,
+}
+
+/// How the render device is chosen. Insert as a resource before the window
+/// is created; later changes have no effect.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdapterSelection {
+    /// Preferred kind of GPU.
+    pub preference: AdapterPreference,
+    /// Graphics APIs to consider.
+    pub backends: wgpu::Backends,
+    /// Use the first adapter whose name contains this (case-insensitive).
+    /// Takes priority over `preference`.
+    pub name: Option<String>,
+}
+
+impl Default for AdapterSelection {
+    fn default() -> Self {
+        Self {
+            preference: AdapterPreference::Default,
+            backends: wgpu::Backends::all(),
+            name: None,
+        }
+    }
+}
+
+impl AdapterSelection {
+    /// Prefer a discrete GPU.
+    pub fn discrete() -> Self {
+        Self::default().preference(AdapterPreference::Discrete)
+    }
+
+    /// Prefer an integrated GPU.
+    pub fn integrated() -> Self {
+        Self::default().preference(AdapterPreference::Integrated)
+    }
+
+    /// Set the preferred kind of GPU.
+    pub fn preference(mut self, preference: AdapterPreference) -> Self {
+        self.preference = preference;
+        self
+    }
+
+    /// Restrict the graphics APIs considered, e.g. `wgpu::Backends::VULKAN`.
+    pub fn backends(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = backends;
+        self
+    }
+
+    /// Prefer the adapter whose name contains `name`.
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Pick one of `candidates`. `None` means "no opinion" — fall back to
+    /// wgpu's choice via [`request_options`](Self::request_options).
+    pub(crate) fn pick(&self, candidates: &[wgpu::AdapterInfo]) -> Option<usize> {
+        if let Some(name) = &self.name {
+            let wanted = name.to_lowercase();
+            match candidates.iter().position(|c| c.name.to_lowercase().contains(&wanted)) {
+                Some(i) => return Some(i),
+                None => log::warn!("No GPU adapter named like '{name}'; using preference"),
+            }
+        }
+
+        let order: &[wgpu::DeviceType] = match self.preference {
+            AdapterPreference::Default => return None,
+            AdapterPreference::Discrete => &[
+                wgpu::DeviceType::DiscreteGpu,
+                wgpu::DeviceType::IntegratedGpu,
+                wgpu::DeviceType::VirtualGpu,
+                wgpu::DeviceType::Other,
+                wgpu::DeviceType::Cpu,
+            ],
+            AdapterPreference::Integrated => &[
+                wgpu::DeviceType::IntegratedGpu,
+                wgpu::DeviceType::DiscreteGpu,
+                wgpu::DeviceType::VirtualGpu,
+                wgpu::DeviceType::Other,
+                wgpu::DeviceType::Cpu,
+            ],
+            AdapterPreference::Software => &[wgpu::DeviceType::Cpu],
+        };
+        let rank = |c: &wgpu::AdapterInfo| order.iter().position(|t| *t == c.device_type);
+
+        // Lowest rank wins; ties keep enumeration order.
+        let best = candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| rank(c).map(|r| (r, i)))
+            .min()?;
+        if best.0 != 0 {
+            log::warn!(
+                "No {:?} GPU adapter found; using {}",
+                self.preference,
+                candidates[best.1].name
+            );
+        }
+        Some(best.1)
+    }
+
+    /// Options for wgpu's own adapter choice when [`pick`](Self::pick) had
+    /// no opinion.
+    pub(crate) fn request_options<'a>(
+        &self,
+        surface: &'a wgpu::Surface<'_>,
+    ) -> wgpu::RequestAdapterOptions<'a, 'a> {
+        wgpu::RequestAdapterOptions {
+            power_preference: match self.preference {
+                AdapterPreference::Discrete => wgpu::PowerPreference::HighPerformance,
+                AdapterPreference::Integrated => wgpu::PowerPreference::LowPower,
+                _ => wgpu::PowerPreference::default(),
+            },
+            compatible_surface: Some(surface),
+            force_fallback_adapter: self.preference == AdapterPreference::Software,
+        }
+    }
+}
+
+/// The GPU the game is rendering on. Inserted as a resource when the window
+/// is created.
+#[derive(Debug, Clone)]
+pub struct AdapterInfo {
+    /// Name, vendor, device type, backend, and driver of the adapter.
+    pub info: wgpu::AdapterInfo,
+    /// Limits of the created device — the ones that can actually be relied on.
+    pub limits: wgpu::Limits,
+    /// Optional features enabled on the created device.
+    pub features: wgpu::Features,
+}
+
+impl AdapterInfo {
+    pub(crate) fn new(adapter: &wgpu::Adapter, device: &wgpu::Device) -> Self {
+        Self {
+            info: adapter.get_info(),
+            limits: device.limits(),
+            features: device.features(),
+        }
+    }
+
+    /// Whether this is a software rasterizer rather than real hardware.
+    pub fn is_software(&self) -> bool {
+        self.info.device_type == wgpu::DeviceType::Cpu
+    }
+}
+
+/// Every adapter available through `backends`, without creating a device.
+pub fn available_adapters(backends: wgpu::Backends) -> Vec<wgpu::AdapterInfo> {
+    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+        backends,
+        ..Default::default()
+    });
+    instance
+        .enumerate_adapters(backends)
+        .iter()
+        .map(wgpu::Adapter::get_info)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn adapter(name: &str, device_type: wgpu::DeviceType) -> wgpu::AdapterInfo {
+        wgpu::AdapterInfo {
+            name: name.to_string(),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: wgpu::Backend::Vulkan,
+        }
+    }
+
+    fn laptop() -> Vec<wgpu::AdapterInfo> {
+        vec![
+            adapter("llvmpipe", wgpu::DeviceType::Cpu),
+            adapter("Intel Iris Xe", wgpu::DeviceType::IntegratedGpu),
+            adapter("NVIDIA GeForce RTX 4060", wgpu::DeviceType::DiscreteGpu),
+        ]
+    }
+
+    #[test]
+    fn preference_ranks_device_types() {
+        let gpus = laptop();
+        assert_eq!(AdapterSelection::default().pick(&gpus), None);
+        assert_eq!(AdapterSelection::discrete().pick(&gpus), Some(2));
+        assert_eq!(AdapterSelection::integrated().pick(&gpus), Some(1));
+        let software = AdapterSelection::default().preference(AdapterPreference::Software);
+        assert_eq!(software.pick(&gpus), Some(0));
+    }
+
+    #[test]
+    fn falls_back_when_preferred_type_is_missing() {
+        let gpus = vec![adapter("Intel UHD", wgpu::DeviceType::IntegratedGpu)];
+        assert_eq!(AdapterSelection::discrete().pick(&gpus), Some(0));
+        let software = AdapterSelection::default().preference(AdapterPreference::Software);
+        assert_eq!(software.pick(&gpus), None);
+        assert_eq!(AdapterSelection::discrete().pick(&[]), None);
+    }
+
+    #[test]
+    fn name_match_wins_over_preference() {
+        let gpus = laptop();
+        assert_eq!(AdapterSelection::discrete().name("iris").pick(&gpus), Some(1));
+        assert_eq!(AdapterSelection::integrated().name("radeon").pick(&gpus), Some(1));
+    }
+}
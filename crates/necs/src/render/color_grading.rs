@@ -0,0 +1,867 @@
+//! # Color Grading — 3D LUT Post-Processing
+//!
+//! A *lookup table* (LUT) maps every input color to an output color. Stored
+//! as a small 3D texture (typically 16³ or 32³), it can express any per-pixel
+//! color transform — contrast curves, tints, desaturation, film looks — as a
+//! single texture fetch. The GPU's trilinear filtering fills in the colors
+//! between the table's entries.
+//!
+//! ## Frame Flow
+//!
+//! Inserting a [`ColorGrading`] resource adds a post-process step: the scene
+//! renders into an offscreen texture, then a fullscreen pass grades it onto
+//! the window surface. Overlays (the editor) draw afterwards, ungraded. The
+//! same pass upscales the scene when
+//! [`GraphicsSettings::resolution_scale`](super::GraphicsSettings) is below
+//! 1.0, grading or not, and while a [`ShaderDiff`](super::ShaderDiff) is
+//! collecting before/after frames.
+//!
+//! ```text
+//!  2D/3D renderer ──► scene texture ──► grade pass ──► surface ──► overlay
+//!                          │              ▲
+//!                          │           LUT (3D texture)
+//!                          └──► optional neutral-LUT screenshot
+//! ```
+//!
+//! The LUT is indexed by *display* (sRGB-encoded) color, which is what an
+//! artist sees in a paint program — so a LUT graded on a screenshot behaves
+//! the same in-game.
+//!
+//! ## LUT Formats
+//!
+//! - **`.cube`** (Adobe/Resolve): text, `LUT_3D_SIZE N` followed by N³ RGB
+//!   float triples, red varying fastest. Exported by most grading tools.
+//! - **Strip PNG** (Unity/Unreal style): an N²×N image made of N square
+//!   slices side by side, one per blue value. Within a slice red increases
+//!   to the right and green increases downward.
+//!
+//! ## Authoring Workflow
+//!
+//! ```text
+//!  1. grading.capture_neutral_screenshot("grade_me.png")
+//!     └─ next frame is saved ungraded, with a neutral 16³ strip in the
+//!        top-left corner
+//!  2. Grade the whole image in Photoshop/Krita/GIMP (curves, hue, ...)
+//!  3. ctx.load_color_grading("grade_me.png")
+//!     └─ the strip is read back from the corner: it went through the same
+//!        adjustments as the scene, so it *is* the grade
+//! ```
+//!
+//! LUT files are hot-reloaded: save the image again and the game updates.
+//!
+//! ## Comparison
+//!
+//! - **Unity** (URP/HDRP): Color Lookup volume override with strip textures
+//!   — the same screenshot-and-strip workflow.
+//! - **Unreal**: `Color Grading LUT` post-process setting with 16³ strips.
+//! - **Bevy**: Tonemapping LUTs are built in; user grading is parametric
+//!   (`ColorGrading` exposure/saturation), not LUT-based.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use wgpu::util::DeviceExt;
+
+use crate::asset::AssetServer;
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+use crate::render::pass::FrameContext;
+use crate::render::readback::TextureReadback;
+use crate::render::settings::graphics_settings;
+
+/// LUT size used for neutral screenshots. A 256×16 strip fits in any window.
+pub const SCREENSHOT_LUT_SIZE: u32 = 16;
+
+/// Default LUT size for [`ColorGrading::neutral`].
+const DEFAULT_LUT_SIZE: u32 = 32;
+
+/// Largest LUT accepted from a file (64³ is already 1 MB of texture).
+const MAX_LUT_SIZE: u32 = 128;
+
+// ── Errors ──────────────────────────────────────────────────────────────
+
+/// Errors that can occur while loading a LUT.
+#[derive(Debug)]
+pub enum LutError {
+    /// The file could not be read or decoded.
+    Read(String),
+    /// The file was read but its contents are not a valid LUT.
+    Parse(String),
+}
+
+impl fmt::Display for LutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LutError::Read(e) => write!(f, "LUT read failed: {e}"),
+            LutError::Parse(e) => write!(f, "LUT parse failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LutError {}
+
+// ── Lut3d ───────────────────────────────────────────────────────────────
+
+/// A 3D color lookup table: `size³` RGB entries, red varying fastest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lut3d {
+    size: u32,
+    data: Vec<[f32; 3]>,
+}
+
+impl Lut3d {
+    /// The identity LUT: every color maps to itself.
+    pub fn neutral(size: u32) -> Self {
+        let size = size.max(2);
+        let max = (size - 1) as f32;
+        let mut data = Vec::with_capacity((size * size * size) as usize);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    data.push([r as f32 / max, g as f32 / max, b as f32 / max]);
+                }
+            }
+        }
+        Self { size, data }
+    }
+
+    /// Number of entries along each axis.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// The output color for the table entry at `(r, g, b)`.
+    pub fn entry(&self, r: u32, g: u32, b: u32) -> [f32; 3] {
+        self.data[(r + g * self.size + b * self.size * self.size) as usize]
+    }
+
+    /// Load a LUT from disk: `.cube` files are parsed as text, anything else
+    /// is decoded as an image (see [`from_image`](Self::from_image)).
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LutError> {
+        let path = path.as_ref();
+        let is_cube = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("cube"));
+
+        if is_cube {
+            let text = std::fs::read_to_string(path)
+                .map_err(|e| LutError::Read(format!("{}: {e}", path.display())))?;
+            Self::parse_cube(&text)
+        } else {
+            let img = image::open(path)
+                .map_err(|e| LutError::Read(format!("{}: {e}", path.display())))?
+                .to_rgba8();
+            let (width, height) = img.dimensions();
+            Self::from_image(img.as_raw(), width, height)
+        }
+    }
+
+    /// Parse the text of an Adobe `.cube` file.
+    pub fn parse_cube(text: &str) -> Result<Self, LutError> {
+        let mut size = None;
+        let mut domain_min = [0.0f32; 3];
+        let mut domain_max = [1.0f32; 3];
+        let mut data = Vec::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut words = line.split_whitespace();
+            let keyword = words.next().unwrap_or_default();
+            let bad_line = || LutError::Parse(format!("line {}: '{line}'", number + 1));
+
+            match keyword {
+                "TITLE" => {}
+                "LUT_1D_SIZE" => {
+                    return Err(LutError::Parse("1D LUTs are not supported".to_owned()));
+                }
+                "LUT_3D_SIZE" => {
+                    let n: u32 = words.next().and_then(|w| w.parse().ok()).ok_or_else(bad_line)?;
+                    if !(2..=MAX_LUT_SIZE).contains(&n) {
+                        return Err(LutError::Parse(format!("unsupported LUT_3D_SIZE {n}")));
+                    }
+                    size = Some(n);
+                }
+                "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                    let values = parse_triple(words).ok_or_else(bad_line)?;
+                    if keyword == "DOMAIN_MIN" {
+                        domain_min = values;
+                    } else {
+                        domain_max = values;
+                    }
+                }
+                _ => {
+                    let values = parse_triple(line.split_whitespace()).ok_or_else(bad_line)?;
+                    data.push(values);
+                }
+            }
+        }
+
+        let size = size.ok_or_else(|| LutError::Parse("missing LUT_3D_SIZE".to_owned()))?;
+        let expected = (size * size * size) as usize;
+        if data.len() != expected {
+            return Err(LutError::Parse(format!(
+                "expected {expected} entries for size {size}, found {}",
+                data.len()
+            )));
+        }
+
+        // Normalize into 0..1 if the file declares a custom domain.
+        for entry in &mut data {
+            for c in 0..3 {
+                let span = domain_max[c] - domain_min[c];
+                if span > 0.0 {
+                    entry[c] = (entry[c] - domain_min[c]) / span;
+                }
+            }
+        }
+
+        Ok(Self { size, data })
+    }
+
+    /// Read a LUT from RGBA8 pixels.
+    ///
+    /// An image exactly N² wide and N tall is read as a strip. Any other image
+    /// is treated as a graded neutral screenshot: a
+    /// [`SCREENSHOT_LUT_SIZE`]³ strip is read from its top-left corner.
+    pub fn from_image(rgba: &[u8], width: u32, height: u32) -> Result<Self, LutError> {
+        let size = if width == height * height {
+            height
+        } else {
+            SCREENSHOT_LUT_SIZE
+        };
+        if !(2..=MAX_LUT_SIZE).contains(&size) || width < size * size || height < size {
+            return Err(LutError::Parse(format!(
+                "{width}×{height} image is neither a LUT strip nor a LUT screenshot"
+            )));
+        }
+
+        let mut data = Vec::with_capacity((size * size * size) as usize);
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let x = b * size + r;
+                    let i = ((g * width + x) * 4) as usize;
+                    data.push([
+                        rgba[i] as f32 / 255.0,
+                        rgba[i + 1] as f32 / 255.0,
+                        rgba[i + 2] as f32 / 255.0,
+                    ]);
+                }
+            }
+        }
+        Ok(Self { size, data })
+    }
+
+    /// Encode as an RGBA8 strip (N² wide, N tall), the layout read by
+    /// [`from_image`](Self::from_image).
+    pub fn to_strip(&self) -> Vec<u8> {
+        let size = self.size;
+        let width = size * size;
+        let mut rgba = vec![255u8; (width * size * 4) as usize];
+        for b in 0..size {
+            for g in 0..size {
+                for r in 0..size {
+                    let i = ((g * width + b * size + r) * 4) as usize;
+                    let [cr, cg, cb] = self.entry(r, g, b);
+                    rgba[i] = to_u8(cr);
+                    rgba[i + 1] = to_u8(cg);
+                    rgba[i + 2] = to_u8(cb);
+                }
+            }
+        }
+        rgba
+    }
+
+    /// Texel data for a `size³` RGBA8 3D texture (x = red, y = green, z = blue).
+    fn to_texture_data(&self) -> Vec<u8> {
+        self.data
+            .iter()
+            .flat_map(|&[r, g, b]| [to_u8(r), to_u8(g), to_u8(b), 255])
+            .collect()
+    }
+}
+
+fn parse_triple<'a>(mut words: impl Iterator<Item = &'a str>) -> Option<[f32; 3]> {
+    let mut values = [0.0; 3];
+    for value in &mut values {
+        *value = words.next()?.parse().ok()?;
+    }
+    words.next().is_none().then_some(values)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+// ── ColorGrading resource ───────────────────────────────────────────────
+
+/// Resource: grade the final frame through a 3D LUT.
+///
+/// Insert directly with a [`Lut3d`], or use
+/// [`Context::load_color_grading`](crate::context::Context::load_color_grading)
+/// to load (and hot-reload) a LUT file. Remove the resource to switch the
+/// post-process step off entirely.
+#[derive(Debug)]
+pub struct ColorGrading {
+    /// When `false`, frames pass through ungraded (screenshots still work).
+    pub enabled: bool,
+    /// Blend between the original (0.0) and fully graded (1.0) image.
+    pub intensity: f32,
+    /// Shared with the render thread's copy, so handing it over is cheap.
+    lut: Arc<Lut3d>,
+    /// Bumped on every LUT change so the renderer knows to re-upload.
+    generation: u64,
+    screenshot: Option<PathBuf>,
+}
+
+impl ColorGrading {
+    /// Grade through the given LUT at full intensity.
+    pub fn new(lut: Lut3d) -> Self {
+        Self {
+            enabled: true,
+            intensity: 1.0,
+            lut: Arc::new(lut),
+            generation: 0,
+            screenshot: None,
+        }
+    }
+
+    /// A neutral (identity) grade — handy as a starting point for
+    /// [`capture_neutral_screenshot`](Self::capture_neutral_screenshot).
+    pub fn neutral() -> Self {
+        Self::new(Lut3d::neutral(DEFAULT_LUT_SIZE))
+    }
+
+    /// Set the grading intensity (builder pattern).
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// The active LUT.
+    pub fn lut(&self) -> &Lut3d {
+        &self.lut
+    }
+
+    /// Replace the active LUT. Uploaded to the GPU on the next frame.
+    pub fn set_lut(&mut self, lut: Lut3d) {
+        self.lut = Arc::new(lut);
+        self.generation += 1;
+    }
+
+    /// Save the next frame, ungraded, as a PNG with a neutral LUT strip in
+    /// the top-left corner. Grade the image externally, then load it back as
+    /// a LUT. See the [module docs](self) for the workflow.
+    pub fn capture_neutral_screenshot(&mut self, path: impl Into<PathBuf>) {
+        self.screenshot = Some(path.into());
+    }
+
+    /// A copy for the [render thread](super::pipelined), which takes over a
+    /// pending screenshot so it is saved exactly once.
+    pub(crate) fn frame_copy(&mut self) -> Self {
+        Self {
+            enabled: self.enabled,
+            intensity: self.intensity,
+            lut: Arc::clone(&self.lut),
+            generation: self.generation,
+            screenshot: self.screenshot.take(),
+        }
+    }
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self::neutral()
+    }
+}
+
+/// Load a LUT file into the [`ColorGrading`] resource (inserting it if
+/// needed) and watch the file for hot-reload. On failure the current grade
+/// is kept and a warning is logged.
+pub(crate) fn load_color_grading(world: &mut World, path: &str) {
+    match Lut3d::load(path) {
+        Ok(lut) => {
+            if let Some(grading) = world.get_resource_mut::<ColorGrading>() {
+                grading.set_lut(lut);
+            } else {
+                world.insert_resource(ColorGrading::new(lut));
+            }
+        }
+        Err(e) => {
+            log::warn!("Failed to load color grading LUT '{path}': {e}");
+            if !world.has_resource::<ColorGrading>() {
+                world.insert_resource(ColorGrading::neutral());
+            }
+        }
+    }
+
+    if let Some(server) = world.get_resource_mut::<AssetServer>() {
+        server.watch_custom(path, reload_lut);
+    }
+}
+
+/// Hot-reload callback for watched LUT files.
+fn reload_lut(world: &mut World, path: &Path) {
+    let Some(grading) = world.get_resource_mut::<ColorGrading>() else {
+        return;
+    };
+    match Lut3d::load(path) {
+        Ok(lut) => {
+            grading.set_lut(lut);
+            log::info!("Reloaded color grading LUT '{}'", path.display());
+        }
+        Err(e) => log::warn!("Keeping previous LUT, reload of '{}' failed: {e}", path.display()),
+    }
+}
+
+// ── GPU side ────────────────────────────────────────────────────────────
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct GradingParams {
+    intensity: f32,
+    lut_size: f32,
+    /// 1 when the surface format is sRGB (shader must encode/decode).
+    srgb: u32,
+    _pad: u32,
+}
+
+/// Offscreen scene target, LUT texture, and the fullscreen grading pipeline.
+pub(crate) struct ColorGradingRenderer {
+    pipeline: wgpu::RenderPipeline,
+    layout: wgpu::BindGroupLayout,
+    scene_sampler: wgpu::Sampler,
+    lut_sampler: wgpu::Sampler,
+    params_buffer: wgpu::Buffer,
+    scene_texture: wgpu::Texture,
+    scene_view: wgpu::TextureView,
+    lut_view: wgpu::TextureView,
+    /// `ColorGrading::generation` of the uploaded LUT (`None` = nothing yet).
+    lut_generation: Option<u64>,
+    /// The scene target was (re)created this frame, so it doesn't hold the
+    /// previous frame.
+    fresh: bool,
+}
+
+impl ColorGradingRenderer {
+    fn new(gpu: &GpuContext) -> Self {
+        let device = &gpu.device;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("color grading shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("color_grading.wgsl").into()),
+        });
+
+        let texture_entry = |binding, view_dimension| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let sampler_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+            count: None,
+        };
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("color grading layout"),
+            entries: &[
+                texture_entry(0, wgpu::TextureViewDimension::D2),
+                sampler_entry(1),
+                texture_entry(2, wgpu::TextureViewDimension::D3),
+                sampler_entry(3),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 4,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("color grading pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("color grading pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: gpu.surface_format(),
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        // Linear so a reduced-resolution scene is smoothed when upscaled; at
+        // full size every sample lands on a texel center.
+        let scene_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("color grading scene sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let lut_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("color grading lut sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("color grading params"),
+            size: std::mem::size_of::<GradingParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let scene_texture = create_scene_texture(gpu, 1, 1);
+        let scene_view = scene_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let lut_view = create_lut_view(gpu, &Lut3d::neutral(2));
+
+        Self {
+            pipeline,
+            layout,
+            scene_sampler,
+            lut_sampler,
+            params_buffer,
+            scene_texture,
+            scene_view,
+            lut_view,
+            lut_generation: None,
+            fresh: true,
+        }
+    }
+
+    /// Recreate the scene target if its wanted size changed.
+    fn resize_if_needed(&mut self, gpu: &GpuContext, (width, height): (u32, u32)) {
+        let size = self.scene_texture.size();
+        if (size.width, size.height) != (width, height) && width > 0 && height > 0 {
+            self.scene_texture = create_scene_texture(gpu, width, height);
+            self.scene_view = self
+                .scene_texture
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            self.fresh = true;
+        }
+    }
+}
+
+fn create_scene_texture(gpu: &GpuContext, width: u32, height: u32) -> wgpu::Texture {
+    gpu.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("color grading scene target"),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: gpu.surface_format(),
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+            | wgpu::TextureUsages::TEXTURE_BINDING
+            | wgpu::TextureUsages::COPY_SRC
+            | wgpu::TextureUsages::COPY_DST,
+        view_formats: &[],
+    })
+}
+
+fn create_lut_view(gpu: &GpuContext, lut: &Lut3d) -> wgpu::TextureView {
+    let texture = gpu.device.create_texture_with_data(
+        &gpu.queue,
+        &wgpu::TextureDescriptor {
+            label: Some("color grading lut"),
+            size: wgpu::Extent3d {
+                width: lut.size,
+                height: lut.size,
+                depth_or_array_layers: lut.size,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D3,
+            // Not sRGB: entries are display-space values, looked up as-is.
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &lut.to_texture_data(),
+    );
+    texture.create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// Redirect the scene render into the offscreen target, sized by the
+/// resolution scale. Returns the surface view to hand back to
+/// [`finish_grading`], or `None` if neither grading nor scaling is on (and
+/// no shader diff needs the target).
+pub(crate) fn begin_grading(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+) -> Option<wgpu::TextureView> {
+    let (width, height) = frame.gpu.surface_size();
+    let size = graphics_settings(world).scaled_size(width, height);
+    if !world.has_resource::<ColorGrading>()
+        && !world.has_resource::<super::ShaderDiff>()
+        && size == (width, height)
+    {
+        return None;
+    }
+    if !world.has_resource::<ColorGradingRenderer>() {
+        world.insert_resource(ColorGradingRenderer::new(frame.gpu));
+    }
+
+    let renderer = world.resource_mut::<ColorGradingRenderer>();
+    renderer.resize_if_needed(frame.gpu, size);
+    let size = renderer.scene_texture.size();
+    frame.size = (size.width, size.height);
+    Some(std::mem::replace(&mut frame.view, renderer.scene_view.clone()))
+}
+
+/// The offscreen scene target for this frame, if the scene renders into
+/// one. `None` too when it was just (re)created and so doesn't hold the
+/// previous frame yet.
+pub(crate) fn scene_target(world: &World) -> Option<wgpu::Texture> {
+    world
+        .get_resource::<ColorGradingRenderer>()
+        .filter(|renderer| !renderer.fresh)
+        .map(|renderer| renderer.scene_texture.clone())
+}
+
+/// Grade the offscreen scene onto the surface and restore `frame.view`.
+/// Returns a pending screenshot readback if one was requested.
+pub(crate) fn finish_grading(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+    surface_view: wgpu::TextureView,
+) -> Option<PendingScreenshot> {
+    frame.view = surface_view;
+    frame.size = frame.gpu.surface_size();
+    let gpu = frame.gpu;
+
+    let mut renderer = world.resource_remove::<ColorGradingRenderer>()?;
+    let grading_allowed = graphics_settings(world).post_effects.color_grading;
+
+    // Without a ColorGrading resource the pass only upscales: intensity 0
+    // passes colors through whatever LUT is bound.
+    let mut params = GradingParams {
+        intensity: 0.0,
+        lut_size: 2.0,
+        srgb: gpu.surface_format().is_srgb() as u32,
+        _pad: 0,
+    };
+    let mut screenshot = None;
+    if let Some(grading) = world.get_resource_mut::<ColorGrading>() {
+        if renderer.lut_generation != Some(grading.generation) {
+            renderer.lut_view = create_lut_view(gpu, &grading.lut);
+            renderer.lut_generation = Some(grading.generation);
+        }
+        if grading.enabled && grading_allowed {
+            params.intensity = grading.intensity.clamp(0.0, 1.0);
+        }
+        params.lut_size = grading.lut.size as f32;
+        screenshot = grading.screenshot.take().map(|path| PendingScreenshot {
+            readback: TextureReadback::record(gpu, &mut frame.encoder, &renderer.scene_texture),
+            path,
+        });
+    }
+    gpu.queue
+        .write_buffer(&renderer.params_buffer, 0, bytemuck::cast_slice(&[params]));
+
+    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("color grading bind group"),
+        layout: &renderer.layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&renderer.scene_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&renderer.scene_sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::TextureView(&renderer.lut_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::Sampler(&renderer.lut_sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 4,
+                resource: renderer.params_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    {
+        let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("color grading pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &frame.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&renderer.pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+
+    renderer.fresh = false;
+    world.insert_resource(renderer);
+    screenshot
+}
+
+// ── Neutral screenshots ─────────────────────────────────────────────────
+
+/// A scene-texture readback destined for a neutral-LUT screenshot. Call
+/// [`save`](Self::save) after the frame is submitted.
+pub(crate) struct PendingScreenshot {
+    readback: TextureReadback,
+    path: PathBuf,
+}
+
+impl PendingScreenshot {
+    /// Wait for the readback, stamp the neutral strip, and write the PNG.
+    pub(crate) fn save(self, device: &wgpu::Device) {
+        let (width, height) = self.readback.size();
+        let Some(mut rgba) = self.readback.read(device) else {
+            return;
+        };
+
+        let size = SCREENSHOT_LUT_SIZE;
+        if width < size * size || height < size {
+            log::warn!(
+                "Window is too small ({}×{}) for a {}×{} LUT strip",
+                width,
+                height,
+                size * size,
+                size
+            );
+            return;
+        }
+        let row_bytes = (width * 4) as usize;
+        let strip = Lut3d::neutral(size).to_strip();
+        let strip_row = (size * size * 4) as usize;
+        for (y, src) in strip.chunks(strip_row).enumerate() {
+            let start = y * row_bytes;
+            rgba[start..start + strip_row].copy_from_slice(src);
+        }
+
+        match image::save_buffer(
+            &self.path,
+            &rgba,
+            width,
+            height,
+            image::ColorType::Rgba8,
+        ) {
+            Ok(()) => log::info!("Saved neutral LUT screenshot to '{}'", self.path.display()),
+            Err(e) => log::warn!("Failed to save LUT screenshot '{}': {e}", self.path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frame_copy_takes_over_the_screenshot() {
+        let mut grading = ColorGrading::neutral();
+        grading.capture_neutral_screenshot("grade_me.png");
+        let copy = grading.frame_copy();
+        assert_eq!(copy.screenshot.as_deref(), Some(Path::new("grade_me.png")));
+        assert!(grading.screenshot.is_none(), "saved once, by the frame");
+        assert!(std::ptr::eq(copy.lut(), grading.lut()));
+    }
+
+    #[test]
+    fn neutral_strip_round_trips() {
+        let lut = Lut3d::neutral(4);
+        let strip = lut.to_strip();
+        let back = Lut3d::from_image(&strip, 16, 4).unwrap();
+        assert_eq!(back.size(), 4);
+        for (a, b) in lut.data.iter().zip(&back.data) {
+            for c in 0..3 {
+                assert!((a[c] - b[c]).abs() < 1.0 / 255.0);
+            }
+        }
+    }
+
+    #[test]
+    fn parses_cube_with_domain_and_comments() {
+        let text = "# graded in resolve\nTITLE \"test\"\nLUT_3D_SIZE 2\nDOMAIN_MIN 0 0 0\nDOMAIN_MAX 2 2 2\n\
+                    0 0 0\n2 0 0\n0 2 0\n2 2 0\n0 0 2\n2 0 2\n0 2 2\n2 2 2\n";
+        let lut = Lut3d::parse_cube(text).unwrap();
+        assert_eq!(lut, Lut3d::neutral(2));
+    }
+
+    #[test]
+    fn rejects_malformed_cube() {
+        assert!(matches!(Lut3d::parse_cube("0 0 0\n"), Err(LutError::Parse(_))));
+        assert!(matches!(
+            Lut3d::parse_cube("LUT_3D_SIZE 2\n0 0 0\n1 1 1\n"),
+            Err(LutError::Parse(_))
+        ));
+        assert!(matches!(Lut3d::parse_cube("LUT_1D_SIZE 16\n"), Err(LutError::Parse(_))));
+    }
+
+    #[test]
+    fn reads_strip_from_screenshot_corner() {
+        let size = SCREENSHOT_LUT_SIZE;
+        let (width, height) = (size * size + 40, size + 10);
+        let mut rgba = vec![0u8; (width * height * 4) as usize];
+        let strip = Lut3d::neutral(size).to_strip();
+        let strip_row = (size * size * 4) as usize;
+        for (y, src) in strip.chunks(strip_row).enumerate() {
+            let start = y * (width * 4) as usize;
+            rgba[start..start + strip_row].copy_from_slice(src);
+        }
+
+        let lut = Lut3d::from_image(&rgba, width, height).unwrap();
+        assert_eq!(lut.size(), size);
+        assert_eq!(lut.entry(size - 1, 0, 0), [1.0, 0.0, 0.0]);
+        assert_eq!(lut.entry(0, size - 1, size - 1), [0.0, 1.0, 1.0]);
+    }
+}
@@ -0,0 +1,65 @@
+// Color grading post-process: sample the offscreen scene, look the color up
+// in a 3D LUT, and write the result to the surface.
+
+struct Params {
+    intensity: f32,
+    lut_size: f32,
+    srgb: u32,
+    _pad: u32,
+}
+
+@group(0) @binding(0) var scene: texture_2d<f32>;
+@group(0) @binding(1) var scene_sampler: sampler;
+@group(0) @binding(2) var lut: texture_3d<f32>;
+@group(0) @binding(3) var lut_sampler: sampler;
+@group(0) @binding(4) var<uniform> params: Params;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// One oversized triangle covers the whole screen — no vertex buffer needed.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let xy = vec2(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4(xy, 0.0, 1.0);
+    out.uv = vec2(xy.x * 0.5 + 0.5, 0.5 - xy.y * 0.5);
+    return out;
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, c <= vec3(0.04045));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureSample(scene, scene_sampler, in.uv);
+
+    // The LUT is indexed by display (sRGB-encoded) color.
+    var color = clamp(texel.rgb, vec3(0.0), vec3(1.0));
+    if params.srgb == 1u {
+        color = linear_to_srgb(color);
+    }
+
+    // Remap 0..1 onto texel centers so the ends of the table aren't blended
+    // with the clamp border.
+    let scale = (params.lut_size - 1.0) / params.lut_size;
+    let offset = 0.5 / params.lut_size;
+    let graded = textureSample(lut, lut_sampler, color * scale + offset).rgb;
+
+    var result = mix(color, graded, params.intensity);
+    if params.srgb == 1u {
+        result = srgb_to_linear(result);
+    }
+    return vec4(result, texel.a);
+}
@@ -0,0 +1,187 @@
+//! # Extract — Copying Render State Out of the ECS
+//!
+//! Rendering used to query the ECS while it recorded GPU commands, so the
+//! simulation and the renderer were tangled together for the whole frame.
+//! The extract phase draws a line between them: once the simulation is done,
+//! [`ExtractedFrame::extract`] copies exactly what the renderer needs into
+//! plain render-only data, and the renderers build their draw calls from
+//! that copy.
+//!
+//! Extraction always runs on the main thread. By default rendering follows
+//! it there; with [pipelined rendering](super::pipelined) on, the extracted
+//! frame goes to a render thread instead, and the next frame's update runs
+//! while it is encoded. Either way the renderers read only the copy.
+//!
+//! ```text
+//!   systems ─► propagate ─► editor UI ─► BeforeRender ─► EXTRACT ─► render
+//!   (mutate the world freely)                             │          │
+//!                                                         ▼          ▼
+//!                                              ExtractedFrame ──► draw calls,
+//!                                              (plain copies)     GPU commands
+//! ```
+//!
+//! ## What Gets Extracted
+//!
+//! - **2D**: the `Camera2d` transform and clear mode, every visible `Sprite`,
+//!   `Shape2d` and `Text` with its global transform. Atlas sprites are
+//!   resolved to their atlas texture and region here.
+//! - **3D**: the `Camera3d` settings and transform, the light uniform
+//!   (directional, ambient, point lights), every visible `Mesh3d` + `Material`
+//!   and `Shape3d` with its transform and billboard setting, soft particles
+//!   with their faded material, and `Text3d` labels.
+//! - **Both**: the live particles of every visible `ParticleEmitter`, and the
+//!   collider outlines while physics debug wireframes are on.
+//! - **UI**: every laid-out node's rect, image, text and text field state,
+//!   in draw order.
+//!
+//! Visibility ([`ComputedVisibility`](super::ComputedVisibility), which
+//! folds in [`Hidden`](super::Hidden)) is resolved here, so the copy only
+//! contains what will actually be drawn. Only the active scene is
+//! extracted — 3D when a `Camera3d` exists, 2D otherwise — and not at all
+//! while its [`Subsystems`](crate::subsystems::Subsystems) switch is off.
+//!
+//! Before extracting, [`prepare_frame`] rasterizes characters text uses for
+//! the first time and lets screen shake decay, so rendering never writes
+//! back to the world's gameplay state.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The render thread consumes a command stream produced by the
+//!   main thread; culling results are copied over each frame.
+//! - **Bevy**: A separate render `World`; "extract" systems copy components
+//!   into it, then prepare/queue/render run in parallel with the next frame.
+//! - **Our approach**: The same extract step, but into a single plain struct
+//!   that is moved, with the GPU stores, to the render thread when
+//!   pipelining.
+
+use crate::ecs::World;
+
+#[cfg(feature = "render2d")]
+use crate::render2d::batch::{extract_2d, Extracted2d};
+#[cfg(feature = "render3d")]
+use crate::render3d::collect::{extract_3d, Extracted3d};
+#[cfg(feature = "render2d")]
+use crate::ui::draw::{extract_ui, ExtractedUi};
+
+/// Render state for one frame, copied out of the world. Stored as a resource
+/// between the extract phase and rendering.
+pub(crate) struct ExtractedFrame {
+    /// Set when the frame renders the 2D scene.
+    #[cfg(feature = "render2d")]
+    pub scene_2d: Option<Extracted2d>,
+    /// Set when the frame renders the 3D scene.
+    #[cfg(feature = "render3d")]
+    pub scene_3d: Option<Extracted3d>,
+    /// Set when there is UI to draw over the scene.
+    #[cfg(feature = "render2d")]
+    pub ui: Option<ExtractedUi>,
+}
+
+/// Get the frame ready to render: cache new glyphs, advance screen shake,
+/// then extract. Runs on the main thread whichever thread renders.
+pub(crate) fn prepare_frame(world: &mut World) -> ExtractedFrame {
+    #[cfg(feature = "render2d")]
+    {
+        crate::render2d::font::cache_glyphs(world);
+        crate::render2d::post::decay_screen_shake(world);
+    }
+    ExtractedFrame::extract(world)
+}
+
+impl ExtractedFrame {
+    /// Copy the active scene's cameras, lights and visible drawables, and
+    /// the laid-out UI.
+    pub(crate) fn extract(world: &mut World) -> Self {
+        #[cfg(all(feature = "render2d", feature = "render3d"))]
+        let use_3d = world.has_component_type::<crate::render3d::Camera3d>();
+        #[cfg(all(feature = "render2d", not(feature = "render3d")))]
+        let use_3d = false;
+        #[cfg(all(not(feature = "render2d"), feature = "render3d"))]
+        let use_3d = true;
+        #[cfg(not(any(feature = "render2d", feature = "render3d")))]
+        let _ = world;
+        #[cfg(any(feature = "render2d", feature = "render3d"))]
+        let subsystems = crate::subsystems::Subsystems::of(world);
+
+        Self {
+            #[cfg(feature = "render2d")]
+            scene_2d: (!use_3d && subsystems.render_2d).then(|| extract_2d(world)),
+            #[cfg(feature = "render3d")]
+            scene_3d: (use_3d && subsystems.render_3d).then(|| extract_3d(world)),
+            #[cfg(feature = "render2d")]
+            ui: subsystems.ui.then(|| extract_ui(world)).flatten(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "render2d", feature = "render3d"))]
+mod tests {
+    use super::*;
+    use crate::ecs::GlobalTransform;
+    use crate::render::{Hidden, Visibility, propagate_visibility};
+    use crate::render2d::{Camera2d, Sprite};
+    use crate::render3d::{Camera3d, Material, Mesh3d};
+
+    #[test]
+    fn extracts_only_the_active_scene() {
+        let mut world = World::new();
+        world.spawn((GlobalTransform::default(), Camera2d));
+        world.spawn((GlobalTransform::default(), Sprite::new()));
+        world.spawn((GlobalTransform::default(), Sprite::new(), Hidden));
+        propagate_visibility(&mut world);
+
+        let frame = ExtractedFrame::extract(&mut world);
+        let scene = frame.scene_2d.expect("2D scene");
+        assert!(scene.camera.is_some());
+        assert_eq!(scene.sprites.len(), 1);
+        assert!(frame.scene_3d.is_none());
+
+        world.spawn((GlobalTransform::default(), Camera3d::default()));
+        world.spawn((GlobalTransform::default(), Mesh3d::cube(), Material::default()));
+        world.spawn((
+            GlobalTransform::default(),
+            Mesh3d::cube(),
+            Material::default(),
+            Visibility::HIDDEN,
+        ));
+        propagate_visibility(&mut world);
+
+        let frame = ExtractedFrame::extract(&mut world);
+        assert!(frame.scene_2d.is_none());
+        let scene = frame.scene_3d.expect("3D scene");
+        assert!(scene.camera.is_some());
+        assert_eq!(scene.meshes.len(), 1);
+
+        // Switching the 3D pass off extracts nothing, not the 2D scene.
+        world.insert_resource(crate::subsystems::Subsystems {
+            render_3d: false,
+            ..Default::default()
+        });
+        let frame = ExtractedFrame::extract(&mut world);
+        assert!(frame.scene_2d.is_none() && frame.scene_3d.is_none());
+    }
+
+    #[test]
+    fn extracts_text_labels_and_ui() {
+        use crate::render2d::font::{FontHandle, Text};
+        use crate::render3d::Text3d;
+        use crate::ui::{UiNode, UiText};
+
+        let mut world = World::new();
+        world.spawn((GlobalTransform::default(), Camera2d));
+        world.spawn((GlobalTransform::default(), Text::new("score", FontHandle(0))));
+        world.spawn((UiNode::new().size(100.0, 20.0), UiText::new("menu", FontHandle(0))));
+        propagate_visibility(&mut world);
+        crate::ui::layout::layout_nodes(&mut world, glam::Vec2::new(800.0, 600.0));
+
+        let frame = ExtractedFrame::extract(&mut world);
+        assert_eq!(frame.scene_2d.expect("2D scene").texts[0].1.content, "score");
+        assert!(frame.ui.is_some(), "laid-out UI");
+
+        world.spawn((GlobalTransform::default(), Camera3d::default()));
+        world.spawn((GlobalTransform::default(), Text3d::new("sign", FontHandle(0))));
+        propagate_visibility(&mut world);
+        let frame = ExtractedFrame::extract(&mut world);
+        assert_eq!(frame.scene_3d.expect("3D scene").labels[0].1.content, "sign");
+    }
+}
@@ -5,38 +5,88 @@
 
 use std::sync::Arc;
 
-/// Wraps the wgpu device, queue, surface, and surface configuration.
+use super::adapter::AdapterSelection;
+
+/// Device features that let the 2D renderer batch sprites across textures
+/// with a texture array indexed per vertex. Requested when the adapter has
+/// them; otherwise sprites batch per texture.
+#[cfg_attr(not(feature = "render2d"), allow(dead_code))]
+pub(crate) const TEXTURE_ARRAY_FEATURES: wgpu::Features = wgpu::Features::TEXTURE_BINDING_ARRAY
+    .union(wgpu::Features::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING);
+
+/// Wraps the wgpu adapter, device, queue, surface, and surface configuration.
 ///
-/// Stored as a resource in the [`World`](crate::ecs::World).
+/// Stored as a resource in the [`World`](crate::ecs::World). Cloning is
+/// cheap — the wgpu handles are shared — and gives the
+/// [render thread](super::pipelined) a context of its own.
+#[derive(Clone)]
 pub struct GpuContext {
+    pub adapter: wgpu::Adapter,
     pub device: wgpu::Device,
     pub queue: wgpu::Queue,
-    pub surface: wgpu::Surface<'static>,
+    pub surface: Arc<wgpu::Surface<'static>>,
     pub surface_config: wgpu::SurfaceConfiguration,
 }
 
 impl GpuContext {
     /// Initialize wgpu: create instance, adapter, device, queue, and configure
-    /// the surface for the given window.
-    pub fn new(window: Arc<winit::window::Window>) -> Self {
+    /// the surface for the given window. The adapter is chosen by `selection`
+    /// (see [`adapter`](super::adapter)). Without `vsync`, frames are presented
+    /// as soon as they are ready (mailbox or immediate, whichever is supported).
+    pub fn new(
+        window: Arc<winit::window::Window>,
+        vsync: bool,
+        selection: &AdapterSelection,
+    ) -> Self {
         let size = window.inner_size();
 
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: selection.backends,
+            ..Default::default()
+        });
 
         let surface = instance.create_surface(window).unwrap();
 
-        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
-            power_preference: wgpu::PowerPreference::default(),
-            compatible_surface: Some(&surface),
-            force_fallback_adapter: false,
-        }))
-        .expect("Failed to find a suitable GPU adapter");
+        // Only adapters that can present to this window are candidates.
+        let mut candidates: Vec<wgpu::Adapter> = instance
+            .enumerate_adapters(selection.backends)
+            .into_iter()
+            .filter(|a| a.is_surface_supported(&surface))
+            .collect();
+        let infos: Vec<_> = candidates.iter().map(wgpu::Adapter::get_info).collect();
+        let adapter = match selection.pick(&infos) {
+            Some(i) => candidates.swap_remove(i),
+            None => {
+                let options = selection.request_options(&surface);
+                pollster::block_on(instance.request_adapter(&options))
+                    .expect("Failed to find a suitable GPU adapter")
+            }
+        };
+        let info = adapter.get_info();
+        log::info!("Using GPU: {} ({:?}, {:?})", info.name, info.device_type, info.backend);
+        crate::version::record_adapter(&info);
 
+        // Binding arrays have no elements by default; take the adapter's.
+        let texture_arrays = adapter.features().contains(TEXTURE_ARRAY_FEATURES);
+        let required_limits = if texture_arrays {
+            let adapter_limits = adapter.limits();
+            wgpu::Limits {
+                max_binding_array_elements_per_shader_stage: adapter_limits
+                    .max_binding_array_elements_per_shader_stage,
+                max_binding_array_sampler_elements_per_shader_stage: adapter_limits
+                    .max_binding_array_sampler_elements_per_shader_stage,
+                ..wgpu::Limits::default()
+            }
+        } else {
+            wgpu::Limits::default()
+        };
         let (device, queue) = pollster::block_on(adapter.request_device(
             &wgpu::DeviceDescriptor {
                 label: Some("necs device".into()),
-                required_features: wgpu::Features::empty(),
-                required_limits: wgpu::Limits::default(),
+                // Lets MSAA use 2× and 8× where the adapter supports them.
+                required_features: adapter.features()
+                    & (wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES | TEXTURE_ARRAY_FEATURES),
+                required_limits,
                 ..Default::default()
             },
         ))
@@ -55,7 +105,11 @@ impl GpuContext {
             format: surface_format,
             width: size.width.max(1),
             height: size.height.max(1),
-            present_mode: wgpu::PresentMode::AutoVsync,
+            present_mode: if vsync {
+                wgpu::PresentMode::AutoVsync
+            } else {
+                wgpu::PresentMode::AutoNoVsync
+            },
             alpha_mode: surface_caps.alpha_modes[0],
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
@@ -63,9 +117,10 @@ impl GpuContext {
         surface.configure(&device, &surface_config);
 
         Self {
+            adapter,
             device,
             queue,
-            surface,
+            surface: Arc::new(surface),
             surface_config,
         }
     }
@@ -1,7 +1,32 @@
 //! Rendering subsystem — wgpu abstraction.
 
+pub mod adapter;
+pub mod capture;
+pub mod color_grading;
+pub(crate) mod extract;
 pub mod gpu;
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub(crate) mod msaa;
 pub mod pass;
+pub(crate) mod pipelined;
+pub mod readback;
+pub mod sampler;
+pub mod settings;
+pub mod shader_defs;
+pub mod shader_diff;
+pub use crate::ecs::visibility;
 
+pub use adapter::{AdapterInfo, AdapterPreference, AdapterSelection, available_adapters};
+pub use capture::FrameCapture;
+pub use color_grading::{ColorGrading, Lut3d, LutError};
 pub use gpu::GpuContext;
-pub use pass::ClearColor;
+pub use pass::{CameraClear, ClearColor};
+pub use readback::{
+    ReadbackComplete, ReadbackData, ReadbackId, ReadbackImage, Readbacks, readback_buffer, readback_texture,
+    wait_for_readback,
+};
+pub use sampler::{SamplerSettings, TextureFilter, TextureWrap};
+pub use settings::{GraphicsSettings, PostEffects, QualityPreset};
+pub use shader_defs::{ShaderDefError, ShaderDefs, preprocess};
+pub use shader_diff::{DiffStats, DiffView, ShaderComparison, ShaderDiff};
+pub use visibility::{ComputedVisibility, Hidden, Visibility, propagate_visibility};
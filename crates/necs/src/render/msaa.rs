@@ -0,0 +1,216 @@
+//! # MSAA — Smoothing Polygon Edges
+//!
+//! A triangle either covers a pixel's center or it doesn't, so edges come
+//! out as stair steps. Multisample anti-aliasing stores several coverage
+//! samples per pixel and runs the fragment shader only once per pixel per
+//! triangle; at the end the samples are averaged (*resolved*) into the
+//! normal target:
+//!
+//! ```text
+//!   1× (aliased)        4× samples              resolved
+//!   ┌──┬──┬──┐          ┌──┬──┬──┐              ┌──┬──┬──┐
+//!   │██│██│  │          │██│█▖│  │              │██│▓▓│  │
+//!   ├──┼──┼──┤   ──►    ├──┼──┼──┤     ──►      ├──┼──┼──┤
+//!   │██│  │  │          │█▙│▘ │  │              │▓▓│░░│  │
+//!   └──┴──┴──┘          └──┴──┴──┘              └──┴──┴──┘
+//! ```
+//!
+//! The sample count comes from
+//! [`GraphicsSettings::msaa_samples`](super::GraphicsSettings). Each frame
+//! the scene draws into a multisampled color texture (and, in 3D, a
+//! multisampled depth buffer) that every scene pass resolves into the
+//! frame's target — the surface, or the offscreen target used by color
+//! grading. The UI and editor overlay draw afterwards, single-sampled.
+//!
+//! Pipelines bake in their sample count, so when the setting changes the
+//! 2D and 3D renderers rebuild theirs on the next frame. Counts the GPU
+//! can't do for the formats involved fall back to the next lower one, with
+//! a warning: 1× and 4× always work, 2× and 8× depend on the adapter.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: MSAA level on the render pipeline asset, per camera
+//!   override; changing it recreates the camera's targets.
+//! - **Bevy**: `Msaa` component on the camera; pipelines are specialized
+//!   per sample count.
+//! - **Godot**: `msaa_2d` / `msaa_3d` on the viewport, 2×–8×.
+//! - **Our approach**: One field on the graphics settings, applied to the
+//!   whole scene; Bevy's per-count pipelines, rebuilt rather than cached.
+
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+use crate::render::pass::FrameContext;
+use crate::render::settings::graphics_settings;
+
+/// Multisampled color texture the scene draws into, kept across frames.
+#[derive(Default)]
+pub(crate) struct MsaaTarget {
+    /// The texture's view, size and sample count.
+    target: Option<(wgpu::TextureView, (u32, u32), u32)>,
+    /// Requested count last warned about as unsupported.
+    warned: u32,
+}
+
+/// What a pipeline's color target must match: texture format and sample
+/// count. Renderers rebuild their pipelines when it changes.
+#[cfg_attr(not(feature = "render3d"), allow(dead_code))]
+pub(crate) type TargetKey = (wgpu::TextureFormat, u32);
+
+/// Where a scene pass draws: the frame's target, through a multisampled
+/// texture when MSAA is on. Cheap to clone (views are reference-counted).
+#[derive(Clone)]
+#[cfg_attr(not(feature = "render3d"), allow(dead_code))]
+pub(crate) struct ColorTarget {
+    pub view: wgpu::TextureView,
+    pub msaa: Option<wgpu::TextureView>,
+    pub format: wgpu::TextureFormat,
+    pub samples: u32,
+}
+
+impl ColorTarget {
+    /// The format and sample count pipelines drawing here need.
+    #[cfg_attr(not(feature = "render3d"), allow(dead_code))]
+    pub fn key(&self) -> TargetKey {
+        (self.format, self.samples)
+    }
+
+    /// A color attachment drawing into this target, resolving the samples
+    /// into the frame's target at the end of the pass.
+    pub fn attachment(&self, load: wgpu::LoadOp<wgpu::Color>) -> wgpu::RenderPassColorAttachment<'_> {
+        let (view, resolve_target) = match &self.msaa {
+            Some(msaa) => (msaa, Some(&self.view)),
+            None => (&self.view, None),
+        };
+        wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load,
+                // Later passes load the samples, so keep them.
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        }
+    }
+}
+
+/// Pipeline multisample state for `samples` per pixel.
+pub(crate) fn multisample_state(samples: u32) -> wgpu::MultisampleState {
+    wgpu::MultisampleState {
+        count: samples,
+        ..Default::default()
+    }
+}
+
+/// Switch the scene render to the multisampled target, if the settings ask
+/// for MSAA. Call after the frame's target is final for the scene (after
+/// [`begin_grading`](super::color_grading::begin_grading)).
+pub(crate) fn begin_msaa(world: &mut World, frame: &mut FrameContext<'_>) {
+    let requested = graphics_settings(world).sample_count();
+    if requested == 1 && !world.has_resource::<MsaaTarget>() {
+        return;
+    }
+    let mut msaa = world.resource_remove::<MsaaTarget>().unwrap_or_default();
+    let samples = supported_samples(frame.gpu, requested);
+    if samples != requested && msaa.warned != requested {
+        log::warn!("{requested}× MSAA isn't supported by this GPU; using {samples}×");
+        msaa.warned = requested;
+    }
+    if samples == 1 {
+        // Free the texture; keep the warning state.
+        msaa.target = None;
+        world.insert_resource(msaa);
+        return;
+    }
+
+    if !msaa
+        .target
+        .as_ref()
+        .is_some_and(|(_, size, count)| *size == frame.size && *count == samples)
+    {
+        msaa.target = Some((create_msaa_view(frame.gpu, frame.size, samples), frame.size, samples));
+    }
+    if let Some((view, _, _)) = &msaa.target {
+        frame.msaa = Some(view.clone());
+        frame.samples = samples;
+    }
+    world.insert_resource(msaa);
+}
+
+/// Back to single-sampled drawing for the passes after the scene.
+pub(crate) fn end_msaa(frame: &mut FrameContext<'_>) {
+    frame.msaa = None;
+    frame.samples = 1;
+}
+
+fn create_msaa_view(gpu: &GpuContext, (width, height): (u32, u32), samples: u32) -> wgpu::TextureView {
+    gpu.device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("msaa color target"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: samples,
+            dimension: wgpu::TextureDimension::D2,
+            format: gpu.surface_format(),
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// The highest sample count up to `requested` that every multisampled
+/// format of the scene supports.
+fn supported_samples(gpu: &GpuContext, requested: u32) -> u32 {
+    #[cfg(feature = "render3d")]
+    let formats = [
+        gpu.surface_format(),
+        crate::render3d::pipeline::DEPTH_FORMAT,
+        crate::render3d::transparency::ACCUM_FORMAT,
+        crate::render3d::transparency::REVEALAGE_FORMAT,
+    ];
+    #[cfg(not(feature = "render3d"))]
+    let formats = [gpu.surface_format()];
+    // Without adapter-specific format features only the WebGPU guarantees
+    // (1× and 4×) may be used.
+    let features = gpu.device.features();
+    let adapter_specific = features.contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES);
+    pick_samples(requested, |count| {
+        formats.iter().all(|&format| {
+            let flags = if adapter_specific {
+                gpu.adapter.get_texture_format_features(format).flags
+            } else {
+                format.guaranteed_format_features(features).flags
+            };
+            let resolvable = format.is_depth_stencil_format()
+                || flags.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE);
+            flags.sample_count_supported(count) && resolvable
+        })
+    })
+}
+
+/// The highest of 8, 4, 2 that is at most `requested` and `supports`, or 1.
+fn pick_samples(requested: u32, supports: impl Fn(u32) -> bool) -> u32 {
+    [8, 4, 2]
+        .into_iter()
+        .find(|&count| count <= requested && supports(count))
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_counts_fall_back_to_lower_ones() {
+        let guaranteed = |count| count == 4;
+        assert_eq!(pick_samples(8, guaranteed), 4);
+        assert_eq!(pick_samples(4, guaranteed), 4);
+        assert_eq!(pick_samples(2, guaranteed), 1);
+        assert_eq!(pick_samples(1, |_| true), 1);
+        assert_eq!(pick_samples(8, |_| true), 8);
+    }
+}
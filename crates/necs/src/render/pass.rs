@@ -1,14 +1,48 @@
 //! Render pass orchestration.
 //!
 //! When both `render2d` and `render3d` features are enabled, runtime dispatch
-//! picks the 3D path if a `Camera3d` component exists, otherwise the 2D path.
-//! Falls back to a simple clear pass when neither feature is enabled.
+//! picks the 3D path if a `Camera3d` component exists, otherwise the 2D path;
+//! the choice is made by the [extract phase](super::extract), which copies
+//! the active scene out of the ECS before rendering starts. Falls back to a
+//! simple clear pass when neither feature is enabled.
+//!
+//! When a [`ColorGrading`](super::ColorGrading) resource exists, or
+//! [`GraphicsSettings`](super::GraphicsSettings) asks for a lower resolution
+//! scale, the scene is rendered offscreen and graded (or just upscaled) onto
+//! the surface before the overlay runs. A [`ShaderDiff`](super::ShaderDiff)
+//! captures its before/after frames from the same offscreen target.
+//!
+//! [2D post effects](crate::render2d::post) render the scene into a target
+//! of their own inside that, and run their chain into it afterwards.
+//!
+//! With [MSAA](super::msaa) on, the scene passes draw into a multisampled
+//! texture and resolve into that target; everything after the scene is
+//! single-sampled.
+//!
+//! ## Clearing
+//!
+//! Each frame starts by clearing the target to the global [`ClearColor`].
+//! A camera entity can override this with a [`CameraClear`] component:
+//!
+//! | `CameraClear` | Load op |
+//! |---------------|---------|
+//! | `Global` (default) | clear to the [`ClearColor`] resource |
+//! | `Color(rgba)` | clear to this camera's own color |
+//! | `Load` | keep the previous contents (trails, accumulation) |
 
 use crate::ecs::World;
+use crate::render::capture::markers_enabled;
+use crate::render::color_grading::{begin_grading, finish_grading};
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+use crate::render::extract::{prepare_frame, ExtractedFrame};
 use crate::render::gpu::GpuContext;
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+use crate::render::msaa::{begin_msaa, end_msaa, ColorTarget};
+use crate::render::shader_diff::{begin_shader_diff, finish_shader_diff};
 
 /// The clear color resource. Set this to change the background color.
-#[derive(Debug, Clone, Copy)]
+/// Scenes can save it, see [`SceneRegistry::register_resource`](crate::scene::SceneRegistry::register_resource).
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct ClearColor(pub [f64; 4]);
 
 impl Default for ClearColor {
@@ -18,6 +52,45 @@ impl Default for ClearColor {
     }
 }
 
+/// Component: how a camera's render target is initialized each frame.
+/// Attach to a `Camera2d` or `Camera3d` entity.
+///
+/// `Load` keeps whatever the target held before. The offscreen target used
+/// by [`ColorGrading`](super::ColorGrading) persists between frames; the
+/// window surface's previous contents are backend-dependent, so `Load`
+/// without grading may show stale or undefined pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum CameraClear {
+    /// Clear to the global [`ClearColor`] resource.
+    #[default]
+    Global,
+    /// Clear to this RGBA color instead of the global one.
+    Color([f64; 4]),
+    /// Don't clear — draw over the previous contents.
+    Load,
+}
+
+/// Resolve the color load op for a camera's extracted [`CameraClear`],
+/// falling back to the [`ClearColor`] resource.
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+pub(crate) fn camera_load_op(clear: CameraClear, world: &World) -> wgpu::LoadOp<wgpu::Color> {
+    let color = match clear {
+        CameraClear::Load => return wgpu::LoadOp::Load,
+        CameraClear::Color(color) => color,
+        CameraClear::Global => world
+            .get_resource::<ClearColor>()
+            .copied()
+            .unwrap_or_default()
+            .0,
+    };
+    wgpu::LoadOp::Clear(wgpu::Color {
+        r: color[0],
+        g: color[1],
+        b: color[2],
+        a: color[3],
+    })
+}
+
 /// Per-frame render context passed to 2D/3D renderers.
 ///
 /// Created by [`render_frame`], which acquires the surface texture and encoder.
@@ -26,7 +99,33 @@ impl Default for ClearColor {
 pub(crate) struct FrameContext<'a> {
     pub encoder: wgpu::CommandEncoder,
     pub view: wgpu::TextureView,
+    /// Pixel size of `view`: the surface, or the smaller offscreen scene
+    /// target under a resolution scale.
+    #[cfg_attr(not(feature = "render3d"), allow(dead_code))]
+    pub size: (u32, u32),
     pub gpu: &'a GpuContext,
+    /// Label individual draws with debug markers (a frame capture is running).
+    #[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+    pub debug_markers: bool,
+    /// Multisampled texture scene passes draw into, resolving to `view`.
+    #[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+    pub msaa: Option<wgpu::TextureView>,
+    /// Samples per pixel of the scene passes: 1 without MSAA.
+    #[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+    pub samples: u32,
+}
+
+#[cfg(any(feature = "render2d", feature = "render3d"))]
+impl FrameContext<'_> {
+    /// Where scene passes draw this frame.
+    pub fn target(&self) -> ColorTarget {
+        ColorTarget {
+            view: self.view.clone(),
+            msaa: self.msaa.clone(),
+            format: self.gpu.surface_format(),
+            samples: self.samples,
+        }
+    }
 }
 
 /// Render a single frame. Dispatches to 2D or 3D renderer based on the scene.
@@ -54,31 +153,54 @@ pub(crate) fn render_frame(
     let mut frame = FrameContext {
         encoder,
         view,
+        size: gpu.surface_size(),
         gpu: &gpu,
+        debug_markers: markers_enabled(world),
+        msaa: None,
+        samples: 1,
     };
 
-    // Dispatch to the appropriate renderer.
-    #[cfg(all(feature = "render2d", feature = "render3d"))]
-    {
-        if world.has_component_type::<crate::render3d::Camera3d>() {
-            crate::render3d::draw::render_meshes_3d(world, &mut frame);
-        } else {
-            crate::render2d::draw::render_sprites_2d(world, &mut frame);
-        }
-    }
+    // Redirect the scene into the offscreen target if color grading or a
+    // resolution scale needs it.
+    let surface_view = begin_grading(world, &mut frame);
+    // After a shader reload, keep last frame (old shader) for the A/B view.
+    let diff_shader = begin_shader_diff(world, &mut frame);
+    #[cfg(feature = "render2d")]
+    let post_view = crate::render2d::post::begin_post_effects(world, &mut frame);
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    begin_msaa(world, &mut frame);
 
-    #[cfg(all(feature = "render2d", not(feature = "render3d")))]
-    {
-        crate::render2d::draw::render_sprites_2d(world, &mut frame);
-    }
+    // Debug groups make frame captures follow this structure.
+    frame.encoder.push_debug_group("scene");
 
-    #[cfg(all(not(feature = "render2d"), feature = "render3d"))]
-    {
-        crate::render3d::draw::render_meshes_3d(world, &mut frame);
-    }
+    // Draw the scene copied out by the extract phase (or extract now if the
+    // frame loop didn't). With its Subsystems switch off, nothing was
+    // extracted and the frame is only cleared.
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    let extracted = world
+        .resource_remove::<ExtractedFrame>()
+        .unwrap_or_else(|| prepare_frame(world));
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    let drawn = {
+        let mut drawn = false;
+
+        #[cfg(feature = "render3d")]
+        if let Some(scene) = &extracted.scene_3d {
+            crate::render3d::draw::render_meshes_3d(world, &mut frame, scene);
+            drawn = true;
+        }
 
+        #[cfg(feature = "render2d")]
+        if let Some(scene) = &extracted.scene_2d {
+            crate::render2d::draw::render_sprites_2d(world, &mut frame, scene);
+            drawn = true;
+        }
+        drawn
+    };
     #[cfg(all(not(feature = "render2d"), not(feature = "render3d")))]
-    {
+    let drawn = false;
+
+    if !drawn {
         let clear_color = world
             .get_resource::<ClearColor>()
             .copied()
@@ -108,13 +230,46 @@ pub(crate) fn render_frame(
         }
     }
 
+    frame.encoder.pop_debug_group();
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    end_msaa(&mut frame);
+
+    #[cfg(feature = "render2d")]
+    if let Some(view) = post_view {
+        frame.encoder.push_debug_group("post effects");
+        crate::render2d::post::finish_post_effects(world, &mut frame, view);
+        frame.encoder.pop_debug_group();
+    }
+
+    let comparison = finish_shader_diff(world, &mut frame, diff_shader);
+
+    frame.encoder.push_debug_group("color grading");
+    let screenshot = surface_view.and_then(|view| finish_grading(world, &mut frame, view));
+    frame.encoder.pop_debug_group();
+
+    #[cfg(feature = "render2d")]
+    if let Some(ui) = &extracted.ui {
+        frame.encoder.push_debug_group("ui");
+        crate::ui::draw::render_ui(world, &mut frame, ui);
+        frame.encoder.pop_debug_group();
+    }
+
     // Apply overlay (editor, debug visualizations, etc.)
+    frame.encoder.push_debug_group("overlay");
     overlay(&mut frame);
+    frame.encoder.pop_debug_group();
 
     // Submit all recorded passes and present.
     gpu.queue.submit(std::iter::once(frame.encoder.finish()));
     output.present();
 
+    if let Some(screenshot) = screenshot {
+        screenshot.save(&gpu.device);
+    }
+    if let Some(comparison) = comparison {
+        comparison.finish(world, &gpu.device);
+    }
+
     world.insert_resource(gpu);
 
     Ok(())
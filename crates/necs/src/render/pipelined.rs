@@ -0,0 +1,327 @@
+//! # Pipelined Rendering — Encoding on a Render Thread
+//!
+//! With [`Game::pipelined_rendering`](crate::game::Game::pipelined_rendering)
+//! on, each frame's GPU commands are recorded, submitted and presented on a
+//! render thread while the main thread already runs the next frame's
+//! update:
+//!
+//! ```text
+//!   main thread:    update N ─► extract N ─► update N+1 ─► wait ─► extract N+1 ─► ...
+//!                                   │                       ▲          │
+//!   render thread:                  └─► encode + present N ─┘          └─► ...
+//! ```
+//!
+//! ## The Handoff
+//!
+//! [`submit_render`] builds a render-side [`World`] for the frame and sends
+//! it to the thread. It holds:
+//!
+//! - a clone of the [`GpuContext`] — device, queue and surface are shared;
+//! - the frame's [`ExtractedFrame`];
+//! - copies of the few settings rendering reads: clear color, graphics
+//!   settings, time, launch options, post effects, the skybox, color grading;
+//! - the GPU-side stores and renderers themselves — textures, meshes, fonts,
+//!   material shaders, pipelines and offscreen targets. These are *moved*:
+//!   while the frame renders, the main world doesn't have them.
+//!
+//! The thread runs the same [`render_frame`] as the serial path against that
+//! world. Every frame extracts into a value of its own that travels with
+//! it, so the frame being encoded and the frame being updated never share
+//! one.
+//!
+//! ## Waiting
+//!
+//! [`finish_render`] waits for the frame in flight and moves everything
+//! back. The frame loop calls it after transforms and particles, before
+//! laying out the UI (which measures text) and extracting the next frame;
+//! that is the stretch that overlaps. Anything else that needs the stores on
+//! the main thread waits first:
+//!
+//! - loading textures, fonts, glTF scenes and material shaders, changing
+//!   samplers or vertex attributes, finishing async loads;
+//! - asset hot reloads and [garbage collection](crate::asset_gc);
+//! - placing the caret in a focused text field;
+//! - window resizes and shutdown.
+//!
+//! A game that uploads every frame (a playing video, say) therefore gets no
+//! overlap, but still renders correctly.
+//!
+//! ## Serial Frames
+//!
+//! Some frames render on the main thread anyway: with the editor open (its
+//! overlay needs the editor), while a [`FrameCapture`](super::FrameCapture)
+//! is capturing (the capture brackets the frame on this thread), and while a
+//! [`ShaderDiff`](super::ShaderDiff) exists (it finishes against the main
+//! world).
+//!
+//! ## What Lags
+//!
+//! - [`RenderStats`](crate::diag::RenderStats) come back with the frame, so
+//!   the next update reads last frame's numbers a frame later.
+//! - `AfterRender` hooks run once the frame is handed off, not presented.
+//! - Input latency is recorded when the frame comes back.
+//! - Surface errors surface a frame later; a lost surface is reconfigured
+//!   then.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Multithreaded rendering — the main thread records a command
+//!   stream that the render thread executes a frame behind.
+//! - **Bevy**: Pipelined rendering swaps the render `World` to a render
+//!   thread; extraction is the sync point between the two.
+//! - **Our approach**: Bevy's shape, with a throwaway render `World` per
+//!   frame that the stores ride along in. Opt-in, off by default.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::Instant;
+
+use crate::ecs::World;
+use crate::input::InputLatency;
+use crate::render::capture::markers_enabled;
+use crate::render::color_grading::ColorGrading;
+use crate::render::extract::ExtractedFrame;
+use crate::render::gpu::GpuContext;
+use crate::render::pass::{render_frame, ClearColor};
+use crate::render::settings::GraphicsSettings;
+use crate::render::shader_diff::ShaderDiff;
+
+/// A rendered frame's world, handed back with how presenting went.
+type Rendered = (World, Result<(), wgpu::SurfaceError>);
+
+/// Resource: the render thread, present while pipelined rendering is on.
+pub(crate) struct RenderThread {
+    /// Frames to render. Dropped to stop the thread.
+    jobs: Option<Sender<World>>,
+    /// Rendered frames coming back. Only locked by `&mut` access.
+    done: Mutex<Receiver<Rendered>>,
+    worker: Option<JoinHandle<()>>,
+    /// Set while a frame is out, with when its input arrived.
+    in_flight: Option<Option<Instant>>,
+    /// The last frame's surface error, until the frame loop takes it.
+    error: Option<wgpu::SurfaceError>,
+}
+
+impl RenderThread {
+    /// Start the render thread. It idles until a frame is submitted.
+    pub(crate) fn new() -> Self {
+        let (jobs, job_rx) = mpsc::channel::<World>();
+        let (done_tx, done) = mpsc::channel::<Rendered>();
+        let worker = std::thread::Builder::new()
+            .name("necs render".into())
+            .spawn(move || {
+                for mut world in job_rx {
+                    let result = render_frame(&mut world, |_| {});
+                    if done_tx.send((world, result)).is_err() {
+                        break;
+                    }
+                }
+            })
+            .expect("failed to spawn the render thread");
+        Self {
+            jobs: Some(jobs),
+            done: Mutex::new(done),
+            worker: Some(worker),
+            in_flight: None,
+            error: None,
+        }
+    }
+}
+
+impl Drop for RenderThread {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread's loop.
+        self.jobs = None;
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Whether this frame can render on the render thread: pipelining is on,
+/// there is a GPU, and nothing needs the frame on the main thread.
+pub(crate) fn can_pipeline(world: &World) -> bool {
+    world.has_resource::<RenderThread>()
+        && world.has_resource::<GpuContext>()
+        && !world.has_resource::<ShaderDiff>()
+        && !markers_enabled(world)
+}
+
+/// Hand the extracted frame to the render thread, with the GPU stores and
+/// renderers it draws with. `input_at` is when the frame's input arrived,
+/// recorded as latency once it is presented.
+pub(crate) fn submit_render(world: &mut World, input_at: Option<Instant>) {
+    finish_render(world);
+    let gpu = world.resource::<GpuContext>().clone();
+    create_renderers(world, &gpu);
+
+    let mut frame = World::new();
+    frame.insert_resource(gpu);
+    move_resource::<ExtractedFrame>(world, &mut frame);
+    copy_resource::<ClearColor>(world, &mut frame);
+    copy_resource::<GraphicsSettings>(world, &mut frame);
+    copy_resource::<crate::time::Time>(world, &mut frame);
+    copy_resource::<crate::launch::LaunchOptions>(world, &mut frame);
+    #[cfg(feature = "diagnostics")]
+    copy_resource::<crate::diag::RenderStats>(world, &mut frame);
+    #[cfg(feature = "render2d")]
+    copy_resource::<crate::render2d::post::PostEffects2d>(world, &mut frame);
+    #[cfg(feature = "render3d")]
+    {
+        copy_resource::<crate::render3d::hdr::PostProcess>(world, &mut frame);
+        copy_resource::<crate::render3d::skybox::Skybox>(world, &mut frame);
+    }
+    if let Some(grading) = world.get_resource_mut::<ColorGrading>() {
+        frame.insert_resource(grading.frame_copy());
+    }
+    move_render_resources(world, &mut frame);
+
+    let thread = world.resource_mut::<RenderThread>();
+    thread.in_flight = Some(input_at);
+    if let Some(jobs) = &thread.jobs {
+        // A thread that died drops the frame; finish_render reports why.
+        let _ = jobs.send(frame);
+    }
+}
+
+/// Wait for the frame in flight, if any, and move the stores and renderers
+/// back into the main world. Re-raises a panic from the render thread.
+pub(crate) fn finish_render(world: &mut World) {
+    let Some(thread) = world.get_resource_mut::<RenderThread>() else {
+        return;
+    };
+    let Some(input_at) = thread.in_flight.take() else {
+        return;
+    };
+    let received = match thread.done.get_mut() {
+        Ok(done) => done.recv().ok(),
+        Err(_) => None,
+    };
+    let Some((mut frame, result)) = received else {
+        // The thread is gone; it can only have panicked.
+        let worker = thread.worker.take().expect("render thread already joined");
+        thread.jobs = None;
+        match worker.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => panic!("render thread stopped"),
+        }
+    };
+    thread.error = result.err();
+
+    move_render_resources(&mut frame, world);
+    #[cfg(feature = "diagnostics")]
+    move_resource::<crate::diag::RenderStats>(&mut frame, world);
+
+    if let Some(at) = input_at
+        && let Some(latency) = world.get_resource_mut::<InputLatency>()
+    {
+        latency.record(at.elapsed());
+    }
+}
+
+/// Take the surface error the last pipelined frame ran into, if any.
+pub(crate) fn take_render_error(world: &mut World) -> Option<wgpu::SurfaceError> {
+    world.get_resource_mut::<RenderThread>()?.error.take()
+}
+
+/// Create the renderers that register shaders for hot reload, here on the
+/// main thread where the [`AssetServer`](crate::asset::AssetServer) is.
+/// The rest are created by the render thread on first use.
+fn create_renderers(world: &mut World, gpu: &GpuContext) {
+    #[cfg(feature = "render2d")]
+    if world
+        .get_resource::<ExtractedFrame>()
+        .is_some_and(|frame| frame.scene_2d.is_some() || frame.ui.is_some())
+    {
+        crate::render2d::draw::ensure_sprite_renderer(world, gpu);
+    }
+    #[cfg(feature = "render3d")]
+    if world.get_resource::<ExtractedFrame>().is_some_and(|frame| frame.scene_3d.is_some()) {
+        let samples = crate::render::settings::graphics_settings(world).sample_count();
+        crate::render3d::draw::ensure_mesh_renderer(world, gpu, (gpu.surface_format(), samples));
+    }
+    #[cfg(not(any(feature = "render2d", feature = "render3d")))]
+    let _ = (world, gpu);
+}
+
+/// Move every GPU-side store and renderer from one world to the other.
+fn move_render_resources(from: &mut World, to: &mut World) {
+    move_resource::<crate::render::color_grading::ColorGradingRenderer>(from, to);
+    #[cfg(any(feature = "render2d", feature = "render3d"))]
+    move_resource::<crate::render::msaa::MsaaTarget>(from, to);
+    #[cfg(feature = "render2d")]
+    {
+        move_resource::<crate::render2d::pipeline::SpriteRenderer>(from, to);
+        move_resource::<crate::render2d::texture::TextureStore>(from, to);
+        move_resource::<crate::render2d::font::FontStore>(from, to);
+        move_resource::<crate::render2d::post::PostRenderer>(from, to);
+        move_resource::<crate::ui::draw::UiRenderer>(from, to);
+    }
+    #[cfg(feature = "physics2d")]
+    move_resource::<crate::render2d::debug_wireframe::DebugWireframeRenderer2d>(from, to);
+    #[cfg(feature = "render3d")]
+    {
+        move_resource::<crate::render3d::pipeline::MeshRenderer>(from, to);
+        move_resource::<crate::render3d::mesh::MeshStore>(from, to);
+        move_resource::<crate::render3d::texture::TextureStore3d>(from, to);
+        move_resource::<crate::render3d::material_shader::MaterialShaders>(from, to);
+        move_resource::<crate::render3d::hdr::HdrRenderer>(from, to);
+        move_resource::<crate::render3d::skybox::SkyboxRenderer>(from, to);
+        move_resource::<crate::render3d::transparency::OitRenderer>(from, to);
+        move_resource::<crate::render3d::soft_particle::SoftParticleRenderer>(from, to);
+        move_resource::<crate::render3d::particles::ParticleRenderer3d>(from, to);
+    }
+    #[cfg(all(feature = "render3d", feature = "render2d"))]
+    move_resource::<crate::render3d::text3d::Text3dRenderer>(from, to);
+    #[cfg(feature = "physics3d")]
+    move_resource::<crate::render3d::debug_wireframe::DebugWireframeRenderer>(from, to);
+}
+
+/// Move resource `T`, if `from` has it.
+fn move_resource<T: 'static + Send + Sync>(from: &mut World, to: &mut World) {
+    if let Some(value) = from.resource_remove::<T>() {
+        to.insert_resource(value);
+    }
+}
+
+/// Copy resource `T`, if `from` has it.
+fn copy_resource<T: 'static + Send + Sync + Clone>(from: &World, to: &mut World) {
+    if let Some(value) = from.get_resource::<T>() {
+        to.insert_resource(value.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resources_move_and_copy_between_worlds() {
+        let mut main = World::new();
+        let mut frame = World::new();
+        main.insert_resource(ClearColor([0.1, 0.2, 0.3, 1.0]));
+        main.insert_resource(String::from("store"));
+
+        copy_resource::<ClearColor>(&main, &mut frame);
+        move_resource::<String>(&mut main, &mut frame);
+        assert_eq!(frame.resource::<ClearColor>().0, [0.1, 0.2, 0.3, 1.0]);
+        assert!(main.has_resource::<ClearColor>());
+        assert_eq!(frame.resource::<String>(), "store");
+        assert!(!main.has_resource::<String>());
+
+        // Moving something that isn't there leaves the target alone.
+        move_resource::<String>(&mut main, &mut frame);
+        assert!(frame.has_resource::<String>());
+    }
+
+    #[test]
+    fn nothing_in_flight_means_nothing_to_wait_for() {
+        let mut world = World::new();
+        finish_render(&mut world);
+        world.insert_resource(RenderThread::new());
+        finish_render(&mut world);
+        assert!(take_render_error(&mut world).is_none());
+        assert!(!can_pipeline(&world), "no GPU, nothing to pipeline");
+    }
+}
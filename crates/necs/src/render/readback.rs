@@ -0,0 +1,509 @@
+//! # Readback — Getting GPU Data Back to the CPU
+//!
+//! Textures and buffers live in GPU memory; the CPU can't just read them.
+//! Reading one back takes three steps: copy it into a buffer the CPU is
+//! allowed to map, submit that copy, then *map* the buffer — which only
+//! completes once the GPU has actually run the copy, often a frame or two
+//! later:
+//!
+//! ```text
+//!   readback_texture(world, &tex)                 frame N
+//!     ├─ encoder: copy tex ──► staging buffer
+//!     ├─ submit
+//!     └─ map_async(staging) ─────────┐
+//!                                    │  GPU catches up...
+//!   process_readbacks (frame start)  ▼            frame N+1, N+2, ...
+//!     ├─ device.poll (non-blocking)
+//!     └─ mapped? ──► copy out, unmap ──► ReadbackComplete { id, result }
+//! ```
+//!
+//! The frame loop polls every frame and sends a [`ReadbackComplete`] event
+//! per finished readback, so nothing stalls waiting on the GPU:
+//!
+//! ```ignore
+//! let id = readback_texture(ctx.world, &picking_texture)?;
+//! // ... later, in a system with an EventReader<ReadbackComplete>:
+//! for done in reader.read(ctx.world.resource::<Events<ReadbackComplete>>()) {
+//!     if done.id == id && let Ok(ReadbackData::Image(image)) = &done.result {
+//!         let rgba = image.to_rgba8();
+//!     }
+//! }
+//! ```
+//!
+//! Code that can afford to stall — golden-image tests, tools — calls
+//! [`wait_for_readback`] instead and gets the data at once.
+//!
+//! Render code that already has an encoder records the copy into it with
+//! [`Readbacks::record_texture`] / [`Readbacks::record_buffer`]; mapping
+//! starts at the next poll, after the encoder has been submitted.
+//!
+//! The source needs `COPY_SRC` usage and, for textures, one sample per pixel
+//! and an uncompressed format. Anything else is refused with a warning.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `AsyncGPUReadback.Request` with a completion callback, or
+//!   the blocking `Texture2D.ReadPixels`.
+//! - **Bevy**: `Readback` component on an image or buffer entity, firing a
+//!   `ReadbackComplete` observer event.
+//! - **Godot**: `RenderingDevice.texture_get_data_async` with a callback.
+//! - **Our approach**: Unity's request shape, with completion delivered as
+//!   an ordinary [event](crate::ecs::event) like Bevy's.
+
+use std::sync::{Arc, OnceLock};
+
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+
+/// Identifies one readback; matched against [`ReadbackComplete::id`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ReadbackId(u64);
+
+/// Pixels read back from a texture, rows tightly packed (no padding).
+#[derive(Debug, Clone)]
+pub struct ReadbackImage {
+    pub width: u32,
+    pub height: u32,
+    /// The source texture's format; `data` holds its texels as stored.
+    pub format: wgpu::TextureFormat,
+    pub data: Vec<u8>,
+}
+
+impl ReadbackImage {
+    /// The pixels as RGBA8, converting from BGRA. `None` for formats other
+    /// than 8-bit RGBA and BGRA.
+    pub fn to_rgba8(&self) -> Option<Vec<u8>> {
+        use wgpu::TextureFormat as F;
+        match self.format {
+            F::Rgba8Unorm | F::Rgba8UnormSrgb => Some(self.data.clone()),
+            F::Bgra8Unorm | F::Bgra8UnormSrgb => {
+                let mut rgba = self.data.clone();
+                for pixel in rgba.chunks_exact_mut(4) {
+                    pixel.swap(0, 2);
+                }
+                Some(rgba)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What a readback returned.
+#[derive(Debug, Clone)]
+pub enum ReadbackData {
+    Image(ReadbackImage),
+    Buffer(Vec<u8>),
+}
+
+/// Event: a readback finished (or failed to map). Registered by the engine.
+#[derive(Debug, Clone)]
+pub struct ReadbackComplete {
+    pub id: ReadbackId,
+    pub result: Result<ReadbackData, wgpu::BufferAsyncError>,
+}
+
+// ── Pending readbacks ───────────────────────────────────────────────────
+
+/// How the staging buffer's bytes are laid out.
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    Image {
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+        /// Bytes per row in the buffer (256-byte aligned).
+        padded_row: u32,
+        /// Bytes per row of texels.
+        row: u32,
+    },
+    Buffer,
+}
+
+/// Set by the `map_async` callback, on whichever thread wgpu calls it.
+type MapStatus = Arc<OnceLock<Result<(), wgpu::BufferAsyncError>>>;
+
+struct Pending {
+    id: ReadbackId,
+    staging: wgpu::Buffer,
+    layout: Layout,
+    /// `None` until mapping starts (after the copy was submitted).
+    mapping: Option<MapStatus>,
+}
+
+impl Pending {
+    fn start_mapping(&mut self) {
+        let status = MapStatus::default();
+        let done = status.clone();
+        self.staging.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+            let _ = done.set(result);
+        });
+        self.mapping = Some(status);
+    }
+
+    /// The mapping result, once the callback has run.
+    fn status(&self) -> Option<Result<(), wgpu::BufferAsyncError>> {
+        self.mapping.as_ref()?.get().cloned()
+    }
+
+    /// Copy the mapped bytes out and unmap.
+    fn finish(
+        self,
+        status: Result<(), wgpu::BufferAsyncError>,
+    ) -> Result<ReadbackData, wgpu::BufferAsyncError> {
+        status?;
+        let data = {
+            let mapped = self.staging.slice(..).get_mapped_range();
+            match self.layout {
+                Layout::Image {
+                    width,
+                    height,
+                    format,
+                    padded_row,
+                    row,
+                } => {
+                    let mut data = Vec::with_capacity((row * height) as usize);
+                    for padded in mapped.chunks(padded_row as usize) {
+                        data.extend_from_slice(&padded[..row as usize]);
+                    }
+                    ReadbackData::Image(ReadbackImage {
+                        width,
+                        height,
+                        format,
+                        data,
+                    })
+                }
+                Layout::Buffer => ReadbackData::Buffer(mapped.to_vec()),
+            }
+        };
+        self.staging.unmap();
+        Ok(data)
+    }
+}
+
+/// Resource: readbacks in flight. Inserted on first use.
+#[derive(Default)]
+pub struct Readbacks {
+    next_id: u64,
+    pending: Vec<Pending>,
+}
+
+impl Readbacks {
+    /// Record a copy of `texture` into `encoder`. Mapping starts at the
+    /// next frame's poll, so submit the encoder before then. `None` (with a
+    /// warning) if the texture can't be copied.
+    pub fn record_texture(
+        &mut self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> Option<ReadbackId> {
+        let (staging, layout) = copy_texture(gpu, encoder, texture)?;
+        Some(self.add(staging, layout))
+    }
+
+    /// Record a copy of all of `buffer` into `encoder`. Same rules as
+    /// [`record_texture`](Self::record_texture).
+    pub fn record_buffer(
+        &mut self,
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        buffer: &wgpu::Buffer,
+    ) -> Option<ReadbackId> {
+        if !buffer.usage().contains(wgpu::BufferUsages::COPY_SRC) {
+            log::warn!("Can't read back a buffer without COPY_SRC usage");
+            return None;
+        }
+        if !buffer.size().is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT) {
+            log::warn!(
+                "Can't read back a {}-byte buffer: the size must be a multiple of {}",
+                buffer.size(),
+                wgpu::COPY_BUFFER_ALIGNMENT
+            );
+            return None;
+        }
+        let staging = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("buffer readback"),
+            size: buffer.size(),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, buffer.size());
+        Some(self.add(staging, Layout::Buffer))
+    }
+
+    /// Whether `id` is still in flight.
+    pub fn is_pending(&self, id: ReadbackId) -> bool {
+        self.pending.iter().any(|pending| pending.id == id)
+    }
+
+    fn add(&mut self, staging: wgpu::Buffer, layout: Layout) -> ReadbackId {
+        let id = ReadbackId(self.next_id);
+        self.next_id += 1;
+        self.pending.push(Pending {
+            id,
+            staging,
+            layout,
+            mapping: None,
+        });
+        id
+    }
+}
+
+// ── Public API ──────────────────────────────────────────────────────────
+
+/// Copy `texture` to the CPU. Completes with a [`ReadbackComplete`] event
+/// a frame or more later. `None` (with a warning) without a GPU or if the
+/// texture can't be copied.
+pub fn readback_texture(world: &mut World, texture: &wgpu::Texture) -> Option<ReadbackId> {
+    submit(world, |readbacks, gpu, encoder| readbacks.record_texture(gpu, encoder, texture))
+}
+
+/// Copy all of `buffer` to the CPU. Completes with a [`ReadbackComplete`]
+/// event a frame or more later. The buffer needs `COPY_SRC` usage and a size
+/// that's a multiple of 4.
+pub fn readback_buffer(world: &mut World, buffer: &wgpu::Buffer) -> Option<ReadbackId> {
+    submit(world, |readbacks, gpu, encoder| readbacks.record_buffer(gpu, encoder, buffer))
+}
+
+/// Block until readback `id` is done and return its data instead of sending
+/// an event. A copy recorded into an encoder must have been submitted.
+/// `None` if `id` isn't in flight (already delivered, or unknown).
+pub fn wait_for_readback(
+    world: &mut World,
+    id: ReadbackId,
+) -> Option<Result<ReadbackData, wgpu::BufferAsyncError>> {
+    let readbacks = world.get_resource_mut::<Readbacks>()?;
+    let index = readbacks.pending.iter().position(|pending| pending.id == id)?;
+    let mut pending = readbacks.pending.remove(index);
+    if pending.mapping.is_none() {
+        pending.start_mapping();
+    }
+    let gpu = world.get_resource::<GpuContext>()?;
+    if let Err(e) = gpu.device.poll(wgpu::PollType::wait_indefinitely()) {
+        log::warn!("Readback wait failed: {e}");
+    }
+    // The callback has run once the wait returns; a lost device leaves it
+    // unset.
+    let status = pending.status().unwrap_or(Err(wgpu::BufferAsyncError));
+    Some(pending.finish(status))
+}
+
+/// Record with a fresh encoder, submit it, and start mapping at once.
+fn submit(
+    world: &mut World,
+    record: impl FnOnce(
+        &mut Readbacks,
+        &GpuContext,
+        &mut wgpu::CommandEncoder,
+    ) -> Option<ReadbackId>,
+) -> Option<ReadbackId> {
+    let mut readbacks = world.resource_remove::<Readbacks>().unwrap_or_default();
+    let Some(gpu) = world.get_resource::<GpuContext>() else {
+        log::warn!("Readback requested before the GPU was initialized");
+        world.insert_resource(readbacks);
+        return None;
+    };
+    let mut encoder = gpu.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+        label: Some("readback encoder"),
+    });
+    let id = record(&mut readbacks, gpu, &mut encoder);
+    if id.is_some() {
+        gpu.queue.submit(Some(encoder.finish()));
+        if let Some(pending) = readbacks.pending.last_mut() {
+            pending.start_mapping();
+        }
+    }
+    world.insert_resource(readbacks);
+    id
+}
+
+/// Start mapping copies submitted since the last call, poll the device
+/// without blocking, and send a [`ReadbackComplete`] for each finished
+/// readback. Called once per frame from the main loop.
+pub(crate) fn process_readbacks(world: &mut World) {
+    let Some(mut readbacks) = world.resource_remove::<Readbacks>() else {
+        return;
+    };
+    if readbacks.pending.is_empty() {
+        world.insert_resource(readbacks);
+        return;
+    }
+    for pending in &mut readbacks.pending {
+        if pending.mapping.is_none() {
+            pending.start_mapping();
+        }
+    }
+    if let Some(gpu) = world.get_resource::<GpuContext>()
+        && let Err(e) = gpu.device.poll(wgpu::PollType::Poll)
+    {
+        log::warn!("Readback poll failed: {e}");
+    }
+
+    let mut finished = Vec::new();
+    let mut i = 0;
+    while i < readbacks.pending.len() {
+        match readbacks.pending[i].status() {
+            Some(status) => {
+                let pending = readbacks.pending.swap_remove(i);
+                let id = pending.id;
+                finished.push(ReadbackComplete {
+                    id,
+                    result: pending.finish(status),
+                });
+            }
+            None => i += 1,
+        }
+    }
+    world.insert_resource(readbacks);
+    for event in finished {
+        if let Err(e) = &event.result {
+            log::warn!("Readback {:?} failed: {e}", event.id);
+        }
+        world.send_event(event);
+    }
+}
+
+// ── Texture copies ──────────────────────────────────────────────────────
+
+/// Record a copy of `texture` into a new staging buffer.
+fn copy_texture(
+    gpu: &GpuContext,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+) -> Option<(wgpu::Buffer, Layout)> {
+    let format = texture.format();
+    if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+        log::warn!("Can't read back a texture without COPY_SRC usage");
+        return None;
+    }
+    if texture.sample_count() > 1 {
+        log::warn!("Can't read back a multisampled texture; resolve it first");
+        return None;
+    }
+    let texel_bytes = match (format.block_dimensions(), format.block_copy_size(None)) {
+        ((1, 1), Some(bytes)) => bytes,
+        _ => {
+            log::warn!("Can't read back a {format:?} texture");
+            return None;
+        }
+    };
+
+    let size = texture.size();
+    let (row, padded_row) = row_pitch(size.width, texel_bytes);
+    let staging = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("texture readback"),
+        size: (padded_row * size.height) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &staging,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row),
+                rows_per_image: None,
+            },
+        },
+        wgpu::Extent3d {
+            depth_or_array_layers: 1,
+            ..size
+        },
+    );
+    let layout = Layout::Image {
+        width: size.width,
+        height: size.height,
+        format,
+        padded_row,
+        row,
+    };
+    Some((staging, layout))
+}
+
+/// Bytes per row of `width` texels, unpadded and padded to the 256-byte
+/// alignment buffer copies need.
+fn row_pitch(width: u32, texel_bytes: u32) -> (u32, u32) {
+    let row = width * texel_bytes;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    (row, row.div_ceil(align) * align)
+}
+
+/// A texture copy recorded into this frame's encoder and read back
+/// blocking, for screenshots and shader diffs. Call [`read`](Self::read)
+/// after the frame is submitted.
+pub(crate) struct TextureReadback {
+    pending: Option<Pending>,
+    width: u32,
+    height: u32,
+}
+
+impl TextureReadback {
+    pub(crate) fn record(
+        gpu: &GpuContext,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> Self {
+        let size = texture.size();
+        let pending = copy_texture(gpu, encoder, texture).map(|(staging, layout)| Pending {
+            id: ReadbackId(u64::MAX),
+            staging,
+            layout,
+            mapping: None,
+        });
+        Self {
+            pending,
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    /// Pixel size of the copied texture.
+    pub(crate) fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Wait for the copy and return its pixels as opaque RGBA8, row by row.
+    pub(crate) fn read(self, device: &wgpu::Device) -> Option<Vec<u8>> {
+        let mut pending = self.pending?;
+        pending.start_mapping();
+        if let Err(e) = device.poll(wgpu::PollType::wait_indefinitely()) {
+            log::warn!("Texture readback failed: {e}");
+            return None;
+        }
+        let status = pending.status().unwrap_or(Err(wgpu::BufferAsyncError));
+        let ReadbackData::Image(image) = pending.finish(status).ok()? else {
+            return None;
+        };
+        let mut rgba = image.to_rgba8()?;
+        for pixel in rgba.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+        Some(rgba)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rows_pad_to_copy_alignment_and_bgra_swaps() {
+        assert_eq!(row_pitch(64, 4), (256, 256));
+        assert_eq!(row_pitch(65, 4), (260, 512));
+        assert_eq!(row_pitch(3, 8), (24, 256));
+
+        let image = ReadbackImage {
+            width: 1,
+            height: 1,
+            format: wgpu::TextureFormat::Bgra8UnormSrgb,
+            data: vec![1, 2, 3, 4],
+        };
+        assert_eq!(image.to_rgba8(), Some(vec![3, 2, 1, 4]));
+        let float = ReadbackImage {
+            format: wgpu::TextureFormat::Rgba16Float,
+            ..image
+        };
+        assert_eq!(float.to_rgba8(), None);
+    }
+}
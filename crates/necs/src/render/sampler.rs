@@ -0,0 +1,204 @@
+//! # Sampler — How a Texture Is Read
+//!
+//! A texture holds pixels; a *sampler* decides what the shader gets when it
+//! asks for the color at a UV that falls between pixels, or outside `0..1`.
+//! The same image can look crisp or smooth, clamped or tiled, depending only
+//! on the sampler it is bound with.
+//!
+//! ```text
+//!   filter                               wrap (UV 0..3 across a quad)
+//!   Nearest   ██░░      Linear  ▓▒░░     Clamp    [A]AAAAAAAA  edge smeared
+//!   (pixel    ██░░      (smooth ▓▒░░     Repeat   [A][A][A]    tiled
+//!    art)     ░░██      blend)  ░▒▓▓     Mirror   [A][Ɐ][A]    tiled, flipped
+//! ```
+//!
+//! [`SamplerSettings`] is chosen per texture when it is loaded
+//! ([`load_texture_with`](crate::render2d::load_texture_with),
+//! [`load_texture_3d_with`](crate::render3d::load_texture_3d_with)) and can be
+//! changed later with `set_texture_sampler` / `set_texture_sampler_3d`.
+//! Textures loaded without settings use their renderer's default: nearest +
+//! clamp for 2D sprites, linear + repeat for 3D materials.
+//!
+//! GPU sampler objects are cached by settings, so a thousand textures with
+//! the same settings share one sampler.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Filter Mode, Wrap Mode and Aniso Level in the texture import
+//!   settings; also writable on `Texture` at runtime.
+//! - **Bevy**: `ImageSampler` on each `Image`, set through
+//!   `ImageLoaderSettings` at load time or by mutating the asset.
+//! - **Godot**: Filter and repeat are properties of the `CanvasItem` /
+//!   material that draws the texture rather than the texture itself.
+//! - **Our approach**: Unity's model — settings belong to the texture, with
+//!   a load-time argument and a setter.
+
+use std::collections::HashMap;
+
+/// How texels are blended when a texture is magnified or minified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureFilter {
+    /// Take the closest texel. Crisp edges; what pixel art wants.
+    Nearest,
+    /// Blend the four closest texels. Smooth, slightly blurry up close.
+    Linear,
+}
+
+/// What happens to UVs outside `0..1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureWrap {
+    /// Repeat the edge texels.
+    Clamp,
+    /// Tile the texture.
+    Repeat,
+    /// Tile the texture, flipping every other copy.
+    Mirror,
+}
+
+/// Sampler configuration for one texture. See the [module docs](self).
+///
+/// ```ignore
+/// let tiles = ctx.load_texture_with(
+///     "tiles.png",
+///     SamplerSettings::nearest().wrap(TextureWrap::Repeat),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SamplerSettings {
+    pub filter: TextureFilter,
+    /// Horizontal wrap mode.
+    pub wrap_u: TextureWrap,
+    /// Vertical wrap mode.
+    pub wrap_v: TextureWrap,
+    /// Maximum anisotropic filtering samples, 1–16. Sharpens textures viewed
+    /// at a steep angle (floors, roads). Only applies with
+    /// [`TextureFilter::Linear`]; 1 turns it off.
+    pub anisotropy: u16,
+}
+
+impl SamplerSettings {
+    /// Nearest filtering, clamped. The 2D sprite default.
+    pub const fn nearest() -> Self {
+        Self {
+            filter: TextureFilter::Nearest,
+            wrap_u: TextureWrap::Clamp,
+            wrap_v: TextureWrap::Clamp,
+            anisotropy: 1,
+        }
+    }
+
+    /// Linear filtering, clamped.
+    pub const fn linear() -> Self {
+        Self {
+            filter: TextureFilter::Linear,
+            ..Self::nearest()
+        }
+    }
+
+    /// Set the filter (builder pattern).
+    pub fn filter(mut self, filter: TextureFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Set both wrap modes (builder pattern).
+    pub fn wrap(mut self, wrap: TextureWrap) -> Self {
+        self.wrap_u = wrap;
+        self.wrap_v = wrap;
+        self
+    }
+
+    /// Set the anisotropy level, clamped to 1–16 (builder pattern).
+    pub fn anisotropy(mut self, samples: u16) -> Self {
+        self.anisotropy = samples.clamp(1, 16);
+        self
+    }
+
+    /// Settings wgpu will accept: anisotropy within 1–16, and only with
+    /// linear filtering.
+    fn sanitized(self) -> Self {
+        let anisotropy = match self.filter {
+            TextureFilter::Linear => self.anisotropy.clamp(1, 16),
+            TextureFilter::Nearest => {
+                if self.anisotropy > 1 {
+                    log::warn!("Anisotropic filtering needs TextureFilter::Linear; ignoring it");
+                }
+                1
+            }
+        };
+        Self { anisotropy, ..self }
+    }
+
+    fn descriptor(&self) -> wgpu::SamplerDescriptor<'static> {
+        let filter = match self.filter {
+            TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+            TextureFilter::Linear => wgpu::FilterMode::Linear,
+        };
+        let address = |wrap| match wrap {
+            TextureWrap::Clamp => wgpu::AddressMode::ClampToEdge,
+            TextureWrap::Repeat => wgpu::AddressMode::Repeat,
+            TextureWrap::Mirror => wgpu::AddressMode::MirrorRepeat,
+        };
+        wgpu::SamplerDescriptor {
+            label: Some("texture sampler"),
+            address_mode_u: address(self.wrap_u),
+            address_mode_v: address(self.wrap_v),
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: filter,
+            anisotropy_clamp: self.anisotropy,
+            ..Default::default()
+        }
+    }
+}
+
+/// GPU samplers keyed by their settings, owned by a texture store.
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+#[derive(Default)]
+pub(crate) struct SamplerCache {
+    samplers: HashMap<SamplerSettings, wgpu::Sampler>,
+}
+
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+impl SamplerCache {
+    /// The sampler for `settings`, creating it on first use. Returns the
+    /// settings actually used, after sanitizing.
+    pub fn get(
+        &mut self,
+        device: &wgpu::Device,
+        settings: SamplerSettings,
+    ) -> (SamplerSettings, wgpu::Sampler) {
+        let settings = settings.sanitized();
+        let sampler = self
+            .samplers
+            .entry(settings)
+            .or_insert_with(|| device.create_sampler(&settings.descriptor()))
+            .clone();
+        (settings, sampler)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn anisotropy_is_clamped_and_needs_linear() {
+        assert_eq!(SamplerSettings::linear().anisotropy(64).anisotropy, 16);
+        assert_eq!(SamplerSettings::linear().anisotropy(0).anisotropy, 1);
+
+        let nearest = SamplerSettings::nearest().anisotropy(8);
+        assert_eq!(nearest.sanitized().anisotropy, 1);
+        let linear = SamplerSettings::linear().anisotropy(8);
+        assert_eq!(linear.sanitized(), linear);
+        assert_eq!(linear.descriptor().anisotropy_clamp, 8);
+    }
+
+    #[test]
+    fn wrap_modes_map_to_address_modes() {
+        let d = SamplerSettings::nearest().wrap(TextureWrap::Mirror).descriptor();
+        assert_eq!(d.address_mode_u, wgpu::AddressMode::MirrorRepeat);
+        assert_eq!(d.address_mode_v, wgpu::AddressMode::MirrorRepeat);
+        assert_eq!(d.mag_filter, wgpu::FilterMode::Nearest);
+    }
+}
@@ -0,0 +1,323 @@
+//! # Graphics Settings — One Resource for the Options Screen
+//!
+//! Quality knobs are scattered across the renderer: the grading pass, the
+//! soft particle shader, texture samplers, the size of the offscreen target.
+//! [`GraphicsSettings`] gathers the ones a player would expect in an options
+//! menu into a single resource. Edit it at any time; the renderer reads it
+//! every frame, so changes apply on the next frame without a restart.
+//!
+//! ```text
+//!   options menu ──► GraphicsSettings (resource)
+//!                        │ read each frame
+//!         ┌──────────────┼──────────────────┬──────────────┬──────────────┐
+//!         ▼              ▼                  ▼              ▼              ▼
+//!   resolution_scale  post_effects      anisotropy    msaa_samples   shadow_resolution
+//!   scene target size grading on/off,   3D linear     multisampled   (recorded only)
+//!   + upscale pass    soft particle     textures      scene target
+//!                     fade, 3D bloom
+//!                     on/off
+//! ```
+//!
+//! `max_lights` caps how many 3D point lights, and separately spot lights,
+//! are shaded per frame.
+//!
+//! ## Presets
+//!
+//! [`QualityPreset`] fills in every field at once; a menu usually offers the
+//! presets plus individual overrides:
+//!
+//! | | Low | Medium | High |
+//! |-|-----|--------|------|
+//! | resolution scale | 0.75 | 1.0 | 1.0 |
+//! | anisotropy | 1× | 4× | 16× |
+//! | soft particles | off | on | on |
+//! | bloom | off | on | on |
+//! | color grading | on | on | on |
+//! | MSAA | 1× | 2× | 4× |
+//! | shadow map | 512 | 1024 | 2048 |
+//! | max lights (each kind) | 16 | 64 | 256 |
+//!
+//! Without the resource the renderer behaves like
+//! [`GraphicsSettings::default`]: full resolution, every effect on, and
+//! textures sampled exactly as they were loaded.
+//!
+//! ## Resolution Scale
+//!
+//! Below 1.0 the scene renders into a smaller offscreen texture that is
+//! stretched over the window, trading sharpness for fill rate. UI and the
+//! editor overlay draw afterwards at full resolution, so text stays crisp.
+//! The offscreen pass is the same one [`ColorGrading`](super::ColorGrading)
+//! uses; with grading off it just copies.
+//!
+//! ## Anti-Aliasing
+//!
+//! `msaa_samples` above 1 renders the scene through a multisampled target;
+//! see [`msaa`](super::msaa) for how, and what happens when the GPU can't
+//! do the requested count.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `QualitySettings` with named levels configured per project,
+//!   plus render-pipeline assets holding MSAA, shadow resolution and render
+//!   scale.
+//! - **Unreal**: `UGameUserSettings` with scalability groups (0–4) and
+//!   `r.ScreenPercentage`.
+//! - **Godot**: Project settings and `Viewport` properties (`msaa_3d`,
+//!   `scaling_3d_scale`), changed individually at runtime.
+//! - **Our approach**: One plain, serializable struct — save it next to the
+//!   player's other options and insert it at startup.
+
+use serde::{Deserialize, Serialize};
+
+/// Smallest accepted resolution scale.
+const MIN_RESOLUTION_SCALE: f32 = 0.25;
+
+/// Largest accepted light limit.
+const MAX_LIGHTS: u32 = 4096;
+
+/// A named bundle of settings. See the [module docs](self) for the values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QualityPreset {
+    Low,
+    Medium,
+    High,
+}
+
+/// Post-process effects that can be switched off for speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PostEffects {
+    /// Apply the [`ColorGrading`](super::ColorGrading) LUT, if one is set.
+    pub color_grading: bool,
+    /// Fade [`SoftParticle`](crate::render3d::SoftParticle)s against the
+    /// scene depth. When off they are cut hard where they meet geometry.
+    pub soft_particles: bool,
+    /// Bloom from the [`PostProcess`](crate::render3d::PostProcess)
+    /// resource, if one is set. Tone mapping stays on either way.
+    pub bloom: bool,
+}
+
+impl Default for PostEffects {
+    fn default() -> Self {
+        Self {
+            color_grading: true,
+            soft_particles: true,
+            bloom: true,
+        }
+    }
+}
+
+/// Resource: player-facing graphics options. See the [module docs](self).
+///
+/// ```ignore
+/// Game::new("My Game")
+///     .resource(GraphicsSettings::preset(QualityPreset::Medium).resolution_scale(0.8))
+///     .run();
+///
+/// // Later, from the options menu:
+/// ctx.world.resource_mut::<GraphicsSettings>().post_effects.soft_particles = false;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GraphicsSettings {
+    /// Multisample anti-aliasing: 1 (off), 2, 4 or 8 samples per pixel.
+    /// Other values round down.
+    pub msaa_samples: u32,
+    /// Shadow map size in texels. Recorded for menus and saved settings;
+    /// the 3D renderer has no shadow maps.
+    pub shadow_resolution: u32,
+    /// Post-process toggles.
+    pub post_effects: PostEffects,
+    /// Minimum anisotropic filtering (1–16) for 3D textures with linear
+    /// filtering. A texture that asks for more keeps its own level;
+    /// nearest-filtered textures are unaffected.
+    pub anisotropy: u16,
+    /// Scene render size relative to the window, 0.25–1.0.
+    pub resolution_scale: f32,
+    /// Most 3D point lights shaded per frame, and separately most spot
+    /// lights (up to 4096). Every pixel loops over all of them, so this is
+    /// a direct fill-rate cost. Lights past the limit are skipped.
+    pub max_lights: u32,
+}
+
+impl GraphicsSettings {
+    /// Every field from a preset.
+    pub fn preset(preset: QualityPreset) -> Self {
+        match preset {
+            QualityPreset::Low => Self {
+                msaa_samples: 1,
+                shadow_resolution: 512,
+                post_effects: PostEffects {
+                    color_grading: true,
+                    soft_particles: false,
+                    bloom: false,
+                },
+                anisotropy: 1,
+                resolution_scale: 0.75,
+                max_lights: 16,
+            },
+            QualityPreset::Medium => Self {
+                msaa_samples: 2,
+                shadow_resolution: 1024,
+                post_effects: PostEffects::default(),
+                anisotropy: 4,
+                resolution_scale: 1.0,
+                max_lights: 64,
+            },
+            QualityPreset::High => Self {
+                msaa_samples: 4,
+                shadow_resolution: 2048,
+                post_effects: PostEffects::default(),
+                anisotropy: 16,
+                resolution_scale: 1.0,
+                max_lights: 256,
+            },
+        }
+    }
+
+    /// The preset these settings match exactly, if any. Handy for showing
+    /// "Custom" in a menu once the player tweaks a field.
+    pub fn matching_preset(&self) -> Option<QualityPreset> {
+        [QualityPreset::Low, QualityPreset::Medium, QualityPreset::High]
+            .into_iter()
+            .find(|&preset| Self::preset(preset) == *self)
+    }
+
+    /// Set the MSAA sample count, rounded down to 1, 2, 4 or 8 (builder
+    /// pattern).
+    pub fn msaa_samples(mut self, samples: u32) -> Self {
+        self.msaa_samples = round_samples(samples);
+        self
+    }
+
+    /// Set the shadow map size, rounded up to a power of two (builder
+    /// pattern).
+    pub fn shadow_resolution(mut self, size: u32) -> Self {
+        self.shadow_resolution = size.clamp(1, 8192).next_power_of_two();
+        self
+    }
+
+    /// Set the post-process toggles (builder pattern).
+    pub fn post_effects(mut self, effects: PostEffects) -> Self {
+        self.post_effects = effects;
+        self
+    }
+
+    /// Set the anisotropy level, clamped to 1–16 (builder pattern).
+    pub fn anisotropy(mut self, samples: u16) -> Self {
+        self.anisotropy = samples.clamp(1, 16);
+        self
+    }
+
+    /// Set the resolution scale, clamped to 0.25–1.0 (builder pattern).
+    pub fn resolution_scale(mut self, scale: f32) -> Self {
+        self.resolution_scale = scale;
+        self.resolution_scale = self.clamped_scale();
+        self
+    }
+
+    /// Set the light limit, clamped to 4096 (builder pattern).
+    pub fn max_lights(mut self, count: u32) -> Self {
+        self.max_lights = count.min(MAX_LIGHTS);
+        self
+    }
+
+    /// The resolution scale the renderer uses: clamped to 0.25–1.0, with
+    /// NaN treated as 1.0. Fields may be set directly, so the renderer
+    /// never trusts them as-is.
+    pub(crate) fn clamped_scale(&self) -> f32 {
+        if self.resolution_scale.is_nan() {
+            return 1.0;
+        }
+        self.resolution_scale.clamp(MIN_RESOLUTION_SCALE, 1.0)
+    }
+
+    /// The light limit the renderer uses, clamped to 4096.
+    #[cfg_attr(not(feature = "render3d"), allow(dead_code))]
+    pub(crate) fn light_limit(&self) -> usize {
+        self.max_lights.min(MAX_LIGHTS) as usize
+    }
+
+    /// The MSAA sample count the renderer asks for: 1, 2, 4 or 8.
+    #[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+    pub(crate) fn sample_count(&self) -> u32 {
+        round_samples(self.msaa_samples)
+    }
+
+    /// Size of the scene target for a `width`×`height` window.
+    pub(crate) fn scaled_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let scale = self.clamped_scale();
+        let scaled = |n: u32| ((n as f32 * scale).round() as u32).max(1);
+        (scaled(width), scaled(height))
+    }
+}
+
+impl Default for GraphicsSettings {
+    /// Full resolution and every effect on, with textures sampled as loaded.
+    fn default() -> Self {
+        Self {
+            msaa_samples: 1,
+            shadow_resolution: 2048,
+            post_effects: PostEffects::default(),
+            anisotropy: 1,
+            resolution_scale: 1.0,
+            max_lights: 256,
+        }
+    }
+}
+
+/// Round a sample count down to one wgpu accepts.
+fn round_samples(samples: u32) -> u32 {
+    match samples {
+        0..=1 => 1,
+        2..=3 => 2,
+        4..=7 => 4,
+        _ => 8,
+    }
+}
+
+/// The world's settings, or the defaults if none were inserted.
+pub(crate) fn graphics_settings(world: &crate::ecs::World) -> GraphicsSettings {
+    world.get_resource::<GraphicsSettings>().copied().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_round_trip_through_matching_preset() {
+        for preset in [QualityPreset::Low, QualityPreset::Medium, QualityPreset::High] {
+            assert_eq!(GraphicsSettings::preset(preset).matching_preset(), Some(preset));
+        }
+        let custom = GraphicsSettings::preset(QualityPreset::High).resolution_scale(0.5);
+        assert_eq!(custom.matching_preset(), None);
+    }
+
+    #[test]
+    fn builders_clamp_to_supported_values() {
+        let settings = GraphicsSettings::default()
+            .msaa_samples(6)
+            .shadow_resolution(1000)
+            .anisotropy(64)
+            .resolution_scale(0.1)
+            .max_lights(100_000);
+        assert_eq!(settings.msaa_samples, 4);
+        assert_eq!(settings.shadow_resolution, 1024);
+        assert_eq!(settings.anisotropy, 16);
+        assert_eq!(settings.resolution_scale, MIN_RESOLUTION_SCALE);
+        assert_eq!(settings.max_lights, MAX_LIGHTS);
+    }
+
+    #[test]
+    fn scaled_size_never_reaches_zero() {
+        let mut settings = GraphicsSettings::default();
+        assert_eq!(settings.scaled_size(1280, 720), (1280, 720));
+        settings.resolution_scale = 0.5;
+        assert_eq!(settings.scaled_size(1280, 720), (640, 360));
+        settings.resolution_scale = f32::NAN;
+        assert_eq!(settings.scaled_size(1280, 720), (1280, 720));
+        settings.resolution_scale = 0.25;
+        assert_eq!(settings.scaled_size(1, 1), (1, 1));
+    }
+}
@@ -0,0 +1,257 @@
+//! # Shader Defs — Compile-Time Switches in WGSL
+//!
+//! One shader that handles every feature (normal maps, emissive maps, fog,
+//! skinning, ...) pays for all of them on every draw: each texture is
+//! sampled and each branch evaluated even when the material doesn't use it.
+//! Shader defs turn those features into compile-time switches. The source
+//! marks optional blocks, and each combination of defs compiles into its own
+//! shader *variant*, with the unused code removed before the GPU sees it:
+//!
+//! ```text
+//!   shader.wgsl                     defs {NORMAL_MAP}      defs {}
+//!   ─────────────────────────       ─────────────────      ─────────────
+//!   var n = in.normal;              var n = in.normal;     var n = in.normal;
+//!   #ifdef NORMAL_MAP
+//!   n = sample_normal(in.uv);       n = sample_normal(..);
+//!   #endif
+//!   return shade(n);                return shade(n);       return shade(n);
+//! ```
+//!
+//! WGSL has no preprocessor, so [`preprocess`] runs before the source is
+//! handed to wgpu. It understands five directives, each on its own line:
+//!
+//! | Directive | Meaning |
+//! |-----------|---------|
+//! | `#ifdef NAME` | Keep the lines up to `#else` / `#endif` if `NAME` is defined |
+//! | `#ifndef NAME` | Keep them if `NAME` is *not* defined |
+//! | `#else` | Flip the current block |
+//! | `#endif` | Close the block |
+//! | `#define NAME` | Define `NAME` for the rest of the file |
+//!
+//! Blocks nest. Removed lines (and the directives themselves) become empty
+//! lines rather than disappearing, so the line numbers in shader compiler
+//! errors still match the file.
+//!
+//! Renderers cache one pipeline per variant they actually draw — the 3D
+//! renderer keys its pipeline cache on the material's maps, so a material
+//! without a normal map never runs normal-mapping code. Material shaders
+//! are preprocessed with the same defs and may test them too.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `#pragma multi_compile` / `shader_feature` keywords, with
+//!   variants compiled at build time and stripped when unused.
+//! - **Bevy**: `#ifdef` shader defs (via naga_oil), pushed by each
+//!   material's `specialize` and cached per pipeline key.
+//! - **Godot**: `#ifdef` / `#define` in its shading language, with render
+//!   modes generating the defines.
+//! - **Our approach**: Bevy's model with a ~100-line line-based
+//!   preprocessor and variants compiled lazily on first draw.
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+/// A set of shader def names. Cheap to compare and hash, so it can be part
+/// of a pipeline cache key.
+///
+/// ```ignore
+/// let defs = ShaderDefs::new().with("NORMAL_MAP").with("FOG");
+/// let wgsl = preprocess(source, &defs)?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ShaderDefs(BTreeSet<String>);
+
+impl ShaderDefs {
+    /// No defs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a def (builder pattern).
+    pub fn with(mut self, name: impl Into<String>) -> Self {
+        self.insert(name);
+        self
+    }
+
+    /// Add a def.
+    pub fn insert(&mut self, name: impl Into<String>) {
+        self.0.insert(name.into());
+    }
+
+    /// Whether `name` is defined.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains(name)
+    }
+
+    /// The defined names, in sorted order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for ShaderDefs {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+// ── Errors ──────────────────────────────────────────────────────────────
+
+/// A malformed directive. Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShaderDefError {
+    /// `#else` without an open `#ifdef` / `#ifndef`, or a second `#else`.
+    UnexpectedElse { line: usize },
+    /// `#endif` without an open `#ifdef` / `#ifndef`.
+    UnexpectedEndif { line: usize },
+    /// An `#ifdef` / `#ifndef` that is never closed.
+    Unterminated { line: usize },
+    /// A directive that needs a name was given none.
+    MissingName { line: usize },
+    /// A line starting with `#` that isn't a known directive.
+    UnknownDirective { line: usize, directive: String },
+}
+
+impl fmt::Display for ShaderDefError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedElse { line } => write!(f, "line {line}: #else without a matching #ifdef"),
+            Self::UnexpectedEndif { line } => write!(f, "line {line}: #endif without a matching #ifdef"),
+            Self::Unterminated { line } => write!(f, "line {line}: #ifdef is never closed with #endif"),
+            Self::MissingName { line } => write!(f, "line {line}: directive needs a def name"),
+            Self::UnknownDirective { line, directive } => {
+                write!(f, "line {line}: unknown directive '{directive}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ShaderDefError {}
+
+// ── Preprocessor ────────────────────────────────────────────────────────
+
+/// One open `#ifdef` / `#ifndef`.
+struct Block {
+    /// Line of the opening directive, for errors.
+    line: usize,
+    /// Whether the enclosing blocks keep their lines.
+    parent_active: bool,
+    /// Whether the current branch's condition holds.
+    condition: bool,
+    seen_else: bool,
+}
+
+impl Block {
+    fn active(&self) -> bool {
+        self.parent_active && self.condition
+    }
+}
+
+/// Apply the `#ifdef` family of directives to WGSL `source` (see the
+/// [module docs](self)). Removed lines are kept as empty lines.
+pub fn preprocess(source: &str, defs: &ShaderDefs) -> Result<String, ShaderDefError> {
+    let mut defs = defs.clone();
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut out = String::with_capacity(source.len());
+
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let active = blocks.last().is_none_or(Block::active);
+        let Some(directive) = text.trim_start().strip_prefix('#') else {
+            if active {
+                out.push_str(text);
+            }
+            out.push('\n');
+            continue;
+        };
+
+        let mut words = directive.split_whitespace();
+        let keyword = words.next().unwrap_or("");
+        let mut name = || words.next().ok_or(ShaderDefError::MissingName { line });
+        match keyword {
+            "ifdef" | "ifndef" => {
+                let defined = defs.contains(name()?);
+                blocks.push(Block {
+                    line,
+                    parent_active: active,
+                    condition: defined == (keyword == "ifdef"),
+                    seen_else: false,
+                });
+            }
+            "else" => match blocks.last_mut() {
+                Some(block) if !block.seen_else => {
+                    block.condition = !block.condition;
+                    block.seen_else = true;
+                }
+                _ => return Err(ShaderDefError::UnexpectedElse { line }),
+            },
+            "endif" => {
+                if blocks.pop().is_none() {
+                    return Err(ShaderDefError::UnexpectedEndif { line });
+                }
+            }
+            "define" => {
+                let name = name()?;
+                if active {
+                    defs.insert(name);
+                }
+            }
+            _ => {
+                return Err(ShaderDefError::UnknownDirective {
+                    line,
+                    directive: format!("#{keyword}"),
+                });
+            }
+        }
+        out.push('\n');
+    }
+
+    match blocks.first() {
+        Some(block) => Err(ShaderDefError::Unterminated { line: block.line }),
+        None => Ok(out),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "a\n#ifdef X\nb\n#ifndef Y\nc\n#else\nd\n#endif\n#endif\ne";
+
+    #[test]
+    fn blocks_nest_and_keep_line_numbers() {
+        let none = preprocess(SOURCE, &ShaderDefs::new()).unwrap();
+        assert_eq!(none, "a\n\n\n\n\n\n\n\n\ne\n");
+        let x = preprocess(SOURCE, &ShaderDefs::new().with("X")).unwrap();
+        assert_eq!(x, "a\n\nb\n\nc\n\n\n\n\ne\n");
+        let xy: ShaderDefs = ["X", "Y"].into_iter().collect();
+        let xy = preprocess(SOURCE, &xy).unwrap();
+        assert_eq!(xy.lines().filter(|l| !l.is_empty()).collect::<Vec<_>>(), ["a", "b", "d", "e"]);
+
+        // `#define` only counts where it's reached.
+        let defined = preprocess("#ifdef Z\n#define X\n#endif\n#ifdef X\nx\n#endif", &ShaderDefs::new());
+        assert_eq!(defined.unwrap().trim(), "");
+        let defined = preprocess("#define X\n#ifdef X\nx\n#endif", &ShaderDefs::new());
+        assert_eq!(defined.unwrap().trim(), "x");
+    }
+
+    #[test]
+    fn malformed_directives_report_their_line() {
+        let defs = ShaderDefs::new();
+        assert_eq!(preprocess("#ifdef X\n", &defs), Err(ShaderDefError::Unterminated { line: 1 }));
+        assert_eq!(preprocess("a\n#endif", &defs), Err(ShaderDefError::UnexpectedEndif { line: 2 }));
+        assert_eq!(
+            preprocess("#ifdef X\n#else\n#else\n#endif", &defs),
+            Err(ShaderDefError::UnexpectedElse { line: 3 })
+        );
+        assert_eq!(preprocess("#ifdef\n#endif", &defs), Err(ShaderDefError::MissingName { line: 1 }));
+        assert!(matches!(
+            preprocess("#import foo", &defs),
+            Err(ShaderDefError::UnknownDirective { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,405 @@
+//! # Shader A/B — Before/After Frames for Hot-Reloaded Shaders
+//!
+//! Tweaking a shader by eye is unreliable: by the time the reload lands,
+//! the old look is a memory. With a [`ShaderDiff`] resource inserted, every
+//! successful shader hot-reload keeps the last frame drawn with the old
+//! shader and the first frame drawn with the new one, measures how much
+//! changed, and lets the game window flip between them.
+//!
+//! ```text
+//!   frame N     old shader ──► scene target ──► (kept on the GPU)
+//!   reload      shader.wgsl saved, new pipeline swapped in
+//!   frame N+1   ┬─ copy scene target ─► "before"   (still frame N)
+//!               ├─ draw with new shader
+//!               └─ copy scene target ─► "after"
+//!                        │ read back once
+//!                        ▼
+//!               DiffStats: 12.4% of pixels changed, mean Δ 3.1, max Δ 188
+//!
+//!   F9 cycles the view:  Live ─► Before ─► After ─► Live
+//! ```
+//!
+//! Both frames are the same moment of the game, a frame apart — pause the
+//! game (the editor's Pause button) for an exact match. While the view is
+//! on Before or After the scene shows the captured frame instead of the
+//! live one; UI and overlays still draw on top. The comparison is listed in
+//! the editor's Shader A/B window and sent to `necs-telemetry` (Assets tab,
+//! `v` cycles the view), and with [`ShaderDiff::save_to`] both frames are
+//! written as PNGs.
+//!
+//! ```ignore
+//! ctx.world.insert_resource(ShaderDiff::new().save_to("target/shader_diff"));
+//! ```
+//!
+//! The scene renders offscreen while the resource exists (like
+//! [`ColorGrading`](super::ColorGrading)); frames are captured before
+//! grading, so a LUT doesn't hide shader changes.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: No built-in A/B; the Frame Debugger shows one frame at a
+//!   time.
+//! - **Unreal**: None for shaders; screenshots compared by hand or with the
+//!   Screenshot Comparison tool used for automated tests.
+//! - **RenderDoc**: Texture viewer diff overlays, for two captures taken by
+//!   hand.
+//! - **Our approach**: Captured automatically on reload, flipped in place in
+//!   the game window, with numbers attached.
+
+use std::path::{Path, PathBuf};
+
+use crate::ecs::World;
+use crate::input::KeyCode;
+use crate::render::color_grading::scene_target;
+use crate::render::gpu::GpuContext;
+use crate::render::pass::FrameContext;
+use crate::render::readback::TextureReadback;
+
+// ── ShaderDiff resource ─────────────────────────────────────────────────
+
+/// What the scene shows while a [`ShaderDiff`] holds a comparison.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffView {
+    /// The game as it renders now.
+    #[default]
+    Live,
+    /// The last frame drawn with the old shader.
+    Before,
+    /// The first frame drawn with the new shader.
+    After,
+}
+
+impl DiffView {
+    /// Live → Before → After → Live.
+    pub fn next(self) -> Self {
+        match self {
+            DiffView::Live => DiffView::Before,
+            DiffView::Before => DiffView::After,
+            DiffView::After => DiffView::Live,
+        }
+    }
+}
+
+/// How much a reload changed the frame. Deltas are per color channel,
+/// 0–255.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DiffStats {
+    /// Fraction of pixels (0.0–1.0) with any channel changed.
+    pub changed: f32,
+    /// Mean absolute channel difference over the whole frame.
+    pub mean_delta: f32,
+    /// Largest channel difference.
+    pub max_delta: u8,
+}
+
+impl DiffStats {
+    /// Compare two RGBA8 images of the same size. Alpha is ignored.
+    pub fn compare(before: &[u8], after: &[u8]) -> Self {
+        let mut pixels = 0usize;
+        let mut changed = 0usize;
+        let mut total = 0u64;
+        let mut max_delta = 0u8;
+        for (a, b) in before.chunks_exact(4).zip(after.chunks_exact(4)) {
+            pixels += 1;
+            let mut differs = false;
+            for channel in 0..3 {
+                let delta = a[channel].abs_diff(b[channel]);
+                total += delta as u64;
+                max_delta = max_delta.max(delta);
+                differs |= delta > 0;
+            }
+            changed += differs as usize;
+        }
+        if pixels == 0 {
+            return Self::default();
+        }
+        Self {
+            changed: changed as f32 / pixels as f32,
+            mean_delta: total as f32 / (pixels * 3) as f32,
+            max_delta,
+        }
+    }
+}
+
+/// The frames on either side of one shader reload.
+#[derive(Debug, Clone)]
+pub struct ShaderComparison {
+    /// The reloaded shader file.
+    pub shader: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    /// Opaque RGBA8 pixels, row by row, drawn with the old shader.
+    pub before: Vec<u8>,
+    /// The same, drawn with the new shader.
+    pub after: Vec<u8>,
+    pub stats: DiffStats,
+    /// Where the before and after PNGs were written, with
+    /// [`ShaderDiff::save_to`].
+    pub saved: Option<(PathBuf, PathBuf)>,
+}
+
+/// Resource: capture before/after frames whenever a shader hot-reloads.
+/// Not inserted by default. See the [module docs](self).
+#[derive(Debug)]
+pub struct ShaderDiff {
+    /// What the scene shows. Has no effect until a comparison exists.
+    pub view: DiffView,
+    /// Key that cycles [`view`](Self::view). `None` disables the hotkey.
+    pub hotkey: Option<KeyCode>,
+    /// Directory to write each comparison's PNGs to. `None` keeps them in
+    /// memory only.
+    pub save_dir: Option<PathBuf>,
+    /// Shader reloaded since the last frame, waiting for its capture.
+    pending: Option<PathBuf>,
+    comparison: Option<ShaderComparison>,
+    generation: u64,
+}
+
+impl ShaderDiff {
+    /// Capture on every shader reload, showing the live game until the view
+    /// is switched.
+    pub fn new() -> Self {
+        Self {
+            view: DiffView::Live,
+            hotkey: Some(KeyCode::F9),
+            save_dir: None,
+            pending: None,
+            comparison: None,
+            generation: 0,
+        }
+    }
+
+    /// Also write each comparison as `<shader>.before.png` and
+    /// `<shader>.after.png` into `dir` (builder pattern).
+    pub fn save_to(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.save_dir = Some(dir.into());
+        self
+    }
+
+    /// The latest comparison, if a shader has reloaded since the resource
+    /// was inserted.
+    pub fn comparison(&self) -> Option<&ShaderComparison> {
+        self.comparison.as_ref()
+    }
+
+    /// Bumped with every new comparison, so viewers know to refresh.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Drop the comparison and go back to the live view.
+    pub fn clear(&mut self) {
+        self.comparison = None;
+        self.view = DiffView::Live;
+    }
+}
+
+impl Default for ShaderDiff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Note a successful shader reload: the next rendered frame captures the
+/// comparison. Does nothing without a [`ShaderDiff`] resource.
+#[cfg_attr(not(any(feature = "render2d", feature = "render3d")), allow(dead_code))]
+pub(crate) fn request_shader_diff(world: &mut World, shader: &Path) {
+    if let Some(diff) = world.get_resource_mut::<ShaderDiff>() {
+        diff.pending = Some(shader.to_path_buf());
+    }
+}
+
+// ── GPU side ────────────────────────────────────────────────────────────
+
+/// The captured frames, kept on the GPU so showing them costs one copy.
+pub(crate) struct DiffTargets {
+    before: wgpu::Texture,
+    after: wgpu::Texture,
+}
+
+impl DiffTargets {
+    fn new(gpu: &GpuContext, like: &wgpu::Texture) -> Self {
+        let create = |label| {
+            gpu.device.create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: like.size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: like.format(),
+                usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            })
+        };
+        Self {
+            before: create("shader diff before"),
+            after: create("shader diff after"),
+        }
+    }
+
+    fn matches(&self, scene: &wgpu::Texture) -> bool {
+        self.before.size() == scene.size() && self.before.format() == scene.format()
+    }
+}
+
+fn copy(encoder: &mut wgpu::CommandEncoder, from: &wgpu::Texture, to: &wgpu::Texture) {
+    encoder.copy_texture_to_texture(from.as_image_copy(), to.as_image_copy(), from.size());
+}
+
+/// Before the scene draws: if a shader reloaded, keep the previous frame
+/// (the old shader's) as "before". Returns the shader to finish the
+/// comparison for.
+pub(crate) fn begin_shader_diff(world: &mut World, frame: &mut FrameContext<'_>) -> Option<PathBuf> {
+    let diff = world.get_resource_mut::<ShaderDiff>()?;
+    let shader = diff.pending.take()?;
+    let showing_capture = diff.view != DiffView::Live && diff.comparison.is_some();
+    let Some(scene) = scene_target(world) else {
+        log::warn!(
+            "No before frame for '{}' (the window was resized or the diff just started)",
+            shader.display()
+        );
+        return None;
+    };
+
+    let targets = world.resource_remove::<DiffTargets>().filter(|targets| targets.matches(&scene));
+    let targets = match targets {
+        // The scene target holds a captured frame, not the last live one;
+        // the previous "after" was drawn with the same shader.
+        Some(targets) if showing_capture => {
+            copy(&mut frame.encoder, &targets.after, &targets.before);
+            targets
+        }
+        targets => {
+            let targets = targets.unwrap_or_else(|| DiffTargets::new(frame.gpu, &scene));
+            copy(&mut frame.encoder, &scene, &targets.before);
+            targets
+        }
+    };
+    world.insert_resource(targets);
+    Some(shader)
+}
+
+/// After the scene draws: keep it as "after" if a comparison is being
+/// captured, then replace it with a captured frame if the view asks for
+/// one. Returns the readbacks to finish once the frame is submitted.
+pub(crate) fn finish_shader_diff(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+    shader: Option<PathBuf>,
+) -> Option<PendingComparison> {
+    let scene = scene_target(world)?;
+    let targets = world
+        .get_resource::<DiffTargets>()
+        .filter(|targets| targets.matches(&scene))?;
+
+    let pending = shader.map(|shader| {
+        copy(&mut frame.encoder, &scene, &targets.after);
+        PendingComparison {
+            shader,
+            before: TextureReadback::record(frame.gpu, &mut frame.encoder, &targets.before),
+            after: TextureReadback::record(frame.gpu, &mut frame.encoder, &targets.after),
+        }
+    });
+
+    let view = world
+        .get_resource::<ShaderDiff>()
+        .filter(|diff| diff.comparison.is_some() || pending.is_some())
+        .map_or(DiffView::Live, |diff| diff.view);
+    match view {
+        DiffView::Live => {}
+        DiffView::Before => copy(&mut frame.encoder, &targets.before, &scene),
+        DiffView::After => copy(&mut frame.encoder, &targets.after, &scene),
+    }
+    pending
+}
+
+/// Before/after readbacks recorded into a frame. Call
+/// [`finish`](Self::finish) after the frame is submitted.
+pub(crate) struct PendingComparison {
+    shader: PathBuf,
+    before: TextureReadback,
+    after: TextureReadback,
+}
+
+impl PendingComparison {
+    /// Read both frames back, measure them, and store the comparison in the
+    /// [`ShaderDiff`] resource.
+    pub(crate) fn finish(self, world: &mut World, device: &wgpu::Device) {
+        let (width, height) = self.before.size();
+        let (Some(before), Some(after)) = (self.before.read(device), self.after.read(device)) else {
+            return;
+        };
+        let Some(diff) = world.get_resource_mut::<ShaderDiff>() else {
+            return;
+        };
+
+        let stats = DiffStats::compare(&before, &after);
+        log::info!(
+            "Shader diff for '{}': {:.1}% of pixels changed, mean Δ {:.2}, max Δ {}",
+            self.shader.display(),
+            stats.changed * 100.0,
+            stats.mean_delta,
+            stats.max_delta
+        );
+        let saved = diff
+            .save_dir
+            .as_deref()
+            .and_then(|dir| save_pair(dir, &self.shader, width, height, &before, &after));
+        diff.comparison = Some(ShaderComparison {
+            shader: self.shader,
+            width,
+            height,
+            before,
+            after,
+            stats,
+            saved,
+        });
+        diff.generation += 1;
+    }
+}
+
+/// Write `<stem>.before.png` and `<stem>.after.png` into `dir`.
+fn save_pair(
+    dir: &Path,
+    shader: &Path,
+    width: u32,
+    height: u32,
+    before: &[u8],
+    after: &[u8],
+) -> Option<(PathBuf, PathBuf)> {
+    if let Err(e) = std::fs::create_dir_all(dir) {
+        log::warn!("Failed to create shader diff directory '{}': {e}", dir.display());
+        return None;
+    }
+    let stem = shader.file_stem().map_or("shader".into(), |stem| stem.to_string_lossy());
+    let before_path = dir.join(format!("{stem}.before.png"));
+    let after_path = dir.join(format!("{stem}.after.png"));
+    for (path, rgba) in [(&before_path, before), (&after_path, after)] {
+        if let Err(e) = image::save_buffer(path, rgba, width, height, image::ColorType::Rgba8) {
+            log::warn!("Failed to save shader diff frame '{}': {e}", path.display());
+            return None;
+        }
+    }
+    Some((before_path, after_path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats_measure_changed_pixels() {
+        let before = [10, 10, 10, 255, 0, 0, 0, 255, 50, 50, 50, 255, 7, 7, 7, 255];
+        let mut after = before;
+        after[0] = 40; // +30 red on the first pixel
+        after[11] = 0; // alpha doesn't count
+        let stats = DiffStats::compare(&before, &after);
+        assert_eq!(stats.changed, 0.25);
+        assert_eq!(stats.max_delta, 30);
+        assert!((stats.mean_delta - 30.0 / 12.0).abs() < 1e-6);
+
+        assert_eq!(DiffStats::compare(&before, &before), DiffStats::default());
+        assert_eq!(DiffView::After.next(), DiffView::Live);
+    }
+}
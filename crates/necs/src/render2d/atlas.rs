@@ -0,0 +1,298 @@
+//! # Atlas — Runtime Packing of Small Textures
+//!
+//! Every texture switch breaks a sprite batch (see [`batch`](super::batch)).
+//! A game built from dozens of small PNGs — icons, tiles, particles, UI
+//! pieces — can end up with one draw call per sprite even though each image
+//! is tiny. The classic fix is an offline packer that merges the images into
+//! one sheet; this module does the same thing at load time, so projects get
+//! the batching benefit without a packing step in their asset pipeline.
+//!
+//! [`load_texture`](super::load_texture) packs any image no larger than
+//! [`TextureAtlasing::max_texture_size`] into a shared *atlas page*. The
+//! returned [`TextureHandle`](super::TextureHandle) still behaves like a
+//! standalone texture — `texture_rect` stays relative to the original image
+//! and auto-sizing uses its original dimensions — and the batcher remaps the
+//! UVs into the page:
+//!
+//! ```text
+//!   atlas page (2048×2048)               sprite UV (0..1 of coin.png)
+//!   ┌────────┬─────┬──────┬──────┐            │
+//!   │ hero   │ coin│ gem  │ key  │  shelf 0   ▼
+//!   ├────────┴──┬──┴──────┼──────┘     page UV = region.min
+//!   │ tile_a    │ tile_b  │          shelf 1     + uv × region.size
+//!   ├───────────┴─────────┘
+//!   │ (free)
+//!   └──────────────────────────────┘
+//! ```
+//!
+//! ## Shelf Packing
+//!
+//! Images are placed left to right on horizontal *shelves*. A new image goes
+//! on the lowest existing shelf that is tall enough and has room left;
+//! otherwise a new shelf is opened below the last one. When a page is full,
+//! a new page is created. Shelf packing wastes some space above short images
+//! but is fast, incremental (no repacking when a texture arrives later), and
+//! works well for the similarly-sized images games tend to load together.
+//!
+//! Each image is surrounded by a one-pixel gutter filled with copies of its
+//! edge pixels, so sampling exactly on a border never picks up a neighbour.
+//!
+//! ## Text, Shapes and Icons on One Page
+//!
+//! Images aren't the only thing that switches textures. Text draws from its
+//! font's glyph atlas, and shapes, untextured sprites and UI panels draw
+//! from the 1x1 white default — so a button icon, its label and its
+//! background each broke the batch. Pages cover all three:
+//!
+//! ```text
+//!   linear page                          what draws from it
+//!   ┌──┬──────────────┬───────┐
+//!   │▓▓│ font atlas   │ icon  │         ▓▓  white block: shapes, panels,
+//!   ├──┘ (arial 24)   ├───────┘             untextured sprites and
+//!   │                 │                     particles in the same batch
+//!   ├─────────────────┤                 font atlas: glyph UVs remapped
+//!   │ font atlas (mono 16)              icon: loaded with the linear
+//!   └───────────────────────────┘             sampler
+//! ```
+//!
+//! - Pages exist per sampler: one set filtered nearest (the default for
+//!   images), one linear (font atlases and images loaded with
+//!   [`SamplerSettings::linear`](crate::render::SamplerSettings::linear)).
+//! - Every page reserves a small white block. Untextured geometry that
+//!   follows a page's images in draw order draws from that block instead of
+//!   the default texture, so it joins their batch.
+//! - Bitmap and distance-field font atlases are packed whole while they
+//!   fit in half a page (see [`TextureAtlasing::should_pack_font`]). When a
+//!   font's atlas grows past that, it moves to a texture of its own.
+//!
+//! ## Limitations
+//!
+//! - A packed texture can't be tiled by pushing `texture_rect` outside
+//!   `0..1` — the page's neighbours would show instead of the clamped edge.
+//!   Use [`SpriteTiling`](super::SpriteTiling) instead, which tiles packed
+//!   textures correctly.
+//! - Hot-reloading a packed image with the same dimensions rewrites its
+//!   region in place. A size change moves it to a standalone texture; the
+//!   old region stays unused until the game restarts.
+//! - Pages are sampled clamped, with either nearest or linear filtering.
+//!   Textures loaded with any other
+//!   [`SamplerSettings`](crate::render::SamplerSettings) stay standalone,
+//!   and changing a packed texture's sampler copies it out.
+//! - Text and icons loaded with the default sampler sit on different
+//!   pages. Sprite batches bind several textures and don't mind, but the
+//!   UI binds one per batch; load UI icons with the linear sampler to keep
+//!   them in the same batch as labels.
+//! - Only [`load_texture`](super::load_texture) and font loading pack.
+//!   Textures made with
+//!   [`create_texture_from_rgba`](super::create_texture_from_rgba) are often
+//!   replaced wholesale and stay standalone.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Sprite Atlas assets, packed in the editor or at build time;
+//!   runtime packing needs `Texture2D.PackTextures` by hand.
+//! - **Bevy**: `TextureAtlasBuilder` packs on request, but the result is a
+//!   separate atlas type — sprites must be rewritten to use it. Fonts get
+//!   their own glyph atlases.
+//! - **Godot**: Fonts share dynamic glyph textures; `CanvasItem` batching
+//!   merges items that use the same texture.
+//! - **Our approach**: Packing is transparent to game code — the same
+//!   `TextureHandle` works whether or not its image was packed, and fonts
+//!   and untextured geometry draw from the same pages.
+
+/// Runtime atlas packing settings. Insert as a resource to change them;
+/// without one, [`TextureAtlasing::default`] is used.
+///
+/// ```ignore
+/// Game::new("My Game")
+///     .resource(TextureAtlasing::disabled())
+///     .run();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextureAtlasing {
+    /// Pack textures loaded with `load_texture`.
+    pub enabled: bool,
+    /// Largest width or height, in pixels, that is packed.
+    pub max_texture_size: u32,
+    /// Width and height of each atlas page, in pixels.
+    pub page_size: u32,
+}
+
+impl TextureAtlasing {
+    /// Settings that never pack.
+    pub fn disabled() -> Self {
+        Self {
+            enabled: false,
+            ..Self::default()
+        }
+    }
+
+    /// Whether an image of this size should be packed.
+    pub(crate) fn should_pack(&self, width: u32, height: u32) -> bool {
+        self.enabled
+            && width <= self.max_texture_size
+            && height <= self.max_texture_size
+            && width + 2 * ATLAS_PADDING <= self.page_size
+            && height + 2 * ATLAS_PADDING <= self.page_size
+    }
+
+    /// Whether a font's glyph atlas of this size should be packed. Font
+    /// atlases are bigger than `max_texture_size` allows, so any size up to
+    /// half a page each way is packed, letting a few fonts share one page.
+    pub(crate) fn should_pack_font(&self, width: u32, height: u32) -> bool {
+        let fits = |side: u32| 2 * (side + 2 * ATLAS_PADDING) <= self.page_size;
+        self.enabled && fits(width) && fits(height)
+    }
+}
+
+impl Default for TextureAtlasing {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_texture_size: 256,
+            page_size: 2048,
+        }
+    }
+}
+
+/// Gutter around each packed image, in pixels.
+pub(crate) const ATLAS_PADDING: u32 = 1;
+
+/// One horizontal row of packed images.
+#[derive(Debug, Clone, Copy)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    /// Where the next image on this shelf starts.
+    cursor_x: u32,
+}
+
+/// Incremental shelf allocator for one square-or-not page.
+#[derive(Debug, Clone)]
+pub(crate) struct ShelfPacker {
+    width: u32,
+    height: u32,
+    shelves: Vec<Shelf>,
+    /// Top of the free space below the last shelf.
+    next_y: u32,
+}
+
+impl ShelfPacker {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            shelves: Vec::new(),
+            next_y: 0,
+        }
+    }
+
+    /// Reserve a `w`×`h` rectangle. Returns its top-left corner, or `None`
+    /// if the page is full.
+    pub fn allocate(&mut self, w: u32, h: u32) -> Option<(u32, u32)> {
+        if w > self.width || h > self.height {
+            return None;
+        }
+
+        // Best fit: the shortest existing shelf the image fits on.
+        let width = self.width;
+        let best = self
+            .shelves
+            .iter_mut()
+            .filter(|s| h <= s.height && s.cursor_x + w <= width)
+            .min_by_key(|s| s.height);
+        if let Some(shelf) = best {
+            let x = shelf.cursor_x;
+            shelf.cursor_x += w;
+            return Some((x, shelf.y));
+        }
+
+        if self.next_y + h > self.height {
+            return None;
+        }
+        let y = self.next_y;
+        self.shelves.push(Shelf {
+            y,
+            height: h,
+            cursor_x: w,
+        });
+        self.next_y += h;
+        Some((0, y))
+    }
+}
+
+/// Copy an RGBA8 image into a buffer `padding` pixels larger on every side,
+/// filling the border with the nearest edge pixel.
+pub(crate) fn extrude(data: &[u8], width: u32, height: u32, padding: u32) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let pad = padding as usize;
+    let out_w = w + 2 * pad;
+    let out_h = h + 2 * pad;
+
+    let mut out = Vec::with_capacity(out_w * out_h * 4);
+    for y in 0..out_h {
+        let src_y = y.saturating_sub(pad).min(h - 1);
+        for x in 0..out_w {
+            let src_x = x.saturating_sub(pad).min(w - 1);
+            let i = (src_y * w + src_x) * 4;
+            out.extend_from_slice(&data[i..i + 4]);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shelves_fill_left_to_right_then_down() {
+        let mut packer = ShelfPacker::new(100, 100);
+        assert_eq!(packer.allocate(40, 30), Some((0, 0)));
+        assert_eq!(packer.allocate(40, 20), Some((40, 0)));
+        // Doesn't fit next to the others: new shelf.
+        assert_eq!(packer.allocate(30, 10), Some((0, 30)));
+        // Short image prefers the short shelf.
+        assert_eq!(packer.allocate(10, 10), Some((30, 30)));
+        assert_eq!(packer.allocate(20, 25), Some((80, 0)));
+    }
+
+    #[test]
+    fn full_page_rejects() {
+        let mut packer = ShelfPacker::new(64, 64);
+        assert_eq!(packer.allocate(65, 1), None);
+        assert_eq!(packer.allocate(64, 60), Some((0, 0)));
+        assert_eq!(packer.allocate(1, 5), None);
+        assert_eq!(packer.allocate(1, 4), Some((0, 60)));
+    }
+
+    #[test]
+    fn extrude_repeats_edge_pixels() {
+        // 2×1 image: red, green.
+        let data = [255, 0, 0, 255, 0, 255, 0, 255];
+        let out = extrude(&data, 2, 1, 1);
+        assert_eq!(out.len(), 4 * 3 * 4);
+        let pixel = |x: usize, y: usize| &out[(y * 4 + x) * 4..(y * 4 + x) * 4 + 4];
+        assert_eq!(pixel(0, 0), &data[0..4]);
+        assert_eq!(pixel(1, 1), &data[0..4]);
+        assert_eq!(pixel(2, 1), &data[4..8]);
+        assert_eq!(pixel(3, 2), &data[4..8]);
+    }
+
+    #[test]
+    fn threshold_and_page_size_limit_packing() {
+        let settings = TextureAtlasing {
+            enabled: true,
+            max_texture_size: 64,
+            page_size: 64,
+        };
+        assert!(settings.should_pack(62, 10));
+        assert!(!settings.should_pack(64, 10));
+        assert!(!TextureAtlasing::disabled().should_pack(1, 1));
+
+        let pages = TextureAtlasing::default();
+        assert!(pages.should_pack_font(512, 512));
+        assert!(!pages.should_pack_font(1024, 1024));
+        assert!(!TextureAtlasing::disabled().should_pack_font(1, 1));
+    }
+}
@@ -1,11 +1,13 @@
 //! # Batch — Collect, Sort, and Group 2D Primitives for Drawing
 //!
 //! This module is the CPU-side heart of the 2D renderer. Each frame it:
-//! 1. Queries all `(Transform, Sprite)`, `(Transform, Shape2d)`, and text entities
+//! 1. Takes the sprites, shapes and text copied out by the
+//!    [extract phase](crate::render::extract)
 //! 2. Emits vertices and indices per primitive (quads for sprites/text, tessellated
 //!    geometry for shapes)
 //! 3. Sorts by Z for correct back-to-front ordering
-//! 4. Groups consecutive same-texture primitives into batches
+//! 4. Groups consecutive primitives into batches: same texture, or up to
+//!    `TEXTURE_ARRAY_SLOTS` textures where the GPU has texture arrays
 //!
 //! ## Why Batching Matters
 //!
@@ -34,7 +36,82 @@
 //! After sorting, primitives are iterated in order. As long as consecutive
 //! primitives share the same texture handle, they're merged into one
 //! [`DrawBatch`]. Shapes always use texture handle 0 (the 1x1 white texture),
-//! so they batch with untextured sprites.
+//! so they batch with untextured sprites. A sprite whose texture was packed
+//! into an [atlas](super::atlas) page batches on the page, so sprites from
+//! different small images share a draw call. So does text whose font atlas
+//! was packed — glyph UVs are remapped into the page like sprite UVs.
+//!
+//! Untextured geometry (shapes, and sprites and particles without a
+//! texture) that follows a batch binding an atlas page doesn't need the
+//! white texture at all: every page keeps a white block, and the geometry
+//! is pointed at it and joins the batch:
+//!
+//! ```text
+//!   Z order:   icon  rect  icon  label      (icon, label packed on page P)
+//!   before:    [P]   [white]  [P]           3 draws
+//!   now:       [P ───────────────────]      1 draw; rect UVs → P's white
+//! ```
+//!
+//! ## Texture Arrays
+//!
+//! Atlases need images packed ahead of time, and a scene alternating
+//! between two big textures still breaks its batch at every switch. On GPUs
+//! with binding arrays, a batch instead holds a small table of textures,
+//! and each vertex (or instance) stores its texture's slot in it:
+//!
+//! ```text
+//!   Z order:   A  B  A  C  B  D        textures: [A]   [B]   [A] ...
+//!   per-texture batches:  6 draws      texture arrays: one draw,
+//!                                      table [A, B, C, D], indices
+//!                                      0  1  0  2  1  3
+//! ```
+//!
+//! A primitive joins the current batch if its texture is already in the
+//! table or the table has a free slot (`TEXTURE_ARRAY_SLOTS`); otherwise
+//! a new batch starts. The fragment shader samples the texture its vertex
+//! names. Without the GPU features every table has one slot, which is
+//! exactly per-texture batching.
+//!
+//! ## Camera Culling
+//!
+//! Before anything is emitted, each sprite, shape and progress bar is
+//! tested against the rectangle the [`Camera2d`] sees. The camera's
+//! transform carries its zoom (scale) and rotation, so the screen rectangle
+//! is pushed through it and the axis-aligned box around the result is the
+//! visible rect:
+//!
+//! ```text
+//!   ┌──────────────┐ visible rect: bounds of the rotated, zoomed screen
+//!   │     ╱╲       │
+//!   │    ╱ ■╲      │     ■ overlaps: emitted and sorted
+//!   │    ╲  ╱    ▲ │     ▲ outside the screen but inside the rect: drawn
+//!   │     ╲╱       │       anyway (conservative)
+//!   └──────────────┘  ●  ● outside: skipped before any transform or sort
+//! ```
+//!
+//! Primitives whose world bounds miss it are dropped before Z-sorting, so a
+//! world of tens of thousands of off-screen sprites costs one bounds test
+//! each. The test is conservative — a rotated camera keeps a little extra
+//! around its corners — and matches how [tilemap](super::tilemap) chunks are
+//! culled. Text and particles are not culled.
+//!
+//! ## Instanced Mode
+//!
+//! Emitting four transformed vertices per sprite is cheap per sprite, but past
+//! a few thousand sprites the CPU transform and the per-frame buffer rebuild
+//! dominate. With [`SpriteRenderMode::Instanced`], each sprite (each tile
+//! piece, for tiled sprites) becomes one [`SpriteInstance`] — its model matrix
+//! already scaled to the quad, UV rectangle and color — and the vertex shader
+//! does the transform. Shapes and text stay as vertices; instanced and vertex
+//! batches interleave in the same Z order, so layering is unchanged.
+//!
+//! ## Distance-Field Text
+//!
+//! Glyphs of [SDF fonts](super::sdf) become [`SdfVertex`] quads, which carry
+//! outline and shadow parameters, in buffers of their own. They batch like
+//! any other primitive, one texture per batch, and draw with the SDF text
+//! pipeline. A text's shadow is its glyphs again, emitted before the text at
+//! the same Z so the sort keeps every shadow under every glyph.
 //!
 //! ## Comparison
 //!
@@ -45,55 +122,244 @@
 //!   mode calls. No explicit batching — each draw is its own call.
 //! - **Love2D** (C++ backend): Automatic batching of consecutive same-texture
 //!   draws, very similar to our approach.
+//! - **Godot** (4.x): The 2D batcher fills a per-batch texture table and
+//!   indexes it per vertex, like our texture arrays.
 
 use crate::ecs::World;
 use crate::ecs::hierarchy::GlobalTransform;
+use crate::math::Rect;
+use crate::particles::{ParticleEmitter, ParticleQuad};
+use crate::render::pass::CameraClear;
+use crate::render::visibility::{ComputedVisibility, is_hidden};
 
-use super::font::FontStore;
+use super::font::{FontStore, TextGlyph, layout_text};
+use super::progress_bar::{BAR_Z_BIAS, ProgressBar2d};
 use super::shapes::Shape2d;
-use super::texture::{TextureHandle, TextureStore};
-use super::vertex::SpriteVertex;
+use super::texture::{TextureHandle, TextureStore, WhiteSpots};
+use super::texture_atlas::{resolve_atlas_sprite, TextureAtlases};
+use super::tilemap::{ExtractedChunk, camera_view, extract_tilemaps, overlaps, transformed_bounds};
+use super::tiling::{tile_pieces, SpriteTiling, TilePiece};
+use super::vertex::{SdfVertex, SpriteInstance, SpriteVertex};
 use super::{Camera2d, Sprite};
 use super::font::Text;
 
-/// A draw command for one batch of primitives sharing the same texture.
+/// How sprites are sent to the GPU. Insert as a resource to change it;
+/// without one, [`SpriteRenderMode::Batched`] is used.
+///
+/// ```ignore
+/// Game::new("Bullet Hell")
+///     .resource(SpriteRenderMode::Instanced)
+///     .run();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpriteRenderMode {
+    /// Transform corners on the CPU and upload four vertices per sprite.
+    #[default]
+    Batched,
+    /// Upload one instance per sprite and transform in the vertex shader.
+    /// Scales to tens of thousands of sprites.
+    Instanced,
+}
+
+/// How a [`DrawBatch`] is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum BatchKind {
+    /// `start..start + count` indexes the shared index buffer.
+    Indexed,
+    /// `start..start + count` indexes the instance buffer.
+    Instanced,
+    /// `start..start + count` indexes the distance-field text index buffer.
+    Sdf,
+}
+
+/// A draw command for one batch of primitives.
 pub(crate) struct DrawBatch {
-    pub texture: TextureHandle,
-    /// Range into the shared index buffer.
-    pub index_start: u32,
-    pub index_count: u32,
+    /// The textures the batch draws from, indexed by each vertex's (or
+    /// instance's) `texture_index`. Only one without texture arrays.
+    pub textures: Vec<TextureHandle>,
+    pub kind: BatchKind,
+    pub start: u32,
+    pub count: u32,
+}
+
+/// Everything the draw pass uploads and draws for one frame.
+pub(crate) struct BatchedFrame {
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+    pub instances: Vec<SpriteInstance>,
+    pub sdf_vertices: Vec<SdfVertex>,
+    pub sdf_indices: Vec<u32>,
+    pub batches: Vec<DrawBatch>,
+    pub view_proj: glam::Mat4,
+    /// Sprites and shapes left out for being outside the camera's view.
+    pub culled: u32,
+}
+
+/// A primitive's geometry, before it is merged into the frame's buffers.
+enum Geometry {
+    Mesh {
+        vertices: Vec<SpriteVertex>,
+        /// Local indices (0-based) into `vertices`.
+        indices: Vec<u32>,
+    },
+    Instances(Vec<SpriteInstance>),
+    /// Distance-field glyph quads, four corners each.
+    Sdf(Vec<SdfVertex>),
 }
 
 /// Intermediate primitive data collected from the ECS before sorting.
 struct CollectedPrimitive {
     z: f32,
     texture: TextureHandle,
-    vertices: Vec<SpriteVertex>,
-    /// Local indices (0-based) into `vertices`.
-    indices: Vec<u32>,
+    geometry: Geometry,
+}
+
+/// 2D scene state copied out of the ECS by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct Extracted2d {
+    /// Global transform of the `Camera2d`, if any.
+    pub camera: Option<glam::Mat4>,
+    pub clear: CameraClear,
+    pub mode: SpriteRenderMode,
+    pub sprites: Vec<(glam::Mat4, Sprite)>,
+    pub shapes: Vec<(glam::Mat4, Shape2d)>,
+    /// On-screen tilemap chunks, already in world space.
+    pub tilemaps: Vec<ExtractedChunk>,
+    /// Live particles of every visible emitter.
+    pub particles: Vec<ExtractedParticles>,
+    /// Progress bars at their world position, Z bias included.
+    pub progress_bars: Vec<(glam::Vec3, ProgressBar2d)>,
+    /// Visible text; glyphs are laid out when batching.
+    pub texts: Vec<(glam::Mat4, Text)>,
+    /// Collider outlines, while [`DebugColliders2d`](super::debug_wireframe::DebugColliders2d)
+    /// is inserted.
+    #[cfg(feature = "physics2d")]
+    pub colliders: Option<super::debug_wireframe::ExtractedColliders2d>,
 }
 
-/// Collect all sprites, shapes, and text, emit geometry, sort by Z, batch by texture.
+/// One emitter's particles, in world space.
+pub(crate) struct ExtractedParticles {
+    /// The emitter's Z, which every particle sorts at.
+    pub z: f32,
+    pub texture: Option<TextureHandle>,
+    pub quads: Vec<ParticleQuad>,
+}
+
+/// Copy the 2D camera and every visible sprite, shape, tilemap chunk,
+/// particle, progress bar and text.
+pub(crate) fn extract_2d(world: &mut World) -> Extracted2d {
+    let mut camera = None;
+    let mut clear = CameraClear::Global;
+    world.query_single::<(&GlobalTransform, Option<&CameraClear>), Camera2d>(|_entity, (gt, camera_clear)| {
+        camera = Some(gt.matrix);
+        clear = camera_clear.copied().unwrap_or_default();
+    });
+
+    let mut sprites = Vec::new();
+    world.query::<(&GlobalTransform, &Sprite, Option<&ComputedVisibility>)>(|_entity, (gt, sprite, vis)| {
+        if !is_hidden(vis) {
+            sprites.push((gt.matrix, sprite.clone()));
+        }
+    });
+    let atlases = world.get_resource::<TextureAtlases>();
+    for (_, sprite) in &mut sprites {
+        resolve_atlas_sprite(sprite, atlases);
+    }
+
+    let mut shapes = Vec::new();
+    world.query::<(&GlobalTransform, &Shape2d, Option<&ComputedVisibility>)>(|_entity, (gt, shape, vis)| {
+        if !is_hidden(vis) {
+            shapes.push((gt.matrix, shape.clone()));
+        }
+    });
+    let tilemaps = extract_tilemaps(world, camera);
+
+    let mut particles = Vec::new();
+    world.query::<(&GlobalTransform, &ParticleEmitter, Option<&ComputedVisibility>)>(|_entity, (gt, emitter, vis)| {
+        if !is_hidden(vis) && !emitter.particles().is_empty() {
+            particles.push(ExtractedParticles {
+                z: gt.matrix.col(3).z,
+                texture: emitter.texture,
+                quads: emitter.quads().collect(),
+            });
+        }
+    });
+
+    // Progress bars follow their owner's position, or their own without one.
+    // A hidden or despawned owner hides the bar.
+    let mut bars = Vec::new();
+    world.query::<(&ProgressBar2d, Option<&GlobalTransform>, Option<&ComputedVisibility>)>(|_entity, (bar, gt, vis)| {
+        if !is_hidden(vis) {
+            bars.push((gt.map(|gt| gt.matrix.col(3).truncate()), bar.clone()));
+        }
+    });
+    let progress_bars = bars
+        .into_iter()
+        .filter_map(|(own, bar)| {
+            let anchor = match bar.owner {
+                Some(owner) => {
+                    if is_hidden(world.get::<ComputedVisibility>(owner)) {
+                        return None;
+                    }
+                    world.get::<GlobalTransform>(owner)?.matrix.col(3).truncate()
+                }
+                None => own?,
+            };
+            Some((anchor + bar.offset.extend(BAR_Z_BIAS), bar))
+        })
+        .collect();
+
+    let mut texts = Vec::new();
+    world.query::<(&GlobalTransform, &Text, Option<&ComputedVisibility>)>(|_entity, (gt, text, vis)| {
+        if !is_hidden(vis) {
+            texts.push((gt.matrix, text.clone()));
+        }
+    });
+
+    Extracted2d {
+        camera,
+        clear,
+        mode: world.get_resource::<SpriteRenderMode>().copied().unwrap_or_default(),
+        sprites,
+        shapes,
+        tilemaps,
+        particles,
+        progress_bars,
+        texts,
+        #[cfg(feature = "physics2d")]
+        colliders: super::debug_wireframe::extract_colliders_2d(world),
+    }
+}
+
+/// Collect all sprites, shapes, and text, emit geometry, sort by Z, batch by
+/// texture — up to `texture_slots` textures per batch.
 ///
-/// `surface_size` is passed in because `GpuContext` has been extracted from the
-/// world by the caller.
+/// Everything drawn comes from the extracted scene, so this never touches
+/// the world. `surface_size` is passed in because `GpuContext` has been
+/// extracted from the world by the caller.
 ///
-/// Returns (vertices, indices, batches, camera_view_proj).
+/// In [instanced mode](SpriteRenderMode::Instanced), sprites become instances
+/// rather than vertices.
 pub(crate) fn collect_and_batch(
-    world: &mut World,
+    scene: &Extracted2d,
     texture_store: &TextureStore,
     font_store: Option<&FontStore>,
     surface_size: (u32, u32),
-) -> (Vec<SpriteVertex>, Vec<u32>, Vec<DrawBatch>, glam::Mat4) {
-    // Camera view-projection
-    let view_proj = compute_camera_vp(world, surface_size);
+    texture_slots: usize,
+) -> BatchedFrame {
+    // Camera view-projection, and the world rect it shows
+    let view_proj = compute_camera_vp(scene.camera, surface_size);
+    let view = camera_view(scene.camera, Some(surface_size));
+    let mut culled = 0;
 
     // Collect sprites
     let default_handle = texture_store.default_handle();
     let mut collected: Vec<CollectedPrimitive> = Vec::new();
 
-    world.query::<(&GlobalTransform, &Sprite)>(|_entity, (gt, sprite)| {
-        let tex_handle = sprite.texture.unwrap_or(default_handle);
+    for (model, sprite) in &scene.sprites {
+        // Packed textures draw from their atlas page, so batch on the page.
+        let (tex_handle, region) =
+            texture_store.draw_source(sprite.texture.unwrap_or(default_handle));
 
         // Determine sprite size
         let size = if sprite.size != glam::Vec2::ZERO {
@@ -105,60 +371,84 @@ pub(crate) fn collect_and_batch(
             // No texture, no explicit size — default to 64x64
             glam::Vec2::new(64.0, 64.0)
         };
+        // Flipping mirrors the quad around its anchor; tile pieces stay
+        // inside it.
+        let flip = glam::Vec2::new(
+            if sprite.flip_x { -1.0 } else { 1.0 },
+            if sprite.flip_y { -1.0 } else { 1.0 },
+        );
+        let offset = sprite.anchor.offset(size);
+        let half = size.abs() * 0.5;
+        let center = offset * flip;
+        if !in_view(view, model, Rect { min: center - half, max: center + half }) {
+            culled += 1;
+            continue;
+        }
 
-        let half = size * 0.5;
         let color = sprite.color.to_array();
 
-        // UV coordinates from texture_rect (with flip support)
-        let rect = &sprite.texture_rect;
-        let (u_min, u_max) = if sprite.flip_x {
-            (rect.max.x, rect.min.x)
-        } else {
-            (rect.min.x, rect.max.x)
+        // UV rectangle of one tile: texture_rect, remapped from the image
+        // into its atlas region.
+        let region_size = region.max - region.min;
+        let rect = Rect {
+            min: region.min + sprite.texture_rect.min * region_size,
+            max: region.min + sprite.texture_rect.max * region_size,
         };
-        let (v_min, v_max) = if sprite.flip_y {
-            (rect.max.y, rect.min.y)
-        } else {
-            (rect.min.y, rect.max.y)
+        let tile = match sprite.tiling {
+            SpriteTiling::Stretch => size,
+            SpriteTiling::Repeat => match sprite.texture {
+                Some(handle) => {
+                    let entry = texture_store.get(handle);
+                    let rect_size = sprite.texture_rect.max - sprite.texture_rect.min;
+                    glam::Vec2::new(entry.width as f32, entry.height as f32) * rect_size.abs()
+                }
+                None => size,
+            },
+            SpriteTiling::RepeatEvery(tile_size) => tile_size,
         };
 
-        // Quad corners in local space, then transformed by global model matrix
-        let model = gt.matrix;
-        let corners = [
-            glam::Vec3::new(-half.x, -half.y, 0.0), // bottom-left
-            glam::Vec3::new( half.x, -half.y, 0.0), // bottom-right
-            glam::Vec3::new( half.x,  half.y, 0.0), // top-right
-            glam::Vec3::new(-half.x,  half.y, 0.0), // top-left
-        ];
-        let uvs = [
-            [u_min, v_max], // bottom-left
-            [u_max, v_max], // bottom-right
-            [u_max, v_min], // top-right
-            [u_min, v_min], // top-left
-        ];
-
-        let mut vertices = Vec::with_capacity(4);
-        for i in 0..4 {
-            let world_pos = model.transform_point3(corners[i]);
-            vertices.push(SpriteVertex {
-                position: [world_pos.x, world_pos.y, world_pos.z],
-                uv: uvs[i],
-                color,
-            });
-        }
+        // One quad per (partial) tile, corners in local space moved off the
+        // anchor, then transformed by the global model matrix. Flipping
+        // mirrors the positions; 2D sprites are double-sided, so winding
+        // doesn't matter.
+        let pieces = anchored_pieces(tile_pieces(size, tile, sprite.uv_offset), offset);
+        let geometry = match scene.mode {
+            SpriteRenderMode::Batched => {
+                let mut vertices = Vec::with_capacity(pieces.len() * 4);
+                let mut indices = Vec::with_capacity(pieces.len() * 6);
+                for piece in &pieces {
+                    let base = vertices.len() as u32;
+                    vertices.extend(piece_vertices(model, flip, piece, rect, color));
+                    indices.extend([0, 1, 2, 0, 2, 3].map(|i| base + i));
+                }
+                Geometry::Mesh { vertices, indices }
+            }
+            SpriteRenderMode::Instanced => Geometry::Instances(
+                pieces
+                    .iter()
+                    .map(|piece| piece_instance(model, flip, piece, rect, color))
+                    .collect(),
+            ),
+        };
 
         collected.push(CollectedPrimitive {
-            z: gt.matrix.col(3).z,
+            z: model.col(3).z,
             texture: tex_handle,
-            vertices,
-            indices: vec![0, 1, 2, 0, 2, 3],
+            geometry,
         });
-    });
+    }
 
     // Collect Shape2d entities
-    world.query::<(&GlobalTransform, &Shape2d)>(|_entity, (gt, shape)| {
+    for (model, shape) in &scene.shapes {
         let (positions, local_indices) = shape.tessellate();
-        let model = gt.matrix;
+        let local = Rect {
+            min: positions.iter().fold(glam::Vec2::MAX, |acc, p| acc.min(glam::Vec2::from(*p))),
+            max: positions.iter().fold(glam::Vec2::MIN, |acc, p| acc.max(glam::Vec2::from(*p))),
+        };
+        if !positions.is_empty() && !in_view(view, model, local) {
+            culled += 1;
+            continue;
+        }
         let color = shape.color.to_array();
 
         let vertices: Vec<SpriteVertex> = positions
@@ -169,128 +459,415 @@ pub(crate) fn collect_and_batch(
                     position: [world_pos.x, world_pos.y, world_pos.z],
                     uv: [0.5, 0.5], // center of white texture
                     color,
+                    texture_index: 0,
                 }
             })
             .collect();
 
         collected.push(CollectedPrimitive {
-            z: gt.matrix.col(3).z,
+            z: model.col(3).z,
             texture: default_handle,
-            vertices,
-            indices: local_indices,
+            geometry: Geometry::Mesh {
+                vertices,
+                indices: local_indices,
+            },
         });
-    });
+    }
 
-    // Collect text entities as glyph quads
-    if let Some(fs) = font_store {
-        world.query::<(&GlobalTransform, &Text)>(|_entity, (gt, text)| {
-            let entry = fs.get(text.font);
-            let color = text.color.to_array();
-            let z = gt.matrix.col(3).z;
-            let model = gt.matrix;
-
-            let mut cursor_x: f32 = 0.0;
-            let mut cursor_y: f32 = 0.0;
-
-            for ch in text.content.chars() {
-                if ch == '\n' {
-                    cursor_x = 0.0;
-                    cursor_y -= entry.line_height;
-                    continue;
-                }
+    // Progress bars: border, background and fill quads in one untextured
+    // primitive, unrotated at the bar's position.
+    for (at, bar) in &scene.progress_bars {
+        let quads = bar.quads();
+        let model = glam::Mat4::from_translation(*at);
+        if !in_view(view, &model, quads[0].0) {
+            culled += 1;
+            continue;
+        }
+        let mut vertices = Vec::with_capacity(quads.len() * 4);
+        let mut indices = Vec::with_capacity(quads.len() * 6);
+        for (rect, color) in quads {
+            let base = vertices.len() as u32;
+            let corners = [
+                rect.min,
+                glam::Vec2::new(rect.max.x, rect.min.y),
+                rect.max,
+                glam::Vec2::new(rect.min.x, rect.max.y),
+            ];
+            vertices.extend(corners.iter().map(|corner| SpriteVertex {
+                position: [at.x + corner.x, at.y + corner.y, at.z],
+                uv: [0.5, 0.5],
+                color: color.to_array(),
+                texture_index: 0,
+            }));
+            indices.extend([0, 1, 2, 0, 2, 3].map(|i| base + i));
+        }
+        collected.push(CollectedPrimitive {
+            z: at.z,
+            texture: default_handle,
+            geometry: Geometry::Mesh { vertices, indices },
+        });
+    }
 
-                let glyph = match entry.glyph(ch) {
-                    Some(g) => g,
-                    None => continue,
-                };
+    // Tilemap chunks. They share the atlas texture and the map's Z, so the
+    // chunks of one map sort next to each other and merge into one batch.
+    for chunk in &scene.tilemaps {
+        let (texture, region) = texture_store.draw_source(chunk.texture);
+        let region_size = region.max - region.min;
+        let vertices = chunk
+            .vertices
+            .iter()
+            .map(|v| SpriteVertex {
+                uv: (region.min + glam::Vec2::from(v.uv) * region_size).to_array(),
+                ..*v
+            })
+            .collect();
+        collected.push(CollectedPrimitive {
+            z: chunk.z,
+            texture,
+            geometry: Geometry::Mesh {
+                vertices,
+                indices: chunk.indices.clone(),
+            },
+        });
+    }
 
-                // Skip zero-size glyphs (e.g. space) — just advance cursor
-                if glyph.width == 0.0 || glyph.height == 0.0 {
-                    cursor_x += glyph.advance;
-                    continue;
-                }
+    // Particles: one primitive per emitter, a square quad per particle
+    // showing the whole texture.
+    for emitter in &scene.particles {
+        let (texture, region) = texture_store.draw_source(emitter.texture.unwrap_or(default_handle));
+        let mut vertices = Vec::with_capacity(emitter.quads.len() * 4);
+        let mut indices = Vec::with_capacity(emitter.quads.len() * 6);
+        for quad in &emitter.quads {
+            let base = vertices.len() as u32;
+            let half = quad.size * 0.5;
+            let corners = [
+                (-half, -half, region.min.x, region.max.y), // bottom-left
+                (half, -half, region.max.x, region.max.y),  // bottom-right
+                (half, half, region.max.x, region.min.y),   // top-right
+                (-half, half, region.min.x, region.min.y),  // top-left
+            ];
+            vertices.extend(corners.map(|(x, y, u, v)| SpriteVertex {
+                position: [quad.position.x + x, quad.position.y + y, quad.position.z],
+                uv: [u, v],
+                color: quad.color,
+                texture_index: 0,
+            }));
+            indices.extend([0, 1, 2, 0, 2, 3].map(|i| base + i));
+        }
+        collected.push(CollectedPrimitive {
+            z: emitter.z,
+            texture,
+            geometry: Geometry::Mesh { vertices, indices },
+        });
+    }
 
-                // Glyph quad in local space relative to Transform origin.
-                // offset_y is ymin from fontdue (baseline-relative, Y-up).
-                let x0 = cursor_x + glyph.offset_x;
-                let y0 = cursor_y + glyph.offset_y;
-                let x1 = x0 + glyph.width;
-                let y1 = y0 + glyph.height;
-
-                let corners = [
-                    glam::Vec3::new(x0, y0, 0.0), // bottom-left
-                    glam::Vec3::new(x1, y0, 0.0), // bottom-right
-                    glam::Vec3::new(x1, y1, 0.0), // top-right
-                    glam::Vec3::new(x0, y1, 0.0), // top-left
-                ];
-                let uvs = [
-                    [glyph.u_min, glyph.v_max], // bottom-left
-                    [glyph.u_max, glyph.v_max], // bottom-right
-                    [glyph.u_max, glyph.v_min], // top-right
-                    [glyph.u_min, glyph.v_min], // top-left
-                ];
-
-                let mut vertices = Vec::with_capacity(4);
-                for i in 0..4 {
-                    let world_pos = model.transform_point3(corners[i]);
-                    vertices.push(SpriteVertex {
-                        position: [world_pos.x, world_pos.y, world_pos.z],
-                        uv: uvs[i],
+    // Collect text as glyph quads
+    if let Some(fs) = font_store {
+        for (matrix, text) in &scene.texts {
+            let mut glyphs = layout_text(fs, text);
+            // Packed font atlases draw from their page.
+            for glyph in &mut glyphs {
+                let (page, region) = texture_store.draw_source(glyph.atlas);
+                let region_size = region.max - region.min;
+                glyph.atlas = page;
+                glyph.uv_min = region.min + glyph.uv_min * region_size;
+                glyph.uv_max = region.min + glyph.uv_max * region_size;
+            }
+            let outline = text.outline.map_or((0.0, [0.0; 4]), |o| (o.width, o.color.to_array()));
+            // Shadows first: the stable sort keeps them under the text.
+            if let Some(shadow) = text.shadow {
+                let color = shadow.color.to_array();
+                for glyph in &glyphs {
+                    let paint = GlyphPaint {
+                        offset: shadow.offset,
                         color,
-                    });
+                        outline: (outline.0, color),
+                        softness: shadow.softness,
+                    };
+                    collected.push(glyph_primitive(matrix, glyph, &paint));
                 }
-
-                collected.push(CollectedPrimitive {
-                    z,
-                    texture: entry.atlas_handle,
-                    vertices,
-                    indices: vec![0, 1, 2, 0, 2, 3],
-                });
-
-                cursor_x += glyph.advance;
             }
-        });
+            for glyph in &glyphs {
+                let paint = GlyphPaint {
+                    offset: glam::Vec2::ZERO,
+                    color: glyph.color,
+                    outline,
+                    softness: 0.0,
+                };
+                collected.push(glyph_primitive(matrix, glyph, &paint));
+            }
+        }
     }
 
     // Sort by Z ascending (back-to-front for painter's algorithm)
     collected.sort_by(|a, b| a.z.partial_cmp(&b.z).unwrap_or(std::cmp::Ordering::Equal));
 
-    // Emit vertices, indices, and batches
+    let mut frame = emit_batches(&collected, texture_slots, &texture_store.white_spots());
+    frame.view_proj = view_proj;
+    frame.culled = culled;
+    frame
+}
+
+/// Merge sorted primitives into the frame's buffers and batches, with up
+/// to `texture_slots` textures per batch. Untextured primitives draw from
+/// the white block of a page the current batch binds, if any.
+fn emit_batches(
+    collected: &[CollectedPrimitive],
+    texture_slots: usize,
+    white: &WhiteSpots,
+) -> BatchedFrame {
     let mut vertices = Vec::with_capacity(collected.len() * 4);
     let mut indices = Vec::with_capacity(collected.len() * 6);
+    let mut instances = Vec::new();
+    let mut sdf_vertices = Vec::new();
+    let mut sdf_indices = Vec::new();
     let mut batches: Vec<DrawBatch> = Vec::new();
 
-    for prim in &collected {
-        let base_vertex = vertices.len() as u32;
+    for prim in collected {
+        let kind = match prim.geometry {
+            Geometry::Mesh { .. } => BatchKind::Indexed,
+            Geometry::Instances(_) => BatchKind::Instanced,
+            Geometry::Sdf(_) => BatchKind::Sdf,
+        };
+        // The SDF text pipeline binds a single texture.
+        let slots = if kind == BatchKind::Sdf { 1 } else { texture_slots };
 
-        vertices.extend_from_slice(&prim.vertices);
+        // Untextured geometry borrows the white block of a page the
+        // current batch already binds.
+        let borrowed = batches
+            .last()
+            .filter(|last| prim.texture == white.default && last.kind == kind)
+            .and_then(|last| {
+                last.textures.iter().find_map(|page| white.pages.get(page).map(|&uv| (*page, uv)))
+            });
+        let texture = borrowed.map_or(prim.texture, |(page, _)| page);
+        let white_uv = borrowed.map(|(_, uv)| uv.to_array());
 
-        // Offset local indices by base_vertex
-        let idx_start = indices.len();
-        for &local_idx in &prim.indices {
-            indices.push(base_vertex + local_idx);
-        }
-        let idx_count = (indices.len() - idx_start) as u32;
+        // Join the current batch if it has the texture or a free slot for
+        // it; otherwise start a new one.
+        let joined = batches
+            .last_mut()
+            .filter(|last| last.kind == kind)
+            .and_then(|last| texture_slot(&mut last.textures, texture, slots));
+        let texture_index = match joined {
+            Some(slot) => slot,
+            None => {
+                let start = match kind {
+                    BatchKind::Indexed => indices.len(),
+                    BatchKind::Instanced => instances.len(),
+                    BatchKind::Sdf => sdf_indices.len(),
+                };
+                batches.push(DrawBatch {
+                    textures: vec![texture],
+                    kind,
+                    start: start as u32,
+                    count: 0,
+                });
+                0
+            }
+        };
 
-        // Extend current batch or start a new one
-        if let Some(last) = batches.last_mut() {
-            if last.texture == prim.texture {
-                last.index_count += idx_count;
-                continue;
+        let count = match &prim.geometry {
+            Geometry::Mesh {
+                vertices: prim_vertices,
+                indices: prim_indices,
+            } => {
+                let base_vertex = vertices.len() as u32;
+                vertices.extend(prim_vertices.iter().map(|v| SpriteVertex {
+                    texture_index,
+                    uv: white_uv.unwrap_or(v.uv),
+                    ..*v
+                }));
+
+                // Offset local indices by base_vertex
+                indices.extend(prim_indices.iter().map(|&i| base_vertex + i));
+                prim_indices.len()
+            }
+            Geometry::Instances(prim_instances) => {
+                instances.extend(prim_instances.iter().map(|i| SpriteInstance {
+                    texture_index,
+                    uv_rect: white_uv.map_or(i.uv_rect, |[u, v]| [u, v, u, v]),
+                    ..*i
+                }));
+                prim_instances.len()
             }
+            Geometry::Sdf(quads) => {
+                for quad in quads.chunks_exact(4) {
+                    let base_vertex = sdf_vertices.len() as u32;
+                    sdf_vertices.extend_from_slice(quad);
+                    sdf_indices.extend([0, 1, 2, 0, 2, 3].map(|i| base_vertex + i));
+                }
+                quads.len() / 4 * 6
+            }
+        };
+        if let Some(batch) = batches.last_mut() {
+            batch.count += count as u32;
         }
-        batches.push(DrawBatch {
-            texture: prim.texture,
-            index_start: idx_start as u32,
-            index_count: idx_count,
-        });
     }
 
-    (vertices, indices, batches, view_proj)
+    BatchedFrame {
+        vertices,
+        indices,
+        instances,
+        sdf_vertices,
+        sdf_indices,
+        batches,
+        view_proj: glam::Mat4::IDENTITY,
+        culled: 0,
+    }
+}
+
+/// The slot of `texture` in a batch's `textures`, adding it if fewer than
+/// `slots` are in use. `None` if the batch is full.
+fn texture_slot(textures: &mut Vec<TextureHandle>, texture: TextureHandle, slots: usize) -> Option<u32> {
+    let slot = match textures.iter().position(|&t| t == texture) {
+        Some(slot) => slot,
+        None if textures.len() < slots => {
+            textures.push(texture);
+            textures.len() - 1
+        }
+        None => return None,
+    };
+    Some(slot as u32)
+}
+
+/// How one copy of a glyph is drawn: the text itself or its shadow.
+struct GlyphPaint {
+    /// Local offset from the laid-out position.
+    offset: glam::Vec2,
+    color: [f32; 4],
+    /// Outline width in local units, and its color.
+    outline: (f32, [f32; 4]),
+    /// Edge blur in local units.
+    softness: f32,
+}
+
+/// A glyph quad in world space: sprite vertices for bitmap fonts, or a
+/// distance-field quad carrying the outline and softness, converted into
+/// distance units at the glyph's size.
+fn glyph_primitive(model: &glam::Mat4, glyph: &TextGlyph, paint: &GlyphPaint) -> CollectedPrimitive {
+    let (min, max) = (glyph.min + paint.offset, glyph.max + paint.offset);
+    let corners = [
+        (min.x, min.y, glyph.uv_min.x, glyph.uv_max.y), // bottom-left
+        (max.x, min.y, glyph.uv_max.x, glyph.uv_max.y), // bottom-right
+        (max.x, max.y, glyph.uv_max.x, glyph.uv_min.y), // top-right
+        (min.x, max.y, glyph.uv_min.x, glyph.uv_min.y), // top-left
+    ];
+    let position = |x, y| model.transform_point3(glam::Vec3::new(x, y, 0.0)).to_array();
+    let geometry = match glyph.distance_field {
+        None => Geometry::Mesh {
+            vertices: corners
+                .map(|(x, y, u, v)| SpriteVertex {
+                    position: position(x, y),
+                    uv: [u, v],
+                    color: paint.color,
+                    texture_index: 0,
+                })
+                .to_vec(),
+            indices: vec![0, 1, 2, 0, 2, 3],
+        },
+        Some(field) => {
+            // Local units per unit of stored distance.
+            let units = field.range * glyph.scale;
+            let params = [
+                field.range,
+                paint.outline.0 / units,
+                paint.softness / units,
+                if field.multi_channel { 1.0 } else { 0.0 },
+            ];
+            Geometry::Sdf(
+                corners
+                    .map(|(x, y, u, v)| SdfVertex {
+                        position: position(x, y),
+                        uv: [u, v],
+                        color: paint.color,
+                        outline_color: paint.outline.1,
+                        params,
+                    })
+                    .to_vec(),
+            )
+        }
+    };
+    CollectedPrimitive {
+        z: model.col(3).z,
+        texture: glyph.atlas,
+        geometry,
+    }
+}
+
+/// Whether `local` (a primitive's bounds before `model`) reaches into the
+/// camera's `view`. Everything is visible without a view.
+fn in_view(view: Option<Rect>, model: &glam::Mat4, local: Rect) -> bool {
+    view.is_none_or(|view| overlaps(view, transformed_bounds(model, local)))
+}
+
+/// Move tile pieces of a centered quad by the anchor `offset`.
+fn anchored_pieces(mut pieces: Vec<TilePiece>, offset: glam::Vec2) -> Vec<TilePiece> {
+    for piece in &mut pieces {
+        piece.local.min += offset;
+        piece.local.max += offset;
+    }
+    pieces
+}
+
+/// The four vertices of one sprite piece, transformed to world space.
+/// `rect` is the UV rectangle of one whole tile.
+fn piece_vertices(
+    model: &glam::Mat4,
+    flip: glam::Vec2,
+    piece: &TilePiece,
+    rect: Rect,
+    color: [f32; 4],
+) -> [SpriteVertex; 4] {
+    let (l, t) = (piece.local, piece.uv);
+    // Texture V runs top-down while local Y runs bottom-up.
+    let u = |f: f32| rect.min.x + f * (rect.max.x - rect.min.x);
+    let v = |f: f32| rect.max.y - f * (rect.max.y - rect.min.y);
+    let corners = [
+        (glam::Vec2::new(l.min.x, l.min.y), [u(t.min.x), v(t.min.y)]), // bottom-left
+        (glam::Vec2::new(l.max.x, l.min.y), [u(t.max.x), v(t.min.y)]), // bottom-right
+        (glam::Vec2::new(l.max.x, l.max.y), [u(t.max.x), v(t.max.y)]), // top-right
+        (glam::Vec2::new(l.min.x, l.max.y), [u(t.min.x), v(t.max.y)]), // top-left
+    ];
+    corners.map(|(corner, uv)| {
+        let corner = corner * flip;
+        let world_pos = model.transform_point3(glam::Vec3::new(corner.x, corner.y, 0.0));
+        SpriteVertex {
+            position: [world_pos.x, world_pos.y, world_pos.z],
+            uv,
+            color,
+            texture_index: 0,
+        }
+    })
+}
+
+/// The same piece as one instance: the model matrix maps the unit quad
+/// `-0.5..0.5` onto the piece, the shader does the rest.
+fn piece_instance(
+    model: &glam::Mat4,
+    flip: glam::Vec2,
+    piece: &TilePiece,
+    rect: Rect,
+    color: [f32; 4],
+) -> SpriteInstance {
+    let (l, t) = (piece.local, piece.uv);
+    let u = |f: f32| rect.min.x + f * (rect.max.x - rect.min.x);
+    let v = |f: f32| rect.max.y - f * (rect.max.y - rect.min.y);
+    let center = (l.min + l.max) * 0.5;
+    let local = glam::Mat4::from_scale(flip.extend(1.0))
+        * glam::Mat4::from_translation(center.extend(0.0))
+        * glam::Mat4::from_scale((l.max - l.min).extend(1.0));
+    SpriteInstance {
+        model: (*model * local).to_cols_array_2d(),
+        uv_rect: [u(t.min.x), v(t.max.y), u(t.max.x), v(t.min.y)],
+        color,
+        texture_index: 0,
+    }
 }
 
-/// Compute the camera view-projection matrix from the Camera2d entity.
-fn compute_camera_vp(world: &mut World, surface_size: (u32, u32)) -> glam::Mat4 {
+/// Compute the camera view-projection matrix from the Camera2d's global
+/// transform (identity without a camera).
+pub(crate) fn compute_camera_vp(camera: Option<glam::Mat4>, surface_size: (u32, u32)) -> glam::Mat4 {
     let (width, height) = surface_size;
     let half_w = width as f32 / 2.0;
     let half_h = height as f32 / 2.0;
@@ -299,11 +876,205 @@ fn compute_camera_vp(world: &mut World, surface_size: (u32, u32)) -> glam::Mat4
     let projection = glam::Mat4::orthographic_rh(-half_w, half_w, -half_h, half_h, -1000.0, 1000.0);
 
     // Camera global transform (inverse = view matrix)
-    let mut camera_matrix = glam::Mat4::IDENTITY;
-    world.query_single::<(&GlobalTransform,), Camera2d>(|_entity, (gt,)| {
-        camera_matrix = gt.matrix;
-    });
-
-    let view = camera_matrix.inverse();
+    let view = camera.unwrap_or(glam::Mat4::IDENTITY).inverse();
     projection * view
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render2d::SpriteAnchor;
+    use crate::math::Vec2;
+    use bytemuck::Zeroable;
+    use std::collections::HashMap;
+
+    /// No atlas pages: untextured primitives keep the white default.
+    fn no_pages() -> WhiteSpots {
+        WhiteSpots {
+            default: TextureHandle(0),
+            pages: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn instance_covers_the_same_quad_as_vertices() {
+        let model = glam::Mat4::from_scale_rotation_translation(
+            glam::Vec3::new(2.0, 1.0, 1.0),
+            glam::Quat::from_rotation_z(0.5),
+            glam::Vec3::new(10.0, -4.0, 3.0),
+        );
+        let flip = Vec2::new(-1.0, 1.0);
+        let piece = tile_pieces(Vec2::new(30.0, 20.0), Vec2::new(20.0, 20.0), Vec2::ZERO)[1];
+        let rect = Rect::from_pixels(16.0, 0.0, 16.0, 16.0, 64.0, 64.0);
+        let color = [1.0, 0.5, 0.25, 1.0];
+
+        let vertices = piece_vertices(&model, flip, &piece, rect, color);
+        let instance = piece_instance(&model, flip, &piece, rect, color);
+
+        // What vs_instanced computes for each corner, in vertex order
+        // (bottom-left, bottom-right, top-right, top-left).
+        let instance_model = glam::Mat4::from_cols_array_2d(&instance.model);
+        let [u0, v0, u1, v1] = instance.uv_rect;
+        for (vertex, f) in vertices.iter().zip([(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]) {
+            let corner = glam::Vec3::new(f.0 - 0.5, f.1 - 0.5, 0.0);
+            let position = instance_model.transform_point3(corner);
+            assert!(position.distance(glam::Vec3::from(vertex.position)) < 1e-4);
+            let uv = [u0 + (u1 - u0) * f.0, v1 + (v0 - v1) * f.1];
+            assert!((uv[0] - vertex.uv[0]).abs() < 1e-6 && (uv[1] - vertex.uv[1]).abs() < 1e-6);
+        }
+        assert_eq!(instance.color, color);
+    }
+
+    #[test]
+    fn anchored_sprite_stands_on_its_origin_when_flipped() {
+        let model = glam::Mat4::from_translation(glam::Vec3::new(100.0, 50.0, 0.0));
+        let size = Vec2::new(20.0, 40.0);
+        let feet = SpriteAnchor::Custom(Vec2::new(0.25, 0.0));
+        let pieces = anchored_pieces(tile_pieces(size, size, Vec2::ZERO), feet.offset(size));
+        let bounds = |flip: Vec2| {
+            let vertices = piece_vertices(&model, flip, &pieces[0], Rect::FULL, [1.0; 4]);
+            let corners = vertices.map(|v| Vec2::new(v.position[0], v.position[1]));
+            let min = corners.iter().fold(Vec2::MAX, |acc, &c| acc.min(c));
+            let max = corners.iter().fold(Vec2::MIN, |acc, &c| acc.max(c));
+            (min, max)
+        };
+
+        assert_eq!(bounds(Vec2::ONE), (Vec2::new(95.0, 50.0), Vec2::new(115.0, 90.0)));
+        // Facing left mirrors around the feet, which stay put.
+        assert_eq!(bounds(Vec2::new(-1.0, 1.0)), (Vec2::new(85.0, 50.0), Vec2::new(105.0, 90.0)));
+        assert_eq!(SpriteAnchor::Center.offset(size), Vec2::ZERO);
+        assert_eq!(SpriteAnchor::TopLeft.offset(size), Vec2::new(10.0, -20.0));
+    }
+
+    #[test]
+    fn culling_follows_camera_zoom_and_rotation() {
+        // Zoomed out 2× and turned 45°, looking at (1000, 0).
+        let camera = glam::Mat4::from_scale_rotation_translation(
+            glam::Vec3::new(2.0, 2.0, 1.0),
+            glam::Quat::from_rotation_z(45f32.to_radians()),
+            glam::Vec3::new(1000.0, 0.0, 0.0),
+        );
+        let view = camera_view(Some(camera), Some((800, 600)));
+        let quad = Rect { min: Vec2::splat(-32.0), max: Vec2::splat(32.0) };
+        let at = |x, y| glam::Mat4::from_translation(glam::Vec3::new(x, y, 0.0));
+
+        assert!(in_view(view, &at(1000.0, 0.0), quad));
+        // Past the unzoomed screen edge, inside the zoomed one.
+        assert!(in_view(view, &at(1900.0, 0.0), quad));
+        assert!(!in_view(view, &at(-500.0, 0.0), quad));
+        assert!(!in_view(view, &at(1000.0, 5000.0), quad));
+        // A huge sprite centered off-screen still reaches in.
+        assert!(in_view(view, &(at(-500.0, 0.0) * glam::Mat4::from_scale(glam::Vec3::splat(20.0))), quad));
+        assert!(in_view(None, &at(-1e6, 0.0), quad));
+    }
+
+    #[test]
+    fn texture_arrays_merge_batches_across_textures() {
+        let quad = |texture| CollectedPrimitive {
+            z: 0.0,
+            texture: TextureHandle(texture),
+            geometry: Geometry::Mesh {
+                vertices: vec![SpriteVertex::zeroed(); 4],
+                indices: vec![0, 1, 2, 0, 2, 3],
+            },
+        };
+        let instance = |texture| CollectedPrimitive {
+            z: 0.0,
+            texture: TextureHandle(texture),
+            geometry: Geometry::Instances(vec![SpriteInstance::zeroed()]),
+        };
+        let scene = [quad(1), quad(2), quad(1), quad(3), instance(3), instance(1)];
+
+        // One slot: a new batch at every texture switch.
+        let single = emit_batches(&scene, 1, &no_pages());
+        assert_eq!(single.batches.len(), 6);
+        assert!(single.vertices.iter().all(|v| v.texture_index == 0));
+
+        // Two slots: 1 and 2 share a batch, 3 needs a new one. Instances
+        // batch separately and start their own table.
+        let arrays = emit_batches(&scene, 2, &no_pages());
+        let tables: Vec<Vec<usize>> =
+            arrays.batches.iter().map(|b| b.textures.iter().map(|t| t.0).collect()).collect();
+        assert_eq!(tables, [vec![1, 2], vec![3], vec![3, 1]]);
+        let counts: Vec<u32> = arrays.batches.iter().map(|b| b.count).collect();
+        assert_eq!(counts, [18, 6, 2]);
+        let per_quad: Vec<u32> = arrays.vertices.chunks(4).map(|quad| quad[0].texture_index).collect();
+        assert_eq!(per_quad, [0, 1, 0, 0]);
+        let per_instance: Vec<u32> = arrays.instances.iter().map(|i| i.texture_index).collect();
+        assert_eq!(per_instance, [0, 1]);
+        assert_eq!((arrays.batches[2].start, arrays.batches[1].start), (0, 18));
+    }
+
+    #[test]
+    fn untextured_primitives_draw_from_the_page_white_block() {
+        let quad = |texture| CollectedPrimitive {
+            z: 0.0,
+            texture: TextureHandle(texture),
+            geometry: Geometry::Mesh {
+                vertices: vec![SpriteVertex::zeroed(); 4],
+                indices: vec![0, 1, 2, 0, 2, 3],
+            },
+        };
+        let instance = |texture| CollectedPrimitive {
+            z: 0.0,
+            texture: TextureHandle(texture),
+            geometry: Geometry::Instances(vec![SpriteInstance::zeroed()]),
+        };
+        let white = WhiteSpots {
+            pages: HashMap::from([(TextureHandle(5), Vec2::new(0.25, 0.5))]),
+            ..no_pages()
+        };
+        // A shape before any page keeps the default; between page sprites
+        // it joins their batch, even with one texture per batch.
+        let scene = [quad(0), quad(5), quad(0), quad(5), instance(5), instance(0)];
+        let frame = emit_batches(&scene, 1, &white);
+        let tables: Vec<Vec<usize>> =
+            frame.batches.iter().map(|b| b.textures.iter().map(|t| t.0).collect()).collect();
+        assert_eq!(tables, [vec![0], vec![5], vec![5]]);
+        assert_eq!(frame.vertices[0].uv, [0.0, 0.0]);
+        assert_eq!(frame.vertices[8].uv, [0.25, 0.5]);
+        assert_eq!(frame.instances[1].uv_rect, [0.25, 0.5, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn sdf_glyphs_batch_apart_with_shadows_underneath() {
+        use super::super::font::DistanceField;
+
+        let glyph = |distance_field, x| TextGlyph {
+            atlas: TextureHandle(4),
+            min: Vec2::new(x, 0.0),
+            max: Vec2::new(x + 10.0, 10.0),
+            uv_min: Vec2::ZERO,
+            uv_max: Vec2::ONE,
+            color: [1.0; 4],
+            scale: 2.0,
+            distance_field,
+        };
+        let field = Some(DistanceField {
+            range: 4.0,
+            multi_channel: false,
+        });
+        let shadow = GlyphPaint {
+            offset: Vec2::new(1.0, -1.0),
+            color: [0.0, 0.0, 0.0, 0.5],
+            outline: (2.0, [0.0, 0.0, 0.0, 0.5]),
+            softness: 4.0,
+        };
+        let model = glam::Mat4::IDENTITY;
+        let prims = [
+            glyph_primitive(&model, &glyph(field, 0.0), &shadow),
+            glyph_primitive(&model, &glyph(field, 10.0), &shadow),
+            glyph_primitive(&model, &glyph(None, 0.0), &shadow),
+        ];
+        let frame = emit_batches(&prims, 16, &no_pages());
+        let kinds: Vec<BatchKind> = frame.batches.iter().map(|b| b.kind).collect();
+        assert_eq!(kinds, [BatchKind::Sdf, BatchKind::Indexed]);
+        assert_eq!((frame.batches[0].count, frame.sdf_indices[6]), (12, 4));
+
+        // Widths in local units become distance units: range 4 at scale 2.
+        let first = frame.sdf_vertices[0];
+        assert_eq!(first.params, [4.0, 0.25, 0.5, 0.0]);
+        assert_eq!(first.position, [1.0, -1.0, 0.0]);
+        assert_eq!(frame.vertices[0].color, shadow.color);
+    }
+}
@@ -7,9 +7,12 @@
 use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 
+use crate::ecs::World;
 use crate::math::Vec2;
-use crate::physics2d::ColliderShape2d;
+use crate::physics2d::{Collider2d, ColliderShape2d};
 use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, multisample_state};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
 
 use super::pipeline::SpriteRenderer;
 
@@ -17,7 +20,7 @@ use super::pipeline::SpriteRenderer;
 
 /// Insert this resource to enable debug collider wireframes in 2D.
 /// Toggle `enabled` at runtime (e.g. with F1).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DebugColliders2d {
     pub enabled: bool,
     pub color: [f32; 4],
@@ -32,6 +35,30 @@ impl Default for DebugColliders2d {
     }
 }
 
+// ── Extract ─────────────────────────────────────────────────────────────
+
+/// The debug settings and every visible collider's pose, copied out by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct ExtractedColliders2d {
+    pub config: DebugColliders2d,
+    pub poses: Vec<(Vec2, f32, ColliderShape2d)>,
+}
+
+/// Copy the colliders to outline, or `None` without a [`DebugColliders2d`]
+/// resource.
+pub(crate) fn extract_colliders_2d(world: &mut World) -> Option<ExtractedColliders2d> {
+    let config = world.get_resource::<DebugColliders2d>()?.clone();
+    let mut poses = Vec::new();
+    world.query::<(&Collider2d, &crate::math::Transform, Option<&ComputedVisibility>)>(|_entity, (coll, tf, vis)| {
+        if is_hidden(vis) {
+            return;
+        }
+        let (angle, _y, _x) = tf.rotation.to_euler(glam::EulerRot::ZYX);
+        poses.push((Vec2::new(tf.translation.x, tf.translation.y), angle, coll.shape));
+    });
+    Some(ExtractedColliders2d { config, poses })
+}
+
 // ── Vertex ──────────────────────────────────────────────────────────────
 
 #[repr(C)]
@@ -56,6 +83,8 @@ impl DebugVertex2d {
 
 pub(crate) struct DebugWireframeRenderer2d {
     pipeline: wgpu::RenderPipeline,
+    /// Sample count the pipeline was built for.
+    pub samples: u32,
     color_buffer: wgpu::Buffer,
     color_bind_group: wgpu::BindGroup,
 }
@@ -65,6 +94,7 @@ impl DebugWireframeRenderer2d {
         device: &wgpu::Device,
         surface_format: wgpu::TextureFormat,
         camera_bind_group_layout: &wgpu::BindGroupLayout,
+        samples: u32,
     ) -> Self {
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("2d debug wireframe shader"),
@@ -121,7 +151,7 @@ impl DebugWireframeRenderer2d {
                 conservative: false,
             },
             depth_stencil: None, // 2D has no depth buffer
-            multisample: wgpu::MultisampleState::default(),
+            multisample: multisample_state(samples),
             multiview: None,
             cache: None,
         });
@@ -143,6 +173,7 @@ impl DebugWireframeRenderer2d {
 
         Self {
             pipeline,
+            samples,
             color_buffer,
             color_bind_group,
         }
@@ -249,7 +280,7 @@ const CIRCLE_SEGMENTS: u32 = 32;
 
 pub(crate) fn render_debug_wireframes_2d(
     encoder: &mut wgpu::CommandEncoder,
-    view: &wgpu::TextureView,
+    target: &ColorTarget,
     gpu: &GpuContext,
     renderer: &SpriteRenderer,
     debug_renderer: &mut DebugWireframeRenderer2d,
@@ -299,15 +330,7 @@ pub(crate) fn render_debug_wireframes_2d(
     {
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("2d debug wireframe pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
+            color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
@@ -8,7 +8,7 @@
 //! ## Per-Frame Flow
 //!
 //! ```text
-//! render_sprites_2d(world)
+//! render_sprites_2d(world, scene)    scene = Extracted2d (extract phase)
 //!   │
 //!   ├─ 1. Lazy init ─── first frame only
 //!   │     Create SpriteRenderer (pipeline, camera buffer, sampler)
@@ -19,18 +19,22 @@
 //!   │     TextureStore from World (we need &mut and & simultaneously)
 //!   │
 //!   ├─ 3. Collect & batch ─── calls batch::collect_and_batch()
-//!   │     Query sprites, emit quads, Z-sort, group by texture
-//!   │     Returns (vertices, indices, batches, view_proj)
+//!   │     Take extracted sprites, emit quads (or instances), Z-sort,
+//!   │     group by texture (several per batch with texture arrays).
+//!   │     Returns a BatchedFrame
 //!   │
 //!   ├─ 4. Upload to GPU
 //!   │     Write camera uniform to buffer
 //!   │     Create fresh vertex + index buffers with frame's data
+//!   │     Write instances into the reused instance buffer
 //!   │
 //!   ├─ 5. Render pass
 //!   │     Acquire surface texture
-//!   │     Clear with ClearColor
+//!   │     Clear with ClearColor (or the camera's CameraClear)
 //!   │     Bind pipeline + camera
-//!   │     For each batch: bind texture, draw_indexed(range)
+//!   │     For each batch: bind texture (or texture array),
+//!   │     draw_indexed(range) — or, for instanced batches, the
+//!   │     instanced pipeline and draw(0..6, range)
 //!   │     Submit command buffer, present
 //!   │
 //!   └─ 6. Reinsert resources ─── put GpuContext, SpriteRenderer,
@@ -41,15 +45,20 @@
 //!
 //! The core challenge is Rust's borrow rules: we need mutable access to the
 //! `SpriteRenderer` (to update its buffers), shared access to `GpuContext`
-//! (for the device and queue), *and* mutable access to `World` (to query
-//! entities). All three live inside `World` as resources.
+//! (for the device and queue), *and* mutable access to `World` (for the
+//! other renderers it creates on first use). All three live inside `World`
+//! as resources.
 //!
 //! The solution is to temporarily *remove* resources from the world with
 //! `resource_remove()`, which returns owned values. With the resources out,
-//! the world is free to be queried. After the frame, everything is reinserted.
+//! the world is free to be used. After the frame, everything is reinserted.
 //! This is safe, simple, and avoids `RefCell`/`Mutex` overhead — the only cost
 //! is three HashMap removes and inserts per frame.
 //!
+//! Entities are never queried here — the scene was copied out by the
+//! extract phase — so with [pipelined rendering](crate::render::pipelined)
+//! the world is the render thread's, which holds resources only.
+//!
 //! ## Surface Errors
 //!
 //! `gpu.surface.get_current_texture()` can fail with:
@@ -65,14 +74,36 @@
 
 use wgpu::util::DeviceExt;
 
-use super::batch::collect_and_batch;
+use super::batch::{collect_and_batch, BatchKind, BatchedFrame, Extracted2d};
 use super::font::FontStore;
-use super::pipeline::SpriteRenderer;
+use super::pipeline::{SpriteRenderer, TEXTURE_ARRAY_SLOTS};
 use super::texture::TextureStore;
 use super::vertex::CameraUniform;
 use crate::asset::{AssetKind, AssetServer};
 use crate::ecs::World;
-use crate::render::pass::{ClearColor, FrameContext};
+use crate::render::pass::{camera_load_op, FrameContext};
+use crate::render::GpuContext;
+
+/// Lazy init: create the SpriteRenderer and TextureStore the first time
+/// anything 2D (the scene or the [UI](crate::ui)) is drawn.
+pub(crate) fn ensure_sprite_renderer(world: &mut World, gpu: &GpuContext) {
+    if world.has_resource::<SpriteRenderer>() {
+        return;
+    }
+    let renderer = SpriteRenderer::new(gpu);
+    let texture_store = TextureStore::new(gpu, &renderer);
+
+    // Register shader file for hot-reload watching.
+    let shader_path = renderer.shader_path.clone();
+    world.insert_resource(renderer);
+    world.insert_resource(texture_store);
+
+    if let Some(path) = shader_path
+        && let Some(server) = world.get_resource_mut::<AssetServer>()
+    {
+        server.watch(path, AssetKind::Shader2d);
+    }
+}
 
 /// Render all 2D sprites for the current frame.
 ///
@@ -82,39 +113,40 @@ use crate::render::pass::{ClearColor, FrameContext};
 /// 3. Sprite collection, sorting, batching
 /// 4. GPU buffer upload
 /// 5. Render pass with draw calls
-pub(crate) fn render_sprites_2d(world: &mut World, frame: &mut FrameContext<'_>) {
+pub(crate) fn render_sprites_2d(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+    scene: &Extracted2d,
+) {
     let gpu = frame.gpu;
-
-    // Lazy init: create SpriteRenderer and TextureStore on first call
-    if !world.has_resource::<SpriteRenderer>() {
-        let renderer = SpriteRenderer::new(gpu);
-        let texture_store = TextureStore::new(gpu, &renderer);
-
-        // Register shader file for hot-reload watching.
-        let shader_path = renderer.shader_path.clone();
-        world.insert_resource(renderer);
-        world.insert_resource(texture_store);
-
-        if let Some(path) = shader_path {
-            if let Some(server) = world.get_resource_mut::<AssetServer>() {
-                server.watch(path, AssetKind::Shader2d);
-            }
-        }
-    }
+    ensure_sprite_renderer(world, gpu);
 
     // Extract resources to avoid borrow conflicts
     let mut renderer = world
         .resource_remove::<SpriteRenderer>()
         .expect("SpriteRenderer missing");
-    let texture_store = world
+    let mut texture_store = world
         .resource_remove::<TextureStore>()
         .expect("TextureStore missing");
     let font_store = world.resource_remove::<FontStore>();
 
-    // Collect and batch sprites + text (world is free to query now)
+    // Collect and batch sprites + text
     let surface_size = gpu.surface_size();
-    let (vertices, indices, batches, view_proj) =
-        collect_and_batch(world, &texture_store, font_store.as_ref(), surface_size);
+    let texture_slots = match renderer.texture_arrays {
+        Some(_) => TEXTURE_ARRAY_SLOTS as usize,
+        None => 1,
+    };
+    #[cfg_attr(not(feature = "diagnostics"), allow(unused_variables))]
+    let BatchedFrame {
+        vertices,
+        indices,
+        instances,
+        sdf_vertices,
+        sdf_indices,
+        batches,
+        view_proj,
+        culled,
+    } = collect_and_batch(scene, &texture_store, font_store.as_ref(), surface_size, texture_slots);
 
     // Update camera uniform
     let camera_uniform = CameraUniform {
@@ -142,103 +174,168 @@ pub(crate) fn render_sprites_2d(world: &mut World, frame: &mut FrameContext<'_>)
         renderer.index_buffer = None;
     }
 
-    // Get clear color
-    let clear_color = world
-        .get_resource::<ClearColor>()
-        .copied()
-        .unwrap_or_default();
+    // Distance-field glyphs have buffers of their own.
+    if !sdf_vertices.is_empty() {
+        renderer.sdf_vertex_buffer = Some(gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("sdf text vertex buffer"),
+            contents: bytemuck::cast_slice(&sdf_vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        }));
+        renderer.sdf_index_buffer = Some(gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("sdf text index buffer"),
+            contents: bytemuck::cast_slice(&sdf_indices),
+            usage: wgpu::BufferUsages::INDEX,
+        }));
+    } else {
+        renderer.sdf_vertex_buffer = None;
+        renderer.sdf_index_buffer = None;
+    }
+
+    // Instances go into a buffer kept across frames, regrown to the next
+    // power of two when too small.
+    if !instances.is_empty() {
+        let bytes: &[u8] = bytemuck::cast_slice(&instances);
+        let too_small = renderer
+            .instance_buffer
+            .as_ref()
+            .is_none_or(|buffer| buffer.size() < bytes.len() as u64);
+        if too_small {
+            renderer.instance_buffer = Some(gpu.device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("sprite instance buffer"),
+                size: (bytes.len() as u64).next_power_of_two(),
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }));
+        }
+        if let Some(buffer) = &renderer.instance_buffer {
+            gpu.queue.write_buffer(buffer, 0, bytes);
+        }
+    }
+
+    // Clear color (or load) for the active camera
+    let load = camera_load_op(scene.clear, world);
+    renderer.prepare_samples(gpu, frame.samples);
+    let target = frame.target();
+
+    // With texture arrays, each batch binds its own table of textures;
+    // distance-field text binds its one atlas.
+    let array_bind_groups: Vec<Option<wgpu::BindGroup>> = match &renderer.texture_arrays {
+        Some(arrays) => batches
+            .iter()
+            .map(|batch| {
+                (batch.kind != BatchKind::Sdf)
+                    .then(|| texture_store.texture_array_bind_group(gpu, &arrays.layout, &batch.textures))
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    let (pipeline, instanced_pipeline) = renderer
+        .texture_array_pipelines(frame.samples)
+        .unwrap_or_else(|| renderer.pipelines(frame.samples));
+    let sdf_pipeline = renderer.sdf_pipeline(frame.samples);
 
     {
         let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("sprite render pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &frame.view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: clear_color.0[0],
-                        g: clear_color.0[1],
-                        b: clear_color.0[2],
-                        a: clear_color.0[3],
-                    }),
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
+            color_attachments: &[Some(target.attachment(load))],
             depth_stencil_attachment: None,
             timestamp_writes: None,
             occlusion_query_set: None,
         });
 
-        if let (Some(vb), Some(ib)) = (&renderer.vertex_buffer, &renderer.index_buffer) {
-            render_pass.set_pipeline(&renderer.pipeline);
-            render_pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, vb.slice(..));
-            render_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
-
-            for batch in &batches {
-                let entry = texture_store.get(batch.texture);
-                render_pass.set_bind_group(1, &entry.bind_group, &[]);
-                render_pass.draw_indexed(
-                    batch.index_start..(batch.index_start + batch.index_count),
-                    0,
-                    0..1,
-                );
+        render_pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
+
+        // Switch pipeline and vertex buffer only when the batch kind changes.
+        let mut bound_kind = None;
+        for (i, batch) in batches.iter().enumerate() {
+            if bound_kind != Some(batch.kind) {
+                match batch.kind {
+                    BatchKind::Indexed => {
+                        let (Some(vb), Some(ib)) = (&renderer.vertex_buffer, &renderer.index_buffer)
+                        else {
+                            continue;
+                        };
+                        render_pass.set_pipeline(pipeline);
+                        render_pass.set_vertex_buffer(0, vb.slice(..));
+                        render_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                    }
+                    BatchKind::Instanced => {
+                        let Some(instance_buffer) = &renderer.instance_buffer else {
+                            continue;
+                        };
+                        render_pass.set_pipeline(instanced_pipeline);
+                        render_pass.set_vertex_buffer(0, instance_buffer.slice(..));
+                    }
+                    BatchKind::Sdf => {
+                        let (Some(pipeline), Some(vb), Some(ib)) =
+                            (sdf_pipeline, &renderer.sdf_vertex_buffer, &renderer.sdf_index_buffer)
+                        else {
+                            continue;
+                        };
+                        render_pass.set_pipeline(pipeline);
+                        render_pass.set_vertex_buffer(0, vb.slice(..));
+                        render_pass.set_index_buffer(ib.slice(..), wgpu::IndexFormat::Uint32);
+                    }
+                }
+                bound_kind = Some(batch.kind);
+            }
+
+            if frame.debug_markers {
+                let textures: Vec<String> = batch.textures.iter().map(|t| t.0.to_string()).collect();
+                render_pass.insert_debug_marker(&format!("textures {}", textures.join(", ")));
+            }
+            match array_bind_groups.get(i).and_then(Option::as_ref) {
+                Some(bind_group) => render_pass.set_bind_group(1, bind_group, &[]),
+                None => render_pass.set_bind_group(1, &texture_store.get(batch.textures[0]).bind_group, &[]),
+            }
+            let range = batch.start..(batch.start + batch.count);
+            match batch.kind {
+                BatchKind::Indexed | BatchKind::Sdf => render_pass.draw_indexed(range, 0, 0..1),
+                BatchKind::Instanced => render_pass.draw(0..6, range),
             }
         }
     }
 
     // ── Debug wireframes ──────────────────────────────────────────────
     #[cfg(feature = "physics2d")]
-    {
-        use super::debug_wireframe::{DebugColliders2d, DebugWireframeRenderer2d, render_debug_wireframes_2d};
-        use crate::physics2d::Collider2d;
-
-        if world.has_resource::<DebugColliders2d>() {
-            // Lazy-init the debug renderer
-            if !world.has_resource::<DebugWireframeRenderer2d>() {
-                let dbg_renderer = DebugWireframeRenderer2d::new(
-                    &gpu.device,
-                    gpu.surface_format(),
-                    &renderer.camera_bind_group_layout,
-                );
-                world.insert_resource(dbg_renderer);
-            }
+    if let Some(colliders) = &scene.colliders {
+        use super::debug_wireframe::{DebugWireframeRenderer2d, render_debug_wireframes_2d};
 
-            // Collect collider poses from ECS components directly
-            let mut poses = Vec::new();
-            world.query::<(&Collider2d, &crate::math::Transform)>(|_entity, (coll, tf)| {
-                let angle = {
-                    let (z, _y, _x) = tf.rotation.to_euler(glam::EulerRot::ZYX);
-                    z
-                };
-                poses.push((glam::Vec2::new(tf.translation.x, tf.translation.y), angle, coll.shape));
-            });
-
-            if let Some(mut dbg_renderer) = world.resource_remove::<DebugWireframeRenderer2d>() {
-                if let Some(debug_config) = world.resource_remove::<DebugColliders2d>() {
-                    render_debug_wireframes_2d(
-                        &mut frame.encoder,
-                        &frame.view,
-                        gpu,
-                        &renderer,
-                        &mut dbg_renderer,
-                        &debug_config,
-                        &poses,
-                    );
-                    world.insert_resource(debug_config);
-                }
-                world.insert_resource(dbg_renderer);
-            }
+        // Lazy-init the debug renderer, rebuilding it if MSAA changed
+        if world
+            .get_resource::<DebugWireframeRenderer2d>()
+            .is_none_or(|r| r.samples != frame.samples)
+        {
+            let dbg_renderer = DebugWireframeRenderer2d::new(
+                &gpu.device,
+                gpu.surface_format(),
+                &renderer.camera_bind_group_layout,
+                frame.samples,
+            );
+            world.insert_resource(dbg_renderer);
         }
+
+        let dbg_renderer = world.resource_mut::<DebugWireframeRenderer2d>();
+        let target = frame.target();
+        render_debug_wireframes_2d(
+            &mut frame.encoder,
+            &target,
+            gpu,
+            &renderer,
+            dbg_renderer,
+            &colliders.config,
+            &colliders.poses,
+        );
     }
 
     // Update diagnostics render stats.
     #[cfg(feature = "diagnostics")]
     if let Some(stats) = world.get_resource_mut::<crate::diag::RenderStats>() {
         stats.draw_calls = batches.len() as u32;
-        stats.vertices = vertices.len() as u32;
+        stats.vertices = (vertices.len() + sdf_vertices.len() + instances.len() * 4) as u32;
         stats.textures_loaded = texture_store.entries.len() as u32;
+        stats.sprites_submitted = (scene.sprites.len() + scene.shapes.len()) as u32 - culled;
+        stats.sprites_culled = culled;
     }
 
     // Reinsert resources
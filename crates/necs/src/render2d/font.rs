@@ -13,6 +13,24 @@
 //! any other texture, so glyph quads flow through the same batching pipeline as
 //! sprites.
 //!
+//! Every other character is rasterized the first time a [`Text`],
+//! [`UiText`](crate::ui::UiText) or label uses it. The font keeps its
+//! rasterizer and a CPU copy of the atlas for this; before each frame is
+//! drawn, new glyphs are packed after the existing ones and changed atlases
+//! re-uploaded. A full atlas doubles in size (up to 4096×4096), keeping the
+//! glyphs already placed where they are:
+//!
+//! ```text
+//!   512×512, full            1024×1024
+//!   ┌────────┐               ┌────────┬────────┐
+//!   │ABCDEFGH│               │ABCDEFGH│        │
+//!   │IJKLMNOP│      ──▶      │IJKLMNOP│ éüß…   │  new glyphs continue
+//!   │abcdefgh│               │abcdefgh│        │  the last row, then
+//!   └────────┘               ├────────┘        │  fill the new space
+//!                            │                 │
+//!                            └─────────────────┘
+//! ```
+//!
 //! ## Text Component
 //!
 //! A `Text` component paired with a `Transform` spawns one quad per visible
@@ -21,16 +39,75 @@
 //! buffers. All glyphs from the same font share a single atlas texture, so
 //! an entire text string is typically one draw call (or merged with adjacent
 //! sprites using the same atlas).
+//!
+//! ## Layout
+//!
+//! [`layout_text`] turns a `Text` into positioned glyphs:
+//!
+//! - **Rich text**: [`TextSpan`]s follow `content`, each with its own color,
+//!   size or font. Sizes scale the font's glyphs, so load a font near the
+//!   largest size it's drawn at.
+//! - **Wrapping**: with a `max_width`, lines break at the last space that
+//!   fits; a word longer than the whole width breaks between letters.
+//! - **Alignment**: [`TextAlign`] places each line inside the block and the
+//!   block around the entity's origin — left edge, center or right edge.
+//!   [`TextAnchor`] does the same vertically; the default keeps the first
+//!   line's baseline on the origin.
+//!
+//! ```text
+//!   TextAlign::Center, max_width 120, TextAnchor::Top
+//!
+//!          ┌──── 120 ────┐
+//!          │   The old   │   ← origin at the top center
+//!          │ door creaks │
+//!          │    open.    │
+//!          └─────────────┘
+//! ```
+//!
+//! ## Distance-Field Fonts
+//!
+//! Bitmap glyphs are drawn at the size they were rasterized at; zoomed in,
+//! they blur. Fonts from [`load_sdf_font`](super::sdf::load_sdf_font) and
+//! [`load_msdf_font`](super::sdf::load_msdf_font) store the distance to
+//! each glyph's edge instead and draw through their own shader, which keeps
+//! edges sharp at any scale and adds [`TextOutline`] and soft
+//! [`TextShadow`]s. See [`sdf`](super::sdf). Shadows work for bitmap fonts
+//! too, as hard offset copies of the text.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: TextMeshPro — SDF atlases built ahead of time or dynamically,
+//!   rich text through inline `<color>`/`<size>` tags, wrapping and
+//!   alignment on the component.
+//! - **Bevy**: `Text2d` with `TextSpan` children laid out by cosmic-text;
+//!   glyphs are cached per font and size in growable atlases.
+//! - **Godot**: `Label` for plain wrapped text, `RichTextLabel` with BBCode
+//!   for styled runs; dynamic fonts rasterize glyphs on demand.
+//! - **Our approach**: spans as a plain `Vec` on the component rather than
+//!   markup or child entities, greedy word wrap (no shaping or kerning), and
+//!   one on-demand atlas per loaded font — coverage bitmaps by default,
+//!   distance fields when asked for.
 
-use wgpu::util::DeviceExt;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
+use glam::Vec2;
+
+use crate::asset::AssetServer;
 use crate::ecs::World;
 use crate::render::GpuContext;
+use crate::render::pipelined::finish_render;
+use crate::render::sampler::SamplerSettings;
 
+use super::atlas::TextureAtlasing;
 use super::pipeline::SpriteRenderer;
 use super::texture::{TextureHandle, TextureStore};
 use super::Color;
 
+/// Where the baseline sits inside a line box, as a fraction of line height
+/// measured from the bottom. Keeps descenders inside the box.
+pub(crate) const BASELINE: f32 = 0.25;
+
 /// Handle to a loaded font in the [`FontStore`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct FontHandle(pub(crate) usize);
@@ -38,6 +115,15 @@ pub struct FontHandle(pub(crate) usize);
 /// A text component. Pair with [`Transform`](crate::math::Transform) to position.
 ///
 /// Each character is rendered as a textured quad using the font's glyph atlas.
+/// See the [module docs](self#layout) for wrapping, alignment and spans.
+///
+/// ```ignore
+/// let dialogue = Text::new("The old door ", font)
+///     .with_span(TextSpan::new("creaks").color(Color::RED).size(32.0))
+///     .with_span(TextSpan::new(" open."))
+///     .max_width(240.0)
+///     .align(TextAlign::Center);
+/// ```
 #[derive(Debug, Clone)]
 pub struct Text {
     /// The string to render.
@@ -46,6 +132,20 @@ pub struct Text {
     pub font: FontHandle,
     /// Tint color (multiplied with the white atlas glyphs).
     pub color: Color,
+    /// Runs drawn after `content`, each able to override the color, size
+    /// and font.
+    pub spans: Vec<TextSpan>,
+    /// Wrap lines to stay within this width, in local units (pixels at
+    /// scale 1). `None` only breaks at `\n`.
+    pub max_width: Option<f32>,
+    /// Horizontal alignment of the lines and the block.
+    pub align: TextAlign,
+    /// Vertical placement of the block around the origin.
+    pub anchor: TextAnchor,
+    /// Border around every glyph. Needs a distance-field font.
+    pub outline: Option<TextOutline>,
+    /// Copy of the text drawn behind it.
+    pub shadow: Option<TextShadow>,
 }
 
 impl Text {
@@ -55,6 +155,12 @@ impl Text {
             content: content.to_owned(),
             font,
             color: Color::WHITE,
+            spans: Vec::new(),
+            max_width: None,
+            align: TextAlign::Left,
+            anchor: TextAnchor::Baseline,
+            outline: None,
+            shadow: None,
         }
     }
 
@@ -63,6 +169,162 @@ impl Text {
         self.color = color;
         self
     }
+
+    /// Append a styled run (builder pattern).
+    pub fn with_span(mut self, span: TextSpan) -> Self {
+        self.spans.push(span);
+        self
+    }
+
+    /// Wrap lines at `width` (builder pattern).
+    pub fn max_width(mut self, width: f32) -> Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set the horizontal alignment (builder pattern).
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Set the vertical anchor (builder pattern).
+    pub fn anchor(mut self, anchor: TextAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Outline the glyphs (builder pattern).
+    pub fn outline(mut self, outline: TextOutline) -> Self {
+        self.outline = Some(outline);
+        self
+    }
+
+    /// Draw a shadow behind the text (builder pattern).
+    pub fn shadow(mut self, shadow: TextShadow) -> Self {
+        self.shadow = Some(shadow);
+        self
+    }
+
+    /// All the text, spans included.
+    pub fn full_text(&self) -> String {
+        let mut text = self.content.clone();
+        for span in &self.spans {
+            text.push_str(&span.content);
+        }
+        text
+    }
+}
+
+/// A run of [`Text`] with its own style. Unset fields inherit the `Text`'s.
+#[derive(Debug, Clone)]
+pub struct TextSpan {
+    pub content: String,
+    pub color: Option<Color>,
+    /// Pixel size, as passed to [`load_font`]; glyphs scale from the size
+    /// the font was loaded at.
+    pub size: Option<f32>,
+    pub font: Option<FontHandle>,
+}
+
+impl TextSpan {
+    /// A run styled like the rest of the text.
+    pub fn new(content: &str) -> Self {
+        Self {
+            content: content.to_owned(),
+            color: None,
+            size: None,
+            font: None,
+        }
+    }
+
+    /// Set the run's color (builder pattern).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Set the run's size in pixels (builder pattern).
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Draw the run in another font (builder pattern).
+    pub fn font(mut self, font: FontHandle) -> Self {
+        self.font = Some(font);
+        self
+    }
+}
+
+/// A border around [`Text`] glyphs, drawn by distance-field fonts only.
+#[derive(Debug, Clone, Copy)]
+pub struct TextOutline {
+    /// Thickness in local units (pixels at scale 1). At most half the
+    /// font's distance range shows; see [`sdf`](super::sdf).
+    pub width: f32,
+    pub color: Color,
+}
+
+impl TextOutline {
+    pub fn new(width: f32, color: Color) -> Self {
+        Self { width, color }
+    }
+}
+
+/// A copy of [`Text`] drawn behind it, outline included.
+#[derive(Debug, Clone, Copy)]
+pub struct TextShadow {
+    /// Offset from the text in local units; Y is up.
+    pub offset: Vec2,
+    pub color: Color,
+    /// Blur radius in local units. Distance-field fonts only; bitmap fonts
+    /// draw a hard shadow.
+    pub softness: f32,
+}
+
+impl TextShadow {
+    /// A hard shadow.
+    pub fn new(offset: Vec2, color: Color) -> Self {
+        Self {
+            offset,
+            color,
+            softness: 0.0,
+        }
+    }
+
+    /// Blur the shadow's edge (builder pattern).
+    pub fn softness(mut self, softness: f32) -> Self {
+        self.softness = softness;
+        self
+    }
+}
+
+/// Horizontal alignment of [`Text`]. Also decides which point of the block
+/// sits on the entity's origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAlign {
+    /// Lines start at the origin.
+    #[default]
+    Left,
+    /// Lines are centered on the origin.
+    Center,
+    /// Lines end at the origin.
+    Right,
+}
+
+/// Which point of a [`Text`] block sits on the entity's origin vertically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextAnchor {
+    /// The first line's baseline; later lines go down.
+    #[default]
+    Baseline,
+    /// The top of the first line.
+    Top,
+    /// The middle of the block.
+    Center,
+    /// The bottom of the last line.
+    Bottom,
 }
 
 /// Per-glyph metrics and UV coordinates in the atlas.
@@ -84,24 +346,69 @@ pub(crate) struct GlyphInfo {
     pub height: f32,
 }
 
+/// How a distance-field font's atlas encodes distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DistanceField {
+    /// Atlas pixels from fully outside (0) to fully inside (1); the edge
+    /// is at 0.5.
+    pub range: f32,
+    /// Distance is the median of RGB (MSDF) rather than alpha.
+    pub multi_channel: bool,
+}
+
 /// Internal entry for one loaded font.
 pub(crate) struct FontEntry {
     /// Glyph info indexed by `(char as u32 - 32)` for ASCII 32–126.
     pub glyphs: Vec<Option<GlyphInfo>>,
-    /// Atlas texture handle in the TextureStore.
+    /// Other characters, added by [`cache_glyphs`]. `None` marks characters
+    /// the font lacks or that didn't fit, so they aren't tried again.
+    pub extra_glyphs: HashMap<char, Option<GlyphInfo>>,
+    /// Atlas texture handle in the TextureStore. It may be packed onto a
+    /// shared page, so draw it through `TextureStore::draw_source`.
     pub atlas_handle: TextureHandle,
     /// Line height in pixels (for newline advancement).
     pub line_height: f32,
+    /// Rasterizer and atlas pixels; `None` until an async load finishes,
+    /// and for prebuilt atlases.
+    pub cache: Option<GlyphCache>,
+    /// `Some` for fonts drawn through the distance-field shader.
+    pub distance_field: Option<DistanceField>,
 }
 
 impl FontEntry {
-    /// Look up glyph info for a character. Returns `None` for unsupported chars.
+    /// Look up glyph info for a character. Returns `None` for unsupported
+    /// chars and ones not cached yet.
     pub fn glyph(&self, ch: char) -> Option<&GlyphInfo> {
         let idx = ch as u32;
-        if idx < 32 || idx > 126 {
-            return None;
+        if (32..=126).contains(&idx) {
+            self.glyphs[(idx - 32) as usize].as_ref()
+        } else {
+            self.extra_glyphs.get(&ch)?.as_ref()
         }
-        self.glyphs[(idx - 32) as usize].as_ref()
+    }
+
+    /// Rasterize `ch` into the atlas if it's new, marking the atlas dirty.
+    fn cache_glyph(&mut self, ch: char) {
+        if ch.is_control() || (' '..='~').contains(&ch) || self.extra_glyphs.contains_key(&ch) {
+            return;
+        }
+        let Some(cache) = self.cache.as_mut() else {
+            return;
+        };
+        let (glyph, grew_from) = cache.add(ch);
+        if let Some(old_size) = grew_from {
+            let placed = self.glyphs.iter_mut().chain(self.extra_glyphs.values_mut());
+            rescale_uvs(placed.flatten(), old_size, cache.size());
+        }
+        self.extra_glyphs.insert(ch, glyph);
+    }
+
+    /// Width of one line of text: the sum of its glyph advances.
+    pub fn line_width(&self, line: &str) -> f32 {
+        line.chars()
+            .filter_map(|ch| self.glyph(ch))
+            .map(|glyph| glyph.advance)
+            .sum()
     }
 }
 
@@ -121,22 +428,315 @@ impl FontStore {
         &self.entries[handle.0]
     }
 
-    fn push(&mut self, entry: FontEntry) -> FontHandle {
+    /// The atlas texture of every loaded font.
+    pub fn atlas_handles(&self) -> impl Iterator<Item = TextureHandle> + '_ {
+        self.entries.iter().map(|entry| entry.atlas_handle)
+    }
+
+    pub(super) fn push(&mut self, entry: FontEntry) -> FontHandle {
         let handle = FontHandle(self.entries.len());
         self.entries.push(entry);
         handle
     }
 }
 
+// ── Layout ──────────────────────────────────────────────────────────────
+
+/// One glyph quad of laid-out [`Text`], in the entity's local space (Y-up).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TextGlyph {
+    pub atlas: TextureHandle,
+    pub min: Vec2,
+    pub max: Vec2,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    pub color: [f32; 4],
+    /// Size relative to the font's glyphs, from a span's `size`.
+    pub scale: f32,
+    pub distance_field: Option<DistanceField>,
+}
+
+/// A styled run: `content` or one of the spans.
+struct Run<'a> {
+    entry: &'a FontEntry,
+    color: [f32; 4],
+    scale: f32,
+}
+
+/// A character placed on a line.
+#[derive(Clone, Copy)]
+struct LineChar {
+    ch: char,
+    run: usize,
+    advance: f32,
+}
+
+/// Lay out `text`: break it into lines, align them, and place a quad for
+/// every visible glyph. See the [module docs](self#layout).
+pub(crate) fn layout_text(fonts: &FontStore, text: &Text) -> Vec<TextGlyph> {
+    let base = fonts.get(text.font);
+    let mut runs = vec![Run {
+        entry: base,
+        color: text.color.to_array(),
+        scale: 1.0,
+    }];
+    let mut contents = vec![text.content.as_str()];
+    for span in &text.spans {
+        let entry = fonts.get(span.font.unwrap_or(text.font));
+        // A font's line height is 1.2× the pixel size it was loaded at.
+        let scale = span.size.map_or(1.0, |size| size * 1.2 / entry.line_height);
+        runs.push(Run {
+            entry,
+            color: span.color.unwrap_or(text.color).to_array(),
+            scale,
+        });
+        contents.push(&span.content);
+    }
+
+    let chars = contents.iter().enumerate().flat_map(|(run, content)| {
+        let runs = &runs;
+        content.chars().map(move |ch| LineChar {
+            ch,
+            run,
+            advance: runs[run].entry.glyph(ch).map_or(0.0, |g| g.advance * runs[run].scale),
+        })
+    });
+    let lines = break_lines(chars, text.max_width);
+
+    // Measure: line widths without trailing spaces, heights from the
+    // tallest run on each line.
+    let widths: Vec<f32> = lines
+        .iter()
+        .map(|line| {
+            let end = line.iter().rposition(|c| !c.ch.is_whitespace()).map_or(0, |i| i + 1);
+            line[..end].iter().map(|c| c.advance).sum()
+        })
+        .collect();
+    let heights: Vec<f32> = lines
+        .iter()
+        .map(|line| {
+            let tallest = line
+                .iter()
+                .map(|c| runs[c.run].entry.line_height * runs[c.run].scale)
+                .fold(0.0, f32::max);
+            if tallest > 0.0 { tallest } else { base.line_height }
+        })
+        .collect();
+
+    let block_width = text
+        .max_width
+        .unwrap_or_else(|| widths.iter().copied().fold(0.0, f32::max));
+    let block_left = match text.align {
+        TextAlign::Left => 0.0,
+        TextAlign::Center => -block_width * 0.5,
+        TextAlign::Right => -block_width,
+    };
+    let block_height: f32 = heights.iter().sum();
+    let mut line_top = match text.anchor {
+        TextAnchor::Baseline => heights[0] * (1.0 - BASELINE),
+        TextAnchor::Top => 0.0,
+        TextAnchor::Center => block_height * 0.5,
+        TextAnchor::Bottom => block_height,
+    };
+
+    let mut glyphs = Vec::new();
+    for ((line, width), height) in lines.iter().zip(widths).zip(heights) {
+        let mut pen = block_left
+            + match text.align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (block_width - width) * 0.5,
+                TextAlign::Right => block_width - width,
+            };
+        let baseline = line_top - height * (1.0 - BASELINE);
+        for c in line {
+            let run = &runs[c.run];
+            if let Some(glyph) = run.entry.glyph(c.ch)
+                && glyph.width > 0.0
+                && glyph.height > 0.0
+            {
+                // offset_y is ymin from fontdue (baseline-relative, Y-up).
+                let min = Vec2::new(pen + glyph.offset_x * run.scale, baseline + glyph.offset_y * run.scale);
+                glyphs.push(TextGlyph {
+                    atlas: run.entry.atlas_handle,
+                    min,
+                    max: min + Vec2::new(glyph.width, glyph.height) * run.scale,
+                    uv_min: Vec2::new(glyph.u_min, glyph.v_min),
+                    uv_max: Vec2::new(glyph.u_max, glyph.v_max),
+                    color: run.color,
+                    scale: run.scale,
+                    distance_field: run.entry.distance_field,
+                });
+            }
+            pen += c.advance;
+        }
+        line_top -= height;
+    }
+    glyphs
+}
+
+/// Split characters into lines at `\n` and, with a `max_width`, at the last
+/// space that fits. A word wider than the whole line breaks where it
+/// overflows. Newlines are dropped; spaces at a wrap point are kept at the
+/// end of the line they ended.
+fn break_lines(chars: impl Iterator<Item = LineChar>, max_width: Option<f32>) -> Vec<Vec<LineChar>> {
+    let mut lines: Vec<Vec<LineChar>> = vec![Vec::new()];
+    let mut width = 0.0;
+    // Index just after the last space on the current line.
+    let mut break_at: Option<usize> = None;
+    for c in chars {
+        if c.ch == '\n' {
+            lines.push(Vec::new());
+            width = 0.0;
+            break_at = None;
+            continue;
+        }
+        let line = lines.last_mut().unwrap();
+        if let Some(max_width) = max_width
+            && !c.ch.is_whitespace()
+            && !line.is_empty()
+            && width + c.advance > max_width
+        {
+            let rest = line.split_off(break_at.unwrap_or(line.len()));
+            width = rest.iter().map(|c| c.advance).sum();
+            break_at = None;
+            lines.push(rest);
+        }
+        let line = lines.last_mut().unwrap();
+        line.push(c);
+        width += c.advance;
+        if c.ch == ' ' {
+            break_at = Some(line.len());
+        }
+    }
+    lines
+}
+
 const ATLAS_SIZE: u32 = 512;
+/// Largest atlas a font grows to; glyphs that don't fit after that are
+/// skipped.
+const MAX_ATLAS_SIZE: u32 = 4096;
 const GLYPH_PADDING: u32 = 1;
 
 /// Load a TTF/OTF font from disk at the given pixel size.
 ///
 /// Rasterizes ASCII 32–126, packs into a 512×512 atlas, uploads as a texture.
+/// Other characters are added to the atlas the first time text uses them.
 /// Returns a [`FontHandle`] for use in [`Text`] components.
 pub fn load_font(world: &mut World, path: &str, size: f32) -> FontHandle {
-    // Ensure TextureStore + SpriteRenderer exist
+    let path = crate::launch::resolve_asset_path(world, path);
+    let path = path.as_ref();
+    ensure_stores(world);
+
+    // Read font file
+    let font_data = std::fs::read(path)
+        .unwrap_or_else(|e| panic!("Failed to read font '{}': {}", path, e));
+    let font = rasterize_font(font_data, size, None)
+        .unwrap_or_else(|e| panic!("Failed to parse font '{}': {}", path, e));
+    push_font(world, font, None)
+}
+
+/// Upload a rasterized font's atlas and add it to the [`FontStore`].
+/// `distance_field` is set for fonts rasterized as distance fields.
+pub(super) fn push_font(world: &mut World, font: RasterizedFont, distance_field: Option<DistanceField>) -> FontHandle {
+
+    // Upload atlas to TextureStore with a Linear sampler for smooth text
+    let mut texture_store = world
+        .resource_remove::<TextureStore>()
+        .expect("TextureStore missing");
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+
+    let mut cache = font.cache;
+    let (width, height) = cache.size();
+    let atlas_handle = upload_font_atlas(
+        gpu,
+        renderer,
+        &mut texture_store,
+        &atlasing,
+        &cache.atlas_rgba,
+        width,
+        height,
+    );
+    cache.dirty = false;
+    world.insert_resource(texture_store);
+
+    world.resource_mut::<FontStore>().push(FontEntry {
+        glyphs: font.glyphs,
+        extra_glyphs: HashMap::new(),
+        atlas_handle,
+        line_height: font.line_height,
+        cache: Some(cache),
+        distance_field,
+    })
+}
+
+/// Start loading a font in the background and return its handle at once.
+///
+/// Reading and rasterizing happen on a loader thread. Until they finish, the
+/// font has no glyphs, so [`Text`] using it draws nothing; track it with
+/// [`AssetServer::load_state`](crate::asset::AssetServer::load_state).
+/// Without an [`AssetServer`] this falls back to [`load_font`].
+pub fn load_font_async(world: &mut World, path: &str, size: f32) -> FontHandle {
+    if !world.has_resource::<AssetServer>() {
+        return load_font(world, path, size);
+    }
+    let path = crate::launch::resolve_asset_path(world, path).into_owned();
+    ensure_stores(world);
+
+    // Reserve a 1x1 atlas; the real one replaces it in place.
+    let mut texture_store = world
+        .resource_remove::<TextureStore>()
+        .expect("TextureStore missing");
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+    let atlas_handle =
+        upload_font_atlas(gpu, renderer, &mut texture_store, &atlasing, &[0; 4], 1, 1);
+    world.insert_resource(texture_store);
+
+    let handle = world.resource_mut::<FontStore>().push(FontEntry {
+        glyphs: (32..=126).map(|_| None).collect(),
+        extra_glyphs: HashMap::new(),
+        atlas_handle,
+        line_height: size * 1.2,
+        cache: None,
+        distance_field: None,
+    });
+
+    world.resource_mut::<AssetServer>().load_async(
+        PathBuf::from(path),
+        move |path| {
+            let font_data = std::fs::read(path).map_err(|e| e.to_string())?;
+            rasterize_font(font_data, size, None)
+        },
+        move |world, font| {
+            let Some(mut texture_store) = world.resource_remove::<TextureStore>() else {
+                return;
+            };
+            let mut cache = font.cache;
+            let (width, height) = cache.size();
+            let gpu = world.resource::<GpuContext>();
+            let renderer = world.resource::<SpriteRenderer>();
+            let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+            let data = &cache.atlas_rgba;
+            texture_store.write_packable(gpu, renderer, &atlasing, atlas_handle, width, height, data);
+            cache.dirty = false;
+            world.insert_resource(texture_store);
+
+            let entry = &mut world.resource_mut::<FontStore>().entries[handle.0];
+            entry.glyphs = font.glyphs;
+            entry.line_height = font.line_height;
+            entry.cache = Some(cache);
+        },
+    );
+    handle
+}
+
+/// Make sure `TextureStore`, `SpriteRenderer` and `FontStore` exist.
+pub(super) fn ensure_stores(world: &mut World) {
+    // The render thread may hold them for the frame in flight.
+    finish_render(world);
     if !world.has_resource::<TextureStore>() {
         let gpu = world.resource::<GpuContext>();
         let renderer = SpriteRenderer::new(gpu);
@@ -148,211 +748,435 @@ pub fn load_font(world: &mut World, path: &str, size: f32) -> FontHandle {
     if !world.has_resource::<FontStore>() {
         world.insert_resource(FontStore::new());
     }
+}
 
-    // Read font file
-    let font_data = std::fs::read(path)
-        .unwrap_or_else(|e| panic!("Failed to read font '{}': {}", path, e));
+/// A font rasterized into an RGBA atlas, not yet uploaded. Built without
+/// World access so async loads can make it on a loader thread.
+pub(super) struct RasterizedFont {
+    glyphs: Vec<Option<GlyphInfo>>,
+    cache: GlyphCache,
+    line_height: f32,
+}
 
+/// Parse a font and rasterize ASCII 32–126 into an atlas, as distance
+/// fields spanning `sdf_range` pixels if set.
+pub(super) fn rasterize_font(font_data: Vec<u8>, size: f32, sdf_range: Option<f32>) -> Result<RasterizedFont, String> {
     let font = fontdue::Font::from_bytes(font_data, fontdue::FontSettings {
         scale: size,
         ..Default::default()
-    })
-    .unwrap_or_else(|e| panic!("Failed to parse font '{}': {}", path, e));
+    })?;
 
-    // Rasterize ASCII 32–126
-    let mut rasterized: Vec<(char, fontdue::Metrics, Vec<u8>)> = Vec::with_capacity(95);
+    let mut cache = GlyphCache::new(font, size, sdf_range);
+    let mut glyphs: Vec<Option<GlyphInfo>> = Vec::with_capacity(95);
     for code in 32u8..=126 {
-        let ch = code as char;
-        let (metrics, bitmap) = font.rasterize(ch, size);
-        rasterized.push((ch, metrics, bitmap));
+        let (glyph, grew_from) = cache.add(code as char);
+        if let Some(old_size) = grew_from {
+            rescale_uvs(glyphs.iter_mut().flatten(), old_size, cache.size());
+        }
+        glyphs.push(glyph);
     }
 
-    // Pack into atlas (row-based)
-    let atlas_w = ATLAS_SIZE;
-    let atlas_h = ATLAS_SIZE;
-    let mut atlas_rgba = vec![0u8; (atlas_w * atlas_h * 4) as usize];
-    let mut cursor_x: u32 = GLYPH_PADDING;
-    let mut cursor_y: u32 = GLYPH_PADDING;
-    let mut row_height: u32 = 0;
+    Ok(RasterizedFont {
+        glyphs,
+        cache,
+        line_height: size * 1.2,
+    })
+}
 
-    let mut glyphs: Vec<Option<GlyphInfo>> = Vec::with_capacity(95);
+// ── Glyph cache ─────────────────────────────────────────────────────────
 
-    let line_height = size * 1.2;
+/// A font's rasterizer and the CPU copy of its atlas, packed row by row.
+pub(crate) struct GlyphCache {
+    font: fontdue::Font,
+    /// Pixel size glyphs are rasterized at.
+    px: f32,
+    /// Distance range for distance-field glyphs; `None` for coverage.
+    sdf_range: Option<f32>,
+    atlas_rgba: Vec<u8>,
+    width: u32,
+    height: u32,
+    cursor_x: u32,
+    cursor_y: u32,
+    row_height: u32,
+    /// Pixels changed since the atlas was last uploaded.
+    dirty: bool,
+}
 
-    for &(ch, ref metrics, ref bitmap) in &rasterized {
-        let gw = metrics.width as u32;
-        let gh = metrics.height as u32;
+impl GlyphCache {
+    fn new(font: fontdue::Font, px: f32, sdf_range: Option<f32>) -> Self {
+        Self {
+            font,
+            px,
+            sdf_range,
+            atlas_rgba: vec![0; (ATLAS_SIZE * ATLAS_SIZE * 4) as usize],
+            width: ATLAS_SIZE,
+            height: ATLAS_SIZE,
+            cursor_x: GLYPH_PADDING,
+            cursor_y: GLYPH_PADDING,
+            row_height: 0,
+            dirty: false,
+        }
+    }
+
+    /// Atlas size in pixels.
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    /// Rasterize and pack `ch`. Returns its glyph (`None` if the font lacks
+    /// it or the atlas is full) and, if the atlas had to grow, its old size:
+    /// UVs of glyphs packed before then must be rescaled.
+    fn add(&mut self, ch: char) -> (Option<GlyphInfo>, Option<(u32, u32)>) {
+        let old_size = self.size();
+        let glyph = self.pack(ch);
+        let grew_from = (self.size() != old_size).then_some(old_size);
+        (glyph, grew_from)
+    }
+
+    fn pack(&mut self, ch: char) -> Option<GlyphInfo> {
+        if ch != ' ' && self.font.lookup_glyph_index(ch) == 0 {
+            return None;
+        }
+        let bitmap = match self.sdf_range {
+            Some(range) => super::sdf::rasterize_sdf(&self.font, ch, self.px, range),
+            None => {
+                let (metrics, coverage) = self.font.rasterize(ch, self.px);
+                GlyphBitmap {
+                    width: metrics.width as u32,
+                    height: metrics.height as u32,
+                    offset_x: metrics.xmin as f32,
+                    offset_y: metrics.ymin as f32,
+                    advance: metrics.advance_width,
+                    alpha: coverage,
+                }
+            }
+        };
+        let gw = bitmap.width;
+        let gh = bitmap.height;
 
         // Space and other zero-size glyphs
         if gw == 0 || gh == 0 {
-            glyphs.push(Some(GlyphInfo {
+            return Some(GlyphInfo {
                 u_min: 0.0,
                 v_min: 0.0,
                 u_max: 0.0,
                 v_max: 0.0,
-                advance: metrics.advance_width,
+                advance: bitmap.advance,
                 offset_x: 0.0,
                 offset_y: 0.0,
                 width: 0.0,
                 height: 0.0,
-            }));
-            continue;
-        }
-
-        // Wrap to next row if needed
-        if cursor_x + gw + GLYPH_PADDING > atlas_w {
-            cursor_x = GLYPH_PADDING;
-            cursor_y += row_height + GLYPH_PADDING;
-            row_height = 0;
+            });
         }
 
-        if cursor_y + gh + GLYPH_PADDING > atlas_h {
-            log::warn!(
-                "Font atlas overflow at char '{}' (U+{:04X}) — atlas too small",
-                ch, ch as u32
-            );
-            glyphs.push(None);
-            continue;
+        // Wrap to next row if needed, growing the atlas once it's full.
+        loop {
+            if self.cursor_x + gw + GLYPH_PADDING > self.width {
+                self.cursor_x = GLYPH_PADDING;
+                self.cursor_y += self.row_height + GLYPH_PADDING;
+                self.row_height = 0;
+            }
+            if self.cursor_x + gw + GLYPH_PADDING <= self.width
+                && self.cursor_y + gh + GLYPH_PADDING <= self.height
+            {
+                break;
+            }
+            if self.width * 2 > MAX_ATLAS_SIZE {
+                log::warn!(
+                    "Font atlas overflow at char '{}' (U+{:04X}) — atlas too small",
+                    ch, ch as u32
+                );
+                return None;
+            }
+            self.grow();
         }
 
         // Copy glyph bitmap into atlas as RGBA [255, 255, 255, alpha]
         for gy in 0..gh {
             for gx in 0..gw {
                 let src_idx = (gy * gw + gx) as usize;
-                let dst_x = cursor_x + gx;
-                let dst_y = cursor_y + gy;
-                let dst_idx = ((dst_y * atlas_w + dst_x) * 4) as usize;
-                let alpha = bitmap[src_idx];
-                atlas_rgba[dst_idx] = 255;
-                atlas_rgba[dst_idx + 1] = 255;
-                atlas_rgba[dst_idx + 2] = 255;
-                atlas_rgba[dst_idx + 3] = alpha;
+                let dst_x = self.cursor_x + gx;
+                let dst_y = self.cursor_y + gy;
+                let dst_idx = ((dst_y * self.width + dst_x) * 4) as usize;
+                self.atlas_rgba[dst_idx..dst_idx + 4].copy_from_slice(&[255, 255, 255, bitmap.alpha[src_idx]]);
             }
         }
+        self.dirty = true;
 
         // Compute UV coordinates (normalized)
-        let u_min = cursor_x as f32 / atlas_w as f32;
-        let v_min = cursor_y as f32 / atlas_h as f32;
-        let u_max = (cursor_x + gw) as f32 / atlas_w as f32;
-        let v_max = (cursor_y + gh) as f32 / atlas_h as f32;
-
-        // fontdue: ymin is the distance from the baseline to the bottom of the glyph
-        // (positive = above baseline for most glyphs). We negate for Y-up coordinate
-        // system where the glyph hangs below the baseline origin.
-        let offset_x = metrics.xmin as f32;
-        let offset_y = metrics.ymin as f32;
-
-        glyphs.push(Some(GlyphInfo {
-            u_min,
-            v_min,
-            u_max,
-            v_max,
-            advance: metrics.advance_width,
-            offset_x,
-            offset_y,
+        let (atlas_w, atlas_h) = (self.width as f32, self.height as f32);
+        let glyph = GlyphInfo {
+            u_min: self.cursor_x as f32 / atlas_w,
+            v_min: self.cursor_y as f32 / atlas_h,
+            u_max: (self.cursor_x + gw) as f32 / atlas_w,
+            v_max: (self.cursor_y + gh) as f32 / atlas_h,
+            advance: bitmap.advance,
+            offset_x: bitmap.offset_x,
+            offset_y: bitmap.offset_y,
             width: gw as f32,
             height: gh as f32,
-        }));
+        };
 
-        cursor_x += gw + GLYPH_PADDING;
-        row_height = row_height.max(gh);
+        self.cursor_x += gw + GLYPH_PADDING;
+        self.row_height = self.row_height.max(gh);
+        Some(glyph)
     }
 
-    // Upload atlas to TextureStore with a Linear sampler for smooth text
-    let mut texture_store = world
-        .resource_remove::<TextureStore>()
-        .expect("TextureStore missing");
-    let gpu = world.resource::<GpuContext>();
-    let renderer = world.resource::<SpriteRenderer>();
-
-    let atlas_handle = upload_font_atlas(
-        gpu,
-        renderer,
-        &mut texture_store,
-        &atlas_rgba,
-        atlas_w,
-        atlas_h,
-    );
+    /// Double the atlas in both directions, keeping existing pixels in the
+    /// top-left corner so their positions stay valid.
+    fn grow(&mut self) {
+        let (old_w, old_h) = self.size();
+        let (new_w, new_h) = (old_w * 2, old_h * 2);
+        let mut rgba = vec![0u8; (new_w * new_h * 4) as usize];
+        let old_row = (old_w * 4) as usize;
+        for (y, row) in self.atlas_rgba.chunks_exact(old_row).enumerate() {
+            let start = y * (new_w * 4) as usize;
+            rgba[start..start + old_row].copy_from_slice(row);
+        }
+        self.atlas_rgba = rgba;
+        self.width = new_w;
+        self.height = new_h;
+    }
+}
 
-    let entry = FontEntry {
-        glyphs,
-        atlas_handle,
-        line_height,
-    };
+/// One rasterized glyph: alpha per pixel, rows top to bottom.
+pub(super) struct GlyphBitmap {
+    pub width: u32,
+    pub height: u32,
+    /// From the pen position to the bitmap's left edge.
+    pub offset_x: f32,
+    /// From the baseline up to the bitmap's bottom edge (fontdue's `ymin`).
+    pub offset_y: f32,
+    pub advance: f32,
+    /// Coverage, or encoded distance for distance-field fonts.
+    pub alpha: Vec<u8>,
+}
 
-    let mut font_store = world
-        .resource_remove::<FontStore>()
-        .expect("FontStore missing");
-    let handle = font_store.push(entry);
+/// Fix the UVs of glyphs packed into an atlas of `old` size after it grew
+/// to `new`.
+fn rescale_uvs<'a>(glyphs: impl Iterator<Item = &'a mut GlyphInfo>, old: (u32, u32), new: (u32, u32)) {
+    let sx = old.0 as f32 / new.0 as f32;
+    let sy = old.1 as f32 / new.1 as f32;
+    for glyph in glyphs {
+        glyph.u_min *= sx;
+        glyph.u_max *= sx;
+        glyph.v_min *= sy;
+        glyph.v_max *= sy;
+    }
+}
 
-    world.insert_resource(texture_store);
-    world.insert_resource(font_store);
+/// Rasterize every character that text components use but their font's
+/// atlas lacks, then upload the atlases that changed. Runs once per frame
+/// before anything is drawn.
+pub(crate) fn cache_glyphs(world: &mut World) {
+    let Some(mut fonts) = world.resource_remove::<FontStore>() else {
+        return;
+    };
+    let mut use_text = |font: FontHandle, content: &str| {
+        if let Some(entry) = fonts.entries.get_mut(font.0) {
+            for ch in content.chars() {
+                entry.cache_glyph(ch);
+            }
+        }
+    };
+    world.query::<(&Text,)>(|_, (text,)| {
+        use_text(text.font, &text.content);
+        for span in &text.spans {
+            use_text(span.font.unwrap_or(text.font), &span.content);
+        }
+    });
+    world.query::<(&crate::ui::UiText,)>(|_, (text,)| use_text(text.font, &text.content));
+    #[cfg(feature = "render3d")]
+    world.query::<(&crate::render3d::Text3d,)>(|_, (text,)| use_text(text.font, &text.content));
 
-    handle
+    let dirty = fonts
+        .entries
+        .iter()
+        .any(|entry| entry.cache.as_ref().is_some_and(|cache| cache.dirty));
+    if dirty
+        && let Some(mut texture_store) = world.resource_remove::<TextureStore>()
+    {
+        let gpu = world.resource::<GpuContext>();
+        let renderer = world.resource::<SpriteRenderer>();
+        let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+        for entry in &mut fonts.entries {
+            if let Some(cache) = entry.cache.as_mut()
+                && cache.dirty
+            {
+                let (width, height) = cache.size();
+                let (handle, data) = (entry.atlas_handle, &cache.atlas_rgba);
+                texture_store.write_packable(gpu, renderer, &atlasing, handle, width, height, data);
+                cache.dirty = false;
+            }
+        }
+        world.insert_resource(texture_store);
+    }
+    world.insert_resource(fonts);
 }
 
-/// Upload the font atlas as a texture with a Linear filter sampler.
-fn upload_font_atlas(
+/// Upload the font atlas as a texture with a Linear filter sampler, packed
+/// onto a shared [atlas](super::atlas) page while it's small enough.
+pub(super) fn upload_font_atlas(
     gpu: &GpuContext,
     renderer: &SpriteRenderer,
     texture_store: &mut TextureStore,
+    atlasing: &TextureAtlasing,
     data: &[u8],
     width: u32,
     height: u32,
 ) -> TextureHandle {
-    let texture = gpu.device.create_texture_with_data(
-        &gpu.queue,
-        &wgpu::TextureDescriptor {
-            label: Some("font atlas"),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        },
-        wgpu::util::TextureDataOrder::LayerMajor,
-        data,
-    );
+    // Linear filtering for smoother text at fractional scales.
+    let sampler = SamplerSettings::linear();
+    if atlasing.should_pack_font(width, height) {
+        return texture_store.pack(gpu, renderer, atlasing, width, height, data, sampler);
+    }
+    texture_store.add_rgba(gpu, renderer, "font atlas", width, height, data, sampler)
+}
 
-    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Use Linear filtering for smoother text at fractional scales
-    let linear_sampler = gpu.device.create_sampler(&wgpu::SamplerDescriptor {
-        label: Some("font atlas sampler"),
-        address_mode_u: wgpu::AddressMode::ClampToEdge,
-        address_mode_v: wgpu::AddressMode::ClampToEdge,
-        mag_filter: wgpu::FilterMode::Linear,
-        min_filter: wgpu::FilterMode::Linear,
-        ..Default::default()
-    });
+    /// A fake font where every printable glyph is a 10×10 box advancing
+    /// 10px, with a 20px line height.
+    fn fonts() -> FontStore {
+        let glyph = GlyphInfo {
+            u_min: 0.0,
+            v_min: 0.0,
+            u_max: 1.0,
+            v_max: 1.0,
+            advance: 10.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            width: 10.0,
+            height: 10.0,
+        };
+        let mut glyphs = vec![Some(glyph); 95];
+        glyphs[0] = Some(GlyphInfo { width: 0.0, height: 0.0, ..glyph });
+        let mut store = FontStore::new();
+        store.push(FontEntry {
+            glyphs,
+            extra_glyphs: HashMap::new(),
+            atlas_handle: TextureHandle(0),
+            line_height: 20.0,
+            cache: None,
+            distance_field: None,
+        });
+        store
+    }
 
-    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some("font atlas bind group"),
-        layout: &renderer.texture_bind_group_layout,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&view),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::Sampler(&linear_sampler),
-            },
-        ],
-    });
+    /// Left edge and baseline of each glyph, rows top to bottom.
+    fn glyph_rows(glyphs: &[TextGlyph]) -> Vec<(f32, Vec<f32>)> {
+        let mut rows: Vec<(f32, Vec<f32>)> = Vec::new();
+        for glyph in glyphs {
+            match rows.last_mut() {
+                Some((y, xs)) if *y == glyph.min.y => xs.push(glyph.min.x),
+                _ => rows.push((glyph.min.y, vec![glyph.min.x])),
+            }
+        }
+        rows
+    }
 
-    let handle = TextureHandle(texture_store.entries.len());
-    texture_store.entries.push(super::texture::TextureEntry {
-        bind_group,
-        width,
-        height,
-    });
+    #[test]
+    fn wraps_at_spaces_and_splits_long_words() {
+        let fonts = fonts();
+        // "aa bb cc" is 80px; 55px fits "aa bb" (50px) but not the space after.
+        let text = Text::new("aa bb cc", FontHandle(0)).max_width(55.0);
+        let rows = glyph_rows(&layout_text(&fonts, &text));
+        assert_eq!(rows, [(0.0, vec![0.0, 10.0, 30.0, 40.0]), (-20.0, vec![0.0, 10.0])]);
 
-    handle
+        // A word wider than the line breaks where it overflows.
+        let text = Text::new("abcdef", FontHandle(0)).max_width(40.0);
+        let rows = glyph_rows(&layout_text(&fonts, &text));
+        assert_eq!(rows[0].1.len(), 4);
+        assert_eq!(rows[1].1, [0.0, 10.0]);
+
+        // Newlines still break, and nothing wraps without a width.
+        let rows_unwrapped = glyph_rows(&layout_text(&fonts, &Text::new("aa bb\ncc", FontHandle(0))));
+        assert_eq!(rows_unwrapped.len(), 2);
+        assert_eq!(rows_unwrapped[0].1.len(), 4);
+    }
+
+    #[test]
+    fn aligns_lines_inside_the_block_and_the_block_on_the_origin() {
+        let fonts = fonts();
+        let text = Text::new("aaaa\nbb", FontHandle(0)).align(TextAlign::Center);
+        let rows = glyph_rows(&layout_text(&fonts, &text));
+        assert_eq!(rows[0].1, [-20.0, -10.0, 0.0, 10.0]);
+        assert_eq!(rows[1].1, [-10.0, 0.0]);
+
+        // The trailing space before a wrap doesn't count toward the width.
+        let text = Text::new("aa bb", FontHandle(0)).max_width(30.0).align(TextAlign::Right);
+        let rows = glyph_rows(&layout_text(&fonts, &text));
+        assert_eq!(rows, [(0.0, vec![-20.0, -10.0]), (-20.0, vec![-20.0, -10.0])]);
+
+        // Two 20px lines; baseline sits 5px above the line bottom.
+        let tops = [
+            (TextAnchor::Baseline, 0.0),
+            (TextAnchor::Top, -15.0),
+            (TextAnchor::Center, 5.0),
+            (TextAnchor::Bottom, 25.0),
+        ];
+        for (anchor, first_baseline) in tops {
+            let text = Text::new("a\na", FontHandle(0)).anchor(anchor);
+            let glyphs = layout_text(&fonts, &text);
+            assert_eq!(glyphs[0].min.y, first_baseline, "{anchor:?}");
+            assert_eq!(glyphs[1].min.y, first_baseline - 20.0, "{anchor:?}");
+        }
+    }
+
+    #[test]
+    fn spans_keep_their_own_color_and_size() {
+        let fonts = fonts();
+        // The fake font's lines are 20px, so it was loaded at 20 / 1.2 px;
+        // twice that draws at double size.
+        let text = Text::new("a", FontHandle(0))
+            .color(Color::RED)
+            .with_span(TextSpan::new("b").size(40.0 / 1.2))
+            .with_span(TextSpan::new("c").color(Color::BLUE));
+        let glyphs = layout_text(&fonts, &text);
+        assert_eq!(glyphs[0].color, Color::RED.to_array());
+        assert_eq!(glyphs[1].color, Color::RED.to_array());
+        assert_eq!(glyphs[2].color, Color::BLUE.to_array());
+        assert!((glyphs[1].max - glyphs[1].min).abs_diff_eq(Vec2::splat(20.0), 1e-4));
+        assert!((glyphs[2].min.x - 30.0).abs() < 1e-4);
+        // The tall span sets the line's height, so all three share a baseline.
+        assert!(glyphs.iter().all(|g| g.min.y == glyphs[0].min.y));
+        assert_eq!(text.full_text(), "abc");
+    }
+
+    #[test]
+    fn atlas_grows_and_keeps_cached_glyphs_in_place() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/assets/LiberationSans-Regular.ttf");
+        // Large glyphs fill the first 512×512 atlas with ASCII alone.
+        let font = rasterize_font(std::fs::read(path).unwrap(), 96.0, None).unwrap();
+        let mut entry = FontEntry {
+            glyphs: font.glyphs,
+            extra_glyphs: HashMap::new(),
+            atlas_handle: TextureHandle(0),
+            line_height: font.line_height,
+            cache: Some(font.cache),
+            distance_field: None,
+        };
+        let size = entry.cache.as_ref().unwrap().size();
+        assert!(size.0 > ATLAS_SIZE, "ASCII at 96px needs a bigger atlas");
+
+        let a = *entry.glyph('A').unwrap();
+        // Latin-1 and Latin Extended-A/B.
+        for ch in '\u{C0}'..='\u{24F}' {
+            entry.cache_glyph(ch);
+        }
+        let cache = entry.cache.as_ref().unwrap();
+        assert!(cache.size().0 > size.0);
+        assert!(entry.glyph('é').is_some_and(|g| g.width > 0.0));
+        // 'A' kept its pixels, so its UVs shrank with the atlas.
+        let moved = entry.glyph('A').unwrap();
+        let scale = size.0 as f32 / cache.size().0 as f32;
+        assert!((moved.u_max - a.u_max * scale).abs() < 1e-6);
+
+        // Characters the font lacks are remembered as missing.
+        entry.cache_glyph('\u{10FFFF}');
+        assert!(entry.glyph('\u{10FFFF}').is_none());
+        assert!(entry.extra_glyphs.contains_key(&'\u{10FFFF}'));
+    }
 }
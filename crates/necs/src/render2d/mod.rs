@@ -60,9 +60,16 @@
 //! **Texture batching.** After sorting, consecutive sprites that share the same
 //! texture are drawn in a single `draw_indexed` call. Switching textures
 //! requires changing the GPU bind group (an expensive operation relative to
-//! just adding more vertices to an existing draw). For games with many sprites,
-//! using a texture atlas (packing many images into one texture) would further
-//! reduce batch breaks, but that's a future optimization.
+//! just adding more vertices to an existing draw). Small textures are packed
+//! into shared [atlas](atlas) pages as they load, so sprites drawn from
+//! different small images still land in the same batch. On GPUs with
+//! texture binding arrays, a batch holds up to 16 textures and each vertex
+//! names the one it samples (see [batch](batch#texture-arrays)).
+//!
+//! **Optional instancing.** For very large sprite counts, the
+//! [`SpriteRenderMode::Instanced`] setting moves the per-sprite transform to
+//! the vertex shader: one instance (matrix, UVs, color) per sprite instead of
+//! four pre-transformed vertices. Draw order and batching are unchanged.
 //!
 //! ## Comparison
 //!
@@ -77,22 +84,54 @@
 //!   commands; the C++ backend does automatic batching of consecutive same-
 //!   texture draws, very similar to our approach.
 
+pub mod atlas;
 pub(crate) mod batch;
 pub(crate) mod draw;
 pub mod font;
 pub(crate) mod pipeline;
+pub mod post;
+pub mod progress_bar;
+pub mod sdf;
 pub mod shapes;
 pub(crate) mod texture;
+pub mod texture_atlas;
+pub mod tiled;
+pub mod tilemap;
+pub mod tiling;
 pub(crate) mod vertex;
+pub mod video;
 
 #[cfg(feature = "physics2d")]
 pub(crate) mod debug_wireframe;
 
 #[cfg(feature = "physics2d")]
 pub use debug_wireframe::DebugColliders2d;
-pub use font::{FontHandle, Text, load_font};
+pub use atlas::TextureAtlasing;
+pub use batch::SpriteRenderMode;
+pub use font::{
+    FontHandle, Text, TextAlign, TextAnchor, TextOutline, TextShadow, TextSpan, load_font, load_font_async,
+};
+pub use post::{
+    ChromaticAberration, CustomEffect, PaletteSwap, PostEffect, PostEffectSlot, PostEffects2d,
+    ScreenShake, Vignette,
+};
+pub use progress_bar::ProgressBar2d;
+pub use sdf::{load_msdf_font, load_sdf_font};
 pub use shapes::{Shape2d, ShapeKind2d};
-pub use texture::{TextureHandle, create_texture_from_rgba, load_texture};
+pub use texture_atlas::{
+    AtlasSprite, TextureAtlas, TextureAtlasHandle, TextureAtlases, add_texture_atlas,
+    load_texture_atlas, pack_texture_atlas,
+};
+pub use tiled::{TiledError, load_tilemap};
+pub use tilemap::{Tile, TileLayer, Tilemap};
+#[cfg(feature = "physics2d")]
+pub use tilemap::TileCollider;
+pub use tiling::{SpriteTiling, UvScroll, scroll_uvs};
+pub use texture::{
+    TextureHandle, create_texture_from_rgba, load_texture, load_texture_async, load_texture_with,
+    set_texture_sampler, update_texture_rgba,
+};
+pub use video::{Video, VideoError, VideoPlayer, update_videos};
 
 use crate::math::{Rect, Vec2};
 
@@ -121,6 +160,17 @@ pub struct Sprite {
     pub flip_y: bool,
     /// UV sub-region of the texture. Defaults to full texture.
     pub texture_rect: Rect,
+    /// Stretch the texture across the quad or repeat it. See [`tiling`].
+    pub tiling: SpriteTiling,
+    /// Shift of the texture within the quad, in tiles; wraps around. Animate
+    /// it (or use [`UvScroll`]) to scroll.
+    pub uv_offset: Vec2,
+    /// Draw one region of a [`TextureAtlas`]. When set, it replaces `texture`
+    /// and `texture_rect` at render time. See [`texture_atlas`].
+    pub atlas: Option<AtlasSprite>,
+    /// The point of the quad that sits on the entity's position, and that
+    /// rotation, scale and flipping happen around.
+    pub anchor: SpriteAnchor,
 }
 
 impl Sprite {
@@ -129,6 +179,15 @@ impl Sprite {
         Self::default()
     }
 
+    /// A sprite showing region `index` of an atlas, sized to the region
+    /// unless [`size`](Self::size) is set.
+    pub fn from_atlas(atlas: TextureAtlasHandle, index: usize) -> Self {
+        Self {
+            atlas: Some(AtlasSprite { atlas, index }),
+            ..Self::default()
+        }
+    }
+
     /// Set the tint color.
     pub fn color(mut self, color: Color) -> Self {
         self.color = color;
@@ -146,6 +205,24 @@ impl Sprite {
         self.texture = Some(texture);
         self
     }
+
+    /// Set how the texture fills the quad.
+    pub fn tiling(mut self, tiling: SpriteTiling) -> Self {
+        self.tiling = tiling;
+        self
+    }
+
+    /// Set the UV offset, in tiles.
+    pub fn uv_offset(mut self, x: f32, y: f32) -> Self {
+        self.uv_offset = Vec2::new(x, y);
+        self
+    }
+
+    /// Set the point that sits on the entity's position.
+    pub fn anchor(mut self, anchor: SpriteAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
 }
 
 impl Default for Sprite {
@@ -157,8 +234,86 @@ impl Default for Sprite {
             flip_x: false,
             flip_y: false,
             texture_rect: Rect::FULL,
+            tiling: SpriteTiling::Stretch,
+            uv_offset: Vec2::ZERO,
+            atlas: None,
+            anchor: SpriteAnchor::Center,
+        }
+    }
+}
+
+/// Which point of a [`Sprite`]'s quad sits on its entity's position — the
+/// pivot it rotates, scales and flips around. A character anchored at
+/// `BottomCenter` stands with its feet on its `Transform`, whatever the
+/// frame's height, with no child entity to offset it:
+///
+/// ```text
+///   Center (default)        BottomCenter            Custom(0.3, 0.1)
+///   ┌─────────┐             ┌─────────┐             ┌─────────┐
+///   │         │             │         │             │         │
+///   │    ●    │             │         │             │         │
+///   │         │             │         │             │  ●      │
+///   └─────────┘             └────●────┘             └─────────┘
+///   ● = Transform position
+/// ```
+///
+/// `Custom` is a normalized point on the quad: `(0, 0)` is the bottom-left
+/// corner, `(1, 1)` the top-right, and values outside 0..1 put the pivot
+/// outside the quad.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpriteAnchor {
+    #[default]
+    Center,
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+    Custom(Vec2),
+}
+
+impl SpriteAnchor {
+    /// The anchor as a normalized point on the quad, `(0, 0)` bottom-left
+    /// to `(1, 1)` top-right.
+    pub fn point(self) -> Vec2 {
+        match self {
+            Self::Center => Vec2::new(0.5, 0.5),
+            Self::TopLeft => Vec2::new(0.0, 1.0),
+            Self::TopCenter => Vec2::new(0.5, 1.0),
+            Self::TopRight => Vec2::new(1.0, 1.0),
+            Self::CenterLeft => Vec2::new(0.0, 0.5),
+            Self::CenterRight => Vec2::new(1.0, 0.5),
+            Self::BottomLeft => Vec2::new(0.0, 0.0),
+            Self::BottomCenter => Vec2::new(0.5, 0.0),
+            Self::BottomRight => Vec2::new(1.0, 0.0),
+            Self::Custom(point) => point,
         }
     }
+
+    /// Where the center of a quad of `size` ends up, relative to the
+    /// entity's position.
+    pub fn offset(self, size: Vec2) -> Vec2 {
+        (Vec2::splat(0.5) - self.point()) * size
+    }
+}
+
+/// A [`Sprite`] with its [`Transform`](crate::math::Transform) — everything
+/// a visible 2D object needs. Spawn it directly or nest it with `#[bundle]`
+/// inside your own [`Bundle`](crate::ecs::Bundle):
+///
+/// ```ignore
+/// world.spawn(SpriteBundle {
+///     sprite: Sprite::new().color(Color::RED).size(32.0, 32.0),
+///     transform: Transform::from_xy(100.0, 0.0),
+/// });
+/// ```
+#[derive(Debug, Clone, Default, crate::ecs::Bundle)]
+pub struct SpriteBundle {
+    pub sprite: Sprite,
+    pub transform: crate::math::Transform,
 }
 
 /// An RGBA color with floating-point components in [0, 1].
@@ -16,10 +16,13 @@
 //! │                                                             │
 //! │  Vertex layout ─── SpriteVertex { pos, uv, color }         │
 //! │                    tells the GPU how to read the buffer     │
+//! │                    (instanced variant: SpriteInstance,      │
+//! │                     vs_instanced)                           │
 //! │                                                             │
 //! │  Bind group layouts                                         │
 //! │    group 0: camera uniform (mat4x4, vertex-only)            │
-//! │    group 1: texture + sampler (fragment-only)               │
+//! │    group 1: texture + sampler (fragment-only), or an        │
+//! │             array of each with texture arrays               │
 //! │                                                             │
 //! │  Blend state ─── ALPHA_BLENDING                             │
 //! │    final = src.rgb × src.a + dst.rgb × (1 - src.a)         │
@@ -51,6 +54,30 @@
 //! CPU and draw back-to-front, every sprite blends correctly without a depth
 //! test.
 //!
+//! ## Multisampling
+//!
+//! `pipeline` and `instanced_pipeline` draw single-sampled; the
+//! [UI](crate::ui) uses them too. With [MSAA](crate::render::msaa) on, the
+//! scene needs copies built for its sample count, made on first use and
+//! rebuilt when the count changes or the shader is hot-reloaded.
+//!
+//! ## Texture Arrays
+//!
+//! Where the GPU supports binding arrays (see
+//! [`TEXTURE_ARRAY_FEATURES`](crate::render::gpu::TEXTURE_ARRAY_FEATURES)),
+//! the renderer also compiles the shader with the `TEXTURE_ARRAY`
+//! [shader def](crate::render::shader_defs) into [`TextureArrays`]: group 1
+//! becomes [`TEXTURE_ARRAY_SLOTS`] textures and samplers, and the scene's
+//! batches draw through those pipelines. The classic pipelines stay for
+//! the UI and for GPUs without the features.
+//!
+//! ## Distance-Field Text
+//!
+//! Glyphs of [SDF fonts](super::sdf) carry outline and shadow parameters
+//! per vertex ([`SdfVertex`]) and draw with `sdf_text.wgsl` through their
+//! own pipeline, sharing the camera and texture layouts. It's built with
+//! the scene's pipelines for its sample count and always binds one texture.
+//!
 //! ## Lazy Initialization
 //!
 //! The [`SpriteRenderer`] is created on the first frame that actually renders,
@@ -73,22 +100,56 @@ use std::path::PathBuf;
 
 use wgpu::util::DeviceExt;
 
-use super::vertex::{CameraUniform, SpriteVertex};
+use super::vertex::{CameraUniform, SdfVertex, SpriteInstance, SpriteVertex};
 use crate::render::GpuContext;
+use crate::render::gpu::TEXTURE_ARRAY_FEATURES;
+use crate::render::msaa::multisample_state;
+use crate::render::shader_defs::{preprocess, ShaderDefs};
+
+const SPRITE_SOURCE: &str = include_str!("shader.wgsl");
+const SDF_TEXT_SOURCE: &str = include_str!("sdf_text.wgsl");
+
+/// Textures one batch can draw from with [`TextureArrays`].
+pub(crate) const TEXTURE_ARRAY_SLOTS: u32 = 16;
 
 /// GPU resources for the 2D sprite renderer. Lazy-initialized on first frame.
 pub(crate) struct SpriteRenderer {
     pub pipeline: wgpu::RenderPipeline,
+    /// Pipeline for [`SpriteRenderMode::Instanced`](super::SpriteRenderMode).
+    pub instanced_pipeline: wgpu::RenderPipeline,
     pub camera_bind_group_layout: wgpu::BindGroupLayout,
     pub texture_bind_group_layout: wgpu::BindGroupLayout,
     pub camera_buffer: wgpu::Buffer,
     pub camera_bind_group: wgpu::BindGroup,
-    pub sampler: wgpu::Sampler,
     pub vertex_buffer: Option<wgpu::Buffer>,
     pub index_buffer: Option<wgpu::Buffer>,
+    /// Instance buffer, reused across frames and regrown when too small.
+    pub instance_buffer: Option<wgpu::Buffer>,
+    /// Vertices and indices of distance-field glyphs.
+    pub sdf_vertex_buffer: Option<wgpu::Buffer>,
+    pub sdf_index_buffer: Option<wgpu::Buffer>,
     /// Path to the shader source file on disk (for hot-reload). `None` if the
     /// source file doesn't exist at runtime (release builds without source).
     pub shader_path: Option<PathBuf>,
+    /// The shader the pipelines were built from (hot-reloaded or built in).
+    shader: wgpu::ShaderModule,
+    /// Sample count, pipeline and instanced pipeline for an MSAA scene.
+    msaa_pipelines: Option<(u32, wgpu::RenderPipeline, wgpu::RenderPipeline)>,
+    /// Scene pipelines sampling a texture array, if the GPU supports them.
+    pub texture_arrays: Option<TextureArrays>,
+    sdf_shader: wgpu::ShaderModule,
+    /// Sample count and distance-field text pipeline for the scene.
+    sdf_pipeline: Option<(u32, wgpu::RenderPipeline)>,
+}
+
+/// The `TEXTURE_ARRAY` variant of the sprite shader and its pipelines.
+pub(crate) struct TextureArrays {
+    /// Group 1: [`TEXTURE_ARRAY_SLOTS`] textures, then as many samplers.
+    pub layout: wgpu::BindGroupLayout,
+    shader: wgpu::ShaderModule,
+    /// Sample count, pipeline and instanced pipeline, for the scene's
+    /// current count.
+    pipelines: Option<(u32, wgpu::RenderPipeline, wgpu::RenderPipeline)>,
 }
 
 impl SpriteRenderer {
@@ -97,11 +158,10 @@ impl SpriteRenderer {
         let device = &gpu.device;
 
         // Shader module
+        let source = preprocess(SPRITE_SOURCE, &ShaderDefs::new()).expect("sprite shader directives are valid");
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("sprite shader"),
-            source: wgpu::ShaderSource::Wgsl(
-                include_str!("shader.wgsl").into(),
-            ),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
         });
 
         // Bind group layout 0: camera uniform
@@ -151,40 +211,10 @@ impl SpriteRenderer {
             push_constant_ranges: &[],
         });
 
-        // Render pipeline — alpha blending enabled for sprites
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("sprite pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[SpriteVertex::LAYOUT],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.surface_format(),
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None, // 2D sprites are double-sided
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        // Render pipelines — alpha blending enabled for sprites
+        let pipeline = sprite_pipeline(gpu, &pipeline_layout, &shader, false, 1, "sprite pipeline");
+        let instanced_pipeline =
+            sprite_pipeline(gpu, &pipeline_layout, &shader, true, 1, "instanced sprite pipeline");
 
         // Camera uniform buffer (identity initially)
         let camera_uniform = CameraUniform {
@@ -206,16 +236,6 @@ impl SpriteRenderer {
             }],
         });
 
-        // Shared sampler for all sprite textures
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("sprite sampler"),
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
         // Locate shader source on disk for hot-reload (dev builds only).
         let shader_path = {
             let p = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -225,63 +245,307 @@ impl SpriteRenderer {
             if p.exists() { Some(p) } else { None }
         };
 
+        let sdf_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("sdf text shader"),
+            source: wgpu::ShaderSource::Wgsl(SDF_TEXT_SOURCE.into()),
+        });
+
         Self {
             pipeline,
+            instanced_pipeline,
             camera_bind_group_layout,
             texture_bind_group_layout,
             camera_buffer,
             camera_bind_group,
-            sampler,
             vertex_buffer: None,
             index_buffer: None,
+            instance_buffer: None,
+            sdf_vertex_buffer: None,
+            sdf_index_buffer: None,
             shader_path,
+            shader,
+            msaa_pipelines: None,
+            texture_arrays: supports_texture_arrays(gpu).then(|| TextureArrays::new(gpu)),
+            sdf_shader,
+            sdf_pipeline: None,
+        }
+    }
+
+    /// Build the scene pipelines for `samples` per pixel, unless they exist.
+    pub fn prepare_samples(&mut self, gpu: &GpuContext, samples: u32) {
+        if self.sdf_pipeline.as_ref().is_none_or(|(count, _)| *count != samples) {
+            let layout = gpu.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("sdf text pipeline layout"),
+                bind_group_layouts: &[&self.camera_bind_group_layout, &self.texture_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+            let pipeline = textured_pipeline(
+                gpu,
+                &layout,
+                &self.sdf_shader,
+                "vs_main",
+                SdfVertex::LAYOUT,
+                samples,
+                "sdf text pipeline",
+            );
+            self.sdf_pipeline = Some((samples, pipeline));
+        }
+        if let Some(arrays) = &self.texture_arrays
+            && arrays.pipelines.as_ref().is_none_or(|(count, ..)| *count != samples)
+        {
+            let pipelines = Some((
+                samples,
+                self.build_texture_array_pipeline(gpu, &arrays.shader, false, samples),
+                self.build_texture_array_pipeline(gpu, &arrays.shader, true, samples),
+            ));
+            if let Some(arrays) = &mut self.texture_arrays {
+                arrays.pipelines = pipelines;
+            }
+        }
+        if samples == 1 || self.msaa_pipelines.as_ref().is_some_and(|(count, ..)| *count == samples) {
+            return;
+        }
+        self.msaa_pipelines = Some((
+            samples,
+            self.build_pipeline(gpu, &self.shader, false, samples),
+            self.build_pipeline(gpu, &self.shader, true, samples),
+        ));
+    }
+
+    /// The pipeline and instanced pipeline for drawing with `samples` per
+    /// pixel, after [`prepare_samples`](Self::prepare_samples).
+    pub fn pipelines(&self, samples: u32) -> (&wgpu::RenderPipeline, &wgpu::RenderPipeline) {
+        match &self.msaa_pipelines {
+            Some((count, pipeline, instanced)) if *count == samples => (pipeline, instanced),
+            _ => (&self.pipeline, &self.instanced_pipeline),
         }
     }
 
-    /// Build a new render pipeline from a shader module (hot-reload).
+    /// The texture array pipeline and instanced pipeline for drawing with
+    /// `samples` per pixel, after [`prepare_samples`](Self::prepare_samples).
+    /// `None` without texture array support.
+    pub fn texture_array_pipelines(&self, samples: u32) -> Option<(&wgpu::RenderPipeline, &wgpu::RenderPipeline)> {
+        match self.texture_arrays.as_ref()?.pipelines.as_ref()? {
+            (count, pipeline, instanced) if *count == samples => Some((pipeline, instanced)),
+            _ => None,
+        }
+    }
+
+    /// The distance-field text pipeline for `samples` per pixel, after
+    /// [`prepare_samples`](Self::prepare_samples).
+    pub fn sdf_pipeline(&self, samples: u32) -> Option<&wgpu::RenderPipeline> {
+        match &self.sdf_pipeline {
+            Some((count, pipeline)) if *count == samples => Some(pipeline),
+            _ => None,
+        }
+    }
+
+    /// Swap in a hot-reloaded shader and its single-sampled pipelines, and
+    /// the `TEXTURE_ARRAY` variant's if the GPU uses it. MSAA pipelines are
+    /// rebuilt from them on demand.
+    pub fn replace_shader(
+        &mut self,
+        shader: wgpu::ShaderModule,
+        pipeline: wgpu::RenderPipeline,
+        instanced_pipeline: wgpu::RenderPipeline,
+        texture_array: Option<(wgpu::ShaderModule, wgpu::RenderPipeline, wgpu::RenderPipeline)>,
+    ) {
+        self.shader = shader;
+        self.pipeline = pipeline;
+        self.instanced_pipeline = instanced_pipeline;
+        self.msaa_pipelines = None;
+        if let (Some(arrays), Some((shader, pipeline, instanced))) = (&mut self.texture_arrays, texture_array) {
+            arrays.shader = shader;
+            arrays.pipelines = Some((1, pipeline, instanced));
+        }
+    }
+
+    /// Build a new render pipeline from a shader module (hot-reload), the
+    /// instanced variant if `instanced` is set, drawing with `samples` per
+    /// pixel.
     ///
     /// Reuses the existing bind group layouts. Returns the candidate pipeline
     /// **without** swapping it in — the caller must check the error scope first
     /// and only assign to `self.pipeline` if valid.
-    pub fn build_pipeline(&self, gpu: &GpuContext, shader: &wgpu::ShaderModule) -> wgpu::RenderPipeline {
+    pub fn build_pipeline(
+        &self,
+        gpu: &GpuContext,
+        shader: &wgpu::ShaderModule,
+        instanced: bool,
+        samples: u32,
+    ) -> wgpu::RenderPipeline {
         let pipeline_layout = gpu.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("sprite pipeline layout (hot-reload)"),
             bind_group_layouts: &[&self.camera_bind_group_layout, &self.texture_bind_group_layout],
             push_constant_ranges: &[],
         });
+        sprite_pipeline(gpu, &pipeline_layout, shader, instanced, samples, "sprite pipeline (hot-reload)")
+    }
+
+    /// Like [`build_pipeline`](Self::build_pipeline), for the
+    /// `TEXTURE_ARRAY` variant of the shader. Requires
+    /// [`texture_arrays`](Self::texture_arrays).
+    pub fn build_texture_array_pipeline(
+        &self,
+        gpu: &GpuContext,
+        shader: &wgpu::ShaderModule,
+        instanced: bool,
+        samples: u32,
+    ) -> wgpu::RenderPipeline {
+        let arrays = self.texture_arrays.as_ref().expect("texture arrays are supported");
+        let pipeline_layout = gpu.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("sprite texture array pipeline layout"),
+            bind_group_layouts: &[&self.camera_bind_group_layout, &arrays.layout],
+            push_constant_ranges: &[],
+        });
+        sprite_pipeline(gpu, &pipeline_layout, shader, instanced, samples, "sprite texture array pipeline")
+    }
+}
+
+impl TextureArrays {
+    fn new(gpu: &GpuContext) -> Self {
+        let device = &gpu.device;
+        let count = std::num::NonZeroU32::new(TEXTURE_ARRAY_SLOTS);
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("texture array bind group layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        multisampled: false,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    },
+                    count,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count,
+                },
+            ],
+        });
+        let defs = ShaderDefs::new().with("TEXTURE_ARRAY");
+        let source = preprocess(SPRITE_SOURCE, &defs).expect("sprite shader directives are valid");
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("sprite shader (texture array)"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+        Self {
+            layout,
+            shader,
+            pipelines: None,
+        }
+    }
+}
+
+/// Whether the device can draw from [`TextureArrays`]: the features, and
+/// room for [`TEXTURE_ARRAY_SLOTS`] textures and samplers per stage.
+fn supports_texture_arrays(gpu: &GpuContext) -> bool {
+    let limits = gpu.device.limits();
+    gpu.device.features().contains(TEXTURE_ARRAY_FEATURES)
+        && limits.max_binding_array_elements_per_shader_stage >= 2 * TEXTURE_ARRAY_SLOTS
+        && limits.max_binding_array_sampler_elements_per_shader_stage >= TEXTURE_ARRAY_SLOTS
+}
+
+/// The sprite pipeline: `vs_main` over [`SpriteVertex`] buffers, or
+/// `vs_instanced` over [`SpriteInstance`] buffers.
+fn sprite_pipeline(
+    gpu: &GpuContext,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    instanced: bool,
+    samples: u32,
+    label: &str,
+) -> wgpu::RenderPipeline {
+    let (entry_point, buffer) = if instanced {
+        ("vs_instanced", SpriteInstance::LAYOUT)
+    } else {
+        ("vs_main", SpriteVertex::LAYOUT)
+    };
+    textured_pipeline(gpu, layout, shader, entry_point, buffer, samples, label)
+}
+
+/// An alpha-blended 2D pipeline reading one vertex buffer, with the
+/// fragment shader's `fs_main`.
+fn textured_pipeline(
+    gpu: &GpuContext,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    entry_point: &str,
+    buffer: wgpu::VertexBufferLayout<'static>,
+    samples: u32,
+    label: &str,
+) -> wgpu::RenderPipeline {
+    gpu.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some(entry_point),
+            buffers: &[buffer],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: gpu.surface_format(),
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode: None, // 2D sprites are double-sided
+            polygon_mode: wgpu::PolygonMode::Fill,
+            unclipped_depth: false,
+            conservative: false,
+        },
+        depth_stencil: None,
+        multisample: multisample_state(samples),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_sprite_variants_compile() {
+        use wgpu::naga::valid::{Capabilities, ValidationFlags, Validator};
+
+        // wgpu grants both with SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING.
+        let arrays = Capabilities::SAMPLED_TEXTURE_AND_STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING
+            | Capabilities::SAMPLER_NON_UNIFORM_INDEXING;
+        for (defs, capabilities) in [
+            (ShaderDefs::new(), Capabilities::default()),
+            (ShaderDefs::new().with("TEXTURE_ARRAY"), Capabilities::default() | arrays),
+        ] {
+            let source = preprocess(SPRITE_SOURCE, &defs).unwrap();
+            let module = wgpu::naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|err| panic!("{defs:?}: {}", err.emit_to_string(&source)));
+            Validator::new(ValidationFlags::all(), capabilities)
+                .validate(&module)
+                .unwrap_or_else(|err| panic!("{defs:?}: {err:?}"));
+        }
+    }
+
+    #[test]
+    fn sdf_text_shader_compiles() {
+        use wgpu::naga::valid::{Capabilities, ValidationFlags, Validator};
 
-        gpu.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("sprite pipeline (hot-reload)"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: shader,
-                entry_point: Some("vs_main"),
-                buffers: &[SpriteVertex::LAYOUT],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.surface_format(),
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        })
+        let module = wgpu::naga::front::wgsl::parse_str(SDF_TEXT_SOURCE)
+            .unwrap_or_else(|err| panic!("{}", err.emit_to_string(SDF_TEXT_SOURCE)));
+        Validator::new(ValidationFlags::all(), Capabilities::default())
+            .validate(&module)
+            .unwrap_or_else(|err| panic!("{err:?}"));
     }
 }
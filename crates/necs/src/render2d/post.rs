@@ -0,0 +1,773 @@
+//! # Post Effects — Fullscreen Passes Over the 2D Scene
+//!
+//! Some looks can't be drawn sprite by sprite: darkened screen corners, the
+//! color fringes of a cheap lens, a shaking camera, a Game Boy palette. They
+//! work on the finished image instead. With a [`PostEffects2d`] resource the
+//! scene renders into an offscreen texture, then each enabled effect runs as
+//! a fullscreen pass reading the previous result:
+//!
+//! ```text
+//!   scene ──► offscreen ──► [vignette] ──► [shake] ──► [custom] ──► frame
+//!                              ping ─────► pong ─────► ping ...
+//! ```
+//!
+//! Effects run in list order and can be switched off or moved at runtime;
+//! the last enabled one writes straight into the frame's target. With none
+//! enabled the offscreen pass is skipped entirely.
+//!
+//! ```ignore
+//! Game::new("Arcade")
+//!     .resource(
+//!         PostEffects2d::new()
+//!             .with(ScreenShake::default())
+//!             .with(ChromaticAberration::default())
+//!             .with(Vignette::default()),
+//!     )
+//!     .run();
+//!
+//! // On a hit:
+//! ctx.world.resource_mut::<PostEffects2d>().shake(0.6);
+//! ```
+//!
+//! The effects apply to the whole scene, 3D included, before
+//! [color grading](crate::render::ColorGrading); the UI draws on top
+//! unaffected.
+//!
+//! ## Custom Effects
+//!
+//! A [`CustomEffect`] is a WGSL `fs_main` fragment shader. It is appended to
+//! `render2d/post_prelude.wgsl`, which provides the input and parameters:
+//!
+//! ```text
+//! @fragment
+//! fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+//!     let color = textureSample(source, source_sampler, in.uv);
+//!     let gray = dot(color.rgb, vec3(0.299, 0.587, 0.114));
+//!     return vec4(mix(color.rgb, vec3(gray), params.values.x), color.a);
+//! }
+//! ```
+//!
+//! A shader that fails to compile is logged once and skipped.
+//!
+//! ## Comparison
+//!
+//! - **Unity** (URP): Volume overrides (Vignette, Chromatic Aberration, ...)
+//!   in a fixed order; custom passes through `ScriptableRendererFeature`.
+//! - **Godot**: `CanvasLayer` + `ColorRect` with a screen-reading shader per
+//!   effect, ordered by layer.
+//! - **Bevy**: Post-process nodes in the render graph; custom ones need a
+//!   render node and pipeline of their own.
+//! - **Our approach**: Godot's ordered list of fullscreen shaders, as one
+//!   resource with built-ins for the common effects.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+use super::Color;
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+use crate::render::pass::FrameContext;
+
+/// Most [`PaletteSwap`] entries used; further ones are ignored.
+pub const MAX_PALETTE_SWAPS: usize = 16;
+
+/// Shared bindings and vertex shader, prepended to every effect.
+const PRELUDE: &str = include_str!("post_prelude.wgsl");
+
+// ── Effects ─────────────────────────────────────────────────────────────
+
+/// Darken (or tint) the screen toward its corners.
+#[derive(Debug, Clone, Copy)]
+pub struct Vignette {
+    /// How far the corners move toward `color`, 0–1.
+    pub intensity: f32,
+    /// Distance from the center where darkening starts: 0 is the center,
+    /// 1 the corners.
+    pub radius: f32,
+    /// Width of the fade from `radius` outward.
+    pub smoothness: f32,
+    /// Color the edges fade to; its alpha scales `intensity`.
+    pub color: Color,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            intensity: 0.6,
+            radius: 0.5,
+            smoothness: 0.5,
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// Split red and blue apart toward the screen edges, like a cheap lens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChromaticAberration {
+    /// Channel offset at the screen edges, in pixels.
+    pub strength: f32,
+}
+
+impl Default for ChromaticAberration {
+    fn default() -> Self {
+        Self { strength: 3.0 }
+    }
+}
+
+/// Trauma-based screen shake: [`add_trauma`](Self::add_trauma) on impacts,
+/// and the image jitters by `max_offset × trauma²` while trauma decays.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScreenShake {
+    /// Current shake amount, 0–1.
+    pub trauma: f32,
+    /// Offset at full trauma, in pixels.
+    pub max_offset: f32,
+    /// How fast the offset changes, in oscillations per second.
+    pub frequency: f32,
+    /// Trauma lost per second.
+    pub decay: f32,
+}
+
+impl Default for ScreenShake {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            max_offset: 16.0,
+            frequency: 15.0,
+            decay: 1.5,
+        }
+    }
+}
+
+impl ScreenShake {
+    /// Add to the trauma, capped at 1.
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.trauma = (self.trauma + amount).clamp(0.0, 1.0);
+    }
+
+    /// The image offset in pixels at `time` seconds. Layered sines at
+    /// unrelated rates, so the motion doesn't visibly repeat.
+    pub fn offset(&self, time: f32) -> [f32; 2] {
+        let t = time * self.frequency;
+        let wave = |phase: f32| {
+            (t + phase).sin() * 0.5 + (t * 2.3 + phase * 1.7).sin() * 0.3 + (t * 4.1 + phase * 3.1).sin() * 0.2
+        };
+        let amount = self.max_offset * self.trauma * self.trauma;
+        [wave(0.0) * amount, wave(11.0) * amount]
+    }
+}
+
+/// Replace colors: pixels within `tolerance` of a swap's first color become
+/// its second. Colors are display values, as picked in a paint program.
+#[derive(Debug, Clone)]
+pub struct PaletteSwap {
+    /// `(from, to)` pairs; the first match wins. At most
+    /// [`MAX_PALETTE_SWAPS`] are used.
+    pub swaps: Vec<(Color, Color)>,
+    /// Largest per-channel difference that still matches.
+    pub tolerance: f32,
+}
+
+impl Default for PaletteSwap {
+    fn default() -> Self {
+        Self {
+            swaps: Vec::new(),
+            tolerance: 0.02,
+        }
+    }
+}
+
+impl PaletteSwap {
+    /// An empty swap list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a swap (builder pattern).
+    pub fn swap(mut self, from: Color, to: Color) -> Self {
+        self.swaps.push((from, to));
+        self
+    }
+
+    /// Set the match tolerance (builder pattern).
+    pub fn tolerance(mut self, tolerance: f32) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+/// A user WGSL fragment shader. See the [module docs](self#custom-effects).
+#[derive(Debug, Clone)]
+pub struct CustomEffect {
+    source: Arc<str>,
+    /// Passed to the shader as `params.values`.
+    pub values: [f32; 4],
+    /// Passed to the shader as `params.color`.
+    pub color: Color,
+}
+
+impl CustomEffect {
+    /// An effect from WGSL source defining `fs_main`.
+    pub fn new(wgsl: &str) -> Self {
+        Self {
+            source: wgsl.into(),
+            values: [0.0; 4],
+            color: Color::WHITE,
+        }
+    }
+
+    /// Read the WGSL source from a file.
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        std::fs::read_to_string(path).map(|source| Self::new(&source))
+    }
+
+    /// Set `params.values` (builder pattern).
+    pub fn values(mut self, values: [f32; 4]) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Set `params.color` (builder pattern).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The WGSL source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+/// One fullscreen effect in a [`PostEffects2d`] chain.
+#[derive(Debug, Clone)]
+pub enum PostEffect {
+    Vignette(Vignette),
+    ChromaticAberration(ChromaticAberration),
+    ScreenShake(ScreenShake),
+    PaletteSwap(PaletteSwap),
+    Custom(CustomEffect),
+}
+
+impl From<Vignette> for PostEffect {
+    fn from(effect: Vignette) -> Self {
+        Self::Vignette(effect)
+    }
+}
+
+impl From<ChromaticAberration> for PostEffect {
+    fn from(effect: ChromaticAberration) -> Self {
+        Self::ChromaticAberration(effect)
+    }
+}
+
+impl From<ScreenShake> for PostEffect {
+    fn from(effect: ScreenShake) -> Self {
+        Self::ScreenShake(effect)
+    }
+}
+
+impl From<PaletteSwap> for PostEffect {
+    fn from(effect: PaletteSwap) -> Self {
+        Self::PaletteSwap(effect)
+    }
+}
+
+impl From<CustomEffect> for PostEffect {
+    fn from(effect: CustomEffect) -> Self {
+        Self::Custom(effect)
+    }
+}
+
+/// An effect and whether it runs.
+#[derive(Debug, Clone)]
+pub struct PostEffectSlot {
+    pub effect: PostEffect,
+    pub enabled: bool,
+}
+
+/// Resource: the post effects run over the scene, in order. See the
+/// [module docs](self).
+#[derive(Debug, Clone, Default)]
+pub struct PostEffects2d {
+    /// The chain, first to last. Reorder or edit freely.
+    pub effects: Vec<PostEffectSlot>,
+}
+
+impl PostEffects2d {
+    /// An empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append an enabled effect (builder pattern).
+    pub fn with(mut self, effect: impl Into<PostEffect>) -> Self {
+        self.push(effect);
+        self
+    }
+
+    /// Append an enabled effect, returning its index.
+    pub fn push(&mut self, effect: impl Into<PostEffect>) -> usize {
+        self.effects.push(PostEffectSlot {
+            effect: effect.into(),
+            enabled: true,
+        });
+        self.effects.len() - 1
+    }
+
+    /// Switch the effect at `index` on or off. Out-of-range indices are
+    /// ignored.
+    pub fn set_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(slot) = self.effects.get_mut(index) {
+            slot.enabled = enabled;
+        }
+    }
+
+    /// Move the effect at `from` to position `to`, shifting the ones in
+    /// between. Out-of-range indices are ignored.
+    pub fn move_effect(&mut self, from: usize, to: usize) {
+        if from < self.effects.len() && to < self.effects.len() {
+            let slot = self.effects.remove(from);
+            self.effects.insert(to, slot);
+        }
+    }
+
+    /// Add trauma to every [`ScreenShake`] in the chain.
+    pub fn shake(&mut self, trauma: f32) {
+        for slot in &mut self.effects {
+            if let PostEffect::ScreenShake(shake) = &mut slot.effect {
+                shake.add_trauma(trauma);
+            }
+        }
+    }
+
+    /// Whether any effect is enabled.
+    pub fn is_active(&self) -> bool {
+        self.effects.iter().any(|slot| slot.enabled)
+    }
+}
+
+// ── GPU side ────────────────────────────────────────────────────────────
+
+/// Mirrors `PostParams` in post_prelude.wgsl.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct EffectParams {
+    resolution: [f32; 2],
+    time: f32,
+    srgb: u32,
+    values: [f32; 4],
+    color: [f32; 4],
+    swap_count: u32,
+    _pad: [u32; 3],
+    swap_from: [[f32; 4]; MAX_PALETTE_SWAPS],
+    swap_to: [[f32; 4]; MAX_PALETTE_SWAPS],
+}
+
+impl EffectParams {
+    /// Parameters for `effect` at `time`, on a `resolution`-sized target.
+    fn new(effect: &PostEffect, resolution: [f32; 2], time: f32, srgb: bool) -> Self {
+        let mut params = Self {
+            resolution,
+            time,
+            srgb: srgb as u32,
+            values: [0.0; 4],
+            color: [0.0; 4],
+            swap_count: 0,
+            _pad: [0; 3],
+            swap_from: [[0.0; 4]; MAX_PALETTE_SWAPS],
+            swap_to: [[0.0; 4]; MAX_PALETTE_SWAPS],
+        };
+        match effect {
+            PostEffect::Vignette(vignette) => {
+                params.values = [vignette.intensity, vignette.radius, vignette.smoothness.max(1e-4), 0.0];
+                params.color = vignette.color.to_array();
+            }
+            PostEffect::ChromaticAberration(aberration) => params.values[0] = aberration.strength,
+            PostEffect::ScreenShake(shake) => {
+                let [x, y] = shake.offset(time);
+                params.values = [x, y, 0.0, 0.0];
+            }
+            PostEffect::PaletteSwap(palette) => {
+                params.values[0] = palette.tolerance;
+                for (i, (from, to)) in palette.swaps.iter().take(MAX_PALETTE_SWAPS).enumerate() {
+                    params.swap_from[i] = from.to_array();
+                    params.swap_to[i] = to.to_array();
+                    params.swap_count += 1;
+                }
+            }
+            PostEffect::Custom(custom) => {
+                params.values = custom.values;
+                params.color = custom.color.to_array();
+            }
+        }
+        params
+    }
+}
+
+/// Offscreen targets and pipelines for the effect chain. Created the first
+/// frame an effect is enabled.
+pub(crate) struct PostRenderer {
+    /// The scene renders here.
+    scene: wgpu::TextureView,
+    /// Ping-pong targets between effects, created as the chain needs them.
+    temps: [Option<wgpu::TextureView>; 2],
+    size: (u32, u32),
+    layout: wgpu::BindGroupLayout,
+    pipeline_layout: wgpu::PipelineLayout,
+    sampler: wgpu::Sampler,
+    vignette: wgpu::RenderPipeline,
+    chromatic: wgpu::RenderPipeline,
+    shake: wgpu::RenderPipeline,
+    palette: wgpu::RenderPipeline,
+    /// Custom effects by source; `None` if it didn't compile.
+    custom: HashMap<Arc<str>, Option<wgpu::RenderPipeline>>,
+    /// One parameter buffer per pass, grown as needed.
+    buffers: Vec<wgpu::Buffer>,
+}
+
+impl PostRenderer {
+    fn new(gpu: &GpuContext) -> Self {
+        let device = &gpu.device;
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("post effect layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("post effect pipeline layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("post effect shader"),
+            source: wgpu::ShaderSource::Wgsl(format!("{PRELUDE}\n{}", include_str!("post.wgsl")).into()),
+        });
+        let pipeline = |entry_point| create_pipeline(gpu, &pipeline_layout, &shader, entry_point);
+        let (vignette, chromatic) = (pipeline("fs_vignette"), pipeline("fs_chromatic"));
+        let (shake, palette) = (pipeline("fs_shake"), pipeline("fs_palette"));
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("post effect sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            scene: create_target(gpu, (1, 1)),
+            temps: [None, None],
+            size: (1, 1),
+            layout,
+            pipeline_layout,
+            sampler,
+            vignette,
+            chromatic,
+            shake,
+            palette,
+            custom: HashMap::new(),
+            buffers: Vec::new(),
+        }
+    }
+
+    /// Recreate the targets if the frame size changed.
+    fn resize_if_needed(&mut self, gpu: &GpuContext, size: (u32, u32)) {
+        if size != self.size {
+            self.scene = create_target(gpu, size);
+            self.temps = [None, None];
+            self.size = size;
+        }
+    }
+
+    /// Compile a custom effect the first time it's seen.
+    fn prepare_custom(&mut self, gpu: &GpuContext, source: &Arc<str>) {
+        if self.custom.contains_key(source) {
+            return;
+        }
+        gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("custom post effect"),
+            source: wgpu::ShaderSource::Wgsl(format!("{PRELUDE}\n{source}").into()),
+        });
+        let pipeline = create_pipeline(gpu, &self.pipeline_layout, &shader, "fs_main");
+        let pipeline = match pollster::block_on(gpu.device.pop_error_scope()) {
+            None => Some(pipeline),
+            Some(err) => {
+                log::warn!("Custom post effect failed to compile; skipping it: {err}");
+                None
+            }
+        };
+        self.custom.insert(source.clone(), pipeline);
+    }
+
+    /// The pipeline for `effect`; `None` for a custom effect that didn't
+    /// compile.
+    fn pipeline(&self, effect: &PostEffect) -> Option<&wgpu::RenderPipeline> {
+        match effect {
+            PostEffect::Vignette(_) => Some(&self.vignette),
+            PostEffect::ChromaticAberration(_) => Some(&self.chromatic),
+            PostEffect::ScreenShake(_) => Some(&self.shake),
+            PostEffect::PaletteSwap(_) => Some(&self.palette),
+            PostEffect::Custom(custom) => self.custom.get(&custom.source)?.as_ref(),
+        }
+    }
+
+    /// The ping-pong target for pass `index`, created on first use.
+    fn temp(&mut self, gpu: &GpuContext, index: usize) -> wgpu::TextureView {
+        let size = self.size;
+        self.temps[index % 2]
+            .get_or_insert_with(|| create_target(gpu, size))
+            .clone()
+    }
+}
+
+/// Let screen shake trauma decay by this frame's delta. Runs on the main
+/// thread before extraction, so rendering only ever reads the chain.
+pub(crate) fn decay_screen_shake(world: &mut World) {
+    let delta = world.get_resource::<crate::time::Time>().map_or(0.0, |time| time.delta_secs());
+    let Some(effects) = world.get_resource_mut::<PostEffects2d>() else {
+        return;
+    };
+    for slot in effects.effects.iter_mut().filter(|slot| slot.enabled) {
+        if let PostEffect::ScreenShake(shake) = &mut slot.effect {
+            shake.trauma = (shake.trauma - shake.decay * delta).max(0.0);
+        }
+    }
+}
+
+/// Redirect the scene into the offscreen target if any post effect is
+/// enabled. Call before [`begin_msaa`](crate::render::msaa::begin_msaa) so
+/// the samples resolve into it. Returns the view to hand back to
+/// [`finish_post_effects`].
+pub(crate) fn begin_post_effects(world: &mut World, frame: &mut FrameContext<'_>) -> Option<wgpu::TextureView> {
+    if !world.get_resource::<PostEffects2d>().is_some_and(PostEffects2d::is_active) {
+        return None;
+    }
+    if !world.has_resource::<PostRenderer>() {
+        world.insert_resource(PostRenderer::new(frame.gpu));
+    }
+    let renderer = world.resource_mut::<PostRenderer>();
+    renderer.resize_if_needed(frame.gpu, frame.size);
+    Some(std::mem::replace(&mut frame.view, renderer.scene.clone()))
+}
+
+/// Run the enabled effects from the offscreen scene into `output`, and make
+/// `output` the frame's view again.
+pub(crate) fn finish_post_effects(world: &mut World, frame: &mut FrameContext<'_>, output: wgpu::TextureView) {
+    frame.view = output;
+    let gpu = frame.gpu;
+    let (Some(mut renderer), Some(effects)) =
+        (world.resource_remove::<PostRenderer>(), world.resource_remove::<PostEffects2d>())
+    else {
+        return;
+    };
+    let time = world.get_resource::<crate::time::Time>().map_or(0.0, |time| time.elapsed_secs());
+
+    for slot in effects.effects.iter().filter(|slot| slot.enabled) {
+        if let PostEffect::Custom(custom) = &slot.effect {
+            renderer.prepare_custom(gpu, &custom.source);
+        }
+    }
+    let resolution = [renderer.size.0 as f32, renderer.size.1 as f32];
+    let srgb = gpu.surface_format().is_srgb();
+    let mut passes: Vec<EffectParams> = Vec::new();
+    let mut pipelines = Vec::new();
+    for slot in effects.effects.iter().filter(|slot| slot.enabled) {
+        if let Some(pipeline) = renderer.pipeline(&slot.effect) {
+            passes.push(EffectParams::new(&slot.effect, resolution, time, srgb));
+            pipelines.push(pipeline.clone());
+        }
+    }
+    if passes.is_empty() {
+        // Every enabled effect failed to compile: a still shake copies.
+        passes.push(EffectParams::new(&ScreenShake::default().into(), resolution, time, srgb));
+        pipelines.push(renderer.shake.clone());
+    }
+    while renderer.buffers.len() < passes.len() {
+        renderer.buffers.push(gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("post effect params"),
+            size: std::mem::size_of::<EffectParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        }));
+    }
+
+    let mut source = renderer.scene.clone();
+    for (i, (params, pipeline)) in passes.iter().zip(&pipelines).enumerate() {
+        gpu.queue
+            .write_buffer(&renderer.buffers[i], 0, bytemuck::bytes_of(params));
+        let target = if i + 1 == passes.len() {
+            frame.view.clone()
+        } else {
+            renderer.temp(gpu, i)
+        };
+        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("post effect bind group"),
+            layout: &renderer.layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&renderer.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: renderer.buffers[i].as_entire_binding(),
+                },
+            ],
+        });
+        {
+            let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("post effect pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        source = target;
+    }
+
+    world.insert_resource(effects);
+    world.insert_resource(renderer);
+}
+
+/// A surface-format texture the scene or an effect renders into.
+fn create_target(gpu: &GpuContext, (width, height): (u32, u32)) -> wgpu::TextureView {
+    gpu.device
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("post effect target"),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: gpu.surface_format(),
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        })
+        .create_view(&wgpu::TextureViewDescriptor::default())
+}
+
+/// A fullscreen pipeline running `entry_point` of `shader`.
+fn create_pipeline(
+    gpu: &GpuContext,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    entry_point: &str,
+) -> wgpu::RenderPipeline {
+    gpu.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(entry_point),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(entry_point),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: gpu.surface_format(),
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chain_toggles_reorders_and_shakes() {
+        let mut effects = PostEffects2d::new()
+            .with(Vignette::default())
+            .with(ScreenShake::default());
+        let custom = effects.push(CustomEffect::new("// noop"));
+        assert_eq!(custom, 2);
+
+        effects.move_effect(2, 0);
+        assert!(matches!(effects.effects[0].effect, PostEffect::Custom(_)));
+        for i in 0..3 {
+            effects.set_enabled(i, false);
+        }
+        assert!(!effects.is_active());
+
+        effects.shake(0.7);
+        effects.shake(0.7);
+        let PostEffect::ScreenShake(shake) = &effects.effects[2].effect else {
+            panic!("shake moved");
+        };
+        assert_eq!(shake.trauma, 1.0);
+        // Full trauma stays within max_offset; none doesn't move at all.
+        let [x, y] = shake.offset(0.37);
+        assert!(x.abs() <= shake.max_offset && y.abs() <= shake.max_offset && (x, y) != (0.0, 0.0));
+        assert_eq!(ScreenShake::default().offset(0.37), [0.0, 0.0]);
+    }
+
+    #[test]
+    fn palette_params_cap_the_swap_count() {
+        let mut palette = PaletteSwap::new().tolerance(0.1);
+        for _ in 0..MAX_PALETTE_SWAPS + 4 {
+            palette = palette.swap(Color::RED, Color::BLUE);
+        }
+        let params = EffectParams::new(&palette.into(), [320.0, 180.0], 0.0, true);
+        assert_eq!(params.swap_count as usize, MAX_PALETTE_SWAPS);
+        assert_eq!((params.values[0], params.swap_to[3]), (0.1, [0.0, 0.0, 1.0, 1.0]));
+    }
+}
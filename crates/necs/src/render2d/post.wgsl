@@ -0,0 +1,54 @@
+// Built-in 2D post effects, appended to post_prelude.wgsl. One entry point
+// per effect; `params.values` is documented on each.
+
+// values: (intensity, radius, smoothness, -). Darkens toward `params.color`
+// outside an ellipse matching the screen's aspect.
+@fragment
+fn fs_vignette(in: VertexOutput) -> @location(0) vec4<f32> {
+    let scene = textureSample(source, source_sampler, in.uv);
+    let distance = length((in.uv - 0.5) * 2.0) / sqrt(2.0);
+    let edge = smoothstep(params.values.y, params.values.y + params.values.z, distance);
+    let amount = edge * params.values.x * params.color.a;
+    return vec4(mix(scene.rgb, params.color.rgb, amount), scene.a);
+}
+
+// values: (strength in pixels, -, -, -). Red and blue are sampled pushed
+// out and pulled in from the center; more so toward the edges.
+@fragment
+fn fs_chromatic(in: VertexOutput) -> @location(0) vec4<f32> {
+    let direction = (in.uv - 0.5) * 2.0;
+    let offset = direction * params.values.x / params.resolution;
+    let red = textureSample(source, source_sampler, in.uv + offset).r;
+    let center = textureSample(source, source_sampler, in.uv);
+    let blue = textureSample(source, source_sampler, in.uv - offset).b;
+    return vec4(red, center.g, blue, center.a);
+}
+
+// values: (offset x, offset y in pixels, -, -). The offset is picked on the
+// CPU each frame; edges stretch the border pixels.
+@fragment
+fn fs_shake(in: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(source, source_sampler, in.uv - params.values.xy / params.resolution);
+}
+
+// values: (tolerance, -, -, -). Colors within `tolerance` of `swap_from[i]`
+// (display values, per channel) become `swap_to[i]`; first match wins.
+@fragment
+fn fs_palette(in: VertexOutput) -> @location(0) vec4<f32> {
+    let scene = textureSample(source, source_sampler, in.uv);
+    var color = scene.rgb;
+    if params.srgb == 1u {
+        color = linear_to_srgb(color);
+    }
+    for (var i = 0u; i < min(params.swap_count, 16u); i++) {
+        let difference = abs(color - params.swap_from[i].rgb);
+        if max(difference.r, max(difference.g, difference.b)) <= params.values.x {
+            color = params.swap_to[i].rgb;
+            break;
+        }
+    }
+    if params.srgb == 1u {
+        color = srgb_to_linear(color);
+    }
+    return vec4(color, scene.a);
+}
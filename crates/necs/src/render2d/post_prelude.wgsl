@@ -0,0 +1,54 @@
+// Shared by every 2D post effect, built-in or custom: the previous result
+// of the chain, the effect's parameters, and a fullscreen vertex shader.
+// Custom effects are appended to this and supply `fs_main`.
+
+struct PostParams {
+    // Target size in pixels.
+    resolution: vec2<f32>,
+    // Seconds since startup.
+    time: f32,
+    // 1 when the target is sRGB: sampled colors are linear, not display.
+    srgb: u32,
+    // Effect-specific numbers (see each effect; `CustomEffect::values`).
+    values: vec4<f32>,
+    // Effect-specific color (`CustomEffect::color`).
+    color: vec4<f32>,
+    // Palette swap: number of used entries in `swap_from`/`swap_to`.
+    swap_count: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+    swap_from: array<vec4<f32>, 16>,
+    swap_to: array<vec4<f32>, 16>,
+}
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: PostParams;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// One oversized triangle covers the whole target — no vertex buffer needed.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let xy = vec2(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4(xy, 0.0, 1.0);
+    out.uv = vec2(xy.x * 0.5 + 0.5, 0.5 - xy.y * 0.5);
+    return out;
+}
+
+fn linear_to_srgb(c: vec3<f32>) -> vec3<f32> {
+    let low = c * 12.92;
+    let high = 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055;
+    return select(high, low, c <= vec3(0.0031308));
+}
+
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3(2.4));
+    return select(high, low, c <= vec3(0.04045));
+}
@@ -0,0 +1,188 @@
+//! # ProgressBar2d — World-Space Bars Above Entities
+//!
+//! Health over an enemy's head, a build timer over a workshop, a reload
+//! ring's flat cousin: a small bar that follows something in the world.
+//! [`ProgressBar2d`] draws one from at most three solid quads through the
+//! sprite batcher, so a hundred bars cost no more draw calls than a hundred
+//! untextured sprites:
+//!
+//! ```text
+//!   ┌──────────────────────┐  border   (optional, drawn first)
+//!   │█████████████░░░░░░░░░│  fill     value × width, from the left edge
+//!   └──────────────────────┘  background the full size
+//!              ▲
+//!              │ offset
+//!              ● owner's position
+//! ```
+//!
+//! The bar is placed at its owner's world position plus `offset`, and is
+//! never rotated or scaled with it: a spinning enemy keeps a level bar.
+//! Without an owner, the bar sits at its own entity's
+//! [`GlobalTransform`](crate::ecs::GlobalTransform). It draws just in front
+//! of whatever it follows (`BAR_Z_BIAS` above the anchor's Z), and is
+//! skipped while the owner is hidden or despawned.
+//!
+//! ```ignore
+//! let enemy = world.spawn((Transform::from_xy(200.0, 80.0), Sprite::new(goblin)));
+//! world.spawn(
+//!     ProgressBar2d::new(40.0, 5.0)
+//!         .following(enemy)
+//!         .offset(0.0, 28.0)
+//!         .border(1.0, Color::BLACK),
+//! );
+//!
+//! // Later, when the goblin takes damage:
+//! bar.set_value(hp / max_hp);
+//! ```
+//!
+//! Keeping the bar on its own entity, rather than as a component of the
+//! enemy, means hiding or despawning the bar never touches the enemy, and an
+//! entity can have several (health and shield).
+//!
+//! ## Comparison
+//!
+//! - **Unity**: A world-space Canvas with a `Slider` or a filled `Image`
+//!   per enemy, usually behind a billboard script.
+//! - **Bevy**: No built-in bar; people spawn two child sprites and scale
+//!   one of them by hand.
+//! - **Godot**: `ProgressBar` or `TextureProgressBar` in a child `Control`,
+//!   positioned each frame from the owner's screen position.
+//! - **Our approach**: One component drawn as plain quads in world space,
+//!   batched with sprites and culled with them.
+
+use super::Color;
+use crate::ecs::Entity;
+use crate::math::{Rect, Vec2};
+
+/// How far in front of its anchor a bar is drawn, so it sorts over the
+/// sprite it follows.
+pub(crate) const BAR_Z_BIAS: f32 = 0.01;
+
+/// A world-space progress bar, e.g. a health bar above an enemy. See the
+/// [module docs](self).
+#[derive(Debug, Clone)]
+pub struct ProgressBar2d {
+    /// How full the bar is, `0.0..=1.0`. Values outside are clamped when drawn.
+    pub value: f32,
+    /// Width and height of the background, in world units.
+    pub size: Vec2,
+    pub fill: Color,
+    pub background: Color,
+    /// Border width and color, drawn around the background.
+    pub border: Option<(f32, Color)>,
+    /// Position of the bar's center relative to its anchor.
+    pub offset: Vec2,
+    /// Entity whose position the bar follows; `None` uses the bar's own
+    /// transform.
+    pub owner: Option<Entity>,
+}
+
+impl Default for ProgressBar2d {
+    fn default() -> Self {
+        Self::new(32.0, 4.0)
+    }
+}
+
+impl ProgressBar2d {
+    /// A full bar `width` by `height`: green on a translucent black
+    /// background, without a border.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            value: 1.0,
+            size: Vec2::new(width, height),
+            fill: Color::rgb(0.2, 0.8, 0.3),
+            background: Color::rgba(0.0, 0.0, 0.0, 0.6),
+            border: None,
+            offset: Vec2::ZERO,
+            owner: None,
+        }
+    }
+
+    /// Start at `value` instead of full.
+    pub fn value(mut self, value: f32) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Set the fill and background colors.
+    pub fn colors(mut self, fill: Color, background: Color) -> Self {
+        self.fill = fill;
+        self.background = background;
+        self
+    }
+
+    /// Draw a border `width` units wide around the background.
+    pub fn border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some((width, color));
+        self
+    }
+
+    /// Place the bar's center at `(x, y)` from its anchor.
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vec2::new(x, y);
+        self
+    }
+
+    /// Follow `owner`'s position instead of the bar's own transform.
+    pub fn following(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Set how full the bar is, clamped to `0.0..=1.0`.
+    pub fn set_value(&mut self, value: f32) {
+        self.value = value.clamp(0.0, 1.0);
+    }
+
+    /// The quads to draw, back to front, centered on the bar's position:
+    /// border, background, fill. Empty parts are left out.
+    pub(crate) fn quads(&self) -> Vec<(Rect, Color)> {
+        let half = self.size * 0.5;
+        let background = Rect { min: -half, max: half };
+        let mut quads = Vec::with_capacity(3);
+        if let Some((width, color)) = self.border
+            && width > 0.0
+        {
+            let outer = Rect {
+                min: background.min - Vec2::splat(width),
+                max: background.max + Vec2::splat(width),
+            };
+            quads.push((outer, color));
+        }
+        quads.push((background, self.background));
+        let value = if self.value.is_nan() { 0.0 } else { self.value.clamp(0.0, 1.0) };
+        if value > 0.0 {
+            let fill = Rect {
+                min: background.min,
+                max: Vec2::new(background.min.x + self.size.x * value, background.max.y),
+            };
+            quads.push((fill, self.fill));
+        }
+        quads
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fill_grows_from_the_left_inside_the_border() {
+        let bar = ProgressBar2d::new(40.0, 4.0).value(0.25).border(1.0, Color::BLACK);
+        let quads = bar.quads();
+        assert_eq!(quads.len(), 3);
+        let (border, background, fill) = (quads[0].0, quads[1].0, quads[2].0);
+        assert_eq!(border.min, Vec2::new(-21.0, -3.0));
+        assert_eq!(border.max, Vec2::new(21.0, 3.0));
+        assert_eq!(background.min, Vec2::new(-20.0, -2.0));
+        assert_eq!(fill.min, background.min);
+        assert_eq!(fill.max, Vec2::new(-10.0, 2.0));
+
+        // Empty and over-full bars: no fill quad, and a fill the full width.
+        let mut bar = ProgressBar2d::new(40.0, 4.0);
+        bar.value = 0.0;
+        assert_eq!(bar.quads().len(), 1);
+        bar.value = 3.0;
+        assert_eq!(bar.quads()[1].0.max.x, 20.0);
+    }
+}
@@ -0,0 +1,555 @@
+//! # SDF / MSDF Fonts — Text That Stays Sharp When Scaled
+//!
+//! A bitmap glyph stores how much of each pixel the glyph covers. Scale it
+//! up and bilinear filtering smears those few pixels into a blur. A
+//! *signed distance field* (SDF) instead stores, per texel, how far that
+//! texel is from the glyph's edge. Filtering distances gives distances, so
+//! the shader can find the edge between texels and draw it one screen pixel
+//! wide at any zoom:
+//!
+//! ```text
+//!   coverage (bitmap)             distance field, range 4
+//!   ┌───┬───┬───┬───┐             ┌───┬───┬───┬───┐
+//!   │ 0 │ 0 │ 64│255│             │.12│.31│.52│.75│   0.5 = the edge
+//!   ├───┼───┼───┼───┤             ├───┼───┼───┼───┤   > 0.5 inside
+//!   │ 0 │ 0 │128│255│             │.14│.33│.55│.80│   < 0.5 outside
+//!   └───┴───┴───┴───┘             └───┴───┴───┴───┘
+//!   scaled 8×: soft ramp          scaled 8×: edge found between
+//!   8 pixels wide                 texels, 1 pixel wide
+//! ```
+//!
+//! The *distance range* is how many atlas pixels the stored values span:
+//! 0 is `range / 2` pixels outside the edge, 1 is `range / 2` inside. The
+//! shader converts it into screen pixels from the UV derivatives, so the
+//! anti-aliased band is always about one pixel wide.
+//!
+//! Because the distance is known around each glyph, styling is cheap:
+//!
+//! - [`TextOutline`](super::font::TextOutline): a second edge moved
+//!   outward by the outline width, filled with the outline color.
+//! - [`TextShadow`](super::font::TextShadow): the glyphs again, offset and
+//!   tinted, with `softness` widening the edge into a blur.
+//!
+//! Both reach at most `range / 2` beyond the glyph, where the field ends.
+//!
+//! ## Two Sources
+//!
+//! - [`load_sdf_font`] generates single-channel SDFs from a TTF/OTF at load
+//!   time, and like bitmap fonts adds new characters on demand. Each glyph
+//!   is rasterized at 4× its size, split into inside and outside, and run
+//!   through an exact Euclidean distance transform (Felzenszwalb &
+//!   Huttenlocher). The distance goes in the atlas' alpha channel.
+//! - [`load_msdf_font`] loads an atlas built ahead of time by
+//!   [msdf-atlas-gen](https://github.com/Chlumsky/msdf-atlas-gen). A
+//!   *multi-channel* SDF stores three distances to different edge segments
+//!   in RGB; their median keeps sharp corners that a single channel rounds
+//!   off. Only the glyphs in the atlas exist.
+//!
+//! ```text
+//!   msdf-atlas-gen -font Inter.ttf -type msdf -size 48 -pxrange 6 \
+//!                  -imageout inter.png -json inter.json
+//!
+//!   let inter = load_msdf_font(world, "fonts/inter.png", "fonts/inter.json");
+//! ```
+//!
+//! Distance-field fonts draw [`Text`](super::font::Text) through
+//! `sdf_text.wgsl`. [`UiText`](crate::ui::UiText) and 3D labels still use the
+//! bitmap shaders, where a generated SDF shows as soft, slightly bold glyphs;
+//! give those a bitmap font.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: TextMeshPro — SDF atlases generated in the editor or at run
+//!   time, with outline, underlay (shadow) and glow in the material.
+//! - **Bevy**: Bitmap glyphs cached per size; SDF text is left to
+//!   third-party crates.
+//! - **Godot**: `FontFile.multichannel_signed_distance_field` generates MSDF
+//!   glyphs on demand; outline and shadow are `Label` theme settings.
+//! - **Our approach**: Generated single-channel SDFs for any TTF, or MSDF
+//!   atlases from msdf-atlas-gen; outline and shadow live on `Text` and
+//!   become per-vertex shader parameters, so styled text still batches.
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+use crate::ecs::World;
+use crate::render::GpuContext;
+
+use super::font::{
+    DistanceField, FontEntry, FontHandle, FontStore, GlyphBitmap, GlyphInfo, ensure_stores, push_font,
+    rasterize_font, upload_font_atlas,
+};
+use super::atlas::TextureAtlasing;
+use super::pipeline::SpriteRenderer;
+use super::texture::TextureStore;
+
+/// Generated glyphs are rasterized this many times larger before the
+/// distance transform, then averaged back down.
+const SUPERSAMPLE: usize = 4;
+
+/// Squared distances used for "no such pixel" by the transform.
+const FAR: f64 = 1e20;
+
+// ── Loading ─────────────────────────────────────────────────────────────
+
+/// Load a TTF/OTF font as signed distance fields, for text that stays
+/// sharp when scaled. `size` is the pixel size glyphs are generated at,
+/// which [`TextSpan::size`](super::font::TextSpan) scales from like any
+/// font; 32–64 works for most text.
+///
+/// The distance range is a quarter of `size` (at least 4 pixels), which
+/// also caps outline width and shadow softness at an eighth of it.
+pub fn load_sdf_font(world: &mut World, path: &str, size: f32) -> FontHandle {
+    let path = crate::launch::resolve_asset_path(world, path);
+    let path = path.as_ref();
+    ensure_stores(world);
+
+    let range = (size / 4.0).max(4.0);
+    let font_data = std::fs::read(path).unwrap_or_else(|e| panic!("Failed to read font '{}': {}", path, e));
+    let font = rasterize_font(font_data, size, Some(range))
+        .unwrap_or_else(|e| panic!("Failed to parse font '{}': {}", path, e));
+    push_font(
+        world,
+        font,
+        Some(DistanceField {
+            range,
+            multi_channel: false,
+        }),
+    )
+}
+
+/// Load a multi-channel SDF atlas made by msdf-atlas-gen: the atlas image
+/// (`-imageout`) and its JSON layout (`-json`). Types `msdf`, `mtsdf` and
+/// `sdf` work. Characters missing from the atlas aren't drawn.
+pub fn load_msdf_font(world: &mut World, image_path: &str, layout_path: &str) -> FontHandle {
+    let image_path = crate::launch::resolve_asset_path(world, image_path).into_owned();
+    let layout_path = crate::launch::resolve_asset_path(world, layout_path).into_owned();
+    ensure_stores(world);
+
+    let json = std::fs::read_to_string(&layout_path)
+        .unwrap_or_else(|e| panic!("Failed to read MSDF layout '{}': {}", layout_path, e));
+    let layout = parse_msdf_layout(&json)
+        .unwrap_or_else(|e| panic!("Failed to parse MSDF layout '{}': {}", layout_path, e));
+    let mut image = image::open(&image_path)
+        .unwrap_or_else(|e| panic!("Failed to load MSDF atlas '{}': {}", image_path, e))
+        .to_rgba8();
+    let (width, height) = image.dimensions();
+    if (width, height) != (layout.atlas.width, layout.atlas.height) {
+        log::warn!(
+            "MSDF atlas '{}' is {}x{}, but its layout says {}x{}",
+            image_path,
+            width,
+            height,
+            layout.atlas.width,
+            layout.atlas.height
+        );
+    }
+    encode_srgb(&mut image);
+
+    let mut texture_store = world
+        .resource_remove::<TextureStore>()
+        .expect("TextureStore missing");
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+    let atlas_handle =
+        upload_font_atlas(gpu, renderer, &mut texture_store, &atlasing, &image, width, height);
+    world.insert_resource(texture_store);
+
+    let (glyphs, extra_glyphs) = msdf_glyphs(&layout);
+    world.resource_mut::<FontStore>().push(FontEntry {
+        glyphs,
+        extra_glyphs,
+        atlas_handle,
+        line_height: layout.metrics.line_height * layout.px_per_unit(),
+        cache: None,
+        distance_field: Some(DistanceField {
+            range: layout.atlas.distance_range,
+            multi_channel: true,
+        }),
+    })
+}
+
+/// Atlases are sampled as sRGB, which would bend stored distances. Encode
+/// the color channels so that decoding gives back the values written by
+/// msdf-atlas-gen. Alpha isn't converted.
+fn encode_srgb(image: &mut image::RgbaImage) {
+    let table: Vec<u8> = (0..=255u8)
+        .map(|v| {
+            let linear = v as f32 / 255.0;
+            let srgb = if linear <= 0.003_130_8 {
+                linear * 12.92
+            } else {
+                1.055 * linear.powf(1.0 / 2.4) - 0.055
+            };
+            (srgb * 255.0).round() as u8
+        })
+        .collect();
+    for pixel in image.pixels_mut() {
+        for channel in &mut pixel.0[..3] {
+            *channel = table[*channel as usize];
+        }
+    }
+}
+
+// ── msdf-atlas-gen layout ───────────────────────────────────────────────
+
+/// The parts of msdf-atlas-gen's JSON we use.
+#[derive(Deserialize)]
+struct MsdfLayout {
+    atlas: MsdfAtlas,
+    metrics: MsdfMetrics,
+    glyphs: Vec<MsdfGlyph>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfAtlas {
+    distance_range: f32,
+    /// Pixels per em.
+    size: f32,
+    width: u32,
+    height: u32,
+    #[serde(default)]
+    y_origin: YOrigin,
+}
+
+/// Whether `atlasBounds` (and `planeBounds`) count Y up from the bottom or
+/// down from the top.
+#[derive(Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum YOrigin {
+    #[default]
+    Bottom,
+    Top,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfMetrics {
+    #[serde(default = "one")]
+    em_size: f32,
+    line_height: f32,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MsdfGlyph {
+    /// Missing when the atlas was generated by glyph index.
+    unicode: Option<u32>,
+    advance: f32,
+    /// Quad around the pen position, in em units. Missing for whitespace.
+    plane_bounds: Option<Bounds>,
+    /// The glyph's rectangle in the atlas, in pixels.
+    atlas_bounds: Option<Bounds>,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+struct Bounds {
+    left: f32,
+    bottom: f32,
+    right: f32,
+    top: f32,
+}
+
+impl MsdfLayout {
+    /// Layout units to pixels at the atlas' size.
+    fn px_per_unit(&self) -> f32 {
+        self.atlas.size / self.metrics.em_size
+    }
+}
+
+fn parse_msdf_layout(json: &str) -> Result<MsdfLayout, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+/// Glyph metrics and UVs from a layout, split like [`FontEntry`]'s: ASCII
+/// 32–126 by index, everything else by character.
+fn msdf_glyphs(layout: &MsdfLayout) -> (Vec<Option<GlyphInfo>>, HashMap<char, Option<GlyphInfo>>) {
+    let scale = layout.px_per_unit();
+    let (width, height) = (layout.atlas.width as f32, layout.atlas.height as f32);
+    let mut ascii = vec![None; 95];
+    let mut extra = HashMap::new();
+    for glyph in &layout.glyphs {
+        let Some(ch) = glyph.unicode.and_then(char::from_u32) else {
+            continue;
+        };
+        let mut info = GlyphInfo {
+            u_min: 0.0,
+            v_min: 0.0,
+            u_max: 0.0,
+            v_max: 0.0,
+            advance: glyph.advance * scale,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            width: 0.0,
+            height: 0.0,
+        };
+        if let (Some(plane), Some(atlas)) = (glyph.plane_bounds, glyph.atlas_bounds) {
+            // Our glyphs are Y-up from the baseline, V down from the top.
+            let (bottom, top, v_min, v_max) = match layout.atlas.y_origin {
+                YOrigin::Bottom => (plane.bottom, plane.top, 1.0 - atlas.top / height, 1.0 - atlas.bottom / height),
+                YOrigin::Top => (-plane.bottom, -plane.top, atlas.top / height, atlas.bottom / height),
+            };
+            info = GlyphInfo {
+                u_min: atlas.left / width,
+                v_min,
+                u_max: atlas.right / width,
+                v_max,
+                offset_x: plane.left * scale,
+                offset_y: bottom * scale,
+                width: (plane.right - plane.left) * scale,
+                height: (top - bottom) * scale,
+                ..info
+            };
+        }
+        match ch as u32 {
+            code @ 32..=126 => ascii[(code - 32) as usize] = Some(info),
+            _ => {
+                extra.insert(ch, Some(info));
+            }
+        }
+    }
+    (ascii, extra)
+}
+
+// ── Generation ──────────────────────────────────────────────────────────
+
+/// Rasterize `ch` at `px` as a signed distance field spanning `range`
+/// pixels, padded by half the range on every side so the field can fall
+/// to zero.
+pub(super) fn rasterize_sdf(font: &fontdue::Font, ch: char, px: f32, range: f32) -> GlyphBitmap {
+    let advance = font.metrics(ch, px).advance_width;
+    let (metrics, coverage) = font.rasterize(ch, px * SUPERSAMPLE as f32);
+    if metrics.width == 0 || metrics.height == 0 {
+        return GlyphBitmap {
+            width: 0,
+            height: 0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            advance,
+            alpha: Vec::new(),
+        };
+    }
+
+    // Supersampled grid: the glyph `pad` from the left and bottom, rounded
+    // up to whole output pixels.
+    let pad = (range * 0.5 * SUPERSAMPLE as f32).ceil() as usize;
+    let out_w = (metrics.width + 2 * pad).div_ceil(SUPERSAMPLE);
+    let out_h = (metrics.height + 2 * pad).div_ceil(SUPERSAMPLE);
+    let (grid_w, grid_h) = (out_w * SUPERSAMPLE, out_h * SUPERSAMPLE);
+    let top = grid_h - pad - metrics.height;
+    let mut inside = vec![false; grid_w * grid_h];
+    for y in 0..metrics.height {
+        for x in 0..metrics.width {
+            inside[(top + y) * grid_w + pad + x] = coverage[y * metrics.width + x] >= 128;
+        }
+    }
+    let distances = signed_distances(&inside, grid_w, grid_h);
+
+    // Average each block of supersamples into one output pixel.
+    let mut alpha = Vec::with_capacity(out_w * out_h);
+    for oy in 0..out_h {
+        for ox in 0..out_w {
+            let mut sum = 0.0;
+            for sy in 0..SUPERSAMPLE {
+                let row = (oy * SUPERSAMPLE + sy) * grid_w + ox * SUPERSAMPLE;
+                sum += distances[row..row + SUPERSAMPLE].iter().sum::<f32>();
+            }
+            let distance = sum / (SUPERSAMPLE * SUPERSAMPLE * SUPERSAMPLE) as f32;
+            alpha.push(encode_distance(distance, range));
+        }
+    }
+
+    let s = SUPERSAMPLE as f32;
+    GlyphBitmap {
+        width: out_w as u32,
+        height: out_h as u32,
+        offset_x: (metrics.xmin as f32 - pad as f32) / s,
+        offset_y: (metrics.ymin as f32 - pad as f32) / s,
+        advance,
+        alpha,
+    }
+}
+
+/// A distance in pixels (positive inside) as a byte: 128 on the edge, 0
+/// and 255 at half the range out and in.
+fn encode_distance(distance: f32, range: f32) -> u8 {
+    ((0.5 + distance / range).clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Distance from each pixel's center to the shape's edge, in pixels:
+/// positive inside, negative outside. The edge lies halfway between an
+/// inside and an outside pixel.
+fn signed_distances(inside: &[bool], width: usize, height: usize) -> Vec<f32> {
+    let to_inside = squared_distances(inside, width, height, true);
+    let to_outside = squared_distances(inside, width, height, false);
+    inside
+        .iter()
+        .zip(to_inside.iter().zip(&to_outside))
+        .map(|(&is_inside, (&to_in, &to_out))| {
+            if is_inside {
+                to_out.sqrt() as f32 - 0.5
+            } else {
+                0.5 - to_in.sqrt() as f32
+            }
+        })
+        .collect()
+}
+
+/// Squared Euclidean distance from every pixel to the nearest one whose
+/// `inside` equals `target`: a 1D transform down each column, then along
+/// each row.
+fn squared_distances(inside: &[bool], width: usize, height: usize, target: bool) -> Vec<f64> {
+    let mut grid: Vec<f64> = inside.iter().map(|&i| if i == target { 0.0 } else { FAR }).collect();
+    let longest = width.max(height);
+    let mut line = vec![0.0; longest];
+    let mut out = vec![0.0; longest];
+    let mut hull = vec![0; longest];
+    let mut bounds = vec![0.0; longest + 1];
+
+    for x in 0..width {
+        for y in 0..height {
+            line[y] = grid[y * width + x];
+        }
+        distance_1d(&line[..height], &mut out[..height], &mut hull, &mut bounds);
+        for y in 0..height {
+            grid[y * width + x] = out[y];
+        }
+    }
+    for y in 0..height {
+        let row = &mut grid[y * width..(y + 1) * width];
+        line[..width].copy_from_slice(row);
+        distance_1d(&line[..width], &mut out[..width], &mut hull, &mut bounds);
+        row.copy_from_slice(&out[..width]);
+    }
+    grid
+}
+
+/// Felzenszwalb & Huttenlocher's 1D transform: `out[q]` is the minimum
+/// over `p` of `(q - p)² + f[p]`, found as the lower envelope of the
+/// parabolas rooted at each `p`. `hull` and `bounds` are scratch space.
+fn distance_1d(f: &[f64], out: &mut [f64], hull: &mut [usize], bounds: &mut [f64]) {
+    let n = f.len();
+    // Where parabolas `p` and `q` intersect.
+    let intersect = |p: usize, q: usize| {
+        ((f[q] + (q * q) as f64) - (f[p] + (p * p) as f64)) / (2.0 * q as f64 - 2.0 * p as f64)
+    };
+    let mut k = 0;
+    hull[0] = 0;
+    bounds[0] = f64::NEG_INFINITY;
+    bounds[1] = f64::INFINITY;
+    for q in 1..n {
+        let mut s = intersect(hull[k], q);
+        while s <= bounds[k] {
+            k -= 1;
+            s = intersect(hull[k], q);
+        }
+        k += 1;
+        hull[k] = q;
+        bounds[k] = s;
+        bounds[k + 1] = f64::INFINITY;
+    }
+    k = 0;
+    for (q, value) in out.iter_mut().enumerate() {
+        while bounds[k + 1] < q as f64 {
+            k += 1;
+        }
+        let p = hull[k];
+        *value = (q as f64 - p as f64).powi(2) + f[p];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_transform_is_euclidean() {
+        // A single inside pixel at (2, 2) in a 7×5 grid.
+        let (width, height) = (7, 5);
+        let mut inside = vec![false; width * height];
+        inside[2 * width + 2] = true;
+        let distances = signed_distances(&inside, width, height);
+        assert_eq!(distances[2 * width + 2], 0.5);
+        // (6, 4) is 4 across and 2 down: √20 from the center, minus half a
+        // pixel to the edge.
+        assert!((distances[4 * width + 6] - (0.5 - 20f32.sqrt())).abs() < 1e-5);
+        assert!(distances.iter().enumerate().all(|(i, &d)| (d > 0.0) == inside[i]));
+    }
+
+    #[test]
+    fn generated_glyphs_put_the_edge_at_half() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("examples/assets/LiberationSans-Regular.ttf");
+        let font = fontdue::Font::from_bytes(std::fs::read(path).unwrap(), fontdue::FontSettings::default()).unwrap();
+        let range = 8.0;
+        let glyph = rasterize_sdf(&font, 'l', 32.0, range);
+        let (metrics, _) = font.rasterize('l', 32.0);
+        // Padded by half the range on each side.
+        assert!(glyph.width as f32 >= metrics.width as f32 + range - 1.0);
+        assert!((glyph.offset_x - (metrics.xmin as f32 - range * 0.5)).abs() <= 1.0);
+
+        // Across the middle row: rising 255 / range per pixel from about 0
+        // at the padding's outer edge, past the edge, and back down.
+        let row = &glyph.alpha[(glyph.height / 2 * glyph.width) as usize..][..glyph.width as usize];
+        assert!(row[0] <= 32 && row[row.len() - 1] <= 32, "{row:?}");
+        assert!(((row[1] - row[0]) as f32 - 255.0 / range).abs() < 2.0, "{row:?}");
+        let peak = *row.iter().max().unwrap();
+        assert!(peak > 128, "the stem's center is inside");
+        let stem = row.iter().filter(|&&a| a >= 128).count() as i32;
+        assert!((stem - metrics.width as i32).abs() <= 1, "stem {stem}px, bitmap {}px", metrics.width);
+
+        let space = rasterize_sdf(&font, ' ', 32.0, range);
+        assert_eq!((space.width, space.height), (0, 0));
+        assert!(space.advance > 0.0);
+    }
+
+    #[test]
+    fn reads_msdf_atlas_gen_layouts() {
+        let json = r#"{
+            "atlas": {"type": "msdf", "distanceRange": 4, "size": 32, "width": 128, "height": 64, "yOrigin": "bottom"},
+            "metrics": {"emSize": 1, "lineHeight": 1.25, "ascender": -0.9, "descender": 0.2},
+            "glyphs": [
+                {"unicode": 32, "advance": 0.25},
+                {"unicode": 65, "advance": 0.5,
+                 "planeBounds": {"left": -0.0625, "bottom": -0.0625, "right": 0.5625, "top": 0.75},
+                 "atlasBounds": {"left": 0.5, "bottom": 0.5, "right": 20.5, "top": 26.5}},
+                {"unicode": 233, "advance": 0.5}
+            ]
+        }"#;
+        let layout = parse_msdf_layout(json).unwrap();
+        assert_eq!(layout.metrics.line_height * layout.px_per_unit(), 40.0);
+        let (ascii, extra) = msdf_glyphs(&layout);
+        let space = ascii[0].unwrap();
+        assert_eq!((space.advance, space.width), (8.0, 0.0));
+
+        let a = ascii['A' as usize - 32].unwrap();
+        assert_eq!((a.advance, a.offset_x, a.offset_y), (16.0, -2.0, -2.0));
+        assert_eq!((a.width, a.height), (20.0, 26.0));
+        // Bottom-origin atlas rows flip into top-down V.
+        assert_eq!((a.v_min, a.v_max), (1.0 - 26.5 / 64.0, 1.0 - 0.5 / 64.0));
+        assert_eq!(a.u_max, 20.5 / 128.0);
+        assert!(extra.contains_key(&'é'));
+
+        // Top-origin layouts count Y down, for both bounds.
+        let json = r#"{
+            "atlas": {"distanceRange": 4, "size": 32, "width": 128, "height": 64, "yOrigin": "top"},
+            "metrics": {"lineHeight": 1.25},
+            "glyphs": [
+                {"unicode": 65, "advance": 0.5,
+                 "planeBounds": {"left": -0.0625, "bottom": 0.0625, "right": 0.5625, "top": -0.75},
+                 "atlasBounds": {"left": 0.5, "bottom": 26.5, "right": 20.5, "top": 0.5}}
+            ]
+        }"#;
+        let layout = parse_msdf_layout(json).unwrap();
+        assert!(layout.atlas.y_origin == YOrigin::Top);
+        let a = msdf_glyphs(&layout).0['A' as usize - 32].unwrap();
+        assert_eq!((a.offset_y, a.height), (-2.0, 26.0));
+        assert_eq!((a.v_min, a.v_max), (0.5 / 64.0, 26.5 / 64.0));
+    }
+}
@@ -0,0 +1,86 @@
+// Distance-field text (see sdf.rs). The glyph atlas stores, per texel, the
+// distance to the glyph's edge: 0.5 on the edge, more inside, less outside.
+// Single-channel SDFs keep it in alpha; multi-channel ones (MSDF) in RGB,
+// where the median of the three is the distance.
+//
+// The fragment shader turns that distance into screen pixels, so the edge
+// is anti-aliased over about one pixel however far the text is zoomed:
+//
+//   distance units ──× px_range──▶ screen pixels ──+0.5, clamp──▶ coverage
+//
+// px_range is the atlas' distance range (in texels) measured in screen
+// pixels, from how fast the UVs change across the screen (fwidth).
+//
+// Outlines are a second edge moved outward; the band between the two
+// edges takes the outline color. A shadow is the same glyphs drawn first,
+// offset, with softness widening the edge into a smoothstep.
+
+// Group 0: camera uniform (shared with the sprite shader)
+@group(0) @binding(0)
+var<uniform> camera: mat4x4<f32>;
+
+// Group 1: glyph atlas + sampler
+@group(1) @binding(0)
+var glyph_atlas: texture_2d<f32>;
+@group(1) @binding(1)
+var glyph_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) outline_color: vec4<f32>,
+    // x: distance range in atlas texels; y: outline width and z: edge
+    // softness, in distance units; w: 1 for multi-channel atlases.
+    @location(4) params: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @location(2) outline_color: vec4<f32>,
+    @location(3) @interpolate(flat) params: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera * vec4<f32>(in.position, 1.0);
+    out.uv = in.uv;
+    out.color = in.color;
+    out.outline_color = in.outline_color;
+    out.params = in.params;
+    return out;
+}
+
+fn median(r: f32, g: f32, b: f32) -> f32 {
+    return max(min(r, g), min(max(r, g), b));
+}
+
+// How much of the pixel lies inside the edge moved outward by `grow`.
+fn coverage(distance: f32, grow: f32, px_range: f32, softness: f32) -> f32 {
+    let d = distance - 0.5 + grow;
+    if softness > 0.0 {
+        return smoothstep(-softness, softness, d);
+    }
+    return clamp(d * px_range + 0.5, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let texel = textureSample(glyph_atlas, glyph_sampler, in.uv);
+    let distance = select(texel.a, median(texel.r, texel.g, texel.b), in.params.w > 0.5);
+
+    let unit_range = vec2<f32>(in.params.x) / vec2<f32>(textureDimensions(glyph_atlas));
+    let screen_texels = vec2<f32>(1.0) / fwidth(in.uv);
+    let px_range = max(0.5 * dot(unit_range, screen_texels), 1.0);
+
+    let fill = coverage(distance, 0.0, px_range, in.params.z);
+    let outer = coverage(distance, in.params.y, px_range, in.params.z);
+    let fill_alpha = in.color.a * fill;
+    let outline_alpha = in.outline_color.a * max(outer - fill, 0.0);
+    let alpha = fill_alpha + outline_alpha;
+    let rgb = (in.color.rgb * fill_alpha + in.outline_color.rgb * outline_alpha) / max(alpha, 1e-5);
+    return vec4<f32>(rgb, alpha);
+}
@@ -50,28 +50,54 @@
 // The split between groups is intentional: group 0 is bound once and left
 // alone, while group 1 is rebound for each texture batch. wgpu tracks bind
 // group state, so only the changed group triggers a state update.
+//
+// Instanced Sprites (vs_instanced)
+//
+// In instanced mode there is no vertex buffer of corners. Each draw covers
+// N instances x 6 vertices; vertex_index picks the corner of a unit quad,
+// and the instance's model matrix places it in the world. Both vertex
+// entry points share fs_main.
+//
+// Texture Arrays (TEXTURE_ARRAY)
+//
+// On GPUs with binding arrays, the renderer compiles this file with the
+// TEXTURE_ARRAY shader def. Group 1 then holds an array of textures and
+// their samplers, and each vertex carries the index of the one it samples,
+// so sprites with different textures share a batch. The index is `flat`:
+// every fragment of a triangle uses its first vertex's value rather than
+// an interpolated one.
 // ============================================================================
 
 // Group 0: camera uniform (set once per frame)
 @group(0) @binding(0)
 var<uniform> camera: mat4x4<f32>;
 
+#ifdef TEXTURE_ARRAY
+// Group 1: per-batch texture array + samplers
+@group(1) @binding(0)
+var sprite_textures: binding_array<texture_2d<f32>>;
+@group(1) @binding(1)
+var sprite_samplers: binding_array<sampler>;
+#else
 // Group 1: per-batch texture + sampler
 @group(1) @binding(0)
 var sprite_texture: texture_2d<f32>;
 @group(1) @binding(1)
 var sprite_sampler: sampler;
+#endif
 
 struct VertexInput {
     @location(0) position: vec3<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) color: vec4<f32>,
+    @location(9) texture_index: u32,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) uv: vec2<f32>,
     @location(1) color: vec4<f32>,
+    @location(2) @interpolate(flat) texture_index: u32,
 };
 
 @vertex
@@ -80,11 +106,50 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.clip_position = camera * vec4<f32>(in.position, 1.0);
     out.uv = in.uv;
     out.color = in.color;
+    out.texture_index = in.texture_index;
+    return out;
+}
+
+struct InstanceInput {
+    @location(3) model_0: vec4<f32>,
+    @location(4) model_1: vec4<f32>,
+    @location(5) model_2: vec4<f32>,
+    @location(6) model_3: vec4<f32>,
+    // Top-left UV in xy, bottom-right UV in zw.
+    @location(7) uv_rect: vec4<f32>,
+    @location(8) color: vec4<f32>,
+    @location(10) texture_index: u32,
+};
+
+@vertex
+fn vs_instanced(@builtin(vertex_index) vertex_index: u32, instance: InstanceInput) -> VertexOutput {
+    // Two triangles: bottom-left, bottom-right, top-right, then bottom-left,
+    // top-right, top-left. (0, 0) is the bottom-left corner.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 0.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0), vec2<f32>(1.0, 1.0), vec2<f32>(0.0, 1.0),
+    );
+    let f = corners[vertex_index];
+    let model = mat4x4<f32>(instance.model_0, instance.model_1, instance.model_2, instance.model_3);
+
+    var out: VertexOutput;
+    out.clip_position = camera * model * vec4<f32>(f - vec2<f32>(0.5, 0.5), 0.0, 1.0);
+    // Texture V runs top-down while the quad's Y runs bottom-up.
+    out.uv = vec2<f32>(
+        mix(instance.uv_rect.x, instance.uv_rect.z, f.x),
+        mix(instance.uv_rect.w, instance.uv_rect.y, f.y),
+    );
+    out.color = instance.color;
+    out.texture_index = instance.texture_index;
     return out;
 }
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef TEXTURE_ARRAY
+    let tex_color = textureSample(sprite_textures[in.texture_index], sprite_samplers[in.texture_index], in.uv);
+#else
     let tex_color = textureSample(sprite_texture, sprite_sampler, in.uv);
+#endif
     return tex_color * in.color;
 }
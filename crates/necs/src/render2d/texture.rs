@@ -18,6 +18,9 @@
 //!   lifetime. The handle is just a `usize` index.
 //! - **Deduplication**: The store caches by file path, so loading the same
 //!   image twice returns the same handle without a second GPU upload.
+//! - **Packing**: A handle doesn't have to own a whole GPU texture. Small
+//!   images share [atlas](super::atlas) pages; the entry records which page
+//!   and where, and the batcher remaps UVs.
 //!
 //! ```text
 //! TextureStore
@@ -53,6 +56,16 @@
 //! from the world, do the work, then *reinsert* it. This is a common pattern
 //! in single-world ECS designs without interior mutability.
 //!
+//! ## Sampling
+//!
+//! Each entry carries its own [`SamplerSettings`]. Textures default to
+//! nearest filtering, which keeps pixel art crisp; [`load_texture_with`]
+//! picks other settings at load time and [`set_texture_sampler`] changes
+//! them later. Atlas pages exist for the two plain clamped samplers —
+//! nearest for images, linear for font atlases and smooth art — so a
+//! texture with any other settings is kept standalone, or copied out of its
+//! page when its sampler changes.
+//!
 //! ## Comparison
 //!
 //! - **Bevy** (`AssetServer`): Loads textures asynchronously, returns a
@@ -65,16 +78,31 @@
 //! - **Macroquad**: `load_texture("path")` is an async function returning a
 //!   `Texture2D` handle. Similar simplicity, but async-based.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use wgpu::util::DeviceExt;
 
 use crate::asset::{AssetKind, AssetServer};
+use crate::asset_gc::FreedEntry;
 use crate::ecs::World;
+use crate::math::{Rect, Vec2};
 use crate::render::GpuContext;
+use crate::render::pipelined::finish_render;
+use crate::render::sampler::{SamplerCache, SamplerSettings};
+
+use super::atlas::{extrude, ShelfPacker, TextureAtlasing, ATLAS_PADDING};
+use super::pipeline::{SpriteRenderer, TEXTURE_ARRAY_SLOTS};
 
-use super::pipeline::SpriteRenderer;
+/// Sampler for 2D textures loaded without explicit settings: crisp pixels.
+pub(crate) const DEFAULT_SAMPLER: SamplerSettings = SamplerSettings::nearest();
+
+/// Samplers atlas pages are made with. Textures sampled any other way
+/// (repeat wrapping, anisotropy) stay standalone.
+const PAGE_SAMPLERS: [SamplerSettings; 2] = [SamplerSettings::nearest(), SamplerSettings::linear()];
+
+/// Size of the white block reserved on every atlas page, in pixels.
+const WHITE_BLOCK: u32 = 2;
 
 /// Handle to a loaded texture in the [`TextureStore`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -83,30 +111,159 @@ pub struct TextureHandle(pub(crate) usize);
 /// Internal entry for a loaded GPU texture.
 pub(crate) struct TextureEntry {
     pub bind_group: wgpu::BindGroup,
+    /// Kept so tools (e.g. the editor's sprite slicer) can display the texture.
+    pub view: wgpu::TextureView,
     pub width: u32,
     pub height: u32,
+    /// Set when the image lives in a shared atlas page. `bind_group` and
+    /// `view` are then the page's.
+    pub packed: Option<PackedRegion>,
+    /// How `bind_group` samples the texture.
+    pub sampler: SamplerSettings,
+}
+
+/// Where a packed texture sits inside its atlas page.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PackedRegion {
+    /// Index into `TextureStore::pages`.
+    pub page: usize,
+    /// Top-left pixel of the image (inside the gutter).
+    pub x: u32,
+    pub y: u32,
+    /// The image's UV rectangle on the page.
+    pub uv: Rect,
+}
+
+/// A shared texture that small images are packed into. Also has its own
+/// entry in `TextureStore::entries` (at `handle`) so batches can bind it.
+pub(crate) struct AtlasPage {
+    pub handle: TextureHandle,
+    texture: wgpu::Texture,
+    packer: ShelfPacker,
+    size: u32,
+    /// Every image on the page is sampled this way.
+    sampler: SamplerSettings,
+    /// UV of the middle of the page's white block, for untextured geometry
+    /// drawn in the same batch as the page's images.
+    white: Vec2,
+}
+
+/// The white default texture and the UV of every atlas page's white
+/// block, by page handle. See [`TextureStore::white_spots`].
+pub(crate) struct WhiteSpots {
+    pub default: TextureHandle,
+    pub pages: HashMap<TextureHandle, Vec2>,
 }
 
 /// Stores all loaded GPU textures and their bind groups.
 pub(crate) struct TextureStore {
     pub entries: Vec<TextureEntry>,
+    pub pages: Vec<AtlasPage>,
     path_cache: HashMap<String, TextureHandle>,
+    samplers: SamplerCache,
+    /// Handles whose GPU texture was released by [`free`](Self::free).
+    freed: HashSet<TextureHandle>,
 }
 
 impl TextureStore {
     /// Create a new store with a 1x1 white default texture at index 0.
     pub fn new(gpu: &GpuContext, renderer: &SpriteRenderer) -> Self {
-        let device = &gpu.device;
-        let queue = &gpu.queue;
+        let mut store = Self {
+            entries: Vec::new(),
+            pages: Vec::new(),
+            path_cache: HashMap::new(),
+            samplers: SamplerCache::default(),
+            freed: HashSet::new(),
+        };
+        store.add_rgba(
+            gpu,
+            renderer,
+            "white 1x1",
+            1,
+            1,
+            &[255u8, 255, 255, 255],
+            DEFAULT_SAMPLER,
+        );
+        store
+    }
+
+    /// The default 1x1 white texture handle.
+    pub fn default_handle(&self) -> TextureHandle {
+        TextureHandle(0)
+    }
+
+    /// Iterate over textures loaded from disk as `(path, handle)` pairs.
+    #[cfg(feature = "editor")]
+    pub fn loaded_paths(&self) -> impl Iterator<Item = (&str, TextureHandle)> {
+        self.path_cache.iter().map(|(path, handle)| (path.as_str(), *handle))
+    }
+
+    /// Get the entry for a handle.
+    pub fn get(&self, handle: TextureHandle) -> &TextureEntry {
+        &self.entries[handle.0]
+    }
+
+    /// The texture to bind when drawing `handle`, and the UV rectangle of
+    /// the image within it (the whole texture unless packed).
+    pub fn draw_source(&self, handle: TextureHandle) -> (TextureHandle, Rect) {
+        match self.entries[handle.0].packed {
+            Some(region) => (self.pages[region.page].handle, region.uv),
+            None => (handle, Rect::FULL),
+        }
+    }
+
+    /// Where untextured geometry can draw from this frame. Drawing it from a
+    /// page's white block rather than the 1x1 default keeps it in the same
+    /// batch as the page's images.
+    pub fn white_spots(&self) -> WhiteSpots {
+        WhiteSpots {
+            default: self.default_handle(),
+            pages: self.pages.iter().map(|page| (page.handle, page.white)).collect(),
+        }
+    }
+
+    /// Whether textures sampled with `sampler` can share an atlas page.
+    pub fn packs_sampler(sampler: SamplerSettings) -> bool {
+        PAGE_SAMPLERS.contains(&sampler)
+    }
+
+    /// Upload RGBA8 data as a standalone texture and return its handle.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_rgba(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        label: &str,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        sampler: SamplerSettings,
+    ) -> TextureHandle {
+        let entry = self.standalone_entry(gpu, renderer, label, width, height, data, sampler);
+        let handle = TextureHandle(self.entries.len());
+        self.entries.push(entry);
+        handle
+    }
 
-        // 1x1 white pixel
-        let texture = device.create_texture_with_data(
-            queue,
+    /// Create a GPU texture from RGBA8 data and wrap it in an entry.
+    #[allow(clippy::too_many_arguments)]
+    fn standalone_entry(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        label: &str,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        sampler: SamplerSettings,
+    ) -> TextureEntry {
+        let texture = gpu.device.create_texture_with_data(
+            &gpu.queue,
             &wgpu::TextureDescriptor {
-                label: Some("white 1x1"),
+                label: Some(label),
                 size: wgpu::Extent3d {
-                    width: 1,
-                    height: 1,
+                    width,
+                    height,
                     depth_or_array_layers: 1,
                 },
                 mip_level_count: 1,
@@ -117,45 +274,303 @@ impl TextureStore {
                 view_formats: &[],
             },
             wgpu::util::TextureDataOrder::LayerMajor,
-            &[255u8, 255, 255, 255],
+            data,
         );
-
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("white 1x1 bind group"),
+        let (sampler, bind_group) = self.bind(gpu, renderer, label, &view, sampler);
+        TextureEntry {
+            bind_group,
+            view,
+            width,
+            height,
+            packed: None,
+            sampler,
+        }
+    }
+
+    /// Bind group 1 of the [texture array](super::pipeline::TextureArrays)
+    /// pipelines: the views and samplers of `textures`, in order, with the
+    /// remaining slots filled by the white default.
+    pub fn texture_array_bind_group(
+        &mut self,
+        gpu: &GpuContext,
+        layout: &wgpu::BindGroupLayout,
+        textures: &[TextureHandle],
+    ) -> wgpu::BindGroup {
+        let handles: Vec<TextureHandle> = textures
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(self.default_handle()))
+            .take(TEXTURE_ARRAY_SLOTS as usize)
+            .collect();
+        let samplers: Vec<wgpu::Sampler> = handles
+            .iter()
+            .map(|handle| self.samplers.get(&gpu.device, self.entries[handle.0].sampler).1)
+            .collect();
+        let samplers: Vec<&wgpu::Sampler> = samplers.iter().collect();
+        let views: Vec<&wgpu::TextureView> = handles.iter().map(|handle| &self.entries[handle.0].view).collect();
+        gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("texture array bind group"),
+            layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureViewArray(&views),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::SamplerArray(&samplers),
+                },
+            ],
+        })
+    }
+
+    /// Bind group 1 of the sprite pipeline: a texture view and its sampler.
+    fn bind(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        label: &str,
+        view: &wgpu::TextureView,
+        sampler: SamplerSettings,
+    ) -> (SamplerSettings, wgpu::BindGroup) {
+        let (sampler, gpu_sampler) = self.samplers.get(&gpu.device, sampler);
+        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some(label),
             layout: &renderer.texture_bind_group_layout,
             entries: &[
                 wgpu::BindGroupEntry {
                     binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
+                    resource: wgpu::BindingResource::TextureView(view),
                 },
                 wgpu::BindGroupEntry {
                     binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&renderer.sampler),
+                    resource: wgpu::BindingResource::Sampler(&gpu_sampler),
                 },
             ],
         });
+        (sampler, bind_group)
+    }
 
-        let default_entry = TextureEntry {
-            bind_group,
-            width: 1,
-            height: 1,
+    /// Pack an RGBA8 image into an atlas page sampled with `sampler`,
+    /// opening a new page if every existing one is full. The caller checks
+    /// [`TextureAtlasing::should_pack`] and
+    /// [`packs_sampler`](Self::packs_sampler) first.
+    #[allow(clippy::too_many_arguments)]
+    pub fn pack(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        settings: &TextureAtlasing,
+        width: u32,
+        height: u32,
+        data: &[u8],
+        sampler: SamplerSettings,
+    ) -> TextureHandle {
+        let (page, x, y) = self.allocate(gpu, renderer, settings, width, height, sampler);
+        let region = self.write_region(gpu, page, x, y, width, height, data);
+        let entry = self.packed_entry(region, width, height);
+        let handle = TextureHandle(self.entries.len());
+        self.entries.push(entry);
+        handle
+    }
+
+    /// Replace a texture's pixels, keeping it packed if it was: a packed
+    /// texture that changed size moves to a fresh region of a page with its
+    /// sampler (the old region stays unused) as long as
+    /// [`TextureAtlasing::should_pack_font`] allows the new size. Everything
+    /// else goes through [`write_rgba`](Self::write_rgba).
+    #[allow(clippy::too_many_arguments)]
+    pub fn write_packable(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        settings: &TextureAtlasing,
+        handle: TextureHandle,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) {
+        let entry = &self.entries[handle.0];
+        let moves = entry.packed.is_some()
+            && (entry.width, entry.height) != (width, height)
+            && settings.should_pack_font(width, height);
+        if !moves {
+            self.write_rgba(gpu, renderer, handle, width, height, data);
+            return;
+        }
+        let sampler = entry.sampler;
+        let (page, x, y) = self.allocate(gpu, renderer, settings, width, height, sampler);
+        let region = self.write_region(gpu, page, x, y, width, height, data);
+        self.entries[handle.0] = self.packed_entry(region, width, height);
+    }
+
+    /// Find room for a `width`×`height` image plus its gutter on a page
+    /// sampled with `sampler`, opening a new page if none has any. Returns
+    /// the page index and the image's top-left pixel.
+    fn allocate(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        settings: &TextureAtlasing,
+        width: u32,
+        height: u32,
+        sampler: SamplerSettings,
+    ) -> (usize, u32, u32) {
+        let (w, h) = (width + 2 * ATLAS_PADDING, height + 2 * ATLAS_PADDING);
+        let existing = self.pages.iter_mut().enumerate().find_map(|(i, page)| {
+            if page.sampler != sampler {
+                return None;
+            }
+            page.packer.allocate(w, h).map(|(x, y)| (i, x, y))
+        });
+        let (page, x, y) = match existing {
+            Some(spot) => spot,
+            None => {
+                let page = self.new_page(gpu, renderer, settings.page_size, sampler);
+                let (x, y) = self.pages[page]
+                    .packer
+                    .allocate(w, h)
+                    .expect("image larger than an empty atlas page");
+                (page, x, y)
+            }
         };
+        (page, x + ATLAS_PADDING, y + ATLAS_PADDING)
+    }
 
-        Self {
-            entries: vec![default_entry],
-            path_cache: HashMap::new(),
+    /// An entry drawing `region` from its page.
+    fn packed_entry(&self, region: PackedRegion, width: u32, height: u32) -> TextureEntry {
+        let page_entry = self.get(self.pages[region.page].handle);
+        TextureEntry {
+            bind_group: page_entry.bind_group.clone(),
+            view: page_entry.view.clone(),
+            width,
+            height,
+            packed: Some(region),
+            sampler: page_entry.sampler,
         }
     }
 
-    /// The default 1x1 white texture handle.
-    pub fn default_handle(&self) -> TextureHandle {
-        TextureHandle(0)
+    /// Create an empty atlas page with its white block and return its index
+    /// in `pages`.
+    fn new_page(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        size: u32,
+        sampler: SamplerSettings,
+    ) -> usize {
+        let index = self.pages.len();
+        let label = format!("atlas page {index}");
+        // New textures are zero-initialized, so unused space is transparent.
+        // COPY_SRC lets a packed image be copied out when its sampler changes.
+        let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&label),
+            size: wgpu::Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_DST
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (sampler, bind_group) = self.bind(gpu, renderer, &label, &view, sampler);
+
+        let handle = TextureHandle(self.entries.len());
+        self.entries.push(TextureEntry {
+            bind_group,
+            view,
+            width: size,
+            height: size,
+            packed: None,
+            sampler,
+        });
+        self.pages.push(AtlasPage {
+            handle,
+            texture,
+            packer: ShelfPacker::new(size, size),
+            size,
+            sampler,
+            white: Vec2::ZERO,
+        });
+
+        // The white block goes first, so it always fits. Its middle sits
+        // between white texels, which stays white under either filter.
+        let padded = WHITE_BLOCK + 2 * ATLAS_PADDING;
+        let (x, y) = self.pages[index]
+            .packer
+            .allocate(padded, padded)
+            .expect("atlas page too small for its white block");
+        let white = [255; (WHITE_BLOCK * WHITE_BLOCK * 4) as usize];
+        let (x, y) = (x + ATLAS_PADDING, y + ATLAS_PADDING);
+        let region = self.write_region(gpu, index, x, y, WHITE_BLOCK, WHITE_BLOCK, &white);
+        self.pages[index].white = (region.uv.min + region.uv.max) * 0.5;
+
+        log::debug!(
+            "Created 2D texture atlas page {index} ({size}x{size}, {:?} filtering)",
+            sampler.filter
+        );
+        index
     }
 
-    /// Get the entry for a handle.
-    pub fn get(&self, handle: TextureHandle) -> &TextureEntry {
-        &self.entries[handle.0]
+    /// Upload an image (plus its extruded gutter) with its top-left pixel at
+    /// `(x, y)` on `page`.
+    #[allow(clippy::too_many_arguments)]
+    fn write_region(
+        &self,
+        gpu: &GpuContext,
+        page: usize,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> PackedRegion {
+        let atlas = &self.pages[page];
+        let padded = extrude(data, width, height, ATLAS_PADDING);
+        let (padded_w, padded_h) = (width + 2 * ATLAS_PADDING, height + 2 * ATLAS_PADDING);
+        gpu.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &atlas.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: x - ATLAS_PADDING,
+                    y: y - ATLAS_PADDING,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &padded,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * padded_w),
+                rows_per_image: Some(padded_h),
+            },
+            wgpu::Extent3d {
+                width: padded_w,
+                height: padded_h,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let size = atlas.size as f32;
+        PackedRegion {
+            page,
+            x,
+            y,
+            uv: Rect {
+                min: Vec2::new(x as f32 / size, y as f32 / size),
+                max: Vec2::new((x + width) as f32 / size, (y + height) as f32 / size),
+            },
+        }
     }
 
     /// Replace the GPU data for an existing texture handle (hot-reload).
@@ -163,6 +578,9 @@ impl TextureStore {
     /// Creates a new GPU texture and bind group from the given RGBA8 data,
     /// then swaps them into the entry at the handle's index. Any sprite
     /// referencing this handle will see the new texture next frame.
+    ///
+    /// A packed texture that kept its size is rewritten in place on its atlas
+    /// page; one that changed size becomes a standalone texture.
     pub fn reload_entry(
         &mut self,
         gpu: &GpuContext,
@@ -172,47 +590,195 @@ impl TextureStore {
         height: u32,
         data: &[u8],
     ) {
-        let texture = gpu.device.create_texture_with_data(
-            &gpu.queue,
-            &wgpu::TextureDescriptor {
-                label: Some("hot-reload texture"),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-                view_formats: &[],
+        let entry = &self.entries[handle.0];
+        if let Some(region) = entry.packed {
+            if (entry.width, entry.height) == (width, height) {
+                self.write_region(gpu, region.page, region.x, region.y, width, height, data);
+                return;
+            }
+            log::info!("Packed texture changed size on reload; moving it out of the atlas");
+        }
+
+        let sampler = entry.sampler;
+        self.entries[handle.0] =
+            self.standalone_entry(gpu, renderer, "hot-reload texture", width, height, data, sampler);
+    }
+
+    /// Overwrite a standalone texture's pixels in place, e.g. with the next
+    /// frame of a video. Packed, freed or resized textures are replaced
+    /// through [`reload_entry`](Self::reload_entry) instead.
+    pub fn write_rgba(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        handle: TextureHandle,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) {
+        let entry = &self.entries[handle.0];
+        let in_place = handle != self.default_handle()
+            && entry.packed.is_none()
+            && !self.freed.contains(&handle)
+            && (entry.width, entry.height) == (width, height);
+        if !in_place {
+            self.reload_entry(gpu, renderer, handle, width, height, data);
+            return;
+        }
+        gpu.queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: entry.view.texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
             },
-            wgpu::util::TextureDataOrder::LayerMajor,
             data,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
         );
+    }
 
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("hot-reload bind group"),
-            layout: &renderer.texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&renderer.sampler),
-                },
-            ],
-        });
+    /// Change how a texture is sampled. A packed texture is first copied out
+    /// of its atlas page into a standalone texture, since the page and its
+    /// sampler are shared.
+    pub fn set_sampler(
+        &mut self,
+        gpu: &GpuContext,
+        renderer: &SpriteRenderer,
+        handle: TextureHandle,
+        sampler: SamplerSettings,
+    ) {
+        let entry = &self.entries[handle.0];
+        if entry.sampler == sampler {
+            return;
+        }
+        if let Some(region) = entry.packed {
+            let view = self.unpack(gpu, region, entry.width, entry.height);
+            self.entries[handle.0].view = view;
+            self.entries[handle.0].packed = None;
+        }
 
-        self.entries[handle.0] = TextureEntry {
-            bind_group,
+        let view = self.entries[handle.0].view.clone();
+        let (sampler, bind_group) = self.bind(gpu, renderer, "texture", &view, sampler);
+        let entry = &mut self.entries[handle.0];
+        entry.sampler = sampler;
+        entry.bind_group = bind_group;
+    }
+
+    /// Handles [`free`](Self::free) would release: standalone textures other
+    /// than the default, excluding atlas pages and those already freed.
+    pub fn freeable(&self) -> impl Iterator<Item = TextureHandle> + '_ {
+        (1..self.entries.len()).map(TextureHandle).filter(|&handle| {
+            self.entries[handle.0].packed.is_none()
+                && !self.freed.contains(&handle)
+                && !self.pages.iter().any(|page| page.handle == handle)
+        })
+    }
+
+    /// Release the GPU texture behind `handle`. See
+    /// [`asset_gc`](crate::asset_gc).
+    ///
+    /// The slot stays, since handles are plain indices that may still be
+    /// held somewhere, but now draws as the white default. The path is
+    /// dropped from the cache so loading it again uploads a fresh copy.
+    /// Returns `None` for handles [`freeable`](Self::freeable) skips.
+    pub fn free(&mut self, handle: TextureHandle) -> Option<FreedEntry> {
+        if !self.freeable().any(|h| h == handle) {
+            return None;
+        }
+        let white = &self.entries[0];
+        let (bind_group, view) = (white.bind_group.clone(), white.view.clone());
+        let entry = &mut self.entries[handle.0];
+        let bytes = entry.width as u64 * entry.height as u64 * 4;
+        entry.bind_group = bind_group;
+        entry.view = view;
+        (entry.width, entry.height) = (1, 1);
+        entry.sampler = DEFAULT_SAMPLER;
+        self.freed.insert(handle);
+
+        let path = self
+            .path_cache
+            .iter()
+            .find(|(_, h)| **h == handle)
+            .map(|(path, _)| path.clone());
+        if let Some(path) = &path {
+            self.path_cache.remove(path);
+        }
+        Some(FreedEntry { bytes, path })
+    }
+
+    /// Copy a packed image out of its atlas page into a texture of its own.
+    fn unpack(
+        &self,
+        gpu: &GpuContext,
+        region: PackedRegion,
+        width: u32,
+        height: u32,
+    ) -> wgpu::TextureView {
+        let size = wgpu::Extent3d {
             width,
             height,
+            depth_or_array_layers: 1,
         };
+        let texture = gpu.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("unpacked texture"),
+            size,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let mut encoder = gpu
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("unpack atlas texture"),
+            });
+        encoder.copy_texture_to_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.pages[region.page].texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: region.x,
+                    y: region.y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyTextureInfo {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            size,
+        );
+        gpu.queue.submit(Some(encoder.finish()));
+        log::debug!("Moved a packed texture out of atlas page {} to change its sampler", region.page);
+        texture.create_view(&wgpu::TextureViewDescriptor::default())
+    }
+}
+
+/// Make sure `SpriteRenderer` and `TextureStore` exist (lazy init once the
+/// `GpuContext` is ready).
+fn ensure_store(world: &mut World) {
+    // The render thread may hold them for the frame in flight.
+    finish_render(world);
+    if !world.has_resource::<TextureStore>() {
+        let gpu = world.resource::<GpuContext>();
+        let renderer = SpriteRenderer::new(gpu);
+        let store = TextureStore::new(gpu, &renderer);
+        world.insert_resource(renderer);
+        world.insert_resource(store);
     }
 }
 
@@ -226,67 +792,39 @@ pub fn create_texture_from_rgba(
     height: u32,
     data: &[u8],
 ) -> TextureHandle {
-    // Ensure TextureStore + SpriteRenderer exist.
-    if !world.has_resource::<TextureStore>() {
-        let gpu = world.resource::<GpuContext>();
-        let renderer = SpriteRenderer::new(gpu);
-        let store = TextureStore::new(gpu, &renderer);
-        world.insert_resource(renderer);
-        world.insert_resource(store);
-    }
-
+    ensure_store(world);
     let mut store = world
         .resource_remove::<TextureStore>()
         .expect("TextureStore not initialized");
 
     let gpu = world.resource::<GpuContext>();
     let renderer = world.resource::<SpriteRenderer>();
+    let handle = store.add_rgba(gpu, renderer, label, width, height, data, DEFAULT_SAMPLER);
 
-    let texture = gpu.device.create_texture_with_data(
-        &gpu.queue,
-        &wgpu::TextureDescriptor {
-            label: Some(label),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        },
-        wgpu::util::TextureDataOrder::LayerMajor,
-        data,
-    );
+    world.insert_resource(store);
+    handle
+}
 
-    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some(label),
-        layout: &renderer.texture_bind_group_layout,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&view),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::Sampler(&renderer.sampler),
-            },
-        ],
-    });
+/// Replace the pixels of a texture made with [`create_texture_from_rgba`].
+/// Same-sized data is written into the existing GPU texture, so this is
+/// cheap enough to stream a new image every frame.
+pub fn update_texture_rgba(
+    world: &mut World,
+    handle: TextureHandle,
+    width: u32,
+    height: u32,
+    data: &[u8],
+) {
+    ensure_store(world);
+    let mut store = world
+        .resource_remove::<TextureStore>()
+        .expect("TextureStore not initialized");
 
-    let handle = TextureHandle(store.entries.len());
-    store.entries.push(TextureEntry {
-        bind_group,
-        width,
-        height,
-    });
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    store.write_rgba(gpu, renderer, handle, width, height, data);
 
     world.insert_resource(store);
-    handle
 }
 
 /// Load a texture from disk and return a handle.
@@ -295,79 +833,124 @@ pub fn create_texture_from_rgba(
 /// the world to avoid borrow conflicts with `GpuContext`.
 ///
 /// The texture is cached by path — loading the same path twice returns the
-/// same handle.
+/// same handle. Small images are packed into a shared atlas page (see
+/// [`TextureAtlasing`]). New textures are sampled with nearest filtering;
+/// use [`load_texture_with`] to choose.
 pub fn load_texture(world: &mut World, path: &str) -> TextureHandle {
-    // Ensure TextureStore + SpriteRenderer exist (lazy init if GpuContext is ready).
-    if !world.has_resource::<TextureStore>() {
-        let gpu = world.resource::<GpuContext>();
-        let renderer = SpriteRenderer::new(gpu);
-        let store = TextureStore::new(gpu, &renderer);
-        world.insert_resource(renderer);
-        world.insert_resource(store);
+    load(world, path, None)
+}
+
+/// Load a texture from disk with the given sampler settings.
+///
+/// If the path was already loaded, the cached handle is returned and its
+/// sampler switched to `sampler`. Small images loaded with
+/// [`SamplerSettings::linear`] are packed onto linear atlas pages, next to
+/// font atlases; other custom settings are never packed.
+pub fn load_texture_with(world: &mut World, path: &str, sampler: SamplerSettings) -> TextureHandle {
+    load(world, path, Some(sampler))
+}
+
+/// Start loading a texture in the background and return its handle at once.
+///
+/// The handle draws as plain white until the image has been decoded on a
+/// loader thread and uploaded, a frame or more later; track it with
+/// [`AssetServer::load_state`](crate::asset::AssetServer::load_state). The
+/// image is never atlas-packed and skips the [`ImportCache`](crate::import::ImportCache).
+/// A path that is already loaded (or loading) returns its existing handle.
+/// Without an [`AssetServer`] this falls back to [`load_texture`].
+pub fn load_texture_async(world: &mut World, path: &str) -> TextureHandle {
+    if !world.has_resource::<AssetServer>() {
+        return load_texture(world, path);
     }
+    let path = crate::launch::resolve_asset_path(world, path).into_owned();
+    ensure_store(world);
 
-    // Check cache first (need to remove store to mutate it)
     let mut store = world
         .resource_remove::<TextureStore>()
         .expect("TextureStore not initialized — GpuContext is missing");
-
-    if let Some(&handle) = store.path_cache.get(path) {
+    if let Some(&handle) = store.path_cache.get(&path) {
         world.insert_resource(store);
         return handle;
     }
 
+    // Reserve the slot now with a white placeholder; the real image replaces
+    // it in place, like a hot-reload.
     let gpu = world.resource::<GpuContext>();
     let renderer = world.resource::<SpriteRenderer>();
+    let handle = store.add_rgba(gpu, renderer, &path, 1, 1, &[255; 4], DEFAULT_SAMPLER);
+    store.path_cache.insert(path.clone(), handle);
+    world.insert_resource(store);
 
-    // Load image from disk
-    let img = image::open(path)
-        .unwrap_or_else(|e| panic!("Failed to load texture '{}': {}", path, e))
-        .to_rgba8();
-    let (width, height) = img.dimensions();
-    let data = img.into_raw();
-
-    let texture = gpu.device.create_texture_with_data(
-        &gpu.queue,
-        &wgpu::TextureDescriptor {
-            label: Some(path),
-            size: wgpu::Extent3d {
-                width,
-                height,
-                depth_or_array_layers: 1,
-            },
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
+    let server = world.resource_mut::<AssetServer>();
+    server.load_async(
+        PathBuf::from(&path),
+        crate::import::decode_rgba,
+        move |world, (width, height, data)| {
+            let Some(mut store) = world.resource_remove::<TextureStore>() else {
+                return;
+            };
+            // Freed by asset_gc while loading: leave the slot alone.
+            if store.path_cache.get(&path) == Some(&handle) {
+                let gpu = world.resource::<GpuContext>();
+                let renderer = world.resource::<SpriteRenderer>();
+                store.reload_entry(gpu, renderer, handle, width, height, &data);
+            }
+            world.insert_resource(store);
+            world
+                .resource_mut::<AssetServer>()
+                .watch(PathBuf::from(&path), AssetKind::Texture2d(handle));
         },
-        wgpu::util::TextureDataOrder::LayerMajor,
-        &data,
     );
+    handle
+}
 
-    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-    let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-        label: Some(path),
-        layout: &renderer.texture_bind_group_layout,
-        entries: &[
-            wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::TextureView(&view),
-            },
-            wgpu::BindGroupEntry {
-                binding: 1,
-                resource: wgpu::BindingResource::Sampler(&renderer.sampler),
-            },
-        ],
-    });
-
-    let handle = TextureHandle(store.entries.len());
-    store.entries.push(TextureEntry {
-        bind_group,
-        width,
-        height,
-    });
+/// Change how an already-loaded texture is sampled. Takes effect next frame.
+pub fn set_texture_sampler(world: &mut World, handle: TextureHandle, sampler: SamplerSettings) {
+    finish_render(world);
+    let Some(mut store) = world.resource_remove::<TextureStore>() else {
+        log::warn!("set_texture_sampler: no textures loaded yet");
+        return;
+    };
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    store.set_sampler(gpu, renderer, handle, sampler);
+    world.insert_resource(store);
+}
+
+fn load(world: &mut World, path: &str, sampler: Option<SamplerSettings>) -> TextureHandle {
+    let path = crate::launch::resolve_asset_path(world, path);
+    let path = path.as_ref();
+    ensure_store(world);
+
+    // Check cache first (need to remove store to mutate it)
+    let mut store = world
+        .resource_remove::<TextureStore>()
+        .expect("TextureStore not initialized — GpuContext is missing");
+
+    if let Some(&handle) = store.path_cache.get(path) {
+        if let Some(sampler) = sampler {
+            let gpu = world.resource::<GpuContext>();
+            let renderer = world.resource::<SpriteRenderer>();
+            store.set_sampler(gpu, renderer, handle, sampler);
+        }
+        world.insert_resource(store);
+        return handle;
+    }
+
+    // Load image from disk
+    let (width, height, data) = crate::import::load_rgba(world, Path::new(path))
+        .unwrap_or_else(|e| panic!("Failed to load texture '{}': {}", path, e));
+
+    let gpu = world.resource::<GpuContext>();
+    let renderer = world.resource::<SpriteRenderer>();
+    let atlasing = world.get_resource::<TextureAtlasing>().copied().unwrap_or_default();
+    let sampler = sampler.unwrap_or(DEFAULT_SAMPLER);
+
+    let handle = if TextureStore::packs_sampler(sampler) && atlasing.should_pack(width, height) {
+        store.pack(gpu, renderer, &atlasing, width, height, &data, sampler)
+    } else {
+        store.add_rgba(gpu, renderer, path, width, height, &data, sampler)
+    };
     store.path_cache.insert(path.to_owned(), handle);
 
     world.insert_resource(store);
@@ -0,0 +1,336 @@
+//! # Texture Atlas — Many Sprites, One Texture
+//!
+//! A [`TextureAtlas`] is one texture plus a list of rectangles inside it.
+//! Sprites pick a rectangle by index with [`Sprite::from_atlas`], so a level
+//! full of different tiles, props and characters still binds one texture and
+//! lands in one draw call:
+//!
+//! ```text
+//!   atlas texture                      sprites
+//!   ┌──────┬──────┬────┐
+//!   │ 0    │ 1    │ 2  │              Sprite::from_atlas(props, 0)  ─┐
+//!   │ tree │ rock │bush│              Sprite::from_atlas(props, 2)  ─┼─► one batch
+//!   ├──────┴──┬───┴────┤              Sprite::from_atlas(props, 4)  ─┘
+//!   │ 3 house │ 4 well │
+//!   └─────────┴────────┘
+//! ```
+//!
+//! ## Building an Atlas
+//!
+//! - [`load_texture_atlas`] reads a pre-made sheet: a JSON descriptor in the
+//!   [`SpriteAtlas`] format (texture path, size, named pixel regions).
+//! - [`pack_texture_atlas`] packs separate image files into a new texture at
+//!   load time, with extruded edges against bleeding. Regions are named after
+//!   the paths.
+//! - [`TextureAtlas::from_grid`] / [`TextureAtlas::add_region`] describe an
+//!   already-loaded texture by hand; register it with [`add_texture_atlas`].
+//!
+//! Regions are looked up by index, or by name via [`TextureAtlas::index_of`].
+//!
+//! ## Relation to Automatic Packing
+//!
+//! [Automatic atlasing](super::atlas) already packs small textures into
+//! shared pages behind the scenes. An explicit atlas is for when you want the
+//! grouping to be deliberate: big sheets above the automatic size limit,
+//! frame-indexed sprites, or sheets exported from a packing tool.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `SpriteAtlas` assets pack sprites at build time; each
+//!   `Sprite` references its atlas implicitly.
+//! - **Bevy**: `TextureAtlasLayout` asset + `TextureAtlasBuilder` for runtime
+//!   packing; `Sprite { texture_atlas: Some(TextureAtlas { layout, index }) }`.
+//! - **Godot**: `AtlasTexture` resources, one per region.
+//! - **Our approach**: Bevy's layout + index model; the atlas is resolved to
+//!   a texture and UV rectangle during extraction, so the batcher is
+//!   unchanged.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::animation::SpriteAtlas;
+use crate::ecs::World;
+use crate::math::{Rect, Vec2};
+use crate::render::GpuContext;
+
+use super::atlas::{ATLAS_PADDING, ShelfPacker, extrude};
+use super::texture::{TextureHandle, create_texture_from_rgba, load_texture};
+use super::Sprite;
+
+/// Smallest side tried when packing an atlas; doubled until everything fits.
+const MIN_PACKED_SIZE: u32 = 256;
+
+/// Handle to a [`TextureAtlas`] registered in [`TextureAtlases`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextureAtlasHandle(pub(crate) usize);
+
+/// A sprite's reference to one region of an atlas. See [`Sprite::from_atlas`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AtlasSprite {
+    pub atlas: TextureAtlasHandle,
+    /// Index into the atlas's regions.
+    pub index: usize,
+}
+
+/// A texture divided into rectangular regions.
+#[derive(Debug, Clone)]
+pub struct TextureAtlas {
+    pub texture: TextureHandle,
+    /// Texture dimensions in pixels.
+    pub size: Vec2,
+    /// Regions in pixels (top-left origin).
+    regions: Vec<Rect>,
+    names: HashMap<String, usize>,
+}
+
+impl TextureAtlas {
+    /// An atlas with no regions yet.
+    pub fn new(texture: TextureHandle, size: Vec2) -> Self {
+        Self {
+            texture,
+            size,
+            regions: Vec::new(),
+            names: HashMap::new(),
+        }
+    }
+
+    /// An atlas of `columns × rows` equal cells covering the whole texture,
+    /// indexed left-to-right, top-to-bottom.
+    pub fn from_grid(texture: TextureHandle, size: Vec2, columns: u32, rows: u32) -> Self {
+        let mut atlas = Self::new(texture, size);
+        let cell = size / Vec2::new(columns.max(1) as f32, rows.max(1) as f32);
+        for row in 0..rows {
+            for col in 0..columns {
+                let min = Vec2::new(col as f32, row as f32) * cell;
+                atlas.regions.push(Rect { min, max: min + cell });
+            }
+        }
+        atlas
+    }
+
+    /// Add a region in pixels and return its index. A `name` makes it
+    /// findable with [`index_of`](Self::index_of).
+    pub fn add_region(&mut self, name: Option<&str>, x: f32, y: f32, w: f32, h: f32) -> usize {
+        let index = self.regions.len();
+        self.regions.push(Rect {
+            min: Vec2::new(x, y),
+            max: Vec2::new(x + w, y + h),
+        });
+        if let Some(name) = name {
+            self.names.insert(name.to_owned(), index);
+        }
+        index
+    }
+
+    /// Index of the region called `name`.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+
+    /// Number of regions.
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// UV rectangle of a region, for [`Sprite::texture_rect`].
+    pub fn uv_rect(&self, index: usize) -> Option<Rect> {
+        let r = self.regions.get(index)?;
+        let size = r.max - r.min;
+        Some(Rect::from_pixels(r.min.x, r.min.y, size.x, size.y, self.size.x, self.size.y))
+    }
+
+    /// Pixel size of a region; what an atlas sprite without an explicit size
+    /// is drawn at.
+    pub fn region_size(&self, index: usize) -> Option<Vec2> {
+        self.regions.get(index).map(|r| r.max - r.min)
+    }
+}
+
+/// Resource: every registered [`TextureAtlas`].
+#[derive(Default)]
+pub struct TextureAtlases {
+    atlases: Vec<TextureAtlas>,
+}
+
+impl TextureAtlases {
+    pub fn add(&mut self, atlas: TextureAtlas) -> TextureAtlasHandle {
+        self.atlases.push(atlas);
+        TextureAtlasHandle(self.atlases.len() - 1)
+    }
+
+    pub fn get(&self, handle: TextureAtlasHandle) -> Option<&TextureAtlas> {
+        self.atlases.get(handle.0)
+    }
+
+    pub fn get_mut(&mut self, handle: TextureAtlasHandle) -> Option<&mut TextureAtlas> {
+        self.atlases.get_mut(handle.0)
+    }
+
+    /// The texture of every registered atlas.
+    pub(crate) fn textures(&self) -> impl Iterator<Item = TextureHandle> + '_ {
+        self.atlases.iter().map(|atlas| atlas.texture)
+    }
+}
+
+/// Register an atlas, creating the [`TextureAtlases`] resource if needed.
+pub fn add_texture_atlas(world: &mut World, atlas: TextureAtlas) -> TextureAtlasHandle {
+    if !world.has_resource::<TextureAtlases>() {
+        world.insert_resource(TextureAtlases::default());
+    }
+    world.resource_mut::<TextureAtlases>().add(atlas)
+}
+
+/// Load a pre-made sheet from a [`SpriteAtlas`] JSON descriptor. The texture
+/// path inside it is loaded with [`load_texture`]; region names carry over.
+/// Returns `None` (with a warning) if the descriptor can't be read.
+pub fn load_texture_atlas(world: &mut World, path: &str) -> Option<TextureAtlasHandle> {
+    let descriptor_path = crate::launch::resolve_asset_path(world, path).into_owned();
+    let descriptor = SpriteAtlas::from_file(&descriptor_path)?;
+    let texture = load_texture(world, &descriptor.texture);
+
+    let mut atlas = TextureAtlas::new(texture, descriptor.texture_size);
+    for r in &descriptor.regions {
+        atlas.add_region(Some(&r.name), r.x, r.y, r.w, r.h);
+    }
+    Some(add_texture_atlas(world, atlas))
+}
+
+/// Pack image files into one new texture. Region `i` is `paths[i]`, also
+/// findable by name under that path. Returns `None` (with a warning) if an
+/// image fails to load or they don't fit in the largest texture the GPU
+/// supports.
+pub fn pack_texture_atlas(world: &mut World, paths: &[&str]) -> Option<TextureAtlasHandle> {
+    let mut images = Vec::with_capacity(paths.len());
+    for &path in paths {
+        let resolved = crate::launch::resolve_asset_path(world, path).into_owned();
+        match crate::import::load_rgba(world, Path::new(&resolved)) {
+            Ok((w, h, data)) => images.push((w, h, data)),
+            Err(e) => {
+                log::warn!("pack_texture_atlas: failed to load '{resolved}': {e}");
+                return None;
+            }
+        }
+    }
+
+    let sizes: Vec<(u32, u32)> = images.iter().map(|&(w, h, _)| (w, h)).collect();
+    let max_side = world.resource::<GpuContext>().device.limits().max_texture_dimension_2d;
+    let Some((side, positions)) = pack_layout(&sizes, max_side) else {
+        log::warn!(
+            "pack_texture_atlas: {} images don't fit in a {max_side}x{max_side} texture",
+            paths.len()
+        );
+        return None;
+    };
+
+    // Blit each image, extruded by ATLAS_PADDING, into the page.
+    let mut pixels = vec![0u8; (side * side * 4) as usize];
+    for ((w, h, data), &(x, y)) in images.iter().zip(&positions) {
+        let (w, h) = (*w, *h);
+        let padded = extrude(data, w, h, ATLAS_PADDING);
+        let padded_w = (w + 2 * ATLAS_PADDING) as usize;
+        let (left, top) = ((x - ATLAS_PADDING) as usize, (y - ATLAS_PADDING) as usize);
+        for (row, src) in padded.chunks_exact(padded_w * 4).enumerate() {
+            let start = ((top + row) * side as usize + left) * 4;
+            pixels[start..start + src.len()].copy_from_slice(src);
+        }
+    }
+
+    let texture = create_texture_from_rgba(world, "texture atlas", side, side, &pixels);
+    let mut atlas = TextureAtlas::new(texture, Vec2::splat(side as f32));
+    for ((path, &(w, h)), &(x, y)) in paths.iter().zip(&sizes).zip(&positions) {
+        atlas.add_region(Some(path), x as f32, y as f32, w as f32, h as f32);
+    }
+    Some(add_texture_atlas(world, atlas))
+}
+
+/// Find the smallest square power-of-two page (at most `max_side`) that fits
+/// every image with padding. Returns the side and each image's top-left
+/// corner, inside its padding.
+fn pack_layout(sizes: &[(u32, u32)], max_side: u32) -> Option<(u32, Vec<(u32, u32)>)> {
+    let mut side = MIN_PACKED_SIZE.min(max_side);
+    loop {
+        let mut packer = ShelfPacker::new(side, side);
+        let positions: Option<Vec<_>> = sizes
+            .iter()
+            .map(|&(w, h)| {
+                packer
+                    .allocate(w + 2 * ATLAS_PADDING, h + 2 * ATLAS_PADDING)
+                    .map(|(x, y)| (x + ATLAS_PADDING, y + ATLAS_PADDING))
+            })
+            .collect();
+        if let Some(positions) = positions {
+            return Some((side, positions));
+        }
+        if side >= max_side {
+            return None;
+        }
+        side = (side * 2).min(max_side);
+    }
+}
+
+/// Point an atlas sprite at its atlas texture and region. Sprites whose
+/// atlas or index doesn't exist are left as they are.
+pub(crate) fn resolve_atlas_sprite(sprite: &mut Sprite, atlases: Option<&TextureAtlases>) {
+    let Some(AtlasSprite { atlas, index }) = sprite.atlas else {
+        return;
+    };
+    let Some(atlas) = atlases.and_then(|a| a.get(atlas)) else {
+        return;
+    };
+    let (Some(rect), Some(size)) = (atlas.uv_rect(index), atlas.region_size(index)) else {
+        return;
+    };
+    sprite.texture = Some(atlas.texture);
+    sprite.texture_rect = rect;
+    if sprite.size == Vec2::ZERO {
+        sprite.size = size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_regions_run_row_major() {
+        let atlas = TextureAtlas::from_grid(TextureHandle(1), Vec2::new(64.0, 32.0), 4, 2);
+        assert_eq!(atlas.len(), 8);
+        assert_eq!(atlas.region_size(5), Some(Vec2::new(16.0, 16.0)));
+        let uv = atlas.uv_rect(5).unwrap();
+        assert_eq!(uv.min, Vec2::new(0.25, 0.5));
+        assert_eq!(uv.max, Vec2::new(0.5, 1.0));
+        assert_eq!(atlas.uv_rect(8), None);
+    }
+
+    #[test]
+    fn packing_grows_the_page_until_everything_fits() {
+        let (side, positions) = pack_layout(&[(200, 200), (200, 200)], 4096).unwrap();
+        assert_eq!(side, 512);
+        assert_eq!(positions[0], (ATLAS_PADDING, ATLAS_PADDING));
+        assert!(pack_layout(&[(300, 300)], 256).is_none());
+    }
+
+    #[test]
+    fn atlas_sprites_resolve_to_texture_and_region() {
+        let mut atlases = TextureAtlases::default();
+        let mut atlas = TextureAtlas::new(TextureHandle(3), Vec2::new(100.0, 100.0));
+        atlas.add_region(Some("coin"), 10.0, 20.0, 30.0, 40.0);
+        assert_eq!(atlas.index_of("coin"), Some(0));
+        let handle = atlases.add(atlas);
+
+        let mut sprite = Sprite::from_atlas(handle, 0);
+        resolve_atlas_sprite(&mut sprite, Some(&atlases));
+        assert_eq!(sprite.texture, Some(TextureHandle(3)));
+        assert_eq!(sprite.size, Vec2::new(30.0, 40.0));
+        assert_eq!(sprite.texture_rect.min, Vec2::new(0.1, 0.2));
+
+        // Unknown index: unchanged.
+        let mut missing = Sprite::from_atlas(handle, 7);
+        resolve_atlas_sprite(&mut missing, Some(&atlases));
+        assert_eq!(missing.texture, None);
+    }
+}
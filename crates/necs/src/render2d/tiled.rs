@@ -0,0 +1,797 @@
+//! # Tiled — Loading Maps from the Tiled Editor
+//!
+//! [Tiled](https://www.mapeditor.org) saves a map as a grid of *global tile
+//! IDs* (gids) per layer, plus one or more *tilesets* that say which image
+//! region each gid shows. [`load_tilemap`] turns that into a [`Tilemap`]:
+//!
+//! ```text
+//!   level.tmx / level.json                          Tilemap
+//!   ┌─────────────────────────┐
+//!   │ tileset  firstgid=1 ────┼──► tiles.png ──► TextureAtlas (grid regions)
+//!   │ layer "ground"  1,1,0,5 ┼──► TileLayer  gid − firstgid = region index
+//!   │ layer "walls"   collision=true ──────────► collision layer (physics2d)
+//!   └─────────────────────────┘
+//! ```
+//!
+//! Both the XML (`.tmx`, with `.tsx` tilesets) and JSON (`.json`/`.tmj`,
+//! with `.json`/`.tsj` tilesets) formats are read. The map file and any
+//! external tileset are hot-reloaded; the tileset image reloads like any
+//! other texture.
+//!
+//! ## Supported Subset
+//!
+//! - Orthogonal, finite maps with tile layer data stored as CSV (the
+//!   default). Base64 or compressed data and infinite maps are rejected with
+//!   an error.
+//! - One tileset per map, built from a single image. Tiles from any other
+//!   tileset are dropped with a warning.
+//! - Horizontal and vertical flips. Diagonal flips (rotation) are ignored.
+//! - A layer with the custom bool property `collision` set to true becomes
+//!   the map's [collision layer](Tilemap::collision_layer).
+//! - Layers inside groups are flattened; object and image layers are
+//!   skipped.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Needs a third-party importer (SuperTiled2Unity) that turns
+//!   `.tmx` files into prefabs at import time.
+//! - **Bevy**: `bevy_ecs_tiled` loads maps as assets on top of
+//!   `bevy_ecs_tilemap`.
+//! - **Godot**: Importer plugins (YATI, Tiled Importer) convert maps to
+//!   `TileMapLayer` scenes.
+//! - **Our approach**: A small built-in reader for the common subset, so a
+//!   level from Tiled is one function call away.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::asset::AssetServer;
+use crate::ecs::World;
+use crate::math::Vec2;
+
+use super::texture::load_texture;
+use super::texture_atlas::{TextureAtlas, TextureAtlasHandle, TextureAtlases, add_texture_atlas};
+use super::tilemap::{Tile, TileLayer, Tilemap};
+
+/// Tiled stores flips in the top bits of each gid.
+const FLIPPED_HORIZONTALLY: u32 = 0x8000_0000;
+const FLIPPED_VERTICALLY: u32 = 0x4000_0000;
+const GID_MASK: u32 = 0x0FFF_FFFF;
+
+/// Errors that can occur while loading a Tiled map.
+#[derive(Debug)]
+pub enum TiledError {
+    /// The map or a tileset could not be read.
+    Read(String),
+    /// The file was read but isn't a well-formed map or tileset.
+    Parse(String),
+    /// The map uses a feature this loader doesn't support.
+    Unsupported(String),
+}
+
+impl fmt::Display for TiledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TiledError::Read(e) => write!(f, "Tiled read failed: {e}"),
+            TiledError::Parse(e) => write!(f, "Tiled parse failed: {e}"),
+            TiledError::Unsupported(e) => write!(f, "unsupported Tiled map: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TiledError {}
+
+// ── Parsed map ──────────────────────────────────────────────────────────
+
+/// A Tiled map reduced to what a [`Tilemap`] needs.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TiledMap {
+    pub width: u32,
+    pub height: u32,
+    pub tile_size: Vec2,
+    pub tileset: TiledTileset,
+    pub layers: Vec<TiledLayer>,
+}
+
+/// A single-image tileset.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub(crate) struct TiledTileset {
+    pub first_gid: u32,
+    pub tile_size: Vec2,
+    pub columns: u32,
+    pub tile_count: u32,
+    pub margin: u32,
+    pub spacing: u32,
+    /// Image path, relative to the file the tileset came from.
+    pub image: PathBuf,
+    pub image_size: Vec2,
+    /// External tileset file, relative to the map.
+    pub source: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TiledLayer {
+    pub name: String,
+    pub visible: bool,
+    pub opacity: f32,
+    pub collision: bool,
+    /// Raw gids, flip bits included; 0 is empty.
+    pub gids: Vec<u32>,
+}
+
+impl TiledTileset {
+    /// Atlas regions in pixels, indexed like the tileset's tiles.
+    fn regions(&self) -> impl Iterator<Item = (f32, f32)> + '_ {
+        let columns = self.columns.max(1);
+        (0..self.tile_count).map(move |i| {
+            let (col, row) = (i % columns, i / columns);
+            (
+                (self.margin + col * (self.tile_size.x as u32 + self.spacing)) as f32,
+                (self.margin + row * (self.tile_size.y as u32 + self.spacing)) as f32,
+            )
+        })
+    }
+}
+
+impl TiledMap {
+    /// Read a map, and its external tileset if it has one.
+    pub(crate) fn load(path: &Path) -> Result<Self, TiledError> {
+        let text = read(path)?;
+        let mut map = if is_json(path) {
+            parse_json_map(&text)?
+        } else {
+            parse_tmx(&text)?
+        };
+        if let Some(source) = map.tileset.source.clone() {
+            let tileset_path = path.parent().unwrap_or(Path::new("")).join(&source);
+            let text = read(&tileset_path)?;
+            let mut tileset = if is_json(&tileset_path) {
+                parse_json_tileset(&text)?
+            } else {
+                parse_tsx(&text)?
+            };
+            tileset.first_gid = map.tileset.first_gid;
+            tileset.source = Some(source.clone());
+            // The image is relative to the tileset file; make it relative
+            // to the map like an inline tileset's.
+            tileset.image = source.parent().unwrap_or(Path::new("")).join(&tileset.image);
+            map.tileset = tileset;
+        }
+        Ok(map)
+    }
+
+    /// The layers as [`TileLayer`]s. Tiles from other tilesets are dropped.
+    fn tile_layers(&self, path: &Path) -> Vec<TileLayer> {
+        let first = self.tileset.first_gid;
+        let last = first + self.tileset.tile_count;
+        let mut foreign = 0;
+        let layers = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let tiles = layer
+                    .gids
+                    .iter()
+                    .map(|&raw| {
+                        let gid = raw & GID_MASK;
+                        if gid == 0 {
+                            return None;
+                        }
+                        if gid < first || gid >= last {
+                            foreign += 1;
+                            return None;
+                        }
+                        Some(Tile {
+                            index: gid - first,
+                            flip_x: raw & FLIPPED_HORIZONTALLY != 0,
+                            flip_y: raw & FLIPPED_VERTICALLY != 0,
+                        })
+                    })
+                    .collect();
+                let mut tile_layer = TileLayer::new(&layer.name).tiles(tiles).opacity(layer.opacity);
+                if !layer.visible {
+                    tile_layer = tile_layer.hidden();
+                }
+                tile_layer
+            })
+            .collect();
+        if foreign > 0 {
+            log::warn!(
+                "Tiled map '{}': dropped {foreign} tiles from tilesets after the first",
+                path.display()
+            );
+        }
+        layers
+    }
+
+    /// Build a [`Tilemap`] drawing from `atlas`.
+    fn tilemap(&self, atlas: TextureAtlasHandle, path: &Path) -> Tilemap {
+        let mut map = Tilemap::new(atlas, self.tile_size, self.width, self.height);
+        for layer in self.tile_layers(path) {
+            map = map.layer(layer);
+        }
+        if let Some(layer) = self.layers.iter().find(|layer| layer.collision) {
+            map = map.collision_layer(&layer.name);
+        }
+        map
+    }
+}
+
+fn read(path: &Path) -> Result<String, TiledError> {
+    std::fs::read_to_string(path).map_err(|e| TiledError::Read(format!("{}: {e}", path.display())))
+}
+
+fn is_json(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("json" | "tmj" | "tsj")
+    )
+}
+
+/// Parse comma-separated gids, as in a CSV `<data>` element.
+fn parse_csv(text: &str) -> Result<Vec<u32>, TiledError> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.parse().map_err(|_| TiledError::Parse(format!("bad tile id '{s}'"))))
+        .collect()
+}
+
+fn check_layer_size(layer: &TiledLayer, width: u32, height: u32) -> Result<(), TiledError> {
+    if layer.gids.len() != (width * height) as usize {
+        return Err(TiledError::Parse(format!(
+            "layer '{}' has {} tiles, expected {width}×{height}",
+            layer.name,
+            layer.gids.len()
+        )));
+    }
+    Ok(())
+}
+
+// ── TMX / TSX (XML) ─────────────────────────────────────────────────────
+
+/// An XML element: just enough of XML for Tiled files.
+#[derive(Debug, Default)]
+struct XmlElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<XmlElement>,
+    text: String,
+}
+
+impl XmlElement {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Attribute parsed as a number, or `default` if absent.
+    fn number<T: std::str::FromStr>(&self, name: &str, default: T) -> Result<T, TiledError> {
+        match self.attr(name) {
+            Some(value) => value.trim().parse().map_err(|_| {
+                TiledError::Parse(format!("<{}> has bad {name}=\"{value}\"", self.name))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    fn child(&self, name: &str) -> Option<&XmlElement> {
+        self.children.iter().find(|c| c.name == name)
+    }
+}
+
+/// Parse an XML document into its root element. Handles declarations,
+/// comments, self-closing tags and the five predefined entities; no DTDs
+/// or CDATA.
+fn parse_xml(text: &str) -> Result<XmlElement, TiledError> {
+    let err = |msg: &str| TiledError::Parse(format!("XML: {msg}"));
+    let mut stack: Vec<XmlElement> = vec![XmlElement::default()];
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let content = &rest[..start];
+        stack.last_mut().expect("root").text.push_str(&unescape(content));
+        rest = &rest[start..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or_else(|| err("unclosed comment"))?;
+            rest = &after[end + 3..];
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            let end = rest.find('>').ok_or_else(|| err("unclosed declaration"))?;
+            rest = &rest[end + 1..];
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or_else(|| err("unclosed end tag"))?;
+            let name = after[..end].trim();
+            let element = stack.pop().filter(|_| !stack.is_empty()).ok_or_else(|| err("unexpected end tag"))?;
+            if element.name != name {
+                return Err(err(&format!("</{name}> closes <{}>", element.name)));
+            }
+            stack.last_mut().expect("root").children.push(element);
+            rest = &after[end + 1..];
+        } else {
+            let end = rest.find('>').ok_or_else(|| err("unclosed tag"))?;
+            let tag = &rest[1..end];
+            let (tag, self_closing) = match tag.strip_suffix('/') {
+                Some(tag) => (tag, true),
+                None => (tag, false),
+            };
+            let element = parse_tag(tag).ok_or_else(|| err(&format!("bad tag <{tag}>")))?;
+            if self_closing {
+                stack.last_mut().expect("root").children.push(element);
+            } else {
+                stack.push(element);
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+    if stack.len() != 1 {
+        return Err(err("unclosed element"));
+    }
+    stack
+        .pop()
+        .and_then(|doc| doc.children.into_iter().next())
+        .ok_or_else(|| err("no root element"))
+}
+
+/// Parse `name key="value" ...` from inside a tag.
+fn parse_tag(tag: &str) -> Option<XmlElement> {
+    let tag = tag.trim();
+    let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+    let mut element = XmlElement {
+        name: tag[..name_end].to_owned(),
+        ..Default::default()
+    };
+    let mut rest = tag[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let close = after[1..].find(quote)?;
+        element
+            .attributes
+            .push((key.to_owned(), unescape(&after[1..close + 1])));
+        rest = after[close + 2..].trim_start();
+    }
+    (!element.name.is_empty()).then_some(element)
+}
+
+fn unescape(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_owned();
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn check_tmx_map(map: &XmlElement) -> Result<(), TiledError> {
+    if map.name != "map" {
+        return Err(TiledError::Parse(format!("root is <{}>, not <map>", map.name)));
+    }
+    if let Some(orientation) = map.attr("orientation")
+        && orientation != "orthogonal"
+    {
+        return Err(TiledError::Unsupported(format!("{orientation} orientation")));
+    }
+    if map.attr("infinite") == Some("1") {
+        return Err(TiledError::Unsupported("infinite map".into()));
+    }
+    Ok(())
+}
+
+fn parse_tmx(text: &str) -> Result<TiledMap, TiledError> {
+    let map = parse_xml(text)?;
+    check_tmx_map(&map)?;
+    let tileset = map
+        .child("tileset")
+        .ok_or_else(|| TiledError::Parse("map has no tileset".into()))?;
+    let tileset = match tileset.attr("source") {
+        Some(source) => TiledTileset {
+            first_gid: tileset.number("firstgid", 1)?,
+            source: Some(PathBuf::from(source)),
+            ..Default::default()
+        },
+        None => TiledTileset {
+            first_gid: tileset.number("firstgid", 1)?,
+            ..tsx_tileset(tileset)?
+        },
+    };
+
+    let (width, height) = (map.number("width", 0)?, map.number("height", 0)?);
+    let mut layers = Vec::new();
+    collect_tmx_layers(&map, &mut layers)?;
+    for layer in &layers {
+        check_layer_size(layer, width, height)?;
+    }
+    Ok(TiledMap {
+        width,
+        height,
+        tile_size: Vec2::new(map.number("tilewidth", 0.0)?, map.number("tileheight", 0.0)?),
+        tileset,
+        layers,
+    })
+}
+
+/// Tile layers in draw order, flattening groups.
+fn collect_tmx_layers(parent: &XmlElement, layers: &mut Vec<TiledLayer>) -> Result<(), TiledError> {
+    for element in &parent.children {
+        match element.name.as_str() {
+            "group" => collect_tmx_layers(element, layers)?,
+            "layer" => {
+                let name = element.attr("name").unwrap_or_default().to_owned();
+                let data = element
+                    .child("data")
+                    .ok_or_else(|| TiledError::Parse(format!("layer '{name}' has no data")))?;
+                match data.attr("encoding") {
+                    Some("csv") => {}
+                    encoding => {
+                        return Err(TiledError::Unsupported(format!(
+                            "layer '{name}' uses {} encoding; save it as CSV",
+                            encoding.unwrap_or("XML")
+                        )));
+                    }
+                }
+                let collision = element.child("properties").is_some_and(|props| {
+                    props.children.iter().any(|p| {
+                        p.attr("name") == Some("collision") && p.attr("value") == Some("true")
+                    })
+                });
+                layers.push(TiledLayer {
+                    name,
+                    visible: element.attr("visible") != Some("0"),
+                    opacity: element.number("opacity", 1.0)?,
+                    collision,
+                    gids: parse_csv(&data.text)?,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Read a `<tileset>` element's own fields (inline or from a `.tsx`).
+fn tsx_tileset(tileset: &XmlElement) -> Result<TiledTileset, TiledError> {
+    let image = tileset
+        .child("image")
+        .ok_or_else(|| TiledError::Unsupported("tileset without a single image".into()))?;
+    Ok(TiledTileset {
+        first_gid: 1,
+        tile_size: Vec2::new(tileset.number("tilewidth", 0.0)?, tileset.number("tileheight", 0.0)?),
+        columns: tileset.number("columns", 0)?,
+        tile_count: tileset.number("tilecount", 0)?,
+        margin: tileset.number("margin", 0)?,
+        spacing: tileset.number("spacing", 0)?,
+        image: PathBuf::from(image.attr("source").unwrap_or_default()),
+        image_size: Vec2::new(image.number("width", 0.0)?, image.number("height", 0.0)?),
+        source: None,
+    })
+}
+
+fn parse_tsx(text: &str) -> Result<TiledTileset, TiledError> {
+    let tileset = parse_xml(text)?;
+    if tileset.name != "tileset" {
+        return Err(TiledError::Parse(format!("root is <{}>, not <tileset>", tileset.name)));
+    }
+    tsx_tileset(&tileset)
+}
+
+// ── JSON ────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize)]
+struct JsonMap {
+    width: u32,
+    height: u32,
+    tilewidth: f32,
+    tileheight: f32,
+    #[serde(default)]
+    infinite: bool,
+    #[serde(default)]
+    orientation: Option<String>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    tilesets: Vec<JsonTileset>,
+}
+
+#[derive(Deserialize)]
+struct JsonLayer {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default = "default_true")]
+    visible: bool,
+    #[serde(default = "default_opacity")]
+    opacity: f32,
+    #[serde(default)]
+    encoding: Option<String>,
+    #[serde(default)]
+    data: Vec<u32>,
+    #[serde(default)]
+    layers: Vec<JsonLayer>,
+    #[serde(default)]
+    properties: Vec<JsonProperty>,
+}
+
+#[derive(Deserialize)]
+struct JsonProperty {
+    name: String,
+    value: serde_json::Value,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct JsonTileset {
+    firstgid: u32,
+    source: Option<String>,
+    tilewidth: f32,
+    tileheight: f32,
+    columns: u32,
+    tilecount: u32,
+    margin: u32,
+    spacing: u32,
+    image: Option<String>,
+    imagewidth: f32,
+    imageheight: f32,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_opacity() -> f32 {
+    1.0
+}
+
+impl JsonTileset {
+    fn into_tileset(self) -> Result<TiledTileset, TiledError> {
+        if let Some(source) = self.source {
+            return Ok(TiledTileset {
+                first_gid: self.firstgid,
+                source: Some(PathBuf::from(source)),
+                ..Default::default()
+            });
+        }
+        let image = self
+            .image
+            .ok_or_else(|| TiledError::Unsupported("tileset without a single image".into()))?;
+        Ok(TiledTileset {
+            first_gid: self.firstgid,
+            tile_size: Vec2::new(self.tilewidth, self.tileheight),
+            columns: self.columns,
+            tile_count: self.tilecount,
+            margin: self.margin,
+            spacing: self.spacing,
+            image: PathBuf::from(image),
+            image_size: Vec2::new(self.imagewidth, self.imageheight),
+            source: None,
+        })
+    }
+}
+
+fn parse_json_map(text: &str) -> Result<TiledMap, TiledError> {
+    let map: JsonMap = serde_json::from_str(text).map_err(|e| TiledError::Parse(e.to_string()))?;
+    if let Some(orientation) = map.orientation.as_deref()
+        && orientation != "orthogonal"
+    {
+        return Err(TiledError::Unsupported(format!("{orientation} orientation")));
+    }
+    if map.infinite {
+        return Err(TiledError::Unsupported("infinite map".into()));
+    }
+    let tileset = map
+        .tilesets
+        .into_iter()
+        .next()
+        .ok_or_else(|| TiledError::Parse("map has no tileset".into()))?
+        .into_tileset()?;
+
+    let mut layers = Vec::new();
+    collect_json_layers(map.layers, &mut layers)?;
+    for layer in &layers {
+        check_layer_size(layer, map.width, map.height)?;
+    }
+    Ok(TiledMap {
+        width: map.width,
+        height: map.height,
+        tile_size: Vec2::new(map.tilewidth, map.tileheight),
+        tileset,
+        layers,
+    })
+}
+
+fn collect_json_layers(source: Vec<JsonLayer>, layers: &mut Vec<TiledLayer>) -> Result<(), TiledError> {
+    for layer in source {
+        match layer.kind.as_str() {
+            "group" => collect_json_layers(layer.layers, layers)?,
+            "tilelayer" => {
+                if let Some(encoding) = layer.encoding.filter(|e| e != "csv") {
+                    return Err(TiledError::Unsupported(format!(
+                        "layer '{}' uses {encoding} encoding; save it as CSV",
+                        layer.name
+                    )));
+                }
+                let collision = layer
+                    .properties
+                    .iter()
+                    .any(|p| p.name == "collision" && p.value == serde_json::Value::Bool(true));
+                layers.push(TiledLayer {
+                    name: layer.name,
+                    visible: layer.visible,
+                    opacity: layer.opacity,
+                    collision,
+                    gids: layer.data,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn parse_json_tileset(text: &str) -> Result<TiledTileset, TiledError> {
+    let tileset: JsonTileset = serde_json::from_str(text).map_err(|e| TiledError::Parse(e.to_string()))?;
+    tileset.into_tileset()
+}
+
+// ── Loading ─────────────────────────────────────────────────────────────
+
+/// Load the tileset image and cut it into an atlas.
+fn tileset_atlas(world: &mut World, tileset: &TiledTileset, dir: &Path) -> TextureAtlas {
+    let image = dir.join(&tileset.image);
+    let texture = load_texture(world, &image.to_string_lossy());
+    let mut atlas = TextureAtlas::new(texture, tileset.image_size);
+    for (x, y) in tileset.regions() {
+        atlas.add_region(None, x, y, tileset.tile_size.x, tileset.tile_size.y);
+    }
+    atlas
+}
+
+/// Load a Tiled map (`.tmx` or `.json`) as a [`Tilemap`], ready to spawn.
+/// The tileset image is loaded as a texture and cut into a new atlas.
+/// The map is watched: edits saved in Tiled update every tilemap spawned
+/// from it. Returns `None` (with a warning) if the map can't be loaded.
+///
+/// ```ignore
+/// if let Some(map) = load_tilemap(world, "levels/1-1.tmx") {
+///     world.spawn((Transform::from_xy(-400.0, 300.0), map));
+/// }
+/// ```
+pub fn load_tilemap(world: &mut World, path: &str) -> Option<Tilemap> {
+    let resolved = PathBuf::from(crate::launch::resolve_asset_path(world, path).into_owned());
+    let parsed = match TiledMap::load(&resolved) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            log::warn!("Failed to load Tiled map '{path}': {e}");
+            return None;
+        }
+    };
+    let dir = resolved.parent().unwrap_or(Path::new("")).to_path_buf();
+    let atlas = tileset_atlas(world, &parsed.tileset, &dir);
+    let handle = add_texture_atlas(world, atlas);
+    let mut map = parsed.tilemap(handle, &resolved);
+    map.set_source(resolved.canonicalize().unwrap_or(resolved.clone()));
+
+    if let Some(server) = world.get_resource_mut::<AssetServer>() {
+        server.watch_custom(&resolved, reload_tilemap);
+        if let Some(source) = &parsed.tileset.source {
+            server.add_dependency(&resolved, dir.join(source));
+        }
+    }
+    Some(map)
+}
+
+/// Hot-reload callback for watched Tiled maps: rebuild the atlas and tiles
+/// of every tilemap loaded from `path`.
+fn reload_tilemap(world: &mut World, path: &Path) {
+    let canonical = path.canonicalize().unwrap_or(path.to_path_buf());
+    let mut atlases = Vec::new();
+    world.query::<(&Tilemap,)>(|_, (map,)| {
+        if map.source() == Some(canonical.as_path()) && !atlases.contains(&map.atlas) {
+            atlases.push(map.atlas);
+        }
+    });
+    if atlases.is_empty() {
+        return;
+    }
+    let parsed = match TiledMap::load(path) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            log::warn!("Keeping previous map, reload of '{}' failed: {e}", path.display());
+            return;
+        }
+    };
+
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let atlas = tileset_atlas(world, &parsed.tileset, dir);
+    if let Some(store) = world.get_resource_mut::<TextureAtlases>() {
+        for &handle in &atlases {
+            if let Some(existing) = store.get_mut(handle) {
+                *existing = atlas.clone();
+            }
+        }
+    }
+    world.query::<(&mut Tilemap,)>(|_, (map,)| {
+        if map.source() == Some(canonical.as_path()) {
+            let fresh = parsed.tilemap(map.atlas, path);
+            map.replace_with(fresh);
+        }
+    });
+    log::info!("Reloaded Tiled map '{}'", path.display());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TMX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<map version="1.10" orientation="orthogonal" width="3" height="2" tilewidth="16" tileheight="16" infinite="0">
+ <!-- A tiny test map -->
+ <tileset firstgid="1" name="tiles" tilewidth="16" tileheight="16" spacing="2" margin="1" tilecount="6" columns="3">
+  <image source="tiles.png" width="55" height="37"/>
+ </tileset>
+ <layer id="1" name="ground" width="3" height="2">
+  <data encoding="csv">
+1,2,0,
+2147483652,0,6
+</data>
+ </layer>
+ <group name="fg">
+  <layer id="2" name="walls &amp; floors" width="3" height="2" opacity="0.5">
+   <properties>
+    <property name="collision" type="bool" value="true"/>
+   </properties>
+   <data encoding="csv">0,0,0,5,5,5</data>
+  </layer>
+ </group>
+ <objectgroup id="3" name="spawns"/>
+</map>"#;
+
+    #[test]
+    fn parses_tmx_with_flips_groups_and_collision() {
+        let map = parse_tmx(TMX).expect("valid map");
+        assert_eq!((map.width, map.height, map.tile_size), (3, 2, Vec2::splat(16.0)));
+        assert_eq!(map.tileset.image, PathBuf::from("tiles.png"));
+        let regions: Vec<_> = map.tileset.regions().collect();
+        assert_eq!(regions[4], (19.0, 19.0));
+
+        assert_eq!(map.layers.len(), 2);
+        assert_eq!(map.layers[1].name, "walls & floors");
+        assert!(map.layers[1].collision && !map.layers[0].collision);
+
+        let tilemap = map.tilemap(TextureAtlasHandle(0), Path::new("test.tmx"));
+        assert_eq!(tilemap.collision_layer_name(), Some("walls & floors"));
+        assert_eq!(tilemap.tile(0, 1, 0), Some(Tile::new(1)));
+        assert_eq!(tilemap.tile(0, 2, 0), None);
+        assert_eq!(tilemap.tile(0, 0, 1), Some(Tile::new(3).flip_x()));
+    }
+
+    #[test]
+    fn parses_json_and_rejects_base64() {
+        let json = r#"{
+            "width": 2, "height": 1, "tilewidth": 8, "tileheight": 8,
+            "orientation": "orthogonal", "infinite": false,
+            "tilesets": [{ "firstgid": 1, "source": "tiles.tsj" }],
+            "layers": [
+                { "type": "tilelayer", "name": "ground", "data": [1, 1073741826],
+                  "properties": [{ "name": "collision", "type": "bool", "value": true }] },
+                { "type": "objectgroup", "name": "spawns" }
+            ]
+        }"#;
+        let map = parse_json_map(json).expect("valid map");
+        assert_eq!(map.tileset.source, Some(PathBuf::from("tiles.tsj")));
+        assert_eq!(map.layers[0].gids, [1, 0x4000_0002]);
+        assert!(map.layers[0].collision);
+
+        let base64 = json.replace(r#""name": "ground","#, r#""name": "ground", "encoding": "base64","#);
+        assert!(matches!(parse_json_map(&base64), Err(TiledError::Unsupported(_))));
+        assert!(matches!(parse_tmx("<map><layer>"), Err(TiledError::Parse(_))));
+    }
+}
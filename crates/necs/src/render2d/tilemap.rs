@@ -0,0 +1,682 @@
+//! # Tilemap — A Level Grid in One Draw Call
+//!
+//! A [`Tilemap`] is a grid of tiles drawn from one [`TextureAtlas`]. Spawning
+//! a sprite per tile works for a few hundred tiles, but a 200×100 level with
+//! three layers is 60,000 entities to query, transform and sort every frame.
+//! A tilemap is one entity instead, and its geometry is built once:
+//!
+//! ```text
+//!   Tilemap (1 entity)                     chunk meshes (cached)
+//!   ┌────────┬────────┬────────┐
+//!   │ chunk  │ chunk  │ chunk  │  set_tile ──► mark one chunk dirty
+//!   │ 16×16  │ 16×16  │ 16×16  │               rebuild it at extract
+//!   ├────────┼────────┼────────┤
+//!   │  ...   │  ...   │  ...   │  off-screen chunks are skipped; the rest
+//!   └────────┴────────┴────────┘  share the atlas texture ──► one batch
+//! ```
+//!
+//! The map is split into chunks of [`CHUNK_TILES`]² tiles. Each chunk keeps a
+//! mesh of all its layers, rebuilt only when one of its tiles changes.
+//! Chunks outside the camera's view are left out of the frame. The rest all
+//! use the atlas texture, so the batcher merges the whole map into a single
+//! draw call.
+//!
+//! ## Coordinates
+//!
+//! Tile `(0, 0)` is the top-left tile and rows run downward, as in Tiled.
+//! The map's top-left corner sits at the entity's
+//! [`Transform`](crate::math::Transform), and it extends right along +X and
+//! down along −Y. Layers are drawn in order, so later layers cover earlier
+//! ones.
+//!
+//! ## Loading and Collision
+//!
+//! [`load_tilemap`](super::tiled::load_tilemap) builds a tilemap from a
+//! [Tiled](https://www.mapeditor.org) map. With the `physics2d` feature, a
+//! map with a [collision layer](Tilemap::collision_layer) gets fixed
+//! colliders for its solid tiles, merged into as few rectangles as possible:
+//!
+//! ```text
+//!   ██████        ┌────┐          six tiles ──► two boxes
+//!   ██              │    │
+//!   ██              ├─┐──┘
+//!                   └─┘
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Tilemap` + `TilemapRenderer` in chunk mode, and a
+//!   `TilemapCollider2D` merged by a `CompositeCollider2D`.
+//! - **Bevy**: No built-in tilemap; `bevy_ecs_tilemap` uses an entity per
+//!   tile and chunked GPU meshes.
+//! - **Godot**: `TileMapLayer` nodes with per-tile physics shapes defined in
+//!   the `TileSet`.
+//! - **Our approach**: Unity's shape — one component, chunked meshes, merged
+//!   colliders — with tiles stored as plain data rather than entities.
+
+use std::path::{Path, PathBuf};
+
+use crate::ecs::World;
+use crate::ecs::hierarchy::GlobalTransform;
+use crate::math::{Rect, Vec2};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
+
+use super::texture::TextureHandle;
+use super::texture_atlas::{TextureAtlas, TextureAtlasHandle, TextureAtlases};
+use super::vertex::SpriteVertex;
+
+/// Side of a chunk, in tiles.
+pub const CHUNK_TILES: u32 = 16;
+
+// ── Components ──────────────────────────────────────────────────────────
+
+/// One cell of a [`TileLayer`]: an atlas region, optionally mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tile {
+    /// Index into the tilemap's atlas regions.
+    pub index: u32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+}
+
+impl Tile {
+    pub fn new(index: u32) -> Self {
+        Self {
+            index,
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    /// Mirror horizontally (builder pattern).
+    pub fn flip_x(mut self) -> Self {
+        self.flip_x = true;
+        self
+    }
+
+    /// Mirror vertically (builder pattern).
+    pub fn flip_y(mut self) -> Self {
+        self.flip_y = true;
+        self
+    }
+}
+
+/// A named grid of [`Tile`]s, row-major from the top-left.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileLayer {
+    name: String,
+    visible: bool,
+    opacity: f32,
+    tiles: Vec<Option<Tile>>,
+}
+
+impl TileLayer {
+    /// An empty layer; it is sized to the map when added.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            visible: true,
+            opacity: 1.0,
+            tiles: Vec::new(),
+        }
+    }
+
+    /// Fill the layer, row-major from the top-left (builder pattern).
+    pub fn tiles(mut self, tiles: Vec<Option<Tile>>) -> Self {
+        self.tiles = tiles;
+        self
+    }
+
+    /// Set the layer's alpha, 0.0–1.0 (builder pattern).
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Start hidden (builder pattern).
+    pub fn hidden(mut self) -> Self {
+        self.visible = false;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+}
+
+/// Component: a grid of tiles drawn from a [`TextureAtlas`]. See the
+/// [module docs](self).
+///
+/// ```ignore
+/// let atlas = add_texture_atlas(world, TextureAtlas::from_grid(tiles, Vec2::splat(64.0), 4, 4));
+/// let map = Tilemap::new(atlas, Vec2::splat(16.0), 40, 30)
+///     .layer(TileLayer::new("ground").tiles(ground))
+///     .collision_layer("ground");
+/// world.spawn((Transform::from_xy(-320.0, 240.0), map));
+/// ```
+#[derive(Clone)]
+pub struct Tilemap {
+    pub atlas: TextureAtlasHandle,
+    /// Size of one tile in world units.
+    pub tile_size: Vec2,
+    width: u32,
+    height: u32,
+    layers: Vec<TileLayer>,
+    collision_layer: Option<String>,
+    /// Colliders need (re)generating (`physics2d`).
+    #[cfg_attr(not(feature = "physics2d"), allow(dead_code))]
+    colliders_dirty: bool,
+    /// The Tiled file this map was loaded from, for hot reload.
+    source: Option<PathBuf>,
+    chunks: Vec<TileChunk>,
+}
+
+/// Cached geometry for one chunk, in the map's local space.
+#[derive(Clone, Default)]
+struct TileChunk {
+    dirty: bool,
+    vertices: Vec<SpriteVertex>,
+    indices: Vec<u32>,
+}
+
+impl Tilemap {
+    /// An empty `width × height` map of `tile_size` tiles.
+    pub fn new(atlas: TextureAtlasHandle, tile_size: Vec2, width: u32, height: u32) -> Self {
+        let chunk_count = (width.div_ceil(CHUNK_TILES) * height.div_ceil(CHUNK_TILES)) as usize;
+        Self {
+            atlas,
+            tile_size,
+            width,
+            height,
+            layers: Vec::new(),
+            collision_layer: None,
+            colliders_dirty: true,
+            source: None,
+            chunks: vec![
+                TileChunk {
+                    dirty: true,
+                    ..Default::default()
+                };
+                chunk_count
+            ],
+        }
+    }
+
+    /// Add a layer on top of the others (builder pattern). Its tiles are
+    /// padded or cut to the map's size.
+    pub fn layer(mut self, mut layer: TileLayer) -> Self {
+        layer.tiles.resize((self.width * self.height) as usize, None);
+        self.layers.push(layer);
+        self.mark_all_dirty();
+        self
+    }
+
+    /// Generate colliders from the layer called `name` (builder pattern).
+    /// Every non-empty tile in it is solid. Needs the `physics2d` feature.
+    pub fn collision_layer(mut self, name: &str) -> Self {
+        self.collision_layer = Some(name.to_owned());
+        self.colliders_dirty = true;
+        self
+    }
+
+    /// Width in tiles.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Height in tiles.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    /// Index of the layer called `name`.
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|layer| layer.name == name)
+    }
+
+    /// Name of the layer colliders are generated from.
+    pub fn collision_layer_name(&self) -> Option<&str> {
+        self.collision_layer.as_deref()
+    }
+
+    /// The Tiled file the map was loaded from, if any.
+    pub fn source(&self) -> Option<&Path> {
+        self.source.as_deref()
+    }
+
+    /// The tile at `(x, y)` in layer `layer`.
+    pub fn tile(&self, layer: usize, x: u32, y: u32) -> Option<Tile> {
+        let i = self.cell(x, y)?;
+        self.layers.get(layer)?.tiles[i]
+    }
+
+    /// Replace the tile at `(x, y)` in layer `layer`. Out-of-range
+    /// coordinates are ignored.
+    pub fn set_tile(&mut self, layer: usize, x: u32, y: u32, tile: Option<Tile>) {
+        let Some(i) = self.cell(x, y) else {
+            return;
+        };
+        let Some(target) = self.layers.get_mut(layer) else {
+            return;
+        };
+        if target.tiles[i] == tile {
+            return;
+        }
+        target.tiles[i] = tile;
+        if self.collision_layer.as_deref() == Some(target.name.as_str()) {
+            self.colliders_dirty = true;
+        }
+        let chunk = self.chunk_of(x, y);
+        self.chunks[chunk].dirty = true;
+    }
+
+    /// Show or hide a layer.
+    pub fn set_layer_visible(&mut self, layer: usize, visible: bool) {
+        if let Some(target) = self.layers.get_mut(layer)
+            && target.visible != visible
+        {
+            target.visible = visible;
+            self.mark_all_dirty();
+        }
+    }
+
+    /// The tile containing `point`, given in the map's local space (see
+    /// [Coordinates](self#coordinates)).
+    pub fn tile_at(&self, point: Vec2) -> Option<(u32, u32)> {
+        let x = (point.x / self.tile_size.x).floor();
+        let y = (-point.y / self.tile_size.y).floor();
+        if x < 0.0 || y < 0.0 || x >= self.width as f32 || y >= self.height as f32 {
+            return None;
+        }
+        Some((x as u32, y as u32))
+    }
+
+    /// Regenerate colliders next physics step, e.g. after moving the map.
+    pub fn rebuild_colliders(&mut self) {
+        self.colliders_dirty = true;
+    }
+
+    /// Take the layers, size and atlas of `other`, as after a hot reload.
+    /// Keeps this map's collision layer unless `other` names one.
+    pub(crate) fn replace_with(&mut self, mut other: Tilemap) {
+        let collision = other.collision_layer.take().or(self.collision_layer.take());
+        let source = self.source.take();
+        *self = other;
+        self.collision_layer = collision;
+        self.source = source;
+    }
+
+    pub(crate) fn set_source(&mut self, source: PathBuf) {
+        self.source = Some(source);
+    }
+
+    /// Index into `tiles` of `(x, y)`, if it's on the map.
+    fn cell(&self, x: u32, y: u32) -> Option<usize> {
+        (x < self.width && y < self.height).then(|| (y * self.width + x) as usize)
+    }
+
+    fn chunk_columns(&self) -> u32 {
+        self.width.div_ceil(CHUNK_TILES)
+    }
+
+    fn chunk_of(&self, x: u32, y: u32) -> usize {
+        ((y / CHUNK_TILES) * self.chunk_columns() + x / CHUNK_TILES) as usize
+    }
+
+    fn mark_all_dirty(&mut self) {
+        for chunk in &mut self.chunks {
+            chunk.dirty = true;
+        }
+    }
+
+    /// Local-space rectangle covered by chunk `i`.
+    fn chunk_bounds(&self, i: usize) -> Rect {
+        let columns = self.chunk_columns().max(1);
+        let (cx, cy) = (i as u32 % columns, i as u32 / columns);
+        let x0 = cx * CHUNK_TILES;
+        let y0 = cy * CHUNK_TILES;
+        let x1 = (x0 + CHUNK_TILES).min(self.width);
+        let y1 = (y0 + CHUNK_TILES).min(self.height);
+        Rect {
+            min: Vec2::new(x0 as f32 * self.tile_size.x, -(y1 as f32) * self.tile_size.y),
+            max: Vec2::new(x1 as f32 * self.tile_size.x, -(y0 as f32) * self.tile_size.y),
+        }
+    }
+
+    /// Rebuild chunk `i`'s mesh: one quad per tile per visible layer, UVs
+    /// in atlas-texture space.
+    fn build_chunk(&mut self, i: usize, atlas: &TextureAtlas) {
+        let columns = self.chunk_columns().max(1);
+        let (x0, y0) = ((i as u32 % columns) * CHUNK_TILES, (i as u32 / columns) * CHUNK_TILES);
+        let (x1, y1) = ((x0 + CHUNK_TILES).min(self.width), (y0 + CHUNK_TILES).min(self.height));
+        let size = self.tile_size;
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for layer in self.layers.iter().filter(|layer| layer.visible) {
+            let color = [1.0, 1.0, 1.0, layer.opacity];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let Some(tile) = layer.tiles[(y * self.width + x) as usize] else {
+                        continue;
+                    };
+                    let Some(uv) = atlas.uv_rect(tile.index as usize) else {
+                        continue;
+                    };
+                    let (u0, u1) = if tile.flip_x { (uv.max.x, uv.min.x) } else { (uv.min.x, uv.max.x) };
+                    let (v0, v1) = if tile.flip_y { (uv.max.y, uv.min.y) } else { (uv.min.y, uv.max.y) };
+                    let left = x as f32 * size.x;
+                    let top = -(y as f32) * size.y;
+                    let base = vertices.len() as u32;
+                    for (px, py, u, v) in [
+                        (left, top - size.y, u0, v1),          // bottom-left
+                        (left + size.x, top - size.y, u1, v1), // bottom-right
+                        (left + size.x, top, u1, v0),          // top-right
+                        (left, top, u0, v0),                   // top-left
+                    ] {
+                        vertices.push(SpriteVertex {
+                            position: [px, py, 0.0],
+                            uv: [u, v],
+                            color,
+                            texture_index: 0,
+                        });
+                    }
+                    indices.extend([0, 1, 2, 0, 2, 3].map(|k| base + k));
+                }
+            }
+        }
+
+        let chunk = &mut self.chunks[i];
+        chunk.vertices = vertices;
+        chunk.indices = indices;
+        chunk.dirty = false;
+    }
+}
+
+// ── Extraction ──────────────────────────────────────────────────────────
+
+/// One visible chunk, copied out for the frame: world-space vertices with
+/// UVs in the atlas texture's space.
+pub(crate) struct ExtractedChunk {
+    pub z: f32,
+    pub texture: TextureHandle,
+    pub vertices: Vec<SpriteVertex>,
+    pub indices: Vec<u32>,
+}
+
+/// World-space rectangle the 2D camera sees, if a surface size is known.
+pub(crate) fn camera_view(camera: Option<glam::Mat4>, surface_size: Option<(u32, u32)>) -> Option<Rect> {
+    let (w, h) = surface_size?;
+    let half = Vec2::new(w as f32, h as f32) * 0.5;
+    let local = Rect {
+        min: -half,
+        max: half,
+    };
+    Some(transformed_bounds(&camera.unwrap_or(glam::Mat4::IDENTITY), local))
+}
+
+/// Axis-aligned bounds of `rect` after transforming it by `model`.
+pub(crate) fn transformed_bounds(model: &glam::Mat4, rect: Rect) -> Rect {
+    let corners = [
+        Vec2::new(rect.min.x, rect.min.y),
+        Vec2::new(rect.max.x, rect.min.y),
+        Vec2::new(rect.max.x, rect.max.y),
+        Vec2::new(rect.min.x, rect.max.y),
+    ]
+    .map(|c| model.transform_point3(c.extend(0.0)).truncate());
+    Rect {
+        min: corners.iter().fold(Vec2::MAX, |acc, c| acc.min(*c)),
+        max: corners.iter().fold(Vec2::MIN, |acc, c| acc.max(*c)),
+    }
+}
+
+pub(crate) fn overlaps(a: Rect, b: Rect) -> bool {
+    a.min.x <= b.max.x && b.min.x <= a.max.x && a.min.y <= b.max.y && b.min.y <= a.max.y
+}
+
+/// Rebuild dirty chunks of every visible tilemap and copy out the ones the
+/// camera can see.
+pub(crate) fn extract_tilemaps(world: &mut World, camera: Option<glam::Mat4>) -> Vec<ExtractedChunk> {
+    let Some(atlases) = world.resource_remove::<TextureAtlases>() else {
+        return Vec::new();
+    };
+    let surface_size = world
+        .get_resource::<crate::render::GpuContext>()
+        .map(|gpu| gpu.surface_size());
+    let view = camera_view(camera, surface_size);
+
+    let mut chunks = Vec::new();
+    world.query::<(&GlobalTransform, &mut Tilemap, Option<&ComputedVisibility>)>(
+        |_entity, (gt, map, vis)| {
+            if is_hidden(vis) {
+                return;
+            }
+            let Some(atlas) = atlases.get(map.atlas) else {
+                return;
+            };
+            let model = gt.matrix;
+            for i in 0..map.chunks.len() {
+                if view.is_some_and(|view| !overlaps(view, transformed_bounds(&model, map.chunk_bounds(i)))) {
+                    continue;
+                }
+                if map.chunks[i].dirty {
+                    map.build_chunk(i, atlas);
+                }
+                let chunk = &map.chunks[i];
+                if chunk.indices.is_empty() {
+                    continue;
+                }
+                chunks.push(ExtractedChunk {
+                    z: model.col(3).z,
+                    texture: atlas.texture,
+                    vertices: chunk
+                        .vertices
+                        .iter()
+                        .map(|v| {
+                            let p = model.transform_point3(glam::Vec3::from(v.position));
+                            SpriteVertex {
+                                position: p.to_array(),
+                                ..*v
+                            }
+                        })
+                        .collect(),
+                    indices: chunk.indices.clone(),
+                });
+            }
+        },
+    );
+
+    world.insert_resource(atlases);
+    chunks
+}
+
+// ── Collision ───────────────────────────────────────────────────────────
+
+/// A rectangle of solid tiles: top-left tile and size, in tiles.
+#[cfg(any(test, feature = "physics2d"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TileRect {
+    pub x: u32,
+    pub y: u32,
+    pub w: u32,
+    pub h: u32,
+}
+
+/// Cover the non-empty tiles of `layer` with few rectangles: runs of solid
+/// tiles along each row, merged with the run directly above when both span
+/// the same columns.
+#[cfg(any(test, feature = "physics2d"))]
+pub(crate) fn solid_rects(map: &Tilemap, layer: usize) -> Vec<TileRect> {
+    let Some(layer) = map.layers.get(layer) else {
+        return Vec::new();
+    };
+    let mut rects: Vec<TileRect> = Vec::new();
+    // Rects that ended on the previous row, by (x, w).
+    let mut open: std::collections::HashMap<(u32, u32), usize> = std::collections::HashMap::new();
+    for y in 0..map.height {
+        let mut next_open = std::collections::HashMap::new();
+        let mut x = 0;
+        while x < map.width {
+            if layer.tiles[(y * map.width + x) as usize].is_none() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < map.width && layer.tiles[(y * map.width + x) as usize].is_some() {
+                x += 1;
+            }
+            let key = (start, x - start);
+            let index = match open.get(&key) {
+                Some(&i) => {
+                    rects[i].h += 1;
+                    i
+                }
+                None => {
+                    rects.push(TileRect {
+                        x: start,
+                        y,
+                        w: x - start,
+                        h: 1,
+                    });
+                    rects.len() - 1
+                }
+            };
+            next_open.insert(key, index);
+        }
+        open = next_open;
+    }
+    rects
+}
+
+/// Component on a collider generated for a tilemap, pointing back at it.
+#[cfg(feature = "physics2d")]
+#[derive(Debug, Clone, Copy)]
+pub struct TileCollider {
+    pub map: crate::ecs::Entity,
+}
+
+/// Regenerate the colliders of tilemaps whose collision layer changed, and
+/// drop colliders whose tilemap is gone. Run by the physics step.
+#[cfg(feature = "physics2d")]
+pub(crate) fn update_tilemap_colliders(world: &mut World) {
+    use crate::ecs::Entity;
+    use crate::math::Transform;
+    use crate::physics2d::{Collider2d, InPhysicsWorld2d, RigidBody2d};
+    use crate::scene::SceneMarker;
+
+    let mut rebuild: Vec<(Entity, Transform, Vec2, Vec<TileRect>)> = Vec::new();
+    let mut keep = std::collections::HashSet::new();
+    world.query::<(&mut Tilemap, &Transform)>(|entity, (map, transform)| {
+        let Some(layer) = map.collision_layer.as_deref().and_then(|name| map.layer_index(name)) else {
+            return;
+        };
+        keep.insert(entity);
+        if map.colliders_dirty {
+            map.colliders_dirty = false;
+            rebuild.push((entity, *transform, map.tile_size, solid_rects(map, layer)));
+        }
+    });
+
+    let mut stale = Vec::new();
+    world.query::<(&TileCollider,)>(|entity, (collider,)| {
+        let rebuilt = rebuild.iter().any(|(map, ..)| *map == collider.map);
+        if rebuilt || !keep.contains(&collider.map) {
+            stale.push(entity);
+        }
+    });
+    for entity in stale {
+        world.despawn(entity);
+    }
+
+    for (map, transform, tile, rects) in rebuild {
+        let scene = world.get::<SceneMarker>(map).cloned();
+        let physics_world = world.get::<InPhysicsWorld2d>(map).cloned();
+        let scale = transform.scale.truncate();
+        for rect in rects {
+            let size = Vec2::new(rect.w as f32, rect.h as f32) * tile;
+            let center = Vec2::new(rect.x as f32 * tile.x, -(rect.y as f32) * tile.y)
+                + Vec2::new(size.x, -size.y) * 0.5;
+            let translation = transform.translation
+                + transform.rotation * (center * scale).extend(0.0);
+            let half = (size * scale).abs() * 0.5;
+            let collider = world.spawn((
+                Transform {
+                    translation,
+                    rotation: transform.rotation,
+                    ..Default::default()
+                },
+                RigidBody2d::fixed(),
+                Collider2d::cuboid(half.x, half.y),
+                TileCollider { map },
+            ));
+            if let Some(scene) = &scene {
+                world.insert(collider, scene.clone());
+            }
+            if let Some(physics_world) = &physics_world {
+                world.insert(collider, physics_world.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map_with(rows: &[&str]) -> Tilemap {
+        let (w, h) = (rows[0].len() as u32, rows.len() as u32);
+        let tiles = rows
+            .iter()
+            .flat_map(|row| row.chars().map(|c| (c == '#').then(|| Tile::new(0))))
+            .collect();
+        Tilemap::new(TextureAtlasHandle(0), Vec2::splat(16.0), w, h)
+            .layer(TileLayer::new("solid").tiles(tiles))
+    }
+
+    #[test]
+    fn solid_tiles_merge_into_rectangles() {
+        let map = map_with(&[
+            "###.", //
+            "#...", //
+            "#..#", //
+        ]);
+        let rects = solid_rects(&map, 0);
+        assert_eq!(rects.len(), 3);
+        assert!(rects.contains(&TileRect { x: 0, y: 0, w: 3, h: 1 }));
+        assert!(rects.contains(&TileRect { x: 0, y: 1, w: 1, h: 2 }));
+        assert!(rects.contains(&TileRect { x: 3, y: 2, w: 1, h: 1 }));
+    }
+
+    #[test]
+    fn set_tile_rebuilds_only_its_chunk() {
+        let mut map = Tilemap::new(TextureAtlasHandle(0), Vec2::splat(8.0), 40, 20)
+            .layer(TileLayer::new("ground"));
+        let atlas = TextureAtlas::from_grid(TextureHandle(0), Vec2::splat(32.0), 2, 2);
+        for i in 0..map.chunks.len() {
+            map.build_chunk(i, &atlas);
+        }
+        assert_eq!(map.chunks.len(), 3 * 2);
+
+        map.set_tile(0, 17, 3, Some(Tile::new(3).flip_x()));
+        let dirty: Vec<usize> = (0..map.chunks.len()).filter(|&i| map.chunks[i].dirty).collect();
+        assert_eq!(dirty, [1]);
+        map.build_chunk(1, &atlas);
+        let quad = &map.chunks[1].vertices;
+        assert_eq!(quad.len(), 4);
+        // Bottom-left corner of tile (17, 3); flipped, so it samples the
+        // right edge of region 3.
+        assert_eq!(quad[0].position, [17.0 * 8.0, -4.0 * 8.0, 0.0]);
+        assert_eq!(quad[0].uv, [1.0, 1.0]);
+
+        assert_eq!(map.tile_at(Vec2::new(17.0 * 8.0 + 1.0, -3.0 * 8.0 - 1.0)), Some((17, 3)));
+        assert_eq!(map.tile_at(Vec2::new(-1.0, -1.0)), None);
+    }
+}
@@ -0,0 +1,191 @@
+//! # Tiling — Repeating and Scrolling Sprite Textures
+//!
+//! By default a sprite's texture is stretched once across its quad. A
+//! [`SpriteTiling`] mode repeats it instead, and [`Sprite::uv_offset`]
+//! slides the texture within the quad, wrapping around at the edges. Together
+//! they cover parallax backgrounds, conveyor belts, scrolling water and
+//! tiled floors without oversized textures or a custom shader:
+//!
+//! ```text
+//!   Stretch                 Repeat                  Repeat + uv_offset.x = 0.5
+//!   ┌───────────────┐       ┌─────┬─────┬─────┐     ┌──┬─────┬─────┬──┐
+//!   │               │       │ ▲▲  │ ▲▲  │ ▲▲  │     │▲ │ ▲▲  │ ▲▲  │ ▲│
+//!   │      ▲▲       │       │▲▲▲▲ │▲▲▲▲ │▲▲▲▲ │     │▲▲│▲▲▲▲ │▲▲▲▲ │▲▲│
+//!   │     ▲▲▲▲      │       └─────┴─────┴─────┘     └──┴─────┴─────┴──┘
+//!   └───────────────┘
+//! ```
+//!
+//! ## How It's Drawn
+//!
+//! Tiling is done on the CPU, like the rest of the 2D batcher: the quad is cut
+//! into one piece per tile (partial tiles at the edges), and each piece gets
+//! UVs spanning its part of the texture. No repeat sampler is involved, so
+//! tiling works with [atlas-packed](super::atlas) textures and with a
+//! `texture_rect` sub-region, and tiled sprites batch with everything else.
+//! The cost is four vertices per visible tile; the tile count per axis is
+//! capped at [`MAX_TILES_PER_AXIS`].
+//!
+//! ## Scrolling
+//!
+//! `uv_offset` is measured in tiles (in stretch mode, the whole sprite counts
+//! as one tile): `0.25` moves the texture a quarter tile to the left. Set it
+//! yourself every frame, or attach [`UvScroll`] and call [`scroll_uvs`] from
+//! an update system to advance it at a constant rate.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `SpriteRenderer` draw mode *Tiled*; scrolling via
+//!   `material.mainTextureOffset`, which needs a repeat-wrapped texture.
+//! - **Bevy**: `ImageScaleMode::Tiled` on `Sprite`; no built-in UV offset.
+//! - **Godot**: `TextureRect` stretch mode *Tile*; `region_rect` + repeat
+//!   for scrolling.
+//! - **Our approach**: Unity's tiled mode plus a UV offset on the sprite,
+//!   both done by cutting the quad, so no sampler changes are needed.
+
+use crate::ecs::World;
+use crate::math::{Rect, Vec2};
+
+use super::Sprite;
+
+/// Most tiles emitted along one axis of a sprite. Tiles smaller than
+/// `size / MAX_TILES_PER_AXIS` are enlarged to fit.
+pub const MAX_TILES_PER_AXIS: u32 = 256;
+
+/// How a [`Sprite`]'s texture fills its quad.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpriteTiling {
+    /// Stretch one copy across the whole quad.
+    #[default]
+    Stretch,
+    /// Repeat at the texture's own pixel size (of its `texture_rect`), so one
+    /// texel covers one world unit.
+    Repeat,
+    /// Repeat every `tile_size` world units.
+    RepeatEvery(Vec2),
+}
+
+/// Component: scroll a [`Sprite`]'s `uv_offset` at a constant rate.
+/// Advanced by [`scroll_uvs`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UvScroll {
+    /// Tiles per second. Positive X moves the texture left, positive Y down.
+    pub velocity: Vec2,
+}
+
+impl UvScroll {
+    pub fn new(x: f32, y: f32) -> Self {
+        Self {
+            velocity: Vec2::new(x, y),
+        }
+    }
+}
+
+/// Advance the `uv_offset` of every sprite with a [`UvScroll`]. The offset
+/// is kept in `0..1`, since it wraps anyway.
+pub fn scroll_uvs(world: &mut World, dt: f32) {
+    world.query::<(&UvScroll, &mut Sprite)>(|_entity, (scroll, sprite)| {
+        let offset = sprite.uv_offset + scroll.velocity * dt;
+        sprite.uv_offset = offset - offset.floor();
+    });
+}
+
+/// One piece of a tiled quad: where it sits in the sprite's local space
+/// (centered on the origin) and which part of a single tile it shows, in
+/// `0..1` tile UVs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct TilePiece {
+    pub local: Rect,
+    pub uv: Rect,
+}
+
+/// Cut a quad of `size` into tile pieces, `tile` world units per repeat,
+/// with the texture shifted by `offset` tiles.
+pub(crate) fn tile_pieces(size: Vec2, tile: Vec2, offset: Vec2) -> Vec<TilePiece> {
+    let min_tile = size / MAX_TILES_PER_AXIS as f32;
+    let tile = tile.max(min_tile);
+    if tile.x <= 0.0 || tile.y <= 0.0 {
+        return Vec::new();
+    }
+    let xs = axis_segments(size.x, tile.x, offset.x);
+    let ys = axis_segments(size.y, tile.y, offset.y);
+    let half = size * 0.5;
+
+    let mut pieces = Vec::with_capacity(xs.len() * ys.len());
+    for &(y0, y1, v0, v1) in &ys {
+        for &(x0, x1, u0, u1) in &xs {
+            pieces.push(TilePiece {
+                local: Rect {
+                    min: Vec2::new(x0, y0) - half,
+                    max: Vec2::new(x1, y1) - half,
+                },
+                uv: Rect {
+                    min: Vec2::new(u0, v0),
+                    max: Vec2::new(u1, v1),
+                },
+            });
+        }
+    }
+    pieces
+}
+
+/// Split `0..length` at tile boundaries. Each segment is
+/// `(start, end, uv_start, uv_end)` with UVs inside one tile.
+fn axis_segments(length: f32, tile: f32, offset: f32) -> Vec<(f32, f32, f32, f32)> {
+    let start = offset;
+    let end = offset + length / tile;
+    let mut segments = Vec::new();
+    let mut k = start.floor();
+    while k < end {
+        let s0 = start.max(k);
+        let s1 = end.min(k + 1.0);
+        if s1 - s0 > 1e-6 {
+            segments.push(((s0 - start) * tile, (s1 - start) * tile, s0 - k, s1 - k));
+        }
+        k += 1.0;
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    #[test]
+    fn stretch_without_offset_is_one_piece() {
+        let size = Vec2::new(40.0, 20.0);
+        let pieces = tile_pieces(size, size, Vec2::ZERO);
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].local.min, Vec2::new(-20.0, -10.0));
+        assert_eq!(pieces[0].uv, Rect::FULL);
+    }
+
+    #[test]
+    fn repeat_cuts_partial_tiles_at_the_edges() {
+        // 2.5 tiles across, shifted half a tile: 0.5 | 1 | 1
+        let pieces = tile_pieces(Vec2::new(50.0, 10.0), Vec2::new(20.0, 10.0), Vec2::new(0.5, 0.0));
+        assert_eq!(pieces.len(), 3);
+        assert!(approx(pieces[0].local.min.x, -25.0) && approx(pieces[0].local.max.x, -15.0));
+        assert!(approx(pieces[0].uv.min.x, 0.5) && approx(pieces[0].uv.max.x, 1.0));
+        assert!(approx(pieces[1].uv.min.x, 0.0) && approx(pieces[1].uv.max.x, 1.0));
+        assert!(approx(pieces[2].local.max.x, 25.0) && approx(pieces[2].uv.max.x, 1.0));
+    }
+
+    #[test]
+    fn tiny_tiles_are_capped() {
+        let pieces = tile_pieces(Vec2::new(1000.0, 1.0), Vec2::new(0.01, 1.0), Vec2::ZERO);
+        assert_eq!(pieces.len(), MAX_TILES_PER_AXIS as usize);
+    }
+
+    #[test]
+    fn scroll_wraps_offset() {
+        let mut world = World::new();
+        let e = world.spawn((Sprite::new(), UvScroll::new(0.75, -0.5)));
+        scroll_uvs(&mut world, 2.0);
+        let offset = world.get::<Sprite>(e).unwrap().uv_offset;
+        assert!(approx(offset.x, 0.5) && approx(offset.y, 0.0));
+    }
+}
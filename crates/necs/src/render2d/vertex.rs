@@ -14,20 +14,24 @@
 //! upload without any copies.
 //!
 //! ```text
-//! SpriteVertex (36 bytes per vertex)
-//! ┌────────────────┬──────────────┬────────────────────────┐
-//! │ position       │ uv           │ color                  │
-//! │ [f32; 3]       │ [f32; 2]     │ [f32; 4]               │
-//! │ 12 bytes       │ 8 bytes      │ 16 bytes               │
-//! │ offset 0       │ offset 12    │ offset 20              │
-//! │ location(0)    │ location(1)  │ location(2)            │
-//! └────────────────┴──────────────┴────────────────────────┘
+//! SpriteVertex (40 bytes per vertex)
+//! ┌────────────────┬──────────────┬────────────────────────┬───────────────┐
+//! │ position       │ uv           │ color                  │ texture_index │
+//! │ [f32; 3]       │ [f32; 2]     │ [f32; 4]               │ u32           │
+//! │ 12 bytes       │ 8 bytes      │ 16 bytes               │ 4 bytes       │
+//! │ offset 0       │ offset 12    │ offset 20              │ offset 36     │
+//! │ location(0)    │ location(1)  │ location(2)            │ location(9)   │
+//! └────────────────┴──────────────┴────────────────────────┴───────────────┘
 //! ```
 //!
 //! The `shader_location` numbers tie each field to an `@location(N)` in the
-//! WGSL shader. The GPU vertex fetcher uses `array_stride` (36) to step
+//! WGSL shader. The GPU vertex fetcher uses `array_stride` (40) to step
 //! between vertices and `offset` to find each attribute within a vertex.
 //!
+//! `texture_index` picks the vertex's texture out of the batch's
+//! [texture array](super::batch#texture-arrays). The batcher fills it in;
+//! without texture arrays it is always 0 and the shader ignores it.
+//!
 //! ## Why Position Is World-Space
 //!
 //! Positions are pre-transformed by the sprite's model matrix on the CPU. The
@@ -37,6 +41,23 @@
 //! positions in local space and passing a per-sprite model matrix — would
 //! require either instanced rendering or separate draw calls per sprite.
 //!
+//! ## Instances (SpriteInstance)
+//!
+//! In [instanced mode](super::SpriteRenderMode) sprites skip the CPU
+//! transform. Each sprite is one [`SpriteInstance`] — a model matrix that maps
+//! a unit quad onto the sprite, the UV rectangle and the tint — and the vertex
+//! shader builds the four corners itself. `step_mode: Instance` makes the GPU
+//! advance through this buffer once per instance instead of once per vertex.
+//!
+//! ```text
+//! SpriteInstance (100 bytes per sprite)
+//! ┌──────────────────────────────┬──────────────┬──────────────┬───────────────┐
+//! │ model (4 columns)            │ uv_rect      │ color        │ texture_index │
+//! │ [[f32; 4]; 4]                │ [f32; 4]     │ [f32; 4]     │ u32           │
+//! │ location(3..=6)              │ location(7)  │ location(8)  │ location(10)  │
+//! └──────────────────────────────┴──────────────┴──────────────┴───────────────┘
+//! ```
+//!
 //! ## Uniform Buffer (CameraUniform)
 //!
 //! A *uniform* is a small piece of data that stays constant across all vertices
@@ -65,6 +86,8 @@ pub(crate) struct SpriteVertex {
     pub position: [f32; 3],
     pub uv: [f32; 2],
     pub color: [f32; 4],
+    /// Slot in the batch's texture array; set by the batcher.
+    pub texture_index: u32,
 }
 
 impl SpriteVertex {
@@ -90,6 +113,69 @@ impl SpriteVertex {
                 shader_location: 2,
                 format: wgpu::VertexFormat::Float32x4,
             },
+            // texture_index
+            wgpu::VertexAttribute {
+                offset: 36,
+                shader_location: 9,
+                format: wgpu::VertexFormat::Uint32,
+            },
+        ],
+    };
+}
+
+/// Per-instance data for [instanced](super::SpriteRenderMode) sprites. The
+/// shader maps the unit quad `-0.5..0.5` through `model`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub(crate) struct SpriteInstance {
+    pub model: [[f32; 4]; 4],
+    /// UVs of the top-left (`xy`) and bottom-right (`zw`) corners.
+    pub uv_rect: [f32; 4],
+    pub color: [f32; 4],
+    /// Slot in the batch's texture array; set by the batcher.
+    pub texture_index: u32,
+}
+
+impl SpriteInstance {
+    pub const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<SpriteInstance>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Instance,
+        attributes: &wgpu::vertex_attr_array![
+            3 => Float32x4, // model column 0
+            4 => Float32x4, // model column 1
+            5 => Float32x4, // model column 2
+            6 => Float32x4, // model column 3
+            7 => Float32x4, // uv_rect
+            8 => Float32x4, // color
+            10 => Uint32,   // texture_index
+        ],
+    };
+}
+
+/// Per-vertex data for [distance-field](super::sdf) glyph quads, drawn by
+/// `sdf_text.wgsl`. Position is in world space, like [`SpriteVertex`].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub(crate) struct SdfVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub color: [f32; 4],
+    pub outline_color: [f32; 4],
+    /// Distance range in atlas pixels, outline width and edge softness in
+    /// distance units, and 1 for multi-channel atlases.
+    pub params: [f32; 4],
+}
+
+impl SdfVertex {
+    pub const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<SdfVertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![
+            0 => Float32x3, // position
+            1 => Float32x2, // uv
+            2 => Float32x4, // color
+            3 => Float32x4, // outline_color
+            4 => Float32x4, // params
         ],
     };
 }
@@ -0,0 +1,804 @@
+//! # Video — Cutscenes and Intros on a Texture
+//!
+//! A [`VideoPlayer`] plays a [`Video`] into a texture that updates as the
+//! video runs. Put the player on an entity with a [`Sprite`] or a
+//! [`UiImage`] and its texture is swapped in automatically; otherwise read
+//! [`VideoPlayer::texture`] and draw it however you like.
+//!
+//! ```text
+//!   intro.avi ──► Video (parsed once: frame index + audio track)
+//!                   │
+//!   update_videos   ├─ clock: audio position, or dt without audio
+//!   (every frame)   ├─ frame = clock × frame rate
+//!                   └─ new frame? decode JPEG ──► texture ──► Sprite / UiImage
+//! ```
+//!
+//! ## Format
+//!
+//! Videos are **Motion JPEG in an AVI file**: every frame is a standalone
+//! JPEG, so decoding needs nothing beyond the image loader the engine
+//! already has, and any frame can be shown without decoding the ones before
+//! it. The sound track, if any, must be uncompressed PCM. Modern codecs
+//! (VP9, AV1, H.264) need decoder libraries this crate doesn't bundle —
+//! convert to MJPEG instead:
+//!
+//! ```text
+//! ffmpeg -i intro.mp4 -c:v mjpeg -q:v 3 -c:a pcm_s16le intro.avi
+//! ```
+//!
+//! MJPEG files are larger than modern codecs for the same quality, and a
+//! frame is decoded on the main thread when it is first shown; it suits
+//! short intros and cutscenes at moderate resolutions.
+//!
+//! ## Audio Sync
+//!
+//! With the `audio` feature and an [`AudioEngine`](crate::audio::AudioEngine)
+//! resource, the sound track plays alongside and is the clock: the frame
+//! shown is the one matching the audio's playback position, so picture and
+//! sound cannot drift apart, even over frame-rate hitches. Without audio,
+//! the video advances by each frame's `dt`.
+//!
+//! ```ignore
+//! let intro = Video::from_file("assets/intro.avi")?;
+//! world.spawn((Transform::default(), Sprite::new(), VideoPlayer::new(intro)));
+//!
+//! // Every frame:
+//! update_videos(&mut ctx.world, dt);
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `VideoPlayer` component with a `VideoClip` (platform
+//!   decoders for H.264, VP8, ...) rendering to a camera or `RenderTexture`.
+//! - **Bevy**: No built-in video playback; third-party crates wrap ffmpeg
+//!   or GStreamer.
+//! - **Godot**: `VideoStreamPlayer` node playing Ogg Theora (`.ogv`) files,
+//!   with the audio track played through the node's bus.
+//! - **Our approach**: Unity's component model with one dependency-free
+//!   format, MJPEG in AVI, synced to the audio clock like Godot.
+
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::ecs::{Entity, World};
+use crate::ui::UiImage;
+
+use super::Sprite;
+use super::texture::{TextureHandle, create_texture_from_rgba, update_texture_rgba};
+
+// ── Errors ──────────────────────────────────────────────────────────────
+
+/// Errors that can occur while opening or decoding a video.
+#[derive(Debug)]
+pub enum VideoError {
+    /// The file could not be read.
+    Read(String),
+    /// The file isn't a well-formed AVI.
+    Parse(String),
+    /// The video uses a codec this player doesn't support.
+    Unsupported(String),
+    /// A frame's image data is corrupt.
+    Decode(String),
+}
+
+impl fmt::Display for VideoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VideoError::Read(e) => write!(f, "video read failed: {e}"),
+            VideoError::Parse(e) => write!(f, "video parse failed: {e}"),
+            VideoError::Unsupported(e) => write!(f, "unsupported video: {e}"),
+            VideoError::Decode(e) => write!(f, "video frame decode failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for VideoError {}
+
+// ── AVI container ───────────────────────────────────────────────────────
+
+/// What [`parse_avi`] finds in a file: where each frame's JPEG is, and the
+/// sound track.
+#[derive(Debug, Clone, PartialEq)]
+struct AviIndex {
+    width: u32,
+    height: u32,
+    /// Seconds each frame is shown.
+    frame_duration: f64,
+    /// Byte range of each frame's JPEG in the file.
+    frames: Vec<Range<usize>>,
+    /// The first PCM sound track, as a WAV file.
+    audio: Option<Vec<u8>>,
+}
+
+/// PCM layout of an AVI sound track (its `WAVEFORMATEX`).
+#[derive(Debug, Clone, Copy)]
+struct PcmFormat {
+    channels: u16,
+    sample_rate: u32,
+    bits: u16,
+}
+
+/// One stream declared in the AVI header.
+#[derive(Debug, Clone, Copy)]
+enum Stream {
+    Video { frame_duration: Option<f64> },
+    Audio(Option<PcmFormat>),
+    Other,
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    bytes
+        .get(offset..offset + 2)
+        .map_or(0, |b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    bytes
+        .get(offset..offset + 4)
+        .map_or(0, |b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+fn fourcc(bytes: &[u8], offset: usize) -> [u8; 4] {
+    bytes
+        .get(offset..offset + 4)
+        .map_or([0; 4], |b| [b[0], b[1], b[2], b[3]])
+}
+
+/// The RIFF chunks in `bytes[range]` as `(id, data range)`, stopping at the
+/// first truncated one. A `LIST` chunk's data starts with its list type.
+fn chunks(bytes: &[u8], range: Range<usize>) -> impl Iterator<Item = ([u8; 4], Range<usize>)> + '_ {
+    let mut pos = range.start;
+    std::iter::from_fn(move || {
+        if pos + 8 > range.end {
+            return None;
+        }
+        let id = fourcc(bytes, pos);
+        let size = u32_at(bytes, pos + 4) as usize;
+        let start = pos + 8;
+        let end = start.checked_add(size).filter(|&end| end <= range.end)?;
+        // Chunks are padded to an even length.
+        pos = end + (size & 1);
+        Some((id, start..end))
+    })
+}
+
+/// The children of a `LIST` chunk of type `kind`, or `None` for any other chunk.
+fn list_children(
+    bytes: &[u8],
+    id: [u8; 4],
+    data: &Range<usize>,
+    kind: &[u8; 4],
+) -> Option<Range<usize>> {
+    let is_list = &id == b"LIST" && data.len() >= 4 && &fourcc(bytes, data.start) == kind;
+    is_list.then(|| data.start + 4..data.end)
+}
+
+/// Index an MJPEG AVI file.
+fn parse_avi(bytes: &[u8]) -> Result<AviIndex, VideoError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"AVI " {
+        return Err(VideoError::Parse("not an AVI file".into()));
+    }
+    let riff_end = (8 + u32_at(bytes, 4) as usize).min(bytes.len());
+
+    let mut size = (0, 0);
+    let mut micros_per_frame = 0;
+    let mut streams = Vec::new();
+    let mut movi = None;
+    for (id, data) in chunks(bytes, 12..riff_end) {
+        if let Some(hdrl) = list_children(bytes, id, &data, b"hdrl") {
+            for (id, data) in chunks(bytes, hdrl) {
+                if &id == b"avih" {
+                    micros_per_frame = u32_at(bytes, data.start);
+                    size = (u32_at(bytes, data.start + 32), u32_at(bytes, data.start + 36));
+                } else if let Some(strl) = list_children(bytes, id, &data, b"strl") {
+                    streams.push(parse_stream(bytes, strl)?);
+                }
+            }
+        } else if let Some(children) = list_children(bytes, id, &data, b"movi") {
+            movi = Some(children);
+        }
+    }
+
+    let video_stream = streams
+        .iter()
+        .position(|stream| matches!(stream, Stream::Video { .. }))
+        .ok_or_else(|| VideoError::Parse("no video stream".into()))?;
+    let audio_stream = streams
+        .iter()
+        .position(|stream| matches!(stream, Stream::Audio(Some(_))));
+    let movi = movi.ok_or_else(|| VideoError::Parse("no 'movi' data".into()))?;
+
+    let mut frames: Vec<Range<usize>> = Vec::new();
+    let mut pcm = Vec::new();
+    collect_movi(bytes, movi, &mut |id, data| {
+        let stream = std::str::from_utf8(&id[..2]).ok().and_then(|s| s.parse::<usize>().ok());
+        match (stream, &id[2..]) {
+            (Some(s), b"dc" | b"db") if s == video_stream => {
+                // An empty frame repeats the previous one.
+                let frame = if data.is_empty() { frames.last().cloned() } else { Some(data) };
+                frames.extend(frame);
+            }
+            (Some(s), b"wb") if Some(s) == audio_stream => pcm.extend_from_slice(&bytes[data]),
+            _ => {}
+        }
+    });
+    if frames.is_empty() {
+        return Err(VideoError::Parse("no video frames".into()));
+    }
+
+    let frame_duration = match streams[video_stream] {
+        Stream::Video { frame_duration: Some(duration) } => duration,
+        _ if micros_per_frame > 0 => micros_per_frame as f64 / 1_000_000.0,
+        _ => return Err(VideoError::Parse("no frame rate".into())),
+    };
+    let audio = audio_stream.and_then(|index| match streams[index] {
+        Stream::Audio(Some(format)) if !pcm.is_empty() => Some(wav_file(format, &pcm)),
+        _ => None,
+    });
+
+    Ok(AviIndex {
+        width: size.0,
+        height: size.1,
+        frame_duration,
+        frames,
+        audio,
+    })
+}
+
+/// Read one `strl` list: the stream header and format.
+fn parse_stream(bytes: &[u8], strl: Range<usize>) -> Result<Stream, VideoError> {
+    let mut kind = [0; 4];
+    let mut handler = [0; 4];
+    let mut rate = (0, 0);
+    let mut format = None;
+    for (id, data) in chunks(bytes, strl) {
+        match &id {
+            b"strh" => {
+                kind = fourcc(bytes, data.start);
+                handler = fourcc(bytes, data.start + 4);
+                rate = (u32_at(bytes, data.start + 20), u32_at(bytes, data.start + 24));
+            }
+            b"strf" => format = Some(data),
+            _ => {}
+        }
+    }
+
+    match &kind {
+        b"vids" => {
+            // The codec is named in the stream header and the BITMAPINFOHEADER;
+            // some writers leave one of them blank.
+            let compression = format.as_ref().map_or([0; 4], |f| fourcc(bytes, f.start + 16));
+            let is_mjpeg = |code: [u8; 4]| code.eq_ignore_ascii_case(b"MJPG");
+            if !is_mjpeg(handler) && !is_mjpeg(compression) {
+                let code = if compression != [0; 4] { compression } else { handler };
+                return Err(VideoError::Unsupported(format!(
+                    "codec '{}'; convert the video to MJPEG",
+                    String::from_utf8_lossy(&code)
+                )));
+            }
+            let (scale, rate) = rate;
+            let frame_duration = (scale > 0 && rate > 0).then(|| scale as f64 / rate as f64);
+            Ok(Stream::Video { frame_duration })
+        }
+        b"auds" => {
+            let format = format.map(|f| (u16_at(bytes, f.start), f));
+            Ok(Stream::Audio(match format {
+                Some((1, f)) => Some(PcmFormat {
+                    channels: u16_at(bytes, f.start + 2),
+                    sample_rate: u32_at(bytes, f.start + 4),
+                    bits: u16_at(bytes, f.start + 14),
+                }),
+                Some((tag, _)) => {
+                    log::warn!("Video sound track skipped: format {tag:#06x} isn't PCM");
+                    None
+                }
+                None => None,
+            }))
+        }
+        _ => Ok(Stream::Other),
+    }
+}
+
+/// Visit the data chunks of the `movi` list, including those grouped in
+/// `rec ` lists.
+fn collect_movi(bytes: &[u8], range: Range<usize>, visit: &mut impl FnMut([u8; 4], Range<usize>)) {
+    for (id, data) in chunks(bytes, range) {
+        match list_children(bytes, id, &data, b"rec ") {
+            Some(children) => collect_movi(bytes, children, visit),
+            None => visit(id, data),
+        }
+    }
+}
+
+/// Wrap raw PCM samples in a WAV header, so the audio loader can play them.
+fn wav_file(format: PcmFormat, pcm: &[u8]) -> Vec<u8> {
+    let block_align = format.channels * format.bits / 8;
+    let mut wav = Vec::with_capacity(44 + pcm.len());
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&format.channels.to_le_bytes());
+    wav.extend_from_slice(&format.sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(format.sample_rate * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&format.bits.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(pcm.len() as u32).to_le_bytes());
+    wav.extend_from_slice(pcm);
+    wav
+}
+
+// ── Video ───────────────────────────────────────────────────────────────
+
+struct VideoData {
+    bytes: Vec<u8>,
+    index: AviIndex,
+}
+
+/// An MJPEG AVI video, cheap to clone. The file is kept in memory and each
+/// frame decoded when it is shown. See the [module docs](self).
+#[derive(Clone)]
+pub struct Video {
+    inner: Arc<VideoData>,
+}
+
+impl Video {
+    /// Read and index a video file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, VideoError> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).map_err(|e| VideoError::Read(format!("{}: {e}", path.display())))?;
+        Self::from_bytes(bytes)
+    }
+
+    /// Index a video already in memory.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, VideoError> {
+        let index = parse_avi(&bytes)?;
+        Ok(Self {
+            inner: Arc::new(VideoData { bytes, index }),
+        })
+    }
+
+    /// Frame size in pixels, as declared by the file.
+    pub fn size(&self) -> (u32, u32) {
+        (self.inner.index.width, self.inner.index.height)
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.inner.index.frames.len()
+    }
+
+    /// Frames per second.
+    pub fn frame_rate(&self) -> f64 {
+        1.0 / self.inner.index.frame_duration
+    }
+
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.duration_secs())
+    }
+
+    /// Whether the file has a PCM sound track.
+    pub fn has_audio(&self) -> bool {
+        self.inner.index.audio.is_some()
+    }
+
+    fn duration_secs(&self) -> f64 {
+        self.frame_count() as f64 * self.inner.index.frame_duration
+    }
+
+    /// The frame shown `time` seconds in.
+    fn frame_at(&self, time: f64) -> usize {
+        let frame = (time.max(0.0) / self.inner.index.frame_duration) as usize;
+        frame.min(self.frame_count() - 1)
+    }
+
+    /// Decode frame `index` to RGBA8.
+    fn decode(&self, index: usize) -> Result<image::RgbaImage, VideoError> {
+        let jpeg = &self.inner.bytes[self.inner.index.frames[index].clone()];
+        image::load_from_memory_with_format(jpeg, image::ImageFormat::Jpeg)
+            .map(|image| image.to_rgba8())
+            .map_err(|e| VideoError::Decode(format!("frame {index}: {e}")))
+    }
+}
+
+impl fmt::Debug for Video {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Video")
+            .field("size", &self.size())
+            .field("frames", &self.frame_count())
+            .field("frame_rate", &self.frame_rate())
+            .field("audio", &self.has_audio())
+            .finish()
+    }
+}
+
+// ── VideoPlayer component ───────────────────────────────────────────────
+
+/// Component: play a [`Video`] into a texture, advanced by
+/// [`update_videos`]. A [`Sprite`] or [`UiImage`] on the same entity shows
+/// it.
+///
+/// ```ignore
+/// world.spawn((
+///     UiNode::new().size(Val::Percent(100.0), Val::Percent(100.0)),
+///     UiImage::default(),
+///     VideoPlayer::new(intro).with_volume(0.8),
+/// ));
+/// ```
+pub struct VideoPlayer {
+    pub video: Video,
+    /// Start over at the end instead of stopping on the last frame.
+    pub looping: bool,
+    /// Sound track volume (amplitude scale, 1.0 = full). Takes effect from
+    /// the next time the sound starts.
+    pub volume: f32,
+    playing: bool,
+    finished: bool,
+    /// Playback position in seconds.
+    time: f64,
+    texture: Option<TextureHandle>,
+    /// Frame currently in `texture`.
+    shown: Option<usize>,
+    #[cfg(feature = "audio")]
+    audio: Option<VideoAudio>,
+}
+
+/// The playing sound track of a [`VideoPlayer`].
+#[cfg(feature = "audio")]
+struct VideoAudio {
+    handle: crate::audio::SoundHandle,
+    /// Video time at which the sound started.
+    offset: f64,
+    paused: bool,
+}
+
+impl VideoPlayer {
+    /// Play `video` from the start.
+    pub fn new(video: Video) -> Self {
+        Self {
+            video,
+            looping: false,
+            volume: 1.0,
+            playing: true,
+            finished: false,
+            time: 0.0,
+            texture: None,
+            shown: None,
+            #[cfg(feature = "audio")]
+            audio: None,
+        }
+    }
+
+    /// Loop the video (builder pattern).
+    pub fn looping(mut self) -> Self {
+        self.looping = true;
+        self
+    }
+
+    /// Set the sound track volume (builder pattern).
+    pub fn with_volume(mut self, volume: f32) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Start paused on the first frame (builder pattern).
+    pub fn paused(mut self) -> Self {
+        self.playing = false;
+        self
+    }
+
+    /// Resume playback, or restart it once finished.
+    pub fn play(&mut self) {
+        if self.finished {
+            self.finished = false;
+            self.time = 0.0;
+        }
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Whether a non-looping video reached its end. It keeps showing the
+    /// last frame.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Playback position.
+    pub fn time(&self) -> Duration {
+        Duration::from_secs_f64(self.time)
+    }
+
+    /// The texture the video plays into; `None` until the first frame has
+    /// been decoded.
+    pub fn texture(&self) -> Option<TextureHandle> {
+        self.texture
+    }
+
+    /// Move the clock forward by `dt`, or to `audio_time` (the sound
+    /// track's position) while the sound plays. Returns `true` when the
+    /// video looped back to the start.
+    fn advance(&mut self, dt: f64, audio_time: Option<f64>) -> bool {
+        if !self.playing {
+            return false;
+        }
+        self.time = audio_time.unwrap_or(self.time + dt);
+        let duration = self.video.duration_secs();
+        if self.time < duration {
+            return false;
+        }
+        if self.looping {
+            self.time %= duration;
+            return true;
+        }
+        self.time = duration;
+        self.playing = false;
+        self.finished = true;
+        false
+    }
+}
+
+impl fmt::Debug for VideoPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VideoPlayer")
+            .field("video", &self.video)
+            .field("looping", &self.looping)
+            .field("volume", &self.volume)
+            .field("playing", &self.playing)
+            .field("finished", &self.finished)
+            .field("time", &self.time)
+            .finish()
+    }
+}
+
+// ── System ──────────────────────────────────────────────────────────────
+
+/// Advance every [`VideoPlayer`], keep its sound track in step, and upload
+/// frames that changed to the player's texture (and to a [`Sprite`] or
+/// [`UiImage`] on the same entity).
+pub fn update_videos(world: &mut World, dt: f32) {
+    if !world.has_component_type::<VideoPlayer>() {
+        return;
+    }
+    #[cfg(feature = "audio")]
+    let mut engine = world.resource_remove::<crate::audio::AudioEngine>();
+
+    let mut changed: Vec<(Entity, Video, usize, Option<TextureHandle>)> = Vec::new();
+    world.query::<(&mut VideoPlayer,)>(|entity, (player,)| {
+        #[cfg(feature = "audio")]
+        let audio_time = sync_audio(player, engine.as_mut());
+        #[cfg(not(feature = "audio"))]
+        let audio_time = None;
+
+        if player.advance(dt as f64, audio_time) {
+            // Looped: the sound restarts with the picture next frame.
+            #[cfg(feature = "audio")]
+            if let Some(mut audio) = player.audio.take() {
+                audio.handle.stop();
+            }
+        }
+        #[cfg(feature = "audio")]
+        if player.finished
+            && let Some(mut audio) = player.audio.take()
+        {
+            audio.handle.stop();
+        }
+
+        let frame = player.video.frame_at(player.time);
+        if player.shown != Some(frame) {
+            player.shown = Some(frame);
+            changed.push((entity, player.video.clone(), frame, player.texture));
+        }
+    });
+
+    #[cfg(feature = "audio")]
+    if let Some(engine) = engine {
+        world.insert_resource(engine);
+    }
+
+    for (entity, video, frame, texture) in changed {
+        let image = match video.decode(frame) {
+            Ok(image) => image,
+            Err(e) => {
+                log::warn!("{e}");
+                continue;
+            }
+        };
+        let (width, height) = image.dimensions();
+        let texture = match texture {
+            Some(texture) => {
+                update_texture_rgba(world, texture, width, height, &image);
+                texture
+            }
+            None => create_texture_from_rgba(world, "video", width, height, &image),
+        };
+
+        if let Some(player) = world.get_mut::<VideoPlayer>(entity) {
+            player.texture = Some(texture);
+        }
+        if let Some(sprite) = world.get_mut::<Sprite>(entity) {
+            sprite.texture = Some(texture);
+        }
+        if let Some(image) = world.get_mut::<UiImage>(entity) {
+            image.texture = Some(texture);
+        }
+    }
+}
+
+/// Start, pause or resume the sound track to match the player, and return
+/// the video time it has reached while it plays.
+#[cfg(feature = "audio")]
+fn sync_audio(player: &mut VideoPlayer, engine: Option<&mut crate::audio::AudioEngine>) -> Option<f64> {
+    let engine = engine?;
+    let wav = player.video.inner.index.audio.as_ref()?;
+
+    if player.audio.is_none() && player.playing {
+        let sound = match crate::audio::SoundData::from_bytes(wav.clone()) {
+            Ok(sound) => sound.volume(player.volume as f64),
+            Err(e) => {
+                log::warn!("Video sound track skipped: {e}");
+                return None;
+            }
+        };
+        match engine.try_play(&sound) {
+            Ok(handle) => {
+                player.audio = Some(VideoAudio {
+                    handle,
+                    offset: player.time,
+                    paused: false,
+                })
+            }
+            Err(e) => log::warn!("Video sound track skipped: {e}"),
+        }
+    }
+
+    let audio = player.audio.as_mut()?;
+    if audio.paused == player.playing {
+        if player.playing {
+            audio.handle.resume();
+        } else {
+            audio.handle.pause();
+        }
+        audio.paused = !player.playing;
+    }
+    // Once the sound ends (it may be a little shorter), the frame clock
+    // carries on alone.
+    (!audio.handle.is_stopped()).then(|| audio.offset + audio.handle.position())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(id: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut out = id.to_vec();
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+        if data.len() % 2 == 1 {
+            out.push(0);
+        }
+        out
+    }
+
+    fn list(kind: &[u8; 4], children: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = kind.to_vec();
+        children.iter().for_each(|child| data.extend_from_slice(child));
+        chunk(b"LIST", &data)
+    }
+
+    fn stream_header(kind: &[u8; 4], handler: &[u8; 4], scale: u32, rate: u32) -> Vec<u8> {
+        let mut strh = [0u8; 56];
+        strh[0..4].copy_from_slice(kind);
+        strh[4..8].copy_from_slice(handler);
+        strh[20..24].copy_from_slice(&scale.to_le_bytes());
+        strh[24..28].copy_from_slice(&rate.to_le_bytes());
+        chunk(b"strh", &strh)
+    }
+
+    fn jpeg(width: u32, height: u32, gray: u8) -> Vec<u8> {
+        let pixels = vec![gray; (width * height * 3) as usize];
+        let mut out = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new(&mut out)
+            .encode(&pixels, width, height, image::ExtendedColorType::Rgb8)
+            .unwrap();
+        out
+    }
+
+    /// A 4×2, 10 fps MJPEG AVI with two frames, an empty (repeat) frame and
+    /// a 16-bit mono PCM track split over two chunks.
+    fn avi(codec: &[u8; 4]) -> Vec<u8> {
+        let mut avih = [0u8; 56];
+        avih[0..4].copy_from_slice(&100_000u32.to_le_bytes());
+        avih[32..36].copy_from_slice(&4u32.to_le_bytes());
+        avih[36..40].copy_from_slice(&2u32.to_le_bytes());
+        let mut wave = [0u8; 16];
+        wave[0..2].copy_from_slice(&1u16.to_le_bytes());
+        wave[2..4].copy_from_slice(&1u16.to_le_bytes());
+        wave[4..8].copy_from_slice(&8000u32.to_le_bytes());
+        wave[14..16].copy_from_slice(&16u16.to_le_bytes());
+
+        let hdrl = list(
+            b"hdrl",
+            &[
+                chunk(b"avih", &avih),
+                list(b"strl", &[stream_header(b"vids", codec, 1, 10), chunk(b"strf", &[0; 40])]),
+                list(b"strl", &[stream_header(b"auds", &[0; 4], 1, 8000), chunk(b"strf", &wave)]),
+            ],
+        );
+        let movi = list(
+            b"movi",
+            &[
+                chunk(b"00dc", &jpeg(4, 2, 0)),
+                chunk(b"01wb", &[1, 2, 3, 4]),
+                list(b"rec ", &[chunk(b"00dc", &jpeg(4, 2, 255)), chunk(b"01wb", &[5, 6])]),
+                chunk(b"00dc", &[]),
+            ],
+        );
+        let mut body = b"AVI ".to_vec();
+        body.extend(hdrl);
+        body.extend(movi);
+        chunk(b"RIFF", &body)
+    }
+
+    #[test]
+    fn mjpeg_avi_is_indexed_and_decoded() {
+        let video = Video::from_bytes(avi(b"MJPG")).unwrap();
+        assert_eq!(video.size(), (4, 2));
+        assert_eq!(video.frame_count(), 3);
+        assert!((video.frame_rate() - 10.0).abs() < 1e-9);
+        assert_eq!(video.frame_at(0.15), 1);
+        assert_eq!(video.frame_at(99.0), 2);
+
+        // The empty third frame repeats the second (white) one.
+        let frames = &video.inner.index.frames;
+        assert_eq!(frames[1], frames[2]);
+        let white = video.decode(2).unwrap();
+        assert_eq!(white.dimensions(), (4, 2));
+        assert!(white.get_pixel(0, 0)[0] > 240);
+        assert!(video.decode(0).unwrap().get_pixel(0, 0)[0] < 15);
+
+        // Both audio chunks end up in one WAV.
+        let wav = video.inner.index.audio.as_ref().unwrap();
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32_at(wav, 24), 8000);
+        assert_eq!(u32_at(wav, 40), 6);
+        assert_eq!(&wav[44..], &[1, 2, 3, 4, 5, 6]);
+
+        let error = Video::from_bytes(avi(b"VP90")).unwrap_err();
+        assert!(matches!(error, VideoError::Unsupported(ref e) if e.contains("VP90")));
+        assert!(matches!(Video::from_bytes(b"RIFF....WAVE".to_vec()), Err(VideoError::Parse(_))));
+    }
+
+    #[test]
+    fn playback_loops_or_stops_on_the_last_frame() {
+        let video = Video::from_bytes(avi(b"mjpg")).unwrap();
+        let mut player = VideoPlayer::new(video.clone()).looping();
+        assert!(!player.advance(0.25, None));
+        assert!(player.advance(0.1, None), "0.35s wraps a 0.3s video");
+        assert!((player.time - 0.05).abs() < 1e-9);
+        // The audio clock wins over dt while it runs.
+        player.advance(1.0, Some(0.12));
+        assert_eq!(video.frame_at(player.time), 1);
+
+        let mut once = VideoPlayer::new(video);
+        once.advance(0.5, None);
+        assert!(once.is_finished() && !once.is_playing());
+        assert_eq!(once.video.frame_at(once.time), 2);
+        once.play();
+        assert!(once.is_playing() && once.time == 0.0);
+        once.pause();
+        assert!(!once.advance(1.0, None));
+        assert_eq!(once.time, 0.0);
+    }
+}
@@ -0,0 +1,204 @@
+//! # Billboard — Geometry That Always Faces the Camera
+//!
+//! A billboard ignores its own rotation and instead copies the camera's, so
+//! the local XY plane is always parallel to the screen. Classic uses: sprites
+//! in a 3D world, particles, health bars, nameplates, and damage numbers.
+//!
+//! ```text
+//!        camera ──────► looks down -Z
+//!          │
+//!          │   billboard local +Z points back at the camera,
+//!          ▼   so a quad in the XY plane is always seen face-on
+//!       ┌─────┐
+//!       │     │  ◄── same orientation as the camera
+//!       └─────┘
+//! ```
+//!
+//! ## Fixed Screen Size
+//!
+//! Under a perspective projection, things shrink with distance. A billboard
+//! with a `screen_size` is rescaled every frame to cancel that out: one local
+//! unit always covers `screen_size` pixels. The world-space size of one pixel
+//! grows linearly with view depth:
+//!
+//! ```text
+//!  world_per_pixel = depth × 2·tan(fov_y / 2) / viewport_height
+//! ```
+//!
+//! Depth is measured along the camera's forward axis (not straight-line
+//! distance), which is what the projection divides by — so the size stays
+//! exact even at the edges of the screen. An orthographic camera has no
+//! divide: one pixel covers the same world size at every depth.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `BillboardRenderer` for sprites; nameplates are usually a
+//!   world-space Canvas with a "look at camera" script.
+//! - **Godot**: `BaseMaterial3D.billboard_mode` plus `fixed_size` — the model
+//!   this component follows, but applied per entity rather than per material.
+//! - **Our approach**: The orientation is substituted into the model matrix
+//!   during draw-call collection; the entity's `Transform` is never touched.
+
+use std::collections::HashMap;
+
+use crate::ecs::{Entity, World};
+use crate::math::{Mat4, Quat, Vec3};
+
+use super::collect::ExtractedCamera3d;
+use super::Projection3d;
+
+/// Depth floor for screen-size scaling, so a billboard at (or behind) the
+/// camera plane doesn't collapse to zero size.
+const MIN_DEPTH: f32 = 1e-3;
+
+/// Component: render this entity facing the camera. Pair with
+/// [`Transform`](crate::math::Transform) and a mesh.
+///
+/// The entity's own rotation is replaced by the camera's; translation and
+/// scale are kept. Build flat billboards in the local XY plane.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Billboard {
+    /// When set, one local unit covers this many pixels on screen regardless
+    /// of distance (multiplied with the entity's scale).
+    pub screen_size: Option<f32>,
+}
+
+impl Billboard {
+    /// Face the camera, keeping world-space size.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Face the camera at a constant on-screen size of `pixels` per local unit.
+    pub fn screen_size(pixels: f32) -> Self {
+        Self {
+            screen_size: Some(pixels),
+        }
+    }
+}
+
+/// Camera data needed to orient and size billboards for one frame.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BillboardView {
+    pub position: Vec3,
+    pub rotation: Quat,
+    pub forward: Vec3,
+    /// World-space height of one pixel: at a view depth of 1, or at every
+    /// depth when `orthographic`.
+    pub pixel_size: f32,
+    pub orthographic: bool,
+}
+
+impl BillboardView {
+    pub fn new(camera: Mat4, projection: Projection3d, surface_size: (u32, u32)) -> Self {
+        let (_, rotation, position) = camera.to_scale_rotation_translation();
+        Self {
+            position,
+            rotation,
+            forward: rotation * Vec3::NEG_Z,
+            pixel_size: projection.pixel_size(surface_size),
+            orthographic: projection.is_orthographic(),
+        }
+    }
+
+    /// View depth of `point` along the camera's forward axis.
+    pub fn depth(&self, point: Vec3) -> f32 {
+        (point - self.position).dot(self.forward)
+    }
+
+    /// World-space size of one pixel at `point`.
+    pub fn world_per_pixel(&self, point: Vec3) -> f32 {
+        if self.orthographic {
+            return self.pixel_size;
+        }
+        self.depth(point).max(MIN_DEPTH) * self.pixel_size
+    }
+
+    /// Rebuild `model` so it faces the camera, keeping translation and scale
+    /// (rescaled to a constant pixel size when `screen_size` is set).
+    pub fn orient(&self, model: Mat4, screen_size: Option<f32>) -> Mat4 {
+        let (mut scale, _, translation) = model.to_scale_rotation_translation();
+        if let Some(pixels) = screen_size {
+            scale *= pixels * self.world_per_pixel(translation);
+        }
+        Mat4::from_scale_rotation_translation(scale, self.rotation, translation)
+    }
+}
+
+/// Build the [`BillboardView`] for the extracted 3D camera.
+pub(crate) fn collect_billboard_view(
+    camera: Option<&ExtractedCamera3d>,
+    surface_size: (u32, u32),
+) -> Option<BillboardView> {
+    camera.map(|cam| BillboardView::new(cam.matrix, cam.projection, surface_size))
+}
+
+/// Gather every [`Billboard`] entity's screen-size setting, keyed by entity.
+pub(crate) fn collect_billboards(world: &mut World) -> HashMap<Entity, Option<f32>> {
+    let mut billboards = HashMap::new();
+    world.query::<(&Billboard,)>(|entity, (billboard,)| {
+        billboards.insert(entity, billboard.screen_size);
+    });
+    billboards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Camera at `position` looking down -Z, 90° fov, 2px tall viewport, so
+    /// one pixel at depth `d` is exactly `d` world units.
+    fn view_at(position: Vec3, rotation: Quat) -> BillboardView {
+        let camera = Mat4::from_rotation_translation(rotation, position);
+        BillboardView::new(camera, Projection3d::perspective(90.0), (2, 2))
+    }
+
+    #[test]
+    fn orient_copies_camera_rotation_and_keeps_translation_scale() {
+        let cam_rot = Quat::from_rotation_y(0.7);
+        let view = view_at(Vec3::ZERO, cam_rot);
+        let model = Mat4::from_scale_rotation_translation(
+            Vec3::splat(2.0),
+            Quat::from_rotation_x(1.2),
+            Vec3::new(1.0, 2.0, -5.0),
+        );
+
+        let (scale, rotation, translation) = view.orient(model, None).to_scale_rotation_translation();
+        assert!(scale.abs_diff_eq(Vec3::splat(2.0), 1e-5));
+        assert!(rotation.abs_diff_eq(cam_rot, 1e-5));
+        assert!(translation.abs_diff_eq(Vec3::new(1.0, 2.0, -5.0), 1e-5));
+    }
+
+    #[test]
+    fn world_per_pixel_grows_with_depth() {
+        let view = view_at(Vec3::ZERO, Quat::IDENTITY);
+        assert!((view.world_per_pixel(Vec3::new(0.0, 0.0, -5.0)) - 5.0).abs() < 1e-4);
+        // Off-axis points use view depth, not straight-line distance.
+        assert!((view.world_per_pixel(Vec3::new(3.0, 0.0, -5.0)) - 5.0).abs() < 1e-4);
+        // Behind the camera clamps instead of flipping.
+        assert!(view.world_per_pixel(Vec3::new(0.0, 0.0, 5.0)) > 0.0);
+    }
+
+    #[test]
+    fn screen_size_scales_with_distance() {
+        let view = view_at(Vec3::ZERO, Quat::IDENTITY);
+        let near = view.orient(Mat4::from_translation(Vec3::new(0.0, 0.0, -2.0)), Some(10.0));
+        let far = view.orient(Mat4::from_translation(Vec3::new(0.0, 0.0, -8.0)), Some(10.0));
+        let near_scale = near.to_scale_rotation_translation().0.x;
+        let far_scale = far.to_scale_rotation_translation().0.x;
+        assert!((near_scale - 20.0).abs() < 1e-3);
+        assert!((far_scale / near_scale - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn orthographic_pixels_ignore_depth() {
+        // 10 world units over 100 pixels: 0.1 units per pixel at any depth.
+        let projection = Projection3d::orthographic_height(10.0);
+        let view = BillboardView::new(Mat4::IDENTITY, projection, (200, 100));
+        assert!((view.world_per_pixel(Vec3::new(0.0, 0.0, -2.0)) - 0.1).abs() < 1e-6);
+        assert!((view.world_per_pixel(Vec3::new(0.0, 0.0, -50.0)) - 0.1).abs() < 1e-6);
+
+        let scaled = BillboardView::new(Mat4::IDENTITY, Projection3d::orthographic_scale(0.5), (200, 100));
+        assert!((scaled.world_per_pixel(Vec3::new(0.0, 0.0, -7.0)) - 0.5).abs() < 1e-6);
+    }
+}
@@ -11,84 +11,186 @@
 //! - A material (bind group 2: uniform + texture)
 //! - A model transform (bind group 3: dynamic offset into model buffer)
 //!
+//! Meshes entirely outside the camera's [frustum](super::frustum) are
+//! dropped before any of this.
+//!
 //! Draw calls are sorted by material to minimize bind group 2 changes.
 //! Within the same material, draw calls are ordered by mesh handle (though
 //! this doesn't save GPU state changes in our current design, it groups
 //! similar geometry for cache friendliness).
 //!
+//! [Transparent](super::transparency) draw calls follow the opaque ones,
+//! sorted far → near instead — blending needs the order more than the
+//! batching.
+//!
 //! ## Camera and Lights
 //!
 //! The camera view-projection matrix and all light data are collected first
-//! and written to their respective uniform buffers (groups 0 and 1). These
-//! are bound once and don't change during the frame.
+//! and written to their respective buffers (groups 0 and 1): the camera,
+//! directional and ambient lights into uniforms, point and spot lights into
+//! storage buffers sized to however many there are, up to
+//! [`GraphicsSettings::max_lights`](crate::render::GraphicsSettings) each.
+//! These are bound once and don't change during the frame.
+//!
+//! ## Extract, Then Collect
+//!
+//! The `extract_*` functions (and [`collect_lights`]) run during the
+//! [extract phase](crate::render::extract): they copy the camera, lights,
+//! and visible meshes out of the ECS. The rest of this module only turns
+//! those copies into uniforms and draw calls — it never touches the world.
 //!
 //! ## Comparison
 //!
 //! - **Bevy**: Uses a sophisticated `RenderPhase` system with sort keys,
 //!   batching, and parallel extraction from the main world to a render world.
-//! - **Our approach**: Simple serial collection into Vec, sort, done.
+//! - **Our approach**: Simple serial extraction into Vec, sort, done.
+
+use std::collections::HashSet;
 
 use crate::ecs::World;
 use crate::ecs::hierarchy::GlobalTransform;
+use crate::render::pass::CameraClear;
+use crate::render::settings::graphics_settings;
+use crate::render::visibility::{ComputedVisibility, is_hidden};
 
+use super::billboard::{collect_billboards, BillboardView};
+use super::frustum::{Aabb, Frustum};
+use super::material_shader::MaterialShader;
 use super::mesh::MeshHandle;
-use super::texture::TextureHandle3d;
+use super::particles::{extract_particles_3d, ExtractedParticles3d};
+use super::soft_particle::{extract_soft_particles, ExtractedSoftParticle};
+use super::texture::{MaterialTextures, TextureHandle3d};
 use super::vertex::{
-    CameraUniform3d, LightUniform, MaterialUniform, ModelUniform, PointLightData, MAX_POINT_LIGHTS,
+    CameraUniform3d, LightUniform, MaterialUniform, ModelUniform, PointLightData, SpotLightData,
 };
 use super::shape::Shape3d;
-use super::{AmbientLight, Camera3d, DirectionalLight, Material, Mesh3d, PointLight};
+use super::skybox::sky_ambient;
+use super::soft_particle::SoftParticle;
+use super::transparency::{AlphaMode, Transparency};
+use super::{
+    AmbientLight, Camera3d, DirectionalLight, Material, Mesh3d, PointLight, Projection3d, SpotLight,
+};
 
 /// A single draw command ready for the render pass.
 pub(crate) struct DrawCall {
     pub mesh: MeshHandle,
     pub material_uniform: MaterialUniform,
-    pub base_color_texture: Option<TextureHandle3d>,
+    pub textures: MaterialTextures,
+    pub shader: Option<MaterialShader>,
+    pub alpha_mode: AlphaMode,
     pub model_uniform: ModelUniform,
 }
 
-/// Collect the camera VP matrix and position from the scene.
+/// The active 3D camera, copied out of the ECS by the extract phase.
+pub(crate) struct ExtractedCamera3d {
+    pub matrix: glam::Mat4,
+    pub projection: Projection3d,
+    pub near: f32,
+    pub far: f32,
+    pub depth_prepass: bool,
+    pub transparency: Transparency,
+    pub clear: CameraClear,
+}
+
+/// A visible mesh (or [`Shape3d`]), copied out of the ECS by the extract
+/// phase.
+pub(crate) struct ExtractedMesh {
+    pub matrix: glam::Mat4,
+    /// `Some(screen_size)` for [`Billboard`](super::Billboard) entities.
+    pub billboard: Option<Option<f32>>,
+    /// Extra scale applied after billboard orientation (shape dimensions).
+    pub scale: glam::Vec3,
+    pub mesh: MeshHandle,
+    pub material_uniform: MaterialUniform,
+    pub textures: MaterialTextures,
+    pub shader: Option<MaterialShader>,
+    pub alpha_mode: AlphaMode,
+}
+
+/// 3D scene state copied out of the ECS by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct Extracted3d {
+    pub camera: Option<ExtractedCamera3d>,
+    pub lights: ExtractedLights,
+    pub meshes: Vec<ExtractedMesh>,
+    pub particles: Vec<ExtractedParticles3d>,
+    pub soft_particles: Vec<ExtractedSoftParticle>,
+    /// Visible [`Text3d`](super::Text3d) at its world position.
+    #[cfg(feature = "render2d")]
+    pub labels: Vec<(glam::Vec3, super::Text3d)>,
+    /// Collider outlines, while [`DebugColliders3d`](super::debug_wireframe::DebugColliders3d)
+    /// is inserted.
+    #[cfg(feature = "physics3d")]
+    pub colliders: Option<super::debug_wireframe::ExtractedColliders3d>,
+}
+
+/// Copy the camera, lights, every visible mesh, particle and label, and
+/// debug collider poses.
+pub(crate) fn extract_3d(world: &mut World) -> Extracted3d {
+    Extracted3d {
+        camera: extract_camera_3d(world),
+        lights: collect_lights(world),
+        meshes: extract_meshes(world),
+        particles: extract_particles_3d(world),
+        soft_particles: extract_soft_particles(world),
+        #[cfg(feature = "render2d")]
+        labels: super::text3d::extract_labels(world),
+        #[cfg(feature = "physics3d")]
+        colliders: super::debug_wireframe::extract_colliders_3d(world),
+    }
+}
+
+/// Copy the active 3D camera's settings.
+pub(crate) fn extract_camera_3d(world: &mut World) -> Option<ExtractedCamera3d> {
+    let mut camera = None;
+    world.query_single::<(&GlobalTransform, &Camera3d, Option<&CameraClear>), Camera3d>(
+        |_entity, (gt, cam, clear)| {
+            camera = Some(ExtractedCamera3d {
+                matrix: gt.matrix,
+                projection: cam.projection,
+                near: cam.near,
+                far: cam.far,
+                depth_prepass: cam.depth_prepass,
+                transparency: cam.transparency,
+                clear: clear.copied().unwrap_or_default(),
+            });
+        },
+    );
+    camera
+}
+
+/// Build the camera uniform: view-projection matrix and position.
 pub(crate) fn collect_camera(
-    world: &mut World,
+    camera: Option<&ExtractedCamera3d>,
     surface_size: (u32, u32),
 ) -> CameraUniform3d {
-    let (width, height) = surface_size;
-    let aspect = width as f32 / height.max(1) as f32;
-
     let mut camera_uniform = CameraUniform3d {
         view_proj: glam::Mat4::IDENTITY.to_cols_array_2d(),
         camera_pos: [0.0; 3],
         _padding: 0.0,
     };
 
-    world.query_single::<(&GlobalTransform, &Camera3d), Camera3d>(|_entity, (gt, cam)| {
-        let projection = glam::Mat4::perspective_rh(
-            cam.fov_y.to_radians(),
-            aspect,
-            cam.near,
-            cam.far,
-        );
-        let view = gt.matrix.inverse();
+    if let Some(cam) = camera {
+        let projection = cam.projection.matrix(surface_size, cam.near, cam.far);
+        let view = cam.matrix.inverse();
         camera_uniform.view_proj = (projection * view).to_cols_array_2d();
-        camera_uniform.camera_pos = gt.matrix.col(3).truncate().to_array();
-    });
+        camera_uniform.camera_pos = cam.matrix.col(3).truncate().to_array();
+    }
 
     camera_uniform
 }
 
-/// Collect all light data into a single uniform struct.
-pub(crate) fn collect_lights(world: &mut World) -> LightUniform {
-    let mut uniform = LightUniform {
-        dir_direction: [0.0, -1.0, 0.0],
-        dir_intensity: 0.0,
-        dir_color: [1.0, 1.0, 1.0],
-        _pad0: 0.0,
-        ambient_color: [1.0, 1.0, 1.0],
-        ambient_intensity: 0.1,
-        point_lights: [bytemuck::Zeroable::zeroed(); MAX_POINT_LIGHTS],
-        point_light_count: 0,
-        _pad1: [0; 3],
-    };
+/// Every light in the scene, packed for the GPU.
+pub(crate) struct ExtractedLights {
+    pub uniform: LightUniform,
+    pub point: Vec<PointLightData>,
+    pub spot: Vec<SpotLightData>,
+}
+
+/// Collect all light data: the uniform, plus the point and spot lights up
+/// to the graphics settings' light limit.
+pub(crate) fn collect_lights(world: &mut World) -> ExtractedLights {
+    let mut uniform = LightUniform::default();
 
     // Directional light (use first found)
     let mut found_dir = false;
@@ -107,94 +209,194 @@ pub(crate) fn collect_lights(world: &mut World) -> LightUniform {
         uniform.ambient_intensity = ambient.intensity;
     }
 
-    // Point lights (up to MAX_POINT_LIGHTS)
-    let mut count = 0u32;
+    // Sky ambient (from the Skybox, once its images have loaded)
+    if let Some((ambient, intensity)) = sky_ambient(world) {
+        uniform.sky_color = ambient.sky;
+        uniform.ground_color = ambient.ground;
+        uniform.sky_intensity = intensity;
+    }
+
+    // Point and spot lights (up to the limit each)
+    let limit = graphics_settings(world).light_limit();
+    let mut point = Vec::new();
     world.query::<(&GlobalTransform, &PointLight)>(|_entity, (gt, light)| {
-        if (count as usize) < MAX_POINT_LIGHTS {
-            uniform.point_lights[count as usize] = PointLightData {
+        if point.len() < limit {
+            point.push(PointLightData {
                 position: gt.matrix.col(3).truncate().to_array(),
                 radius: light.radius,
                 color: light.color,
                 intensity: light.intensity,
-            };
-            count += 1;
+                attenuation: light.attenuation.shader_id(),
+                _pad: [0; 3],
+            });
         }
     });
-    uniform.point_light_count = count;
+    let mut spot = Vec::new();
+    world.query::<(&GlobalTransform, &SpotLight)>(|_entity, (gt, light)| {
+        if spot.len() < limit {
+            spot.push(spot_light_data(gt, light));
+        }
+    });
+    uniform.point_light_count = point.len() as u32;
+    uniform.spot_light_count = spot.len() as u32;
 
-    uniform
+    ExtractedLights { uniform, point, spot }
 }
 
-/// Collect all mesh entities into draw calls, sorted by material.
-pub(crate) fn collect_draw_calls(world: &mut World) -> Vec<DrawCall> {
-    let mut calls = Vec::new();
-
-    world.query::<(&GlobalTransform, &Mesh3d, &Material)>(|_entity, (gt, mesh3d, material)| {
-        let model = gt.matrix;
-        // Normal matrix: inverse transpose of upper 3x3, stored as mat4x4.
-        // For uniform scale, this equals the model matrix itself.
-        // For non-uniform scale, we need the proper inverse transpose.
-        let normal_matrix = model.inverse().transpose();
-
-        let mat_uniform = MaterialUniform {
-            base_color: material.base_color,
-            metallic: material.metallic,
-            roughness: material.roughness,
-            _pad0: [0.0; 2],
-            emissive: material.emissive,
-            _pad1: 0.0,
-        };
-
-        let model_uniform = ModelUniform {
-            model: model.to_cols_array_2d(),
-            normal_matrix: normal_matrix.to_cols_array_2d(),
-        };
-
-        calls.push(DrawCall {
+/// Pack a spot light, pointing along the entity's −Z axis.
+fn spot_light_data(gt: &GlobalTransform, light: &SpotLight) -> SpotLightData {
+    // Keep the cone below a hemisphere and the core inside the cone; the
+    // shader fades between the two cosines, so they must differ.
+    let outer = light.outer_angle.clamp(0.1, 89.0);
+    let inner = light.inner_angle.clamp(0.0, outer);
+    let direction = (-gt.matrix.col(2).truncate()).normalize_or(glam::Vec3::NEG_Z);
+    SpotLightData {
+        position: gt.matrix.col(3).truncate().to_array(),
+        range: light.range,
+        color: light.color,
+        intensity: light.intensity,
+        direction: direction.to_array(),
+        cos_inner: inner.to_radians().cos(),
+        cos_outer: outer.to_radians().cos(),
+        attenuation: light.attenuation.shader_id(),
+        _pad: [0; 2],
+    }
+}
+
+/// Copy every visible mesh entity (`Mesh3d` + `Material`, or `Shape3d`)
+/// out of the ECS, except soft particles.
+pub(crate) fn extract_meshes(world: &mut World) -> Vec<ExtractedMesh> {
+    let mut meshes = Vec::new();
+    let billboards = collect_billboards(world);
+    // Soft particles get their own transparent pass (see `soft_particle`).
+    let mut soft = HashSet::new();
+    world.query::<(&SoftParticle,)>(|entity, _| {
+        soft.insert(entity);
+    });
+
+    world.query::<(&GlobalTransform, &Mesh3d, &Material, Option<&ComputedVisibility>)>(|entity, (gt, mesh3d, material, vis)| {
+        if soft.contains(&entity) || is_hidden(vis) {
+            return;
+        }
+        meshes.push(ExtractedMesh {
+            matrix: gt.matrix,
+            billboard: billboards.get(&entity).copied(),
+            scale: glam::Vec3::ONE,
             mesh: mesh3d.mesh,
-            material_uniform: mat_uniform,
-            base_color_texture: material.base_color_texture,
-            model_uniform,
+            material_uniform: MaterialUniform {
+                base_color: material.base_color,
+                metallic: material.metallic,
+                roughness: material.roughness,
+                normal_scale: material.normal_scale,
+                ao_strength: material.ao_strength,
+                emissive: material.emissive,
+                normal_mapped: material.normal_texture.is_some() as u32,
+            },
+            textures: MaterialTextures::of(material),
+            shader: material.shader,
+            alpha_mode: material.alpha_mode,
         });
     });
 
     // Collect Shape3d entities (single-component alternative to Mesh3d + Material).
-    world.query::<(&GlobalTransform, &Shape3d)>(|_entity, (gt, shape)| {
-        let shape_scale = shape.shape_scale();
-        let model = gt.matrix * glam::Mat4::from_scale(shape_scale);
-        let normal_matrix = model.inverse().transpose();
-
-        let mat_uniform = MaterialUniform {
-            base_color: shape.base_color,
-            metallic: shape.metallic,
-            roughness: shape.roughness,
-            _pad0: [0.0; 2],
-            emissive: [0.0, 0.0, 0.0],
-            _pad1: 0.0,
-        };
-
-        let model_uniform = ModelUniform {
-            model: model.to_cols_array_2d(),
-            normal_matrix: normal_matrix.to_cols_array_2d(),
-        };
-
-        calls.push(DrawCall {
+    world.query::<(&GlobalTransform, &Shape3d, Option<&ComputedVisibility>)>(|entity, (gt, shape, vis)| {
+        if is_hidden(vis) {
+            return;
+        }
+        meshes.push(ExtractedMesh {
+            matrix: gt.matrix,
+            billboard: billboards.get(&entity).copied(),
+            scale: shape.shape_scale(),
             mesh: shape.mesh_handle(),
-            material_uniform: mat_uniform,
-            base_color_texture: None,
-            model_uniform,
+            material_uniform: MaterialUniform {
+                base_color: shape.base_color,
+                metallic: shape.metallic,
+                roughness: shape.roughness,
+                normal_scale: 1.0,
+                ao_strength: 1.0,
+                emissive: [0.0, 0.0, 0.0],
+                normal_mapped: 0,
+            },
+            textures: MaterialTextures::default(),
+            shader: None,
+            alpha_mode: AlphaMode::Opaque,
         });
     });
 
-    // Sort by material parameters to minimize bind group 2 changes.
-    // We use a simple key: (texture handle, metallic bits, roughness bits).
-    calls.sort_by(|a, b| {
-        let key_a = material_sort_key(&a.material_uniform, a.base_color_texture);
-        let key_b = material_sort_key(&b.material_uniform, b.base_color_texture);
-        key_a.cmp(&key_b)
+    meshes
+}
+
+/// Frustum culling for [`collect_draw_calls`].
+pub(crate) struct Culling<'a> {
+    pub frustum: Frustum,
+    /// A mesh's bounds in mesh space.
+    pub bounds: &'a dyn Fn(MeshHandle) -> Aabb,
+}
+
+/// Turn extracted meshes into draw calls: opaque ones sorted by material,
+/// then [`AlphaMode::Blend`] ones sorted far → near. With `culling`, meshes
+/// outside the frustum get none.
+///
+/// Billboard meshes have their rotation replaced by the camera's (when a
+/// camera `view` is available).
+pub(crate) fn collect_draw_calls(
+    meshes: &[ExtractedMesh],
+    view: Option<&BillboardView>,
+    culling: Option<&Culling<'_>>,
+) -> Vec<DrawCall> {
+    let mut calls: Vec<(f32, DrawCall)> = meshes
+        .iter()
+        .filter_map(|mesh| {
+            let oriented = match (view, mesh.billboard) {
+                (Some(view), Some(screen_size)) => view.orient(mesh.matrix, screen_size),
+                _ => mesh.matrix,
+            };
+            let model = oriented * glam::Mat4::from_scale(mesh.scale);
+            if let Some(culling) = culling
+                && !culling.frustum.intersects(&(culling.bounds)(mesh.mesh).transformed(&model))
+            {
+                return None;
+            }
+            // Normal matrix: inverse transpose of upper 3x3, stored as mat4x4.
+            // For uniform scale, this equals the model matrix itself.
+            // For non-uniform scale, we need the proper inverse transpose.
+            let normal_matrix = model.inverse().transpose();
+            let depth = view.map_or(0.0, |view| view.depth(model.col(3).truncate()));
+
+            let call = DrawCall {
+                mesh: mesh.mesh,
+                material_uniform: mesh.material_uniform,
+                textures: mesh.textures,
+                shader: mesh.shader,
+                alpha_mode: mesh.alpha_mode,
+                model_uniform: ModelUniform {
+                    model: model.to_cols_array_2d(),
+                    normal_matrix: normal_matrix.to_cols_array_2d(),
+                },
+            };
+            Some((depth, call))
+        })
+        .collect();
+
+    // Opaque first. Sort those by shader to minimize pipeline switches, then
+    // by material parameters to minimize bind group 2 changes. The material
+    // key is simple: (texture handle, metallic bits, roughness bits).
+    // Transparent calls sort by view depth, far → near.
+    calls.sort_by(|(depth_a, a), (depth_b, b)| {
+        let blend_a = a.alpha_mode == AlphaMode::Blend;
+        let blend_b = b.alpha_mode == AlphaMode::Blend;
+        blend_a.cmp(&blend_b).then_with(|| {
+            if blend_a {
+                depth_b.total_cmp(depth_a)
+            } else {
+                let key_a = (a.shader, material_sort_key(&a.material_uniform, a.textures.base_color));
+                let key_b = (b.shader, material_sort_key(&b.material_uniform, b.textures.base_color));
+                key_a.cmp(&key_b)
+            }
+        })
     });
 
-    calls
+    calls.into_iter().map(|(_, call)| call).collect()
 }
 
 /// Generate a sort key for a material to group similar materials together.
@@ -7,9 +7,12 @@
 use bytemuck::{Pod, Zeroable};
 use wgpu::util::DeviceExt;
 
+use crate::ecs::World;
 use crate::math::{Quat, Vec3};
-use crate::physics3d::ColliderShape3d;
+use crate::physics3d::{Collider3d, ColliderShape3d};
 use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
 
 use super::pipeline::{MeshRenderer, DEPTH_FORMAT};
 
@@ -17,7 +20,7 @@ use super::pipeline::{MeshRenderer, DEPTH_FORMAT};
 
 /// Insert this resource to enable debug collider wireframes in 3D.
 /// Toggle `enabled` at runtime (e.g. with F1).
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DebugColliders3d {
     pub enabled: bool,
     pub color: [f32; 4],
@@ -32,6 +35,28 @@ impl Default for DebugColliders3d {
     }
 }
 
+// ── Extract ─────────────────────────────────────────────────────────────
+
+/// The debug settings and every visible collider's pose, copied out by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct ExtractedColliders3d {
+    pub config: DebugColliders3d,
+    pub poses: Vec<(Vec3, Quat, ColliderShape3d)>,
+}
+
+/// Copy the colliders to outline, or `None` without a [`DebugColliders3d`]
+/// resource.
+pub(crate) fn extract_colliders_3d(world: &mut World) -> Option<ExtractedColliders3d> {
+    let config = world.get_resource::<DebugColliders3d>()?.clone();
+    let mut poses = Vec::new();
+    world.query::<(&Collider3d, &crate::math::Transform, Option<&ComputedVisibility>)>(|_entity, (coll, tf, vis)| {
+        if !is_hidden(vis) {
+            poses.push((tf.translation, tf.rotation, coll.shape));
+        }
+    });
+    Some(ExtractedColliders3d { config, poses })
+}
+
 // ── Vertex ──────────────────────────────────────────────────────────────
 
 #[repr(C)]
@@ -56,6 +81,8 @@ impl DebugVertex {
 
 pub(crate) struct DebugWireframeRenderer {
     pipeline: wgpu::RenderPipeline,
+    /// Color target the pipeline was built for.
+    pub target: TargetKey,
     color_buffer: wgpu::Buffer,
     color_bind_group: wgpu::BindGroup,
 }
@@ -63,9 +90,10 @@ pub(crate) struct DebugWireframeRenderer {
 impl DebugWireframeRenderer {
     pub fn new(
         device: &wgpu::Device,
-        surface_format: wgpu::TextureFormat,
+        target: TargetKey,
         camera_bind_group_layout: &wgpu::BindGroupLayout,
     ) -> Self {
+        let (format, samples) = target;
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("3d debug wireframe shader"),
             source: wgpu::ShaderSource::Wgsl(include_str!("debug_wireframe.wgsl").into()),
@@ -105,7 +133,7 @@ impl DebugWireframeRenderer {
                 module: &shader,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
+                    format,
                     blend: Some(wgpu::BlendState::ALPHA_BLENDING),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -127,7 +155,7 @@ impl DebugWireframeRenderer {
                 stencil: wgpu::StencilState::default(),
                 bias: wgpu::DepthBiasState::default(),
             }),
-            multisample: wgpu::MultisampleState::default(),
+            multisample: multisample_state(samples),
             multiview: None,
             cache: None,
         });
@@ -149,6 +177,7 @@ impl DebugWireframeRenderer {
 
         Self {
             pipeline,
+            target,
             color_buffer,
             color_bind_group,
         }
@@ -359,7 +388,7 @@ const CIRCLE_SEGMENTS: u32 = 32;
 
 pub(crate) fn render_debug_wireframes_3d(
     encoder: &mut wgpu::CommandEncoder,
-    view: &wgpu::TextureView,
+    target: &ColorTarget,
     gpu: &GpuContext,
     renderer: &MeshRenderer,
     debug_renderer: &mut DebugWireframeRenderer,
@@ -412,15 +441,7 @@ pub(crate) fn render_debug_wireframes_3d(
     {
         let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("3d debug wireframe pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
+            color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                 view: &renderer.depth_texture,
                 depth_ops: Some(wgpu::Operations {
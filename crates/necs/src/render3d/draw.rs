@@ -7,29 +7,58 @@
 //! ## Per-Frame Flow
 //!
 //! ```text
-//! render_meshes_3d(world)
+//! render_meshes_3d(world, scene)     scene = Extracted3d (extract phase)
 //!   │
 //!   ├─ 1. Lazy init ─── first frame only
 //!   │     Create MeshRenderer, MeshStore, TextureStore3d
 //!   │
-//!   ├─ 2. Extract resources ─── remove from World
+//!   ├─ 2. Extract resources ─── remove from World; apply the
+//!   │     GraphicsSettings anisotropy to TextureStore3d
 //!   │
-//!   ├─ 3. Depth check ─── recreate depth texture if resized
+//!   ├─ 2b. HDR (PostProcess) ─── draw into the Rgba16Float target
+//!   │     instead of the frame's
 //!   │
-//!   ├─ 4. Collect lights ─── query lights → write LightUniform
+//!   ├─ 3. Depth check ─── recreate depth texture if the target resized;
+//!   │     rebuild pipelines if the target format or MSAA sample count
+//!   │     changed
 //!   │
-//!   ├─ 5. Camera VP ─── query Camera3d → perspective × inverse view
+//!   ├─ 4. Lights ─── write the extracted LightUniform and the point
+//!   │     and spot light arrays (growing their buffers if needed)
 //!   │
-//!   ├─ 6. Collect draw calls ─── query (Transform, Mesh3d, Material)
-//!   │     Sort by material, write ModelUniforms to dynamic buffer
+//!   ├─ 5. Camera VP ─── extracted camera → projection × inverse view
+//!   │
+//!   ├─ 6. Collect draw calls ─── from the extracted meshes
+//!   │     Face Billboards at the camera, sort opaque by material,
+//!   │     then transparent far → near; split off SoftParticles
+//!   │     (sorted far → near); write ModelUniforms to dynamic buffer
 //!   │
 //!   ├─ 7. Create material bind groups (group 2)
 //!   │
+//!   ├─ 7b. Depth prepass (Camera3d::depth_prepass)
+//!   │     Depth-only draw of every opaque mesh, no fragment shader
+//!   │
+//!   ├─ 7d. Skybox ─── environment drawn over the whole target,
+//!   │     in place of the clear color (unless CameraClear::Load)
+//!   │
 //!   ├─ 8. Render pass
-//!   │     Clear color+depth, bind pipeline
+//!   │     Clear color (or load it, per CameraClear or over the
+//!   │     skybox); clear depth unless the prepass filled it; bind pipeline
 //!   │     Bind groups 0+1 once
 //!   │     Loop: bind group 2 per material, group 3 per object
-//!   │     draw_indexed for each object
+//!   │     draw_indexed for each object; sorted transparent last
+//!   │
+//!   ├─ 8a. Weighted-blended OIT (Camera3d::transparency)
+//!   │     Accumulate transparent meshes, composite over the scene
+//!   │
+//!   ├─ 8b. Soft particles ─── alpha-blended, faded against the depth texture
+//!   │
+//!   ├─ 8c. Emitter particles ─── billboards sorted far → near, depth-tested
+//!   │
+//!   ├─ 8d. Debug wireframes (physics3d)
+//!   │
+//!   ├─ 8e. Text3d labels (render2d) ─── alpha-blended, depth-tested
+//!   │
+//!   ├─ 8f. Bloom and tone mapping (PostProcess) ─── HDR → frame target
 //!   │
 //!   └─ 9. Reinsert resources
 //! ```
@@ -47,43 +76,66 @@
 //! - **Bevy**: Extraction happens in a separate "render world" with parallel
 //!   systems. Multiple render phases (shadow, opaque, transparent) with
 //!   sort keys and batching.
-//! - **Our approach**: Single-pass forward rendering, serial extraction,
-//!   minimal indirection.
+//! - **Our approach**: Single-pass forward rendering, extraction into plain
+//!   copies (encoded on a [render thread](crate::render::pipelined) when
+//!   pipelined), minimal indirection.
 
 use wgpu::util::DeviceExt;
 
-use super::collect::{collect_camera, collect_draw_calls, collect_lights, DrawCall};
+use super::billboard::collect_billboard_view;
+use super::collect::{collect_camera, collect_draw_calls, Culling, DrawCall, Extracted3d};
+use super::frustum::Frustum;
+use super::hdr::{begin_hdr, finish_hdr};
+use super::material_shader::MaterialShaders;
 use super::mesh::MeshStore;
-use super::pipeline::MeshRenderer;
-use super::texture::{TextureHandle3d, TextureStore3d};
+use super::particles::{render_particles_3d, ParticleRenderer3d};
+use super::pipeline::{ColorOutput, MeshRenderer, PbrDefs, PipelineKey};
+use super::skybox::render_skybox;
+use super::soft_particle::{collect_soft_particles, render_soft_particles, SoftParticleRenderer};
+use super::texture::{MaterialTextures, TextureStore3d};
+use super::transparency::{color_output, AlphaMode, OitRenderer};
 use super::vertex::MaterialUniform;
 use crate::asset::{AssetKind, AssetServer};
 use crate::ecs::World;
 use crate::render::gpu::GpuContext;
-use crate::render::pass::{ClearColor, FrameContext};
+use crate::render::msaa::TargetKey;
+use crate::render::pass::{camera_load_op, CameraClear, FrameContext};
+use crate::render::settings::graphics_settings;
+
+/// Lazy init: create the MeshRenderer, MeshStore and TextureStore3d the
+/// first time the 3D scene is drawn. `target` is only the renderer's first
+/// guess; a draw into a different target rebuilds its pipelines.
+pub(crate) fn ensure_mesh_renderer(world: &mut World, gpu: &GpuContext, target: TargetKey) {
+    if world.has_resource::<MeshRenderer>() {
+        return;
+    }
+    let renderer = MeshRenderer::new(gpu, target);
+    let mesh_store = MeshStore::new(gpu);
+    let texture_store = TextureStore3d::new(gpu);
+
+    // Register shader file for hot-reload watching.
+    let shader_path = renderer.shader_path.clone();
+    world.insert_resource(renderer);
+    world.insert_resource(mesh_store);
+    world.insert_resource(texture_store);
+
+    if let Some(path) = shader_path
+        && let Some(server) = world.get_resource_mut::<AssetServer>()
+    {
+        server.watch(path, AssetKind::Shader3d);
+    }
+}
 
 /// Render all 3D meshes for the current frame.
-pub(crate) fn render_meshes_3d(world: &mut World, frame: &mut FrameContext<'_>) {
+pub(crate) fn render_meshes_3d(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+    scene: &Extracted3d,
+) {
     let gpu = frame.gpu;
 
     // ── 1. Lazy init ────────────────────────────────────────────────────
-    if !world.has_resource::<MeshRenderer>() {
-        let renderer = MeshRenderer::new(gpu);
-        let mesh_store = MeshStore::new(gpu);
-        let texture_store = TextureStore3d::new(gpu);
-
-        // Register shader file for hot-reload watching.
-        let shader_path = renderer.shader_path.clone();
-        world.insert_resource(renderer);
-        world.insert_resource(mesh_store);
-        world.insert_resource(texture_store);
-
-        if let Some(path) = shader_path {
-            if let Some(server) = world.get_resource_mut::<AssetServer>() {
-                server.watch(path, AssetKind::Shader3d);
-            }
-        }
-    }
+    ensure_mesh_renderer(world, gpu, frame.target().key());
 
     // ── 2. Extract resources ────────────────────────────────────────────
     let mut renderer = world
@@ -92,34 +144,57 @@ pub(crate) fn render_meshes_3d(world: &mut World, frame: &mut FrameContext<'_>)
     let mesh_store = world
         .resource_remove::<MeshStore>()
         .expect("MeshStore missing");
-    let texture_store = world
+    let mut texture_store = world
         .resource_remove::<TextureStore3d>()
         .expect("TextureStore3d missing");
+    texture_store.set_min_anisotropy(gpu, graphics_settings(world).anisotropy);
+
+    // ── 2b. HDR ─────────────────────────────────────────────────────────
+    let hdr = begin_hdr(world, frame);
+    let target = hdr.clone().unwrap_or_else(|| frame.target());
+    if renderer.target != target.key() {
+        renderer.set_target(gpu, target.key());
+    }
 
     // ── 3. Depth check ──────────────────────────────────────────────────
+    // The depth buffer matches the color target, which is smaller than the
+    // surface under a resolution scale. Projection still uses the surface
+    // size: same aspect, and screen-sized billboards keep their size.
+    let (tw, th) = frame.size;
+    renderer.resize_depth_if_needed(&gpu.device, tw, th);
     let (sw, sh) = gpu.surface_size();
-    renderer.resize_depth_if_needed(&gpu.device, sw, sh);
 
-    // ── 4. Collect lights ───────────────────────────────────────────────
-    let light_uniform = collect_lights(world);
-    gpu.queue
-        .write_buffer(&renderer.light_buffer, 0, bytemuck::cast_slice(&[light_uniform]));
+    // ── 4. Lights ───────────────────────────────────────────────────────
+    renderer.write_lights(gpu, &scene.lights);
 
     // ── 5. Camera ───────────────────────────────────────────────────────
-    let camera_uniform = collect_camera(world, (sw, sh));
+    let camera_uniform = collect_camera(scene.camera.as_ref(), (sw, sh));
     gpu.queue
         .write_buffer(&renderer.camera_buffer, 0, bytemuck::cast_slice(&[camera_uniform]));
 
     // ── 6. Collect draw calls ───────────────────────────────────────────
-    let draw_calls = collect_draw_calls(world);
-
-    // Write model uniforms to the dynamic buffer
-    let model_stride = if !draw_calls.is_empty() {
-        let stride = renderer.ensure_model_capacity(&gpu.device, draw_calls.len());
-        let mut model_data = vec![0u8; stride as usize * draw_calls.len()];
-        for (i, call) in draw_calls.iter().enumerate() {
+    let billboard_view = collect_billboard_view(scene.camera.as_ref(), (sw, sh));
+    let bounds = |mesh| mesh_store.get(mesh).bounds;
+    let culling = Culling {
+        frustum: Frustum::from_view_proj(&glam::Mat4::from_cols_array_2d(&camera_uniform.view_proj)),
+        bounds: &bounds,
+    };
+    let draw_calls = collect_draw_calls(&scene.meshes, billboard_view.as_ref(), Some(&culling));
+    let soft_draws = collect_soft_particles(&scene.soft_particles, billboard_view.as_ref());
+
+    // Write model uniforms to the dynamic buffer: meshes first, then soft
+    // particles.
+    let model_count = draw_calls.len() + soft_draws.len();
+    let model_stride = if model_count > 0 {
+        let stride = renderer.ensure_model_capacity(&gpu.device, model_count);
+        let mut model_data = vec![0u8; stride as usize * model_count];
+        let models = draw_calls
+            .iter()
+            .map(|call| &call.model_uniform)
+            .chain(soft_draws.iter().map(|draw| &draw.model_uniform));
+        for (i, model) in models.enumerate() {
             let offset = i * stride as usize;
-            let bytes = bytemuck::bytes_of(&call.model_uniform);
+            let bytes = bytemuck::bytes_of(model);
             model_data[offset..offset + bytes.len()].copy_from_slice(bytes);
         }
         gpu.queue.write_buffer(&renderer.model_buffer, 0, &model_data);
@@ -136,33 +211,104 @@ pub(crate) fn render_meshes_3d(world: &mut World, frame: &mut FrameContext<'_>)
         &draw_calls,
     );
 
+    // ── 7b. Depth prepass ───────────────────────────────────────────────
+    let depth_prepass =
+        scene.camera.as_ref().is_some_and(|cam| cam.depth_prepass) && !draw_calls.is_empty();
+
+    if depth_prepass {
+        let mut prepass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("3d depth prepass"),
+            color_attachments: &[],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &renderer.depth_texture,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        prepass.set_pipeline(&renderer.prepass_pipeline);
+        prepass.set_bind_group(0, &renderer.camera_bind_group, &[]);
+        let opaque = draw_calls.iter().enumerate().filter(|(_, call)| call.alpha_mode == AlphaMode::Opaque);
+        for (i, call) in opaque {
+            let dynamic_offset = i as u32 * model_stride;
+            prepass.set_bind_group(1, &renderer.model_bind_group, &[dynamic_offset]);
+
+            let gpu_mesh = mesh_store.get(call.mesh);
+            prepass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+            prepass.set_index_buffer(gpu_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            prepass.draw_indexed(0..gpu_mesh.index_count, 0, 0..1);
+        }
+    }
+
+    // ── 7c. Pipeline variants ───────────────────────────────────────────
+    // Meshes with extra attributes, a material shader, or texture maps
+    // beyond the base color (shader defs) need their own pipeline; build any
+    // missing ones before the pass borrows the renderer.
+    let material_shaders = world.get_resource_mut::<MaterialShaders>();
+    for shader in material_shaders.map(|shaders| shaders.take_changed()).unwrap_or_default() {
+        renderer.forget_material_shader(shader);
+    }
+    let transparency = scene.camera.as_ref().map(|cam| cam.transparency).unwrap_or_default();
+    let pipeline_keys: Vec<PipelineKey> = draw_calls
+        .iter()
+        .map(|call| {
+            let output = color_output(call.alpha_mode, call.shader, transparency);
+            PipelineKey {
+                shader: call.shader,
+                attributes: mesh_store.get(call.mesh).attributes,
+                prepassed: depth_prepass && output == ColorOutput::Opaque,
+                output,
+                defs: PbrDefs::of(&call.textures),
+            }
+        })
+        .collect();
+    let material_shaders = world.get_resource::<MaterialShaders>();
+    for key in &pipeline_keys {
+        renderer.prepare_pipeline(gpu, *key, material_shaders);
+    }
+
+    // ── 7d. Skybox ──────────────────────────────────────────────────────
+    let clear = scene.camera.as_ref().map(|cam| cam.clear).unwrap_or_default();
+    let sky_drawn = clear != CameraClear::Load
+        && scene
+            .camera
+            .as_ref()
+            .is_some_and(|cam| render_skybox(world, frame, &target, cam));
+
     // ── 8. Render pass ──────────────────────────────────────────────────
-    let clear_color = world
-        .get_resource::<ClearColor>()
-        .copied()
-        .unwrap_or_default();
+    let color_load = if sky_drawn {
+        wgpu::LoadOp::Load
+    } else {
+        camera_load_op(clear, world)
+    };
+    let depth_load = if depth_prepass {
+        wgpu::LoadOp::Load
+    } else {
+        wgpu::LoadOp::Clear(1.0)
+    };
+    let mesh_draws = MeshDraws {
+        renderer: &renderer,
+        mesh_store: &mesh_store,
+        draw_calls: &draw_calls,
+        pipeline_keys: &pipeline_keys,
+        material_bind_groups: &material_bind_groups,
+        model_stride,
+        debug_markers: frame.debug_markers,
+    };
 
     {
         let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("3d render pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: &frame.view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: clear_color.0[0],
-                        g: clear_color.0[1],
-                        b: clear_color.0[2],
-                        a: clear_color.0[3],
-                    }),
-                    store: wgpu::StoreOp::Store,
-                },
-                depth_slice: None,
-            })],
+            color_attachments: &[Some(target.attachment(color_load))],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                 view: &renderer.depth_texture,
                 depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(1.0),
+                    load: depth_load,
                     store: wgpu::StoreOp::Store,
                 }),
                 stencil_ops: None,
@@ -171,88 +317,155 @@ pub(crate) fn render_meshes_3d(world: &mut World, frame: &mut FrameContext<'_>)
             occlusion_query_set: None,
         });
 
-        if !draw_calls.is_empty() {
-            render_pass.set_pipeline(&renderer.pipeline);
-            render_pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
-            render_pass.set_bind_group(1, &renderer.light_bind_group, &[]);
-
-            let mut current_material_idx: Option<usize> = None;
-
-            for (i, call) in draw_calls.iter().enumerate() {
-                // Bind material group 2 only when it changes
-                let mat_idx = material_bind_groups
-                    .iter()
-                    .position(|m| m.draw_indices.contains(&i))
-                    .unwrap_or(0);
-
-                if current_material_idx != Some(mat_idx) {
-                    render_pass.set_bind_group(
-                        2,
-                        &material_bind_groups[mat_idx].bind_group,
-                        &[],
-                    );
-                    current_material_idx = Some(mat_idx);
-                }
-
-                // Bind model group 3 with dynamic offset
-                let dynamic_offset = i as u32 * model_stride;
-                render_pass.set_bind_group(3, &renderer.model_bind_group, &[dynamic_offset]);
-
-                // Bind mesh buffers and draw
-                let gpu_mesh = mesh_store.get(call.mesh);
-                render_pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
-                render_pass.set_index_buffer(gpu_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..gpu_mesh.index_count, 0, 0..1);
+        // Opaque, then sorted transparent (already far → near).
+        mesh_draws.draw(&mut render_pass, |output| output != ColorOutput::Accumulated);
+    }
+
+    // ── 8a. Weighted-blended transparency ───────────────────────────────
+    if pipeline_keys.iter().any(|key| key.output == ColorOutput::Accumulated) {
+        if world.get_resource::<OitRenderer>().is_none_or(|oit| oit.target != target.key()) {
+            world.insert_resource(OitRenderer::new(gpu, frame.size, target.key()));
+        }
+        if let Some(mut oit) = world.resource_remove::<OitRenderer>() {
+            oit.resize_if_needed(&gpu.device, frame.size);
+            {
+                let mut accumulation = oit.begin_accumulation(&mut frame.encoder, &renderer.depth_texture);
+                mesh_draws.draw(&mut accumulation, |output| output == ColorOutput::Accumulated);
             }
+            oit.composite(&mut frame.encoder, &target);
+            world.insert_resource(oit);
         }
     }
 
-    // ── 8b. Debug wireframes ────────────────────────────────────────────
-    #[cfg(feature = "physics3d")]
+    // ── 8b. Soft particles ──────────────────────────────────────────────
+    if !soft_draws.is_empty() {
+        if world
+            .get_resource::<SoftParticleRenderer>()
+            .is_none_or(|soft| soft.target != target.key())
+        {
+            let soft_renderer = SoftParticleRenderer::new(&gpu.device, &renderer);
+            world.insert_resource(soft_renderer);
+        }
+        if let Some(soft_renderer) = world.resource_remove::<SoftParticleRenderer>() {
+            render_soft_particles(
+                scene.camera.as_ref(),
+                frame,
+                &target,
+                &renderer,
+                &soft_renderer,
+                &mesh_store,
+                &texture_store,
+                &soft_draws,
+                draw_calls.len(),
+                model_stride,
+            );
+            world.insert_resource(soft_renderer);
+        }
+    }
+
+    // ── 8c. Emitter particles ───────────────────────────────────────────
+    if let Some(view) = &billboard_view
+        && !scene.particles.is_empty()
     {
-        use super::debug_wireframe::{DebugColliders3d, DebugWireframeRenderer, render_debug_wireframes_3d};
-        use crate::physics3d::Collider3d;
-
-        if world.has_resource::<DebugColliders3d>() {
-            // Lazy-init the debug renderer
-            if !world.has_resource::<DebugWireframeRenderer>() {
-                let dbg_renderer = DebugWireframeRenderer::new(
-                    &gpu.device,
-                    gpu.surface_format(),
-                    &renderer.camera_bind_group_layout,
-                );
-                world.insert_resource(dbg_renderer);
-            }
+        if world
+            .get_resource::<ParticleRenderer3d>()
+            .is_none_or(|particles| particles.target != target.key())
+        {
+            let particle_renderer =
+                ParticleRenderer3d::new(&gpu.device, target.key(), &renderer.camera_bind_group_layout);
+            world.insert_resource(particle_renderer);
+        }
+        render_particles_3d(
+            &mut frame.encoder,
+            &target,
+            gpu,
+            &renderer,
+            world.resource::<ParticleRenderer3d>(),
+            &texture_store,
+            &scene.particles,
+            view,
+        );
+    }
 
-            // Collect collider poses from ECS components directly
-            let mut poses = Vec::new();
-            world.query::<(&Collider3d, &crate::math::Transform)>(|_entity, (coll, tf)| {
-                poses.push((tf.translation, tf.rotation, coll.shape));
-            });
+    // ── 8d. Debug wireframes ────────────────────────────────────────────
+    #[cfg(feature = "physics3d")]
+    if let Some(colliders) = &scene.colliders {
+        use super::debug_wireframe::{DebugWireframeRenderer, render_debug_wireframes_3d};
+
+        // Lazy-init the debug renderer, rebuilding it if the target changed
+        if world
+            .get_resource::<DebugWireframeRenderer>()
+            .is_none_or(|r| r.target != target.key())
+        {
+            let dbg_renderer =
+                DebugWireframeRenderer::new(&gpu.device, target.key(), &renderer.camera_bind_group_layout);
+            world.insert_resource(dbg_renderer);
+        }
 
-            if let Some(mut dbg_renderer) = world.resource_remove::<DebugWireframeRenderer>() {
-                if let Some(debug_config) = world.resource_remove::<DebugColliders3d>() {
-                    render_debug_wireframes_3d(
-                        &mut frame.encoder,
-                        &frame.view,
-                        gpu,
-                        &renderer,
-                        &mut dbg_renderer,
-                        &debug_config,
-                        &poses,
-                    );
-                    world.insert_resource(debug_config);
-                }
-                world.insert_resource(dbg_renderer);
-            }
+        render_debug_wireframes_3d(
+            &mut frame.encoder,
+            &target,
+            gpu,
+            &renderer,
+            world.resource_mut::<DebugWireframeRenderer>(),
+            &colliders.config,
+            &colliders.poses,
+        );
+    }
+
+    // ── 8e. World-space text ────────────────────────────────────────────
+    #[cfg(feature = "render2d")]
+    if let Some(view) = &billboard_view
+        && !scene.labels.is_empty()
+    {
+        use super::text3d::{collect_labels, render_text_3d, Text3dRenderer};
+        use crate::render2d::font::FontStore;
+        use crate::render2d::texture::TextureStore;
+
+        if world
+            .get_resource::<Text3dRenderer>()
+            .is_none_or(|text| text.target != target.key())
+        {
+            let text_renderer =
+                Text3dRenderer::new(&gpu.device, target.key(), &renderer.camera_bind_group_layout);
+            world.insert_resource(text_renderer);
         }
+
+        // Fonts (and their atlases) only exist once `load_font` has run.
+        if let Some(font_store) = world.resource_remove::<FontStore>() {
+            let labels = collect_labels(&scene.labels, &font_store, view);
+            let text_renderer = world.resource::<Text3dRenderer>();
+            let sprite_textures = world.resource::<TextureStore>();
+            render_text_3d(
+                &mut frame.encoder,
+                &target,
+                gpu,
+                &renderer,
+                text_renderer,
+                sprite_textures,
+                labels,
+            );
+            world.insert_resource(font_store);
+        }
+    }
+
+    // ── 8f. Bloom and tone mapping ──────────────────────────────────────
+    if hdr.is_some() {
+        finish_hdr(world, frame);
     }
 
     // Update diagnostics render stats.
     #[cfg(feature = "diagnostics")]
     if let Some(stats) = world.get_resource_mut::<crate::diag::RenderStats>() {
-        stats.draw_calls = draw_calls.len() as u32;
-        stats.vertices = draw_calls.iter().map(|c| mesh_store.get(c.mesh).index_count).sum();
+        stats.draw_calls = model_count as u32;
+        stats.meshes_submitted = draw_calls.len() as u32;
+        stats.meshes_culled = (scene.meshes.len() - draw_calls.len()) as u32;
+        stats.vertices = draw_calls
+            .iter()
+            .map(|c| c.mesh)
+            .chain(soft_draws.iter().map(|d| d.mesh))
+            .map(|mesh| mesh_store.get(mesh).index_count)
+            .sum();
         stats.textures_loaded = texture_store.entries.len() as u32;
     }
 
@@ -262,6 +475,73 @@ pub(crate) fn render_meshes_3d(world: &mut World, frame: &mut FrameContext<'_>)
     world.insert_resource(texture_store);
 }
 
+/// Everything needed to replay the mesh draw calls into a render pass.
+struct MeshDraws<'a> {
+    renderer: &'a MeshRenderer,
+    mesh_store: &'a MeshStore,
+    draw_calls: &'a [DrawCall],
+    pipeline_keys: &'a [PipelineKey],
+    material_bind_groups: &'a [MaterialBindGroupEntry],
+    model_stride: u32,
+    debug_markers: bool,
+}
+
+impl MeshDraws<'_> {
+    /// Draw every call whose pipeline output passes `include`, in order.
+    fn draw(&self, render_pass: &mut wgpu::RenderPass<'_>, include: impl Fn(ColorOutput) -> bool) {
+        if !self.pipeline_keys.iter().any(|key| include(key.output)) {
+            return;
+        }
+        render_pass.set_bind_group(0, &self.renderer.camera_bind_group, &[]);
+        render_pass.set_bind_group(1, &self.renderer.light_bind_group, &[]);
+
+        let mut current_material_idx: Option<usize> = None;
+        let mut current_pipeline: Option<PipelineKey> = None;
+
+        for (i, call) in self.draw_calls.iter().enumerate() {
+            let key = self.pipeline_keys[i];
+            if !include(key.output) {
+                continue;
+            }
+
+            // Switch pipelines only when the shader, mesh layout or output
+            // changes
+            if current_pipeline != Some(key) {
+                render_pass.set_pipeline(self.renderer.pipeline_for(key));
+                current_pipeline = Some(key);
+            }
+
+            // Bind material group 2 only when it changes
+            let mat_idx = self
+                .material_bind_groups
+                .iter()
+                .position(|m| m.draw_indices.contains(&i))
+                .unwrap_or(0);
+
+            if current_material_idx != Some(mat_idx) {
+                render_pass.set_bind_group(2, &self.material_bind_groups[mat_idx].bind_group, &[]);
+                current_material_idx = Some(mat_idx);
+            }
+
+            // Bind model group 3 with dynamic offset
+            let dynamic_offset = i as u32 * self.model_stride;
+            render_pass.set_bind_group(3, &self.renderer.model_bind_group, &[dynamic_offset]);
+
+            // Bind mesh buffers and draw
+            let gpu_mesh = self.mesh_store.get(call.mesh);
+            if self.debug_markers {
+                render_pass.insert_debug_marker(&format!("mesh {}", call.mesh.0));
+            }
+            render_pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+            if let Some(attributes) = &gpu_mesh.attribute_buffer {
+                render_pass.set_vertex_buffer(1, attributes.slice(..));
+            }
+            render_pass.set_index_buffer(gpu_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..gpu_mesh.index_count, 0, 0..1);
+        }
+    }
+}
+
 /// A material bind group (group 2) shared by one or more draw calls.
 struct MaterialBindGroupEntry {
     bind_group: wgpu::BindGroup,
@@ -269,7 +549,7 @@ struct MaterialBindGroupEntry {
 }
 
 /// Create material bind groups, deduplicating when consecutive draw calls
-/// share the same material parameters and texture.
+/// share the same material parameters and textures.
 fn create_material_bind_groups(
     gpu: &GpuContext,
     renderer: &MeshRenderer,
@@ -288,9 +568,9 @@ fn create_material_bind_groups(
             let last_idx = last.draw_indices[0];
             same_material(
                 &draw_calls[last_idx].material_uniform,
-                draw_calls[last_idx].base_color_texture,
+                &draw_calls[last_idx].textures,
                 &call.material_uniform,
-                call.base_color_texture,
+                &call.textures,
             )
         });
 
@@ -306,29 +586,14 @@ fn create_material_bind_groups(
                         usage: wgpu::BufferUsages::UNIFORM,
                     });
 
-            let tex_handle = call
-                .base_color_texture
-                .unwrap_or(texture_store.default_handle());
-            let tex_entry = texture_store.get(tex_handle);
-
-            let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("3d material bind group"),
-                layout: &renderer.material_bind_group_layout,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: mat_buffer.as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: wgpu::BindingResource::TextureView(&tex_entry.view),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: wgpu::BindingResource::Sampler(&renderer.sampler),
-                    },
-                ],
-            });
+            let bind_group = create_material_bind_group(
+                gpu,
+                renderer,
+                texture_store,
+                &mat_buffer,
+                &call.textures,
+                "3d material bind group",
+            );
 
             groups.push(MaterialBindGroupEntry {
                 bind_group,
@@ -340,16 +605,45 @@ fn create_material_bind_groups(
     groups
 }
 
-/// Check if two materials are identical (same uniform data and texture).
+/// A material bind group (group 2): the uniform `buffer` at binding 0,
+/// then a texture and sampler per map, in [`MaterialTextures::bound`] order.
+pub(crate) fn create_material_bind_group(
+    gpu: &GpuContext,
+    renderer: &MeshRenderer,
+    texture_store: &TextureStore3d,
+    buffer: &wgpu::Buffer,
+    textures: &MaterialTextures,
+    label: &str,
+) -> wgpu::BindGroup {
+    let maps = textures.bound().map(|handle| texture_store.get(handle));
+    let mut entries = vec![wgpu::BindGroupEntry {
+        binding: 0,
+        resource: buffer.as_entire_binding(),
+    }];
+    for (i, map) in maps.iter().enumerate() {
+        let binding = 1 + 2 * i as u32;
+        entries.push(wgpu::BindGroupEntry {
+            binding,
+            resource: wgpu::BindingResource::TextureView(&map.view),
+        });
+        entries.push(wgpu::BindGroupEntry {
+            binding: binding + 1,
+            resource: wgpu::BindingResource::Sampler(&map.sampler),
+        });
+    }
+    gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some(label),
+        layout: &renderer.material_bind_group_layout,
+        entries: &entries,
+    })
+}
+
+/// Check if two materials are identical (same uniform data and textures).
 fn same_material(
     a_uniform: &MaterialUniform,
-    a_tex: Option<TextureHandle3d>,
+    a_textures: &MaterialTextures,
     b_uniform: &MaterialUniform,
-    b_tex: Option<TextureHandle3d>,
+    b_textures: &MaterialTextures,
 ) -> bool {
-    a_tex == b_tex
-        && a_uniform.base_color == b_uniform.base_color
-        && a_uniform.metallic == b_uniform.metallic
-        && a_uniform.roughness == b_uniform.roughness
-        && a_uniform.emissive == b_uniform.emissive
+    a_textures == b_textures && bytemuck::bytes_of(a_uniform) == bytemuck::bytes_of(b_uniform)
 }
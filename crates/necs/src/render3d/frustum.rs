@@ -0,0 +1,151 @@
+//! # Frustum Culling — Skipping Meshes the Camera Can't See
+//!
+//! The camera sees a truncated pyramid, the *frustum*, bounded by six
+//! planes. Anything entirely outside one of them can't reach the screen, so
+//! its draw call is wasted work: vertex shading, and for the GPU, triangles
+//! that get clipped away anyway.
+//!
+//! ```text
+//!            far plane
+//!        ┌───────────────┐
+//!         \      ●      /      ● inside: drawn
+//!   ■      \           /       ■ outside the left plane: culled
+//!           \    ◆────/─┐      ◆ straddling: drawn (clipped by the GPU)
+//!            \   └───/──┘
+//!             └─────┘ near plane
+//!               cam
+//! ```
+//!
+//! Every mesh gets an axis-aligned bounding box ([`Aabb`]) when it is
+//! uploaded. Each frame, the box is moved into world space with the
+//! entity's model matrix and tested against the planes, which come straight
+//! out of the view-projection matrix (Gribb–Hartmann): a point is inside
+//! when `-w ≤ x ≤ w`, `-w ≤ y ≤ w` and `0 ≤ z ≤ w` in clip space, and each
+//! inequality is a plane in world space.
+//!
+//! The test is conservative: a box can be outside the frustum near a corner
+//! yet cross every single plane, and is then drawn anyway. The bounds come
+//! from the uploaded vertices, so a [material shader](super::material_shader)
+//! that pushes vertices far outside them can pop at the screen edges.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Per-renderer bounds culled on worker threads, plus optional
+//!   baked occlusion culling.
+//! - **Bevy**: `Aabb` component computed from the mesh, culled by
+//!   `check_visibility` into `ViewVisibility`.
+//! - **Our approach**: Bevy's test, done while building draw calls; counts
+//!   land in [`RenderStats`](crate::diag::RenderStats).
+
+use glam::{Mat4, Vec3, Vec4};
+
+/// An axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    /// The smallest box around `points`. Empty input gives a box at the
+    /// origin.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Self {
+        let mut points = points.into_iter();
+        let Some(first) = points.next() else {
+            return Self { min: Vec3::ZERO, max: Vec3::ZERO };
+        };
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Self { min, max }
+    }
+
+    /// The box around this one after `matrix` (which may rotate, scale, or
+    /// shear it).
+    pub fn transformed(&self, matrix: &Mat4) -> Self {
+        let center = matrix.transform_point3((self.min + self.max) * 0.5);
+        let half = (self.max - self.min) * 0.5;
+        // Each world axis gathers the absolute contribution of every local
+        // axis.
+        let extent = matrix.x_axis.truncate().abs() * half.x
+            + matrix.y_axis.truncate().abs() * half.y
+            + matrix.z_axis.truncate().abs() * half.z;
+        Self {
+            min: center - extent,
+            max: center + extent,
+        }
+    }
+}
+
+/// The six planes of a camera's view volume, each as `(normal, distance)`
+/// with the normal pointing inward.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Frustum {
+    planes: [Vec4; 6],
+}
+
+impl Frustum {
+    /// Extract the planes from a view-projection matrix with wgpu's 0..1
+    /// clip depth.
+    pub fn from_view_proj(view_proj: &Mat4) -> Self {
+        let (x, y, z, w) = (view_proj.row(0), view_proj.row(1), view_proj.row(2), view_proj.row(3));
+        Self {
+            planes: [w + x, w - x, w + y, w - y, z, w - z],
+        }
+    }
+
+    /// Whether any part of `aabb` (in world space) may be visible.
+    pub fn intersects(&self, aabb: &Aabb) -> bool {
+        self.planes.iter().all(|plane| {
+            // The box corner furthest along the plane normal.
+            let normal = plane.truncate();
+            let corner = Vec3::select(normal.cmpge(Vec3::ZERO), aabb.max, aabb.min);
+            normal.dot(corner) + plane.w >= 0.0
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn culls_boxes_outside_the_view() {
+        // Camera at +10 Z looking down -Z, 90° fov, far plane at 50.
+        let projection = Mat4::perspective_rh(90f32.to_radians(), 1.0, 0.1, 50.0);
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let frustum = Frustum::from_view_proj(&(projection * view));
+        let unit = Aabb::from_points([Vec3::splat(-0.5), Vec3::splat(0.5)]);
+        let at = |x, y, z| unit.transformed(&Mat4::from_translation(Vec3::new(x, y, z)));
+
+        assert!(frustum.intersects(&at(0.0, 0.0, 0.0)));
+        assert!(!frustum.intersects(&at(0.0, 0.0, 20.0)), "behind the camera");
+        assert!(!frustum.intersects(&at(15.0, 0.0, 0.0)), "off to the side");
+        assert!(!frustum.intersects(&at(0.0, 0.0, -60.0)), "past the far plane");
+        // Centered outside the view but reaching into it.
+        assert!(frustum.intersects(&at(10.3, 0.0, 0.0)));
+
+        // A rotated box grows to contain its corners.
+        let spun = unit.transformed(&Mat4::from_rotation_z(45f32.to_radians()));
+        assert!((spun.max.x - 0.5f32.hypot(0.5)).abs() < 1e-5);
+        assert!((spun.max.z - 0.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn orthographic_view_keeps_its_size_with_depth() {
+        use crate::render3d::Projection3d;
+
+        // 10 units tall on a 2:1 viewport, so 20 wide, at every depth.
+        let projection = Projection3d::orthographic_height(10.0).matrix((200, 100), 0.1, 50.0);
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        let view_proj = projection * view;
+        for z in [5.0, -30.0] {
+            let top_right = view_proj.project_point3(Vec3::new(10.0, 5.0, z));
+            assert!(top_right.truncate().abs_diff_eq(glam::Vec2::ONE, 1e-5));
+        }
+
+        let frustum = Frustum::from_view_proj(&view_proj);
+        let unit = Aabb::from_points([Vec3::splat(-0.5), Vec3::splat(0.5)]);
+        let at = |x, y, z| unit.transformed(&Mat4::from_translation(Vec3::new(x, y, z)));
+        assert!(frustum.intersects(&at(9.0, 0.0, -30.0)), "no perspective narrowing");
+        assert!(!frustum.intersects(&at(11.0, 0.0, 0.0)), "off to the side");
+    }
+}
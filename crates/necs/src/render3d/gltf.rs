@@ -17,6 +17,8 @@
 //! - **Positions**: `POSITION` accessor → `MeshVertex.position`
 //! - **Normals**: `NORMAL` accessor → `MeshVertex.normal`
 //! - **UVs**: `TEXCOORD_0` accessor → `MeshVertex.uv` (default [0,0] if absent)
+//! - **Vertex colors**: `COLOR_0` accessor → `VertexAttributes.colors`
+//! - **Second UV set**: `TEXCOORD_1` accessor → `VertexAttributes.uv1`
 //! - **Indices**: Index accessor → `u32` index buffer
 //!
 //! For each material:
@@ -24,14 +26,20 @@
 //! - **metallic_factor** → `Material.metallic`
 //! - **roughness_factor** → `Material.roughness`
 //! - **emissive_factor** → `Material.emissive`
-//! - **base_color_texture** → loaded into TextureStore3d if present
+//! - **alpha_mode** → `Material.alpha_mode` (`BLEND` only; `MASK` draws opaque)
+//! - **base_color_texture**, **normal_texture** (and its `scale`),
+//!   **metallic_roughness_texture**, **emissive_texture**,
+//!   **occlusion_texture** (and its `strength`) → loaded into
+//!   TextureStore3d if present, each image once per file: base color and
+//!   emissive as sRGB, the others as linear data
 //!
 //! ## What We Skip (For Now)
 //!
 //! - Animations, skins, morph targets
 //! - Scene hierarchy (all meshes placed at origin)
-//! - Normal maps, occlusion maps, emissive maps
-//! - Multiple UV sets
+//! - Texture coordinate sets other than the first, texture transforms and
+//!   samplers (all maps use `TEXCOORD_0`, linear filtering and repeat)
+//! - Custom (`_NAME`) attributes — set those with `set_vertex_attributes`
 //!
 //! ## Comparison
 //!
@@ -39,15 +47,22 @@
 //!   morph targets, and async loading.
 //! - **three.js**: `GLTFLoader` returns a scene graph with all features.
 //! - **Our approach**: Minimal extraction — just geometry and basic PBR
-//!   materials. The caller spawns entities manually.
+//!   materials. The caller spawns entities manually, either from
+//!   `load_gltf`'s return value or in `load_gltf_async`'s callback once the
+//!   file has been parsed in the background.
 
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::asset::AssetServer;
 use crate::ecs::World;
 use crate::render::GpuContext;
+use crate::render::pipelined::finish_render;
 
-use super::mesh::MeshStore;
-use super::texture::TextureStore3d;
+use super::mesh::{MeshStore, VertexAttributes};
+use super::texture::{MaterialTextures, TextureHandle3d, TextureStore3d};
 use super::vertex::MeshVertex;
-use super::{Material, MeshHandle};
+use super::{AlphaMode, Material, MeshHandle};
 
 /// Load a glTF/GLB file and return (MeshHandle, Material) pairs.
 ///
@@ -66,6 +81,10 @@ use super::{Material, MeshHandle};
 /// }
 /// ```
 pub fn load_gltf(world: &mut World, path: &str) -> Vec<(MeshHandle, Material)> {
+    let path = crate::launch::resolve_asset_path(world, path);
+    let path = path.as_ref();
+    // The render thread may hold the stores for the frame in flight.
+    finish_render(world);
     let mut mesh_store = world
         .resource_remove::<MeshStore>()
         .expect("MeshStore not initialized — render at least one frame first");
@@ -74,23 +93,79 @@ pub fn load_gltf(world: &mut World, path: &str) -> Vec<(MeshHandle, Material)> {
         .expect("TextureStore3d not initialized");
     let gpu = world.resource::<GpuContext>();
 
-    let result = load_gltf_inner(gpu, &mut mesh_store, &mut texture_store, path);
+    let imported = gltf::import(path)
+        .unwrap_or_else(|e| panic!("Failed to load glTF '{path}': {e}"));
+    let result = upload_gltf(gpu, &mut mesh_store, &mut texture_store, path, imported);
 
     world.insert_resource(mesh_store);
     world.insert_resource(texture_store);
     result
 }
 
-fn load_gltf_inner(
+/// Load a glTF/GLB file in the background.
+///
+/// Parsing and image decoding run on a loader thread; once done,
+/// `on_loaded` runs on the main thread at the start of a frame with the same
+/// parts [`load_gltf`] returns, ready to spawn. Track progress with
+/// [`AssetServer::load_state`]. Without an [`AssetServer`] the file is
+/// loaded synchronously and `on_loaded` runs right away.
+///
+/// # Example
+/// ```ignore
+/// load_gltf_async(world, "assets/helmet.glb", |world, parts| {
+///     for (mesh, material) in parts {
+///         world.spawn((Transform::default(), Mesh3d { mesh }, material));
+///     }
+/// });
+/// ```
+pub fn load_gltf_async(
+    world: &mut World,
+    path: &str,
+    on_loaded: impl FnOnce(&mut World, Vec<(MeshHandle, Material)>) + Send + 'static,
+) {
+    if !world.has_resource::<AssetServer>() {
+        let parts = load_gltf(world, path);
+        on_loaded(world, parts);
+        return;
+    }
+    let path = crate::launch::resolve_asset_path(world, path).into_owned();
+
+    world.resource_mut::<AssetServer>().load_async(
+        PathBuf::from(&path),
+        |path| gltf::import(path).map_err(|e| e.to_string()),
+        move |world, imported| {
+            let (Some(mut mesh_store), Some(mut texture_store)) = (
+                world.resource_remove::<MeshStore>(),
+                world.resource_remove::<TextureStore3d>(),
+            ) else {
+                log::warn!("glTF '{path}' loaded before the 3D renderer started; dropping it");
+                return;
+            };
+            let gpu = world.resource::<GpuContext>();
+            let parts = upload_gltf(gpu, &mut mesh_store, &mut texture_store, &path, imported);
+            world.insert_resource(mesh_store);
+            world.insert_resource(texture_store);
+            on_loaded(world, parts);
+        },
+    );
+}
+
+/// The output of `gltf::import`: document, buffers and decoded images.
+type Imported = (gltf::Document, Vec<gltf::buffer::Data>, Vec<gltf::image::Data>);
+
+/// Build meshes and upload textures from an imported file.
+fn upload_gltf(
     gpu: &GpuContext,
     mesh_store: &mut MeshStore,
     texture_store: &mut TextureStore3d,
     path: &str,
+    (document, buffers, images): Imported,
 ) -> Vec<(MeshHandle, Material)> {
-    let (document, buffers, images) = gltf::import(path)
-        .unwrap_or_else(|e| panic!("Failed to load glTF '{path}': {e}"));
 
     let mut results = Vec::new();
+    // (Image index, sRGB) → handle, so maps shared between materials upload
+    // once per color space.
+    let mut uploaded: HashMap<(usize, bool), Option<TextureHandle3d>> = HashMap::new();
 
     for mesh in document.meshes() {
         for primitive in mesh.primitives() {
@@ -132,56 +207,62 @@ fn load_gltf_inner(
                 .into_u32()
                 .collect();
 
-            let mesh_handle = mesh_store.upload(gpu, &vertices, &indices);
+            // Optional extras: vertex colors (often baked AO) and a second UV set
+            let mut attributes = VertexAttributes::new();
+            if let Some(colors) = reader.read_colors(0) {
+                attributes = attributes.colors(colors.into_rgba_f32().collect());
+            }
+            if let Some(uv1) = reader.read_tex_coords(1) {
+                attributes = attributes.uv1(uv1.into_f32().collect());
+            }
+
+            let mesh_handle = mesh_store.upload_with(gpu, &vertices, &indices, attributes);
 
             // Extract material
             let material = {
-                let pbr = primitive.material().pbr_metallic_roughness();
+                let source = primitive.material();
+                let pbr = source.pbr_metallic_roughness();
                 let base_color_factor = pbr.base_color_factor();
                 let metallic = pbr.metallic_factor();
                 let roughness = pbr.roughness_factor();
-                let emissive = primitive.material().emissive_factor();
-
-                // Base color texture
-                let base_color_texture = pbr.base_color_texture().and_then(|info| {
-                    let tex = info.texture();
-                    let source = tex.source();
-                    let image = &images[source.index()];
-                    match image.format {
-                        gltf::image::Format::R8G8B8A8 => {
-                            Some(texture_store.upload_rgba8(
-                                gpu,
-                                &format!("{path}:tex{}", source.index()),
-                                image.width,
-                                image.height,
-                                &image.pixels,
-                            ))
-                        }
-                        gltf::image::Format::R8G8B8 => {
-                            // Convert RGB to RGBA
-                            let mut rgba = Vec::with_capacity(image.pixels.len() / 3 * 4);
-                            for chunk in image.pixels.chunks(3) {
-                                rgba.extend_from_slice(chunk);
-                                rgba.push(255);
-                            }
-                            Some(texture_store.upload_rgba8(
-                                gpu,
-                                &format!("{path}:tex{}", source.index()),
-                                image.width,
-                                image.height,
-                                &rgba,
-                            ))
-                        }
-                        _ => None, // Skip unsupported formats
-                    }
+                let emissive = source.emissive_factor();
+                // MASK (alpha cutoff) isn't supported; those draw opaque.
+                let alpha_mode = match source.alpha_mode() {
+                    gltf::material::AlphaMode::Blend => AlphaMode::Blend,
+                    _ => AlphaMode::Opaque,
+                };
+
+                // Texture maps
+                let [
+                    base_color_texture,
+                    normal_texture,
+                    metallic_roughness_texture,
+                    emissive_texture,
+                    ao_texture,
+                ] = texture_slots(&source).map(|slot| {
+                    let (index, srgb) = slot?;
+                    *uploaded.entry((index, srgb)).or_insert_with(|| {
+                        let label = format!("{path}:tex{index}");
+                        upload_image(gpu, texture_store, &label, &images[index], srgb)
+                    })
                 });
+                let normal = source.normal_texture();
+                let occlusion = source.occlusion_texture();
 
                 Material {
                     base_color: base_color_factor,
                     base_color_texture,
                     metallic,
                     roughness,
+                    normal_texture,
+                    normal_scale: normal.map_or(1.0, |info| info.scale()),
+                    metallic_roughness_texture,
                     emissive,
+                    emissive_texture,
+                    ao_texture,
+                    ao_strength: occlusion.map_or(1.0, |info| info.strength()),
+                    shader: None,
+                    alpha_mode,
                 }
             };
 
@@ -191,3 +272,75 @@ fn load_gltf_inner(
 
     results
 }
+
+/// The image each map of `material` samples and whether it is a color map
+/// (sRGB), in [`MaterialTextures::bound`] order.
+fn texture_slots(material: &gltf::Material<'_>) -> [Option<(usize, bool)>; 5] {
+    let pbr = material.pbr_metallic_roughness();
+    let images = [
+        pbr.base_color_texture().map(|info| info.texture().source().index()),
+        material.normal_texture().map(|info| info.texture().source().index()),
+        pbr.metallic_roughness_texture().map(|info| info.texture().source().index()),
+        material.emissive_texture().map(|info| info.texture().source().index()),
+        material.occlusion_texture().map(|info| info.texture().source().index()),
+    ];
+    std::array::from_fn(|i| images[i].map(|index| (index, MaterialTextures::SRGB[i])))
+}
+
+/// Upload a decoded glTF image as RGBA8, as a color map (`srgb`) or a data
+/// map. Returns `None` (with a warning) for pixel formats other than 8-bit
+/// RGB and RGBA.
+fn upload_image(
+    gpu: &GpuContext,
+    texture_store: &mut TextureStore3d,
+    label: &str,
+    image: &gltf::image::Data,
+    srgb: bool,
+) -> Option<TextureHandle3d> {
+    match image.format {
+        gltf::image::Format::R8G8B8A8 => {
+            Some(texture_store.upload_rgba8(gpu, label, image.width, image.height, &image.pixels, srgb))
+        }
+        gltf::image::Format::R8G8B8 => {
+            // Convert RGB to RGBA
+            let mut rgba = Vec::with_capacity(image.pixels.len() / 3 * 4);
+            for chunk in image.pixels.chunks(3) {
+                rgba.extend_from_slice(chunk);
+                rgba.push(255);
+            }
+            Some(texture_store.upload_rgba8(gpu, label, image.width, image.height, &rgba, srgb))
+        }
+        format => {
+            log::warn!("{label}: unsupported glTF image format {format:?}; skipping the texture");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_come_from_their_slots_with_the_right_color_space() {
+        // Image 0 is both the base color and the emissive map; the data maps
+        // each have their own, except metallic-roughness which is unset.
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "images": [{ "uri": "color.png" }, { "uri": "normal.png" }, { "uri": "ao.png" }],
+            "textures": [{ "source": 0 }, { "source": 1 }, { "source": 2 }],
+            "materials": [{
+                "pbrMetallicRoughness": { "baseColorTexture": { "index": 0 } },
+                "normalTexture": { "index": 1, "scale": 0.5 },
+                "emissiveTexture": { "index": 0 },
+                "occlusionTexture": { "index": 2 }
+            }]
+        }"#;
+        let document = gltf::Gltf::from_slice(json.as_bytes()).unwrap().document;
+        let material = document.materials().next().unwrap();
+        assert_eq!(
+            texture_slots(&material),
+            [Some((0, true)), Some((1, false)), None, Some((0, true)), Some((2, false))]
+        );
+    }
+}
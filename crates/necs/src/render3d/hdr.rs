@@ -0,0 +1,624 @@
+//! # HDR — Tone Mapping and Bloom
+//!
+//! Lighting adds up: a white surface under two lights, or an emissive
+//! material at `[4.0, 2.0, 0.5]`, is brighter than 1.0. An 8-bit surface
+//! clips all of that to the same flat white. With a [`PostProcess`]
+//! resource the 3D scene instead renders into a floating-point
+//! (`Rgba16Float`) target that keeps the real values, and a final pass maps
+//! them onto the display:
+//!
+//! ```text
+//!   3D passes ──► HDR target (Rgba16Float, resolved if MSAA)
+//!                    │
+//!                    ├─► prefilter ── keep what's above `threshold`
+//!                    │      ▼
+//!                    │   bloom mip 0 ─► 1 ─► ... ─► n   downsample (blur)
+//!                    │   bloom mip 0 ◄─ 1 ◄─ ... ◄─ n   upsample, add
+//!                    │      │
+//!                    ▼      ▼
+//!   tone map:  (scene × exposure + bloom × intensity) ──► ACES / Reinhard
+//!                    │
+//!                    ▼
+//!   frame target ──► 2D, UI, grading as usual
+//! ```
+//!
+//! **Bloom** is the glow around bright things: the bright parts are blurred
+//! by halving them down a short mip chain and adding each level back onto
+//! the one above it, which spreads light wide at little cost. Only values
+//! above [`Bloom::threshold`] take part, so emissive materials brighter than
+//! 1.0 glow while ordinary lit surfaces don't.
+//!
+//! **Tone mapping** squeezes the unbounded range into 0–1.
+//! [`Tonemapping::Aces`] is the filmic curve most engines default to;
+//! [`Tonemapping::Reinhard`] is flatter but never shifts hues.
+//!
+//! Only the 3D scene is HDR. 2D sprites and the UI draw on top of the tone
+//! mapped result as before, and [`ColorGrading`](crate::render::ColorGrading)
+//! grades the final frame. The camera's clear color is tone mapped along
+//! with the scene. Bloom can be switched off for speed with
+//! [`PostEffects::bloom`](crate::render::PostEffects::bloom).
+//!
+//! ## Comparison
+//!
+//! - **Unity** (URP): HDR toggle on the pipeline asset; Tonemapping and
+//!   Bloom volume overrides with threshold and intensity.
+//! - **Bevy**: `Camera { hdr: true }`, a `Tonemapping` component (ACES,
+//!   Reinhard, AgX, ...) and a `Bloom` component using the same
+//!   downsample/upsample chain.
+//! - **Godot**: `Environment` resource with tonemap mode, exposure and
+//!   glow levels.
+//! - **Our approach**: One resource holding Bevy's settings; inserting it
+//!   turns HDR on, removing it turns it off.
+
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+use crate::render::pass::FrameContext;
+use crate::render::settings::graphics_settings;
+
+/// Format of the HDR scene target and the bloom chain.
+pub(crate) const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Most bloom mip levels; each halves the size again.
+const MAX_BLOOM_MIPS: u32 = 6;
+
+// ── Public types ────────────────────────────────────────────────────────
+
+/// How HDR colors are mapped onto the display's 0–1 range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Tonemapping {
+    /// Filmic ACES fit: soft shoulder, highlights fade to white.
+    #[default]
+    Aces,
+    /// `x / (1 + x)` per channel: never clips, keeps hues, less contrast.
+    Reinhard,
+    /// No curve; everything above 1.0 clips, like rendering without HDR.
+    Clamp,
+}
+
+/// Glow around bright parts of the 3D scene. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bloom {
+    /// Brightness (after exposure) where bloom starts. Above 1.0 only
+    /// emissive materials and strong highlights glow.
+    pub threshold: f32,
+    /// Width of the soft transition around `threshold`; 0 is a hard cut.
+    pub knee: f32,
+    /// How strongly the blurred glow is added back.
+    pub intensity: f32,
+}
+
+impl Default for Bloom {
+    fn default() -> Self {
+        Self {
+            threshold: 1.0,
+            knee: 0.5,
+            intensity: 0.3,
+        }
+    }
+}
+
+/// Resource: render the 3D scene in HDR, with tone mapping and optional
+/// bloom. See the [module docs](self).
+///
+/// ```ignore
+/// Game::new("Neon")
+///     .resource(PostProcess::default().exposure(1.2).bloom(Bloom {
+///         intensity: 0.5,
+///         ..Default::default()
+///     }))
+///     .run();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PostProcess {
+    /// Curve from HDR to display colors.
+    pub tonemapping: Tonemapping,
+    /// Multiplier on scene colors before bloom and tone mapping.
+    pub exposure: f32,
+    /// Bloom settings; `None` turns bloom off.
+    pub bloom: Option<Bloom>,
+}
+
+impl Default for PostProcess {
+    fn default() -> Self {
+        Self {
+            tonemapping: Tonemapping::default(),
+            exposure: 1.0,
+            bloom: Some(Bloom::default()),
+        }
+    }
+}
+
+impl PostProcess {
+    /// Set the tone mapping curve (builder pattern).
+    pub fn tonemapping(mut self, tonemapping: Tonemapping) -> Self {
+        self.tonemapping = tonemapping;
+        self
+    }
+
+    /// Set the exposure multiplier (builder pattern).
+    pub fn exposure(mut self, exposure: f32) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Turn bloom on with these settings (builder pattern).
+    pub fn bloom(mut self, bloom: Bloom) -> Self {
+        self.bloom = Some(bloom);
+        self
+    }
+
+    /// Turn bloom off (builder pattern).
+    pub fn without_bloom(mut self) -> Self {
+        self.bloom = None;
+        self
+    }
+}
+
+// ── GPU side ────────────────────────────────────────────────────────────
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+struct HdrParams {
+    threshold: f32,
+    knee: f32,
+    intensity: f32,
+    exposure: f32,
+    tonemapping: u32,
+    _pad: [u32; 3],
+}
+
+impl HdrParams {
+    /// Shader parameters for `settings`, with `bloom` already filtered by
+    /// the graphics settings. Negative and NaN inputs are zeroed.
+    fn new(settings: &PostProcess, bloom: Option<Bloom>) -> Self {
+        let non_negative = |value: f32| if value > 0.0 { value } else { 0.0 };
+        let bloom = bloom.unwrap_or(Bloom {
+            intensity: 0.0,
+            ..Default::default()
+        });
+        Self {
+            threshold: non_negative(bloom.threshold),
+            knee: non_negative(bloom.knee),
+            intensity: non_negative(bloom.intensity),
+            exposure: non_negative(settings.exposure),
+            tonemapping: match settings.tonemapping {
+                Tonemapping::Aces => 0,
+                Tonemapping::Reinhard => 1,
+                Tonemapping::Clamp => 2,
+            },
+            _pad: [0; 3],
+        }
+    }
+}
+
+/// HDR scene target, bloom chain and the post-process pipelines. Created
+/// the first frame a [`PostProcess`] resource exists.
+pub(crate) struct HdrRenderer {
+    /// Single-sampled scene target, read by bloom and tone mapping.
+    scene: wgpu::TextureView,
+    /// Multisampled scene target resolving into `scene`, under MSAA.
+    msaa: Option<wgpu::TextureView>,
+    size: (u32, u32),
+    samples: u32,
+    /// One view per bloom mip level, largest first. Empty until bloom is
+    /// first used at this size.
+    bloom_mips: Vec<wgpu::TextureView>,
+    /// Stand-in bloom texture while bloom is off.
+    black: wgpu::TextureView,
+    sampler: wgpu::Sampler,
+    params_buffer: wgpu::Buffer,
+    shader: wgpu::ShaderModule,
+    bloom_layout: wgpu::BindGroupLayout,
+    tonemap_layout: wgpu::BindGroupLayout,
+    prefilter: wgpu::RenderPipeline,
+    downsample: wgpu::RenderPipeline,
+    upsample: wgpu::RenderPipeline,
+    /// Tone mapping pipeline and the frame target it was built for.
+    tonemap: (TargetKey, wgpu::RenderPipeline),
+}
+
+impl HdrRenderer {
+    fn new(gpu: &GpuContext, target: TargetKey) -> Self {
+        let device = &gpu.device;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("hdr shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("hdr.wgsl").into()),
+        });
+
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let common = [
+            texture_entry(0),
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ];
+        let bloom_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("bloom layout"),
+            entries: &common,
+        });
+        let tonemap_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("tonemap layout"),
+            entries: &[common[0], common[1], common[2], texture_entry(3)],
+        });
+
+        let additive = wgpu::BlendComponent {
+            src_factor: wgpu::BlendFactor::One,
+            dst_factor: wgpu::BlendFactor::One,
+            operation: wgpu::BlendOperation::Add,
+        };
+        let bloom_pipeline = |entry_point, blend| {
+            create_pipeline(device, &shader, &bloom_layout, entry_point, (HDR_FORMAT, 1), blend)
+        };
+        let prefilter = bloom_pipeline("fs_prefilter", None);
+        let downsample = bloom_pipeline("fs_downsample", None);
+        let upsample = bloom_pipeline(
+            "fs_upsample",
+            Some(wgpu::BlendState {
+                color: additive,
+                alpha: additive,
+            }),
+        );
+        let tonemap = create_pipeline(device, &shader, &tonemap_layout, "fs_tonemap", target, None);
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("hdr sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("hdr params"),
+            size: std::mem::size_of::<HdrParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let black = create_texture(gpu, "bloom stand-in", (1, 1), 1, 1)
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        Self {
+            scene: black.clone(),
+            msaa: None,
+            size: (0, 0),
+            samples: 1,
+            bloom_mips: Vec::new(),
+            black,
+            sampler,
+            params_buffer,
+            shader,
+            bloom_layout,
+            tonemap_layout,
+            prefilter,
+            downsample,
+            upsample,
+            tonemap: (target, tonemap),
+        }
+    }
+
+    /// Recreate the scene target if the frame's size or sample count
+    /// changed. The bloom chain follows on its next use.
+    fn resize_if_needed(&mut self, gpu: &GpuContext, size: (u32, u32), samples: u32) {
+        if (self.size, self.samples) == (size, samples) {
+            return;
+        }
+        let view = |texture: wgpu::Texture| texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.scene = view(create_texture(gpu, "hdr scene target", size, 1, 1));
+        self.msaa =
+            (samples > 1).then(|| view(create_texture(gpu, "hdr scene target (msaa)", size, 1, samples)));
+        self.size = size;
+        self.samples = samples;
+        self.bloom_mips.clear();
+    }
+
+    /// Create the bloom chain for the current size if it doesn't exist.
+    fn ensure_bloom_chain(&mut self, gpu: &GpuContext) {
+        if !self.bloom_mips.is_empty() {
+            return;
+        }
+        let (width, height) = self.size;
+        let size = ((width / 2).max(1), (height / 2).max(1));
+        let mips = bloom_mip_count(size);
+        let texture = create_texture(gpu, "bloom chain", size, mips, 1);
+        self.bloom_mips = (0..mips)
+            .map(|mip| {
+                texture.create_view(&wgpu::TextureViewDescriptor {
+                    label: Some("bloom mip"),
+                    base_mip_level: mip,
+                    mip_level_count: Some(1),
+                    ..Default::default()
+                })
+            })
+            .collect();
+    }
+
+    /// Rebuild the tone mapping pipeline if the frame target changed.
+    fn prepare_tonemap(&mut self, gpu: &GpuContext, target: TargetKey) {
+        if self.tonemap.0 != target {
+            let pipeline = create_pipeline(
+                &gpu.device,
+                &self.shader,
+                &self.tonemap_layout,
+                "fs_tonemap",
+                target,
+                None,
+            );
+            self.tonemap = (target, pipeline);
+        }
+    }
+
+    /// Bind `source` for a bloom pass, or with `bloom` for tone mapping.
+    fn bind_group(
+        &self,
+        gpu: &GpuContext,
+        source: &wgpu::TextureView,
+        bloom: Option<&wgpu::TextureView>,
+    ) -> wgpu::BindGroup {
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(source),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&self.sampler),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: self.params_buffer.as_entire_binding(),
+            },
+        ];
+        if let Some(bloom) = bloom {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 3,
+                resource: wgpu::BindingResource::TextureView(bloom),
+            });
+        }
+        gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("hdr bind group"),
+            layout: if bloom.is_some() { &self.tonemap_layout } else { &self.bloom_layout },
+            entries: &entries,
+        })
+    }
+
+    /// Prefilter the scene into bloom mip 0, blur down the chain, and add
+    /// the levels back up.
+    fn run_bloom(&self, gpu: &GpuContext, encoder: &mut wgpu::CommandEncoder) {
+        let mips = &self.bloom_mips;
+        fullscreen_pass(
+            encoder,
+            "bloom prefilter",
+            &mips[0],
+            &self.prefilter,
+            &self.bind_group(gpu, &self.scene, None),
+        );
+        for i in 1..mips.len() {
+            let bind_group = self.bind_group(gpu, &mips[i - 1], None);
+            fullscreen_pass(encoder, "bloom downsample", &mips[i], &self.downsample, &bind_group);
+        }
+        for i in (1..mips.len()).rev() {
+            let bind_group = self.bind_group(gpu, &mips[i], None);
+            let mut pass = begin_pass(encoder, "bloom upsample", &mips[i - 1], wgpu::LoadOp::Load);
+            pass.set_pipeline(&self.upsample);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+/// Redirect the 3D scene into the HDR target, if a [`PostProcess`]
+/// resource asks for it. Returns the target for the 3D passes; pass it on
+/// to [`finish_hdr`] when they're done.
+pub(crate) fn begin_hdr(world: &mut World, frame: &FrameContext<'_>) -> Option<ColorTarget> {
+    if !world.has_resource::<PostProcess>() {
+        return None;
+    }
+    if !world.has_resource::<HdrRenderer>() {
+        world.insert_resource(HdrRenderer::new(frame.gpu, frame.target().key()));
+    }
+    let hdr = world.resource_mut::<HdrRenderer>();
+    hdr.resize_if_needed(frame.gpu, frame.size, frame.samples);
+    Some(ColorTarget {
+        view: hdr.scene.clone(),
+        msaa: hdr.msaa.clone(),
+        format: HDR_FORMAT,
+        samples: hdr.samples,
+    })
+}
+
+/// Run bloom and tone map the HDR scene onto the frame's target.
+pub(crate) fn finish_hdr(world: &mut World, frame: &mut FrameContext<'_>) {
+    let Some(mut hdr) = world.resource_remove::<HdrRenderer>() else {
+        return;
+    };
+    let gpu = frame.gpu;
+    let settings = world.get_resource::<PostProcess>().copied().unwrap_or_default();
+    let bloom = settings.bloom.filter(|_| graphics_settings(world).post_effects.bloom);
+    gpu.queue
+        .write_buffer(&hdr.params_buffer, 0, bytemuck::bytes_of(&HdrParams::new(&settings, bloom)));
+
+    if bloom.is_some() {
+        hdr.ensure_bloom_chain(gpu);
+        hdr.run_bloom(gpu, &mut frame.encoder);
+    }
+    let target = frame.target();
+    hdr.prepare_tonemap(gpu, target.key());
+    let glow = if bloom.is_some() { &hdr.bloom_mips[0] } else { &hdr.black };
+    let bind_group = hdr.bind_group(gpu, &hdr.scene, Some(glow));
+    {
+        let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("tonemap pass"),
+            color_attachments: &[Some(target.attachment(wgpu::LoadOp::Clear(wgpu::Color::BLACK)))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&hdr.tonemap.1);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+    world.insert_resource(hdr);
+}
+
+/// Number of bloom mip levels for a chain starting at `size`: halve until
+/// the short side is about 4 pixels, at most [`MAX_BLOOM_MIPS`].
+fn bloom_mip_count((width, height): (u32, u32)) -> u32 {
+    let short = width.min(height).max(1);
+    (u32::BITS - short.leading_zeros()).saturating_sub(2).clamp(1, MAX_BLOOM_MIPS)
+}
+
+/// An HDR-format texture; single-sampled ones can also be sampled.
+fn create_texture(
+    gpu: &GpuContext,
+    label: &str,
+    (width, height): (u32, u32),
+    mips: u32,
+    samples: u32,
+) -> wgpu::Texture {
+    gpu.device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: mips,
+        sample_count: samples,
+        dimension: wgpu::TextureDimension::D2,
+        format: HDR_FORMAT,
+        usage: if samples > 1 {
+            wgpu::TextureUsages::RENDER_ATTACHMENT
+        } else {
+            wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING
+        },
+        view_formats: &[],
+    })
+}
+
+/// A fullscreen-triangle pipeline running `entry_point` of the HDR shader.
+fn create_pipeline(
+    device: &wgpu::Device,
+    shader: &wgpu::ShaderModule,
+    layout: &wgpu::BindGroupLayout,
+    entry_point: &str,
+    (format, samples): TargetKey,
+    blend: Option<wgpu::BlendState>,
+) -> wgpu::RenderPipeline {
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("hdr pipeline layout"),
+        bind_group_layouts: &[layout],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(entry_point),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(entry_point),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: multisample_state(samples),
+        multiview: None,
+        cache: None,
+    })
+}
+
+fn begin_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    label: &str,
+    view: &wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+            depth_slice: None,
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    })
+}
+
+/// Draw `pipeline` over all of `view`, replacing its contents.
+fn fullscreen_pass(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    view: &wgpu::TextureView,
+    pipeline: &wgpu::RenderPipeline,
+    bind_group: &wgpu::BindGroup,
+) {
+    let mut pass = begin_pass(encoder, label, view, wgpu::LoadOp::Clear(wgpu::Color::BLACK));
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.draw(0..3, 0..1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bloom_chain_stops_near_four_pixels() {
+        assert_eq!(bloom_mip_count((640, 360)), MAX_BLOOM_MIPS);
+        assert_eq!(bloom_mip_count((64, 16)), 3); // 16 → 8 → 4
+        assert_eq!(bloom_mip_count((8, 8)), 2);
+        assert_eq!(bloom_mip_count((1, 1)), 1);
+    }
+
+    #[test]
+    fn params_zero_bloom_when_off_and_reject_negatives() {
+        let settings = PostProcess::default().tonemapping(Tonemapping::Reinhard).exposure(-2.0);
+        let params = HdrParams::new(&settings, None);
+        assert_eq!((params.intensity, params.exposure, params.tonemapping), (0.0, 0.0, 1));
+
+        let bloom = Bloom {
+            intensity: f32::NAN,
+            ..Default::default()
+        };
+        let params = HdrParams::new(&PostProcess::default(), Some(bloom));
+        assert_eq!((params.threshold, params.intensity, params.exposure), (1.0, 0.0, 1.0));
+    }
+}
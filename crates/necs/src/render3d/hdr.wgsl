@@ -0,0 +1,114 @@
+// HDR post-processing for the 3D scene: a bloom chain and tone mapping.
+//
+// Bloom: the bright parts of the scene (prefilter) are blurred by halving
+// them down a mip chain and adding each level back onto the one above it
+// on the way up. Tone mapping then adds the blur to the scene and squeezes
+// the unbounded HDR range into the 0..1 a display can show.
+
+struct Params {
+    threshold: f32,
+    knee: f32,
+    intensity: f32,
+    exposure: f32,
+    // 0 = ACES, 1 = Reinhard, 2 = clamp
+    tonemapping: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var source: texture_2d<f32>;
+@group(0) @binding(1) var source_sampler: sampler;
+@group(0) @binding(2) var<uniform> params: Params;
+// Tone mapping only: the top of the bloom chain.
+@group(0) @binding(3) var bloom: texture_2d<f32>;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// One oversized triangle covers the whole target — no vertex buffer needed.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let xy = vec2(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4(xy, 0.0, 1.0);
+    out.uv = vec2(xy.x * 0.5 + 0.5, 0.5 - xy.y * 0.5);
+    return out;
+}
+
+fn tap(uv: vec2<f32>, offset: vec2<f32>) -> vec3<f32> {
+    let texel = 1.0 / vec2<f32>(textureDimensions(source));
+    return textureSample(source, source_sampler, uv + offset * texel).rgb;
+}
+
+// ── Bloom ───────────────────────────────────────────────────────────────
+
+// Keep what is brighter than the threshold, with a soft knee so the cutoff
+// doesn't show as a hard edge around highlights.
+@fragment
+fn fs_prefilter(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Clamp stray huge values so one pixel can't flood the chain.
+    let color = min(tap(in.uv, vec2(0.0)) * params.exposure, vec3(1000.0));
+    let brightness = max(color.r, max(color.g, color.b));
+    let soft = clamp(brightness - params.threshold + params.knee, 0.0, 2.0 * params.knee);
+    let curve = soft * soft / (4.0 * params.knee + 1e-5);
+    let weight = max(curve, brightness - params.threshold) / max(brightness, 1e-5);
+    return vec4(color * weight, 1.0);
+}
+
+// Half the size: the center plus four diagonal taps between texels, each a
+// bilinear average of four.
+@fragment
+fn fs_downsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    var sum = tap(in.uv, vec2(0.0)) * 4.0;
+    sum += tap(in.uv, vec2(-1.0, -1.0));
+    sum += tap(in.uv, vec2(1.0, -1.0));
+    sum += tap(in.uv, vec2(-1.0, 1.0));
+    sum += tap(in.uv, vec2(1.0, 1.0));
+    return vec4(sum / 8.0, 1.0);
+}
+
+// Double the size with a 3×3 tent filter; blended additively onto the
+// larger level.
+@fragment
+fn fs_upsample(in: VertexOutput) -> @location(0) vec4<f32> {
+    var sum = tap(in.uv, vec2(0.0)) * 4.0;
+    sum += (tap(in.uv, vec2(-1.0, 0.0)) + tap(in.uv, vec2(1.0, 0.0))) * 2.0;
+    sum += (tap(in.uv, vec2(0.0, -1.0)) + tap(in.uv, vec2(0.0, 1.0))) * 2.0;
+    sum += tap(in.uv, vec2(-1.0, -1.0)) + tap(in.uv, vec2(1.0, -1.0));
+    sum += tap(in.uv, vec2(-1.0, 1.0)) + tap(in.uv, vec2(1.0, 1.0));
+    return vec4(sum / 16.0, 1.0);
+}
+
+// ── Tone mapping ────────────────────────────────────────────────────────
+
+// Narkowicz's fit of the ACES filmic curve: a gentle toe, a long shoulder,
+// highlights desaturate as they approach white.
+fn aces(x: vec3<f32>) -> vec3<f32> {
+    return clamp((x * (2.51 * x + 0.03)) / (x * (2.43 * x + 0.59) + 0.14), vec3(0.0), vec3(1.0));
+}
+
+// x / (1 + x): never clips, keeps hues, flatter than ACES.
+fn reinhard(x: vec3<f32>) -> vec3<f32> {
+    return x / (1.0 + x);
+}
+
+@fragment
+fn fs_tonemap(in: VertexOutput) -> @location(0) vec4<f32> {
+    let scene = textureSample(source, source_sampler, in.uv).rgb * params.exposure;
+    let glow = textureSample(bloom, source_sampler, in.uv).rgb * params.intensity;
+    let color = max(scene + glow, vec3(0.0));
+    switch params.tonemapping {
+        case 0u: {
+            return vec4(aces(color), 1.0);
+        }
+        case 1u: {
+            return vec4(reinhard(color), 1.0);
+        }
+        default: {
+            return vec4(clamp(color, vec3(0.0), vec3(1.0)), 1.0);
+        }
+    }
+}
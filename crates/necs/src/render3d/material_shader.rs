@@ -0,0 +1,169 @@
+//! # Material Shaders — Custom WGSL for 3D Materials
+//!
+//! The built-in PBR shader covers most surfaces, but effects like wind sway,
+//! dissolve, or lightmaps need their own shader code — and usually their own
+//! per-vertex data. A [`MaterialShader`] swaps the PBR shader for a WGSL file
+//! on any [`Material`](super::Material) that names it:
+//!
+//! ```text
+//!   Material { shader: Some(wind), .. }     Mesh (COLOR | CUSTOM)
+//!                 │                                │
+//!                 └──────────┐      ┌──────────────┘
+//!                            ▼      ▼
+//!         pipeline cache: (shader, attributes, prepassed, output, defs)
+//!                            │
+//!              hit ──► reuse │ miss ──► build: slot 0 + slot-1 layout
+//!                            │          for exactly these attributes
+//!                            ▼
+//!                        draw_indexed
+//! ```
+//!
+//! ## Writing One
+//!
+//! A material shader is a complete WGSL module with `vs_main` and `fs_main`
+//! entry points. It sees the same bind groups as the PBR shader (camera,
+//! lights, material, model — copy `render3d/shader.wgsl` as a starting
+//! point) and may read any of the optional vertex attributes:
+//!
+//! ```text
+//! @vertex
+//! fn vs_main(@location(0) position: vec3<f32>, @location(1) normal: vec3<f32>,
+//!            @location(2) uv: vec2<f32>, @location(5) sway: vec4<f32>) -> VertexOutput
+//! ```
+//!
+//! Each mesh gets a pipeline whose vertex layout matches the attributes it
+//! carries (see [`MeshAttributes`](super::vertex::MeshAttributes)). If the
+//! shader reads an attribute the mesh lacks, or fails to compile, the mesh
+//! is drawn with the PBR shader instead and a warning is logged once.
+//! Material shader files are hot-reloaded.
+//!
+//! The source is run through the [shader def](crate::render::shader_defs)
+//! preprocessor first, with the same defs as the PBR shader — one per
+//! texture map the material sets (`NORMAL_MAP`, `METALLIC_ROUGHNESS_MAP`,
+//! `EMISSIVE_MAP`, `OCCLUSION_MAP`) — so `#ifdef` blocks work here too.
+//!
+//! With [`AlphaMode::Blend`](super::AlphaMode::Blend), the alpha `fs_main`
+//! returns is blended over the scene, always in sorted order (see
+//! [`transparency`](super::transparency)).
+//!
+//! Under a [`PostProcess`](super::PostProcess) the scene is tone mapped
+//! afterwards, so colors above 1.0 are kept. A shader that declares
+//! `override hdr_output: bool = false;` (as the PBR one does) sees it set
+//! to `true` then, and can skip its own tone mapping.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Shader Graph or hand-written shaders; mesh channels (colors,
+//!   UV2–UV8) are read through declared vertex inputs.
+//! - **Bevy**: The `Material` trait supplies shader paths and specializes
+//!   the pipeline on the mesh's `MeshVertexBufferLayout`.
+//! - **Godot**: `ShaderMaterial` with a shading language exposing `COLOR`,
+//!   `UV2` and `CUSTOM0`–`CUSTOM3` built-ins.
+//! - **Our approach**: Bevy-style specialization keyed on a small attribute
+//!   bitset, with plain WGSL files and fixed shader locations.
+
+use std::path::{Path, PathBuf};
+
+use crate::asset::AssetServer;
+use crate::ecs::World;
+use crate::render::pipelined::finish_render;
+
+/// Handle to a WGSL shader loaded with [`load_material_shader`]. Set it as
+/// [`Material::shader`](super::Material::shader).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MaterialShader(pub(crate) usize);
+
+/// One loaded shader file.
+struct ShaderSource {
+    path: PathBuf,
+    source: String,
+}
+
+/// Resource: the source of every loaded material shader. The renderer
+/// compiles them on first use and again after a reload.
+#[derive(Default)]
+pub(crate) struct MaterialShaders {
+    shaders: Vec<ShaderSource>,
+    /// Reloaded since the renderer last looked.
+    changed: Vec<MaterialShader>,
+}
+
+impl MaterialShaders {
+    pub fn source(&self, shader: MaterialShader) -> Option<&str> {
+        self.shaders.get(shader.0).map(|s| s.source.as_str())
+    }
+
+    pub fn path(&self, shader: MaterialShader) -> Option<&Path> {
+        self.shaders.get(shader.0).map(|s| s.path.as_path())
+    }
+
+    /// Shaders reloaded since the last call.
+    pub fn take_changed(&mut self) -> Vec<MaterialShader> {
+        std::mem::take(&mut self.changed)
+    }
+
+    fn find(&self, path: &Path) -> Option<MaterialShader> {
+        self.shaders.iter().position(|s| s.path == path).map(MaterialShader)
+    }
+}
+
+/// Load a WGSL material shader. Loading the same file twice returns the
+/// same handle. Returns `None` (with a warning) if the file can't be read;
+/// compile errors are reported when a mesh first draws with it.
+///
+/// ```ignore
+/// let wind = load_material_shader(world, "shaders/wind.wgsl").unwrap();
+/// world.spawn((Transform::default(), Mesh3d { mesh: tree }, Material {
+///     shader: Some(wind),
+///     ..Default::default()
+/// }));
+/// ```
+pub fn load_material_shader(world: &mut World, path: &str) -> Option<MaterialShader> {
+    let resolved = PathBuf::from(crate::launch::resolve_asset_path(world, path).into_owned());
+    let key = resolved.canonicalize().unwrap_or(resolved.clone());
+    finish_render(world);
+    if let Some(shaders) = world.get_resource::<MaterialShaders>()
+        && let Some(existing) = shaders.find(&key)
+    {
+        return Some(existing);
+    }
+
+    let source = match std::fs::read_to_string(&resolved) {
+        Ok(source) => source,
+        Err(e) => {
+            log::warn!("Failed to load material shader '{path}': {e}");
+            return None;
+        }
+    };
+    if !world.has_resource::<MaterialShaders>() {
+        world.insert_resource(MaterialShaders::default());
+    }
+    let shaders = world.resource_mut::<MaterialShaders>();
+    let handle = MaterialShader(shaders.shaders.len());
+    shaders.shaders.push(ShaderSource { path: key, source });
+
+    if let Some(server) = world.get_resource_mut::<AssetServer>() {
+        server.watch_custom(resolved, reload_material_shader);
+    }
+    Some(handle)
+}
+
+/// Hot-reload callback for watched material shaders. The renderer drops
+/// the old pipelines and compiles the new source next frame.
+fn reload_material_shader(world: &mut World, path: &Path) {
+    let key = path.canonicalize().unwrap_or(path.to_path_buf());
+    let Some(shaders) = world.get_resource_mut::<MaterialShaders>() else {
+        return;
+    };
+    let Some(handle) = shaders.find(&key) else {
+        return;
+    };
+    match std::fs::read_to_string(path) {
+        Ok(source) => {
+            shaders.shaders[handle.0].source = source;
+            shaders.changed.push(handle);
+            crate::render::shader_diff::request_shader_diff(world, path);
+        }
+        Err(e) => log::warn!("Keeping previous material shader, reload of '{}' failed: {e}", path.display()),
+    }
+}
@@ -28,7 +28,16 @@
 //!
 //! Each uploaded mesh becomes a [`GpuMesh`]: a vertex buffer, an index buffer,
 //! and an index count. During rendering, the draw call binds these buffers
-//! and issues `draw_indexed(0..index_count)`.
+//! and issues `draw_indexed(0..index_count)`. Its local bounding box is kept
+//! too, for [frustum culling](super::frustum).
+//!
+//! ## Extra Attributes
+//!
+//! A mesh may also carry [`VertexAttributes`] — vertex colors, a second UV
+//! set, custom data — uploaded to a second buffer. glTF files bring their
+//! `COLOR_0` and `TEXCOORD_1` along; [`set_vertex_attributes`] attaches data
+//! from code. See [`vertex`](super::vertex#optional-attributes) for the
+//! layout.
 //!
 //! ## Comparison
 //!
@@ -37,33 +46,142 @@
 //! - **three.js**: `BufferGeometry` holds typed arrays that are uploaded
 //!   lazily when first rendered.
 
+use std::collections::HashSet;
+
 use wgpu::util::DeviceExt;
 
+use super::frustum::Aabb;
 use super::shapes;
-use super::vertex::MeshVertex;
+use super::vertex::{MeshAttributes, MeshVertex, interleave_extras};
+use crate::asset_gc::FreedEntry;
+use crate::ecs::World;
 use crate::render::GpuContext;
+use crate::render::pipelined::finish_render;
 
 /// Handle to a mesh in the [`MeshStore`]. Lightweight and `Copy`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct MeshHandle(pub(crate) usize);
 
+/// Optional per-vertex data for a mesh, one entry per vertex in each
+/// stream. See [`MeshAttributes`] for the shader locations.
+///
+/// ```ignore
+/// // Per-vertex sway weights for a wind shader, on a mesh from glTF.
+/// let (tree, material) = load_gltf(world, "tree.glb").remove(0);
+/// set_vertex_attributes(world, tree, VertexAttributes::new().custom(weights));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VertexAttributes {
+    /// Linear RGBA colors.
+    pub colors: Option<Vec<[f32; 4]>>,
+    /// Second UV set.
+    pub uv1: Option<Vec<[f32; 2]>>,
+    /// Application data for material shaders.
+    pub custom: Option<Vec<[f32; 4]>>,
+}
+
+impl VertexAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set vertex colors (builder pattern).
+    pub fn colors(mut self, colors: Vec<[f32; 4]>) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Set the second UV set (builder pattern).
+    pub fn uv1(mut self, uv1: Vec<[f32; 2]>) -> Self {
+        self.uv1 = Some(uv1);
+        self
+    }
+
+    /// Set custom data (builder pattern).
+    pub fn custom(mut self, custom: Vec<[f32; 4]>) -> Self {
+        self.custom = Some(custom);
+        self
+    }
+
+    /// Which attributes are present.
+    pub fn kinds(&self) -> MeshAttributes {
+        let mut kinds = MeshAttributes::NONE;
+        if self.colors.is_some() {
+            kinds |= MeshAttributes::COLOR;
+        }
+        if self.uv1.is_some() {
+            kinds |= MeshAttributes::UV1;
+        }
+        if self.custom.is_some() {
+            kinds |= MeshAttributes::CUSTOM;
+        }
+        kinds
+    }
+
+    /// Drop (with a warning) streams that don't have one entry per vertex.
+    fn matching(mut self, vertex_count: usize) -> Self {
+        fn check<T>(stream: &mut Option<Vec<T>>, name: &str, vertex_count: usize) {
+            if let Some(values) = stream
+                && values.len() != vertex_count
+            {
+                log::warn!(
+                    "Ignoring {name} vertex attribute: {} values for {vertex_count} vertices",
+                    values.len()
+                );
+                *stream = None;
+            }
+        }
+        check(&mut self.colors, "color", vertex_count);
+        check(&mut self.uv1, "uv1", vertex_count);
+        check(&mut self.custom, "custom", vertex_count);
+        self
+    }
+
+    /// The streams interleaved for the slot-1 buffer.
+    fn interleave(&self, vertex_count: usize) -> Vec<f32> {
+        let mut streams: Vec<(&[f32], usize)> = Vec::new();
+        if let Some(colors) = &self.colors {
+            streams.push((colors.as_flattened(), 4));
+        }
+        if let Some(uv1) = &self.uv1 {
+            streams.push((uv1.as_flattened(), 2));
+        }
+        if let Some(custom) = &self.custom {
+            streams.push((custom.as_flattened(), 4));
+        }
+        interleave_extras(&streams, vertex_count)
+    }
+}
+
 /// A mesh that has been uploaded to GPU buffers.
 pub(crate) struct GpuMesh {
     pub vertex_buffer: wgpu::Buffer,
     pub index_buffer: wgpu::Buffer,
     pub index_count: u32,
+    pub vertex_count: u32,
+    /// Bounds of the vertex positions, in mesh space.
+    pub bounds: Aabb,
+    /// Optional attributes in `attribute_buffer` (vertex slot 1).
+    pub attributes: MeshAttributes,
+    pub attribute_buffer: Option<wgpu::Buffer>,
 }
 
 /// Stores all uploaded meshes. Pre-populated with built-in primitives.
 pub(crate) struct MeshStore {
     meshes: Vec<GpuMesh>,
+    /// Handles whose buffers were released by [`free`](Self::free).
+    freed: HashSet<MeshHandle>,
 }
 
+/// Meshes below this index are the built-in primitives and never freed.
+const BUILTIN_MESHES: usize = 4;
+
 impl MeshStore {
     /// Create a new store and upload the built-in primitives.
     pub fn new(gpu: &GpuContext) -> Self {
         let mut store = Self {
             meshes: Vec::new(),
+            freed: HashSet::new(),
         };
 
         // Built-in primitives: cube(0), plane(1), sphere(2), cylinder(3)
@@ -84,6 +202,17 @@ impl MeshStore {
 
     /// Upload mesh data to the GPU and return a handle.
     pub fn upload(&mut self, gpu: &GpuContext, vertices: &[MeshVertex], indices: &[u32]) -> MeshHandle {
+        self.upload_with(gpu, vertices, indices, VertexAttributes::default())
+    }
+
+    /// Upload mesh data with optional attributes and return a handle.
+    pub fn upload_with(
+        &mut self,
+        gpu: &GpuContext,
+        vertices: &[MeshVertex],
+        indices: &[u32],
+        attributes: VertexAttributes,
+    ) -> MeshHandle {
         let vertex_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("mesh vertex buffer"),
             contents: bytemuck::cast_slice(vertices),
@@ -99,14 +228,84 @@ impl MeshStore {
             vertex_buffer,
             index_buffer,
             index_count: indices.len() as u32,
+            vertex_count: vertices.len() as u32,
+            bounds: Aabb::from_points(vertices.iter().map(|v| glam::Vec3::from(v.position))),
+            attributes: MeshAttributes::NONE,
+            attribute_buffer: None,
         });
+        self.set_attributes(gpu, handle, attributes);
         handle
     }
 
+    /// Replace the optional attributes of an uploaded mesh. Returns `false`
+    /// for built-in or freed meshes, which can't carry attributes.
+    pub fn set_attributes(&mut self, gpu: &GpuContext, handle: MeshHandle, attributes: VertexAttributes) -> bool {
+        if handle.0 < BUILTIN_MESHES {
+            return false;
+        }
+        let Some(mesh) = self.meshes.get_mut(handle.0).filter(|_| !self.freed.contains(&handle)) else {
+            return false;
+        };
+        let attributes = attributes.matching(mesh.vertex_count as usize);
+        mesh.attributes = attributes.kinds();
+        mesh.attribute_buffer = (!mesh.attributes.is_empty()).then(|| {
+            gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("mesh attribute buffer"),
+                contents: bytemuck::cast_slice(&attributes.interleave(mesh.vertex_count as usize)),
+                usage: wgpu::BufferUsages::VERTEX,
+            })
+        });
+        true
+    }
+
     /// Get the GPU mesh for a handle.
     pub fn get(&self, handle: MeshHandle) -> &GpuMesh {
         &self.meshes[handle.0]
     }
+
+    /// Handles [`free`](Self::free) would release: uploaded meshes that
+    /// aren't built in or already freed.
+    pub fn freeable(&self) -> impl Iterator<Item = MeshHandle> + '_ {
+        (BUILTIN_MESHES..self.meshes.len())
+            .map(MeshHandle)
+            .filter(|handle| !self.freed.contains(handle))
+    }
+
+    /// Release the buffers behind `handle`. The slot stays valid and draws
+    /// nothing. See [`asset_gc`](crate::asset_gc).
+    pub fn free(&mut self, handle: MeshHandle) -> Option<FreedEntry> {
+        if handle.0 < BUILTIN_MESHES || handle.0 >= self.meshes.len() || !self.freed.insert(handle) {
+            return None;
+        }
+        let cube = &self.meshes[0];
+        let (vertex_buffer, index_buffer) = (cube.vertex_buffer.clone(), cube.index_buffer.clone());
+        let mesh = &mut self.meshes[handle.0];
+        let bytes = mesh.vertex_buffer.size() + mesh.index_buffer.size();
+        let bytes = bytes + mesh.attribute_buffer.as_ref().map_or(0, |b| b.size());
+        mesh.vertex_buffer = vertex_buffer;
+        mesh.index_buffer = index_buffer;
+        mesh.index_count = 0;
+        mesh.attributes = MeshAttributes::NONE;
+        mesh.attribute_buffer = None;
+        Some(FreedEntry { bytes, path: None })
+    }
+}
+
+/// Attach optional per-vertex data to a mesh, replacing any it had. Each
+/// stream needs one entry per vertex; mismatched streams are dropped with a
+/// warning, as are attempts on built-in meshes.
+pub fn set_vertex_attributes(world: &mut World, mesh: MeshHandle, attributes: VertexAttributes) {
+    finish_render(world);
+    let Some(mut store) = world.resource_remove::<MeshStore>() else {
+        log::warn!("set_vertex_attributes: no meshes uploaded yet");
+        return;
+    };
+    if let Some(gpu) = world.get_resource::<GpuContext>()
+        && !store.set_attributes(gpu, mesh, attributes)
+    {
+        log::warn!("set_vertex_attributes: mesh {} is built in or freed", mesh.0);
+    }
+    world.insert_resource(store);
 }
 
 /// Well-known handle for the built-in cube mesh.
@@ -128,3 +327,19 @@ pub(crate) fn mesh_sphere() -> MeshHandle {
 pub(crate) fn mesh_cylinder() -> MeshHandle {
     MeshHandle(3)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mismatched_streams_are_dropped() {
+        let attributes = VertexAttributes::new()
+            .colors(vec![[1.0; 4]; 3])
+            .uv1(vec![[0.0; 2]; 2])
+            .custom(vec![[0.5; 4]; 3])
+            .matching(3);
+        assert_eq!(attributes.kinds(), MeshAttributes::COLOR | MeshAttributes::CUSTOM);
+        assert_eq!(attributes.interleave(3).len(), 3 * 8);
+    }
+}
@@ -1,7 +1,8 @@
 //! # Render3d — 3D PBR Mesh Rendering
 //!
 //! A physically-based 3D renderer that draws textured meshes with metallic-
-//! roughness materials, directional and point lights, and a perspective camera.
+//! roughness materials, directional, point and spot lights, and a perspective
+//! or orthographic camera.
 //! Built on the same patterns as the 2D sprite renderer: lazy initialization,
 //! extract/reinsert for borrow safety, and handle-based resource management.
 //!
@@ -12,21 +13,24 @@
 //!         │                                      │
 //!         ▼                                      ▼
 //!   ┌───────────────┐          ┌──────────────────────────────────┐
-//!   │ perspective   │          │       collect draw calls          │
-//!   │ projection ×  │          │  query entities, build model      │
+//!   │ Projection3d  │          │       collect draw calls          │
+//!   │ matrix ×      │          │  query entities, build model      │
 //!   │ inverse view  │          │  + normal matrices, sort by       │
 //!   └───────┬───────┘          │  material to minimize rebinds    │
 //!           │                  └──────────────┬───────────────────┘
 //!           │                                 │
 //!           │      DirectionalLight           │
 //!           │      PointLight × N             │
-//!           │      AmbientLight               │
+//!           │      SpotLight × N              │
+//!           │      AmbientLight, Skybox       │
 //!           │           │                     │
 //!           ▼           ▼                     ▼
 //!   ┌─────────────────────────────────────────────────────┐
+//!   │  Skybox pass (optional) — environment background     │
+//!   ├─────────────────────────────────────────────────────┤
 //!   │  GPU render pass                                     │
 //!   │  • bind groups 0+1 once (camera + lights)           │
-//!   │  • for each material: bind group 2 (material+tex)   │
+//!   │  • for each material: bind group 2 (params + maps)  │
 //!   │  • for each object: bind group 3 (model, dyn offset)│
 //!   │  • bind mesh buffers, draw_indexed                   │
 //!   │  • depth buffer for correct occlusion                │
@@ -54,8 +58,8 @@
 //! | Group | Content | Changes | Strategy |
 //! |-------|---------|---------|----------|
 //! | 0 | Camera VP + position | Once/frame | Single uniform buffer |
-//! | 1 | All lights | Once/frame | Single uniform buffer |
-//! | 2 | Material params + texture | Per material | Recreated per frame |
+//! | 1 | All lights | Once/frame | Uniform + point/spot storage buffers |
+//! | 2 | Material params + texture maps | Per material | Recreated per frame |
 //! | 3 | Model + normal matrices | Per object | Dynamic uniform buffer |
 //!
 //! Group 3 uses *dynamic offsets*: one large buffer holds all model matrices
@@ -68,54 +72,193 @@
 //!   clustered forward rendering for hundreds of lights. Far more complex.
 //! - **three.js**: `MeshStandardMaterial` implements the same PBR model.
 //!   WebGL/WebGPU backend handles bind groups automatically.
-//! - **Our approach**: Minimal forward renderer with fixed point light limit
-//!   (8) and no shadows. Optimized for clarity and learning.
+//! - **Our approach**: Minimal forward renderer that loops over every point
+//!   and spot light per pixel, up to
+//!   [`GraphicsSettings::max_lights`](crate::render::GraphicsSettings) of
+//!   each, and no shadows, plus opt-in [HDR with bloom](hdr) and a
+//!   [skybox](skybox) with hemisphere ambient. Optimized for clarity and
+//!   learning.
 
+pub(crate) mod billboard;
 pub(crate) mod collect;
 pub(crate) mod draw;
+pub(crate) mod frustum;
+pub mod hdr;
+pub mod material_shader;
 pub(crate) mod mesh;
 pub(crate) mod pipeline;
 pub mod shape;
 pub(crate) mod shapes;
+pub mod skybox;
+pub(crate) mod particles;
+pub(crate) mod soft_particle;
 pub(crate) mod texture;
+pub mod transparency;
 pub(crate) mod vertex;
 
 pub(crate) mod gltf;
 #[cfg(feature = "physics3d")]
 pub(crate) mod debug_wireframe;
+#[cfg(feature = "render2d")]
+pub(crate) mod text3d;
 
 #[cfg(feature = "physics3d")]
 pub use debug_wireframe::DebugColliders3d;
-pub use mesh::MeshHandle;
+pub use billboard::Billboard;
+pub use hdr::{Bloom, PostProcess, Tonemapping};
+pub use material_shader::{MaterialShader, load_material_shader};
+pub use mesh::{MeshHandle, VertexAttributes, set_vertex_attributes};
 pub use shape::{Shape3d, ShapeKind3d};
-pub use texture::{TextureHandle3d, load_texture_3d};
-pub use self::gltf::load_gltf;
+pub use skybox::{Skybox, SkyboxSource};
+pub use soft_particle::SoftParticle;
+#[cfg(feature = "render2d")]
+pub use text3d::Text3d;
+pub use texture::{
+    TextureHandle3d, load_data_texture_3d, load_texture_3d, load_texture_3d_async,
+    load_texture_3d_with, set_texture_sampler_3d,
+};
+pub use self::gltf::{load_gltf, load_gltf_async};
+pub use transparency::{AlphaMode, Transparency};
+pub use vertex::MeshAttributes;
 
-use crate::math::Vec3;
+use crate::math::{Mat4, Vec3};
 use mesh::{mesh_cube, mesh_cylinder, mesh_plane, mesh_sphere};
 
-/// Marker component for a 3D perspective camera. Pair with
+/// Marker component for a 3D camera. Pair with
 /// [`Transform`](crate::math::Transform).
 ///
-/// The camera uses a perspective projection: objects farther away appear
-/// smaller, just like in real life. Field of view, near plane, and far plane
-/// control the visible volume (the *frustum*).
+/// The [`projection`](Self::projection), near plane, and far plane control
+/// the visible volume (the *frustum*). The default is a perspective
+/// projection: objects farther away appear smaller, just like in real life.
 #[derive(Debug)]
 pub struct Camera3d {
-    /// Vertical field of view in degrees. Default: 45.
-    pub fov_y: f32,
+    /// Perspective or orthographic; change it at any time. Default: 45°
+    /// perspective.
+    pub projection: Projection3d,
     /// Near clipping plane distance. Objects closer than this are invisible.
     pub near: f32,
     /// Far clipping plane distance. Objects farther than this are invisible.
     pub far: f32,
+    /// Render a depth-only pass before shading, so each pixel runs the PBR
+    /// fragment shader once — for the nearest surface only. Pays for itself
+    /// in dense scenes with lots of overdraw; costs an extra vertex pass
+    /// otherwise. Default: off.
+    pub depth_prepass: bool,
+    /// How [`AlphaMode::Blend`] meshes combine. See [`transparency`].
+    /// Default: [`Transparency::Sorted`].
+    pub transparency: Transparency,
+}
+
+impl Camera3d {
+    /// Set the projection (builder pattern).
+    pub fn projection(mut self, projection: Projection3d) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Enable or disable the depth prepass (builder pattern).
+    pub fn depth_prepass(mut self, enabled: bool) -> Self {
+        self.depth_prepass = enabled;
+        self
+    }
+
+    /// Set how transparent meshes combine (builder pattern).
+    pub fn transparency(mut self, transparency: Transparency) -> Self {
+        self.transparency = transparency;
+        self
+    }
 }
 
 impl Default for Camera3d {
     fn default() -> Self {
         Self {
-            fov_y: 45.0,
+            projection: Projection3d::perspective(45.0),
             near: 0.1,
             far: 1000.0,
+            depth_prepass: false,
+            transparency: Transparency::Sorted,
+        }
+    }
+}
+
+/// How a [`Camera3d`] maps the view volume onto the screen.
+///
+/// ```text
+///   Perspective                    Orthographic
+///   ╲            ╱                 │            │
+///    ╲  ▢    ▢  ╱  farther things  │ ▢       ▢  │  same size at
+///     ╲   ▢    ╱   look smaller    │    ▢       │  every depth
+///      ╲      ╱                    │            │
+///        eye                       └── screen ──┘
+/// ```
+///
+/// Orthographic views suit isometric, strategy and CAD-style cameras. Their
+/// visible width follows the window's aspect ratio; [`OrthographicSize`]
+/// picks the height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Projection3d {
+    /// Objects farther away appear smaller.
+    Perspective {
+        /// Vertical field of view in degrees.
+        fov_y: f32,
+    },
+    /// Parallel lines stay parallel and size doesn't change with distance.
+    Orthographic(OrthographicSize),
+}
+
+/// How much of the world an orthographic [`Projection3d`] shows vertically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrthographicSize {
+    /// World units per pixel: a bigger window shows more of the world.
+    Scale(f32),
+    /// World units from the bottom of the screen to the top, whatever the
+    /// window size.
+    FixedHeight(f32),
+}
+
+impl Projection3d {
+    /// Perspective projection with a vertical field of view in degrees.
+    pub fn perspective(fov_y: f32) -> Self {
+        Self::Perspective { fov_y }
+    }
+
+    /// Orthographic projection showing `height` world units vertically.
+    pub fn orthographic_height(height: f32) -> Self {
+        Self::Orthographic(OrthographicSize::FixedHeight(height))
+    }
+
+    /// Orthographic projection at `scale` world units per pixel.
+    pub fn orthographic_scale(scale: f32) -> Self {
+        Self::Orthographic(OrthographicSize::Scale(scale))
+    }
+
+    pub fn is_orthographic(&self) -> bool {
+        matches!(self, Self::Orthographic(_))
+    }
+
+    /// World-space height of one pixel on a `surface_size` viewport: at a
+    /// view depth of 1 for perspective, at every depth for orthographic.
+    pub fn pixel_size(&self, surface_size: (u32, u32)) -> f32 {
+        let height = surface_size.1.max(1) as f32;
+        match *self {
+            Self::Perspective { fov_y } => 2.0 * (fov_y.to_radians() * 0.5).tan() / height,
+            Self::Orthographic(OrthographicSize::Scale(scale)) => scale,
+            Self::Orthographic(OrthographicSize::FixedHeight(world_height)) => world_height / height,
+        }
+    }
+
+    /// The projection matrix for a `surface_size` viewport, mapping depth
+    /// `near..far` to `0..1`.
+    pub fn matrix(&self, surface_size: (u32, u32), near: f32, far: f32) -> Mat4 {
+        let (width, height) = surface_size;
+        let aspect = width as f32 / height.max(1) as f32;
+        match *self {
+            Self::Perspective { fov_y } => Mat4::perspective_rh(fov_y.to_radians(), aspect, near, far),
+            Self::Orthographic(_) => {
+                let half_h = self.pixel_size(surface_size) * height.max(1) as f32 * 0.5;
+                let half_w = half_h * aspect;
+                Mat4::orthographic_rh(-half_w, half_w, -half_h, half_h, near, far)
+            }
         }
     }
 }
@@ -165,9 +308,31 @@ impl Mesh3d {
 /// | Gold | 1.0 | 0.3 | (1.0, 0.766, 0.336) |
 /// | Mirror | 1.0 | 0.0 | (0.95, 0.95, 0.95) |
 /// | Rough metal | 1.0 | 0.8 | any metallic color |
+///
+/// ## Texture Maps
+///
+/// Each factor can be varied across the surface by a texture, sampled with
+/// the mesh's first UV set and multiplied in — the glTF convention, so maps
+/// exported for glTF work unchanged:
+///
+/// Load color maps with [`load_texture_3d`] and the others, which hold
+/// plain numbers, with [`load_data_texture_3d`]:
+///
+/// | Map | Channels | Multiplies |
+/// |-----|----------|------------|
+/// | `base_color_texture` | RGBA (sRGB) | `base_color` |
+/// | `normal_texture` | RGB tangent-space normal, +Y up | — (`normal_scale` scales the bumps) |
+/// | `metallic_roughness_texture` | G = roughness, B = metallic | `roughness`, `metallic` |
+/// | `emissive_texture` | RGB (sRGB) | `emissive` |
+/// | `ao_texture` | R = ambient occlusion | ambient light (by `ao_strength`) |
+///
+/// Meshes carry no tangents, so the normal map's tangent frame is derived
+/// per pixel from the UV layout.
 #[derive(Debug)]
 pub struct Material {
-    /// Base color (albedo). Alpha channel is ignored (opaque only).
+    /// Base color (albedo). The alpha channel is used with
+    /// [`AlphaMode::Blend`] and on [`SoftParticle`] entities, and ignored
+    /// otherwise.
     pub base_color: [f32; 4],
     /// Optional base color texture. Sampled and multiplied with `base_color`.
     pub base_color_texture: Option<TextureHandle3d>,
@@ -175,8 +340,31 @@ pub struct Material {
     pub metallic: f32,
     /// Roughness factor [0.0, 1.0]. 0 = mirror-smooth, 1 = fully rough.
     pub roughness: f32,
-    /// Emissive color (self-illumination), added after lighting.
+    /// Optional tangent-space normal map, adding surface detail the
+    /// geometry doesn't have.
+    pub normal_texture: Option<TextureHandle3d>,
+    /// How strongly the normal map bends the normal: 0 = flat, 1 = as
+    /// authored.
+    pub normal_scale: f32,
+    /// Optional map with roughness in green and metallic in blue.
+    pub metallic_roughness_texture: Option<TextureHandle3d>,
+    /// Emissive color (self-illumination), added after lighting. Values
+    /// above 1.0 glow under a [`PostProcess`] with bloom.
     pub emissive: [f32; 3],
+    /// Optional emissive map. Multiplied with `emissive`, so set that to
+    /// white to use the map as is.
+    pub emissive_texture: Option<TextureHandle3d>,
+    /// Optional ambient occlusion map (red channel), darkening the ambient
+    /// light in creases and cavities.
+    pub ao_texture: Option<TextureHandle3d>,
+    /// How much the ambient occlusion map applies: 0 = not at all, 1 =
+    /// fully.
+    pub ao_strength: f32,
+    /// Draw with a custom WGSL shader instead of the PBR one. See
+    /// [`material_shader`].
+    pub shader: Option<MaterialShader>,
+    /// Opaque, or blended by alpha. See [`transparency`].
+    pub alpha_mode: AlphaMode,
 }
 
 impl Default for Material {
@@ -186,7 +374,15 @@ impl Default for Material {
             base_color_texture: None,
             metallic: 0.0,
             roughness: 0.5,
+            normal_texture: None,
+            normal_scale: 1.0,
+            metallic_roughness_texture: None,
             emissive: [0.0, 0.0, 0.0],
+            emissive_texture: None,
+            ao_texture: None,
+            ao_strength: 1.0,
+            shader: None,
+            alpha_mode: AlphaMode::Opaque,
         }
     }
 }
@@ -216,10 +412,46 @@ impl Default for DirectionalLight {
     }
 }
 
+/// How a point or spot light fades with distance. Every curve reaches zero
+/// at the light's radius (range), so lights never end in a hard edge.
+///
+/// ```text
+///   1 ┤█▄                 1 ┤▀▀▄▄                1 ┤▀▀▀▀▄▄
+///     │ ▀▄                  │    ▀▀▄▄              │      ▀▄
+///     │   ▀▀▄▄▄             │        ▀▀▄▄          │        ▀▄▄
+///   0 ┼────────▀▀▀▀▀┤     0 ┼────────────▀▀┤     0 ┼───────────▀▀┤
+///       InverseSquare          Linear               Smooth
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Attenuation {
+    /// Physically based 1/d², windowed to reach zero at the radius. Very
+    /// bright up close with a long, dim tail; `intensity` is the brightness
+    /// one unit away.
+    #[default]
+    InverseSquare,
+    /// Full intensity at the light, falling in a straight line to zero.
+    Linear,
+    /// A soft-edged pool: near full intensity for most of the radius, then
+    /// an S-curve down to zero.
+    Smooth,
+}
+
+impl Attenuation {
+    /// The value the shader switches on.
+    pub(crate) fn shader_id(self) -> u32 {
+        match self {
+            Attenuation::InverseSquare => 0,
+            Attenuation::Linear => 1,
+            Attenuation::Smooth => 2,
+        }
+    }
+}
+
 /// A point light — emits light in all directions from a position.
 ///
-/// Pair with [`Transform`](crate::math::Transform) for position.
-/// Up to 8 point lights are supported per scene.
+/// Pair with [`Transform`](crate::math::Transform) for position. Up to
+/// [`GraphicsSettings::max_lights`](crate::render::GraphicsSettings) point
+/// lights are shaded per frame; the rest are skipped.
 #[derive(Debug)]
 pub struct PointLight {
     /// Light color (linear RGB).
@@ -228,6 +460,8 @@ pub struct PointLight {
     pub intensity: f32,
     /// Maximum radius of influence. Light falls off to zero at this distance.
     pub radius: f32,
+    /// Falloff curve between the light and `radius`.
+    pub attenuation: Attenuation,
 }
 
 impl Default for PointLight {
@@ -236,6 +470,55 @@ impl Default for PointLight {
             color: [1.0, 1.0, 1.0],
             intensity: 1.0,
             radius: 10.0,
+            attenuation: Attenuation::InverseSquare,
+        }
+    }
+}
+
+/// A spot light — a cone of light from a position, like a flashlight or a
+/// stage light.
+///
+/// Pair with [`Transform`](crate::math::Transform): the cone points along
+/// the entity's forward axis (−Z), so `Transform::looking_at` aims it.
+/// Inside `inner_angle` the light is at full strength; between the inner and
+/// outer angles it fades to zero:
+///
+/// ```text
+///          ╱ ╲          outer_angle: edge of the cone
+///         ╱ ▒ ╲         inner_angle: edge of the full-strength core
+///        ╱ ▒█▒ ╲
+///       ╱ ▒███▒ ╲       both measured from the center line
+/// ```
+///
+/// Shares the per-frame limit with point lights — up to
+/// [`GraphicsSettings::max_lights`](crate::render::GraphicsSettings) spot
+/// lights are shaded.
+#[derive(Debug)]
+pub struct SpotLight {
+    /// Light color (linear RGB).
+    pub color: [f32; 3],
+    /// Intensity multiplier.
+    pub intensity: f32,
+    /// Maximum distance of influence. Light falls off to zero here.
+    pub range: f32,
+    /// Half-angle of the full-strength core, in degrees. Clamped to
+    /// `outer_angle`.
+    pub inner_angle: f32,
+    /// Half-angle of the whole cone, in degrees (below 90).
+    pub outer_angle: f32,
+    /// Falloff curve between the light and `range`.
+    pub attenuation: Attenuation,
+}
+
+impl Default for SpotLight {
+    fn default() -> Self {
+        Self {
+            color: [1.0, 1.0, 1.0],
+            intensity: 1.0,
+            range: 10.0,
+            inner_angle: 15.0,
+            outer_angle: 25.0,
+            attenuation: Attenuation::InverseSquare,
         }
     }
 }
@@ -247,7 +530,10 @@ impl Default for PointLight {
 /// ```ignore
 /// world.insert_resource(AmbientLight { intensity: 0.1, ..Default::default() });
 /// ```
-#[derive(Debug)]
+///
+/// Register it with [`SceneRegistry::register_resource`](crate::scene::SceneRegistry::register_resource)
+/// to save it with scenes.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct AmbientLight {
     /// Light color (linear RGB).
     pub color: [f32; 3],
@@ -0,0 +1,26 @@
+// Weighted-blended OIT resolve: turn the accumulation and revealage targets
+// written by `fs_oit` (shader.wgsl) into one color, alpha-blended over the
+// opaque scene.
+
+@group(0) @binding(0) var accum_texture: texture_2d<f32>;
+@group(0) @binding(1) var revealage_texture: texture_2d<f32>;
+
+// One oversized triangle covers the whole screen — no vertex buffer needed.
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let xy = vec2(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    return vec4(xy, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(position.xy);
+    let revealage = textureLoad(revealage_texture, pixel, 0).r;
+    // Nothing transparent covered this pixel.
+    if revealage >= 0.9999 {
+        discard;
+    }
+    let accum = textureLoad(accum_texture, pixel, 0);
+    let average = accum.rgb / max(accum.a, 1e-5);
+    return vec4(average, 1.0 - revealage);
+}
@@ -0,0 +1,43 @@
+// Billboard particle shader for 3D particle emitters.
+// Quads are expanded along the camera axes on the CPU, so the vertex stage
+// only applies the view-projection.
+
+struct Camera {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+    _padding: f32,
+}
+
+@group(0) @binding(0) var<uniform> camera: Camera;
+@group(1) @binding(0) var particle_texture: texture_2d<f32>;
+@group(1) @binding(1) var particle_sampler: sampler;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * vec4(in.position, 1.0);
+    out.uv = in.uv;
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(particle_texture, particle_sampler, in.uv) * in.color;
+    if color.a < 0.01 {
+        discard;
+    }
+    return color;
+}
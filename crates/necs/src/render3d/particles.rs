@@ -0,0 +1,349 @@
+//! # Particles (3D) — Billboard Quads for Particle Emitters
+//!
+//! The 3D half of [`ParticleEmitter`](crate::particles::ParticleEmitter)
+//! rendering. The simulation lives in [`particles`](crate::particles); here
+//! every live particle becomes a square quad facing the camera.
+//!
+//! ```text
+//!   extract: emitter ─► (texture, [position, size, color] per particle)
+//!   draw:    every particle, all emitters ─► sort far → near
+//!            ─► quad along camera right/up ─► one vertex buffer
+//!            ─► one draw per run of particles sharing a texture
+//! ```
+//!
+//! Particles are sorted as one list rather than per emitter, so two
+//! overlapping smoke columns blend correctly. They are drawn after the
+//! opaque meshes, depth-tested but not depth-written, like
+//! [`Text3d`](super::Text3d) labels.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: The particle system's renderer module in "Billboard" mode,
+//!   sorted by distance.
+//! - **Godot**: `CPUParticles3D` with a billboard `StandardMaterial3D`.
+//! - **Our approach**: CPU-built quads in one dynamic buffer per frame.
+
+use std::collections::HashMap;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::ecs::World;
+use crate::math::Vec3;
+use crate::particles::{ParticleEmitter, ParticleQuad};
+use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
+
+use super::billboard::BillboardView;
+use super::pipeline::{MeshRenderer, DEPTH_FORMAT};
+use super::texture::{TextureHandle3d, TextureStore3d};
+
+// ── Extraction ──────────────────────────────────────────────────────────
+
+/// One emitter's particles, copied out of the ECS by the extract phase.
+pub(crate) struct ExtractedParticles3d {
+    pub texture: Option<TextureHandle3d>,
+    pub quads: Vec<ParticleQuad>,
+}
+
+/// Copy the live particles of every visible emitter.
+pub(crate) fn extract_particles_3d(world: &mut World) -> Vec<ExtractedParticles3d> {
+    let mut emitters = Vec::new();
+    world.query::<(&ParticleEmitter, Option<&ComputedVisibility>)>(|_entity, (emitter, vis)| {
+        if !is_hidden(vis) && !emitter.particles().is_empty() {
+            emitters.push(ExtractedParticles3d {
+                texture: emitter.texture_3d,
+                quads: emitter.quads().collect(),
+            });
+        }
+    });
+    emitters
+}
+
+// ── Geometry ────────────────────────────────────────────────────────────
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct ParticleVertex {
+    position: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl ParticleVertex {
+    const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<ParticleVertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2, 2 => Float32x4],
+    };
+}
+
+/// Camera-facing quads for every particle in front of the camera, sorted
+/// far → near, with the texture runs to draw them in.
+fn build_quads(
+    emitters: &[ExtractedParticles3d],
+    view: &BillboardView,
+    default_texture: TextureHandle3d,
+) -> (Vec<ParticleVertex>, Vec<(TextureHandle3d, std::ops::Range<u32>)>) {
+    let mut visible: Vec<(f32, TextureHandle3d, &ParticleQuad)> = Vec::new();
+    for emitter in emitters {
+        let texture = emitter.texture.unwrap_or(default_texture);
+        for quad in &emitter.quads {
+            let depth = view.depth(quad.position);
+            if depth > 0.0 {
+                visible.push((depth, texture, quad));
+            }
+        }
+    }
+    visible.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let (right, up) = (view.rotation * Vec3::X, view.rotation * Vec3::Y);
+    let mut vertices = Vec::with_capacity(visible.len() * 4);
+    let mut runs: Vec<(TextureHandle3d, std::ops::Range<u32>)> = Vec::new();
+    for (_, texture, quad) in visible {
+        let half = quad.size * 0.5;
+        let corners = [
+            (-half, -half, 0.0, 1.0),
+            (half, -half, 1.0, 1.0),
+            (half, half, 1.0, 0.0),
+            (-half, half, 0.0, 0.0),
+        ];
+        for (x, y, u, v) in corners {
+            vertices.push(ParticleVertex {
+                position: (quad.position + right * x + up * y).to_array(),
+                uv: [u, v],
+                color: quad.color,
+            });
+        }
+
+        // Six indices per quad; extend the run while the texture repeats.
+        let end = (vertices.len() / 4 * 6) as u32;
+        match runs.last_mut() {
+            Some((run_texture, range)) if *run_texture == texture => range.end = end,
+            _ => runs.push((texture, end - 6..end)),
+        }
+    }
+    (vertices, runs)
+}
+
+// ── Renderer ────────────────────────────────────────────────────────────
+
+/// GPU resources for 3D particles. Lazy-initialized the first frame a
+/// particle is drawn.
+pub(crate) struct ParticleRenderer3d {
+    pipeline: wgpu::RenderPipeline,
+    /// Color target the pipeline was built for.
+    pub target: TargetKey,
+    texture_layout: wgpu::BindGroupLayout,
+}
+
+impl ParticleRenderer3d {
+    pub fn new(
+        device: &wgpu::Device,
+        target: TargetKey,
+        camera_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let (format, samples) = target;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("3d particle shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("particle.wgsl").into()),
+        });
+
+        // Particle texture + its sampler (group 1)
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("3d particle texture layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("3d particle pipeline layout"),
+            bind_group_layouts: &[camera_bind_group_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("3d particle pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[ParticleVertex::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            // Hidden behind opaque geometry; sorted rather than depth-written
+            // among themselves.
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: multisample_state(samples),
+            multiview: None,
+            cache: None,
+        });
+
+        Self {
+            pipeline,
+            target,
+            texture_layout,
+        }
+    }
+}
+
+/// Draw every extracted particle on top of the opaque 3D scene.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_particles_3d(
+    encoder: &mut wgpu::CommandEncoder,
+    target: &ColorTarget,
+    gpu: &GpuContext,
+    renderer: &MeshRenderer,
+    particle_renderer: &ParticleRenderer3d,
+    texture_store: &TextureStore3d,
+    emitters: &[ExtractedParticles3d],
+    view: &BillboardView,
+) {
+    let (vertices, runs) = build_quads(emitters, view, texture_store.default_handle());
+    if vertices.is_empty() {
+        return;
+    }
+    let indices: Vec<u32> = (0..(vertices.len() / 4) as u32)
+        .flat_map(|quad| [0, 1, 2, 0, 2, 3].map(|i| quad * 4 + i))
+        .collect();
+
+    let vertex_buffer = gpu
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("3d particle vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+    let index_buffer = gpu
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("3d particle indices"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+    // One bind group per distinct texture this frame.
+    let mut texture_groups: HashMap<TextureHandle3d, wgpu::BindGroup> = HashMap::new();
+    for (texture, _) in &runs {
+        texture_groups.entry(*texture).or_insert_with(|| {
+            let entry = texture_store.get(*texture);
+            gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("3d particle texture bind group"),
+                layout: &particle_renderer.texture_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(&entry.view),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&entry.sampler),
+                    },
+                ],
+            })
+        });
+    }
+
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("3d particle pass"),
+        color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: &renderer.depth_texture,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    pass.set_pipeline(&particle_renderer.pipeline);
+    pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
+    pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+    pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+    for (texture, range) in runs {
+        pass.set_bind_group(1, &texture_groups[&texture], &[]);
+        pass.draw_indexed(range, 0, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Mat4;
+    use crate::render3d::Projection3d;
+
+    fn emitter(texture: usize, depths: &[f32]) -> ExtractedParticles3d {
+        ExtractedParticles3d {
+            texture: Some(TextureHandle3d(texture)),
+            quads: depths
+                .iter()
+                .map(|&depth| ParticleQuad {
+                    position: Vec3::new(0.0, 0.0, -depth),
+                    size: 1.0,
+                    color: [1.0; 4],
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn particles_sort_far_to_near_across_emitters() {
+        // Camera at the origin looking down -Z.
+        let view = BillboardView::new(Mat4::IDENTITY, Projection3d::perspective(90.0), (2, 2));
+        let emitters = [emitter(1, &[2.0, 8.0]), emitter(2, &[5.0, -1.0])];
+        let (vertices, runs) = build_quads(&emitters, &view, TextureHandle3d(0));
+
+        // The particle behind the camera is dropped.
+        assert_eq!(vertices.len(), 3 * 4);
+        let depths: Vec<f32> = vertices.chunks(4).map(|quad| -quad[0].position[2]).collect();
+        assert_eq!(depths, vec![8.0, 5.0, 2.0]);
+        let textures: Vec<usize> = runs.iter().map(|(texture, _)| texture.0).collect();
+        assert_eq!(textures, vec![1, 2, 1]);
+        assert_eq!(runs[2].1, 12..18);
+    }
+}
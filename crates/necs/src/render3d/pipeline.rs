@@ -8,6 +8,9 @@
 //!
 //! - **Four bind group layouts**: Camera, lights, material, and model
 //!   transform. See [`vertex`](super::vertex) for the uniform buffer layouts.
+//!   Point and spot lights live in storage buffers next to the light
+//!   uniform, grown (and the light bind group rebuilt) when a frame has
+//!   more lights than fit.
 //!
 //! - **Backface culling**: Triangles facing away from the camera are skipped.
 //!   This halves the fragment workload for closed meshes (cubes, spheres).
@@ -30,6 +33,51 @@
 //! provides high precision across the entire depth range and is universally
 //! supported. The depth texture must be recreated whenever the window resizes.
 //!
+//! ## Depth Prepass
+//!
+//! With [`Camera3d::depth_prepass`](super::Camera3d::depth_prepass) on, a
+//! cheap depth-only pipeline (`prepass.wgsl`) runs first. The PBR pipeline
+//! then uses `prepassed_pipeline` — same shader, but depth compare
+//! `LessEqual` with writes off — so hidden fragments are rejected before
+//! shading:
+//!
+//! ```text
+//!   without prepass              with prepass
+//!   ┌──────────────────┐         ┌──────────────────┐
+//!   │ shade A (hidden) │         │ depth A, B, C    │  vertex only
+//!   │ shade B (hidden) │         ├──────────────────┤
+//!   │ shade C          │         │ shade C          │  1 shade per pixel
+//!   └──────────────────┘         └──────────────────┘
+//! ```
+//!
+//! ## Pipeline Variants
+//!
+//! The pipelines above only read the shared vertex buffer. Meshes with
+//! [optional attributes](super::vertex#optional-attributes), and materials
+//! with a [material shader](super::material_shader), need pipelines whose
+//! vertex layout and shader match. These are built the first time a
+//! [`PipelineKey`] is drawn and cached; the common case (PBR, no extras)
+//! keeps using `pipeline` directly.
+//!
+//! [Transparent](super::transparency) draws are always variants: their
+//! [`ColorOutput`] blends over the scene (or into the OIT targets) and
+//! tests depth without writing it.
+//!
+//! The key also carries the material's [shader defs](crate::render::shader_defs)
+//! ([`PbrDefs`]): one per texture map it actually sets. `shader.wgsl` wraps
+//! each map's sampling in `#ifdef`, so a plain colored material compiles to
+//! a shader that samples only the base color texture. Each combination of
+//! defs is preprocessed and compiled once, on first draw.
+//!
+//! ## Multisampling
+//!
+//! Every pipeline shares one color format and sample count, `target`, and
+//! the depth buffer has the same sample count. The sample count follows
+//! [MSAA](crate::render::msaa); the format is the surface's, or the
+//! [HDR](super::hdr) target's. When either changes,
+//! [`MeshRenderer::set_target`] rebuilds the base pipelines and depth
+//! buffer and drops the variants, which rebuild on demand.
+//!
 //! ## Comparison
 //!
 //! - **Bevy**: Uses a `RenderPipelineCache` with hot-reloading, specialization
@@ -37,21 +85,40 @@
 //! - **wgpu examples**: Similar structure but without the bind group split by
 //!   change frequency — typically one or two bind groups.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use wgpu::util::DeviceExt;
 
+use super::material_shader::{MaterialShader, MaterialShaders};
+use super::hdr::HDR_FORMAT;
+use super::transparency::accumulation_targets;
+use super::collect::ExtractedLights;
+use super::texture::MaterialTextures;
 use super::vertex::{
-    CameraUniform3d, LightUniform, MeshVertex, ModelUniform,
+    CameraUniform3d, ExtraVertexLayout, LightUniform, MeshAttributes, MeshVertex, ModelUniform,
+    PointLightData, SpotLightData,
 };
 use crate::render::GpuContext;
+use crate::render::msaa::{TargetKey, multisample_state};
+use crate::render::shader_defs::{ShaderDefs, preprocess};
 
 /// Depth texture format used by the 3D renderer.
 pub(crate) const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
 
+/// Texture maps in a material bind group, each a texture + sampler pair.
+const MATERIAL_MAPS: u32 = 5;
+
+/// Source of the built-in PBR shader, before preprocessing.
+const PBR_SOURCE: &str = include_str!("shader.wgsl");
+
 /// All GPU resources for the 3D mesh renderer. Lazy-initialized on first frame.
 pub(crate) struct MeshRenderer {
     pub pipeline: wgpu::RenderPipeline,
+    /// PBR pipeline for use after a depth prepass (`LessEqual`, no depth write).
+    pub prepassed_pipeline: wgpu::RenderPipeline,
+    /// Depth-only pipeline for the prepass (groups: camera, model).
+    pub prepass_pipeline: wgpu::RenderPipeline,
 
     // Bind group layouts (needed to create per-frame bind groups and hot-reload)
     pub camera_bind_group_layout: wgpu::BindGroupLayout,
@@ -64,13 +131,18 @@ pub(crate) struct MeshRenderer {
     pub camera_bind_group: wgpu::BindGroup,
     pub light_buffer: wgpu::Buffer,
     pub light_bind_group: wgpu::BindGroup,
+    // Point and spot light storage buffers (resized as needed)
+    pub point_light_buffer: wgpu::Buffer,
+    pub spot_light_buffer: wgpu::Buffer,
+    pub light_capacity: (usize, usize), // point, spot slots
 
-    // Shared sampler for all 3D textures
-    pub sampler: wgpu::Sampler,
 
     // Depth buffer (recreated on resize)
     pub depth_texture: wgpu::TextureView,
     pub depth_size: (u32, u32),
+    /// Color format and MSAA sample count of the pipelines (and the
+    /// depth buffer's sample count).
+    pub target: TargetKey,
 
     // Dynamic model uniform buffer (resized as needed)
     pub model_buffer: wgpu::Buffer,
@@ -79,17 +151,114 @@ pub(crate) struct MeshRenderer {
 
     /// Path to the shader source file on disk (for hot-reload).
     pub shader_path: Option<PathBuf>,
+
+    // Pipeline variants (see the module docs)
+    /// The PBR shader compiled without defs.
+    pbr_shader: wgpu::ShaderModule,
+    /// PBR source (built-in or hot-reloaded), for compiling other defs.
+    pbr_source: String,
+    pipeline_layout: wgpu::PipelineLayout,
+    /// `None` marks a variant that failed to build; its draws fall back.
+    variants: HashMap<PipelineKey, Option<wgpu::RenderPipeline>>,
+    pbr_modules: HashMap<PbrDefs, Option<wgpu::ShaderModule>>,
+    material_modules: HashMap<(MaterialShader, PbrDefs), Option<wgpu::ShaderModule>>,
+}
+
+/// Everything a draw call's pipeline depends on besides the shared bind
+/// group layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct PipelineKey {
+    /// `None` for the built-in PBR shader.
+    pub shader: Option<MaterialShader>,
+    /// Optional attributes of the mesh being drawn.
+    pub attributes: MeshAttributes,
+    /// Drawn after a depth prepass.
+    pub prepassed: bool,
+    /// Where the fragment color goes.
+    pub output: ColorOutput,
+    /// Shader defs from the material's texture maps.
+    pub defs: PbrDefs,
+}
+
+impl PipelineKey {
+    /// Served by the fixed `pipeline` / `prepassed_pipeline`.
+    fn is_base(&self) -> bool {
+        self.shader.is_none()
+            && self.attributes.is_empty()
+            && self.output == ColorOutput::Opaque
+            && self.defs.is_empty()
+    }
+}
+
+/// Which optional features of the PBR shader a material uses, as a bitset
+/// of [shader defs](crate::render::shader_defs). Part of [`PipelineKey`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub(crate) struct PbrDefs(u8);
+
+impl PbrDefs {
+    pub const NORMAL_MAP: Self = Self(1);
+    pub const METALLIC_ROUGHNESS_MAP: Self = Self(1 << 1);
+    pub const EMISSIVE_MAP: Self = Self(1 << 2);
+    pub const OCCLUSION_MAP: Self = Self(1 << 3);
+
+    /// Def names, as tested with `#ifdef` in `shader.wgsl`.
+    const NAMES: [(Self, &'static str); 4] = [
+        (Self::NORMAL_MAP, "NORMAL_MAP"),
+        (Self::METALLIC_ROUGHNESS_MAP, "METALLIC_ROUGHNESS_MAP"),
+        (Self::EMISSIVE_MAP, "EMISSIVE_MAP"),
+        (Self::OCCLUSION_MAP, "OCCLUSION_MAP"),
+    ];
+
+    /// One def per map the material sets (the base color texture is always
+    /// sampled).
+    pub fn of(textures: &MaterialTextures) -> Self {
+        let maps = [
+            (textures.normal, Self::NORMAL_MAP),
+            (textures.metallic_roughness, Self::METALLIC_ROUGHNESS_MAP),
+            (textures.emissive, Self::EMISSIVE_MAP),
+            (textures.ao, Self::OCCLUSION_MAP),
+        ];
+        Self(maps.iter().filter(|(map, _)| map.is_some()).fold(0, |bits, (_, def)| bits | def.0))
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The named defs to preprocess with.
+    pub fn shader_defs(self) -> ShaderDefs {
+        Self::NAMES
+            .into_iter()
+            .filter(|(def, _)| self.0 & def.0 != 0)
+            .map(|(_, name)| name)
+            .collect()
+    }
+}
+
+/// How a mesh pipeline writes color and depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum ColorOutput {
+    /// Replace the color and write depth.
+    Opaque,
+    /// Alpha-blend over the target; test depth without writing it.
+    Blended,
+    /// Weighted-blended OIT: `fs_oit` into the two accumulation targets;
+    /// test depth without writing it.
+    Accumulated,
 }
 
 impl MeshRenderer {
-    /// Create the 3D renderer from the current GPU context.
-    pub fn new(gpu: &GpuContext) -> Self {
+    /// Create the 3D renderer from the current GPU context, drawing into
+    /// `target`.
+    pub fn new(gpu: &GpuContext, target: TargetKey) -> Self {
+        let (format, samples) = target;
         let device = &gpu.device;
 
         // ── Shader ──────────────────────────────────────────────────────
+        let base_source = preprocess(PBR_SOURCE, &ShaderDefs::new()).expect("shader.wgsl directives are balanced");
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("pbr shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader.wgsl").into()),
+            source: wgpu::ShaderSource::Wgsl(base_source.into()),
         });
 
         // ── Bind group layout 0: Camera (per frame) ────────────────────
@@ -109,56 +278,64 @@ impl MeshRenderer {
             });
 
         // ── Bind group layout 1: Lights (per frame) ────────────────────
+        // LightUniform, then the point (1) and spot (2) light arrays.
+        let light_entry = |binding, ty| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let light_storage = wgpu::BufferBindingType::Storage { read_only: true };
         let light_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("3d light layout"),
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::FRAGMENT,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                }],
+                entries: &[
+                    light_entry(0, wgpu::BufferBindingType::Uniform),
+                    light_entry(1, light_storage),
+                    light_entry(2, light_storage),
+                ],
             });
 
         // ── Bind group layout 2: Material (per material) ───────────────
+        // MaterialUniform, then a texture + sampler pair per map: base
+        // color (1, 2), normal (3, 4), metallic-roughness (5, 6), emissive
+        // (7, 8), ambient occlusion (9, 10).
+        let mut material_entries = vec![wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        }];
+        for binding in (1..=MATERIAL_MAPS * 2).step_by(2) {
+            material_entries.push(wgpu::BindGroupLayoutEntry {
+                binding,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    multisampled: false,
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                },
+                count: None,
+            });
+            material_entries.push(wgpu::BindGroupLayoutEntry {
+                binding: binding + 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            });
+        }
         let material_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 label: Some("3d material layout"),
-                entries: &[
-                    // MaterialUniform
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                    // base_color_texture
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Texture {
-                            multisampled: false,
-                            view_dimension: wgpu::TextureViewDimension::D2,
-                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
-                        },
-                        count: None,
-                    },
-                    // sampler
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 2,
-                        visibility: wgpu::ShaderStages::FRAGMENT,
-                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
-                        count: None,
-                    },
-                ],
+                entries: &material_entries,
             });
 
         // ── Bind group layout 3: Model (per object, dynamic offset) ────
@@ -191,46 +368,12 @@ impl MeshRenderer {
             push_constant_ranges: &[],
         });
 
-        // ── Render pipeline ─────────────────────────────────────────────
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("3d pbr pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[MeshVertex::LAYOUT],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.surface_format(),
-                    blend: None, // opaque only
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        // ── Render pipelines ────────────────────────────────────────────
+        let pipeline = create_pbr_pipeline(device, &pipeline_layout, &shader, format, false, ColorOutput::Opaque, None, "vs_main", "fs_main", samples);
+        let prepassed_pipeline =
+            create_pbr_pipeline(device, &pipeline_layout, &shader, format, true, ColorOutput::Opaque, None, "vs_main", "fs_main", samples);
+        let prepass_pipeline =
+            create_prepass_pipeline(device, &camera_bind_group_layout, &model_bind_group_layout, samples);
 
         // ── Camera buffer + bind group ──────────────────────────────────
         let camera_uniform = CameraUniform3d {
@@ -253,45 +396,23 @@ impl MeshRenderer {
         });
 
         // ── Light buffer + bind group ───────────────────────────────────
-        let light_uniform = LightUniform {
-            dir_direction: [0.0, -1.0, 0.0],
-            dir_intensity: 0.0,
-            dir_color: [1.0, 1.0, 1.0],
-            _pad0: 0.0,
-            ambient_color: [1.0, 1.0, 1.0],
-            ambient_intensity: 0.1,
-            point_lights: [bytemuck::Zeroable::zeroed(); 8],
-            point_light_count: 0,
-            _pad1: [0; 3],
-        };
         let light_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("3d light buffer"),
-            contents: bytemuck::cast_slice(&[light_uniform]),
+            contents: bytemuck::cast_slice(&[LightUniform::default()]),
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
-        let light_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("3d light bind group"),
-            layout: &light_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: light_buffer.as_entire_binding(),
-            }],
-        });
-
-        // ── Shared sampler ──────────────────────────────────────────────
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            label: Some("3d sampler"),
-            address_mode_u: wgpu::AddressMode::Repeat,
-            address_mode_v: wgpu::AddressMode::Repeat,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Linear,
-            ..Default::default()
-        });
+        let initial_lights = 8;
+        let point_light_buffer = create_light_storage::<PointLightData>(device, "3d point lights", initial_lights);
+        let spot_light_buffer = create_light_storage::<SpotLightData>(device, "3d spot lights", initial_lights);
+        let light_bind_group = create_light_bind_group(
+            device,
+            &light_bind_group_layout,
+            [&light_buffer, &point_light_buffer, &spot_light_buffer],
+        );
 
         // ── Depth texture ───────────────────────────────────────────────
         let (w, h) = gpu.surface_size();
-        let depth_texture = create_depth_texture(device, w, h);
+        let depth_texture = create_depth_texture(device, w, h, samples);
 
         // ── Dynamic model buffer ────────────────────────────────────────
         let initial_capacity = 64;
@@ -309,6 +430,8 @@ impl MeshRenderer {
 
         Self {
             pipeline,
+            prepassed_pipeline,
+            prepass_pipeline,
             camera_bind_group_layout,
             light_bind_group_layout,
             material_bind_group_layout,
@@ -317,24 +440,86 @@ impl MeshRenderer {
             camera_bind_group,
             light_buffer,
             light_bind_group,
-            sampler,
+            point_light_buffer,
+            spot_light_buffer,
+            light_capacity: (initial_lights, initial_lights),
             depth_texture,
             depth_size: (w, h),
+            target,
             model_buffer,
             model_bind_group,
             model_buffer_capacity: initial_capacity,
             shader_path,
+            pbr_shader: shader,
+            pbr_source: PBR_SOURCE.to_string(),
+            pipeline_layout,
+            variants: HashMap::new(),
+            pbr_modules: HashMap::new(),
+            material_modules: HashMap::new(),
         }
     }
 
     /// Recreate the depth texture if the surface size changed.
     pub fn resize_depth_if_needed(&mut self, device: &wgpu::Device, width: u32, height: u32) {
         if (width, height) != self.depth_size && width > 0 && height > 0 {
-            self.depth_texture = create_depth_texture(device, width, height);
+            self.depth_texture = create_depth_texture(device, width, height, self.target.1);
             self.depth_size = (width, height);
         }
     }
 
+    /// Switch to drawing into `target`: rebuild the base pipelines and the
+    /// depth buffer, and drop the variants.
+    pub fn set_target(&mut self, gpu: &GpuContext, target: TargetKey) {
+        let device = &gpu.device;
+        let (format, samples) = target;
+        let (shader, layout) = (&self.pbr_shader, &self.pipeline_layout);
+        self.pipeline =
+            create_pbr_pipeline(device, layout, shader, format, false, ColorOutput::Opaque, None, "vs_main", "fs_main", samples);
+        self.prepassed_pipeline =
+            create_pbr_pipeline(device, layout, shader, format, true, ColorOutput::Opaque, None, "vs_main", "fs_main", samples);
+        self.prepass_pipeline =
+            create_prepass_pipeline(device, &self.camera_bind_group_layout, &self.model_bind_group_layout, samples);
+        self.variants.clear();
+        let (width, height) = self.depth_size;
+        self.depth_texture = create_depth_texture(device, width, height, samples);
+        self.target = target;
+    }
+
+    /// Upload this frame's lights, growing the point and spot light buffers
+    /// (and rebuilding the light bind group) if they're too small.
+    pub fn write_lights(&mut self, gpu: &GpuContext, lights: &ExtractedLights) {
+        let device = &gpu.device;
+        let (point_cap, spot_cap) = self.light_capacity;
+        if lights.point.len() > point_cap || lights.spot.len() > spot_cap {
+            if lights.point.len() > point_cap {
+                let cap = lights.point.len().next_power_of_two();
+                self.point_light_buffer = create_light_storage::<PointLightData>(device, "3d point lights", cap);
+                self.light_capacity.0 = cap;
+            }
+            if lights.spot.len() > spot_cap {
+                let cap = lights.spot.len().next_power_of_two();
+                self.spot_light_buffer = create_light_storage::<SpotLightData>(device, "3d spot lights", cap);
+                self.light_capacity.1 = cap;
+            }
+            self.light_bind_group = create_light_bind_group(
+                device,
+                &self.light_bind_group_layout,
+                [&self.light_buffer, &self.point_light_buffer, &self.spot_light_buffer],
+            );
+        }
+
+        gpu.queue
+            .write_buffer(&self.light_buffer, 0, bytemuck::cast_slice(&[lights.uniform]));
+        if !lights.point.is_empty() {
+            gpu.queue
+                .write_buffer(&self.point_light_buffer, 0, bytemuck::cast_slice(&lights.point));
+        }
+        if !lights.spot.is_empty() {
+            gpu.queue
+                .write_buffer(&self.spot_light_buffer, 0, bytemuck::cast_slice(&lights.spot));
+        }
+    }
+
     /// Ensure the dynamic model buffer can hold `count` entries.
     /// Recreates if needed. Returns the aligned stride in bytes.
     pub fn ensure_model_capacity(
@@ -361,8 +546,14 @@ impl MeshRenderer {
     ///
     /// Reuses the existing bind group layouts. Returns the candidate pipeline
     /// **without** swapping it in — the caller must check the error scope first
-    /// and only assign to `self.pipeline` if valid.
-    pub fn build_pipeline(&self, gpu: &GpuContext, shader: &wgpu::ShaderModule) -> wgpu::RenderPipeline {
+    /// and only assign to `self.pipeline` (or `self.prepassed_pipeline` when
+    /// `depth_prepass` is set) if valid.
+    pub fn build_pipeline(
+        &self,
+        gpu: &GpuContext,
+        shader: &wgpu::ShaderModule,
+        depth_prepass: bool,
+    ) -> wgpu::RenderPipeline {
         let pipeline_layout = gpu.device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("3d pipeline layout (hot-reload)"),
             bind_group_layouts: &[
@@ -374,50 +565,322 @@ impl MeshRenderer {
             push_constant_ranges: &[],
         });
 
-        gpu.device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("3d pbr pipeline (hot-reload)"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: shader,
-                entry_point: Some("vs_main"),
-                buffers: &[MeshVertex::LAYOUT],
-                compilation_options: Default::default(),
+        create_pbr_pipeline(
+            &gpu.device,
+            &pipeline_layout,
+            shader,
+            self.target.0,
+            depth_prepass,
+            ColorOutput::Opaque,
+            None,
+            "vs_main",
+            "fs_main",
+            self.target.1,
+        )
+    }
+
+    /// Swap in a hot-reloaded PBR shader (its unprocessed `source`, and the
+    /// module and base pipelines compiled from it without defs). Variants
+    /// built from the old shader are rebuilt on demand.
+    pub fn replace_pbr_shader(
+        &mut self,
+        source: String,
+        shader: wgpu::ShaderModule,
+        pipeline: wgpu::RenderPipeline,
+        prepassed_pipeline: wgpu::RenderPipeline,
+    ) {
+        self.pbr_source = source;
+        self.pbr_shader = shader;
+        self.pipeline = pipeline;
+        self.prepassed_pipeline = prepassed_pipeline;
+        self.pbr_modules.clear();
+        self.variants.retain(|key, _| key.shader.is_some());
+    }
+
+    /// Drop everything compiled from `shader` after its source changed.
+    pub fn forget_material_shader(&mut self, shader: MaterialShader) {
+        self.material_modules.retain(|(compiled, _), _| *compiled != shader);
+        self.variants.retain(|key, _| key.shader != Some(shader));
+    }
+
+    /// Build the pipeline for `key` (and the PBR fallback for its mesh
+    /// layout) unless it's cached. A material shader that doesn't compile,
+    /// or reads attributes the mesh lacks, is cached as failed and reported
+    /// once.
+    pub fn prepare_pipeline(&mut self, gpu: &GpuContext, key: PipelineKey, shaders: Option<&MaterialShaders>) {
+        if key.shader.is_some() {
+            self.prepare_pipeline(gpu, PipelineKey { shader: None, ..key }, shaders);
+        }
+        if key.is_base() || self.variants.contains_key(&key) {
+            return;
+        }
+
+        // The PBR shader has a fragment entry point per output; a material
+        // shader's `fs_main` blends by whatever alpha it returns.
+        let fragment_entry_point = match (key.shader, key.output) {
+            (_, ColorOutput::Accumulated) => "fs_oit",
+            (None, ColorOutput::Blended) => "fs_blend",
+            _ => "fs_main",
+        };
+        let (module, entry_point) = match key.shader {
+            Some(shader) => (self.material_module(gpu, shader, key.defs, shaders), "vs_main"),
+            None if key.attributes.contains(MeshAttributes::COLOR) => (self.pbr_module(gpu, key.defs), "vs_main_colored"),
+            None => (self.pbr_module(gpu, key.defs), "vs_main"),
+        };
+        let pipeline = module.and_then(|module| {
+            let extra = ExtraVertexLayout::new(key.attributes);
+            gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+            let pipeline = create_pbr_pipeline(
+                &gpu.device,
+                &self.pipeline_layout,
+                &module,
+                self.target.0,
+                key.prepassed,
+                key.output,
+                extra.as_ref(),
+                entry_point,
+                fragment_entry_point,
+                self.target.1,
+            );
+            match pollster::block_on(gpu.device.pop_error_scope()) {
+                None => Some(pipeline),
+                Some(err) => {
+                    let name = key
+                        .shader
+                        .and_then(|shader| shaders?.path(shader))
+                        .map_or("pbr".into(), |path| path.display().to_string());
+                    log::warn!(
+                        "Material shader '{name}' can't draw meshes with attributes {:?};                          using the PBR shader: {err}",
+                        key.attributes
+                    );
+                    None
+                }
+            }
+        });
+        self.variants.insert(key, pipeline);
+    }
+
+    /// The PBR shader compiled with `defs`, compiling it on first use.
+    fn pbr_module(&mut self, gpu: &GpuContext, defs: PbrDefs) -> Option<wgpu::ShaderModule> {
+        if defs.is_empty() {
+            return Some(self.pbr_shader.clone());
+        }
+        if let Some(module) = self.pbr_modules.get(&defs) {
+            return module.clone();
+        }
+        let module = match compile_with_defs(gpu, &self.pbr_source, defs, "pbr shader (variant)") {
+            Ok(module) => Some(module),
+            Err(err) => {
+                let names: Vec<_> = defs.shader_defs().iter().map(str::to_owned).collect();
+                log::warn!(
+                    "PBR shader with defs [{}] failed to compile; using the base shader: {err}",
+                    names.join(", ")
+                );
+                None
+            }
+        };
+        self.pbr_modules.insert(defs, module.clone());
+        module
+    }
+
+    /// The compiled module for a material shader with `defs`, compiling it
+    /// on first use.
+    fn material_module(
+        &mut self,
+        gpu: &GpuContext,
+        shader: MaterialShader,
+        defs: PbrDefs,
+        shaders: Option<&MaterialShaders>,
+    ) -> Option<wgpu::ShaderModule> {
+        if let Some(module) = self.material_modules.get(&(shader, defs)) {
+            return module.clone();
+        }
+        let source = shaders.and_then(|shaders| shaders.source(shader));
+        let module = source.and_then(|source| match compile_with_defs(gpu, source, defs, "material shader") {
+            Ok(module) => Some(module),
+            Err(err) => {
+                let path = shaders.and_then(|shaders| shaders.path(shader));
+                log::warn!(
+                    "Material shader '{}' failed to compile; using the PBR shader: {err}",
+                    path.map_or("?".into(), |p| p.display().to_string())
+                );
+                None
+            }
+        });
+        self.material_modules.insert((shader, defs), module.clone());
+        module
+    }
+
+    /// The pipeline to draw `key` with: its variant if it built, else the
+    /// PBR pipeline for the same mesh layout, else the base pipeline.
+    pub fn pipeline_for(&self, key: PipelineKey) -> &wgpu::RenderPipeline {
+        let base = if key.prepassed {
+            &self.prepassed_pipeline
+        } else {
+            &self.pipeline
+        };
+        if key.is_base() {
+            return base;
+        }
+        let built = |key: PipelineKey| self.variants.get(&key).and_then(Option::as_ref);
+        built(key)
+            .or_else(|| built(PipelineKey { shader: None, ..key }))
+            .unwrap_or(base)
+    }
+}
+
+/// Preprocess `source` with `defs` and compile it, catching validation
+/// errors.
+fn compile_with_defs(
+    gpu: &GpuContext,
+    source: &str,
+    defs: PbrDefs,
+    label: &str,
+) -> Result<wgpu::ShaderModule, String> {
+    let source = preprocess(source, &defs.shader_defs()).map_err(|err| err.to_string())?;
+    gpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+    let module = gpu.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    match pollster::block_on(gpu.device.pop_error_scope()) {
+        None => Ok(module),
+        Some(err) => Err(err.to_string()),
+    }
+}
+
+/// Create the PBR render pipeline. After a depth prepass the depth buffer
+/// already holds the nearest surface, so the test becomes `LessEqual` and
+/// depth writes are skipped; transparent `output`s skip them too. `extra`
+/// adds the slot-1 layout for a mesh's optional attributes. Drawing into
+/// the [HDR](super::hdr) target sets the shader's `hdr_output` override.
+#[allow(clippy::too_many_arguments)]
+fn create_pbr_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::PipelineLayout,
+    shader: &wgpu::ShaderModule,
+    format: wgpu::TextureFormat,
+    depth_prepass: bool,
+    output: ColorOutput,
+    extra: Option<&ExtraVertexLayout>,
+    vertex_entry_point: &str,
+    fragment_entry_point: &str,
+    samples: u32,
+) -> wgpu::RenderPipeline {
+    let buffers = match extra {
+        Some(extra) => vec![MeshVertex::LAYOUT, extra.layout()],
+        None => vec![MeshVertex::LAYOUT],
+    };
+    let target = |blend| {
+        [Some(wgpu::ColorTargetState {
+            format,
+            blend,
+            write_mask: wgpu::ColorWrites::ALL,
+        })]
+    };
+    let opaque = target(None);
+    let blended = target(Some(wgpu::BlendState::ALPHA_BLENDING));
+    let accumulated = accumulation_targets();
+    let (targets, label): (&[_], _) = match output {
+        ColorOutput::Opaque if depth_prepass => (&opaque, "3d pbr pipeline (prepassed)"),
+        ColorOutput::Opaque => (&opaque, "3d pbr pipeline"),
+        ColorOutput::Blended => (&blended, "3d pbr pipeline (blended)"),
+        ColorOutput::Accumulated => (&accumulated, "3d pbr pipeline (oit)"),
+    };
+    let opaque = output == ColorOutput::Opaque;
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(label),
+        layout: Some(layout),
+        vertex: wgpu::VertexState {
+            module: shader,
+            entry_point: Some(vertex_entry_point),
+            buffers: &buffers,
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(fragment_entry_point),
+            targets,
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants: if format == HDR_FORMAT { &[("hdr_output", 1.0)] } else { &[] },
+                ..Default::default()
             },
-            fragment: Some(wgpu::FragmentState {
-                module: shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: gpu.surface_format(),
-                    blend: None,
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: Some(wgpu::Face::Back),
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
+        }),
+        primitive: mesh_primitive_state(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: opaque && !depth_prepass,
+            depth_compare: if depth_prepass && opaque {
+                wgpu::CompareFunction::LessEqual
+            } else {
+                wgpu::CompareFunction::Less
             },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: DEPTH_FORMAT,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less,
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        })
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: multisample_state(samples),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Create the depth-only prepass pipeline: vertex stage only, no color target.
+fn create_prepass_pipeline(
+    device: &wgpu::Device,
+    camera_layout: &wgpu::BindGroupLayout,
+    model_layout: &wgpu::BindGroupLayout,
+    samples: u32,
+) -> wgpu::RenderPipeline {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("3d depth prepass shader"),
+        source: wgpu::ShaderSource::Wgsl(include_str!("prepass.wgsl").into()),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("3d depth prepass layout"),
+        bind_group_layouts: &[camera_layout, model_layout],
+        push_constant_ranges: &[],
+    });
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("3d depth prepass pipeline"),
+        layout: Some(&layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[MeshVertex::LAYOUT],
+            compilation_options: Default::default(),
+        },
+        fragment: None,
+        primitive: mesh_primitive_state(),
+        depth_stencil: Some(wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: wgpu::CompareFunction::Less,
+            stencil: wgpu::StencilState::default(),
+            bias: wgpu::DepthBiasState::default(),
+        }),
+        multisample: multisample_state(samples),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Triangle lists with back-face culling — shared by the PBR and prepass
+/// pipelines so they rasterize identical coverage.
+fn mesh_primitive_state() -> wgpu::PrimitiveState {
+    wgpu::PrimitiveState {
+        topology: wgpu::PrimitiveTopology::TriangleList,
+        strip_index_format: None,
+        front_face: wgpu::FrontFace::Ccw,
+        cull_mode: Some(wgpu::Face::Back),
+        polygon_mode: wgpu::PolygonMode::Fill,
+        unclipped_depth: false,
+        conservative: false,
     }
 }
 
-/// Create a depth texture at the given dimensions.
-fn create_depth_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu::TextureView {
+/// Create a depth texture at the given dimensions and sample count.
+fn create_depth_texture(device: &wgpu::Device, width: u32, height: u32, samples: u32) -> wgpu::TextureView {
     let texture = device.create_texture(&wgpu::TextureDescriptor {
         label: Some("3d depth texture"),
         size: wgpu::Extent3d {
@@ -426,7 +889,7 @@ fn create_depth_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu:
             depth_or_array_layers: 1,
         },
         mip_level_count: 1,
-        sample_count: 1,
+        sample_count: samples,
         dimension: wgpu::TextureDimension::D2,
         format: DEPTH_FORMAT,
         usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
@@ -435,6 +898,37 @@ fn create_depth_texture(device: &wgpu::Device, width: u32, height: u32) -> wgpu:
     texture.create_view(&wgpu::TextureViewDescriptor::default())
 }
 
+/// Create a storage buffer for `capacity` lights of type `T`.
+fn create_light_storage<T>(device: &wgpu::Device, label: &str, capacity: usize) -> wgpu::Buffer {
+    device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some(label),
+        size: (std::mem::size_of::<T>() * capacity.max(1)) as u64,
+        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    })
+}
+
+/// Bind the light uniform and the point and spot light buffers (group 1).
+fn create_light_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    buffers: [&wgpu::Buffer; 3],
+) -> wgpu::BindGroup {
+    let entries: Vec<_> = buffers
+        .iter()
+        .enumerate()
+        .map(|(binding, buffer)| wgpu::BindGroupEntry {
+            binding: binding as u32,
+            resource: buffer.as_entire_binding(),
+        })
+        .collect();
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("3d light bind group"),
+        layout,
+        entries: &entries,
+    })
+}
+
 /// Create a dynamic model uniform buffer with the given capacity.
 fn create_model_buffer(
     device: &wgpu::Device,
@@ -472,3 +966,23 @@ fn create_model_buffer(
 fn align_up(value: usize, align: usize) -> usize {
     (value + align - 1) & !(align - 1)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_pbr_variant_compiles() {
+        use wgpu::naga::valid::{Capabilities, ValidationFlags, Validator};
+
+        for bits in 0..1 << PbrDefs::NAMES.len() {
+            let defs = PbrDefs(bits);
+            let source = preprocess(PBR_SOURCE, &defs.shader_defs()).unwrap();
+            let module = wgpu::naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|err| panic!("{defs:?}: {}", err.emit_to_string(&source)));
+            Validator::new(ValidationFlags::all(), Capabilities::default())
+                .validate(&module)
+                .unwrap_or_else(|err| panic!("{defs:?}: {err:?}"));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+// ============================================================================
+// Depth Prepass — Fill the depth buffer before shading
+//
+// Draws every opaque mesh with no fragment shader and no color target, so the
+// GPU only writes depth. The main PBR pass then runs with depth compare
+// `LessEqual` and depth writes off: only the nearest surface at each pixel
+// passes the test, so the expensive lighting math runs once per pixel no
+// matter how many meshes overlap.
+//
+// The clip-space position MUST be computed exactly like `vs_main` in
+// shader.wgsl — same uniforms, same operation order — and both outputs are
+// marked `@invariant` so the compiler can't produce slightly different depth
+// values that would fail the equality test.
+// ============================================================================
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+};
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+};
+// Same bind group layout as the PBR shader's group 3, bound here at group 1.
+@group(1) @binding(0)
+var<uniform> model: ModelUniform;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) @invariant clip_position: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    let world_pos = model.model * vec4<f32>(in.position, 1.0);
+    out.clip_position = camera.view_proj * world_pos;
+    return out;
+}
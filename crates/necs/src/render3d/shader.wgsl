@@ -28,6 +28,11 @@
 //
 // The three functions D, F, G model how microfacets on the surface
 // distribute, reflect, and shadow light. See each function below for details.
+//
+// Shader defs: the renderer preprocesses this file (render/shader_defs.rs)
+// with one def per texture map the material sets — NORMAL_MAP,
+// METALLIC_ROUGHNESS_MAP, EMISSIVE_MAP, OCCLUSION_MAP — so materials
+// without a map skip sampling it entirely.
 // ============================================================================
 
 // ── Bind Group 0: Camera (per frame) ────────────────────────────────────────
@@ -46,6 +51,21 @@ struct PointLightData {
     radius: f32,
     color: vec3<f32>,
     intensity: f32,
+    // 0 = inverse square, 1 = linear, 2 = smooth (see `attenuate`)
+    attenuation: u32,
+};
+
+struct SpotLightData {
+    position: vec3<f32>,
+    range: f32,
+    color: vec3<f32>,
+    intensity: f32,
+    // Unit vector the cone points along.
+    direction: vec3<f32>,
+    // Cosines of the half-angles: full strength inside inner, zero outside outer.
+    cos_inner: f32,
+    cos_outer: f32,
+    attenuation: u32,
 };
 
 struct LightUniform {
@@ -57,16 +77,20 @@ struct LightUniform {
     // Ambient
     ambient_color: vec3<f32>,
     ambient_intensity: f32,
-    // Point lights (fixed array of 8)
-    point_lights: array<PointLightData, 8>,
-    // Count (use individual u32 padding to match Rust [u32; 3] alignment)
+    // Entries of the arrays below in use (the buffers may be larger).
     point_light_count: u32,
-    _pad1a: u32,
-    _pad1b: u32,
-    _pad1c: u32,
+    spot_light_count: u32,
+    // Hemisphere ambient from the Skybox (zero intensity without one).
+    sky_color: vec3<f32>,
+    sky_intensity: f32,
+    ground_color: vec3<f32>,
 };
 @group(1) @binding(0)
 var<uniform> lights: LightUniform;
+@group(1) @binding(1)
+var<storage, read> point_lights: array<PointLightData>;
+@group(1) @binding(2)
+var<storage, read> spot_lights: array<SpotLightData>;
 
 // ── Bind Group 2: Material (per material) ───────────────────────────────────
 
@@ -74,9 +98,12 @@ struct MaterialUniform {
     base_color: vec4<f32>,
     metallic: f32,
     roughness: f32,
-    _pad0: vec2<f32>,
+    normal_scale: f32,
+    ao_strength: f32,
     emissive: vec3<f32>,
-    _pad1: f32,
+    // 1 if normal_texture is a real normal map (unset maps are white). This
+    // shader tests NORMAL_MAP instead; kept for material shaders.
+    normal_mapped: u32,
 };
 @group(2) @binding(0)
 var<uniform> material: MaterialUniform;
@@ -84,6 +111,24 @@ var<uniform> material: MaterialUniform;
 var base_color_texture: texture_2d<f32>;
 @group(2) @binding(2)
 var base_color_sampler: sampler;
+@group(2) @binding(3)
+var normal_texture: texture_2d<f32>;
+@group(2) @binding(4)
+var normal_sampler: sampler;
+// Green = roughness, blue = metallic (glTF packing).
+@group(2) @binding(5)
+var metallic_roughness_texture: texture_2d<f32>;
+@group(2) @binding(6)
+var metallic_roughness_sampler: sampler;
+@group(2) @binding(7)
+var emissive_texture: texture_2d<f32>;
+@group(2) @binding(8)
+var emissive_sampler: sampler;
+// Red = ambient occlusion.
+@group(2) @binding(9)
+var ao_texture: texture_2d<f32>;
+@group(2) @binding(10)
+var ao_sampler: sampler;
 
 // ── Bind Group 3: Model (per object, dynamic offset) ────────────────────────
 
@@ -94,6 +139,10 @@ struct ModelUniform {
 @group(3) @binding(0)
 var<uniform> model: ModelUniform;
 
+// Set by the pipeline when drawing into the HDR target of a PostProcess,
+// which tone maps the whole scene afterwards (see hdr.wgsl).
+override hdr_output: bool = false;
+
 // ── Vertex Shader ───────────────────────────────────────────────────────────
 
 struct VertexInput {
@@ -103,14 +152,17 @@ struct VertexInput {
 };
 
 struct VertexOutput {
-    @builtin(position) clip_position: vec4<f32>,
+    // @invariant: must match prepass.wgsl bit-for-bit for the depth prepass.
+    @builtin(position) @invariant clip_position: vec4<f32>,
     @location(0) world_pos: vec3<f32>,
     @location(1) world_normal: vec3<f32>,
     @location(2) uv: vec2<f32>,
+    // Vertex color, multiplied into the base color (white if the mesh has none).
+    @location(3) color: vec4<f32>,
 };
 
-@vertex
-fn vs_main(in: VertexInput) -> VertexOutput {
+// Shared by both vertex entry points (WGSL can't call an entry point).
+fn transform_vertex(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
 
     // Transform position from local space → world space → clip space.
@@ -124,6 +176,22 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.world_normal = normalize((model.normal_matrix * vec4<f32>(in.normal, 0.0)).xyz);
 
     out.uv = in.uv;
+    out.color = vec4<f32>(1.0);
+    return out;
+}
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    return transform_vertex(in);
+}
+
+// Entry point for meshes with vertex colors (e.g. baked ambient occlusion).
+// The pipeline picks it when the mesh's slot-1 buffer has a COLOR attribute;
+// see vertex.rs for the optional attribute layout.
+@vertex
+fn vs_main_colored(in: VertexInput, @location(3) color: vec4<f32>) -> VertexOutput {
+    var out = transform_vertex(in);
+    out.color = color;
     return out;
 }
 
@@ -222,18 +290,101 @@ fn compute_light(
     return (diffuse + specular) * light_radiance * n_dot_l;
 }
 
+// ── Texture Maps ────────────────────────────────────────────────────────────
+
+// Color maps (base color, emissive) are sRGB textures, so sampling decodes
+// them; the data maps (normal, metallic-roughness, AO) are plain Rgba8Unorm.
+
+// Bend the surface normal by the normal map.
+//
+// A normal map stores directions in *tangent space*: X along the texture's
+// U axis, Y up the image, Z out of the surface. Meshes here carry no
+// tangents, so the frame is rebuilt per pixel from how world position and
+// UV change across neighboring pixels (Schüler's "cotangent frame"):
+//
+//   T = direction in which u grows, B = direction in which v shrinks
+//   (glTF images start at the top, so "up the image" is -v), N = normal
+//
+// `sampled` is the map's stored value in [0, 1], remapped to [-1, 1].
+fn apply_normal_map(normal: vec3<f32>, world_pos: vec3<f32>, uv: vec2<f32>, sampled: vec3<f32>) -> vec3<f32> {
+    let dp1 = dpdx(world_pos);
+    let dp2 = dpdy(world_pos);
+    let duv1 = dpdx(uv);
+    let duv2 = dpdy(uv);
+
+    // Solve for the world-space directions of +u and +v.
+    let dp2_perp = cross(dp2, normal);
+    let dp1_perp = cross(normal, dp1);
+    let t = dp2_perp * duv1.x + dp1_perp * duv2.x;
+    let b = dp2_perp * duv1.y + dp1_perp * duv2.y;
+    // Scale-invariant: one factor for both keeps the frame's shape.
+    let inv_max = inverseSqrt(max(max(dot(t, t), dot(b, b)), 1e-20));
+
+    var tangent_normal = sampled * 2.0 - 1.0;
+    tangent_normal = vec3<f32>(tangent_normal.xy * material.normal_scale, tangent_normal.z);
+    let tbn = mat3x3<f32>(t * inv_max, -b * inv_max, normal);
+    return normalize(tbn * tangent_normal);
+}
+
+// ── Attenuation ─────────────────────────────────────────────────────────────
+
+// 0 → 1 along an S-curve, clamped (smoothstep without its edge arguments).
+fn smooth_fade(x: f32) -> f32 {
+    let t = clamp(x, 0.0, 1.0);
+    return t * t * (3.0 - 2.0 * t);
+}
+
+// How much of a light's intensity reaches `distance`, reaching zero at
+// `range` for every curve:
+//
+//   0 inverse square: saturate(1 - (d/r)⁴) × 1/d²
+//     Physically based. The (d/r)⁴ window fades to zero at the range
+//     instead of cutting off.
+//   1 linear:         1 - d/r
+//   2 smooth:         S-curve from 1 at the light to 0 at the range
+fn attenuate(distance: f32, range: f32, mode: u32) -> f32 {
+    let x = distance / range;
+    switch mode {
+        case 1u: {
+            return clamp(1.0 - x, 0.0, 1.0);
+        }
+        case 2u: {
+            return smooth_fade(1.0 - x);
+        }
+        default: {
+            let x2 = x * x;
+            let falloff = clamp(1.0 - x2 * x2, 0.0, 1.0);
+            return falloff / (distance * distance + 0.0001);
+        }
+    }
+}
+
 // ── Fragment Shader ─────────────────────────────────────────────────────────
 
-@fragment
-fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+// Lit, tone-mapped color. Alpha is the material's (texture × base color ×
+// vertex color); only the transparent entry points below use it.
+fn shade(in: VertexOutput) -> vec4<f32> {
     // Sample base color texture and multiply by material color
     let tex_color = textureSample(base_color_texture, base_color_sampler, in.uv);
-    let base_color = tex_color.rgb * material.base_color.rgb;
-
-    let metallic = material.metallic;
-    let roughness = max(material.roughness, 0.04); // clamp to avoid singularity
-
-    let normal = normalize(in.world_normal);
+    let base_color = tex_color.rgb * material.base_color.rgb * in.color.rgb;
+    let alpha = tex_color.a * material.base_color.a * in.color.a;
+
+    var metallic = material.metallic;
+    var roughness = material.roughness;
+#ifdef METALLIC_ROUGHNESS_MAP
+    // Metallic-roughness map: factors × (blue, green).
+    let metallic_roughness =
+        textureSample(metallic_roughness_texture, metallic_roughness_sampler, in.uv).rgb;
+    metallic *= metallic_roughness.b;
+    roughness *= metallic_roughness.g;
+#endif
+    roughness = max(roughness, 0.04); // clamp to avoid singularity
+
+    var normal = normalize(in.world_normal);
+#ifdef NORMAL_MAP
+    let normal_sample = textureSample(normal_texture, normal_sampler, in.uv).rgb;
+    normal = apply_normal_map(normal, in.world_pos, in.uv, normal_sample);
+#endif
     let view_dir = normalize(camera.camera_pos - in.world_pos);
 
     // F0: reflectance at normal incidence
@@ -252,7 +403,7 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
 
     // ── Point lights ────────────────────────────────────────────────────
     for (var i = 0u; i < lights.point_light_count; i++) {
-        let pl = lights.point_lights[i];
+        let pl = point_lights[i];
         let to_light = pl.position - in.world_pos;
         let distance = length(to_light);
 
@@ -262,31 +413,108 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         }
 
         let light_dir = to_light / distance;
+        let attenuation = attenuate(distance, pl.radius, pl.attenuation);
+        let radiance = pl.color * pl.intensity * attenuation;
+        lo += compute_light(light_dir, radiance, normal, view_dir, base_color, metallic, roughness, f0);
+    }
 
-        // Inverse-square attenuation with smooth falloff at radius boundary
-        //   attenuation = saturate(1 - (d/r)⁴) × 1/d²
-        // The (d/r)⁴ term smoothly fades to zero at the radius edge,
-        // preventing a hard cutoff.
-        let d_over_r = distance / pl.radius;
-        let d_over_r2 = d_over_r * d_over_r;
-        let falloff = clamp(1.0 - d_over_r2 * d_over_r2, 0.0, 1.0);
-        let attenuation = falloff / (distance * distance + 0.0001);
+    // ── Spot lights ─────────────────────────────────────────────────────
+    // A point light masked to a cone: compare the angle between the cone's
+    // axis and the direction to this pixel (as cosines) against the inner
+    // and outer half-angles.
+    for (var i = 0u; i < lights.spot_light_count; i++) {
+        let sl = spot_lights[i];
+        let to_light = sl.position - in.world_pos;
+        let distance = length(to_light);
+        if distance > sl.range {
+            continue;
+        }
 
-        let radiance = pl.color * pl.intensity * attenuation;
+        let light_dir = to_light / distance;
+        let cos_angle = dot(-light_dir, sl.direction);
+        let cone = smooth_fade((cos_angle - sl.cos_outer) / max(sl.cos_inner - sl.cos_outer, 1e-4));
+        if cone <= 0.0 {
+            continue;
+        }
+
+        let attenuation = attenuate(distance, sl.range, sl.attenuation) * cone;
+        let radiance = sl.color * sl.intensity * attenuation;
         lo += compute_light(light_dir, radiance, normal, view_dir, base_color, metallic, roughness, f0);
     }
 
     // ── Ambient ─────────────────────────────────────────────────────────
-    // A simple constant term to prevent pure-black shadows. In a production
-    // renderer, this would be replaced by image-based lighting (IBL) or
-    // screen-space ambient occlusion (SSAO).
-    let ambient = lights.ambient_color * lights.ambient_intensity * base_color;
+    // A constant term to prevent pure-black shadows, plus the Skybox's
+    // hemisphere light: its sky color from above, its ground color from
+    // below. A production renderer would use full image-based lighting
+    // (IBL) and screen-space ambient occlusion (SSAO). The AO map darkens
+    // creases the ambient light wouldn't reach; direct light has its own
+    // falloff.
+    var occlusion = 1.0;
+#ifdef OCCLUSION_MAP
+    let ao_sample = textureSample(ao_texture, ao_sampler, in.uv).r;
+    occlusion = mix(1.0, ao_sample, material.ao_strength);
+#endif
+    let hemisphere = mix(lights.ground_color, lights.sky_color, normal.y * 0.5 + 0.5) * lights.sky_intensity;
+    let ambient = (lights.ambient_color * lights.ambient_intensity + hemisphere) * base_color * occlusion;
 
     // ── Final color ─────────────────────────────────────────────────────
-    var color = ambient + lo + material.emissive;
+    var emissive = material.emissive;
+#ifdef EMISSIVE_MAP
+    emissive *= textureSample(emissive_texture, emissive_sampler, in.uv).rgb;
+#endif
+    var color = ambient + lo + emissive;
 
     // Simple Reinhard tone mapping: maps HDR [0, ∞) to LDR [0, 1)
-    // Without this, bright highlights would clip to white.
-    color = color / (color + vec3<f32>(1.0));
-    return vec4<f32>(color, 1.0);
+    // Without this, bright highlights would clip to white. An HDR target
+    // keeps the real values for bloom and the post-process tone mapping.
+    if !hdr_output {
+        color = color / (color + vec3<f32>(1.0));
+    }
+    return vec4<f32>(color, alpha);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(shade(in).rgb, 1.0);
+}
+
+// Entry point for `AlphaMode::Blend` materials drawn sorted.
+@fragment
+fn fs_blend(in: VertexOutput) -> @location(0) vec4<f32> {
+    return shade(in);
+}
+
+// ── Weighted-Blended OIT ────────────────────────────────────────────────────
+//
+// Transparent meshes under `Transparency::WeightedBlended` write here instead
+// of blending onto the scene. Both targets blend in any order:
+//
+//   accum     += (rgb × a, a) × w     (One, One)
+//   revealage ×= (1 − a)              (Zero, OneMinusSrc)
+//
+// The composite pass (oit.wgsl) divides accum.rgb by accum.a for a weighted
+// average color and covers the scene by 1 − revealage.
+
+struct OitOutput {
+    @location(0) accum: vec4<f32>,
+    @location(1) revealage: f32,
+};
+
+// Weight from McGuire & Bavoil 2013 (eq. 10, depth-buffer variant): nearer
+// and more opaque fragments dominate the average. The clamp keeps the sum
+// inside f16 range.
+fn oit_weight(depth: f32, alpha: f32) -> f32 {
+    let a = min(1.0, alpha * 10.0) + 0.01;
+    let d = 1.0 - depth * 0.9;
+    return clamp(a * a * a * 1e8 * d * d * d, 1e-2, 3e3);
+}
+
+@fragment
+fn fs_oit(in: VertexOutput) -> OitOutput {
+    let color = shade(in);
+    let weight = oit_weight(in.clip_position.z, color.a);
+    var out: OitOutput;
+    out.accum = vec4<f32>(color.rgb * color.a, color.a) * weight;
+    out.revealage = color.a;
+    return out;
 }
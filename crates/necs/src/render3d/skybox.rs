@@ -0,0 +1,683 @@
+//! # Skybox — The Environment Behind the Scene
+//!
+//! Anything the camera sees that no mesh covers is *sky*. Instead of a flat
+//! clear color, a [`Skybox`] resource fills those pixels with an image of a
+//! distant environment, looked up by view direction only — the camera can
+//! move forever and never get closer to it:
+//!
+//! ```text
+//!            +Y                          cubemap: 6 square faces
+//!             │   sky direction            ┌────┐
+//!             │  ╱                          │ +Y │
+//!             │ ╱                      ┌────┼────┼────┬────┐
+//!   camera ●──┼──── −Z (forward)       │ −X │ +Z │ +X │ −Z │
+//!                                      └────┼────┼────┴────┘
+//!                                           │ −Y │
+//!                                           └────┘
+//!                                      equirectangular: one 2:1 image,
+//!                                      longitude across, latitude down
+//! ```
+//!
+//! Two source layouts are accepted:
+//!
+//! - **Cubemap** — six images, given in the order +X, −X, +Y, −Y, +Z, −Z
+//!   (the order every cubemap tool exports).
+//! - **Equirectangular** — a single latitude-longitude panorama, usually an
+//!   `.hdr` file from an HDRI library. The image center faces −Z.
+//!
+//! `.hdr` and other float images are used as-is (linear light, values above
+//! 1.0 kept for bloom); 8-bit images are treated as sRGB. The sky is drawn
+//! in its own pass before the scene: a fullscreen triangle whose fragment
+//! shader unprojects each pixel into a direction. Opaque geometry then
+//! draws over it. The sky replaces the camera's clear color, except under
+//! [`CameraClear::Load`](crate::render::CameraClear), which keeps whatever
+//! is already in the target.
+//!
+//! ## Sky Ambient
+//!
+//! A real environment lights the scene too — a blue sky tints everything
+//! facing up, green grass what faces down. Setting [`Skybox::ambient`]
+//! above zero approximates this *image-based lighting* with two colors
+//! averaged from the image when it loads: the upper hemisphere (sky) and
+//! the lower one (ground), each weighted by how directly it faces straight
+//! up or down. The PBR shader blends between them by the surface normal:
+//!
+//! ```text
+//!   ambient = AmbientLight + mix(ground, sky, normal.y × 0.5 + 0.5) × ambient
+//! ```
+//!
+//! Real IBL convolves the whole image per direction and adds specular
+//! reflections; two colors capture most of the mood for a tiny cost.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Skybox material (6-sided, cubemap or panoramic) in the
+//!   Lighting window; ambient from the skybox as spherical harmonics.
+//! - **Bevy**: `Skybox` component with a cubemap, plus a separate
+//!   `EnvironmentMapLight` for prefiltered diffuse and specular IBL.
+//! - **Godot**: `Sky` resource in the `WorldEnvironment` with panorama or
+//!   procedural materials; ambient and reflections can come from the sky.
+//! - **Our approach**: Unity's setup — one resource for the background and
+//!   its ambient — with Godot's hemisphere-style ambient instead of
+//!   spherical harmonics.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use super::collect::ExtractedCamera3d;
+use super::hdr::HDR_FORMAT;
+use super::{Camera3d, Projection3d};
+use crate::ecs::World;
+use crate::render::gpu::GpuContext;
+use crate::render::msaa::{multisample_state, ColorTarget, TargetKey};
+use crate::render::pass::FrameContext;
+use crate::render::shader_defs::{preprocess, ShaderDefs};
+
+const SKYBOX_SOURCE: &str = include_str!("skybox.wgsl");
+
+/// Texture format of loaded skies: half floats keep HDR values and stay
+/// filterable on every GPU.
+const SKY_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+
+/// Upper bound on texels read per image (or cube face) for the ambient
+/// colors, along each axis. Averages need far fewer than a full HDRI.
+const AMBIENT_SAMPLES: u32 = 64;
+
+// ── Public API ──────────────────────────────────────────────────────────
+
+/// Where a [`Skybox`] image comes from. Paths are relative to the asset
+/// directory, like every other asset.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SkyboxSource {
+    /// Six square faces in the order +X, −X, +Y, −Y, +Z, −Z.
+    Cubemap([String; 6]),
+    /// One latitude-longitude panorama, twice as wide as it is tall.
+    Equirectangular(String),
+}
+
+/// Environment drawn behind all 3D geometry (see the [module docs](self)).
+///
+/// ```ignore
+/// world.insert_resource(Skybox::equirectangular("sky/meadow.hdr").ambient(0.5));
+/// world.insert_resource(Skybox::cubemap([
+///     "sky/px.png".into(), "sky/nx.png".into(),
+///     "sky/py.png".into(), "sky/ny.png".into(),
+///     "sky/pz.png".into(), "sky/nz.png".into(),
+/// ]));
+/// ```
+///
+/// Images load the first frame the resource is seen (and whenever the
+/// source changes), blocking that frame. If they fail to load, a warning
+/// is logged and the camera's clear color shows instead. Register it with
+/// [`SceneRegistry::register_resource`](crate::scene::SceneRegistry::register_resource)
+/// to save it with scenes.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Skybox {
+    pub source: SkyboxSource,
+    /// Multiplier on the image's colors, for the background and the
+    /// ambient light alike.
+    pub brightness: f32,
+    /// Strength of the ambient light taken from the sky. 0.0 (default)
+    /// leaves lighting to [`AmbientLight`](super::AmbientLight) alone.
+    pub ambient: f32,
+    /// Rotation of the environment about the world Y axis, in degrees.
+    pub rotation: f32,
+}
+
+impl Skybox {
+    /// A skybox from six cubemap faces: +X, −X, +Y, −Y, +Z, −Z.
+    pub fn cubemap(faces: [String; 6]) -> Self {
+        Self::new(SkyboxSource::Cubemap(faces))
+    }
+
+    /// A skybox from one equirectangular (latitude-longitude) image.
+    pub fn equirectangular(path: impl Into<String>) -> Self {
+        Self::new(SkyboxSource::Equirectangular(path.into()))
+    }
+
+    fn new(source: SkyboxSource) -> Self {
+        Self {
+            source,
+            brightness: 1.0,
+            ambient: 0.0,
+            rotation: 0.0,
+        }
+    }
+
+    /// Set the brightness multiplier (builder pattern).
+    pub fn brightness(mut self, brightness: f32) -> Self {
+        self.brightness = brightness;
+        self
+    }
+
+    /// Set the sky ambient strength (builder pattern).
+    pub fn ambient(mut self, ambient: f32) -> Self {
+        self.ambient = ambient;
+        self
+    }
+
+    /// Set the rotation about Y in degrees (builder pattern).
+    pub fn rotation(mut self, degrees: f32) -> Self {
+        self.rotation = degrees;
+        self
+    }
+}
+
+// ── Sky ambient ─────────────────────────────────────────────────────────
+
+/// Average light arriving from the upper and lower hemispheres, in linear
+/// RGB before [`Skybox::brightness`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct SkyAmbient {
+    pub sky: [f32; 3],
+    pub ground: [f32; 3],
+}
+
+/// The sky ambient colors and their strength, if the current [`Skybox`]
+/// has loaded and asks for ambient light.
+pub(crate) fn sky_ambient(world: &World) -> Option<(SkyAmbient, f32)> {
+    let skybox = world.get_resource::<Skybox>()?;
+    let sky = world.get_resource::<SkyboxRenderer>()?.sky.as_ref()?;
+    (skybox.ambient > 0.0 && sky.source == skybox.source)
+        .then_some((sky.ambient, skybox.ambient * skybox.brightness))
+}
+
+/// Cosine-weighted averages over the two hemispheres. Each sample is the
+/// Y component of its direction, the solid angle it covers (any common
+/// scale) and its color.
+fn hemisphere_ambient(samples: impl Iterator<Item = (f32, f32, [f32; 3])>) -> SkyAmbient {
+    let mut sums = [[0.0f64; 4]; 2];
+    for (y, solid_angle, color) in samples {
+        let (sum, weight) = if y >= 0.0 {
+            (&mut sums[0], y * solid_angle)
+        } else {
+            (&mut sums[1], -y * solid_angle)
+        };
+        for (total, channel) in sum.iter_mut().zip(color) {
+            *total += (channel * weight) as f64;
+        }
+        sum[3] += weight as f64;
+    }
+    let average = |sum: [f64; 4]| {
+        let weight = sum[3].max(f64::MIN_POSITIVE);
+        [0, 1, 2].map(|i| (sum[i] / weight) as f32)
+    };
+    SkyAmbient {
+        sky: average(sums[0]),
+        ground: average(sums[1]),
+    }
+}
+
+/// Every `step`-th row and column index of `size` texels, at texel centers.
+fn sample_grid(size: u32) -> impl Iterator<Item = u32> + Clone {
+    (0..size).step_by(size.div_ceil(AMBIENT_SAMPLES).max(1) as usize)
+}
+
+/// Ambient samples of a latitude-longitude image. Rows shrink towards the
+/// poles, so each texel covers `sin(latitude from the pole)` of solid angle.
+fn equirect_samples(image: &SkyImage) -> impl Iterator<Item = (f32, f32, [f32; 3])> + '_ {
+    sample_grid(image.height).flat_map(move |row| {
+        let theta = (row as f32 + 0.5) / image.height as f32 * std::f32::consts::PI;
+        sample_grid(image.width).map(move |column| (theta.cos(), theta.sin(), image.rgb(column, row)))
+    })
+}
+
+/// Ambient samples of six cube faces (+X, −X, +Y, −Y, +Z, −Z). Side faces
+/// have +Y at their top row; texels far from a face's center cover less
+/// solid angle.
+fn cube_samples(faces: &[SkyImage]) -> impl Iterator<Item = (f32, f32, [f32; 3])> + '_ {
+    faces.iter().enumerate().flat_map(|(face, image)| {
+        let size = image.width;
+        sample_grid(size).flat_map(move |row| {
+            sample_grid(size).map(move |column| {
+                let a = (column as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let b = (row as f32 + 0.5) / size as f32 * 2.0 - 1.0;
+                let length = (1.0 + a * a + b * b).sqrt();
+                let y = match face {
+                    2 => 1.0,
+                    3 => -1.0,
+                    _ => -b,
+                } / length;
+                (y, length.powi(-3), image.rgb(column, row))
+            })
+        })
+    })
+}
+
+// ── Image loading ───────────────────────────────────────────────────────
+
+/// A decoded sky image in linear RGBA.
+struct SkyImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl SkyImage {
+    fn rgb(&self, x: u32, y: u32) -> [f32; 3] {
+        let [r, g, b, _] = self.pixels[(y * self.width + x) as usize];
+        [r, g, b]
+    }
+
+    /// The pixels as half floats, for [`SKY_FORMAT`].
+    fn to_f16_bytes(&self) -> Vec<u8> {
+        let halves: Vec<u16> = self
+            .pixels
+            .iter()
+            .flatten()
+            .map(|&value| half::f16::from_f32(value).to_bits())
+            .collect();
+        bytemuck::cast_slice(&halves).to_vec()
+    }
+}
+
+/// Decode an image file to linear RGBA. Float formats (`.hdr`, `.exr`)
+/// already are linear; everything else is sRGB-decoded.
+fn load_image(path: &Path) -> Result<SkyImage, String> {
+    let image = image::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let linear = matches!(image, image::DynamicImage::ImageRgb32F(_) | image::DynamicImage::ImageRgba32F(_));
+    let rgba = image.to_rgba32f();
+    let (width, height) = rgba.dimensions();
+    let mut pixels: Vec<[f32; 4]> = bytemuck::cast_slice(rgba.as_raw()).to_vec();
+    if !linear {
+        for pixel in &mut pixels {
+            for channel in &mut pixel[..3] {
+                *channel = srgb_to_linear(*channel);
+            }
+        }
+    }
+    Ok(SkyImage { width, height, pixels })
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Decode the images of `source` and upload them as a cube or 2D texture.
+fn load_sky(world: &World, gpu: &GpuContext, source: &SkyboxSource) -> Result<(wgpu::TextureView, SkyAmbient), String> {
+    let paths: Vec<&str> = match source {
+        SkyboxSource::Cubemap(faces) => faces.iter().map(String::as_str).collect(),
+        SkyboxSource::Equirectangular(path) => vec![path],
+    };
+    let images = paths
+        .iter()
+        .map(|path| load_image(Path::new(crate::launch::resolve_asset_path(world, path).as_ref())))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (width, height) = (images[0].width, images[0].height);
+    let max_size = gpu.device.limits().max_texture_dimension_2d;
+    if width > max_size || height > max_size {
+        return Err(format!("{width}×{height} exceeds the GPU's texture size limit of {max_size}"));
+    }
+    let cube = matches!(source, SkyboxSource::Cubemap(_));
+    if cube && images.iter().any(|image| (image.width, image.height) != (width, width)) {
+        return Err("cubemap faces must be square and all the same size".to_owned());
+    }
+
+    let data: Vec<u8> = images.iter().flat_map(SkyImage::to_f16_bytes).collect();
+    let texture = gpu.device.create_texture_with_data(
+        &gpu.queue,
+        &wgpu::TextureDescriptor {
+            label: Some("skybox texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: images.len() as u32,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: SKY_FORMAT,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        },
+        wgpu::util::TextureDataOrder::LayerMajor,
+        &data,
+    );
+    let view = texture.create_view(&wgpu::TextureViewDescriptor {
+        dimension: Some(if cube {
+            wgpu::TextureViewDimension::Cube
+        } else {
+            wgpu::TextureViewDimension::D2
+        }),
+        ..Default::default()
+    });
+    let ambient = if cube {
+        hemisphere_ambient(cube_samples(&images))
+    } else {
+        hemisphere_ambient(equirect_samples(&images[0]))
+    };
+    Ok((view, ambient))
+}
+
+// ── Renderer ────────────────────────────────────────────────────────────
+
+/// Uniform for `skybox.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct SkyUniform {
+    inv_view_proj: [[f32; 4]; 4], // 64 bytes
+    brightness: f32,              // 4 bytes
+    _pad: [f32; 3],               // 12 bytes → 80
+}
+
+/// The sky currently on the GPU.
+struct LoadedSky {
+    source: SkyboxSource,
+    bind_group: wgpu::BindGroup,
+    equirect: bool,
+    ambient: SkyAmbient,
+}
+
+/// GPU state of the [`Skybox`]. Created the first frame one exists.
+pub(crate) struct SkyboxRenderer {
+    /// Bind group layouts for cubemaps and equirectangular images, indexed
+    /// by `equirect as usize`.
+    layouts: [wgpu::BindGroupLayout; 2],
+    uniform_buffer: wgpu::Buffer,
+    sampler: wgpu::Sampler,
+    /// Pipelines per frame target and source layout.
+    pipelines: HashMap<(TargetKey, bool), wgpu::RenderPipeline>,
+    sky: Option<LoadedSky>,
+    /// Source that last failed to load, so the warning isn't repeated
+    /// every frame.
+    failed: Option<SkyboxSource>,
+}
+
+impl SkyboxRenderer {
+    fn new(device: &wgpu::Device) -> Self {
+        let layout = |view_dimension| {
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                label: Some("skybox layout"),
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+            })
+        };
+        let layouts = [layout(wgpu::TextureViewDimension::Cube), layout(wgpu::TextureViewDimension::D2)];
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("skybox uniform"),
+            size: std::mem::size_of::<SkyUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        // Longitude wraps around; latitude stops at the poles.
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("skybox sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            layouts,
+            uniform_buffer,
+            sampler,
+            pipelines: HashMap::new(),
+            sky: None,
+            failed: None,
+        }
+    }
+
+    /// Load `source` unless it is already loaded or already failed.
+    fn load_if_changed(&mut self, world: &World, gpu: &GpuContext, source: &SkyboxSource) {
+        if self.sky.as_ref().is_some_and(|sky| sky.source == *source) || self.failed.as_ref() == Some(source) {
+            return;
+        }
+        match load_sky(world, gpu, source) {
+            Ok((view, ambient)) => {
+                let equirect = matches!(source, SkyboxSource::Equirectangular(_));
+                let bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: Some("skybox bind group"),
+                    layout: &self.layouts[equirect as usize],
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: self.uniform_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::Sampler(&self.sampler),
+                        },
+                    ],
+                });
+                self.sky = Some(LoadedSky {
+                    source: source.clone(),
+                    bind_group,
+                    equirect,
+                    ambient,
+                });
+                self.failed = None;
+            }
+            Err(e) => {
+                log::warn!("Failed to load skybox: {e}");
+                self.sky = None;
+                self.failed = Some(source.clone());
+            }
+        }
+    }
+
+    /// Build the pipeline for `target` and the loaded layout if missing.
+    fn prepare_pipeline(&mut self, device: &wgpu::Device, target: TargetKey, equirect: bool) {
+        let layout = &self.layouts[equirect as usize];
+        self.pipelines
+            .entry((target, equirect))
+            .or_insert_with(|| create_pipeline(device, layout, target, equirect));
+    }
+}
+
+fn create_pipeline(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    (format, samples): TargetKey,
+    equirect: bool,
+) -> wgpu::RenderPipeline {
+    let defs = if equirect {
+        ShaderDefs::new().with("EQUIRECT")
+    } else {
+        ShaderDefs::new()
+    };
+    let source = preprocess(SKYBOX_SOURCE, &defs).expect("skybox.wgsl directives are valid");
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("skybox shader"),
+        source: wgpu::ShaderSource::Wgsl(source.into()),
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("skybox pipeline layout"),
+        bind_group_layouts: &[layout],
+        push_constant_ranges: &[],
+    });
+    let constants: &[(&str, f64)] = if format == HDR_FORMAT { &[("hdr_output", 1.0)] } else { &[] };
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("skybox pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            buffers: &[],
+            compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions {
+                constants,
+                ..Default::default()
+            },
+        }),
+        primitive: wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: multisample_state(samples),
+        multiview: None,
+        cache: None,
+    })
+}
+
+/// Clip space → sky direction for `camera`: the inverse of its projection
+/// and view rotation (the sky ignores translation), turned by the
+/// skybox's `rotation`.
+fn inverse_view_proj(camera: &ExtractedCamera3d, surface_size: (u32, u32), rotation: f32) -> glam::Mat4 {
+    // Orthographic rays are parallel and would all see the same sky color;
+    // look at the sky through the default perspective instead.
+    let projection = match camera.projection {
+        Projection3d::Orthographic(_) => Camera3d::default().projection,
+        perspective => perspective,
+    };
+    let projection = projection.matrix(surface_size, camera.near, camera.far);
+    let view = glam::Mat4::from_mat3(glam::Mat3::from_mat4(camera.matrix)).inverse();
+    glam::Mat4::from_rotation_y(-rotation.to_radians()) * (projection * view).inverse()
+}
+
+/// Draw the [`Skybox`] into `target`, replacing its contents. Returns
+/// whether a sky was drawn: `false` without a `Skybox` or while its images
+/// can't be loaded.
+pub(crate) fn render_skybox(
+    world: &mut World,
+    frame: &mut FrameContext<'_>,
+    target: &ColorTarget,
+    camera: &ExtractedCamera3d,
+) -> bool {
+    let Some(skybox) = world.get_resource::<Skybox>().cloned() else {
+        return false;
+    };
+    let gpu = frame.gpu;
+    let mut renderer = world
+        .resource_remove::<SkyboxRenderer>()
+        .unwrap_or_else(|| SkyboxRenderer::new(&gpu.device));
+    renderer.load_if_changed(world, gpu, &skybox.source);
+    let Some(equirect) = renderer.sky.as_ref().map(|sky| sky.equirect) else {
+        world.insert_resource(renderer);
+        return false;
+    };
+
+    let uniform = SkyUniform {
+        inv_view_proj: inverse_view_proj(camera, gpu.surface_size(), skybox.rotation).to_cols_array_2d(),
+        brightness: skybox.brightness,
+        _pad: [0.0; 3],
+    };
+    gpu.queue.write_buffer(&renderer.uniform_buffer, 0, bytemuck::bytes_of(&uniform));
+    renderer.prepare_pipeline(&gpu.device, target.key(), equirect);
+
+    if let Some(sky) = &renderer.sky
+        && let Some(pipeline) = renderer.pipelines.get(&(target.key(), equirect))
+    {
+        let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("skybox pass"),
+            color_attachments: &[Some(target.attachment(wgpu::LoadOp::Clear(wgpu::Color::BLACK)))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &sky.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+    world.insert_resource(renderer);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(width: u32, height: u32, color: impl Fn(u32) -> [f32; 3]) -> SkyImage {
+        let pixels = (0..width * height)
+            .map(|i| {
+                let [r, g, b] = color(i / width);
+                [r, g, b, 1.0]
+            })
+            .collect();
+        SkyImage { width, height, pixels }
+    }
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.into_iter().zip(expected) {
+            assert!((a - e).abs() < 1e-3, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn hemispheres_average_what_is_above_and_below() {
+        let blue = [0.2, 0.4, 1.0];
+        let uniform = hemisphere_ambient(equirect_samples(&image(256, 128, |_| blue)));
+        assert_close(uniform.sky, blue);
+        assert_close(uniform.ground, blue);
+
+        // Blue above the horizon, green below.
+        let green = [0.1, 0.5, 0.1];
+        let split = image(256, 128, |row| if row < 64 { blue } else { green });
+        let ambient = hemisphere_ambient(equirect_samples(&split));
+        assert_close(ambient.sky, blue);
+        assert_close(ambient.ground, green);
+
+        // The same environment as a cubemap: +Y face and the upper half of
+        // the sides blue, the rest green.
+        let faces: Vec<SkyImage> = (0..6)
+            .map(|face| match face {
+                2 => image(32, 32, |_| blue),
+                3 => image(32, 32, |_| green),
+                _ => image(32, 32, |row| if row < 16 { blue } else { green }),
+            })
+            .collect();
+        let ambient = hemisphere_ambient(cube_samples(&faces));
+        assert_close(ambient.sky, blue);
+        assert_close(ambient.ground, green);
+    }
+
+    #[test]
+    fn both_sky_variants_compile() {
+        use wgpu::naga::valid::{Capabilities, ValidationFlags, Validator};
+
+        for defs in [ShaderDefs::new(), ShaderDefs::new().with("EQUIRECT")] {
+            let source = preprocess(SKYBOX_SOURCE, &defs).unwrap();
+            let module = wgpu::naga::front::wgsl::parse_str(&source)
+                .unwrap_or_else(|err| panic!("{defs:?}: {}", err.emit_to_string(&source)));
+            Validator::new(ValidationFlags::all(), Capabilities::default())
+                .validate(&module)
+                .unwrap_or_else(|err| panic!("{defs:?}: {err:?}"));
+        }
+    }
+}
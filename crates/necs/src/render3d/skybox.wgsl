@@ -0,0 +1,65 @@
+// Skybox background: a fullscreen triangle that looks up the environment in
+// the direction each pixel faces. EQUIRECT selects a 2D latitude-longitude
+// image instead of a cubemap (see skybox.rs).
+
+struct SkyUniform {
+    // Clip space → world direction: inverse(projection × view rotation).
+    inv_view_proj: mat4x4<f32>,
+    brightness: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> sky: SkyUniform;
+#ifdef EQUIRECT
+@group(0) @binding(1)
+var sky_texture: texture_2d<f32>;
+#else
+@group(0) @binding(1)
+var sky_texture: texture_cube<f32>;
+#endif
+@group(0) @binding(2)
+var sky_sampler: sampler;
+
+// Set when drawing into the HDR target of a PostProcess, like the PBR shader.
+override hdr_output: bool = false;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let xy = vec2(f32((index << 1u) & 2u), f32(index & 2u)) * 2.0 - 1.0;
+    var out: VertexOutput;
+    out.clip_position = vec4(xy, 0.0, 1.0);
+    out.ndc = xy;
+    return out;
+}
+
+const PI: f32 = 3.14159265359;
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Unproject a point on the far plane; without translation in the view
+    // matrix, its position is the view direction.
+    let far = sky.inv_view_proj * vec4(in.ndc, 1.0, 1.0);
+    let dir = normalize(far.xyz / far.w);
+
+#ifdef EQUIRECT
+    // Longitude around +Y (−Z at the image center), latitude from the top.
+    let uv = vec2(atan2(dir.x, -dir.z) / (2.0 * PI) + 0.5, acos(clamp(dir.y, -1.0, 1.0)) / PI);
+    // Explicit LOD: the atan2 seam would otherwise pick the smallest mip.
+    var color = textureSampleLevel(sky_texture, sky_sampler, uv, 0.0).rgb;
+#else
+    // Cubemaps follow the left-handed DirectX/OpenGL face convention.
+    var color = textureSampleLevel(sky_texture, sky_sampler, vec3(dir.xy, -dir.z), 0.0).rgb;
+#endif
+    color *= sky.brightness;
+
+    // Same Reinhard curve as the PBR shader when nothing tone maps later.
+    if !hdr_output {
+        color = color / (color + vec3<f32>(1.0));
+    }
+    return vec4(color, 1.0);
+}
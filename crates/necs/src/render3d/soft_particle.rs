@@ -0,0 +1,481 @@
+//! # Soft Particles — Depth-Faded Transparent Quads
+//!
+//! Smoke, fire, dust, and glow sprites are flat quads. Where one crosses the
+//! floor or a wall, the depth test slices it along a perfectly straight line
+//! that gives the trick away. Soft particles hide that seam: each fragment
+//! compares its own depth with the opaque scene behind it and fades out as
+//! the gap closes.
+//!
+//! ```text
+//!   hard (depth test only)          soft (depth fade)
+//!        ░░░░░░░░                        ░░░░░░░░
+//!        ░░░░░░░░                        ░░░░░░░░
+//!        ▓▓▓▓▓▓▓▓  ◄── cut line          ▒▒▒▒▒▒▒▒  ◄── fades over
+//!   ━━━━━━━━━━━━━━━━ floor          ━━━━━━━━━━━━━━━━   fade_distance
+//! ```
+//!
+//! ## Rendering
+//!
+//! Soft particles are pulled out of the opaque mesh list and drawn in their
+//! own pass after it, once the depth buffer holds every opaque surface:
+//!
+//! ```text
+//!   3d render pass (opaque) ──► depth texture
+//!                                   │ sampled
+//!                                   ▼
+//!   soft particle pass: sort far → near, alpha blend,
+//!                       alpha × saturate((scene − particle) / fade_distance)
+//! ```
+//!
+//! The pass has no depth attachment — the depth texture is bound for reading,
+//! so the shader does the occlusion test itself (a fade of zero discards).
+//! Particles are unlit: `base_color × texture + emissive`, with the material's
+//! alpha honored. Under [MSAA](crate::render::msaa) the depth texture is
+//! multisampled, and the shader reads its first sample.
+//!
+//! Pair with [`Billboard`](super::Billboard) for camera-facing quads; 2D
+//! sprite art works the same way once it's on a billboard. Pure 2D scenes
+//! don't need this — sprites are painter-sorted, with no depth buffer to
+//! intersect.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: "Soft Particles" toggle in the particle shaders, with a
+//!   per-material fade factor; needs the camera depth texture enabled.
+//! - **Godot**: `BaseMaterial3D.proximity_fade_enabled` and
+//!   `proximity_fade_distance` — the model this component follows.
+//! - **Our approach**: A component on any `Mesh3d` + `Material` entity,
+//!   drawn in a dedicated transparent pass that reads the opaque depth.
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::ecs::World;
+use crate::ecs::hierarchy::GlobalTransform;
+use crate::render::visibility::{ComputedVisibility, is_hidden};
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+use crate::render::pass::FrameContext;
+use crate::render::settings::graphics_settings;
+
+use super::billboard::{collect_billboards, BillboardView};
+use super::collect::ExtractedCamera3d;
+use super::draw::create_material_bind_group;
+use super::mesh::{MeshHandle, MeshStore};
+use super::pipeline::MeshRenderer;
+use super::texture::{MaterialTextures, TextureHandle3d, TextureStore3d};
+use super::vertex::{MeshVertex, ModelUniform};
+use super::{Camera3d, Material, Mesh3d};
+
+/// Smallest accepted fade distance, so the shader never divides by zero.
+const MIN_FADE_DISTANCE: f32 = 1e-4;
+
+// ── Public component ────────────────────────────────────────────────────
+
+/// Component: draw this mesh as a soft particle — alpha-blended, unlit, and
+/// faded out where it approaches opaque geometry. Pair with
+/// [`Mesh3d`] and [`Material`] (whose `base_color` alpha is used).
+#[derive(Debug, Clone, Copy)]
+pub struct SoftParticle {
+    /// World-space distance over which the particle fades to nothing as it
+    /// nears the surface behind it. Default: 0.5.
+    pub fade_distance: f32,
+}
+
+impl SoftParticle {
+    /// Fade out over `fade_distance` world units.
+    pub fn new(fade_distance: f32) -> Self {
+        Self {
+            fade_distance: fade_distance.max(MIN_FADE_DISTANCE),
+        }
+    }
+}
+
+impl Default for SoftParticle {
+    fn default() -> Self {
+        Self { fade_distance: 0.5 }
+    }
+}
+
+// ── Collection ──────────────────────────────────────────────────────────
+
+/// Per-draw material for the soft particle shader (group 2, binding 0).
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct SoftMaterialUniform {
+    base_color: [f32; 4],
+    emissive: [f32; 3],
+    fade_distance: f32,
+}
+
+/// Camera clip planes for linearizing depth (group 1, binding 1).
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct DepthParams {
+    near: f32,
+    far: f32,
+    /// 1 when the depth buffer is linear (orthographic camera).
+    orthographic: u32,
+    _pad: f32,
+}
+
+/// One soft particle ready to draw.
+pub(crate) struct SoftDraw {
+    /// View depth, for back-to-front sorting.
+    depth: f32,
+    pub mesh: MeshHandle,
+    material: SoftMaterialUniform,
+    texture: Option<TextureHandle3d>,
+    pub model_uniform: ModelUniform,
+}
+
+/// A visible [`SoftParticle`] mesh, copied out by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct ExtractedSoftParticle {
+    matrix: glam::Mat4,
+    /// `Some(screen_size)` for [`Billboard`](super::Billboard) entities.
+    billboard: Option<Option<f32>>,
+    mesh: MeshHandle,
+    material: SoftMaterialUniform,
+    texture: Option<TextureHandle3d>,
+}
+
+/// Copy every visible [`SoftParticle`] mesh.
+pub(crate) fn extract_soft_particles(world: &mut World) -> Vec<ExtractedSoftParticle> {
+    if !world.has_component_type::<SoftParticle>() {
+        return Vec::new();
+    }
+
+    // With the effect off, the minimum fade makes the cut as hard as a plain
+    // depth test.
+    let soft_enabled = graphics_settings(world).post_effects.soft_particles;
+    let billboards = collect_billboards(world);
+    let mut particles = Vec::new();
+    world.query::<(&GlobalTransform, &Mesh3d, &Material, &SoftParticle, Option<&ComputedVisibility>)>(
+        |entity, (gt, mesh3d, material, soft, vis)| {
+            if is_hidden(vis) {
+                return;
+            }
+            particles.push(ExtractedSoftParticle {
+                matrix: gt.matrix,
+                billboard: billboards.get(&entity).copied(),
+                mesh: mesh3d.mesh,
+                material: SoftMaterialUniform {
+                    base_color: material.base_color,
+                    emissive: material.emissive,
+                    fade_distance: if soft_enabled {
+                        soft.fade_distance.max(MIN_FADE_DISTANCE)
+                    } else {
+                        MIN_FADE_DISTANCE
+                    },
+                },
+                texture: material.base_color_texture,
+            });
+        },
+    );
+    particles
+}
+
+/// Turn the extracted soft particles into draws, facing billboards at the
+/// camera and sorted far → near so overlapping particles blend correctly.
+pub(crate) fn collect_soft_particles(
+    particles: &[ExtractedSoftParticle],
+    view: Option<&BillboardView>,
+) -> Vec<SoftDraw> {
+    let mut draws: Vec<SoftDraw> = particles
+        .iter()
+        .map(|particle| {
+            let model = match (view, particle.billboard) {
+                (Some(view), Some(screen_size)) => view.orient(particle.matrix, screen_size),
+                _ => particle.matrix,
+            };
+            let position = model.col(3).truncate();
+            SoftDraw {
+                depth: view.map_or(0.0, |view| view.depth(position)),
+                mesh: particle.mesh,
+                material: particle.material,
+                texture: particle.texture,
+                model_uniform: ModelUniform {
+                    model: model.to_cols_array_2d(),
+                    normal_matrix: model.inverse().transpose().to_cols_array_2d(),
+                },
+            }
+        })
+        .collect();
+
+    draws.sort_by(|a, b| b.depth.total_cmp(&a.depth));
+    draws
+}
+
+/// The extracted camera's clip planes and projection.
+fn depth_params(camera: Option<&ExtractedCamera3d>) -> DepthParams {
+    let defaults = Camera3d::default();
+    DepthParams {
+        near: camera.map_or(defaults.near, |cam| cam.near),
+        far: camera.map_or(defaults.far, |cam| cam.far),
+        orthographic: camera.is_some_and(|cam| cam.projection.is_orthographic()) as u32,
+        _pad: 0.0,
+    }
+}
+
+// ── Renderer ────────────────────────────────────────────────────────────
+
+/// GPU resources for the soft particle pass. Lazy-initialized the first
+/// frame a [`SoftParticle`] is drawn.
+pub(crate) struct SoftParticleRenderer {
+    pipeline: wgpu::RenderPipeline,
+    /// Color target the pipeline was built for.
+    pub target: TargetKey,
+    depth_layout: wgpu::BindGroupLayout,
+    depth_params_buffer: wgpu::Buffer,
+}
+
+impl SoftParticleRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        renderer: &MeshRenderer,
+    ) -> Self {
+        let target = renderer.target;
+        let (format, samples) = target;
+        let source = include_str!("soft_particle.wgsl");
+        let source = if samples > 1 {
+            source.replace("texture_depth_2d", "texture_depth_multisampled_2d")
+        } else {
+            source.to_string()
+        };
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("soft particle shader"),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        // Scene depth + clip planes (group 1)
+        let depth_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("soft particle depth layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Depth,
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: samples > 1,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        // Camera and model groups are shared with the PBR pipeline; the
+        // material layout matches too (uniform + texture + sampler).
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("soft particle pipeline layout"),
+            bind_group_layouts: &[
+                &renderer.camera_bind_group_layout,
+                &depth_layout,
+                &renderer.material_bind_group_layout,
+                &renderer.model_bind_group_layout,
+            ],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("soft particle pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[MeshVertex::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            // Particle quads are seen from both sides.
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            // Occlusion is tested in the shader against the sampled depth.
+            depth_stencil: None,
+            multisample: multisample_state(samples),
+            multiview: None,
+            cache: None,
+        });
+
+        let depth_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("soft particle depth params"),
+            size: std::mem::size_of::<DepthParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            target,
+            depth_layout,
+            depth_params_buffer,
+        }
+    }
+}
+
+/// Draw collected soft particles over the opaque 3D scene. Their model
+/// uniforms start at slot `first_model` of the renderer's dynamic buffer.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn render_soft_particles(
+    camera: Option<&ExtractedCamera3d>,
+    frame: &mut FrameContext<'_>,
+    target: &ColorTarget,
+    renderer: &MeshRenderer,
+    soft_renderer: &SoftParticleRenderer,
+    mesh_store: &MeshStore,
+    texture_store: &TextureStore3d,
+    draws: &[SoftDraw],
+    first_model: usize,
+    model_stride: u32,
+) {
+    if draws.is_empty() {
+        return;
+    }
+    let gpu = frame.gpu;
+
+    let params = depth_params(camera);
+    gpu.queue
+        .write_buffer(&soft_renderer.depth_params_buffer, 0, bytemuck::bytes_of(&params));
+
+    // Rebuilt each frame: the depth texture is recreated on resize.
+    let depth_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("soft particle depth bind group"),
+        layout: &soft_renderer.depth_layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&renderer.depth_texture),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: soft_renderer.depth_params_buffer.as_entire_binding(),
+            },
+        ],
+    });
+
+    let material_bind_groups: Vec<wgpu::BindGroup> = draws
+        .iter()
+        .map(|draw| {
+            let buffer = gpu
+                .device
+                .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("soft particle material buffer"),
+                    contents: bytemuck::bytes_of(&draw.material),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+            // Soft particles read the base color map only.
+            let textures = MaterialTextures {
+                base_color: draw.texture,
+                ..Default::default()
+            };
+            create_material_bind_group(
+                gpu,
+                renderer,
+                texture_store,
+                &buffer,
+                &textures,
+                "soft particle material bind group",
+            )
+        })
+        .collect();
+
+    let mut pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("soft particle pass"),
+        color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    pass.set_pipeline(&soft_renderer.pipeline);
+    pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
+    pass.set_bind_group(1, &depth_bind_group, &[]);
+    for (i, draw) in draws.iter().enumerate() {
+        let dynamic_offset = (first_model + i) as u32 * model_stride;
+        pass.set_bind_group(2, &material_bind_groups[i], &[]);
+        pass.set_bind_group(3, &renderer.model_bind_group, &[dynamic_offset]);
+
+        let gpu_mesh = mesh_store.get(draw.mesh);
+        if frame.debug_markers {
+            pass.insert_debug_marker(&format!("soft particle mesh {}", draw.mesh.0));
+        }
+        pass.set_vertex_buffer(0, gpu_mesh.vertex_buffer.slice(..));
+        pass.set_index_buffer(gpu_mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        pass.draw_indexed(0..gpu_mesh.index_count, 0, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Hidden, propagate_visibility};
+    use crate::math::{Mat4, Vec3};
+    use crate::render3d::collect::{collect_draw_calls, extract_meshes};
+    use crate::render3d::Projection3d;
+
+    fn at(z: f32) -> GlobalTransform {
+        GlobalTransform {
+            matrix: Mat4::from_translation(Vec3::new(0.0, 0.0, z)),
+        }
+    }
+
+    fn mesh(index: usize) -> Mesh3d {
+        Mesh3d { mesh: MeshHandle(index) }
+    }
+
+    #[test]
+    fn soft_particles_sort_far_to_near_and_skip_the_opaque_list() {
+        let mut world = World::new();
+        for z in [-2.0, -9.0, -5.0] {
+            world.spawn((at(z), mesh(0), Material::default(), SoftParticle::default()));
+        }
+        world.spawn((at(-4.0), mesh(1), Material::default()));
+
+        // Camera at the origin looking down -Z.
+        let view = BillboardView::new(Mat4::IDENTITY, Projection3d::perspective(90.0), (2, 2));
+        let draws = collect_soft_particles(&extract_soft_particles(&mut world), Some(&view));
+        let depths: Vec<f32> = draws.iter().map(|d| d.depth).collect();
+        assert_eq!(depths, vec![9.0, 5.0, 2.0]);
+
+        let opaque = collect_draw_calls(&extract_meshes(&mut world), Some(&view), None);
+        assert_eq!(opaque.len(), 1);
+        assert_eq!(opaque[0].mesh, MeshHandle(1));
+    }
+
+    #[test]
+    fn hidden_particles_are_not_collected() {
+        let mut world = World::new();
+        world.spawn((at(-3.0), mesh(0), Material::default(), SoftParticle::default()));
+        world.spawn((at(-3.0), mesh(0), Material::default(), SoftParticle::new(0.0), Hidden));
+        propagate_visibility(&mut world);
+
+        assert_eq!(extract_soft_particles(&mut world).len(), 1);
+        assert!(SoftParticle::new(0.0).fade_distance > 0.0);
+    }
+}
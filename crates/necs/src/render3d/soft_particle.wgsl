@@ -0,0 +1,111 @@
+// ============================================================================
+// Soft Particles — Fade out where a particle meets opaque geometry
+//
+// A flat quad that pokes through the floor normally shows a hard line where
+// the depth test cuts it. This pass instead reads the opaque depth buffer and
+// compares it with the particle's own depth, both converted back to linear
+// view distance:
+//
+//   fade = saturate((scene_depth - particle_depth) / fade_distance)
+//
+// Fragments behind the scene are discarded (fade = 0), which replaces the
+// hardware depth test — the depth texture is bound for sampling here, so it
+// can't also be the depth attachment.
+//
+// Particles are unlit: base color × texture + emissive, with alpha fading.
+// ============================================================================
+
+// ── Bind Group 0: Camera (per frame) ────────────────────────────────────────
+
+struct CameraUniform {
+    view_proj: mat4x4<f32>,
+    camera_pos: vec3<f32>,
+};
+@group(0) @binding(0)
+var<uniform> camera: CameraUniform;
+
+// ── Bind Group 1: Scene depth (per frame) ───────────────────────────────────
+
+struct DepthParams {
+    near: f32,
+    far: f32,
+    // 1 for an orthographic camera.
+    orthographic: u32,
+};
+@group(1) @binding(0)
+var scene_depth: texture_depth_2d;
+@group(1) @binding(1)
+var<uniform> depth_params: DepthParams;
+
+// ── Bind Group 2: Particle material (per draw) ──────────────────────────────
+
+struct SoftMaterial {
+    base_color: vec4<f32>,
+    emissive: vec3<f32>,
+    fade_distance: f32,
+};
+@group(2) @binding(0)
+var<uniform> material: SoftMaterial;
+@group(2) @binding(1)
+var base_color_texture: texture_2d<f32>;
+@group(2) @binding(2)
+var base_color_sampler: sampler;
+
+// ── Bind Group 3: Model (per object, dynamic offset) ────────────────────────
+
+struct ModelUniform {
+    model: mat4x4<f32>,
+    normal_matrix: mat4x4<f32>,
+};
+@group(3) @binding(0)
+var<uniform> model: ModelUniform;
+
+// ── Shaders ─────────────────────────────────────────────────────────────────
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) uv: vec2<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = camera.view_proj * model.model * vec4<f32>(in.position, 1.0);
+    out.uv = in.uv;
+    return out;
+}
+
+// Depth buffer value (0 at near, 1 at far) → distance along the view axis.
+// Inverts the 3D camera's projection: `orthographic_rh` stores depth
+// linearly, `perspective_rh` hyperbolically.
+fn linear_depth(depth: f32) -> f32 {
+    let near = depth_params.near;
+    let far = depth_params.far;
+    if depth_params.orthographic != 0u {
+        return near + depth * (far - near);
+    }
+    return near * far / (far - depth * (far - near));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Sample before any discard: implicit-derivative sampling needs uniform
+    // control flow.
+    let tex = textureSample(base_color_texture, base_color_sampler, in.uv);
+
+    let scene = linear_depth(textureLoad(scene_depth, vec2<i32>(in.clip_position.xy), 0));
+    let particle = linear_depth(in.clip_position.z);
+    let fade = saturate((scene - particle) / max(material.fade_distance, 1e-4));
+    if fade <= 0.0 {
+        discard;
+    }
+
+    let color = material.base_color.rgb * tex.rgb + material.emissive;
+    return vec4<f32>(color, material.base_color.a * tex.a * fade);
+}
@@ -0,0 +1,518 @@
+//! # Text3d — World-Space Text Labels
+//!
+//! Text anchored to a point in the 3D world: nameplates, damage numbers,
+//! debug annotations. Labels reuse the 2D font atlas (see
+//! [`load_font`](crate::render2d::load_font)), so the same [`FontHandle`]
+//! works for both screen text and world text.
+//!
+//! ## Rendering
+//!
+//! Every label is a billboard: glyph quads are laid out in font pixels,
+//! centered on the entity's position, and expanded along the camera's right
+//! and up axes on the CPU. Labels are drawn after the opaque meshes in their
+//! own alpha-blended pass that reads the depth buffer without writing it, so
+//! walls hide nameplates but labels never hide each other incorrectly.
+//!
+//! ```text
+//!   per label:  layout glyphs (font px) ─► × scale ─► along camera right/up
+//!   per frame:  sort labels far → near ─► one vertex buffer
+//!               one draw per run of labels sharing an atlas
+//! ```
+//!
+//! Size comes from either `height` (world-space line height — labels shrink
+//! with distance like any other object) or `screen_size` (line height in
+//! pixels — constant on screen, the usual choice for nameplates).
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `TextMeshPro` (world-space) with a look-at-camera script.
+//! - **Godot**: `Label3D` with `billboard` and `fixed_size` — the model this
+//!   component follows.
+//! - **Our approach**: Bitmap atlas glyphs only (no SDF), so very large
+//!   labels get blurry; load the font at a size close to the on-screen size.
+
+use std::collections::HashMap;
+
+use bytemuck::{Pod, Zeroable};
+use wgpu::util::DeviceExt;
+
+use crate::ecs::World;
+use crate::ecs::hierarchy::GlobalTransform;
+use crate::math::{Vec2, Vec3};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
+use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+use crate::render2d::Color;
+use crate::render2d::font::{BASELINE, FontEntry, FontHandle, FontStore};
+use crate::render2d::texture::{TextureHandle, TextureStore};
+
+use super::billboard::BillboardView;
+use super::pipeline::{MeshRenderer, DEPTH_FORMAT};
+
+// ── Public component ────────────────────────────────────────────────────
+
+/// Component: camera-facing text at the entity's world position. Pair with
+/// [`Transform`](crate::math::Transform).
+///
+/// Only the translation is used — labels always face the camera. Multi-line
+/// text (`\n`) is centered line by line around the entity's position.
+#[derive(Debug, Clone)]
+pub struct Text3d {
+    /// The string to render.
+    pub content: String,
+    /// Which font to use.
+    pub font: FontHandle,
+    /// Tint color (multiplied with the white atlas glyphs).
+    pub color: Color,
+    /// Line height in world units. Ignored when `screen_size` is set.
+    pub height: f32,
+    /// Line height in pixels, constant regardless of distance.
+    pub screen_size: Option<f32>,
+}
+
+impl Text3d {
+    /// Create a world-space label with a line height of 0.5 units.
+    pub fn new(content: &str, font: FontHandle) -> Self {
+        Self {
+            content: content.to_owned(),
+            font,
+            color: Color::WHITE,
+            height: 0.5,
+            screen_size: None,
+        }
+    }
+
+    /// Set the text color (builder pattern).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Set the world-space line height (builder pattern).
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// Keep the line height at `pixels` on screen (builder pattern).
+    pub fn screen_size(mut self, pixels: f32) -> Self {
+        self.screen_size = Some(pixels);
+        self
+    }
+}
+
+// ── Layout ──────────────────────────────────────────────────────────────
+
+/// One glyph quad in label space (font pixels, Y-up, centered on the anchor).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct GlyphQuad {
+    pub min: Vec2,
+    pub max: Vec2,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+}
+
+/// Lay out `content` as glyph quads centered on the origin: each line is
+/// centered horizontally, and the block of lines is centered vertically.
+pub(crate) fn layout_label(entry: &FontEntry, content: &str) -> Vec<GlyphQuad> {
+    let lines: Vec<&str> = content.split('\n').collect();
+    let line_height = entry.line_height;
+    let top = lines.len() as f32 * line_height * 0.5;
+
+    let mut quads = Vec::new();
+    for (row, line) in lines.iter().enumerate() {
+        let width = entry.line_width(line);
+        let baseline = top - (row as f32 + 1.0 - BASELINE) * line_height;
+        let mut cursor_x = -width * 0.5;
+
+        for ch in line.chars() {
+            let Some(glyph) = entry.glyph(ch) else {
+                continue;
+            };
+            if glyph.width > 0.0 && glyph.height > 0.0 {
+                let min = Vec2::new(cursor_x + glyph.offset_x, baseline + glyph.offset_y);
+                quads.push(GlyphQuad {
+                    min,
+                    max: min + Vec2::new(glyph.width, glyph.height),
+                    // Atlas V runs top-down, quad Y runs bottom-up.
+                    uv_min: Vec2::new(glyph.u_min, glyph.v_max),
+                    uv_max: Vec2::new(glyph.u_max, glyph.v_min),
+                });
+            }
+            cursor_x += glyph.advance;
+        }
+    }
+    quads
+}
+
+// ── Collection ──────────────────────────────────────────────────────────
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct Text3dVertex {
+    position: [f32; 3],
+    uv: [f32; 2],
+    color: [f32; 4],
+}
+
+impl Text3dVertex {
+    const LAYOUT: wgpu::VertexBufferLayout<'static> = wgpu::VertexBufferLayout {
+        array_stride: std::mem::size_of::<Text3dVertex>() as wgpu::BufferAddress,
+        step_mode: wgpu::VertexStepMode::Vertex,
+        attributes: &wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x2, 2 => Float32x4],
+    };
+}
+
+/// One label's glyph geometry, ready to be sorted and merged.
+pub(crate) struct LabelMesh {
+    depth: f32,
+    atlas: TextureHandle,
+    vertices: Vec<Text3dVertex>,
+}
+
+/// Copy every visible [`Text3d`] with its world position, for the
+/// [extract phase](crate::render::extract).
+pub(crate) fn extract_labels(world: &mut World) -> Vec<(Vec3, Text3d)> {
+    let mut labels = Vec::new();
+    world.query::<(&GlobalTransform, &Text3d, Option<&ComputedVisibility>)>(|_entity, (gt, text, vis)| {
+        if !is_hidden(vis) {
+            labels.push((gt.matrix.col(3).truncate(), text.clone()));
+        }
+    });
+    labels
+}
+
+/// Build camera-facing glyph quads for every extracted [`Text3d`] in front
+/// of the camera.
+pub(crate) fn collect_labels(
+    extracted: &[(Vec3, Text3d)],
+    fonts: &FontStore,
+    view: &BillboardView,
+) -> Vec<LabelMesh> {
+    let mut labels = Vec::new();
+    let (right, up) = (view.rotation * Vec3::X, view.rotation * Vec3::Y);
+
+    for (anchor, text) in extracted {
+        let anchor = *anchor;
+        let depth = view.depth(anchor);
+        if depth <= 0.0 {
+            continue;
+        }
+
+        let entry = fonts.get(text.font);
+        let line_height = match text.screen_size {
+            Some(pixels) => pixels * view.world_per_pixel(anchor),
+            None => text.height,
+        };
+        let scale = line_height / entry.line_height;
+        let color = text.color.to_array();
+
+        let quads = layout_label(entry, &text.content);
+        let mut vertices = Vec::with_capacity(quads.len() * 4);
+        for quad in quads {
+            let corners = [
+                (quad.min.x, quad.min.y, quad.uv_min.x, quad.uv_min.y),
+                (quad.max.x, quad.min.y, quad.uv_max.x, quad.uv_min.y),
+                (quad.max.x, quad.max.y, quad.uv_max.x, quad.uv_max.y),
+                (quad.min.x, quad.max.y, quad.uv_min.x, quad.uv_max.y),
+            ];
+            for (x, y, u, v) in corners {
+                let position = anchor + right * (x * scale) + up * (y * scale);
+                vertices.push(Text3dVertex {
+                    position: position.to_array(),
+                    uv: [u, v],
+                    color,
+                });
+            }
+        }
+
+        if !vertices.is_empty() {
+            labels.push(LabelMesh {
+                depth,
+                atlas: entry.atlas_handle,
+                vertices,
+            });
+        }
+    }
+
+    labels
+}
+
+// ── Renderer ────────────────────────────────────────────────────────────
+
+pub(crate) struct Text3dRenderer {
+    pipeline: wgpu::RenderPipeline,
+    /// Color target the pipeline was built for.
+    pub target: TargetKey,
+    texture_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+}
+
+impl Text3dRenderer {
+    pub fn new(
+        device: &wgpu::Device,
+        target: TargetKey,
+        camera_bind_group_layout: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let (format, samples) = target;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("3d text shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("text3d.wgsl").into()),
+        });
+
+        // Atlas texture + sampler (group 1)
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("3d text atlas layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("3d text pipeline layout"),
+            bind_group_layouts: &[camera_bind_group_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("3d text pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[Text3dVertex::LAYOUT],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            // Occluded by opaque geometry, but labels don't occlude each other
+            // (they're sorted back-to-front instead).
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: multisample_state(samples),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("3d text sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        Self {
+            pipeline,
+            target,
+            texture_layout,
+            sampler,
+        }
+    }
+}
+
+/// Draw all collected labels on top of the 3D scene.
+pub(crate) fn render_text_3d(
+    encoder: &mut wgpu::CommandEncoder,
+    target: &ColorTarget,
+    gpu: &GpuContext,
+    renderer: &MeshRenderer,
+    text_renderer: &Text3dRenderer,
+    texture_store: &TextureStore,
+    mut labels: Vec<LabelMesh>,
+) {
+    if labels.is_empty() {
+        return;
+    }
+
+    // Far → near so overlapping labels blend correctly.
+    labels.sort_by(|a, b| b.depth.total_cmp(&a.depth));
+
+    // Merge into one vertex/index buffer; one batch per run of same-atlas labels.
+    // A packed font atlas draws from its page, with UVs remapped into it.
+    let mut vertices: Vec<Text3dVertex> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    let mut batches: Vec<(TextureHandle, std::ops::Range<u32>)> = Vec::new();
+    for label in &labels {
+        let start = indices.len() as u32;
+        for quad in 0..(label.vertices.len() / 4) as u32 {
+            let base = vertices.len() as u32 + quad * 4;
+            indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+        }
+        let (page, region) = texture_store.draw_source(label.atlas);
+        let region_size = region.max - region.min;
+        vertices.extend(label.vertices.iter().map(|v| Text3dVertex {
+            uv: (region.min + Vec2::from(v.uv) * region_size).to_array(),
+            ..*v
+        }));
+        let end = indices.len() as u32;
+
+        match batches.last_mut() {
+            Some((atlas, range)) if *atlas == page => range.end = end,
+            _ => batches.push((page, start..end)),
+        }
+    }
+
+    let vertex_buffer = gpu
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("3d text vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+    let index_buffer = gpu
+        .device
+        .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("3d text indices"),
+            contents: bytemuck::cast_slice(&indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+    // One bind group per distinct atlas this frame.
+    let mut atlas_groups: HashMap<TextureHandle, wgpu::BindGroup> = HashMap::new();
+    for (atlas, _) in &batches {
+        atlas_groups.entry(*atlas).or_insert_with(|| {
+            gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("3d text atlas bind group"),
+                layout: &text_renderer.texture_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(
+                            &texture_store.get(*atlas).view,
+                        ),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&text_renderer.sampler),
+                    },
+                ],
+            })
+        });
+    }
+
+    let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("3d text pass"),
+        color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+        depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+            view: &renderer.depth_texture,
+            depth_ops: Some(wgpu::Operations {
+                load: wgpu::LoadOp::Load,
+                store: wgpu::StoreOp::Store,
+            }),
+            stencil_ops: None,
+        }),
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    pass.set_pipeline(&text_renderer.pipeline);
+    pass.set_bind_group(0, &renderer.camera_bind_group, &[]);
+    pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+    pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+    for (atlas, range) in batches {
+        pass.set_bind_group(1, &atlas_groups[&atlas], &[]);
+        pass.draw_indexed(range, 0, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render2d::font::GlyphInfo;
+
+    /// A fake font where every printable glyph is a 10×10 box advancing 10px.
+    fn font() -> FontEntry {
+        let glyph = GlyphInfo {
+            u_min: 0.0,
+            v_min: 0.0,
+            u_max: 1.0,
+            v_max: 1.0,
+            advance: 10.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            width: 10.0,
+            height: 10.0,
+        };
+        let space = GlyphInfo {
+            width: 0.0,
+            height: 0.0,
+            ..glyph
+        };
+        let mut glyphs = vec![Some(glyph); 95];
+        glyphs[0] = Some(space);
+        FontEntry {
+            glyphs,
+            extra_glyphs: Default::default(),
+            atlas_handle: TextureHandle(0),
+            line_height: 20.0,
+            cache: None,
+            distance_field: None,
+        }
+    }
+
+    #[test]
+    fn single_line_is_centered_horizontally() {
+        let quads = layout_label(&font(), "ab");
+        assert_eq!(quads.len(), 2);
+        assert_eq!(quads[0].min.x, -10.0);
+        assert_eq!(quads[1].max.x, 10.0);
+        // One 20px line box spans -10..10; baseline sits 5px above its bottom.
+        assert_eq!(quads[0].min.y, -5.0);
+    }
+
+    #[test]
+    fn spaces_advance_without_quads() {
+        let quads = layout_label(&font(), "a b");
+        assert_eq!(quads.len(), 2);
+        assert_eq!(quads[0].min.x, -15.0);
+        assert_eq!(quads[1].min.x, 5.0);
+    }
+
+    #[test]
+    fn lines_stack_downward_and_center_independently() {
+        let quads = layout_label(&font(), "abcd\nab");
+        assert_eq!(quads.len(), 6);
+        assert_eq!(quads[0].min.x, -20.0);
+        assert_eq!(quads[4].min.x, -10.0);
+        assert_eq!(quads[0].min.y - quads[4].min.y, 20.0);
+    }
+}
@@ -13,6 +13,20 @@
 //! white texture is bound. The shader samples it (always white) and uses the
 //! material's `base_color` field directly. This avoids branching in the shader.
 //!
+//! The other maps fall back to it as well: white leaves the metallic,
+//! roughness and emissive factors as they are and means "no occlusion". A
+//! missing normal map is the one case the shader branches on (white isn't a
+//! flat normal).
+//!
+//! ## Color and Data Maps
+//!
+//! Color maps (base color, emissive) are uploaded as `Rgba8UnormSrgb`, so
+//! sampling decodes them to linear. The data maps (normal,
+//! metallic-roughness, AO) store plain numbers and are uploaded as
+//! `Rgba8Unorm`, so the shader reads them as they were authored. Load the
+//! former with [`load_texture_3d`] and the latter with
+//! [`load_data_texture_3d`]; glTF files pick by the slot a texture fills.
+//!
 //! ## Comparison
 //!
 //! - **Bevy**: Uses an `AssetServer` with typed `Handle<Image>`, async loading,
@@ -20,32 +34,103 @@
 //! - **three.js**: `TextureLoader` with callbacks, shared `Texture` objects.
 //! - **Our approach**: Synchronous, index-based, with path deduplication.
 
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 use wgpu::util::DeviceExt;
 
 use crate::asset::{AssetKind, AssetServer};
+use crate::asset_gc::FreedEntry;
 use crate::ecs::World;
 use crate::render::GpuContext;
+use crate::render::pipelined::finish_render;
+use crate::render::sampler::{SamplerCache, SamplerSettings, TextureFilter, TextureWrap};
+
+/// Sampler for 3D textures loaded without explicit settings: smooth and
+/// tiling, as material UVs often run past `0..1`.
+pub(crate) const DEFAULT_SAMPLER: SamplerSettings = SamplerSettings {
+    filter: TextureFilter::Linear,
+    wrap_u: TextureWrap::Repeat,
+    wrap_v: TextureWrap::Repeat,
+    anisotropy: 1,
+};
 
 /// Handle to a loaded texture in the 3D [`TextureStore3d`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TextureHandle3d(pub(crate) usize);
 
+/// The texture maps of one [`Material`](super::Material), as bound to
+/// material bind group 2.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct MaterialTextures {
+    pub base_color: Option<TextureHandle3d>,
+    pub normal: Option<TextureHandle3d>,
+    pub metallic_roughness: Option<TextureHandle3d>,
+    pub emissive: Option<TextureHandle3d>,
+    pub ao: Option<TextureHandle3d>,
+}
+
+impl MaterialTextures {
+    /// The maps of `material`.
+    pub fn of(material: &super::Material) -> Self {
+        Self {
+            base_color: material.base_color_texture,
+            normal: material.normal_texture,
+            metallic_roughness: material.metallic_roughness_texture,
+            emissive: material.emissive_texture,
+            ao: material.ao_texture,
+        }
+    }
+
+    /// Whether each map, in [`bound`](Self::bound) order, holds color
+    /// (uploaded as sRGB) rather than data.
+    pub const SRGB: [bool; 5] = [true, false, false, true, false];
+
+    /// The maps in binding order; unset ones are the white default.
+    pub fn bound(&self) -> [TextureHandle3d; 5] {
+        [self.base_color, self.normal, self.metallic_roughness, self.emissive, self.ao]
+            .map(|handle| handle.unwrap_or(TextureStore3d::DEFAULT))
+    }
+
+    /// The handles that are set.
+    pub fn handles(&self) -> impl Iterator<Item = TextureHandle3d> {
+        [self.base_color, self.normal, self.metallic_roughness, self.emissive, self.ao]
+            .into_iter()
+            .flatten()
+    }
+}
+
 /// Internal entry for a loaded GPU texture.
 pub(crate) struct TextureEntry3d {
     pub view: wgpu::TextureView,
-    #[allow(dead_code)]
+    pub sampler: wgpu::Sampler,
+    pub sampler_settings: SamplerSettings,
     pub width: u32,
-    #[allow(dead_code)]
     pub height: u32,
+    /// Uploaded as `Rgba8UnormSrgb` (a color map) rather than `Rgba8Unorm`.
+    pub srgb: bool,
+}
+
+/// The format of a texture uploaded as a color (`srgb`) or data map.
+fn texture_format(srgb: bool) -> wgpu::TextureFormat {
+    if srgb {
+        wgpu::TextureFormat::Rgba8UnormSrgb
+    } else {
+        wgpu::TextureFormat::Rgba8Unorm
+    }
 }
 
 /// Stores all loaded GPU textures for the 3D renderer.
 pub(crate) struct TextureStore3d {
     pub entries: Vec<TextureEntry3d>,
-    path_cache: HashMap<String, TextureHandle3d>,
+    /// Loaded files by path and whether they were loaded as color.
+    path_cache: HashMap<(String, bool), TextureHandle3d>,
+    samplers: SamplerCache,
+    /// Handles whose GPU texture was released by [`free`](Self::free).
+    freed: HashSet<TextureHandle3d>,
+    /// Anisotropy floor for linear-filtered textures, from
+    /// [`GraphicsSettings`](crate::render::GraphicsSettings).
+    min_anisotropy: u16,
 }
 
 impl TextureStore3d {
@@ -72,20 +157,31 @@ impl TextureStore3d {
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let mut samplers = SamplerCache::default();
+        let (sampler_settings, sampler) = samplers.get(&gpu.device, DEFAULT_SAMPLER);
 
         Self {
             entries: vec![TextureEntry3d {
                 view,
+                sampler,
+                sampler_settings,
                 width: 1,
                 height: 1,
+                srgb: true,
             }],
             path_cache: HashMap::new(),
+            samplers,
+            freed: HashSet::new(),
+            min_anisotropy: 1,
         }
     }
 
+    /// The 1x1 white default, white in either color space.
+    pub const DEFAULT: TextureHandle3d = TextureHandle3d(0);
+
     /// The default 1x1 white texture handle.
     pub fn default_handle(&self) -> TextureHandle3d {
-        TextureHandle3d(0)
+        Self::DEFAULT
     }
 
     /// Get the entry for a handle.
@@ -93,8 +189,8 @@ impl TextureStore3d {
         &self.entries[handle.0]
     }
 
-
-    /// Upload a texture from raw RGBA8 data.
+    /// Upload a texture from raw RGBA8 data, as a color map (`srgb`) or a
+    /// data map.
     pub fn upload_rgba8(
         &mut self,
         gpu: &GpuContext,
@@ -102,6 +198,7 @@ impl TextureStore3d {
         width: u32,
         height: u32,
         data: &[u8],
+        srgb: bool,
     ) -> TextureHandle3d {
         let texture = gpu.device.create_texture_with_data(
             &gpu.queue,
@@ -115,7 +212,7 @@ impl TextureStore3d {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                format: texture_format(srgb),
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
             },
@@ -124,20 +221,25 @@ impl TextureStore3d {
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let (sampler_settings, sampler) = self.sampler(&gpu.device, DEFAULT_SAMPLER);
         let handle = TextureHandle3d(self.entries.len());
         self.entries.push(TextureEntry3d {
             view,
+            sampler,
+            sampler_settings,
             width,
             height,
+            srgb,
         });
         handle
     }
 
     /// Replace the GPU data for an existing texture handle (hot-reload).
     ///
-    /// Creates a new GPU texture view from the given RGBA8 data and swaps it
-    /// into the entry at the handle's index. Bind groups referencing this
-    /// texture are recreated each frame anyway, so they'll pick up the new view.
+    /// Creates a new GPU texture view from the given RGBA8 data, in the
+    /// entry's format, and swaps it into the entry at the handle's index.
+    /// Bind groups referencing this texture are recreated each frame anyway,
+    /// so they'll pick up the new view.
     pub fn reload_entry(
         &mut self,
         gpu: &GpuContext,
@@ -158,7 +260,7 @@ impl TextureStore3d {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                format: texture_format(self.entries[handle.0].srgb),
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
                 view_formats: &[],
             },
@@ -167,37 +269,124 @@ impl TextureStore3d {
         );
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        self.entries[handle.0] = TextureEntry3d {
-            view,
-            width,
-            height,
-        };
+        let entry = &mut self.entries[handle.0];
+        entry.view = view;
+        entry.width = width;
+        entry.height = height;
+    }
+
+    /// Raise every linear-filtered texture to at least `level` anisotropy.
+    /// Textures keep the settings they asked for, so lowering the level
+    /// again restores them. Does nothing if the level is unchanged.
+    pub fn set_min_anisotropy(&mut self, gpu: &GpuContext, level: u16) {
+        let level = level.clamp(1, 16);
+        if level == self.min_anisotropy {
+            return;
+        }
+        self.min_anisotropy = level;
+        for i in 0..self.entries.len() {
+            let (_, sampler) = self.sampler(&gpu.device, self.entries[i].sampler_settings);
+            self.entries[i].sampler = sampler;
+        }
+    }
+
+    /// The GPU sampler for `settings` with the anisotropy floor applied.
+    /// Returns the settings as requested (sanitized), not as raised.
+    fn sampler(
+        &mut self,
+        device: &wgpu::Device,
+        settings: SamplerSettings,
+    ) -> (SamplerSettings, wgpu::Sampler) {
+        let (settings, sampler) = self.samplers.get(device, settings);
+        if settings.filter != TextureFilter::Linear || settings.anisotropy >= self.min_anisotropy {
+            return (settings, sampler);
+        }
+        let (_, raised) = self.samplers.get(device, settings.anisotropy(self.min_anisotropy));
+        (settings, raised)
+    }
+
+    /// Handles [`free`](Self::free) would release: everything but the
+    /// default and textures already freed.
+    pub fn freeable(&self) -> impl Iterator<Item = TextureHandle3d> + '_ {
+        (1..self.entries.len())
+            .map(TextureHandle3d)
+            .filter(|handle| !self.freed.contains(handle))
+    }
+
+    /// Release the GPU texture behind `handle`; the slot then samples as the
+    /// white default and the path is dropped from the cache. Same rules as
+    /// the 2D store's `free`.
+    pub fn free(&mut self, handle: TextureHandle3d) -> Option<FreedEntry> {
+        if handle.0 == 0 || handle.0 >= self.entries.len() || !self.freed.insert(handle) {
+            return None;
+        }
+        let view = self.entries[0].view.clone();
+        let entry = &mut self.entries[handle.0];
+        let bytes = entry.width as u64 * entry.height as u64 * 4;
+        entry.view = view;
+        (entry.width, entry.height) = (1, 1);
+
+        let key = self
+            .path_cache
+            .iter()
+            .find(|(_, h)| **h == handle)
+            .map(|(key, _)| key.clone());
+        if let Some(key) = &key {
+            self.path_cache.remove(key);
+        }
+        Some(FreedEntry { bytes, path: key.map(|(path, _)| path) })
+    }
+
+    /// Change how a texture is sampled. Material bind groups are rebuilt
+    /// every frame, so this takes effect on the next one.
+    pub fn set_sampler(&mut self, gpu: &GpuContext, handle: TextureHandle3d, sampler: SamplerSettings) {
+        if self.entries[handle.0].sampler_settings == sampler {
+            return;
+        }
+        let (sampler_settings, sampler) = self.sampler(&gpu.device, sampler);
+        let entry = &mut self.entries[handle.0];
+        entry.sampler = sampler;
+        entry.sampler_settings = sampler_settings;
     }
 }
 
-/// Load a texture from disk for the 3D renderer.
+/// Load a color map (base color, emissive) from disk for the 3D renderer.
 ///
-/// Uses the extract/reinsert pattern to avoid borrow conflicts.
+/// Uses the extract/reinsert pattern to avoid borrow conflicts. New
+/// textures are sampled with linear filtering and repeat wrapping; use
+/// [`load_texture_3d_with`] to choose.
 pub fn load_texture_3d(world: &mut World, path: &str) -> TextureHandle3d {
+    load_texture_3d_as(world, path, true)
+}
+
+/// Load a data map (normal, metallic-roughness, AO) from disk for the 3D
+/// renderer. Unlike [`load_texture_3d`], the pixels aren't treated as sRGB
+/// colors; see [Color and Data Maps](self#color-and-data-maps).
+pub fn load_data_texture_3d(world: &mut World, path: &str) -> TextureHandle3d {
+    load_texture_3d_as(world, path, false)
+}
+
+fn load_texture_3d_as(world: &mut World, path: &str, srgb: bool) -> TextureHandle3d {
+    // The render thread may hold the store for the frame in flight.
+    finish_render(world);
+    let path = crate::launch::resolve_asset_path(world, path);
+    let path = path.as_ref();
     let mut store = world
         .resource_remove::<TextureStore3d>()
         .expect("TextureStore3d not initialized — render at least one frame first");
 
-    if let Some(&handle) = store.path_cache.get(path) {
+    let key = (path.to_owned(), srgb);
+    if let Some(&handle) = store.path_cache.get(&key) {
         world.insert_resource(store);
         return handle;
     }
 
+    let (width, height, data) = crate::import::load_rgba(world, Path::new(path))
+        .unwrap_or_else(|e| panic!("Failed to load 3D texture '{path}': {e}"));
     let gpu = world.resource::<GpuContext>();
 
-    let img = image::open(path)
-        .unwrap_or_else(|e| panic!("Failed to load 3D texture '{path}': {e}"))
-        .to_rgba8();
-    let (width, height) = img.dimensions();
-    let data = img.into_raw();
-
-    let handle = store.upload_rgba8(gpu, path, width, height, &data);
-    store.path_cache.insert(path.to_owned(), handle);
+    let handle = store.upload_rgba8(gpu, path, width, height, &data, srgb);
+    store.path_cache.insert(key, handle);
 
     world.insert_resource(store);
 
@@ -208,3 +397,93 @@ pub fn load_texture_3d(world: &mut World, path: &str) -> TextureHandle3d {
 
     handle
 }
+
+/// Load a texture from disk for the 3D renderer with the given sampler
+/// settings. If the path was already loaded, the cached handle is returned
+/// and its sampler switched to `sampler`.
+pub fn load_texture_3d_with(
+    world: &mut World,
+    path: &str,
+    sampler: SamplerSettings,
+) -> TextureHandle3d {
+    let handle = load_texture_3d(world, path);
+    set_texture_sampler_3d(world, handle, sampler);
+    handle
+}
+
+/// Start loading a color map in the background for the 3D renderer and
+/// return its handle at once. The handle samples as white until the image has been
+/// decoded on a loader thread and uploaded; track it with
+/// [`AssetServer::load_state`](crate::asset::AssetServer::load_state). Skips
+/// the import cache. Without an [`AssetServer`] this falls back to
+/// [`load_texture_3d`].
+pub fn load_texture_3d_async(world: &mut World, path: &str) -> TextureHandle3d {
+    if !world.has_resource::<AssetServer>() {
+        return load_texture_3d(world, path);
+    }
+    let path = crate::launch::resolve_asset_path(world, path).into_owned();
+    finish_render(world);
+    let mut store = world
+        .resource_remove::<TextureStore3d>()
+        .expect("TextureStore3d not initialized — render at least one frame first");
+    let key = (path.clone(), true);
+    if let Some(&handle) = store.path_cache.get(&key) {
+        world.insert_resource(store);
+        return handle;
+    }
+
+    let gpu = world.resource::<GpuContext>();
+    let handle = store.upload_rgba8(gpu, &path, 1, 1, &[255; 4], true);
+    store.path_cache.insert(key.clone(), handle);
+    world.insert_resource(store);
+
+    world.resource_mut::<AssetServer>().load_async(
+        PathBuf::from(&path),
+        crate::import::decode_rgba,
+        move |world, (width, height, data)| {
+            let Some(mut store) = world.resource_remove::<TextureStore3d>() else {
+                return;
+            };
+            if store.path_cache.get(&key) == Some(&handle) {
+                let gpu = world.resource::<GpuContext>();
+                store.reload_entry(gpu, handle, width, height, &data);
+            }
+            world.insert_resource(store);
+            world
+                .resource_mut::<AssetServer>()
+                .watch(PathBuf::from(&path), AssetKind::Texture3d(handle));
+        },
+    );
+    handle
+}
+
+/// Change how an already-loaded 3D texture is sampled.
+pub fn set_texture_sampler_3d(world: &mut World, handle: TextureHandle3d, sampler: SamplerSettings) {
+    finish_render(world);
+    let Some(mut store) = world.resource_remove::<TextureStore3d>() else {
+        log::warn!("set_texture_sampler_3d: no 3D textures loaded yet");
+        return;
+    };
+    store.set_sampler(world.resource::<GpuContext>(), handle, sampler);
+    world.insert_resource(store);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bound_follows_binding_order_with_white_for_gaps() {
+        let textures = MaterialTextures {
+            base_color: Some(TextureHandle3d(1)),
+            normal: Some(TextureHandle3d(2)),
+            metallic_roughness: None,
+            emissive: Some(TextureHandle3d(4)),
+            ao: Some(TextureHandle3d(5)),
+        };
+        assert_eq!(textures.bound(), [1, 2, 0, 4, 5].map(TextureHandle3d));
+        assert_eq!(MaterialTextures::default().bound(), [TextureStore3d::DEFAULT; 5]);
+        // Only base color and emissive are colors.
+        assert_eq!(MaterialTextures::SRGB, [true, false, false, true, false]);
+    }
+}
@@ -0,0 +1,429 @@
+//! # Transparency — Sorted Blending or Weighted-Blended OIT
+//!
+//! A [`Material`](super::Material) with [`AlphaMode::Blend`] is see-through:
+//! its `base_color` alpha (times the texture's and the vertex color's)
+//! decides how much of the scene behind it shows. Transparent meshes are
+//! pulled out of the opaque list and drawn after it, depth-tested against
+//! the opaque scene but never writing depth. How they combine with each
+//! other is chosen per camera with [`Transparency`].
+//!
+//! ## Sorted (default)
+//!
+//! Meshes are sorted far → near by their origin and alpha-blended in that
+//! order. Correct as long as the sort is: one window in front of another is
+//! fine, but two meshes that intersect — or one large mesh whose origin is
+//! nearer than the small one inside it — can only be ordered one way per
+//! frame, and the order *pops* as the camera moves.
+//!
+//! ## Weighted-Blended OIT
+//!
+//! Order-independent transparency (McGuire & Bavoil, 2013) replaces the
+//! ordered blend with two sums that commute, so draw order stops mattering:
+//!
+//! ```text
+//!   opaque pass ──► scene color + depth
+//!                          │ depth (test only)
+//!                          ▼
+//!   accumulate ──► accum      Σ (rgb × a, a) × w(depth, a)   Rgba16Float
+//!              └─► revealage  Π (1 − a)                      R16Float
+//!                          │
+//!                          ▼
+//!   composite ──► scene = mix(scene, accum.rgb / accum.a, 1 − revealage)
+//! ```
+//!
+//! The weight `w` favors near, opaque fragments, so the front layer reads
+//! through. The result is an approximation: heavily stacked layers of
+//! similar depth average together instead of occluding each other, and very
+//! high alpha looks a little washed out. For glass, water, foliage cards and
+//! force fields that's rarely visible; popping is.
+//!
+//! Under weighted-blended OIT, materials with a
+//! [material shader](super::material_shader) can't be accumulated (the
+//! shader has no `fs_oit` entry point) and are still drawn sorted, before
+//! the composite.
+//!
+//! [`SoftParticle`](super::SoftParticle)s and particle emitters keep their
+//! own sorted passes either way.
+//!
+//! With [MSAA](crate::render::msaa) on, the accumulation pass draws into
+//! multisampled copies of the two targets (the depth buffer it tests
+//! against is multisampled) and resolves them into the ones the composite
+//! reads, averaging the sums per pixel.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Transparent materials go in the Transparent render queue,
+//!   sorted back to front; HDRP adds per-material sorting priority. No
+//!   built-in OIT.
+//! - **Bevy**: `AlphaMode::Blend` sorted by distance; an
+//!   `OrderIndependentTransparencySettings` camera component adds per-pixel
+//!   linked-list OIT.
+//! - **Godot**: `Transparency` on `BaseMaterial3D`, sorted by object
+//!   center; per-object `sorting_offset` to fix the order by hand.
+//! - **Our approach**: `AlphaMode::Blend` on the material, sorted by
+//!   default; one camera field switches to weighted-blended OIT, which needs
+//!   only two extra render targets and no per-pixel lists.
+
+use crate::render::gpu::GpuContext;
+use crate::render::msaa::{ColorTarget, TargetKey, multisample_state};
+
+use super::material_shader::MaterialShader;
+use super::pipeline::ColorOutput;
+
+/// Accumulation target format: premultiplied color × weight, summed.
+pub(crate) const ACCUM_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
+/// Revealage target format: the product of `1 − alpha`.
+pub(crate) const REVEALAGE_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R16Float;
+
+// ── Public types ────────────────────────────────────────────────────────
+
+/// How a [`Material`](super::Material) treats its alpha.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+    /// Alpha is ignored; the mesh hides everything behind it.
+    #[default]
+    Opaque,
+    /// Alpha-blended over the scene. See the [module docs](self).
+    Blend,
+}
+
+/// How a [`Camera3d`](super::Camera3d) combines overlapping transparent
+/// meshes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Transparency {
+    /// Sort far → near and blend in order.
+    #[default]
+    Sorted,
+    /// Weighted-blended order-independent transparency: no sort, no
+    /// popping, approximate colors where many layers overlap.
+    WeightedBlended,
+}
+
+// ── Pipeline targets ────────────────────────────────────────────────────
+
+/// Where a draw call's color goes under the camera's `transparency`.
+/// Material shaders have no `fs_oit`, so they stay sorted.
+pub(crate) fn color_output(
+    alpha_mode: AlphaMode,
+    shader: Option<MaterialShader>,
+    transparency: Transparency,
+) -> ColorOutput {
+    match (alpha_mode, transparency) {
+        (AlphaMode::Opaque, _) => ColorOutput::Opaque,
+        (AlphaMode::Blend, Transparency::WeightedBlended) if shader.is_none() => ColorOutput::Accumulated,
+        (AlphaMode::Blend, _) => ColorOutput::Blended,
+    }
+}
+
+/// The two color targets `fs_oit` writes, with their order-independent
+/// blend equations.
+pub(crate) fn accumulation_targets() -> [Option<wgpu::ColorTargetState>; 2] {
+    let additive = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::One,
+        dst_factor: wgpu::BlendFactor::One,
+        operation: wgpu::BlendOperation::Add,
+    };
+    let revealage = wgpu::BlendComponent {
+        src_factor: wgpu::BlendFactor::Zero,
+        dst_factor: wgpu::BlendFactor::OneMinusSrc,
+        operation: wgpu::BlendOperation::Add,
+    };
+    [
+        Some(wgpu::ColorTargetState {
+            format: ACCUM_FORMAT,
+            blend: Some(wgpu::BlendState {
+                color: additive,
+                alpha: additive,
+            }),
+            write_mask: wgpu::ColorWrites::ALL,
+        }),
+        Some(wgpu::ColorTargetState {
+            format: REVEALAGE_FORMAT,
+            blend: Some(wgpu::BlendState {
+                color: revealage,
+                alpha: revealage,
+            }),
+            write_mask: wgpu::ColorWrites::ALL,
+        }),
+    ]
+}
+
+// ── GPU resources ───────────────────────────────────────────────────────
+
+/// Accumulation targets and the composite pipeline. Created the first time
+/// a camera uses [`Transparency::WeightedBlended`].
+pub(crate) struct OitRenderer {
+    targets: OitTargets,
+    size: (u32, u32),
+    /// Color target the composite pipeline was built for; its sample
+    /// count is the accumulation pass's too.
+    pub target: TargetKey,
+    bind_group_layout: wgpu::BindGroupLayout,
+    composite_pipeline: wgpu::RenderPipeline,
+}
+
+/// The accumulation targets at one size.
+struct OitTargets {
+    accum: wgpu::TextureView,
+    revealage: wgpu::TextureView,
+    /// Multisampled accum and revealage, resolved into the two above.
+    msaa: Option<(wgpu::TextureView, wgpu::TextureView)>,
+    /// Reads `accum` and `revealage` for the composite.
+    bind_group: wgpu::BindGroup,
+}
+
+impl OitRenderer {
+    pub fn new(gpu: &GpuContext, size: (u32, u32), target: TargetKey) -> Self {
+        let (format, samples) = target;
+        let device = &gpu.device;
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("oit composite shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("oit.wgsl").into()),
+        });
+
+        let texture_entry = |binding| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("oit composite bind group layout"),
+            entries: &[texture_entry(0), texture_entry(1)],
+        });
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("oit composite pipeline layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let composite_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("oit composite pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::COLOR,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: multisample_state(samples),
+            multiview: None,
+            cache: None,
+        });
+
+        let targets = create_targets(device, &bind_group_layout, size, samples);
+        Self {
+            targets,
+            size,
+            target,
+            bind_group_layout,
+            composite_pipeline,
+        }
+    }
+
+    /// Recreate the targets if the scene target changed size.
+    pub fn resize_if_needed(&mut self, device: &wgpu::Device, size: (u32, u32)) {
+        if self.size != size {
+            self.targets = create_targets(device, &self.bind_group_layout, size, self.target.1);
+            self.size = size;
+        }
+    }
+
+    /// Begin the accumulation pass: both targets cleared, `depth` loaded
+    /// for testing.
+    pub fn begin_accumulation<'a>(
+        &self,
+        encoder: &'a mut wgpu::CommandEncoder,
+        depth: &wgpu::TextureView,
+    ) -> wgpu::RenderPass<'a> {
+        let target = |view, resolve_target, clear| {
+            Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear),
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })
+        };
+        let targets = &self.targets;
+        let color_attachments = match &targets.msaa {
+            Some((accum, revealage)) => [
+                target(accum, Some(&targets.accum), wgpu::Color::TRANSPARENT),
+                target(revealage, Some(&targets.revealage), wgpu::Color::WHITE),
+            ],
+            None => [
+                target(&targets.accum, None, wgpu::Color::TRANSPARENT),
+                target(&targets.revealage, None, wgpu::Color::WHITE),
+            ],
+        };
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("oit accumulation pass"),
+            color_attachments: &color_attachments,
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: depth,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        })
+    }
+
+    /// Blend the accumulated transparency over `target`.
+    pub fn composite(&self, encoder: &mut wgpu::CommandEncoder, target: &ColorTarget) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("oit composite pass"),
+            color_attachments: &[Some(target.attachment(wgpu::LoadOp::Load))],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.composite_pipeline);
+        pass.set_bind_group(0, &self.targets.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+/// Create the accumulation and revealage textures (plus multisampled
+/// copies for `samples` > 1) and the composite bind group reading them.
+fn create_targets(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    (width, height): (u32, u32),
+    samples: u32,
+) -> OitTargets {
+    let target = |label, format, sample_count| {
+        device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: Some(label),
+                size: wgpu::Extent3d {
+                    width: width.max(1),
+                    height: height.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format,
+                usage: if sample_count > 1 {
+                    wgpu::TextureUsages::RENDER_ATTACHMENT
+                } else {
+                    wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING
+                },
+                view_formats: &[],
+            })
+            .create_view(&wgpu::TextureViewDescriptor::default())
+    };
+    let accum = target("oit accum texture", ACCUM_FORMAT, 1);
+    let revealage = target("oit revealage texture", REVEALAGE_FORMAT, 1);
+    let msaa = (samples > 1).then(|| {
+        (
+            target("oit accum texture (msaa)", ACCUM_FORMAT, samples),
+            target("oit revealage texture (msaa)", REVEALAGE_FORMAT, samples),
+        )
+    });
+    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: Some("oit composite bind group"),
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&accum),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(&revealage),
+            },
+        ],
+    });
+    OitTargets {
+        accum,
+        revealage,
+        msaa,
+        bind_group,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render3d::billboard::BillboardView;
+    use crate::render3d::collect::{collect_draw_calls, ExtractedMesh};
+    use crate::render3d::mesh::MeshHandle;
+    use crate::render3d::Projection3d;
+    use crate::render3d::vertex::MaterialUniform;
+
+    fn mesh(index: usize, z: f32, alpha_mode: AlphaMode) -> ExtractedMesh {
+        ExtractedMesh {
+            matrix: glam::Mat4::from_translation(glam::Vec3::new(0.0, 0.0, z)),
+            billboard: None,
+            scale: glam::Vec3::ONE,
+            mesh: MeshHandle(index),
+            material_uniform: MaterialUniform {
+                base_color: [1.0, 1.0, 1.0, 0.5],
+                metallic: 0.0,
+                roughness: 0.5,
+                normal_scale: 1.0,
+                ao_strength: 1.0,
+                emissive: [0.0; 3],
+                normal_mapped: 0,
+            },
+            textures: Default::default(),
+            shader: None,
+            alpha_mode,
+        }
+    }
+
+    #[test]
+    fn transparent_draws_follow_opaque_far_to_near() {
+        // Camera at z = 10 looking down -Z: lower z is farther away.
+        let view = BillboardView::new(
+            glam::Mat4::from_translation(glam::Vec3::new(0.0, 0.0, 10.0)),
+            Projection3d::perspective(45.0),
+            (1280, 720),
+        );
+        let meshes = [
+            mesh(0, -5.0, AlphaMode::Blend),
+            mesh(1, 0.0, AlphaMode::Opaque),
+            mesh(2, -20.0, AlphaMode::Blend),
+            mesh(3, 5.0, AlphaMode::Blend),
+            mesh(4, -30.0, AlphaMode::Opaque),
+        ];
+        let order: Vec<usize> = collect_draw_calls(&meshes, Some(&view), None)
+            .iter()
+            .map(|call| call.mesh.0)
+            .collect();
+        assert!(order[..2].contains(&1) && order[..2].contains(&4));
+        assert_eq!(order[2..], [2, 0, 3]);
+    }
+
+    #[test]
+    fn material_shaders_stay_sorted_under_oit() {
+        let oit = Transparency::WeightedBlended;
+        assert_eq!(color_output(AlphaMode::Opaque, None, oit), ColorOutput::Opaque);
+        assert_eq!(color_output(AlphaMode::Blend, None, oit), ColorOutput::Accumulated);
+        assert_eq!(color_output(AlphaMode::Blend, Some(MaterialShader(0)), oit), ColorOutput::Blended);
+        assert_eq!(color_output(AlphaMode::Blend, None, Transparency::Sorted), ColorOutput::Blended);
+    }
+}
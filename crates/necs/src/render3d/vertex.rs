@@ -23,6 +23,27 @@
 //! omit tangent vectors (needed for normal mapping) to keep things simple —
 //! that's a future phase.
 //!
+//! ## Optional Attributes
+//!
+//! Some meshes carry more per-vertex data: vertex colors (often baked ambient
+//! occlusion), a second UV set (lightmaps, detail textures), or custom data
+//! for a [material shader](super::material_shader). These live in a second
+//! vertex buffer, holding only the attributes the mesh has:
+//!
+//! ```text
+//! slot 0: MeshVertex (always)      slot 1: extras (per mesh, interleaved)
+//! ┌──────────┬────────┬──────┐     ┌──────────────┬────────┬──────────────┐
+//! │ position │ normal │ uv   │     │ color        │ uv1    │ custom       │
+//! │ loc 0    │ loc 1  │ loc 2│     │ loc 3, vec4  │ loc 4  │ loc 5, vec4  │
+//! └──────────┴────────┴──────┘     └──────────────┴────────┴──────────────┘
+//!                                    each column present only if the mesh
+//!                                    has it; shader locations never move
+//! ```
+//!
+//! Every mesh still shares slot 0's layout, so depth-only passes ignore the
+//! extras entirely. The main pass builds one pipeline per combination of
+//! [`MeshAttributes`] it actually sees.
+//!
 //! ## Uniform Buffers
 //!
 //! 3D rendering needs four categories of data beyond vertex attributes, each
@@ -35,8 +56,9 @@
 //! │   80 bytes                                                  │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │ Group 1 — Lights (per frame)                                │
-//! │   1 directional light + ambient + 8 point lights + count    │
-//! │   304 bytes                                                 │
+//! │   uniform: 1 directional light + ambient + light counts     │
+//! │   64 bytes                                                  │
+//! │   storage: point lights (48 bytes each), spot lights (64)   │
 //! ├─────────────────────────────────────────────────────────────┤
 //! │ Group 2 — Material (per material)                           │
 //! │   base_color, metallic, roughness, emissive                 │
@@ -107,6 +129,122 @@ impl MeshVertex {
     };
 }
 
+/// Which optional vertex attributes a mesh carries, beyond position, normal
+/// and UV. Combine with `|`.
+///
+/// | Attribute | Shader location | Type |
+/// |-----------|-----------------|------|
+/// | [`COLOR`](Self::COLOR) | 3 | `vec4<f32>` |
+/// | [`UV1`](Self::UV1) | 4 | `vec2<f32>` |
+/// | [`CUSTOM`](Self::CUSTOM) | 5 | `vec4<f32>` |
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct MeshAttributes(u8);
+
+impl MeshAttributes {
+    pub const NONE: Self = Self(0);
+    /// Linear RGBA vertex color (glTF `COLOR_0`).
+    pub const COLOR: Self = Self(1);
+    /// Second texture coordinate set (glTF `TEXCOORD_1`).
+    pub const UV1: Self = Self(1 << 1);
+    /// Four floats of application data, for material shaders.
+    pub const CUSTOM: Self = Self(1 << 2);
+
+    /// Returns `true` if every attribute in `other` is present.
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl std::fmt::Debug for MeshAttributes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = [(Self::COLOR, "COLOR"), (Self::UV1, "UV1"), (Self::CUSTOM, "CUSTOM")]
+            .into_iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|(_, name)| name)
+            .collect();
+        if names.is_empty() {
+            write!(f, "NONE")
+        } else {
+            write!(f, "{}", names.join(" | "))
+        }
+    }
+}
+
+impl std::ops::BitOr for MeshAttributes {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for MeshAttributes {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Each optional attribute: flag, shader location, format, size in floats.
+const OPTIONAL_ATTRIBUTES: [(MeshAttributes, u32, wgpu::VertexFormat, usize); 3] = [
+    (MeshAttributes::COLOR, 3, wgpu::VertexFormat::Float32x4, 4),
+    (MeshAttributes::UV1, 4, wgpu::VertexFormat::Float32x2, 2),
+    (MeshAttributes::CUSTOM, 5, wgpu::VertexFormat::Float32x4, 4),
+];
+
+/// Vertex layout of the slot-1 buffer holding a mesh's optional attributes.
+/// Owns its attribute list, which [`wgpu::VertexBufferLayout`] borrows.
+pub(crate) struct ExtraVertexLayout {
+    pub stride: u64,
+    pub attributes: Vec<wgpu::VertexAttribute>,
+}
+
+impl ExtraVertexLayout {
+    /// Interleaved layout for `attributes`, or `None` if there are none.
+    pub fn new(attributes: MeshAttributes) -> Option<Self> {
+        let mut offset = 0;
+        let mut list = Vec::new();
+        for (flag, location, format, floats) in OPTIONAL_ATTRIBUTES {
+            if attributes.contains(flag) {
+                list.push(wgpu::VertexAttribute {
+                    offset,
+                    shader_location: location,
+                    format,
+                });
+                offset += (floats * 4) as u64;
+            }
+        }
+        (!list.is_empty()).then_some(Self {
+            stride: offset,
+            attributes: list,
+        })
+    }
+
+    pub fn layout(&self) -> wgpu::VertexBufferLayout<'_> {
+        wgpu::VertexBufferLayout {
+            array_stride: self.stride,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &self.attributes,
+        }
+    }
+}
+
+/// Interleave optional attribute streams into slot-1 vertex data, in
+/// [`ExtraVertexLayout`] order. Each stream has `floats` values per vertex.
+pub(crate) fn interleave_extras(streams: &[(&[f32], usize)], vertex_count: usize) -> Vec<f32> {
+    let stride: usize = streams.iter().map(|(_, floats)| floats).sum();
+    let mut data = Vec::with_capacity(stride * vertex_count);
+    for v in 0..vertex_count {
+        for (values, floats) in streams {
+            data.extend_from_slice(&values[v * floats..(v + 1) * floats]);
+        }
+    }
+    data
+}
+
 /// Camera uniform: view-projection matrix + world-space position.
 ///
 /// The camera position is needed for specular reflection calculations — the
@@ -122,25 +260,44 @@ pub(crate) struct CameraUniform3d {
 
 /// Data for a single point light, packed for GPU upload.
 ///
-/// 32 bytes per light: position (vec3 + pad), color (vec3 + pad) where the
-/// padding slots hold intensity and radius.
+/// 48 bytes per light: position (vec3 + pad), color (vec3 + pad) where the
+/// padding slots hold intensity and radius, then the attenuation curve
+/// padded to the struct's 16-byte alignment.
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub(crate) struct PointLightData {
     pub position: [f32; 3], // 12 bytes
     pub radius: f32,        // 4 bytes
     pub color: [f32; 3],    // 12 bytes
-    pub intensity: f32,     // 4 bytes → total 32
+    pub intensity: f32,     // 4 bytes
+    pub attenuation: u32,   // 4 bytes
+    pub _pad: [u32; 3],     // 12 bytes → total 48
 }
 
-/// Maximum number of point lights supported per frame. Fixed array avoids
-/// dynamic shader arrays, which require more complex buffer management.
-pub(crate) const MAX_POINT_LIGHTS: usize = 8;
+/// Data for a single spot light, packed for GPU upload.
+///
+/// Like [`PointLightData`] plus the cone: its direction and the cosines of
+/// the inner and outer half-angles (the shader compares against the cosine
+/// of the angle to each pixel, so no trigonometry per pixel).
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+pub(crate) struct SpotLightData {
+    pub position: [f32; 3],  // 12 bytes
+    pub range: f32,          // 4 bytes
+    pub color: [f32; 3],     // 12 bytes
+    pub intensity: f32,      // 4 bytes
+    pub direction: [f32; 3], // 12 bytes
+    pub cos_inner: f32,      // 4 bytes
+    pub cos_outer: f32,      // 4 bytes
+    pub attenuation: u32,    // 4 bytes
+    pub _pad: [u32; 2],      // 8 bytes → total 64
+}
 
-/// Light uniform: all lighting data packed into one buffer.
+/// Light uniform: the directional and ambient lights, and how many entries
+/// of the point and spot light storage buffers are in use.
 ///
-/// Layout: directional light (32 bytes) + ambient (16 bytes) +
-/// 8 point lights (256 bytes) + count (16 bytes with padding) = 320 bytes.
+/// Layout: directional light (32 bytes) + ambient (16 bytes) + counts
+/// (16 bytes with padding) = 64 bytes.
 #[repr(C)]
 #[derive(Clone, Copy, Pod, Zeroable)]
 pub(crate) struct LightUniform {
@@ -154,12 +311,38 @@ pub(crate) struct LightUniform {
     pub ambient_color: [f32; 3], // 12 bytes
     pub ambient_intensity: f32,  // 4 bytes → 16
 
-    // Point lights (fixed array of 8)
-    pub point_lights: [PointLightData; MAX_POINT_LIGHTS], // 256 bytes
-
-    // Count
+    // Counts
     pub point_light_count: u32, // 4 bytes
-    pub _pad1: [u32; 3],       // 12 bytes → 16
+    pub spot_light_count: u32,  // 4 bytes
+    pub _pad1: [u32; 2],        // 8 bytes → 16
+
+    // Sky ambient (from a Skybox; zero intensity without one)
+    pub sky_color: [f32; 3],    // 12 bytes
+    pub sky_intensity: f32,     // 4 bytes
+    pub ground_color: [f32; 3], // 12 bytes
+    pub _pad2: f32,             // 4 bytes → 32
+}
+
+impl Default for LightUniform {
+    /// No directional light, dim white ambient, no point or spot lights,
+    /// no sky ambient.
+    fn default() -> Self {
+        Self {
+            dir_direction: [0.0, -1.0, 0.0],
+            dir_intensity: 0.0,
+            dir_color: [1.0, 1.0, 1.0],
+            _pad0: 0.0,
+            ambient_color: [1.0, 1.0, 1.0],
+            ambient_intensity: 0.1,
+            point_light_count: 0,
+            spot_light_count: 0,
+            _pad1: [0; 2],
+            sky_color: [0.0; 3],
+            sky_intensity: 0.0,
+            ground_color: [0.0; 3],
+            _pad2: 0.0,
+        }
+    }
 }
 
 /// Material uniform: PBR metallic-roughness parameters.
@@ -169,9 +352,10 @@ pub(crate) struct MaterialUniform {
     pub base_color: [f32; 4],  // 16 bytes
     pub metallic: f32,         // 4 bytes
     pub roughness: f32,        // 4 bytes
-    pub _pad0: [f32; 2],       // 8 bytes → 32
+    pub normal_scale: f32,     // 4 bytes
+    pub ao_strength: f32,      // 4 bytes → 32
     pub emissive: [f32; 3],    // 12 bytes
-    pub _pad1: f32,            // 4 bytes → 48
+    pub normal_mapped: u32,    // 4 bytes → 48 (1 if a normal map is bound)
 }
 
 /// Model uniform: transform + normal matrix, per object.
@@ -184,3 +368,40 @@ pub(crate) struct ModelUniform {
     pub model: [[f32; 4]; 4],         // 64 bytes
     pub normal_matrix: [[f32; 4]; 4], // 64 bytes → total 128
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_layout_packs_only_present_attributes() {
+        assert!(ExtraVertexLayout::new(MeshAttributes::NONE).is_none());
+
+        let layout = ExtraVertexLayout::new(MeshAttributes::COLOR | MeshAttributes::CUSTOM).unwrap();
+        assert_eq!(layout.stride, 32);
+        let placed: Vec<_> = layout
+            .attributes
+            .iter()
+            .map(|a| (a.shader_location, a.offset))
+            .collect();
+        // Locations stay fixed; offsets close the gap left by UV1.
+        assert_eq!(placed, [(3, 0), (5, 16)]);
+    }
+
+    #[test]
+    fn light_structs_match_wgsl_layout() {
+        // WGSL rounds struct sizes up to their 16-byte (vec3) alignment;
+        // storage array strides must match exactly.
+        assert_eq!(std::mem::size_of::<PointLightData>(), 48);
+        assert_eq!(std::mem::size_of::<SpotLightData>(), 64);
+        assert_eq!(std::mem::size_of::<LightUniform>(), 96);
+    }
+
+    #[test]
+    fn interleave_alternates_streams_per_vertex() {
+        let uv1 = [0.0, 1.0, 2.0, 3.0];
+        let custom = [9.0; 8];
+        let data = interleave_extras(&[(&uv1, 2), (&custom, 4)], 2);
+        assert_eq!(data, [0.0, 1.0, 9.0, 9.0, 9.0, 9.0, 2.0, 3.0, 9.0, 9.0, 9.0, 9.0]);
+    }
+}
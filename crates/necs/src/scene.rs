@@ -21,35 +21,137 @@
 //! let entities = load_scene(&mut world, &registry, &data);
 //! let entities = load_scene_from_file(&mut world, &registry, "level.json");
 //! ```
+//!
+//! ## Validation
+//!
+//! Scene files are edited by hand and by tools, and go stale when a component
+//! is renamed. Loading never panics on bad data; instead every problem is
+//! reported as a [`SceneError`] saying *where* it is — a line and column for
+//! malformed JSON, an entity id and component name for everything else:
+//!
+//! ```text
+//!   level.json ──► parse_scene ──► Syntax { line, column }   (nothing loads)
+//!                      │
+//!                      ▼
+//!                  validate ──► UnknownComponent { entity, component }
+//!                      │        InvalidComponent { entity, component, message }
+//!                      │        DuplicateId { entity }
+//!                      │        MissingChild { entity, child }
+//!                      ▼
+//!       Strict:  any error → Err(errors), nothing spawned
+//!       Lenient: bad entities skipped, dangling children dropped
+//! ```
+//!
+//! Both modes log each error, and file loads also add it to the asset reload
+//! log so it shows up in `necs-telemetry`. [`load_scene`] and
+//! [`load_scene_from_file`] are lenient; use [`try_load_scene`] /
+//! [`try_load_scene_from_file`] with [`SceneLoadMode::Strict`] to reject a
+//! broken file as a whole, or [`validate_scene`] to check one without loading.
+//!
+//! ## Format and Versions
+//!
+//! Besides components, a scene keeps each entity's name and tags, and any
+//! resources registered with [`SceneRegistry::register_resource`] (ambient
+//! light, clear color, ...). Every file records the format version it was
+//! written with:
+//!
+//! ```text
+//!   {
+//!     "version": 3,
+//!     "resources": { "ClearColor": [0.1, 0.1, 0.2, 1.0] },
+//!     "entities": [
+//!       { "id": 0, "name": "player", "tags": ["hero"],
+//!         "components": { "Transform": { ... } } }
+//!     ]
+//!   }
+//! ```
+//!
+//! [`parse_scene`] upgrades older files one version at a time before
+//! decoding, so a file saved by any earlier release still loads (a file
+//! without `"version"` is version 1). A file from a *newer* release is
+//! rejected with [`SceneError::UnsupportedVersion`] rather than guessed at.
+//!
+//! Names must be unique in the world: loading a scene whose entity name is
+//! already taken (say, the same level twice) logs a warning and spawns that
+//! entity without its name.
+//!
+//! ## Stable Ids
+//!
+//! An [`Entity`] id is only good until the entity is despawned; load the
+//! level again and the door's target spawn point has a different id. A
+//! [`Uid`] is an id that lives in the scene file instead, so a reference
+//! stored as a `Uid` survives unloading and reloading, in any order:
+//!
+//! ```text
+//!   castle.json                          world (after load, unload, load)
+//!   { "id": 4, "uid": "6f1c…-…",         Entity(57v3) ── Uid(6f1c…) ◄─┐
+//!     "name": "east_gate" }                                           │
+//!   { "id": 9, "components": {           Door { target: Uid(6f1c…) } ─┘
+//!       "Door": { "target": "6f1c…" } } }    world.by_uid(target) → Entity(57v3)
+//! ```
+//!
+//! Saving writes each entity's `Uid` component. A named entity without one
+//! gets a uid hashed from its name path (`"castle/east_gate"`), so it stays
+//! the same from save to save; give unnamed entities one with
+//! [`Uid::new`]. Loading inserts the component again. Like names, a uid
+//! already in the world is not loaded twice: the second entity logs a
+//! warning and loads without one.
+//!
+//! Components refer to other entities through an
+//! [`EntityRef`](crate::entity_ref::EntityRef), which is saved as a uid and
+//! resolved once its target loads.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Scenes are YAML with per-object file IDs; a serialized
+//!   version per class drives `FormerlySerializedAs` and upgrade callbacks.
+//! - **Bevy**: `DynamicScene` stores entities and resources through the
+//!   type registry; there is no built-in format version.
+//! - **Godot**: `.tscn` carries a `format=` number and nodes keep their
+//!   names and groups; the engine converts older formats on load.
+//! - **Our approach**: Godot's explicit format number with step-by-step
+//!   upgrades, and Bevy's registry-driven resources.
 
 use std::any::{Any, TypeId};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
 use std::path::Path;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use serde::{Deserialize, Serialize};
 
 use crate::ecs::hierarchy::{Children, GlobalTransform, Parent};
 use crate::ecs::world::World;
 use crate::ecs::Entity;
+use crate::entity_ref::{EntityRef, collect_refs, resolve_refs};
 
 // ── SceneRegistry ────────────────────────────────────────────────────────
 
 type SerializeFn = fn(&dyn Any) -> Option<serde_json::Value>;
-type DeserializeFn = fn(serde_json::Value) -> Option<Box<dyn Any + Send + Sync>>;
+type DeserializeFn = fn(serde_json::Value) -> Result<Box<dyn Any + Send + Sync>, String>;
 
 struct ComponentFns {
     serialize: SerializeFn,
     deserialize: DeserializeFn,
-    default_fn: Option<Box<dyn Fn() -> serde_json::Value>>,
+    default_fn: Option<Box<dyn Fn() -> serde_json::Value + Send + Sync>>,
     short_name: String,
 }
 
+struct ResourceFns {
+    save: fn(&World) -> Option<serde_json::Value>,
+    deserialize: DeserializeFn,
+    insert: fn(&mut World, Box<dyn Any + Send + Sync>),
+}
+
 /// Maps component types to serialize/deserialize function pointers.
 ///
 /// Register each component type you want to include in saved scenes.
 pub struct SceneRegistry {
     by_type_id: HashMap<TypeId, ComponentFns>,
     by_name: HashMap<String, TypeId>,
+    resources: HashMap<String, ResourceFns>,
 }
 
 impl SceneRegistry {
@@ -57,6 +159,7 @@ impl SceneRegistry {
         Self {
             by_type_id: HashMap::new(),
             by_name: HashMap::new(),
+            resources: HashMap::new(),
         }
     }
 
@@ -77,8 +180,8 @@ impl SceneRegistry {
                 serde_json::to_value(val).ok()
             },
             deserialize: |json| {
-                let val: T = serde_json::from_value(json).ok()?;
-                Some(Box::new(val))
+                let val: T = serde_json::from_value(json).map_err(|e| e.to_string())?;
+                Ok(Box::new(val))
             },
             default_fn: None,
             short_name: short.clone(),
@@ -103,8 +206,8 @@ impl SceneRegistry {
                 serde_json::to_value(val).ok()
             },
             deserialize: |json| {
-                let val: T = serde_json::from_value(json).ok()?;
-                Some(Box::new(val))
+                let val: T = serde_json::from_value(json).map_err(|e| e.to_string())?;
+                Ok(Box::new(val))
             },
             default_fn: Some(Box::new({
                 let default = default.clone();
@@ -117,6 +220,28 @@ impl SceneRegistry {
         self.by_name.insert(short, type_id);
     }
 
+    /// Register a resource type to save with the scene. Saving skips it if
+    /// the world doesn't have it; loading replaces the world's copy.
+    pub fn register_resource<T>(&mut self)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+    {
+        let fns = ResourceFns {
+            save: |world| serde_json::to_value(world.get_resource::<T>()?).ok(),
+            deserialize: |json| {
+                let val: T = serde_json::from_value(json).map_err(|e| e.to_string())?;
+                Ok(Box::new(val))
+            },
+            insert: |world, boxed| {
+                if let Ok(val) = boxed.downcast::<T>() {
+                    world.insert_resource(*val);
+                }
+            },
+        };
+        self.resources
+            .insert(short_type_name(std::any::type_name::<T>()), fns);
+    }
+
     /// Returns all registered component names (for "Add Component" dropdown).
     pub fn component_names(&self) -> Vec<&str> {
         self.by_name.keys().map(|s| s.as_str()).collect()
@@ -130,6 +255,48 @@ impl SceneRegistry {
         Some(default_fn())
     }
 
+    /// Registered name of a component type, if it is registered.
+    #[cfg_attr(not(feature = "editor"), allow(dead_code))]
+    pub(crate) fn component_name(&self, type_id: TypeId) -> Option<&str> {
+        Some(self.by_type_id.get(&type_id)?.short_name.as_str())
+    }
+
+    /// Type of a registered component name.
+    #[cfg_attr(not(feature = "editor"), allow(dead_code))]
+    pub(crate) fn component_type(&self, name: &str) -> Option<TypeId> {
+        self.by_name.get(name).copied()
+    }
+
+    /// An entity's registered component as JSON.
+    #[cfg_attr(not(feature = "editor"), allow(dead_code))]
+    pub(crate) fn component_value(
+        &self,
+        world: &World,
+        entity: Entity,
+        name: &str,
+    ) -> Option<serde_json::Value> {
+        let type_id = self.component_type(name)?;
+        let any = world.get_any_by_type_id(entity, type_id)?;
+        (self.by_type_id.get(&type_id)?.serialize)(any)
+    }
+
+    /// Decode a registered component from JSON and insert it on `entity`.
+    #[cfg_attr(not(feature = "editor"), allow(dead_code))]
+    pub(crate) fn insert_component(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        name: &str,
+        value: serde_json::Value,
+    ) -> Result<(), String> {
+        let type_id = self
+            .component_type(name)
+            .ok_or_else(|| format!("component '{name}' is not registered"))?;
+        let boxed = (self.by_type_id[&type_id].deserialize)(value)?;
+        insert_any(world, entity, type_id, name, boxed);
+        Ok(())
+    }
+
     // ── Convenience methods (wrap the free functions) ────────────────
 
     /// Save all entities in the world to a [`SceneData`].
@@ -181,61 +348,170 @@ impl SceneRegistry {
 
 // ── Scene Data (JSON wire format) ────────────────────────────────────────
 
+/// Version of the scene format [`save_scene`] writes.
+///
+/// - 1: entities with components and children.
+/// - 2: adds entity names and tags, and scene resources.
+/// - 3: adds stable entity uids.
+pub const SCENE_FORMAT_VERSION: u32 = 3;
+
 /// A serialized scene containing entities and their components.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SceneData {
+    /// Format version the scene was written with. Files from before
+    /// versioning have none and count as version 1.
+    #[serde(default = "first_version")]
+    pub version: u32,
     pub entities: Vec<SceneEntity>,
+    /// Registered resources by type name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub resources: BTreeMap<String, serde_json::Value>,
+}
+
+impl Default for SceneData {
+    fn default() -> Self {
+        Self {
+            version: SCENE_FORMAT_VERSION,
+            entities: Vec::new(),
+            resources: BTreeMap::new(),
+        }
+    }
+}
+
+fn first_version() -> u32 {
+    1
 }
 
 /// A single entity in a serialized scene.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SceneEntity {
     pub id: u32,
+    /// Unique name, as given to `ctx.spawn("player")`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Stable id that survives reloading; see [`Uid`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub uid: Option<Uid>,
     pub components: HashMap<String, serde_json::Value>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub children: Vec<u32>,
 }
 
+// ── Stable ids ───────────────────────────────────────────────────────────
+
+/// Component: an entity id that is saved with the scene and survives
+/// unloading and reloading it, for references between scenes. Look the
+/// entity up with [`World::by_uid`]. See [stable ids](self#stable-ids).
+///
+/// Written as a UUID, `"6f1c2a9e-03d4-4b8e-9a51-7c2e0d4f8b13"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Uid(pub u128);
+
+impl Uid {
+    /// A new random id (a version 4 UUID).
+    pub fn new() -> Self {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        // Each RandomState is freshly keyed; the counter and clock make
+        // sure two calls never hash the same input anyway.
+        let state = std::collections::hash_map::RandomState::new();
+        let mut bits = 0u128;
+        for half in 0..2u8 {
+            let mut hasher = state.build_hasher();
+            hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+            hasher.write_u128(nanos);
+            hasher.write_u8(half);
+            bits = bits << 64 | hasher.finish() as u128;
+        }
+        // Version 4, RFC 4122 variant.
+        bits = (bits & !(0xF << 76)) | (0x4 << 76);
+        bits = (bits & !(0x3 << 62)) | (0x2 << 62);
+        Self(bits)
+    }
+
+    /// An id derived from a path such as `"castle/east_gate"`: the same
+    /// path always gives the same id (FNV-1a, 128-bit).
+    pub fn from_path(path: &str) -> Self {
+        const OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+        const PRIME: u128 = 0x0000000001000000000000000000013b;
+        let hash = path
+            .bytes()
+            .fold(OFFSET, |hash, byte| (hash ^ byte as u128).wrapping_mul(PRIME));
+        Self(hash)
+    }
+
+    /// The uid saved for a named entity without a [`Uid`]: hashed from the
+    /// names of it and its named ancestors.
+    pub(crate) fn from_names(world: &World, entity: Entity) -> Option<Self> {
+        let mut path = world.entity_name(entity)?.to_string();
+        let mut current = world.get::<Parent>(entity).map(|p| p.0);
+        while let Some(ancestor) = current {
+            if let Some(name) = world.entity_name(ancestor) {
+                path = format!("{name}/{path}");
+            }
+            current = world.get::<Parent>(ancestor).map(|p| p.0);
+        }
+        Some(Self::from_path(&path))
+    }
+}
+
+impl Default for Uid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for Uid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = format!("{:032x}", self.0);
+        write!(f, "{}-{}-{}-{}-{}", &hex[..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..])
+    }
+}
+
+impl FromStr for Uid {
+    type Err = String;
+
+    /// Parse 32 hex digits, with or without the UUID hyphens.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex: String = s.chars().filter(|&ch| ch != '-').collect();
+        if hex.len() != 32 {
+            return Err(format!("invalid uid \"{s}\": expected 32 hex digits"));
+        }
+        u128::from_str_radix(&hex, 16)
+            .map(Self)
+            .map_err(|_| format!("invalid uid \"{s}\": expected 32 hex digits"))
+    }
+}
+
+impl Serialize for Uid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Uid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 // ── Save / Load functions ────────────────────────────────────────────────
 
 /// Save all entities in the world to a [`SceneData`].
 ///
 /// Hierarchy relationships are encoded in `SceneEntity.children` rather than
 /// as components. `GlobalTransform`, `Parent`, and `Children` are not serialized.
+/// Registered resources the world has are saved alongside the entities.
 pub fn save_scene(world: &World, registry: &SceneRegistry) -> SceneData {
     // First pass: collect all entities and their serialized components.
     let mut entity_map: HashMap<u32, SceneEntity> = HashMap::new();
-    let skip_types = [
-        TypeId::of::<Parent>(),
-        TypeId::of::<Children>(),
-        TypeId::of::<GlobalTransform>(),
-        TypeId::of::<SceneMarker>(),
-    ];
-
     world.for_each_entity(|entity, type_ids| {
-        let mut components = HashMap::new();
-
-        for &tid in type_ids {
-            if skip_types.contains(&tid) {
-                continue;
-            }
-            if let Some(fns) = registry.by_type_id.get(&tid) {
-                if let Some(any) = world.get_any_by_type_id(entity, tid) {
-                    if let Some(json) = (fns.serialize)(any) {
-                        components.insert(fns.short_name.clone(), json);
-                    }
-                }
-            }
-        }
-
-        entity_map.insert(
-            entity.index(),
-            SceneEntity {
-                id: entity.index(),
-                components,
-                children: Vec::new(),
-            },
-        );
+        entity_map.insert(entity.index(), save_entity(world, registry, entity, type_ids));
     });
 
     // Second pass: populate children lists from hierarchy.
@@ -279,37 +555,451 @@ pub fn save_scene(world: &World, registry: &SceneRegistry) -> SceneData {
         }
     }
 
-    SceneData { entities }
+    let resources = registry
+        .resources
+        .iter()
+        .filter_map(|(name, fns)| Some((name.clone(), (fns.save)(world)?)))
+        .collect();
+
+    SceneData {
+        version: SCENE_FORMAT_VERSION,
+        entities,
+        resources,
+    }
+}
+
+/// Components a scene never stores as values: the hierarchy is saved as
+/// child lists, and the rest are rebuilt on load.
+pub(crate) fn structural_types() -> [TypeId; 5] {
+    [
+        TypeId::of::<Parent>(),
+        TypeId::of::<Children>(),
+        TypeId::of::<GlobalTransform>(),
+        TypeId::of::<SceneMarker>(),
+        TypeId::of::<Uid>(),
+    ]
+}
+
+/// Serialize one entity's registered components, name and tags, with its
+/// id set to its index. Children are left for the caller.
+pub(crate) fn save_entity(
+    world: &World,
+    registry: &SceneRegistry,
+    entity: Entity,
+    type_ids: &[TypeId],
+) -> SceneEntity {
+    let skip_types = structural_types();
+    let mut components = HashMap::new();
+
+    for &tid in type_ids {
+        if skip_types.contains(&tid) {
+            continue;
+        }
+        if let Some(fns) = registry.by_type_id.get(&tid) {
+            if let Some(any) = world.get_any_by_type_id(entity, tid) {
+                if let Some(json) = (fns.serialize)(any) {
+                    components.insert(fns.short_name.clone(), json);
+                }
+            }
+        }
+    }
+
+    SceneEntity {
+        id: entity.index(),
+        name: world.entity_name(entity).map(str::to_string),
+        tags: world.entity_tags(entity),
+        uid: world
+            .get::<Uid>(entity)
+            .copied()
+            .or_else(|| Uid::from_names(world, entity)),
+        components,
+        children: Vec::new(),
+    }
 }
 
 /// Load entities from a [`SceneData`] into the world.
 ///
-/// Returns the list of spawned entities.
+/// Lenient: entities that fail validation are skipped and the problems are
+/// logged (see [`try_load_scene`]). Returns the list of spawned entities.
 pub fn load_scene(
     world: &mut World,
     registry: &SceneRegistry,
     data: &SceneData,
 ) -> Vec<Entity> {
+    try_load_scene(world, registry, data, SceneLoadMode::Lenient).unwrap_or_default()
+}
+
+/// Validate and load entities from a [`SceneData`].
+///
+/// Every problem is logged. In [`SceneLoadMode::Strict`] any problem loads
+/// nothing and returns all of them; in [`SceneLoadMode::Lenient`] this
+/// always succeeds, skipping what it can't load.
+pub fn try_load_scene(
+    world: &mut World,
+    registry: &SceneRegistry,
+    data: &SceneData,
+    mode: SceneLoadMode,
+) -> Result<Vec<Entity>, Vec<SceneError>> {
+    load_checked(world, registry, data, mode, None)
+}
+
+/// Save all entities to a JSON file.
+pub fn save_scene_to_file(world: &World, registry: &SceneRegistry, path: impl AsRef<Path>) {
+    let data = save_scene(world, registry);
+    let json = serde_json::to_string_pretty(&data).expect("Failed to serialize scene");
+    std::fs::write(path, json).expect("Failed to write scene file");
+}
+
+/// Load entities from a JSON file.
+///
+/// Lenient, like [`load_scene`]. A file that can't be read or parsed loads
+/// nothing.
+pub fn load_scene_from_file(
+    world: &mut World,
+    registry: &SceneRegistry,
+    path: impl AsRef<Path>,
+) -> Vec<Entity> {
+    try_load_scene_from_file(world, registry, path, SceneLoadMode::Lenient).unwrap_or_default()
+}
+
+/// Validate and load entities from a JSON file. See [`try_load_scene`].
+///
+/// Problems are also added to the asset reload log (`diagnostics` feature).
+pub fn try_load_scene_from_file(
+    world: &mut World,
+    registry: &SceneRegistry,
+    path: impl AsRef<Path>,
+    mode: SceneLoadMode,
+) -> Result<Vec<Entity>, Vec<SceneError>> {
+    let path = path.as_ref();
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| SceneError::Io(e.to_string()))
+        .and_then(|json| parse_scene(&json));
+    match data {
+        Ok(data) => load_checked(world, registry, &data, mode, Some(path)),
+        Err(e) => {
+            let errors = vec![e];
+            report(world, &errors, Some(path));
+            Err(errors)
+        }
+    }
+}
+
+// ── Validation ───────────────────────────────────────────────────────────
+
+/// How [`try_load_scene`] treats a scene with problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SceneLoadMode {
+    /// Load nothing if anything is wrong.
+    Strict,
+    /// Skip bad entities and dangling children; load the rest.
+    #[default]
+    Lenient,
+}
+
+/// A problem found while loading a scene.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneError {
+    /// The file couldn't be read.
+    Io(String),
+    /// Malformed JSON, or JSON not shaped like a scene (e.g. `"id": "three"`).
+    Syntax { line: usize, column: usize, message: String },
+    /// The file was written by a newer release, in a format this one
+    /// doesn't know.
+    UnsupportedVersion { version: u32 },
+    /// A second entity with an id already used in the scene.
+    DuplicateId { entity: u32 },
+    /// A component name that isn't registered in the [`SceneRegistry`].
+    UnknownComponent { entity: u32, component: String },
+    /// A component whose value doesn't match its type (wrong field type,
+    /// missing field, ...).
+    InvalidComponent { entity: u32, component: String, message: String },
+    /// `children` lists an id no entity in the scene has.
+    MissingChild { entity: u32, child: u32 },
+    /// A resource name that isn't registered in the [`SceneRegistry`].
+    UnknownResource { resource: String },
+    /// A resource whose value doesn't match its type.
+    InvalidResource { resource: String, message: String },
+}
+
+impl SceneError {
+    /// Id of the scene entity the problem is in, if it's in one.
+    pub fn entity(&self) -> Option<u32> {
+        match self {
+            SceneError::Io(_)
+            | SceneError::Syntax { .. }
+            | SceneError::UnsupportedVersion { .. }
+            | SceneError::UnknownResource { .. }
+            | SceneError::InvalidResource { .. } => None,
+            SceneError::DuplicateId { entity }
+            | SceneError::UnknownComponent { entity, .. }
+            | SceneError::InvalidComponent { entity, .. }
+            | SceneError::MissingChild { entity, .. } => Some(*entity),
+        }
+    }
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(message) => write!(f, "can't read scene: {message}"),
+            SceneError::Syntax { line, column, message } => {
+                write!(f, "line {line}, column {column}: {message}")
+            }
+            SceneError::UnsupportedVersion { version } => write!(
+                f,
+                "scene format version {version} is newer than this build supports ({SCENE_FORMAT_VERSION})"
+            ),
+            SceneError::DuplicateId { entity } => write!(f, "entity {entity}: duplicate id"),
+            SceneError::UnknownComponent { entity, component } => {
+                write!(f, "entity {entity}, component '{component}': not registered")
+            }
+            SceneError::InvalidComponent { entity, component, message } => {
+                write!(f, "entity {entity}, component '{component}': {message}")
+            }
+            SceneError::MissingChild { entity, child } => {
+                write!(f, "entity {entity}, children: no entity with id {child}")
+            }
+            SceneError::UnknownResource { resource } => {
+                write!(f, "resource '{resource}': not registered")
+            }
+            SceneError::InvalidResource { resource, message } => {
+                write!(f, "resource '{resource}': {message}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// Parse scene JSON, reporting malformed input with its line and column.
+///
+/// Files written in an older format version are upgraded to the current one
+/// (see [`SCENE_FORMAT_VERSION`]).
+pub fn parse_scene(json: &str) -> Result<SceneData, SceneError> {
+    let mut tree: serde_json::Value = serde_json::from_str(json).map_err(syntax_error)?;
+    let version = match tree.get("version") {
+        None => first_version(),
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| SceneError::Syntax {
+                line: 0,
+                column: 0,
+                message: format!("invalid version {v}, expected an integer"),
+            })?,
+    };
+    if version > SCENE_FORMAT_VERSION {
+        return Err(SceneError::UnsupportedVersion { version });
+    }
+    if version == SCENE_FORMAT_VERSION {
+        return serde_json::from_str(json).map_err(syntax_error);
+    }
+
+    for migrate in &MIGRATIONS[version.saturating_sub(1) as usize..] {
+        migrate(&mut tree);
+    }
+    if let Some(scene) = tree.as_object_mut() {
+        scene.insert("version".into(), SCENE_FORMAT_VERSION.into());
+    }
+    serde_json::from_value(tree).map_err(|e| {
+        // The upgraded tree has no positions. The original text almost
+        // always has the same problem, at a line and column.
+        serde_json::from_str::<SceneData>(json).err().map_or_else(
+            || SceneError::Syntax { line: 0, column: 0, message: e.to_string() },
+            syntax_error,
+        )
+    })
+}
+
+/// Upgrades from each format version to the next: entry `n` turns a
+/// version `n + 1` scene into version `n + 2`.
+const MIGRATIONS: [fn(&mut serde_json::Value); SCENE_FORMAT_VERSION as usize - 1] = [
+    // 1 → 2: names, tags and resources are new and optional, so a version 1
+    // scene is already a valid version 2 one.
+    |_| {},
+    // 2 → 3: uids are new and optional.
+    |_| {},
+];
+
+fn syntax_error(e: serde_json::Error) -> SceneError {
+    SceneError::Syntax {
+        line: e.line(),
+        column: e.column(),
+        message: e.to_string(),
+    }
+}
+
+/// Check a scene against the registry without loading it.
+pub fn validate_scene(registry: &SceneRegistry, data: &SceneData) -> Vec<SceneError> {
+    decode_scene(registry, data).errors
+}
+
+/// Components of one scene entity, deserialized and ready to insert, with
+/// the [`EntityRef`]s each holds.
+type DecodedEntity = Vec<(TypeId, String, Box<dyn Any + Send + Sync>, Vec<EntityRef>)>;
+
+/// A scene's contents, deserialized and ready to insert.
+struct DecodedScene<'r> {
+    /// Per scene entity; `None` if any of its components (or its id) is bad.
+    entities: Vec<Option<DecodedEntity>>,
+    resources: Vec<(&'r ResourceFns, Box<dyn Any + Send + Sync>)>,
+    errors: Vec<SceneError>,
+}
+
+/// Deserialize every resource and entity component. Dangling children are
+/// reported but don't make their parent bad.
+fn decode_scene<'r>(registry: &'r SceneRegistry, data: &SceneData) -> DecodedScene<'r> {
+    let ids: HashSet<u32> = data.entities.iter().map(|e| e.id).collect();
+    let mut seen = HashSet::new();
+    let mut errors = Vec::new();
+    let mut decoded = Vec::with_capacity(data.entities.len());
+
+    let mut resources = Vec::with_capacity(data.resources.len());
+    for (name, json) in &data.resources {
+        let Some(fns) = registry.resources.get(name) else {
+            errors.push(SceneError::UnknownResource { resource: name.clone() });
+            continue;
+        };
+        match (fns.deserialize)(json.clone()) {
+            Ok(boxed) => resources.push((fns, boxed)),
+            Err(message) => errors.push(SceneError::InvalidResource {
+                resource: name.clone(),
+                message,
+            }),
+        }
+    }
+
+    for scene_entity in &data.entities {
+        let entity = scene_entity.id;
+        let before = errors.len();
+        if !seen.insert(entity) {
+            errors.push(SceneError::DuplicateId { entity });
+        }
+
+        // Sorted so errors come out in a stable order.
+        let mut names: Vec<&String> = scene_entity.components.keys().collect();
+        names.sort();
+        let mut components = Vec::with_capacity(names.len());
+        for name in names {
+            let fns = registry
+                .by_name
+                .get(name)
+                .and_then(|tid| Some((*tid, registry.by_type_id.get(tid)?)));
+            let Some((type_id, fns)) = fns else {
+                errors.push(SceneError::UnknownComponent {
+                    entity,
+                    component: name.clone(),
+                });
+                continue;
+            };
+            let json = scene_entity.components[name].clone();
+            let (result, refs) = collect_refs(|| (fns.deserialize)(json));
+            match result {
+                Ok(boxed) => components.push((type_id, name.clone(), boxed, refs)),
+                Err(message) => errors.push(SceneError::InvalidComponent {
+                    entity,
+                    component: name.clone(),
+                    message,
+                }),
+            }
+        }
+        let ok = errors.len() == before;
+
+        for &child in &scene_entity.children {
+            if !ids.contains(&child) {
+                errors.push(SceneError::MissingChild { entity, child });
+            }
+        }
+
+        decoded.push(ok.then_some(components));
+    }
+
+    DecodedScene {
+        entities: decoded,
+        resources,
+        errors,
+    }
+}
+
+/// Validate, report, and (unless strict and broken) spawn a scene.
+fn load_checked(
+    world: &mut World,
+    registry: &SceneRegistry,
+    data: &SceneData,
+    mode: SceneLoadMode,
+    path: Option<&Path>,
+) -> Result<Vec<Entity>, Vec<SceneError>> {
+    spawn_scene(world, registry, data, mode, path).map(|id_map| id_map.into_values().collect())
+}
+
+/// [`load_checked`], returning which entity each scene id became.
+pub(crate) fn spawn_scene(
+    world: &mut World,
+    registry: &SceneRegistry,
+    data: &SceneData,
+    mode: SceneLoadMode,
+    path: Option<&Path>,
+) -> Result<HashMap<u32, Entity>, Vec<SceneError>> {
+    let DecodedScene { entities: decoded, resources, errors } = decode_scene(registry, data);
+    report(world, &errors, path);
+    if mode == SceneLoadMode::Strict && !errors.is_empty() {
+        return Err(errors);
+    }
+
+    for (fns, boxed) in resources {
+        (fns.insert)(world, boxed);
+    }
+
     // Map from scene entity ID → spawned Entity.
     let mut id_map: HashMap<u32, Entity> = HashMap::new();
+    let mut refs = Vec::new();
+    let mut uids = HashMap::new();
+    if data.entities.iter().any(|e| e.uid.is_some()) {
+        world.query::<(&Uid,)>(|entity, (uid,)| {
+            uids.insert(*uid, entity);
+        });
+    }
 
-    // First pass: spawn all entities with their components.
-    for scene_entity in &data.entities {
+    // First pass: spawn all valid entities with their components.
+    for (scene_entity, components) in data.entities.iter().zip(decoded) {
+        let Some(components) = components else {
+            continue;
+        };
         let entity = world.spawn_empty();
         id_map.insert(scene_entity.id, entity);
-
-        for (name, json) in &scene_entity.components {
-            if let Some(&type_id) = registry.by_name.get(name) {
-                if let Some(fns) = registry.by_type_id.get(&type_id) {
-                    if let Some(boxed) = (fns.deserialize)(json.clone()) {
-                        insert_any(world, entity, type_id, name, boxed);
-                    }
+        for (type_id, name, boxed, component_refs) in components {
+            refs.extend(component_refs.into_iter().map(|r| (entity, name.clone(), r)));
+            insert_any(world, entity, type_id, &name, boxed);
+        }
+        if let Some(name) = &scene_entity.name {
+            match world.try_named(name) {
+                Some(existing) => log::warn!(
+                    "Scene entity {}: name \"{name}\" is already used by {existing:?}; loading it unnamed",
+                    scene_entity.id
+                ),
+                None => world.name_entity(entity, name),
+            }
+        }
+        for tag in &scene_entity.tags {
+            world.tag(entity, tag);
+        }
+        if let Some(uid) = scene_entity.uid {
+            match uids.get(&uid) {
+                Some(existing) => log::warn!(
+                    "Scene entity {}: uid {uid} is already used by {existing:?}; loading it without one",
+                    scene_entity.id
+                ),
+                None => {
+                    uids.insert(uid, entity);
+                    world.insert(entity, uid);
                 }
             }
         }
     }
 
-    // Second pass: reconstruct hierarchy from children arrays.
+    // Second pass: reconstruct hierarchy from children arrays. Children that
+    // were skipped or don't exist are left out.
     for scene_entity in &data.entities {
         if scene_entity.children.is_empty() {
             continue;
@@ -332,25 +1022,27 @@ pub fn load_scene(
         }
     }
 
-    id_map.values().copied().collect()
+    // This scene's references, and older ones it may have brought the
+    // targets for.
+    resolve_refs(world, refs);
+    Ok(id_map)
 }
 
-/// Save all entities to a JSON file.
-pub fn save_scene_to_file(world: &World, registry: &SceneRegistry, path: impl AsRef<Path>) {
-    let data = save_scene(world, registry);
-    let json = serde_json::to_string_pretty(&data).expect("Failed to serialize scene");
-    std::fs::write(path, json).expect("Failed to write scene file");
-}
-
-/// Load entities from a JSON file.
-pub fn load_scene_from_file(
-    world: &mut World,
-    registry: &SceneRegistry,
-    path: impl AsRef<Path>,
-) -> Vec<Entity> {
-    let json = std::fs::read_to_string(path).expect("Failed to read scene file");
-    let data: SceneData = serde_json::from_str(&json).expect("Failed to deserialize scene");
-    load_scene(world, registry, &data)
+/// Log scene problems, and add them to the reload log when they came from a
+/// file.
+fn report(world: &mut World, errors: &[SceneError], path: Option<&Path>) {
+    let source = path.map_or_else(|| "scene".to_string(), |p| p.display().to_string());
+    for error in errors {
+        log::warn!("{source}: {error}");
+    }
+    #[cfg(feature = "diagnostics")]
+    if let Some(path) = path {
+        for error in errors {
+            crate::asset::push_reload_event(world, path, "Scene", false, Some(error.to_string()));
+        }
+    }
+    #[cfg(not(feature = "diagnostics"))]
+    let _ = world;
 }
 
 // ── Phase 3: Scene Switching ─────────────────────────────────────────────
@@ -534,7 +1226,7 @@ mod tests {
                         m.insert("Health".into(), serde_json::json!(42));
                         m
                     },
-                    children: vec![],
+                    ..Default::default()
                 },
                 SceneEntity {
                     id: 1,
@@ -543,9 +1235,10 @@ mod tests {
                         m.insert("Health".into(), serde_json::json!(99));
                         m
                     },
-                    children: vec![],
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         let tagged = load_scene_tagged(&mut world, &registry, &data, "menu");
@@ -574,8 +1267,9 @@ mod tests {
                     m.insert("Health".into(), serde_json::json!(10));
                     m
                 },
-                children: vec![],
+                ..Default::default()
             }],
+            ..Default::default()
         };
         let scene_b = SceneData {
             entities: vec![
@@ -586,7 +1280,7 @@ mod tests {
                         m.insert("Health".into(), serde_json::json!(50));
                         m
                     },
-                    children: vec![],
+                    ..Default::default()
                 },
                 SceneEntity {
                     id: 1,
@@ -595,9 +1289,10 @@ mod tests {
                         m.insert("Health".into(), serde_json::json!(60));
                         m
                     },
-                    children: vec![],
+                    ..Default::default()
                 },
             ],
+            ..Default::default()
         };
 
         load_scene_tagged(&mut world, &registry, &scene_a, "a");
@@ -624,4 +1319,185 @@ mod tests {
         assert!(registry.default_value("Name").is_none());
         assert!(registry.default_value("Nonexistent").is_none());
     }
+
+    #[test]
+    fn syntax_errors_have_line_and_column() {
+        let json = "{\n  \"entities\": [\n    { \"id\": \"three\", \"components\": {} }\n  ]\n}";
+        match parse_scene(json) {
+            Err(SceneError::Syntax { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected a syntax error, got {other:?}"),
+        }
+    }
+
+    fn broken_scene() -> SceneData {
+        serde_json::from_value(serde_json::json!({
+            "entities": [
+                { "id": 0, "components": { "Health": 10 }, "children": [1, 7] },
+                { "id": 1, "components": { "Health": "full" } },
+                { "id": 2, "components": { "Mana": 5, "Health": 3 } },
+                { "id": 0, "components": {} },
+                { "id": 3, "components": { "Name": "ok" } },
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn validation_reports_each_problem() {
+        let errors = validate_scene(&test_registry(), &broken_scene());
+        assert_eq!(
+            errors,
+            [
+                SceneError::MissingChild { entity: 0, child: 7 },
+                SceneError::InvalidComponent {
+                    entity: 1,
+                    component: "Health".into(),
+                    message: "invalid type: string \"full\", expected u32".into(),
+                },
+                SceneError::UnknownComponent { entity: 2, component: "Mana".into() },
+                SceneError::DuplicateId { entity: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn strict_loads_nothing_and_lenient_skips_bad_entities() {
+        let registry = test_registry();
+        let mut world = World::new();
+
+        let strict = try_load_scene(&mut world, &registry, &broken_scene(), SceneLoadMode::Strict);
+        assert_eq!(strict.unwrap_err().len(), 4);
+        assert_eq!(world.entity_count(), 0);
+
+        // Entities 0 and 3 survive; 0 loses its bad child and its missing one.
+        let loaded = load_scene(&mut world, &registry, &broken_scene());
+        assert_eq!(loaded.len(), 2);
+        let mut health = Vec::new();
+        world.query::<(&Health,)>(|_, (h,)| health.push(h.0));
+        assert_eq!(health, [10]);
+        let mut children = 0;
+        world.query::<(&Children,)>(|_, _| children += 1);
+        assert_eq!(children, 0);
+    }
+
+    #[test]
+    fn unreadable_file_loads_nothing() {
+        let registry = test_registry();
+        let mut world = World::new();
+        let path = "/nonexistent/necs_scene.json";
+        assert!(load_scene_from_file(&mut world, &registry, path).is_empty());
+        let result = try_load_scene_from_file(&mut world, &registry, path, SceneLoadMode::Lenient);
+        assert!(matches!(result.unwrap_err()[..], [SceneError::Io(_)]));
+    }
+
+    #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+    struct Gravity(f32);
+
+    #[test]
+    fn names_tags_and_resources_round_trip() {
+        let mut registry = test_registry();
+        registry.register_resource::<Gravity>();
+        registry.register_resource::<String>();
+        let mut world = World::new();
+
+        let player = world.spawn((Health(3),));
+        world.name_entity(player, "player");
+        world.tag(player, "hero");
+        world.tag(player, "saved");
+        world.insert_resource(Gravity(-9.8));
+
+        let json = serde_json::to_string(&save_scene(&world, &registry)).unwrap();
+        let data = parse_scene(&json).unwrap();
+        assert_eq!(data.version, SCENE_FORMAT_VERSION);
+        // Unset resources aren't saved.
+        assert_eq!(data.resources.keys().collect::<Vec<_>>(), ["Gravity"]);
+
+        let mut loaded = World::new();
+        loaded.insert_resource(Gravity(0.0));
+        load_scene(&mut loaded, &registry, &data);
+        let player = loaded.named("player");
+        assert_eq!(loaded.get::<Health>(player), Some(&Health(3)));
+        assert_eq!(loaded.entity_tags(player), ["hero", "saved"]);
+        assert_eq!(loaded.resource::<Gravity>(), &Gravity(-9.8));
+
+        // A second copy can't take the name, but keeps everything else.
+        load_scene(&mut loaded, &registry, &data);
+        assert_eq!(loaded.entity_count(), 2);
+        assert_eq!(loaded.tagged_count("hero"), 2);
+        assert_eq!(loaded.named("player"), player);
+    }
+
+    #[test]
+    fn older_versions_load_and_newer_are_rejected() {
+        let v1 = r#"{ "entities": [ { "id": 0, "components": { "Health": 7 } } ] }"#;
+        let data = parse_scene(v1).unwrap();
+        assert_eq!(data.version, SCENE_FORMAT_VERSION);
+        let mut world = World::new();
+        assert_eq!(load_scene(&mut world, &test_registry(), &data).len(), 1);
+
+        let future = r#"{ "version": 99, "entities": [] }"#;
+        assert_eq!(
+            parse_scene(future).unwrap_err(),
+            SceneError::UnsupportedVersion { version: 99 }
+        );
+    }
+
+    #[test]
+    fn uids_survive_reloading() {
+        #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq)]
+        struct Door {
+            target: Uid,
+        }
+        let mut registry = test_registry();
+        registry.register::<Door>();
+        let mut world = World::new();
+        let castle = world.spawn((Health(1),));
+        world.name_entity(castle, "castle");
+        let gate = world.spawn_child(castle, (Health(2),));
+        world.name_entity(gate, "east_gate");
+        let spawn_point = Uid::new();
+        world.spawn((spawn_point, Door { target: Uid::from_path("castle/east_gate") }));
+
+        let json = serde_json::to_string(&save_scene(&world, &registry)).unwrap();
+        assert!(json.contains(&format!("\"{spawn_point}\"")));
+        let data = parse_scene(&json).unwrap();
+
+        let mut loaded = World::new();
+        load_scene_tagged(&mut loaded, &registry, &data, "castle");
+        unload_scene(&mut loaded, "castle");
+        load_scene_tagged(&mut loaded, &registry, &data, "castle");
+        let door = loaded.by_uid(spawn_point).unwrap();
+        let target = loaded.by_uid(loaded.get::<Door>(door).unwrap().target);
+        assert_eq!(target, Some(loaded.named("east_gate")));
+
+        // A second copy loads without the taken uids.
+        load_scene(&mut loaded, &registry, &data);
+        assert_eq!(loaded.entity_count(), 6);
+        assert_eq!(loaded.by_uid(spawn_point), Some(door));
+
+        assert_eq!(spawn_point.to_string().parse::<Uid>(), Ok(spawn_point));
+        assert!("not-a-uid".parse::<Uid>().is_err());
+    }
+
+    #[test]
+    fn bad_resources_are_reported() {
+        let mut registry = test_registry();
+        registry.register_resource::<Gravity>();
+        let data: SceneData = serde_json::from_value(serde_json::json!({
+            "version": 3,
+            "resources": { "Gravity": "down", "Wind": 2.0 },
+            "entities": [],
+        }))
+        .unwrap();
+        assert_eq!(
+            validate_scene(&registry, &data),
+            [
+                SceneError::InvalidResource {
+                    resource: "Gravity".into(),
+                    message: "invalid type: string \"down\", expected f32".into(),
+                },
+                SceneError::UnknownResource { resource: "Wind".into() },
+            ]
+        );
+    }
 }
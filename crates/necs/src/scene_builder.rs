@@ -6,6 +6,26 @@
 //!
 //! Use the [`Scenes`] plugin to register everything automatically.
 //!
+//! # Transient Entities
+//!
+//! A scene switch despawns the exited scene's template entities, but not
+//! what its systems spawned at runtime — bullets, enemies, popups. Mark
+//! those [`Transient`] and they go too; mark anything that must survive
+//! [`Persistent`]:
+//!
+//! ```text
+//!   goto("menu") while in "game":
+//!     1. game's on_exit runs         (transient entities still exist)
+//!     2. cleanup, per SceneCleanup:
+//!          SceneMarker("game")  ──► despawned
+//!          Transient / tagged   ──► despawned
+//!          Persistent           ──► kept, detached if its parent goes
+//!     3. menu's templates spawn, on_enter runs
+//! ```
+//!
+//! [`SceneCleanup`] configures step 2, e.g. to treat every `"bullet"`
+//! tagged entity as transient without adding the marker.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -30,6 +50,8 @@
 use std::cell::RefCell;
 use std::sync::Mutex;
 
+use std::collections::HashSet;
+
 use crate::context::Context;
 use crate::ecs::hierarchy::{Children, GlobalTransform, Parent};
 use crate::ecs::Entity;
@@ -301,6 +323,101 @@ impl std::fmt::Debug for SceneManager {
     }
 }
 
+// ── Transient Entities ─────────────────────────────────────────────────
+
+/// Marker: despawn this entity and its descendants on the next scene
+/// switch. See the [module docs](self#transient-entities).
+///
+/// ```ignore
+/// ctx.spawn((Transform::from_xy(x, y), Sprite::new(), Bullet, Transient));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Transient;
+
+/// Marker: keep this entity across scene switches, even if it belongs to
+/// the exited scene or is [`Transient`]. If its parent is despawned it is
+/// detached and kept as a root, with its own subtree.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Persistent;
+
+/// Resource: what a scene switch despawns. Optional; without it every
+/// field is at its default.
+///
+/// ```ignore
+/// Game::new("Shooter")
+///     .resource(SceneCleanup::default().tag("bullet").tag("enemy"))
+/// ```
+#[derive(Debug, Clone)]
+pub struct SceneCleanup {
+    /// Despawn the entities the exited scene's templates spawned. Default
+    /// `true`.
+    pub scene_entities: bool,
+    /// Despawn [`Transient`] entities and those with a `transient_tags`
+    /// tag. Default `true`.
+    pub transient: bool,
+    /// Tags whose entities count as [`Transient`].
+    pub transient_tags: Vec<String>,
+}
+
+impl Default for SceneCleanup {
+    fn default() -> Self {
+        Self {
+            scene_entities: true,
+            transient: true,
+            transient_tags: Vec::new(),
+        }
+    }
+}
+
+impl SceneCleanup {
+    /// Treat entities with `tag` as [`Transient`] (builder pattern).
+    pub fn tag(mut self, tag: &str) -> Self {
+        self.transient_tags.push(tag.to_string());
+        self
+    }
+}
+
+/// Despawn what leaving `scene` cleans up, keeping [`Persistent`] entities.
+/// Returns the number of subtrees despawned.
+fn cleanup_scene(world: &mut World, cleanup: &SceneCleanup, scene: &str) -> usize {
+    let mut doomed = HashSet::new();
+    if cleanup.scene_entities {
+        world.query::<(&SceneMarker,)>(|entity, (marker,)| {
+            if marker.0 == scene {
+                doomed.insert(entity);
+            }
+        });
+    }
+    if cleanup.transient {
+        world.query::<(&Transient,)>(|entity, _| {
+            doomed.insert(entity);
+        });
+        for tag in &cleanup.transient_tags {
+            doomed.extend(world.iter_tagged(tag));
+        }
+    }
+    let mut persistent = Vec::new();
+    world.query::<(&Persistent,)>(|entity, _| persistent.push(entity));
+    for &entity in &persistent {
+        doomed.remove(&entity);
+    }
+
+    // Rescue persistent entities whose nearest doomed ancestor isn't
+    // shielded by a persistent one in between.
+    for entity in persistent {
+        let under_doomed = world
+            .ancestors(entity)
+            .take_while(|&ancestor| world.get::<Persistent>(ancestor).is_none())
+            .any(|ancestor| doomed.contains(&ancestor));
+        if under_doomed {
+            world.remove_parent(entity);
+        }
+    }
+
+    // Despawning a parent first takes doomed descendants with it.
+    doomed.into_iter().filter(|&entity| world.despawn_recursive(entity)).count()
+}
+
 // ── Transition System ──────────────────────────────────────────────────
 
 /// Process pending scene transitions.
@@ -333,16 +450,10 @@ fn scene_transition_system(ctx: &mut Context) {
             }
         }
 
-        // Unload current scene entities.
-        let mut to_despawn = Vec::new();
-        ctx.world.query::<(&SceneMarker,)>(|entity, (marker,)| {
-            if marker.0 == *active_name {
-                to_despawn.push(entity);
-            }
-        });
-        for entity in to_despawn {
-            ctx.world.despawn_recursive(entity);
-        }
+        // Unload the scene's entities and transient ones.
+        let cleanup = ctx.world.get_resource::<SceneCleanup>().cloned().unwrap_or_default();
+        let despawned = cleanup_scene(&mut ctx.world, &cleanup, active_name);
+        log::debug!("SceneManager: left '{active_name}', despawned {despawned} entities");
     }
 
     // Find the target scene and spawn its templates.
@@ -442,3 +553,41 @@ impl crate::game::Plugin for Scenes {
         game.add_update_system(scene_transition_system);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Transform;
+
+    #[test]
+    fn cleanup_despawns_transient_and_keeps_persistent() {
+        let mut world = World::new();
+        let level = world.spawn((SceneMarker("game".into()),));
+        let kept = world.spawn_child(level, (Transform::default(), Persistent));
+        let kept_child = world.spawn_child(kept, (Transform::default(),));
+        let bullet = world.spawn((Transient,));
+        let enemy = world.spawn((Transform::default(),));
+        world.tag(enemy, "enemy");
+        let other_scene = world.spawn((SceneMarker("menu".into()),));
+        let saved = world.spawn((Transient, Persistent));
+
+        let cleanup = SceneCleanup::default().tag("enemy");
+        assert_eq!(cleanup_scene(&mut world, &cleanup, "game"), 3);
+        for entity in [level, bullet, enemy] {
+            assert!(!world.is_alive(entity));
+        }
+        for entity in [kept, kept_child, other_scene, saved] {
+            assert!(world.is_alive(entity));
+        }
+        assert!(world.get::<Parent>(kept).is_none());
+        assert_eq!(world.ancestors(kept_child).collect::<Vec<_>>(), [kept]);
+
+        let only_scene = SceneCleanup {
+            transient: false,
+            ..Default::default()
+        };
+        let bullet = world.spawn((Transient,));
+        assert_eq!(cleanup_scene(&mut world, &only_scene, "menu"), 1);
+        assert!(world.is_alive(bullet) && !world.is_alive(other_scene));
+    }
+}
@@ -0,0 +1,104 @@
+//! # Subsystems — Switching Engine Work On and Off at Run Time
+//!
+//! Compile-time features decide which subsystems exist; the [`Subsystems`]
+//! resource decides which of them run this frame. Turning one off skips its
+//! work entirely rather than hiding the result:
+//!
+//! | Switch | Off means |
+//! |--------|-----------|
+//! | `render_3d` | No 3D extract or pass; the screen is cleared to [`ClearColor`](crate::render::ClearColor) |
+//! | `render_2d` | No 2D extract or pass (sprites, tilemaps, text), same clear |
+//! | `ui` | No UI pass; layout and buttons still run |
+//! | `physics` | 2D and 3D physics worlds aren't stepped; bodies freeze in place |
+//! | `particles` | Emitters neither spawn nor move particles |
+//! | `audio` | The audio engine is muted (`audio` feature) |
+//!
+//! Two uses motivate it:
+//!
+//! ```text
+//!   profiling                            menu-only states
+//!   ─────────                            ────────────────
+//!   frame 9.1 ms                         main menu over a frozen level:
+//!   └ physics off → 6.0 ms               physics: false, particles: false
+//!     so physics costs ~3.1 ms           the level stays drawn, nothing moves
+//! ```
+//!
+//! [`Game`](crate::game::Game) inserts the resource with everything on, so
+//! any system can flip a switch:
+//!
+//! ```ignore
+//! fn toggle_physics(ctx: &mut Context) {
+//!     if ctx.input.keys.just_pressed(KeyCode::F6) {
+//!         let subsystems = ctx.world.resource_mut::<Subsystems>();
+//!         subsystems.physics = !subsystems.physics;
+//!     }
+//! }
+//! ```
+//!
+//! Without the resource everything runs, as before it existed.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: Per-system switches in different places —
+//!   `Physics.simulationMode`, `AudioListener.pause`, a camera's culling
+//!   mask or `enabled` flag.
+//! - **Bevy**: Run conditions on system sets, or removing plugins' systems;
+//!   any system can be gated, but there's no one switchboard.
+//! - **Godot**: `PhysicsServer3D.set_active`, `AudioServer` bus mute, and
+//!   a viewport's `disable_3d`.
+//! - **Our approach**: One plain resource of booleans, checked where each
+//!   subsystem's per-frame work starts.
+
+use crate::ecs::World;
+
+/// Resource: which engine subsystems run. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Subsystems {
+    /// Extract and draw the 3D scene.
+    pub render_3d: bool,
+    /// Extract and draw the 2D scene.
+    pub render_2d: bool,
+    /// Draw the UI.
+    pub ui: bool,
+    /// Step the 2D and 3D physics worlds.
+    pub physics: bool,
+    /// Spawn and move particles.
+    pub particles: bool,
+    /// Play sound; off mutes the audio engine.
+    pub audio: bool,
+}
+
+impl Default for Subsystems {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl Subsystems {
+    /// Everything on.
+    pub fn all() -> Self {
+        Self {
+            render_3d: true,
+            render_2d: true,
+            ui: true,
+            physics: true,
+            particles: true,
+            audio: true,
+        }
+    }
+
+    /// The world's switches, or everything on without the resource.
+    #[cfg_attr(
+        not(any(
+            feature = "render2d",
+            feature = "render3d",
+            feature = "audio",
+            feature = "physics2d",
+            feature = "physics3d"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn of(world: &World) -> Self {
+        world.get_resource::<Self>().copied().unwrap_or_default()
+    }
+}
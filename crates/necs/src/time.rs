@@ -2,9 +2,53 @@
 //!
 //! The [`Time`] resource is updated by the framework at the start of each
 //! frame. Systems can read it to get frame delta time and total elapsed time.
+//!
+//! ## Fixed Timestep
+//!
+//! Frame delta varies with the display and the load, which makes simulation
+//! results depend on frame rate: a jump that clears a gap at 60 FPS may fall
+//! short at 30. Systems registered with [`Game::fixed_update`](crate::game::Game::fixed_update)
+//! instead run on a fixed tick (60 Hz by default). Each frame the frame delta
+//! is added to an accumulator, and one tick runs for every whole
+//! [`fixed_delta`](Time::fixed_delta) it holds — zero, one or several per
+//! frame:
+//!
+//! ```text
+//!   frames:       |-------|-------|-------|-------|      (e.g. 144 Hz)
+//!   fixed ticks:  |-----------|-----------|--------      (60 Hz)
+//!                            ▲       ▲
+//!                            │       fixed_alpha = accumulator / fixed_delta
+//!                            a tick ran during this frame
+//! ```
+//!
+//! What's left in the accumulator is exposed as
+//! [`fixed_alpha`](Time::fixed_alpha) (0..1), how far the current frame is
+//! into the next tick; rendering code uses it to blend between the last two
+//! simulated states (see [`interpolation`](crate::interpolation)).
+//!
+//! After a long stall the accumulator could hold dozens of ticks, and running
+//! them all makes the next frame slower still — the "spiral of death". At
+//! most [`max_fixed_steps`](Time::set_max_fixed_steps) ticks run per frame;
+//! the rest of the backlog is dropped and the simulation briefly runs slower
+//! than real time.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `FixedUpdate` at `Time.fixedDeltaTime`, capped by
+//!   `Time.maximumDeltaTime`.
+//! - **Bevy**: The `FixedUpdate` schedule driven by `Time<Fixed>`, with
+//!   `overstep_fraction` as the alpha.
+//! - **Our approach**: Unity's model. Fixed systems read
+//!   [`fixed_delta_secs`](Time::fixed_delta_secs) explicitly; `delta_secs`
+//!   stays the frame delta everywhere.
 
 use std::time::{Duration, Instant};
 
+/// Fixed tick rate used until [`Time::set_fixed_hz`] is called.
+const DEFAULT_FIXED_HZ: f64 = 60.0;
+/// Catch-up limit used until [`Time::set_max_fixed_steps`] is called.
+const DEFAULT_MAX_FIXED_STEPS: u32 = 5;
+
 /// Frame timing resource. Inserted by the framework and updated each frame.
 #[derive(Clone, Copy)]
 pub struct Time {
@@ -18,6 +62,14 @@ pub struct Time {
     elapsed: Duration,
     /// Frame counter.
     frame_count: u64,
+    /// Length of one fixed tick.
+    fixed_delta: Duration,
+    /// Frame time not yet consumed by fixed ticks.
+    fixed_accumulator: Duration,
+    /// Most fixed ticks run in one frame.
+    max_fixed_steps: u32,
+    /// Fixed ticks run so far.
+    fixed_tick_count: u64,
 }
 
 impl Time {
@@ -29,6 +81,10 @@ impl Time {
             delta: Duration::ZERO,
             elapsed: Duration::ZERO,
             frame_count: 0,
+            fixed_delta: Duration::from_secs_f64(1.0 / DEFAULT_FIXED_HZ),
+            fixed_accumulator: Duration::ZERO,
+            max_fixed_steps: DEFAULT_MAX_FIXED_STEPS,
+            fixed_tick_count: 0,
         }
     }
 
@@ -74,4 +130,103 @@ impl Time {
             0.0
         }
     }
+
+    // ── Fixed timestep ──────────────────────────────────────────────────
+
+    /// Length of one fixed tick.
+    pub fn fixed_delta(&self) -> Duration {
+        self.fixed_delta
+    }
+
+    /// Length of one fixed tick in seconds. Use this instead of
+    /// [`delta_secs`](Self::delta_secs) in fixed-update systems.
+    pub fn fixed_delta_secs(&self) -> f32 {
+        self.fixed_delta.as_secs_f32()
+    }
+
+    /// Set the fixed tick rate in ticks per second. Non-positive rates are
+    /// ignored with a warning.
+    pub fn set_fixed_hz(&mut self, hz: f64) {
+        if !(hz > 0.0 && hz.is_finite()) {
+            log::warn!("Ignoring invalid fixed tick rate {hz} Hz");
+            return;
+        }
+        self.fixed_delta = Duration::from_secs_f64(1.0 / hz);
+    }
+
+    /// Most fixed ticks run in a single frame (at least 1). Backlog beyond
+    /// this is dropped.
+    pub fn set_max_fixed_steps(&mut self, steps: u32) {
+        self.max_fixed_steps = steps.max(1);
+    }
+
+    /// How far the current frame is into the next fixed tick, 0..1. Blend
+    /// the last two fixed-step states with it for smooth rendering.
+    pub fn fixed_alpha(&self) -> f32 {
+        (self.fixed_accumulator.as_secs_f64() / self.fixed_delta.as_secs_f64()) as f32
+    }
+
+    /// Number of fixed ticks run so far.
+    pub fn fixed_tick_count(&self) -> u64 {
+        self.fixed_tick_count
+    }
+
+    /// Add this frame's delta to the accumulator and take out the fixed
+    /// ticks due, capped at `max_fixed_steps`. Returns how many to run.
+    pub(crate) fn advance_fixed(&mut self) -> u32 {
+        self.fixed_accumulator += self.delta;
+        let due = self.fixed_accumulator.as_nanos() / self.fixed_delta.as_nanos();
+        let steps = due.min(self.max_fixed_steps as u128) as u32;
+        self.fixed_accumulator -= self.fixed_delta * steps;
+        if due > steps as u128 {
+            log::debug!("Fixed update fell behind; dropping {} ticks", due - steps as u128);
+            self.fixed_accumulator = Duration::from_nanos(
+                (self.fixed_accumulator.as_nanos() % self.fixed_delta.as_nanos()) as u64,
+            );
+        }
+        steps
+    }
+
+    /// Count one fixed tick as run.
+    pub(crate) fn finish_fixed_step(&mut self) {
+        self.fixed_tick_count += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(time: &mut Time, ms: u64) -> u32 {
+        time.delta = Duration::from_millis(ms);
+        time.advance_fixed()
+    }
+
+    #[test]
+    fn accumulator_runs_whole_ticks_and_keeps_remainder() {
+        let mut time = Time::new();
+        time.set_fixed_hz(50.0); // 20 ms ticks
+        assert_eq!(frame(&mut time, 15), 0);
+        assert!((time.fixed_alpha() - 0.75).abs() < 1e-6);
+        assert_eq!(frame(&mut time, 30), 2);
+        assert!((time.fixed_alpha() - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn backlog_beyond_max_steps_is_dropped() {
+        let mut time = Time::new();
+        time.set_fixed_hz(100.0); // 10 ms ticks
+        time.set_max_fixed_steps(3);
+        assert_eq!(frame(&mut time, 1_005), 3);
+        assert!((time.fixed_alpha() - 0.5).abs() < 1e-6);
+        assert_eq!(frame(&mut time, 10), 1);
+    }
+
+    #[test]
+    fn invalid_rate_is_ignored() {
+        let mut time = Time::new();
+        time.set_fixed_hz(0.0);
+        time.set_fixed_hz(f64::NAN);
+        assert_eq!(time.fixed_delta(), Duration::from_secs_f64(1.0 / DEFAULT_FIXED_HZ));
+    }
 }
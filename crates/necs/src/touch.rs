@@ -0,0 +1,408 @@
+//! # Touch — Fingers, Taps, Drags and Pinches
+//!
+//! A touch screen reports every finger separately: each gets an id when it
+//! lands, moves while it stays down, and ends when it lifts (or is
+//! cancelled, e.g. by the OS taking over the gesture). [`Touches`] tracks
+//! all of them, read with
+//! [`InputState::touches`](crate::context::InputState::touches):
+//!
+//! ```text
+//!   finger 3:  Began ─► Moved … Stationary … Moved ─► Ended
+//!              │                                      │
+//!              start_position                         still listed this
+//!              started                                frame, gone the next
+//! ```
+//!
+//! Most games want gestures rather than raw fingers, so [`Touches`] also
+//! recognizes the three common ones:
+//!
+//! ```text
+//!   tap()    one finger down and up within 0.3s, moved < 16px ─► where
+//!   drag()   one finger held and moved ≥ 16px ─► the Touch (delta this frame)
+//!   pinch()  two fingers held ─► Pinch { center, scale, rotation, pan }
+//!            (relative to the previous frame)
+//! ```
+//!
+//! ```ignore
+//! let touches = ctx.input.touches();
+//! if let Some(at) = touches.tap() {
+//!     fire_at(at);
+//! }
+//! if let Some(pinch) = touches.pinch() {
+//!     camera.zoom *= pinch.scale;
+//! } else if let Some(finger) = touches.drag() {
+//!     camera.pan(-finger.delta());
+//! }
+//! ```
+//!
+//! ## Mouse Emulation
+//!
+//! So that a prototype written for the mouse runs on a phone unchanged, the
+//! first finger down also drives the mouse: it moves the
+//! [`CursorPosition`](crate::input::CursorPosition) and holds
+//! [`MouseButton::Left`] until it lifts. The synthesized `CursorMoved`,
+//! `MousePressed` and `MouseReleased` events follow the
+//! [`InputEvent::Touch`] in the frame's events. Fingers that land while the
+//! first is down only show up as touches. Turn emulation off with
+//! [`InputState::set_touch_mouse_emulation`](crate::context::InputState::set_touch_mouse_emulation)
+//! once the game handles touch itself.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Input.touches` with `TouchPhase` per finger and
+//!   `Input.simulateMouseWithTouches` (on by default); gestures are left to
+//!   the game or the Enhanced Touch API.
+//! - **Bevy**: `Touches` resource with just-pressed/released per finger and
+//!   `TouchInput` events; no gestures, no mouse emulation.
+//! - **Godot**: `InputEventScreenTouch`/`ScreenDrag` events, the
+//!   `emulate_mouse_from_touch` project setting, and
+//!   `InputEventMagnifyGesture`/`PanGesture` from trackpads.
+//! - **Our approach**: Unity's per-finger phases and mouse emulation, plus
+//!   tap, drag and pinch recognizers on the same state.
+
+use std::time::{Duration, Instant};
+
+use crate::input::{InputEvent, MouseButton};
+use crate::math::Vec2;
+
+/// How far a finger may wander, in pixels, and still count as a tap; a
+/// finger that goes further is dragging.
+pub const TAP_SLOP: f32 = 16.0;
+
+/// The longest a finger may stay down and still count as a tap.
+pub const TAP_TIME: Duration = Duration::from_millis(300);
+
+/// Where a finger is in its life. See the [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TouchPhase {
+    /// Landed this frame.
+    Began,
+    /// Moved this frame.
+    Moved,
+    /// Down, and hasn't moved this frame.
+    Stationary,
+    /// Lifted this frame.
+    Ended,
+    /// Taken away by the system this frame (e.g. an OS gesture); not a tap.
+    Cancelled,
+}
+
+/// One finger on the screen. Positions are in window coordinates, like
+/// [`CursorPosition`](crate::input::CursorPosition).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Touch {
+    /// Stays the same from [`Began`](TouchPhase::Began) to
+    /// [`Ended`](TouchPhase::Ended); may be reused by a later finger.
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Vec2,
+    /// Position at the end of the previous frame.
+    pub previous_position: Vec2,
+    /// Where the finger landed.
+    pub start_position: Vec2,
+    /// When the finger landed.
+    pub started: Instant,
+    /// When the finger was last reported.
+    last_seen: Instant,
+    /// Whether it has ever been further than [`TAP_SLOP`] from the start.
+    dragged: bool,
+}
+
+impl Touch {
+    /// Movement this frame.
+    pub fn delta(&self) -> Vec2 {
+        self.position - self.previous_position
+    }
+
+    /// How long the finger has been (or was) down.
+    pub fn duration(&self) -> Duration {
+        self.last_seen - self.started
+    }
+
+    /// Whether the finger has moved past [`TAP_SLOP`] since it landed.
+    pub fn is_dragging(&self) -> bool {
+        self.dragged
+    }
+
+    /// Still on the screen: not ended or cancelled.
+    pub fn is_held(&self) -> bool {
+        !matches!(self.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+    }
+}
+
+/// Two fingers moving relative to each other since the previous frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pinch {
+    /// Midpoint between the fingers.
+    pub center: Vec2,
+    /// Movement of the midpoint this frame.
+    pub pan: Vec2,
+    /// Finger spread now over the spread last frame: above 1 when spreading
+    /// apart, below 1 when pinching together.
+    pub scale: f32,
+    /// Counter-clockwise turn of the line between the fingers this frame, in
+    /// radians (window Y points down, so it's clockwise on screen).
+    pub rotation: f32,
+}
+
+/// Every finger on the screen this frame, plus those lifted this frame.
+/// See the [module docs](self).
+#[derive(Debug, Clone)]
+pub struct Touches {
+    touches: Vec<Touch>,
+    /// The finger driving mouse emulation.
+    primary: Option<u64>,
+    pub(crate) emulate_mouse: bool,
+}
+
+impl Default for Touches {
+    fn default() -> Self {
+        Self {
+            touches: Vec::new(),
+            primary: None,
+            emulate_mouse: true,
+        }
+    }
+}
+
+impl Touches {
+    /// Every finger this frame, including those that ended this frame, in
+    /// the order they landed.
+    pub fn iter(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter()
+    }
+
+    /// Fingers still on the screen.
+    pub fn held(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| touch.is_held())
+    }
+
+    /// Fingers that landed this frame.
+    pub fn just_began(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| touch.phase == TouchPhase::Began)
+    }
+
+    /// Fingers lifted (or cancelled) this frame.
+    pub fn just_ended(&self) -> impl Iterator<Item = &Touch> {
+        self.touches.iter().filter(|touch| !touch.is_held())
+    }
+
+    /// The finger with this id, if it's down or ended this frame.
+    pub fn get(&self, id: u64) -> Option<&Touch> {
+        self.touches.iter().find(|touch| touch.id == id)
+    }
+
+    /// Number of fingers on the screen.
+    pub fn len(&self) -> usize {
+        self.held().count()
+    }
+
+    /// No finger on the screen.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Where a finger tapped this frame: lifted within [`TAP_TIME`] of
+    /// landing, never having moved [`TAP_SLOP`] away.
+    pub fn tap(&self) -> Option<Vec2> {
+        self.touches
+            .iter()
+            .find(|touch| {
+                touch.phase == TouchPhase::Ended && !touch.dragged && touch.duration() <= TAP_TIME
+            })
+            .map(|touch| touch.position)
+    }
+
+    /// The one finger on the screen, once it has moved past [`TAP_SLOP`].
+    /// `None` while a second finger is down, so a pinch isn't also a drag.
+    pub fn drag(&self) -> Option<&Touch> {
+        let mut held = self.held();
+        match (held.next(), held.next()) {
+            (Some(touch), None) if touch.dragged => Some(touch),
+            _ => None,
+        }
+    }
+
+    /// The two fingers on the screen, compared with the previous frame.
+    /// `None` unless exactly two are down.
+    pub fn pinch(&self) -> Option<Pinch> {
+        let mut held = self.held();
+        let (Some(a), Some(b), None) = (held.next(), held.next(), held.next()) else {
+            return None;
+        };
+        let (now, before) = (b.position - a.position, b.previous_position - a.previous_position);
+        let center = (a.position + b.position) / 2.0;
+        let previous_center = (a.previous_position + b.previous_position) / 2.0;
+        let spread = before.length();
+        Some(Pinch {
+            center,
+            pan: center - previous_center,
+            scale: if spread > 0.0 { now.length() / spread } else { 1.0 },
+            rotation: before.angle_to(now),
+        })
+    }
+
+    /// Apply one reported touch. Returns the mouse events the touch stands
+    /// in for, if it's the [primary](self#mouse-emulation) finger.
+    pub(crate) fn apply(
+        &mut self,
+        id: u64,
+        phase: TouchPhase,
+        position: Vec2,
+        at: Instant,
+    ) -> [Option<InputEvent>; 2] {
+        match self.touches.iter().position(|touch| touch.id == id) {
+            Some(index) if phase != TouchPhase::Began => {
+                let touch = &mut self.touches[index];
+                // A finger that lands and moves in one frame still began.
+                touch.phase = match (touch.phase, phase) {
+                    (TouchPhase::Began, TouchPhase::Moved) => TouchPhase::Began,
+                    _ => phase,
+                };
+                touch.position = position;
+                touch.last_seen = at;
+                touch.dragged |= position.distance(touch.start_position) >= TAP_SLOP;
+            }
+            // Ended without being seen to begin (e.g. the window gained
+            // focus mid-touch): nothing to track.
+            None if phase != TouchPhase::Began => return [None, None],
+            found => {
+                if self.primary.is_none() && self.is_empty() {
+                    self.primary = Some(id);
+                }
+                let touch = Touch {
+                    id,
+                    phase: TouchPhase::Began,
+                    position,
+                    previous_position: position,
+                    start_position: position,
+                    started: at,
+                    last_seen: at,
+                    dragged: false,
+                };
+                match found {
+                    Some(index) => self.touches[index] = touch,
+                    None => self.touches.push(touch),
+                }
+            }
+        }
+
+        if self.primary != Some(id) {
+            return [None, None];
+        }
+        let button = match phase {
+            TouchPhase::Began => Some(InputEvent::MousePressed(MouseButton::Left)),
+            TouchPhase::Moved | TouchPhase::Stationary => None,
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.primary = None;
+                Some(InputEvent::MouseReleased(MouseButton::Left))
+            }
+        };
+        if !self.emulate_mouse {
+            return [None, None];
+        }
+        [Some(InputEvent::CursorMoved { x: position.x, y: position.y }), button]
+    }
+
+    /// Drop lifted fingers and start a new frame for the rest. Called after
+    /// update systems.
+    pub(crate) fn end_frame(&mut self) {
+        self.touches.retain(Touch::is_held);
+        for touch in &mut self.touches {
+            touch.previous_position = touch.position;
+            touch.phase = TouchPhase::Stationary;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::InputState;
+    use crate::input::{CursorPosition, InputQueue};
+
+    #[test]
+    fn gestures_from_finger_phases() {
+        let mut touches = Touches::default();
+        let t0 = Instant::now();
+        let ms = |n| t0 + Duration::from_millis(n);
+
+        // A quick tap that wobbles a little.
+        touches.apply(1, TouchPhase::Began, Vec2::new(100.0, 100.0), t0);
+        touches.apply(1, TouchPhase::Moved, Vec2::new(105.0, 98.0), ms(50));
+        touches.apply(1, TouchPhase::Ended, Vec2::new(105.0, 98.0), ms(120));
+        assert_eq!(touches.tap(), Some(Vec2::new(105.0, 98.0)));
+        assert_eq!((touches.iter().count(), touches.len()), (1, 0));
+        touches.end_frame();
+        assert_eq!(touches.tap(), None);
+        assert!(touches.iter().next().is_none());
+
+        // Held too long: a long press, not a tap.
+        touches.apply(2, TouchPhase::Began, Vec2::ZERO, t0);
+        touches.apply(2, TouchPhase::Ended, Vec2::ZERO, ms(800));
+        assert_eq!(touches.tap(), None);
+        touches.end_frame();
+
+        // A drag starts once the finger passes the slop.
+        touches.apply(3, TouchPhase::Began, Vec2::new(0.0, 0.0), t0);
+        touches.end_frame();
+        touches.apply(3, TouchPhase::Moved, Vec2::new(10.0, 0.0), ms(20));
+        assert!(touches.drag().is_none());
+        touches.end_frame();
+        touches.apply(3, TouchPhase::Moved, Vec2::new(30.0, 0.0), ms(40));
+        assert_eq!(touches.drag().unwrap().delta(), Vec2::new(20.0, 0.0));
+
+        // A second finger turns it into a pinch.
+        touches.apply(4, TouchPhase::Began, Vec2::new(30.0, 100.0), ms(40));
+        touches.end_frame();
+        assert!(touches.drag().is_none());
+        touches.apply(4, TouchPhase::Moved, Vec2::new(30.0, 200.0), ms(60));
+        let pinch = touches.pinch().unwrap();
+        assert_eq!(pinch.scale, 2.0);
+        assert_eq!(pinch.center, Vec2::new(30.0, 100.0));
+        assert_eq!(pinch.pan, Vec2::new(0.0, 50.0));
+        assert!(pinch.rotation.abs() < 1e-6);
+    }
+
+    #[test]
+    fn first_finger_emulates_the_mouse() {
+        let mut queue = InputQueue::default();
+        let mut input = InputState::new();
+        let mut cursor = CursorPosition::default();
+        let touch = |id, phase, x, y| InputEvent::Touch { id, phase, x, y };
+
+        queue.push(touch(7, TouchPhase::Began, 40.0, 60.0));
+        queue.push(touch(8, TouchPhase::Began, 90.0, 60.0));
+        queue.drain(&mut input, &mut cursor);
+        assert!(input.mouse_just_pressed(MouseButton::Left));
+        assert_eq!((cursor.x, cursor.y), (40.0, 60.0));
+        let events: Vec<InputEvent> = input.events().iter().map(|timed| timed.event).collect();
+        assert_eq!(
+            events,
+            [
+                touch(7, TouchPhase::Began, 40.0, 60.0),
+                InputEvent::CursorMoved { x: 40.0, y: 60.0 },
+                InputEvent::MousePressed(MouseButton::Left),
+                touch(8, TouchPhase::Began, 90.0, 60.0),
+            ]
+        );
+        input.clear_just();
+
+        // The second finger moving leaves the mouse alone.
+        queue.push(touch(8, TouchPhase::Moved, 95.0, 60.0));
+        queue.push(touch(7, TouchPhase::Ended, 42.0, 61.0));
+        queue.drain(&mut input, &mut cursor);
+        assert!(input.mouse_just_released(MouseButton::Left));
+        assert_eq!((cursor.x, cursor.y), (42.0, 61.0));
+        assert_eq!(input.touches().len(), 1);
+        input.clear_just();
+
+        input.set_touch_mouse_emulation(false);
+        queue.push(touch(8, TouchPhase::Ended, 95.0, 60.0));
+        queue.push(touch(9, TouchPhase::Began, 0.0, 0.0));
+        queue.drain(&mut input, &mut cursor);
+        assert!(!input.mouse_pressed(MouseButton::Left));
+        assert_eq!((cursor.x, cursor.y), (42.0, 61.0));
+        assert_eq!(input.touches().len(), 1);
+    }
+}
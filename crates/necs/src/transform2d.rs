@@ -0,0 +1,273 @@
+//! # Transform2d — Position, Angle and Scale for Pure-2D Games
+//!
+//! Everything in the engine is placed by a 3D [`Transform`]: a `Vec3`
+//! position and a `Quat` rotation. A 2D game only ever wants an X/Y
+//! position and one angle, and spelling those through a quaternion
+//! (`Quat::from_rotation_z`, `to_euler(EulerRot::ZYX).0`) gets old fast.
+//! [`Transform2d`] is the 2D view of the same thing:
+//!
+//! ```text
+//!   Transform2d                         Transform (what renders)
+//!   translation  (x, y)        ──►      translation  (x, y, z)  ← z kept
+//!   rotation     θ radians     ──►      rotation     about +Z by θ
+//!   scale        (sx, sy)      ──►      scale        (sx, sy, z)
+//! ```
+//!
+//! Angles are counter-clockwise from +X, so at rotation 0 an entity faces
+//! right and [`forward`](Transform2d::forward) is `(1, 0)`:
+//!
+//! ```ignore
+//! ctx.spawn("ship").insert(Transform2d::from_xy(0.0, 0.0)).insert(sprite);
+//!
+//! fn steer(ctx: &mut Context, ship: Entity, target: Vec2) {
+//!     let dt = ctx.time.delta_secs();
+//!     let tf = ctx.world.get_mut::<Transform2d>(ship).unwrap();
+//!     tf.rotate_towards(target, 3.0 * dt); // at most 3 rad/s
+//!     tf.move_forward(200.0 * dt);
+//! }
+//! ```
+//!
+//! ## Syncing
+//!
+//! The engine keeps the two components in step, both ways: once after the
+//! fixed steps (so physics moves show up in `Transform2d` before update
+//! systems run) and once before transform propagation. Whichever side
+//! changed since the last sync wins — `Transform2d` if both did. So an
+//! entity can be moved through either, and physics, interpolation and the
+//! editor keep writing `Transform` as they always have. An entity with a
+//! `Transform2d` and no `Transform` gets one at Z 0; draw order still comes
+//! from the `Transform`'s Z, which `Transform2d` never touches.
+//!
+//! ## Comparison
+//!
+//! - **Unity**: One 3D `Transform` for everything; 2D code uses
+//!   `transform.position` with Z ignored and `eulerAngles.z`.
+//! - **Bevy**: One 3D `Transform`; `Transform2d` is a long-standing
+//!   community request.
+//! - **Godot**: Separate `Node2D` with `position`, `rotation` (radians) and
+//!   `scale`, plus `look_at` and `rotate_toward` helpers.
+//! - **Our approach**: Godot's 2D fields as an optional component, synced to
+//!   the one 3D `Transform` the renderers and physics already read.
+
+use crate::ecs::World;
+use crate::math::{Quat, Transform, Vec2, Vec3};
+
+/// Component: a 2D position, counter-clockwise angle in radians and scale,
+/// kept in sync with the entity's [`Transform`]. See the
+/// [module docs](self).
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Transform2d {
+    pub translation: Vec2,
+    /// Counter-clockwise from +X, in radians.
+    pub rotation: f32,
+    pub scale: Vec2,
+}
+
+impl Transform2d {
+    /// At the origin, facing +X, scale 1.
+    pub const IDENTITY: Self = Self {
+        translation: Vec2::ZERO,
+        rotation: 0.0,
+        scale: Vec2::ONE,
+    };
+
+    /// At `(x, y)`.
+    pub fn from_xy(x: f32, y: f32) -> Self {
+        Self::from_translation(Vec2::new(x, y))
+    }
+
+    /// At `translation`.
+    pub fn from_translation(translation: Vec2) -> Self {
+        Self {
+            translation,
+            ..Self::IDENTITY
+        }
+    }
+
+    /// The 2D part of a 3D transform: X/Y, the angle about Z and X/Y scale.
+    pub fn from_transform(transform: &Transform) -> Self {
+        let right = transform.rotation * Vec3::X;
+        Self {
+            translation: transform.translation.truncate(),
+            rotation: right.y.atan2(right.x),
+            scale: transform.scale.truncate(),
+        }
+    }
+
+    /// Return a copy with this angle.
+    pub fn with_rotation(mut self, radians: f32) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    /// Return a copy with uniform scale applied.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = Vec2::splat(scale);
+        self
+    }
+
+    /// The 3D transform at depth `z`.
+    pub fn to_transform(&self, z: f32) -> Transform {
+        Transform {
+            translation: self.translation.extend(z),
+            rotation: Quat::from_rotation_z(self.rotation),
+            scale: self.scale.extend(1.0),
+        }
+    }
+
+    /// The direction the entity faces: +X rotated by `rotation`.
+    pub fn forward(&self) -> Vec2 {
+        Vec2::from_angle(self.rotation)
+    }
+
+    /// 90° counter-clockwise from [`forward`](Self::forward) — +Y at
+    /// rotation 0.
+    pub fn up(&self) -> Vec2 {
+        self.forward().perp()
+    }
+
+    /// Move `distance` along [`forward`](Self::forward).
+    pub fn move_forward(&mut self, distance: f32) {
+        self.translation += self.forward() * distance;
+    }
+
+    /// Turn counter-clockwise by `radians`.
+    pub fn rotate(&mut self, radians: f32) {
+        self.rotation = wrap_angle(self.rotation + radians);
+    }
+
+    /// Face `target` straight away.
+    pub fn look_at(&mut self, target: Vec2) {
+        let to = target - self.translation;
+        if to != Vec2::ZERO {
+            self.rotation = to.y.atan2(to.x);
+        }
+    }
+
+    /// Turn toward `target` the short way round by at most `max_radians`.
+    /// Returns `true` once facing it.
+    pub fn rotate_towards(&mut self, target: Vec2, max_radians: f32) -> bool {
+        let to = target - self.translation;
+        if to == Vec2::ZERO {
+            return true;
+        }
+        let diff = wrap_angle(to.y.atan2(to.x) - self.rotation);
+        let step = diff.clamp(-max_radians, max_radians);
+        self.rotation = wrap_angle(self.rotation + step);
+        step == diff
+    }
+
+    /// Move `point` from local to parent space: scale, rotate, translate.
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.translation + Vec2::from_angle(self.rotation).rotate(point * self.scale)
+    }
+}
+
+impl Default for Transform2d {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Wrap an angle into -π..=π.
+fn wrap_angle(radians: f32) -> f32 {
+    let wrapped = (radians + std::f32::consts::PI).rem_euclid(std::f32::consts::TAU);
+    wrapped - std::f32::consts::PI
+}
+
+// ── Sync ─────────────────────────────────────────────────────────────────
+
+/// Both components as of the last sync, to tell which one changed since.
+#[derive(Debug, Clone, Copy)]
+struct Synced2d {
+    transform2d: Transform2d,
+    transform: Transform,
+}
+
+/// Bring every [`Transform2d`] and its [`Transform`] into agreement. The
+/// engine calls this twice per frame; call it yourself after moving
+/// entities outside the frame loop (tests, tools).
+pub fn sync_transform2d(world: &mut World) {
+    for entity in world.entities_with::<Transform2d>() {
+        let Some(&tf2d) = world.get::<Transform2d>(entity) else {
+            continue;
+        };
+        let synced = world.get::<Synced2d>(entity).copied();
+        let (transform2d, transform) = match world.get::<Transform>(entity).copied() {
+            None => (tf2d, push(&tf2d, &Transform::IDENTITY)),
+            Some(tf) => match synced {
+                Some(s) if s.transform2d == tf2d && s.transform != tf => {
+                    (Transform2d::from_transform(&tf), tf)
+                }
+                Some(s) if s.transform2d == tf2d => continue,
+                _ => (tf2d, push(&tf2d, &tf)),
+            },
+        };
+        world.insert(entity, transform2d);
+        world.insert(entity, transform);
+        world.insert(entity, Synced2d { transform2d, transform });
+    }
+}
+
+/// `transform` with its 2D part replaced by `tf2d`. Z position and scale are
+/// kept.
+fn push(tf2d: &Transform2d, transform: &Transform) -> Transform {
+    let mut out = tf2d.to_transform(transform.translation.z);
+    out.scale.z = transform.scale.z;
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, PI};
+
+    #[test]
+    fn helpers_turn_and_move() {
+        let mut tf = Transform2d::from_xy(1.0, 1.0);
+        tf.move_forward(2.0);
+        assert_eq!(tf.translation, Vec2::new(3.0, 1.0));
+
+        assert!(!tf.rotate_towards(Vec2::new(3.0, 5.0), 0.5));
+        assert!((tf.rotation - 0.5).abs() < 1e-6);
+        assert!(tf.rotate_towards(Vec2::new(3.0, 5.0), 2.0));
+        assert!((tf.rotation - FRAC_PI_2).abs() < 1e-6);
+        assert!(tf.up().abs_diff_eq(Vec2::new(-1.0, 0.0), 1e-6));
+
+        // The short way round across ±π.
+        tf.rotation = 3.0;
+        tf.rotate_towards(tf.translation + Vec2::from_angle(-3.0), 0.1);
+        assert!((tf.rotation - 3.1).abs() < 1e-5);
+        tf.rotate(0.2);
+        assert!(tf.rotation < 0.0 && tf.rotation > -PI);
+
+        let tf = Transform2d::from_xy(10.0, 0.0).with_rotation(FRAC_PI_2).with_scale(2.0);
+        assert!(tf.transform_point(Vec2::X).abs_diff_eq(Vec2::new(10.0, 2.0), 1e-5));
+        let round = Transform2d::from_transform(&tf.to_transform(4.0));
+        assert!((round.rotation - tf.rotation).abs() < 1e-6 && round.scale == tf.scale);
+    }
+
+    #[test]
+    fn sync_follows_whichever_side_changed() {
+        let mut world = World::new();
+        let e = world.spawn((Transform2d::from_xy(5.0, 6.0),));
+        let layered =
+            world.spawn((Transform2d::from_xy(1.0, 0.0), Transform::from_xyz(0.0, 0.0, 7.0)));
+        sync_transform2d(&mut world);
+        assert_eq!(world.get::<Transform>(e).unwrap().translation, Vec3::new(5.0, 6.0, 0.0));
+        assert_eq!(world.get::<Transform>(layered).unwrap().translation, Vec3::new(1.0, 0.0, 7.0));
+
+        // Physics moves the Transform: Transform2d follows.
+        world.get_mut::<Transform>(e).unwrap().translation.x = 9.0;
+        sync_transform2d(&mut world);
+        assert_eq!(world.get::<Transform2d>(e).unwrap().translation, Vec2::new(9.0, 6.0));
+
+        // A system moves the Transform2d: Transform follows, even if both changed.
+        world.get_mut::<Transform2d>(e).unwrap().rotation = FRAC_PI_2;
+        world.get_mut::<Transform>(e).unwrap().translation.y = -1.0;
+        sync_transform2d(&mut world);
+        let tf = world.get::<Transform>(e).unwrap();
+        assert_eq!(tf.translation, Vec3::new(9.0, 6.0, 0.0));
+        assert!((tf.rotation * Vec3::X).abs_diff_eq(Vec3::Y, 1e-6));
+    }
+}
@@ -0,0 +1,203 @@
+//! # Triggers — Enter/Exit Events for Sensor Volumes
+//!
+//! A trigger volume is a collider that detects overlaps but never pushes
+//! anything: checkpoints, damage zones, pickup radii, automatic doors. Attach
+//! `TriggerVolume2d` / `TriggerVolume3d` to an entity with a `Transform` — no
+//! rigid body required — and the physics step reports every body that starts
+//! or stops overlapping it.
+//!
+//! ```text
+//!   step N-1        step N          step N+1
+//!   ┌─────┐        ┌─────┐         ┌─────┐
+//!   │     │   ●    │   ● │         │     │ ●
+//!   └─────┘        └─────┘         └─────┘
+//!                  TriggerEntered  TriggerExited
+//! ```
+//!
+//! Events land in the [`TriggerEvents`] resource. They are kept for one
+//! frame: from the physics step that produced them until the next frame's
+//! physics step, so systems running after physics see them the same frame
+//! and systems running before see them on the next.
+//!
+//! ```ignore
+//! fn checkpoints(ctx: &mut Context) {
+//!     let events = ctx.world.resource::<TriggerEvents>();
+//!     for enter in events.entered() {
+//!         log::info!("{:?} reached checkpoint {:?}", enter.other, enter.trigger);
+//!     }
+//! }
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `OnTriggerEnter`/`OnTriggerExit` callbacks on a collider
+//!   with `isTrigger` — but Unity requires a Rigidbody on one side.
+//! - **Bevy + Rapier**: `Sensor` component plus `CollisionEvent::Started/Stopped`
+//!   read through an event reader.
+//! - **Our approach**: Overlap sets are diffed after every fixed step, so an
+//!   object that passes through within a single frame still produces both
+//!   events.
+
+use std::collections::HashSet;
+
+use crate::ecs::Entity;
+
+/// Something started overlapping a trigger volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerEntered {
+    /// The entity with the trigger volume.
+    pub trigger: Entity,
+    /// The entity that entered it.
+    pub other: Entity,
+}
+
+/// Something stopped overlapping a trigger volume (or was despawned while
+/// inside it).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerExited {
+    /// The entity with the trigger volume.
+    pub trigger: Entity,
+    /// The entity that left it.
+    pub other: Entity,
+}
+
+/// Resource holding this frame's trigger events, oldest first.
+///
+/// Inserted by the physics plugins. 2D and 3D triggers share one resource.
+#[derive(Debug, Default)]
+pub struct TriggerEvents {
+    entered: Vec<TriggerEntered>,
+    exited: Vec<TriggerExited>,
+    /// Frame the events belong to, so 2D and 3D physics steps in the same
+    /// frame don't clear each other's events.
+    frame: Option<u64>,
+}
+
+impl TriggerEvents {
+    /// Overlaps that started this frame.
+    pub fn entered(&self) -> &[TriggerEntered] {
+        &self.entered
+    }
+
+    /// Overlaps that ended this frame.
+    pub fn exited(&self) -> &[TriggerExited] {
+        &self.exited
+    }
+
+    /// Entities that entered `trigger` this frame.
+    pub fn entered_by(&self, trigger: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.entered
+            .iter()
+            .filter(move |e| e.trigger == trigger)
+            .map(|e| e.other)
+    }
+
+    /// Entities that left `trigger` this frame.
+    pub fn exited_by(&self, trigger: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.exited
+            .iter()
+            .filter(move |e| e.trigger == trigger)
+            .map(|e| e.other)
+    }
+
+    /// Drop the previous frame's events the first time a physics step runs
+    /// in `frame`.
+    pub(crate) fn begin_frame(&mut self, frame: u64) {
+        if self.frame != Some(frame) {
+            self.entered.clear();
+            self.exited.clear();
+            self.frame = Some(frame);
+        }
+    }
+}
+
+/// The set of `(trigger, other)` pairs overlapping after the last step.
+/// Owned by each physics world.
+#[derive(Debug, Default)]
+pub(crate) struct TriggerOverlaps {
+    pairs: HashSet<(Entity, Entity)>,
+}
+
+impl TriggerOverlaps {
+    /// Replace the overlap set with `now`, emitting enter/exit events for the
+    /// difference. Events are sorted by entity index for determinism.
+    pub fn update(&mut self, now: HashSet<(Entity, Entity)>, events: &mut TriggerEvents) {
+        let key = |&(a, b): &(Entity, Entity)| (a.index(), b.index());
+
+        let mut entered: Vec<_> = now.difference(&self.pairs).copied().collect();
+        entered.sort_by_key(key);
+        events
+            .entered
+            .extend(entered.into_iter().map(|(trigger, other)| TriggerEntered { trigger, other }));
+
+        let mut exited: Vec<_> = self.pairs.difference(&now).copied().collect();
+        exited.sort_by_key(key);
+        events
+            .exited
+            .extend(exited.into_iter().map(|(trigger, other)| TriggerExited { trigger, other }));
+
+        self.pairs = now;
+    }
+
+    /// Forget every pair involving `trigger` without emitting events (the
+    /// trigger itself was despawned).
+    pub fn forget_trigger(&mut self, trigger: Entity) {
+        self.pairs.retain(|&(t, _)| t != trigger);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::World;
+
+    #[test]
+    fn diff_emits_enter_then_exit() {
+        let mut world = World::new();
+        let (zone, player) = (world.spawn_empty(), world.spawn_empty());
+        let mut overlaps = TriggerOverlaps::default();
+        let mut events = TriggerEvents::default();
+
+        events.begin_frame(1);
+        overlaps.update(HashSet::from([(zone, player)]), &mut events);
+        overlaps.update(HashSet::from([(zone, player)]), &mut events);
+        assert_eq!(events.entered(), &[TriggerEntered { trigger: zone, other: player }]);
+        assert!(events.exited().is_empty());
+
+        events.begin_frame(2);
+        overlaps.update(HashSet::new(), &mut events);
+        assert!(events.entered().is_empty());
+        assert_eq!(events.exited_by(zone).collect::<Vec<_>>(), vec![player]);
+    }
+
+    #[test]
+    fn same_frame_steps_accumulate() {
+        let mut world = World::new();
+        let (zone, coin) = (world.spawn_empty(), world.spawn_empty());
+        let mut overlaps = TriggerOverlaps::default();
+        let mut events = TriggerEvents::default();
+
+        // Passes through within one frame (two fixed steps).
+        events.begin_frame(7);
+        overlaps.update(HashSet::from([(zone, coin)]), &mut events);
+        events.begin_frame(7);
+        overlaps.update(HashSet::new(), &mut events);
+
+        assert_eq!(events.entered_by(zone).count(), 1);
+        assert_eq!(events.exited_by(zone).count(), 1);
+    }
+
+    #[test]
+    fn forgotten_trigger_emits_nothing() {
+        let mut world = World::new();
+        let (zone, player) = (world.spawn_empty(), world.spawn_empty());
+        let mut overlaps = TriggerOverlaps::default();
+        let mut events = TriggerEvents::default();
+
+        overlaps.update(HashSet::from([(zone, player)]), &mut events);
+        events.begin_frame(2);
+        overlaps.forget_trigger(zone);
+        overlaps.update(HashSet::new(), &mut events);
+        assert!(events.exited().is_empty());
+    }
+}
@@ -0,0 +1,245 @@
+//! Mouse hover, press and click for [`Button`]s. See the [module docs](super).
+
+use crate::ecs::hierarchy::Parent;
+use crate::ecs::{Entity, World};
+use crate::input::{CursorPosition, Input, MouseButton};
+use crate::math::Vec2;
+use crate::render2d::Color;
+
+use super::{ComputedNode, UiImage};
+
+/// What the mouse is doing to a [`Button`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Interaction {
+    #[default]
+    None,
+    /// The cursor is over the button.
+    Hovered,
+    /// The left mouse button went down on the button and is still held.
+    Pressed,
+}
+
+/// [`UiImage`] colors a [`Button`] switches between as its
+/// [`Interaction`] changes.
+#[derive(Debug, Clone, Copy)]
+pub struct ButtonColors {
+    pub normal: Color,
+    pub hovered: Color,
+    pub pressed: Color,
+}
+
+impl Default for ButtonColors {
+    fn default() -> Self {
+        Self {
+            normal: Color::rgb(0.25, 0.25, 0.3),
+            hovered: Color::rgb(0.35, 0.35, 0.42),
+            pressed: Color::rgb(0.18, 0.18, 0.22),
+        }
+    }
+}
+
+/// Component: make a [`UiNode`](super::UiNode) clickable.
+///
+/// A click is a left press and release both over the button; releasing
+/// anywhere else cancels it. Children (e.g. its label) count as part of
+/// the button, and nodes painted on top of it block the mouse.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Button {
+    pub interaction: Interaction,
+    /// Tint the entity's [`UiImage`] to match `interaction`.
+    pub colors: Option<ButtonColors>,
+    /// A press started on this button and hasn't been released yet.
+    armed: bool,
+    clicked: bool,
+}
+
+impl Button {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tint the button's [`UiImage`] on hover and press (builder pattern).
+    pub fn colors(mut self, colors: ButtonColors) -> Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    /// Returns `true` on the frame the button was clicked.
+    pub fn clicked(&self) -> bool {
+        self.clicked
+    }
+
+    /// Returns `true` while the cursor is over the button.
+    pub fn is_hovered(&self) -> bool {
+        self.interaction != Interaction::None
+    }
+
+    /// Returns `true` while the button is held down.
+    pub fn is_pressed(&self) -> bool {
+        self.interaction == Interaction::Pressed
+    }
+
+    /// Click the button this frame without the mouse, e.g. from keyboard or
+    /// gamepad [focus](crate::focus).
+    pub(crate) fn activate(&mut self) {
+        self.clicked = true;
+    }
+
+    /// Advance one frame: `over` is whether the cursor is over the button.
+    fn update(&mut self, over: bool, mouse: &Input<MouseButton>) {
+        if over && mouse.just_pressed(MouseButton::Left) {
+            self.armed = true;
+        }
+        let held = mouse.pressed(MouseButton::Left);
+        self.clicked = self.armed && !held && over;
+        if !held {
+            self.armed = false;
+        }
+        self.interaction = match (over, self.armed) {
+            (true, true) => Interaction::Pressed,
+            (true, false) => Interaction::Hovered,
+            (false, _) => Interaction::None,
+        };
+    }
+}
+
+/// Resource: the UI node under the mouse cursor, if any. Check it before
+/// treating a click as a click on the game world.
+///
+/// ```ignore
+/// let over_ui = ctx.world.get_resource::<UiPointer>().is_some_and(|p| p.over_ui());
+/// if ctx.input.mouse_just_pressed(MouseButton::Left) && !over_ui { fire(ctx); }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UiPointer {
+    /// The topmost node under the cursor.
+    pub hovered: Option<Entity>,
+}
+
+impl UiPointer {
+    /// Returns `true` if the cursor is over any UI node.
+    pub fn over_ui(&self) -> bool {
+        self.hovered.is_some()
+    }
+}
+
+/// Update every [`Button`] from the mouse, using last frame's layout (what
+/// the player saw when they clicked). Run once per frame before the update
+/// systems.
+pub(crate) fn update_buttons(world: &mut World, mouse: &Input<MouseButton>, cursor: CursorPosition) {
+    let point = Vec2::new(cursor.x, cursor.y);
+    let mut top: Option<(u32, Entity)> = None;
+    world.query::<(&ComputedNode,)>(|entity, (node,)| {
+        if node.contains(point) && top.is_none_or(|(order, _)| node.order > order) {
+            top = Some((node.order, entity));
+        }
+    });
+    let hovered = top.map(|(_, entity)| entity);
+
+    // The button under the cursor is the topmost node or its nearest
+    // button ancestor.
+    let mut target = hovered;
+    while let Some(entity) = target
+        && world.get::<Button>(entity).is_none()
+    {
+        target = world.get::<Parent>(entity).map(|p| p.0);
+    }
+
+    let mut tints = Vec::new();
+    world.query::<(&mut Button,)>(|entity, (button,)| {
+        button.update(target == Some(entity), mouse);
+        if let Some(colors) = button.colors {
+            let tint = match button.interaction {
+                Interaction::None => colors.normal,
+                Interaction::Hovered => colors.hovered,
+                Interaction::Pressed => colors.pressed,
+            };
+            tints.push((entity, tint));
+        }
+    });
+    for (entity, tint) in tints {
+        if let Some(image) = world.get_mut::<UiImage>(entity) {
+            image.color = tint;
+        }
+    }
+
+    match world.get_resource_mut::<UiPointer>() {
+        Some(pointer) => pointer.hovered = hovered,
+        None => world.insert_resource(UiPointer { hovered }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::Rect;
+
+    fn node(world: &mut World, min: (f32, f32), max: (f32, f32), order: u32) -> Entity {
+        world.spawn((ComputedNode {
+            rect: Rect {
+                min: Vec2::new(min.0, min.1),
+                max: Vec2::new(max.0, max.1),
+            },
+            order,
+        },))
+    }
+
+    #[test]
+    fn click_needs_press_and_release_over_the_button() {
+        let mut world = World::new();
+        let button = node(&mut world, (0.0, 0.0), (100.0, 40.0), 0);
+        world.insert(button, Button::new());
+        let label = world.spawn_child(button, (ComputedNode {
+            rect: Rect {
+                min: Vec2::new(10.0, 10.0),
+                max: Vec2::new(50.0, 30.0),
+            },
+            order: 1,
+        },));
+        let over_label = CursorPosition { x: 20.0, y: 20.0 };
+        let outside = CursorPosition { x: 200.0, y: 20.0 };
+        let mut mouse = Input::new();
+        let state = |world: &World| *world.get::<Button>(button).unwrap();
+
+        update_buttons(&mut world, &mouse, over_label);
+        assert_eq!(state(&world).interaction, Interaction::Hovered);
+        assert_eq!(world.resource::<UiPointer>().hovered, Some(label));
+
+        mouse.press(MouseButton::Left);
+        update_buttons(&mut world, &mouse, over_label);
+        assert!(state(&world).is_pressed() && !state(&world).clicked());
+
+        mouse.clear_just();
+        mouse.release(MouseButton::Left);
+        update_buttons(&mut world, &mouse, over_label);
+        assert!(state(&world).clicked());
+        mouse.clear_just();
+        update_buttons(&mut world, &mouse, over_label);
+        assert!(!state(&world).clicked());
+
+        // Releasing somewhere else cancels the click.
+        mouse.press(MouseButton::Left);
+        update_buttons(&mut world, &mouse, over_label);
+        mouse.clear_just();
+        update_buttons(&mut world, &mouse, outside);
+        assert_eq!(state(&world).interaction, Interaction::None);
+        mouse.release(MouseButton::Left);
+        update_buttons(&mut world, &mouse, outside);
+        assert!(!state(&world).clicked());
+        mouse.clear_just();
+        update_buttons(&mut world, &mouse, over_label);
+        assert!(!state(&world).clicked());
+    }
+
+    #[test]
+    fn nodes_on_top_block_the_mouse() {
+        let mut world = World::new();
+        let button = node(&mut world, (0.0, 0.0), (100.0, 40.0), 0);
+        world.insert(button, Button::new());
+        let popup = node(&mut world, (50.0, 0.0), (150.0, 100.0), 5);
+
+        update_buttons(&mut world, &Input::new(), CursorPosition { x: 60.0, y: 20.0 });
+        assert_eq!(world.get::<Button>(button).unwrap().interaction, Interaction::None);
+        assert_eq!(world.resource::<UiPointer>().hovered, Some(popup));
+    }
+}
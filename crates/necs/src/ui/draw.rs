@@ -0,0 +1,354 @@
+//! The UI render pass: quads for every laid-out node, drawn over the world
+//! in screen pixels. See the [module docs](super).
+
+use std::ops::Range;
+
+use wgpu::util::DeviceExt;
+
+use crate::ecs::{Entity, World};
+use crate::math::{Mat4, Rect, Vec2};
+use crate::render::GpuContext;
+use crate::render::pass::FrameContext;
+use crate::render2d::draw::ensure_sprite_renderer;
+use crate::render2d::font::{BASELINE, FontEntry, FontStore};
+use crate::render2d::pipeline::SpriteRenderer;
+use crate::render2d::texture::{TextureHandle, TextureStore, WhiteSpots};
+use crate::render2d::vertex::{CameraUniform, SpriteVertex};
+
+use super::text_input::TextSpans;
+use super::{ComputedNode, TextFocus, TextInput, TextInputColors, UiImage, UiNode, UiText};
+
+/// Resource: the UI's own camera uniform. The sprite pipeline is shared
+/// with the 2D renderer, but its camera buffer still holds the world
+/// projection for this frame's sprite pass.
+pub(crate) struct UiRenderer {
+    camera_buffer: wgpu::Buffer,
+    camera_bind_group: wgpu::BindGroup,
+}
+
+impl UiRenderer {
+    fn new(gpu: &GpuContext, sprites: &SpriteRenderer) -> Self {
+        let camera_buffer = gpu.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("ui camera uniform buffer"),
+            size: std::mem::size_of::<CameraUniform>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let camera_bind_group = gpu.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("ui camera bind group"),
+            layout: &sprites.camera_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: camera_buffer.as_entire_binding(),
+            }],
+        });
+        Self {
+            camera_buffer,
+            camera_bind_group,
+        }
+    }
+}
+
+/// A run of consecutive quads sharing a texture.
+struct UiBatch {
+    texture: TextureHandle,
+    start: u32,
+    count: u32,
+}
+
+/// Quads for the whole UI, in paint order.
+struct UiMesh {
+    vertices: Vec<SpriteVertex>,
+    indices: Vec<u32>,
+    batches: Vec<UiBatch>,
+    /// Where untextured quads draw from.
+    white: WhiteSpots,
+}
+
+impl UiMesh {
+    fn new(white: WhiteSpots) -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            batches: Vec::new(),
+            white,
+        }
+    }
+
+    /// Append an untextured quad. After a quad from an atlas page it draws
+    /// from the page's white block, so panels, carets and selections don't
+    /// break the batch between packed icons and text.
+    fn solid(&mut self, rect: Rect, color: [f32; 4]) {
+        let page = self.batches.last().and_then(|batch| {
+            self.white.pages.get(&batch.texture).map(|&uv| (batch.texture, uv))
+        });
+        let (texture, uv) = match page {
+            Some((page, uv)) => (page, Rect { min: uv, max: uv }),
+            None => (self.white.default, Rect::FULL),
+        };
+        self.quad(texture, rect, uv, color);
+    }
+
+    /// Append a screen-space quad; `uv` is top-left to bottom-right.
+    fn quad(&mut self, texture: TextureHandle, rect: Rect, uv: Rect, color: [f32; 4]) {
+        let base = self.vertices.len() as u32;
+        let corners = [
+            (rect.min.x, rect.min.y, uv.min.x, uv.min.y),
+            (rect.max.x, rect.min.y, uv.max.x, uv.min.y),
+            (rect.max.x, rect.max.y, uv.max.x, uv.max.y),
+            (rect.min.x, rect.max.y, uv.min.x, uv.max.y),
+        ];
+        for (x, y, u, v) in corners {
+            self.vertices.push(SpriteVertex {
+                position: [x, y, 0.0],
+                uv: [u, v],
+                color,
+                texture_index: 0,
+            });
+        }
+        self.indices
+            .extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+
+        match self.batches.last_mut() {
+            Some(batch) if batch.texture == texture => batch.count += 6,
+            _ => self.batches.push(UiBatch {
+                texture,
+                start: self.indices.len() as u32 - 6,
+                count: 6,
+            }),
+        }
+    }
+
+    /// Append the glyphs of `text`, top-left aligned in `area`.
+    fn text(&mut self, textures: &TextureStore, fonts: &FontStore, text: &UiText, area: Rect) {
+        let entry = fonts.get(text.font);
+        // A packed font atlas draws from its page.
+        let (atlas, region) = textures.draw_source(entry.atlas_handle);
+        let region_size = region.max - region.min;
+        let color = text.color.to_array();
+        // Whole pixels keep glyphs crisp.
+        let origin = area.min.round();
+        for (row, line) in text.content.split('\n').enumerate() {
+            let baseline = origin.y + (row as f32 + 1.0 - BASELINE) * entry.line_height;
+            let mut cursor = origin.x;
+            for glyph in line.chars().filter_map(|ch| entry.glyph(ch)) {
+                if glyph.width > 0.0 && glyph.height > 0.0 {
+                    // offset_y is the glyph bottom above the baseline (Y-up).
+                    let min = Vec2::new(
+                        cursor + glyph.offset_x,
+                        baseline - glyph.offset_y - glyph.height,
+                    );
+                    let rect = Rect {
+                        min,
+                        max: min + Vec2::new(glyph.width, glyph.height),
+                    };
+                    let uv = Rect {
+                        min: region.min + Vec2::new(glyph.u_min, glyph.v_min) * region_size,
+                        max: region.min + Vec2::new(glyph.u_max, glyph.v_max) * region_size,
+                    };
+                    self.quad(atlas, rect, uv, color);
+                }
+                cursor += glyph.advance;
+            }
+        }
+    }
+}
+
+/// The area covered by bytes `range` of the first line of `content` drawn
+/// at `origin`, or `None` if the range isn't on character boundaries.
+fn span_rect(font: &FontEntry, content: &str, origin: Vec2, range: Range<usize>) -> Option<Rect> {
+    let x = |at: usize| content.get(..at).map(|before| origin.x + font.line_width(before));
+    Some(Rect {
+        min: Vec2::new(x(range.start)?, origin.y),
+        max: Vec2::new(x(range.end)?, origin.y + font.line_height),
+    })
+}
+
+// ── Extract ─────────────────────────────────────────────────────────────
+
+/// Every laid-out node, back to front, copied out by the
+/// [extract phase](crate::render::extract).
+pub(crate) struct ExtractedUi {
+    nodes: Vec<ExtractedNode>,
+}
+
+/// What one [`ComputedNode`] draws.
+struct ExtractedNode {
+    rect: Rect,
+    image: Option<UiImage>,
+    /// The node's text and the area it is drawn in.
+    text: Option<(UiText, Rect)>,
+    /// The selection, caret and composition of the focused [`TextInput`].
+    field: Option<(TextSpans, TextInputColors)>,
+}
+
+/// Copy every node with a [`ComputedNode`] in draw order, or `None` when
+/// there are none.
+pub(crate) fn extract_ui(world: &mut World) -> Option<ExtractedUi> {
+    if !world.has_component_type::<ComputedNode>() {
+        return None;
+    }
+    let mut nodes: Vec<(u32, Entity, Rect)> = Vec::new();
+    world.query::<(&ComputedNode,)>(|entity, (node,)| nodes.push((node.order, entity, node.rect)));
+    nodes.sort_by_key(|&(order, _, _)| order);
+    let focused = world.get_resource::<TextFocus>().and_then(|focus| focus.focused());
+
+    let nodes = nodes
+        .into_iter()
+        .map(|(_, entity, rect)| {
+            let text = world.get::<UiText>(entity).map(|text| {
+                let padding = world.get::<UiNode>(entity).map(|n| n.padding).unwrap_or_default();
+                let area = Rect {
+                    min: rect.min + padding.min(),
+                    max: rect.max,
+                };
+                (text.clone(), area)
+            });
+            let field = world
+                .get::<TextInput>(entity)
+                .filter(|_| focused == Some(entity))
+                .map(|field| (field.spans(), field.colors));
+            ExtractedNode {
+                rect,
+                image: world.get::<UiImage>(entity).copied(),
+                text,
+                field,
+            }
+        })
+        .collect();
+    Some(ExtractedUi { nodes })
+}
+
+/// Build the quads for every extracted node: its image first, then its
+/// text, back to front. The focused [`TextInput`] adds its selection behind
+/// the text and its caret and composition underline on top.
+fn collect_ui(ui: &ExtractedUi, textures: &TextureStore, fonts: Option<&FontStore>) -> UiMesh {
+    let mut mesh = UiMesh::new(textures.white_spots());
+    for node in &ui.nodes {
+        if let Some(image) = &node.image {
+            match image.texture {
+                Some(texture) => {
+                    let (texture, uv) = textures.draw_source(texture);
+                    mesh.quad(texture, node.rect, uv, image.color.to_array());
+                }
+                None => mesh.solid(node.rect, image.color.to_array()),
+            }
+        }
+        if let Some((text, area)) = &node.text
+            && let Some(fonts) = fonts
+        {
+            let Some((spans, colors)) = &node.field else {
+                mesh.text(textures, fonts, text, *area);
+                continue;
+            };
+            let font = fonts.get(text.font);
+            let origin = area.min.round();
+            if let Some(selection) = spans.selection.clone()
+                && let Some(rect) = span_rect(font, &text.content, origin, selection)
+            {
+                mesh.solid(rect, colors.selection.to_array());
+            }
+            mesh.text(textures, fonts, text, *area);
+            // About 1px at small sizes, thicker for large text.
+            let thickness = (font.line_height / 16.0).round().max(1.0);
+            if let Some(preedit) = spans.preedit.clone()
+                && let Some(rect) = span_rect(font, &text.content, origin, preedit)
+            {
+                let underline = Rect {
+                    min: Vec2::new(rect.min.x, rect.max.y - thickness),
+                    max: rect.max,
+                };
+                mesh.solid(underline, colors.text.to_array());
+            }
+            if spans.caret_visible
+                && let Some(rect) = span_rect(font, &text.content, origin, spans.caret..spans.caret)
+            {
+                let caret = Rect {
+                    min: rect.min,
+                    max: Vec2::new(rect.min.x + thickness, rect.max.y),
+                };
+                mesh.solid(caret, colors.caret.to_array());
+            }
+        }
+    }
+    mesh
+}
+
+/// Draw the UI over the finished frame with a pixel projection (origin top
+/// left, Y down). Called from `render_frame` after post-processing, so UI
+/// is never bloomed or color graded.
+pub(crate) fn render_ui(world: &mut World, frame: &mut FrameContext<'_>, ui_nodes: &ExtractedUi) {
+    let gpu = frame.gpu;
+    ensure_sprite_renderer(world, gpu);
+    let (Some(sprites), Some(textures)) = (
+        world.resource_remove::<SpriteRenderer>(),
+        world.resource_remove::<TextureStore>(),
+    ) else {
+        return;
+    };
+    if !world.has_resource::<UiRenderer>() {
+        let ui = UiRenderer::new(gpu, &sprites);
+        world.insert_resource(ui);
+    }
+    let ui = world.resource_remove::<UiRenderer>().expect("UiRenderer missing");
+    let fonts = world.resource_remove::<FontStore>();
+
+    let mesh = collect_ui(ui_nodes, &textures, fonts.as_ref());
+    if !mesh.batches.is_empty() {
+        let (width, height) = gpu.surface_size();
+        let projection = Mat4::orthographic_rh(0.0, width as f32, height as f32, 0.0, -1.0, 1.0);
+        let uniform = CameraUniform {
+            view_proj: projection.to_cols_array_2d(),
+        };
+        gpu.queue
+            .write_buffer(&ui.camera_buffer, 0, bytemuck::cast_slice(&[uniform]));
+
+        let vertex_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("ui vertex buffer"),
+            contents: bytemuck::cast_slice(&mesh.vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let index_buffer = gpu.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("ui index buffer"),
+            contents: bytemuck::cast_slice(&mesh.indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        let mut render_pass = frame.encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("ui render pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &frame.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+                depth_slice: None,
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        render_pass.set_pipeline(&sprites.pipeline);
+        render_pass.set_bind_group(0, &ui.camera_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        for batch in &mesh.batches {
+            render_pass.set_bind_group(1, &textures.get(batch.texture).bind_group, &[]);
+            render_pass.draw_indexed(batch.start..batch.start + batch.count, 0, 0..1);
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    if let Some(stats) = world.get_resource_mut::<crate::diag::RenderStats>() {
+        stats.draw_calls += mesh.batches.len() as u32;
+        stats.vertices += mesh.vertices.len() as u32;
+    }
+
+    world.insert_resource(ui);
+    world.insert_resource(sprites);
+    world.insert_resource(textures);
+    if let Some(fonts) = fonts {
+        world.insert_resource(fonts);
+    }
+}
@@ -0,0 +1,329 @@
+//! Measure and arrange the UI tree. See the [module docs](super).
+
+use std::collections::HashMap;
+
+use crate::ecs::hierarchy::{Children, Parent};
+use crate::ecs::{Entity, World};
+use crate::math::{Rect, Vec2};
+use crate::render::visibility::{ComputedVisibility, is_hidden};
+use crate::render2d::font::FontStore;
+use crate::render2d::texture::TextureStore;
+
+use super::{ComputedNode, UiAlign, UiImage, UiJustify, UiLayout, UiNode, UiText};
+
+/// One visible node, with everything layout needs copied out of the world.
+struct TreeNode {
+    entity: Entity,
+    node: UiNode,
+    /// Size of the node's own text or image, before padding.
+    content: Vec2,
+    children: Vec<usize>,
+}
+
+/// The visible UI nodes, linked parent to child.
+struct UiTree {
+    nodes: Vec<TreeNode>,
+    /// Root indices in paint order.
+    roots: Vec<usize>,
+}
+
+impl UiTree {
+    fn build(world: &mut World) -> Self {
+        let mut visible = Vec::new();
+        world.query::<(&UiNode, Option<&ComputedVisibility>)>(|entity, (node, vis)| {
+            if !is_hidden(vis) {
+                visible.push((entity, *node));
+            }
+        });
+
+        let index: HashMap<Entity, usize> =
+            visible.iter().enumerate().map(|(i, (entity, _))| (*entity, i)).collect();
+        let fonts = world.get_resource::<FontStore>();
+        let textures = world.get_resource::<TextureStore>();
+
+        let mut nodes = Vec::with_capacity(visible.len());
+        let mut roots = Vec::new();
+        for (i, &(entity, node)) in visible.iter().enumerate() {
+            // A node whose parent is a hidden UI node is hidden with it, not
+            // promoted to a root.
+            let parent = world.get::<Parent>(entity).map(|p| p.0);
+            if parent.is_none_or(|p| world.get::<UiNode>(p).is_none()) {
+                roots.push(i);
+            }
+            let children = world
+                .get::<Children>(entity)
+                .map(|c| c.0.iter().filter_map(|child| index.get(child).copied()).collect())
+                .unwrap_or_default();
+
+            let mut content = Vec2::ZERO;
+            if let Some(text) = world.get::<UiText>(entity)
+                && let Some(fonts) = fonts
+            {
+                content = content.max(text_size(fonts, text));
+            }
+            if let Some(image) = world.get::<UiImage>(entity)
+                && let (Some(texture), Some(textures)) = (image.texture, textures)
+            {
+                let entry = textures.get(texture);
+                content = content.max(Vec2::new(entry.width as f32, entry.height as f32));
+            }
+
+            nodes.push(TreeNode {
+                entity,
+                node,
+                content,
+                children,
+            });
+        }
+
+        roots.sort_by_key(|&i| (nodes[i].node.z_index, nodes[i].entity.index()));
+        Self { nodes, roots }
+    }
+
+    /// Size of node `i` (padding included, margin not) given the space its
+    /// parent offers.
+    fn measure(&self, i: usize, available: Vec2) -> Vec2 {
+        let TreeNode {
+            node,
+            content,
+            children,
+            ..
+        } = &self.nodes[i];
+        let fixed_w = node.width.resolve(available.x);
+        let fixed_h = node.height.resolve(available.y);
+        let padding = node.padding.size();
+        let inner = (Vec2::new(fixed_w.unwrap_or(available.x), fixed_h.unwrap_or(available.y))
+            - padding)
+            .max(Vec2::ZERO);
+
+        let outer: Vec<Vec2> = children
+            .iter()
+            .map(|&c| self.measure(c, inner) + self.nodes[c].node.margin.size())
+            .collect();
+        let gaps = node.gap * outer.len().saturating_sub(1) as f32;
+        let sum = outer.iter().fold(Vec2::ZERO, |acc, s| acc + *s);
+        let max = outer.iter().fold(Vec2::ZERO, |acc, s| acc.max(*s));
+        let packed = match node.layout {
+            UiLayout::Row => Vec2::new(sum.x + gaps, max.y),
+            UiLayout::Column => Vec2::new(max.x, sum.y + gaps),
+            UiLayout::Stack => max,
+        };
+
+        let fit = content.max(packed) + padding;
+        Vec2::new(fixed_w.unwrap_or(fit.x), fixed_h.unwrap_or(fit.y))
+    }
+
+    /// Place node `i` at `rect`, then its children inside it.
+    fn arrange(&self, i: usize, rect: Rect, out: &mut Vec<(Entity, Rect)>) {
+        let TreeNode {
+            entity,
+            node,
+            children,
+            ..
+        } = &self.nodes[i];
+        out.push((*entity, rect));
+
+        let min = rect.min + node.padding.min();
+        let size = (rect.max - rect.min - node.padding.size()).max(Vec2::ZERO);
+        let inner = Rect {
+            min,
+            max: min + size,
+        };
+
+        if node.layout == UiLayout::Stack {
+            for &c in children {
+                let child = &self.nodes[c].node;
+                let rect = anchored(child, inner, self.measure(c, size));
+                self.arrange(c, rect, out);
+            }
+            return;
+        }
+
+        let (main, cross) = if node.layout == UiLayout::Row { (0, 1) } else { (1, 0) };
+        let sizes: Vec<Vec2> = children
+            .iter()
+            .map(|&c| {
+                let child = &self.nodes[c].node;
+                let mut s = self.measure(c, size);
+                let cross_auto = if cross == 0 { child.width } else { child.height };
+                if node.align == UiAlign::Stretch && cross_auto == super::UiSize::Auto {
+                    s[cross] = (size[cross] - child.margin.size()[cross]).max(0.0);
+                }
+                s
+            })
+            .collect();
+
+        let count = children.len();
+        let total: f32 = children
+            .iter()
+            .zip(&sizes)
+            .map(|(&c, s)| s[main] + self.nodes[c].node.margin.size()[main])
+            .sum::<f32>()
+            + node.gap * count.saturating_sub(1) as f32;
+        let free = size[main] - total;
+        let (mut cursor, spacing) = match node.justify {
+            UiJustify::Start => (0.0, node.gap),
+            UiJustify::Center => (free * 0.5, node.gap),
+            UiJustify::End => (free, node.gap),
+            UiJustify::SpaceBetween if count > 1 && free > 0.0 => {
+                (0.0, node.gap + free / (count - 1) as f32)
+            }
+            UiJustify::SpaceBetween => (0.0, node.gap),
+        };
+
+        for (&c, s) in children.iter().zip(&sizes) {
+            let child = &self.nodes[c].node;
+            let margin = child.margin.size();
+            let outer_cross = s[cross] + margin[cross];
+            let cross_pos = match node.align {
+                UiAlign::Start | UiAlign::Stretch => 0.0,
+                UiAlign::Center => (size[cross] - outer_cross) * 0.5,
+                UiAlign::End => size[cross] - outer_cross,
+            };
+            let mut pos = Vec2::ZERO;
+            pos[main] = cursor;
+            pos[cross] = cross_pos;
+            let min = inner.min + pos + child.margin.min() + child.offset;
+            self.arrange(
+                c,
+                Rect {
+                    min,
+                    max: min + *s,
+                },
+                out,
+            );
+            cursor += s[main] + margin[main] + spacing;
+        }
+    }
+}
+
+/// Where a node of `size` goes in `area` by its anchor, margin and offset.
+fn anchored(node: &UiNode, area: Rect, size: Vec2) -> Rect {
+    let outer = size + node.margin.size();
+    let min = area.min + (area.max - area.min - outer) * node.anchor.fraction()
+        + node.margin.min()
+        + node.offset;
+    Rect {
+        min,
+        max: min + size,
+    }
+}
+
+/// Width of the widest line and the height of all lines.
+pub(super) fn text_size(fonts: &FontStore, text: &UiText) -> Vec2 {
+    let entry = fonts.get(text.font);
+    let lines = text.content.split('\n');
+    let (count, width) = lines.fold((0, 0.0f32), |(count, width), line| {
+        (count + 1, width.max(entry.line_width(line)))
+    });
+    Vec2::new(width, count as f32 * entry.line_height)
+}
+
+/// Lay out every visible UI tree on a screen of `screen` pixels, writing a
+/// [`ComputedNode`] to each node. Hidden nodes lose theirs.
+pub(crate) fn layout_nodes(world: &mut World, screen: Vec2) {
+    let tree = UiTree::build(world);
+    let screen_rect = Rect {
+        min: Vec2::ZERO,
+        max: screen,
+    };
+    let mut placed = Vec::with_capacity(tree.nodes.len());
+    for &root in &tree.roots {
+        let rect = anchored(&tree.nodes[root].node, screen_rect, tree.measure(root, screen));
+        tree.arrange(root, rect, &mut placed);
+    }
+
+    let mut stale = Vec::new();
+    world.query::<(&ComputedNode,)>(|entity, _| stale.push(entity));
+    let placed_set: HashMap<Entity, u32> = placed
+        .iter()
+        .enumerate()
+        .map(|(order, (entity, _))| (*entity, order as u32))
+        .collect();
+    for entity in stale {
+        if !placed_set.contains_key(&entity) {
+            world.remove::<ComputedNode>(entity);
+        }
+    }
+
+    for (order, (entity, rect)) in placed.into_iter().enumerate() {
+        let computed = ComputedNode {
+            rect,
+            order: order as u32,
+        };
+        match world.get_mut::<ComputedNode>(entity) {
+            Some(existing) => *existing = computed,
+            None => world.insert(entity, computed),
+        }
+    }
+}
+
+/// Lay out the UI for the current surface size. Run once per frame after
+/// the update systems.
+pub(crate) fn layout_ui(world: &mut World) {
+    let Some(gpu) = world.get_resource::<crate::render::GpuContext>() else {
+        return;
+    };
+    let (width, height) = gpu.surface_size();
+    layout_nodes(world, Vec2::new(width as f32, height as f32));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::{Hidden, propagate_visibility};
+    use crate::ui::{UiAnchor, UiEdges};
+
+    fn rect(world: &World, entity: Entity) -> (f32, f32, f32, f32) {
+        let r = world.get::<ComputedNode>(entity).expect("laid out").rect;
+        (r.min.x, r.min.y, r.max.x, r.max.y)
+    }
+
+    #[test]
+    fn column_fits_children_and_centers_on_screen() {
+        let mut world = World::new();
+        let menu = world.spawn((UiNode::new()
+            .anchor(UiAnchor::Center)
+            .padding(UiEdges::all(10.0))
+            .gap(5.0)
+            .align(UiAlign::Center),));
+        let wide = world.spawn_child(menu, (UiNode::new().size(100.0, 20.0),));
+        let narrow = world.spawn_child(
+            menu,
+            (UiNode::new().size(40.0, 20.0).margin(UiEdges::axes(0.0, 2.5)),),
+        );
+
+        layout_nodes(&mut world, Vec2::new(800.0, 600.0));
+        // 100 + 2×10 wide; 20 + 5 + (20 + 5) + 2×10 tall.
+        assert_eq!(rect(&world, menu), (340.0, 265.0, 460.0, 335.0));
+        assert_eq!(rect(&world, wide), (350.0, 275.0, 450.0, 295.0));
+        assert_eq!(rect(&world, narrow), (380.0, 302.5, 420.0, 322.5));
+        let order = |e| world.get::<ComputedNode>(e).unwrap().order;
+        assert!(order(menu) < order(wide) && order(wide) < order(narrow));
+    }
+
+    #[test]
+    fn row_justifies_and_hidden_subtrees_drop_out() {
+        let mut world = World::new();
+        let bar = world.spawn((UiNode::new()
+            .row()
+            .width(crate::ui::UiSize::Percent(1.0))
+            .justify(UiJustify::SpaceBetween)
+            .align(UiAlign::Stretch)
+            .anchor(UiAnchor::Bottom),));
+        let left = world.spawn_child(bar, (UiNode::new().size(50.0, 30.0),));
+        let right = world.spawn_child(bar, (UiNode::new().width(crate::ui::UiSize::Px(50.0)),));
+
+        layout_nodes(&mut world, Vec2::new(400.0, 300.0));
+        assert_eq!(rect(&world, bar), (0.0, 270.0, 400.0, 300.0));
+        assert_eq!(rect(&world, left), (0.0, 270.0, 50.0, 300.0));
+        // Stretched to the row's height.
+        assert_eq!(rect(&world, right), (350.0, 270.0, 400.0, 300.0));
+
+        world.insert(bar, Hidden);
+        propagate_visibility(&mut world);
+        layout_nodes(&mut world, Vec2::new(400.0, 300.0));
+        assert!(world.get::<ComputedNode>(bar).is_none());
+        assert!(world.get::<ComputedNode>(left).is_none());
+    }
+}
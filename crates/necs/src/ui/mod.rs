@@ -0,0 +1,423 @@
+//! # UI — Menus and HUDs in Screen Space
+//!
+//! Sprites live in the world: they move with the camera and are measured in
+//! world units. Menus and HUDs belong to the *screen* — a health bar stays in
+//! the top-left corner however far the player walks. A UI is a tree of
+//! [`UiNode`]s, built from ordinary entities and the usual parent/child
+//! hierarchy, laid out in pixels and drawn on top of everything else.
+//!
+//! ```text
+//!   screen (0,0) ────────────────────────────────────────► x
+//!   │  ┌ root: UiNode, anchor TopLeft, Row ───────────┐
+//!   │  │ ┌ UiImage ┐ gap ┌ UiText "HP 80" ┐          │
+//!   │  │ └─────────┘     └────────────────┘          │
+//!   │  └──────────────────────────────────────────────┘
+//!   │                ┌ root: anchor Center, Column ┐
+//!   │                │   ┌ Button ┐                │ padding
+//!   │                │   │ "Play" │                │
+//!   │                │   └────────┘ gap            │
+//!   │                │   ┌ Button ┐                │
+//!   │                │   │ "Quit" │                │
+//!   ▼ y              └─────────────────────────────┘
+//! ```
+//!
+//! ## Layout
+//!
+//! Each frame, after the update systems have run, every node gets a screen
+//! rectangle ([`ComputedNode`]):
+//!
+//! 1. **Measure** (bottom-up): a node's size is its [`UiSize`] when given,
+//!    otherwise whatever fits its content — its children laid out along its
+//!    [`UiLayout`] plus gaps, or its text or image — plus padding.
+//! 2. **Arrange** (top-down): root nodes are placed on the screen by their
+//!    [`UiAnchor`] and offset. A `Row` or `Column` places its children one
+//!    after another on the main axis ([`UiJustify`]) and aligns them on the
+//!    cross axis ([`UiAlign`]); a `Stack` places each child by the child's
+//!    own anchor, like a little screen.
+//!
+//! Margins are space around a node, padding is space inside it. Positions
+//! are in physical pixels with the origin at the top-left corner and Y
+//! pointing down, the same as [`CursorPosition`](crate::input::CursorPosition).
+//!
+//! ## Widgets
+//!
+//! - [`UiImage`] fills its node with a texture or a solid color — panels,
+//!   icons and button backgrounds.
+//! - [`UiText`] draws a string with a loaded font.
+//! - [`Button`] tracks hover and press from the mouse and reports clicks.
+//! - [`TextInput`] is an editable text field with selection, clipboard and
+//!   IME support; see [`text_input`].
+//!
+//! ```ignore
+//! let menu = ctx.world.spawn((UiNode::new().anchor(UiAnchor::Center).gap(8.0),));
+//! let play = ctx.world.spawn_child(menu, (
+//!     UiNode::new().size(160.0, 40.0).align(UiAlign::Center).justify(UiJustify::Center),
+//!     UiImage::solid(Color::rgb(0.2, 0.2, 0.3)),
+//!     Button::new().colors(ButtonColors::default()),
+//! ));
+//! ctx.world.spawn_child(play, (UiNode::new(), UiText::new("Play", font)));
+//!
+//! // later, in a system
+//! if ctx.world.get::<Button>(play).is_some_and(|b| b.clicked()) { start_game(ctx); }
+//! ```
+//!
+//! ## Drawing
+//!
+//! The UI gets its own render pass after the scene (and after
+//! [color grading](crate::render::ColorGrading), so menus aren't tinted),
+//! with an orthographic projection that maps pixels straight to the screen.
+//! Nodes are painted in tree order — parents before children, earlier
+//! siblings before later ones, roots by [`UiNode::z_index`] — and reuse the
+//! sprite pipeline, so consecutive quads sharing a texture share a draw call.
+//! Nodes that are [`Hidden`](crate::render::Hidden) or invisible are skipped
+//! with their subtree, and take no space in the layout.
+//!
+//! ## Comparison
+//!
+//! - **Unity** (uGUI): A `Canvas` of `RectTransform`s with anchors, plus
+//!   `HorizontalLayoutGroup` / `VerticalLayoutGroup` components; `Button`
+//!   with color-tint transitions. The closest match to this module.
+//! - **Bevy** (`bevy_ui`): `Node` entities laid out with full flexbox and
+//!   grid (taffy), `Interaction` component for hover/press.
+//! - **Godot**: `Control` nodes with anchors, `HBoxContainer` /
+//!   `VBoxContainer` and signal-emitting `Button`s.
+//! - **Our approach**: Unity's anchors and layout groups folded into one
+//!   node component — rows, columns and stacks, no flex grow or wrapping.
+
+mod button;
+pub(crate) mod draw;
+pub(crate) mod layout;
+pub mod text_input;
+
+pub use button::{Button, ButtonColors, Interaction, UiPointer};
+pub(crate) use button::update_buttons;
+pub(crate) use layout::layout_ui;
+pub use text_input::{TextFocus, TextInput, TextInputColors, TextSubmitted};
+pub(crate) use text_input::{update_text_inputs, FocusChange};
+
+use crate::math::{Rect, Vec2};
+use crate::render2d::font::FontHandle;
+use crate::render2d::texture::TextureHandle;
+use crate::render2d::Color;
+
+// ── Layout settings ─────────────────────────────────────────────────────
+
+/// A node's width or height.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UiSize {
+    /// Fit the content.
+    #[default]
+    Auto,
+    /// A fixed number of pixels.
+    Px(f32),
+    /// A fraction (0.0–1.0) of the parent's content area, or of the screen
+    /// for root nodes.
+    Percent(f32),
+}
+
+impl UiSize {
+    /// The size in pixels given the space available, or `None` for `Auto`.
+    fn resolve(self, available: f32) -> Option<f32> {
+        match self {
+            Self::Auto => None,
+            Self::Px(px) => Some(px.max(0.0)),
+            Self::Percent(fraction) => Some((fraction * available).max(0.0)),
+        }
+    }
+}
+
+/// How a node arranges its children.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiLayout {
+    /// Left to right.
+    Row,
+    /// Top to bottom.
+    #[default]
+    Column,
+    /// On top of each other, each placed by its own [`UiAnchor`].
+    Stack,
+}
+
+/// Where a node sits within its parent's content area (or the screen, for
+/// roots and children of a [`UiLayout::Stack`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiAnchor {
+    #[default]
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl UiAnchor {
+    /// The anchor point as a fraction of the area, `(0, 0)` top-left.
+    fn fraction(self) -> Vec2 {
+        let x = match self {
+            Self::TopLeft | Self::Left | Self::BottomLeft => 0.0,
+            Self::Top | Self::Center | Self::Bottom => 0.5,
+            Self::TopRight | Self::Right | Self::BottomRight => 1.0,
+        };
+        let y = match self {
+            Self::TopLeft | Self::Top | Self::TopRight => 0.0,
+            Self::Left | Self::Center | Self::Right => 0.5,
+            Self::BottomLeft | Self::Bottom | Self::BottomRight => 1.0,
+        };
+        Vec2::new(x, y)
+    }
+}
+
+/// Cross-axis placement of children in a row or column: vertical in a row,
+/// horizontal in a column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiAlign {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// Grow `Auto`-sized children to fill the cross axis.
+    Stretch,
+}
+
+/// Main-axis placement of children in a row or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UiJustify {
+    #[default]
+    Start,
+    Center,
+    End,
+    /// First child at the start, last at the end, the rest evenly between.
+    SpaceBetween,
+}
+
+/// Space on each side of a node, in pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UiEdges {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl UiEdges {
+    /// The same space on all four sides.
+    pub fn all(px: f32) -> Self {
+        Self::axes(px, px)
+    }
+
+    /// `horizontal` on the left and right, `vertical` on the top and bottom.
+    pub fn axes(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
+
+    /// Total horizontal and vertical space.
+    fn size(&self) -> Vec2 {
+        Vec2::new(self.left + self.right, self.top + self.bottom)
+    }
+
+    /// Top-left corner offset.
+    fn min(&self) -> Vec2 {
+        Vec2::new(self.left, self.top)
+    }
+}
+
+// ── Components ──────────────────────────────────────────────────────────
+
+/// Component: a rectangle in the UI tree. See the [module docs](self).
+///
+/// Children are ordinary hierarchy children
+/// ([`World::spawn_child`](crate::ecs::World::spawn_child)) that also have a
+/// `UiNode`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct UiNode {
+    pub width: UiSize,
+    pub height: UiSize,
+    pub layout: UiLayout,
+    /// Placement on the screen (roots) or in a `Stack` parent.
+    pub anchor: UiAnchor,
+    /// Pixels to shift the node from where layout placed it.
+    pub offset: Vec2,
+    pub margin: UiEdges,
+    pub padding: UiEdges,
+    /// Pixels between consecutive children in a row or column.
+    pub gap: f32,
+    pub align: UiAlign,
+    pub justify: UiJustify,
+    /// Paint order of root nodes; higher is drawn on top and gets the mouse
+    /// first. Ignored on children.
+    pub z_index: i32,
+}
+
+impl UiNode {
+    /// An auto-sized column at the top-left.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fixed size in pixels (builder pattern).
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = UiSize::Px(width);
+        self.height = UiSize::Px(height);
+        self
+    }
+
+    /// Width (builder pattern).
+    pub fn width(mut self, width: UiSize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Height (builder pattern).
+    pub fn height(mut self, height: UiSize) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// How children are arranged (builder pattern).
+    pub fn layout(mut self, layout: UiLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Lay children out left to right (builder pattern).
+    pub fn row(self) -> Self {
+        self.layout(UiLayout::Row)
+    }
+
+    /// Lay children out top to bottom (builder pattern).
+    pub fn column(self) -> Self {
+        self.layout(UiLayout::Column)
+    }
+
+    /// Placement on the screen or in a stack (builder pattern).
+    pub fn anchor(mut self, anchor: UiAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Shift from the laid-out position, in pixels (builder pattern).
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = Vec2::new(x, y);
+        self
+    }
+
+    /// Space around the node (builder pattern).
+    pub fn margin(mut self, margin: UiEdges) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Space inside the node, around its content (builder pattern).
+    pub fn padding(mut self, padding: UiEdges) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Space between children (builder pattern).
+    pub fn gap(mut self, gap: f32) -> Self {
+        self.gap = gap.max(0.0);
+        self
+    }
+
+    /// Cross-axis alignment of children (builder pattern).
+    pub fn align(mut self, align: UiAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Main-axis placement of children (builder pattern).
+    pub fn justify(mut self, justify: UiJustify) -> Self {
+        self.justify = justify;
+        self
+    }
+
+    /// Paint order among root nodes (builder pattern).
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+/// Component written by the layout pass: where a [`UiNode`] ended up.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComputedNode {
+    /// Screen rectangle in pixels, origin top-left, Y down.
+    pub rect: Rect,
+    /// Paint order across the whole UI; higher is drawn later.
+    pub order: u32,
+}
+
+impl ComputedNode {
+    /// Whether the screen point `(x, y)` lies inside the node.
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.cmpge(self.rect.min).all() && point.cmplt(self.rect.max).all()
+    }
+}
+
+/// Component: fill the node with a texture, tinted by `color`, or with a
+/// solid color when there is no texture. An `Auto`-sized node takes the
+/// texture's size.
+#[derive(Debug, Clone, Copy)]
+pub struct UiImage {
+    pub texture: Option<TextureHandle>,
+    pub color: Color,
+}
+
+impl UiImage {
+    /// Draw `texture` untinted.
+    pub fn new(texture: TextureHandle) -> Self {
+        Self {
+            texture: Some(texture),
+            color: Color::WHITE,
+        }
+    }
+
+    /// A solid rectangle of `color`.
+    pub fn solid(color: Color) -> Self {
+        Self {
+            texture: None,
+            color,
+        }
+    }
+
+    /// Set the tint (builder pattern).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// Component: a string drawn in the node's content area. An `Auto`-sized
+/// node takes the size of the text. `\n` starts a new line.
+#[derive(Debug, Clone)]
+pub struct UiText {
+    pub content: String,
+    pub font: FontHandle,
+    pub color: Color,
+}
+
+impl UiText {
+    /// White text in `font`.
+    pub fn new(content: &str, font: FontHandle) -> Self {
+        Self {
+            content: content.to_owned(),
+            font,
+            color: Color::WHITE,
+        }
+    }
+
+    /// Set the text color (builder pattern).
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
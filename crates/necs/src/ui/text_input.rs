@@ -0,0 +1,748 @@
+//! # Text Input — Editable Text Fields
+//!
+//! A [`TextInput`] turns a [`UiNode`](super::UiNode) into a single-line text
+//! field: player names, chat, server addresses, console commands. Click it
+//! (or set [`TextFocus`]) to give it the keyboard:
+//!
+//! ```text
+//!   ┌ UiImage background ────────────────────┐
+//!   │ Player░░░░░░░One▏漢̲字̲                  │   ░ selection
+//!   └────────────────────────────────────────┘   ▏ caret
+//!           └ selected ┘   └ IME composition, underlined
+//! ```
+//!
+//! | Input | Effect |
+//! |-------|--------|
+//! | Click / drag | place the caret / select |
+//! | ← → (Ctrl: by word, Shift: select) | move the caret |
+//! | Home / End | start / end of the text |
+//! | Backspace / Delete (Ctrl: by word) | delete |
+//! | Ctrl+A / C / X / V | select all, copy, cut, paste ([`Clipboard`]) |
+//! | Enter | submit: [`TextInput::submitted`] and a [`TextSubmitted`] event |
+//! | Escape, or a click elsewhere | give up focus |
+//!
+//! Typed characters come from the keyboard layout and the IME, not from key
+//! codes (see [text input](crate::input#text)), so `@`, `é` and `漢字` all
+//! type correctly. While a field has focus the window accepts IME input and
+//! the [keyboard hooks](crate::hooks#on-screen-keyboards) run for on-screen
+//! keyboards. Key presses still reach update systems; check
+//! [`TextFocus::is_typing`] before treating WASD as movement.
+//!
+//! The entity's [`UiText`] supplies the font: the field rewrites its content
+//! each frame with the text, the composition, or the placeholder. Glyphs the
+//! font lacks are kept in the text but not drawn, and text wider than the
+//! node overflows it — size the node for the expected length, or cap it
+//! with [`TextInput::max_chars`].
+//!
+//! ```ignore
+//! let name = ctx.world.spawn((
+//!     UiNode::new().size(240.0, 32.0).padding(UiEdges::all(6.0)),
+//!     UiImage::solid(Color::rgb(0.1, 0.1, 0.12)),
+//!     UiText::new("", font),
+//!     TextInput::new().placeholder("Your name").max_chars(16),
+//! ));
+//!
+//! // later, in a system
+//! if let Some(input) = ctx.world.get::<TextInput>(name) && input.submitted() {
+//!     join_lobby(input.text());
+//! }
+//! ```
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `TMP_InputField` with `onSubmit` / `onValueChanged`;
+//!   `TouchScreenKeyboard.Open` for mobile keyboards.
+//! - **Bevy**: No built-in text field; `ReceivedCharacter` / `Ime` events
+//!   for games that write one.
+//! - **Godot**: `LineEdit` with `text_submitted`, and
+//!   `DisplayServer.virtual_keyboard_show`.
+//! - **Our approach**: A `LineEdit`-style component over the existing UI
+//!   nodes, with a per-frame flag and an event for submission.
+
+use std::ops::Range;
+use std::time::Instant;
+
+use crate::clipboard::Clipboard;
+use crate::context::InputState;
+use crate::ecs::hierarchy::Parent;
+use crate::ecs::{Entity, Events, World};
+use crate::input::{CursorPosition, InputEvent, KeyCode, MouseButton, TextEvent};
+use crate::math::Rect;
+use crate::render::pipelined::finish_render;
+use crate::render2d::font::{FontEntry, FontStore};
+use crate::render2d::Color;
+
+use super::{ComputedNode, UiNode, UiPointer, UiText};
+
+/// Seconds the caret stays on, then off.
+const BLINK: f32 = 0.53;
+
+/// Colors a [`TextInput`] draws with. The font comes from its [`UiText`].
+#[derive(Debug, Clone, Copy)]
+pub struct TextInputColors {
+    pub text: Color,
+    pub placeholder: Color,
+    pub caret: Color,
+    pub selection: Color,
+}
+
+impl Default for TextInputColors {
+    fn default() -> Self {
+        Self {
+            text: Color::WHITE,
+            placeholder: Color::rgba(1.0, 1.0, 1.0, 0.4),
+            caret: Color::WHITE,
+            selection: Color::rgba(0.3, 0.5, 0.9, 0.6),
+        }
+    }
+}
+
+/// Component: make a [`UiNode`](super::UiNode) with a [`UiText`] an
+/// editable single-line text field. See the [module docs](self).
+///
+/// Positions (the caret, the selection) are byte offsets into
+/// [`text`](Self::text), always on character boundaries.
+#[derive(Debug, Clone, Default)]
+pub struct TextInput {
+    text: String,
+    /// Shown, dimmed, while the text is empty.
+    pub placeholder: String,
+    /// The most characters the text may hold.
+    pub max_chars: Option<usize>,
+    pub colors: TextInputColors,
+    cursor: usize,
+    /// The other end of the selection; equal to `cursor` when none.
+    anchor: usize,
+    /// IME composition in progress, shown at the caret.
+    preedit: String,
+    preedit_cursor: Option<usize>,
+    /// Seconds since the caret last moved, for blinking.
+    blink: f32,
+    dragging: bool,
+    changed: bool,
+    submitted: bool,
+}
+
+/// Event: the player pressed Enter in a [`TextInput`]. Registered by
+/// [`Game`](crate::game::Game).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSubmitted {
+    pub entity: Entity,
+    pub text: String,
+}
+
+/// What a key did, beyond editing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyOutcome {
+    Edited,
+    Submit,
+    Blur,
+    Ignored,
+}
+
+/// Modifier keys held while a key was pressed.
+#[derive(Debug, Clone, Copy, Default)]
+struct Modifiers {
+    /// Ctrl, or Cmd on macOS.
+    command: bool,
+    shift: bool,
+    alt: bool,
+}
+
+impl TextInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start with `text`, caret at the end (builder pattern).
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.set_text(text);
+        self
+    }
+
+    /// Show `placeholder` while the field is empty (builder pattern).
+    pub fn placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_owned();
+        self
+    }
+
+    /// Accept at most `max` characters (builder pattern).
+    pub fn max_chars(mut self, max: usize) -> Self {
+        self.max_chars = Some(max);
+        self
+    }
+
+    /// Set the colors (builder pattern).
+    pub fn colors(mut self, colors: TextInputColors) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// The current text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replace the text and put the caret at the end. Doesn't count as a
+    /// [`changed`](Self::changed) edit.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.chars().filter(|ch| !ch.is_control()).collect();
+        if let Some(max) = self.max_chars
+            && let Some((end, _)) = self.text.char_indices().nth(max)
+        {
+            self.text.truncate(end);
+        }
+        self.cursor = self.text.len();
+        self.anchor = self.cursor;
+    }
+
+    /// The caret position.
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The selected range, start before end; empty when nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// The selected text.
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selection()]
+    }
+
+    /// Select from `start` to `end` (the caret), clamped to the text and
+    /// snapped back to character boundaries.
+    pub fn select(&mut self, start: usize, end: usize) {
+        self.anchor = self.boundary(start);
+        self.cursor = self.boundary(end);
+        self.blink = 0.0;
+    }
+
+    /// Select the whole text.
+    pub fn select_all(&mut self) {
+        self.select(0, self.text.len());
+    }
+
+    /// The IME composition in progress, if the player is composing.
+    pub fn preedit(&self) -> &str {
+        &self.preedit
+    }
+
+    /// Returns `true` on a frame the player edited the text.
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Returns `true` on the frame the player pressed Enter.
+    pub fn submitted(&self) -> bool {
+        self.submitted
+    }
+
+    // ── Editing ─────────────────────────────────────────────────────────
+
+    /// Replace the selection with `text`, dropping control characters and
+    /// whatever doesn't fit `max_chars`.
+    fn insert(&mut self, text: &str) {
+        let selection = self.selection();
+        let room = self.max_chars.map_or(usize::MAX, |max| {
+            let kept = self.text.chars().count() - self.text[selection.clone()].chars().count();
+            max.saturating_sub(kept)
+        });
+        let text: String = text.chars().filter(|ch| !ch.is_control()).take(room).collect();
+        if text.is_empty() && selection.is_empty() {
+            return;
+        }
+        self.text.replace_range(selection.clone(), &text);
+        self.cursor = selection.start + text.len();
+        self.anchor = self.cursor;
+        self.changed = true;
+        self.blink = 0.0;
+    }
+
+    /// Delete the selection, or from the caret to `to` if nothing is
+    /// selected.
+    fn delete_to(&mut self, to: usize) {
+        if self.cursor == self.anchor {
+            self.anchor = to;
+        }
+        self.insert("");
+    }
+
+    /// Move the caret to `to`, extending the selection if `extend`.
+    fn move_to(&mut self, to: usize, extend: bool) {
+        self.cursor = to;
+        if !extend {
+            self.anchor = to;
+        }
+        self.blink = 0.0;
+    }
+
+    /// Clamp `at` to the text and back to a character boundary.
+    fn boundary(&self, at: usize) -> usize {
+        let mut at = at.min(self.text.len());
+        while !self.text.is_char_boundary(at) {
+            at -= 1;
+        }
+        at
+    }
+
+    /// The position one character (or word) left of the caret.
+    fn left_of(&self, at: usize, word: bool) -> usize {
+        let before = &self.text[..at];
+        if !word {
+            return before.char_indices().next_back().map_or(0, |(i, _)| i);
+        }
+        let trimmed = before.trim_end();
+        trimmed
+            .char_indices()
+            .rev()
+            .find(|(_, ch)| ch.is_whitespace())
+            .map_or(0, |(i, ch)| i + ch.len_utf8())
+    }
+
+    /// The position one character (or word) right of the caret.
+    fn right_of(&self, at: usize, word: bool) -> usize {
+        let after = &self.text[at..];
+        if !word {
+            return at + after.chars().next().map_or(0, char::len_utf8);
+        }
+        let skipped = after.len() - after.trim_start().len();
+        at + skipped
+            + after[skipped..]
+                .find(char::is_whitespace)
+                .unwrap_or(after.len() - skipped)
+    }
+
+    /// Apply a key press.
+    fn key(&mut self, key: KeyCode, mods: Modifiers, clipboard: &mut Clipboard) -> KeyOutcome {
+        let (word, extend) = (mods.command, mods.shift);
+        match key {
+            KeyCode::ArrowLeft if !extend && self.cursor != self.anchor => {
+                self.move_to(self.selection().start, false)
+            }
+            KeyCode::ArrowRight if !extend && self.cursor != self.anchor => {
+                self.move_to(self.selection().end, false)
+            }
+            KeyCode::ArrowLeft => self.move_to(self.left_of(self.cursor, word), extend),
+            KeyCode::ArrowRight => self.move_to(self.right_of(self.cursor, word), extend),
+            KeyCode::Home | KeyCode::ArrowUp => self.move_to(0, extend),
+            KeyCode::End | KeyCode::ArrowDown => self.move_to(self.text.len(), extend),
+            KeyCode::Backspace => self.delete_to(self.left_of(self.cursor, word)),
+            KeyCode::Delete => self.delete_to(self.right_of(self.cursor, word)),
+            KeyCode::Enter | KeyCode::NumpadEnter => return KeyOutcome::Submit,
+            KeyCode::Escape => return KeyOutcome::Blur,
+            KeyCode::KeyA if mods.command => self.select_all(),
+            KeyCode::KeyC if mods.command => {
+                if self.cursor != self.anchor {
+                    clipboard.set(self.selected_text());
+                }
+            }
+            KeyCode::KeyX if mods.command => {
+                if self.cursor != self.anchor {
+                    clipboard.set(self.selected_text());
+                    self.insert("");
+                }
+            }
+            KeyCode::KeyV if mods.command => self.insert(&clipboard.get()),
+            _ => return KeyOutcome::Ignored,
+        }
+        KeyOutcome::Edited
+    }
+
+    /// Apply typed text or an IME composition update.
+    fn text_event(&mut self, event: &TextEvent, mods: Modifiers) {
+        match event {
+            // Ctrl+letter shortcuts can arrive as text too; AltGr (reported
+            // as Ctrl+Alt on Windows) types real characters.
+            TextEvent::Commit(_) if mods.command && !mods.alt => {}
+            TextEvent::Commit(text) => self.insert(text),
+            TextEvent::Preedit { text, cursor } => {
+                self.preedit = text.clone();
+                self.preedit_cursor = cursor.map(|(start, _)| start);
+                self.blink = 0.0;
+            }
+        }
+    }
+
+    // ── Display ─────────────────────────────────────────────────────────
+
+    /// The string to draw: the text with the composition at the caret, or
+    /// the placeholder when both are empty.
+    fn display(&self) -> (String, bool) {
+        if self.text.is_empty() && self.preedit.is_empty() {
+            return (self.placeholder.clone(), true);
+        }
+        let mut display = self.text.clone();
+        display.insert_str(self.cursor, &self.preedit);
+        (display, false)
+    }
+
+    /// Byte ranges within the [`display`](Self::display) string to draw
+    /// for a focused field: the selection, the caret, and the composition.
+    pub(crate) fn spans(&self) -> TextSpans {
+        let composing = !self.preedit.is_empty();
+        let selection = self.selection();
+        TextSpans {
+            selection: (!composing && !selection.is_empty()).then_some(selection),
+            caret: self.cursor + self.preedit_cursor.unwrap_or(self.preedit.len()),
+            caret_visible: self.blink % (2.0 * BLINK) < BLINK,
+            preedit: composing.then(|| self.cursor..self.cursor + self.preedit.len()),
+        }
+    }
+
+    /// The text position nearest `x` pixels from the start of the text.
+    fn position_at(&self, font: &FontEntry, x: f32) -> usize {
+        let mut left = 0.0;
+        for (i, ch) in self.text.char_indices() {
+            let advance = font.glyph(ch).map_or(0.0, |glyph| glyph.advance);
+            if x < left + advance / 2.0 {
+                return i;
+            }
+            left += advance;
+        }
+        self.text.len()
+    }
+}
+
+/// Parts of a focused [`TextInput`] drawn around its text.
+pub(crate) struct TextSpans {
+    pub selection: Option<Range<usize>>,
+    pub caret: usize,
+    pub caret_visible: bool,
+    pub preedit: Option<Range<usize>>,
+}
+
+// ── Focus ────────────────────────────────────────────────────────────────
+
+/// Resource: the [`TextInput`] that has the keyboard, if any. Inserted by
+/// the framework.
+///
+/// ```ignore
+/// let typing = ctx.world.get_resource::<TextFocus>().is_some_and(|f| f.is_typing());
+/// if !typing && ctx.input.pressed(KeyCode::KeyW) { walk_forward(ctx); }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextFocus {
+    entity: Option<Entity>,
+    /// The focused field's screen rectangle from the last layout, e.g. to
+    /// scroll it above an on-screen keyboard.
+    pub rect: Option<Rect>,
+    /// The focus at the end of the last update, to detect changes.
+    last: Option<Entity>,
+    /// A field consumed keys this frame (Escape blurs and still counts).
+    typed: bool,
+}
+
+impl TextFocus {
+    /// The focused field.
+    pub fn focused(&self) -> Option<Entity> {
+        self.entity
+    }
+
+    /// Give `entity`'s [`TextInput`] the keyboard, from next frame.
+    pub fn focus(&mut self, entity: Entity) {
+        self.entity = Some(entity);
+    }
+
+    /// Take the keyboard away from whichever field has it.
+    pub fn clear(&mut self) {
+        self.entity = None;
+    }
+
+    /// Returns `true` while the player is typing into a field: keys this
+    /// frame belong to it rather than to gameplay.
+    pub fn is_typing(&self) -> bool {
+        self.entity.is_some() || self.typed
+    }
+}
+
+/// How [`TextFocus`] changed in an update, for IME and on-screen keyboards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FocusChange {
+    Gained,
+    Lost,
+}
+
+// ── Update ───────────────────────────────────────────────────────────────
+
+/// Update every [`TextInput`] from this frame's input, using last frame's
+/// layout, and rewrite their [`UiText`]. Run after
+/// [`update_buttons`](super::update_buttons), which finds the hovered node.
+pub(crate) fn update_text_inputs(
+    world: &mut World,
+    input: &InputState,
+    cursor: CursorPosition,
+    dt: f32,
+) -> Option<FocusChange> {
+    let mut focus = world.resource_remove::<TextFocus>().unwrap_or_default();
+    let mut clipboard = world.resource_remove::<Clipboard>().unwrap_or_else(Clipboard::local);
+
+    world.query::<(&mut TextInput,)>(|_, (text_input,)| {
+        text_input.changed = false;
+        text_input.submitted = false;
+        text_input.blink += dt;
+    });
+    if focus.entity.is_some_and(|entity| world.get::<TextInput>(entity).is_none()) {
+        focus.entity = None;
+    }
+
+    // Clicks focus the field under the cursor (or its nearest field
+    // ancestor) and blur it anywhere else.
+    let mouse = &input.mouse;
+    if mouse.just_pressed(MouseButton::Left) {
+        let mut target = world.get_resource::<UiPointer>().and_then(|pointer| pointer.hovered);
+        while let Some(entity) = target
+            && world.get::<TextInput>(entity).is_none()
+        {
+            target = world.get::<Parent>(entity).map(|p| p.0);
+        }
+        focus.entity = target;
+    }
+    let mods = Modifiers {
+        command: [KeyCode::ControlLeft, KeyCode::ControlRight, KeyCode::SuperLeft, KeyCode::SuperRight]
+            .into_iter()
+            .any(|key| input.pressed(key)),
+        shift: input.pressed(KeyCode::ShiftLeft) || input.pressed(KeyCode::ShiftRight),
+        alt: input.pressed(KeyCode::AltLeft) || input.pressed(KeyCode::AltRight),
+    };
+
+    // Switching fields drops the old one's composition.
+    if focus.last != focus.entity
+        && let Some(previous) = focus.last
+        && let Some(text_input) = world.get_mut::<TextInput>(previous)
+    {
+        text_input.preedit.clear();
+        text_input.dragging = false;
+    }
+
+    // Caret placement needs the fonts, which the render thread may hold
+    // for the frame in flight.
+    if focus.entity.is_some() {
+        finish_render(world);
+    }
+    let fonts = world.resource_remove::<FontStore>();
+
+    focus.typed = false;
+    if let Some(entity) = focus.entity {
+        let x = text_origin(world, entity).map(|origin| cursor.x - origin);
+        let font = fonts
+            .as_ref()
+            .zip(world.get::<UiText>(entity))
+            .map(|(fonts, text)| fonts.get(text.font));
+        let clicked_again = focus.last == Some(entity);
+        let text_input = world.get_mut::<TextInput>(entity).expect("focused TextInput missing");
+
+        // A click here just focused this field; dragging selects.
+        if let (Some(font), Some(x)) = (font, x) {
+            if mouse.just_pressed(MouseButton::Left) {
+                let at = text_input.position_at(font, x);
+                text_input.move_to(at, mods.shift && clicked_again);
+                text_input.dragging = true;
+            } else if text_input.dragging && mouse.pressed(MouseButton::Left) {
+                let at = text_input.position_at(font, x);
+                text_input.move_to(at, true);
+            }
+        }
+        if !mouse.pressed(MouseButton::Left) {
+            text_input.dragging = false;
+        }
+
+        // Keys and typed text, interleaved in the order they arrived.
+        let mut steps: Vec<(Instant, Step)> = input
+            .events()
+            .iter()
+            .filter_map(|timed| match timed.event {
+                InputEvent::KeyPressed(key) => Some((timed.at, Step::Key(key))),
+                _ => None,
+            })
+            .chain(input.text_events().iter().map(|timed| (timed.at, Step::Text(&timed.event))))
+            .collect();
+        steps.sort_by_key(|(at, _)| *at);
+        let mut blurred = false;
+        for (_, step) in steps {
+            focus.typed = true;
+            match step {
+                Step::Key(key) => match text_input.key(key, mods, &mut clipboard) {
+                    KeyOutcome::Submit => text_input.submitted = true,
+                    KeyOutcome::Blur => {
+                        blurred = true;
+                        break;
+                    }
+                    KeyOutcome::Edited | KeyOutcome::Ignored => {}
+                },
+                Step::Text(event) => text_input.text_event(event, mods),
+            }
+        }
+        let submitted = text_input.submitted.then(|| text_input.text.clone());
+        if blurred {
+            text_input.preedit.clear();
+            focus.entity = None;
+        }
+        if let Some(text) = submitted
+            && let Some(events) = world.get_resource_mut::<Events<TextSubmitted>>()
+        {
+            events.send(TextSubmitted { entity, text });
+        }
+    }
+
+    // Show the text, composition or placeholder through the UiText.
+    let mut displays = Vec::new();
+    world.query::<(&TextInput,)>(|entity, (text_input,)| {
+        let (content, placeholder) = text_input.display();
+        let colors = text_input.colors;
+        displays.push((entity, content, if placeholder { colors.placeholder } else { colors.text }));
+    });
+    for (entity, content, color) in displays {
+        if let Some(text) = world.get_mut::<UiText>(entity) {
+            if text.content != content {
+                text.content = content;
+            }
+            text.color = color;
+        }
+    }
+
+    let change = match (focus.last.is_some(), focus.entity.is_some()) {
+        (false, true) => Some(FocusChange::Gained),
+        (true, false) => Some(FocusChange::Lost),
+        _ => None,
+    };
+    focus.last = focus.entity;
+    focus.rect = focus.entity.and_then(|entity| world.get::<ComputedNode>(entity)).map(|node| node.rect);
+    world.insert_resource(focus);
+    world.insert_resource(clipboard);
+    if let Some(fonts) = fonts {
+        world.insert_resource(fonts);
+    }
+    change
+}
+
+/// A key press or typed text, to apply in arrival order.
+enum Step<'a> {
+    Key(KeyCode),
+    Text(&'a TextEvent),
+}
+
+/// Where `entity`'s text starts on screen, as drawn: after its padding,
+/// on a whole pixel.
+fn text_origin(world: &World, entity: Entity) -> Option<f32> {
+    let rect = world.get::<ComputedNode>(entity)?.rect;
+    let padding = world.get::<UiNode>(entity).map(|node| node.padding).unwrap_or_default();
+    Some((rect.min + padding.min()).x.round())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ecs::EventReader;
+    use crate::input::{TimedInput, TimedText};
+    use crate::math::Vec2;
+
+    const NONE: Modifiers = Modifiers {
+        command: false,
+        shift: false,
+        alt: false,
+    };
+    const CTRL: Modifiers = Modifiers {
+        command: true,
+        shift: false,
+        alt: false,
+    };
+
+    #[test]
+    fn editing_moves_by_characters_and_words() {
+        let mut clipboard = Clipboard::local();
+        let mut field = TextInput::new().with_text("héllo wörld");
+        field.key(KeyCode::ArrowLeft, CTRL, &mut clipboard);
+        assert_eq!(&field.text()[field.cursor()..], "wörld");
+        field.key(KeyCode::Backspace, CTRL, &mut clipboard);
+        assert_eq!(field.text(), "wörld");
+
+        field.key(KeyCode::ArrowRight, NONE, &mut clipboard);
+        field.key(KeyCode::ArrowRight, NONE, &mut clipboard);
+        field.key(KeyCode::Backspace, NONE, &mut clipboard);
+        assert_eq!(field.text(), "wrld");
+        field.insert("ö");
+        assert_eq!(field.text(), "wörld");
+        assert!(field.changed());
+    }
+
+    #[test]
+    fn clipboard_and_limits() {
+        let mut clipboard = Clipboard::local();
+        let mut field = TextInput::new().with_text("copy me").max_chars(10);
+        field.key(KeyCode::KeyA, CTRL, &mut clipboard);
+        field.key(KeyCode::KeyX, CTRL, &mut clipboard);
+        assert_eq!((field.text(), clipboard.get().as_str()), ("", "copy me"));
+
+        field.key(KeyCode::KeyV, CTRL, &mut clipboard);
+        field.key(KeyCode::KeyV, CTRL, &mut clipboard);
+        assert_eq!(field.text(), "copy mecop");
+
+        // Typing replaces a Shift selection, up to the limit.
+        field.key(KeyCode::Home, NONE, &mut clipboard);
+        let shift = Modifiers { shift: true, ..NONE };
+        field.key(KeyCode::ArrowRight, Modifiers { command: true, ..shift }, &mut clipboard);
+        assert_eq!(field.selected_text(), "copy");
+        field.text_event(&TextEvent::Commit("\tpaste\n".into()), NONE);
+        assert_eq!(field.text(), "past mecop");
+    }
+
+    #[test]
+    fn composition_is_shown_at_the_caret() {
+        let mut field = TextInput::new().with_text("ab");
+        field.key(KeyCode::ArrowLeft, NONE, &mut Clipboard::local());
+        field.text_event(&TextEvent::Preedit { text: "漢字".into(), cursor: Some((3, 3)) }, NONE);
+        assert_eq!(field.display().0, "a漢字b");
+        let spans = field.spans();
+        assert_eq!((spans.caret, spans.preedit), (4, Some(1..7)));
+
+        field.text_event(&TextEvent::Preedit { text: String::new(), cursor: None }, NONE);
+        field.text_event(&TextEvent::Commit("漢字".into()), NONE);
+        assert_eq!((field.text(), field.cursor()), ("a漢字b", 7));
+    }
+
+    #[test]
+    fn click_focuses_typing_edits_and_enter_submits() {
+        let mut world = World::new();
+        world.insert_resource(Clipboard::local());
+        world.insert_resource(Events::<TextSubmitted>::default());
+        let field = world.spawn((
+            ComputedNode {
+                rect: Rect {
+                    min: Vec2::ZERO,
+                    max: Vec2::new(200.0, 30.0),
+                },
+                order: 0,
+            },
+            TextInput::new().placeholder("Name"),
+        ));
+        world.insert_resource(UiPointer { hovered: Some(field) });
+
+        let mut input = InputState::new();
+        input.mouse.press(MouseButton::Left);
+        let cursor = CursorPosition { x: 10.0, y: 10.0 };
+        let change = update_text_inputs(&mut world, &input, cursor, 0.016);
+        assert_eq!(change, Some(FocusChange::Gained));
+        assert_eq!(world.resource::<TextFocus>().focused(), Some(field));
+
+        input.clear_just();
+        let at = Instant::now();
+        input.text.push(TimedText { event: TextEvent::Commit("Ann".into()), at });
+        input.events.push(TimedInput { event: InputEvent::KeyPressed(KeyCode::Enter), at });
+        assert_eq!(update_text_inputs(&mut world, &input, cursor, 0.016), None);
+        let state = world.get::<TextInput>(field).unwrap();
+        assert!(state.changed() && state.submitted());
+        assert_eq!(state.text(), "Ann");
+        let mut reader = EventReader::<TextSubmitted>::default();
+        let submitted: Vec<_> = reader.read(world.resource()).cloned().collect();
+        assert_eq!(submitted, vec![TextSubmitted { entity: field, text: "Ann".into() }]);
+
+        // Escape gives focus up but still counts as typing this frame.
+        input.clear_just();
+        input.events.push(TimedInput { event: InputEvent::KeyPressed(KeyCode::Escape), at: Instant::now() });
+        assert_eq!(update_text_inputs(&mut world, &input, cursor, 0.016), Some(FocusChange::Lost));
+        let focus = world.resource::<TextFocus>();
+        assert!(focus.focused().is_none() && focus.is_typing());
+    }
+}
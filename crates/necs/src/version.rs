@@ -0,0 +1,178 @@
+//! # Build Info — Which Engine Build Is This?
+//!
+//! A bug report that says "the sprites flicker" is only useful next to the
+//! build that flickered: which engine version, which Cargo features, which
+//! commit, and which graphics backend. [`build_info`] collects all of it in
+//! one serializable [`BuildInfo`]:
+//!
+//! ```text
+//!   compile time                      run time
+//!   ────────────                      ────────
+//!   CARGO_PKG_VERSION  ─► version     GpuContext::new ─► backend, adapter
+//!   cfg!(feature = ..) ─► features      (once the window opens)
+//!   build.rs git hash  ─► git_hash
+//!   debug_assertions   ─► profile
+//!
+//!   necs 0.1.0 (3f9c2ab1e, debug) [render2d, render3d, diagnostics] on Vulkan (RTX 3060)
+//! ```
+//!
+//! The same line shows up wherever a build needs identifying:
+//!
+//! - logged when [`Game::run`](crate::game::Game::run) starts,
+//! - printed after every panic message (see [`install_panic_hook`]),
+//! - sent to `necs-telemetry` when it connects (`diagnostics` feature),
+//! - in the editor's About window, with a button to copy it.
+//!
+//! ```ignore
+//! let info = necs::build_info();
+//! if !info.has_feature("physics2d") {
+//!     log::warn!("this level needs physics2d");
+//! }
+//! std::fs::write("build.json", serde_json::to_string_pretty(&info)?)?;
+//! ```
+//!
+//! The git hash comes from a build script running `git rev-parse`, so it is
+//! `None` when the engine is built outside a git checkout (e.g. from
+//! crates.io).
+//!
+//! ## Comparison
+//!
+//! - **Unity**: `Application.unityVersion` and `SystemInfo.graphicsDeviceType`;
+//!   crash reports attach both.
+//! - **Bevy**: No build-info API; `bevy::diagnostic::SystemInfo` logs the OS
+//!   and CPU, and the wgpu adapter is logged at startup.
+//! - **Godot**: `Engine.get_version_info()` returns version and commit hash
+//!   as a dictionary; `OS.has_feature` answers feature tags.
+//! - **Our approach**: Godot's version info plus the enabled Cargo features
+//!   and the GPU backend, as one serde struct.
+
+use std::sync::{Once, OnceLock};
+
+use serde::{Deserialize, Serialize};
+
+/// Every optional Cargo feature of the engine, and whether this build has
+/// it. `default` and `full` are sets of these and aren't listed.
+const FEATURES: &[(&str, bool)] = &[
+    ("render2d", cfg!(feature = "render2d")),
+    ("render3d", cfg!(feature = "render3d")),
+    ("diagnostics", cfg!(feature = "diagnostics")),
+    ("clipboard", cfg!(feature = "clipboard")),
+    ("audio", cfg!(feature = "audio")),
+    ("physics2d", cfg!(feature = "physics2d")),
+    ("physics3d", cfg!(feature = "physics3d")),
+    ("editor", cfg!(feature = "editor")),
+    ("renderdoc", cfg!(feature = "renderdoc")),
+    ("rayon", cfg!(feature = "rayon")),
+    ("gamepad", cfg!(feature = "gamepad")),
+];
+
+/// Backend and adapter name of the GPU in use, set when the window opens.
+static ADAPTER: OnceLock<(String, String)> = OnceLock::new();
+
+/// The engine build and the GPU it runs on. See the [module docs](self).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    /// Engine crate version, e.g. `"0.1.0"`.
+    pub version: String,
+    /// Cargo features compiled in, in `Cargo.toml` order.
+    pub features: Vec<String>,
+    /// Short hash of the commit the engine was built from; `None` outside a
+    /// git checkout.
+    pub git_hash: Option<String>,
+    /// `"debug"` or `"release"` (whether debug assertions are on).
+    pub profile: String,
+    /// wgpu backend in use (`"Vulkan"`, `"Metal"`, `"Dx12"`, ...); `None`
+    /// until the window opens, and in headless runs.
+    pub backend: Option<String>,
+    /// Name of the GPU adapter in use; `None` like `backend`.
+    pub adapter: Option<String>,
+}
+
+impl BuildInfo {
+    /// Whether the engine was compiled with Cargo feature `name`.
+    pub fn has_feature(&self, name: &str) -> bool {
+        self.features.iter().any(|feature| feature == name)
+    }
+}
+
+impl std::fmt::Display for BuildInfo {
+    /// One line: `necs 0.1.0 (3f9c2ab1e, debug) [render2d, audio] on Vulkan (RTX 3060)`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "necs {} (", self.version)?;
+        if let Some(hash) = &self.git_hash {
+            write!(f, "{hash}, ")?;
+        }
+        write!(f, "{}) [{}]", self.profile, self.features.join(", "))?;
+        if let Some(backend) = &self.backend {
+            write!(f, " on {backend}")?;
+            if let Some(adapter) = &self.adapter {
+                write!(f, " ({adapter})")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The running engine's version, features, git hash and GPU backend.
+pub fn build_info() -> BuildInfo {
+    let adapter = ADAPTER.get();
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        features: FEATURES
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name.to_string())
+            .collect(),
+        git_hash: option_env!("NECS_GIT_HASH").map(str::to_string),
+        profile: if cfg!(debug_assertions) { "debug" } else { "release" }.to_string(),
+        backend: adapter.map(|(backend, _)| backend.clone()),
+        adapter: adapter.map(|(_, name)| name.clone()),
+    }
+}
+
+/// Record the GPU the window renders with. Only the first call counts.
+pub(crate) fn record_adapter(info: &wgpu::AdapterInfo) {
+    let _ = ADAPTER.set((format!("{:?}", info.backend), info.name.clone()));
+}
+
+/// Print the [`build_info`] line to stderr after every panic message, so
+/// crash output pasted into a bug report names the build. The previous hook
+/// still runs first. [`Game::run`](crate::game::Game::run) installs it;
+/// later calls do nothing.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |panic| {
+            previous(panic);
+            eprintln!("build: {}", build_info());
+        }));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn features_match_the_manifest() {
+        // Every feature in Cargo.toml except the `default` and `full` sets.
+        let manifest = include_str!("../Cargo.toml");
+        let section = manifest.split("[features]").nth(1).unwrap();
+        let declared: Vec<&str> = section
+            .lines()
+            .take_while(|line| !line.starts_with('['))
+            .filter_map(|line| line.split_once(" = ").map(|(name, _)| name))
+            .filter(|name| !matches!(*name, "default" | "full"))
+            .collect();
+        let listed: Vec<&str> = FEATURES.iter().map(|(name, _)| *name).collect();
+        assert_eq!(listed, declared);
+
+        let info = build_info();
+        assert_eq!(info.has_feature("render2d"), cfg!(feature = "render2d"));
+        let line = info.to_string();
+        assert!(line.starts_with(&format!("necs {} (", env!("CARGO_PKG_VERSION"))));
+        let json = serde_json::to_string(&info).unwrap();
+        assert_eq!(serde_json::from_str::<BuildInfo>(&json).unwrap(), info);
+    }
+}
@@ -3,29 +3,73 @@
 //! Implements [`winit::application::ApplicationHandler`] to drive the event
 //! loop. This handles window creation, input forwarding, resize, and the
 //! main game loop (systems + rendering each frame).
+//!
+//! With [`LaunchOptions::headless`](crate::launch::LaunchOptions::headless)
+//! the same frame logic runs from [`WinitApp::run_headless`] instead, without
+//! an event loop or window.
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use winit::application::ApplicationHandler;
-use winit::event::{ElementState, WindowEvent};
+use winit::event::{DeviceEvent, DeviceId, ElementState, Ime, MouseScrollDelta, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
 use winit::keyboard::PhysicalKey;
-use winit::window::{Window, WindowId};
+use winit::window::{Fullscreen, Window, WindowId};
 
-use crate::asset::process_asset_reloads;
+use crate::asset::{process_asset_reloads, process_async_loads};
 use crate::context::Context;
+use crate::cursor::{CursorGrab, CursorOptions};
+use crate::ecs::system::Schedule;
+use crate::hooks::{Hook, Hooks};
+use crate::input::{CursorPosition, InputEvent, InputLatency, InputQueue, ScrollDelta, TextEvent};
+use crate::launch::LaunchOptions;
+use crate::render::capture::{CaptureBackend, FrameCapture};
+use crate::render::readback::process_readbacks;
+use crate::render::shader_diff::ShaderDiff;
 use crate::ecs::hierarchy::propagate_transforms;
 use crate::ecs::world::World;
+use crate::lifecycle::{LifecycleEvent, ShutdownReason, ShutdownRequested, WindowLifecycle};
+#[cfg(any(feature = "render2d", feature = "render3d", feature = "audio"))]
+use crate::subsystems::Subsystems;
+use crate::touch::TouchPhase;
+use crate::render::adapter::{AdapterInfo, AdapterSelection};
 use crate::render::gpu::GpuContext;
+use crate::render::extract::prepare_frame;
+use crate::render::pipelined::{can_pipeline, finish_render, submit_render, take_render_error};
 use crate::render::pass::{render_frame, FrameContext};
+use crate::render::visibility::propagate_visibility;
+use crate::scene_builder::SceneManager;
+
+/// Frame pacing for headless runs, which have no vsync to wait on (60 Hz).
+const HEADLESS_FRAME_TIME: Duration = Duration::from_micros(16_667);
 
 /// The application state that winit drives.
 pub(crate) struct WinitApp {
     ctx: Context,
     startup_systems: Vec<Box<dyn FnMut(&mut Context)>>,
-    systems: Vec<Box<dyn FnMut(&mut Context)>>,
+    systems: Schedule<Context>,
+    /// Fixed-timestep systems, run before `systems`.
+    fixed_systems: Schedule<Context>,
+    /// Catches panics, so a failing save can't skip teardown.
+    shutdown_systems: Schedule<Context>,
+    hooks: Hooks,
+    /// Input events received since the last frame.
+    input_queue: InputQueue,
+    /// An IME composition is in progress, so key presses aren't typed text.
+    ime_composing: bool,
+    /// The [`CursorOptions`] last applied to the window; `None` to apply
+    /// them again (e.g. after the OS dropped the grab on focus loss).
+    applied_cursor: Option<CursorOptions>,
+    /// Last seen [`Subsystems::audio`], to mute when it flips.
+    #[cfg(feature = "audio")]
+    audio_enabled: bool,
+    /// RenderDoc connection for [`FrameCapture`] requests.
+    capture: CaptureBackend,
     window: Option<Arc<Window>>,
     started: bool,
+    /// Why the event loop was asked to exit (set before `exiting` runs).
+    shutdown_reason: Option<ShutdownReason>,
     title: String,
     #[cfg(feature = "editor")]
     editor: Option<crate::editor::EditorState>,
@@ -35,28 +79,456 @@ impl WinitApp {
     pub fn new(
         ctx: Context,
         startup_systems: Vec<Box<dyn FnMut(&mut Context)>>,
-        systems: Vec<Box<dyn FnMut(&mut Context)>>,
+        systems: Schedule<Context>,
+        fixed_systems: Schedule<Context>,
+        shutdown_systems: Schedule<Context>,
+        hooks: Hooks,
         title: String,
     ) -> Self {
         Self {
             ctx,
             startup_systems,
             systems,
+            fixed_systems,
+            shutdown_systems,
+            hooks,
+            input_queue: InputQueue::default(),
+            ime_composing: false,
+            applied_cursor: None,
+            #[cfg(feature = "audio")]
+            audio_enabled: true,
+            capture: CaptureBackend::connect(),
             window: None,
             started: false,
+            shutdown_reason: None,
             title,
             #[cfg(feature = "editor")]
             editor: None,
         }
     }
+
+    /// Record a lifecycle event and apply auto-pause side effects (audio mute)
+    /// when the paused state flips.
+    fn lifecycle_event(&mut self, event: LifecycleEvent) {
+        let Some(lifecycle) = self.ctx.world.get_resource_mut::<WindowLifecycle>() else {
+            return;
+        };
+        lifecycle.push(event);
+        let Some(paused) = lifecycle.update_pause() else {
+            return;
+        };
+        log::info!("Game {}", if paused { "auto-paused" } else { "resumed" });
+
+        #[cfg(feature = "audio")]
+        {
+            let disabled = !Subsystems::of(&self.ctx.world).audio;
+            if let Some(engine) = self.ctx.world.get_resource_mut::<crate::audio::AudioEngine>() {
+                engine.set_muted(paused || disabled);
+            }
+        }
+    }
+
+    /// Mute or unmute the audio engine when a system flips
+    /// [`Subsystems::audio`].
+    #[cfg(feature = "audio")]
+    fn sync_audio_switch(&mut self) {
+        let enabled = Subsystems::of(&self.ctx.world).audio;
+        if enabled == self.audio_enabled {
+            return;
+        }
+        self.audio_enabled = enabled;
+        let paused = self
+            .ctx
+            .world
+            .get_resource::<WindowLifecycle>()
+            .is_some_and(|lc| lc.is_paused());
+        if let Some(engine) = self.ctx.world.get_resource_mut::<crate::audio::AudioEngine>() {
+            engine.set_muted(!enabled || paused);
+        }
+    }
+
+    /// Ask the event loop to exit. The shutdown sequence runs in `exiting`.
+    fn request_shutdown(&mut self, event_loop: &ActiveEventLoop, reason: ShutdownReason) {
+        self.shutdown_reason.get_or_insert(reason);
+        event_loop.exit();
+    }
+
+    /// Run startup systems once, with the launch-time resources they may
+    /// depend on already in place.
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+
+        #[cfg(feature = "diagnostics")]
+        if !self.ctx.world.has_resource::<crate::diag::DiagSender>() {
+            let port = self
+                .ctx
+                .world
+                .get_resource::<LaunchOptions>()
+                .map_or(crate::launch::DEFAULT_DIAGNOSTICS_PORT, |o| o.diagnostics_port);
+            match crate::diag::DiagSender::bind(port) {
+                Some(sender) => self.ctx.world.insert_resource(sender),
+                None => log::warn!("Diagnostics disabled: could not open UDP sockets for port {port}"),
+            }
+        }
+
+        for system in self.startup_systems.iter_mut() {
+            system(&mut self.ctx);
+        }
+
+        // A scene named on the command line wins over `Scenes::start` and any
+        // transition queued by startup systems.
+        let scene = self.ctx.world.get_resource::<LaunchOptions>().and_then(|o| o.scene.clone());
+        if let Some(scene) = scene {
+            match self.ctx.world.get_resource_mut::<SceneManager>() {
+                Some(manager) => manager.goto(&scene),
+                None => log::warn!("--scene {scene}: no Scenes plugin registered"),
+            }
+        }
+    }
+
+    /// Update text fields from this frame's input. When one gains focus,
+    /// accept IME input and run the keyboard hooks; when it loses focus,
+    /// undo both.
+    #[cfg(feature = "render2d")]
+    fn update_text_fields(&mut self) {
+        let change = crate::ui::update_text_inputs(
+            &mut self.ctx.world,
+            &self.ctx.input,
+            self.ctx.cursor,
+            self.ctx.time.delta_secs(),
+        );
+        let window = self.window.clone();
+        match change {
+            Some(crate::ui::FocusChange::Gained) => {
+                if let Some(window) = &window {
+                    window.set_ime_allowed(true);
+                }
+                self.hooks.run(Hook::KeyboardRequested, &mut self.ctx);
+            }
+            Some(crate::ui::FocusChange::Lost) => {
+                if let Some(window) = &window {
+                    window.set_ime_allowed(false);
+                }
+                self.ime_composing = false;
+                self.hooks.run(Hook::KeyboardDismissed, &mut self.ctx);
+            }
+            None => {}
+        }
+        // Keep the IME candidate window next to the field.
+        if let Some(window) = &window
+            && let Some(rect) = self.ctx.world.get_resource::<crate::ui::TextFocus>().and_then(|f| f.rect)
+        {
+            let size = rect.max - rect.min;
+            window.set_ime_cursor_area(
+                winit::dpi::PhysicalPosition::new(rect.min.x, rect.min.y),
+                winit::dpi::PhysicalSize::new(size.x, size.y),
+            );
+        }
+    }
+
+    /// Grab, hide or warp the OS cursor as [`CursorOptions`] asks, touching
+    /// the window only for what changed. The editor gets a free cursor.
+    fn apply_cursor_options(&mut self) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+        let Some(options) = self.ctx.world.get_resource_mut::<CursorOptions>() else {
+            return;
+        };
+        let warp = options.take_warp();
+        #[allow(unused_mut)]
+        let mut wanted = options.clone();
+        #[cfg(feature = "editor")]
+        if self.editor.as_ref().is_some_and(|editor| editor.visible) {
+            wanted = CursorOptions::default();
+        }
+
+        if let Some(at) = warp {
+            match window.set_cursor_position(winit::dpi::PhysicalPosition::new(at.x, at.y)) {
+                Ok(()) => self.ctx.cursor = CursorPosition { x: at.x, y: at.y },
+                Err(err) => log::warn!("Cannot move the cursor: {err}"),
+            }
+        }
+        let applied = self.applied_cursor.as_ref();
+        if applied.is_none_or(|applied| applied.grab != wanted.grab) {
+            grab_cursor(&window, wanted.grab);
+        }
+        if applied.is_none_or(|applied| applied.visible != wanted.visible) {
+            window.set_cursor_visible(wanted.visible);
+        }
+        self.applied_cursor = Some(wanted);
+    }
+
+    /// Run one frame: hooks, update systems, transform propagation and
+    /// rendering. Returns the reason if the frame asked the game to stop.
+    fn frame(&mut self) -> Option<ShutdownReason> {
+        // Update timing.
+        self.ctx.time.update();
+        // Sync Time to world resource (physics systems read it from here).
+        self.ctx.world.insert_resource(self.ctx.time);
+
+        self.hooks.run(Hook::FrameStart, &mut self.ctx);
+
+        // Process any pending asset hot-reloads, then finish async loads and
+        // GPU readbacks.
+        process_asset_reloads(&mut self.ctx.world);
+        process_async_loads(&mut self.ctx.world);
+        process_readbacks(&mut self.ctx.world);
+
+        // Sample input as late as possible: right before update systems.
+        let input_at = self.input_queue.drain(&mut self.ctx.input, &mut self.ctx.cursor);
+        self.ctx.input.update_device();
+        if let Some(capture) = self.ctx.world.get_resource_mut::<FrameCapture>()
+            && capture.hotkey.is_some_and(|key| self.ctx.input.keys.just_pressed(key))
+        {
+            capture.request();
+        }
+        if let Some(diff) = self.ctx.world.get_resource_mut::<ShaderDiff>()
+            && diff.hotkey.is_some_and(|key| self.ctx.input.keys.just_pressed(key))
+        {
+            diff.view = diff.view.next();
+        }
+
+        // Hover and click UI buttons against last frame's layout.
+        #[cfg(feature = "render2d")]
+        crate::ui::update_buttons(&mut self.ctx.world, &self.ctx.input.mouse, self.ctx.cursor);
+
+        // Type into the focused text field; IME and on-screen keyboards
+        // follow its focus.
+        #[cfg(feature = "render2d")]
+        self.update_text_fields();
+
+        // Run game systems (skipped while auto-paused). Lifecycle
+        // events are kept until systems have had a chance to see them.
+        #[cfg(feature = "diagnostics")]
+        let _systems_start = std::time::Instant::now();
+        let (paused, hidden) = self
+            .ctx
+            .world
+            .get_resource::<WindowLifecycle>()
+            .map_or((false, false), |lc| (lc.is_paused(), lc.is_hidden()));
+        // The editor's Pause freezes the simulation too; Step lets one
+        // frame through.
+        #[cfg(feature = "editor")]
+        let paused = paused
+            | self.editor.as_mut().is_some_and(|editor| editor.simulation_frozen(&mut self.ctx.world));
+        if !paused {
+            self.run_fixed_steps();
+            // Physics moves written to Transform show up in Transform2d.
+            crate::transform2d::sync_transform2d(&mut self.ctx.world);
+            self.systems.run(&mut self.ctx);
+            #[cfg(feature = "diagnostics")]
+            self.ctx.world.insert_resource(crate::diag::SystemTimings(std::mem::take(
+                &mut self.systems.timings,
+            )));
+            if let Some(lifecycle) = self.ctx.world.get_resource_mut::<WindowLifecycle>() {
+                lifecycle.clear_events();
+            }
+        }
+        self.apply_cursor_options();
+
+        // Clear per-frame input state.
+        self.ctx.input.clear_just();
+
+        #[cfg(feature = "audio")]
+        self.sync_audio_switch();
+
+        // Button prompts follow the device the player last used.
+        #[cfg(feature = "render2d")]
+        crate::glyph::update_input_prompts(&mut self.ctx.world, self.ctx.input.device());
+
+        // Late latch: cursor-following entities ignore whatever lag the
+        // systems introduced and snap to the latest sample.
+        #[cfg(feature = "render2d")]
+        crate::input::late_latch_cursor(&mut self.ctx.world, self.ctx.cursor);
+
+        // Propagate parent→child transforms so GlobalTransform is up to date.
+        crate::transform2d::sync_transform2d(&mut self.ctx.world);
+        propagate_transforms(&mut self.ctx.world);
+        crate::constraint::apply_constraints(&mut self.ctx.world);
+        propagate_visibility(&mut self.ctx.world);
+
+        // Move particles, spawning from the emitters' new positions.
+        #[cfg(any(feature = "render2d", feature = "render3d"))]
+        if !paused && Subsystems::of(&self.ctx.world).particles {
+            crate::particles::update_particles(&mut self.ctx.world, self.ctx.time.delta_secs());
+        }
+
+        // With pipelined rendering, wait for last frame's render thread:
+        // layout measures text with the fonts it had.
+        finish_render(&mut self.ctx.world);
+        if let Some(error) = take_render_error(&mut self.ctx.world)
+            && !surface_ok(&mut self.ctx.world, Err(error))
+        {
+            return Some(ShutdownReason::GpuError);
+        }
+
+        // Lay out the UI for this frame's draw and next frame's hit tests.
+        #[cfg(feature = "render2d")]
+        crate::ui::layout_ui(&mut self.ctx.world);
+
+        // Release textures and meshes nothing uses any more, if due.
+        #[cfg(any(feature = "render2d", feature = "render3d"))]
+        crate::asset_gc::run_asset_gc(&mut self.ctx.world, self.ctx.time.delta());
+
+        // Build editor UI (must happen before render so paint jobs are ready).
+        #[cfg(feature = "editor")]
+        {
+            if let Some(window) = &self.window {
+                if let Some(editor) = &mut self.editor {
+                    editor.build_ui(&mut self.ctx.world, window);
+                }
+            }
+        }
+
+        if !hidden {
+            self.hooks.run(Hook::BeforeRender, &mut self.ctx);
+            // Extract: copy what the renderer needs out of the ECS, here
+            // whichever thread renders; see render::extract.
+            if self.ctx.world.has_resource::<GpuContext>() {
+                let extracted = prepare_frame(&mut self.ctx.world);
+                self.ctx.world.insert_resource(extracted);
+            }
+            self.capture.begin_frame(&mut self.ctx.world);
+        }
+
+        // Render (with editor overlay when enabled), or hand the frame to
+        // the render thread. Nothing to present to while minimized or
+        // occluded.
+        let pipelined = !hidden && can_pipeline(&self.ctx.world);
+        #[cfg(feature = "editor")]
+        let pipelined = pipelined && self.editor.is_none();
+        if pipelined {
+            submit_render(&mut self.ctx.world, input_at.filter(|_| self.window.is_some()));
+        }
+        #[cfg(feature = "editor")]
+        let render_ok = hidden || pipelined || {
+            let editor = &mut self.editor;
+            render_world(&mut self.ctx.world, |frame| {
+                if let Some(ed) = editor.as_mut() {
+                    ed.render_overlay(frame);
+                }
+            })
+        };
+        #[cfg(not(feature = "editor"))]
+        let render_ok = hidden || pipelined || render_world(&mut self.ctx.world, |_| {});
+        self.capture.end_frame(&mut self.ctx.world);
+        if !render_ok {
+            return Some(ShutdownReason::GpuError);
+        }
+
+        if !hidden {
+            // The frame is presented; measure how long its input waited. A
+            // pipelined frame records this when it comes back.
+            if !pipelined
+                && let Some(at) = input_at
+                && self.window.is_some()
+                && let Some(latency) = self.ctx.world.get_resource_mut::<InputLatency>()
+            {
+                latency.record(at.elapsed());
+            }
+            self.hooks.run(Hook::AfterRender, &mut self.ctx);
+        }
+        self.hooks.run(Hook::FrameEnd, &mut self.ctx);
+
+        if self.ctx.exit_requested {
+            log::info!("Exit requested, exiting.");
+            return Some(ShutdownReason::Exit);
+        }
+
+        None
+    }
+
+    /// Run the fixed-update systems once per fixed tick due this frame. The
+    /// accumulator only advances while systems run, so pauses don't build
+    /// up a backlog.
+    fn run_fixed_steps(&mut self) {
+        let steps = self.ctx.time.advance_fixed();
+        for _ in 0..steps {
+            self.ctx.world.insert_resource(self.ctx.time);
+            self.fixed_systems.run(&mut self.ctx);
+            self.ctx.time.finish_fixed_step();
+        }
+        self.ctx.world.insert_resource(self.ctx.time);
+    }
+
+    /// Run shutdown systems and hooks, then tear everything down.
+    fn shutdown(&mut self) {
+        let reason = self.shutdown_reason.unwrap_or(ShutdownReason::WindowClosed);
+        log::info!("Shutting down ({reason:?})");
+        finish_render(&mut self.ctx.world);
+        self.ctx.world.insert_resource(ShutdownRequested { reason });
+
+        self.shutdown_systems.run(&mut self.ctx);
+        self.hooks.run(Hook::Shutdown, &mut self.ctx);
+
+        self.teardown();
+    }
+
+    /// Drive the game without winit: no window, no GPU, no input. Frames are
+    /// paced to [`HEADLESS_FRAME_TIME`] until a system calls `Context::exit`.
+    pub fn run_headless(&mut self) {
+        log::info!("Running headless");
+        self.start();
+
+        loop {
+            let frame_start = Instant::now();
+            let stop = self.frame();
+
+            #[cfg(feature = "diagnostics")]
+            crate::diag::send_diagnostics(&mut self.ctx.world, &self.ctx.time);
+
+            if let Some(reason) = stop {
+                self.shutdown_reason = Some(reason);
+                break;
+            }
+            if let Some(rest) = HEADLESS_FRAME_TIME.checked_sub(frame_start.elapsed()) {
+                std::thread::sleep(rest);
+            }
+        }
+
+        self.shutdown();
+    }
+
+    /// Drop everything in a fixed order: audio, editor, world contents, then
+    /// the GPU context once the device is idle, then the window.
+    fn teardown(&mut self) {
+        #[cfg(feature = "audio")]
+        drop(self.ctx.world.resource_remove::<crate::audio::AudioEngine>());
+
+        #[cfg(feature = "editor")]
+        {
+            self.editor = None;
+        }
+
+        finish_render(&mut self.ctx.world);
+        let gpu = self.ctx.world.resource_remove::<GpuContext>();
+        self.ctx.world.despawn_all();
+        self.ctx.world.clear_resources();
+
+        if let Some(gpu) = gpu {
+            if let Err(e) = gpu.device.poll(wgpu::PollType::wait_indefinitely()) {
+                log::warn!("GPU did not go idle during shutdown: {e}");
+            }
+            drop(gpu);
+        }
+
+        self.window = None;
+    }
 }
 
 impl ApplicationHandler for WinitApp {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         if self.window.is_none() {
+            let launch = self.ctx.world.get_resource::<LaunchOptions>().cloned().unwrap_or_default();
+            let (width, height) = launch.window_size;
             let attrs = Window::default_attributes()
                 .with_title(&self.title)
-                .with_inner_size(winit::dpi::LogicalSize::new(1280.0, 720.0));
+                .with_inner_size(winit::dpi::LogicalSize::new(width as f64, height as f64))
+                .with_fullscreen(launch.fullscreen.then_some(Fullscreen::Borderless(None)));
             let window = Arc::new(
                 event_loop
                     .create_window(attrs)
@@ -64,7 +536,14 @@ impl ApplicationHandler for WinitApp {
             );
 
             // Initialize GPU.
-            let gpu = GpuContext::new(window.clone());
+            let selection = self
+                .ctx
+                .world
+                .get_resource::<AdapterSelection>()
+                .cloned()
+                .unwrap_or_default();
+            let gpu = GpuContext::new(window.clone(), launch.vsync, &selection);
+            self.ctx.world.insert_resource(AdapterInfo::new(&gpu.adapter, &gpu.device));
             self.ctx.world.insert_resource(gpu);
 
             // Initialize editor if the feature is enabled.
@@ -77,13 +556,7 @@ impl ApplicationHandler for WinitApp {
             self.window = Some(window);
         }
 
-        // Run startup systems once.
-        if !self.started {
-            self.started = true;
-            for system in self.startup_systems.iter_mut() {
-                system(&mut self.ctx);
-            }
-        }
+        self.start();
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
@@ -101,14 +574,44 @@ impl ApplicationHandler for WinitApp {
 
         match event {
             WindowEvent::CloseRequested => {
-                log::info!("Window close requested, exiting.");
-                event_loop.exit();
+                if self.hooks.close_allowed(&mut self.ctx) {
+                    log::info!("Window close requested, exiting.");
+                    self.request_shutdown(event_loop, ShutdownReason::WindowClosed);
+                } else {
+                    log::info!("Window close requested, vetoed by a hook.");
+                }
             }
 
             WindowEvent::Resized(size) => {
+                // The frame in flight presents to the surface being resized.
+                finish_render(&mut self.ctx.world);
                 if let Some(gpu) = self.ctx.world.get_resource_mut::<GpuContext>() {
                     gpu.resize(size.width, size.height);
                 }
+                // Most platforms report minimize as a zero-sized resize.
+                if size.width == 0 || size.height == 0 {
+                    self.lifecycle_event(LifecycleEvent::Minimized);
+                } else {
+                    self.lifecycle_event(LifecycleEvent::Restored);
+                }
+            }
+
+            WindowEvent::Focused(focused) => {
+                // The OS drops cursor grabs with focus; grab again on return.
+                self.applied_cursor = None;
+                self.lifecycle_event(if focused {
+                    LifecycleEvent::FocusGained
+                } else {
+                    LifecycleEvent::FocusLost
+                });
+            }
+
+            WindowEvent::Occluded(occluded) => {
+                self.lifecycle_event(if occluded {
+                    LifecycleEvent::Occluded
+                } else {
+                    LifecycleEvent::Visible
+                });
             }
 
             WindowEvent::KeyboardInput { event, .. } => {
@@ -126,69 +629,74 @@ impl ApplicationHandler for WinitApp {
                 }
 
                 if let PhysicalKey::Code(key_code) = event.physical_key {
-                    match event.state {
-                        ElementState::Pressed => self.ctx.input.keys.press(key_code),
-                        ElementState::Released => self.ctx.input.keys.release(key_code),
+                    self.input_queue.push(match event.state {
+                        ElementState::Pressed => InputEvent::KeyPressed(key_code),
+                        ElementState::Released => InputEvent::KeyReleased(key_code),
+                    });
+                }
+                // Backspace, Enter, Tab etc. also produce control characters;
+                // they're handled as keys.
+                if event.state == ElementState::Pressed
+                    && !self.ime_composing
+                    && let Some(text) = &event.text
+                {
+                    let text: String = text.chars().filter(|ch| !ch.is_control()).collect();
+                    if !text.is_empty() {
+                        self.input_queue.push_text(TextEvent::Commit(text));
                     }
                 }
             }
 
-            WindowEvent::MouseInput { button, state, .. } => match state {
-                ElementState::Pressed => self.ctx.input.mouse.press(button),
-                ElementState::Released => self.ctx.input.mouse.release(button),
+            WindowEvent::Ime(ime) => match ime {
+                Ime::Preedit(text, cursor) => {
+                    self.ime_composing = !text.is_empty();
+                    self.input_queue.push_text(TextEvent::Preedit { text, cursor });
+                }
+                Ime::Commit(text) => {
+                    self.ime_composing = false;
+                    self.input_queue.push_text(TextEvent::Commit(text));
+                }
+                Ime::Enabled | Ime::Disabled => self.ime_composing = false,
             },
 
-            WindowEvent::CursorMoved { position, .. } => {
-                self.ctx.cursor.x = position.x as f32;
-                self.ctx.cursor.y = position.y as f32;
+            WindowEvent::MouseInput { button, state, .. } => {
+                self.input_queue.push(match state {
+                    ElementState::Pressed => InputEvent::MousePressed(button),
+                    ElementState::Released => InputEvent::MouseReleased(button),
+                });
             }
 
-            WindowEvent::RedrawRequested => {
-                // Update timing.
-                self.ctx.time.update();
-                // Sync Time to world resource (physics systems read it from here).
-                self.ctx.world.insert_resource(self.ctx.time);
-
-                // Process any pending asset hot-reloads.
-                process_asset_reloads(&mut self.ctx.world);
-
-                // Run game systems.
-                #[cfg(feature = "diagnostics")]
-                let _systems_start = std::time::Instant::now();
-                for system in self.systems.iter_mut() {
-                    system(&mut self.ctx);
-                }
-
-                // Clear per-frame input state.
-                self.ctx.input.keys.clear_just();
-                self.ctx.input.mouse.clear_just();
+            WindowEvent::CursorMoved { position, .. } => {
+                self.input_queue
+                    .push(InputEvent::CursorMoved { x: position.x as f32, y: position.y as f32 });
+            }
 
-                // Propagate parent→child transforms so GlobalTransform is up to date.
-                propagate_transforms(&mut self.ctx.world);
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.input_queue.push(InputEvent::Scroll(match delta {
+                    MouseScrollDelta::LineDelta(x, y) => ScrollDelta::Lines { x, y },
+                    MouseScrollDelta::PixelDelta(d) => ScrollDelta::Pixels { x: d.x as f32, y: d.y as f32 },
+                }));
+            }
 
-                // Build editor UI (must happen before render so paint jobs are ready).
-                #[cfg(feature = "editor")]
-                {
-                    if let Some(window) = &self.window {
-                        if let Some(editor) = &mut self.editor {
-                            editor.build_ui(&mut self.ctx.world, window);
-                        }
-                    }
-                }
+            WindowEvent::Touch(touch) => {
+                let phase = match touch.phase {
+                    winit::event::TouchPhase::Started => TouchPhase::Began,
+                    winit::event::TouchPhase::Moved => TouchPhase::Moved,
+                    winit::event::TouchPhase::Ended => TouchPhase::Ended,
+                    winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
+                };
+                self.input_queue.push(InputEvent::Touch {
+                    id: touch.id,
+                    phase,
+                    x: touch.location.x as f32,
+                    y: touch.location.y as f32,
+                });
+            }
 
-                // Render (with editor overlay when enabled).
-                #[cfg(feature = "editor")]
-                {
-                    let editor = &mut self.editor;
-                    render_world(event_loop, &mut self.ctx.world, |frame| {
-                        if let Some(ed) = editor.as_mut() {
-                            ed.render_overlay(frame);
-                        }
-                    });
-                }
-                #[cfg(not(feature = "editor"))]
-                {
-                    render_world(event_loop, &mut self.ctx.world, |_| {});
+            WindowEvent::RedrawRequested => {
+                if let Some(reason) = self.frame() {
+                    self.request_shutdown(event_loop, reason);
+                    return;
                 }
 
                 // Request next frame.
@@ -201,34 +709,68 @@ impl ApplicationHandler for WinitApp {
         }
     }
 
+    fn device_event(&mut self, _event_loop: &ActiveEventLoop, _id: DeviceId, event: DeviceEvent) {
+        if let DeviceEvent::MouseMotion { delta: (x, y) } = event {
+            self.input_queue.push(InputEvent::MouseMotion { x: x as f32, y: y as f32 });
+        }
+    }
+
     fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
         #[cfg(feature = "diagnostics")]
         crate::diag::send_diagnostics(&mut self.ctx.world, &self.ctx.time);
     }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.shutdown();
+    }
 }
 
-/// Render the world and handle surface errors.
-fn render_world(
-    event_loop: &ActiveEventLoop,
-    world: &mut World,
-    overlay: impl FnOnce(&mut FrameContext<'_>),
-) {
+/// Set the cursor grab, falling back to the other grab mode where the
+/// platform lacks one: Windows and X11 can't lock, macOS can't confine.
+fn grab_cursor(window: &Window, grab: CursorGrab) {
+    use winit::window::CursorGrabMode;
+    let (mode, fallback) = match grab {
+        CursorGrab::None => (CursorGrabMode::None, None),
+        CursorGrab::Confined => (CursorGrabMode::Confined, Some(CursorGrabMode::Locked)),
+        CursorGrab::Locked => (CursorGrabMode::Locked, Some(CursorGrabMode::Confined)),
+    };
+    let result = window.set_cursor_grab(mode).or_else(|err| match fallback {
+        Some(fallback) => window.set_cursor_grab(fallback),
+        None => Err(err),
+    });
+    if let Err(err) = result {
+        log::warn!("Cannot grab the cursor ({grab:?}): {err}");
+    }
+}
+
+/// Render the world and handle surface errors. Returns `false` on a fatal
+/// error that should shut the game down.
+fn render_world(world: &mut World, overlay: impl FnOnce(&mut FrameContext<'_>)) -> bool {
     if world.has_resource::<GpuContext>() {
-        match render_frame(world, overlay) {
-            Ok(()) => {}
-            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
-                if let Some(gpu) = world.get_resource_mut::<GpuContext>() {
-                    let (w, h) = gpu.surface_size();
-                    gpu.resize(w, h);
-                }
-            }
-            Err(wgpu::SurfaceError::OutOfMemory) => {
-                log::error!("Out of GPU memory!");
-                event_loop.exit();
-            }
-            Err(e) => {
-                log::warn!("Surface error: {:?}", e);
+        let result = render_frame(world, overlay);
+        return surface_ok(world, result);
+    }
+    true
+}
+
+/// Handle how rendering a frame went, here or on the render thread.
+/// Returns `false` on a fatal error that should shut the game down.
+fn surface_ok(world: &mut World, result: Result<(), wgpu::SurfaceError>) -> bool {
+    match result {
+        Ok(()) => {}
+        Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+            if let Some(gpu) = world.get_resource_mut::<GpuContext>() {
+                let (w, h) = gpu.surface_size();
+                gpu.resize(w, h);
             }
         }
+        Err(wgpu::SurfaceError::OutOfMemory) => {
+            log::error!("Out of GPU memory!");
+            return false;
+        }
+        Err(e) => {
+            log::warn!("Surface error: {:?}", e);
+        }
     }
+    true
 }